@@ -3,6 +3,10 @@
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
+mod widget;
+
+pub use widget::*;
+
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
@@ -10,11 +14,11 @@ use std::sync::{
 
 pub use accesskit;
 use accesskit::NodeBuilder;
-use bevy_app::Plugin;
+use bevy_app::{Plugin, PostUpdate, PreUpdate};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::{Component, Entity, Event},
-    schedule::SystemSet,
+    schedule::{IntoSystemConfigs, SystemSet},
     system::Resource,
 };
 
@@ -105,6 +109,13 @@ impl Plugin for AccessibilityPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         app.init_resource::<AccessibilityRequested>()
             .init_resource::<ManageAccessibilityUpdates>()
-            .init_resource::<Focus>();
+            .init_resource::<Focus>()
+            .add_event::<ActionRequest>()
+            .add_event::<WidgetActionRequest>()
+            .add_systems(PreUpdate, send_widget_action_requests)
+            .add_systems(
+                PostUpdate,
+                update_accessible_widgets.before(AccessibilitySystem::Update),
+            );
     }
 }
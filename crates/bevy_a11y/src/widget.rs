@@ -0,0 +1,284 @@
+//! Accessibility information for user-defined widgets.
+
+use crate::{AccessibilityNode, ActionRequest};
+use accesskit::{Action, ActionData, Checked, NodeBuilder, Role};
+use bevy_ecs::prelude::{
+    Changed, Commands, Component, Entity, Event, EventReader, EventWriter, Query,
+};
+
+/// The numeric value of a widget such as a slider, progress bar or spin button.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccessibleNumericValue {
+    /// The current value.
+    pub value: f64,
+    /// The lowest value the widget accepts.
+    pub min: f64,
+    /// The highest value the widget accepts.
+    pub max: f64,
+    /// The amount the value changes by on [`Action::Increment`] and [`Action::Decrement`], if any.
+    pub step: Option<f64>,
+}
+
+/// Describes a user-defined widget to assistive technologies.
+///
+/// The entity's [`AccessibilityNode`] is kept in sync with this component, so the role, states and
+/// supported actions of a widget can be updated without rebuilding the node by hand. The node is
+/// inserted if the entity doesn't have one yet.
+///
+/// Requests from assistive technologies to perform one of the widget's [`actions`](Self::actions)
+/// are sent as [`WidgetActionRequest`] events.
+///
+/// ```
+/// # use bevy_a11y::{accesskit::Role, AccessibleWidget};
+/// let volume = AccessibleWidget::slider(0.8, 0.0, 1.0, 0.1).with_name("Volume");
+/// let mute = AccessibleWidget::toggle(Role::Switch, false).with_name("Mute");
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct AccessibleWidget {
+    /// The kind of widget.
+    pub role: Role,
+    /// The label announced for the widget.
+    pub name: Option<String>,
+    /// A longer description of the widget.
+    pub description: Option<String>,
+    /// The text value of the widget, e.g. the contents of a text input.
+    pub value: Option<String>,
+    /// The numeric value of the widget, e.g. the position of a slider.
+    pub numeric_value: Option<AccessibleNumericValue>,
+    /// Whether a toggle, checkbox or switch is on, or `None` if the widget can't be toggled.
+    pub toggled: Option<bool>,
+    /// Whether the widget is expanded, or `None` if the widget can't be expanded or collapsed.
+    pub expanded: Option<bool>,
+    /// Whether the widget is disabled.
+    pub disabled: bool,
+    /// The actions assistive technologies can request on the widget.
+    pub actions: Vec<Action>,
+}
+
+impl AccessibleWidget {
+    /// Creates a widget with the given `role` that doesn't support any action.
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            name: None,
+            description: None,
+            value: None,
+            numeric_value: None,
+            toggled: None,
+            expanded: None,
+            disabled: false,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Creates a [`Role::Slider`] that can be incremented, decremented or set to a value.
+    pub fn slider(value: f64, min: f64, max: f64, step: f64) -> Self {
+        Self::new(Role::Slider)
+            .with_numeric_value(AccessibleNumericValue {
+                value,
+                min,
+                max,
+                step: Some(step),
+            })
+            .with_action(Action::Increment)
+            .with_action(Action::Decrement)
+            .with_action(Action::SetValue)
+    }
+
+    /// Creates a toggleable widget, such as a [`Role::CheckBox`], [`Role::Switch`] or
+    /// [`Role::ToggleButton`], that can be clicked.
+    pub fn toggle(role: Role, toggled: bool) -> Self {
+        Self::new(role)
+            .with_toggled(toggled)
+            .with_action(Action::Default)
+    }
+
+    /// Sets the label announced for the widget.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the description of the widget.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the text value of the widget.
+    #[must_use]
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets the numeric value of the widget.
+    #[must_use]
+    pub fn with_numeric_value(mut self, numeric_value: AccessibleNumericValue) -> Self {
+        self.numeric_value = Some(numeric_value);
+        self
+    }
+
+    /// Sets whether the widget is toggled on.
+    #[must_use]
+    pub fn with_toggled(mut self, toggled: bool) -> Self {
+        self.toggled = Some(toggled);
+        self
+    }
+
+    /// Sets whether the widget is expanded.
+    #[must_use]
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = Some(expanded);
+        self
+    }
+
+    /// Sets whether the widget is disabled.
+    #[must_use]
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Adds an action assistive technologies can request on the widget.
+    #[must_use]
+    pub fn with_action(mut self, action: Action) -> Self {
+        if !self.actions.contains(&action) {
+            self.actions.push(action);
+        }
+        self
+    }
+
+    /// Returns `true` if assistive technologies can request `action` on the widget.
+    pub fn supports(&self, action: Action) -> bool {
+        !self.disabled && self.actions.contains(&action)
+    }
+
+    /// Writes the role, states and actions of the widget to `node`.
+    pub fn apply_to_node(&self, node: &mut NodeBuilder) {
+        node.set_role(self.role);
+        match &self.name {
+            Some(name) => node.set_name(name.as_str()),
+            None => node.clear_name(),
+        }
+        match &self.description {
+            Some(description) => node.set_description(description.as_str()),
+            None => node.clear_description(),
+        }
+        match &self.value {
+            Some(value) => node.set_value(value.as_str()),
+            None => node.clear_value(),
+        }
+        match self.numeric_value {
+            Some(numeric_value) => {
+                node.set_numeric_value(numeric_value.value);
+                node.set_min_numeric_value(numeric_value.min);
+                node.set_max_numeric_value(numeric_value.max);
+                match numeric_value.step {
+                    Some(step) => node.set_numeric_value_step(step),
+                    None => node.clear_numeric_value_step(),
+                }
+            }
+            None => {
+                node.clear_numeric_value();
+                node.clear_min_numeric_value();
+                node.clear_max_numeric_value();
+                node.clear_numeric_value_step();
+            }
+        }
+        match self.toggled {
+            Some(true) => node.set_checked(Checked::True),
+            Some(false) => node.set_checked(Checked::False),
+            None => node.clear_checked(),
+        }
+        match self.expanded {
+            Some(expanded) => node.set_expanded(expanded),
+            None => node.clear_expanded(),
+        }
+        if self.disabled {
+            node.set_disabled();
+        } else {
+            node.clear_disabled();
+        }
+        node.clear_actions();
+        for action in &self.actions {
+            node.add_action(*action);
+        }
+    }
+}
+
+/// An action requested by an assistive technology on an entity with an [`AccessibleWidget`].
+///
+/// Only actions in the widget's [`actions`](AccessibleWidget::actions) are sent, and none are sent
+/// while the widget is disabled. It's up to the widget to perform the action, e.g. by changing the
+/// value of a slider on [`Action::SetValue`], and to update its [`AccessibleWidget`] to match.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct WidgetActionRequest {
+    /// The widget the action is requested on.
+    pub entity: Entity,
+    /// The requested action.
+    pub action: Action,
+    /// Additional data needed by some actions, like the value requested by [`Action::SetValue`].
+    pub data: Option<ActionData>,
+}
+
+impl WidgetActionRequest {
+    /// The numeric value requested by an [`Action::SetValue`], if any.
+    pub fn numeric_value(&self) -> Option<f64> {
+        match self.data {
+            Some(ActionData::NumericValue(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The text value requested by an [`Action::SetValue`] or [`Action::ReplaceSelectedText`], if any.
+    pub fn value(&self) -> Option<&str> {
+        match &self.data {
+            Some(ActionData::Value(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Updates the [`AccessibilityNode`] of entities whose [`AccessibleWidget`] changed.
+pub fn update_accessible_widgets(
+    mut commands: Commands,
+    mut widgets: Query<
+        (Entity, &AccessibleWidget, Option<&mut AccessibilityNode>),
+        Changed<AccessibleWidget>,
+    >,
+) {
+    for (entity, widget, node) in &mut widgets {
+        if let Some(mut node) = node {
+            widget.apply_to_node(&mut node);
+        } else {
+            let mut node = NodeBuilder::new(widget.role);
+            widget.apply_to_node(&mut node);
+            commands.entity(entity).insert(AccessibilityNode(node));
+        }
+    }
+}
+
+/// Sends a [`WidgetActionRequest`] for each [`ActionRequest`] targeting an [`AccessibleWidget`]
+/// that supports the requested action.
+pub fn send_widget_action_requests(
+    mut requests: EventReader<ActionRequest>,
+    mut widget_requests: EventWriter<WidgetActionRequest>,
+    widgets: Query<&AccessibleWidget>,
+) {
+    for request in requests.read() {
+        let entity = Entity::from_bits(request.target.0);
+        let Ok(widget) = widgets.get(entity) else {
+            continue;
+        };
+        if widget.supports(request.action) {
+            widget_requests.send(WidgetActionRequest {
+                entity,
+                action: request.action,
+                data: request.data.clone(),
+            });
+        }
+    }
+}
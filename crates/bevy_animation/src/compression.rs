@@ -0,0 +1,765 @@
+use std::borrow::Cow;
+
+use bevy_asset::{
+    io::{Reader, Writer},
+    saver::{AssetSaver, SavedAsset},
+    AssetLoader, AsyncReadExt, AsyncWriteExt, LoadContext,
+};
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{AnimationClip, Keyframes, VariableCurve};
+
+/// The attribute of a [`Transform`](bevy_transform::prelude::Transform) or
+/// [`MorphWeights`](bevy_render::mesh::morph::MorphWeights) animated by [`Keyframes`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyframeAttribute {
+    /// The rotation of the transform.
+    Rotation,
+    /// The translation of the transform.
+    Translation,
+    /// The scale of the transform.
+    Scale,
+    /// The morph weights.
+    Weights,
+}
+
+/// Keyframes quantized to 16 bits per component by [`AnimationClip::compress`].
+///
+/// Only the keyframes around the sampled time are decoded when the curve is sampled, so
+/// compressed clips stay compressed in memory while they play.
+#[derive(Reflect, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompressedKeyframes {
+    attribute: KeyframeAttribute,
+    /// The number of components of each keyframe.
+    stride: usize,
+    /// The minimum of each component.
+    offsets: Vec<f32>,
+    /// The difference between two consecutive quantized values of each component.
+    steps: Vec<f32>,
+    values: Vec<u16>,
+}
+
+impl CompressedKeyframes {
+    /// Quantizes `keyframes`, with `keyframe_count` keyframes.
+    ///
+    /// Returns `None` if the keyframes are already compressed, or if there are none.
+    pub fn new(keyframes: &Keyframes, keyframe_count: usize) -> Option<Self> {
+        let (attribute, values): (_, Vec<f32>) = match keyframes {
+            Keyframes::Rotation(keyframes) => (
+                KeyframeAttribute::Rotation,
+                keyframes
+                    .iter()
+                    .flat_map(|rotation| rotation.to_array())
+                    .collect(),
+            ),
+            Keyframes::Translation(keyframes) => (
+                KeyframeAttribute::Translation,
+                keyframes
+                    .iter()
+                    .flat_map(|translation| translation.to_array())
+                    .collect(),
+            ),
+            Keyframes::Scale(keyframes) => (
+                KeyframeAttribute::Scale,
+                keyframes
+                    .iter()
+                    .flat_map(|scale| scale.to_array())
+                    .collect(),
+            ),
+            Keyframes::Weights(keyframes) => (KeyframeAttribute::Weights, keyframes.clone()),
+            Keyframes::Compressed(_) => return None,
+        };
+        let stride = values.len().checked_div(keyframe_count).unwrap_or(0);
+        if stride == 0 {
+            return None;
+        }
+
+        let mut offsets = vec![f32::INFINITY; stride];
+        let mut maximums = vec![f32::NEG_INFINITY; stride];
+        for keyframe in values.chunks_exact(stride) {
+            for (component, value) in keyframe.iter().enumerate() {
+                offsets[component] = offsets[component].min(*value);
+                maximums[component] = maximums[component].max(*value);
+            }
+        }
+        let steps: Vec<f32> = offsets
+            .iter()
+            .zip(&maximums)
+            .map(|(minimum, maximum)| (maximum - minimum) / u16::MAX as f32)
+            .collect();
+        let values = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let component = index % stride;
+                if steps[component] > 0.0 {
+                    ((value - offsets[component]) / steps[component]).round() as u16
+                } else {
+                    0
+                }
+            })
+            .collect();
+        Some(Self {
+            attribute,
+            stride,
+            offsets,
+            steps,
+            values,
+        })
+    }
+
+    /// The attribute animated by the keyframes.
+    pub fn attribute(&self) -> KeyframeAttribute {
+        self.attribute
+    }
+
+    /// The number of keyframes.
+    pub fn len(&self) -> usize {
+        self.values.len().checked_div(self.stride).unwrap_or(0)
+    }
+
+    /// Whether there are no keyframes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of components of each keyframe, which is the number of morph targets for
+    /// [`KeyframeAttribute::Weights`].
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Decodes the keyframes from `start` to `end` included.
+    pub fn decode(&self, start: usize, end: usize) -> Keyframes {
+        let values = self.values[self.stride * start..self.stride * (end + 1)]
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let component = index % self.stride;
+                self.offsets[component] + *value as f32 * self.steps[component]
+            });
+        match self.attribute {
+            KeyframeAttribute::Rotation => Keyframes::Rotation(
+                values
+                    .collect::<Vec<_>>()
+                    .chunks_exact(4)
+                    .map(|rotation| Quat::from_slice(rotation).normalize())
+                    .collect(),
+            ),
+            KeyframeAttribute::Translation => Keyframes::Translation(
+                values
+                    .collect::<Vec<_>>()
+                    .chunks_exact(3)
+                    .map(Vec3::from_slice)
+                    .collect(),
+            ),
+            KeyframeAttribute::Scale => Keyframes::Scale(
+                values
+                    .collect::<Vec<_>>()
+                    .chunks_exact(3)
+                    .map(Vec3::from_slice)
+                    .collect(),
+            ),
+            KeyframeAttribute::Weights => Keyframes::Weights(values.collect()),
+        }
+    }
+
+    /// Scales the decoded values by `factor`.
+    pub(crate) fn scale(&mut self, factor: f32) {
+        for offset in &mut self.offsets {
+            *offset *= factor;
+        }
+        for step in &mut self.steps {
+            *step *= factor;
+        }
+    }
+}
+
+impl Keyframes {
+    /// The attribute animated by the keyframes.
+    pub fn attribute(&self) -> KeyframeAttribute {
+        match self {
+            Keyframes::Rotation(_) => KeyframeAttribute::Rotation,
+            Keyframes::Translation(_) => KeyframeAttribute::Translation,
+            Keyframes::Scale(_) => KeyframeAttribute::Scale,
+            Keyframes::Weights(_) => KeyframeAttribute::Weights,
+            Keyframes::Compressed(compressed) => compressed.attribute,
+        }
+    }
+
+    /// The keyframes from `start` to `end` included, and the index of `start` in them.
+    ///
+    /// Compressed keyframes are decoded, and others are borrowed as they are.
+    pub(crate) fn window(&self, start: usize, end: usize) -> (Cow<'_, Keyframes>, usize) {
+        match self {
+            Keyframes::Compressed(compressed) => (Cow::Owned(compressed.decode(start, end)), 0),
+            keyframes => (Cow::Borrowed(keyframes), start),
+        }
+    }
+
+    /// The number of morph targets of weights keyframes, with `keyframe_count` keyframes.
+    pub(crate) fn morph_target_count(&self, keyframe_count: usize) -> usize {
+        match self {
+            Keyframes::Weights(keyframes) => keyframes.len() / keyframe_count.max(1),
+            Keyframes::Compressed(compressed) => compressed.stride,
+            _ => 0,
+        }
+    }
+}
+
+/// Settings of [`AnimationClip::compress`], also used by [`AnimationClipSaver`] to compress
+/// clips in the asset processor.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClipCompressionSettings {
+    /// The maximum distance between the translation of the clip with and without a removed
+    /// keyframe.
+    pub translation_tolerance: f32,
+    /// The maximum angle in radians between the rotation of the clip with and without a removed
+    /// keyframe.
+    pub rotation_tolerance: f32,
+    /// The maximum difference between the scale of the clip with and without a removed keyframe.
+    pub scale_tolerance: f32,
+    /// The maximum difference between the morph weights of the clip with and without a removed
+    /// keyframe.
+    pub weight_tolerance: f32,
+    /// Whether the keyframes are quantized to 16 bits per component.
+    pub quantize: bool,
+}
+
+impl Default for ClipCompressionSettings {
+    fn default() -> Self {
+        Self {
+            translation_tolerance: 0.0001,
+            rotation_tolerance: 0.0005,
+            scale_tolerance: 0.0001,
+            weight_tolerance: 0.001,
+            quantize: true,
+        }
+    }
+}
+
+impl AnimationClip {
+    /// Reduces the memory used by the clip, by removing the keyframes that can be interpolated
+    /// from their neighbors and quantizing the others.
+    ///
+    /// Curves that are already compressed and [`PropertyCurve`](crate::PropertyCurve)s are kept
+    /// as they are.
+    pub fn compress(&mut self, settings: &ClipCompressionSettings) {
+        for curve in self.curves.iter_mut().flatten() {
+            if matches!(curve.keyframes, Keyframes::Compressed(_)) {
+                continue;
+            }
+            reduce_keyframes(curve, settings);
+            if settings.quantize {
+                let count = curve.keyframe_timestamps.len();
+                if let Some(compressed) = CompressedKeyframes::new(&curve.keyframes, count) {
+                    curve.keyframes = Keyframes::Compressed(compressed);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the keyframes of `curve` which are within the tolerance of the curve without them.
+fn reduce_keyframes(curve: &mut VariableCurve, settings: &ClipCompressionSettings) {
+    let timestamps = &curve.keyframe_timestamps;
+    if timestamps.len() < 3 {
+        return;
+    }
+    let stride = curve.keyframes.morph_target_count(timestamps.len());
+    let keyframes: Vec<Vec<f32>> = match &curve.keyframes {
+        Keyframes::Rotation(keyframes) => keyframes.iter().map(|k| k.to_array().into()).collect(),
+        Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+            keyframes.iter().map(|k| k.to_array().into()).collect()
+        }
+        Keyframes::Weights(keyframes) => keyframes
+            .chunks_exact(stride.max(1))
+            .map(<[f32]>::to_vec)
+            .collect(),
+        Keyframes::Compressed(_) => return,
+    };
+    let attribute = curve.keyframes.attribute();
+    let tolerance = match attribute {
+        KeyframeAttribute::Rotation => settings.rotation_tolerance,
+        KeyframeAttribute::Translation => settings.translation_tolerance,
+        KeyframeAttribute::Scale => settings.scale_tolerance,
+        KeyframeAttribute::Weights => settings.weight_tolerance,
+    };
+    // The error of interpolating between `a` and `b` by `t` instead of `keyframe`
+    let error = |a: &[f32], b: &[f32], t: f32, keyframe: &[f32]| match attribute {
+        KeyframeAttribute::Rotation => {
+            let (a, mut b) = (Quat::from_slice(a), Quat::from_slice(b));
+            if a.dot(b) < 0.0 {
+                b = -b;
+            }
+            a.normalize()
+                .slerp(b.normalize(), t)
+                .angle_between(Quat::from_slice(keyframe).normalize())
+        }
+        KeyframeAttribute::Translation | KeyframeAttribute::Scale => Vec3::from_slice(a)
+            .lerp(Vec3::from_slice(b), t)
+            .distance(Vec3::from_slice(keyframe)),
+        KeyframeAttribute::Weights => a
+            .iter()
+            .zip(b)
+            .zip(keyframe)
+            .map(|((a, b), keyframe)| (a + (b - a) * t - keyframe).abs())
+            .fold(0.0, f32::max),
+    };
+
+    // Keep each keyframe which can't be removed along with the ones removed since the last kept
+    // keyframe
+    let last = timestamps.len() - 1;
+    let mut kept = vec![0];
+    for index in 1..last {
+        let start = *kept.last().unwrap();
+        let duration = timestamps[index + 1] - timestamps[start];
+        let removable = (start + 1..=index).all(|removed| {
+            let t = (timestamps[removed] - timestamps[start]) / duration;
+            error(
+                &keyframes[start],
+                &keyframes[index + 1],
+                t,
+                &keyframes[removed],
+            ) <= tolerance
+        });
+        if !removable {
+            kept.push(index);
+        }
+    }
+    kept.push(last);
+    if kept.len() == timestamps.len() {
+        return;
+    }
+
+    curve.keyframe_timestamps = kept.iter().map(|index| timestamps[*index]).collect();
+    match &mut curve.keyframes {
+        Keyframes::Rotation(keyframes) => *keyframes = kept.iter().map(|i| keyframes[*i]).collect(),
+        Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+            *keyframes = kept.iter().map(|i| keyframes[*i]).collect();
+        }
+        Keyframes::Weights(keyframes) => {
+            *keyframes = kept
+                .iter()
+                .flat_map(|i| keyframes[stride * i..stride * (i + 1)].to_vec())
+                .collect();
+        }
+        Keyframes::Compressed(_) => {}
+    }
+}
+
+/// Loads [`AnimationClip`]s from `.anim.ron` files.
+#[derive(Default)]
+pub struct AnimationClipLoader;
+
+/// Possible errors that can be produced by [`AnimationClipLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum AnimationClipLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load animation clip: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON](ron) Error
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for AnimationClipLoader {
+    type Asset = AnimationClip;
+    type Settings = ();
+    type Error = AnimationClipLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<AnimationClip, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(ron::de::from_bytes(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.ron"]
+    }
+}
+
+/// Saves [`AnimationClip`]s compressed with [`AnimationClip::compress`], to compress them in the
+/// asset processor.
+#[derive(Default)]
+pub struct AnimationClipSaver;
+
+/// Possible errors that can be produced by [`AnimationClipSaver`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum AnimationClipSaverError {
+    /// An [IO](std::io) Error
+    #[error("Could not save animation clip: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON](ron) Error
+    #[error("Could not serialize RON: {0}")]
+    Ron(#[from] ron::Error),
+}
+
+impl AssetSaver for AnimationClipSaver {
+    type Asset = AnimationClip;
+    type Settings = ClipCompressionSettings;
+    type OutputLoader = AnimationClipLoader;
+    type Error = AnimationClipSaverError;
+
+    fn save<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: SavedAsset<'a, Self::Asset>,
+        settings: &'a Self::Settings,
+    ) -> BoxedFuture<'a, Result<(), Self::Error>> {
+        Box::pin(async move {
+            let mut clip = asset.get().clone();
+            clip.compress(settings);
+            let ron = ron::ser::to_string(&clip)?;
+            writer.write_all(ron.as_bytes()).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy_app::App;
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        AssetApp, AssetPlugin, AssetServer, Assets, LoadedAsset,
+    };
+    use bevy_core::{Name, TaskPoolPlugin};
+    use bevy_ecs::prelude::*;
+    use bevy_time::Time;
+    use bevy_transform::prelude::Transform;
+
+    use super::*;
+    use crate::{animation_player, find_keyframe, AnimationPlayer, EntityPath};
+
+    const TIMESTAMPS: [f32; 11] = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+
+    fn translation(t: f32) -> Vec3 {
+        Vec3::new(t, (t * 3.0).sin(), 2.0)
+    }
+
+    fn rotation(t: f32) -> Quat {
+        Quat::from_rotation_y(t * 2.0) * Quat::from_rotation_x((t * 4.0).sin())
+    }
+
+    fn scale(t: f32) -> Vec3 {
+        Vec3::new(1.0 + t, 1.0 + t * t, 1.0)
+    }
+
+    fn curve(keyframes: Keyframes) -> VariableCurve {
+        VariableCurve {
+            keyframe_timestamps: TIMESTAMPS.to_vec(),
+            keyframes,
+        }
+    }
+
+    /// A clip animating the translation, rotation and scale of the entity named "root".
+    fn test_clip() -> AnimationClip {
+        let path = EntityPath {
+            parts: vec![Name::new("root")],
+        };
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path.clone(),
+            curve(Keyframes::Translation(
+                TIMESTAMPS.iter().map(|t| translation(*t)).collect(),
+            )),
+        );
+        clip.add_curve_to_path(
+            path.clone(),
+            curve(Keyframes::Rotation(
+                TIMESTAMPS.iter().map(|t| rotation(*t)).collect(),
+            )),
+        );
+        clip.add_curve_to_path(
+            path,
+            curve(Keyframes::Scale(
+                TIMESTAMPS.iter().map(|t| scale(*t)).collect(),
+            )),
+        );
+        clip
+    }
+
+    /// Asserts that each component of the decoded keyframes is within half a step of `values`.
+    fn assert_within_half_a_step(compressed: &CompressedKeyframes, values: &[f32]) {
+        let decoded: Vec<f32> = match compressed.decode(0, compressed.len() - 1) {
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+                keyframes.iter().flat_map(|k| k.to_array()).collect()
+            }
+            Keyframes::Weights(keyframes) => keyframes,
+            _ => unreachable!(),
+        };
+        assert_eq!(decoded.len(), values.len());
+        for (index, (decoded, value)) in decoded.iter().zip(values).enumerate() {
+            let step = compressed.steps[index % compressed.stride];
+            assert!(
+                (decoded - value).abs() <= step / 2.0 + f32::EPSILON,
+                "{decoded} is more than half a step of {step} from {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn quantization_round_trip() {
+        let translations: Vec<Vec3> = TIMESTAMPS.iter().map(|t| translation(*t)).collect();
+        let compressed =
+            CompressedKeyframes::new(&Keyframes::Translation(translations.clone()), 11).unwrap();
+        assert_eq!(compressed.attribute(), KeyframeAttribute::Translation);
+        assert_eq!((compressed.len(), compressed.stride()), (11, 3));
+        // The z component is constant.
+        assert_eq!(compressed.steps[2], 0.0);
+        let values: Vec<f32> = translations.iter().flat_map(|t| t.to_array()).collect();
+        assert_within_half_a_step(&compressed, &values);
+
+        let scales: Vec<Vec3> = TIMESTAMPS.iter().map(|t| scale(*t)).collect();
+        let compressed = CompressedKeyframes::new(&Keyframes::Scale(scales.clone()), 11).unwrap();
+        assert_eq!(compressed.attribute(), KeyframeAttribute::Scale);
+        let values: Vec<f32> = scales.iter().flat_map(|s| s.to_array()).collect();
+        assert_within_half_a_step(&compressed, &values);
+
+        // Two morph targets, the second one constant.
+        let weights: Vec<f32> = TIMESTAMPS.iter().flat_map(|t| [*t * 0.5, 0.25]).collect();
+        let compressed =
+            CompressedKeyframes::new(&Keyframes::Weights(weights.clone()), 11).unwrap();
+        assert_eq!(compressed.attribute(), KeyframeAttribute::Weights);
+        assert_eq!(compressed.stride(), 2);
+        assert_eq!(compressed.steps[1], 0.0);
+        assert_within_half_a_step(&compressed, &weights);
+
+        // Rotations are normalized when decoded, so they are compared by angle.
+        let rotations: Vec<Quat> = TIMESTAMPS.iter().map(|t| rotation(*t)).collect();
+        let compressed =
+            CompressedKeyframes::new(&Keyframes::Rotation(rotations.clone()), 11).unwrap();
+        assert_eq!(compressed.attribute(), KeyframeAttribute::Rotation);
+        let Keyframes::Rotation(decoded) = compressed.decode(0, 10) else {
+            panic!("rotations should be decoded as rotations");
+        };
+        for (decoded, rotation) in decoded.iter().zip(&rotations) {
+            assert!(decoded.is_normalized());
+            assert!(decoded.abs_diff_eq(*rotation, 1e-4));
+        }
+    }
+
+    #[test]
+    fn nothing_to_quantize() {
+        assert_eq!(
+            CompressedKeyframes::new(&Keyframes::Translation(Vec::new()), 0),
+            None
+        );
+        let compressed =
+            CompressedKeyframes::new(&Keyframes::Translation(vec![Vec3::ONE]), 1).unwrap();
+        assert_eq!(
+            CompressedKeyframes::new(&Keyframes::Compressed(compressed), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_window() {
+        let translations: Vec<Vec3> = TIMESTAMPS.iter().map(|t| translation(*t)).collect();
+        let keyframes = Keyframes::Translation(translations.clone());
+        let compressed = Keyframes::Compressed(CompressedKeyframes::new(&keyframes, 11).unwrap());
+
+        // Uncompressed keyframes are borrowed, with the index of the start in all of them.
+        let (window, start) = keyframes.window(3, 4);
+        assert!(matches!(window, Cow::Borrowed(_)));
+        assert_eq!(start, 3);
+
+        for start in 0..10 {
+            let (window, index) = compressed.window(start, start + 1);
+            let Keyframes::Translation(window) = window.as_ref() else {
+                panic!("translations should be decoded as translations");
+            };
+            assert_eq!(index, 0);
+            assert_eq!(window.len(), 2);
+            for (decoded, translation) in window.iter().zip(&translations[start..=start + 1]) {
+                assert!(decoded.distance(*translation) < 1e-4);
+            }
+        }
+    }
+
+    /// Samples translation keyframes at `time`.
+    fn sample_translation(curve: &VariableCurve, time: f32) -> Vec3 {
+        let Keyframes::Translation(keyframes) = &curve.keyframes else {
+            panic!("the curve should animate the translation");
+        };
+        let timestamps = &curve.keyframe_timestamps;
+        match find_keyframe(timestamps, time) {
+            Some(start) => {
+                let t = (time - timestamps[start]) / (timestamps[start + 1] - timestamps[start]);
+                keyframes[start].lerp(keyframes[start + 1], t)
+            }
+            None if time <= timestamps[0] => keyframes[0],
+            None => *keyframes.last().unwrap(),
+        }
+    }
+
+    #[test]
+    fn reduce_keyframes_within_tolerance() {
+        let settings = ClipCompressionSettings {
+            translation_tolerance: 0.01,
+            rotation_tolerance: 0.001,
+            ..Default::default()
+        };
+
+        // A straight line with a bump in the middle.
+        let translations: Vec<Vec3> = TIMESTAMPS
+            .iter()
+            .map(|t| Vec3::new(*t, if *t == 0.5 { 1.0 } else { 0.0 }, 0.0))
+            .collect();
+        let original = curve(Keyframes::Translation(translations));
+        let mut reduced = original.clone();
+        reduce_keyframes(&mut reduced, &settings);
+        assert_eq!(reduced.keyframe_timestamps, [0.0, 0.4, 0.5, 0.6, 1.0]);
+        for time in TIMESTAMPS.iter().chain(&[0.05, 0.45, 0.55, 0.95]) {
+            assert!(
+                sample_translation(&reduced, *time).distance(sample_translation(&original, *time))
+                    <= settings.translation_tolerance
+            );
+        }
+
+        // A curve that isn't linear keeps enough keyframes to stay within the tolerance, and its
+        // endpoints.
+        let curved_settings = ClipCompressionSettings {
+            translation_tolerance: 0.05,
+            ..settings.clone()
+        };
+        let original = curve(Keyframes::Translation(
+            TIMESTAMPS.iter().map(|t| translation(*t)).collect(),
+        ));
+        let mut reduced = original.clone();
+        reduce_keyframes(&mut reduced, &curved_settings);
+        assert!(reduced.keyframe_timestamps.len() < TIMESTAMPS.len());
+        assert!(reduced.keyframe_timestamps.len() > 2);
+        assert_eq!(reduced.keyframe_timestamps.first(), Some(&0.0));
+        assert_eq!(reduced.keyframe_timestamps.last(), Some(&1.0));
+        for time in TIMESTAMPS {
+            assert!(
+                sample_translation(&reduced, time).distance(sample_translation(&original, time))
+                    <= curved_settings.translation_tolerance
+            );
+        }
+
+        // Rotations around a single axis at a constant speed only need their endpoints.
+        let mut rotations = curve(Keyframes::Rotation(
+            TIMESTAMPS
+                .iter()
+                .map(|t| Quat::from_rotation_z(*t))
+                .collect(),
+        ));
+        reduce_keyframes(&mut rotations, &settings);
+        assert_eq!(rotations.keyframe_timestamps, [0.0, 1.0]);
+        let Keyframes::Rotation(keyframes) = &rotations.keyframes else {
+            panic!("the curve should animate the rotation");
+        };
+        assert_eq!(keyframes, &[Quat::IDENTITY, Quat::from_rotation_z(1.0)]);
+    }
+
+    #[test]
+    fn saved_clips_load_compressed() {
+        let settings = ClipCompressionSettings::default();
+        let asset = LoadedAsset::from(test_clip()).into();
+        let mut bytes = Vec::new();
+        futures_lite::future::block_on(AnimationClipSaver.save(
+            &mut bytes,
+            SavedAsset::from_loaded(&asset).unwrap(),
+            &settings,
+        ))
+        .unwrap();
+
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("clip.anim.ron"), bytes);
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<AnimationClip>()
+        .init_asset_loader::<AnimationClipLoader>();
+        let handle = app
+            .world
+            .resource::<AssetServer>()
+            .load::<AnimationClip>("clip.anim.ron");
+
+        let mut expected = test_clip();
+        expected.compress(&settings);
+        for _ in 0..10000 {
+            app.update();
+            if let Some(loaded) = app.world.resource::<Assets<AnimationClip>>().get(&handle) {
+                assert_eq!(loaded.duration(), expected.duration());
+                assert_eq!(loaded.paths(), expected.paths());
+                for (loaded, expected) in loaded.curves()[0].iter().zip(&expected.curves()[0]) {
+                    assert_eq!(loaded.keyframe_timestamps, expected.keyframe_timestamps);
+                    let (Keyframes::Compressed(loaded), Keyframes::Compressed(expected)) =
+                        (&loaded.keyframes, &expected.keyframes)
+                    else {
+                        panic!("the saved keyframes should be compressed");
+                    };
+                    assert_eq!(loaded, expected);
+                }
+                return;
+            }
+        }
+        panic!("the clip should be loaded");
+    }
+
+    /// The transform of the entity named "root" after it is animated by `clip` at `seek_time`.
+    fn animated_transform(clip: AnimationClip, seek_time: f32) -> Transform {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let mut clips = Assets::<AnimationClip>::default();
+        let mut player = AnimationPlayer::default();
+        player.play(clips.add(clip)).seek_to(seek_time);
+        world.insert_resource(clips);
+        let root = world
+            .spawn((Name::new("root"), player, Transform::default()))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(animation_player);
+        schedule.run(&mut world);
+        *world.get::<Transform>(root).unwrap()
+    }
+
+    #[test]
+    fn compressed_clips_animate_like_uncompressed_clips() {
+        let clip = test_clip();
+        let mut compressed = clip.clone();
+        compressed.compress(&ClipCompressionSettings::default());
+        assert!(compressed
+            .curves()
+            .iter()
+            .flatten()
+            .all(|curve| matches!(curve.keyframes, Keyframes::Compressed(_))));
+
+        for seek_time in [0.0, 0.05, 0.3, 0.42, 0.77, 0.99] {
+            let expected = animated_transform(clip.clone(), seek_time);
+            let transform = animated_transform(compressed.clone(), seek_time);
+            assert!(transform.translation.distance(expected.translation) < 1e-3);
+            assert!(transform.rotation.angle_between(expected.rotation) < 1e-3);
+            assert!(transform.scale.distance(expected.scale) < 1e-3);
+            // The clip is actually sampled.
+            assert!(transform.translation.distance(translation(seek_time)) < 0.1);
+        }
+    }
+}
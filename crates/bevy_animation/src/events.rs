@@ -0,0 +1,198 @@
+use bevy_asset::AssetId;
+use bevy_ecs::{prelude::*, system::SystemId};
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{AnimationClip, AnimationGraphPlayer, AnimationPlayer};
+
+/// A named event at a time of an [`AnimationClip`], such as a footstep or the frame where an
+/// attack hits.
+#[derive(Reflect, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClipEvent {
+    /// The time of the event in the clip, in seconds.
+    pub time: f32,
+    /// The name of the event.
+    pub name: String,
+}
+
+/// Sent when the playback of an [`AnimationClip`] crosses one of its [`ClipEvent`]s.
+///
+/// Events are only sent when the animation advances with time. Seeking doesn't send the events
+/// skipped over, so that scrubbing through an animation doesn't replay them, and events crossed
+/// while playing in reverse are sent in reverse order.
+///
+/// When animations are blended, each clip contributing to the pose sends its events with its
+/// weight, so that for example only the footsteps of the dominant clip can be played.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct AnimationEvent {
+    /// The entity with the [`AnimationPlayer`] or [`AnimationGraphPlayer`] playing the clip.
+    pub entity: Entity,
+    /// The clip of the event.
+    pub clip: AssetId<AnimationClip>,
+    /// The name of the event.
+    pub name: String,
+    /// The weight of the clip in the pose, between `0.0` and `1.0`.
+    pub weight: f32,
+}
+
+/// Systems to run when the [`AnimationPlayer`] or [`AnimationGraphPlayer`] of this entity sends
+/// an [`AnimationEvent`], by event name.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_animation::{AnimationEvent, AnimationEventCallbacks};
+/// fn play_footstep(In(event): In<AnimationEvent>) {
+///     // Play a sound at `event.entity`
+/// }
+///
+/// fn setup(world: &mut World) {
+///     let footstep = world.register_system(play_footstep);
+///     world.spawn(AnimationEventCallbacks::default().with("footstep", footstep));
+/// }
+/// ```
+#[derive(Component, Default, Clone, Debug)]
+pub struct AnimationEventCallbacks {
+    callbacks: HashMap<String, SystemId<AnimationEvent>>,
+}
+
+impl AnimationEventCallbacks {
+    /// Runs `system` when an event called `name` is sent.
+    pub fn with(mut self, name: impl Into<String>, system: SystemId<AnimationEvent>) -> Self {
+        self.insert(name, system);
+        self
+    }
+
+    /// Runs `system` when an event called `name` is sent, replacing the previous system.
+    pub fn insert(&mut self, name: impl Into<String>, system: SystemId<AnimationEvent>) {
+        self.callbacks.insert(name.into(), system);
+    }
+
+    /// Stops running a system when an event called `name` is sent.
+    pub fn remove(&mut self, name: &str) -> Option<SystemId<AnimationEvent>> {
+        self.callbacks.remove(name)
+    }
+}
+
+impl AnimationClip {
+    /// [`ClipEvent`]s of the clip, sorted by time.
+    pub fn events(&self) -> &[ClipEvent] {
+        &self.events
+    }
+
+    /// Adds a [`ClipEvent`] at `time`.
+    pub fn add_event(&mut self, time: f32, name: impl Into<String>) {
+        let index = self.events.partition_point(|event| event.time <= time);
+        self.events.insert(
+            index,
+            ClipEvent {
+                time,
+                name: name.into(),
+            },
+        );
+        self.duration = self.duration.max(time);
+    }
+
+    /// Calls `f` for the events crossed when playing from `seek_time` for `advance` seconds.
+    ///
+    /// Events at `seek_time` are crossed, and events at the end aren't, so that an event is
+    /// only crossed once over consecutive frames. Looping clips can cross each event several
+    /// times, and clips that don't loop stop at their ends.
+    pub(crate) fn crossed_events(
+        &self,
+        seek_time: f32,
+        advance: f32,
+        looping: bool,
+        mut f: impl FnMut(&ClipEvent),
+    ) {
+        if self.events.is_empty() || advance == 0.0 {
+            return;
+        }
+        let end = seek_time + advance;
+        if !looping || self.duration <= 0.0 {
+            // Events at the ends of the clip are crossed when playback stops there
+            let (start, end) = (
+                seek_time.clamp(0.0, self.duration),
+                end.clamp(0.0, self.duration),
+            );
+            let clamped = end == 0.0 || end == self.duration;
+            let crossed = |time: f32| {
+                if advance > 0.0 {
+                    start <= time && (time < end || clamped && time == end)
+                } else {
+                    end <= time && time <= start && (time > end || clamped)
+                }
+            };
+            self.for_each_event(advance < 0.0, |event| {
+                if crossed(event.time) {
+                    f(event);
+                }
+            });
+            return;
+        }
+
+        // Unroll the loops covered by this advance
+        let (first_loop, last_loop) = (
+            (seek_time.min(end) / self.duration).floor() as i64,
+            (seek_time.max(end) / self.duration).floor() as i64,
+        );
+        let loops: Box<dyn Iterator<Item = i64>> = if advance > 0.0 {
+            Box::new(first_loop..=last_loop)
+        } else {
+            Box::new((first_loop..=last_loop).rev())
+        };
+        for loop_index in loops {
+            let offset = loop_index as f32 * self.duration;
+            self.for_each_event(advance < 0.0, |event| {
+                let time = event.time + offset;
+                let crossed = if advance > 0.0 {
+                    seek_time <= time && time < end
+                } else {
+                    end < time && time <= seek_time
+                };
+                if crossed {
+                    f(event);
+                }
+            });
+        }
+    }
+
+    fn for_each_event(&self, reverse: bool, f: impl FnMut(&ClipEvent)) {
+        if reverse {
+            self.events.iter().rev().for_each(f);
+        } else {
+            self.events.iter().for_each(f);
+        }
+    }
+}
+
+/// System that sends the [`AnimationEvent`]s crossed by animation players, and runs their
+/// [`AnimationEventCallbacks`].
+pub fn send_animation_events(
+    mut commands: Commands,
+    mut events: EventWriter<AnimationEvent>,
+    mut players: Query<
+        (
+            Option<&mut AnimationPlayer>,
+            Option<&mut AnimationGraphPlayer>,
+            Option<&AnimationEventCallbacks>,
+        ),
+        Or<(With<AnimationPlayer>, With<AnimationGraphPlayer>)>,
+    >,
+) {
+    for (player, graph_player, callbacks) in &mut players {
+        // Draining the events doesn't change the players, so paused players aren't updated
+        let player_events =
+            player.map(|mut player| std::mem::take(&mut player.bypass_change_detection().events));
+        let graph_events = graph_player
+            .map(|mut player| std::mem::take(&mut player.bypass_change_detection().events));
+        for event in player_events.into_iter().chain(graph_events).flatten() {
+            if let Some(system) =
+                callbacks.and_then(|callbacks| callbacks.callbacks.get(&event.name))
+            {
+                commands.run_system_with_input(*system, event.clone());
+            }
+            events.send(event);
+        }
+    }
+}
@@ -0,0 +1,1279 @@
+use std::time::Duration;
+
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{Children, Parent};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_render::mesh::morph::MorphWeights;
+use bevy_time::Time;
+use bevy_transform::prelude::Transform;
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::{
+    entity_from_path,
+    pose::{sample_clip, Pose},
+    state_machine::StateMachineState,
+    verify_no_ancestor_player, AnimationClip, AnimationEvent, AnimationPlayer, EntityPath,
+    RootMotion, StateMachineNode,
+};
+
+/// The index of a node in an [`AnimationGraph`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnimationNodeIndex(usize);
+
+impl AnimationNodeIndex {
+    /// Returns the position of the node in [`AnimationGraph::nodes`].
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A graph of [`AnimationNode`]s, blending animation clips into a single pose.
+///
+/// Leaves of the graph play [`AnimationClip`]s, and the other nodes blend the poses of their
+/// children depending on the parameters of the [`AnimationGraphPlayer`]. The graph is evaluated
+/// each frame from its [`root`](Self::root), so it can be edited while it plays through
+/// [`Assets<AnimationGraph>`].
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_math::Vec2;
+/// # use bevy_animation::{AnimationClip, AnimationGraph, Blend2dNode, ClipNode};
+/// # let [idle, walk, run, strafe_left, strafe_right]: [Handle<AnimationClip>; 5] = Default::default();
+/// let mut graph = AnimationGraph::new();
+/// let idle = graph.add_node(ClipNode::new(idle));
+/// let walk = graph.add_node(ClipNode::new(walk));
+/// let run = graph.add_node(ClipNode::new(run));
+/// let strafe_left = graph.add_node(ClipNode::new(strafe_left));
+/// let strafe_right = graph.add_node(ClipNode::new(strafe_right));
+/// let locomotion = graph.add_node(
+///     Blend2dNode::new("strafe", "forward")
+///         .with_child(Vec2::ZERO, idle)
+///         .with_child(Vec2::new(0.0, 1.5), walk)
+///         .with_child(Vec2::new(0.0, 5.0), run)
+///         .with_child(Vec2::new(-1.5, 0.0), strafe_left)
+///         .with_child(Vec2::new(1.5, 0.0), strafe_right),
+/// );
+/// graph.set_root(locomotion);
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Default)]
+pub struct AnimationGraph {
+    pub(crate) nodes: Vec<AnimationNode>,
+    root: Option<AnimationNodeIndex>,
+}
+
+impl AnimationGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph playing a single clip.
+    pub fn from_clip(clip: Handle<AnimationClip>) -> Self {
+        let mut graph = Self::new();
+        let root = graph.add_node(ClipNode::new(clip));
+        graph.set_root(root);
+        graph
+    }
+
+    /// Adds a node to the graph, and returns its index.
+    ///
+    /// The node isn't played until it's the root or a descendant of the root.
+    pub fn add_node(&mut self, node: impl Into<AnimationNode>) -> AnimationNodeIndex {
+        self.nodes.push(node.into());
+        AnimationNodeIndex(self.nodes.len() - 1)
+    }
+
+    /// Returns the node at `index`.
+    pub fn node(&self, index: AnimationNodeIndex) -> Option<&AnimationNode> {
+        self.nodes.get(index.0)
+    }
+
+    /// Returns the node at `index`, to edit it.
+    pub fn node_mut(&mut self, index: AnimationNodeIndex) -> Option<&mut AnimationNode> {
+        self.nodes.get_mut(index.0)
+    }
+
+    /// All the nodes of the graph, indexed by [`AnimationNodeIndex::index`].
+    pub fn nodes(&self) -> &[AnimationNode] {
+        &self.nodes
+    }
+
+    /// The node whose pose is applied to the animated entities.
+    pub fn root(&self) -> Option<AnimationNodeIndex> {
+        self.root
+    }
+
+    /// Sets the node whose pose is applied to the animated entities.
+    pub fn set_root(&mut self, root: AnimationNodeIndex) {
+        self.root = Some(root);
+    }
+
+    /// Returns the state machine at `index`, if that node is one.
+    pub fn state_machine(&self, index: AnimationNodeIndex) -> Option<&StateMachineNode> {
+        match self.node(index) {
+            Some(AnimationNode::StateMachine(machine)) => Some(machine),
+            _ => None,
+        }
+    }
+}
+
+/// A node of an [`AnimationGraph`].
+#[derive(Reflect, Clone, Debug)]
+pub enum AnimationNode {
+    /// Plays an animation clip.
+    Clip(ClipNode),
+    /// Blends between its children along a parameter.
+    Blend1d(Blend1dNode),
+    /// Blends between its children on a plane of two parameters.
+    Blend2d(Blend2dNode),
+    /// Layers a pose over another one.
+    Layer(LayerNode),
+    /// Plays one of its states, and cross-fades between them.
+    StateMachine(StateMachineNode),
+}
+
+impl From<ClipNode> for AnimationNode {
+    fn from(node: ClipNode) -> Self {
+        Self::Clip(node)
+    }
+}
+
+impl From<Blend1dNode> for AnimationNode {
+    fn from(node: Blend1dNode) -> Self {
+        Self::Blend1d(node)
+    }
+}
+
+impl From<Blend2dNode> for AnimationNode {
+    fn from(node: Blend2dNode) -> Self {
+        Self::Blend2d(node)
+    }
+}
+
+impl From<LayerNode> for AnimationNode {
+    fn from(node: LayerNode) -> Self {
+        Self::Layer(node)
+    }
+}
+
+impl From<StateMachineNode> for AnimationNode {
+    fn from(node: StateMachineNode) -> Self {
+        Self::StateMachine(node)
+    }
+}
+
+/// An [`AnimationNode`] playing an animation clip.
+///
+/// Each clip node keeps its own time in the [`AnimationGraphPlayer`], which advances even when
+/// the node doesn't contribute to the pose.
+#[derive(Reflect, Clone, Debug)]
+pub struct ClipNode {
+    /// The clip to play.
+    pub clip: Handle<AnimationClip>,
+    /// The speed of the clip, multiplied with the speed of the player.
+    pub speed: f32,
+    /// Whether the clip restarts when it ends, or holds its last pose.
+    pub looping: bool,
+}
+
+impl ClipNode {
+    /// Creates a node looping `clip` at normal speed.
+    pub fn new(clip: Handle<AnimationClip>) -> Self {
+        Self {
+            clip,
+            speed: 1.0,
+            looping: true,
+        }
+    }
+
+    /// Sets the speed of the clip.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Plays the clip once and holds its last pose.
+    pub fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+}
+
+/// An [`AnimationNode`] blending between its children along a parameter, for example between
+/// walking and running depending on the speed of a character.
+///
+/// Children are placed at a position on the parameter's axis. The two children around the value
+/// of the parameter are blended by how close they are, and values outside of the children play
+/// the closest one.
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct Blend1dNode {
+    /// The name of the parameter of the [`AnimationGraphPlayer`] to blend along.
+    pub parameter: String,
+    /// The position of each child on the parameter's axis.
+    pub children: Vec<(f32, AnimationNodeIndex)>,
+}
+
+impl Blend1dNode {
+    /// Creates a node blending along `parameter`, without children.
+    pub fn new(parameter: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child at `position` on the parameter's axis.
+    pub fn with_child(mut self, position: f32, child: AnimationNodeIndex) -> Self {
+        self.children.push((position, child));
+        self
+    }
+
+    /// Returns the weight of the children contributing to the pose when the parameter is
+    /// `value`.
+    pub fn weights(&self, value: f32) -> Vec<(AnimationNodeIndex, f32)> {
+        let mut below: Option<(f32, AnimationNodeIndex)> = None;
+        let mut above: Option<(f32, AnimationNodeIndex)> = None;
+        for &(position, child) in &self.children {
+            if position <= value && !matches!(below, Some((closest, _)) if closest >= position) {
+                below = Some((position, child));
+            }
+            if position >= value && !matches!(above, Some((closest, _)) if closest <= position) {
+                above = Some((position, child));
+            }
+        }
+        match (below, above) {
+            (Some((start, below)), Some((end, above))) if end > start => {
+                let t = (value - start) / (end - start);
+                vec![(below, 1.0 - t), (above, t)]
+            }
+            (Some((_, child)), _) | (None, Some((_, child))) => vec![(child, 1.0)],
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+/// An [`AnimationNode`] blending between its children on a plane of two parameters, for example
+/// between walking in eight directions depending on the velocity of a character.
+///
+/// Children are placed at a point of the plane, and weighted with gradient band interpolation:
+/// a child fades out as the parameters move towards any of the other children.
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct Blend2dNode {
+    /// The name of the parameter of the [`AnimationGraphPlayer`] on the x axis.
+    pub x_parameter: String,
+    /// The name of the parameter of the [`AnimationGraphPlayer`] on the y axis.
+    pub y_parameter: String,
+    /// The point of each child on the plane.
+    pub children: Vec<(Vec2, AnimationNodeIndex)>,
+}
+
+impl Blend2dNode {
+    /// Creates a node blending on the plane of `x_parameter` and `y_parameter`, without children.
+    pub fn new(x_parameter: impl Into<String>, y_parameter: impl Into<String>) -> Self {
+        Self {
+            x_parameter: x_parameter.into(),
+            y_parameter: y_parameter.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child at `point` on the plane.
+    pub fn with_child(mut self, point: Vec2, child: AnimationNodeIndex) -> Self {
+        self.children.push((point, child));
+        self
+    }
+
+    /// Returns the weight of each child when the parameters are `value`. The weights aren't
+    /// normalized.
+    pub fn weights(&self, value: Vec2) -> Vec<(AnimationNodeIndex, f32)> {
+        self.children
+            .iter()
+            .map(|&(point, child)| {
+                let to_value = value - point;
+                let weight = self
+                    .children
+                    .iter()
+                    .filter(|(other, _)| *other != point)
+                    .map(|(other, _)| {
+                        let edge = *other - point;
+                        1.0 - to_value.dot(edge) / edge.length_squared()
+                    })
+                    .fold(1.0, f32::min);
+                (child, weight.max(0.0))
+            })
+            .collect()
+    }
+}
+
+/// How a [`LayerNode`] combines its layer with its base.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayerBlend {
+    /// The layer replaces the base, for example to aim a weapon while walking.
+    #[default]
+    Override,
+    /// The difference between the layer and its first frame is added to the base, for example
+    /// to lean or breathe on top of any other animation.
+    Additive,
+}
+
+/// An [`AnimationNode`] layering a pose over a base pose.
+#[derive(Reflect, Clone, Debug)]
+pub struct LayerNode {
+    /// The node playing the base pose.
+    pub base: AnimationNodeIndex,
+    /// The node playing the layer.
+    pub layer: AnimationNodeIndex,
+    /// How the layer is combined with the base.
+    pub blend: LayerBlend,
+    /// The weight of the layer, between `0.0` and `1.0`.
+    pub weight: f32,
+    /// The name of a parameter of the [`AnimationGraphPlayer`] multiplied with the weight, to
+    /// fade the layer in and out.
+    pub weight_parameter: Option<String>,
+    /// The bones affected by the layer, or all of them if `None`.
+    pub mask: Option<AnimationMask>,
+}
+
+impl LayerNode {
+    /// Creates a node replacing `base` with `layer`.
+    pub fn new(base: AnimationNodeIndex, layer: AnimationNodeIndex) -> Self {
+        Self {
+            base,
+            layer,
+            blend: LayerBlend::Override,
+            weight: 1.0,
+            weight_parameter: None,
+            mask: None,
+        }
+    }
+
+    /// Creates a node adding `layer` to `base`.
+    pub fn additive(base: AnimationNodeIndex, layer: AnimationNodeIndex) -> Self {
+        Self {
+            blend: LayerBlend::Additive,
+            ..Self::new(base, layer)
+        }
+    }
+
+    /// Sets the weight of the layer.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Multiplies the weight of the layer with a parameter of the [`AnimationGraphPlayer`].
+    pub fn with_weight_parameter(mut self, parameter: impl Into<String>) -> Self {
+        self.weight_parameter = Some(parameter.into());
+        self
+    }
+
+    /// Only applies the layer to the bones of `mask`.
+    pub fn with_mask(mut self, mask: AnimationMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+}
+
+/// A set of bones affected by a [`LayerNode`].
+///
+/// Adding a bone also adds all of its descendants, so that a mask can select the upper body of a
+/// character with its spine.
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct AnimationMask {
+    /// The paths of the bones in the mask.
+    pub bones: Vec<EntityPath>,
+}
+
+impl AnimationMask {
+    /// Creates an empty mask.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a bone and its descendants to the mask.
+    pub fn with_bone(mut self, bone: EntityPath) -> Self {
+        self.bones.push(bone);
+        self
+    }
+
+    /// Whether the bone at `path` is in the mask.
+    pub fn contains(&self, path: &EntityPath) -> bool {
+        self.bones
+            .iter()
+            .any(|bone| path.parts.starts_with(&bone.parts))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct NodeState {
+    seek_time: f32,
+    state_machine: Option<StateMachineState>,
+}
+
+/// Plays an [`AnimationGraph`] on an entity and its descendants.
+///
+/// The parameters of the graph are set on the player, so that entities can share a graph. An
+/// entity shouldn't have both an [`AnimationPlayer`] and an `AnimationGraphPlayer`.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct AnimationGraphPlayer {
+    pub(crate) graph: Handle<AnimationGraph>,
+    parameters: HashMap<String, f32>,
+    paused: bool,
+    speed: f32,
+    #[reflect(ignore)]
+    nodes: Vec<NodeState>,
+    #[reflect(ignore)]
+    pub(crate) path_cache: HashMap<EntityPath, Vec<Option<Entity>>>,
+    /// States requested with [`Self::set_state`], entered on the next update.
+    #[reflect(ignore)]
+    requested_states: Vec<(AnimationNodeIndex, String, f32)>,
+    /// Events crossed by the clips, sent by [`send_animation_events`](crate::send_animation_events).
+    #[reflect(ignore)]
+    pub(crate) events: Vec<AnimationEvent>,
+}
+
+impl Default for AnimationGraphPlayer {
+    fn default() -> Self {
+        Self {
+            graph: Default::default(),
+            parameters: HashMap::default(),
+            paused: false,
+            speed: 1.0,
+            nodes: Vec::new(),
+            path_cache: HashMap::default(),
+            requested_states: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl AnimationGraphPlayer {
+    /// Creates a player for `graph`.
+    pub fn new(graph: Handle<AnimationGraph>) -> Self {
+        Self {
+            graph,
+            ..Default::default()
+        }
+    }
+
+    /// Handle to the graph being played.
+    pub fn graph(&self) -> &Handle<AnimationGraph> {
+        &self.graph
+    }
+
+    /// Plays another graph, restarting all clips.
+    pub fn set_graph(&mut self, graph: Handle<AnimationGraph>) -> &mut Self {
+        self.graph = graph;
+        self.restart();
+        self
+    }
+
+    /// Value of a parameter of the graph, or `0.0` if it isn't set.
+    pub fn parameter(&self, name: &str) -> f32 {
+        self.parameters.get(name).copied().unwrap_or(0.0)
+    }
+
+    /// Set a parameter of the graph.
+    pub fn set_parameter(&mut self, name: &str, value: f32) -> &mut Self {
+        if let Some(parameter) = self.parameters.get_mut(name) {
+            *parameter = value;
+        } else {
+            self.parameters.insert(name.to_owned(), value);
+        }
+        self
+    }
+
+    /// Set a trigger of the graph, taking the first transition waiting for it.
+    ///
+    /// The trigger stays set until a transition with a
+    /// [`TransitionCondition::Trigger`](crate::TransitionCondition::Trigger) on it is taken.
+    pub fn set_trigger(&mut self, name: &str) -> &mut Self {
+        self.set_parameter(name, 1.0)
+    }
+
+    /// Index of the current state of a [`StateMachineNode`] of the graph.
+    ///
+    /// Returns `None` if the node isn't a state machine that has started playing.
+    pub fn current_state(&self, node: AnimationNodeIndex) -> Option<usize> {
+        let state_machine = self.nodes.get(node.0)?.state_machine.as_ref()?;
+        Some(state_machine.current)
+    }
+
+    /// Check if a [`StateMachineNode`] of the graph is cross-fading between states.
+    pub fn is_transitioning(&self, node: AnimationNodeIndex) -> bool {
+        self.nodes
+            .get(node.0)
+            .and_then(|state| state.state_machine.as_ref())
+            .is_some_and(StateMachineState::is_transitioning)
+    }
+
+    /// Enter the state called `state` of a [`StateMachineNode`], cross-fading over
+    /// `transition_duration` regardless of the transitions of the machine.
+    pub fn set_state(
+        &mut self,
+        node: AnimationNodeIndex,
+        state: impl Into<String>,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        self.requested_states
+            .push((node, state.into(), transition_duration.as_secs_f32()));
+        self
+    }
+
+    /// Pause the graph
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the graph
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the graph paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Speed of the graph playback
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set the speed of the graph playback
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Seek time of a [`ClipNode`] of the graph.
+    pub fn seek_time(&self, node: AnimationNodeIndex) -> f32 {
+        self.nodes.get(node.0).map_or(0.0, |state| state.seek_time)
+    }
+
+    /// Seek to a specific time in a [`ClipNode`] of the graph.
+    ///
+    /// The [`AnimationEvent`]s skipped over aren't sent.
+    pub fn seek_to(&mut self, node: AnimationNodeIndex, seek_time: f32) -> &mut Self {
+        if self.nodes.len() <= node.0 {
+            self.nodes.resize(node.0 + 1, NodeState::default());
+        }
+        self.nodes[node.0].seek_time = seek_time;
+        self
+    }
+
+    /// Restart all the clips of the graph.
+    pub fn restart(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Advances the time of the clip nodes of `graph`, and records the events and root motion
+    /// they cross.
+    fn update(
+        &mut self,
+        root: Entity,
+        delta: f32,
+        graph: &AnimationGraph,
+        clips: &Assets<AnimationClip>,
+        mut root_motion: Option<&mut RootMotion>,
+    ) {
+        self.nodes.resize(graph.nodes.len(), NodeState::default());
+
+        let mut entered = Vec::new();
+        for (node, state, transition_duration) in std::mem::take(&mut self.requested_states) {
+            let Some(machine) = graph.state_machine(node) else {
+                warn!("Animation graph node {:?} isn't a state machine.", node);
+                continue;
+            };
+            let Some(index) = machine.state(&state) else {
+                warn!("Animation state machine has no state called {:?}.", state);
+                continue;
+            };
+            self.nodes[node.0]
+                .state_machine
+                .get_or_insert_with(|| StateMachineState::new(machine))
+                .enter(index, transition_duration);
+            entered.push(machine.states[index].node);
+        }
+        for (node, state) in graph.nodes.iter().zip(&mut self.nodes) {
+            let AnimationNode::StateMachine(machine) = node else {
+                continue;
+            };
+            let state_machine = state
+                .state_machine
+                .get_or_insert_with(|| StateMachineState::new(machine));
+            if let Some(index) = state_machine.update(machine, &mut self.parameters, delta) {
+                entered.push(machine.states[index].node);
+            }
+        }
+        // Entered states play from their start
+        for node in entered {
+            self.restart_node(graph, node, 0);
+        }
+
+        // Only the clips contributing to the pose send their events
+        let mut weights = vec![0.0; graph.nodes.len()];
+        if let Some(graph_root) = graph.root {
+            self.add_node_weights(graph, graph_root, 1.0, 0, &mut weights);
+        }
+
+        for ((node, state), weight) in graph.nodes.iter().zip(&mut self.nodes).zip(weights) {
+            let AnimationNode::Clip(clip_node) = node else {
+                continue;
+            };
+            let Some(clip) = clips.get(&clip_node.clip) else {
+                continue;
+            };
+            if let Some(root_motion) = &mut root_motion {
+                root_motion.add_motion(
+                    clip,
+                    state.seek_time,
+                    delta * clip_node.speed,
+                    clip_node.looping,
+                    weight,
+                );
+            }
+            if weight > 0.0 {
+                let clip_id = clip_node.clip.id();
+                let events = &mut self.events;
+                clip.crossed_events(
+                    state.seek_time,
+                    delta * clip_node.speed,
+                    clip_node.looping,
+                    |event| {
+                        events.push(AnimationEvent {
+                            entity: root,
+                            clip: clip_id,
+                            name: event.name.clone(),
+                            weight,
+                        });
+                    },
+                );
+            }
+            state.seek_time += delta * clip_node.speed;
+            if clip_node.looping && clip.duration > 0.0 {
+                state.seek_time = state.seek_time.rem_euclid(clip.duration);
+            } else {
+                state.seek_time = state.seek_time.clamp(0.0, clip.duration);
+            }
+        }
+    }
+
+    /// Adds the weight of `node` and its descendants in the pose to `weights`.
+    fn add_node_weights(
+        &self,
+        graph: &AnimationGraph,
+        node: AnimationNodeIndex,
+        weight: f32,
+        depth: usize,
+        weights: &mut [f32],
+    ) {
+        if weight <= 0.0 || depth > graph.nodes.len() {
+            return;
+        }
+        let Some(node_data) = graph.nodes.get(node.0) else {
+            return;
+        };
+        weights[node.0] += weight;
+
+        match node_data {
+            AnimationNode::Clip(_) => {}
+            AnimationNode::Blend1d(blend) => {
+                for (child, child_weight) in blend.weights(self.parameter(&blend.parameter)) {
+                    self.add_node_weights(graph, child, weight * child_weight, depth + 1, weights);
+                }
+            }
+            AnimationNode::Blend2d(blend) => {
+                let value = Vec2::new(
+                    self.parameter(&blend.x_parameter),
+                    self.parameter(&blend.y_parameter),
+                );
+                let children = blend.weights(value);
+                let total_weight: f32 = children.iter().map(|(_, weight)| weight).sum();
+                for (child, child_weight) in children {
+                    let child_weight = weight * child_weight / total_weight;
+                    self.add_node_weights(graph, child, child_weight, depth + 1, weights);
+                }
+            }
+            AnimationNode::Layer(layer) => {
+                let layer_weight = match &layer.weight_parameter {
+                    Some(parameter) => layer.weight * self.parameter(parameter),
+                    None => layer.weight,
+                }
+                .clamp(0.0, 1.0);
+                // A masked layer only hides part of its base
+                let base_weight = match (layer.blend, &layer.mask) {
+                    (LayerBlend::Override, None) => weight * (1.0 - layer_weight),
+                    _ => weight,
+                };
+                self.add_node_weights(graph, layer.base, base_weight, depth + 1, weights);
+                self.add_node_weights(
+                    graph,
+                    layer.layer,
+                    weight * layer_weight,
+                    depth + 1,
+                    weights,
+                );
+            }
+            AnimationNode::StateMachine(machine) => {
+                let state_machine = self.nodes[node.0].state_machine.as_ref();
+                let current = state_machine.map_or(machine.initial_state, |state| state.current);
+                // Fade the states in the same order as the pose
+                let mut states = vec![(current, 1.0)];
+                for fading in state_machine
+                    .into_iter()
+                    .flat_map(|state| state.fading.iter().rev())
+                {
+                    for (_, state_weight) in &mut states {
+                        *state_weight *= 1.0 - fading.weight;
+                    }
+                    states.push((fading.state, fading.weight));
+                }
+                for (state, state_weight) in states {
+                    if let Some(state) = machine.states.get(state) {
+                        self.add_node_weights(
+                            graph,
+                            state.node,
+                            weight * state_weight,
+                            depth + 1,
+                            weights,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Restarts the clips of `node` and its descendants.
+    fn restart_node(&mut self, graph: &AnimationGraph, node: AnimationNodeIndex, depth: usize) {
+        let Some(node_data) = graph.nodes.get(node.0) else {
+            return;
+        };
+        if depth > graph.nodes.len() {
+            return;
+        }
+        match node_data {
+            AnimationNode::Clip(_) => self.nodes[node.0].seek_time = 0.0,
+            AnimationNode::Blend1d(blend) => {
+                for &(_, child) in &blend.children {
+                    self.restart_node(graph, child, depth + 1);
+                }
+            }
+            AnimationNode::Blend2d(blend) => {
+                for &(_, child) in &blend.children {
+                    self.restart_node(graph, child, depth + 1);
+                }
+            }
+            AnimationNode::Layer(layer) => {
+                self.restart_node(graph, layer.base, depth + 1);
+                self.restart_node(graph, layer.layer, depth + 1);
+            }
+            AnimationNode::StateMachine(machine) => {
+                self.nodes[node.0].state_machine = Some(StateMachineState::new(machine));
+                if let Some(state) = machine.states.get(machine.initial_state) {
+                    self.restart_node(graph, state.node, depth + 1);
+                }
+            }
+        }
+    }
+}
+
+/// Samples the pose of the nodes of a graph.
+struct GraphEvaluator<'a, 'p> {
+    graph: &'a AnimationGraph,
+    clips: &'a Assets<AnimationClip>,
+    player: &'p AnimationGraphPlayer,
+    root_motion: Option<&'p RootMotion>,
+}
+
+impl<'a, 'p> GraphEvaluator<'a, 'p> {
+    /// Returns the pose of `node`, or the pose of its first frame if `reference` is set.
+    fn evaluate(&self, node: AnimationNodeIndex, reference: bool, depth: usize) -> Pose<'a> {
+        if depth > self.graph.nodes.len() {
+            warn!("Animation graph has a cycle through node {:?}.", node);
+            return Pose::default();
+        }
+        let Some(node_data) = self.graph.nodes.get(node.0) else {
+            return Pose::default();
+        };
+
+        match node_data {
+            AnimationNode::Clip(clip_node) => {
+                let Some(clip) = self.clips.get(&clip_node.clip) else {
+                    return Pose::default();
+                };
+                let seek_time = if reference {
+                    0.0
+                } else {
+                    self.player.seek_time(node)
+                };
+                let mut pose = sample_clip(clip, seek_time);
+                if let Some(root_motion) = self.root_motion.filter(|_| clip.root_motion()) {
+                    if let (Some(bone), Some(curves)) = (
+                        pose.get_mut(&root_motion.bone),
+                        clip.get_curves_by_path(&root_motion.bone),
+                    ) {
+                        root_motion.strip(curves, bone);
+                    }
+                }
+                pose
+            }
+            AnimationNode::Blend1d(blend) => {
+                let weights = blend.weights(self.player.parameter(&blend.parameter));
+                self.blend(&weights, reference, depth)
+            }
+            AnimationNode::Blend2d(blend) => {
+                let value = Vec2::new(
+                    self.player.parameter(&blend.x_parameter),
+                    self.player.parameter(&blend.y_parameter),
+                );
+                self.blend(&blend.weights(value), reference, depth)
+            }
+            AnimationNode::Layer(layer) => {
+                let mut pose = self.evaluate(layer.base, reference, depth + 1);
+                let weight = match &layer.weight_parameter {
+                    Some(parameter) => layer.weight * self.player.parameter(parameter),
+                    None => layer.weight,
+                };
+                if weight <= 0.0 {
+                    return pose;
+                }
+
+                let layer_pose = self.evaluate(layer.layer, reference, depth + 1);
+                let masked = |path: &EntityPath| match &layer.mask {
+                    Some(mask) => !mask.contains(path),
+                    None => false,
+                };
+                match layer.blend {
+                    LayerBlend::Override => {
+                        for (path, bone) in layer_pose {
+                            if !masked(path) {
+                                pose.entry(path).or_default().lerp(&bone, weight);
+                            }
+                        }
+                    }
+                    LayerBlend::Additive => {
+                        let reference_pose = self.evaluate(layer.layer, true, depth + 1);
+                        for (path, bone) in layer_pose {
+                            if let (false, Some(reference)) =
+                                (masked(path), reference_pose.get(path))
+                            {
+                                pose.entry(path).or_default().add(&bone, reference, weight);
+                            }
+                        }
+                    }
+                }
+                pose
+            }
+            AnimationNode::StateMachine(machine) => {
+                let state_machine = self
+                    .player
+                    .nodes
+                    .get(node.0)
+                    .and_then(|state| state.state_machine.as_ref());
+                let current = state_machine.map_or(machine.initial_state, |state| state.current);
+                let Some(current) = machine.states.get(current) else {
+                    return Pose::default();
+                };
+                let mut pose = self.evaluate(current.node, reference, depth + 1);
+                if reference {
+                    return pose;
+                }
+
+                // Fade from the newest state to the oldest, so that an interrupted cross-fade
+                // starts from its blended pose
+                let fading = state_machine
+                    .into_iter()
+                    .flat_map(|state| state.fading.iter().rev());
+                for fading in fading {
+                    let Some(fading_state) = machine.states.get(fading.state) else {
+                        continue;
+                    };
+                    for (path, bone) in self.evaluate(fading_state.node, reference, depth + 1) {
+                        pose.entry(path).or_default().lerp(&bone, fading.weight);
+                    }
+                }
+                pose
+            }
+        }
+    }
+
+    /// Returns the weighted average of the poses of `children`.
+    fn blend(
+        &self,
+        children: &[(AnimationNodeIndex, f32)],
+        reference: bool,
+        depth: usize,
+    ) -> Pose<'a> {
+        let mut pose = Pose::default();
+        let mut total_weight = 0.0;
+        for &(child, weight) in children {
+            if weight <= 0.0 {
+                continue;
+            }
+            total_weight += weight;
+            let t = weight / total_weight;
+            for (path, bone) in self.evaluate(child, reference, depth + 1) {
+                pose.entry(path).or_default().lerp(&bone, t);
+            }
+        }
+        pose
+    }
+}
+
+/// System that will play all animation graphs, using any entity with an
+/// [`AnimationGraphPlayer`] as an animation root
+#[allow(clippy::too_many_arguments)]
+pub fn animation_graph_player(
+    time: Res<Time>,
+    graphs: Res<Assets<AnimationGraph>>,
+    clips: Res<Assets<AnimationClip>>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+    transforms: Query<&mut Transform>,
+    morphs: Query<&mut MorphWeights>,
+    parents: Query<(
+        Has<AnimationPlayer>,
+        Has<AnimationGraphPlayer>,
+        Option<&Parent>,
+    )>,
+    mut graph_players: Query<(
+        Entity,
+        Option<&Parent>,
+        &mut AnimationGraphPlayer,
+        Option<&mut RootMotion>,
+    )>,
+) {
+    graph_players
+        .par_iter_mut()
+        .for_each(|(root, maybe_parent, mut player, mut root_motion)| {
+            if let Some(root_motion) = &mut root_motion {
+                root_motion.reset();
+            }
+            // Continue if paused unless the player was changed, to apply seeks and parameters
+            if player.paused && !player.is_changed() {
+                return;
+            }
+            let Some(graph) = graphs.get(&player.graph) else {
+                return;
+            };
+            let Some(graph_root) = graph.root else {
+                return;
+            };
+            if !verify_no_ancestor_player(maybe_parent, &parents) {
+                warn!("Animation player on {:?} has a conflicting animation player on an ancestor. Cannot safely animate.", root);
+                return;
+            }
+
+            let delta = if player.paused {
+                0.0
+            } else {
+                time.delta_seconds() * player.speed
+            };
+            player.update(root, delta, graph, &clips, root_motion.as_deref_mut());
+
+            let evaluator = GraphEvaluator {
+                graph,
+                clips: &clips,
+                player: &player,
+                root_motion: root_motion.as_deref(),
+            };
+            let pose = evaluator.evaluate(graph_root, false, 0);
+
+            let mut any_path_found = false;
+            for (path, bone) in &pose {
+                if !player.path_cache.contains_key(*path) {
+                    player.path_cache.insert((*path).clone(), Vec::new());
+                }
+                let path_cache = player.path_cache.get_mut(*path).unwrap();
+                let Some(target) = entity_from_path(root, path, &children, &names, path_cache)
+                else {
+                    continue;
+                };
+                any_path_found = true;
+                // SAFETY: The verify_no_ancestor_player check above ensures that two animation
+                // players cannot alias any of their descendant Transforms, as in `animation_player`.
+                let Ok(mut transform) = (unsafe { transforms.get_unchecked(target) }) else {
+                    continue;
+                };
+                if let Some(translation) = bone.translation {
+                    transform.translation = translation;
+                }
+                if let Some(rotation) = bone.rotation {
+                    transform.rotation = rotation;
+                }
+                if let Some(scale) = bone.scale {
+                    transform.scale = scale;
+                }
+                if let Some(weights) = &bone.weights {
+                    // SAFETY: As above, there can't be other players with this target so this fetch can't alias
+                    if let Ok(mut morphs) = unsafe { morphs.get_unchecked(target) } {
+                        for (morph_weight, weight) in morphs.weights_mut().iter_mut().zip(weights) {
+                            *morph_weight = *weight;
+                        }
+                    }
+                }
+            }
+
+            if !pose.is_empty() && !any_path_found {
+                warn!("Animation player on {root:?} did not match any entity paths.");
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_core::Name;
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{Keyframes, VariableCurve};
+
+    fn root_path() -> EntityPath {
+        EntityPath {
+            parts: vec![Name::new("root")],
+        }
+    }
+
+    /// A clip of a second translating the entity named "root" from `start` to `end` on the x
+    /// axis.
+    fn clip(start: f32, end: f32) -> AnimationClip {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            root_path(),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Translation(vec![Vec3::X * start, Vec3::X * end]),
+            },
+        );
+        clip
+    }
+
+    struct GraphTest {
+        world: World,
+        schedule: Schedule,
+        root: Entity,
+    }
+
+    impl GraphTest {
+        fn new(clips: Assets<AnimationClip>, graph: AnimationGraph) -> Self {
+            let mut world = World::new();
+            world.init_resource::<Time>();
+            let mut graphs = Assets::<AnimationGraph>::default();
+            let player = AnimationGraphPlayer::new(graphs.add(graph));
+            world.insert_resource(clips);
+            world.insert_resource(graphs);
+            let root = world
+                .spawn((Name::new("root"), player, Transform::default()))
+                .id();
+
+            let mut schedule = Schedule::default();
+            schedule.add_systems(animation_graph_player);
+            Self {
+                world,
+                schedule,
+                root,
+            }
+        }
+
+        fn player(&mut self) -> Mut<'_, AnimationGraphPlayer> {
+            self.world
+                .get_mut::<AnimationGraphPlayer>(self.root)
+                .unwrap()
+        }
+
+        /// Plays the graph for `delta` seconds, and returns the animated x translation.
+        fn update(&mut self, delta: f32) -> f32 {
+            self.world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(delta));
+            self.schedule.run(&mut self.world);
+            self.world
+                .get::<Transform>(self.root)
+                .unwrap()
+                .translation
+                .x
+        }
+    }
+
+    fn assert_weights(
+        weights: Vec<(AnimationNodeIndex, f32)>,
+        expected: &[(AnimationNodeIndex, f32)],
+    ) {
+        assert_eq!(weights.len(), expected.len(), "{weights:?}");
+        for ((child, weight), (expected_child, expected_weight)) in weights.iter().zip(expected) {
+            assert_eq!(child, expected_child);
+            assert!(
+                (weight - expected_weight).abs() < 1e-6,
+                "{weight} != {expected_weight}"
+            );
+        }
+    }
+
+    #[test]
+    fn blend_1d_weights() {
+        let [a, b, c] = [0, 1, 2].map(AnimationNodeIndex);
+        let blend = Blend1dNode::new("speed")
+            .with_child(0.0, a)
+            .with_child(3.0, c)
+            .with_child(1.0, b);
+        assert_weights(blend.weights(0.25), &[(a, 0.75), (b, 0.25)]);
+        assert_weights(blend.weights(2.0), &[(b, 0.5), (c, 0.5)]);
+        assert_weights(blend.weights(1.0), &[(b, 1.0)]);
+        // The closest child plays outside of the children
+        assert_weights(blend.weights(-1.0), &[(a, 1.0)]);
+        assert_weights(blend.weights(5.0), &[(c, 1.0)]);
+        assert_weights(Blend1dNode::new("speed").weights(1.0), &[]);
+    }
+
+    #[test]
+    fn blend_2d_weights() {
+        let [a, b, c] = [0, 1, 2].map(AnimationNodeIndex);
+        let blend = Blend2dNode::new("x", "y")
+            .with_child(Vec2::ZERO, a)
+            .with_child(Vec2::X, b)
+            .with_child(Vec2::Y, c);
+        assert_weights(blend.weights(Vec2::ZERO), &[(a, 1.0), (b, 0.0), (c, 0.0)]);
+        assert_weights(blend.weights(Vec2::X), &[(a, 0.0), (b, 1.0), (c, 0.0)]);
+        assert_weights(
+            blend.weights(Vec2::new(0.5, 0.0)),
+            &[(a, 0.5), (b, 0.5), (c, 0.0)],
+        );
+        // Weights are never negative past a child
+        assert_weights(
+            blend.weights(Vec2::new(2.0, 0.0)),
+            &[(a, 0.0), (b, 1.0), (c, 0.0)],
+        );
+    }
+
+    #[test]
+    fn mask_contains_descendants() {
+        let spine = EntityPath {
+            parts: vec![Name::new("root"), Name::new("spine")],
+        };
+        let mut arm = spine.clone();
+        arm.parts.push(Name::new("arm"));
+        let mask = AnimationMask::new().with_bone(spine.clone());
+        assert!(mask.contains(&spine));
+        assert!(mask.contains(&arm));
+        assert!(!mask.contains(&root_path()));
+        assert!(!AnimationMask::new().contains(&spine));
+    }
+
+    /// A graph with clips holding the entity named "root" at 0, 2 and 4 on the x axis.
+    fn still_clips() -> (
+        Assets<AnimationClip>,
+        AnimationGraph,
+        [AnimationNodeIndex; 3],
+    ) {
+        let mut clips = Assets::<AnimationClip>::default();
+        let mut graph = AnimationGraph::new();
+        let nodes = [0.0, 2.0, 4.0].map(|x| graph.add_node(ClipNode::new(clips.add(clip(x, x)))));
+        (clips, graph, nodes)
+    }
+
+    #[test]
+    fn blend_1d_mixes_poses() {
+        let (clips, mut graph, [zero, two, _]) = still_clips();
+        let blend = graph.add_node(
+            Blend1dNode::new("speed")
+                .with_child(0.0, zero)
+                .with_child(1.0, two),
+        );
+        graph.set_root(blend);
+        let mut test = GraphTest::new(clips, graph);
+
+        test.player().set_parameter("speed", 0.25);
+        assert!((test.update(0.1) - 0.5).abs() < 1e-5);
+        test.player().set_parameter("speed", 2.0);
+        assert!((test.update(0.1) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn blend_2d_normalizes_weights() {
+        let (clips, mut graph, [zero, two, four]) = still_clips();
+        let blend = graph.add_node(
+            Blend2dNode::new("x", "y")
+                .with_child(Vec2::ZERO, zero)
+                .with_child(Vec2::X, two)
+                .with_child(Vec2::Y, four),
+        );
+        graph.set_root(blend);
+        let mut test = GraphTest::new(clips, graph);
+
+        test.player().set_parameter("x", 0.5);
+        assert!((test.update(0.1) - 1.0).abs() < 1e-5);
+        test.player()
+            .set_parameter("x", 0.0)
+            .set_parameter("y", 0.5);
+        assert!((test.update(0.1) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn override_layer() {
+        let (clips, mut graph, [zero, two, _]) = still_clips();
+        let layer = graph.add_node(LayerNode::new(zero, two).with_weight_parameter("aim"));
+        graph.set_root(layer);
+        let mut test = GraphTest::new(clips, graph);
+
+        assert_eq!(test.update(0.1), 0.0);
+        test.player().set_parameter("aim", 0.25);
+        assert!((test.update(0.1) - 0.5).abs() < 1e-5);
+        test.player().set_parameter("aim", 1.0);
+        assert!((test.update(0.1) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn masked_layer_keeps_the_base_pose_outside_of_the_mask() {
+        let (clips, mut graph, [zero, two, _]) = still_clips();
+        let arm = EntityPath {
+            parts: vec![Name::new("root"), Name::new("arm")],
+        };
+        let layer = graph
+            .add_node(LayerNode::new(zero, two).with_mask(AnimationMask::new().with_bone(arm)));
+        graph.set_root(layer);
+        let mut test = GraphTest::new(clips, graph);
+
+        assert_eq!(test.update(0.1), 0.0);
+    }
+
+    #[test]
+    fn additive_layer_adds_the_difference_with_the_first_frame() {
+        let mut clips = Assets::<AnimationClip>::default();
+        let mut graph = AnimationGraph::new();
+        let base = graph.add_node(ClipNode::new(clips.add(clip(1.0, 1.0))));
+        let lean = graph.add_node(ClipNode::new(clips.add(clip(3.0, 4.0))));
+        let layer = graph.add_node(LayerNode::additive(base, lean).with_weight(0.5));
+        graph.set_root(layer);
+        let mut test = GraphTest::new(clips, graph);
+
+        assert!((test.update(0.0) - 1.0).abs() < 1e-5);
+        assert!((test.update(0.5) - 1.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn clip_nodes_loop_or_hold_their_last_pose() {
+        let mut clips = Assets::<AnimationClip>::default();
+        let mut graph = AnimationGraph::new();
+        let handle = clips.add(clip(0.0, 1.0));
+        let looping = graph.add_node(ClipNode::new(handle.clone()).with_speed(2.0));
+        let once = graph.add_node(ClipNode::new(handle).with_speed(2.0).once());
+        let layer = graph.add_node(LayerNode::new(looping, once).with_weight(0.0));
+        graph.set_root(layer);
+        let mut test = GraphTest::new(clips, graph);
+
+        assert!((test.update(0.75) - 0.5).abs() < 1e-5);
+        assert!((test.player().seek_time(looping) - 0.5).abs() < 1e-5);
+        assert_eq!(test.player().seek_time(once), 1.0);
+
+        // Clips keep their time when they don't contribute to the pose
+        test.player().seek_to(once, 0.25).set_speed(0.5);
+        test.update(0.5);
+        assert!((test.player().seek_time(looping) - 0.0).abs() < 1e-5);
+        assert!((test.player().seek_time(once) - 0.75).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,569 @@
+use bevy_ecs::{
+    entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::ReflectMapEntities,
+};
+use bevy_hierarchy::Parent;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+
+/// Bends the two bones above this entity, such as a thigh and a shin above a foot, so that this
+/// entity reaches a target.
+///
+/// The chain is solved analytically after the animations are applied, and before the transforms
+/// are propagated, so it follows the animated pose. When the target is out of reach, the chain
+/// stretches towards it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_animation::TwoBoneIk;
+/// fn place_foot(mut commands: Commands, foot: Entity, ground: Entity, knee_hint: Entity) {
+///     commands
+///         .entity(foot)
+///         .insert(TwoBoneIk::new(ground).with_pole_target(knee_hint));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, MapEntities)]
+pub struct TwoBoneIk {
+    /// The entity this entity reaches for.
+    pub target: Entity,
+    /// The entity the middle joint bends towards, such as a point in front of a knee.
+    ///
+    /// Without a pole target, the middle joint bends the way it's animated.
+    pub pole_target: Option<Entity>,
+    /// How much the solved chain overrides the animated pose, between `0.0` and `1.0`.
+    pub weight: f32,
+}
+
+// `Entity` has no meaningful default, but reflecting the component requires one.
+impl FromWorld for TwoBoneIk {
+    fn from_world(_world: &mut World) -> Self {
+        Self::new(Entity::PLACEHOLDER)
+    }
+}
+
+impl MapEntities for TwoBoneIk {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.target = entity_mapper.get_or_reserve(self.target);
+        if let Some(pole_target) = &mut self.pole_target {
+            *pole_target = entity_mapper.get_or_reserve(*pole_target);
+        }
+    }
+}
+
+impl TwoBoneIk {
+    /// Reaches for `target` with a weight of `1.0`.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            pole_target: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Bends the middle joint towards `pole_target`.
+    pub fn with_pole_target(mut self, pole_target: Entity) -> Self {
+        self.pole_target = Some(pole_target);
+        self
+    }
+
+    /// Sets how much the solved chain overrides the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Rotates a chain of bones above this entity so that this entity reaches a target, with the
+/// FABRIK algorithm.
+///
+/// Like [`TwoBoneIk`], the chain is solved after the animations are applied. With a
+/// `chain_length` of `1`, the parent of this entity points it at the target, for example with
+/// an entity in front of the eyes of a head to look at the target.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, MapEntities)]
+pub struct FabrikIk {
+    /// The entity this entity reaches for.
+    pub target: Entity,
+    /// The entity the joints of the chain bend towards.
+    pub pole_target: Option<Entity>,
+    /// The number of bones rotated, starting with the parent of this entity.
+    pub chain_length: usize,
+    /// The maximum number of iterations of the solver.
+    pub iterations: usize,
+    /// The distance to the target under which the solver stops iterating.
+    pub tolerance: f32,
+    /// How much the solved chain overrides the animated pose, between `0.0` and `1.0`.
+    pub weight: f32,
+}
+
+// `Entity` has no meaningful default, but reflecting the component requires one.
+impl FromWorld for FabrikIk {
+    fn from_world(_world: &mut World) -> Self {
+        Self::new(Entity::PLACEHOLDER, 1)
+    }
+}
+
+impl MapEntities for FabrikIk {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.target = entity_mapper.get_or_reserve(self.target);
+        if let Some(pole_target) = &mut self.pole_target {
+            *pole_target = entity_mapper.get_or_reserve(*pole_target);
+        }
+    }
+}
+
+impl FabrikIk {
+    /// Reaches for `target` by rotating the `chain_length` bones above this entity.
+    pub fn new(target: Entity, chain_length: usize) -> Self {
+        Self {
+            target,
+            pole_target: None,
+            chain_length,
+            iterations: 10,
+            tolerance: 0.001,
+            weight: 1.0,
+        }
+    }
+
+    /// Bends the joints of the chain towards `pole_target`.
+    pub fn with_pole_target(mut self, pole_target: Entity) -> Self {
+        self.pole_target = Some(pole_target);
+        self
+    }
+
+    /// Sets how much the solved chain overrides the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+type IkTransforms<'w, 's> = Query<'w, 's, (&'static mut Transform, Option<&'static Parent>)>;
+
+/// System that solves the [`TwoBoneIk`] and [`FabrikIk`] chains, in the local transforms of
+/// their bones.
+pub fn solve_inverse_kinematics(
+    two_bone_chains: Query<(Entity, &TwoBoneIk)>,
+    fabrik_chains: Query<(Entity, &FabrikIk)>,
+    mut transforms: IkTransforms,
+) {
+    for (entity, ik) in &two_bone_chains {
+        if ik.weight <= 0.0 {
+            continue;
+        }
+        let Some(chain) = Chain::new(entity, 2, ik.target, ik.pole_target, &transforms) else {
+            continue;
+        };
+        let mut positions = chain.positions.clone();
+        solve_two_bone(&mut positions, chain.target, chain.pole);
+        chain.apply(&positions, ik.weight, &mut transforms);
+    }
+
+    for (entity, ik) in &fabrik_chains {
+        if ik.weight <= 0.0 || ik.chain_length == 0 {
+            continue;
+        }
+        let Some(chain) = Chain::new(
+            entity,
+            ik.chain_length,
+            ik.target,
+            ik.pole_target,
+            &transforms,
+        ) else {
+            continue;
+        };
+        let mut positions = chain.positions.clone();
+        solve_fabrik(&mut positions, chain.target, ik.iterations, ik.tolerance);
+        if let Some(pole) = chain.pole {
+            bend_towards(&mut positions, pole);
+        }
+        chain.apply(&positions, ik.weight, &mut transforms);
+    }
+}
+
+/// The joints of a chain, with their global transforms in the animated pose.
+struct Chain {
+    /// The joints from the root of the chain to its end.
+    joints: Vec<Entity>,
+    positions: Vec<Vec3>,
+    rotations: Vec<Quat>,
+    /// The global rotation of the parent of the root.
+    parent_rotation: Quat,
+    target: Vec3,
+    pole: Option<Vec3>,
+}
+
+impl Chain {
+    fn new(
+        end: Entity,
+        length: usize,
+        target: Entity,
+        pole: Option<Entity>,
+        transforms: &IkTransforms,
+    ) -> Option<Self> {
+        let mut joints = vec![end];
+        for _ in 0..length {
+            let (_, parent) = transforms.get(*joints.last().unwrap()).ok()?;
+            joints.push(parent?.get());
+        }
+        joints.reverse();
+
+        // The transforms aren't propagated yet, so the global transforms of this frame are
+        // computed from the hierarchy.
+        let (_, root_parent) = transforms.get(joints[0]).ok()?;
+        let parent_global = match root_parent {
+            Some(parent) => global_transform(parent.get(), transforms)?,
+            None => GlobalTransform::IDENTITY,
+        };
+        let mut global = parent_global;
+        let (mut positions, mut rotations) = (Vec::new(), Vec::new());
+        for joint in &joints {
+            let (transform, _) = transforms.get(*joint).ok()?;
+            global = global * *transform;
+            let (_, rotation, translation) = global.to_scale_rotation_translation();
+            positions.push(translation);
+            rotations.push(rotation);
+        }
+
+        let target = global_transform(target, transforms)?.translation();
+        let pole = match pole {
+            Some(pole) => Some(global_transform(pole, transforms)?.translation()),
+            None => None,
+        };
+        Some(Self {
+            joints,
+            positions,
+            rotations,
+            parent_rotation: parent_global.to_scale_rotation_translation().1,
+            target,
+            pole,
+        })
+    }
+
+    /// Rotates the bones of the chain so that its joints move to `positions`, blended with the
+    /// animated pose by `weight`.
+    fn apply(&self, positions: &[Vec3], weight: f32, transforms: &mut IkTransforms) {
+        let mut parent_rotation = self.parent_rotation;
+        // The rotation applied to the bones below the ones already solved
+        let mut delta = Quat::IDENTITY;
+        for bone in 0..self.joints.len() - 1 {
+            let from =
+                (delta * (self.positions[bone + 1] - self.positions[bone])).normalize_or_zero();
+            let to = (positions[bone + 1] - positions[bone]).normalize_or_zero();
+            if from != Vec3::ZERO && to != Vec3::ZERO {
+                delta = Quat::from_rotation_arc(from, to) * delta;
+            }
+            let rotation = (delta * self.rotations[bone]).normalize();
+            if let Ok((mut transform, _)) = transforms.get_mut(self.joints[bone]) {
+                let solved = parent_rotation.inverse() * rotation;
+                transform.rotation = transform.rotation.slerp(solved, weight.min(1.0));
+            }
+            parent_rotation = rotation;
+        }
+    }
+}
+
+/// Computes the global transform of `entity` from the local transforms of its ancestors.
+fn global_transform(entity: Entity, transforms: &IkTransforms) -> Option<GlobalTransform> {
+    let (transform, mut parent) = transforms.get(entity).ok()?;
+    let mut global = GlobalTransform::from(*transform);
+    while let Some(entity) = parent {
+        let (transform, grandparent) = transforms.get(entity.get()).ok()?;
+        global = GlobalTransform::from(*transform) * global;
+        parent = grandparent;
+    }
+    Some(global)
+}
+
+/// Moves the joints of a chain of two bones so that its end reaches `target`, bending towards
+/// `pole` or the way the chain is already bent.
+fn solve_two_bone(positions: &mut [Vec3], target: Vec3, pole: Option<Vec3>) {
+    let [root, middle, end] = positions else {
+        return;
+    };
+    let upper = root.distance(*middle);
+    let lower = middle.distance(*end);
+    let to_target = target - *root;
+    let direction = to_target.normalize_or_zero();
+    if direction == Vec3::ZERO || upper == 0.0 || lower == 0.0 {
+        return;
+    }
+    let distance = to_target
+        .length()
+        .clamp((upper - lower).abs(), upper + lower);
+
+    let hint = pole.unwrap_or(*middle) - *root;
+    let mut bend = hint.reject_from_normalized(direction).normalize_or_zero();
+    if bend == Vec3::ZERO {
+        bend = direction.any_orthonormal_vector();
+    }
+    // The law of cosines gives the angle of the upper bone with the direction to the target
+    let cos = ((upper * upper + distance * distance - lower * lower) / (2.0 * upper * distance))
+        .clamp(-1.0, 1.0);
+    let sin = (1.0 - cos * cos).sqrt();
+    *middle = *root + (direction * cos + bend * sin) * upper;
+    *end = *root + direction * distance;
+}
+
+/// Moves the joints of a chain so that its end reaches `target`, with the FABRIK algorithm.
+fn solve_fabrik(positions: &mut [Vec3], target: Vec3, iterations: usize, tolerance: f32) {
+    let lengths: Vec<f32> = positions
+        .windows(2)
+        .map(|joints| joints[0].distance(joints[1]))
+        .collect();
+    let root = positions[0];
+    let last = positions.len() - 1;
+
+    if root.distance(target) >= lengths.iter().sum() {
+        // Out of reach, stretch towards the target
+        let direction = (target - root).normalize_or_zero();
+        for (bone, length) in lengths.iter().enumerate() {
+            positions[bone + 1] = positions[bone] + direction * *length;
+        }
+        return;
+    }
+
+    for _ in 0..iterations {
+        if positions[last].distance(target) <= tolerance {
+            break;
+        }
+        // Backward pass, from the end to the root
+        positions[last] = target;
+        for bone in (0..last).rev() {
+            let direction = (positions[bone] - positions[bone + 1]).normalize_or_zero();
+            positions[bone] = positions[bone + 1] + direction * lengths[bone];
+        }
+        // Forward pass, from the root to the end
+        positions[0] = root;
+        for bone in 0..last {
+            let direction = (positions[bone + 1] - positions[bone]).normalize_or_zero();
+            positions[bone + 1] = positions[bone] + direction * lengths[bone];
+        }
+    }
+}
+
+/// Rotates each joint between the root and the end of a chain around the line between its
+/// neighbours, towards `pole`.
+fn bend_towards(positions: &mut [Vec3], pole: Vec3) {
+    for joint in 1..positions.len().saturating_sub(1) {
+        let (previous, next) = (positions[joint - 1], positions[joint + 1]);
+        let axis = (next - previous).normalize_or_zero();
+        if axis == Vec3::ZERO {
+            continue;
+        }
+        let from = (positions[joint] - previous)
+            .reject_from_normalized(axis)
+            .normalize_or_zero();
+        let to = (pole - previous)
+            .reject_from_normalized(axis)
+            .normalize_or_zero();
+        if from != Vec3::ZERO && to != Vec3::ZERO {
+            let angle = from.cross(to).dot(axis).atan2(from.dot(to));
+            let rotation = Quat::from_axis_angle(axis, angle);
+            positions[joint] = previous + rotation * (positions[joint] - previous);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_core::{Name, TaskPoolPlugin};
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_time::TimePlugin;
+    use bevy_transform::{
+        systems::{propagate_transforms, sync_simple_transforms},
+        TransformBundle, TransformPlugin,
+    };
+
+    use super::*;
+    use crate::{
+        AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes, VariableCurve,
+    };
+
+    const EPSILON: f32 = 1e-4;
+
+    fn assert_close(actual: Vec3, expected: Vec3) {
+        assert!(
+            actual.abs_diff_eq(expected, EPSILON),
+            "{actual} is not {expected}"
+        );
+    }
+
+    fn straight_chain(joints: usize) -> Vec<Vec3> {
+        (0..joints).map(|joint| Vec3::Y * joint as f32).collect()
+    }
+
+    #[test]
+    fn two_bone_reaches_target() {
+        let mut positions = straight_chain(3);
+        let target = Vec3::new(1.0, 1.0, 0.0);
+        solve_two_bone(&mut positions, target, None);
+
+        assert_close(positions[0], Vec3::ZERO);
+        assert_close(positions[2], target);
+        assert!((positions[0].distance(positions[1]) - 1.0).abs() < EPSILON);
+        assert!((positions[1].distance(positions[2]) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn two_bone_stretches_towards_unreachable_target() {
+        let mut positions = straight_chain(3);
+        solve_two_bone(&mut positions, Vec3::new(0.0, 0.0, 5.0), None);
+
+        assert_close(positions[0], Vec3::ZERO);
+        assert_close(positions[1], Vec3::Z);
+        assert_close(positions[2], Vec3::Z * 2.0);
+    }
+
+    #[test]
+    fn two_bone_bends_towards_pole() {
+        let target = Vec3::new(0.0, 1.5, 0.0);
+        for pole in [Vec3::Z, Vec3::NEG_Z, Vec3::X] {
+            let mut positions = straight_chain(3);
+            solve_two_bone(&mut positions, target, Some(pole));
+
+            assert_close(positions[2], target);
+            let bend = (positions[1] - positions[0]).reject_from(Vec3::Y);
+            assert!(bend.normalize().abs_diff_eq(pole, EPSILON), "{bend}");
+        }
+    }
+
+    #[test]
+    fn fabrik_reaches_target() {
+        let mut positions: Vec<Vec3> = (0..4).map(|joint| Vec3::X * joint as f32).collect();
+        let target = Vec3::new(1.0, 2.0, 0.0);
+        solve_fabrik(&mut positions, target, 100, 0.001);
+
+        assert_close(positions[0], Vec3::ZERO);
+        assert!(positions[3].distance(target) <= 0.001);
+        for bone in positions.windows(2) {
+            assert!((bone[0].distance(bone[1]) - 1.0).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn fabrik_stretches_towards_unreachable_target() {
+        let mut positions: Vec<Vec3> = (0..4).map(|joint| Vec3::X * joint as f32).collect();
+        solve_fabrik(&mut positions, Vec3::new(0.0, 10.0, 0.0), 10, 0.001);
+
+        assert_eq!(positions, straight_chain(4));
+    }
+
+    #[test]
+    fn bend_towards_rotates_around_neighbours() {
+        let mut positions = vec![Vec3::ZERO, Vec3::new(0.5, 0.5, 0.0), Vec3::X];
+        bend_towards(&mut positions, Vec3::new(0.5, 0.0, 1.0));
+
+        assert_close(positions[0], Vec3::ZERO);
+        assert_close(positions[1], Vec3::new(0.5, 0.0, 0.5));
+        assert_close(positions[2], Vec3::X);
+    }
+
+    /// Spawns an upper bone at the origin, with a lower bone and an end each one unit above
+    /// their parent, and returns them with the target of the end.
+    fn spawn_leg(world: &mut World, ik: impl FnOnce(Entity) -> TwoBoneIk) -> [Entity; 4] {
+        let target = world
+            .spawn(TransformBundle::from_transform(Transform::from_xyz(
+                1.0, 1.0, 0.0,
+            )))
+            .id();
+        let end = world
+            .spawn((
+                Name::new("end"),
+                TransformBundle::from_transform(Transform::from_xyz(0.0, 1.0, 0.0)),
+                ik(target),
+            ))
+            .id();
+        let lower = world
+            .spawn((
+                Name::new("lower"),
+                TransformBundle::from_transform(Transform::from_xyz(0.0, 1.0, 0.0)),
+            ))
+            .add_child(end)
+            .id();
+        let upper = world
+            .spawn((Name::new("upper"), TransformBundle::default()))
+            .add_child(lower)
+            .id();
+        [upper, lower, end, target]
+    }
+
+    fn solve(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                solve_inverse_kinematics,
+                (sync_simple_transforms, propagate_transforms),
+            )
+                .chain(),
+        );
+        schedule.run(world);
+    }
+
+    #[test]
+    fn solved_chain_reaches_target() {
+        let mut world = World::new();
+        let [_, _, end, _] = spawn_leg(&mut world, TwoBoneIk::new);
+        solve(&mut world);
+
+        let end = world.get::<GlobalTransform>(end).unwrap();
+        assert_close(end.translation(), Vec3::new(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn zero_weight_keeps_pose() {
+        let mut world = World::new();
+        let leg = spawn_leg(&mut world, |target| TwoBoneIk::new(target).with_weight(0.0));
+        let pose: Vec<Transform> = leg
+            .iter()
+            .map(|entity| *world.get::<Transform>(*entity).unwrap())
+            .collect();
+        solve(&mut world);
+
+        for (entity, transform) in leg.iter().zip(pose) {
+            assert_eq!(*world.get::<Transform>(*entity).unwrap(), transform);
+        }
+    }
+
+    #[test]
+    fn chains_are_solved_between_animation_and_propagation() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            TimePlugin,
+            AssetPlugin::default(),
+            TransformPlugin,
+            AnimationPlugin,
+        ));
+
+        // The clip bends the upper bone away from the target, which the chain then overrides.
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            EntityPath {
+                parts: vec![Name::new("root"), Name::new("upper")],
+            },
+            VariableCurve {
+                keyframe_timestamps: vec![0.0],
+                keyframes: Keyframes::Rotation(vec![Quat::from_rotation_z(1.0)]),
+            },
+        );
+        let clip = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+
+        let [upper, _, end, _] = spawn_leg(&mut app.world, TwoBoneIk::new);
+        let mut player = AnimationPlayer::default();
+        player.play(clip);
+        app.world
+            .spawn((Name::new("root"), TransformBundle::default(), player))
+            .add_child(upper);
+
+        app.update();
+
+        let end = app.world.get::<GlobalTransform>(end).unwrap();
+        assert_close(end.translation(), Vec3::new(1.0, 1.0, 0.0));
+    }
+}
@@ -2,11 +2,22 @@
 
 #![warn(missing_docs)]
 
+mod compression;
+mod events;
+mod graph;
+mod ik;
+mod pose;
+mod property;
+mod retarget;
+mod root_motion;
+mod state_machine;
+mod tween;
+
 use std::ops::Deref;
 use std::time::Duration;
 
 use bevy_app::{App, Plugin, PostUpdate};
-use bevy_asset::{Asset, AssetApp, Assets, Handle};
+use bevy_asset::{processor::LoadAndSave, Asset, AssetApp, Assets, Handle};
 use bevy_core::Name;
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::{Children, Parent};
@@ -16,17 +27,35 @@ use bevy_render::mesh::morph::MorphWeights;
 use bevy_time::Time;
 use bevy_transform::{prelude::Transform, TransformSystem};
 use bevy_utils::{tracing::warn, HashMap};
+use property::AnimatedProperty;
+use serde::{Deserialize, Serialize};
+
+pub use compression::{
+    AnimationClipLoader, AnimationClipLoaderError, AnimationClipSaver, AnimationClipSaverError,
+    ClipCompressionSettings, CompressedKeyframes, KeyframeAttribute,
+};
+pub use events::{send_animation_events, AnimationEvent, AnimationEventCallbacks, ClipEvent};
+pub use graph::*;
+pub use ik::{solve_inverse_kinematics, FabrikIk, TwoBoneIk};
+pub use property::{animate_properties, PropertyCurve, PropertyKeyframes, PropertyValue};
+pub use retarget::{
+    retarget_animations, AnimationRetargeting, BoneRetarget, RetargetMap, RetargetedAnimations,
+};
+pub use root_motion::RootMotion;
+pub use state_machine::{GraphState, StateMachineNode, StateTransition, TransitionCondition};
+pub use tween::{update_tweens, EaseFunction, Tween, TweenCompleted, TweenStep, TweenTarget};
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes, VariableCurve,
+        AnimationClip, AnimationEvent, AnimationGraph, AnimationGraphPlayer, AnimationPlayer,
+        AnimationPlugin, EaseFunction, EntityPath, Keyframes, Tween, TweenTarget, VariableCurve,
     };
 }
 
 /// List of keyframes for one of the attribute of a [`Transform`].
-#[derive(Reflect, Clone, Debug)]
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
 pub enum Keyframes {
     /// Keyframes for rotation.
     Rotation(Vec<Quat>),
@@ -43,12 +72,14 @@ pub enum Keyframes {
     ///
     /// [glTF design]: https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#animations
     Weights(Vec<f32>),
+    /// Keyframes for one of the other attributes, compressed by [`AnimationClip::compress`].
+    Compressed(CompressedKeyframes),
 }
 
 /// Describes how an attribute of a [`Transform`] or [`MorphWeights`] should be animated.
 ///
 /// `keyframe_timestamps` and `keyframes` should have the same length.
-#[derive(Reflect, Clone, Debug)]
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
 pub struct VariableCurve {
     /// Timestamp for each of the keyframes.
     pub keyframe_timestamps: Vec<f32>,
@@ -56,19 +87,49 @@ pub struct VariableCurve {
     pub keyframes: Keyframes,
 }
 
+impl VariableCurve {
+    /// Find the index of the keyframe at or before the current time.
+    ///
+    /// Returns `None` if the curve hasn't started yet, or if it's finished. The keyframe after
+    /// the returned index can be used to interpolate the curve.
+    pub fn find_current_keyframe(&self, seek_time: f32) -> Option<usize> {
+        find_keyframe(&self.keyframe_timestamps, seek_time)
+    }
+}
+
+/// Find the index of the timestamp at or before `seek_time`, or `None` if it's before the first
+/// timestamp, or at or after the last one.
+pub(crate) fn find_keyframe(keyframe_timestamps: &[f32], seek_time: f32) -> Option<usize> {
+    // PERF: finding the current keyframe can be optimised
+    match keyframe_timestamps.binary_search_by(|probe| probe.partial_cmp(&seek_time).unwrap()) {
+        Ok(n) if n >= keyframe_timestamps.len() - 1 => None, // this curve is finished
+        Ok(i) => Some(i),
+        Err(0) => None, // this curve isn't started yet
+        Err(n) if n > keyframe_timestamps.len() - 1 => None, // this curve is finished
+        Err(i) => Some(i - 1),
+    }
+}
+
 /// Path to an entity, with [`Name`]s. Each entity in a path must have a name.
-#[derive(Reflect, Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[derive(Reflect, Clone, Debug, Hash, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct EntityPath {
     /// Parts of the path
     pub parts: Vec<Name>,
 }
 
 /// A list of [`VariableCurve`], and the [`EntityPath`] to which they apply.
-#[derive(Asset, Reflect, Clone, Debug, Default)]
+///
+/// A clip can also have [`ClipEvent`]s, sent as [`AnimationEvent`]s when it plays, and
+/// [`PropertyCurve`]s animating the fields of other components.
+#[derive(Asset, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct AnimationClip {
     curves: Vec<Vec<VariableCurve>>,
     paths: HashMap<EntityPath, usize>,
     duration: f32,
+    events: Vec<ClipEvent>,
+    root_motion: bool,
+    /// The property curves of each path, by the index of the path like `curves`.
+    properties: Vec<Vec<PropertyCurve>>,
 }
 
 impl AnimationClip {
@@ -94,6 +155,13 @@ impl AnimationClip {
         self.paths.get(path).and_then(|id| self.curves.get(*id))
     }
 
+    /// The [`EntityPath`]s animated by the clip, with the index of their curves in
+    /// [`AnimationClip::curves`].
+    #[inline]
+    pub fn paths(&self) -> &HashMap<EntityPath, usize> {
+        &self.paths
+    }
+
     /// Duration of the clip, represented in seconds
     #[inline]
     pub fn duration(&self) -> f32 {
@@ -241,6 +309,14 @@ pub struct AnimationPlayer {
     // Once a transition is finished, it will be automatically removed from the list
     #[reflect(ignore)]
     transitions: Vec<AnimationTransition>,
+
+    /// Events crossed by the animations, sent by [`send_animation_events`].
+    #[reflect(ignore)]
+    events: Vec<AnimationEvent>,
+
+    /// Fields of components sampled from property curves, set by [`animate_properties`].
+    #[reflect(ignore)]
+    properties: Vec<AnimatedProperty>,
 }
 
 impl AnimationPlayer {
@@ -452,19 +528,24 @@ fn entity_from_path(
     Some(current_entity)
 }
 
-/// Verify that there are no ancestors of a given entity that have an [`AnimationPlayer`].
+/// Verify that there are no ancestors of a given entity that have an [`AnimationPlayer`] or an
+/// [`AnimationGraphPlayer`].
 fn verify_no_ancestor_player(
     player_parent: Option<&Parent>,
-    parents: &Query<(Has<AnimationPlayer>, Option<&Parent>)>,
+    parents: &Query<(
+        Has<AnimationPlayer>,
+        Has<AnimationGraphPlayer>,
+        Option<&Parent>,
+    )>,
 ) -> bool {
     let Some(mut current) = player_parent.map(Parent::get) else {
         return true;
     };
     loop {
-        let Ok((has_player, parent)) = parents.get(current) else {
+        let Ok((has_player, has_graph_player, parent)) = parents.get(current) else {
             return true;
         };
-        if has_player {
+        if has_player || has_graph_player {
             return false;
         }
         if let Some(parent) = parent {
@@ -485,16 +566,26 @@ pub fn animation_player(
     names: Query<&Name>,
     transforms: Query<&mut Transform>,
     morphs: Query<&mut MorphWeights>,
-    parents: Query<(Has<AnimationPlayer>, Option<&Parent>)>,
-    mut animation_players: Query<(Entity, Option<&Parent>, &mut AnimationPlayer)>,
+    parents: Query<(
+        Has<AnimationPlayer>,
+        Has<AnimationGraphPlayer>,
+        Option<&Parent>,
+    )>,
+    mut animation_players: Query<(
+        Entity,
+        Option<&Parent>,
+        &mut AnimationPlayer,
+        Option<&mut RootMotion>,
+    )>,
 ) {
     animation_players
         .par_iter_mut()
-        .for_each(|(root, maybe_parent, mut player)| {
+        .for_each(|(root, maybe_parent, mut player, root_motion)| {
             update_transitions(&mut player, &time);
             run_animation_player(
                 root,
                 player,
+                root_motion,
                 &time,
                 &animations,
                 &names,
@@ -511,15 +602,23 @@ pub fn animation_player(
 fn run_animation_player(
     root: Entity,
     mut player: Mut<AnimationPlayer>,
+    mut root_motion: Option<Mut<RootMotion>>,
     time: &Time,
     animations: &Assets<AnimationClip>,
     names: &Query<&Name>,
     transforms: &Query<&mut Transform>,
     morphs: &Query<&mut MorphWeights>,
     maybe_parent: Option<&Parent>,
-    parents: &Query<(Has<AnimationPlayer>, Option<&Parent>)>,
+    parents: &Query<(
+        Has<AnimationPlayer>,
+        Has<AnimationGraphPlayer>,
+        Option<&Parent>,
+    )>,
     children: &Query<&Children>,
 ) {
+    if let Some(root_motion) = &mut root_motion {
+        root_motion.reset();
+    }
     let paused = player.paused;
     // Continue if paused unless the `AnimationPlayer` was changed
     // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
@@ -527,10 +626,14 @@ fn run_animation_player(
         return;
     }
 
+    let player = &mut *player;
     // Apply the main animation
     apply_animation(
         1.0,
         &mut player.animation,
+        &mut player.events,
+        &mut player.properties,
+        root_motion.as_deref_mut(),
         paused,
         root,
         time,
@@ -553,6 +656,9 @@ fn run_animation_player(
         apply_animation(
             *current_weight,
             animation,
+            &mut player.events,
+            &mut player.properties,
+            root_motion.as_deref_mut(),
             paused,
             root,
             time,
@@ -595,6 +701,9 @@ fn get_keyframe(target_count: usize, keyframes: &[f32], key_index: usize) -> &[f
 fn apply_animation(
     weight: f32,
     animation: &mut PlayingAnimation,
+    events: &mut Vec<AnimationEvent>,
+    properties: &mut Vec<AnimatedProperty>,
+    mut root_motion: Option<&mut RootMotion>,
     paused: bool,
     root: Entity,
     time: &Time,
@@ -603,15 +712,44 @@ fn apply_animation(
     transforms: &Query<&mut Transform>,
     morphs: &Query<&mut MorphWeights>,
     maybe_parent: Option<&Parent>,
-    parents: &Query<(Has<AnimationPlayer>, Option<&Parent>)>,
+    parents: &Query<(
+        Has<AnimationPlayer>,
+        Has<AnimationGraphPlayer>,
+        Option<&Parent>,
+    )>,
     children: &Query<&Children>,
 ) {
     if let Some(animation_clip) = animations.get(&animation.animation_clip) {
         // We don't return early because seek_to() may have been called on the animation player.
-        animation.update(
-            if paused { 0.0 } else { time.delta_seconds() },
-            animation_clip.duration,
-        );
+        let delta = if paused { 0.0 } else { time.delta_seconds() };
+        let (seek_time, was_finished) = (animation.seek_time, animation.is_finished());
+        animation.update(delta, animation_clip.duration);
+
+        if !was_finished && weight > 0.0 {
+            if let Some(root_motion) = &mut root_motion {
+                root_motion.add_motion(
+                    animation_clip,
+                    seek_time,
+                    delta * animation.speed,
+                    !animation.is_finished(),
+                    weight,
+                );
+            }
+            let clip = animation.animation_clip.id();
+            animation_clip.crossed_events(
+                seek_time,
+                delta * animation.speed,
+                !animation.is_finished(),
+                |event| {
+                    events.push(AnimationEvent {
+                        entity: root,
+                        clip,
+                        name: event.name.clone(),
+                        weight,
+                    });
+                },
+            );
+        }
 
         if animation.path_cache.len() != animation_clip.paths.len() {
             animation.path_cache = vec![Vec::new(); animation_clip.paths.len()];
@@ -629,6 +767,9 @@ fn apply_animation(
                 continue;
             };
             any_path_found = true;
+            if let Some(curves) = animation_clip.properties.get(*bone_id) {
+                AnimatedProperty::sample(properties, target, curves, animation.seek_time, weight);
+            }
             // SAFETY: The verify_no_ancestor_player check above ensures that two animation players cannot alias
             // any of their descendant Transforms.
             //
@@ -646,10 +787,27 @@ fn apply_animation(
             };
             // SAFETY: As above, there can't be other AnimationPlayers with this target so this fetch can't alias
             let mut morphs = unsafe { morphs.get_unchecked(target) };
+            let root_motion = root_motion
+                .as_deref()
+                .filter(|root_motion| root_motion.strips(animation_clip, path));
             for curve in curves {
+                // The extracted motion of the root bone holds its first keyframe
+                let seek_time = match (curve.keyframes.attribute(), root_motion) {
+                    (KeyframeAttribute::Translation, Some(root_motion))
+                        if root_motion.translation =>
+                    {
+                        f32::NEG_INFINITY
+                    }
+                    (KeyframeAttribute::Rotation, Some(root_motion)) if root_motion.rotation => {
+                        f32::NEG_INFINITY
+                    }
+                    _ => animation.seek_time,
+                };
+
                 // Some curves have only one keyframe used to set a transform
                 if curve.keyframe_timestamps.len() == 1 {
-                    match &curve.keyframes {
+                    let (keyframes, _) = curve.keyframes.window(0, 0);
+                    match keyframes.as_ref() {
                         Keyframes::Rotation(keyframes) => {
                             transform.rotation = transform.rotation.slerp(keyframes[0], weight);
                         }
@@ -670,28 +828,27 @@ fn apply_animation(
                                 );
                             }
                         }
+                        // Decoded by `window`
+                        Keyframes::Compressed(_) => {}
                     }
                     continue;
                 }
 
                 // Find the current keyframe
-                // PERF: finding the current keyframe can be optimised
-                let step_start = match curve
-                    .keyframe_timestamps
-                    .binary_search_by(|probe| probe.partial_cmp(&animation.seek_time).unwrap())
-                {
-                    Ok(n) if n >= curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                    Ok(i) => i,
-                    Err(0) => continue, // this curve isn't started yet
-                    Err(n) if n > curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                    Err(i) => i - 1,
+                let (step_start, lerp) = match curve.find_current_keyframe(seek_time) {
+                    Some(step_start) => {
+                        let ts_start = curve.keyframe_timestamps[step_start];
+                        let ts_end = curve.keyframe_timestamps[step_start + 1];
+                        (step_start, (seek_time - ts_start) / (ts_end - ts_start))
+                    }
+                    None if seek_time == f32::NEG_INFINITY => (0, 0.0),
+                    None => continue,
                 };
-                let ts_start = curve.keyframe_timestamps[step_start];
-                let ts_end = curve.keyframe_timestamps[step_start + 1];
-                let lerp = (animation.seek_time - ts_start) / (ts_end - ts_start);
 
-                // Apply the keyframe
-                match &curve.keyframes {
+                // Apply the keyframe, decoding only the keyframes around the seek time of
+                // compressed curves
+                let (keyframes, step_start) = curve.keyframes.window(step_start, step_start + 1);
+                match keyframes.as_ref() {
                     Keyframes::Rotation(keyframes) => {
                         let rot_start = keyframes[step_start];
                         let mut rot_end = keyframes[step_start + 1];
@@ -727,6 +884,8 @@ fn apply_animation(
                             lerp_morph_weights(morphs.weights_mut(), result, weight);
                         }
                     }
+                    // Decoded by `window`
+                    Keyframes::Compressed(_) => {}
                 }
             }
         }
@@ -752,10 +911,40 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<AnimationClip>()
             .register_asset_reflect::<AnimationClip>()
+            .init_asset_loader::<AnimationClipLoader>()
+            .register_asset_processor::<LoadAndSave<AnimationClipLoader, AnimationClipSaver>>(
+                AnimationClipSaver.into(),
+            )
+            .set_default_asset_processor::<LoadAndSave<AnimationClipLoader, AnimationClipSaver>>(
+                "anim.ron",
+            )
+            .init_asset::<AnimationGraph>()
+            .register_asset_reflect::<AnimationGraph>()
+            .init_asset::<RetargetMap>()
+            .register_asset_reflect::<RetargetMap>()
             .register_type::<AnimationPlayer>()
+            .register_type::<AnimationGraphPlayer>()
+            .register_type::<RootMotion>()
+            .register_type::<TwoBoneIk>()
+            .register_type::<FabrikIk>()
+            .register_type::<AnimationRetargeting>()
+            .register_type::<Tween>()
+            .init_resource::<RetargetedAnimations>()
+            .add_event::<AnimationEvent>()
+            .add_event::<TweenCompleted>()
             .add_systems(
                 PostUpdate,
-                animation_player.before(TransformSystem::TransformPropagate),
+                (
+                    retarget_animations,
+                    animation_player,
+                    animation_graph_player,
+                    update_tweens,
+                    solve_inverse_kinematics,
+                    animate_properties,
+                    send_animation_events,
+                )
+                    .chain()
+                    .before(TransformSystem::TransformPropagate),
             );
     }
 }
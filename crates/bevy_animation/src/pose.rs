@@ -0,0 +1,140 @@
+use bevy_math::{Quat, Vec3};
+use bevy_utils::HashMap;
+
+use crate::{AnimationClip, EntityPath, Keyframes, VariableCurve};
+
+/// The sampled transform and morph weights of a bone.
+///
+/// Curves don't have to animate every attribute of a bone, so each attribute is optional. Blending
+/// keeps the attributes that only one of the poses has.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct BonePose {
+    pub(crate) translation: Option<Vec3>,
+    pub(crate) rotation: Option<Quat>,
+    pub(crate) scale: Option<Vec3>,
+    pub(crate) weights: Option<Vec<f32>>,
+}
+
+/// The sampled bones of an animation, by their path.
+pub(crate) type Pose<'a> = HashMap<&'a EntityPath, BonePose>;
+
+impl BonePose {
+    /// Interpolates towards `other` by `t`.
+    pub(crate) fn lerp(&mut self, other: &BonePose, t: f32) {
+        fn lerp_attribute<T: Copy>(a: &mut Option<T>, b: Option<T>, lerp: impl Fn(T, T) -> T) {
+            *a = match (*a, b) {
+                (Some(a), Some(b)) => Some(lerp(a, b)),
+                (a, b) => a.or(b),
+            };
+        }
+        lerp_attribute(&mut self.translation, other.translation, |a, b| {
+            a.lerp(b, t)
+        });
+        lerp_attribute(&mut self.rotation, other.rotation, |a, b| a.slerp(b, t));
+        lerp_attribute(&mut self.scale, other.scale, |a, b| a.lerp(b, t));
+        match (&mut self.weights, &other.weights) {
+            (Some(weights), Some(other)) => {
+                for (weight, other) in weights.iter_mut().zip(other) {
+                    *weight += (*other - *weight) * t;
+                }
+            }
+            (weights @ None, Some(other)) => *weights = Some(other.clone()),
+            _ => {}
+        }
+    }
+
+    /// Adds the difference between `pose` and `reference`, scaled by `weight`.
+    pub(crate) fn add(&mut self, pose: &BonePose, reference: &BonePose, weight: f32) {
+        if let (Some(translation), Some(reference)) = (pose.translation, reference.translation) {
+            let delta = (translation - reference) * weight;
+            *self.translation.get_or_insert(Vec3::ZERO) += delta;
+        }
+        if let (Some(rotation), Some(reference)) = (pose.rotation, reference.rotation) {
+            let delta = Quat::IDENTITY.slerp(reference.inverse() * rotation, weight);
+            let base = self.rotation.get_or_insert(Quat::IDENTITY);
+            *base = (*base * delta).normalize();
+        }
+        if let (Some(scale), Some(reference)) = (pose.scale, reference.scale) {
+            let delta = Vec3::ONE.lerp(scale / reference, weight);
+            *self.scale.get_or_insert(Vec3::ONE) *= delta;
+        }
+        if let (Some(weights), Some(reference)) = (&pose.weights, &reference.weights) {
+            let base = self.weights.get_or_insert_with(|| vec![0.0; weights.len()]);
+            for ((base, weight_value), reference) in base.iter_mut().zip(weights).zip(reference) {
+                *base += (weight_value - reference) * weight;
+            }
+        }
+    }
+}
+
+/// Samples the bones of `clip` at `seek_time`.
+///
+/// Unlike [`AnimationPlayer`](crate::AnimationPlayer), curves that haven't started or have
+/// finished hold their first or last keyframe, so that poses can be blended without gaps.
+pub(crate) fn sample_clip(clip: &AnimationClip, seek_time: f32) -> Pose<'_> {
+    clip.paths
+        .iter()
+        .map(|(path, bone_id)| (path, sample_bone(&clip.curves[*bone_id], seek_time)))
+        .collect()
+}
+
+/// Samples the curves of a bone at `seek_time`, holding their first or last keyframe outside of
+/// them.
+pub(crate) fn sample_bone(curves: &[VariableCurve], seek_time: f32) -> BonePose {
+    let mut bone = BonePose::default();
+    for curve in curves {
+        sample_curve(curve, seek_time, &mut bone);
+    }
+    bone
+}
+
+fn sample_curve(curve: &VariableCurve, seek_time: f32, bone: &mut BonePose) {
+    let timestamps = &curve.keyframe_timestamps;
+    let Some(last) = timestamps.len().checked_sub(1) else {
+        return;
+    };
+    let (step_start, lerp) = match curve.find_current_keyframe(seek_time) {
+        Some(step_start) => {
+            let ts_start = timestamps[step_start];
+            let ts_end = timestamps[step_start + 1];
+            (step_start, (seek_time - ts_start) / (ts_end - ts_start))
+        }
+        None if seek_time < timestamps[0] => (0, 0.0),
+        None => (last, 0.0),
+    };
+    let step_end = (step_start + 1).min(last);
+    let target_count = curve.keyframes.morph_target_count(timestamps.len());
+    // Only the sampled keyframes of compressed curves are decoded
+    let (keyframes, window_start) = curve.keyframes.window(step_start, step_end);
+    let (step_start, step_end) = (window_start, window_start + step_end - step_start);
+
+    match keyframes.as_ref() {
+        Keyframes::Rotation(keyframes) => {
+            let rot_start = keyframes[step_start];
+            let mut rot_end = keyframes[step_end];
+            // Choose the smallest angle for the rotation
+            if rot_end.dot(rot_start) < 0.0 {
+                rot_end = -rot_end;
+            }
+            bone.rotation = Some(rot_start.normalize().slerp(rot_end.normalize(), lerp));
+        }
+        Keyframes::Translation(keyframes) => {
+            bone.translation = Some(keyframes[step_start].lerp(keyframes[step_end], lerp));
+        }
+        Keyframes::Scale(keyframes) => {
+            bone.scale = Some(keyframes[step_start].lerp(keyframes[step_end], lerp));
+        }
+        Keyframes::Weights(keyframes) => {
+            let morph_start = &keyframes[target_count * step_start..][..target_count];
+            let morph_end = &keyframes[target_count * step_end..][..target_count];
+            let weights = morph_start
+                .iter()
+                .zip(morph_end)
+                .map(|(a, b)| *a + lerp * (*b - *a))
+                .collect();
+            bone.weights = Some(weights);
+        }
+        // Decoded by `window`
+        Keyframes::Compressed(_) => {}
+    }
+}
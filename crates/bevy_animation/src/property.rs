@@ -0,0 +1,342 @@
+use bevy_ecs::{prelude::*, reflect::AppTypeRegistry, reflect::ReflectComponent};
+use bevy_math::{Quat, Vec2, Vec3, Vec4};
+use bevy_reflect::{GetPath, Reflect, TypeRegistry};
+use bevy_render::color::Color;
+use bevy_utils::tracing::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::{find_keyframe, AnimationClip, AnimationPlayer, EntityPath};
+
+/// List of keyframes for a [`PropertyCurve`], by type of the animated field.
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
+pub enum PropertyKeyframes {
+    /// Keyframes for a `f32` field.
+    F32(Vec<f32>),
+    /// Keyframes for a [`Vec2`] field.
+    Vec2(Vec<Vec2>),
+    /// Keyframes for a [`Vec3`] field.
+    Vec3(Vec<Vec3>),
+    /// Keyframes for a [`Vec4`] field.
+    Vec4(Vec<Vec4>),
+    /// Keyframes for a [`Quat`] field, interpolated spherically.
+    Quat(Vec<Quat>),
+    /// Keyframes for a [`Color`] field, interpolated in linear RGBA.
+    Color(Vec<Color>),
+}
+
+/// Describes how a field of any reflected component should be animated, such as the intensity
+/// of a `PointLight` or the color of a `Sprite`.
+///
+/// The component is found in the [`AppTypeRegistry`] by its type path, and the field by its
+/// reflection path. Outside of its keyframes, the curve holds its first or last keyframe.
+///
+/// ```
+/// # use bevy_core::Name;
+/// # use bevy_animation::{AnimationClip, EntityPath, PropertyCurve, PropertyKeyframes};
+/// let mut clip = AnimationClip::default();
+/// clip.add_property_curve_to_path(
+///     EntityPath {
+///         parts: vec![Name::new("lamp")],
+///     },
+///     PropertyCurve::new(
+///         "PointLight",
+///         "intensity",
+///         vec![0.0, 2.0],
+///         PropertyKeyframes::F32(vec![800.0, 0.0]),
+///     ),
+/// );
+/// ```
+///
+/// Property curves are played by [`AnimationPlayer`]s.
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
+pub struct PropertyCurve {
+    /// The type path of the component, or its short type path if it's unambiguous.
+    pub component: String,
+    /// The reflection path to the animated field in the component.
+    pub field: String,
+    /// Timestamp for each of the keyframes.
+    pub keyframe_timestamps: Vec<f32>,
+    /// List of the keyframes.
+    pub keyframes: PropertyKeyframes,
+}
+
+impl PropertyCurve {
+    /// Creates a curve animating `field` of `component`.
+    pub fn new(
+        component: impl Into<String>,
+        field: impl Into<String>,
+        keyframe_timestamps: Vec<f32>,
+        keyframes: PropertyKeyframes,
+    ) -> Self {
+        Self {
+            component: component.into(),
+            field: field.into(),
+            keyframe_timestamps,
+            keyframes,
+        }
+    }
+
+    /// Samples the curve at `seek_time`, holding its first or last keyframe outside of it.
+    fn sample(&self, seek_time: f32) -> Option<PropertyValue> {
+        let timestamps = &self.keyframe_timestamps;
+        let last = timestamps.len().checked_sub(1)?;
+        let (step_start, lerp) = match find_keyframe(timestamps, seek_time) {
+            Some(step_start) => {
+                let ts_start = timestamps[step_start];
+                let ts_end = timestamps[step_start + 1];
+                (step_start, (seek_time - ts_start) / (ts_end - ts_start))
+            }
+            None if seek_time < timestamps[0] => (0, 0.0),
+            None => (last, 0.0),
+        };
+        let step_end = (step_start + 1).min(last);
+
+        fn keyframes<T: Copy>(
+            keyframes: &[T],
+            (start, end): (usize, usize),
+            value: impl Fn(T) -> PropertyValue,
+        ) -> Option<(PropertyValue, PropertyValue)> {
+            Some((value(*keyframes.get(start)?), value(*keyframes.get(end)?)))
+        }
+        let steps = (step_start, step_end);
+        let (start, end) = match &self.keyframes {
+            PropertyKeyframes::F32(values) => keyframes(values, steps, PropertyValue::F32),
+            PropertyKeyframes::Vec2(values) => keyframes(values, steps, PropertyValue::Vec2),
+            PropertyKeyframes::Vec3(values) => keyframes(values, steps, PropertyValue::Vec3),
+            PropertyKeyframes::Vec4(values) => keyframes(values, steps, PropertyValue::Vec4),
+            PropertyKeyframes::Quat(values) => keyframes(values, steps, PropertyValue::Quat),
+            PropertyKeyframes::Color(values) => keyframes(values, steps, PropertyValue::Color),
+        }?;
+        Some(start.lerp(end, lerp))
+    }
+}
+
+/// A value of an animated field, sampled from a [`PropertyCurve`] or tweened by a
+/// [`Tween`](crate::Tween).
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// A `f32` value.
+    F32(f32),
+    /// A [`Vec2`] value.
+    Vec2(Vec2),
+    /// A [`Vec3`] value.
+    Vec3(Vec3),
+    /// A [`Vec4`] value.
+    Vec4(Vec4),
+    /// A [`Quat`] value, interpolated spherically.
+    Quat(Quat),
+    /// A [`Color`] value, interpolated in linear RGBA.
+    Color(Color),
+}
+
+macro_rules! impl_from_value {
+    ($($ty:ident),*) => {
+        $(
+            impl From<$ty> for PropertyValue {
+                fn from(value: $ty) -> Self {
+                    Self::$ty(value)
+                }
+            }
+        )*
+    };
+}
+
+impl From<f32> for PropertyValue {
+    fn from(value: f32) -> Self {
+        Self::F32(value)
+    }
+}
+
+impl_from_value!(Vec2, Vec3, Vec4, Quat, Color);
+
+impl PropertyValue {
+    /// Interpolates towards `other` by `t`, or jumps to it if it has another type.
+    pub(crate) fn lerp(self, other: PropertyValue, t: f32) -> PropertyValue {
+        match (self, other) {
+            (Self::F32(a), Self::F32(b)) => Self::F32(a + (b - a) * t),
+            (Self::Vec2(a), Self::Vec2(b)) => Self::Vec2(a.lerp(b, t)),
+            (Self::Vec3(a), Self::Vec3(b)) => Self::Vec3(a.lerp(b, t)),
+            (Self::Vec4(a), Self::Vec4(b)) => Self::Vec4(a.lerp(b, t)),
+            (Self::Quat(a), Self::Quat(b)) => {
+                // Choose the smallest angle for the rotation
+                let b = if a.dot(b) < 0.0 { -b } else { b };
+                Self::Quat(a.normalize().slerp(b.normalize(), t))
+            }
+            (Self::Color(a), Self::Color(b)) => {
+                let color = Color::rgba_linear_from_array(
+                    Vec4::from(a.as_linear_rgba_f32()).lerp(Vec4::from(b.as_linear_rgba_f32()), t),
+                );
+                // Keep the color space of the keyframes
+                Self::Color(match a {
+                    Color::Rgba { .. } => color.as_rgba(),
+                    Color::RgbaLinear { .. } => color,
+                    Color::Hsla { .. } => color.as_hsla(),
+                    Color::Lcha { .. } => color.as_lcha(),
+                })
+            }
+            (_, other) => other,
+        }
+    }
+
+    /// Reads a value of one of the supported types from a reflected field.
+    pub(crate) fn from_field(field: &dyn Reflect) -> Option<PropertyValue> {
+        let any = field.as_any();
+        any.downcast_ref::<f32>()
+            .map(|value| Self::F32(*value))
+            .or_else(|| any.downcast_ref::<Vec2>().map(|value| Self::Vec2(*value)))
+            .or_else(|| any.downcast_ref::<Vec3>().map(|value| Self::Vec3(*value)))
+            .or_else(|| any.downcast_ref::<Vec4>().map(|value| Self::Vec4(*value)))
+            .or_else(|| any.downcast_ref::<Quat>().map(|value| Self::Quat(*value)))
+            .or_else(|| any.downcast_ref::<Color>().map(|value| Self::Color(*value)))
+    }
+
+    pub(crate) fn into_reflect(self) -> Box<dyn Reflect> {
+        match self {
+            Self::F32(value) => Box::new(value),
+            Self::Vec2(value) => Box::new(value),
+            Self::Vec3(value) => Box::new(value),
+            Self::Vec4(value) => Box::new(value),
+            Self::Quat(value) => Box::new(value),
+            Self::Color(value) => Box::new(value),
+        }
+    }
+}
+
+/// A value sampled by an [`AnimationPlayer`] for a field of a component, set by
+/// [`animate_properties`].
+#[derive(Clone, Debug)]
+pub(crate) struct AnimatedProperty {
+    entity: Entity,
+    component: String,
+    field: String,
+    value: PropertyValue,
+}
+
+impl AnimatedProperty {
+    /// Samples the property curves of `target` at `seek_time`, blending them with the values
+    /// already sampled by `weight`.
+    pub(crate) fn sample(
+        properties: &mut Vec<AnimatedProperty>,
+        target: Entity,
+        curves: &[PropertyCurve],
+        seek_time: f32,
+        weight: f32,
+    ) {
+        for curve in curves {
+            let Some(value) = curve.sample(seek_time) else {
+                continue;
+            };
+            let existing = properties.iter_mut().find(|property| {
+                property.entity == target
+                    && property.component == curve.component
+                    && property.field == curve.field
+            });
+            match existing {
+                Some(property) => property.value = property.value.lerp(value, weight),
+                None => properties.push(AnimatedProperty {
+                    entity: target,
+                    component: curve.component.clone(),
+                    field: curve.field.clone(),
+                    value,
+                }),
+            }
+        }
+    }
+}
+
+impl AnimationClip {
+    /// Add a [`PropertyCurve`] to an [`EntityPath`].
+    pub fn add_property_curve_to_path(&mut self, path: EntityPath, curve: PropertyCurve) {
+        self.duration = self
+            .duration
+            .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
+        let bone_id = *self.paths.entry(path).or_insert_with(|| {
+            self.curves.push(Vec::new());
+            self.curves.len() - 1
+        });
+        if self.properties.len() <= bone_id {
+            self.properties.resize(bone_id + 1, Vec::new());
+        }
+        self.properties[bone_id].push(curve);
+    }
+
+    /// [`PropertyCurve`]s for a given [`EntityPath`].
+    pub fn get_property_curves_by_path(&self, path: &EntityPath) -> Option<&[PropertyCurve]> {
+        let bone_id = self.paths.get(path)?;
+        self.properties.get(*bone_id).map(Vec::as_slice)
+    }
+}
+
+/// System that sets the fields of components sampled from [`PropertyCurve`]s by
+/// [`AnimationPlayer`]s, with reflection.
+pub fn animate_properties(world: &mut World) {
+    let mut players = world.query::<&mut AnimationPlayer>();
+    let properties: Vec<_> = players
+        .iter_mut(world)
+        .flat_map(|mut player| std::mem::take(&mut player.bypass_change_detection().properties))
+        .collect();
+    if properties.is_empty() {
+        return;
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for property in properties {
+        let value = property.value;
+        with_field(
+            world,
+            &registry,
+            property.entity,
+            &property.component,
+            &property.field,
+            |field| {
+                if field.set(value.into_reflect()).is_err() {
+                    warn!(
+                        "Can't animate field {:?} of {:?} with {:?}, its type is different.",
+                        property.field, property.component, value
+                    );
+                }
+            },
+        );
+    }
+}
+
+/// Calls `f` with the `field` of the `component` of `entity`, found by reflection.
+///
+/// Returns `None` if the entity doesn't have the component, and warns if it can't be reflected
+/// or doesn't have the field.
+pub(crate) fn with_field<R>(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    component: &str,
+    field: &str,
+    f: impl FnOnce(&mut dyn Reflect) -> R,
+) -> Option<R> {
+    let Some(registration) = registry
+        .get_with_type_path(component)
+        .or_else(|| registry.get_with_short_type_path(component))
+    else {
+        warn!("Can't animate {:?}, its type isn't registered.", component);
+        return None;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        warn!(
+            "Can't animate {:?}, it doesn't reflect `Component`.",
+            component
+        );
+        return None;
+    };
+    let mut entity = world.get_entity_mut(entity)?;
+    let mut component_value = reflect_component.reflect_mut(&mut entity)?;
+    match component_value.reflect_path_mut(field) {
+        Ok(field) => Some(f(field)),
+        Err(error) => {
+            warn!(
+                "Can't animate field {:?} of {:?}: {}",
+                field, component, error
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use bevy_asset::{Asset, AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{
+    AnimationClip, AnimationGraph, AnimationGraphPlayer, AnimationNode, AnimationPlayer,
+    EntityPath, KeyframeAttribute, Keyframes, PlayingAnimation,
+};
+
+/// Maps the bones of animations authored for a skeleton onto the bones of another skeleton, so
+/// that a library of animations can drive characters with different rigs.
+///
+/// Bones are mapped by name, to their path in the other skeleton, with the ratio between the
+/// sizes of the skeletons to scale their translations. Curves of bones without a mapping are
+/// kept as they are.
+///
+/// ```
+/// # use bevy_core::Name;
+/// # use bevy_animation::{EntityPath, RetargetMap};
+/// let hips = EntityPath {
+///     parts: vec![Name::new("robot"), Name::new("pelvis")],
+/// };
+/// // The robot is twice as tall as the skeleton of the animations
+/// let map = RetargetMap::new().with_bone("mixamorig:Hips", hips, 2.0);
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Default)]
+pub struct RetargetMap {
+    /// The bones of the target skeleton, by name of the bone in the source skeleton.
+    pub bones: HashMap<String, BoneRetarget>,
+}
+
+/// The bone of the target skeleton a bone of a [`RetargetMap`] is mapped to.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct BoneRetarget {
+    /// The path to the bone in the target skeleton.
+    pub path: EntityPath,
+    /// The ratio between the length of the bone in the target skeleton and in the source
+    /// skeleton, applied to its animated translation.
+    pub translation_ratio: f32,
+}
+
+impl RetargetMap {
+    /// Creates a map without bones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the bone called `source` to the bone at `target`, scaling its translation by
+    /// `translation_ratio`.
+    pub fn with_bone(
+        mut self,
+        source: impl Into<String>,
+        target: EntityPath,
+        translation_ratio: f32,
+    ) -> Self {
+        self.bones.insert(
+            source.into(),
+            BoneRetarget {
+                path: target,
+                translation_ratio,
+            },
+        );
+        self
+    }
+
+    /// The bone of the target skeleton the bone at `path` is mapped to.
+    pub fn bone(&self, path: &EntityPath) -> Option<&BoneRetarget> {
+        let name = path.parts.last()?;
+        self.bones.get(name.as_str())
+    }
+}
+
+impl AnimationClip {
+    /// Creates a copy of the clip with its curves mapped onto another skeleton by `map`.
+    pub fn retarget(&self, map: &RetargetMap) -> AnimationClip {
+        let mut clip = AnimationClip {
+            curves: Vec::new(),
+            paths: HashMap::default(),
+            properties: Vec::new(),
+            ..self.clone()
+        };
+        for (path, bone_id) in &self.paths {
+            let bone = map.bone(path);
+            let path = bone.map_or_else(|| path.clone(), |bone| bone.path.clone());
+            for curve in &self.curves[*bone_id] {
+                let mut curve = curve.clone();
+                match (bone, &mut curve.keyframes) {
+                    (Some(bone), Keyframes::Translation(keyframes)) => {
+                        for translation in keyframes {
+                            *translation *= bone.translation_ratio;
+                        }
+                    }
+                    (Some(bone), Keyframes::Compressed(keyframes))
+                        if keyframes.attribute() == KeyframeAttribute::Translation =>
+                    {
+                        keyframes.scale(bone.translation_ratio);
+                    }
+                    _ => {}
+                }
+                clip.add_curve_to_path(path.clone(), curve);
+            }
+            for curve in self.properties.get(*bone_id).into_iter().flatten() {
+                clip.add_property_curve_to_path(path.clone(), curve.clone());
+            }
+        }
+        clip
+    }
+}
+
+/// Plays the animations of the [`AnimationPlayer`] or [`AnimationGraphPlayer`] of this entity
+/// retargeted with a [`RetargetMap`].
+///
+/// The players play retargeted copies of their clips and graphs, created by
+/// [`retarget_animations`] once the assets are loaded, so their handles change to the copies.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct AnimationRetargeting(pub Handle<RetargetMap>);
+
+/// The retargeted copies of animation clips and graphs, by their original asset and
+/// [`RetargetMap`].
+#[derive(Resource, Default)]
+pub struct RetargetedAnimations {
+    clips: HashMap<(AssetId<AnimationClip>, AssetId<RetargetMap>), Handle<AnimationClip>>,
+    graphs: HashMap<(AssetId<AnimationGraph>, AssetId<RetargetMap>), Handle<AnimationGraph>>,
+    retargeted_clips: HashSet<AssetId<AnimationClip>>,
+    retargeted_graphs: HashSet<AssetId<AnimationGraph>>,
+}
+
+impl RetargetedAnimations {
+    /// The copy of `clip` retargeted with `map`, if a player has played it.
+    pub fn clip(
+        &self,
+        clip: impl Into<AssetId<AnimationClip>>,
+        map: impl Into<AssetId<RetargetMap>>,
+    ) -> Option<&Handle<AnimationClip>> {
+        self.clips.get(&(clip.into(), map.into()))
+    }
+
+    /// The copy of `graph` retargeted with `map`, if a player has played it.
+    pub fn graph(
+        &self,
+        graph: impl Into<AssetId<AnimationGraph>>,
+        map: impl Into<AssetId<RetargetMap>>,
+    ) -> Option<&Handle<AnimationGraph>> {
+        self.graphs.get(&(graph.into(), map.into()))
+    }
+
+    /// The retargeted copy of `clip`, created if needed, or `None` if `clip` is already a copy
+    /// or isn't loaded.
+    fn retarget_clip(
+        &mut self,
+        clip: AssetId<AnimationClip>,
+        map: AssetId<RetargetMap>,
+        clips: &mut Assets<AnimationClip>,
+        maps: &Assets<RetargetMap>,
+    ) -> Option<Handle<AnimationClip>> {
+        if self.retargeted_clips.contains(&clip) {
+            return None;
+        }
+        if let Some(handle) = self.clips.get(&(clip, map)) {
+            return Some(handle.clone());
+        }
+        let retargeted = clips.get(clip)?.retarget(maps.get(map)?);
+        let handle = clips.add(retargeted);
+        self.retargeted_clips.insert(handle.id());
+        self.clips.insert((clip, map), handle.clone());
+        Some(handle)
+    }
+
+    /// The retargeted copy of `graph`, created with copies of its clips if needed, or `None` if
+    /// `graph` is already a copy or it or its clips aren't loaded.
+    fn retarget_graph(
+        &mut self,
+        graph: AssetId<AnimationGraph>,
+        map: AssetId<RetargetMap>,
+        graphs: &mut Assets<AnimationGraph>,
+        clips: &mut Assets<AnimationClip>,
+        maps: &Assets<RetargetMap>,
+    ) -> Option<Handle<AnimationGraph>> {
+        if self.retargeted_graphs.contains(&graph) {
+            return None;
+        }
+        if let Some(handle) = self.graphs.get(&(graph, map)) {
+            return Some(handle.clone());
+        }
+        let retargeted = self.retarget_graph_clips(graphs.get(graph)?, map, clips, maps)?;
+        let handle = graphs.add(retargeted);
+        self.retargeted_graphs.insert(handle.id());
+        self.graphs.insert((graph, map), handle.clone());
+        Some(handle)
+    }
+
+    /// A copy of `graph` playing the retargeted copies of its clips.
+    fn retarget_graph_clips(
+        &mut self,
+        graph: &AnimationGraph,
+        map: AssetId<RetargetMap>,
+        clips: &mut Assets<AnimationClip>,
+        maps: &Assets<RetargetMap>,
+    ) -> Option<AnimationGraph> {
+        let mut graph = graph.clone();
+        for node in &mut graph.nodes {
+            if let AnimationNode::Clip(node) = node {
+                node.clip = self.retarget_clip(node.clip.id(), map, clips, maps)?;
+            }
+        }
+        Some(graph)
+    }
+
+    /// Retargets the existing copies of clips and graphs again after their original assets or
+    /// map changed, or forgets them when the original assets were removed.
+    fn refresh(
+        &mut self,
+        changed: &Changes,
+        graphs: &mut Assets<AnimationGraph>,
+        clips: &mut Assets<AnimationClip>,
+        maps: &Assets<RetargetMap>,
+    ) {
+        self.clips.retain(|(clip, map), handle| {
+            let removed =
+                changed.removed_clips.contains(clip) || changed.removed_maps.contains(map);
+            if removed {
+                self.retargeted_clips.remove(&handle.id());
+            }
+            !removed
+        });
+        self.graphs.retain(|(graph, map), handle| {
+            let removed =
+                changed.removed_graphs.contains(graph) || changed.removed_maps.contains(map);
+            if removed {
+                self.retargeted_graphs.remove(&handle.id());
+            }
+            !removed
+        });
+
+        for ((clip, map), handle) in &self.clips {
+            if !changed.clips.contains(clip) && !changed.maps.contains(map) {
+                continue;
+            }
+            if let (Some(clip), Some(map)) = (clips.get(*clip), maps.get(*map)) {
+                let retargeted = clip.retarget(map);
+                clips.insert(handle, retargeted);
+            }
+        }
+
+        let outdated: Vec<_> = self
+            .graphs
+            .iter()
+            .filter(|((graph, map), _)| {
+                changed.graphs.contains(graph) || changed.maps.contains(map)
+            })
+            .map(|(key, handle)| (*key, handle.id()))
+            .collect();
+        for ((graph, map), handle) in outdated {
+            let Some(graph) = graphs.get(graph).cloned() else {
+                continue;
+            };
+            if let Some(retargeted) = self.retarget_graph_clips(&graph, map, clips, maps) {
+                graphs.insert(handle, retargeted);
+            }
+        }
+    }
+}
+
+/// The original assets changed or removed since the last update.
+#[derive(Default)]
+struct Changes {
+    clips: HashSet<AssetId<AnimationClip>>,
+    graphs: HashSet<AssetId<AnimationGraph>>,
+    maps: HashSet<AssetId<RetargetMap>>,
+    removed_clips: HashSet<AssetId<AnimationClip>>,
+    removed_graphs: HashSet<AssetId<AnimationGraph>>,
+    removed_maps: HashSet<AssetId<RetargetMap>>,
+}
+
+fn collect_changes<A: Asset>(
+    events: &mut EventReader<AssetEvent<A>>,
+    changed: &mut HashSet<AssetId<A>>,
+    removed: &mut HashSet<AssetId<A>>,
+) {
+    for event in events.read() {
+        match event {
+            AssetEvent::Modified { id } => {
+                changed.insert(*id);
+            }
+            AssetEvent::Removed { id } => {
+                removed.insert(*id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System that makes the players with an [`AnimationRetargeting`] play retargeted copies of
+/// their clips and graphs, and keeps the copies up to date with the original assets.
+#[allow(clippy::too_many_arguments)]
+pub fn retarget_animations(
+    mut retargeted: ResMut<RetargetedAnimations>,
+    mut clips: ResMut<Assets<AnimationClip>>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+    maps: Res<Assets<RetargetMap>>,
+    mut clip_events: EventReader<AssetEvent<AnimationClip>>,
+    mut graph_events: EventReader<AssetEvent<AnimationGraph>>,
+    mut map_events: EventReader<AssetEvent<RetargetMap>>,
+    mut players: Query<(
+        &AnimationRetargeting,
+        Option<&mut AnimationPlayer>,
+        Option<&mut AnimationGraphPlayer>,
+    )>,
+) {
+    let mut changes = Changes::default();
+    collect_changes(
+        &mut clip_events,
+        &mut changes.clips,
+        &mut changes.removed_clips,
+    );
+    collect_changes(
+        &mut graph_events,
+        &mut changes.graphs,
+        &mut changes.removed_graphs,
+    );
+    collect_changes(
+        &mut map_events,
+        &mut changes.maps,
+        &mut changes.removed_maps,
+    );
+    retargeted.refresh(&changes, &mut graphs, &mut clips, &maps);
+
+    for (retargeting, player, graph_player) in &mut players {
+        let map = retargeting.0.id();
+        if let Some(mut player) = player {
+            let mut retarget = |animation: &PlayingAnimation| {
+                retargeted.retarget_clip(animation.animation_clip.id(), map, &mut clips, &maps)
+            };
+            if let Some(clip) = retarget(&player.animation) {
+                player.animation.animation_clip = clip;
+                player.animation.path_cache.clear();
+            }
+            for index in 0..player.transitions.len() {
+                if let Some(clip) = retarget(&player.transitions[index].animation) {
+                    let animation = &mut player.transitions[index].animation;
+                    animation.animation_clip = clip;
+                    animation.path_cache.clear();
+                }
+            }
+        }
+        if let Some(mut player) = graph_player {
+            let graph = player.graph.id();
+            if let Some(graph) =
+                retargeted.retarget_graph(graph, map, &mut graphs, &mut clips, &maps)
+            {
+                // The copy has the same nodes, so the state of the player is kept
+                player.graph = graph;
+                player.path_cache.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_core::Name;
+    use bevy_math::{Quat, Vec3};
+
+    use super::*;
+    use crate::VariableCurve;
+
+    fn path(parts: &[&str]) -> EntityPath {
+        EntityPath {
+            parts: parts
+                .iter()
+                .map(|part| Name::new(part.to_string()))
+                .collect(),
+        }
+    }
+
+    fn curve(keyframes: Keyframes) -> VariableCurve {
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0],
+            keyframes,
+        }
+    }
+
+    /// A clip moving and turning the hips, and swinging the tail.
+    fn source_clip() -> AnimationClip {
+        let mut clip = AnimationClip::default();
+        let hips = path(&["Armature", "mixamorig:Hips"]);
+        clip.add_curve_to_path(
+            hips.clone(),
+            curve(Keyframes::Translation(vec![
+                Vec3::Y,
+                Vec3::new(1.0, 1.0, 0.0),
+            ])),
+        );
+        clip.add_curve_to_path(
+            hips,
+            curve(Keyframes::Rotation(vec![
+                Quat::IDENTITY,
+                Quat::from_rotation_y(1.0),
+            ])),
+        );
+        clip.add_curve_to_path(
+            path(&["Armature", "Tail"]),
+            curve(Keyframes::Translation(vec![Vec3::ZERO, Vec3::X])),
+        );
+        clip
+    }
+
+    fn translations(clip: &AnimationClip, path: &EntityPath) -> Vec<Vec3> {
+        clip.get_curves_by_path(path)
+            .into_iter()
+            .flatten()
+            .find_map(|curve| match &curve.keyframes {
+                Keyframes::Translation(keyframes) => Some(keyframes.clone()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn map(translation_ratio: f32) -> RetargetMap {
+        RetargetMap::new().with_bone(
+            "mixamorig:Hips",
+            path(&["robot", "pelvis"]),
+            translation_ratio,
+        )
+    }
+
+    #[test]
+    fn bones_are_mapped_by_name() {
+        let map = map(2.0);
+        let bone = map.bone(&path(&["Other", "Armature", "mixamorig:Hips"]));
+        assert_eq!(
+            bone.map(|bone| &bone.path),
+            Some(&path(&["robot", "pelvis"]))
+        );
+        assert_eq!(bone.map(|bone| bone.translation_ratio), Some(2.0));
+        assert_eq!(map.bone(&path(&["mixamorig:Hips", "Tail"])), None);
+        assert_eq!(map.bone(&path(&[])), None);
+    }
+
+    #[test]
+    fn retargeted_clip_moves_and_scales_curves() {
+        let source = source_clip();
+        let clip = source.retarget(&map(2.0));
+        let pelvis = path(&["robot", "pelvis"]);
+        let tail = path(&["Armature", "Tail"]);
+
+        assert_eq!(clip.paths().len(), 2);
+        assert!(clip
+            .get_curves_by_path(&path(&["Armature", "mixamorig:Hips"]))
+            .is_none());
+        assert_eq!(
+            translations(&clip, &pelvis),
+            [Vec3::Y * 2.0, Vec3::new(2.0, 2.0, 0.0)]
+        );
+        // Rotations don't depend on the size of the skeleton
+        let rotation = clip
+            .get_curves_by_path(&pelvis)
+            .unwrap()
+            .iter()
+            .any(|curve| {
+                matches!(&curve.keyframes, Keyframes::Rotation(keyframes)
+                if keyframes[1] == Quat::from_rotation_y(1.0))
+            });
+        assert!(rotation);
+        // Bones without a mapping are kept as they are
+        assert_eq!(translations(&clip, &tail), translations(&source, &tail));
+        assert_eq!(clip.duration(), source.duration());
+    }
+
+    #[test]
+    fn players_play_retargeted_copies() {
+        let mut world = World::new();
+        world.init_resource::<RetargetedAnimations>();
+        world.init_resource::<Events<AssetEvent<AnimationClip>>>();
+        world.init_resource::<Events<AssetEvent<AnimationGraph>>>();
+        world.init_resource::<Events<AssetEvent<RetargetMap>>>();
+        let mut clips = Assets::<AnimationClip>::default();
+        let mut graphs = Assets::<AnimationGraph>::default();
+        let mut maps = Assets::<RetargetMap>::default();
+        let source = clips.add(source_clip());
+        let graph = graphs.add(AnimationGraph::from_clip(source.clone()));
+        let map = maps.add(map(2.0));
+        world.insert_resource(clips);
+        world.insert_resource(graphs);
+        world.insert_resource(maps);
+
+        let mut player = AnimationPlayer::default();
+        player.play(source.clone());
+        let player = world
+            .spawn((player, AnimationRetargeting(map.clone())))
+            .id();
+        let graph_player = world
+            .spawn((
+                AnimationGraphPlayer::new(graph.clone()),
+                AnimationRetargeting(map.clone()),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(retarget_animations);
+        schedule.run(&mut world);
+        // Running again doesn't retarget the copies
+        schedule.run(&mut world);
+
+        let retargeted = world.resource::<RetargetedAnimations>();
+        let clip = retargeted.clip(&source, &map).unwrap().clone();
+        let retargeted_graph = retargeted.graph(&graph, &map).unwrap().clone();
+        assert_eq!(world.resource::<Assets<AnimationClip>>().len(), 2);
+        assert_eq!(
+            world
+                .get::<AnimationPlayer>(player)
+                .unwrap()
+                .animation_clip(),
+            &clip
+        );
+        assert_eq!(
+            world
+                .get::<AnimationGraphPlayer>(graph_player)
+                .unwrap()
+                .graph(),
+            &retargeted_graph
+        );
+        // Graphs and players share the copies of the clips
+        let graphs = world.resource::<Assets<AnimationGraph>>();
+        let root = graphs.get(&retargeted_graph).unwrap().root().unwrap();
+        let Some(AnimationNode::Clip(node)) = graphs.get(&retargeted_graph).unwrap().node(root)
+        else {
+            panic!("the root of the copy should play a clip");
+        };
+        assert_eq!(node.clip, clip);
+
+        // The copies are retargeted again when the map changes
+        let mut maps = world.resource_mut::<Assets<RetargetMap>>();
+        maps.get_mut(&map)
+            .unwrap()
+            .bones
+            .get_mut("mixamorig:Hips")
+            .unwrap()
+            .translation_ratio = 3.0;
+        world.send_event(AssetEvent::Modified { id: map.id() });
+        schedule.run(&mut world);
+        let clips = world.resource::<Assets<AnimationClip>>();
+        assert_eq!(
+            translations(clips.get(&clip).unwrap(), &path(&["robot", "pelvis"])),
+            [Vec3::Y * 3.0, Vec3::new(3.0, 3.0, 0.0)]
+        );
+    }
+}
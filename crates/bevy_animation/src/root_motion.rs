@@ -0,0 +1,237 @@
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::prelude::Transform;
+
+use crate::{
+    pose::{sample_bone, BonePose},
+    AnimationClip, EntityPath, VariableCurve,
+};
+
+/// Extracts the motion of the root bone from the animations of an [`AnimationPlayer`] or
+/// [`AnimationGraphPlayer`] on the same entity.
+///
+/// The root bone of clips with [`AnimationClip::set_root_motion`] holds its first keyframe,
+/// and its motion is exposed as a delta each frame instead, for a character controller to move
+/// the character with it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::prelude::Transform;
+/// # use bevy_animation::RootMotion;
+/// fn move_characters(mut characters: Query<(&RootMotion, &mut Transform)>) {
+///     for (root_motion, mut transform) in &mut characters {
+///         root_motion.apply(&mut transform);
+///     }
+/// }
+/// ```
+///
+/// The root bone should be a descendant of the player, since its own transform is animated.
+///
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+/// [`AnimationGraphPlayer`]: crate::AnimationGraphPlayer
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct RootMotion {
+    /// The path to the root bone, starting with the name of the player.
+    pub bone: EntityPath,
+    /// Whether the translation of the root bone is extracted.
+    pub translation: bool,
+    /// Whether the rotation of the root bone is extracted.
+    pub rotation: bool,
+    #[reflect(ignore)]
+    delta: Motion,
+}
+
+impl Default for RootMotion {
+    fn default() -> Self {
+        Self::new(EntityPath::default())
+    }
+}
+
+impl RootMotion {
+    /// Extracts the translation and rotation of `bone`.
+    pub fn new(bone: EntityPath) -> Self {
+        Self {
+            bone,
+            translation: true,
+            rotation: true,
+            delta: Motion::IDENTITY,
+        }
+    }
+
+    /// Only extracts the translation of the root bone.
+    pub fn translation_only(mut self) -> Self {
+        self.rotation = false;
+        self
+    }
+
+    /// The translation of the root bone during the last frame, relative to its rotation at the
+    /// start of the frame.
+    pub fn delta_translation(&self) -> Vec3 {
+        self.delta.translation
+    }
+
+    /// The rotation of the root bone during the last frame.
+    pub fn delta_rotation(&self) -> Quat {
+        self.delta.rotation
+    }
+
+    /// Moves `transform` by the motion of the root bone during the last frame.
+    pub fn apply(&self, transform: &mut Transform) {
+        transform.translation += transform.rotation * self.delta.translation;
+        transform.rotation = (transform.rotation * self.delta.rotation).normalize();
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.delta = Motion::IDENTITY;
+    }
+
+    /// Adds the motion of `clip` when playing from `seek_time` for `advance` seconds, scaled by
+    /// `weight`.
+    pub(crate) fn add_motion(
+        &mut self,
+        clip: &AnimationClip,
+        seek_time: f32,
+        advance: f32,
+        looping: bool,
+        weight: f32,
+    ) {
+        if !clip.root_motion || advance == 0.0 || weight <= 0.0 {
+            return;
+        }
+        let Some(curves) = clip.get_curves_by_path(&self.bone) else {
+            return;
+        };
+        let motion = self.clip_motion(curves, clip.duration, seek_time, advance, looping);
+        self.delta = self.delta.mul(Motion {
+            translation: motion.translation * weight,
+            rotation: Quat::IDENTITY.slerp(motion.rotation, weight),
+        });
+    }
+
+    /// Holds the extracted attributes of the root bone at their first keyframe.
+    pub(crate) fn strip(&self, curves: &[VariableCurve], bone: &mut BonePose) {
+        let reference = sample_bone(curves, f32::NEG_INFINITY);
+        if self.translation {
+            bone.translation = reference.translation;
+        }
+        if self.rotation {
+            bone.rotation = reference.rotation;
+        }
+    }
+
+    /// Whether the root bone of `clip` holds its first keyframe when it's on `path`.
+    pub(crate) fn strips(&self, clip: &AnimationClip, path: &EntityPath) -> bool {
+        clip.root_motion && path == &self.bone
+    }
+
+    fn clip_motion(
+        &self,
+        curves: &[VariableCurve],
+        duration: f32,
+        seek_time: f32,
+        advance: f32,
+        looping: bool,
+    ) -> Motion {
+        let reference = self.bone_motion(curves, f32::NEG_INFINITY).inverse();
+        // The motion of the character to keep the root bone at its first keyframe
+        let character = |time: f32| self.bone_motion(curves, time).mul(reference);
+        let segment = |start: f32, end: f32| character(start).inverse().mul(character(end));
+
+        let end = seek_time + advance;
+        if !looping || duration <= 0.0 {
+            return segment(seek_time.clamp(0.0, duration), end.clamp(0.0, duration));
+        }
+
+        // Unroll the loops covered by this advance
+        let (first_loop, last_loop) = (
+            (seek_time.min(end) / duration).floor() as i64,
+            (seek_time.max(end) / duration).floor() as i64,
+        );
+        let mut motion = Motion::IDENTITY;
+        for loop_index in first_loop..=last_loop {
+            let loop_index = if advance > 0.0 {
+                loop_index
+            } else {
+                first_loop + last_loop - loop_index
+            };
+            let offset = loop_index as f32 * duration;
+            let (start, end) = if advance > 0.0 {
+                (seek_time.max(offset), end.min(offset + duration))
+            } else {
+                (seek_time.min(offset + duration), end.max(offset))
+            };
+            motion = motion.mul(segment(start - offset, end - offset));
+        }
+        motion
+    }
+
+    /// The extracted attributes of the root bone at `time`.
+    fn bone_motion(&self, curves: &[VariableCurve], time: f32) -> Motion {
+        let bone = sample_bone(curves, time);
+        let reference = sample_bone(curves, f32::NEG_INFINITY);
+        fn pick<T>(extract: bool, bone: Option<T>, reference: Option<T>) -> Option<T> {
+            if extract {
+                bone.or(reference)
+            } else {
+                reference
+            }
+        }
+        Motion {
+            translation: pick(self.translation, bone.translation, reference.translation)
+                .unwrap_or(Vec3::ZERO),
+            rotation: pick(self.rotation, bone.rotation, reference.rotation)
+                .unwrap_or(Quat::IDENTITY),
+        }
+    }
+}
+
+/// A rigid motion, without scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Motion {
+    translation: Vec3,
+    rotation: Quat,
+}
+
+impl Default for Motion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Motion {
+    const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+    };
+
+    /// Applies `other` after `self`, in the space of `self`.
+    fn mul(self, other: Motion) -> Motion {
+        Motion {
+            translation: self.translation + self.rotation * other.translation,
+            rotation: (self.rotation * other.rotation).normalize(),
+        }
+    }
+
+    fn inverse(self) -> Motion {
+        let rotation = self.rotation.inverse();
+        Motion {
+            translation: -(rotation * self.translation),
+            rotation,
+        }
+    }
+}
+
+impl AnimationClip {
+    /// Whether the motion of the root bone is extracted by [`RootMotion`].
+    pub fn root_motion(&self) -> bool {
+        self.root_motion
+    }
+
+    /// Set whether the motion of the root bone is extracted by [`RootMotion`], instead of
+    /// being played on the bone.
+    pub fn set_root_motion(&mut self, root_motion: bool) {
+        self.root_motion = root_motion;
+    }
+}
@@ -0,0 +1,409 @@
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+
+use crate::AnimationNodeIndex;
+
+/// An [`AnimationNode`](crate::AnimationNode) playing one of its states at a time, and
+/// cross-fading between them when the conditions of a transition are met.
+///
+/// Each state plays a node of the same [`AnimationGraph`](crate::AnimationGraph), which is
+/// restarted when the state is entered. Transitions are checked every frame in order, and the
+/// first one whose conditions are met is taken.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_animation::{AnimationClip, AnimationGraph, ClipNode, StateMachineNode, StateTransition, TransitionCondition};
+/// # let [idle, jump]: [Handle<AnimationClip>; 2] = Default::default();
+/// let mut graph = AnimationGraph::new();
+/// let idle_clip = graph.add_node(ClipNode::new(idle));
+/// let jump_clip = graph.add_node(ClipNode::new(jump).once());
+///
+/// let mut machine = StateMachineNode::new();
+/// let idle = machine.add_state("idle", idle_clip);
+/// let jump = machine.add_state("jump", jump_clip);
+/// machine.add_transition(
+///     StateTransition::new(idle, jump)
+///         .when(TransitionCondition::Trigger("jump".to_string()))
+///         .with_duration(0.1),
+/// );
+/// machine.add_transition(
+///     StateTransition::new(jump, idle)
+///         .when(TransitionCondition::TimeInState(0.8))
+///         .with_duration(0.3),
+/// );
+/// let root = graph.add_node(machine);
+/// graph.set_root(root);
+/// ```
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct StateMachineNode {
+    /// The states of the machine.
+    pub states: Vec<GraphState>,
+    /// The transitions between states, checked in order.
+    pub transitions: Vec<StateTransition>,
+    /// The index of the state played first.
+    pub initial_state: usize,
+}
+
+impl StateMachineNode {
+    /// Creates a machine without states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a state playing `node`, and returns its index. The first state added is the initial
+    /// state.
+    pub fn add_state(&mut self, name: impl Into<String>, node: AnimationNodeIndex) -> usize {
+        self.states.push(GraphState {
+            name: name.into(),
+            node,
+        });
+        self.states.len() - 1
+    }
+
+    /// Adds a transition, checked after the existing ones.
+    pub fn add_transition(&mut self, transition: StateTransition) {
+        self.transitions.push(transition);
+    }
+
+    /// Returns the index of the state called `name`.
+    pub fn state(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+}
+
+/// A state of a [`StateMachineNode`].
+#[derive(Reflect, Clone, Debug)]
+pub struct GraphState {
+    /// The name of the state, to control it from an
+    /// [`AnimationGraphPlayer`](crate::AnimationGraphPlayer).
+    pub name: String,
+    /// The node played in this state.
+    pub node: AnimationNodeIndex,
+}
+
+/// A transition between the states of a [`StateMachineNode`].
+#[derive(Reflect, Clone, Debug)]
+pub struct StateTransition {
+    /// The state the transition leaves, or `None` to leave any other state.
+    pub from: Option<usize>,
+    /// The state the transition enters.
+    pub to: usize,
+    /// The conditions which must all be met to take the transition.
+    pub conditions: Vec<TransitionCondition>,
+    /// The duration of the cross-fade in seconds.
+    pub duration: f32,
+    /// Whether other transitions can be taken during the cross-fade.
+    ///
+    /// When a transition is interrupted, the cross-fade in progress fades out to the new state.
+    pub interruptible: bool,
+}
+
+impl StateTransition {
+    /// Creates a transition from `from` to `to`, without conditions or cross-fade.
+    pub fn new(from: usize, to: usize) -> Self {
+        Self {
+            from: Some(from),
+            to,
+            conditions: Vec::new(),
+            duration: 0.0,
+            interruptible: false,
+        }
+    }
+
+    /// Creates a transition from any other state to `to`.
+    pub fn from_any(to: usize) -> Self {
+        Self {
+            from: None,
+            ..Self::new(0, to)
+        }
+    }
+
+    /// Adds a condition to the transition.
+    pub fn when(mut self, condition: TransitionCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Sets the duration of the cross-fade in seconds.
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Lets other transitions interrupt the cross-fade.
+    pub fn interruptible(mut self) -> Self {
+        self.interruptible = true;
+        self
+    }
+}
+
+/// A condition of a [`StateTransition`], on the parameters of the
+/// [`AnimationGraphPlayer`](crate::AnimationGraphPlayer).
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub enum TransitionCondition {
+    /// The parameter is greater than the value.
+    Greater(String, f32),
+    /// The parameter is less than the value.
+    Less(String, f32),
+    /// The parameter isn't zero.
+    True(String),
+    /// The parameter is zero.
+    False(String),
+    /// The parameter was set with
+    /// [`AnimationGraphPlayer::set_trigger`](crate::AnimationGraphPlayer::set_trigger). The
+    /// trigger is reset when the transition is taken.
+    Trigger(String),
+    /// The state has played for at least this many seconds.
+    TimeInState(f32),
+}
+
+impl TransitionCondition {
+    fn is_met(&self, parameters: &HashMap<String, f32>, time_in_state: f32) -> bool {
+        let parameter = |name: &str| parameters.get(name).copied().unwrap_or(0.0);
+        match self {
+            Self::Greater(name, value) => parameter(name) > *value,
+            Self::Less(name, value) => parameter(name) < *value,
+            Self::True(name) | Self::Trigger(name) => parameter(name) != 0.0,
+            Self::False(name) => parameter(name) == 0.0,
+            Self::TimeInState(duration) => time_in_state >= *duration,
+        }
+    }
+}
+
+/// A state fading out after a transition.
+#[derive(Clone, Debug)]
+pub(crate) struct FadingState {
+    pub(crate) state: usize,
+    /// The current weight. Starts at 1.0 and goes to 0.0 during the fade-out.
+    pub(crate) weight: f32,
+    /// How much to decrease `weight` per second
+    weight_decline_per_sec: f32,
+}
+
+/// The state of a [`StateMachineNode`] playing in an
+/// [`AnimationGraphPlayer`](crate::AnimationGraphPlayer).
+#[derive(Clone, Debug)]
+pub(crate) struct StateMachineState {
+    pub(crate) current: usize,
+    time_in_state: f32,
+    /// The states fading out, from the oldest to the newest.
+    pub(crate) fading: Vec<FadingState>,
+    /// Whether the newest fade-out can be interrupted.
+    interruptible: bool,
+}
+
+impl StateMachineState {
+    pub(crate) fn new(machine: &StateMachineNode) -> Self {
+        Self {
+            current: machine.initial_state,
+            time_in_state: 0.0,
+            fading: Vec::new(),
+            interruptible: true,
+        }
+    }
+
+    /// Whether a cross-fade is in progress.
+    pub(crate) fn is_transitioning(&self) -> bool {
+        !self.fading.is_empty()
+    }
+
+    /// Takes the first transition whose conditions are met, and advances the cross-fades.
+    ///
+    /// Returns the state entered, if any.
+    pub(crate) fn update(
+        &mut self,
+        machine: &StateMachineNode,
+        parameters: &mut HashMap<String, f32>,
+        delta: f32,
+    ) -> Option<usize> {
+        self.time_in_state += delta;
+        self.fading.retain_mut(|fading| {
+            fading.weight -= fading.weight_decline_per_sec * delta;
+            fading.weight > 0.0
+        });
+
+        if self.is_transitioning() && !self.interruptible {
+            return None;
+        }
+        let transition = machine.transitions.iter().find(|transition| {
+            let from = match transition.from {
+                Some(from) => from == self.current,
+                None => transition.to != self.current,
+            };
+            from && transition.to < machine.states.len()
+                && transition
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.is_met(parameters, self.time_in_state))
+        })?;
+
+        for condition in &transition.conditions {
+            if let TransitionCondition::Trigger(name) = condition {
+                if let Some(trigger) = parameters.get_mut(name) {
+                    *trigger = 0.0;
+                }
+            }
+        }
+        self.enter(transition.to, transition.duration);
+        self.interruptible = transition.interruptible;
+        Some(transition.to)
+    }
+
+    /// Cross-fades to `state` over `duration` seconds.
+    pub(crate) fn enter(&mut self, state: usize, duration: f32) {
+        if duration > 0.0 {
+            self.fading.push(FadingState {
+                state: self.current,
+                weight: 1.0,
+                weight_decline_per_sec: 1.0 / duration,
+            });
+        } else {
+            self.fading.clear();
+        }
+        self.current = state;
+        self.time_in_state = 0.0;
+        self.interruptible = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Handle;
+
+    use super::*;
+    use crate::{AnimationGraph, ClipNode};
+
+    const IDLE: usize = 0;
+    const JUMP: usize = 1;
+    const FALL: usize = 2;
+
+    /// A machine jumping from idle on the "jump" trigger, falling after a second of jump, and
+    /// landing when "grounded" is set.
+    fn machine() -> StateMachineNode {
+        let mut graph = AnimationGraph::new();
+        let mut machine = StateMachineNode::new();
+        for name in ["idle", "jump", "fall"] {
+            machine.add_state(name, graph.add_node(ClipNode::new(Handle::default())));
+        }
+        machine.add_transition(
+            StateTransition::new(IDLE, JUMP)
+                .when(TransitionCondition::Trigger("jump".to_string()))
+                .with_duration(0.5),
+        );
+        machine.add_transition(
+            StateTransition::new(JUMP, FALL).when(TransitionCondition::TimeInState(1.0)),
+        );
+        machine.add_transition(
+            StateTransition::from_any(IDLE).when(TransitionCondition::True("grounded".to_string())),
+        );
+        machine
+    }
+
+    fn weights(state: &StateMachineState) -> Vec<(usize, f32)> {
+        state
+            .fading
+            .iter()
+            .map(|fading| (fading.state, fading.weight))
+            .collect()
+    }
+
+    #[test]
+    fn conditions() {
+        let parameters: HashMap<String, f32> = [("speed".to_string(), 2.0)].into_iter().collect();
+        let condition = |condition: TransitionCondition| condition.is_met(&parameters, 0.5);
+        assert!(condition(TransitionCondition::Greater(
+            "speed".to_string(),
+            1.0
+        )));
+        assert!(!condition(TransitionCondition::Less(
+            "speed".to_string(),
+            1.0
+        )));
+        assert!(condition(TransitionCondition::True("speed".to_string())));
+        assert!(!condition(TransitionCondition::False("speed".to_string())));
+        // Missing parameters are zero
+        assert!(condition(TransitionCondition::False("crouch".to_string())));
+        assert!(!condition(TransitionCondition::Trigger("jump".to_string())));
+        assert!(condition(TransitionCondition::TimeInState(0.5)));
+        assert!(!condition(TransitionCondition::TimeInState(0.75)));
+    }
+
+    #[test]
+    fn trigger_cross_fades_and_is_reset() {
+        let machine = machine();
+        let mut state = StateMachineState::new(&machine);
+        let mut parameters = HashMap::default();
+        assert_eq!(state.update(&machine, &mut parameters, 0.1), None);
+        assert_eq!(state.current, IDLE);
+
+        parameters.insert("jump".to_string(), 1.0);
+        assert_eq!(state.update(&machine, &mut parameters, 0.1), Some(JUMP));
+        assert_eq!(parameters["jump"], 0.0);
+        assert_eq!(state.current, JUMP);
+        assert_eq!(weights(&state), [(IDLE, 1.0)]);
+
+        // Idle fades out over the duration of the transition
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), None);
+        assert_eq!(weights(&state), [(IDLE, 0.5)]);
+        assert!(state.is_transitioning());
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), None);
+        assert!(!state.is_transitioning());
+    }
+
+    #[test]
+    fn time_in_state() {
+        let machine = machine();
+        let mut state = StateMachineState::new(&machine);
+        let mut parameters = HashMap::default();
+        state.enter(JUMP, 0.0);
+        assert_eq!(state.update(&machine, &mut parameters, 0.5), None);
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), None);
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), Some(FALL));
+        // The time restarts in the new state, and a cut doesn't fade
+        assert!(!state.is_transitioning());
+        assert_eq!(state.update(&machine, &mut parameters, 0.75), None);
+        assert_eq!(state.current, FALL);
+    }
+
+    #[test]
+    fn any_state_transitions_skip_their_target() {
+        let machine = machine();
+        let mut state = StateMachineState::new(&machine);
+        let mut parameters: HashMap<String, f32> =
+            [("grounded".to_string(), 1.0)].into_iter().collect();
+        assert_eq!(state.update(&machine, &mut parameters, 0.1), None);
+
+        state.enter(FALL, 0.0);
+        assert_eq!(state.update(&machine, &mut parameters, 0.1), Some(IDLE));
+        // Conditions other than triggers stay set
+        assert_eq!(parameters["grounded"], 1.0);
+    }
+
+    #[test]
+    fn interruptible_transitions() {
+        let mut machine = machine();
+        let mut state = StateMachineState::new(&machine);
+        let mut parameters: HashMap<String, f32> =
+            [("jump".to_string(), 1.0)].into_iter().collect();
+        state.update(&machine, &mut parameters, 0.1);
+
+        // Landing waits for the jump cross-fade to finish
+        parameters.insert("grounded".to_string(), 1.0);
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), None);
+        assert_eq!(state.current, JUMP);
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), Some(IDLE));
+
+        // Once interruptible, the blended pose fades out to the new state
+        machine.transitions[0].interruptible = true;
+        machine.transitions[2].duration = 0.25;
+        let mut state = StateMachineState::new(&machine);
+        parameters.insert("grounded".to_string(), 0.0);
+        parameters.insert("jump".to_string(), 1.0);
+        state.update(&machine, &mut parameters, 0.1);
+        parameters.insert("grounded".to_string(), 1.0);
+        assert_eq!(state.update(&machine, &mut parameters, 0.25), Some(IDLE));
+        assert_eq!(weights(&state), [(IDLE, 0.5), (JUMP, 1.0)]);
+        assert_eq!(state.update(&machine, &mut parameters, 0.125), None);
+        assert_eq!(weights(&state), [(IDLE, 0.25), (JUMP, 0.5)]);
+    }
+}
@@ -0,0 +1,625 @@
+use std::{f32::consts::PI, ops::RangeInclusive, time::Duration};
+
+use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+use bevy_math::{cubic_splines::CubicSegment, curve::Curve, Quat, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::prelude::Transform;
+use bevy_utils::tracing::warn;
+
+use crate::property::{with_field, PropertyValue};
+
+/// A function easing the progress of a [`Tween`], from `0.0` at its start to `1.0` at its end.
+///
+/// It's also a [`Curve`] over `0.0..=1.0`, to be sampled or resampled like other curves.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub enum EaseFunction {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slowly, with a quadratic speed.
+    QuadraticIn,
+    /// Ends slowly, with a quadratic speed.
+    QuadraticOut,
+    /// Starts and ends slowly, with a quadratic speed.
+    QuadraticInOut,
+    /// Starts slowly, with a cubic speed.
+    CubicIn,
+    /// Ends slowly, with a cubic speed.
+    CubicOut,
+    /// Starts and ends slowly, with a cubic speed.
+    CubicInOut,
+    /// Starts slowly, following a sine wave.
+    SineIn,
+    /// Ends slowly, following a sine wave.
+    SineOut,
+    /// Starts and ends slowly, following a sine wave.
+    SineInOut,
+    /// Starts very slowly, with an exponential speed.
+    ExponentialIn,
+    /// Ends very slowly, with an exponential speed.
+    ExponentialOut,
+    /// Starts and ends very slowly, with an exponential speed.
+    ExponentialInOut,
+    /// Goes slightly backward before moving towards the target.
+    BackIn,
+    /// Overshoots the target before settling on it.
+    BackOut,
+    /// Goes slightly backward at the start and overshoots the target at the end.
+    BackInOut,
+    /// Oscillates around the target like a spring before settling on it.
+    ElasticOut,
+    /// Bounces on the target like a dropped ball.
+    BounceOut,
+    /// Jumps to the target in this number of equal steps.
+    Steps(u32),
+    /// Follows a cubic Bezier curve from `(0, 0)` to `(1, 1)` with these two control points, like
+    /// CSS's `cubic-bezier()`. See [`CubicSegment::new_bezier`].
+    CubicBezier(Vec2, Vec2),
+}
+
+impl EaseFunction {
+    /// Eases the progress `t`, between `0.0` and `1.0`.
+    ///
+    /// The result is `0.0` at the start and `1.0` at the end, but can go beyond them in-between,
+    /// such as with [`EaseFunction::BackOut`].
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        // Constants of the "back" easings, for a 10% overshoot
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+        match self {
+            // No steps is a linear easing
+            EaseFunction::Linear | EaseFunction::Steps(0) => t,
+            EaseFunction::QuadraticIn => t * t,
+            EaseFunction::QuadraticOut => 1.0 - (1.0 - t).powi(2),
+            EaseFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            EaseFunction::CubicIn => t.powi(3),
+            EaseFunction::CubicOut => 1.0 - (1.0 - t).powi(3),
+            EaseFunction::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t.powi(3)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            EaseFunction::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            EaseFunction::SineOut => (t * PI / 2.0).sin(),
+            EaseFunction::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            EaseFunction::ExponentialIn if t == 0.0 => 0.0,
+            EaseFunction::ExponentialIn => 2f32.powf(10.0 * t - 10.0),
+            EaseFunction::ExponentialOut if t == 1.0 => 1.0,
+            EaseFunction::ExponentialOut => 1.0 - 2f32.powf(-10.0 * t),
+            EaseFunction::ExponentialInOut | EaseFunction::ElasticOut if t == 0.0 || t == 1.0 => t,
+            EaseFunction::ExponentialInOut => {
+                if t < 0.5 {
+                    2f32.powf(20.0 * t - 10.0) / 2.0
+                } else {
+                    (2.0 - 2f32.powf(-20.0 * t + 10.0)) / 2.0
+                }
+            }
+            EaseFunction::BackIn => (BACK + 1.0) * t.powi(3) - BACK * t * t,
+            EaseFunction::BackOut => {
+                1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2)
+            }
+            EaseFunction::BackInOut => {
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2)
+                        * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT)
+                        + 2.0)
+                        / 2.0
+                }
+            }
+            EaseFunction::ElasticOut => {
+                2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+            }
+            EaseFunction::BounceOut => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+                if t < 1.0 / D {
+                    N * t * t
+                } else if t < 2.0 / D {
+                    let t = t - 1.5 / D;
+                    N * t * t + 0.75
+                } else if t < 2.5 / D {
+                    let t = t - 2.25 / D;
+                    N * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D;
+                    N * t * t + 0.984375
+                }
+            }
+            EaseFunction::Steps(steps) => (t * steps as f32).floor() / steps as f32,
+            EaseFunction::CubicBezier(p1, p2) => CubicSegment::new_bezier(p1, p2).ease(t),
+        }
+    }
+}
+
+impl Curve<f32> for EaseFunction {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        0.0..=1.0
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> f32 {
+        self.ease(t)
+    }
+}
+
+/// What a step of a [`Tween`] animates.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub enum TweenTarget {
+    /// Moves the [`Transform`] of the entity to this translation.
+    Translation(Vec3),
+    /// Turns the [`Transform`] of the entity to this rotation.
+    Rotation(Quat),
+    /// Scales the [`Transform`] of the entity to this scale.
+    Scale(Vec3),
+    /// Changes a field of any reflected component of the entity to this value.
+    Property {
+        /// The type path of the component, or its short type path if it's unambiguous.
+        component: String,
+        /// The reflection path to the field in the component.
+        field: String,
+        /// The value to reach.
+        value: PropertyValue,
+    },
+}
+
+impl TweenTarget {
+    /// Targets a field of any reflected component, such as the `intensity` of a `PointLight`.
+    pub fn property(
+        component: impl Into<String>,
+        field: impl Into<String>,
+        value: impl Into<PropertyValue>,
+    ) -> Self {
+        Self::Property {
+            component: component.into(),
+            field: field.into(),
+            value: value.into(),
+        }
+    }
+
+    /// The target value.
+    fn value(&self) -> PropertyValue {
+        match self {
+            TweenTarget::Translation(translation) => PropertyValue::Vec3(*translation),
+            TweenTarget::Rotation(rotation) => PropertyValue::Quat(*rotation),
+            TweenTarget::Scale(scale) => PropertyValue::Vec3(*scale),
+            TweenTarget::Property { value, .. } => *value,
+        }
+    }
+}
+
+/// A step of a [`Tween`].
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct TweenStep {
+    /// What is animated, or `None` to wait.
+    pub target: Option<TweenTarget>,
+    /// How long the step lasts.
+    pub duration: Duration,
+    /// How the value progresses towards its target.
+    pub ease: EaseFunction,
+}
+
+/// Animates the [`Transform`] or the fields of reflected components of this entity towards
+/// targets, one step after the other.
+///
+/// Each step starts from the value at the time it starts. The tween follows the [`Time`] of the
+/// app, so it stops when it's paused. When all its steps are done, the component is removed and
+/// a [`TweenCompleted`] event is sent.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_animation::{EaseFunction, Tween, TweenTarget};
+/// # use bevy_math::Vec3;
+/// // Jump up, wait, and fall back down
+/// let tween = Tween::new(
+///     TweenTarget::Translation(Vec3::Y),
+///     Duration::from_millis(300),
+///     EaseFunction::QuadraticOut,
+/// )
+/// .then_wait(Duration::from_millis(100))
+/// .then(
+///     TweenTarget::Translation(Vec3::ZERO),
+///     Duration::from_millis(300),
+///     EaseFunction::BounceOut,
+/// );
+/// ```
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct Tween {
+    steps: Vec<TweenStep>,
+    /// The index of the current step.
+    step: usize,
+    /// How long the current step has been playing.
+    elapsed: Duration,
+    /// The value at the start of the current step, read when it starts.
+    start: Option<PropertyValue>,
+    paused: bool,
+}
+
+impl Tween {
+    /// Creates a tween animating `target` for `duration`.
+    pub fn new(target: TweenTarget, duration: Duration, ease: EaseFunction) -> Self {
+        Self::default().then(target, duration, ease)
+    }
+
+    /// Adds a step animating `target` for `duration`, after the previous ones.
+    pub fn then(mut self, target: TweenTarget, duration: Duration, ease: EaseFunction) -> Self {
+        self.steps.push(TweenStep {
+            target: Some(target),
+            duration,
+            ease,
+        });
+        self
+    }
+
+    /// Adds a step waiting for `duration`, after the previous ones.
+    pub fn then_wait(mut self, duration: Duration) -> Self {
+        self.steps.push(TweenStep {
+            target: None,
+            duration,
+            ease: EaseFunction::Linear,
+        });
+        self
+    }
+
+    /// The steps of the tween.
+    pub fn steps(&self) -> &[TweenStep] {
+        &self.steps
+    }
+
+    /// The index of the current step.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Pause the tween.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the tween.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the tween paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// Sent when all the steps of a [`Tween`] are done, before it's removed.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TweenCompleted {
+    /// The entity with the tween.
+    pub entity: Entity,
+}
+
+/// Reads the current value of `target` on `entity`.
+fn read(
+    world: &mut World,
+    registry: &AppTypeRegistry,
+    entity: Entity,
+    target: &TweenTarget,
+) -> Option<PropertyValue> {
+    match target {
+        TweenTarget::Translation(_) => Some(PropertyValue::Vec3(
+            world.get::<Transform>(entity)?.translation,
+        )),
+        TweenTarget::Rotation(_) => Some(PropertyValue::Quat(
+            world.get::<Transform>(entity)?.rotation,
+        )),
+        TweenTarget::Scale(_) => Some(PropertyValue::Vec3(world.get::<Transform>(entity)?.scale)),
+        TweenTarget::Property {
+            component, field, ..
+        } => with_field(world, &registry.read(), entity, component, field, |field| {
+            PropertyValue::from_field(field)
+        })
+        .flatten(),
+    }
+}
+
+/// Sets `value` as the value of `target` on `entity`.
+fn write(
+    world: &mut World,
+    registry: &AppTypeRegistry,
+    entity: Entity,
+    target: &TweenTarget,
+    value: PropertyValue,
+) {
+    match (target, value) {
+        (TweenTarget::Translation(_), PropertyValue::Vec3(translation)) => {
+            if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+                transform.translation = translation;
+            }
+        }
+        (TweenTarget::Rotation(_), PropertyValue::Quat(rotation)) => {
+            if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+                transform.rotation = rotation;
+            }
+        }
+        (TweenTarget::Scale(_), PropertyValue::Vec3(scale)) => {
+            if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+                transform.scale = scale;
+            }
+        }
+        (
+            TweenTarget::Property {
+                component, field, ..
+            },
+            value,
+        ) => {
+            with_field(
+                world,
+                &registry.read(),
+                entity,
+                component,
+                field,
+                |reflect| {
+                    if reflect.set(value.into_reflect()).is_err() {
+                        warn!(
+                            "Can't tween field {:?} of {:?} with {:?}, its type is different.",
+                            field, component, value
+                        );
+                    }
+                },
+            );
+        }
+        _ => {}
+    }
+}
+
+/// System that advances the [`Tween`]s, and sets the values they animate.
+pub fn update_tweens(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let entities: Vec<_> = world
+        .query_filtered::<Entity, With<Tween>>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        let Some(tween) = world.get::<Tween>(entity) else {
+            continue;
+        };
+        if tween.paused {
+            continue;
+        }
+        let mut tween = tween.clone();
+        let mut delta = delta;
+        let finished = loop {
+            let Some(step) = tween.steps.get(tween.step) else {
+                break true;
+            };
+            tween.elapsed += delta;
+            if let Some(target) = &step.target {
+                if tween.start.is_none() {
+                    tween.start = read(world, &registry, entity, target);
+                }
+                let progress = if step.duration.is_zero() {
+                    1.0
+                } else {
+                    tween.elapsed.as_secs_f32() / step.duration.as_secs_f32()
+                };
+                if let Some(start) = tween.start {
+                    let value = start.lerp(target.value(), step.ease.ease(progress));
+                    write(world, &registry, entity, target, value);
+                }
+            }
+            if tween.elapsed < step.duration {
+                break false;
+            }
+            // Carry the rest of the time over to the next step
+            delta = tween.elapsed - step.duration;
+            tween.elapsed = Duration::ZERO;
+            tween.start = None;
+            tween.step += 1;
+        };
+
+        if finished {
+            world.entity_mut(entity).remove::<Tween>();
+            world.send_event(TweenCompleted { entity });
+        } else if let Some(mut current) = world.get_mut::<Tween>(entity) {
+            *current = tween;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::Events;
+
+    use super::*;
+
+    const EASE_FUNCTIONS: [EaseFunction; 21] = [
+        EaseFunction::Linear,
+        EaseFunction::QuadraticIn,
+        EaseFunction::QuadraticOut,
+        EaseFunction::QuadraticInOut,
+        EaseFunction::CubicIn,
+        EaseFunction::CubicOut,
+        EaseFunction::CubicInOut,
+        EaseFunction::SineIn,
+        EaseFunction::SineOut,
+        EaseFunction::SineInOut,
+        EaseFunction::ExponentialIn,
+        EaseFunction::ExponentialOut,
+        EaseFunction::ExponentialInOut,
+        EaseFunction::BackIn,
+        EaseFunction::BackOut,
+        EaseFunction::BackInOut,
+        EaseFunction::ElasticOut,
+        EaseFunction::BounceOut,
+        EaseFunction::Steps(0),
+        EaseFunction::Steps(4),
+        EaseFunction::CubicBezier(Vec2::new(0.25, 0.1), Vec2::new(0.25, 1.0)),
+    ];
+
+    #[test]
+    fn ease_endpoints() {
+        for ease in EASE_FUNCTIONS {
+            assert!(
+                ease.ease(0.0).abs() < 1e-5,
+                "{ease:?} starts at {}",
+                ease.ease(0.0)
+            );
+            assert!(
+                (ease.ease(1.0) - 1.0).abs() < 1e-5,
+                "{ease:?} ends at {}",
+                ease.ease(1.0)
+            );
+            // The progress is clamped
+            assert_eq!(ease.ease(-1.0), ease.ease(0.0), "{ease:?}");
+            assert_eq!(ease.ease(2.0), ease.ease(1.0), "{ease:?}");
+            // In-out easings are halfway through at half of the time
+            if matches!(
+                ease,
+                EaseFunction::QuadraticInOut
+                    | EaseFunction::CubicInOut
+                    | EaseFunction::SineInOut
+                    | EaseFunction::ExponentialInOut
+                    | EaseFunction::BackInOut
+            ) {
+                assert!((ease.ease(0.5) - 0.5).abs() < 1e-5, "{ease:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn ease_shapes() {
+        assert_eq!(EaseFunction::Linear.ease(0.3), 0.3);
+        assert_eq!(EaseFunction::QuadraticIn.ease(0.5), 0.25);
+        assert_eq!(EaseFunction::QuadraticOut.ease(0.5), 0.75);
+        assert_eq!(EaseFunction::Steps(4).ease(0.3), 0.25);
+        assert_eq!(EaseFunction::Steps(4).ease(0.99), 0.75);
+        assert!(EaseFunction::BackIn.ease(0.2) < 0.0);
+        assert!(EaseFunction::BackOut.ease(0.8) > 1.0);
+        assert!(EaseFunction::ElasticOut.ease(0.2) > 1.0);
+        let samples = (0..=100).map(|t| EaseFunction::BounceOut.ease(t as f32 / 100.0));
+        assert!(samples
+            .into_iter()
+            .all(|value| (0.0..=1.0).contains(&value)));
+        assert_eq!(EaseFunction::SineIn.domain(), 0.0..=1.0);
+        assert_eq!(
+            EaseFunction::SineIn.sample(0.5),
+            EaseFunction::SineIn.ease(0.5)
+        );
+    }
+
+    struct TweenTest {
+        world: World,
+        schedule: Schedule,
+        entity: Entity,
+    }
+
+    impl TweenTest {
+        fn new(tween: Tween) -> Self {
+            let mut world = World::new();
+            world.init_resource::<Time>();
+            world.init_resource::<Events<TweenCompleted>>();
+            let registry = AppTypeRegistry::default();
+            registry.write().register::<Transform>();
+            world.insert_resource(registry);
+            let entity = world.spawn((Transform::default(), tween)).id();
+
+            let mut schedule = Schedule::default();
+            schedule.add_systems(update_tweens);
+            Self {
+                world,
+                schedule,
+                entity,
+            }
+        }
+
+        fn update(&mut self, millis: u64) -> Transform {
+            self.world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            self.schedule.run(&mut self.world);
+            *self.world.get::<Transform>(self.entity).unwrap()
+        }
+
+        fn tween(&self) -> Option<&Tween> {
+            self.world.get::<Tween>(self.entity)
+        }
+
+        fn tween_mut(&mut self) -> Mut<'_, Tween> {
+            self.world.get_mut::<Tween>(self.entity).unwrap()
+        }
+    }
+
+    #[test]
+    fn steps_play_one_after_the_other() {
+        let millis = Duration::from_millis;
+        let tween = Tween::new(
+            TweenTarget::Translation(Vec3::X),
+            millis(1000),
+            EaseFunction::Linear,
+        )
+        .then_wait(millis(500))
+        .then(
+            TweenTarget::property("Transform", "scale.y", 3.0),
+            millis(1000),
+            EaseFunction::QuadraticIn,
+        );
+        let mut test = TweenTest::new(tween);
+
+        let transform = test.update(250);
+        assert!((transform.translation.x - 0.25).abs() < 1e-5);
+        assert_eq!(test.tween().unwrap().step(), 0);
+
+        // The time left after a step is carried over to the next ones
+        let transform = test.update(1500);
+        assert_eq!(transform.translation, Vec3::X);
+        assert_eq!(test.tween().unwrap().step(), 2);
+        // The property starts from its value when the step starts
+        assert!((transform.scale.y - 1.125).abs() < 1e-5);
+
+        test.tween_mut().pause();
+        test.update(1000);
+        assert_eq!(test.tween().unwrap().step(), 2);
+        test.tween_mut().resume();
+
+        let transform = test.update(1000);
+        assert_eq!(transform.scale, Vec3::new(1.0, 3.0, 1.0));
+        assert!(test.tween().is_none());
+        let completed: Vec<_> = test
+            .world
+            .resource_mut::<Events<TweenCompleted>>()
+            .drain()
+            .collect();
+        assert_eq!(
+            completed,
+            [TweenCompleted {
+                entity: test.entity
+            }]
+        );
+    }
+
+    #[test]
+    fn zero_duration_steps_jump_to_their_target() {
+        let tween = Tween::new(
+            TweenTarget::Rotation(Quat::from_rotation_z(1.0)),
+            Duration::ZERO,
+            EaseFunction::Linear,
+        );
+        let mut test = TweenTest::new(tween);
+        let transform = test.update(0);
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(1.0), 1e-6));
+        assert!(test.tween().is_none());
+    }
+}
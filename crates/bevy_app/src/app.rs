@@ -1,16 +1,21 @@
-use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
+use crate::{First, Last, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event_recording::{EventRecording, EventReplay},
+    index::IndexStorage,
     prelude::*,
     schedule::{
-        apply_state_transition, common_conditions::run_once as run_once_condition,
-        run_enter_schedule, InternedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
-        ScheduleBuildSettings, ScheduleLabel,
+        apply_computed_state_transition, apply_state_transition, apply_sub_state_transition,
+        clear_state_scoped_entities, common_conditions::run_once as run_once_condition,
+        run_enter_schedule, ApplyStateTransition, InternedScheduleLabel, IntoSystemConfigs,
+        IntoSystemSetConfigs, ScheduleBuildSettings, ScheduleLabel,
     },
 };
 use bevy_utils::{intern::Interned, thiserror::Error, tracing::debug, HashMap, HashSet};
 use std::{
+    collections::BTreeMap,
     fmt::Debug,
+    hash::Hash,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
 };
 
@@ -184,6 +189,8 @@ impl Default for App {
         let mut app = App::empty();
         #[cfg(feature = "bevy_reflect")]
         app.init_resource::<AppTypeRegistry>();
+        #[cfg(feature = "bevy_reflect")]
+        app.init_resource::<AppFunctionRegistry>();
 
         app.add_plugins(MainSchedulePlugin);
 
@@ -372,7 +379,8 @@ impl App {
                     run_enter_schedule::<S>.run_if(run_once_condition()),
                     apply_state_transition::<S>,
                 )
-                    .chain(),
+                    .chain()
+                    .in_set(ApplyStateTransition::<S>::default()),
             );
 
         // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
@@ -382,6 +390,45 @@ impl App {
         self
     }
 
+    /// Adds an instance of [`apply_computed_state_transition::<S>`] in [`StateTransition`],
+    /// which updates the [`State<S>`] resource of the [`ComputedStates`] `S` after each
+    /// transition of its source state.
+    ///
+    /// The source state must be added to the app as well.
+    pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            apply_computed_state_transition::<S>
+                .in_set(ApplyStateTransition::<S>::default())
+                .after(ApplyStateTransition::<S::SourceStates>::default()),
+        )
+    }
+
+    /// Adds a [`NextState<S>`] resource and an instance of [`apply_sub_state_transition::<S>`]
+    /// in [`StateTransition`], which inserts or removes the [`State<S>`] resource of the
+    /// [`SubStates`] `S` after each transition of its source state, and applies its own
+    /// transitions while it exists.
+    ///
+    /// The source state must be added to the app as well.
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        self.init_resource::<NextState<S>>().add_systems(
+            StateTransition,
+            apply_sub_state_transition::<S>
+                .in_set(ApplyStateTransition::<S>::default())
+                .after(ApplyStateTransition::<S::SourceStates>::default()),
+        )
+    }
+
+    /// Adds an instance of [`clear_state_scoped_entities::<S>`] in [`StateTransition`], which
+    /// despawns the entities with a [`StateScoped<S>`] component when the state `S` exits their
+    /// value.
+    pub fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            clear_state_scoped_entities::<S>.after(ApplyStateTransition::<S>::default()),
+        )
+    }
+
     /// Adds a system to the given schedule in this app's [`Schedules`].
     ///
     /// # Examples
@@ -472,6 +519,110 @@ impl App {
         self
     }
 
+    /// Setup the application to record the events of type `T` in an [`EventRecording`].
+    ///
+    /// This is done by inserting the `recording` [`Resource`] and its
+    /// [`record_system`](EventRecording::record_system) into [`Last`], after calling
+    /// [`add_event`](Self::add_event). Recording the same events again replaces the recording.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event_recording::EventRecording, prelude::*};
+    /// #
+    /// # #[derive(Event, Clone)]
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// app.record_events::<MyEvent>(EventRecording::with_capacity(1024));
+    /// ```
+    pub fn record_events<T>(&mut self, recording: EventRecording<T>) -> &mut Self
+    where
+        T: Event + Clone,
+    {
+        let recorded = self.world.contains_resource::<EventRecording<T>>();
+        self.add_event::<T>().insert_resource(recording);
+        if !recorded {
+            self.add_systems(Last, EventRecording::<T>::record_system);
+        }
+        self
+    }
+
+    /// Setup the application to send the events of type `T` of an [`EventReplay`], in the
+    /// frames they were recorded in.
+    ///
+    /// This is done by inserting the `replay` [`Resource`] and its
+    /// [`replay_system`](EventReplay::replay_system) into [`First`], after calling
+    /// [`add_event`](Self::add_event). Replaying the same events again replaces the replay.
+    pub fn replay_events<T>(&mut self, replay: EventReplay<T>) -> &mut Self
+    where
+        T: Event,
+    {
+        let replayed = self.world.contains_resource::<EventReplay<T>>();
+        self.add_event::<T>().insert_resource(replay);
+        if !replayed {
+            self.add_systems(
+                First,
+                EventReplay::<T>::replay_system.after(bevy_ecs::event::event_update_system::<T>),
+            );
+        }
+        self
+    }
+
+    /// Setup the application to maintain a [`ComponentIndex`] of the component `C`, to look up
+    /// entities by the value of their component.
+    ///
+    /// This is done by adding the [`ComponentIndex::<C>`] [`Resource`], and inserting its
+    /// [`update_system`](ComponentIndex::update_system) into [`First`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+    /// struct ChunkCoord(i32, i32);
+    ///
+    /// fn spawn_enemies(chunks: Res<ComponentIndex<ChunkCoord>>) {
+    ///     let chunk = chunks.get(&ChunkCoord(3, 4));
+    /// }
+    ///
+    /// App::new()
+    ///     .add_component_index::<ChunkCoord>()
+    ///     .add_systems(Update, spawn_enemies);
+    /// ```
+    pub fn add_component_index<C>(&mut self) -> &mut Self
+    where
+        C: Component + Clone + Hash + Eq,
+    {
+        self.add_index::<C, HashMap<C, Vec<Entity>>>()
+    }
+
+    /// Setup the application to maintain an [`OrderedComponentIndex`] of the component `C`, to
+    /// look up entities by ranges of values of their component.
+    ///
+    /// See [`App::add_component_index`].
+    pub fn add_ordered_component_index<C>(&mut self) -> &mut Self
+    where
+        C: Component + Clone + Ord,
+    {
+        self.add_index::<C, BTreeMap<C, Vec<Entity>>>()
+    }
+
+    fn add_index<C, S>(&mut self) -> &mut Self
+    where
+        C: Component + Clone,
+        S: IndexStorage<C>,
+    {
+        if !self.world.contains_resource::<ComponentIndex<C, S>>() {
+            self.init_resource::<ComponentIndex<C, S>>()
+                .add_systems(First, ComponentIndex::<C, S>::update_system);
+        }
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -762,6 +913,32 @@ impl App {
         self
     }
 
+    /// Registers `function` under `name` in the [`FunctionRegistry`](bevy_reflect::func::FunctionRegistry)
+    /// resource, so that it can be called with reflected arguments.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy_app::App;
+    ///
+    /// fn add(a: i32, b: i32) -> i32 {
+    ///     a + b
+    /// }
+    ///
+    /// App::new().register_function("add", add);
+    /// ```
+    ///
+    /// See [`bevy_reflect::func::FunctionRegistry::register`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_function<M>(
+        &mut self,
+        name: impl Into<std::borrow::Cow<'static, str>>,
+        function: impl bevy_reflect::func::IntoFunction<M>,
+    ) -> &mut Self {
+        let registry = self.world.resource_mut::<AppFunctionRegistry>();
+        registry.write().register(name, function);
+        self
+    }
+
     /// Retrieves a `SubApp` stored inside this [`App`].
     ///
     /// # Panics
@@ -992,7 +1169,7 @@ mod tests {
     use std::marker::PhantomData;
 
     use bevy_ecs::{
-        schedule::{OnEnter, States},
+        schedule::{ComputedStates, NextState, OnEnter, State, StateScoped, States, SubStates},
         system::Commands,
     };
 
@@ -1088,6 +1265,90 @@ mod tests {
         assert_eq!(app.world.entities().len(), 2);
     }
 
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    enum GameState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    enum Pause {
+        #[default]
+        Running,
+        Paused,
+    }
+
+    impl SubStates for Pause {
+        type SourceStates = GameState;
+
+        fn should_exist(source: &GameState) -> bool {
+            *source == GameState::InGame
+        }
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    struct Simulating;
+
+    impl ComputedStates for Simulating {
+        type SourceStates = Pause;
+
+        fn compute(source: &Pause) -> Option<Self> {
+            (*source == Pause::Running).then_some(Simulating)
+        }
+    }
+
+    #[test]
+    fn sub_and_computed_states() {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .add_sub_state::<Pause>()
+            .add_computed_state::<Simulating>()
+            .enable_state_scoped_entities::<GameState>()
+            .enable_state_scoped_entities::<Pause>()
+            .add_systems(OnEnter(GameState::InGame), |mut commands: Commands| {
+                commands.spawn(StateScoped(GameState::InGame));
+            })
+            .add_systems(OnEnter(Pause::Paused), |mut commands: Commands| {
+                commands.spawn(StateScoped(Pause::Paused));
+            });
+
+        app.update();
+        assert!(app.world.get_resource::<State<Pause>>().is_none());
+        assert!(app.world.get_resource::<State<Simulating>>().is_none());
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Pause>>(), Pause::Running);
+        assert!(app.world.get_resource::<State<Simulating>>().is_some());
+        assert_eq!(app.world.entities().len(), 1);
+
+        app.world
+            .resource_mut::<NextState<Pause>>()
+            .set(Pause::Paused);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Pause>>(), Pause::Paused);
+        assert!(app.world.get_resource::<State<Simulating>>().is_none());
+        assert_eq!(app.world.entities().len(), 2);
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.update();
+        assert!(app.world.get_resource::<State<Pause>>().is_none());
+        assert!(app.world.get_resource::<State<Simulating>>().is_none());
+        assert_eq!(app.world.entities().len(), 0);
+
+        // The sub-state starts over from its default value.
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Pause>>(), Pause::Running);
+    }
+
     #[test]
     fn test_derive_app_label() {
         use super::AppLabel;
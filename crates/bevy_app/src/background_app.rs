@@ -0,0 +1,308 @@
+use crate::{App, AppExit, AppLabel, InternedAppLabel, PluginsState};
+use bevy_ecs::{
+    event::{Events, ManualEventReader},
+    system::Resource,
+};
+use bevy_utils::{tracing::debug, HashMap, Instant};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::{thread::JoinHandle, time::Duration};
+
+/// An [`App`] running on its own thread, at its own cadence, alongside the main [`App`].
+///
+/// Unlike a [`SubApp`](crate::SubApp), which is updated right after the main app in each
+/// [`App::update`], a background app is updated in a loop on a dedicated thread, so it suits work
+/// which shouldn't hold up the frames of the main app: a server simulation running at a fixed
+/// rate, or the generation of a world in the background.
+///
+/// The worlds of the apps can only communicate through typed channels, added with
+/// [`App::add_channel_to`] before the background app is inserted. Entities aren't shared between
+/// the worlds, so the messages should identify them in a way both apps agree on.
+///
+/// # Example
+///
+/// ```rust
+/// # use bevy_app::{App, AppLabel, BackgroundApp, Inbox, Outbox, Update};
+/// # use bevy_ecs::prelude::*;
+/// # use std::time::Duration;
+/// #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+/// struct Simulation;
+///
+/// struct Tick(u32);
+///
+/// let mut app = App::new();
+/// let mut simulation = App::new();
+/// simulation.add_channel_to::<Tick>(&mut app);
+/// simulation.add_systems(Update, |mut tick: Local<u32>, outbox: Res<Outbox<Tick>>| {
+///     *tick += 1;
+///     outbox.send(Tick(*tick));
+/// });
+///
+/// app.insert_background_app(
+///     Simulation,
+///     BackgroundApp::new(simulation).with_wait(Duration::from_millis(10)),
+/// );
+/// let tick = app.world.resource::<Inbox<Tick>>().recv_timeout(Duration::from_secs(10));
+/// assert_eq!(tick.map(|tick| tick.0), Some(1));
+/// ```
+pub struct BackgroundApp {
+    /// The [`BackgroundApp`]'s instance of [`App`]
+    pub app: App,
+    wait: Option<Duration>,
+}
+
+impl BackgroundApp {
+    /// Creates a background app updating as fast as possible.
+    pub fn new(app: App) -> Self {
+        Self { app, wait: None }
+    }
+
+    /// Sets the minimum duration between the start of two updates of the app, so that it updates
+    /// at a steady rate instead of as fast as possible.
+    pub fn with_wait(mut self, wait: Duration) -> Self {
+        self.wait = Some(wait);
+        self
+    }
+
+    /// Starts updating the app on a new thread, until the handle is stopped or dropped, or the
+    /// app sends an [`AppExit`] event.
+    fn spawn(self) -> BackgroundAppHandle {
+        let (stop_sender, stop_receiver) = crossbeam_channel::bounded(1);
+        let thread = std::thread::Builder::new()
+            .name("background app".to_string())
+            .spawn(move || self.run(stop_receiver))
+            .expect("Failed to spawn the thread of the background app");
+        BackgroundAppHandle {
+            stop: stop_sender,
+            thread,
+        }
+    }
+
+    fn run(self, stop: Receiver<()>) -> App {
+        let BackgroundApp { mut app, wait } = self;
+        if app.plugins_state() != PluginsState::Cleaned {
+            while app.plugins_state() == PluginsState::Adding {
+                std::thread::yield_now();
+            }
+            app.finish();
+            app.cleanup();
+        }
+
+        let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
+        loop {
+            let start_time = Instant::now();
+
+            app.update();
+
+            if let Some(app_exit_events) = app.world.get_resource::<Events<AppExit>>() {
+                if app_exit_event_reader.read(app_exit_events).last().is_some() {
+                    debug!("exiting background app after an AppExit event");
+                    return app;
+                }
+            }
+
+            let delay = wait.and_then(|wait| wait.checked_sub(start_time.elapsed()));
+            let stopped = match delay {
+                Some(delay) => !matches!(stop.recv_timeout(delay), Err(RecvTimeoutError::Timeout)),
+                None => !matches!(stop.try_recv(), Err(TryRecvError::Empty)),
+            };
+            if stopped {
+                return app;
+            }
+        }
+    }
+}
+
+/// The thread of a running [`BackgroundApp`].
+struct BackgroundAppHandle {
+    stop: Sender<()>,
+    thread: JoinHandle<App>,
+}
+
+impl BackgroundAppHandle {
+    /// Stops the background app after its current update, and returns it.
+    fn stop(self) -> App {
+        // The thread may have already exited, and dropped the receiver.
+        let _ = self.stop.send(());
+        match self.thread.join() {
+            Ok(app) => app,
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+}
+
+/// A [`Resource`] of the main app storing the threads of its [`BackgroundApp`]s.
+///
+/// Dropping it stops the background apps after their current update, without waiting for them.
+#[derive(Resource, Default)]
+pub(crate) struct BackgroundApps {
+    apps: HashMap<InternedAppLabel, BackgroundAppHandle>,
+}
+
+impl App {
+    /// Starts updating a [`BackgroundApp`] on its own thread, replacing and stopping the
+    /// background app which had the same label.
+    pub fn insert_background_app(
+        &mut self,
+        label: impl AppLabel,
+        background_app: BackgroundApp,
+    ) -> &mut Self {
+        let handle = background_app.spawn();
+        let previous = self
+            .world
+            .get_resource_or_insert_with(BackgroundApps::default)
+            .apps
+            .insert(label.intern(), handle);
+        if let Some(previous) = previous {
+            previous.stop();
+        }
+        self
+    }
+
+    /// Stops the [`BackgroundApp`] with the label after its current update, waiting for it, and
+    /// returns its [`App`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the background app panicked.
+    pub fn remove_background_app(&mut self, label: impl AppLabel) -> Option<App> {
+        self.world
+            .get_resource_mut::<BackgroundApps>()?
+            .apps
+            .remove(&label.intern())
+            .map(BackgroundAppHandle::stop)
+    }
+
+    /// Returns `true` if the [`BackgroundApp`] with the label is running, and `false` if it
+    /// exited or doesn't exist.
+    pub fn is_background_app_running(&self, label: impl AppLabel) -> bool {
+        self.world
+            .get_resource::<BackgroundApps>()
+            .and_then(|background_apps| background_apps.apps.get(&label.intern()))
+            .is_some_and(|handle| !handle.thread.is_finished())
+    }
+
+    /// Adds a channel sending values of type `T` from this app to the `receiver` app, by
+    /// inserting an [`Outbox<T>`] resource in this app and an [`Inbox<T>`] resource in the
+    /// `receiver` app.
+    ///
+    /// The apps usually run on different threads, one of them being a [`BackgroundApp`]. Only one
+    /// channel can carry values of a type to an app, so wrap the values in different types for
+    /// different channels.
+    pub fn add_channel_to<T: Send + 'static>(&mut self, receiver: &mut App) -> &mut Self {
+        let (sender, inbox) = crossbeam_channel::unbounded::<T>();
+        receiver.insert_resource(Inbox(inbox));
+        self.insert_resource(Outbox(sender))
+    }
+}
+
+/// A [`Resource`] sending values of type `T` to the [`Inbox<T>`] of another app.
+///
+/// It is added by [`App::add_channel_to`].
+#[derive(Resource, Clone)]
+pub struct Outbox<T: Send + 'static>(Sender<T>);
+
+impl<T: Send + 'static> Outbox<T> {
+    /// Sends the value to the other app, returning `false` if the other app was dropped.
+    pub fn send(&self, value: T) -> bool {
+        self.0.send(value).is_ok()
+    }
+}
+
+/// A [`Resource`] receiving values of type `T` sent by the [`Outbox<T>`] of another app.
+///
+/// It is added by [`App::add_channel_to`].
+#[derive(Resource)]
+pub struct Inbox<T: Send + 'static>(Receiver<T>);
+
+impl<T: Send + 'static> Inbox<T> {
+    /// Returns the values received since they were last read, without waiting for more.
+    pub fn try_iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.0.try_iter()
+    }
+
+    /// Returns the next value, waiting at most for the `timeout` if none was received yet.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        self.0.recv_timeout(timeout).ok()
+    }
+
+    /// Returns the number of values received and not read yet.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if all of the values received were read.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_app, prelude::*};
+    use bevy_ecs::prelude::*;
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    struct Server;
+
+    struct Spawn(u32);
+
+    struct Spawned(u32);
+
+    #[derive(Resource, Default)]
+    struct Count(u32);
+
+    #[test]
+    fn background_app_channels() {
+        let mut app = App::new();
+        let mut server = App::new();
+        app.add_channel_to::<Spawn>(&mut server);
+        server
+            .add_channel_to::<Spawned>(&mut app)
+            .init_resource::<Count>()
+            .add_systems(
+                Update,
+                |inbox: Res<Inbox<Spawn>>,
+                 outbox: Res<Outbox<Spawned>>,
+                 mut count: ResMut<Count>| {
+                    for spawn in inbox.try_iter() {
+                        count.0 += spawn.0;
+                        outbox.send(Spawned(count.0));
+                    }
+                },
+            );
+        app.insert_background_app(Server, BackgroundApp::new(server));
+        assert!(app.is_background_app_running(Server));
+
+        let outbox = app.world.resource::<Outbox<Spawn>>();
+        assert!(outbox.send(Spawn(2)));
+        assert!(outbox.send(Spawn(3)));
+        let inbox = app.world.resource::<Inbox<Spawned>>();
+        let timeout = Duration::from_secs(10);
+        assert_eq!(
+            inbox.recv_timeout(timeout).map(|spawned| spawned.0),
+            Some(2)
+        );
+        assert_eq!(
+            inbox.recv_timeout(timeout).map(|spawned| spawned.0),
+            Some(5)
+        );
+
+        let server = app.remove_background_app(Server).unwrap();
+        assert_eq!(server.world.resource::<Count>().0, 5);
+        assert!(!app.is_background_app_running(Server));
+    }
+
+    #[test]
+    fn background_app_exit() {
+        let mut app = App::new();
+        let mut server = App::new();
+        server.add_systems(Update, |mut exit: EventWriter<AppExit>| {
+            exit.send(AppExit);
+        });
+        app.insert_background_app(Server, BackgroundApp::new(server));
+
+        let server = app.remove_background_app(Server).unwrap();
+        assert!(!server.world.resource::<Events<AppExit>>().is_empty());
+    }
+}
@@ -3,6 +3,8 @@
 #![warn(missing_docs)]
 
 mod app;
+#[cfg(not(target_arch = "wasm32"))]
+mod background_app;
 mod main_schedule;
 mod plugin;
 mod plugin_group;
@@ -12,6 +14,8 @@ mod schedule_runner;
 pub mod ci_testing;
 
 pub use app::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use background_app::*;
 pub use bevy_derive::DynamicPlugin;
 pub use main_schedule::*;
 pub use plugin::*;
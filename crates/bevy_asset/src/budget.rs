@@ -0,0 +1,167 @@
+use crate::{Asset, AssetEvent, AssetId, AssetServer, Assets};
+use bevy_ecs::prelude::*;
+use bevy_utils::{HashMap, HashSet};
+
+/// An [`Asset`] which can estimate how much memory it uses, so that it can be kept within an
+/// [`AssetMemoryBudget`].
+pub trait AssetMemoryUsage: Asset {
+    /// Returns the approximate number of bytes used by this asset.
+    fn memory_usage(&self) -> usize;
+}
+
+/// A [`Resource`] limiting the memory used by the loaded assets of type `A`, added with
+/// [`AssetApp::set_asset_memory_budget`](crate::AssetApp::set_asset_memory_budget).
+///
+/// When the assets use more than [`AssetMemoryBudget::max_bytes`], the least recently used assets
+/// which were loaded from a path are evicted: they are removed from [`Assets`] while their handles
+/// stay valid. Marking an evicted asset as used with [`AssetMemoryBudget::mark_used`] reloads it
+/// from its path.
+///
+/// Assets are considered used when they are added or modified, and when they are marked as used.
+/// Assets which were not loaded from a path, like the ones added with [`Assets::add`], can't be
+/// reloaded and are never evicted.
+#[derive(Resource)]
+pub struct AssetMemoryBudget<A: AssetMemoryUsage> {
+    max_bytes: usize,
+    used_bytes: usize,
+    frame: u32,
+    resident: HashMap<AssetId<A>, ResidentAsset>,
+    evicted: HashSet<AssetId<A>>,
+    reload_requests: HashSet<AssetId<A>>,
+    reloading: HashSet<AssetId<A>>,
+}
+
+struct ResidentAsset {
+    bytes: usize,
+    last_used: u32,
+}
+
+impl<A: AssetMemoryUsage> AssetMemoryBudget<A> {
+    /// Creates a budget allowing the assets to use up to `max_bytes`.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            used_bytes: 0,
+            frame: 0,
+            resident: HashMap::default(),
+            evicted: HashSet::default(),
+            reload_requests: HashSet::default(),
+            reloading: HashSet::default(),
+        }
+    }
+
+    /// Returns the number of bytes the assets are allowed to use.
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Sets the number of bytes the assets are allowed to use.
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+    }
+
+    /// Returns the number of bytes used by the loaded assets.
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// Returns the number of loaded assets.
+    pub fn resident_len(&self) -> usize {
+        self.resident.len()
+    }
+
+    /// Returns the number of assets which are evicted and not reloaded yet.
+    pub fn evicted_len(&self) -> usize {
+        self.evicted.len()
+    }
+
+    /// Returns `true` if the asset was evicted and is not reloaded yet.
+    pub fn is_evicted(&self, id: impl Into<AssetId<A>>) -> bool {
+        self.evicted.contains(&id.into())
+    }
+
+    /// Marks the asset as used, which delays its eviction, or reloads it if it was evicted.
+    pub fn mark_used(&mut self, id: impl Into<AssetId<A>>) {
+        let id = id.into();
+        if let Some(resident) = self.resident.get_mut(&id) {
+            resident.last_used = self.frame;
+        } else if self.evicted.contains(&id) {
+            self.reload_requests.insert(id);
+        }
+    }
+}
+
+/// Tracks the memory used by the assets of type `A`, reloads the evicted assets which were
+/// marked as used, and evicts the least recently used assets when they exceed the
+/// [`AssetMemoryBudget`].
+pub fn apply_asset_memory_budget<A: AssetMemoryUsage>(
+    mut budget: ResMut<AssetMemoryBudget<A>>,
+    mut events: EventReader<AssetEvent<A>>,
+    mut assets: ResMut<Assets<A>>,
+    server: Res<AssetServer>,
+) {
+    let budget = &mut *budget;
+    budget.frame = budget.frame.wrapping_add(1);
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let Some(asset) = assets.get(id) else {
+                    continue;
+                };
+                let bytes = asset.memory_usage();
+                let last_used = budget.frame;
+                if let Some(previous) = budget
+                    .resident
+                    .insert(id, ResidentAsset { bytes, last_used })
+                {
+                    budget.used_bytes -= previous.bytes;
+                }
+                budget.used_bytes += bytes;
+                budget.evicted.remove(&id);
+                budget.reloading.remove(&id);
+            }
+            AssetEvent::Removed { id } => {
+                if let Some(resident) = budget.resident.remove(&id) {
+                    budget.used_bytes -= resident.bytes;
+                }
+            }
+            AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    // Forget the evicted assets whose handles were all dropped in the meantime.
+    budget.evicted.retain(|id| server.is_managed(*id));
+    budget.reloading.retain(|id| budget.evicted.contains(id));
+    for id in budget.reload_requests.drain() {
+        if !budget.reloading.insert(id) {
+            continue;
+        }
+        if let Some(path) = server.get_path(id) {
+            server.reload(path);
+        }
+    }
+
+    if budget.used_bytes <= budget.max_bytes {
+        return;
+    }
+    let mut candidates = budget
+        .resident
+        .iter()
+        .filter(|(id, resident)| {
+            resident.last_used != budget.frame && server.get_path(**id).is_some()
+        })
+        .map(|(id, resident)| (*id, resident.last_used))
+        .collect::<Vec<_>>();
+    // Frames wrap around, so the oldest uses are the furthest behind the current frame.
+    candidates
+        .sort_by_key(|(_, last_used)| std::cmp::Reverse(budget.frame.wrapping_sub(*last_used)));
+    for (id, _) in candidates {
+        if budget.used_bytes <= budget.max_bytes {
+            break;
+        }
+        let resident = budget.resident.remove(&id).unwrap();
+        budget.used_bytes -= resident.bytes;
+        budget.evicted.insert(id);
+        assets.remove(id);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{AssetMemoryBudget, AssetMemoryUsage};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+use bevy_utils::FixedState;
+use std::{any::TypeId, hash::BuildHasher, marker::PhantomData};
+
+/// Adds a diagnostic of the memory used by the loaded assets of type `A`, in MiB, to an App.
+///
+/// The assets must have an [`AssetMemoryBudget`], which is added with
+/// [`AssetApp::set_asset_memory_budget`](crate::AssetApp::set_asset_memory_budget).
+pub struct AssetMemoryDiagnosticsPlugin<A: AssetMemoryUsage> {
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: AssetMemoryUsage> Default for AssetMemoryDiagnosticsPlugin<A> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A: AssetMemoryUsage> Plugin for AssetMemoryDiagnosticsPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(
+            Diagnostic::new(
+                Self::diagnostic_id(),
+                format!("{}_memory", A::short_type_path()),
+                20,
+            )
+            .with_suffix("MiB"),
+        )
+        .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl<A: AssetMemoryUsage> AssetMemoryDiagnosticsPlugin<A> {
+    /// Returns the [`DiagnosticId`] of the memory used by the assets of type `A`.
+    pub fn diagnostic_id() -> DiagnosticId {
+        DiagnosticId::from_u128(FixedState.hash_one(TypeId::of::<A>()) as u128)
+    }
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, budget: Res<AssetMemoryBudget<A>>) {
+        diagnostics.add_measurement(Self::diagnostic_id(), || {
+            budget.used_bytes() as f64 / (1024.0 * 1024.0)
+        });
+    }
+}
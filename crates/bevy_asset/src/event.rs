@@ -1,4 +1,4 @@
-use crate::{Asset, AssetId};
+use crate::{Asset, AssetId, AssetPath};
 use bevy_ecs::event::Event;
 use std::fmt::Debug;
 
@@ -75,3 +75,18 @@ impl<A: Asset> PartialEq for AssetEvent<A> {
 }
 
 impl<A: Asset> Eq for AssetEvent<A> {}
+
+/// An event sent when an asset file changes in a watched [`AssetSource`](crate::io::AssetSource),
+/// describing the assets which are hot-reloaded because of the change.
+///
+/// Once reloaded, these assets and the assets which depend on them through their handles emit
+/// [`AssetEvent::Modified`].
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct AssetSourceChanged {
+    /// The path of the changed asset file.
+    pub path: AssetPath<'static>,
+    /// The paths of the loaded assets which are reloaded: the changed asset itself, and the assets
+    /// which read it while loading with [`LoadContext::load_direct`](crate::LoadContext::load_direct),
+    /// recursively.
+    pub reloaded: Vec<AssetPath<'static>>,
+}
@@ -0,0 +1,229 @@
+use crate::{
+    io::{AssetReader, AssetReaderError, PathStream, Reader, VecReader},
+    Asset, AssetLoader, AssetPath, AsyncReadExt, LoadContext,
+};
+use bevy_utils::BoxedFuture;
+use std::{path::Path, str::FromStr};
+use thiserror::Error;
+
+/// The name of the [`AssetSource`](crate::io::AssetSource) of the assets generated by
+/// [`AssetGenerator`]s.
+pub const PROCEDURAL: &str = "proc";
+
+/// Generates an [`Asset`] from parameters rather than from the bytes of a file.
+///
+/// A generator is registered under a name with
+/// [`AssetApp::register_asset_generator`](crate::AssetApp::register_asset_generator), and its
+/// assets are loaded like any other asset, from the [`PROCEDURAL`] source with the parameters in the
+/// query of the path:
+///
+/// ```
+/// # use bevy_asset::{prelude::*, AssetGenerator, GeneratorParamError, GeneratorParams, LoadContext};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_utils::BoxedFuture;
+/// #[derive(Asset, TypePath)]
+/// struct Noise(Vec<f32>);
+///
+/// struct NoiseGenerator;
+///
+/// impl AssetGenerator for NoiseGenerator {
+///     type Asset = Noise;
+///     type Error = GeneratorParamError;
+///
+///     fn generate<'a>(
+///         &'a self,
+///         params: &'a GeneratorParams,
+///         _load_context: &'a mut LoadContext,
+///     ) -> BoxedFuture<'a, Result<Noise, GeneratorParamError>> {
+///         Box::pin(async move {
+///             let seed: u32 = params.parse("seed")?;
+///             let size: usize = params.parse_or("size", 16)?;
+///             Ok(Noise((0..size).map(|i| ((seed as usize * 31 + i) % 7) as f32).collect()))
+///         })
+///     }
+/// }
+///
+/// fn load_noise(asset_server: Res<AssetServer>) {
+///     let noise: Handle<Noise> = asset_server.load("proc://noise?seed=5&size=64");
+/// }
+/// ```
+///
+/// The same parameters always point to the same asset, so changing them loads a new asset. The
+/// generator can load dependencies through its [`LoadContext`]: its assets are reloaded when the
+/// assets it read with [`LoadContext::load_direct`] change, and they can be reloaded explicitly with
+/// [`AssetServer::reload_generated`](crate::AssetServer::reload_generated), for example after
+/// changing the data the generator reads outside of its parameters. Generated assets can also be
+/// loaded by the [`AssetProcessor`](crate::processor::AssetProcessor), to be used by the processed
+/// assets.
+pub trait AssetGenerator: Send + Sync + 'static {
+    /// The [`Asset`] generated by this [`AssetGenerator`].
+    type Asset: Asset;
+    /// The type of [error](`std::error::Error`) which could be encountered by this generator.
+    type Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Asynchronously generates [`AssetGenerator::Asset`] (and any labeled assets) from the `params`.
+    fn generate<'a>(
+        &'a self,
+        params: &'a GeneratorParams,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>>;
+}
+
+/// The parameters of a generated asset, from the `key=value` pairs of the query of its path,
+/// separated by `&`.
+///
+/// Since `#` separates the label of an [`AssetPath`], it can't be used in the parameters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeneratorParams {
+    params: Vec<(String, String)>,
+}
+
+impl GeneratorParams {
+    /// Parses the parameters from the query of a path, such as `seed=5&size=64`. A parameter
+    /// without a `=` has an empty value.
+    pub fn from_query(query: &str) -> Self {
+        let params = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| match param.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (param.to_string(), String::new()),
+            })
+            .collect();
+        Self { params }
+    }
+
+    /// Returns the value of the parameter `key`, if it is set.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the value of the parameter `key`, which must be set.
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<T, GeneratorParamError> {
+        let value = self
+            .get(key)
+            .ok_or_else(|| GeneratorParamError::Missing(key.to_string()))?;
+        value.parse().map_err(|_| GeneratorParamError::Invalid {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Parses the value of the parameter `key`, or returns `default` if it is not set.
+    pub fn parse_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, GeneratorParamError> {
+        match self.get(key) {
+            Some(_) => self.parse(key),
+            None => Ok(default),
+        }
+    }
+
+    /// Iterates over the `(key, value)` pairs of the parameters, in the order of the query.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+/// An error returned when reading the [`GeneratorParams`] of a generated asset.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GeneratorParamError {
+    #[error("missing generator parameter '{0}'")]
+    Missing(String),
+    #[error("invalid value '{value}' for generator parameter '{key}'")]
+    Invalid { key: String, value: String },
+}
+
+/// Returns the name of the [`AssetGenerator`] of the asset at `path`, if it is in the [`PROCEDURAL`]
+/// source.
+pub(crate) fn generator_name<'a>(path: &'a AssetPath) -> Option<&'a str> {
+    if path.source().as_str() != Some(PROCEDURAL) {
+        return None;
+    }
+    let path = path.path().to_str()?;
+    Some(path.split_once('?').map_or(path, |(name, _)| name))
+}
+
+/// Loads the assets of an [`AssetGenerator`] from the query of their path, which is read by the
+/// [`ProceduralAssetReader`].
+pub(crate) struct GeneratorLoader<G: AssetGenerator> {
+    generator: G,
+}
+
+impl<G: AssetGenerator> GeneratorLoader<G> {
+    pub(crate) fn new(generator: G) -> Self {
+        Self { generator }
+    }
+}
+
+impl<G: AssetGenerator> AssetLoader for GeneratorLoader<G> {
+    type Asset = G::Asset;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<G::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut query = String::new();
+            reader.read_to_string(&mut query).await?;
+            let params = GeneratorParams::from_query(&query);
+            self.generator
+                .generate(&params, load_context)
+                .await
+                .map_err(Into::into)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[]
+    }
+}
+
+/// The [`AssetReader`] of the [`PROCEDURAL`] source, which reads the query of the path of a generated
+/// asset as its bytes. Generated assets don't have meta files or folders.
+pub(crate) struct ProceduralAssetReader;
+
+impl AssetReader for ProceduralAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move {
+            let query = path
+                .to_str()
+                .and_then(|path| path.split_once('?'))
+                .map_or("", |(_, query)| query);
+            let reader: Box<Reader> = Box::new(VecReader::new(query.as_bytes().to_vec()));
+            Ok(reader)
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_owned())) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_owned())) })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move { Ok(false) })
+    }
+}
@@ -12,9 +12,12 @@ pub mod embedded;
 pub mod file;
 pub mod gated;
 pub mod memory;
+pub mod pak;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
+#[cfg(feature = "http")]
+pub mod web;
 
 mod source;
 
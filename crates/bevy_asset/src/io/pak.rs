@@ -0,0 +1,543 @@
+//! Bundling assets in a single `.pak` archive.
+//!
+//! Shipping the assets of a game as loose files exposes them to the players, and makes
+//! installations with thousands of small files slow to copy. A [`PakWriter`] packs the assets and
+//! their meta files, usually the output of the [`AssetProcessor`](crate::processor::AssetProcessor),
+//! into one archive, which a [`PakAssetReader`] reads as an [`AssetSource`](super::AssetSource).
+//!
+//! An archive starts with an index of its entries, each with the range of its bytes, its
+//! compression and the [`blake3`] hash of its uncompressed bytes, followed by the bytes of the
+//! entries. The entries can be compressed with [zstd](PakCompression::Zstd) when the `zstd`
+//! feature is enabled.
+
+use crate::io::{AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+use bevy_utils::{BoxedFuture, HashMap};
+use futures_lite::{AsyncReadExt, StreamExt};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"BPAK";
+const VERSION: u32 = 1;
+
+/// The compression of the entries of a `.pak` archive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PakCompression {
+    /// The entries are stored as is.
+    #[default]
+    None,
+    /// The entries are compressed with zstd at the given level, from 1 to 22.
+    ///
+    /// Writing and reading compressed entries requires the `zstd` feature.
+    Zstd(i32),
+}
+
+impl PakCompression {
+    fn tag(self) -> u8 {
+        match self {
+            PakCompression::None => 0,
+            PakCompression::Zstd(_) => 1,
+        }
+    }
+
+    fn compress(self, bytes: &[u8]) -> Result<Vec<u8>, PakError> {
+        match self {
+            PakCompression::None => Ok(bytes.to_vec()),
+            #[cfg(feature = "zstd")]
+            PakCompression::Zstd(level) => Ok(zstd::bulk::compress(bytes, level)?),
+            #[cfg(not(feature = "zstd"))]
+            PakCompression::Zstd(_) => Err(PakError::UnsupportedCompression(self.tag())),
+        }
+    }
+}
+
+/// An error that occurred while reading or writing a `.pak` archive.
+#[derive(Error, Debug)]
+pub enum PakError {
+    /// Encountered an I/O error.
+    #[error("encountered an io error while accessing a pak archive: {0}")]
+    Io(#[from] std::io::Error),
+    /// The bytes don't start with the header of a `.pak` archive.
+    #[error("the bytes are not a pak archive")]
+    InvalidMagic,
+    /// The archive was written by an unsupported version of the format.
+    #[error("the pak archive has version {0}, but only version {VERSION} is supported")]
+    UnsupportedVersion(u32),
+    /// The index of the archive is truncated or references bytes outside of the archive.
+    #[error("the pak archive is corrupted")]
+    Corrupted,
+    /// The entry is compressed in a way this build can't decompress.
+    #[error("the compression {0} of the pak archive is not supported, enable the `zstd` feature")]
+    UnsupportedCompression(u8),
+    /// The bytes of the entry don't match the hash stored in the index.
+    #[error("the content of {0:?} doesn't match its hash in the pak archive")]
+    HashMismatch(PathBuf),
+}
+
+impl From<PakError> for AssetReaderError {
+    fn from(error: PakError) -> Self {
+        match error {
+            PakError::Io(error) => AssetReaderError::Io(error),
+            error => {
+                AssetReaderError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+            }
+        }
+    }
+}
+
+/// The location of the bytes of an asset or a meta file in a `.pak` archive.
+#[derive(Clone, Debug)]
+struct PakSection {
+    offset: u64,
+    stored_len: u64,
+    compression: u8,
+    hash: [u8; 32],
+}
+
+#[derive(Clone, Debug)]
+struct PakEntry {
+    asset: PakSection,
+    meta: Option<PakSection>,
+}
+
+/// An [`AssetReader`] reading the assets bundled in a `.pak` archive written by a [`PakWriter`].
+///
+/// The archive is kept in memory, and its entries are decompressed when they are read. Unless
+/// [disabled](Self::with_verification), the bytes of each entry are checked against the hash
+/// stored in the index, to detect corrupted installations.
+///
+/// ```
+/// # use bevy_asset::io::{pak::{PakAssetReader, PakWriter}, AssetReader, AssetSource};
+/// # use std::path::Path;
+/// let mut pak = PakWriter::new();
+/// pak.add_asset("textures/player.png", b"png bytes".to_vec());
+/// let reader = PakAssetReader::from_bytes(pak.to_bytes().unwrap()).unwrap();
+///
+/// let source = AssetSource::build().with_reader(move || Box::new(reader.clone()));
+/// ```
+#[derive(Clone)]
+pub struct PakAssetReader {
+    data: Arc<[u8]>,
+    data_start: usize,
+    entries: Arc<HashMap<PathBuf, PakEntry>>,
+    directories: Arc<HashMap<PathBuf, Vec<PathBuf>>>,
+    verify: bool,
+}
+
+impl PakAssetReader {
+    /// Reads the archive stored in the `bytes`.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Result<Self, PakError> {
+        let data: Arc<[u8]> = bytes.into();
+        let mut cursor = Cursor {
+            bytes: &data,
+            position: 0,
+        };
+        if cursor.take(MAGIC.len())? != MAGIC {
+            return Err(PakError::InvalidMagic);
+        }
+        let version = cursor.u32()?;
+        if version != VERSION {
+            return Err(PakError::UnsupportedVersion(version));
+        }
+
+        let entry_count = cursor.u32()?;
+        let mut entries = HashMap::default();
+        let mut directories = HashMap::<PathBuf, Vec<PathBuf>>::default();
+        for _ in 0..entry_count {
+            let path_len = cursor.u32()? as usize;
+            let path =
+                std::str::from_utf8(cursor.take(path_len)?).map_err(|_| PakError::Corrupted)?;
+            let path = PathBuf::from(path);
+            let asset = cursor.section()?;
+            let meta = match cursor.take(1)?[0] {
+                0 => None,
+                _ => Some(cursor.section()?),
+            };
+
+            // Registers the path, and its ancestors which weren't registered yet, in their
+            // parent directory.
+            let mut child = path.clone();
+            while let Some(parent) = child.parent() {
+                let children = directories.entry(parent.to_path_buf()).or_default();
+                let is_new_directory = children.is_empty();
+                if !children.contains(&child) {
+                    children.push(child.clone());
+                }
+                if !is_new_directory {
+                    break;
+                }
+                child = parent.to_path_buf();
+            }
+            entries.insert(path, PakEntry { asset, meta });
+        }
+
+        let reader = Self {
+            data_start: cursor.position,
+            data,
+            entries: Arc::new(entries),
+            directories: Arc::new(directories),
+            verify: true,
+        };
+        for entry in reader.entries.values() {
+            for section in std::iter::once(&entry.asset).chain(&entry.meta) {
+                reader.stored_bytes(section)?;
+            }
+        }
+        Ok(reader)
+    }
+
+    /// Reads the archive stored in the file at `path`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PakError> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Sets whether the bytes of the entries are checked against their hash when they are read.
+    pub fn with_verification(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Returns the paths of the assets in the archive, in no particular order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    fn stored_bytes(&self, section: &PakSection) -> Result<&[u8], PakError> {
+        let start = usize::try_from(section.offset)
+            .ok()
+            .and_then(|offset| offset.checked_add(self.data_start))
+            .ok_or(PakError::Corrupted)?;
+        let end = usize::try_from(section.stored_len)
+            .ok()
+            .and_then(|len| start.checked_add(len))
+            .ok_or(PakError::Corrupted)?;
+        self.data.get(start..end).ok_or(PakError::Corrupted)
+    }
+
+    fn read_section(&self, path: &Path, section: &PakSection) -> Result<Vec<u8>, PakError> {
+        let stored = self.stored_bytes(section)?;
+        let bytes = match section.compression {
+            0 => stored.to_vec(),
+            #[cfg(feature = "zstd")]
+            1 => zstd::stream::decode_all(stored)?,
+            compression => return Err(PakError::UnsupportedCompression(compression)),
+        };
+        if self.verify && *blake3::hash(&bytes).as_bytes() != section.hash {
+            return Err(PakError::HashMismatch(path.to_path_buf()));
+        }
+        Ok(bytes)
+    }
+}
+
+impl AssetReader for PakAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move {
+            let entry = self
+                .entries
+                .get(path)
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+            let bytes = self.read_section(path, &entry.asset)?;
+            let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+            Ok(reader)
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move {
+            let meta = self
+                .entries
+                .get(path)
+                .and_then(|entry| entry.meta.as_ref())
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+            let bytes = self.read_section(path, meta)?;
+            let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+            Ok(reader)
+        })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        Box::pin(async move {
+            let children = self
+                .directories
+                .get(path)
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+            let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children.clone()));
+            Ok(stream)
+        })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move { Ok(self.directories.contains_key(path)) })
+    }
+}
+
+/// A little-endian reader of the index of an archive.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PakError> {
+        let end = self.position.checked_add(len).ok_or(PakError::Corrupted)?;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(PakError::Corrupted)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, PakError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, PakError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn section(&mut self) -> Result<PakSection, PakError> {
+        Ok(PakSection {
+            offset: self.u64()?,
+            stored_len: self.u64()?,
+            compression: self.take(1)?[0],
+            hash: self.take(32)?.try_into().unwrap(),
+        })
+    }
+}
+
+/// Packs assets and their meta files into a `.pak` archive, read by a [`PakAssetReader`].
+///
+/// The assets are usually added from the processed [`AssetReader`] of a source with
+/// [`PakWriter::add_reader`], or [`AssetProcessor::write_pak`](crate::processor::AssetProcessor::write_pak)
+/// once the processor is finished.
+#[derive(Default)]
+pub struct PakWriter {
+    compression: PakCompression,
+    assets: HashMap<PathBuf, (Vec<u8>, Option<Vec<u8>>)>,
+}
+
+impl PakWriter {
+    /// Creates an empty archive, storing the entries without compression.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression of the entries.
+    pub fn with_compression(mut self, compression: PakCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Adds the asset `bytes` at the `path`, replacing the asset already at the path.
+    pub fn add_asset(&mut self, path: impl Into<PathBuf>, bytes: Vec<u8>) -> &mut Self {
+        let entry = self.assets.entry(path.into()).or_default();
+        entry.0 = bytes;
+        self
+    }
+
+    /// Adds the meta `bytes` of the asset at the `path`.
+    ///
+    /// This _should not_ include storage specific extensions like `.meta`.
+    pub fn add_meta(&mut self, path: impl Into<PathBuf>, bytes: Vec<u8>) -> &mut Self {
+        let entry = self.assets.entry(path.into()).or_default();
+        entry.1 = Some(bytes);
+        self
+    }
+
+    /// Returns the number of assets in the archive.
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    /// Returns `true` if the archive contains no assets.
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Adds all the assets, with their meta files, in the directory at `path` of the `reader` and
+    /// in its subdirectories.
+    pub async fn add_reader(
+        &mut self,
+        reader: &dyn AssetReader,
+        path: &Path,
+    ) -> Result<(), AssetReaderError> {
+        let mut directories = vec![path.to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            let mut paths = reader.read_directory(&directory).await?;
+            while let Some(path) = paths.next().await {
+                if reader.is_directory(&path).await? {
+                    directories.push(path);
+                    continue;
+                }
+                let mut bytes = Vec::new();
+                reader.read(&path).await?.read_to_end(&mut bytes).await?;
+                match reader.read_meta_bytes(&path).await {
+                    Ok(meta) => {
+                        self.add_meta(path.clone(), meta);
+                    }
+                    Err(AssetReaderError::NotFound(_)) => {}
+                    Err(error) => return Err(error),
+                }
+                self.add_asset(path, bytes);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the archive to the `writer`.
+    pub fn write(&self, mut writer: impl Write) -> Result<(), PakError> {
+        // Sorted so that the same assets always produce the same archive.
+        let mut assets: Vec<_> = self.assets.iter().collect();
+        assets.sort_by_key(|(path, _)| *path);
+
+        let mut index = Vec::new();
+        let mut data = Vec::new();
+        index.extend_from_slice(MAGIC);
+        index.extend_from_slice(&VERSION.to_le_bytes());
+        index.extend_from_slice(&(assets.len() as u32).to_le_bytes());
+        for (path, (bytes, meta)) in assets {
+            // Forward slashes keep the archives portable across platforms.
+            let path = path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            index.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            index.extend_from_slice(path.as_bytes());
+            self.write_section(&mut index, &mut data, bytes)?;
+            match meta {
+                Some(meta) => {
+                    index.push(1);
+                    self.write_section(&mut index, &mut data, meta)?;
+                }
+                None => index.push(0),
+            }
+        }
+
+        writer.write_all(&index)?;
+        writer.write_all(&data)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the bytes of the archive.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PakError> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn write_section(
+        &self,
+        index: &mut Vec<u8>,
+        data: &mut Vec<u8>,
+        bytes: &[u8],
+    ) -> Result<(), PakError> {
+        let stored = self.compression.compress(bytes)?;
+        index.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        index.extend_from_slice(&(stored.len() as u64).to_le_bytes());
+        index.push(self.compression.tag());
+        index.extend_from_slice(blake3::hash(bytes).as_bytes());
+        data.extend_from_slice(&stored);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+
+    fn read_to_string(reader: &PakAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        bevy_tasks::block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn pack_and_read() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("a.txt"), "a");
+        dir.insert_asset_text(Path::new("x/y/b.txt"), "b");
+        dir.insert_meta_text(Path::new("x/y/b.txt"), "b meta");
+        dir.insert_asset_text(Path::new("x/c.txt"), "c");
+        let source = MemoryAssetReader { root: dir };
+
+        let mut pak = PakWriter::new();
+        bevy_tasks::block_on(pak.add_reader(&source, Path::new(""))).unwrap();
+        assert_eq!(pak.len(), 3);
+        let bytes = pak.to_bytes().unwrap();
+        let reader = PakAssetReader::from_bytes(bytes.clone()).unwrap();
+
+        assert_eq!(read_to_string(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read_to_string(&reader, "x/y/b.txt").unwrap(), "b");
+        let meta = bevy_tasks::block_on(reader.read_meta_bytes(Path::new("x/y/b.txt"))).unwrap();
+        assert_eq!(meta, b"b meta");
+        assert!(matches!(
+            bevy_tasks::block_on(reader.read_meta_bytes(Path::new("a.txt"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        assert!(matches!(
+            read_to_string(&reader, "d.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        bevy_tasks::block_on(async {
+            assert!(reader.is_directory(Path::new("x/y")).await.unwrap());
+            assert!(!reader.is_directory(Path::new("a.txt")).await.unwrap());
+            let mut paths: Vec<_> = reader
+                .read_directory(Path::new("x"))
+                .await
+                .unwrap()
+                .collect()
+                .await;
+            paths.sort();
+            assert_eq!(paths, [PathBuf::from("x/c.txt"), PathBuf::from("x/y")]);
+        });
+
+        // Corrupting the content of an entry is detected by its hash.
+        let mut corrupted = bytes;
+        *corrupted.last_mut().unwrap() ^= 1;
+        let reader = PakAssetReader::from_bytes(corrupted).unwrap();
+        let b_meta = Path::new("x/y/b.txt");
+        assert!(bevy_tasks::block_on(reader.read_meta_bytes(b_meta)).is_err());
+        let reader = reader.with_verification(false);
+        let meta = bevy_tasks::block_on(reader.read_meta_bytes(b_meta)).unwrap();
+        assert_eq!(meta, b"b met`");
+        assert_eq!(read_to_string(&reader, "a.txt").unwrap(), "a");
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_compression() {
+        let mut pak = PakWriter::new().with_compression(PakCompression::Zstd(3));
+        pak.add_asset("a.txt", "a".repeat(1000).into_bytes());
+        let bytes = pak.to_bytes().unwrap();
+        assert!(bytes.len() < 1000);
+        let reader = PakAssetReader::from_bytes(bytes).unwrap();
+        assert_eq!(read_to_string(&reader, "a.txt").unwrap(), "a".repeat(1000));
+    }
+}
@@ -0,0 +1,514 @@
+//! Loading assets over HTTP(S), for example from a CDN.
+//!
+//! The [`WebAssetPlugin`] registers the `http` and `https` [`AssetSource`]s, so that assets are
+//! loaded from URLs through the usual [`AssetServer`](crate::AssetServer) API:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_asset::{io::web::WebAssetPlugin, AssetPlugin, AssetServer, Handle, LoadedUntypedAsset};
+//! let mut app = App::new();
+//! app.add_plugins((WebAssetPlugin::default(), AssetPlugin::default()));
+//!
+//! let asset_server = app.world.resource::<AssetServer>();
+//! let handle: Handle<LoadedUntypedAsset> =
+//!     asset_server.load_untyped("https://example.com/textures/player.png");
+//! ```
+//!
+//! On native platforms, the responses are cached on disk, and revalidated with their `ETag` and
+//! `Last-Modified` headers, so that unchanged assets aren't downloaded again and are still
+//! available offline. Failed requests are retried with an exponential backoff. On the web, the
+//! requests are made with `fetch`, and cached by the browser.
+//!
+//! Web servers usually don't serve meta files next to the assets, so each load costs a failed
+//! request for the meta file unless [`AssetMetaCheck::Never`](crate::AssetMetaCheck::Never) is
+//! used.
+
+use crate::io::{AssetSource, AssetSourceBuilder, AssetSourceBuilders};
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::{Res, Resource},
+};
+use crossbeam_channel::{Receiver, Sender};
+use std::{path::PathBuf, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::io::{get_meta_path, AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+#[cfg(not(target_arch = "wasm32"))]
+use bevy_utils::BoxedFuture;
+#[cfg(not(target_arch = "wasm32"))]
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{io::Read, path::Path};
+
+/// Registers the `http` and `https` [`AssetSource`]s, reading assets from URLs.
+///
+/// This must be added before the [`AssetPlugin`](crate::AssetPlugin).
+#[derive(Clone, Debug)]
+pub struct WebAssetPlugin {
+    /// The directory where the downloaded assets are cached, or `None` to disable the cache.
+    ///
+    /// This is ignored on the web, where the browser caches the responses.
+    pub cache_dir: Option<PathBuf>,
+    /// The number of times a request is retried after a network error, or an error status
+    /// returned by an overloaded server.
+    pub retries: u32,
+    /// The delay before the first retry of a request, which doubles for each of the next
+    /// retries.
+    pub backoff: Duration,
+    /// The maximum duration of a request.
+    pub timeout: Duration,
+}
+
+impl Default for WebAssetPlugin {
+    fn default() -> Self {
+        Self {
+            cache_dir: Some(PathBuf::from(Self::DEFAULT_CACHE_DIR)),
+            retries: 3,
+            backoff: Duration::from_millis(250),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl WebAssetPlugin {
+    const DEFAULT_CACHE_DIR: &'static str = "web_asset_cache";
+}
+
+impl Plugin for WebAssetPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        {
+            let mut sources = app
+                .world
+                .get_resource_or_insert_with::<AssetSourceBuilders>(Default::default);
+            for scheme in ["http", "https"] {
+                sources.insert(scheme, self.source(scheme, sender.clone()));
+            }
+        }
+        app.add_event::<WebAssetEvent>()
+            .insert_resource(WebAssetEventReceiver(receiver))
+            .add_systems(First, send_web_asset_events);
+    }
+}
+
+impl WebAssetPlugin {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn source(&self, scheme: &'static str, events: Sender<WebAssetEvent>) -> AssetSourceBuilder {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let reader = WebAssetReader {
+            scheme,
+            agent,
+            cache_dir: self.cache_dir.as_ref().map(|dir| dir.join(scheme)),
+            retries: self.retries,
+            backoff: self.backoff,
+            events,
+        };
+        AssetSource::build().with_reader(move || Box::new(reader.clone()))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn source(&self, scheme: &'static str, _events: Sender<WebAssetEvent>) -> AssetSourceBuilder {
+        AssetSource::build().with_reader(move || {
+            Box::new(crate::io::wasm::HttpWasmAssetReader::new(format!(
+                "{scheme}://"
+            )))
+        })
+    }
+}
+
+/// An event describing the progress of the download of an asset by the `http` and `https`
+/// [`AssetSource`]s of the [`WebAssetPlugin`].
+///
+/// These events are only sent on native platforms.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub enum WebAssetEvent {
+    /// A part of the response was downloaded.
+    Progress {
+        /// The URL of the asset.
+        url: String,
+        /// The number of bytes downloaded so far.
+        downloaded: u64,
+        /// The size of the response, if the server sent it.
+        total: Option<u64>,
+    },
+    /// The response was fully downloaded.
+    Downloaded {
+        /// The URL of the asset.
+        url: String,
+        /// The size of the response.
+        bytes: u64,
+    },
+    /// The asset was read from the cache, because it didn't change since it was cached, or the
+    /// server couldn't be reached.
+    Cached {
+        /// The URL of the asset.
+        url: String,
+    },
+    /// A request failed and will be retried.
+    Retrying {
+        /// The URL of the asset.
+        url: String,
+        /// The number of the retry, starting at 1.
+        attempt: u32,
+        /// The error of the failed request.
+        error: String,
+    },
+    /// The asset couldn't be downloaded.
+    Failed {
+        /// The URL of the asset.
+        url: String,
+        /// The error of the last request.
+        error: String,
+    },
+}
+
+#[derive(Resource)]
+struct WebAssetEventReceiver(Receiver<WebAssetEvent>);
+
+/// Sends the [`WebAssetEvent`]s of the downloads since the last run.
+fn send_web_asset_events(
+    receiver: Res<WebAssetEventReceiver>,
+    mut events: EventWriter<WebAssetEvent>,
+) {
+    events.send_batch(receiver.0.try_iter());
+}
+
+/// The validators of a cached response, stored next to its body.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct CachedResponse {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_cache_files(
+    body_path: &Path,
+    body: &[u8],
+    response_path: &Path,
+    response: &CachedResponse,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(body_path.parent().unwrap())?;
+    std::fs::write(body_path, body)?;
+    let response = ron::to_string(response)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, error))?;
+    std::fs::write(response_path, response)
+}
+
+/// An [`AssetReader`] downloading assets with HTTP(S) requests, registered by the
+/// [`WebAssetPlugin`].
+///
+/// The paths read are URLs without their scheme, like `example.com/textures/player.png`.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub struct WebAssetReader {
+    scheme: &'static str,
+    agent: ureq::Agent,
+    cache_dir: Option<PathBuf>,
+    retries: u32,
+    backoff: Duration,
+    events: Sender<WebAssetEvent>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl WebAssetReader {
+    fn url(&self, path: &Path) -> String {
+        let path = path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("{}://{path}", self.scheme)
+    }
+
+    fn cache_paths(&self, url: &str) -> Option<(PathBuf, PathBuf)> {
+        let dir = self.cache_dir.as_ref()?;
+        let name = blake3::hash(url.as_bytes()).to_hex();
+        Some((
+            dir.join(format!("{name}.bin")),
+            dir.join(format!("{name}.ron")),
+        ))
+    }
+
+    fn read_cache(&self, url: &str) -> Option<(CachedResponse, PathBuf)> {
+        let (body_path, response_path) = self.cache_paths(url)?;
+        let response = std::fs::read_to_string(response_path).ok()?;
+        let response: CachedResponse = ron::from_str(&response).ok()?;
+        (response.url == url && body_path.is_file()).then_some((response, body_path))
+    }
+
+    fn write_cache(&self, response: &CachedResponse, body: &[u8]) {
+        let Some((body_path, response_path)) = self.cache_paths(&response.url) else {
+            return;
+        };
+        // Responses which can't be revalidated are still cached, for offline use.
+        if let Err(error) = write_cache_files(&body_path, body, &response_path, response) {
+            bevy_log::warn!("Failed to cache {}: {error}", response.url);
+        }
+    }
+
+    fn send(&self, event: WebAssetEvent) {
+        // The receiver is dropped with the app, while loads may still be running.
+        let _ = self.events.send(event);
+    }
+
+    /// Downloads the body at the `url`, blocking the thread.
+    fn fetch_blocking(&self, url: String, path: PathBuf) -> Result<Vec<u8>, AssetReaderError> {
+        let cached = self.read_cache(&url);
+        let mut attempt = 0;
+        let error = loop {
+            let mut request = self.agent.get(&url);
+            if let Some((response, _)) = &cached {
+                if let Some(etag) = &response.etag {
+                    request = request.set("If-None-Match", etag);
+                }
+                if let Some(last_modified) = &response.last_modified {
+                    request = request.set("If-Modified-Since", last_modified);
+                }
+            }
+
+            let retryable_error = match request.call() {
+                Ok(response) if response.status() == 304 && cached.is_some() => {
+                    let (_, body_path) = cached.unwrap();
+                    self.send(WebAssetEvent::Cached { url });
+                    return Ok(std::fs::read(body_path)?);
+                }
+                Ok(response) => {
+                    let validators = CachedResponse {
+                        url: url.clone(),
+                        etag: response.header("ETag").map(ToOwned::to_owned),
+                        last_modified: response.header("Last-Modified").map(ToOwned::to_owned),
+                    };
+                    match self.read_body(&url, response) {
+                        Ok(body) => {
+                            self.write_cache(&validators, &body);
+                            return Ok(body);
+                        }
+                        Err(error) => error.to_string(),
+                    }
+                }
+                Err(ureq::Error::Status(404 | 410, _)) => {
+                    return Err(AssetReaderError::NotFound(path));
+                }
+                Err(ureq::Error::Status(status, _)) if status != 429 && status < 500 => {
+                    let error = format!("Encountered unexpected HTTP status {status}");
+                    self.send(WebAssetEvent::Failed {
+                        url,
+                        error: error.clone(),
+                    });
+                    return Err(AssetReaderError::Io(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        error,
+                    )));
+                }
+                Err(error) => error.to_string(),
+            };
+
+            if attempt == self.retries {
+                break retryable_error;
+            }
+            attempt += 1;
+            self.send(WebAssetEvent::Retrying {
+                url: url.clone(),
+                attempt,
+                error: retryable_error,
+            });
+            std::thread::sleep(
+                self.backoff
+                    .saturating_mul(2u32.saturating_pow(attempt - 1)),
+            );
+        };
+
+        // The server can't be reached, so the cached asset is better than nothing.
+        if let Some((_, body_path)) = cached {
+            if let Ok(body) = std::fs::read(body_path) {
+                self.send(WebAssetEvent::Cached { url });
+                return Ok(body);
+            }
+        }
+        self.send(WebAssetEvent::Failed {
+            url,
+            error: error.clone(),
+        });
+        Err(AssetReaderError::Io(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            error,
+        )))
+    }
+
+    fn read_body(&self, url: &str, response: ureq::Response) -> std::io::Result<Vec<u8>> {
+        let total = response
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok());
+        let mut body = Vec::with_capacity(total.unwrap_or(0).min(1 << 26) as usize);
+        let mut reader = response.into_reader();
+        // Read in chunks rather than with `read_to_end`, to report the progress.
+        let mut chunk = vec![0; 1 << 16];
+        loop {
+            let read = reader.read(&mut chunk)?;
+            if read == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..read]);
+            self.send(WebAssetEvent::Progress {
+                url: url.to_owned(),
+                downloaded: body.len() as u64,
+                total,
+            });
+        }
+        self.send(WebAssetEvent::Downloaded {
+            url: url.to_owned(),
+            bytes: body.len() as u64,
+        });
+        Ok(body)
+    }
+
+    async fn fetch<'a>(&self, path: PathBuf) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let url = self.url(&path);
+        let reader = self.clone();
+        let body = blocking::unblock(move || {
+            let body = reader.fetch_blocking(url.clone(), path)?;
+            Ok::<_, AssetReaderError>(body)
+        })
+        .await?;
+        let reader: Box<Reader> = Box::new(VecReader::new(body));
+        Ok(reader)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AssetReader for WebAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(self.fetch(path.to_path_buf()))
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(self.fetch(get_meta_path(path)))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        // HTTP has no way to list the content of a directory.
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_path_buf())) })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move { Ok(false) })
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use futures_lite::AsyncReadExt;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    /// Serves the same body with an `ETag`, answering the requests revalidating it with a
+    /// `304 Not Modified`, until `requests` requests were served.
+    fn serve(requests: usize) -> (u16, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let mut request_lines = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut revalidated = false;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    revalidated |= header.to_lowercase() == "if-none-match: \"v1\"\r\n";
+                }
+                let response = if request_line.contains(".meta") {
+                    "HTTP/1.1 404 Not Found\r\nConnection: close\r\nContent-Length: 0\r\n\r\n"
+                } else if revalidated {
+                    "HTTP/1.1 304 Not Modified\r\nConnection: close\r\nETag: \"v1\"\r\n\r\n"
+                } else {
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nETag: \"v1\"\r\nContent-Length: 5\r\n\r\nhello"
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+                request_lines.push(request_line.trim().to_string());
+            }
+            request_lines
+        });
+        (port, server)
+    }
+
+    #[test]
+    fn web_asset_cache() {
+        let (port, server) = serve(3);
+        let cache_dir = std::env::temp_dir().join(format!("bevy_web_asset_cache_{port}"));
+        let (events, receiver) = crossbeam_channel::unbounded();
+        let reader = WebAssetReader {
+            scheme: "http",
+            agent: ureq::agent(),
+            cache_dir: Some(cache_dir.clone()),
+            retries: 0,
+            backoff: Duration::ZERO,
+            events,
+        };
+        let path = PathBuf::from(format!("127.0.0.1:{port}/a.txt"));
+        let read = |path| {
+            bevy_tasks::block_on(async {
+                let mut bytes = Vec::new();
+                reader.read(path).await?.read_to_end(&mut bytes).await?;
+                Ok::<_, AssetReaderError>(String::from_utf8(bytes).unwrap())
+            })
+        };
+
+        assert_eq!(read(&path).unwrap(), "hello");
+        assert!(matches!(
+            bevy_tasks::block_on(reader.read_meta_bytes(&path)),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        // The second read is revalidated, and served from the cache.
+        assert_eq!(read(&path).unwrap(), "hello");
+        let url = format!("http://{}", path.display());
+        let events: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(
+            events,
+            [
+                WebAssetEvent::Progress {
+                    url: url.clone(),
+                    downloaded: 5,
+                    total: Some(5),
+                },
+                WebAssetEvent::Downloaded {
+                    url: url.clone(),
+                    bytes: 5,
+                },
+                WebAssetEvent::Cached { url: url.clone() },
+            ]
+        );
+        assert_eq!(server.join().unwrap().len(), 3);
+
+        // The server is gone, but the asset is still in the cache.
+        assert_eq!(read(&path).unwrap(), "hello");
+        assert_eq!(
+            receiver.try_iter().last(),
+            Some(WebAssetEvent::Cached { url })
+        );
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+pub mod diagnostic;
 pub mod io;
 pub mod meta;
 pub mod processor;
@@ -12,26 +13,34 @@ pub mod prelude {
 }
 
 mod assets;
+mod budget;
 mod event;
 mod folder;
+mod generator;
 mod handle;
 mod id;
 mod loader;
 mod path;
 mod reflect;
+mod reload;
 mod server;
+mod tracker;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
+pub use budget::*;
 pub use event::*;
 pub use folder::*;
 pub use futures_lite::{AsyncReadExt, AsyncWriteExt};
+pub use generator::*;
 pub use handle::*;
 pub use id::*;
 pub use loader::*;
 pub use path::*;
 pub use reflect::*;
+pub use reload::*;
 pub use server::*;
+pub use tracker::*;
 
 pub use bevy_utils::BoxedFuture;
 
@@ -39,14 +48,18 @@ pub use bevy_utils::BoxedFuture;
 pub use ron;
 
 use crate::{
-    io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
+    generator::ProceduralAssetReader,
+    io::{
+        embedded::EmbeddedAssetRegistry, AssetSource, AssetSourceBuilder, AssetSourceBuilders,
+        AssetSourceId,
+    },
     processor::{AssetProcessor, Process},
 };
 use bevy_app::{App, First, MainScheduleOrder, Plugin, PostUpdate};
 use bevy_ecs::{
     reflect::AppTypeRegistry,
     schedule::{IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel, SystemSet},
-    system::Resource,
+    system::{IntoSystem, Resource},
     world::FromWorld,
 };
 use bevy_log::error;
@@ -154,6 +167,12 @@ impl Plugin for AssetPlugin {
                     .then_some(self.processed_file_path.as_str()),
             );
             embedded.register_source(&mut sources);
+            sources.insert(
+                PROCEDURAL,
+                AssetSource::build()
+                    .with_reader(|| Box::new(ProceduralAssetReader))
+                    .with_processed_reader(|| Box::new(ProceduralAssetReader)),
+            );
         }
         {
             let mut watch = cfg!(feature = "watch");
@@ -217,7 +236,13 @@ impl Plugin for AssetPlugin {
                 UpdateAssets,
                 TrackAssets.after(handle_internal_asset_events),
             )
-            .add_systems(UpdateAssets, handle_internal_asset_events);
+            .add_event::<LoadingFinished>()
+            .add_event::<AssetSourceChanged>()
+            .add_systems(UpdateAssets, handle_internal_asset_events)
+            .add_systems(
+                UpdateAssets,
+                update_loading_trackers.after(handle_internal_asset_events),
+            );
 
         let mut order = app.world.resource_mut::<MainScheduleOrder>();
         order.insert_after(First, UpdateAssets);
@@ -279,6 +304,13 @@ impl VisitAssetDependencies for Vec<UntypedHandle> {
 pub trait AssetApp {
     /// Registers the given `loader` in the [`App`]'s [`AssetServer`].
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
+    /// Registers the given `generator` under `name` in the [`App`]'s [`AssetServer`]. See
+    /// [`AssetGenerator`] for more info.
+    fn register_asset_generator<G: AssetGenerator>(
+        &mut self,
+        name: &str,
+        generator: G,
+    ) -> &mut Self;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
@@ -312,6 +344,15 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Limits the memory used by the loaded assets of type `A` to `max_bytes`, by evicting the least
+    /// recently used ones. See [`AssetMemoryBudget`] for more info.
+    fn set_asset_memory_budget<A: AssetMemoryUsage>(&mut self, max_bytes: usize) -> &mut Self;
+    /// Adds a `system` which runs with the [`AssetId`] of every asset of type `A` which is
+    /// modified, for example when it is hot reloaded. See [`AssetReloadSystems`] for more info.
+    fn add_asset_reload_system<A: Asset, M>(
+        &mut self,
+        system: impl IntoSystem<AssetId<A>, (), M> + 'static,
+    ) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -320,6 +361,17 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_generator<G: AssetGenerator>(
+        &mut self,
+        name: &str,
+        generator: G,
+    ) -> &mut Self {
+        self.world
+            .resource::<AssetServer>()
+            .register_generator(name, generator);
+        self
+    }
+
     fn init_asset_loader<L: AssetLoader + FromWorld>(&mut self) -> &mut Self {
         let loader = L::from_world(&mut self.world);
         self.register_asset_loader(loader)
@@ -373,6 +425,35 @@ impl AssetApp for App {
         self
     }
 
+    fn set_asset_memory_budget<A: AssetMemoryUsage>(&mut self, max_bytes: usize) -> &mut Self {
+        if let Some(mut budget) = self.world.get_resource_mut::<AssetMemoryBudget<A>>() {
+            budget.set_max_bytes(max_bytes);
+            return self;
+        }
+        self.insert_resource(AssetMemoryBudget::<A>::new(max_bytes))
+            .add_systems(
+                UpdateAssets,
+                apply_asset_memory_budget::<A>.after(TrackAssets),
+            )
+    }
+
+    fn add_asset_reload_system<A: Asset, M>(
+        &mut self,
+        system: impl IntoSystem<AssetId<A>, (), M> + 'static,
+    ) -> &mut Self {
+        let system = self.world.register_system(system);
+        if let Some(mut reload_systems) = self.world.get_resource_mut::<AssetReloadSystems<A>>() {
+            reload_systems.add(system);
+            return self;
+        }
+        let mut reload_systems = AssetReloadSystems::<A>::default();
+        reload_systems.add(system);
+        self.insert_resource(reload_systems).add_systems(
+            AssetEvents,
+            run_asset_reload_systems::<A>.after(Assets::<A>::asset_events),
+        )
+    }
+
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self {
         if let Some(asset_processor) = self.world.get_resource::<AssetProcessor>() {
             asset_processor.register_processor(processor);
@@ -427,15 +508,18 @@ mod tests {
     use crate::{
         self as bevy_asset,
         folder::LoadedFolder,
+        generator::{AssetGenerator, GeneratorParamError, GeneratorParams},
         handle::Handle,
         io::{
             gated::{GateOpener, GatedReader},
             memory::{Dir, MemoryAssetReader},
-            AssetSource, AssetSourceId, Reader,
+            AssetSource, AssetSourceId, Reader, Writer,
         },
-        loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetPath, AssetPlugin, AssetServer, Assets,
-        DependencyLoadState, LoadState, RecursiveDependencyLoadState,
+        loader::{AssetLoader, AssetMigration, ErasedAssetLoader, LoadContext},
+        saver::{AssetSaver, SavedAsset},
+        Asset, AssetApp, AssetEvent, AssetId, AssetMemoryBudget, AssetMemoryUsage, AssetPath,
+        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState, LoadingFinished,
+        LoadingProgress, LoadingTracker, RecursiveDependencyLoadState, UntypedAssetId,
     };
     use bevy_app::{App, Update};
     use bevy_core::TaskPoolPlugin;
@@ -446,10 +530,10 @@ mod tests {
     };
     use bevy_log::LogPlugin;
     use bevy_reflect::TypePath;
-    use bevy_utils::BoxedFuture;
-    use futures_lite::AsyncReadExt;
+    use bevy_utils::{BoxedFuture, HashSet};
+    use futures_lite::{AsyncReadExt, AsyncWriteExt};
     use serde::{Deserialize, Serialize};
-    use std::path::Path;
+    use std::{path::Path, sync::Arc};
     use thiserror::Error;
 
     #[derive(Asset, TypePath, Debug)]
@@ -1196,6 +1280,560 @@ mod tests {
         });
     }
 
+    #[test]
+    fn loading_tracker() {
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let handle: Handle<CoolText> = app.world.resource::<AssetServer>().load(a_path);
+        let tracker = app.world.spawn(LoadingTracker::new().with(handle)).id();
+
+        app.update();
+        let progress = |world: &World| world.get::<LoadingTracker>(tracker).unwrap().progress();
+        assert_eq!(
+            progress(&app.world),
+            LoadingProgress {
+                loaded: 0,
+                failed: 0,
+                total: 1,
+                bytes_read: 0,
+            }
+        );
+
+        // The dependency of `a` is only known once `a` is loaded.
+        gate_opener.open(a_path);
+        run_app_until(&mut app, |world| {
+            (progress(world).loaded == 1).then_some(())
+        });
+        assert_eq!(progress(&app.world).total, 2);
+        assert_eq!(progress(&app.world).bytes_read, a_ron.len() as u64);
+        assert!(!app
+            .world
+            .get::<LoadingTracker>(tracker)
+            .unwrap()
+            .is_finished());
+
+        gate_opener.open(b_path);
+        let mut reader = ManualEventReader::<LoadingFinished>::default();
+        run_app_until(&mut app, |world| {
+            let events = world.resource::<Events<LoadingFinished>>();
+            reader.read(events).next().cloned().map(|event| {
+                assert_eq!(event.entity, tracker);
+                assert_eq!(event.progress.loaded, 2);
+                assert_eq!(
+                    event.progress.bytes_read,
+                    (a_ron.len() + b_ron.len()) as u64
+                );
+            })
+        });
+        assert!(app
+            .world
+            .get::<LoadingTracker>(tracker)
+            .unwrap()
+            .is_finished());
+    }
+
+    #[test]
+    fn reload_notifies_dependants() {
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = test_app(dir.clone());
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .init_resource::<StoredEvents>()
+            .register_asset_loader(CoolTextLoader)
+            .add_systems(Update, store_asset_events);
+
+        let a: Handle<CoolText> = app.world.resource::<AssetServer>().load(a_path);
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        gate_opener.open(c_path);
+        run_app_until(&mut app, |world| {
+            let server = world.resource::<AssetServer>();
+            server.is_loaded_with_dependencies(&a).then_some(())
+        });
+        let b_id = app
+            .world
+            .resource::<AssetServer>()
+            .get_handle::<CoolText>(b_path)
+            .unwrap()
+            .id();
+        app.world.resource_mut::<StoredEvents>().0.clear();
+
+        // Reloading `c` notifies the assets which depend on it, recursively.
+        dir.insert_asset_text(Path::new(c_path), &c_ron.replace("\"c\"", "\"new c\""));
+        app.world.resource::<AssetServer>().reload(c_path);
+        gate_opener.open(c_path);
+        run_app_until(&mut app, |world| {
+            let events = &world.resource::<StoredEvents>().0;
+            (events.contains(&AssetEvent::Modified { id: a.id() })).then_some(())
+        });
+        let events = std::mem::take(&mut app.world.resource_mut::<StoredEvents>().0);
+        assert!(events.contains(&AssetEvent::Modified { id: b_id }));
+        let c_id = app
+            .world
+            .resource::<AssetServer>()
+            .get_handle::<CoolText>(c_path)
+            .unwrap()
+            .id();
+        assert_eq!(get::<CoolText>(&app.world, c_id).unwrap().text, "new c");
+    }
+
+    #[test]
+    fn dependency_graph() {
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let a: Handle<CoolText> = app.world.resource::<AssetServer>().load(a_path);
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        gate_opener.open(c_path);
+        run_app_until(&mut app, |world| {
+            let server = world.resource::<AssetServer>();
+            server.is_loaded_with_dependencies(&a).then_some(())
+        });
+
+        let server = app.world.resource::<AssetServer>();
+        let id = |path| server.get_handle_untyped(path).unwrap().id();
+        let (a, b, c) = (a.id().untyped(), id(b_path), id(c_path));
+        let set = |ids: Vec<UntypedAssetId>| ids.into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(set(server.get_dependencies(a).unwrap()), set(vec![b, c]));
+        assert!(server.get_dependencies(c).unwrap().is_empty());
+        assert_eq!(set(server.get_dependants(c)), set(vec![a, b]));
+        assert!(server.get_dependants(a).is_empty());
+        assert_eq!(set(server.get_recursive_dependencies(a)), set(vec![b, c]));
+        assert_eq!(server.get_recursive_dependencies(b), vec![c]);
+    }
+
+    #[derive(Clone, Default)]
+    struct TextGenerator {
+        suffix: Arc<std::sync::Mutex<String>>,
+    }
+
+    impl AssetGenerator for TextGenerator {
+        type Asset = CoolText;
+        type Error = GeneratorParamError;
+
+        fn generate<'a>(
+            &'a self,
+            params: &'a GeneratorParams,
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<CoolText, GeneratorParamError>> {
+            Box::pin(async move {
+                let text: String = params.parse("text")?;
+                let count: usize = params.parse_or("count", 1)?;
+                Ok(CoolText {
+                    text: text.repeat(count) + &self.suffix.lock().unwrap(),
+                    embedded: String::new(),
+                    dependencies: params
+                        .get("dependency")
+                        .map(|path| load_context.load(path.to_string()))
+                        .into_iter()
+                        .collect(),
+                    sub_texts: Vec::new(),
+                })
+            })
+        }
+    }
+
+    #[test]
+    fn generate_procedural_asset() {
+        let dir = Dir::default();
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        let generator = TextGenerator::default();
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .register_asset_generator("text", generator.clone());
+
+        let server = app.world.resource::<AssetServer>().clone();
+        let generated: Handle<CoolText> =
+            server.load("proc://text?text=ab&count=3&dependency=c.cool.ron");
+        let invalid: Handle<CoolText> = server.load("proc://text?text=ab&count=many");
+        gate_opener.open(c_path);
+        run_app_until(&mut app, |world| {
+            let server = world.resource::<AssetServer>();
+            (server.is_loaded_with_dependencies(&generated)
+                && server.load_state(&invalid) == LoadState::Failed)
+                .then_some(())
+        });
+        assert_eq!(
+            get::<CoolText>(&app.world, generated.id()).unwrap().text,
+            "ababab"
+        );
+        let c_id = server.get_handle_untyped(c_path).unwrap().id();
+        assert_eq!(server.get_dependencies(&generated), Some(vec![c_id]));
+
+        // The same parameters point to the same asset.
+        let same: Handle<CoolText> =
+            server.load("proc://text?text=ab&count=3&dependency=c.cool.ron");
+        assert_eq!(same, generated);
+
+        // Changing the data read by the generator outside of the parameters requires a reload.
+        *generator.suffix.lock().unwrap() = "!".to_string();
+        server.reload_generated("text");
+        run_app_until(&mut app, |world| {
+            let text = &get::<CoolText>(world, generated.id())?.text;
+            (text == "ababab!").then_some(())
+        });
+    }
+
+    #[derive(Asset, TypePath, Debug)]
+    struct VersionedText(String);
+
+    #[derive(Default)]
+    struct VersionedTextLoader;
+
+    impl AssetLoader for VersionedTextLoader {
+        type Asset = VersionedText;
+        type Settings = ();
+        type Error = std::io::Error;
+        const FORMAT_VERSION: u32 = 2;
+
+        fn load<'a>(
+            &'a self,
+            reader: &'a mut Reader,
+            _settings: &'a Self::Settings,
+            _load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+            Box::pin(async move {
+                let mut text = String::new();
+                reader.read_to_string(&mut text).await?;
+                Ok(VersionedText(text))
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["versioned"]
+        }
+
+        fn migrate(&self, version: u32, migration: &mut AssetMigration) -> Result<(), Self::Error> {
+            match version {
+                0 => migration.bytes.make_ascii_uppercase(),
+                _ => migration.bytes.push(b'!'),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn migrate_asset_format() {
+        let dir = Dir::default();
+
+        let path = "text.versioned";
+        let meta = format!(
+            r#"(
+    meta_format_version: "1.0",
+    asset: Load(
+        loader: "{}",
+        settings: (),
+    ),
+)"#,
+            std::any::type_name::<VersionedTextLoader>()
+        );
+        dir.insert_asset_text(Path::new(path), "hello");
+        dir.insert_meta_text(Path::new(path), &meta);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<VersionedText>()
+            .register_asset_loader(VersionedTextLoader);
+        let handle: Handle<VersionedText> = app.world.resource::<AssetServer>().load(path);
+        gate_opener.open(path);
+        run_app_until(&mut app, |world| {
+            let text = get::<VersionedText>(world, handle.id())?;
+            assert_eq!(text.0, "HELLO!");
+            Some(())
+        });
+
+        // The migrated meta records the current format version.
+        let mut migration = AssetMigration {
+            bytes: b"hello".to_vec(),
+            meta: Some(meta.into_bytes()),
+        };
+        ErasedAssetLoader::migrate(&VersionedTextLoader, 1, &mut migration).unwrap();
+        assert_eq!(migration.bytes, b"hello!");
+        let meta = String::from_utf8(migration.meta.unwrap()).unwrap();
+        assert!(meta.contains("asset_format_version: 2"));
+    }
+
+    impl AssetMemoryUsage for CoolText {
+        fn memory_usage(&self) -> usize {
+            self.text.len()
+        }
+    }
+
+    #[test]
+    fn asset_memory_budget() {
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "aaaa",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "bbbb",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .set_asset_memory_budget::<CoolText>(6);
+
+        let a: Handle<CoolText> = app.world.resource::<AssetServer>().load(a_path);
+        gate_opener.open(a_path);
+        run_app_until(&mut app, |world| {
+            let budget = world.resource::<AssetMemoryBudget<CoolText>>();
+            (budget.used_bytes() == 4).then_some(())
+        });
+
+        // Loading `b` exceeds the budget, so the least recently used `a` is evicted.
+        let b: Handle<CoolText> = app.world.resource::<AssetServer>().load(b_path);
+        gate_opener.open(b_path);
+        run_app_until(&mut app, |world| {
+            let budget = world.resource::<AssetMemoryBudget<CoolText>>();
+            budget.is_evicted(&a).then_some(())
+        });
+        assert!(get::<CoolText>(&app.world, a.id()).is_none());
+        assert_eq!(get::<CoolText>(&app.world, b.id()).unwrap().text, "bbbb");
+        let budget = app.world.resource::<AssetMemoryBudget<CoolText>>();
+        assert_eq!(budget.used_bytes(), 4);
+        assert_eq!(budget.resident_len(), 1);
+
+        // Using `a` again reloads it from its path, and evicts `b` instead.
+        app.world
+            .resource_mut::<AssetMemoryBudget<CoolText>>()
+            .mark_used(&a);
+        gate_opener.open(a_path);
+        run_app_until(&mut app, |world| {
+            let budget = world.resource::<AssetMemoryBudget<CoolText>>();
+            budget.is_evicted(&b).then_some(())
+        });
+        assert_eq!(get::<CoolText>(&app.world, a.id()).unwrap().text, "aaaa");
+        assert!(get::<CoolText>(&app.world, b.id()).is_none());
+        assert!(!app
+            .world
+            .resource::<AssetMemoryBudget<CoolText>>()
+            .is_evicted(&a));
+    }
+
+    #[test]
+    fn asset_reload_systems() {
+        #[derive(Resource, Default)]
+        struct Reloaded(Vec<AssetId<SubText>>);
+
+        let (mut app, _) = test_app(Dir::default());
+        app.init_asset::<SubText>()
+            .init_resource::<Reloaded>()
+            .add_asset_reload_system(
+                |In(id): In<AssetId<SubText>>, mut reloaded: ResMut<Reloaded>| {
+                    reloaded.0.push(id);
+                },
+            );
+
+        let handle = app.world.resource_mut::<Assets<SubText>>().add(SubText {
+            text: "a".to_string(),
+        });
+        app.update();
+        assert!(app.world.resource::<Reloaded>().0.is_empty());
+
+        app.world
+            .resource_mut::<Assets<SubText>>()
+            .get_mut(&handle)
+            .unwrap()
+            .text = "b".to_string();
+        app.update();
+        assert_eq!(app.world.resource::<Reloaded>().0, vec![handle.id()]);
+    }
+
+    #[derive(Default)]
+    struct CoolTextSaver;
+
+    impl AssetSaver for CoolTextSaver {
+        type Asset = CoolText;
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+        type Error = ron::Error;
+
+        fn save<'a>(
+            &'a self,
+            writer: &'a mut Writer,
+            asset: SavedAsset<'a, Self::Asset>,
+            _settings: &'a Self::Settings,
+        ) -> BoxedFuture<'a, Result<(), Self::Error>> {
+            Box::pin(async move {
+                let ron = CoolTextRon {
+                    text: asset.text.clone(),
+                    dependencies: Vec::new(),
+                    embedded_dependencies: Vec::new(),
+                    sub_texts: Vec::new(),
+                };
+                writer.write_all(ron::to_string(&ron)?.as_bytes()).await?;
+                Ok(())
+            })
+        }
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    #[test]
+    fn save_runtime_asset() {
+        use crate::io::file::{FileAssetReader, FileAssetWriter};
+
+        let root = std::env::temp_dir().join(format!("bevy_asset_save_{}", std::process::id()));
+        let reader_root = root.clone();
+        let writer_root = root.clone();
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(FileAssetReader::new(&reader_root)))
+                .with_writer(move || Some(Box::new(FileAssetWriter::new(&writer_root)))),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let server = app.world.resource::<AssetServer>().clone();
+        let asset = CoolText {
+            text: "saved".to_string(),
+            embedded: String::new(),
+            dependencies: Vec::new(),
+            sub_texts: Vec::new(),
+        };
+        let path = "saved/text.cool.ron";
+        futures_lite::future::block_on(server.save(path, &asset, &CoolTextSaver, &())).unwrap();
+
+        let meta = std::fs::read_to_string(root.join("saved/text.cool.ron.meta")).unwrap();
+        assert!(meta.contains(std::any::type_name::<CoolTextLoader>()));
+
+        let handle: Handle<CoolText> = server.load(path);
+        run_app_until(&mut app, |world| {
+            let text = get::<CoolText>(world, handle.id())?;
+            assert_eq!(text.text, "saved");
+            Some(())
+        });
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
     #[test]
     fn ignore_system_ambiguities_on_assets() {
         let mut app = App::new();
@@ -29,6 +29,12 @@ pub trait AssetLoader: Send + Sync + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The type of [error](`std::error::Error`) which could be encountered by this loader.
     type Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>;
+    /// The version of the asset format read by this [`AssetLoader`], which is stored in the `.meta` files of the assets.
+    /// Bump it when making a breaking change to the format or to [`AssetLoader::Settings`], and upgrade the assets saved
+    /// with older versions in [`AssetLoader::migrate`].
+    ///
+    /// Assets without a `.meta` file are assumed to use the current version.
+    const FORMAT_VERSION: u32 = 0;
     /// Asynchronously loads [`AssetLoader::Asset`] (and any other labeled assets) from the bytes provided by [`Reader`].
     fn load<'a>(
         &'a self,
@@ -39,6 +45,53 @@ pub trait AssetLoader: Send + Sync + 'static {
 
     /// Returns a list of extensions supported by this asset loader, without the preceding dot.
     fn extensions(&self) -> &[&str];
+
+    /// Upgrades the data of an asset saved with the format `version` of this [`AssetLoader`] to the format `version + 1`.
+    /// Before an asset saved with an older [`AssetLoader::FORMAT_VERSION`] is loaded, this is called for each version
+    /// between the asset's and the current one. The [`AssetProcessor`] can also write the upgraded assets back to
+    /// their source with [`AssetProcessor::upgrade_assets`].
+    ///
+    /// By default, the data is unchanged, which is enough for versions that are compatible with the previous one.
+    ///
+    /// [`AssetProcessor`]: crate::processor::AssetProcessor
+    /// [`AssetProcessor::upgrade_assets`]: crate::processor::AssetProcessor::upgrade_assets
+    fn migrate(&self, _version: u32, _migration: &mut AssetMigration) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// The data of an asset saved with an older format version of its [`AssetLoader`], upgraded by [`AssetLoader::migrate`].
+pub struct AssetMigration {
+    /// The bytes of the asset.
+    pub bytes: Vec<u8>,
+    /// The bytes of the `.meta` file of the asset, if it has one, where the [`AssetLoader::Settings`] are stored.
+    pub meta: Option<Vec<u8>>,
+}
+
+/// Reads the asset at `asset_path`, saved with the format `version` of the `loader`, and upgrades it to the current
+/// [`AssetLoader::FORMAT_VERSION`].
+pub(crate) async fn migrate_asset(
+    asset_path: &AssetPath<'_>,
+    loader: &dyn ErasedAssetLoader,
+    version: u32,
+    reader: &mut Reader<'_>,
+    meta: Option<Vec<u8>>,
+) -> Result<AssetMigration, AssetLoadError> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(AssetReaderError::Io)?;
+    let mut migration = AssetMigration { bytes, meta };
+    loader.migrate(version, &mut migration).map_err(|error| {
+        AssetLoadError::AssetMigrationError {
+            path: asset_path.clone_owned(),
+            loader_name: loader.type_name(),
+            version,
+            error,
+        }
+    })?;
+    Ok(migration)
 }
 
 /// Provides type-erased access to an [`AssetLoader`].
@@ -56,6 +109,15 @@ pub trait ErasedAssetLoader: Send + Sync + 'static {
 
     /// Returns a list of extensions supported by this asset loader, without the preceding dot.
     fn extensions(&self) -> &[&str];
+    /// Returns the [`AssetLoader::FORMAT_VERSION`] of the loader.
+    fn format_version(&self) -> u32;
+    /// Upgrades the data of an asset saved with the format `version` to the current [`AssetLoader::FORMAT_VERSION`],
+    /// including the version stored in its meta.
+    fn migrate(
+        &self,
+        version: u32,
+        migration: &mut AssetMigration,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>;
     /// Deserializes metadata from the input `meta` bytes into the appropriate type (erased as [`Box<dyn AssetMetaDyn>`]).
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default meta value for the [`AssetLoader`] (erased as [`Box<dyn AssetMetaDyn>`]).
@@ -113,6 +175,26 @@ where
         <L as AssetLoader>::extensions(self)
     }
 
+    fn format_version(&self) -> u32 {
+        L::FORMAT_VERSION
+    }
+
+    fn migrate(
+        &self,
+        version: u32,
+        migration: &mut AssetMigration,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        for version in version..L::FORMAT_VERSION {
+            <L as AssetLoader>::migrate(self, version, migration).map_err(|error| error.into())?;
+        }
+        if let Some(meta_bytes) = &mut migration.meta {
+            let mut meta = AssetMeta::<L, ()>::deserialize(meta_bytes)?;
+            meta.asset_format_version = L::FORMAT_VERSION;
+            *meta_bytes = AssetMetaDyn::serialize(&meta);
+        }
+        Ok(())
+    }
+
     fn type_name(&self) -> &'static str {
         std::any::type_name::<L>()
     }
@@ -17,6 +17,10 @@ pub struct AssetMeta<L: AssetLoader, P: Process> {
     /// The version of the meta format being used. This will change whenever a breaking change is made to
     /// the meta format.
     pub meta_format_version: String,
+    /// The version of the format of the asset, as declared by [`AssetLoader::FORMAT_VERSION`]. Assets saved with older
+    /// versions are upgraded with [`AssetLoader::migrate`] when they are loaded.
+    #[serde(default, skip_serializing_if = "is_initial_format_version")]
+    pub asset_format_version: u32,
     /// Information produced by the [`AssetProcessor`] _after_ processing this asset.
     /// This will only exist alongside processed versions of assets. You should not manually set it in your asset source files.
     ///
@@ -31,6 +35,7 @@ impl<L: AssetLoader, P: Process> AssetMeta<L, P> {
     pub fn new(asset: AssetAction<L::Settings, P::Settings>) -> Self {
         Self {
             meta_format_version: META_FORMAT_VERSION.to_string(),
+            asset_format_version: L::FORMAT_VERSION,
             processed_info: None,
             asset,
         }
@@ -42,6 +47,10 @@ impl<L: AssetLoader, P: Process> AssetMeta<L, P> {
     }
 }
 
+fn is_initial_format_version(version: &u32) -> bool {
+    *version == 0
+}
+
 /// Configures how an asset source file should be handled by the asset system.
 #[derive(Serialize, Deserialize)]
 pub enum AssetAction<LoaderSettings, ProcessSettings> {
@@ -93,6 +102,8 @@ pub struct ProcessDependencyInfo {
 // using a type registry.
 #[derive(Serialize, Deserialize)]
 pub struct AssetMetaMinimal {
+    #[serde(default)]
+    pub asset_format_version: u32,
     pub asset: AssetActionMinimal,
 }
 
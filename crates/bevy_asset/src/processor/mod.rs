@@ -6,9 +6,11 @@ pub use process::*;
 
 use crate::{
     io::{
-        AssetReader, AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent,
-        AssetSourceId, AssetSources, AssetWriter, AssetWriterError, MissingAssetSourceError,
+        pak::PakWriter, AssetReader, AssetReaderError, AssetSource, AssetSourceBuilders,
+        AssetSourceEvent, AssetSourceId, AssetSources, AssetWriter, AssetWriterError,
+        MissingAssetSourceError, MissingAssetWriterError, MissingProcessedAssetReaderError,
     },
+    loader::migrate_asset,
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
         AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
@@ -117,6 +119,91 @@ impl AssetProcessor {
         &self.data.sources
     }
 
+    /// Waits until the processor is finished, then adds all of the processed assets of the
+    /// source to the `pak` archive, so that they can be shipped as a single file read by a
+    /// [`PakAssetReader`](crate::io::pak::PakAssetReader).
+    pub async fn write_pak<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+        pak: &mut PakWriter,
+    ) -> Result<(), WritePakError> {
+        self.data.wait_until_finished().await;
+        let source = self.get_source(source)?;
+        let reader = source.processed_reader()?;
+        pak.add_reader(reader, Path::new("")).await?;
+        Ok(())
+    }
+
+    /// Upgrades the assets of the `source` which were saved with an older [`AssetLoader::FORMAT_VERSION`] of their
+    /// loader with [`AssetLoader::migrate`], and writes them back to the source. This saves migrating them on each
+    /// load, and lets projects drop the migrations of old formats once their assets are upgraded. Returns the paths
+    /// of the upgraded assets.
+    ///
+    /// Only the assets with a `.meta` file configured to load them are upgraded, since the assets without one are
+    /// assumed to use the current format.
+    ///
+    /// [`AssetLoader::FORMAT_VERSION`]: crate::AssetLoader::FORMAT_VERSION
+    /// [`AssetLoader::migrate`]: crate::AssetLoader::migrate
+    pub async fn upgrade_assets<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+    ) -> Result<Vec<AssetPath<'static>>, UpgradeAssetsError> {
+        let source = self.get_source(source)?;
+        let reader = source.reader();
+        let writer = source.writer()?;
+        let mut upgraded = Vec::new();
+        let mut directories = vec![PathBuf::new()];
+        while let Some(directory) = directories.pop() {
+            let mut paths = reader.read_directory(&directory).await?;
+            while let Some(path) = paths.next().await {
+                if reader.is_directory(&path).await? {
+                    directories.push(path);
+                    continue;
+                }
+                let meta_bytes = match reader.read_meta_bytes(&path).await {
+                    Ok(meta_bytes) => meta_bytes,
+                    Err(AssetReaderError::NotFound(_)) => continue,
+                    Err(err) => return Err(err.into()),
+                };
+                let asset_path = AssetPath::from_path(&path)
+                    .with_source(source.id())
+                    .into_owned();
+                let minimal: AssetMetaMinimal = ron::de::from_bytes(&meta_bytes).map_err(|e| {
+                    AssetLoadError::DeserializeMeta {
+                        path: asset_path.clone(),
+                        error: Box::new(DeserializeMetaError::DeserializeMinimal(e)),
+                    }
+                })?;
+                let AssetActionMinimal::Load { loader } = minimal.asset else {
+                    continue;
+                };
+                let loader = self
+                    .server
+                    .get_asset_loader_with_type_name(&loader)
+                    .await
+                    .map_err(AssetLoadError::from)?;
+                if minimal.asset_format_version >= loader.format_version() {
+                    continue;
+                }
+                let mut asset_reader = reader.read(&path).await?;
+                let migration = migrate_asset(
+                    &asset_path,
+                    &*loader,
+                    minimal.asset_format_version,
+                    &mut asset_reader,
+                    Some(meta_bytes),
+                )
+                .await?;
+                writer.write_bytes(&path, &migration.bytes).await?;
+                writer
+                    .write_meta_bytes(&path, &migration.meta.unwrap())
+                    .await?;
+                upgraded.push(asset_path);
+            }
+        }
+        Ok(upgraded)
+    }
+
     /// Logs an unrecoverable error. On the next run of the processor, all assets will be regenerated. This should only be used as a last resort.
     /// Every call to this should be considered with scrutiny and ideally replaced with something more granular.
     async fn log_unrecoverable(&self) {
@@ -1317,3 +1404,29 @@ pub enum InitializeError {
     #[error("Failed to validate asset log: {0}")]
     ValidateLogError(ValidateLogError),
 }
+
+/// An error that occurred while upgrading assets with [`AssetProcessor::upgrade_assets`].
+#[derive(Error, Debug)]
+pub enum UpgradeAssetsError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingAssetWriter(#[from] MissingAssetWriterError),
+    #[error(transparent)]
+    AssetReaderError(#[from] AssetReaderError),
+    #[error(transparent)]
+    AssetWriterError(#[from] AssetWriterError),
+    #[error(transparent)]
+    AssetLoadError(#[from] AssetLoadError),
+}
+
+/// An error that occurred while packing the processed assets with [`AssetProcessor::write_pak`].
+#[derive(Error, Debug)]
+pub enum WritePakError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    #[error(transparent)]
+    AssetReaderError(#[from] AssetReaderError),
+}
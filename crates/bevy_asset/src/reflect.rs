@@ -3,7 +3,7 @@ use std::any::{Any, TypeId};
 use bevy_ecs::world::{unsafe_world_cell::UnsafeWorldCell, World};
 use bevy_reflect::{FromReflect, FromType, Reflect};
 
-use crate::{Asset, Assets, Handle, UntypedAssetId, UntypedHandle};
+use crate::{Asset, AssetPath, Assets, Handle, LoadContext, UntypedAssetId, UntypedHandle};
 
 /// Type data for the [`TypeRegistry`](bevy_reflect::TypeRegistry) used to operate on reflected [`Asset`]s.
 ///
@@ -198,6 +198,7 @@ pub struct ReflectHandle {
     asset_type_id: TypeId,
     downcast_handle_untyped: fn(&dyn Any) -> Option<UntypedHandle>,
     typed: fn(UntypedHandle) -> Box<dyn Reflect>,
+    load: fn(&mut LoadContext, AssetPath<'static>) -> UntypedHandle,
 }
 impl ReflectHandle {
     /// The [`TypeId`] of the asset
@@ -215,6 +216,16 @@ impl ReflectHandle {
     pub fn typed(&self, handle: UntypedHandle) -> Box<dyn Reflect> {
         (self.typed)(handle)
     }
+
+    /// Loads the asset at `path` as a dependency of the asset loaded by `load_context`, like
+    /// [`LoadContext::load`] with the asset type of the handle.
+    pub fn load<'b>(
+        &self,
+        load_context: &mut LoadContext,
+        path: impl Into<AssetPath<'b>>,
+    ) -> UntypedHandle {
+        (self.load)(load_context, path.into().into_owned())
+    }
 }
 
 impl<A: Asset> FromType<Handle<A>> for ReflectHandle {
@@ -227,6 +238,7 @@ impl<A: Asset> FromType<Handle<A>> for ReflectHandle {
                     .map(|h| h.clone().untyped())
             },
             typed: |handle: UntypedHandle| Box::new(handle.typed_debug_checked::<A>()),
+            load: |load_context, path| load_context.load::<A>(path).untyped(),
         }
     }
 }
@@ -0,0 +1,48 @@
+use crate::{Asset, AssetEvent, AssetId};
+use bevy_ecs::{prelude::*, system::SystemId};
+
+/// A [`Resource`] holding the systems which run when an asset of type `A` is modified, added
+/// with [`AssetApp::add_asset_reload_system`](crate::AssetApp::add_asset_reload_system).
+///
+/// This lets plugins react to hot reloaded assets, like a scripting plugin re-running a script
+/// whose source changed, without reading the [`AssetEvent`]s themselves.
+#[derive(Resource)]
+pub struct AssetReloadSystems<A: Asset> {
+    systems: Vec<SystemId<AssetId<A>>>,
+}
+
+impl<A: Asset> Default for AssetReloadSystems<A> {
+    fn default() -> Self {
+        Self {
+            systems: Vec::new(),
+        }
+    }
+}
+
+impl<A: Asset> AssetReloadSystems<A> {
+    /// Adds the registered `system`, which will run with the id of each modified asset.
+    pub fn add(&mut self, system: SystemId<AssetId<A>>) {
+        self.systems.push(system);
+    }
+
+    /// Returns the registered systems.
+    pub fn systems(&self) -> &[SystemId<AssetId<A>>] {
+        &self.systems
+    }
+}
+
+/// Runs the [`AssetReloadSystems`] of `A` with the id of each asset which was modified, either
+/// because it was reloaded or because it was accessed mutably in [`Assets`](crate::Assets).
+pub fn run_asset_reload_systems<A: Asset>(
+    mut commands: Commands,
+    reload_systems: Res<AssetReloadSystems<A>>,
+    mut events: EventReader<AssetEvent<A>>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = *event {
+            for system in &reload_systems.systems {
+                commands.run_system_with_input(*system, id);
+            }
+        }
+    }
+}
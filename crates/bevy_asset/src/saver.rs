@@ -67,7 +67,7 @@ impl<S: AssetSaver> ErasedAssetSaver for S {
 /// An [`Asset`] (and any labeled "sub assets") intended to be saved.
 pub struct SavedAsset<'a, A: Asset> {
     value: &'a A,
-    labeled_assets: &'a HashMap<CowArc<'static, str>, LabeledAsset>,
+    labeled_assets: Option<&'a HashMap<CowArc<'static, str>, LabeledAsset>>,
 }
 
 impl<'a, A: Asset> Deref for SavedAsset<'a, A> {
@@ -84,10 +84,19 @@ impl<'a, A: Asset> SavedAsset<'a, A> {
         let value = asset.value.downcast_ref::<A>()?;
         Some(SavedAsset {
             value,
-            labeled_assets: &asset.labeled_assets,
+            labeled_assets: Some(&asset.labeled_assets),
         })
     }
 
+    /// Creates a new [`SavedAsset`] without labeled assets from the runtime `asset`, such as an
+    /// asset stored in [`Assets`](crate::Assets).
+    pub fn from_asset(asset: &'a A) -> Self {
+        SavedAsset {
+            value: asset,
+            labeled_assets: None,
+        }
+    }
+
     /// Retrieves the value of this asset.
     #[inline]
     pub fn get(&self) -> &'a A {
@@ -99,11 +108,11 @@ impl<'a, A: Asset> SavedAsset<'a, A> {
         &self,
         label: impl Into<CowArc<'static, str>>,
     ) -> Option<SavedAsset<B>> {
-        let labeled = self.labeled_assets.get(&label.into())?;
+        let labeled = self.labeled_assets?.get(&label.into())?;
         let value = labeled.asset.value.downcast_ref::<B>()?;
         Some(SavedAsset {
             value,
-            labeled_assets: &labeled.asset.labeled_assets,
+            labeled_assets: Some(&labeled.asset.labeled_assets),
         })
     }
 
@@ -112,12 +121,14 @@ impl<'a, A: Asset> SavedAsset<'a, A> {
         &self,
         label: impl Into<CowArc<'static, str>>,
     ) -> Option<&ErasedLoadedAsset> {
-        let labeled = self.labeled_assets.get(&label.into())?;
+        let labeled = self.labeled_assets?.get(&label.into())?;
         Some(&labeled.asset)
     }
 
     /// Iterate over all labels for "labeled assets" in the loaded asset
     pub fn iter_labels(&self) -> impl Iterator<Item = &str> {
-        self.labeled_assets.keys().map(|s| &**s)
+        self.labeled_assets
+            .into_iter()
+            .flat_map(|labeled_assets| labeled_assets.keys().map(|s| &**s))
     }
 }
@@ -27,6 +27,12 @@ pub(crate) struct AssetInfo {
     failed_rec_dependencies: HashSet<UntypedAssetId>,
     dependants_waiting_on_load: HashSet<UntypedAssetId>,
     dependants_waiting_on_recursive_dep_load: HashSet<UntypedAssetId>,
+    /// The assets this asset depends on, set once it is loaded.
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
+    /// The loaded assets which depend on this asset. Used to notify them when this asset is reloaded.
+    pub(crate) dependants: HashSet<UntypedAssetId>,
+    /// The number of bytes read by the loader of this asset.
+    pub(crate) bytes_read: u64,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
     /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
@@ -54,6 +60,9 @@ impl AssetInfo {
             loader_dependencies: HashMap::default(),
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
+            dependencies: HashSet::default(),
+            dependants: HashSet::default(),
+            bytes_read: 0,
             handle_drops_to_skip: 0,
         }
     }
@@ -74,6 +83,7 @@ pub(crate) struct AssetInfos {
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<String>>,
     pub(crate) handle_providers: HashMap<TypeId, AssetHandleProvider>,
     pub(crate) dependency_loaded_event_sender: HashMap<TypeId, fn(&mut World, UntypedAssetId)>,
+    pub(crate) dependency_modified_event_sender: HashMap<TypeId, fn(&mut World, UntypedAssetId)>,
 }
 
 impl std::fmt::Debug for AssetInfos {
@@ -264,6 +274,11 @@ impl AssetInfos {
         self.infos.contains_key(&id)
     }
 
+    /// Iterates over the paths of the assets loaded from a path, including their labeled assets.
+    pub(crate) fn paths(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.path_to_id.keys()
+    }
+
     pub(crate) fn get_mut(&mut self, id: UntypedAssetId) -> Option<&mut AssetInfo> {
         self.infos.get_mut(&id)
     }
@@ -324,6 +339,24 @@ impl AssetInfos {
         sender: &Sender<InternalAssetEvent>,
     ) {
         loaded_asset.value.insert(loaded_asset_id, world);
+        let dependencies = loaded_asset.dependencies.clone();
+        let previous_dependencies = self
+            .get_mut(loaded_asset_id)
+            .map(|info| std::mem::take(&mut info.dependencies))
+            .unwrap_or_default();
+        for dependency in &previous_dependencies {
+            if let Some(info) = self.get_mut(*dependency) {
+                info.dependants.remove(&loaded_asset_id);
+            }
+        }
+        for dependency in &dependencies {
+            if let Some(info) = self.get_mut(*dependency) {
+                info.dependants.insert(loaded_asset_id);
+            }
+        }
+        // This must happen before the load states are propagated, to skip the dependants which are
+        // waiting on this load
+        self.send_dependants_modified(loaded_asset_id, world);
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
@@ -401,9 +434,10 @@ impl AssetInfos {
                     .expect("Asset info should always exist at this point");
                 if let Some(asset_path) = &info.path {
                     for loader_dependency in loaded_asset.loader_dependencies.keys() {
+                        // Changes are reported for whole asset files, so labels are ignored
                         let dependants = self
                             .loader_dependants
-                            .entry(loader_dependency.clone())
+                            .entry(loader_dependency.without_label().into_owned())
                             .or_default();
                         dependants.insert(asset_path.clone());
                     }
@@ -412,6 +446,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -470,6 +505,36 @@ impl AssetInfos {
         }
     }
 
+    /// Sends [`AssetEvent::Modified`](crate::AssetEvent::Modified) events for the dependants of a
+    /// reloaded asset, recursively, so that the assets built from it can be updated. Dependants
+    /// which are still waiting on their dependencies are skipped, as this is not a reload for them.
+    fn send_dependants_modified(&self, reloaded_id: UntypedAssetId, world: &mut World) {
+        let mut visited = HashSet::new();
+        let mut stack = vec![reloaded_id];
+        while let Some(id) = stack.pop() {
+            let Some(info) = self.infos.get(&id) else {
+                continue;
+            };
+            for dependant in &info.dependants {
+                let Some(dependant_info) = self.infos.get(dependant) else {
+                    continue;
+                };
+                if matches!(
+                    dependant_info.rec_dep_load_state,
+                    RecursiveDependencyLoadState::Loaded | RecursiveDependencyLoadState::Failed
+                ) && visited.insert(*dependant)
+                {
+                    let sender = self
+                        .dependency_modified_event_sender
+                        .get(&dependant.type_id())
+                        .expect("Asset event sender should exist");
+                    sender(world, *dependant);
+                    stack.push(*dependant);
+                }
+            }
+        }
+    }
+
     /// Recursively propagates loaded state up the dependency tree.
     fn propagate_loaded_state(
         infos: &mut AssetInfos,
@@ -561,7 +626,8 @@ impl AssetInfos {
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<String>>,
     ) {
         for loader_dependency in info.loader_dependencies.keys() {
-            if let Some(dependants) = loader_dependants.get_mut(loader_dependency) {
+            let loader_dependency = loader_dependency.without_label().into_owned();
+            if let Some(dependants) = loader_dependants.get_mut(&loader_dependency) {
                 dependants.remove(path);
             }
         }
@@ -603,6 +669,11 @@ impl AssetInfos {
         }
 
         let info = entry.remove();
+        for dependency in &info.dependencies {
+            if let Some(dependency_info) = infos.get_mut(dependency) {
+                dependency_info.dependants.remove(&id);
+            }
+        }
         let Some(path) = &info.path else {
             return true;
         };
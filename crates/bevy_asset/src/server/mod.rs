@@ -2,25 +2,29 @@ mod info;
 
 use crate::{
     folder::LoadedFolder,
+    generator::{generator_name, AssetGenerator, GeneratorLoader},
     io::{
         AssetReader, AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        MissingAssetSourceError, MissingProcessedAssetReaderError, Reader,
+        AssetWriterError, MissingAssetSourceError, MissingAssetWriterError,
+        MissingProcessedAssetReaderError, Reader, VecReader,
     },
-    loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
+    loader::{migrate_asset, AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
-        loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
-        MetaTransform, Settings,
+        loader_settings_meta_transform, AssetAction, AssetActionMinimal, AssetMeta, AssetMetaDyn,
+        AssetMetaMinimal, MetaTransform, Settings,
     },
     path::AssetPath,
-    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetMetaCheck, Assets, DeserializeMetaError,
-    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId, UntypedHandle,
+    saver::{AssetSaver, SavedAsset},
+    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetMetaCheck, AssetSourceChanged, Assets,
+    DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset, LoadingProgress,
+    UntypedAssetId, UntypedHandle,
 };
 use bevy_ecs::prelude::*;
 use bevy_log::{error, info, warn};
 use bevy_tasks::IoTaskPool;
 use bevy_utils::{CowArc, HashMap, HashSet};
 use crossbeam_channel::{Receiver, Sender};
-use futures_lite::StreamExt;
+use futures_lite::{AsyncWriteExt, StreamExt};
 use info::*;
 use parking_lot::RwLock;
 use std::path::PathBuf;
@@ -165,6 +169,16 @@ impl AssetServer {
         }
     }
 
+    /// Registers the given `generator` under `name`. It generates the assets of the [`PROCEDURAL`](crate::PROCEDURAL)
+    /// source whose path is `name` followed by their parameters, like `proc://noise?seed=5` for a generator named `noise`.
+    pub fn register_generator<G: AssetGenerator>(&self, name: &str, generator: G) {
+        let mut loaders = self.data.loaders.write();
+        let loader: Arc<dyn ErasedAssetLoader> = Arc::new(GeneratorLoader::new(generator));
+        let index = loaders.values.len();
+        loaders.values.push(MaybeAssetLoader::Ready(loader));
+        loaders.generator_to_index.insert(name.to_string(), index);
+    }
+
     /// Registers a new [`Asset`] type. [`Asset`] types must be registered before assets of that type can be loaded.
     pub fn register_asset<A: Asset>(&self, assets: &Assets<A>) {
         self.register_handle_provider(assets.get_handle_provider());
@@ -173,11 +187,18 @@ impl AssetServer {
                 .resource_mut::<Events<AssetEvent<A>>>()
                 .send(AssetEvent::LoadedWithDependencies { id: id.typed() });
         }
-        self.data
-            .infos
-            .write()
+        fn modified_sender<A: Asset>(world: &mut World, id: UntypedAssetId) {
+            world
+                .resource_mut::<Events<AssetEvent<A>>>()
+                .send(AssetEvent::Modified { id: id.typed() });
+        }
+        let mut infos = self.data.infos.write();
+        infos
             .dependency_loaded_event_sender
             .insert(TypeId::of::<A>(), sender::<A>);
+        infos
+            .dependency_modified_event_sender
+            .insert(TypeId::of::<A>(), modified_sender::<A>);
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -235,6 +256,14 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
     ) -> Result<Arc<dyn ErasedAssetLoader>, MissingAssetLoaderForExtensionError> {
         let path = path.into();
+        if let Some(name) = generator_name(&path) {
+            let loaders = self.data.loaders.read();
+            if let Some(index) = loaders.generator_to_index.get(name) {
+                if let MaybeAssetLoader::Ready(loader) = &loaders.values[*index] {
+                    return Ok(loader.clone());
+                }
+            }
+        }
         let full_extension =
             path.get_full_extension()
                 .ok_or(MissingAssetLoaderForExtensionError {
@@ -478,11 +507,19 @@ impl AssetServer {
             (*meta_transform)(&mut *meta);
         }
 
+        let mut reader = CountingReader {
+            reader: &mut *reader,
+            bytes_read: 0,
+        };
         match self
-            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut *reader, true, false)
+            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut reader, true, false)
             .await
         {
             Ok(loaded_asset) => {
+                if let Some(info) = self.data.infos.write().get_mut(base_handle.id()) {
+                    info.bytes_read = reader.bytes_read;
+                }
+
                 let final_handle = if let Some(label) = path.label_cow() {
                     match loaded_asset.labeled_assets.get(&label) {
                         Some(labeled_asset) => labeled_asset.handle.clone(),
@@ -536,6 +573,22 @@ impl AssetServer {
             .detach();
     }
 
+    /// Kicks off a reload of all the loaded assets generated by the [`AssetGenerator`] registered as `name`, for
+    /// example after changing the data it reads outside of the parameters of the assets.
+    pub fn reload_generated(&self, name: &str) {
+        let paths = self
+            .data
+            .infos
+            .read()
+            .paths()
+            .filter(|path| path.label().is_none() && generator_name(path) == Some(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in paths {
+            self.reload(path);
+        }
+    }
+
     /// Queues a new asset to be tracked by the [`AssetServer`] and returns a [`Handle`] to it. This can be used to track
     /// dependencies of assets created at runtime.
     ///
@@ -581,6 +634,79 @@ impl AssetServer {
         handle
     }
 
+    /// Saves the runtime `asset` to `path` with the given [`AssetSaver`], for example to write an asset
+    /// modified by an editor back to its [`AssetSource`]. This writes both the asset bytes and its
+    /// `.meta` file, configured to load the asset with [`AssetSaver::OutputLoader`] and the settings
+    /// returned by the saver.
+    ///
+    /// The asset is written with the unprocessed [`AssetWriter`](crate::io::AssetWriter) of the
+    /// path's source. When the [`AssetServer`] runs in [`AssetServerMode::Processed`], the
+    /// [`AssetProcessor`](crate::processor::AssetProcessor) will then process the saved asset.
+    ///
+    /// This does not block on the save, so it can be spawned on the [`IoTaskPool`] from a system
+    /// with a clone of the asset:
+    ///
+    /// ```
+    /// # use bevy_asset::{prelude::*, saver::AssetSaver};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_tasks::IoTaskPool;
+    /// fn save_asset<S: AssetSaver>(server: &AssetServer, asset: S::Asset, saver: S)
+    /// where
+    ///     S::Asset: Clone,
+    /// {
+    ///     let server = server.clone();
+    ///     IoTaskPool::get()
+    ///         .spawn(async move {
+    ///             if let Err(err) = server
+    ///                 .save("edited.asset", &asset, &saver, &Default::default())
+    ///                 .await
+    ///             {
+    ///                 eprintln!("{err}");
+    ///             }
+    ///         })
+    ///         .detach();
+    /// }
+    /// ```
+    pub async fn save<'a, S: AssetSaver>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        asset: &S::Asset,
+        saver: &S,
+        settings: &S::Settings,
+    ) -> Result<(), SaveAssetError> {
+        let path = path.into().into_owned();
+        let source = self.get_source(path.source())?;
+        let asset_writer = source.writer()?;
+        let writer_err = |err| SaveAssetError::AssetWriterError {
+            path: path.clone(),
+            err,
+        };
+
+        let mut writer = asset_writer.write(path.path()).await.map_err(writer_err)?;
+        let loader_settings = saver
+            .save(&mut *writer, SavedAsset::from_asset(asset), settings)
+            .await
+            .map_err(|error| SaveAssetError::AssetSaverError {
+                path: path.clone(),
+                saver_name: std::any::type_name::<S>(),
+                error: error.into(),
+            })?;
+        writer
+            .flush()
+            .await
+            .map_err(|err| writer_err(AssetWriterError::Io(err)))?;
+
+        let meta = AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+            loader: std::any::type_name::<S::OutputLoader>().to_string(),
+            settings: loader_settings,
+        });
+        asset_writer
+            .write_meta_bytes(path.path(), &meta.serialize())
+            .await
+            .map_err(writer_err)?;
+        Ok(())
+    }
+
     /// Loads all assets from the specified folder recursively. The [`LoadedFolder`] asset (when it loads) will
     /// contain handles to all assets in the folder. You can wait for all assets to load by checking the [`LoadedFolder`]'s
     /// [`RecursiveDependencyLoadState`].
@@ -747,6 +873,105 @@ impl AssetServer {
             && self.recursive_dependency_load_state(id) == RecursiveDependencyLoadState::Loaded
     }
 
+    /// Returns the progress of the loads of the assets with the `ids` and of their recursive
+    /// dependencies, each asset being counted once.
+    ///
+    /// The dependencies of an asset are only known once it is loaded, so the total grows as
+    /// the loads progress. Assets which aren't managed by the [`AssetServer`], like the ones added
+    /// to [`Assets`] directly, are considered loaded.
+    pub fn get_loading_progress(
+        &self,
+        ids: impl IntoIterator<Item = UntypedAssetId>,
+    ) -> LoadingProgress {
+        let infos = self.data.infos.read();
+        let mut progress = LoadingProgress::default();
+        let mut visited = HashSet::new();
+        let mut ids: Vec<_> = ids.into_iter().collect();
+        while let Some(id) = ids.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            progress.total += 1;
+            let Some(info) = infos.get(id) else {
+                progress.loaded += 1;
+                continue;
+            };
+            match info.load_state {
+                LoadState::Loaded => {
+                    progress.loaded += 1;
+                    progress.bytes_read += info.bytes_read;
+                }
+                LoadState::Failed => progress.failed += 1,
+                LoadState::NotLoaded | LoadState::Loading => {}
+            }
+            ids.extend(info.dependencies.iter().copied());
+        }
+        progress
+    }
+
+    /// Returns the assets the asset with the given `id` depends on through its handles, if it is
+    /// loaded and managed by the [`AssetServer`].
+    pub fn get_dependencies(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        let infos = self.data.infos.read();
+        let info = infos.get(id.into())?;
+        (info.load_state == LoadState::Loaded).then(|| info.dependencies.iter().copied().collect())
+    }
+
+    /// Returns the loaded assets which depend on the asset with the given `id` through their
+    /// handles, for example the materials using a texture.
+    pub fn get_dependants(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(|info| info.dependants.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the assets the asset with the given `id` depends on, directly or through other
+    /// dependencies, each asset being returned once.
+    ///
+    /// The subtree of an asset stays loaded while the asset holds the handles of its
+    /// dependencies. Combined with [`AssetServer::get_dependants`], this can be used to find the
+    /// dependencies which are only used by the asset before unloading it.
+    pub fn get_recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        let infos = self.data.infos.read();
+        let root = id.into();
+        let mut visited = HashSet::new();
+        let mut dependencies = Vec::new();
+        let mut ids = vec![root];
+        while let Some(id) = ids.pop() {
+            let Some(info) = infos.get(id) else {
+                continue;
+            };
+            for dependency in &info.dependencies {
+                if *dependency != root && visited.insert(*dependency) {
+                    dependencies.push(*dependency);
+                    ids.push(*dependency);
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Returns the paths of the assets which read the asset at `path` while loading with
+    /// [`LoadContext::load_direct`], and which are reloaded when it changes.
+    ///
+    /// These "loader dependencies" are only tracked when watching for changes.
+    pub fn get_loader_dependants<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+    ) -> Vec<AssetPath<'static>> {
+        let path = path.into();
+        self.data
+            .infos
+            .read()
+            .loader_dependants
+            .get(&path.without_label().into_owned())
+            .map(|dependants| dependants.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Returns an active handle for the given path, if the asset at the given path has already started loading,
     /// or is still "alive".
     pub fn get_handle<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Option<Handle<A>> {
@@ -884,6 +1109,22 @@ impl AssetServer {
                         }
                     };
                     let loader = self.get_asset_loader_with_type_name(&loader_name).await?;
+                    let (meta_bytes, reader) =
+                        if minimal.asset_format_version < loader.format_version() {
+                            let mut reader = reader;
+                            let migration = migrate_asset(
+                                asset_path,
+                                &*loader,
+                                minimal.asset_format_version,
+                                &mut reader,
+                                Some(meta_bytes),
+                            )
+                            .await?;
+                            let reader: Box<Reader<'a>> = Box::new(VecReader::new(migration.bytes));
+                            (migration.meta.unwrap(), reader)
+                        } else {
+                            (meta_bytes, reader)
+                        };
                     let meta = loader.deserialize_meta(&meta_bytes).map_err(|e| {
                         AssetLoadError::DeserializeMeta {
                             path: asset_path.clone_owned(),
@@ -981,15 +1222,13 @@ pub fn handle_internal_asset_events(world: &mut World) {
             }
         };
 
-        let mut paths_to_reload = HashSet::new();
+        let mut changed_paths = HashSet::new();
         let mut handle_event = |source: AssetSourceId<'static>, event: AssetSourceEvent| {
             match event {
                 // TODO: if the asset was processed and the processed file was changed, the first modified event
                 // should be skipped?
                 AssetSourceEvent::ModifiedAsset(path) | AssetSourceEvent::ModifiedMeta(path) => {
-                    let path = AssetPath::from(path).with_source(source);
-                    queue_ancestors(&path, &infos, &mut paths_to_reload);
-                    paths_to_reload.insert(path);
+                    changed_paths.insert(AssetPath::from(path).with_source(source));
                 }
                 AssetSourceEvent::RenamedFolder { old, new } => {
                     reload_parent_folders(old, &source);
@@ -1024,6 +1263,22 @@ pub fn handle_internal_asset_events(world: &mut World) {
             }
         }
 
+        let mut paths_to_reload = HashSet::new();
+        let mut changes = Vec::new();
+        for path in changed_paths {
+            let mut reloaded = HashSet::new();
+            queue_ancestors(&path, &infos, &mut reloaded);
+            reloaded.insert(path.clone());
+            reloaded.retain(|path| infos.should_reload(path));
+            paths_to_reload.extend(reloaded.iter().cloned());
+            changes.push(AssetSourceChanged {
+                path,
+                reloaded: reloaded.into_iter().collect(),
+            });
+        }
+        drop(infos);
+        world.send_event_batch(changes);
+
         for path in paths_to_reload {
             server.reload(path);
         }
@@ -1036,6 +1291,7 @@ pub(crate) struct AssetLoaders {
     extension_to_index: HashMap<String, usize>,
     type_name_to_index: HashMap<&'static str, usize>,
     preregistered_loaders: HashMap<&'static str, usize>,
+    generator_to_index: HashMap<String, usize>,
 }
 
 #[derive(Clone)]
@@ -1047,6 +1303,26 @@ enum MaybeAssetLoader {
     },
 }
 
+/// A [`Reader`] counting the bytes read by an [`AssetLoader`].
+struct CountingReader<'a, 'b> {
+    reader: &'a mut Reader<'b>,
+    bytes_read: u64,
+}
+
+impl futures_io::AsyncRead for CountingReader<'_, '_> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> std::task::Poll<futures_io::Result<usize>> {
+        let result = std::pin::Pin::new(&mut *self.reader).poll_read(cx, buf);
+        if let std::task::Poll::Ready(Ok(read)) = result {
+            self.bytes_read += read as u64;
+        }
+        result
+    }
+}
+
 /// Internal events for asset load results  
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum InternalAssetEvent {
@@ -1138,6 +1414,13 @@ pub enum AssetLoadError {
         loader_name: &'static str,
         error: Box<dyn std::error::Error + Send + Sync + 'static>,
     },
+    #[error("Failed to upgrade asset '{path}' from format version {version} with asset loader '{loader_name}': {error}")]
+    AssetMigrationError {
+        path: AssetPath<'static>,
+        loader_name: &'static str,
+        version: u32,
+        error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
     #[error("The file at '{base_path}' does not contain the labeled asset '{label}'.")]
     MissingLabel {
         base_path: AssetPath<'static>,
@@ -1145,6 +1428,26 @@ pub enum AssetLoadError {
     },
 }
 
+/// An error that occurs when saving an [`Asset`] with [`AssetServer::save`].
+#[derive(Error, Debug)]
+pub enum SaveAssetError {
+    #[error(transparent)]
+    MissingAssetSourceError(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingAssetWriterError(#[from] MissingAssetWriterError),
+    #[error("Failed to write asset '{path}': {err}")]
+    AssetWriterError {
+        path: AssetPath<'static>,
+        err: AssetWriterError,
+    },
+    #[error("Failed to save asset '{path}' with asset saver '{saver_name}': {error}")]
+    AssetSaverError {
+        path: AssetPath<'static>,
+        saver_name: &'static str,
+        error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
 /// An error that occurs when an [`AssetLoader`] is not registered for a given extension.
 #[derive(Error, Debug)]
 #[error("no `AssetLoader` found{}", format_missing_asset_ext(.extensions))]
@@ -0,0 +1,139 @@
+use crate::{AssetServer, UntypedHandle};
+use bevy_ecs::prelude::*;
+
+/// The progress of the loads of a group of assets and of their recursive dependencies, returned
+/// by [`AssetServer::get_loading_progress`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LoadingProgress {
+    /// The number of assets which are loaded.
+    pub loaded: usize,
+    /// The number of assets which failed to load.
+    pub failed: usize,
+    /// The number of assets of the group and of their dependencies known so far.
+    pub total: usize,
+    /// The number of bytes read by the loaders of the loaded assets.
+    pub bytes_read: u64,
+}
+
+impl LoadingProgress {
+    /// Returns `true` if all of the assets are either loaded or failed to load.
+    pub fn is_finished(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+
+    /// Returns the fraction of the assets which are finished loading, between `0.0` and `1.0`,
+    /// for example to fill a progress bar.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        }
+    }
+}
+
+/// A [`Component`] tracking the loads of a group of assets, such as the assets of a level behind
+/// a loading screen, and of their recursive dependencies.
+///
+/// Its progress is updated by [`update_loading_trackers`] after the asset events of each frame,
+/// which sends a [`LoadingFinished`] event once all the assets are loaded or failed to load.
+/// The tracker holds strong handles, so the assets stay loaded while it exists.
+///
+/// ```
+/// # use bevy_asset::{prelude::*, LoadingFinished, LoadingTracker};
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Resource)]
+/// # struct LevelAssets(Vec<UntypedHandle>);
+/// fn start_loading(mut commands: Commands, level: Res<LevelAssets>) {
+///     let mut tracker = LoadingTracker::new();
+///     for handle in &level.0 {
+///         tracker.add(handle.clone());
+///     }
+///     commands.spawn(tracker);
+/// }
+///
+/// fn update_loading_screen(trackers: Query<&LoadingTracker>) {
+///     for tracker in &trackers {
+///         println!("Loading... {:.0}%", tracker.progress().fraction() * 100.0);
+///     }
+/// }
+///
+/// fn finish_loading(mut finished: EventReader<LoadingFinished>) {
+///     for event in finished.read() {
+///         println!("Loaded {} assets", event.progress.loaded);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default)]
+pub struct LoadingTracker {
+    handles: Vec<UntypedHandle>,
+    progress: LoadingProgress,
+    finished: bool,
+}
+
+impl LoadingTracker {
+    /// Creates a tracker without assets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the asset of the `handle` to the tracked assets.
+    pub fn with(mut self, handle: impl Into<UntypedHandle>) -> Self {
+        self.add(handle);
+        self
+    }
+
+    /// Adds the asset of the `handle` to the tracked assets, which resumes the tracking if all
+    /// of the assets were already finished loading.
+    pub fn add(&mut self, handle: impl Into<UntypedHandle>) {
+        self.handles.push(handle.into());
+        self.finished = false;
+    }
+
+    /// Returns the handles of the tracked assets.
+    pub fn handles(&self) -> &[UntypedHandle] {
+        &self.handles
+    }
+
+    /// Returns the progress of the loads, as of the last run of [`update_loading_trackers`].
+    pub fn progress(&self) -> LoadingProgress {
+        self.progress
+    }
+
+    /// Returns `true` if all of the assets are either loaded or failed to load, as of the last
+    /// run of [`update_loading_trackers`].
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// An [`Event`] sent when all of the assets of a [`LoadingTracker`] are either loaded or failed
+/// to load.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct LoadingFinished {
+    /// The entity of the [`LoadingTracker`].
+    pub entity: Entity,
+    /// The final progress of the loads.
+    pub progress: LoadingProgress,
+}
+
+/// Updates the progress of the [`LoadingTracker`]s, and sends the [`LoadingFinished`] events.
+pub fn update_loading_trackers(
+    server: Res<AssetServer>,
+    mut trackers: Query<(Entity, &mut LoadingTracker)>,
+    mut finished: EventWriter<LoadingFinished>,
+) {
+    for (entity, mut tracker) in &mut trackers {
+        if tracker.finished {
+            continue;
+        }
+        let progress = server.get_loading_progress(tracker.handles.iter().map(UntypedHandle::id));
+        if progress != tracker.progress {
+            tracker.progress = progress;
+        }
+        if progress.is_finished() {
+            tracker.finished = true;
+            finished.send(LoadingFinished { entity, progress });
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use crate::{AudioSource, Decodable};
+use crate::{AudioSource, Decodable, MixerBus};
 use bevy_asset::{Asset, Handle};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
 use bevy_reflect::prelude::*;
+use std::time::Duration;
 
 /// Defines the volume to play an audio source at.
 #[derive(Clone, Copy, Debug, Reflect)]
@@ -88,11 +89,25 @@ pub struct PlaybackSettings {
     pub paused: bool,
     /// Enables spatial audio for this source.
     ///
-    /// See also: [`SpatialListener`].
+    /// See also: [`SpatialListener`] and [`SpatialEmitter`](crate::SpatialEmitter).
     ///
-    /// Note: Bevy does not currently support HRTF or any other high-quality 3D sound rendering
-    /// features. Spatial audio is implemented via simple left-right stereo panning.
+    /// Spatial audio is rendered with left-right stereo panning, or with a binaural model of
+    /// the head if [`SpatialListener::hrtf`] is enabled.
     pub spatial: bool,
+    /// The mixer bus to play on, so the sound follows the volume of its group in the
+    /// [`AudioMixerSettings`](crate::AudioMixerSettings).
+    ///
+    /// Ignored if the entity has an [`OutputBus`](crate::OutputBus). If `None`, the sound
+    /// plays directly on the audio device.
+    pub bus: Option<MixerBus>,
+    /// Decodes the sound in chunks on a background task, instead of on the audio thread.
+    ///
+    /// This is recommended for long sounds such as music, and is required to seek in the
+    /// sound with [`AudioSinkPlayback::try_seek`](crate::AudioSinkPlayback::try_seek).
+    pub streaming: bool,
+    /// The time on the [`AudioClock`](crate::AudioClock) at which the sound starts, with sample
+    /// accuracy. If `None` or if that time has already passed, the sound starts right away.
+    pub start_at: Option<Duration>,
 }
 
 impl Default for PlaybackSettings {
@@ -110,6 +125,9 @@ impl PlaybackSettings {
         speed: 1.0,
         paused: false,
         spatial: false,
+        bus: None,
+        streaming: false,
+        start_at: None,
     };
 
     /// Will play the associated audio source in a loop.
@@ -119,6 +137,9 @@ impl PlaybackSettings {
         speed: 1.0,
         paused: false,
         spatial: false,
+        bus: None,
+        streaming: false,
+        start_at: None,
     };
 
     /// Will play the associated audio source once and despawn the entity afterwards.
@@ -128,6 +149,9 @@ impl PlaybackSettings {
         speed: 1.0,
         paused: false,
         spatial: false,
+        bus: None,
+        streaming: false,
+        start_at: None,
     };
 
     /// Will play the associated audio source once and remove the audio components afterwards.
@@ -137,6 +161,9 @@ impl PlaybackSettings {
         speed: 1.0,
         paused: false,
         spatial: false,
+        bus: None,
+        streaming: false,
+        start_at: None,
     };
 
     /// Helper to start in a paused state.
@@ -162,6 +189,24 @@ impl PlaybackSettings {
         self.spatial = spatial;
         self
     }
+
+    /// Helper to enable or disable streaming.
+    pub const fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Helper to start at a time on the [`AudioClock`](crate::AudioClock).
+    pub const fn with_start_at(mut self, start: Duration) -> Self {
+        self.start_at = Some(start);
+        self
+    }
+
+    /// Helper to play on a mixer bus.
+    pub const fn with_bus(mut self, bus: MixerBus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
 }
 
 /// Settings for the listener for spatial audio sources.
@@ -175,6 +220,12 @@ pub struct SpatialListener {
     pub left_ear_offset: Vec3,
     /// Right ear position relative to the `GlobalTransform`.
     pub right_ear_offset: Vec3,
+    /// Renders spatial audio binaurally, for headphones.
+    ///
+    /// Instead of panning sounds between the ears, each ear is delayed and filtered according
+    /// to the direction of the sound, using a spherical model of the head. Only the direction
+    /// of the ears is used, so this works with any gap between them.
+    pub hrtf: bool,
 }
 
 impl Default for SpatialListener {
@@ -192,6 +243,7 @@ impl SpatialListener {
         SpatialListener {
             left_ear_offset: Vec3::X * gap / -2.0,
             right_ear_offset: Vec3::X * gap / 2.0,
+            hrtf: false,
         }
     }
 }
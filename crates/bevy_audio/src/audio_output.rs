@@ -1,47 +1,103 @@
 use crate::{
-    AudioSourceBundle, Decodable, GlobalVolume, PlaybackMode, PlaybackSettings, SpatialAudioSink,
-    SpatialListener, SpatialScale, Volume,
+    bus::{routing, sound_output},
+    clock::ClockState,
+    device::{find_output_device, AudioStreams},
+    graph::{audio_graph, AudioGraph, AudioGraphHandle, GraphCommand, GraphOutput},
+    spatial::SpatialParameters,
+    streaming::{stream, track_position},
+    AudioClock, AudioDevice, AudioMixer, AudioSends, AudioSinkPlayback, AudioSourceBundle,
+    Decodable, GlobalVolume, OutputBus, PlaybackMode, PlaybackSettings, SpatialAudioSink,
+    SpatialEmitter, SpatialListener, SpatialScale, SpeedOfSound, Volume,
 };
 use bevy_asset::{Asset, Assets, Handle};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::Vec3;
+use bevy_time::Time;
 use bevy_transform::prelude::GlobalTransform;
 use bevy_utils::tracing::warn;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
+use rodio::{
+    cpal::{
+        traits::{DeviceTrait, HostTrait},
+        Device,
+    },
+    OutputStream, Sink, Source,
+};
+use std::sync::{Arc, Mutex};
 
 use crate::AudioSink;
 
+/// The sample rate of the audio graph if the audio device doesn't report its own.
+const DEFAULT_SAMPLE_RATE: u32 = 48_000;
+
 /// Used internally to play audio on the current "audio device"
 ///
-/// ## Note
-///
-/// Initializing this resource will leak [`OutputStream`]
-/// using [`std::mem::forget`].
-/// This is done to avoid storing this in the struct (and making this `!Send`)
-/// while preventing it from dropping (to avoid halting of audio).
-///
-/// This is fine when initializing this once (as is default when adding this plugin),
-/// since the memory cost will be the same.
-/// However, repeatedly inserting this resource into the app will **leak more memory**.
+/// All audio is mixed by an [`AudioGraph`], which is controlled through this resource. The
+/// graph plays on the device selected by the [`AudioDeviceSettings`](crate::AudioDeviceSettings),
+/// and keeps its sounds when the device changes.
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
-    stream_handle: Option<OutputStreamHandle>,
+    pub(crate) graph: AudioGraphHandle,
+    pub(crate) clock: AudioClock,
+    /// The graph, shared with the stream of the audio device.
+    output: Arc<Mutex<AudioGraph>>,
+    /// The name of the device playing the graph, if any.
+    pub(crate) device: Option<String>,
 }
 
-impl Default for AudioOutput {
-    fn default() -> Self {
-        if let Ok((stream, stream_handle)) = OutputStream::try_default() {
-            // We leak `OutputStream` to prevent the audio from stopping.
-            std::mem::forget(stream);
-            Self {
-                stream_handle: Some(stream_handle),
+impl AudioOutput {
+    /// Creates the audio graph, and plays it on `device` or on the default device.
+    pub(crate) fn open(device: &AudioDevice, streams: &mut AudioStreams) -> Self {
+        let device = match device {
+            AudioDevice::Named(name) => find_output_device(name),
+            AudioDevice::Default => None,
+        }
+        .or_else(|| rodio::cpal::default_host().default_output_device());
+
+        // Mix at the rate of the device, so the graph output doesn't need to be resampled.
+        let sample_rate = device
+            .as_ref()
+            .and_then(|device| device.default_output_config().ok())
+            .map_or(DEFAULT_SAMPLE_RATE, |config| config.sample_rate().0);
+        let clock = Arc::new(ClockState::new(sample_rate));
+        let (graph, output) = audio_graph(sample_rate, clock.clone());
+        let mut audio_output = Self {
+            graph,
+            clock: AudioClock::new(clock),
+            output: Arc::new(Mutex::new(output)),
+            device: None,
+        };
+
+        match device {
+            Some(device) => {
+                audio_output.play_on(&device, streams);
             }
-        } else {
-            warn!("No audio device found.");
-            Self {
-                stream_handle: None,
+            None => warn!("No audio device found."),
+        }
+        audio_output
+    }
+
+    /// Plays the graph on `device` instead of the current device. Returns `false` if the
+    /// device can't be opened, in which case the current device keeps playing.
+    pub(crate) fn play_on(&mut self, device: &Device, streams: &mut AudioStreams) -> bool {
+        let name = device.name().unwrap_or_default();
+        let (stream, stream_handle) = match OutputStream::try_from_device(device) {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!("Error opening audio device {name}: {err:?}");
+                return false;
             }
+        };
+
+        // Stop the current device before the new one starts mixing the graph.
+        streams.output = None;
+        self.device = None;
+        if let Err(err) = stream_handle.play_raw(GraphOutput::new(self.output.clone())) {
+            warn!("Error playing the audio graph on {name}: {err:?}");
+            return false;
         }
+        streams.output = Some(stream);
+        self.device = Some(name);
+        true
     }
 }
 
@@ -85,6 +141,14 @@ impl<'w, 's> EarPositions<'w, 's> {
         (left_ear, right_ear)
     }
 
+    /// Returns `true` if the listener uses binaural rendering.
+    pub(crate) fn hrtf(&self) -> bool {
+        self.query
+            .iter()
+            .next()
+            .is_some_and(|(_, _, settings)| settings.hrtf)
+    }
+
     pub(crate) fn multiple_listeners(&self) -> bool {
         self.query.iter().len() > 1
     }
@@ -98,33 +162,86 @@ impl<'w, 's> EarPositions<'w, 's> {
 ///
 /// This system detects such entities, checks if their source asset
 /// data is available, and creates/inserts the sink.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
     audio_output: Res<AudioOutput>,
     audio_sources: Res<Assets<Source>>,
     global_volume: Res<GlobalVolume>,
+    mixer: Res<AudioMixer>,
     query_nonplaying: Query<
         (
             Entity,
             &Handle<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&OutputBus>,
+            Option<&AudioSends>,
+            Option<&SpatialEmitter>,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
     ear_positions: EarPositions,
+    speed_of_sound: Res<SpeedOfSound>,
+    clock: Res<AudioClock>,
     mut commands: Commands,
 ) where
     f32: rodio::cpal::FromSample<Source::DecoderItem>,
 {
-    let Some(stream_handle) = audio_output.stream_handle.as_ref() else {
-        // audio output unavailable; cannot play sound
-        return;
-    };
+    let graph = &audio_output.graph;
 
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    for (
+        entity,
+        source_handle,
+        settings,
+        maybe_emitter_transform,
+        output_bus,
+        sends,
+        emitter_settings,
+    ) in &query_nonplaying
+    {
         let Some(audio_source) = audio_sources.get(source_handle) else {
             continue;
         };
+        let looping = matches!(settings.mode, PlaybackMode::Loop);
+        let streamed = settings
+            .streaming
+            .then(|| audio_source.streamed())
+            .flatten();
+        if settings.streaming && streamed.is_none() {
+            warn!(
+                "{} can't be streamed, decoding it on the audio thread instead.",
+                std::any::type_name::<Source>()
+            );
+        }
+        let (source, playhead): (Box<dyn rodio::Source<Item = f32> + Send>, _) = match streamed {
+            Some(streamed) => {
+                let (source, playhead) = stream(streamed, looping);
+                (Box::new(source), playhead)
+            }
+            None if looping => {
+                let (source, playhead) =
+                    track_position(audio_source.decoder().repeat_infinite().convert_samples());
+                (Box::new(source), playhead)
+            }
+            None => {
+                let (source, playhead) = track_position(audio_source.decoder().convert_samples());
+                (Box::new(source), playhead)
+            }
+        };
+
+        let start = settings.start_at.map_or(0, |start| clock.frame_at(start));
+        if start > 0 && start < clock.frame_at(clock.render_time()) {
+            warn!("Sound scheduled at {:?} started late.", settings.start_at);
+        }
+
+        // the sink plays into the audio graph, which mixes it with the other sounds
+        let (sink, sink_output) = Sink::new_idle();
+        graph.send(GraphCommand::AddInput {
+            entity,
+            source: Box::new(sink_output),
+            routing: routing(sound_output(output_bus, Some(settings), &mixer), sends),
+            start,
+        });
         // audio data is available (has loaded), begin playback and insert sink component
         if settings.spatial {
             let (left_ear, right_ear) = ear_positions.get();
@@ -138,25 +255,31 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 );
             }
 
-            let emitter_translation = if let Some(emitter_transform) = maybe_emitter_transform {
-                (emitter_transform.translation() * ear_positions.scale.0).into()
-            } else {
-                warn!("Spatial AudioBundle with no GlobalTransform component. Using zero.");
-                Vec3::ZERO.into()
-            };
+            let (emitter_translation, emitter_forward) =
+                if let Some(emitter_transform) = maybe_emitter_transform {
+                    (
+                        emitter_transform.translation() * ear_positions.scale.0,
+                        emitter_transform.forward(),
+                    )
+                } else {
+                    warn!("Spatial AudioBundle with no GlobalTransform component. Using zero.");
+                    (Vec3::ZERO, Vec3::NEG_Z)
+                };
 
-            let sink = match SpatialSink::try_new(
-                stream_handle,
-                emitter_translation,
-                left_ear.into(),
-                right_ear.into(),
-            ) {
-                Ok(sink) => sink,
-                Err(err) => {
-                    warn!("Error creating spatial sink: {err:?}");
-                    continue;
-                }
-            };
+            let sink = SpatialAudioSink::new(
+                sink,
+                SpatialParameters {
+                    emitter: emitter_translation,
+                    emitter_forward,
+                    left_ear,
+                    right_ear,
+                    hrtf: ear_positions.hrtf(),
+                    speed_of_sound: speed_of_sound.0,
+                    settings: emitter_settings.cloned().unwrap_or_default(),
+                    ..Default::default()
+                },
+                playhead,
+            );
 
             sink.set_speed(settings.speed);
             match settings.volume {
@@ -168,39 +291,25 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 sink.pause();
             }
 
+            sink.append(source);
             match settings.mode {
-                PlaybackMode::Loop => {
-                    sink.append(audio_source.decoder().repeat_infinite());
-                    commands.entity(entity).insert(SpatialAudioSink { sink });
-                }
-                PlaybackMode::Once => {
-                    sink.append(audio_source.decoder());
-                    commands.entity(entity).insert(SpatialAudioSink { sink });
+                PlaybackMode::Loop | PlaybackMode::Once => {
+                    commands.entity(entity).insert(sink);
                 }
                 PlaybackMode::Despawn => {
-                    sink.append(audio_source.decoder());
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((SpatialAudioSink { sink }, PlaybackDespawnMarker));
+                        .insert((sink, PlaybackDespawnMarker));
                 }
                 PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((SpatialAudioSink { sink }, PlaybackRemoveMarker));
+                        .insert((sink, PlaybackRemoveMarker));
                 }
             };
         } else {
-            let sink = match Sink::try_new(stream_handle) {
-                Ok(sink) => sink,
-                Err(err) => {
-                    warn!("Error creating sink: {err:?}");
-                    continue;
-                }
-            };
-
             sink.set_speed(settings.speed);
             match settings.volume {
                 Volume::Relative(vol) => sink.set_volume(vol.0 * global_volume.volume.0),
@@ -211,28 +320,23 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 sink.pause();
             }
 
+            let sink = AudioSink { sink, playhead };
+            sink.append(source);
             match settings.mode {
-                PlaybackMode::Loop => {
-                    sink.append(audio_source.decoder().repeat_infinite());
-                    commands.entity(entity).insert(AudioSink { sink });
-                }
-                PlaybackMode::Once => {
-                    sink.append(audio_source.decoder());
-                    commands.entity(entity).insert(AudioSink { sink });
+                PlaybackMode::Loop | PlaybackMode::Once => {
+                    commands.entity(entity).insert(sink);
                 }
                 PlaybackMode::Despawn => {
-                    sink.append(audio_source.decoder());
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((AudioSink { sink }, PlaybackDespawnMarker));
+                        .insert((sink, PlaybackDespawnMarker));
                 }
                 PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
                     commands
                         .entity(entity)
                         // PERF: insert as bundle to reduce archetype moves
-                        .insert((AudioSink { sink }, PlaybackRemoveMarker));
+                        .insert((sink, PlaybackRemoveMarker));
                 }
             };
         }
@@ -286,39 +390,66 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
 
 /// Run Condition to only play audio if the audio output is available
 pub(crate) fn audio_output_available(audio_output: Res<AudioOutput>) -> bool {
-    audio_output.stream_handle.is_some()
+    audio_output.device.is_some()
 }
 
-/// Updates spatial audio sinks when emitter positions change.
+/// Updates spatial audio sinks when emitters move or their [`SpatialEmitter`] changes.
 pub(crate) fn update_emitter_positions(
-    mut emitters: Query<(&mut GlobalTransform, &SpatialAudioSink), Changed<GlobalTransform>>,
+    emitters: Query<(
+        &GlobalTransform,
+        &SpatialAudioSink,
+        Option<Ref<SpatialEmitter>>,
+    )>,
+    mut removed_settings: RemovedComponents<SpatialEmitter>,
     spatial_scale: Res<SpatialScale>,
+    time: Res<Time>,
 ) {
-    for (transform, sink) in emitters.iter_mut() {
+    for entity in removed_settings.read() {
+        if let Ok((_, sink, None)) = emitters.get(entity) {
+            sink.parameters().settings = SpatialEmitter::default();
+        }
+    }
+
+    let delta = time.delta_seconds();
+    for (transform, sink, settings) in &emitters {
         let translation = transform.translation() * spatial_scale.0;
-        sink.set_emitter_position(translation);
+        let mut parameters = sink.parameters();
+        parameters.emitter_velocity = if delta > 0.0 {
+            (translation - parameters.emitter) / delta
+        } else {
+            Vec3::ZERO
+        };
+        parameters.emitter = translation;
+        parameters.emitter_forward = transform.forward();
+        if let Some(settings) = settings.filter(DetectChanges::is_changed) {
+            parameters.settings = settings.clone();
+        }
     }
 }
 
-/// Updates spatial audio sink ear positions when spatial listeners change.
+/// Updates spatial audio sinks when the spatial listener moves or changes.
 pub(crate) fn update_listener_positions(
-    mut emitters: Query<&SpatialAudioSink>,
-    changed_listener: Query<
-        (),
-        (
-            Or<(Changed<SpatialListener>, Changed<GlobalTransform>)>,
-            With<SpatialListener>,
-        ),
-    >,
+    emitters: Query<&SpatialAudioSink>,
     ear_positions: EarPositions,
+    speed_of_sound: Res<SpeedOfSound>,
+    time: Res<Time>,
 ) {
-    if !ear_positions.scale.is_changed() && changed_listener.is_empty() {
-        return;
-    }
-
     let (left_ear, right_ear) = ear_positions.get();
+    let hrtf = ear_positions.hrtf();
+    let center = (left_ear + right_ear) / 2.0;
+    let delta = time.delta_seconds();
 
-    for sink in emitters.iter_mut() {
-        sink.set_ears_position(left_ear, right_ear);
+    for sink in &emitters {
+        let mut parameters = sink.parameters();
+        let previous_center = (parameters.left_ear + parameters.right_ear) / 2.0;
+        parameters.listener_velocity = if delta > 0.0 {
+            (center - previous_center) / delta
+        } else {
+            Vec3::ZERO
+        };
+        parameters.left_ear = left_ear;
+        parameters.right_ear = right_ear;
+        parameters.hrtf = hrtf;
+        parameters.speed_of_sound = speed_of_sound.0;
     }
 }
@@ -1,6 +1,6 @@
 use bevy_asset::{
     io::{AsyncReadExt, Reader},
-    Asset, AssetLoader, LoadContext,
+    Asset, AssetLoader, AssetMemoryUsage, LoadContext,
 };
 use bevy_reflect::TypePath;
 use bevy_utils::BoxedFuture;
@@ -21,6 +21,12 @@ pub struct AudioSource {
     pub bytes: Arc<[u8]>,
 }
 
+impl AssetMemoryUsage for AudioSource {
+    fn memory_usage(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
 impl AsRef<[u8]> for AudioSource {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
@@ -94,6 +100,17 @@ pub trait Decodable: Send + Sync + 'static {
 
     /// Build and return a [`Self::Decoder`] of the implementing type
     fn decoder(&self) -> Self::Decoder;
+
+    /// Returns an owned copy of the sound, used to decode it in chunks on a background task
+    /// when it's played with [`PlaybackSettings::streaming`](crate::PlaybackSettings::streaming).
+    ///
+    /// Returns `None` by default, in which case the sound is decoded on the audio thread.
+    fn streamed(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 impl Decodable for AudioSource {
@@ -103,6 +120,10 @@ impl Decodable for AudioSource {
     fn decoder(&self) -> Self::Decoder {
         rodio::Decoder::new(Cursor::new(self.clone())).unwrap()
     }
+
+    fn streamed(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
 /// A trait that allows adding a custom audio source to the object.
@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use bevy_ecs::{entity::Entity, prelude::*};
+use bevy_reflect::prelude::*;
+
+use crate::{
+    audio_output::AudioOutput,
+    graph::{GraphCommand, Routing},
+    AudioEffect, AudioMixer, AudioSink, PlaybackSettings, SpatialAudioSink, VolumeLevel,
+};
+
+/// A node of the audio graph mixing sounds and other buses together.
+///
+/// Route sounds and buses to a bus with [`OutputBus`] and [`AudioSends`]. The mixed signal goes
+/// through the [`AudioEffects`] of the bus, is scaled by its volume, and is then passed on to
+/// the [`OutputBus`] and [`AudioSends`] of the bus itself. A bus without an [`OutputBus`] plays
+/// on the audio device.
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioBundle, AudioBus, AudioEffect, AudioEffects, AudioSend, AudioSends, OutputBus};
+/// # use bevy_asset::AssetServer;
+/// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     let reverb = commands
+///         .spawn((AudioBus::default(), AudioEffects(vec![AudioEffect::reverb(0.8)])))
+///         .id();
+///     let music = commands
+///         .spawn((
+///             AudioBus::default(),
+///             AudioEffects(vec![AudioEffect::low_pass(2_000.0)]),
+///             AudioSends(vec![AudioSend { bus: reverb, level: 0.3 }]),
+///         ))
+///         .id();
+///     commands.spawn((
+///         AudioBundle {
+///             source: asset_server.load("music.ogg"),
+///             ..Default::default()
+///         },
+///         OutputBus(music),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioBus {
+    /// The volume applied to the signal of the bus, after its effects.
+    pub volume: VolumeLevel,
+}
+
+impl AudioBus {
+    /// Creates a bus with the given volume.
+    pub fn new(volume: f32) -> Self {
+        Self {
+            volume: VolumeLevel::new(volume),
+        }
+    }
+}
+
+/// The [`AudioBus`] a sound or bus outputs to.
+///
+/// Without this component, sounds and buses play directly on the audio device. If the bus
+/// doesn't exist, or if routing to it would create a cycle, the signal plays on the audio
+/// device instead.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct OutputBus(pub Entity);
+
+// `Entity` has no meaningful default, but reflecting the component requires one.
+impl FromWorld for OutputBus {
+    fn from_world(_world: &mut World) -> Self {
+        OutputBus(Entity::PLACEHOLDER)
+    }
+}
+
+/// A copy of the signal of a sound or bus, sent to an additional [`AudioBus`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct AudioSend {
+    /// The bus receiving the signal.
+    pub bus: Entity,
+    /// The volume of the copy of the signal.
+    pub level: f32,
+}
+
+/// Sends copies of the signal of a sound or bus to other [`AudioBus`]es, in addition to its
+/// [`OutputBus`].
+///
+/// Sends are typically used to share a single reverb bus between many sounds.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioSends(pub Vec<AudioSend>);
+
+/// The effects applied in order to the signal of an [`AudioBus`].
+///
+/// Effects can be changed at any time, including while sounds are playing through the bus.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioEffects(pub Vec<AudioEffect>);
+
+/// Lowers the volume of an [`AudioBus`] while another bus is playing.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct Ducking {
+    /// The bus that lowers the volume while it plays.
+    pub trigger: Entity,
+    /// The volume multiplier applied while the trigger bus plays.
+    pub volume: f32,
+    /// The level in decibels above which the trigger bus is considered to be playing.
+    pub threshold: f32,
+    /// How fast the volume is lowered once the trigger bus starts playing.
+    pub attack: Duration,
+    /// How fast the volume is restored once the trigger bus stops playing.
+    pub release: Duration,
+}
+
+impl Ducking {
+    /// Lowers the volume to `volume` while `trigger` plays, with a smooth release.
+    pub const fn new(trigger: Entity, volume: f32) -> Self {
+        Self {
+            trigger,
+            volume,
+            threshold: -50.0,
+            attack: Duration::from_millis(50),
+            release: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Side-chain ducking of an [`AudioBus`]: its volume is lowered while other buses play, e.g. to
+/// make dialogue easier to hear over music.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioDucking(pub Vec<Ducking>);
+
+/// Returns where the signal of a sound or bus goes.
+pub(crate) fn routing(output: Option<Entity>, sends: Option<&AudioSends>) -> Routing {
+    Routing {
+        output,
+        sends: sends.map(|sends| sends.0.clone()).unwrap_or_default(),
+    }
+}
+
+/// Returns the bus a sound outputs to: its [`OutputBus`] if any, or its mixer bus.
+pub(crate) fn sound_output(
+    output: Option<&OutputBus>,
+    settings: Option<&PlaybackSettings>,
+    mixer: &AudioMixer,
+) -> Option<Entity> {
+    output
+        .map(|output| output.0)
+        .or_else(|| Some(mixer.bus(settings?.bus?)))
+}
+
+/// Sends the changes to [`AudioBus`]es to the audio graph.
+pub(crate) fn update_audio_buses(
+    audio_output: Res<AudioOutput>,
+    buses: Query<(
+        Entity,
+        Ref<AudioBus>,
+        Option<Ref<OutputBus>>,
+        Option<Ref<AudioSends>>,
+        Option<Ref<AudioEffects>>,
+        Option<Ref<AudioDucking>>,
+    )>,
+    mut removed_buses: RemovedComponents<AudioBus>,
+    mut removed_outputs: RemovedComponents<OutputBus>,
+    mut removed_sends: RemovedComponents<AudioSends>,
+    mut removed_effects: RemovedComponents<AudioEffects>,
+    mut removed_ducking: RemovedComponents<AudioDucking>,
+) {
+    let graph = &audio_output.graph;
+
+    for entity in removed_buses.read() {
+        if !buses.contains(entity) {
+            graph.send(GraphCommand::RemoveBus { entity });
+        }
+    }
+
+    // Removed components are the only changes `Ref` doesn't detect
+    let removed: Vec<Entity> = removed_outputs
+        .read()
+        .chain(removed_sends.read())
+        .chain(removed_effects.read())
+        .chain(removed_ducking.read())
+        .collect();
+
+    for (entity, bus, output, sends, effects, ducking) in &buses {
+        let changed = bus.is_changed()
+            || output.as_ref().is_some_and(DetectChanges::is_changed)
+            || sends.as_ref().is_some_and(DetectChanges::is_changed)
+            || effects.as_ref().is_some_and(DetectChanges::is_changed)
+            || ducking.as_ref().is_some_and(DetectChanges::is_changed)
+            || removed.contains(&entity);
+        if !changed {
+            continue;
+        }
+        graph.send(GraphCommand::SetBus {
+            entity,
+            volume: bus.volume.get(),
+            routing: routing(output.map(|output| output.0), sends.as_deref()),
+            effects: effects.map(|effects| effects.0.clone()).unwrap_or_default(),
+            ducking: ducking.map(|ducking| ducking.0.clone()).unwrap_or_default(),
+        });
+    }
+}
+
+/// Sends the changes to the routing of playing sounds to the audio graph.
+pub(crate) fn update_audio_routing(
+    audio_output: Res<AudioOutput>,
+    mixer: Res<AudioMixer>,
+    sounds: Query<
+        (
+            Entity,
+            Option<&OutputBus>,
+            Option<&AudioSends>,
+            Option<&PlaybackSettings>,
+        ),
+        (
+            Or<(With<AudioSink>, With<SpatialAudioSink>)>,
+            Without<AudioBus>,
+            Or<(Changed<OutputBus>, Changed<AudioSends>)>,
+        ),
+    >,
+    all_sounds: Query<
+        (
+            Entity,
+            Option<&OutputBus>,
+            Option<&AudioSends>,
+            Option<&PlaybackSettings>,
+        ),
+        (
+            Or<(With<AudioSink>, With<SpatialAudioSink>)>,
+            Without<AudioBus>,
+        ),
+    >,
+    mut removed_outputs: RemovedComponents<OutputBus>,
+    mut removed_sends: RemovedComponents<AudioSends>,
+) {
+    let graph = &audio_output.graph;
+
+    let removed = removed_outputs
+        .read()
+        .chain(removed_sends.read())
+        .filter_map(|entity| all_sounds.get(entity).ok());
+    for (entity, output, sends, settings) in sounds.iter().chain(removed) {
+        graph.send(GraphCommand::SetInputRouting {
+            entity,
+            routing: routing(sound_output(output, settings, &mixer), sends),
+        });
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::Instant;
+
+use crate::{audio_output::AudioOutput, AudioSinkPlayback};
+
+/// The state of the [`AudioClock`], updated by the audio graph.
+#[derive(Default)]
+pub(crate) struct ClockState {
+    sample_rate: u32,
+    /// The number of frames mixed by the graph.
+    rendered: AtomicU64,
+    /// The first frame of the last buffer requested by the audio device, and when it was
+    /// requested.
+    sync: Mutex<Option<(u64, Instant)>>,
+}
+
+impl ClockState {
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn set_rendered(&self, frames: u64) {
+        self.rendered.store(frames, Ordering::Relaxed);
+    }
+
+    /// Records that the audio device requested a new buffer starting at `frame`.
+    pub(crate) fn sync(&self, frame: u64, instant: Instant) {
+        // Never block the audio thread, the next buffer will sync instead
+        if let Ok(mut sync) = self.sync.try_lock() {
+            *sync = Some((frame, instant));
+        }
+    }
+}
+
+/// The timeline of the audio output, to schedule sounds with sample accuracy.
+///
+/// Times on this clock count from when the audio output started, and advance with the audio
+/// device rather than with the frame time. Start a sound at a given time with
+/// [`PlaybackSettings::start_at`](crate::PlaybackSettings::start_at).
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioBundle, AudioClock, AudioSource, PlaybackSettings, Tempo};
+/// # use bevy_asset::Handle;
+/// #[derive(Resource)]
+/// struct Song {
+///     tempo: Tempo,
+///     stinger: Handle<AudioSource>,
+/// }
+///
+/// fn play_stinger(song: Res<Song>, clock: Res<AudioClock>, mut commands: Commands) {
+///     // Leave a frame of margin for the sound to reach the audio thread
+///     let start = song
+///         .tempo
+///         .next_beat(clock.render_time() + Duration::from_millis(20));
+///     commands.spawn(AudioBundle {
+///         source: song.stinger.clone(),
+///         settings: PlaybackSettings::DESPAWN.with_start_at(start),
+///     });
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct AudioClock {
+    state: Arc<ClockState>,
+    /// The latency added by the system after Bevy hands audio to the device, for example by
+    /// the mixer of the OS or by wireless headphones.
+    ///
+    /// This isn't measurable, so it defaults to zero. Games that need tight synchronization
+    /// usually let players calibrate it.
+    pub output_latency: Duration,
+}
+
+impl AudioClock {
+    pub(crate) fn new(state: Arc<ClockState>) -> Self {
+        Self {
+            state,
+            output_latency: Duration::ZERO,
+        }
+    }
+
+    /// Returns the sample rate of the audio output, or zero if there is no audio device.
+    pub fn sample_rate(&self) -> u32 {
+        self.state.sample_rate
+    }
+
+    /// Returns the time of the next frame to be mixed.
+    ///
+    /// This is the earliest time a sound can start. Sounds take up to a frame to reach the
+    /// audio thread, so they should be scheduled a little later than this.
+    pub fn render_time(&self) -> Duration {
+        self.frame_time(self.state.rendered.load(Ordering::Relaxed))
+    }
+
+    /// Returns the time of the audio currently heard.
+    ///
+    /// This is behind [`render_time`](Self::render_time) by the buffer of the audio device
+    /// and the [`output_latency`](Self::output_latency).
+    pub fn now(&self) -> Duration {
+        let Some((frame, instant)) = *self.state.sync.lock().unwrap() else {
+            return Duration::ZERO;
+        };
+        let now = self.frame_time(frame) + instant.elapsed();
+        now.min(self.render_time())
+            .saturating_sub(self.output_latency)
+    }
+
+    /// Returns how long it takes for mixed audio to be heard.
+    pub fn latency(&self) -> Duration {
+        self.render_time().saturating_sub(self.now())
+    }
+
+    /// Returns the position of the audio currently heard from a sink, compensating for the
+    /// [`latency`](Self::latency) of the audio output.
+    pub fn heard_position(&self, sink: &impl AudioSinkPlayback) -> Duration {
+        sink.position().saturating_sub(self.latency())
+    }
+
+    /// Returns the index of the frame played at `time`.
+    pub(crate) fn frame_at(&self, time: Duration) -> u64 {
+        (time.as_secs_f64() * self.state.sample_rate as f64).round() as u64
+    }
+
+    fn frame_time(&self, frame: u64) -> Duration {
+        if self.state.sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(frame as f64 / self.state.sample_rate as f64)
+    }
+}
+
+impl FromWorld for AudioClock {
+    fn from_world(world: &mut World) -> Self {
+        world.resource::<AudioOutput>().clock.clone()
+    }
+}
+
+/// A musical tempo on the [`AudioClock`], to schedule sounds on beats.
+///
+/// Tempos slower than [`Tempo::MIN_BEATS_PER_MINUTE`], including zero, negative and `NaN`
+/// values of [`beats_per_minute`](Self::beats_per_minute), are treated as that minimum.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct Tempo {
+    /// The number of beats per minute.
+    pub beats_per_minute: f64,
+    /// The time of the first beat on the [`AudioClock`].
+    pub start: Duration,
+}
+
+impl Tempo {
+    /// The slowest tempo, a beat every hundred minutes.
+    pub const MIN_BEATS_PER_MINUTE: f64 = 0.01;
+
+    /// Creates a tempo with its first beat at `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `beats_per_minute` is not a positive number.
+    pub fn new(beats_per_minute: f64, start: Duration) -> Self {
+        assert!(
+            beats_per_minute > 0.0,
+            "the tempo must be positive, got {beats_per_minute} beats per minute"
+        );
+        Self {
+            beats_per_minute,
+            start,
+        }
+    }
+
+    /// Returns the number of beats per minute, at least [`Tempo::MIN_BEATS_PER_MINUTE`].
+    fn clamped_beats_per_minute(&self) -> f64 {
+        self.beats_per_minute.max(Self::MIN_BEATS_PER_MINUTE)
+    }
+
+    /// Returns the duration of a beat.
+    pub fn beat_duration(&self) -> Duration {
+        Duration::from_secs_f64(60.0 / self.clamped_beats_per_minute())
+    }
+
+    /// Returns the beat at `time`, counting from zero at [`start`](Self::start). The fractional
+    /// part is the progress through the beat.
+    pub fn beat_at(&self, time: Duration) -> f64 {
+        (time.as_secs_f64() - self.start.as_secs_f64()) * self.clamped_beats_per_minute() / 60.0
+    }
+
+    /// Returns the time of `beat`, or zero if it's before the clock started.
+    pub fn time_of_beat(&self, beat: f64) -> Duration {
+        let time = self.start.as_secs_f64() + beat * 60.0 / self.clamped_beats_per_minute();
+        Duration::from_secs_f64(time.max(0.0))
+    }
+
+    /// Returns the time of the first beat at or after `time`.
+    pub fn next_beat(&self, time: Duration) -> Duration {
+        self.next_multiple(time, 1.0)
+    }
+
+    /// Returns the time of the first multiple of `beats` at or after `time`, for example `4.0`
+    /// for the next bar in 4/4, or `0.5` for the next eighth note.
+    pub fn next_multiple(&self, time: Duration, beats: f64) -> Duration {
+        // Tolerate rounding errors, so that times on a beat stay on it
+        let beat = (self.beat_at(time) / beats - 1e-9).ceil() * beats;
+        self.time_of_beat(beat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Tempo;
+
+    fn assert_near(a: Duration, b: Duration) {
+        assert!(
+            a.abs_diff(b) < Duration::from_nanos(10),
+            "{a:?} is not {b:?}"
+        );
+    }
+
+    #[test]
+    fn next_beat() {
+        // A beat every half second, starting at one second
+        let tempo = Tempo::new(120.0, Duration::from_secs(1));
+        assert_eq!(tempo.beat_duration(), Duration::from_millis(500));
+
+        assert_near(
+            tempo.next_beat(Duration::from_millis(1100)),
+            Duration::from_millis(1500),
+        );
+        assert_near(
+            tempo.next_beat(Duration::from_millis(1499)),
+            Duration::from_millis(1500),
+        );
+        assert_near(
+            tempo.next_beat(Duration::from_millis(1501)),
+            Duration::from_millis(2000),
+        );
+        // Before the first beat
+        assert_near(
+            tempo.next_beat(Duration::from_millis(300)),
+            Duration::from_millis(500),
+        );
+        assert_near(tempo.next_beat(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn next_beat_on_beat() {
+        let tempo = Tempo::new(120.0, Duration::from_secs(1));
+        assert_near(
+            tempo.next_beat(Duration::from_secs(2)),
+            Duration::from_secs(2),
+        );
+
+        // Times from `time_of_beat` stay on their beat, instead of skipping to the next one
+        let on_beat = tempo.time_of_beat(7.0);
+        assert_near(on_beat, Duration::from_millis(4500));
+        assert_near(tempo.next_beat(on_beat), on_beat);
+        assert_near(tempo.next_multiple(on_beat, 0.5), on_beat);
+
+        // Including with a tempo whose beats don't fall on whole nanoseconds
+        let tempo = Tempo::new(97.3, Duration::from_millis(250));
+        for beat in 0..100 {
+            let time = tempo.time_of_beat(beat as f64);
+            assert_near(tempo.next_beat(time), time);
+        }
+    }
+
+    #[test]
+    fn next_multiple() {
+        let tempo = Tempo::new(120.0, Duration::from_secs(1));
+        // The next bar in 4/4
+        assert_near(
+            tempo.next_multiple(Duration::from_millis(1100), 4.0),
+            Duration::from_secs(3),
+        );
+        assert_near(
+            tempo.next_multiple(Duration::from_secs(3), 4.0),
+            Duration::from_secs(3),
+        );
+        // The next eighth note
+        assert_near(
+            tempo.next_multiple(Duration::from_millis(1100), 0.5),
+            Duration::from_millis(1250),
+        );
+        assert_near(
+            tempo.next_multiple(Duration::from_millis(1250), 0.5),
+            Duration::from_millis(1250),
+        );
+    }
+
+    #[test]
+    fn invalid_tempo_is_clamped() {
+        for beats_per_minute in [0.0, -60.0, f64::NAN] {
+            let tempo = Tempo {
+                beats_per_minute,
+                start: Duration::from_secs(1),
+            };
+            assert_eq!(tempo.beat_duration(), Duration::from_secs(6000));
+            assert_near(
+                tempo.next_beat(Duration::from_secs(2)),
+                Duration::from_secs(6001),
+            );
+            assert_near(tempo.time_of_beat(-1.0), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_zero_tempo() {
+        Tempo::new(0.0, Duration::ZERO);
+    }
+}
@@ -0,0 +1,564 @@
+use std::{
+    sync::mpsc::{self, Receiver, SyncSender},
+    time::Duration,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::tracing::warn;
+use rodio::{
+    cpal::{
+        default_host,
+        traits::{DeviceTrait, HostTrait, StreamTrait},
+        BuildStreamError, Device, FromSample, InputCallbackInfo, Sample, SampleFormat, SizedSample,
+        Stream, StreamConfig,
+    },
+    OutputStream,
+};
+
+use crate::audio_output::AudioOutput;
+
+/// How often the default audio devices are checked for changes.
+const DEFAULT_DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many polls of the default devices happen between two refreshes of the whole list.
+///
+/// Listing devices is slow on some platforms, and noisy with ALSA.
+const POLLS_PER_DEVICE_LIST: u32 = 10;
+
+/// How many buffers of captured audio are kept if the app doesn't read them.
+const CAPTURE_BUFFERS: usize = 32;
+
+/// An audio device to play or capture audio with.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash)]
+pub enum AudioDevice {
+    /// The default device of the system, followed when it changes.
+    #[default]
+    Default,
+    /// The device with this name, as listed in [`AudioDevices`]. The default device is used
+    /// while it isn't available.
+    Named(String),
+}
+
+/// The audio devices used to play and capture audio.
+///
+/// Changing this resource switches devices right away, without interrupting playing sounds.
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AudioDeviceSettings {
+    /// The device playing audio.
+    pub output: AudioDevice,
+    /// The device captured into the [`AudioInput`].
+    pub input: AudioDevice,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct DeviceList {
+    outputs: Vec<String>,
+    inputs: Vec<String>,
+    default_output: Option<String>,
+    default_input: Option<String>,
+}
+
+impl DeviceList {
+    fn enumerate() -> Self {
+        let host = default_host();
+        let names = |devices: &mut dyn Iterator<Item = Device>| {
+            devices.filter_map(|device| device.name().ok()).collect()
+        };
+        let mut list = Self {
+            outputs: host
+                .output_devices()
+                .map(|mut devices| names(&mut devices))
+                .unwrap_or_default(),
+            inputs: host
+                .input_devices()
+                .map(|mut devices| names(&mut devices))
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        list.update_defaults();
+        list
+    }
+
+    /// Updates the default devices, returning `true` if they changed.
+    fn update_defaults(&mut self) -> bool {
+        let host = default_host();
+        let default_output = host.default_output_device().and_then(|d| d.name().ok());
+        let default_input = host.default_input_device().and_then(|d| d.name().ok());
+        let changed = default_output != self.default_output || default_input != self.default_input;
+        self.default_output = default_output;
+        self.default_input = default_input;
+        changed
+    }
+}
+
+/// The audio devices of the system.
+///
+/// The list is refreshed in the background every few seconds, and right away when the default
+/// devices change. An [`AudioDeviceEvent::DevicesChanged`] is sent when it changes. The list
+/// stays empty if no audio device was available when the app started.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AudioDevices {
+    list: DeviceList,
+}
+
+impl AudioDevices {
+    /// Returns the names of the devices that can play audio.
+    pub fn outputs(&self) -> &[String] {
+        &self.list.outputs
+    }
+
+    /// Returns the names of the devices that can capture audio.
+    pub fn inputs(&self) -> &[String] {
+        &self.list.inputs
+    }
+
+    /// Returns the name of the default output device of the system.
+    pub fn default_output(&self) -> Option<&str> {
+        self.list.default_output.as_deref()
+    }
+
+    /// Returns the name of the default input device of the system.
+    pub fn default_input(&self) -> Option<&str> {
+        self.list.default_input.as_deref()
+    }
+
+    /// Returns the name of the output device to use for `device`.
+    fn resolve_output<'a>(&'a self, device: &'a AudioDevice) -> Option<&'a str> {
+        match device {
+            AudioDevice::Named(name) if self.list.outputs.contains(name) => Some(name),
+            _ => self.default_output(),
+        }
+    }
+
+    /// Returns the name of the input device to use for `device`.
+    fn resolve_input<'a>(&'a self, device: &'a AudioDevice) -> Option<&'a str> {
+        match device {
+            AudioDevice::Named(name) if self.list.inputs.contains(name) => Some(name),
+            _ => self.default_input(),
+        }
+    }
+}
+
+/// A change to the audio devices.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub enum AudioDeviceEvent {
+    /// Devices were connected or disconnected, or the default devices changed.
+    DevicesChanged,
+    /// Audio now plays on the device with this name.
+    OutputChanged(String),
+    /// The [`AudioInput`] now captures the device with this name.
+    InputChanged(String),
+}
+
+/// Audio captured from an input device, such as a microphone.
+///
+/// Capture starts once [`enabled`](Self::enabled) is set, on the input device of the
+/// [`AudioDeviceSettings`]. The samples captured during a frame are available until the next
+/// frame, so they must be read every frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::AudioInput;
+/// fn react_to_microphone(input: Res<AudioInput>) {
+///     if input.level() > 0.5 {
+///         println!("Loud noise!");
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct AudioInput {
+    /// Captures audio while `true`.
+    ///
+    /// Some platforms ask the user for permission to use the microphone when capture starts.
+    pub enabled: bool,
+    samples: Vec<f32>,
+    channels: u16,
+    sample_rate: u32,
+    device: Option<String>,
+}
+
+impl AudioInput {
+    /// Returns the interleaved samples captured since the last frame.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Returns the number of channels of the [`samples`](Self::samples).
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Returns the sample rate of the [`samples`](Self::samples).
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the name of the device being captured, if any.
+    pub fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    /// Returns the peak amplitude of the samples captured since the last frame.
+    pub fn level(&self) -> f32 {
+        self.samples
+            .iter()
+            .fold(0.0, |level, sample| level.max(sample.abs()))
+    }
+}
+
+struct InputCapture {
+    // Capture stops when the stream is dropped
+    _stream: Stream,
+    device: String,
+    samples: Receiver<Vec<f32>>,
+}
+
+/// The streams of the audio devices, which must stay on the main thread.
+#[derive(Default)]
+pub(crate) struct AudioStreams {
+    pub(crate) output: Option<OutputStream>,
+    input: Option<InputCapture>,
+    /// Set when capture failed to start, so it isn't retried until the devices change.
+    input_failed: bool,
+    device_lists: Option<Receiver<DeviceList>>,
+}
+
+impl AudioStreams {
+    /// Starts refreshing the list of devices in the background.
+    pub(crate) fn watch_devices(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        self.device_lists = Some(receiver);
+
+        #[cfg(target_arch = "wasm32")]
+        let _ = sender.send(DeviceList::enumerate());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let watcher = std::thread::Builder::new()
+                .name("bevy_audio devices".to_string())
+                .spawn(move || {
+                    let mut list = DeviceList::enumerate();
+                    if sender.send(list.clone()).is_err() {
+                        return;
+                    }
+                    let mut polls = 0;
+                    loop {
+                        std::thread::sleep(DEFAULT_DEVICE_POLL_INTERVAL);
+                        polls += 1;
+                        let previous = list.clone();
+                        if list.update_defaults() || polls == POLLS_PER_DEVICE_LIST {
+                            list = DeviceList::enumerate();
+                            polls = 0;
+                        }
+                        if list != previous && sender.send(list.clone()).is_err() {
+                            return;
+                        }
+                    }
+                });
+            if let Err(err) = watcher {
+                warn!("Error watching audio devices: {err}");
+            }
+        }
+    }
+}
+
+/// Returns the output device named `name`.
+pub(crate) fn find_output_device(name: &str) -> Option<Device> {
+    default_host()
+        .output_devices()
+        .ok()?
+        .find(|device| device.name().is_ok_and(|n| n == name))
+}
+
+/// Returns the input device named `name`.
+fn find_input_device(name: &str) -> Option<Device> {
+    default_host()
+        .input_devices()
+        .ok()?
+        .find(|device| device.name().is_ok_and(|n| n == name))
+}
+
+/// Updates the [`AudioDevices`] with the latest list of devices.
+pub(crate) fn update_audio_devices(
+    streams: NonSend<AudioStreams>,
+    mut devices: ResMut<AudioDevices>,
+    mut events: EventWriter<AudioDeviceEvent>,
+) {
+    let Some(lists) = &streams.device_lists else {
+        return;
+    };
+    if let Some(list) = lists.try_iter().last() {
+        devices.list = list;
+        events.send(AudioDeviceEvent::DevicesChanged);
+    }
+}
+
+/// Switches the audio output when the [`AudioDeviceSettings`] or the default device change.
+pub(crate) fn select_audio_output(
+    settings: Res<AudioDeviceSettings>,
+    devices: Res<AudioDevices>,
+    mut output: ResMut<AudioOutput>,
+    mut streams: NonSendMut<AudioStreams>,
+    mut events: EventWriter<AudioDeviceEvent>,
+) {
+    if !settings.is_changed() && !devices.is_changed() {
+        return;
+    }
+    if let AudioDevice::Named(name) = &settings.output {
+        if settings.is_changed() && !devices.list.outputs.contains(name) {
+            warn!("Audio output device {name} not found, using the default device.");
+        }
+    }
+
+    let Some(name) = devices.resolve_output(&settings.output) else {
+        return;
+    };
+    if output.device.as_deref() == Some(name) {
+        return;
+    }
+    let Some(device) = find_output_device(name) else {
+        return;
+    };
+    if output.play_on(&device, &mut streams) {
+        events.send(AudioDeviceEvent::OutputChanged(name.to_string()));
+    }
+}
+
+/// Starts and stops capturing audio into the [`AudioInput`], and collects the captured samples.
+pub(crate) fn update_audio_input(
+    settings: Res<AudioDeviceSettings>,
+    devices: Res<AudioDevices>,
+    mut input: ResMut<AudioInput>,
+    mut streams: NonSendMut<AudioStreams>,
+    mut events: EventWriter<AudioDeviceEvent>,
+) {
+    input.samples.clear();
+    if !input.enabled {
+        if streams.input.take().is_some() {
+            input.device = None;
+        }
+        streams.input_failed = false;
+        return;
+    }
+
+    if settings.is_changed() || devices.is_changed() {
+        streams.input_failed = false;
+    }
+    let capturing = streams
+        .input
+        .as_ref()
+        .map(|capture| capture.device.as_str());
+    let switch_to = devices
+        .resolve_input(&settings.input)
+        .filter(|name| !streams.input_failed && Some(*name) != capturing);
+    if let Some(name) = switch_to {
+        match find_input_device(name).and_then(|device| capture(&device, &mut input)) {
+            Some(capture) => {
+                input.device = Some(capture.device.clone());
+                events.send(AudioDeviceEvent::InputChanged(capture.device.clone()));
+                streams.input = Some(capture);
+            }
+            None => streams.input_failed = true,
+        }
+    }
+
+    if let Some(capture) = &streams.input {
+        for samples in capture.samples.try_iter() {
+            input.samples.extend(samples);
+        }
+    }
+}
+
+/// Starts capturing `device`, and sets the format of the [`AudioInput`] to the captured format.
+fn capture(device: &Device, input: &mut AudioInput) -> Option<InputCapture> {
+    let name = device.name().unwrap_or_default();
+    let config = match device.default_input_config() {
+        Ok(config) => config,
+        Err(err) => {
+            warn!("Error capturing audio device {name}: {err}");
+            return None;
+        }
+    };
+
+    let (sender, samples) = mpsc::sync_channel(CAPTURE_BUFFERS);
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        SampleFormat::F32 => build_input::<f32>(device, &stream_config, sender),
+        SampleFormat::F64 => build_input::<f64>(device, &stream_config, sender),
+        SampleFormat::I16 => build_input::<i16>(device, &stream_config, sender),
+        SampleFormat::I32 => build_input::<i32>(device, &stream_config, sender),
+        SampleFormat::U16 => build_input::<u16>(device, &stream_config, sender),
+        format => {
+            warn!("Audio device {name} captures unsupported {format} samples.");
+            return None;
+        }
+    };
+    let stream = match stream {
+        Ok(stream) => stream,
+        Err(err) => {
+            warn!("Error capturing audio device {name}: {err}");
+            return None;
+        }
+    };
+    if let Err(err) = stream.play() {
+        warn!("Error capturing audio device {name}: {err}");
+        return None;
+    }
+
+    input.channels = stream_config.channels;
+    input.sample_rate = stream_config.sample_rate.0;
+    Some(InputCapture {
+        _stream: stream,
+        device: name,
+        samples,
+    })
+}
+
+fn build_input<T>(
+    device: &Device,
+    config: &StreamConfig,
+    sender: SyncSender<Vec<f32>>,
+) -> Result<Stream, BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &InputCallbackInfo| {
+            // Drop audio rather than buffer it forever if the app stops reading it
+            let _ = sender.try_send(
+                data.iter()
+                    .map(|sample| f32::from_sample(*sample))
+                    .collect(),
+            );
+        },
+        |err| warn!("Error capturing audio: {err}"),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, schedule::Schedule};
+
+    use super::*;
+
+    fn devices() -> AudioDevices {
+        AudioDevices {
+            list: DeviceList {
+                outputs: vec!["Speakers".to_string(), "Headphones".to_string()],
+                inputs: vec!["Microphone".to_string()],
+                default_output: Some("Speakers".to_string()),
+                default_input: Some("Microphone".to_string()),
+            },
+        }
+    }
+
+    #[test]
+    fn named_devices_fall_back_to_default() {
+        let devices = devices();
+        let named = |name: &str| AudioDevice::Named(name.to_string());
+        assert_eq!(
+            devices.resolve_output(&AudioDevice::Default),
+            Some("Speakers")
+        );
+        assert_eq!(
+            devices.resolve_output(&named("Headphones")),
+            Some("Headphones")
+        );
+        assert_eq!(
+            devices.resolve_output(&named("Unplugged")),
+            Some("Speakers")
+        );
+        // An input device can't be used as an output
+        assert_eq!(
+            devices.resolve_output(&named("Microphone")),
+            Some("Speakers")
+        );
+        assert_eq!(
+            devices.resolve_input(&named("Microphone")),
+            Some("Microphone")
+        );
+        assert_eq!(
+            devices.resolve_input(&named("Headphones")),
+            Some("Microphone")
+        );
+
+        assert_eq!(
+            AudioDevices::default().resolve_output(&named("Headphones")),
+            None
+        );
+    }
+
+    #[test]
+    fn device_list_updates() {
+        let mut world = World::new();
+        world.init_resource::<AudioDevices>();
+        world.init_resource::<Events<AudioDeviceEvent>>();
+        let (sender, receiver) = mpsc::channel();
+        world.insert_non_send_resource(AudioStreams {
+            device_lists: Some(receiver),
+            ..Default::default()
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_audio_devices);
+
+        // Only the latest list is kept
+        sender.send(DeviceList::default()).unwrap();
+        sender.send(devices().list).unwrap();
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<AudioDevices>().outputs(),
+            devices().outputs()
+        );
+        let events = world
+            .resource_mut::<Events<AudioDeviceEvent>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![AudioDeviceEvent::DevicesChanged]);
+
+        // Nothing is sent until the devices change again
+        schedule.run(&mut world);
+        assert!(world.resource::<Events<AudioDeviceEvent>>().is_empty());
+    }
+
+    #[test]
+    fn input_level_is_the_peak() {
+        let input = AudioInput {
+            samples: vec![0.1, -0.6, 0.3],
+            ..Default::default()
+        };
+        assert_eq!(input.level(), 0.6);
+        assert_eq!(AudioInput::default().level(), 0.0);
+    }
+
+    #[test]
+    fn disabled_input_stops_capture() {
+        let mut world = World::new();
+        world.init_resource::<AudioDeviceSettings>();
+        world.insert_resource(devices());
+        world.insert_resource(AudioInput {
+            samples: vec![0.5; 4],
+            ..Default::default()
+        });
+        world.init_resource::<Events<AudioDeviceEvent>>();
+        world.insert_non_send_resource(AudioStreams {
+            input_failed: true,
+            ..Default::default()
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_audio_input);
+        schedule.run(&mut world);
+
+        // The samples of the last frame are cleared, and capture can be retried once enabled
+        let input = world.resource::<AudioInput>();
+        assert!(input.samples().is_empty());
+        assert_eq!(input.level(), 0.0);
+        assert!(!world.non_send_resource::<AudioStreams>().input_failed);
+        assert!(world.resource::<Events<AudioDeviceEvent>>().is_empty());
+    }
+}
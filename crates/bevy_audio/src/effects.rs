@@ -0,0 +1,594 @@
+use std::{f32::consts::PI, time::Duration};
+
+use bevy_reflect::prelude::*;
+
+/// A built-in effect processing the signal of an [`AudioBus`](crate::AudioBus).
+///
+/// Effects are added to a bus through its [`AudioEffects`](crate::AudioEffects) component.
+/// Changing the parameters of an effect takes effect while the audio is playing, without
+/// resetting the effect (so reverb and delay tails are preserved).
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum AudioEffect {
+    /// Attenuates the frequencies above the cutoff frequency.
+    LowPass {
+        /// The frequency in hertz above which the signal is attenuated.
+        cutoff_frequency: f32,
+        /// The resonance of the filter. [`FRAC_1_SQRT_2`](std::f32::consts::FRAC_1_SQRT_2)
+        /// gives a flat response.
+        q: f32,
+    },
+    /// Attenuates the frequencies below the cutoff frequency.
+    HighPass {
+        /// The frequency in hertz below which the signal is attenuated.
+        cutoff_frequency: f32,
+        /// The resonance of the filter. [`FRAC_1_SQRT_2`](std::f32::consts::FRAC_1_SQRT_2)
+        /// gives a flat response.
+        q: f32,
+    },
+    /// Repeats the signal after a delay, like an echo.
+    Delay {
+        /// The time between the signal and its first repeat.
+        time: Duration,
+        /// The portion of each repeat fed back into the delay, between `0.0` and `1.0` (excluded).
+        feedback: f32,
+        /// The balance between the unprocessed (`0.0`) and the delayed (`1.0`) signal.
+        mix: f32,
+    },
+    /// Simulates the reflections of the sound in a room.
+    Reverb {
+        /// The size of the simulated room, between `0.0` and `1.0`. Larger rooms have longer tails.
+        room_size: f32,
+        /// How much the high frequencies of the reflections are absorbed, between `0.0` and `1.0`.
+        damping: f32,
+        /// The balance between the unprocessed (`0.0`) and the reverberated (`1.0`) signal.
+        mix: f32,
+    },
+    /// Reduces the volume of the signal when it is louder than a threshold.
+    Compressor {
+        /// The level in decibels above which the signal is compressed.
+        threshold: f32,
+        /// How much the signal above the threshold is compressed, e.g. `4.0` for a 4:1 ratio.
+        ratio: f32,
+        /// How fast the compressor reacts when the signal gets louder.
+        attack: Duration,
+        /// How fast the compressor recovers when the signal gets quieter.
+        release: Duration,
+        /// The gain in decibels applied after compressing the signal.
+        makeup_gain: f32,
+    },
+}
+
+impl AudioEffect {
+    /// Creates a low-pass filter with a flat response.
+    pub const fn low_pass(cutoff_frequency: f32) -> Self {
+        Self::LowPass {
+            cutoff_frequency,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    /// Creates a high-pass filter with a flat response.
+    pub const fn high_pass(cutoff_frequency: f32) -> Self {
+        Self::HighPass {
+            cutoff_frequency,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    /// Creates a delay with a few audible repeats.
+    pub const fn delay(time: Duration) -> Self {
+        Self::Delay {
+            time,
+            feedback: 0.35,
+            mix: 0.35,
+        }
+    }
+
+    /// Creates a reverb for a room of the given size, between `0.0` and `1.0`.
+    pub const fn reverb(room_size: f32) -> Self {
+        Self::Reverb {
+            room_size,
+            damping: 0.5,
+            mix: 0.3,
+        }
+    }
+
+    /// Creates a compressor with a fast attack and release, and no makeup gain.
+    pub const fn compressor(threshold: f32, ratio: f32) -> Self {
+        Self::Compressor {
+            threshold,
+            ratio,
+            attack: Duration::from_millis(10),
+            release: Duration::from_millis(100),
+            makeup_gain: 0.0,
+        }
+    }
+}
+
+/// Returns the coefficient of a one-pole filter moving towards its target over about `time`.
+pub(crate) fn smoothing_coefficient(time: Duration, sample_rate: f32) -> f32 {
+    let samples = time.as_secs_f32() * sample_rate;
+    if samples > 0.0 {
+        (-1.0 / samples).exp()
+    } else {
+        0.0
+    }
+}
+
+/// The state of an [`AudioEffect`] running on the audio thread.
+pub(crate) enum EffectProcessor {
+    Biquad(Biquad),
+    Delay(DelayLine),
+    Reverb(Box<Reverb>),
+    Compressor(Compressor),
+}
+
+impl EffectProcessor {
+    pub(crate) fn new(effect: &AudioEffect, sample_rate: u32) -> Self {
+        let sample_rate = sample_rate as f32;
+        let mut processor = match effect {
+            AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. } => {
+                Self::Biquad(Biquad::default())
+            }
+            AudioEffect::Delay { .. } => Self::Delay(DelayLine::default()),
+            AudioEffect::Reverb { .. } => Self::Reverb(Box::new(Reverb::new(sample_rate))),
+            AudioEffect::Compressor { .. } => Self::Compressor(Compressor::default()),
+        };
+        processor.update(effect, sample_rate);
+        processor
+    }
+
+    /// Applies new parameters to the effect, keeping its state.
+    ///
+    /// Returns `false` if `effect` is of another kind, in which case a new processor is needed.
+    pub(crate) fn try_update(&mut self, effect: &AudioEffect, sample_rate: u32) -> bool {
+        let same_kind = matches!(
+            (&*self, effect),
+            (
+                Self::Biquad(_),
+                AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. }
+            ) | (Self::Delay(_), AudioEffect::Delay { .. })
+                | (Self::Reverb(_), AudioEffect::Reverb { .. })
+                | (Self::Compressor(_), AudioEffect::Compressor { .. })
+        );
+        if same_kind {
+            self.update(effect, sample_rate as f32);
+        }
+        same_kind
+    }
+
+    fn update(&mut self, effect: &AudioEffect, sample_rate: f32) {
+        match (self, *effect) {
+            (
+                Self::Biquad(biquad),
+                AudioEffect::LowPass {
+                    cutoff_frequency,
+                    q,
+                },
+            ) => biquad.set_low_pass(cutoff_frequency, q, sample_rate),
+            (
+                Self::Biquad(biquad),
+                AudioEffect::HighPass {
+                    cutoff_frequency,
+                    q,
+                },
+            ) => biquad.set_high_pass(cutoff_frequency, q, sample_rate),
+            (
+                Self::Delay(delay),
+                AudioEffect::Delay {
+                    time,
+                    feedback,
+                    mix,
+                },
+            ) => delay.set(time, feedback, mix, sample_rate),
+            (
+                Self::Reverb(reverb),
+                AudioEffect::Reverb {
+                    room_size,
+                    damping,
+                    mix,
+                },
+            ) => reverb.set(room_size, damping, mix),
+            (
+                Self::Compressor(compressor),
+                AudioEffect::Compressor {
+                    threshold,
+                    ratio,
+                    attack,
+                    release,
+                    makeup_gain,
+                },
+            ) => compressor.set(threshold, ratio, attack, release, makeup_gain, sample_rate),
+            _ => {}
+        }
+    }
+
+    /// Processes one stereo frame.
+    pub(crate) fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        match self {
+            Self::Biquad(biquad) => biquad.process(frame),
+            Self::Delay(delay) => delay.process(frame),
+            Self::Reverb(reverb) => reverb.process(frame),
+            Self::Compressor(compressor) => compressor.process(frame),
+        }
+    }
+}
+
+/// A second order filter, using the coefficients of the Audio EQ Cookbook.
+#[derive(Default)]
+pub(crate) struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    // Transposed direct form II state of each channel
+    z1: [f32; 2],
+    z2: [f32; 2],
+}
+
+impl Biquad {
+    fn set_low_pass(&mut self, cutoff_frequency: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::intermediates(cutoff_frequency, q, sample_rate);
+        let b1 = 1.0 - cos;
+        self.set_coefficients(b1 / 2.0, b1, b1 / 2.0, cos, alpha);
+    }
+
+    fn set_high_pass(&mut self, cutoff_frequency: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::intermediates(cutoff_frequency, q, sample_rate);
+        let b1 = -(1.0 + cos);
+        self.set_coefficients(-b1 / 2.0, b1, -b1 / 2.0, cos, alpha);
+    }
+
+    fn intermediates(cutoff_frequency: f32, q: f32, sample_rate: f32) -> (f32, f32) {
+        let cutoff_frequency = cutoff_frequency.clamp(10.0, sample_rate * 0.49);
+        let w0 = 2.0 * PI * cutoff_frequency / sample_rate;
+        (w0.cos(), w0.sin() / (2.0 * q.max(0.01)))
+    }
+
+    fn set_coefficients(&mut self, b0: f32, b1: f32, b2: f32, cos: f32, alpha: f32) {
+        let a0 = 1.0 + alpha;
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = -2.0 * cos / a0;
+        self.a2 = (1.0 - alpha) / a0;
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let mut output = [0.0; 2];
+        for (channel, x) in frame.into_iter().enumerate() {
+            let y = self.b0 * x + self.z1[channel];
+            self.z1[channel] = self.b1 * x - self.a1 * y + self.z2[channel];
+            self.z2[channel] = self.b2 * x - self.a2 * y;
+            output[channel] = y;
+        }
+        output
+    }
+}
+
+/// A feedback delay line.
+#[derive(Default)]
+pub(crate) struct DelayLine {
+    buffer: Vec<[f32; 2]>,
+    position: usize,
+    feedback: f32,
+    mix: f32,
+}
+
+impl DelayLine {
+    fn set(&mut self, time: Duration, feedback: f32, mix: f32, sample_rate: f32) {
+        let len = ((time.as_secs_f32() * sample_rate) as usize).max(1);
+        if len != self.buffer.len() {
+            self.buffer.resize(len, [0.0; 2]);
+            self.position %= len;
+        }
+        self.feedback = feedback.clamp(0.0, 0.99);
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let delayed = self.buffer[self.position];
+        let mut output = [0.0; 2];
+        for channel in 0..2 {
+            self.buffer[self.position][channel] = frame[channel] + delayed[channel] * self.feedback;
+            output[channel] = frame[channel] * (1.0 - self.mix) + delayed[channel] * self.mix;
+        }
+        self.position = (self.position + 1) % self.buffer.len();
+        output
+    }
+}
+
+// Tunings of the Freeverb algorithm, in samples at 44.1kHz
+const COMB_TUNINGS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALLPASS_TUNINGS: [usize; 4] = [556, 441, 341, 225];
+const STEREO_SPREAD: usize = 23;
+
+struct Comb {
+    buffer: Vec<f32>,
+    position: usize,
+    filter_store: f32,
+}
+
+impl Comb {
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.buffer[self.position];
+        self.filter_store = output * (1.0 - damping) + self.filter_store * damping;
+        self.buffer[self.position] = input + self.filter_store * feedback;
+        self.position = (self.position + 1) % self.buffer.len();
+        output
+    }
+}
+
+struct Allpass {
+    buffer: Vec<f32>,
+    position: usize,
+}
+
+impl Allpass {
+    fn process(&mut self, input: f32) -> f32 {
+        let buffered = self.buffer[self.position];
+        self.buffer[self.position] = input + buffered * 0.5;
+        self.position = (self.position + 1) % self.buffer.len();
+        buffered - input
+    }
+}
+
+/// A Freeverb reverb: parallel comb filters followed by allpass filters, for each channel.
+pub(crate) struct Reverb {
+    combs: [Vec<Comb>; 2],
+    allpasses: [Vec<Allpass>; 2],
+    feedback: f32,
+    damping: f32,
+    mix: f32,
+}
+
+impl Reverb {
+    fn new(sample_rate: f32) -> Self {
+        let scale = sample_rate / 44_100.0;
+        let len = |tuning: usize, channel: usize| {
+            (((tuning + channel * STEREO_SPREAD) as f32 * scale) as usize).max(1)
+        };
+        let combs = |channel| {
+            COMB_TUNINGS
+                .iter()
+                .map(|&tuning| Comb {
+                    buffer: vec![0.0; len(tuning, channel)],
+                    position: 0,
+                    filter_store: 0.0,
+                })
+                .collect()
+        };
+        let allpasses = |channel| {
+            ALLPASS_TUNINGS
+                .iter()
+                .map(|&tuning| Allpass {
+                    buffer: vec![0.0; len(tuning, channel)],
+                    position: 0,
+                })
+                .collect()
+        };
+        Self {
+            combs: [combs(0), combs(1)],
+            allpasses: [allpasses(0), allpasses(1)],
+            feedback: 0.0,
+            damping: 0.0,
+            mix: 0.0,
+        }
+    }
+
+    fn set(&mut self, room_size: f32, damping: f32, mix: f32) {
+        self.feedback = room_size.clamp(0.0, 1.0) * 0.28 + 0.7;
+        self.damping = damping.clamp(0.0, 1.0) * 0.4;
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let input = (frame[0] + frame[1]) * 0.015;
+        let mut output = [0.0; 2];
+        for channel in 0..2 {
+            let mut wet = 0.0;
+            for comb in &mut self.combs[channel] {
+                wet += comb.process(input, self.feedback, self.damping);
+            }
+            for allpass in &mut self.allpasses[channel] {
+                wet = allpass.process(wet);
+            }
+            output[channel] = frame[channel] * (1.0 - self.mix) + wet * 3.0 * self.mix;
+        }
+        output
+    }
+}
+
+/// A feed-forward compressor with a peak envelope follower.
+#[derive(Default)]
+pub(crate) struct Compressor {
+    threshold: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup_gain: f32,
+    envelope: f32,
+}
+
+impl Compressor {
+    fn set(
+        &mut self,
+        threshold: f32,
+        ratio: f32,
+        attack: Duration,
+        release: Duration,
+        makeup_gain: f32,
+        sample_rate: f32,
+    ) {
+        self.threshold = threshold;
+        self.ratio = ratio.max(1.0);
+        self.attack = smoothing_coefficient(attack, sample_rate);
+        self.release = smoothing_coefficient(release, sample_rate);
+        self.makeup_gain = makeup_gain;
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let level = frame[0].abs().max(frame[1].abs());
+        let coefficient = if level > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = level + coefficient * (self.envelope - level);
+
+        let over = 20.0 * self.envelope.max(1e-6).log10() - self.threshold;
+        let reduction = if over > 0.0 {
+            over * (1.0 - 1.0 / self.ratio)
+        } else {
+            0.0
+        };
+        let gain = 10f32.powf((self.makeup_gain - reduction) / 20.0);
+        [frame[0] * gain, frame[1] * gain]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{AudioEffect, Biquad, Compressor, DelayLine, EffectProcessor};
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    /// Returns the peak of the output of `process` for the last samples of a long run of
+    /// `input`, once the filter settled.
+    fn run(mut process: impl FnMut([f32; 2]) -> [f32; 2], input: impl Fn(usize) -> f32) -> f32 {
+        let mut peak = 0.0f32;
+        for i in 0..4800 {
+            let output = process([input(i), input(i)]);
+            assert_eq!(output[0], output[1]);
+            if i >= 4000 {
+                peak = peak.max(output[0].abs());
+            }
+        }
+        peak
+    }
+
+    fn dc(_: usize) -> f32 {
+        1.0
+    }
+
+    fn nyquist(i: usize) -> f32 {
+        [1.0, -1.0][i % 2]
+    }
+
+    #[test]
+    fn low_pass() {
+        let mut biquad = Biquad::default();
+        biquad.set_low_pass(1000.0, std::f32::consts::FRAC_1_SQRT_2, SAMPLE_RATE);
+        assert!((run(|frame| biquad.process(frame), dc) - 1.0).abs() < 1e-3);
+
+        let mut biquad = Biquad::default();
+        biquad.set_low_pass(1000.0, std::f32::consts::FRAC_1_SQRT_2, SAMPLE_RATE);
+        assert!(run(|frame| biquad.process(frame), nyquist) < 1e-3);
+    }
+
+    #[test]
+    fn high_pass() {
+        let mut biquad = Biquad::default();
+        biquad.set_high_pass(1000.0, std::f32::consts::FRAC_1_SQRT_2, SAMPLE_RATE);
+        assert!(run(|frame| biquad.process(frame), dc) < 1e-3);
+
+        let mut biquad = Biquad::default();
+        biquad.set_high_pass(1000.0, std::f32::consts::FRAC_1_SQRT_2, SAMPLE_RATE);
+        assert!((run(|frame| biquad.process(frame), nyquist) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn delay_line() {
+        let mut delay = DelayLine::default();
+        delay.set(Duration::from_millis(100), 0.5, 1.0, 100.0);
+        assert_eq!(delay.buffer.len(), 10);
+
+        // An impulse is repeated every 10 frames, halving each time
+        let output: Vec<f32> = (0..35)
+            .map(|i| delay.process([if i == 0 { 1.0 } else { 0.0 }; 2])[0])
+            .collect();
+        for (i, sample) in output.into_iter().enumerate() {
+            let expected = match i {
+                10 => 1.0,
+                20 => 0.5,
+                30 => 0.25,
+                _ => 0.0,
+            };
+            assert_eq!(sample, expected, "frame {i}");
+        }
+
+        // Half of the unprocessed signal is kept with a mix of 0.5
+        let mut delay = DelayLine::default();
+        delay.set(Duration::from_millis(100), 0.0, 0.5, 100.0);
+        assert_eq!(delay.process([1.0, -1.0]), [0.5, -0.5]);
+    }
+
+    #[test]
+    fn compressor() {
+        let mut compressor = Compressor::default();
+        // Reacts instantly
+        compressor.set(-20.0, 4.0, Duration::ZERO, Duration::ZERO, 0.0, SAMPLE_RATE);
+
+        // 0dB is 20dB over the threshold, reduced to 5dB over it
+        let [left, right] = compressor.process([1.0, -1.0]);
+        let expected = 10f32.powf(-15.0 / 20.0);
+        assert!((left - expected).abs() < 1e-5);
+        assert!((right + expected).abs() < 1e-5);
+
+        // -40dB is below the threshold, and left untouched
+        let [left, _] = compressor.process([0.01, 0.01]);
+        assert!((left - 0.01).abs() < 1e-7);
+
+        // The makeup gain applies to all levels
+        compressor.set(-20.0, 4.0, Duration::ZERO, Duration::ZERO, 6.0, SAMPLE_RATE);
+        let [left, _] = compressor.process([0.01, 0.01]);
+        assert!((left - 0.01 * 10f32.powf(6.0 / 20.0)).abs() < 1e-7);
+    }
+
+    #[test]
+    fn compressor_attack() {
+        let mut compressor = Compressor::default();
+        compressor.set(
+            -20.0,
+            4.0,
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+            0.0,
+            SAMPLE_RATE,
+        );
+        // The envelope takes time to reach a sudden loud signal, so the start passes through
+        let first = compressor.process([1.0, 1.0])[0];
+        let mut last = first;
+        for _ in 0..4800 {
+            last = compressor.process([1.0, 1.0])[0];
+        }
+        assert!(first > 0.9);
+        assert!((last - 10f32.powf(-15.0 / 20.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn try_update_keeps_state() {
+        let delay = |feedback, mix| AudioEffect::Delay {
+            time: Duration::from_millis(100),
+            feedback,
+            mix,
+        };
+        let mut processor = EffectProcessor::new(&delay(0.5, 1.0), 100);
+        processor.process([1.0; 2]);
+        for _ in 1..10 {
+            processor.process([0.0; 2]);
+        }
+
+        // The impulse is still in the delay line after a change of parameters
+        assert!(processor.try_update(&delay(0.5, 0.5), 100));
+        assert_eq!(processor.process([0.0; 2]), [0.5; 2]);
+
+        // Another kind of effect needs a new processor
+        assert!(!processor.try_update(&AudioEffect::low_pass(1000.0), 100));
+        let mut processor = EffectProcessor::new(&AudioEffect::low_pass(1000.0), 48_000);
+        assert!(processor.try_update(&AudioEffect::high_pass(1000.0), 48_000));
+    }
+}
@@ -0,0 +1,817 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use bevy_ecs::entity::Entity;
+use bevy_utils::{tracing::warn, Instant};
+use rodio::{source::UniformSourceIterator, Source};
+
+use crate::{
+    clock::ClockState,
+    effects::{smoothing_coefficient, EffectProcessor},
+    AudioEffect, AudioSend, Ducking,
+};
+
+/// The number of channels the graph mixes in.
+pub(crate) const GRAPH_CHANNELS: u16 = 2;
+
+/// How many frames are processed between two checks for new commands.
+const FRAMES_PER_COMMAND_CHECK: u32 = 64;
+
+/// How many samples the audio device takes from the graph at once.
+const OUTPUT_BUFFER_SAMPLES: usize = FRAMES_PER_COMMAND_CHECK as usize * GRAPH_CHANNELS as usize;
+
+/// How long volume changes are smoothed over, to avoid clicks.
+const VOLUME_SMOOTHING_TIME: Duration = Duration::from_millis(5);
+
+/// How long the measured level of a bus takes to fall once it gets quieter.
+const LEVEL_RELEASE_TIME: Duration = Duration::from_millis(100);
+
+/// Where the signal of a sound or bus goes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Routing {
+    /// The bus to output to, or `None` for the audio device.
+    pub(crate) output: Option<Entity>,
+    /// Additional buses receiving a copy of the signal.
+    pub(crate) sends: Vec<AudioSend>,
+}
+
+/// A change to the graph, sent from the ECS to the audio thread.
+pub(crate) enum GraphCommand {
+    /// Starts mixing a sound into the graph at frame `start`, or right away if that frame is
+    /// already mixed. The sound is removed once it ends.
+    AddInput {
+        entity: Entity,
+        source: Box<dyn Source<Item = f32> + Send>,
+        routing: Routing,
+        start: u64,
+    },
+    /// Changes the routing of the sounds of an entity.
+    SetInputRouting { entity: Entity, routing: Routing },
+    /// Adds a bus, or updates it if it already exists.
+    SetBus {
+        entity: Entity,
+        volume: f32,
+        routing: Routing,
+        effects: Vec<AudioEffect>,
+        ducking: Vec<Ducking>,
+    },
+    /// Removes a bus. Sounds and buses routed to it are played on the audio device instead.
+    RemoveBus { entity: Entity },
+}
+
+/// Sends [`GraphCommand`]s to the [`AudioGraph`] playing on the audio device.
+#[derive(Clone)]
+pub(crate) struct AudioGraphHandle {
+    sender: Sender<GraphCommand>,
+}
+
+impl AudioGraphHandle {
+    pub(crate) fn send(&self, command: GraphCommand) {
+        // The graph is only dropped if the audio device is lost, in which case there is nothing
+        // left to play the command on.
+        let _ = self.sender.send(command);
+    }
+}
+
+/// Resolved indices of a [`Routing`]. Outputs and sends always point to buses processed later.
+#[derive(Default)]
+struct ResolvedRouting {
+    output: Option<usize>,
+    sends: Vec<(usize, f32)>,
+}
+
+struct Input {
+    entity: Entity,
+    source: UniformSourceIterator<Box<dyn Source<Item = f32> + Send>, f32>,
+    routing: Routing,
+    resolved: ResolvedRouting,
+    start: u64,
+}
+
+struct Bus {
+    entity: Entity,
+    volume: f32,
+    routing: Routing,
+    effects: Vec<EffectProcessor>,
+    ducking: Vec<Ducking>,
+    duckers: Vec<Ducker>,
+    resolved: ResolvedRouting,
+    input: [f32; 2],
+    /// The smoothed volume applied to the signal, including ducking.
+    gain: f32,
+    /// The peak level of the output of the bus.
+    level: f32,
+}
+
+/// The state of a [`Ducking`] of a bus.
+struct Ducker {
+    trigger: Option<usize>,
+    threshold: f32,
+    volume: f32,
+    attack: f32,
+    release: f32,
+    active: bool,
+    /// How much the volume is lowered, from `0.0` to `1.0`.
+    amount: f32,
+}
+
+impl Ducker {
+    fn new(ducking: &Ducking, sample_rate: f32) -> Self {
+        Self {
+            trigger: None,
+            threshold: 10f32.powf(ducking.threshold / 20.0),
+            volume: ducking.volume,
+            attack: smoothing_coefficient(ducking.attack, sample_rate),
+            release: smoothing_coefficient(ducking.release, sample_rate),
+            active: false,
+            amount: 0.0,
+        }
+    }
+}
+
+/// A node-based mixing graph, played as a single [`Source`] on the audio device.
+///
+/// Each frame, sounds are mixed into the buses they are routed to, then each bus applies its
+/// effects and volume and passes its signal on to its output and sends. Buses are sorted so
+/// that a bus is processed after all the buses feeding it, which makes the graph sample-accurate.
+///
+/// The graph also drives the [`AudioClock`](crate::AudioClock): sounds start on the exact frame
+/// they are scheduled for.
+pub(crate) struct AudioGraph {
+    commands: Receiver<GraphCommand>,
+    clock: Arc<ClockState>,
+    /// The index of the next frame to mix.
+    frame_index: u64,
+    last_command_check: Option<Instant>,
+    sample_rate: u32,
+    volume_smoothing: f32,
+    level_release: f32,
+    inputs: Vec<Input>,
+    buses: Vec<Bus>,
+    frame: [f32; 2],
+    channel: usize,
+    frames_until_command_check: u32,
+}
+
+/// Creates a graph mixing at `sample_rate`, and the handle to control it.
+pub(crate) fn audio_graph(
+    sample_rate: u32,
+    clock: Arc<ClockState>,
+) -> (AudioGraphHandle, AudioGraph) {
+    let (sender, commands) = mpsc::channel();
+    (
+        AudioGraphHandle { sender },
+        AudioGraph {
+            commands,
+            clock,
+            frame_index: 0,
+            last_command_check: None,
+            sample_rate,
+            volume_smoothing: smoothing_coefficient(VOLUME_SMOOTHING_TIME, sample_rate as f32),
+            level_release: smoothing_coefficient(LEVEL_RELEASE_TIME, sample_rate as f32),
+            inputs: Vec::new(),
+            buses: Vec::new(),
+            frame: [0.0; 2],
+            channel: 0,
+            frames_until_command_check: 0,
+        },
+    )
+}
+
+impl AudioGraph {
+    fn apply_commands(&mut self) {
+        let mut topology_changed = false;
+        let mut ducking_changed = false;
+        for command in self.commands.try_iter() {
+            match command {
+                GraphCommand::AddInput {
+                    entity,
+                    source,
+                    routing,
+                    start,
+                } => {
+                    let mut input = Input {
+                        entity,
+                        source: UniformSourceIterator::new(
+                            source,
+                            GRAPH_CHANNELS,
+                            self.sample_rate,
+                        ),
+                        routing,
+                        resolved: ResolvedRouting::default(),
+                        start,
+                    };
+                    input.resolved = resolve(&self.buses, None, &input.routing);
+                    self.inputs.push(input);
+                }
+                GraphCommand::SetInputRouting { entity, routing } => {
+                    for input in self.inputs.iter_mut().filter(|i| i.entity == entity) {
+                        input.resolved = resolve(&self.buses, None, &routing);
+                        input.routing = routing.clone();
+                    }
+                }
+                GraphCommand::SetBus {
+                    entity,
+                    volume,
+                    routing,
+                    effects,
+                    ducking,
+                } => {
+                    let sample_rate = self.sample_rate;
+                    let bus = match self.buses.iter_mut().position(|b| b.entity == entity) {
+                        Some(index) => &mut self.buses[index],
+                        None => {
+                            self.buses.push(Bus {
+                                entity,
+                                volume,
+                                routing: routing.clone(),
+                                effects: Vec::new(),
+                                ducking: Vec::new(),
+                                duckers: Vec::new(),
+                                resolved: ResolvedRouting::default(),
+                                input: [0.0; 2],
+                                gain: volume,
+                                level: 0.0,
+                            });
+                            topology_changed = true;
+                            self.buses.last_mut().unwrap()
+                        }
+                    };
+                    bus.volume = volume;
+                    if bus.routing != routing {
+                        bus.routing = routing;
+                        topology_changed = true;
+                    }
+                    if bus.ducking != ducking {
+                        bus.duckers = ducking
+                            .iter()
+                            .map(|ducking| Ducker::new(ducking, sample_rate as f32))
+                            .collect();
+                        bus.ducking = ducking;
+                        ducking_changed = true;
+                    }
+                    // Keep the state of effects that are still there, so tails aren't cut
+                    bus.effects.truncate(effects.len());
+                    for (index, effect) in effects.iter().enumerate() {
+                        if let Some(processor) = bus.effects.get_mut(index) {
+                            if !processor.try_update(effect, sample_rate) {
+                                *processor = EffectProcessor::new(effect, sample_rate);
+                            }
+                        } else {
+                            bus.effects.push(EffectProcessor::new(effect, sample_rate));
+                        }
+                    }
+                }
+                GraphCommand::RemoveBus { entity } => {
+                    self.buses.retain(|bus| bus.entity != entity);
+                    topology_changed = true;
+                }
+            }
+        }
+        if topology_changed {
+            self.sort_buses();
+        } else if ducking_changed {
+            self.resolve_duckers();
+        }
+    }
+
+    /// Updates the [`ClockState`] with the frames mixed so far.
+    fn sync_clock(&mut self) {
+        self.clock.set_rendered(self.frame_index);
+        // The device requests frames in buffers, which are mixed much faster than they play.
+        // A long pause since the last check means a new buffer was requested, and its first
+        // frame is about to be played.
+        let now = Instant::now();
+        let check_duration =
+            Duration::from_secs_f64(FRAMES_PER_COMMAND_CHECK as f64 / self.sample_rate as f64);
+        let new_buffer = match self.last_command_check {
+            Some(last) => now - last > check_duration / 2,
+            None => true,
+        };
+        if new_buffer {
+            self.clock.sync(self.frame_index, now);
+        }
+        self.last_command_check = Some(now);
+    }
+
+    /// Resolves the bus indices of the triggers of all duckings.
+    fn resolve_duckers(&mut self) {
+        for index in 0..self.buses.len() {
+            for ducker in 0..self.buses[index].duckers.len() {
+                let trigger = self.buses[index].ducking[ducker].trigger;
+                self.buses[index].duckers[ducker].trigger =
+                    self.buses.iter().position(|bus| bus.entity == trigger);
+            }
+        }
+    }
+
+    /// Sorts the buses so that each bus comes after all the buses feeding it, and resolves the
+    /// routing of all buses and inputs.
+    fn sort_buses(&mut self) {
+        let targets = |bus: &Bus| {
+            bus.routing
+                .output
+                .into_iter()
+                .chain(bus.routing.sends.iter().map(|send| send.bus))
+                .filter(move |target| *target != bus.entity)
+                .collect::<Vec<_>>()
+        };
+
+        // Kahn's algorithm. Buses that are part of a cycle are kept in their current order.
+        let mut incoming = vec![0; self.buses.len()];
+        for bus in &self.buses {
+            for target in targets(bus) {
+                if let Some(index) = self.buses.iter().position(|b| b.entity == target) {
+                    incoming[index] += 1;
+                }
+            }
+        }
+        let mut order = Vec::with_capacity(self.buses.len());
+        let mut ready: Vec<usize> = (0..self.buses.len())
+            .filter(|index| incoming[*index] == 0)
+            .collect();
+        while let Some(index) = ready.pop() {
+            order.push(index);
+            for target in targets(&self.buses[index]) {
+                if let Some(target) = self.buses.iter().position(|b| b.entity == target) {
+                    incoming[target] -= 1;
+                    if incoming[target] == 0 {
+                        ready.push(target);
+                    }
+                }
+            }
+        }
+        if order.len() < self.buses.len() {
+            warn!("Audio buses are routed in a cycle. Some of their routes will be ignored.");
+            let cycles: Vec<usize> = (0..self.buses.len())
+                .filter(|index| !order.contains(index))
+                .collect();
+            order.extend(cycles);
+        }
+
+        let mut buses: Vec<Option<Bus>> = self.buses.drain(..).map(Some).collect();
+        self.buses = order
+            .into_iter()
+            .map(|index| buses[index].take().unwrap())
+            .collect();
+
+        for index in 0..self.buses.len() {
+            let resolved = resolve(&self.buses, Some(index), &self.buses[index].routing);
+            self.buses[index].resolved = resolved;
+        }
+        for input in &mut self.inputs {
+            input.resolved = resolve(&self.buses, None, &input.routing);
+        }
+        self.resolve_duckers();
+    }
+
+    /// Mixes the next frame of all inputs through the buses.
+    fn process_frame(&mut self) -> [f32; 2] {
+        let mut output = [0.0; 2];
+        let (volume_smoothing, level_release) = (self.volume_smoothing, self.level_release);
+        let buses = &mut self.buses;
+        for bus in buses.iter_mut() {
+            bus.input = [0.0; 2];
+        }
+
+        let frame_index = self.frame_index;
+        self.inputs.retain_mut(|input| {
+            if input.start > frame_index {
+                return true;
+            }
+            let (Some(left), Some(right)) = (input.source.next(), input.source.next()) else {
+                return false;
+            };
+            let frame = [left, right];
+            mix_into(buses, &mut output, input.resolved.output, frame, 1.0);
+            for &(send, level) in &input.resolved.sends {
+                mix_into(buses, &mut output, Some(send), frame, level);
+            }
+            true
+        });
+
+        for index in 0..buses.len() {
+            let bus = &mut buses[index];
+            let mut frame = bus.input;
+            for effect in &mut bus.effects {
+                frame = effect.process(frame);
+            }
+
+            let mut volume = bus.volume;
+            for ducker in &mut bus.duckers {
+                let (amount, coefficient) = if ducker.active {
+                    (1.0, ducker.attack)
+                } else {
+                    (0.0, ducker.release)
+                };
+                ducker.amount = amount + coefficient * (ducker.amount - amount);
+                volume *= 1.0 - ducker.amount * (1.0 - ducker.volume);
+            }
+            bus.gain = volume + volume_smoothing * (bus.gain - volume);
+            let frame = [frame[0] * bus.gain, frame[1] * bus.gain];
+
+            let peak = frame[0].abs().max(frame[1].abs());
+            bus.level = if peak > bus.level {
+                peak
+            } else {
+                bus.level * level_release
+            };
+
+            let target = bus.resolved.output;
+            mix_into(buses, &mut output, target, frame, 1.0);
+            for send in 0..buses[index].resolved.sends.len() {
+                let (send, level) = buses[index].resolved.sends[send];
+                mix_into(buses, &mut output, Some(send), frame, level);
+            }
+        }
+
+        // Buses are ducked based on the level of their trigger in the previous frame, so the
+        // order in which they are processed doesn't matter.
+        for index in 0..buses.len() {
+            for ducker in 0..buses[index].duckers.len() {
+                let ducker_state = &buses[index].duckers[ducker];
+                let active = ducker_state
+                    .trigger
+                    .is_some_and(|trigger| buses[trigger].level > ducker_state.threshold);
+                buses[index].duckers[ducker].active = active;
+            }
+        }
+        output
+    }
+}
+
+/// Resolves the bus indices of a routing. `from` is the index of the bus being routed, if any.
+fn resolve(buses: &[Bus], from: Option<usize>, routing: &Routing) -> ResolvedRouting {
+    // A bus can only feed buses processed after it, any other route is part of a cycle.
+    let find = |entity: Entity| {
+        buses
+            .iter()
+            .position(|bus| bus.entity == entity)
+            .filter(|index| match from {
+                Some(from) => *index > from,
+                None => true,
+            })
+    };
+    ResolvedRouting {
+        output: routing.output.and_then(find),
+        sends: routing
+            .sends
+            .iter()
+            .filter_map(|send| Some((find(send.bus)?, send.level)))
+            .collect(),
+    }
+}
+
+fn mix_into(
+    buses: &mut [Bus],
+    output: &mut [f32; 2],
+    target: Option<usize>,
+    frame: [f32; 2],
+    level: f32,
+) {
+    let target = match target {
+        Some(index) => &mut buses[index].input,
+        None => output,
+    };
+    target[0] += frame[0] * level;
+    target[1] += frame[1] * level;
+}
+
+impl Iterator for AudioGraph {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            if self.frames_until_command_check == 0 {
+                self.sync_clock();
+                self.apply_commands();
+                self.frames_until_command_check = FRAMES_PER_COMMAND_CHECK;
+            }
+            self.frames_until_command_check -= 1;
+            self.frame = self.process_frame();
+            self.frame_index += 1;
+        }
+        let sample = self.frame[self.channel];
+        self.channel = (self.channel + 1) % GRAPH_CHANNELS as usize;
+        Some(sample)
+    }
+}
+
+impl Source for AudioGraph {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        GRAPH_CHANNELS
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Plays an [`AudioGraph`] shared between audio devices, so that the device can be switched
+/// without interrupting sounds.
+pub(crate) struct GraphOutput {
+    graph: Arc<Mutex<AudioGraph>>,
+    sample_rate: u32,
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl GraphOutput {
+    pub(crate) fn new(graph: Arc<Mutex<AudioGraph>>) -> Self {
+        let sample_rate = graph.lock().unwrap().sample_rate;
+        Self {
+            graph,
+            sample_rate,
+            buffer: Vec::with_capacity(OUTPUT_BUFFER_SAMPLES),
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for GraphOutput {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        // Lock the graph once per buffer rather than once per sample
+        if self.index == self.buffer.len() {
+            let mut graph = self.graph.lock().unwrap();
+            self.buffer.clear();
+            self.buffer
+                .extend(graph.by_ref().take(OUTPUT_BUFFER_SAMPLES));
+            self.index = 0;
+        }
+        let sample = self.buffer[self.index];
+        self.index += 1;
+        Some(sample)
+    }
+}
+
+impl Source for GraphOutput {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        GRAPH_CHANNELS
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use bevy_ecs::entity::Entity;
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{
+        audio_graph, AudioGraph, AudioGraphHandle, GraphCommand, GraphOutput, Routing,
+        OUTPUT_BUFFER_SAMPLES,
+    };
+    use crate::{clock::ClockState, AudioEffect, AudioSend, Ducking};
+
+    fn test_graph() -> (AudioGraphHandle, AudioGraph) {
+        audio_graph(48_000, Arc::new(ClockState::new(48_000)))
+    }
+
+    fn bus(index: u32) -> Entity {
+        Entity::from_raw(index)
+    }
+
+    fn set_bus(
+        handle: &AudioGraphHandle,
+        entity: Entity,
+        output: Option<Entity>,
+        sends: &[Entity],
+    ) {
+        handle.send(GraphCommand::SetBus {
+            entity,
+            volume: 1.0,
+            routing: Routing {
+                output,
+                sends: sends
+                    .iter()
+                    .map(|bus| AudioSend {
+                        bus: *bus,
+                        level: 0.5,
+                    })
+                    .collect(),
+            },
+            effects: Vec::new(),
+            ducking: Vec::new(),
+        });
+    }
+
+    fn order(graph: &AudioGraph) -> Vec<Entity> {
+        graph.buses.iter().map(|bus| bus.entity).collect()
+    }
+
+    fn position(graph: &AudioGraph, entity: Entity) -> usize {
+        graph
+            .buses
+            .iter()
+            .position(|bus| bus.entity == entity)
+            .unwrap()
+    }
+
+    #[test]
+    fn buses_are_sorted_after_their_inputs() {
+        let (handle, mut graph) = test_graph();
+        // music -> master <- sfx, and sfx sends to reverb -> master
+        let (master, music, sfx, reverb) = (bus(0), bus(1), bus(2), bus(3));
+        set_bus(&handle, master, None, &[]);
+        set_bus(&handle, reverb, Some(master), &[]);
+        set_bus(&handle, music, Some(master), &[]);
+        set_bus(&handle, sfx, Some(master), &[reverb]);
+        graph.apply_commands();
+
+        assert_eq!(graph.buses.len(), 4);
+        assert!(position(&graph, sfx) < position(&graph, reverb));
+        assert!(position(&graph, reverb) < position(&graph, master));
+        assert!(position(&graph, music) < position(&graph, master));
+
+        let sfx = &graph.buses[position(&graph, sfx)];
+        assert_eq!(sfx.resolved.output, Some(position(&graph, master)));
+        assert_eq!(sfx.resolved.sends, [(position(&graph, reverb), 0.5)]);
+        assert_eq!(graph.buses[position(&graph, master)].resolved.output, None);
+
+        // Rerouting a bus sorts the buses again
+        set_bus(&handle, master, Some(music), &[]);
+        set_bus(&handle, music, None, &[]);
+        graph.apply_commands();
+        assert!(position(&graph, master) < position(&graph, music));
+        assert_eq!(
+            graph.buses[position(&graph, master)].resolved.output,
+            Some(position(&graph, music))
+        );
+    }
+
+    #[test]
+    fn cycles_fall_back_to_current_order() {
+        let (handle, mut graph) = test_graph();
+        // a -> b -> a, with c feeding a
+        let (a, b, c) = (bus(0), bus(1), bus(2));
+        set_bus(&handle, a, Some(b), &[]);
+        set_bus(&handle, b, Some(a), &[]);
+        set_bus(&handle, c, Some(a), &[]);
+        graph.apply_commands();
+
+        // c is sorted first, then the cycle in the order the buses were added
+        assert_eq!(order(&graph), [c, a, b]);
+        // The route closing the cycle is ignored, and b plays on the device instead
+        assert_eq!(graph.buses[1].resolved.output, Some(2));
+        assert_eq!(graph.buses[2].resolved.output, None);
+        assert_eq!(graph.buses[0].resolved.output, Some(1));
+
+        // A bus routed to itself plays on the device
+        set_bus(&handle, c, Some(c), &[c]);
+        graph.apply_commands();
+        let c = &graph.buses[position(&graph, c)];
+        assert_eq!(c.resolved.output, None);
+        assert!(c.resolved.sends.is_empty());
+    }
+
+    #[test]
+    fn removed_bus_output_plays_on_device() {
+        let (handle, mut graph) = test_graph();
+        let (a, b) = (bus(0), bus(1));
+        set_bus(&handle, a, Some(b), &[]);
+        set_bus(&handle, b, None, &[]);
+        graph.apply_commands();
+        assert_eq!(graph.buses[position(&graph, a)].resolved.output, Some(1));
+
+        handle.send(GraphCommand::RemoveBus { entity: b });
+        graph.apply_commands();
+        assert_eq!(order(&graph), [a]);
+        assert_eq!(graph.buses[0].resolved.output, None);
+    }
+
+    #[test]
+    fn ducking_lowers_volume_while_trigger_plays() {
+        let (handle, mut graph) = test_graph();
+        let (music, voice) = (bus(0), bus(1));
+        handle.send(GraphCommand::SetBus {
+            entity: music,
+            volume: 1.0,
+            routing: Routing::default(),
+            effects: Vec::new(),
+            ducking: vec![Ducking {
+                attack: Duration::from_millis(10),
+                release: Duration::from_millis(100),
+                ..Ducking::new(voice, 0.25)
+            }],
+        });
+        set_bus(&handle, voice, None, &[]);
+        // One second of voice
+        handle.send(GraphCommand::AddInput {
+            entity: bus(2),
+            source: Box::new(SamplesBuffer::new(2, 48_000, vec![0.5; 96_000])),
+            routing: Routing {
+                output: Some(voice),
+                sends: Vec::new(),
+            },
+            start: 0,
+        });
+        graph.apply_commands();
+        let gain = |graph: &AudioGraph| graph.buses[position(graph, music)].gain;
+
+        for _ in 0..24_000 {
+            graph.process_frame();
+        }
+        assert!((gain(&graph) - 0.25).abs() < 0.01, "{}", gain(&graph));
+        assert_eq!(graph.buses[position(&graph, voice)].gain, 1.0);
+
+        // The volume is restored once the voice has ended
+        for _ in 0..120_000 {
+            graph.process_frame();
+        }
+        assert!(graph.inputs.is_empty());
+        assert!((gain(&graph) - 1.0).abs() < 0.01, "{}", gain(&graph));
+    }
+
+    #[test]
+    fn updating_a_bus_keeps_its_effects() {
+        let (handle, mut graph) = test_graph();
+        let effects = |mix| {
+            vec![AudioEffect::Delay {
+                time: std::time::Duration::from_millis(10),
+                feedback: 0.0,
+                mix,
+            }]
+        };
+        let set = |mix| {
+            handle.send(GraphCommand::SetBus {
+                entity: bus(0),
+                volume: 1.0,
+                routing: Routing::default(),
+                effects: effects(mix),
+                ducking: Vec::new(),
+            });
+        };
+        set(1.0);
+        graph.apply_commands();
+
+        // An impulse enters the delay of the bus
+        graph.buses[0].effects[0].process([1.0; 2]);
+
+        // Changing the mix of the delay keeps the impulse in it, 480 frames later
+        set(0.5);
+        graph.apply_commands();
+        let effect = &mut graph.buses[0].effects[0];
+        for _ in 1..480 {
+            assert_eq!(effect.process([0.0; 2]), [0.0; 2]);
+        }
+        assert_eq!(effect.process([0.0; 2]), [0.5; 2]);
+    }
+
+    #[test]
+    fn switching_outputs_keeps_playing() {
+        let (handle, graph) = test_graph();
+        let samples: Vec<f32> = (1..=1000).map(|sample| sample as f32 / 1000.0).collect();
+        handle.send(GraphCommand::AddInput {
+            entity: bus(0),
+            source: Box::new(SamplesBuffer::new(2, 48_000, samples.clone())),
+            routing: Routing::default(),
+            start: 0,
+        });
+        let graph = Arc::new(Mutex::new(graph));
+
+        // The first device plays a whole buffer, then the second one continues the sound
+        let first: Vec<f32> = GraphOutput::new(graph.clone())
+            .take(OUTPUT_BUFFER_SAMPLES)
+            .collect();
+        let second: Vec<f32> = GraphOutput::new(graph).take(1000).collect();
+        assert_eq!(first, samples[..OUTPUT_BUFFER_SAMPLES]);
+        assert_eq!(
+            second[..1000 - OUTPUT_BUFFER_SAMPLES],
+            samples[OUTPUT_BUFFER_SAMPLES..]
+        );
+        // Silence is played once the sound ends
+        assert!(second[1000 - OUTPUT_BUFFER_SAMPLES..]
+            .iter()
+            .all(|sample| *sample == 0.0));
+    }
+}
@@ -26,26 +26,43 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod bus;
+mod clock;
+mod device;
+mod effects;
+mod graph;
+mod mixer;
 mod pitch;
 mod sinks;
+mod spatial;
+mod streaming;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioBundle, AudioSink, AudioSinkPlayback, AudioSource, AudioSourceBundle, Decodable,
-        GlobalVolume, Pitch, PitchBundle, PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBundle, AudioBus, AudioClock, AudioDeviceSettings, AudioEffect, AudioEffects,
+        AudioMixerSettings, AudioSink, AudioSinkPlayback, AudioSource, AudioSourceBundle,
+        Decodable, GlobalVolume, MixerBus, OutputBus, Pitch, PitchBundle, PlaybackSettings,
+        SpatialAudioSink, SpatialEmitter, SpatialListener,
     };
 }
 
 pub use audio::*;
 pub use audio_source::*;
+pub use bus::*;
+pub use clock::{AudioClock, Tempo};
+pub use device::{AudioDevice, AudioDeviceEvent, AudioDeviceSettings, AudioDevices, AudioInput};
+pub use effects::AudioEffect;
+pub use mixer::*;
 pub use pitch::*;
 
 pub use rodio::cpal::Sample as CpalSample;
 pub use rodio::source::Source;
 pub use rodio::Sample;
 pub use sinks::*;
+pub use spatial::{DistanceAttenuation, SoundCone, SpatialEmitter, SpeedOfSound};
+pub use streaming::SeekError;
 
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp};
@@ -53,6 +70,9 @@ use bevy_ecs::prelude::*;
 use bevy_transform::TransformSystem;
 
 use audio_output::*;
+use bus::{update_audio_buses, update_audio_routing};
+use device::{select_audio_output, update_audio_devices, update_audio_input, AudioStreams};
+use mixer::apply_audio_mixer_settings;
 
 /// Set for the audio playback systems, so they can share a run condition
 #[derive(SystemSet, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -61,6 +81,9 @@ struct AudioPlaySet;
 /// Adds support for audio playback to a Bevy Application
 ///
 /// Insert an [`AudioBundle`] onto your entities to play audio.
+///
+/// Audio is mixed through a graph of [`AudioBus`]es, which can apply [`AudioEffects`] to the
+/// sounds routed to them.
 #[derive(Default)]
 pub struct AudioPlugin {
     /// The global volume for all audio entities with a [`Volume::Relative`] volume.
@@ -68,19 +91,54 @@ pub struct AudioPlugin {
     /// The scale factor applied to the positions of audio sources and listeners for
     /// spatial audio.
     pub spatial_scale: SpatialScale,
+    /// The speed of sound used for spatial audio.
+    pub speed_of_sound: SpeedOfSound,
+    /// The initial volume and ducking of the mixer buses.
+    pub mixer_settings: AudioMixerSettings,
+    /// The initial audio devices.
+    pub device_settings: AudioDeviceSettings,
 }
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
+        let mut streams = AudioStreams::default();
+        let audio_output = AudioOutput::open(&self.device_settings.output, &mut streams);
+        // Don't keep probing for devices on systems without audio, such as servers
+        if audio_output.device.is_some() {
+            streams.watch_devices();
+        }
+
         app.register_type::<VolumeLevel>()
             .register_type::<GlobalVolume>()
             .register_type::<SpatialListener>()
             .register_type::<SpatialScale>()
+            .register_type::<SpatialEmitter>()
+            .register_type::<SpeedOfSound>()
             .register_type::<PlaybackMode>()
             .register_type::<Volume>()
             .register_type::<PlaybackSettings>()
+            .register_type::<AudioBus>()
+            .register_type::<OutputBus>()
+            .register_type::<AudioSends>()
+            .register_type::<AudioEffects>()
+            .register_type::<AudioDucking>()
+            .register_type::<MixerBus>()
+            .register_type::<AudioMixerSettings>()
+            .register_type::<Tempo>()
+            .register_type::<AudioDevice>()
+            .register_type::<AudioDeviceSettings>()
             .insert_resource(self.global_volume)
             .insert_resource(self.spatial_scale)
+            .insert_resource(self.speed_of_sound)
+            .insert_resource(self.mixer_settings.clone())
+            .insert_resource(self.device_settings.clone())
+            .insert_resource(audio_output)
+            .insert_non_send_resource(streams)
+            .init_resource::<AudioClock>()
+            .init_resource::<AudioDevices>()
+            .init_resource::<AudioInput>()
+            .init_resource::<AudioMixer>()
+            .add_event::<AudioDeviceEvent>()
             .configure_sets(
                 PostUpdate,
                 AudioPlaySet
@@ -89,9 +147,24 @@ impl Plugin for AudioPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaySet),
+                (
+                    update_emitter_positions,
+                    update_listener_positions,
+                    apply_audio_mixer_settings.before(update_audio_buses),
+                    update_audio_buses,
+                    update_audio_routing,
+                )
+                    .in_set(AudioPlaySet),
             )
-            .init_resource::<AudioOutput>();
+            .add_systems(
+                PreUpdate,
+                (
+                    update_audio_devices,
+                    select_audio_output,
+                    update_audio_input,
+                )
+                    .chain(),
+            );
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
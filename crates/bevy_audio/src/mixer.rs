@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use bevy_ecs::{entity::Entity, prelude::*};
+use bevy_reflect::prelude::*;
+
+use crate::{AudioBus, AudioDucking, Ducking, OutputBus, VolumeLevel};
+
+/// A named bus of the audio mixer, grouping sounds so their volume can be controlled together.
+///
+/// Sounds are played on a mixer bus with [`PlaybackSettings::bus`](crate::PlaybackSettings::bus).
+/// The [`Music`](MixerBus::Music), [`Sfx`](MixerBus::Sfx) and [`Voice`](MixerBus::Voice) buses
+/// output to the [`Master`](MixerBus::Master) bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum MixerBus {
+    /// The bus all other mixer buses output to.
+    Master,
+    /// Background music.
+    Music,
+    /// Sound effects.
+    Sfx,
+    /// Dialogue and voice chat.
+    Voice,
+}
+
+/// The volume of a [`MixerBus`].
+#[derive(Clone, Copy, Debug, Default, Reflect)]
+pub struct MixerBusSettings {
+    /// The volume of the bus.
+    pub volume: VolumeLevel,
+    /// Silences the bus without changing its volume.
+    pub muted: bool,
+}
+
+impl MixerBusSettings {
+    /// Returns the volume of the bus, or zero if it is muted.
+    pub fn effective_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.volume.get()
+        }
+    }
+}
+
+/// Lowers the volume of a [`MixerBus`] while another mixer bus is playing.
+#[derive(Clone, Copy, Debug, Reflect)]
+pub struct MixerDucking {
+    /// The bus whose volume is lowered.
+    pub bus: MixerBus,
+    /// The bus that lowers the volume while it plays.
+    pub trigger: MixerBus,
+    /// The volume multiplier applied while the trigger bus plays.
+    pub volume: f32,
+    /// How fast the volume is lowered once the trigger bus starts playing.
+    pub attack: Duration,
+    /// How fast the volume is restored once the trigger bus stops playing.
+    pub release: Duration,
+}
+
+/// The settings of the audio mixer.
+///
+/// This resource can be saved and loaded through reflection to persist the volume settings of
+/// players. Changes are applied to the [`AudioMixer`] buses immediately, including to playing
+/// sounds.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AudioMixerSettings {
+    /// The settings of the [`MixerBus::Master`] bus.
+    pub master: MixerBusSettings,
+    /// The settings of the [`MixerBus::Music`] bus.
+    pub music: MixerBusSettings,
+    /// The settings of the [`MixerBus::Sfx`] bus.
+    pub sfx: MixerBusSettings,
+    /// The settings of the [`MixerBus::Voice`] bus.
+    pub voice: MixerBusSettings,
+    /// The side-chain ducking between buses. By default, music is lowered while voice plays.
+    pub ducking: Vec<MixerDucking>,
+}
+
+impl Default for AudioMixerSettings {
+    fn default() -> Self {
+        Self {
+            master: MixerBusSettings::default(),
+            music: MixerBusSettings::default(),
+            sfx: MixerBusSettings::default(),
+            voice: MixerBusSettings::default(),
+            ducking: vec![MixerDucking {
+                bus: MixerBus::Music,
+                trigger: MixerBus::Voice,
+                volume: 0.3,
+                attack: Duration::from_millis(50),
+                release: Duration::from_millis(500),
+            }],
+        }
+    }
+}
+
+impl AudioMixerSettings {
+    /// Returns the settings of `bus`.
+    pub fn bus(&self, bus: MixerBus) -> &MixerBusSettings {
+        match bus {
+            MixerBus::Master => &self.master,
+            MixerBus::Music => &self.music,
+            MixerBus::Sfx => &self.sfx,
+            MixerBus::Voice => &self.voice,
+        }
+    }
+
+    /// Returns the settings of `bus` mutably.
+    pub fn bus_mut(&mut self, bus: MixerBus) -> &mut MixerBusSettings {
+        match bus {
+            MixerBus::Master => &mut self.master,
+            MixerBus::Music => &mut self.music,
+            MixerBus::Sfx => &mut self.sfx,
+            MixerBus::Voice => &mut self.voice,
+        }
+    }
+}
+
+/// The [`AudioBus`] entities of the mixer buses.
+///
+/// [`AudioEffects`](crate::AudioEffects) can be added to these entities to process a whole group
+/// of sounds, but their volume is controlled by the [`AudioMixerSettings`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct AudioMixer {
+    master: Entity,
+    music: Entity,
+    sfx: Entity,
+    voice: Entity,
+}
+
+impl AudioMixer {
+    /// Returns the entity of `bus`.
+    pub fn bus(&self, bus: MixerBus) -> Entity {
+        match bus {
+            MixerBus::Master => self.master,
+            MixerBus::Music => self.music,
+            MixerBus::Sfx => self.sfx,
+            MixerBus::Voice => self.voice,
+        }
+    }
+}
+
+impl FromWorld for AudioMixer {
+    fn from_world(world: &mut World) -> Self {
+        let master = world.spawn(AudioBus::default()).id();
+        let mut spawn_group = || world.spawn((AudioBus::default(), OutputBus(master))).id();
+        Self {
+            master,
+            music: spawn_group(),
+            sfx: spawn_group(),
+            voice: spawn_group(),
+        }
+    }
+}
+
+/// Applies the [`AudioMixerSettings`] to the [`AudioMixer`] buses when they change.
+pub(crate) fn apply_audio_mixer_settings(
+    mut commands: Commands,
+    settings: Res<AudioMixerSettings>,
+    mixer: Res<AudioMixer>,
+    mut buses: Query<&mut AudioBus>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for bus in [
+        MixerBus::Master,
+        MixerBus::Music,
+        MixerBus::Sfx,
+        MixerBus::Voice,
+    ] {
+        let entity = mixer.bus(bus);
+        let Ok(mut audio_bus) = buses.get_mut(entity) else {
+            continue;
+        };
+        audio_bus.volume = VolumeLevel::new(settings.bus(bus).effective_volume());
+
+        let ducking = settings
+            .ducking
+            .iter()
+            .filter(|ducking| ducking.bus == bus)
+            .map(|ducking| Ducking {
+                attack: ducking.attack,
+                release: ducking.release,
+                ..Ducking::new(mixer.bus(ducking.trigger), ducking.volume)
+            })
+            .collect();
+        commands.entity(entity).insert(AudioDucking(ducking));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::schedule::Schedule;
+
+    use super::*;
+
+    #[test]
+    fn muted_bus_is_silent() {
+        let mut settings = MixerBusSettings {
+            volume: VolumeLevel::new(0.5),
+            muted: false,
+        };
+        assert_eq!(settings.effective_volume(), 0.5);
+        settings.muted = true;
+        assert_eq!(settings.effective_volume(), 0.0);
+    }
+
+    #[test]
+    fn settings_are_applied_to_the_buses() {
+        let mut world = World::new();
+        world.init_resource::<AudioMixer>();
+        world.init_resource::<AudioMixerSettings>();
+        let mixer = *world.resource::<AudioMixer>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_audio_mixer_settings);
+
+        {
+            let mut settings = world.resource_mut::<AudioMixerSettings>();
+            settings.bus_mut(MixerBus::Music).volume = VolumeLevel::new(0.8);
+            settings.bus_mut(MixerBus::Sfx).muted = true;
+        }
+        schedule.run(&mut world);
+
+        let volume =
+            |world: &World, bus| world.get::<AudioBus>(mixer.bus(bus)).unwrap().volume.get();
+        assert_eq!(volume(&world, MixerBus::Master), 1.0);
+        assert_eq!(volume(&world, MixerBus::Music), 0.8);
+        assert_eq!(volume(&world, MixerBus::Sfx), 0.0);
+        assert_eq!(volume(&world, MixerBus::Voice), 1.0);
+
+        // The groups output to the master bus
+        for bus in [MixerBus::Music, MixerBus::Sfx, MixerBus::Voice] {
+            let output = world.get::<OutputBus>(mixer.bus(bus)).unwrap();
+            assert_eq!(output.0, mixer.bus(MixerBus::Master));
+        }
+        assert!(world
+            .get::<OutputBus>(mixer.bus(MixerBus::Master))
+            .is_none());
+
+        // By default, the music is ducked by the voice
+        let music_ducking = world
+            .get::<AudioDucking>(mixer.bus(MixerBus::Music))
+            .unwrap();
+        assert_eq!(music_ducking.0.len(), 1);
+        assert_eq!(music_ducking.0[0].trigger, mixer.bus(MixerBus::Voice));
+        assert_eq!(music_ducking.0[0].volume, 0.3);
+        assert!(world
+            .get::<AudioDucking>(mixer.bus(MixerBus::Sfx))
+            .unwrap()
+            .0
+            .is_empty());
+
+        // The buses are only updated when the settings change
+        world
+            .get_mut::<AudioBus>(mixer.bus(MixerBus::Voice))
+            .unwrap()
+            .volume = VolumeLevel::new(0.1);
+        schedule.run(&mut world);
+        assert_eq!(volume(&world, MixerBus::Voice), 0.1);
+
+        world
+            .resource_mut::<AudioMixerSettings>()
+            .bus_mut(MixerBus::Sfx)
+            .muted = false;
+        schedule.run(&mut world);
+        assert_eq!(volume(&world, MixerBus::Sfx), 1.0);
+        assert_eq!(volume(&world, MixerBus::Voice), 1.0);
+    }
+}
@@ -29,6 +29,10 @@ impl Decodable for Pitch {
     fn decoder(&self) -> Self::Decoder {
         SineWave::new(self.frequency).take_duration(self.duration)
     }
+
+    fn streamed(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
 /// Bundle for playing a bevy note sound
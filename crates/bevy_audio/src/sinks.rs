@@ -1,7 +1,18 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
 use bevy_ecs::component::Component;
 use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
-use rodio::{Sink, SpatialSink};
+use rodio::{Sink, Source};
+
+use crate::{
+    spatial::{SpatialParameters, SpatialSource},
+    streaming::Playhead,
+    SeekError,
+};
 
 /// Common interactions with an audio sink.
 pub trait AudioSinkPlayback {
@@ -71,6 +82,21 @@ pub trait AudioSinkPlayback {
 
     /// Returns true if this sink has no more sounds to play.
     fn empty(&self) -> bool;
+
+    /// Returns the position of the playhead in the sound.
+    ///
+    /// This is the position of the last sample sent to the audio device, which is heard after
+    /// the latency of the device. It doesn't advance while the sink is paused, and isn't
+    /// affected by its speed. Looping sounds that aren't
+    /// [streamed](crate::PlaybackSettings::streaming) keep counting across repetitions.
+    fn position(&self) -> Duration;
+
+    /// Moves the playhead of the sound to `position`.
+    ///
+    /// Only sounds played with [`PlaybackSettings::streaming`](crate::PlaybackSettings::streaming)
+    /// can seek. The sound is silent until the new position is decoded, which usually takes a
+    /// few milliseconds.
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError>;
 }
 
 /// Used to control audio during playback.
@@ -86,6 +112,7 @@ pub trait AudioSinkPlayback {
 #[derive(Component)]
 pub struct AudioSink {
     pub(crate) sink: Sink,
+    pub(crate) playhead: Playhead,
 }
 
 impl AudioSinkPlayback for AudioSink {
@@ -124,6 +151,27 @@ impl AudioSinkPlayback for AudioSink {
     fn empty(&self) -> bool {
         self.sink.empty()
     }
+
+    fn position(&self) -> Duration {
+        self.playhead.position()
+    }
+
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError> {
+        if self.sink.empty() {
+            return Err(SeekError::Finished);
+        }
+        self.playhead.seek(position)
+    }
+}
+
+impl AudioSink {
+    /// Appends a sound to the queue of sounds to play.
+    pub(crate) fn append<S>(&self, source: S)
+    where
+        S: Source<Item = f32> + Send + 'static,
+    {
+        self.sink.append(source);
+    }
 }
 
 /// Used to control spatial audio during playback.
@@ -138,7 +186,9 @@ impl AudioSinkPlayback for AudioSink {
 /// that source is unchanged, that translates to the audio restarting.
 #[derive(Component)]
 pub struct SpatialAudioSink {
-    pub(crate) sink: SpatialSink,
+    pub(crate) sink: Sink,
+    parameters: Arc<Mutex<SpatialParameters>>,
+    playhead: Playhead,
 }
 
 impl AudioSinkPlayback for SpatialAudioSink {
@@ -177,13 +227,47 @@ impl AudioSinkPlayback for SpatialAudioSink {
     fn empty(&self) -> bool {
         self.sink.empty()
     }
+
+    fn position(&self) -> Duration {
+        self.playhead.position()
+    }
+
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError> {
+        if self.sink.empty() {
+            return Err(SeekError::Finished);
+        }
+        self.playhead.seek(position)
+    }
 }
 
 impl SpatialAudioSink {
+    /// Wraps a sink to render its sounds with the given parameters.
+    pub(crate) fn new(sink: Sink, parameters: SpatialParameters, playhead: Playhead) -> Self {
+        Self {
+            sink,
+            parameters: Arc::new(Mutex::new(parameters)),
+            playhead,
+        }
+    }
+
+    /// Appends a sound to the queue of sounds to play, rendered according to the parameters.
+    pub(crate) fn append<S>(&self, source: S)
+    where
+        S: Source<Item = f32> + Send + 'static,
+    {
+        self.sink
+            .append(SpatialSource::new(source, self.parameters.clone()));
+    }
+
+    pub(crate) fn parameters(&self) -> MutexGuard<'_, SpatialParameters> {
+        self.parameters.lock().unwrap()
+    }
+
     /// Set the two ears position.
     pub fn set_ears_position(&self, left_position: Vec3, right_position: Vec3) {
-        self.sink.set_left_ear_position(left_position.to_array());
-        self.sink.set_right_ear_position(right_position.to_array());
+        let mut parameters = self.parameters();
+        parameters.left_ear = left_position;
+        parameters.right_ear = right_position;
     }
 
     /// Set the listener position, with an ear on each side separated by `gap`.
@@ -196,6 +280,21 @@ impl SpatialAudioSink {
 
     /// Set the emitter position.
     pub fn set_emitter_position(&self, position: Vec3) {
-        self.sink.set_emitter_position(position.to_array());
+        self.parameters().emitter = position;
+    }
+
+    /// Set the emitter velocity, used for the doppler effect.
+    ///
+    /// This is computed from the `GlobalTransform` of the emitter every frame.
+    pub fn set_emitter_velocity(&self, velocity: Vec3) {
+        self.parameters().emitter_velocity = velocity;
+    }
+
+    /// Set the listener velocity, used for the doppler effect.
+    ///
+    /// This is computed from the `GlobalTransform` of the [`SpatialListener`](crate::SpatialListener)
+    /// every frame.
+    pub fn set_listener_velocity(&self, velocity: Vec3) {
+        self.parameters().listener_velocity = velocity;
     }
 }
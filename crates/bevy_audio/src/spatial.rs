@@ -0,0 +1,605 @@
+use std::{
+    f32::consts::PI,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_math::{Vec2, Vec3};
+use bevy_reflect::prelude::*;
+use rodio::Source;
+
+use crate::effects::smoothing_coefficient;
+
+/// How a sound gets quieter as the distance between the emitter and the listener grows.
+///
+/// Distances are measured after applying the [`SpatialScale`](crate::SpatialScale).
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+pub enum DistanceAttenuation {
+    /// The volume is divided by the square of the distance, and is full within a distance of
+    /// `1.0`.
+    #[default]
+    InverseSquare,
+    /// The volume is inversely proportional to the distance beyond the reference distance.
+    Inverse {
+        /// The distance within which the sound plays at full volume.
+        reference_distance: f32,
+        /// How fast the volume decreases beyond the reference distance.
+        rolloff_factor: f32,
+    },
+    /// The volume decreases linearly from full volume to silence.
+    Linear {
+        /// The distance within which the sound plays at full volume.
+        reference_distance: f32,
+        /// The distance beyond which the sound is silent.
+        max_distance: f32,
+    },
+    /// The volume decreases exponentially beyond the reference distance.
+    Exponential {
+        /// The distance within which the sound plays at full volume.
+        reference_distance: f32,
+        /// The exponent of the decrease.
+        rolloff_factor: f32,
+    },
+    /// The volume follows a curve of `(distance, volume)` points sorted by distance, and is
+    /// linearly interpolated between them.
+    Curve(Vec<Vec2>),
+}
+
+impl DistanceAttenuation {
+    /// Returns the volume multiplier of a sound at `distance` from the listener.
+    pub fn volume(&self, distance: f32) -> f32 {
+        let distance = distance.max(0.0);
+        match self {
+            Self::InverseSquare => (1.0 / (distance * distance)).min(1.0),
+            Self::Inverse {
+                reference_distance,
+                rolloff_factor,
+            } => {
+                let over = (distance - reference_distance).max(0.0);
+                reference_distance / (reference_distance + rolloff_factor * over).max(f32::EPSILON)
+            }
+            Self::Linear {
+                reference_distance,
+                max_distance,
+            } => {
+                let range = (max_distance - reference_distance).max(f32::EPSILON);
+                1.0 - ((distance - reference_distance) / range).clamp(0.0, 1.0)
+            }
+            Self::Exponential {
+                reference_distance,
+                rolloff_factor,
+            } => (distance.max(*reference_distance) / reference_distance.max(f32::EPSILON))
+                .powf(-rolloff_factor),
+            Self::Curve(points) => {
+                let Some(end) = points.iter().position(|point| point.x > distance) else {
+                    return points.last().map_or(1.0, |point| point.y);
+                };
+                if end == 0 {
+                    return points[0].y;
+                }
+                let (start, end) = (points[end - 1], points[end]);
+                let t = (distance - start.x) / (end.x - start.x);
+                start.y + (end.y - start.y) * t
+            }
+        }
+    }
+}
+
+/// Makes an emitter directional: it plays at full volume in front of it, and quieter behind it.
+///
+/// The cone points along the forward direction (`-Z`) of the `GlobalTransform` of the emitter.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct SoundCone {
+    /// The angle in radians of the cone within which the sound plays at full volume.
+    pub inner_angle: f32,
+    /// The angle in radians of the cone outside of which the sound plays at `outer_volume`.
+    pub outer_angle: f32,
+    /// The volume multiplier outside of the outer cone.
+    pub outer_volume: f32,
+}
+
+impl SoundCone {
+    /// Returns the volume multiplier of a sound emitted along `forward` and heard in the
+    /// `to_listener` direction.
+    pub fn volume(&self, forward: Vec3, to_listener: Vec3) -> f32 {
+        let angle = forward.angle_between(to_listener);
+        if !angle.is_finite() || angle * 2.0 <= self.inner_angle {
+            return 1.0;
+        }
+        if angle * 2.0 >= self.outer_angle {
+            return self.outer_volume;
+        }
+        let t = (angle * 2.0 - self.inner_angle) / (self.outer_angle - self.inner_angle);
+        1.0 + (self.outer_volume - 1.0) * t
+    }
+}
+
+/// Configures how a spatial sound is heard, on an entity played with
+/// [`PlaybackSettings::spatial`](crate::PlaybackSettings::spatial).
+///
+/// Changes are applied while the sound is playing.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpatialEmitter {
+    /// How the sound gets quieter with distance.
+    pub attenuation: DistanceAttenuation,
+    /// The cone the sound is emitted in, or `None` to emit in all directions.
+    pub cone: Option<SoundCone>,
+    /// How much the doppler effect changes the pitch when the emitter and the listener move
+    /// towards or away from each other. `1.0` is physically accurate, and `0.0` disables it.
+    pub doppler_factor: f32,
+}
+
+/// The speed of sound used for the doppler effect, in units per second after applying the
+/// [`SpatialScale`](crate::SpatialScale).
+///
+/// Default is `343.0`, the speed of sound in air in meters per second.
+#[derive(Resource, Clone, Copy, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SpeedOfSound(pub f32);
+
+impl Default for SpeedOfSound {
+    fn default() -> Self {
+        Self(343.0)
+    }
+}
+
+/// The positions and settings a [`SpatialSource`] is rendered with, shared with the ECS.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SpatialParameters {
+    pub(crate) emitter: Vec3,
+    pub(crate) emitter_velocity: Vec3,
+    pub(crate) emitter_forward: Vec3,
+    pub(crate) left_ear: Vec3,
+    pub(crate) right_ear: Vec3,
+    pub(crate) listener_velocity: Vec3,
+    pub(crate) hrtf: bool,
+    pub(crate) speed_of_sound: f32,
+    pub(crate) settings: SpatialEmitter,
+}
+
+/// How often the parameters are read by the audio thread.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long changes of gain, delay and pitch are smoothed over, to avoid clicks.
+const SMOOTHING_TIME: Duration = Duration::from_millis(20);
+
+/// The radius of the head in the spherical head model, in meters.
+const HEAD_RADIUS: f32 = 0.0875;
+
+/// The speed of sound used for the spherical head model, in meters per second.
+const HEAD_SPEED_OF_SOUND: f32 = 343.0;
+
+/// The doppler effect is limited to this pitch range.
+const MAX_DOPPLER_RATE: f32 = 4.0;
+
+/// The values a [`SpatialSource`] moves towards after each parameter update.
+#[derive(Clone, Copy)]
+struct Targets {
+    gains: [f32; 2],
+    /// Interaural time delay of each ear, in seconds.
+    delays: [f32; 2],
+    /// Head shadow of each ear, in the `alpha` of the Brown-Duda model.
+    shadows: [f32; 2],
+    rate: f32,
+}
+
+impl SpatialParameters {
+    fn targets(&self) -> Targets {
+        let settings = &self.settings;
+        let center = (self.left_ear + self.right_ear) / 2.0;
+        let to_listener = center - self.emitter;
+        let cone = settings
+            .cone
+            .map_or(1.0, |cone| cone.volume(self.emitter_forward, to_listener));
+
+        let rate = if settings.doppler_factor != 0.0 && to_listener.length_squared() > 0.0 {
+            let direction = to_listener.normalize();
+            let speed_of_sound = self.speed_of_sound.max(f32::EPSILON);
+            let emitter_speed = (self.emitter_velocity.dot(direction) * settings.doppler_factor)
+                .min(speed_of_sound * 0.99);
+            let listener_speed = self.listener_velocity.dot(direction) * settings.doppler_factor;
+            ((speed_of_sound - listener_speed) / (speed_of_sound - emitter_speed))
+                .clamp(1.0 / MAX_DOPPLER_RATE, MAX_DOPPLER_RATE)
+        } else {
+            1.0
+        };
+
+        if self.hrtf {
+            let gain = settings.attenuation.volume(to_listener.length()) * cone;
+            let ear_axis = (self.right_ear - self.left_ear).normalize_or_zero();
+            let direction = (-to_listener).normalize_or_zero();
+            // Angles between the source and each ear, from 0 (facing the ear) to PI
+            let lateral = direction.dot(ear_axis).clamp(-1.0, 1.0);
+            let angles = [(-lateral).acos(), lateral.acos()];
+            Targets {
+                gains: [gain; 2],
+                delays: angles.map(woodworth_delay),
+                shadows: angles.map(head_shadow),
+                rate,
+            }
+        } else {
+            // Each ear is attenuated by its own distance, and louder the closer it is compared
+            // to the other ear. The closest ear plays at full volume, the other at half volume
+            // at most.
+            let left_distance = self.left_ear.distance(self.emitter);
+            let right_distance = self.right_ear.distance(self.emitter);
+            let max_difference = self.left_ear.distance(self.right_ear).max(f32::EPSILON);
+            let pan = |distance: f32, other_distance: f32| {
+                (((other_distance - distance) / max_difference + 1.0) / 4.0 + 0.5).min(1.0)
+            };
+            Targets {
+                gains: [
+                    pan(left_distance, right_distance)
+                        * settings.attenuation.volume(left_distance)
+                        * cone,
+                    pan(right_distance, left_distance)
+                        * settings.attenuation.volume(right_distance)
+                        * cone,
+                ],
+                delays: [0.0; 2],
+                shadows: [1.0; 2],
+                rate,
+            }
+        }
+    }
+}
+
+/// The interaural time delay of an ear at `angle` from the source, using Woodworth's formula.
+///
+/// A constant offset is added so the delay is never negative.
+fn woodworth_delay(angle: f32) -> f32 {
+    let delay = if angle < PI / 2.0 {
+        -angle.cos()
+    } else {
+        angle - PI / 2.0
+    };
+    HEAD_RADIUS / HEAD_SPEED_OF_SOUND * (1.0 + delay)
+}
+
+/// The head shadow of an ear at `angle` from the source, in the Brown-Duda spherical head model.
+fn head_shadow(angle: f32) -> f32 {
+    const ALPHA_MIN: f32 = 0.1;
+    const THETA_MIN: f32 = 150.0 / 180.0 * PI;
+    (1.0 + ALPHA_MIN / 2.0) + (1.0 - ALPHA_MIN / 2.0) * (angle / THETA_MIN * PI).cos()
+}
+
+/// The head shadow filter of one ear, a first order high shelf.
+#[derive(Default)]
+struct HeadShadowFilter {
+    b0: f32,
+    b1: f32,
+    a1: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl HeadShadowFilter {
+    fn set(&mut self, alpha: f32, sample_rate: f32) {
+        // Bilinear transform of (1 + alpha * s * T) / (1 + s * T), with T = a / c
+        let k = 2.0 * sample_rate * HEAD_RADIUS / HEAD_SPEED_OF_SOUND;
+        self.b0 = (1.0 + alpha * k) / (1.0 + k);
+        self.b1 = (1.0 - alpha * k) / (1.0 + k);
+        self.a1 = (1.0 - k) / (1.0 + k);
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 - self.a1 * self.y1;
+        self.x1 = x;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Renders a sound at a position in space, as stereo.
+///
+/// The input is mixed down to mono, resampled for the doppler effect, then panned to each ear.
+/// With [`SpatialListener::hrtf`](crate::SpatialListener::hrtf), each ear is delayed and
+/// filtered by a spherical head model instead of being panned, for binaural rendering on
+/// headphones.
+pub(crate) struct SpatialSource<I: Source<Item = f32>> {
+    input: I,
+    parameters: Arc<Mutex<SpatialParameters>>,
+    sample_rate: u32,
+    targets: Targets,
+    smoothing: f32,
+    frames_until_update: u32,
+    // Doppler resampling
+    rate: f32,
+    position: f32,
+    previous: f32,
+    current: f32,
+    // Per ear rendering
+    gains: [f32; 2],
+    delays: [f32; 2],
+    filters: [HeadShadowFilter; 2],
+    history: Vec<f32>,
+    history_position: usize,
+    output: [f32; 2],
+    channel: usize,
+}
+
+impl<I: Source<Item = f32>> SpatialSource<I> {
+    pub(crate) fn new(input: I, parameters: Arc<Mutex<SpatialParameters>>) -> Self {
+        let sample_rate = input.sample_rate();
+        let targets = parameters.lock().unwrap().targets();
+        let sample_rate_f32 = sample_rate as f32;
+        let mut filters = [HeadShadowFilter::default(), HeadShadowFilter::default()];
+        for (filter, shadow) in filters.iter_mut().zip(targets.shadows) {
+            filter.set(shadow, sample_rate_f32);
+        }
+        // Long enough for the largest interaural time delay
+        let history_len =
+            (2.0 * HEAD_RADIUS / HEAD_SPEED_OF_SOUND * sample_rate_f32).ceil() as usize + 2;
+        Self {
+            input,
+            parameters,
+            sample_rate,
+            targets,
+            smoothing: smoothing_coefficient(SMOOTHING_TIME, sample_rate_f32),
+            frames_until_update: 0,
+            rate: targets.rate,
+            position: 0.0,
+            previous: 0.0,
+            current: 0.0,
+            gains: targets.gains,
+            delays: targets.delays.map(|delay| delay * sample_rate_f32),
+            filters,
+            history: vec![0.0; history_len],
+            history_position: 0,
+            output: [0.0; 2],
+            channel: 0,
+        }
+    }
+
+    /// Returns the next frame of the input mixed down to mono.
+    fn next_input_frame(&mut self) -> Option<f32> {
+        let channels = self.input.channels().max(1);
+        let mut sum = self.input.next()?;
+        for _ in 1..channels {
+            sum += self.input.next().unwrap_or(0.0);
+        }
+        Some(sum / channels as f32)
+    }
+
+    /// Reads the ear history `delay` samples in the past, with linear interpolation.
+    fn delayed(&self, delay: f32) -> f32 {
+        let len = self.history.len();
+        let delay = delay.clamp(0.0, (len - 2) as f32);
+        let whole = delay as usize;
+        let fraction = delay - whole as f32;
+        let a = self.history[(self.history_position + len - whole) % len];
+        let b = self.history[(self.history_position + len - whole - 1) % len];
+        a + (b - a) * fraction
+    }
+
+    fn next_frame(&mut self) -> Option<[f32; 2]> {
+        let sample_rate = self.sample_rate as f32;
+        if self.frames_until_update == 0 {
+            self.targets = self.parameters.lock().unwrap().targets();
+            for (filter, shadow) in self.filters.iter_mut().zip(self.targets.shadows) {
+                filter.set(shadow, sample_rate);
+            }
+            self.frames_until_update = (UPDATE_INTERVAL.as_secs_f32() * sample_rate) as u32;
+        }
+        self.frames_until_update = self.frames_until_update.saturating_sub(1);
+
+        let smoothing = self.smoothing;
+        let smooth = |value: f32, target: f32| target + smoothing * (value - target);
+        self.rate = smooth(self.rate, self.targets.rate);
+        for ear in 0..2 {
+            self.gains[ear] = smooth(self.gains[ear], self.targets.gains[ear]);
+            self.delays[ear] = smooth(self.delays[ear], self.targets.delays[ear] * sample_rate);
+        }
+
+        self.position += self.rate;
+        while self.position >= 1.0 {
+            self.position -= 1.0;
+            self.previous = self.current;
+            self.current = self.next_input_frame()?;
+        }
+        let mono = self.previous + (self.current - self.previous) * self.position;
+
+        self.history_position = (self.history_position + 1) % self.history.len();
+        self.history[self.history_position] = mono;
+        let mut frame = [0.0; 2];
+        for (ear, sample) in frame.iter_mut().enumerate() {
+            let delayed = self.delayed(self.delays[ear]);
+            *sample = self.filters[ear].process(delayed) * self.gains[ear];
+        }
+        Some(frame)
+    }
+}
+
+impl<I: Source<Item = f32>> Iterator for SpatialSource<I> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            self.output = self.next_frame()?;
+        }
+        let sample = self.output[self.channel];
+        self.channel = (self.channel + 1) % 2;
+        Some(sample)
+    }
+}
+
+impl<I: Source<Item = f32>> Source for SpatialSource<I> {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        // The doppler effect changes the duration
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy_math::Quat;
+    use rodio::buffer::SamplesBuffer;
+
+    use super::*;
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "{actual} is not {expected}"
+        );
+    }
+
+    #[test]
+    fn distance_attenuation() {
+        let inverse_square = DistanceAttenuation::InverseSquare;
+        assert_near(inverse_square.volume(0.5), 1.0);
+        assert_near(inverse_square.volume(2.0), 0.25);
+
+        let inverse = DistanceAttenuation::Inverse {
+            reference_distance: 1.0,
+            rolloff_factor: 1.0,
+        };
+        assert_near(inverse.volume(0.5), 1.0);
+        assert_near(inverse.volume(3.0), 1.0 / 3.0);
+
+        let linear = DistanceAttenuation::Linear {
+            reference_distance: 1.0,
+            max_distance: 5.0,
+        };
+        assert_near(linear.volume(0.5), 1.0);
+        assert_near(linear.volume(3.0), 0.5);
+        assert_near(linear.volume(10.0), 0.0);
+
+        let exponential = DistanceAttenuation::Exponential {
+            reference_distance: 1.0,
+            rolloff_factor: 2.0,
+        };
+        assert_near(exponential.volume(0.5), 1.0);
+        assert_near(exponential.volume(2.0), 0.25);
+
+        let curve = DistanceAttenuation::Curve(vec![Vec2::new(1.0, 1.0), Vec2::new(5.0, 0.2)]);
+        assert_near(curve.volume(0.0), 1.0);
+        assert_near(curve.volume(3.0), 0.6);
+        assert_near(curve.volume(8.0), 0.2);
+        assert_near(DistanceAttenuation::Curve(Vec::new()).volume(3.0), 1.0);
+
+        // Negative distances are treated as zero
+        assert_near(linear.volume(-1.0), 1.0);
+    }
+
+    #[test]
+    fn sound_cone() {
+        let cone = SoundCone {
+            inner_angle: FRAC_PI_2,
+            outer_angle: PI,
+            outer_volume: 0.2,
+        };
+        assert_near(cone.volume(Vec3::NEG_Z, Vec3::NEG_Z), 1.0);
+        assert_near(cone.volume(Vec3::NEG_Z, Vec3::Z), 0.2);
+        // Halfway between the inner and outer cones
+        let direction = Quat::from_rotation_y(PI * 3.0 / 8.0) * Vec3::NEG_Z;
+        assert_near(cone.volume(Vec3::NEG_Z, direction), 0.6);
+        // A listener on the emitter hears it at full volume
+        assert_near(cone.volume(Vec3::NEG_Z, Vec3::ZERO), 1.0);
+    }
+
+    fn parameters(emitter: Vec3) -> SpatialParameters {
+        SpatialParameters {
+            emitter,
+            emitter_forward: Vec3::NEG_Z,
+            left_ear: Vec3::new(-0.1, 0.0, 0.0),
+            right_ear: Vec3::new(0.1, 0.0, 0.0),
+            speed_of_sound: 343.0,
+            settings: SpatialEmitter {
+                doppler_factor: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn closest_ear_is_louder() {
+        let targets = parameters(Vec3::new(0.0, 0.0, -0.5)).targets();
+        assert_near(targets.gains[0], targets.gains[1]);
+
+        let targets = parameters(Vec3::new(0.6, 0.0, 0.0)).targets();
+        // The right ear is at full volume, the left one at half volume
+        assert_near(targets.gains[1], 1.0);
+        assert_near(
+            targets.gains[0],
+            0.5 * DistanceAttenuation::InverseSquare.volume(0.7),
+        );
+        assert_eq!(targets.delays, [0.0; 2]);
+    }
+
+    #[test]
+    fn doppler_rate() {
+        let mut approaching = parameters(Vec3::new(0.0, 0.0, -10.0));
+        approaching.emitter_velocity = Vec3::new(0.0, 0.0, 34.3);
+        assert_near(approaching.targets().rate, 1.0 / 0.9);
+
+        let mut receding = approaching.clone();
+        receding.emitter_velocity = -receding.emitter_velocity;
+        assert_near(receding.targets().rate, 1.0 / 1.1);
+
+        let mut listener_moving = parameters(Vec3::new(0.0, 0.0, -10.0));
+        listener_moving.listener_velocity = Vec3::new(0.0, 0.0, -34.3);
+        assert_near(listener_moving.targets().rate, 1.1);
+
+        approaching.settings.doppler_factor = 0.0;
+        assert_near(approaching.targets().rate, 1.0);
+    }
+
+    #[test]
+    fn hrtf_delays_and_shadows_the_far_ear() {
+        let mut right = parameters(Vec3::new(2.0, 0.0, 0.0));
+        right.hrtf = true;
+        let targets = right.targets();
+        assert_eq!(targets.gains[0], targets.gains[1]);
+        assert!(targets.delays[0] > targets.delays[1]);
+        assert_near(targets.delays[1], 0.0);
+        assert_near(
+            targets.delays[0],
+            HEAD_RADIUS / HEAD_SPEED_OF_SOUND * (1.0 + FRAC_PI_2),
+        );
+        // The near ear is boosted and the far ear is low-passed
+        assert!(targets.shadows[1] > 1.0);
+        assert!(targets.shadows[0] < 1.0);
+
+        let mut front = parameters(Vec3::new(0.0, 0.0, -2.0));
+        front.hrtf = true;
+        let targets = front.targets();
+        assert_near(targets.delays[0], targets.delays[1]);
+        assert_near(targets.shadows[0], targets.shadows[1]);
+    }
+
+    #[test]
+    fn spatial_source_pans_a_mono_source() {
+        let input = SamplesBuffer::new(1, 48_000, vec![0.5; 4_800]);
+        let source = SpatialSource::new(
+            input,
+            Arc::new(Mutex::new(parameters(Vec3::new(0.6, 0.0, 0.0)))),
+        );
+        assert_eq!(source.channels(), 2);
+        assert_eq!(source.sample_rate(), 48_000);
+
+        let samples: Vec<f32> = source.collect();
+        // Every input frame is played once without doppler
+        assert_eq!(samples.len(), 2 * 4_800);
+        let [left, right] = [samples[samples.len() - 2], samples[samples.len() - 1]];
+        assert_near(right, 0.5);
+        assert_near(left, 0.25 * DistanceAttenuation::InverseSquare.volume(0.7));
+    }
+}
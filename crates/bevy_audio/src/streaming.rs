@@ -0,0 +1,536 @@
+use std::{
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_channel::{Receiver, Sender, TryRecvError};
+use bevy_tasks::AsyncComputeTaskPool;
+use rodio::{cpal::FromSample, source::SamplesConverter, Source};
+use thiserror::Error;
+
+use crate::Decodable;
+
+/// The number of frames decoded at once when streaming a sound.
+const CHUNK_FRAMES: usize = 4096;
+
+/// The number of decoded chunks buffered ahead of playback when streaming a sound.
+///
+/// At 44.1 kHz, this is about three quarters of a second of audio.
+const BUFFERED_CHUNKS: usize = 8;
+
+/// An error returned when seeking in a playing sound fails.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekError {
+    /// Only sounds played with [`PlaybackSettings::streaming`](crate::PlaybackSettings::streaming)
+    /// can seek.
+    #[error("only streamed sounds can seek")]
+    NotStreamed,
+    /// The sound has finished playing.
+    #[error("the sound has finished playing")]
+    Finished,
+}
+
+#[derive(Default)]
+struct PlayheadState {
+    /// The index of the next frame to play.
+    frame: AtomicU64,
+    sample_rate: AtomicU32,
+    /// Incremented by each seek, so that chunks decoded before the seek aren't played.
+    generation: AtomicU32,
+}
+
+/// A request to move the playhead of a streamed sound.
+struct Seek {
+    generation: u32,
+    frame: u64,
+}
+
+/// The playback position of a sound, shared between its sink and the audio thread.
+#[derive(Clone, Default)]
+pub(crate) struct Playhead {
+    state: Arc<PlayheadState>,
+    seeks: Option<Sender<Seek>>,
+}
+
+impl Playhead {
+    fn new(sample_rate: u32, seeks: Option<Sender<Seek>>) -> Self {
+        let state = PlayheadState {
+            sample_rate: AtomicU32::new(sample_rate),
+            ..Default::default()
+        };
+        Self {
+            state: Arc::new(state),
+            seeks,
+        }
+    }
+
+    /// Returns the position of the next sample to play.
+    pub(crate) fn position(&self) -> Duration {
+        let sample_rate = self.state.sample_rate.load(Ordering::Relaxed);
+        if sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        let frame = self.state.frame.load(Ordering::Relaxed);
+        Duration::from_secs_f64(frame as f64 / sample_rate as f64)
+    }
+
+    /// Asks the streaming task to continue decoding from `position`.
+    pub(crate) fn seek(&self, position: Duration) -> Result<(), SeekError> {
+        let Some(seeks) = &self.seeks else {
+            return Err(SeekError::NotStreamed);
+        };
+        let sample_rate = self.state.sample_rate.load(Ordering::Relaxed);
+        let frame = (position.as_secs_f64() * sample_rate as f64).round() as u64;
+        let generation = self
+            .state
+            .generation
+            .fetch_add(1, Ordering::AcqRel)
+            .wrapping_add(1);
+        self.state.frame.store(frame, Ordering::Relaxed);
+        seeks
+            .try_send(Seek { generation, frame })
+            .map_err(|_| SeekError::Finished)
+    }
+}
+
+/// Counts the frames played from a sound decoded on the audio thread.
+pub(crate) struct TrackPosition<S> {
+    inner: S,
+    state: Arc<PlayheadState>,
+    frame: u64,
+    sample: u16,
+}
+
+/// Wraps `source` to track its playback position.
+pub(crate) fn track_position<S>(source: S) -> (TrackPosition<S>, Playhead)
+where
+    S: Source<Item = f32>,
+{
+    let playhead = Playhead::new(source.sample_rate(), None);
+    let source = TrackPosition {
+        inner: source,
+        state: playhead.state.clone(),
+        frame: 0,
+        sample: 0,
+    };
+    (source, playhead)
+}
+
+impl<S> Iterator for TrackPosition<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.inner.next()?;
+        self.sample += 1;
+        if self.sample >= self.inner.channels() {
+            self.sample = 0;
+            self.frame += 1;
+            self.state.frame.store(self.frame, Ordering::Relaxed);
+        }
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S> Source for TrackPosition<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// Decoded samples of a streamed sound. An empty chunk marks the end of the sound.
+struct Chunk {
+    generation: u32,
+    start_frame: u64,
+    samples: Vec<f32>,
+}
+
+/// Decodes a sound in chunks of whole frames.
+struct ChunkDecoder<T: Decodable> {
+    source: T,
+    decoder: SamplesConverter<T::Decoder, f32>,
+    channels: usize,
+    looping: bool,
+    generation: u32,
+    /// The index of the next frame to decode.
+    frame: u64,
+}
+
+impl<T> ChunkDecoder<T>
+where
+    T: Decodable,
+    f32: FromSample<T::DecoderItem>,
+{
+    fn new(source: T, looping: bool) -> Self {
+        let decoder = source.decoder().convert_samples();
+        Self {
+            channels: decoder.channels().max(1) as usize,
+            source,
+            decoder,
+            looping,
+            generation: 0,
+            frame: 0,
+        }
+    }
+
+    fn next_chunk(&mut self) -> Chunk {
+        let len = CHUNK_FRAMES * self.channels;
+        let mut samples = Vec::with_capacity(len);
+        samples.extend(self.decoder.by_ref().take(len));
+        // An empty sound would restart forever
+        if samples.is_empty() && self.looping && self.frame > 0 {
+            self.restart();
+            samples.extend(self.decoder.by_ref().take(len));
+        }
+        samples.truncate(samples.len() - samples.len() % self.channels);
+
+        let chunk = Chunk {
+            generation: self.generation,
+            start_frame: self.frame,
+            samples,
+        };
+        self.frame += (chunk.samples.len() / self.channels) as u64;
+        chunk
+    }
+
+    fn restart(&mut self) {
+        self.decoder = self.source.decoder().convert_samples();
+        self.frame = 0;
+    }
+
+    fn seek(&mut self, seek: Seek) {
+        // Decoders can only go forward, so seeking back restarts from the beginning.
+        if seek.frame < self.frame {
+            self.restart();
+        }
+        let skip = (seek.frame - self.frame) as usize * self.channels;
+        self.decoder.by_ref().take(skip).for_each(drop);
+        self.frame = seek.frame;
+        self.generation = seek.generation;
+    }
+}
+
+/// Decodes chunks ahead of playback until the sound ends or is dropped.
+async fn decode_chunks<T>(
+    mut decoder: ChunkDecoder<T>,
+    chunks: Sender<Chunk>,
+    seeks: Receiver<Seek>,
+) where
+    T: Decodable,
+    f32: FromSample<T::DecoderItem>,
+{
+    loop {
+        while let Ok(seek) = seeks.try_recv() {
+            decoder.seek(seek);
+        }
+        let chunk = decoder.next_chunk();
+        let finished = chunk.samples.is_empty();
+        if chunks.send(chunk).await.is_err() {
+            // The sound was dropped
+            return;
+        }
+        if finished {
+            // The end of the sound may still be playing, and can seek back
+            let Ok(seek) = seeks.recv().await else {
+                return;
+            };
+            decoder.seek(seek);
+        }
+    }
+}
+
+/// A sound decoded in chunks on the [`AsyncComputeTaskPool`], and played from a bounded buffer.
+pub(crate) struct StreamingSource {
+    chunks: Receiver<Chunk>,
+    state: Arc<PlayheadState>,
+    generation: u32,
+    samples: Vec<f32>,
+    start_frame: u64,
+    index: usize,
+    /// The index of the next sample in its frame.
+    sample: u16,
+    /// The remaining samples of a silent frame, played when the buffer runs out.
+    silence: u16,
+    finished: bool,
+    channels: u16,
+    sample_rate: u32,
+    total_duration: Option<Duration>,
+}
+
+/// Streams `source`, decoding it on a background task instead of the audio thread.
+pub(crate) fn stream<T>(source: T, looping: bool) -> (StreamingSource, Playhead)
+where
+    T: Decodable,
+    f32: FromSample<T::DecoderItem>,
+{
+    let mut decoder = ChunkDecoder::new(source, looping);
+    let channels = decoder.decoder.channels();
+    let sample_rate = decoder.decoder.sample_rate();
+    let total_duration = if looping {
+        None
+    } else {
+        decoder.decoder.total_duration()
+    };
+
+    let (chunk_sender, chunk_receiver) = async_channel::bounded(BUFFERED_CHUNKS);
+    let (seek_sender, seek_receiver) = async_channel::unbounded();
+    // Decode the first chunk right away, so that playback doesn't wait for the task to start
+    let _ = chunk_sender.try_send(decoder.next_chunk());
+    AsyncComputeTaskPool::get()
+        .spawn(decode_chunks(decoder, chunk_sender, seek_receiver))
+        .detach();
+
+    let playhead = Playhead::new(sample_rate, Some(seek_sender));
+    let source = StreamingSource {
+        chunks: chunk_receiver,
+        state: playhead.state.clone(),
+        generation: 0,
+        samples: Vec::new(),
+        start_frame: 0,
+        index: 0,
+        sample: 0,
+        silence: 0,
+        finished: false,
+        channels,
+        sample_rate,
+        total_duration,
+    };
+    (source, playhead)
+}
+
+impl Iterator for StreamingSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.finished {
+            return None;
+        }
+        if self.silence > 0 {
+            self.silence -= 1;
+            return Some(0.0);
+        }
+
+        if self.sample == 0 {
+            let generation = self.state.generation.load(Ordering::Acquire);
+            if generation != self.generation {
+                // Skip the rest of the chunk, it was decoded before seeking
+                self.generation = generation;
+                self.index = self.samples.len();
+            }
+        }
+
+        while self.index >= self.samples.len() {
+            match self.chunks.try_recv() {
+                Ok(chunk) if chunk.generation != self.generation => {}
+                Ok(chunk) if chunk.samples.is_empty() => {
+                    self.finished = true;
+                    return None;
+                }
+                Ok(chunk) => {
+                    self.samples = chunk.samples;
+                    self.start_frame = chunk.start_frame;
+                    self.index = 0;
+                }
+                Err(TryRecvError::Empty) => {
+                    // The decoder fell behind, play silence until it catches up
+                    self.silence = self.channels.saturating_sub(1);
+                    return Some(0.0);
+                }
+                Err(TryRecvError::Closed) => {
+                    self.finished = true;
+                    return None;
+                }
+            }
+        }
+
+        let sample = self.samples[self.index];
+        self.index += 1;
+        self.sample += 1;
+        if self.sample >= self.channels {
+            self.sample = 0;
+            let frame = self.start_frame + (self.index / self.channels as usize) as u64;
+            self.state.frame.store(frame, Ordering::Relaxed);
+        }
+        Some(sample)
+    }
+}
+
+impl Source for StreamingSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_tasks::TaskPool;
+    use rodio::buffer::SamplesBuffer;
+
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 44_100;
+
+    /// A stereo sound whose frame `n` holds the samples `n + 1` and `-(n + 1)`.
+    struct Ramp {
+        frames: usize,
+    }
+
+    fn ramp(frames: std::ops::Range<usize>) -> Vec<f32> {
+        frames
+            .flat_map(|frame| [frame as f32 + 1.0, -(frame as f32 + 1.0)])
+            .collect()
+    }
+
+    impl Decodable for Ramp {
+        type DecoderItem = f32;
+        type Decoder = SamplesBuffer<f32>;
+
+        fn decoder(&self) -> Self::Decoder {
+            SamplesBuffer::new(2, SAMPLE_RATE, ramp(0..self.frames))
+        }
+    }
+
+    fn frames(count: usize) -> Duration {
+        Duration::from_secs_f64(count as f64 / SAMPLE_RATE as f64)
+    }
+
+    /// Plays the next `count` samples, skipping the silence played while the decoder catches up.
+    fn play(source: &mut StreamingSource, count: usize) -> Vec<f32> {
+        source.filter(|sample| *sample != 0.0).take(count).collect()
+    }
+
+    #[test]
+    fn decoder_chunks_whole_frames() {
+        let mut decoder = ChunkDecoder::new(
+            Ramp {
+                frames: CHUNK_FRAMES + 10,
+            },
+            false,
+        );
+        let chunk = decoder.next_chunk();
+        assert_eq!(chunk.start_frame, 0);
+        assert_eq!(chunk.samples, ramp(0..CHUNK_FRAMES));
+        let chunk = decoder.next_chunk();
+        assert_eq!(chunk.start_frame, CHUNK_FRAMES as u64);
+        assert_eq!(chunk.samples, ramp(CHUNK_FRAMES..CHUNK_FRAMES + 10));
+        assert!(decoder.next_chunk().samples.is_empty());
+
+        // A looping sound restarts once it ends
+        let mut decoder = ChunkDecoder::new(Ramp { frames: 10 }, true);
+        assert_eq!(decoder.next_chunk().samples, ramp(0..10));
+        let chunk = decoder.next_chunk();
+        assert_eq!(chunk.start_frame, 0);
+        assert_eq!(chunk.samples, ramp(0..10));
+    }
+
+    #[test]
+    fn decoder_seeks_forward_and_back() {
+        let mut decoder = ChunkDecoder::new(Ramp { frames: 1000 }, false);
+        decoder.next_chunk();
+        decoder.seek(Seek {
+            generation: 1,
+            frame: 100,
+        });
+        let chunk = decoder.next_chunk();
+        assert_eq!(chunk.generation, 1);
+        assert_eq!(chunk.start_frame, 100);
+        assert_eq!(chunk.samples, ramp(100..1000));
+
+        decoder.seek(Seek {
+            generation: 2,
+            frame: 5,
+        });
+        let chunk = decoder.next_chunk();
+        assert_eq!(chunk.generation, 2);
+        assert_eq!(chunk.samples, ramp(5..1000));
+    }
+
+    #[test]
+    fn streamed_sound_plays_every_frame() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let length = 3 * CHUNK_FRAMES + 100;
+        let (mut source, playhead) = stream(Ramp { frames: length }, false);
+        assert_eq!(source.channels(), 2);
+        assert_eq!(source.sample_rate(), SAMPLE_RATE);
+        assert_eq!(source.total_duration(), Some(frames(length)));
+
+        assert_eq!(play(&mut source, 2 * length + 1), ramp(0..length));
+        assert_eq!(playhead.position(), frames(length));
+    }
+
+    #[test]
+    fn streamed_sound_seeks() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let length = 3 * CHUNK_FRAMES + 100;
+        let (mut source, playhead) = stream(Ramp { frames: length }, false);
+
+        assert_eq!(play(&mut source, 1000), ramp(0..500));
+        assert_eq!(playhead.position(), frames(500));
+
+        playhead.seek(frames(2 * CHUNK_FRAMES)).unwrap();
+        assert_eq!(playhead.position(), frames(2 * CHUNK_FRAMES));
+        assert_eq!(
+            play(&mut source, 1000),
+            ramp(2 * CHUNK_FRAMES..2 * CHUNK_FRAMES + 500)
+        );
+
+        // Seeking back restarts the decoding
+        playhead.seek(frames(10)).unwrap();
+        assert_eq!(play(&mut source, 2 * length), ramp(10..length));
+        assert_eq!(playhead.position(), frames(length));
+    }
+
+    #[test]
+    fn tracked_position() {
+        let source = SamplesBuffer::new(2, 4, ramp(0..4));
+        let (mut source, playhead) = track_position(source);
+        assert_eq!(playhead.position(), Duration::ZERO);
+
+        // The position moves once a whole frame is played
+        source.next();
+        assert_eq!(playhead.position(), Duration::ZERO);
+        source.next();
+        source.next();
+        assert_eq!(playhead.position(), Duration::from_millis(250));
+        source.for_each(drop);
+        assert_eq!(playhead.position(), Duration::from_secs(1));
+
+        assert_eq!(playhead.seek(Duration::ZERO), Err(SeekError::NotStreamed));
+    }
+}
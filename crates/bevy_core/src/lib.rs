@@ -40,7 +40,9 @@ pub struct TypeRegistrationPlugin;
 
 impl Plugin for TypeRegistrationPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Entity>().register_type::<Name>();
+        app.register_type::<Entity>()
+            .register_type::<Disabled>()
+            .register_type::<Name>();
 
         register_rust_types(app);
         register_math_types(app);
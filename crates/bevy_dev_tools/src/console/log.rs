@@ -0,0 +1,81 @@
+use super::{Console, ConsoleLineKind};
+use bevy_ecs::system::ResMut;
+use bevy_log::{
+    tracing_subscriber::{
+        layer::{Context, SubscriberExt},
+        Layer,
+    },
+    BoxedSubscriber, Level,
+};
+use bevy_utils::tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use std::{collections::VecDeque, fmt::Write, sync::Mutex};
+
+/// The number of captured logs kept until they are echoed in a [`Console`].
+const MAX_CAPTURED_LOGS: usize = 1024;
+
+/// The logs captured by [`ConsoleLogLayer`], waiting to be echoed in the [`Console`].
+///
+/// The subscriber is global, so the captured logs are too.
+static CAPTURED_LOGS: Mutex<VecDeque<(Level, String)>> = Mutex::new(VecDeque::new());
+
+/// Captures the logs to echo them in the [`Console`], to be set as the
+/// [`LogPlugin::update_subscriber`](bevy_log::LogPlugin::update_subscriber).
+///
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup};
+/// # use bevy_dev_tools::console::{capture_console_logs, ConsolePlugin};
+/// # use bevy_log::LogPlugin;
+/// App::new()
+///     .add_plugins(DefaultPlugins.set(LogPlugin {
+///         update_subscriber: Some(capture_console_logs),
+///         ..Default::default()
+///     }))
+///     .add_plugins(ConsolePlugin::default())
+///     .run();
+/// ```
+pub fn capture_console_logs(subscriber: BoxedSubscriber) -> BoxedSubscriber {
+    Box::new(subscriber.with(ConsoleLogLayer))
+}
+
+struct ConsoleLogLayer;
+
+impl<S: Subscriber> Layer<S> for ConsoleLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = LogVisitor(format!("{}: ", metadata.target()));
+        event.record(&mut visitor);
+        let Ok(mut logs) = CAPTURED_LOGS.lock() else {
+            return;
+        };
+        if logs.len() == MAX_CAPTURED_LOGS {
+            logs.pop_front();
+        }
+        logs.push_back((*metadata.level(), visitor.0));
+    }
+}
+
+/// Formats the fields of a log, the message first.
+struct LogVisitor(String);
+
+impl Visit for LogVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{value:?}");
+        } else {
+            let _ = write!(self.0, " {}={value:?}", field.name());
+        }
+    }
+}
+
+/// Echoes the logs captured by [`capture_console_logs`] in the [`Console`].
+pub fn echo_console_logs(mut console: ResMut<Console>) {
+    let Ok(mut logs) = CAPTURED_LOGS.lock() else {
+        return;
+    };
+    for (level, text) in logs.drain(..) {
+        console.push_line(ConsoleLineKind::Log(level), text);
+    }
+}
@@ -0,0 +1,697 @@
+//! An in-game developer console.
+//!
+//! The console is a text overlay, toggled with [`ConsoleSettings::toggle_key`], in which
+//! commands are typed and run. Commands are registered one-shot systems, added with
+//! [`ConsoleApp::add_console_command`]: the arguments typed after the name of a command are
+//! parsed into the reflected input of its system, so a command taking a `struct Spawn { count:
+//! u32 }` is run with `spawn 3`.
+//!
+//! Each argument is parsed as [RON](ron), except for [`String`] arguments which are taken as is.
+//! Arguments containing spaces are quoted, like `say "hello world"`.
+//!
+//! ```
+//! # use bevy_app::App;
+//! # use bevy_dev_tools::console::{Console, ConsoleApp, ConsolePlugin};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! #[derive(Reflect)]
+//! struct Spawn {
+//!     count: u32,
+//! }
+//!
+//! fn spawn(In(spawn): In<Spawn>, mut console: ResMut<Console>) {
+//!     console.print(format!("spawning {} enemies", spawn.count));
+//! }
+//!
+//! App::new()
+//!     .add_plugins(ConsolePlugin::default())
+//!     .add_console_command("spawn", "Spawns enemies", spawn);
+//! ```
+//!
+//! The logs can be echoed in the console by adding [`capture_console_logs`] to the
+//! [`LogPlugin`](bevy_log::LogPlugin).
+
+mod log;
+mod ui;
+
+pub use log::capture_console_logs;
+pub use ui::{ConsoleInputText, ConsoleLogText, ConsoleRoot};
+
+use crate::parse::parse_reflect_value;
+use bevy_app::{App, Plugin, PostUpdate, Startup, Update};
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectSystemError, ReflectSystemId},
+};
+use bevy_input::keyboard::KeyCode;
+use bevy_log::Level;
+use bevy_reflect::{
+    DynamicStruct, DynamicTuple, DynamicTupleStruct, FromReflect, GetTypeRegistration, Reflect,
+    TypeInfo, TypeRegistry, Typed,
+};
+use bevy_utils::HashMap;
+use std::{any::TypeId, borrow::Cow, collections::VecDeque};
+use thiserror::Error;
+
+/// Adds the developer [`Console`], with its overlay and the `help` and `clear` commands.
+pub struct ConsolePlugin {
+    /// The key opening and closing the console.
+    pub toggle_key: KeyCode,
+    /// The number of lines kept in the console.
+    pub max_lines: usize,
+}
+
+impl Default for ConsolePlugin {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::Grave,
+            max_lines: 256,
+        }
+    }
+}
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ConsoleSettings {
+            toggle_key: self.toggle_key,
+            max_lines: self.max_lines,
+        })
+        .init_resource::<Console>()
+        .init_resource::<ConsoleCommands>()
+        .add_console_command("help", "Lists the commands", help)
+        .add_console_command("clear", "Clears the console", clear)
+        .add_systems(
+            Update,
+            (ui::handle_console_input, run_console_commands).chain(),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                log::echo_console_logs,
+                trim_console_lines,
+                ui::update_console_ui,
+            )
+                .chain(),
+        )
+        .add_systems(Startup, ui::spawn_console_ui);
+    }
+}
+
+/// The settings of the [`Console`], set by the [`ConsolePlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct ConsoleSettings {
+    /// The key opening and closing the console.
+    pub toggle_key: KeyCode,
+    /// The number of lines kept in the console, older lines are removed.
+    pub max_lines: usize,
+}
+
+/// The state of the developer console: whether it is open, the input being typed, the command
+/// history and the printed lines.
+#[derive(Resource, Default, Debug)]
+pub struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    lines: VecDeque<ConsoleLine>,
+    pending: Vec<String>,
+}
+
+/// A line printed in the [`Console`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsoleLine {
+    /// What printed the line.
+    pub kind: ConsoleLineKind,
+    /// The text of the line.
+    pub text: String,
+}
+
+/// What printed a [`ConsoleLine`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsoleLineKind {
+    /// A command entered in the console.
+    Command,
+    /// The output of a command.
+    Output,
+    /// An error running a command.
+    Error,
+    /// A log captured by [`capture_console_logs`].
+    Log(Level),
+}
+
+impl Console {
+    /// Returns `true` if the console is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens or closes the console.
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    /// Opens the console if it is closed, and closes it otherwise.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Returns the input being typed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Replaces the input being typed.
+    pub fn set_input(&mut self, input: impl Into<String>) {
+        self.input = input.into();
+    }
+
+    /// Returns the input being typed, to be edited.
+    pub fn input_mut(&mut self) -> &mut String {
+        &mut self.input
+    }
+
+    /// Runs the input being typed, and clears it.
+    pub fn submit(&mut self) {
+        let input = std::mem::take(&mut self.input);
+        self.run(input);
+    }
+
+    /// Runs `command` in the next [`Update`] schedule, like if it was typed in the console, and
+    /// adds it to the history.
+    pub fn run(&mut self, command: impl Into<String>) {
+        let command = command.into();
+        self.history_index = None;
+        if command.trim().is_empty() {
+            return;
+        }
+        if self.history.last() != Some(&command) {
+            self.history.push(command.clone());
+        }
+        self.pending.push(command);
+    }
+
+    /// Returns the commands which were run, from the oldest to the newest.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Replaces the input with the previous command of the history.
+    pub fn previous_command(&mut self) {
+        let index = match self.history_index {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.history.is_empty() => return,
+            None => self.history.len() - 1,
+        };
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    /// Replaces the input with the next command of the history, or clears it after the newest
+    /// command.
+    pub fn next_command(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.input = self.history[index + 1].clone();
+        } else {
+            self.history_index = None;
+            self.input.clear();
+        }
+    }
+
+    /// Completes the name of the command being typed, up to the longest prefix shared by the
+    /// matching commands, and returns the names of these commands.
+    pub fn autocomplete<'a>(&mut self, commands: &'a ConsoleCommands) -> Vec<&'a str> {
+        if self.input.contains(char::is_whitespace) {
+            return Vec::new();
+        }
+        let candidates = commands.complete(&self.input);
+        if let Some((first, rest)) = candidates.split_first() {
+            let mut prefix = *first;
+            for candidate in rest {
+                let shared = prefix
+                    .char_indices()
+                    .zip(candidate.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(prefix.len().min(candidate.len()), |((index, _), _)| index);
+                prefix = &prefix[..shared];
+            }
+            self.input = prefix.to_string();
+            if rest.is_empty() {
+                self.input.push(' ');
+            }
+        }
+        candidates
+    }
+
+    /// Returns the printed lines, from the oldest to the newest.
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &ConsoleLine> + ExactSizeIterator {
+        self.lines.iter()
+    }
+
+    /// Prints a line of `kind`.
+    pub fn push_line(&mut self, kind: ConsoleLineKind, text: impl Into<String>) {
+        self.lines.push_back(ConsoleLine {
+            kind,
+            text: text.into(),
+        });
+    }
+
+    /// Prints the output of a command.
+    pub fn print(&mut self, text: impl Into<String>) {
+        self.push_line(ConsoleLineKind::Output, text);
+    }
+
+    /// Prints an error.
+    pub fn print_error(&mut self, text: impl Into<String>) {
+        self.push_line(ConsoleLineKind::Error, text);
+    }
+
+    /// Removes the printed lines.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+/// A command of the [`Console`], added with [`ConsoleApp::add_console_command`].
+#[derive(Clone, Debug)]
+pub struct ConsoleCommand {
+    name: Cow<'static, str>,
+    help: Cow<'static, str>,
+    system: ReflectSystemId,
+    input: &'static TypeInfo,
+}
+
+impl ConsoleCommand {
+    /// Returns the name of the command.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the description of the command.
+    pub fn help(&self) -> &str {
+        &self.help
+    }
+
+    /// Returns the system run by the command.
+    pub fn system(&self) -> ReflectSystemId {
+        self.system
+    }
+
+    /// Returns the name of the command followed by the names of its arguments, like
+    /// `spawn <count>`.
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        let mut push_argument = |argument: &str| {
+            usage.push_str(" <");
+            usage.push_str(argument);
+            usage.push('>');
+        };
+        match self.input {
+            TypeInfo::Struct(info) => info.iter().for_each(|field| push_argument(field.name())),
+            TypeInfo::TupleStruct(info) => info
+                .iter()
+                .for_each(|field| push_argument(short_type_path(field.type_path()))),
+            TypeInfo::Tuple(info) => info
+                .iter()
+                .for_each(|field| push_argument(short_type_path(field.type_path()))),
+            info => push_argument(short_type_path(info.type_path())),
+        }
+        usage
+    }
+
+    /// Parses the arguments of the command into the input of its system.
+    ///
+    /// The types of the arguments must be registered in `registry`.
+    pub fn parse_input(
+        &self,
+        arguments: &[&str],
+        registry: &TypeRegistry,
+    ) -> Result<Box<dyn Reflect>, ConsoleError> {
+        let expected = match self.input {
+            TypeInfo::Struct(info) => info.field_len(),
+            TypeInfo::TupleStruct(info) => info.field_len(),
+            TypeInfo::Tuple(info) => info.field_len(),
+            _ => 1,
+        };
+        if arguments.len() > expected {
+            return Err(ConsoleError::TooManyArguments {
+                command: self.name.to_string(),
+                expected,
+            });
+        }
+        let argument = |index: usize, name: &str, type_id: TypeId| {
+            let argument = arguments
+                .get(index)
+                .ok_or_else(|| ConsoleError::MissingArgument {
+                    command: self.name.to_string(),
+                    argument: name.to_string(),
+                })?;
+            parse_argument(argument, type_id, registry)
+        };
+        Ok(match self.input {
+            TypeInfo::Struct(info) => {
+                let mut input = DynamicStruct::default();
+                input.set_represented_type(Some(self.input));
+                for (index, field) in info.iter().enumerate() {
+                    input.insert_boxed(
+                        field.name(),
+                        argument(index, field.name(), field.type_id())?,
+                    );
+                }
+                Box::new(input)
+            }
+            TypeInfo::TupleStruct(info) => {
+                let mut input = DynamicTupleStruct::default();
+                input.set_represented_type(Some(self.input));
+                for (index, field) in info.iter().enumerate() {
+                    let name = short_type_path(field.type_path());
+                    input.insert_boxed(argument(index, name, field.type_id())?);
+                }
+                Box::new(input)
+            }
+            TypeInfo::Tuple(info) => {
+                let mut input = DynamicTuple::default();
+                input.set_represented_type(Some(self.input));
+                for (index, field) in info.iter().enumerate() {
+                    let name = short_type_path(field.type_path());
+                    input.insert_boxed(argument(index, name, field.type_id())?);
+                }
+                Box::new(input)
+            }
+            info => argument(0, short_type_path(info.type_path()), info.type_id())?,
+        })
+    }
+}
+
+fn parse_argument(
+    argument: &str,
+    type_id: TypeId,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, ConsoleError> {
+    let registration = registry
+        .get(type_id)
+        .ok_or_else(|| ConsoleError::UnregisteredArgument(format!("{type_id:?}")))?;
+    parse_reflect_value(argument, registration, registry).map_err(|error| {
+        ConsoleError::InvalidArgument {
+            argument: argument.to_string(),
+            type_path: registration.type_info().type_path(),
+            error: error.to_string(),
+        }
+    })
+}
+
+fn short_type_path(type_path: &str) -> &str {
+    type_path.rsplit("::").next().unwrap_or(type_path)
+}
+
+/// A [`Resource`] holding the commands of the [`Console`].
+#[derive(Resource, Default, Debug)]
+pub struct ConsoleCommands {
+    commands: HashMap<Cow<'static, str>, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    /// Returns the command with the name.
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name)
+    }
+
+    /// Returns the commands, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        let mut commands = self.commands.values().collect::<Vec<_>>();
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        commands.into_iter()
+    }
+
+    /// Returns the names of the commands starting with `prefix`, sorted.
+    pub fn complete(&self, prefix: &str) -> Vec<&str> {
+        let mut names = self
+            .commands
+            .keys()
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| &**name)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    /// Adds a command, replacing and returning the command which had its name before.
+    pub fn insert(&mut self, command: ConsoleCommand) -> Option<ConsoleCommand> {
+        self.commands.insert(command.name.clone(), command)
+    }
+
+    /// Removes the command with the name, returning it.
+    ///
+    /// This doesn't remove its system from the world: use
+    /// [`World::remove_system`](bevy_ecs::world::World::remove_system) for that.
+    pub fn remove(&mut self, name: &str) -> Option<ConsoleCommand> {
+        self.commands.remove(name)
+    }
+}
+
+/// An error running a command of the [`Console`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConsoleError {
+    /// No command has the name.
+    #[error("unknown command `{0}`, type `help` to list the commands")]
+    UnknownCommand(String),
+    /// An argument of the command is missing.
+    #[error("missing argument <{argument}> of `{command}`")]
+    MissingArgument {
+        /// The name of the command.
+        command: String,
+        /// The name of the missing argument.
+        argument: String,
+    },
+    /// More arguments were given than the command takes.
+    #[error("`{command}` takes {expected} arguments")]
+    TooManyArguments {
+        /// The name of the command.
+        command: String,
+        /// The number of arguments the command takes.
+        expected: usize,
+    },
+    /// An argument couldn't be parsed.
+    #[error("invalid argument `{argument}` of type {type_path}: {error}")]
+    InvalidArgument {
+        /// The argument.
+        argument: String,
+        /// The type path of the argument.
+        type_path: &'static str,
+        /// The parsing error.
+        error: String,
+    },
+    /// The type of an argument isn't registered in the [`AppTypeRegistry`].
+    #[error("the type {0} of an argument is not registered")]
+    UnregisteredArgument(String),
+    /// An argument was not closed by a quote.
+    #[error("unclosed quote")]
+    UnclosedQuote,
+    /// The system of the command couldn't run.
+    #[error(transparent)]
+    System(#[from] ReflectSystemError),
+}
+
+/// Splits a command line into its words, keeping the quoted words together.
+fn split_command_line(line: &str) -> Result<Vec<&str>, ConsoleError> {
+    let mut words = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        let (word, next) = if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').ok_or(ConsoleError::UnclosedQuote)?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        words.push(word);
+        rest = next.trim_start();
+    }
+    Ok(words)
+}
+
+/// Runs a command line in `world`, returning the output of its system.
+///
+/// This is how the [`Console`] runs the commands, which doesn't need to be open.
+pub fn run_console_command(
+    world: &mut World,
+    line: &str,
+) -> Result<Box<dyn Reflect>, ConsoleError> {
+    let words = split_command_line(line)?;
+    let Some((name, arguments)) = words.split_first() else {
+        return Ok(Box::new(()));
+    };
+    let commands = world.resource::<ConsoleCommands>();
+    let command = commands
+        .get(name)
+        .ok_or_else(|| ConsoleError::UnknownCommand(name.to_string()))?;
+    let input = command.parse_input(arguments, &world.resource::<AppTypeRegistry>().read())?;
+    let system = command.system;
+    Ok(world.run_reflect_system(system, input)?)
+}
+
+/// Runs the commands entered in the [`Console`], and prints their output.
+pub fn run_console_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Console>().pending);
+    for line in pending {
+        world
+            .resource_mut::<Console>()
+            .push_line(ConsoleLineKind::Command, format!("> {line}"));
+        let result = run_console_command(world, &line);
+        let mut console = world.resource_mut::<Console>();
+        match result {
+            Ok(output) if output.is::<()>() => {}
+            Ok(output) => console.print(format!("{output:?}")),
+            Err(error) => console.print_error(error.to_string()),
+        }
+    }
+}
+
+/// Removes the oldest lines of the [`Console`] beyond [`ConsoleSettings::max_lines`].
+pub fn trim_console_lines(settings: Res<ConsoleSettings>, mut console: ResMut<Console>) {
+    let excess = console.lines.len().saturating_sub(settings.max_lines);
+    if excess > 0 {
+        console.lines.drain(..excess);
+    }
+}
+
+fn help(commands: Res<ConsoleCommands>, mut console: ResMut<Console>) {
+    for command in commands.iter() {
+        console.print(format!("{} - {}", command.usage(), command.help()));
+    }
+}
+
+fn clear(mut console: ResMut<Console>) {
+    console.clear();
+}
+
+/// Adds commands to the [`Console`].
+pub trait ConsoleApp {
+    /// Registers `system` as a one-shot system, and adds a command named `name` running it.
+    ///
+    /// The arguments of the command are parsed into the input `I` of the system: the fields of
+    /// a struct or a tuple are parsed from an argument each, and other types from a single
+    /// argument. The output of the system is printed, unless it is `()`.
+    fn add_console_command<I, O, M>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        help: impl Into<Cow<'static, str>>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) -> &mut Self
+    where
+        I: FromReflect + Typed + GetTypeRegistration,
+        O: Reflect;
+}
+
+impl ConsoleApp for App {
+    fn add_console_command<I, O, M>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        help: impl Into<Cow<'static, str>>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) -> &mut Self
+    where
+        I: FromReflect + Typed + GetTypeRegistration,
+        O: Reflect,
+    {
+        let name = name.into();
+        self.register_type::<I>();
+        let system = self.world.register_reflect_system(name.clone(), system);
+        self.world
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .insert(ConsoleCommand {
+                name,
+                help: help.into(),
+                system,
+                input: I::type_info(),
+            });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_console_command, Console, ConsoleApp, ConsoleError, ConsolePlugin};
+    use bevy_app::App;
+    use bevy_ecs::prelude::*;
+    use bevy_input::{keyboard::KeyCode, ButtonInput};
+    use bevy_reflect::Reflect;
+    use bevy_window::ReceivedCharacter;
+
+    #[derive(Reflect)]
+    struct Spawn {
+        name: String,
+        count: u32,
+    }
+
+    #[derive(Resource, Default)]
+    struct Spawned(Vec<(String, u32)>);
+
+    fn spawn(In(spawn): In<Spawn>, mut spawned: ResMut<Spawned>) -> usize {
+        spawned.0.push((spawn.name, spawn.count));
+        spawned.0.len()
+    }
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .add_event::<ReceivedCharacter>()
+            .add_plugins(ConsolePlugin::default())
+            .init_resource::<Spawned>()
+            .add_console_command("spawn", "Spawns enemies", spawn);
+        app
+    }
+
+    #[test]
+    fn run_commands() {
+        let mut app = test_app();
+
+        let output = run_console_command(&mut app.world, r#"spawn "big bat" 3"#).unwrap();
+        assert_eq!(Some(&1), output.downcast_ref::<usize>());
+        assert_eq!(
+            app.world.resource::<Spawned>().0,
+            vec![("big bat".to_string(), 3)]
+        );
+
+        assert!(matches!(
+            run_console_command(&mut app.world, "spawn bat"),
+            Err(ConsoleError::MissingArgument { .. })
+        ));
+        assert!(matches!(
+            run_console_command(&mut app.world, "spawn bat three"),
+            Err(ConsoleError::InvalidArgument { .. })
+        ));
+        assert_eq!(
+            run_console_command(&mut app.world, "despawn").unwrap_err(),
+            ConsoleError::UnknownCommand("despawn".to_string())
+        );
+    }
+
+    #[test]
+    fn history_and_autocomplete() {
+        let mut app = test_app();
+
+        app.world
+            .resource_scope(|world, mut console: Mut<Console>| {
+                console.set_input("sp");
+                assert_eq!(console.autocomplete(world.resource()), vec!["spawn"]);
+                assert_eq!(console.input(), "spawn ");
+                console.input_mut().push_str("bat 2");
+                console.submit();
+            });
+        app.update();
+
+        let mut console = app.world.resource_mut::<Console>();
+        assert_eq!(console.history(), ["spawn bat 2"]);
+        assert_eq!(console.lines().last().unwrap().text, "1");
+        console.previous_command();
+        assert_eq!(console.input(), "spawn bat 2");
+        console.next_command();
+        assert_eq!(console.input(), "");
+    }
+}
@@ -0,0 +1,161 @@
+use super::{Console, ConsoleCommands, ConsoleLineKind, ConsoleSettings};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_log::Level;
+use bevy_render::{color::Color, view::Visibility};
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    FlexDirection, JustifyContent, Overflow, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_window::ReceivedCharacter;
+
+const FONT_SIZE: f32 = 16.0;
+
+/// Marks the root node of the [`Console`] overlay.
+#[derive(Component, Default)]
+pub struct ConsoleRoot;
+
+/// Marks the text node showing the lines of the [`Console`].
+#[derive(Component, Default)]
+pub struct ConsoleLogText;
+
+/// Marks the text node showing the input of the [`Console`].
+#[derive(Component, Default)]
+pub struct ConsoleInputText;
+
+/// Spawns the hidden [`Console`] overlay, drawn above the other UI nodes.
+pub(super) fn spawn_console_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            ConsoleRoot,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(40.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    overflow: Overflow::clip(),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0.05, 0.05, 0.05, 0.85).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((ConsoleLogText, TextBundle::default()));
+            parent.spawn((
+                ConsoleInputText,
+                TextBundle::from_section("", text_style(Color::WHITE)),
+            ));
+        });
+}
+
+/// Toggles the [`Console`] with [`ConsoleSettings::toggle_key`], and edits its input with the
+/// keyboard while it is open.
+///
+/// `Enter` runs the input, `Tab` completes the name of a command, and the arrow keys go through
+/// the history.
+pub(super) fn handle_console_input(
+    settings: Res<ConsoleSettings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut console: ResMut<Console>,
+    commands: Res<ConsoleCommands>,
+) {
+    if keys.just_pressed(settings.toggle_key) {
+        console.toggle();
+        // The toggle key also sends its character, which shouldn't be typed.
+        characters.clear();
+        return;
+    }
+    if !console.is_open() {
+        characters.clear();
+        return;
+    }
+
+    for event in characters.read() {
+        if !event.char.is_control() {
+            console.input_mut().push(event.char);
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        console.input_mut().pop();
+    }
+    if keys.just_pressed(KeyCode::Tab) {
+        let candidates = console.autocomplete(&commands);
+        if candidates.len() > 1 {
+            console.print(candidates.join("  "));
+        }
+    }
+    if keys.just_pressed(KeyCode::Up) {
+        console.previous_command();
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        console.next_command();
+    }
+    if keys.just_pressed(KeyCode::Return) {
+        console.submit();
+    }
+}
+
+/// Shows the state of the [`Console`] in its overlay.
+pub(super) fn update_console_ui(
+    console: Res<Console>,
+    mut roots: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut log_texts: Query<&mut Text, (With<ConsoleLogText>, Without<ConsoleInputText>)>,
+    mut input_texts: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleLogText>)>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    let visibility = if console.is_open() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut root_visibility in &mut roots {
+        root_visibility.set_if_neq(visibility);
+    }
+    for mut text in &mut log_texts {
+        text.sections = console
+            .lines()
+            .map(|line| {
+                TextSection::new(
+                    format!("{}\n", line.text),
+                    text_style(line_color(line.kind)),
+                )
+            })
+            .collect();
+    }
+    for mut text in &mut input_texts {
+        text.sections = vec![TextSection::new(
+            format!("> {}_", console.input()),
+            text_style(Color::WHITE),
+        )];
+    }
+}
+
+fn line_color(kind: ConsoleLineKind) -> Color {
+    match kind {
+        ConsoleLineKind::Command => Color::GRAY,
+        ConsoleLineKind::Output => Color::WHITE,
+        ConsoleLineKind::Error => Color::TOMATO,
+        ConsoleLineKind::Log(level) if level == Level::ERROR => Color::TOMATO,
+        ConsoleLineKind::Log(level) if level == Level::WARN => Color::ORANGE,
+        ConsoleLineKind::Log(_) => Color::SILVER,
+    }
+}
+
+fn text_style(color: Color) -> TextStyle {
+    TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..Default::default()
+    }
+}
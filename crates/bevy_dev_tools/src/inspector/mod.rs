@@ -0,0 +1,476 @@
+//! An in-game entity inspector.
+//!
+//! The inspector is an overlay, toggled with [`InspectorSettings::toggle_key`], listing the
+//! entities of the world. Selecting an entity shows the values of its reflected components,
+//! which are edited by clicking a field, typing its new value and pressing `Enter`.
+//!
+//! Only the components registered with `#[reflect(Component)]` are shown. The new values of the
+//! fields are parsed like the arguments of the [`Console`](crate::console::Console): [`String`]s
+//! are taken as is, and other types are parsed as [RON](ron).
+//!
+//! The overlay is built on top of [`inspect_entity`] and [`set_reflected_field`], which can also
+//! be used to build other debugging tools.
+
+mod ui;
+
+pub use ui::{InspectorButton, InspectorNode};
+
+use crate::parse::parse_reflect_value;
+use bevy_app::{App, Plugin, PostUpdate, Startup, Update};
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponentAccessError},
+};
+use bevy_input::keyboard::KeyCode;
+use bevy_reflect::{GetPath, Reflect, ReflectRef, TypeRegistry, VariantType};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// Adds the entity [`Inspector`] and its overlay.
+pub struct InspectorPlugin {
+    /// The key opening and closing the inspector.
+    pub toggle_key: KeyCode,
+    /// The maximum number of entities listed by the inspector.
+    pub max_listed_entities: usize,
+    /// The number of frames between two refreshes of the shown values.
+    pub refresh_frames: u32,
+}
+
+impl Default for InspectorPlugin {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::F1,
+            max_listed_entities: 64,
+            refresh_frames: 10,
+        }
+    }
+}
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InspectorSettings {
+            toggle_key: self.toggle_key,
+            max_listed_entities: self.max_listed_entities,
+            refresh_frames: self.refresh_frames,
+        })
+        .init_resource::<Inspector>()
+        .add_systems(Startup, ui::spawn_inspector_ui)
+        .add_systems(
+            Update,
+            (
+                ui::handle_inspector_input,
+                ui::handle_inspector_clicks,
+                apply_inspector_edits,
+            )
+                .chain(),
+        )
+        .add_systems(PostUpdate, ui::update_inspector_ui);
+    }
+}
+
+/// The settings of the [`Inspector`], set by the [`InspectorPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct InspectorSettings {
+    /// The key opening and closing the inspector.
+    pub toggle_key: KeyCode,
+    /// The maximum number of entities listed by the inspector.
+    pub max_listed_entities: usize,
+    /// The number of frames between two refreshes of the shown values.
+    pub refresh_frames: u32,
+}
+
+/// The state of the entity inspector: whether it is open, the selected entity and the field
+/// being edited.
+#[derive(Resource, Default, Debug)]
+pub struct Inspector {
+    open: bool,
+    selected: Option<Entity>,
+    edit: Option<FieldEdit>,
+    pending: Option<FieldEdit>,
+    error: Option<String>,
+}
+
+/// A field of a component being edited in the [`Inspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldEdit {
+    /// The entity of the component.
+    pub entity: Entity,
+    /// The [`TypeId`] of the component.
+    pub component: TypeId,
+    /// The path of the field in the component, see [`GetPath`].
+    pub path: String,
+    /// The new value of the field, as typed.
+    pub text: String,
+}
+
+impl Inspector {
+    /// Returns `true` if the inspector is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens or closes the inspector.
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    /// Opens the inspector if it is closed, and closes it otherwise.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Returns the selected entity, whose components are shown.
+    pub fn selected(&self) -> Option<Entity> {
+        self.selected
+    }
+
+    /// Selects the entity whose components are shown, and cancels the edit in progress.
+    pub fn select(&mut self, entity: Option<Entity>) {
+        self.selected = entity;
+        self.edit = None;
+        self.error = None;
+    }
+
+    /// Returns the field being edited.
+    pub fn edit(&self) -> Option<&FieldEdit> {
+        self.edit.as_ref()
+    }
+
+    /// Starts editing the field at `path` in the `component` of the selected entity, with
+    /// `text` as its initial value.
+    ///
+    /// Does nothing if no entity is selected.
+    pub fn start_edit(
+        &mut self,
+        component: TypeId,
+        path: impl Into<String>,
+        text: impl Into<String>,
+    ) {
+        let Some(entity) = self.selected else {
+            return;
+        };
+        self.edit = Some(FieldEdit {
+            entity,
+            component,
+            path: path.into(),
+            text: text.into(),
+        });
+        self.error = None;
+    }
+
+    /// Returns the new value of the field being edited, to be typed.
+    pub fn edit_text_mut(&mut self) -> Option<&mut String> {
+        self.edit.as_mut().map(|edit| &mut edit.text)
+    }
+
+    /// Stops editing the field without changing it.
+    pub fn cancel_edit(&mut self) {
+        self.edit = None;
+    }
+
+    /// Stops editing the field, and sets it to its new value in the next [`Update`] schedule.
+    pub fn commit_edit(&mut self) {
+        self.pending = self.edit.take();
+    }
+
+    /// Returns the error which occurred setting the last edited field.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// A reflected component of an entity, returned by [`inspect_entity`].
+#[derive(Clone, Debug)]
+pub struct InspectedComponent {
+    /// The [`TypeId`] of the component.
+    pub type_id: TypeId,
+    /// The type path of the component.
+    pub type_path: &'static str,
+    /// The fields of the component, in order.
+    pub fields: Vec<InspectedField>,
+}
+
+/// A field of an [`InspectedComponent`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InspectedField {
+    /// The path of the field in its component, see [`GetPath`]. It is empty for the component
+    /// itself.
+    pub path: String,
+    /// The formatted value of the field.
+    pub value: String,
+    /// The value of the field as typed to set it, which differs from [`InspectedField::value`]
+    /// for [`String`]s as they are typed without quotes.
+    pub text: String,
+    /// Whether the field can be set with [`set_reflected_field`].
+    ///
+    /// Only the fields which don't have fields of their own are editable: primitive values and
+    /// enums without fields. Maps are shown as a whole, and are not editable.
+    pub editable: bool,
+}
+
+/// The maximum depth of the fields returned by [`inspect_entity`].
+const MAX_DEPTH: usize = 8;
+
+/// The maximum number of items of a list or an array returned by [`inspect_entity`].
+const MAX_ITEMS: usize = 16;
+
+/// Returns the components of `entity` registered with `#[reflect(Component)]` in the
+/// [`AppTypeRegistry`], with their fields, sorted by type path.
+///
+/// The fields are flattened: a struct field holding a struct is returned as the fields of the
+/// latter, with paths like `.position.x`.
+///
+/// Returns an empty list if the entity doesn't exist.
+pub fn inspect_entity(world: &World, entity: Entity) -> Vec<InspectedComponent> {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    let mut components = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            let value = world.get_reflect(entity, type_id).ok()?;
+            let mut fields = Vec::new();
+            inspect_fields(value, String::new(), 0, &mut fields);
+            Some(InspectedComponent {
+                type_id,
+                type_path: value.get_represented_type_info()?.type_path(),
+                fields,
+            })
+        })
+        .collect::<Vec<_>>();
+    components.sort_by_key(|component| component.type_path);
+    components
+}
+
+fn inspect_fields(
+    value: &dyn Reflect,
+    path: String,
+    depth: usize,
+    fields: &mut Vec<InspectedField>,
+) {
+    if depth == MAX_DEPTH {
+        fields.push(InspectedField::new(path, value, false));
+        return;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for index in 0..value.field_len() {
+                let name = value.name_at(index).unwrap();
+                let field = value.field_at(index).unwrap();
+                inspect_fields(field, format!("{path}.{name}"), depth + 1, fields);
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for (index, field) in value.iter_fields().enumerate() {
+                inspect_fields(field, format!("{path}.{index}"), depth + 1, fields);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for (index, field) in value.iter_fields().enumerate() {
+                inspect_fields(field, format!("{path}.{index}"), depth + 1, fields);
+            }
+        }
+        ReflectRef::List(value) => {
+            for (index, item) in value.iter().take(MAX_ITEMS).enumerate() {
+                inspect_fields(item, format!("{path}[{index}]"), depth + 1, fields);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for (index, item) in value.iter().take(MAX_ITEMS).enumerate() {
+                inspect_fields(item, format!("{path}[{index}]"), depth + 1, fields);
+            }
+        }
+        ReflectRef::Enum(enum_value) => match enum_value.variant_type() {
+            VariantType::Unit => fields.push(InspectedField::new(path, value, true)),
+            VariantType::Tuple => {
+                for (index, field) in enum_value.iter_fields().enumerate() {
+                    inspect_fields(field.value(), format!("{path}.{index}"), depth + 1, fields);
+                }
+            }
+            VariantType::Struct => {
+                for field in enum_value.iter_fields() {
+                    let name = field.name().unwrap();
+                    inspect_fields(field.value(), format!("{path}.{name}"), depth + 1, fields);
+                }
+            }
+        },
+        ReflectRef::Map(_) => fields.push(InspectedField::new(path, value, false)),
+        ReflectRef::Value(_) => fields.push(InspectedField::new(path, value, true)),
+    }
+}
+
+impl InspectedField {
+    fn new(path: String, value: &dyn Reflect, editable: bool) -> Self {
+        let formatted = format!("{value:?}");
+        Self {
+            path,
+            text: value
+                .downcast_ref::<String>()
+                .cloned()
+                .unwrap_or_else(|| formatted.clone()),
+            value: formatted,
+            editable,
+        }
+    }
+}
+
+/// An error setting a field with [`set_reflected_field`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InspectorError {
+    /// The component couldn't be accessed.
+    #[error(transparent)]
+    Access(#[from] ReflectComponentAccessError),
+    /// The component has no field at the path.
+    #[error("invalid field path: {0}")]
+    InvalidPath(String),
+    /// The type of the field isn't registered in the [`AppTypeRegistry`].
+    #[error("the type {0} of the field is not registered")]
+    UnregisteredField(String),
+    /// The new value couldn't be parsed.
+    #[error("invalid value for a {type_path}: {error}")]
+    InvalidValue {
+        /// The type path of the field.
+        type_path: &'static str,
+        /// The parsing error.
+        error: String,
+    },
+}
+
+/// Sets the field at `path` in the `component` of `entity` to `value`, parsed like a value
+/// typed in the [`Inspector`].
+pub fn set_reflected_field(
+    world: &mut World,
+    entity: Entity,
+    component: TypeId,
+    path: &str,
+    value: &str,
+) -> Result<(), InspectorError> {
+    let registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or(ReflectComponentAccessError::MissingAppTypeRegistry)?
+        .clone();
+    let registry = registry.read();
+    let mut component = world.get_reflect_mut(entity, component)?;
+    let field = if path.is_empty() {
+        &mut *component
+    } else {
+        component
+            .reflect_path_mut(path)
+            .map_err(|error| InspectorError::InvalidPath(error.to_string()))?
+    };
+    let value = parse_field(field, value, &registry)?;
+    field.apply(&*value);
+    Ok(())
+}
+
+fn parse_field(
+    field: &dyn Reflect,
+    value: &str,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, InspectorError> {
+    let registration = field
+        .get_represented_type_info()
+        .and_then(|type_info| registry.get(type_info.type_id()))
+        .ok_or_else(|| InspectorError::UnregisteredField(field.reflect_type_path().to_string()))?;
+    parse_reflect_value(value, registration, registry).map_err(|error| {
+        InspectorError::InvalidValue {
+            type_path: registration.type_info().type_path(),
+            error: error.to_string(),
+        }
+    })
+}
+
+/// Sets the fields committed in the [`Inspector`].
+pub fn apply_inspector_edits(world: &mut World) {
+    let Some(edit) = world.resource_mut::<Inspector>().pending.take() else {
+        return;
+    };
+    let result = set_reflected_field(world, edit.entity, edit.component, &edit.path, &edit.text);
+    world.resource_mut::<Inspector>().error = result.err().map(|error| error.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inspect_entity, set_reflected_field, InspectedField, InspectorError};
+    use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+    use bevy_reflect::Reflect;
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Enemy {
+        name: String,
+        health: f32,
+        position: (i32, i32),
+        state: State,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    enum State {
+        #[default]
+        Idle,
+        Chasing,
+    }
+
+    fn field(path: &str, value: &str, text: &str) -> InspectedField {
+        InspectedField {
+            path: path.to_string(),
+            value: value.to_string(),
+            text: text.to_string(),
+            editable: true,
+        }
+    }
+
+    #[test]
+    fn inspect_and_edit_fields() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Enemy>();
+            registry.register::<State>();
+        }
+        let entity = world
+            .spawn(Enemy {
+                name: "bat".to_string(),
+                health: 1.0,
+                ..Default::default()
+            })
+            .id();
+
+        let components = inspect_entity(&world, entity);
+        assert_eq!(components.len(), 1);
+        assert_eq!(
+            components[0].fields,
+            vec![
+                field(".name", "\"bat\"", "bat"),
+                field(".health", "1.0", "1.0"),
+                field(".position.0", "0", "0"),
+                field(".position.1", "0", "0"),
+                field(".state", "Idle", "Idle"),
+            ]
+        );
+
+        let enemy = TypeId::of::<Enemy>();
+        set_reflected_field(&mut world, entity, enemy, ".name", "big bat").unwrap();
+        set_reflected_field(&mut world, entity, enemy, ".health", "2.5").unwrap();
+        set_reflected_field(&mut world, entity, enemy, ".position.1", "-3").unwrap();
+        set_reflected_field(&mut world, entity, enemy, ".state", "Chasing").unwrap();
+        let enemy_ref = world.get::<Enemy>(entity).unwrap();
+        assert_eq!(enemy_ref.name, "big bat");
+        assert_eq!(enemy_ref.health, 2.5);
+        assert_eq!(enemy_ref.position, (0, -3));
+        assert_eq!(enemy_ref.state, State::Chasing);
+
+        assert!(matches!(
+            set_reflected_field(&mut world, entity, enemy, ".health", "lots"),
+            Err(InspectorError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            set_reflected_field(&mut world, entity, enemy, ".mana", "1.0"),
+            Err(InspectorError::InvalidPath(_))
+        ));
+    }
+}
@@ -0,0 +1,269 @@
+use super::{inspect_entity, Inspector, InspectorSettings};
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildWorldChildren, DespawnRecursiveExt};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_render::{color::Color, view::Visibility};
+use bevy_text::TextStyle;
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    FlexDirection, Interaction, Overflow, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_window::ReceivedCharacter;
+use std::any::TypeId;
+
+const FONT_SIZE: f32 = 14.0;
+
+/// Marks the nodes of the [`Inspector`] overlay, which are not listed by the inspector.
+#[derive(Component, Default)]
+pub struct InspectorNode;
+
+/// A clickable node of the [`Inspector`] overlay.
+#[derive(Component, Clone, Debug)]
+pub enum InspectorButton {
+    /// Selects the entity.
+    Entity(Entity),
+    /// Starts editing a field of a component of the selected entity.
+    Field {
+        /// The [`TypeId`] of the component.
+        component: TypeId,
+        /// The path of the field in the component.
+        path: String,
+        /// The initial value of the edited field.
+        text: String,
+    },
+}
+
+#[derive(Component)]
+pub(super) struct InspectorRoot;
+
+#[derive(Component)]
+pub(super) struct InspectorEntityList;
+
+#[derive(Component)]
+pub(super) struct InspectorComponentList;
+
+/// Spawns the hidden [`Inspector`] overlay, on the right of the screen.
+pub(super) fn spawn_inspector_ui(mut commands: Commands) {
+    let column = |width: Val| NodeBundle {
+        style: Style {
+            width,
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            overflow: Overflow::clip(),
+            padding: UiRect::all(Val::Px(4.0)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    commands
+        .spawn((
+            InspectorRoot,
+            InspectorNode,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(0.0),
+                    width: Val::Percent(50.0),
+                    height: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0.05, 0.05, 0.05, 0.85).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX - 1),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                InspectorEntityList,
+                InspectorNode,
+                column(Val::Percent(35.0)),
+            ));
+            parent.spawn((
+                InspectorComponentList,
+                InspectorNode,
+                column(Val::Percent(65.0)),
+            ));
+        });
+}
+
+/// Toggles the [`Inspector`] with [`InspectorSettings::toggle_key`], and types the new value of
+/// the field being edited.
+///
+/// `Enter` sets the field and `Escape` cancels the edit.
+pub(super) fn handle_inspector_input(
+    settings: Res<InspectorSettings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut inspector: ResMut<Inspector>,
+) {
+    if keys.just_pressed(settings.toggle_key) {
+        inspector.toggle();
+    }
+    if !inspector.is_open() || inspector.edit().is_none() {
+        characters.clear();
+        return;
+    }
+
+    for event in characters.read() {
+        if let Some(text) = inspector.edit_text_mut() {
+            if !event.char.is_control() {
+                text.push(event.char);
+            }
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        if let Some(text) = inspector.edit_text_mut() {
+            text.pop();
+        }
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        inspector.cancel_edit();
+    }
+    if keys.just_pressed(KeyCode::Return) {
+        inspector.commit_edit();
+    }
+}
+
+/// Selects the clicked entities and starts editing the clicked fields.
+pub(super) fn handle_inspector_clicks(
+    buttons: Query<(&Interaction, &InspectorButton), Changed<Interaction>>,
+    mut inspector: ResMut<Inspector>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            InspectorButton::Entity(entity) => inspector.select(Some(*entity)),
+            InspectorButton::Field {
+                component,
+                path,
+                text,
+            } => inspector.start_edit(*component, path.clone(), text.clone()),
+        }
+    }
+}
+
+/// Rebuilds the [`Inspector`] overlay when the inspector changes, and every
+/// [`InspectorSettings::refresh_frames`] frames while it is open.
+pub(super) fn update_inspector_ui(world: &mut World, mut frame: Local<u32>) {
+    let refresh_frames = world.resource::<InspectorSettings>().refresh_frames.max(1);
+    *frame = frame.wrapping_add(1);
+    let inspector = world.resource_ref::<Inspector>();
+    let open = inspector.is_open();
+    if !inspector.is_changed() && (!open || *frame % refresh_frames != 0) {
+        return;
+    }
+
+    let visibility = if open {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let mut roots = world.query_filtered::<&mut Visibility, With<InspectorRoot>>();
+    for mut root_visibility in roots.iter_mut(world) {
+        root_visibility.set_if_neq(visibility);
+    }
+    if !open {
+        return;
+    }
+
+    let max_listed_entities = world.resource::<InspectorSettings>().max_listed_entities;
+    let entities = world
+        .query_filtered::<(Entity, Option<&Name>), Without<InspectorNode>>()
+        .iter(world)
+        .take(max_listed_entities)
+        .map(|(entity, name)| match name {
+            Some(name) => (entity, format!("{entity:?} {name}")),
+            None => (entity, format!("{entity:?}")),
+        })
+        .collect::<Vec<_>>();
+    let inspector = world.resource::<Inspector>();
+    let selected = inspector.selected();
+    let edit = inspector.edit().cloned();
+    let error = inspector.error().map(str::to_string);
+    let components = selected
+        .map(|entity| inspect_entity(world, entity))
+        .unwrap_or_default();
+
+    let mut entity_lists = world.query_filtered::<Entity, With<InspectorEntityList>>();
+    for list in entity_lists.iter(world).collect::<Vec<_>>() {
+        world
+            .entity_mut(list)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for (entity, label) in &entities {
+                    let color = if Some(*entity) == selected {
+                        Color::YELLOW
+                    } else {
+                        Color::WHITE
+                    };
+                    parent.spawn((
+                        TextBundle::from_section(label.clone(), text_style(color)),
+                        Interaction::default(),
+                        InspectorButton::Entity(*entity),
+                        InspectorNode,
+                    ));
+                }
+            });
+    }
+
+    let mut component_lists = world.query_filtered::<Entity, With<InspectorComponentList>>();
+    for list in component_lists.iter(world).collect::<Vec<_>>() {
+        world
+            .entity_mut(list)
+            .despawn_descendants()
+            .with_children(|parent| {
+                if let Some(error) = &error {
+                    parent.spawn((
+                        TextBundle::from_section(error.clone(), text_style(Color::TOMATO)),
+                        InspectorNode,
+                    ));
+                }
+                for component in &components {
+                    parent.spawn((
+                        TextBundle::from_section(component.type_path, text_style(Color::CYAN)),
+                        InspectorNode,
+                    ));
+                    for field in &component.fields {
+                        let editing = edit.as_ref().filter(|edit| {
+                            edit.component == component.type_id && edit.path == field.path
+                        });
+                        let (value, color) = match editing {
+                            Some(edit) => (format!("{}_", edit.text), Color::YELLOW),
+                            None if field.editable => (field.value.clone(), Color::WHITE),
+                            None => (field.value.clone(), Color::GRAY),
+                        };
+                        let label = match field.path.trim_start_matches('.') {
+                            "" => format!("  {value}"),
+                            path => format!("  {path}: {value}"),
+                        };
+                        let mut node = parent.spawn((
+                            TextBundle::from_section(label, text_style(color)),
+                            InspectorNode,
+                        ));
+                        if field.editable {
+                            node.insert((
+                                Interaction::default(),
+                                InspectorButton::Field {
+                                    component: component.type_id,
+                                    path: field.path.clone(),
+                                    text: field.text.clone(),
+                                },
+                            ));
+                        }
+                    }
+                }
+            });
+    }
+}
+
+fn text_style(color: Color) -> TextStyle {
+    TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,26 @@
+#![warn(missing_docs)]
+
+//! Developer tools for Bevy Engine.
+//!
+//! These tools help while developing a game, and are usually left out of release builds:
+//! - the [`console`], an in-game overlay running commands registered as one-shot systems.
+//! - the [`inspector`], an in-game overlay listing the entities and editing their reflected
+//!   components.
+//! - the [`perf_overlay`], an on-screen overlay showing the frame rate, a frame time graph and
+//!   other diagnostics.
+
+pub mod console;
+pub mod inspector;
+pub mod perf_overlay;
+
+mod parse;
+
+/// The `bevy_dev_tools` prelude.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        console::{Console, ConsoleApp, ConsolePlugin},
+        inspector::{Inspector, InspectorPlugin},
+        perf_overlay::{PerfOverlayPlugin, PerfOverlaySettings},
+    };
+}
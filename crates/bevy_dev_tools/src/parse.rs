@@ -0,0 +1,20 @@
+use bevy_reflect::{serde::TypedReflectDeserializer, Reflect, TypeRegistration, TypeRegistry};
+use serde::de::DeserializeSeed;
+use std::any::TypeId;
+
+/// Parses `text` into a value of the registered type, as typed by a user: [`String`]s are taken
+/// as is, and other types are parsed as [RON](ron).
+pub(crate) fn parse_reflect_value(
+    text: &str,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, ron::Error> {
+    if registration.type_id() == TypeId::of::<String>() {
+        return Ok(Box::new(text.to_string()));
+    }
+    let mut deserializer = ron::Deserializer::from_str(text).map_err(|error| error.code)?;
+    let value =
+        TypedReflectDeserializer::new(registration, registry).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
@@ -0,0 +1,280 @@
+//! An on-screen performance overlay.
+//!
+//! The overlay, toggled with [`PerfOverlaySettings::toggle_key`], shows the frame rate, the frame
+//! time with a graph of the last frames, and the number of entities. Any other diagnostic of the
+//! [`DiagnosticsStore`], such as render timings or the run time of a system, can be added to it
+//! with [`PerfOverlaySettings::diagnostics`], and is shown once it has measurements.
+
+use bevy_app::{App, Plugin, PostUpdate, Startup, Update};
+use bevy_diagnostic::{
+    DiagnosticId, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_render::{color::Color, view::Visibility};
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use std::collections::VecDeque;
+
+const FONT_SIZE: f32 = 14.0;
+
+/// The number of frames shown by the frame time graph.
+const GRAPH_FRAMES: usize = 90;
+
+/// Adds the performance overlay, along with the [`FrameTimeDiagnosticsPlugin`] and the
+/// [`EntityCountDiagnosticsPlugin`] it shows the measurements of.
+pub struct PerfOverlayPlugin {
+    /// Whether the overlay is shown when the app starts.
+    pub visible: bool,
+    /// The key showing and hiding the overlay.
+    pub toggle_key: KeyCode,
+}
+
+impl Default for PerfOverlayPlugin {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            toggle_key: KeyCode::F2,
+        }
+    }
+}
+
+impl Plugin for PerfOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+        app.insert_resource(PerfOverlaySettings {
+            visible: self.visible,
+            toggle_key: self.toggle_key,
+            ..Default::default()
+        })
+        .init_resource::<FrameTimeHistory>()
+        .add_systems(Startup, spawn_perf_overlay_ui)
+        .add_systems(Update, toggle_perf_overlay)
+        .add_systems(PostUpdate, update_perf_overlay_ui);
+    }
+}
+
+/// The settings of the performance overlay, which can be changed at runtime.
+#[derive(Resource, Clone, Debug)]
+pub struct PerfOverlaySettings {
+    /// Whether the overlay is shown.
+    pub visible: bool,
+    /// The key showing and hiding the overlay.
+    pub toggle_key: KeyCode,
+    /// The number of frames between two refreshes of the shown values.
+    ///
+    /// The frame time graph is updated every frame.
+    pub refresh_frames: u32,
+    /// The frame time aimed for, in milliseconds. The bars of the graph are green below it,
+    /// yellow below twice it, and red above.
+    pub target_frame_time: f32,
+    /// Other diagnostics shown below the frame time and the number of entities.
+    pub diagnostics: Vec<DiagnosticId>,
+}
+
+impl Default for PerfOverlaySettings {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            toggle_key: KeyCode::F2,
+            refresh_frames: 10,
+            target_frame_time: 1000.0 / 60.0,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+/// The frame times of the last [`GRAPH_FRAMES`] frames, in milliseconds.
+#[derive(Resource, Default)]
+struct FrameTimeHistory(VecDeque<f64>);
+
+/// Marks the root node of the performance overlay.
+#[derive(Component, Default)]
+pub struct PerfOverlayRoot;
+
+#[derive(Component)]
+struct PerfOverlayText;
+
+/// A bar of the frame time graph, showing the frame at this index from the oldest.
+#[derive(Component)]
+struct PerfOverlayBar(usize);
+
+/// Spawns the performance overlay, in the top left corner of the screen.
+fn spawn_perf_overlay_ui(mut commands: Commands, settings: Res<PerfOverlaySettings>) {
+    commands
+        .spawn((
+            PerfOverlayRoot,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0.05, 0.05, 0.05, 0.75).into(),
+                visibility: visibility(settings.visible),
+                z_index: ZIndex::Global(i32::MAX - 2),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((PerfOverlayText, TextBundle::default()));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(GRAPH_FRAMES as f32 * 2.0),
+                        height: Val::Px(40.0),
+                        margin: UiRect::top(Val::Px(4.0)),
+                        align_items: AlignItems::FlexEnd,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|graph| {
+                    for index in 0..GRAPH_FRAMES {
+                        graph.spawn((
+                            PerfOverlayBar(index),
+                            NodeBundle {
+                                style: Style {
+                                    width: Val::Px(2.0),
+                                    height: Val::Percent(0.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ));
+                    }
+                });
+        });
+}
+
+/// Shows and hides the overlay with [`PerfOverlaySettings::toggle_key`].
+fn toggle_perf_overlay(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<PerfOverlaySettings>) {
+    if keys.just_pressed(settings.toggle_key) {
+        settings.visible = !settings.visible;
+    }
+}
+
+/// Records the frame time, and shows the measurements in the overlay while it is visible.
+fn update_perf_overlay_ui(
+    settings: Res<PerfOverlaySettings>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut history: ResMut<FrameTimeHistory>,
+    mut frame: Local<u32>,
+    mut roots: Query<&mut Visibility, With<PerfOverlayRoot>>,
+    mut texts: Query<&mut Text, With<PerfOverlayText>>,
+    mut bars: Query<(&PerfOverlayBar, &mut Style, &mut BackgroundColor)>,
+) {
+    if let Some(frame_time) = diagnostics.get(FrameTimeDiagnosticsPlugin::FRAME_TIME) {
+        if let Some(value) = frame_time.value() {
+            if history.0.len() == GRAPH_FRAMES {
+                history.0.pop_front();
+            }
+            history.0.push_back(value);
+        }
+    }
+
+    if settings.is_changed() {
+        for mut root_visibility in &mut roots {
+            root_visibility.set_if_neq(visibility(settings.visible));
+        }
+    }
+    if !settings.visible {
+        return;
+    }
+
+    // The graph is aligned to the right, with the newest frame last.
+    let target = settings.target_frame_time as f64;
+    let first_bar = GRAPH_FRAMES - history.0.len();
+    for (bar, mut style, mut color) in &mut bars {
+        let Some(frame_time) = bar.0.checked_sub(first_bar).map(|index| history.0[index]) else {
+            style.height = Val::Percent(0.0);
+            continue;
+        };
+        // The graph goes up to three times the target frame time.
+        style.height = Val::Percent((frame_time / (3.0 * target)).min(1.0) as f32 * 100.0);
+        color.0 = if frame_time <= target {
+            Color::GREEN
+        } else if frame_time <= 2.0 * target {
+            Color::YELLOW
+        } else {
+            Color::RED
+        };
+    }
+
+    *frame = frame.wrapping_add(1);
+    if !settings.is_changed() && *frame % settings.refresh_frames.max(1) != 0 {
+        return;
+    }
+    let smoothed = |id| {
+        diagnostics
+            .get(id)
+            .and_then(|diagnostic| diagnostic.smoothed())
+    };
+    let mut lines = Vec::new();
+    if let Some(fps) = smoothed(FrameTimeDiagnosticsPlugin::FPS) {
+        lines.push((format!("FPS {fps:.0}"), Color::WHITE));
+    }
+    if let Some(frame_time) = smoothed(FrameTimeDiagnosticsPlugin::FRAME_TIME) {
+        let max = history.0.iter().copied().fold(0.0, f64::max);
+        let color = if frame_time <= target {
+            Color::WHITE
+        } else {
+            Color::ORANGE
+        };
+        lines.push((format!("Frame {frame_time:.2} ms (max {max:.2} ms)"), color));
+    }
+    if let Some(entities) = smoothed(EntityCountDiagnosticsPlugin::ENTITY_COUNT) {
+        lines.push((format!("Entities {entities:.0}"), Color::WHITE));
+    }
+    for diagnostic in settings
+        .diagnostics
+        .iter()
+        .filter_map(|&id| diagnostics.get(id))
+    {
+        if let Some(value) = diagnostic.smoothed() {
+            lines.push((
+                format!("{} {value:.2}{}", diagnostic.name, diagnostic.suffix),
+                Color::SILVER,
+            ));
+        }
+    }
+
+    for mut text in &mut texts {
+        text.sections = lines
+            .iter()
+            .enumerate()
+            .map(|(index, (line, color))| {
+                let separator = if index == 0 { "" } else { "\n" };
+                TextSection::new(format!("{separator}{line}"), text_style(*color))
+            })
+            .collect();
+    }
+}
+
+fn visibility(visible: bool) -> Visibility {
+    if visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn text_style(color: Color) -> TextStyle {
+    TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..Default::default()
+    }
+}
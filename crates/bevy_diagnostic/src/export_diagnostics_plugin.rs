@@ -0,0 +1,573 @@
+use crate::{DiagnosticId, DiagnosticsStore};
+use bevy_app::{prelude::*, AppExit};
+use bevy_ecs::prelude::*;
+use bevy_log::{error, info};
+use bevy_utils::{Duration, HashMap, Instant};
+use std::{borrow::Cow, fmt::Write as _, fs, io, net::UdpSocket, path::PathBuf};
+
+/// An App Plugin that exports the measurements of the diagnostics to [`DiagnosticsSink`]s.
+///
+/// Every new measurement of the diagnostics is exported, not only the ones kept in their history.
+/// The plugin can write them to a CSV or a JSON file when the app exits, and send them to a UDP
+/// socket every frame for an external dashboard. Other sinks can be added with
+/// [`DiagnosticsExporter::add_sink`].
+///
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins};
+/// # use bevy_diagnostic::{DiagnosticsExportPlugin, FrameTimeDiagnosticsPlugin};
+/// App::new()
+///     .add_plugins((DefaultPlugins, FrameTimeDiagnosticsPlugin))
+///     .add_plugins(DiagnosticsExportPlugin {
+///         csv_path: Some("diagnostics.csv".into()),
+///         socket_address: Some("127.0.0.1:9000".to_string()),
+///         ..Default::default()
+///     })
+///     .run();
+/// ```
+#[derive(Default)]
+pub struct DiagnosticsExportPlugin {
+    /// The diagnostics to export, or `None` for all of them.
+    pub filter: Option<Vec<DiagnosticId>>,
+    /// The file the measurements are written to as CSV when the app exits.
+    pub csv_path: Option<PathBuf>,
+    /// The file the measurements are written to as JSON when the app exits.
+    pub json_path: Option<PathBuf>,
+    /// The address of the UDP socket the measurements are sent to every frame, as lines of JSON.
+    pub socket_address: Option<String>,
+}
+
+impl Plugin for DiagnosticsExportPlugin {
+    fn build(&self, app: &mut App) {
+        let mut exporter = DiagnosticsExporter {
+            filter: self.filter.clone(),
+            start: Instant::now(),
+            last_exported: HashMap::default(),
+            sinks: Vec::new(),
+            finished: false,
+        };
+        if let Some(path) = &self.csv_path {
+            exporter.add_sink(CsvFileSink::new(path.clone()));
+        }
+        if let Some(path) = &self.json_path {
+            exporter.add_sink(JsonFileSink::new(path.clone()));
+        }
+        if let Some(address) = &self.socket_address {
+            match UdpSink::new(address.clone()) {
+                Ok(sink) => exporter.add_sink(sink),
+                Err(err) => error!("Could not open a socket to export the diagnostics: {err}"),
+            }
+        }
+        app.init_resource::<DiagnosticsStore>()
+            .insert_resource(exporter)
+            .add_systems(Last, export_diagnostics_system);
+    }
+}
+
+/// A measurement of a diagnostic, exported by the [`DiagnosticsExportPlugin`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticSample {
+    /// The id of the diagnostic.
+    pub id: DiagnosticId,
+    /// The name of the diagnostic.
+    pub name: Cow<'static, str>,
+    /// The suffix of the values of the diagnostic, such as `ms`.
+    pub suffix: Cow<'static, str>,
+    /// The time of the measurement, since the [`DiagnosticsExportPlugin`] was added.
+    pub time: Duration,
+    /// The value of the measurement.
+    pub value: f64,
+}
+
+/// A destination for the measurements exported by the [`DiagnosticsExportPlugin`].
+pub trait DiagnosticsSink: Send + Sync + 'static {
+    /// Exports the new measurements of a frame.
+    fn export(&mut self, samples: &[DiagnosticSample]);
+
+    /// Finishes the export, when the app exits.
+    fn finish(&mut self) {}
+}
+
+/// The [`DiagnosticsSink`]s of the [`DiagnosticsExportPlugin`].
+#[derive(Resource)]
+pub struct DiagnosticsExporter {
+    filter: Option<Vec<DiagnosticId>>,
+    start: Instant,
+    last_exported: HashMap<DiagnosticId, Instant>,
+    sinks: Vec<Box<dyn DiagnosticsSink>>,
+    finished: bool,
+}
+
+impl DiagnosticsExporter {
+    /// Adds a sink the next measurements are exported to.
+    pub fn add_sink(&mut self, sink: impl DiagnosticsSink) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Finishes the export of all the sinks, which is done when the app exits.
+    ///
+    /// No more measurements are exported after this.
+    pub fn finish(&mut self) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+        for sink in &mut self.sinks {
+            sink.finish();
+        }
+    }
+}
+
+impl Drop for DiagnosticsExporter {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Exports the measurements added since the last frame, and finishes the export when the app
+/// exits.
+fn export_diagnostics_system(
+    mut exporter: ResMut<DiagnosticsExporter>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut exit: EventReader<AppExit>,
+) {
+    if exporter.finished {
+        return;
+    }
+    let exporter = &mut *exporter;
+    let mut samples = Vec::new();
+    for diagnostic in diagnostics.iter().filter(|diagnostic| {
+        diagnostic.is_enabled
+            && exporter
+                .filter
+                .iter()
+                .all(|filter| filter.contains(&diagnostic.id))
+    }) {
+        let last_exported = exporter.last_exported.get(&diagnostic.id).copied();
+        for measurement in diagnostic
+            .measurements()
+            .filter(|measurement| last_exported.iter().all(|last| measurement.time > *last))
+        {
+            samples.push(DiagnosticSample {
+                id: diagnostic.id,
+                name: diagnostic.name.clone(),
+                suffix: diagnostic.suffix.clone(),
+                time: measurement.time.saturating_duration_since(exporter.start),
+                value: measurement.value,
+            });
+        }
+        if let Some(measurement) = diagnostic.measurement() {
+            exporter
+                .last_exported
+                .insert(diagnostic.id, measurement.time);
+        }
+    }
+    if !samples.is_empty() {
+        for sink in &mut exporter.sinks {
+            sink.export(&samples);
+        }
+    }
+    if exit.read().next().is_some() {
+        exporter.finish();
+    }
+}
+
+/// A [`DiagnosticsSink`] writing the measurements to a CSV file when the app exits.
+///
+/// The file has a `time,diagnostic,value,suffix` header, and a row per measurement, with the
+/// time in seconds.
+pub struct CsvFileSink {
+    path: PathBuf,
+    samples: Vec<DiagnosticSample>,
+}
+
+impl CsvFileSink {
+    /// Creates a sink writing to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl DiagnosticsSink for CsvFileSink {
+    fn export(&mut self, samples: &[DiagnosticSample]) {
+        self.samples.extend_from_slice(samples);
+    }
+
+    fn finish(&mut self) {
+        let mut csv = String::from("time,diagnostic,value,suffix\n");
+        for sample in &self.samples {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                sample.time.as_secs_f64(),
+                csv_field(&sample.name),
+                sample.value,
+                csv_field(&sample.suffix),
+            );
+        }
+        write_export(&self.path, csv);
+    }
+}
+
+/// A [`DiagnosticsSink`] writing the measurements to a JSON file when the app exits.
+///
+/// The file holds an array with an object per diagnostic, with its `name`, `suffix`, and
+/// `samples` as `[time, value]` pairs with the time in seconds.
+pub struct JsonFileSink {
+    path: PathBuf,
+    samples: Vec<DiagnosticSample>,
+}
+
+impl JsonFileSink {
+    /// Creates a sink writing to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl DiagnosticsSink for JsonFileSink {
+    fn export(&mut self, samples: &[DiagnosticSample]) {
+        self.samples.extend_from_slice(samples);
+    }
+
+    fn finish(&mut self) {
+        // The diagnostics are written in the order they were first measured.
+        let mut order = Vec::new();
+        let mut diagnostics = HashMap::<DiagnosticId, Vec<&DiagnosticSample>>::default();
+        for sample in &self.samples {
+            diagnostics
+                .entry(sample.id)
+                .or_insert_with(|| {
+                    order.push(sample.id);
+                    Vec::new()
+                })
+                .push(sample);
+        }
+
+        let mut json = String::from("[");
+        for (index, id) in order.iter().enumerate() {
+            let samples = &diagnostics[id];
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "\n  {{\"name\": {}, \"suffix\": {}, \"samples\": [",
+                json_string(&samples[0].name),
+                json_string(&samples[0].suffix),
+            );
+            for (index, sample) in samples.iter().enumerate() {
+                if index > 0 {
+                    json.push_str(", ");
+                }
+                let _ = write!(
+                    json,
+                    "[{}, {}]",
+                    sample.time.as_secs_f64(),
+                    json_number(sample.value)
+                );
+            }
+            json.push_str("]}");
+        }
+        json.push_str("\n]\n");
+        write_export(&self.path, json);
+    }
+}
+
+/// A [`DiagnosticsSink`] sending the measurements of each frame to a UDP socket.
+///
+/// Each measurement is sent as a line of JSON, such as
+/// `{"time": 1.5, "name": "fps", "value": 60.0}`, with the time in seconds. The lines are
+/// grouped in datagrams of at most [`UdpSink::MAX_DATAGRAM_SIZE`] bytes.
+pub struct UdpSink {
+    socket: UdpSocket,
+    address: String,
+}
+
+impl UdpSink {
+    /// The maximum size of the datagrams sent, which fits in the usual MTU.
+    pub const MAX_DATAGRAM_SIZE: usize = 1200;
+
+    /// Creates a sink sending to `address`, such as `127.0.0.1:9000`.
+    pub fn new(address: impl Into<String>) -> io::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            address: address.into(),
+        })
+    }
+
+    fn send(&self, datagram: &str) {
+        if let Err(err) = self.socket.send_to(datagram.as_bytes(), &self.address) {
+            if err.kind() != io::ErrorKind::WouldBlock {
+                error!("Could not send the diagnostics to {}: {err}", self.address);
+            }
+        }
+    }
+}
+
+impl DiagnosticsSink for UdpSink {
+    fn export(&mut self, samples: &[DiagnosticSample]) {
+        let mut datagram = String::new();
+        for sample in samples {
+            let line = format!(
+                "{{\"time\": {}, \"name\": {}, \"value\": {}}}\n",
+                sample.time.as_secs_f64(),
+                json_string(&sample.name),
+                json_number(sample.value),
+            );
+            if !datagram.is_empty() && datagram.len() + line.len() > Self::MAX_DATAGRAM_SIZE {
+                self.send(&datagram);
+                datagram.clear();
+            }
+            datagram.push_str(&line);
+        }
+        if !datagram.is_empty() {
+            self.send(&datagram);
+        }
+    }
+}
+
+fn write_export(path: &PathBuf, contents: String) {
+    match fs::write(path, contents) {
+        Ok(()) => info!("Exported the diagnostics to {}", path.display()),
+        Err(err) => error!(
+            "Could not export the diagnostics to {}: {err}",
+            path.display()
+        ),
+    }
+}
+
+/// Quotes a CSV field if it holds a separator, a quote or a line break.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Formats a value as a JSON number, or `null` if it isn't finite.
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Diagnostic, DiagnosticMeasurement};
+    use std::sync::{Arc, Mutex};
+
+    const FPS: DiagnosticId = DiagnosticId::from_u128(1);
+    const FRAME_TIME: DiagnosticId = DiagnosticId::from_u128(2);
+
+    fn sample(id: DiagnosticId, millis: u64, value: f64) -> DiagnosticSample {
+        let (name, suffix) = if id == FPS {
+            ("fps", "")
+        } else {
+            ("frame time, total", "ms")
+        };
+        DiagnosticSample {
+            id,
+            name: name.into(),
+            suffix: suffix.into(),
+            time: Duration::from_millis(millis),
+            value,
+        }
+    }
+
+    /// Exports `samples` in two frames to `sink`, finishes it and returns what it wrote to
+    /// `path`.
+    fn export_to_file(mut sink: impl DiagnosticsSink, path: PathBuf) -> String {
+        sink.export(&[sample(FPS, 500, 60.), sample(FRAME_TIME, 500, 16.5)]);
+        sink.export(&[sample(FPS, 1500, f64::NAN)]);
+        sink.finish();
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(path);
+        contents
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bevy_diagnostic_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn fields_are_escaped() {
+        assert_eq!(csv_field("fps"), "fps");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+
+        assert_eq!(json_string("fps"), "\"fps\"");
+        assert_eq!(
+            json_string("\"a\\b\"\n\t\u{1}"),
+            "\"\\\"a\\\\b\\\"\\n\\t\\u0001\""
+        );
+        assert_eq!(json_number(60.), "60");
+        assert_eq!(json_number(-0.25), "-0.25");
+        assert_eq!(json_number(f64::NAN), "null");
+        assert_eq!(json_number(f64::INFINITY), "null");
+    }
+
+    #[test]
+    fn csv_file() {
+        let path = temp_path("export.csv");
+        assert_eq!(
+            export_to_file(CsvFileSink::new(&path), path),
+            "time,diagnostic,value,suffix\n\
+             0.5,fps,60,\n\
+             0.5,\"frame time, total\",16.5,ms\n\
+             1.5,fps,NaN,\n"
+        );
+    }
+
+    #[test]
+    fn json_file() {
+        let path = temp_path("export.json");
+        assert_eq!(
+            export_to_file(JsonFileSink::new(&path), path),
+            "[\n  \
+             {\"name\": \"fps\", \"suffix\": \"\", \"samples\": [[0.5, 60], [1.5, null]]},\n  \
+             {\"name\": \"frame time, total\", \"suffix\": \"ms\", \"samples\": [[0.5, 16.5]]}\n\
+             ]\n"
+        );
+
+        let path = temp_path("empty.json");
+        let mut sink = JsonFileSink::new(&path);
+        sink.finish();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[\n]\n");
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn udp_datagrams() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut sink = UdpSink::new(receiver.local_addr().unwrap().to_string()).unwrap();
+        let receive = || {
+            let mut buffer = [0; 2 * UdpSink::MAX_DATAGRAM_SIZE];
+            let length = receiver.recv(&mut buffer).unwrap();
+            String::from_utf8(buffer[..length].to_vec()).unwrap()
+        };
+
+        sink.export(&[sample(FPS, 1500, 60.), sample(FRAME_TIME, 1500, f64::NAN)]);
+        assert_eq!(
+            receive(),
+            "{\"time\": 1.5, \"name\": \"fps\", \"value\": 60}\n\
+             {\"time\": 1.5, \"name\": \"frame time, total\", \"value\": null}\n"
+        );
+
+        // The lines are split in datagrams that fit the MTU
+        let samples: Vec<_> = (0..100).map(|index| sample(FPS, index, 60.)).collect();
+        sink.export(&samples);
+        let mut lines = Vec::new();
+        while lines.len() < samples.len() {
+            let datagram = receive();
+            assert!(datagram.len() <= UdpSink::MAX_DATAGRAM_SIZE);
+            assert!(datagram.ends_with('\n'));
+            lines.extend(datagram.lines().map(str::to_string));
+        }
+        assert_eq!(lines.len(), 100);
+        assert_eq!(
+            lines[99],
+            "{\"time\": 0.099, \"name\": \"fps\", \"value\": 60}"
+        );
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        exports: Arc<Mutex<Vec<Vec<f64>>>>,
+        finished: Arc<Mutex<bool>>,
+    }
+
+    impl DiagnosticsSink for RecordingSink {
+        fn export(&mut self, samples: &[DiagnosticSample]) {
+            let values = samples.iter().map(|sample| sample.value).collect();
+            self.exports.lock().unwrap().push(values);
+        }
+
+        fn finish(&mut self) {
+            *self.finished.lock().unwrap() = true;
+        }
+    }
+
+    #[test]
+    fn new_measurements_are_exported_until_the_app_exits() {
+        let mut app = App::new();
+        app.add_plugins(DiagnosticsExportPlugin {
+            filter: Some(vec![FPS]),
+            ..Default::default()
+        });
+        let sink = RecordingSink::default();
+        app.world
+            .resource_mut::<DiagnosticsExporter>()
+            .add_sink(sink.clone());
+        let mut store = app.world.resource_mut::<DiagnosticsStore>();
+        store.add(Diagnostic::new(FPS, "fps", 2));
+        store.add(Diagnostic::new(FRAME_TIME, "frame time", 2));
+
+        let measure = |app: &mut App, values: &[f64]| {
+            let mut store = app.world.resource_mut::<DiagnosticsStore>();
+            for &value in values {
+                for id in [FPS, FRAME_TIME] {
+                    store
+                        .get_mut(id)
+                        .unwrap()
+                        .add_measurement(DiagnosticMeasurement {
+                            time: Instant::now(),
+                            value,
+                        });
+                }
+                // Measurements are told apart by their time
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            app.update();
+        };
+
+        // Only the measurements added since the last frame are exported, even once the older
+        // ones are dropped from the history
+        measure(&mut app, &[1., 2.]);
+        measure(&mut app, &[]);
+        measure(&mut app, &[3.]);
+        assert_eq!(*sink.exports.lock().unwrap(), [vec![1., 2.], vec![3.]]);
+        assert!(!*sink.finished.lock().unwrap());
+
+        app.world.send_event(AppExit);
+        measure(&mut app, &[4.]);
+        assert!(*sink.finished.lock().unwrap());
+        measure(&mut app, &[5.]);
+        assert_eq!(sink.exports.lock().unwrap().len(), 3);
+    }
+}
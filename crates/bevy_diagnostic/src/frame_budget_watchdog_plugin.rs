@@ -0,0 +1,237 @@
+use crate::{
+    system_time_diagnostics_plugin::SystemTimeDiagnosticsState, SystemTimeDiagnosticsPlugin,
+};
+use bevy_app::{prelude::*, Main};
+use bevy_core::FrameCount;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel, SystemRunTimes},
+};
+use bevy_log::warn;
+use bevy_time::{Real, Time};
+use bevy_utils::{get_short_name, Duration, HashMap, Instant};
+use std::{borrow::Cow, fmt::Write};
+
+/// An App Plugin that watches for frames taking longer than a budget, and sends a
+/// [`FrameBudgetExceeded`] event reporting the systems which took the longest to run in them.
+///
+/// A frame is measured from the last update of [`Time<Real>`], at the start of the frame, to
+/// the end of the [`Last`] schedule.
+///
+/// This helps diagnosing rare hitches, such as the ones seen in playtests, without a profiler
+/// attached. Timing the systems has a small cost for every system that runs, so this plugin is
+/// opt-in.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_diagnostic::FrameBudgetExceeded;
+/// fn report_hitches(mut reports: EventReader<FrameBudgetExceeded>) {
+///     for report in reports.read() {
+///         if let Some(system) = report.hottest_systems.first() {
+///             println!("{:?} spent in {}", system.duration, system.name);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(report_hitches);
+/// ```
+pub struct FrameBudgetWatchdogPlugin {
+    /// The longest a frame can take without being reported.
+    pub budget: Duration,
+    /// The maximum number of systems listed in a report.
+    pub hottest_systems: usize,
+    /// Whether to also log a warning for each report.
+    pub log_reports: bool,
+}
+
+impl Default for FrameBudgetWatchdogPlugin {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs_f64(1.0 / 30.0),
+            hottest_systems: 5,
+            log_reports: true,
+        }
+    }
+}
+
+impl Plugin for FrameBudgetWatchdogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SystemRunTimes>()
+            .insert_resource(FrameBudgetWatchdog {
+                budget: self.budget,
+                hottest_systems: self.hottest_systems,
+                log_reports: self.log_reports,
+            })
+            .add_event::<FrameBudgetExceeded>()
+            .add_systems(
+                Last,
+                frame_budget_watchdog_system.before(SystemTimeDiagnosticsPlugin::diagnostic_system),
+            );
+    }
+}
+
+/// The settings of the [`FrameBudgetWatchdogPlugin`], which can be changed at runtime.
+#[derive(Resource, Debug)]
+pub struct FrameBudgetWatchdog {
+    /// The longest a frame can take without being reported.
+    pub budget: Duration,
+    /// The maximum number of systems listed in a report.
+    pub hottest_systems: usize,
+    /// Whether to also log a warning for each report.
+    pub log_reports: bool,
+}
+
+/// An [`Event`] sent by the [`FrameBudgetWatchdogPlugin`] when a frame took longer than its
+/// budget.
+#[derive(Event, Clone, Debug)]
+pub struct FrameBudgetExceeded {
+    /// The [`FrameCount`] of the frame.
+    pub frame: u32,
+    /// How long the frame took.
+    pub frame_time: Duration,
+    /// The budget of the frame.
+    pub budget: Duration,
+    /// The systems which took the longest to run in the frame, the longest first.
+    pub hottest_systems: Vec<SystemFrameTime>,
+}
+
+/// How long a system took to run in a frame reported by [`FrameBudgetExceeded`].
+///
+/// The run times of a system which runs several times in a frame are summed.
+#[derive(Clone, Debug)]
+pub struct SystemFrameTime {
+    /// The schedule the system ran in.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// How long the system took to run in the frame.
+    pub duration: Duration,
+}
+
+/// Measures the time since the start of the frame, and reports the hottest systems of the
+/// frames over budget.
+fn frame_budget_watchdog_system(
+    watchdog: Res<FrameBudgetWatchdog>,
+    time: Res<Time<Real>>,
+    mut run_times: ResMut<SystemRunTimes>,
+    frame_count: Res<FrameCount>,
+    mut reports: EventWriter<FrameBudgetExceeded>,
+    system_time_diagnostics: Option<Res<SystemTimeDiagnosticsState>>,
+) {
+    let frame_time = time
+        .last_update()
+        .map(|frame_start| Instant::now().duration_since(frame_start));
+
+    if let Some(frame_time) = frame_time.filter(|frame_time| *frame_time > watchdog.budget) {
+        let mut system_times = HashMap::<_, SystemFrameTime>::new();
+        // The system of the `Main` schedule runs all the other schedules, so it would always be
+        // the hottest.
+        let main = Main.intern();
+        for run_time in run_times
+            .iter()
+            .filter(|run_time| run_time.schedule != main)
+        {
+            system_times
+                .entry((run_time.schedule, run_time.name.clone()))
+                .or_insert_with(|| SystemFrameTime {
+                    schedule: run_time.schedule,
+                    name: run_time.name.clone(),
+                    duration: Duration::ZERO,
+                })
+                .duration += run_time.duration;
+        }
+        let mut hottest_systems = system_times.into_values().collect::<Vec<_>>();
+        hottest_systems.sort_by_key(|system| std::cmp::Reverse(system.duration));
+        hottest_systems.truncate(watchdog.hottest_systems);
+
+        let report = FrameBudgetExceeded {
+            frame: frame_count.0,
+            frame_time,
+            budget: watchdog.budget,
+            hottest_systems,
+        };
+        if watchdog.log_reports {
+            let mut message = format!(
+                "Frame {} took {:.2}ms, over its budget of {:.2}ms",
+                report.frame,
+                report.frame_time.as_secs_f64() * 1000.0,
+                report.budget.as_secs_f64() * 1000.0,
+            );
+            for system in &report.hottest_systems {
+                let _ = write!(
+                    message,
+                    "\n  {:>8.3}ms {:?} {}",
+                    system.duration.as_secs_f64() * 1000.0,
+                    system.schedule,
+                    get_short_name(&system.name),
+                );
+            }
+            warn!(target: "bevy frame budget", "{message}");
+        }
+        reports.send(report);
+    }
+
+    // The run times are left to the diagnostics of the systems when they are recorded.
+    if system_time_diagnostics.is_none() {
+        run_times.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_core::FrameCountPlugin;
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+
+    fn slow_system() {
+        std::thread::sleep(Duration::from_millis(2));
+    }
+
+    fn fast_system() {}
+
+    /// Runs a frame which started `frame_time` ago, and returns the reports sent for it.
+    fn run_frame(app: &mut App, frame_time: Duration) -> Vec<FrameBudgetExceeded> {
+        app.insert_resource(TimeUpdateStrategy::ManualInstant(
+            Instant::now() - frame_time,
+        ));
+        app.update();
+        app.world
+            .resource_mut::<Events<FrameBudgetExceeded>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn reports_frames_over_budget() {
+        let mut app = App::new();
+        app.add_plugins((
+            TimePlugin,
+            FrameCountPlugin,
+            FrameBudgetWatchdogPlugin {
+                budget: Duration::from_millis(50),
+                hottest_systems: 1,
+                log_reports: false,
+            },
+        ))
+        .add_systems(Update, (slow_system, fast_system));
+
+        assert!(run_frame(&mut app, Duration::ZERO).is_empty());
+
+        let reports = run_frame(&mut app, Duration::from_millis(100));
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].frame_time >= Duration::from_millis(100));
+        assert_eq!(reports[0].budget, Duration::from_millis(50));
+        // Only the hottest system is listed, and never the one running the schedules.
+        assert_eq!(reports[0].hottest_systems.len(), 1);
+        assert_eq!(
+            get_short_name(&reports[0].hottest_systems[0].name),
+            "slow_system"
+        );
+
+        assert!(run_frame(&mut app, Duration::from_millis(10)).is_empty());
+
+        // The budget can be raised at runtime.
+        app.world.resource_mut::<FrameBudgetWatchdog>().budget = Duration::from_millis(200);
+        assert!(run_frame(&mut app, Duration::from_millis(100)).is_empty());
+        assert_eq!(run_frame(&mut app, Duration::from_millis(300)).len(), 1);
+    }
+}
@@ -1,15 +1,26 @@
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
+mod export_diagnostics_plugin;
+mod frame_budget_watchdog_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
 mod system_information_diagnostics_plugin;
+mod system_time_diagnostics_plugin;
 
 use bevy_app::prelude::*;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use export_diagnostics_plugin::{
+    CsvFileSink, DiagnosticSample, DiagnosticsExportPlugin, DiagnosticsExporter, DiagnosticsSink,
+    JsonFileSink, UdpSink,
+};
+pub use frame_budget_watchdog_plugin::{
+    FrameBudgetExceeded, FrameBudgetWatchdog, FrameBudgetWatchdogPlugin, SystemFrameTime,
+};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
 pub use system_information_diagnostics_plugin::SystemInformationDiagnosticsPlugin;
+pub use system_time_diagnostics_plugin::SystemTimeDiagnosticsPlugin;
 
 /// Adds core diagnostics resources to an App.
 #[derive(Default)]
@@ -0,0 +1,170 @@
+use crate::{Diagnostic, DiagnosticId, DiagnosticMeasurement, DiagnosticsStore};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, SystemRunTimes},
+};
+use bevy_log::trace;
+use bevy_utils::{get_short_name, FixedState, HashMap, Instant};
+use std::hash::{BuildHasher, Hash, Hasher};
+
+/// Adds a "system time" diagnostic for each system, the milliseconds it took to run in the
+/// last frame.
+///
+/// The run times of a system which runs several times in a frame are summed. Timing the
+/// systems has a small cost for every system that runs, so this plugin is opt-in.
+pub struct SystemTimeDiagnosticsPlugin {
+    /// Whether to also emit a `trace` level event with the run time of each system, every
+    /// frame.
+    pub emit_tracing_events: bool,
+    /// The maximum number of measurements each diagnostic keeps.
+    pub max_history_length: usize,
+}
+
+/// State used by the [`SystemTimeDiagnosticsPlugin`]
+#[derive(Resource)]
+pub(crate) struct SystemTimeDiagnosticsState {
+    emit_tracing_events: bool,
+    max_history_length: usize,
+}
+
+impl Default for SystemTimeDiagnosticsPlugin {
+    fn default() -> Self {
+        SystemTimeDiagnosticsPlugin {
+            emit_tracing_events: false,
+            max_history_length: 20,
+        }
+    }
+}
+
+impl Plugin for SystemTimeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<SystemRunTimes>()
+            .insert_resource(SystemTimeDiagnosticsState {
+                emit_tracing_events: self.emit_tracing_events,
+                max_history_length: self.max_history_length,
+            })
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl SystemTimeDiagnosticsPlugin {
+    /// Returns the id of the diagnostic of the system with the name in the schedule.
+    pub fn diagnostic_id(schedule: InternedScheduleLabel, system_name: &str) -> DiagnosticId {
+        let hash = |salt: u64| {
+            let mut hasher = FixedState.build_hasher();
+            salt.hash(&mut hasher);
+            format!("{schedule:?}").hash(&mut hasher);
+            system_name.hash(&mut hasher);
+            hasher.finish()
+        };
+        DiagnosticId::from_u128((hash(0) as u128) << 64 | hash(1) as u128)
+    }
+
+    /// Adds a measurement to the diagnostic of each system that ran since the last frame,
+    /// registering the diagnostics of the systems that never ran before.
+    pub(crate) fn diagnostic_system(
+        state: Res<SystemTimeDiagnosticsState>,
+        mut diagnostics: ResMut<DiagnosticsStore>,
+        mut run_times: ResMut<SystemRunTimes>,
+    ) {
+        let mut frame_times = HashMap::new();
+        for run_time in run_times.drain() {
+            let id = Self::diagnostic_id(run_time.schedule, &run_time.name);
+            let (_, _, milliseconds) =
+                frame_times
+                    .entry(id)
+                    .or_insert((run_time.schedule, run_time.name, 0.0));
+            *milliseconds += run_time.duration.as_secs_f64() * 1000.0;
+        }
+
+        let time = Instant::now();
+        for (id, (schedule, name, milliseconds)) in frame_times {
+            if state.emit_tracing_events {
+                trace!(
+                    target: "bevy system time",
+                    schedule = ?schedule,
+                    system = &*name,
+                    "{milliseconds:.6}ms"
+                );
+            }
+            if diagnostics.get(id).is_none() {
+                let diagnostic =
+                    Diagnostic::new(id, get_short_name(&name), state.max_history_length)
+                        .with_suffix("ms");
+                diagnostics.add(diagnostic);
+            }
+            if let Some(diagnostic) = diagnostics
+                .get_mut(id)
+                .filter(|diagnostic| diagnostic.is_enabled)
+            {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: milliseconds,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Main;
+    use bevy_ecs::schedule::ScheduleLabel;
+    use bevy_utils::Duration;
+
+    fn slow_system() {
+        std::thread::sleep(Duration::from_millis(2));
+    }
+
+    fn fast_system() {}
+
+    #[test]
+    fn system_times_are_measured() {
+        let mut app = App::new();
+        app.add_plugins(SystemTimeDiagnosticsPlugin::default())
+            .add_systems(Update, (slow_system, fast_system))
+            .add_systems(PostUpdate, slow_system);
+        app.update();
+        app.update();
+
+        let diagnostics = app.world.resource::<DiagnosticsStore>();
+        let diagnostic = |schedule: InternedScheduleLabel, name: &str| {
+            diagnostics
+                .get(SystemTimeDiagnosticsPlugin::diagnostic_id(schedule, name))
+                .unwrap()
+        };
+        let slow_system_name = IntoSystem::into_system(slow_system).name();
+        let fast_system_name = IntoSystem::into_system(fast_system).name();
+
+        let slow = diagnostic(Update.intern(), &slow_system_name);
+        assert_eq!(slow.name, "slow_system");
+        assert_eq!(slow.suffix, "ms");
+        assert_eq!(slow.measurements().count(), 2);
+        assert!(slow
+            .measurements()
+            .all(|measurement| measurement.value >= 2.0));
+
+        // The same system in another schedule has its own diagnostic.
+        let post_update_slow = diagnostic(PostUpdate.intern(), &slow_system_name);
+        assert_ne!(post_update_slow.id, slow.id);
+        assert_eq!(post_update_slow.measurements().count(), 2);
+
+        let fast = diagnostic(Update.intern(), &fast_system_name);
+        assert_eq!(fast.measurements().count(), 2);
+        assert!(fast
+            .measurements()
+            .all(|measurement| measurement.value < 2.0));
+
+        // The run times are drained by the diagnostics every frame, so only the ones recorded
+        // after them are left.
+        let (last, main) = (Last.intern(), Main.intern());
+        assert!(app
+            .world
+            .resource::<SystemRunTimes>()
+            .iter()
+            .all(|run_time| run_time.schedule == last || run_time.schedule == main));
+    }
+}
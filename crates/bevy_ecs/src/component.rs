@@ -388,6 +388,25 @@ impl ComponentDescriptor {
         }
     }
 
+    /// Create a new `ComponentDescriptor` for a component defined at runtime, whose values are
+    /// stored as a `T`.
+    ///
+    /// Unlike [`ComponentDescriptor::new`], each call describes a distinct component, which
+    /// can't be found from the [`TypeId`] of `T`.
+    pub fn new_dynamic<T: Send + Sync + 'static>(
+        name: impl Into<Cow<'static, str>>,
+        storage_type: StorageType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            storage_type,
+            is_send_and_sync: true,
+            type_id: Some(TypeId::of::<T>()),
+            layout: Layout::new::<T>(),
+            drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+        }
+    }
+
     /// Create a new `ComponentDescriptor` for a resource.
     ///
     /// The [`StorageType`] for resources is always [`TableStorage`].
@@ -0,0 +1,105 @@
+//! Disabling entities without despawning them.
+//!
+//! Entities with the [`Disabled`] component are hidden from every query which doesn't mention
+//! it, so systems skip them as if they were despawned while their components are kept for when
+//! they are enabled again by removing the component. This is useful for pooled objects or the
+//! entities of inactive levels.
+//!
+//! A query sees disabled entities when it accesses or filters on the component:
+//! - `Query<&A, With<Disabled>>` only sees the disabled entities,
+//! - `Query<(&A, Has<Disabled>)>` sees every entity and tells whether it is disabled,
+//! - `Query<&A, Allows<Disabled>>` sees every entity.
+//!
+//! Entities are always reachable directly through the [`World`](crate::world::World), for
+//! example with [`World::entity`](crate::world::World::entity).
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::query::Allows;
+//! #[derive(Component)]
+//! struct Bullet;
+//!
+//! let mut world = World::new();
+//! world.spawn(Bullet);
+//! let pooled = world.spawn((Bullet, Disabled)).id();
+//!
+//! let mut bullets = world.query_filtered::<(), With<Bullet>>();
+//! assert_eq!(bullets.iter(&world).count(), 1);
+//!
+//! let mut all_bullets = world.query_filtered::<(), (With<Bullet>, Allows<Disabled>)>();
+//! assert_eq!(all_bullets.iter(&world).count(), 2);
+//!
+//! // Take the bullet out of the pool.
+//! world.entity_mut(pooled).remove::<Disabled>();
+//! assert_eq!(bullets.iter(&world).count(), 2);
+//! ```
+
+use crate as bevy_ecs;
+use crate::component::Component;
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::ReflectComponent;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::std_traits::ReflectDefault;
+
+/// Marks an entity as disabled, hiding it from the queries which don't mention this component.
+///
+/// Bevy's own systems stop at disabled entities when walking hierarchies, which pauses the
+/// transform propagation of their descendants and hides them from rendering.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Component, Default)
+)]
+pub struct Disabled;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        query::{Allows, Has},
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[test]
+    fn disabled_entities_are_hidden() {
+        let mut world = World::new();
+        let enabled = world.spawn(A).id();
+        let disabled = world.spawn((A, Disabled)).id();
+
+        let mut query = world.query::<Entity>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [enabled]);
+        assert!(query.get(&world, disabled).is_err());
+
+        let mut query = world.query_filtered::<Entity, With<Disabled>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [disabled]);
+
+        let mut query = world.query::<(Entity, Has<Disabled>)>();
+        assert_eq!(
+            query.iter(&world).collect::<Vec<_>>(),
+            [(enabled, false), (disabled, true)]
+        );
+
+        let mut query = world.query_filtered::<Entity, (With<A>, Allows<Disabled>)>();
+        assert_eq!(query.iter(&world).count(), 2);
+
+        world.entity_mut(disabled).remove::<Disabled>();
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn disabled_queries_are_disjoint() {
+        fn system(_: Query<&mut A>, _: Query<&mut A, With<Disabled>>) {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(&mut world);
+    }
+}
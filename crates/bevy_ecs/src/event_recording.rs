@@ -0,0 +1,269 @@
+//! Recording of the events sent in a [`World`](crate::world::World), to replay them in another.
+//!
+//! An [`EventRecording`] resource stores each event of its type with the tick of the frame it
+//! was sent in, either all of them or only the latest in a ring buffer. The recording can be
+//! saved to a file with any `serde` format, and loaded back as an [`EventReplay`] which sends
+//! the events in the same frames of another world. This is a building block for replays, for
+//! relaying inputs over the network, and for test fixtures.
+//!
+//! `bevy_app` runs the systems of the recordings added with `App::record_events` at the end of
+//! each frame, and those of the replays added with `App::replay_events` at its start.
+//!
+//! ```
+//! # use bevy_ecs::{event_recording::{EventRecording, EventReplay}, prelude::*};
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! struct Jump(u32);
+//!
+//! fn jump(mut jumps: EventWriter<Jump>) {
+//!     jumps.send(Jump(1));
+//! }
+//!
+//! let mut world = World::new();
+//! world.init_resource::<Events<Jump>>();
+//! world.init_resource::<EventRecording<Jump>>();
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems((jump, EventRecording::<Jump>::record_system).chain());
+//! schedule.run(&mut world);
+//!
+//! let replay = world.resource::<EventRecording<Jump>>().to_replay();
+//! let mut other_world = World::new();
+//! other_world.init_resource::<Events<Jump>>();
+//! other_world.insert_resource(replay);
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(EventReplay::<Jump>::replay_system);
+//! schedule.run(&mut other_world);
+//!
+//! let mut events = other_world.resource_mut::<Events<Jump>>();
+//! assert_eq!(events.drain().collect::<Vec<_>>(), [Jump(1)]);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    event::{Event, Events, ManualEventReader},
+    system::{Res, ResMut, Resource},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
+
+/// A [`Resource`] recording the events of type `E` with the tick of the frame they were sent
+/// in.
+///
+/// The events are recorded by its [`record_system`](Self::record_system), which should run
+/// once per frame, after the events were sent. The tick is the number of times it ran before,
+/// so the first frame of the recording has the tick `0`.
+///
+/// With `serde`, the recording serializes as the sequence of its `(tick, event)` pairs, which
+/// deserialize as an [`EventReplay`].
+#[derive(Resource)]
+pub struct EventRecording<E: Event + Clone> {
+    events: VecDeque<(u32, E)>,
+    capacity: Option<usize>,
+    reader: ManualEventReader<E>,
+    tick: u32,
+}
+
+impl<E: Event + Clone> Default for EventRecording<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event + Clone> EventRecording<E> {
+    /// Creates a recording keeping all of the events.
+    pub fn new() -> Self {
+        Self {
+            events: VecDeque::new(),
+            capacity: None,
+            reader: ManualEventReader::default(),
+            tick: 0,
+        }
+    }
+
+    /// Creates a recording keeping only the latest `capacity` events, discarding the oldest
+    /// ones like a ring buffer.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Returns the tick the events sent in the current frame will be recorded with.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns the recorded events with their tick, from the oldest to the latest.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (u32, &E)> {
+        self.events.iter().map(|(tick, event)| (*tick, event))
+    }
+
+    /// Returns the number of recorded events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no events were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Removes the recorded events, without resetting the tick.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Returns an [`EventReplay`] of the recorded events.
+    pub fn to_replay(&self) -> EventReplay<E> {
+        EventReplay::new(self.events.iter().cloned())
+    }
+
+    /// A system recording the events `E` sent since the last time it ran, then advancing to the
+    /// next tick.
+    pub fn record_system(mut recording: ResMut<Self>, events: Res<Events<E>>) {
+        let EventRecording {
+            events: recorded,
+            capacity,
+            reader,
+            tick,
+        } = &mut *recording;
+        for event in reader.read(&events) {
+            recorded.push_back((*tick, event.clone()));
+        }
+        if let Some(capacity) = *capacity {
+            let discarded = recorded.len().saturating_sub(capacity);
+            recorded.drain(..discarded);
+        }
+        *tick += 1;
+    }
+}
+
+impl<E: Event + Clone + Serialize> Serialize for EventRecording<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.events)
+    }
+}
+
+/// A [`Resource`] sending recorded events of type `E` in the frames with the same tick as the
+/// frame they were recorded in.
+///
+/// The events are sent by its [`replay_system`](Self::replay_system), which should run once
+/// per frame, before the systems reading the events. The tick is the number of times it ran
+/// before, like that of an [`EventRecording`].
+#[derive(Resource)]
+pub struct EventReplay<E: Event> {
+    events: VecDeque<(u32, E)>,
+    tick: u32,
+}
+
+impl<E: Event> EventReplay<E> {
+    /// Creates a replay of the events with their tick.
+    pub fn new(events: impl IntoIterator<Item = (u32, E)>) -> Self {
+        let mut events: Vec<_> = events.into_iter().collect();
+        events.sort_by_key(|(tick, _)| *tick);
+        Self {
+            events: events.into(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the tick of the events which will be sent in the current frame.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns the number of events which remain to be sent.
+    pub fn remaining(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if all of the events were sent.
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// A system sending the events of the current tick, then advancing to the next tick.
+    pub fn replay_system(mut replay: ResMut<Self>, mut events: ResMut<Events<E>>) {
+        let tick = replay.tick;
+        while replay
+            .events
+            .front()
+            .is_some_and(|(event_tick, _)| *event_tick <= tick)
+        {
+            let (_, event) = replay.events.pop_front().unwrap();
+            events.send(event);
+        }
+        replay.tick += 1;
+    }
+}
+
+impl<'de, E: Event + Deserialize<'de>> Deserialize<'de> for EventReplay<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<(u32, E)>::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schedule::Schedule, world::World};
+
+    #[derive(Event, Clone, Copy, Debug, PartialEq)]
+    struct Input(u32);
+
+    #[test]
+    fn record_and_replay() {
+        let mut world = World::new();
+        world.init_resource::<Events<Input>>();
+        world.insert_resource(EventRecording::<Input>::with_capacity(4));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(EventRecording::<Input>::record_system);
+
+        for frame in 0..4 {
+            for i in 0..frame {
+                world.send_event(Input(frame * 10 + i));
+            }
+            schedule.run(&mut world);
+        }
+        let recording = world.resource::<EventRecording<Input>>();
+        assert_eq!(recording.tick(), 4);
+        let recorded: Vec<_> = recording
+            .iter()
+            .map(|(tick, event)| (tick, *event))
+            .collect();
+        assert_eq!(
+            recorded,
+            [
+                (2, Input(21)),
+                (3, Input(30)),
+                (3, Input(31)),
+                (3, Input(32))
+            ]
+        );
+        let replay = recording.to_replay();
+
+        let mut other_world = World::new();
+        other_world.init_resource::<Events<Input>>();
+        other_world.insert_resource(replay);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(EventReplay::<Input>::replay_system);
+
+        let mut replayed = Vec::new();
+        for _ in 0..4 {
+            schedule.run(&mut other_world);
+            let mut events = other_world.resource_mut::<Events<Input>>();
+            replayed.push(events.drain().collect::<Vec<_>>());
+        }
+        assert_eq!(
+            replayed,
+            [
+                vec![],
+                vec![],
+                vec![Input(21)],
+                vec![Input(30), Input(31), Input(32)]
+            ]
+        );
+        assert!(other_world.resource::<EventReplay<Input>>().is_finished());
+    }
+}
@@ -0,0 +1,241 @@
+//! Indices to look up entities by the value of one of their components.
+//!
+//! Finding the entities with a given component value through a [`Query`](crate::system::Query)
+//! means testing every entity with the component. A [`ComponentIndex`] resource instead keeps
+//! a map from each value of the component to the entities which have it, so they are found in
+//! constant time, while an [`OrderedComponentIndex`] keeps the values sorted to find the
+//! entities in a range of values.
+//!
+//! The index is brought up to date with the changes and removals of the component by
+//! [`ComponentIndex::update_system`], which `bevy_app` runs at the start of each frame for the
+//! indices added with `App::add_component_index` and `App::add_ordered_component_index`.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! struct ChunkCoord(i32, i32);
+//!
+//! fn find_neighbors(index: Res<ComponentIndex<ChunkCoord>>) {
+//!     for &chunk in index.get(&ChunkCoord(3, 4)) {
+//!         println!("{chunk:?} is at (3, 4)");
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! world.init_resource::<ComponentIndex<ChunkCoord>>();
+//! world.spawn(ChunkCoord(3, 4));
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems((ComponentIndex::<ChunkCoord>::update_system, find_neighbors).chain());
+//! schedule.run(&mut world);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    component::Component,
+    entity::Entity,
+    query::Changed,
+    removal_detection::RemovedComponents,
+    system::{Query, ResMut, Resource},
+};
+use bevy_utils::{EntityHashMap, HashMap};
+use std::{collections::BTreeMap, hash::Hash, ops::RangeBounds};
+
+/// A map from component values to the entities with that value, used as the storage of a
+/// [`ComponentIndex`].
+pub trait IndexStorage<C>: Default + Send + Sync + 'static {
+    /// Returns the entities with the value, if there are any.
+    fn get(&self, value: &C) -> Option<&Vec<Entity>>;
+    /// Returns the entities with the value, inserting an empty list if there are none.
+    fn get_or_insert(&mut self, value: C) -> &mut Vec<Entity>;
+    /// Returns the entities with the value, if there are any.
+    fn get_mut(&mut self, value: &C) -> Option<&mut Vec<Entity>>;
+    /// Removes the value and the entities with it.
+    fn remove(&mut self, value: &C);
+}
+
+impl<C: Hash + Eq + Send + Sync + 'static> IndexStorage<C> for HashMap<C, Vec<Entity>> {
+    fn get(&self, value: &C) -> Option<&Vec<Entity>> {
+        HashMap::get(self, value)
+    }
+
+    fn get_or_insert(&mut self, value: C) -> &mut Vec<Entity> {
+        self.entry(value).or_default()
+    }
+
+    fn get_mut(&mut self, value: &C) -> Option<&mut Vec<Entity>> {
+        HashMap::get_mut(self, value)
+    }
+
+    fn remove(&mut self, value: &C) {
+        HashMap::remove(self, value);
+    }
+}
+
+impl<C: Ord + Send + Sync + 'static> IndexStorage<C> for BTreeMap<C, Vec<Entity>> {
+    fn get(&self, value: &C) -> Option<&Vec<Entity>> {
+        BTreeMap::get(self, value)
+    }
+
+    fn get_or_insert(&mut self, value: C) -> &mut Vec<Entity> {
+        self.entry(value).or_default()
+    }
+
+    fn get_mut(&mut self, value: &C) -> Option<&mut Vec<Entity>> {
+        BTreeMap::get_mut(self, value)
+    }
+
+    fn remove(&mut self, value: &C) {
+        BTreeMap::remove(self, value);
+    }
+}
+
+/// A [`Resource`] mapping each value of the component `C` to the entities with that value.
+///
+/// The index is kept up to date by its [`update_system`](Self::update_system), lookups made
+/// before it runs don't see the latest changes to the component. See the
+/// [module-level documentation](self) for an example.
+#[derive(Resource)]
+pub struct ComponentIndex<C: Component + Clone, S: IndexStorage<C> = HashMap<C, Vec<Entity>>> {
+    entities: S,
+    values: EntityHashMap<Entity, C>,
+}
+
+/// A [`ComponentIndex`] which keeps the values of the component sorted, to find the entities
+/// with values in a [range](OrderedComponentIndex::range).
+pub type OrderedComponentIndex<C> = ComponentIndex<C, BTreeMap<C, Vec<Entity>>>;
+
+impl<C: Component + Clone, S: IndexStorage<C>> Default for ComponentIndex<C, S> {
+    fn default() -> Self {
+        Self {
+            entities: S::default(),
+            values: EntityHashMap::default(),
+        }
+    }
+}
+
+impl<C: Component + Clone, S: IndexStorage<C>> ComponentIndex<C, S> {
+    /// Returns the entities with the value, in no particular order.
+    pub fn get(&self, value: &C) -> &[Entity] {
+        self.entities.get(value).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns `true` if any entity has the value.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.get(value).is_some()
+    }
+
+    /// Returns the value of the component of an entity when the index was last updated.
+    pub fn value(&self, entity: Entity) -> Option<&C> {
+        self.values.get(&entity)
+    }
+
+    /// Returns the number of entities in the index.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no entities in the index.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Sets the value of an entity in the index, moving it from its previous value.
+    pub fn insert(&mut self, entity: Entity, value: C) {
+        self.remove(entity);
+        self.entities.get_or_insert(value.clone()).push(entity);
+        self.values.insert(entity, value);
+    }
+
+    /// Removes an entity from the index, returning its previous value.
+    pub fn remove(&mut self, entity: Entity) -> Option<C> {
+        let value = self.values.remove(&entity)?;
+        if let Some(entities) = self.entities.get_mut(&value) {
+            entities.retain(|&other| other != entity);
+            if entities.is_empty() {
+                self.entities.remove(&value);
+            }
+        }
+        Some(value)
+    }
+
+    /// A system updating the index with the components `C` that were changed, added or
+    /// removed since the last time it ran.
+    pub fn update_system(
+        mut index: ResMut<Self>,
+        changed: Query<(Entity, &C), Changed<C>>,
+        mut removed: RemovedComponents<C>,
+    ) {
+        for entity in removed.read() {
+            index.remove(entity);
+        }
+        for (entity, value) in &changed {
+            index.insert(entity, value.clone());
+        }
+    }
+}
+
+impl<C: Component + Clone + Ord> OrderedComponentIndex<C> {
+    /// Returns the values in the range and the entities with each of them, in ascending order
+    /// of the values.
+    pub fn range(&self, range: impl RangeBounds<C>) -> impl Iterator<Item = (&C, &[Entity])> {
+        self.entities
+            .range(range)
+            .map(|(value, entities)| (value, entities.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schedule::Schedule, world::World};
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct Coord(i32, i32);
+
+    #[test]
+    fn hash_index() {
+        let mut world = World::new();
+        world.init_resource::<ComponentIndex<Coord>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ComponentIndex::<Coord>::update_system);
+
+        let a = world.spawn(Coord(0, 0)).id();
+        let b = world.spawn(Coord(0, 0)).id();
+        let c = world.spawn(Coord(1, 0)).id();
+        schedule.run(&mut world);
+        let index = world.resource::<ComponentIndex<Coord>>();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get(&Coord(0, 0)), [a, b]);
+        assert_eq!(index.get(&Coord(1, 0)), [c]);
+        assert!(!index.contains(&Coord(2, 0)));
+
+        world.get_mut::<Coord>(a).unwrap().0 = 2;
+        world.entity_mut(b).remove::<Coord>();
+        world.despawn(c);
+        schedule.run(&mut world);
+        let index = world.resource::<ComponentIndex<Coord>>();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get(&Coord(2, 0)), [a]);
+        assert_eq!(index.value(a), Some(&Coord(2, 0)));
+        assert!(!index.contains(&Coord(0, 0)));
+        assert!(!index.contains(&Coord(1, 0)));
+    }
+
+    #[test]
+    fn ordered_index() {
+        let mut world = World::new();
+        world.init_resource::<OrderedComponentIndex<Coord>>();
+        let entities: Vec<Entity> = (0..10).map(|x| world.spawn(Coord(x, 0)).id()).collect();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(OrderedComponentIndex::<Coord>::update_system);
+        schedule.run(&mut world);
+
+        let index = world.resource::<OrderedComponentIndex<Coord>>();
+        let found: Vec<Entity> = index
+            .range(Coord(3, 0)..Coord(6, 0))
+            .flat_map(|(_, entities)| entities.iter().copied())
+            .collect();
+        assert_eq!(found, entities[3..6]);
+    }
+}
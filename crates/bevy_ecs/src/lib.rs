@@ -9,10 +9,14 @@ pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
+pub mod entity_disabling;
 pub mod event;
+pub mod event_recording;
+pub mod index;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relationship;
 pub mod removal_detection;
 pub mod schedule;
 pub mod storage;
@@ -27,20 +31,26 @@ pub use bevy_ptr as ptr;
 pub mod prelude {
     #[doc(hidden)]
     #[cfg(feature = "bevy_reflect")]
-    pub use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource};
+    pub use crate::reflect::{
+        AppFunctionRegistry, AppTypeRegistry, ReflectComponent, ReflectResource,
+    };
     #[doc(hidden)]
     pub use crate::{
         bundle::Bundle,
         change_detection::{DetectChanges, DetectChangesMut, Mut, Ref},
         component::Component,
         entity::Entity,
+        entity_disabling::Disabled,
         event::{Event, EventReader, EventWriter, Events},
+        index::{ComponentIndex, OrderedComponentIndex},
         query::{Added, AnyOf, Changed, Has, Or, QueryState, With, Without},
+        relationship::{Relations, Relationship, ReverseRelations},
         removal_detection::RemovedComponents,
         schedule::{
-            apply_deferred, apply_state_transition, common_conditions::*, Condition,
-            IntoSystemConfigs, IntoSystemSet, IntoSystemSetConfigs, NextState, OnEnter, OnExit,
-            OnTransition, Schedule, Schedules, State, States, SystemSet,
+            apply_deferred, apply_state_transition, common_conditions::*, ComputedStates,
+            Condition, IntoSystemConfigs, IntoSystemSet, IntoSystemSetConfigs, NextState, OnEnter,
+            OnExit, OnTransition, Schedule, Schedules, State, StateScoped, States, SubStates,
+            SystemSet,
         },
         system::{
             Commands, Deferred, In, IntoSystem, Local, NonSend, NonSendMut, ParallelCommands,
@@ -64,6 +74,7 @@ mod tests {
         change_detection::Ref,
         component::{Component, ComponentId},
         entity::Entity,
+        entity_disabling::Disabled,
         query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
         system::Resource,
         world::{EntityRef, Mut, World},
@@ -1375,8 +1386,11 @@ mod tests {
         let mut expected = FilteredAccess::<ComponentId>::default();
         let a_id = world.components.get_id(TypeId::of::<A>()).unwrap();
         let b_id = world.components.get_id(TypeId::of::<B>()).unwrap();
+        let disabled_id = world.components.get_id(TypeId::of::<Disabled>()).unwrap();
         expected.add_write(a_id);
         expected.add_read(b_id);
+        // Disabled entities are hidden from queries by default.
+        expected.and_without(disabled_id);
         assert!(
             query.component_access.eq(&expected),
             "ComponentId access from query fetch and query filter should be combined"
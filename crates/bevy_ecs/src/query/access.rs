@@ -59,6 +59,8 @@ pub struct Access<T: SparseSetIndex> {
     /// Is `true` if this has mutable access to all elements in the collection.
     /// If this is true, then `reads_all` must also be true.
     writes_all: bool,
+    /// Elements that are not accessed, but whose presence in an archetype affects the results.
+    archetypal: FixedBitSet,
     marker: PhantomData<T>,
 }
 
@@ -72,6 +74,7 @@ impl<T: SparseSetIndex + fmt::Debug> fmt::Debug for Access<T> {
             .field("writes", &FormattedBitSet::<T>::new(&self.writes))
             .field("reads_all", &self.reads_all)
             .field("writes_all", &self.writes_all)
+            .field("archetypal", &FormattedBitSet::<T>::new(&self.archetypal))
             .finish()
     }
 }
@@ -90,6 +93,7 @@ impl<T: SparseSetIndex> Access<T> {
             writes_all: false,
             reads_and_writes: FixedBitSet::new(),
             writes: FixedBitSet::new(),
+            archetypal: FixedBitSet::new(),
             marker: PhantomData,
         }
     }
@@ -116,6 +120,21 @@ impl<T: SparseSetIndex> Access<T> {
         self.writes.insert(index.sparse_set_index());
     }
 
+    /// Adds an element whose presence in an archetype affects the results, without accessing
+    /// it, such as the component of a [`Has`](crate::query::Has) query.
+    ///
+    /// This doesn't affect the compatibility of accesses.
+    pub fn add_archetypal(&mut self, index: T) {
+        self.archetypal.grow(index.sparse_set_index() + 1);
+        self.archetypal.insert(index.sparse_set_index());
+    }
+
+    /// Returns `true` if the presence of the element given by `index` in an archetype affects
+    /// the results, see [`Access::add_archetypal`].
+    pub fn has_archetypal(&self, index: T) -> bool {
+        self.archetypal.contains(index.sparse_set_index())
+    }
+
     /// Returns `true` if this can access the element given by `index`.
     pub fn has_read(&self, index: T) -> bool {
         self.reads_all || self.reads_and_writes.contains(index.sparse_set_index())
@@ -163,6 +182,7 @@ impl<T: SparseSetIndex> Access<T> {
         self.writes_all = false;
         self.reads_and_writes.clear();
         self.writes.clear();
+        self.archetypal.clear();
     }
 
     /// Adds all access from `other`.
@@ -171,6 +191,7 @@ impl<T: SparseSetIndex> Access<T> {
         self.writes_all = self.writes_all || other.writes_all;
         self.reads_and_writes.union_with(&other.reads_and_writes);
         self.writes.union_with(&other.writes);
+        self.archetypal.union_with(&other.archetypal);
     }
 
     /// Returns `true` if the access and `other` can be active at the same time.
@@ -339,6 +360,20 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         }
     }
 
+    /// Returns `true` if the element given by `index` is explicitly accessed, filtered on, or
+    /// otherwise affects the results.
+    ///
+    /// Unlike [`Access::has_read`], this doesn't consider accesses to all elements.
+    pub fn mentions(&self, index: T) -> bool {
+        let index = index.sparse_set_index();
+        self.access.reads_and_writes.contains(index)
+            || self.access.archetypal.contains(index)
+            || self
+                .filter_sets
+                .iter()
+                .any(|filter| filter.with.contains(index) || filter.without.contains(index))
+    }
+
     /// Appends an array of filters: corresponds to a disjunction (OR) operation.
     ///
     /// As the underlying array of filters represents a disjunction,
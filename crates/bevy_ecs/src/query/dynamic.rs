@@ -0,0 +1,154 @@
+use crate::{
+    archetype::{Archetype, ArchetypeGeneration, ArchetypeId},
+    component::ComponentId,
+    entity_disabling::Disabled,
+    world::{EntityMut, EntityRef, World, WorldId},
+};
+
+use super::DebugCheckedUnwrap;
+
+/// A query over components chosen at runtime by their [`ComponentId`], for the components
+/// which don't have a Rust type, like those registered by a scripting layer with
+/// [`World::init_component_with_descriptor`].
+///
+/// The query matches the entities which have every component passed to [`with`](Self::with)
+/// and none of those passed to [`without`](Self::without), and yields an [`EntityRef`] or
+/// [`EntityMut`] to fetch their components with `get_by_id` and `get_mut_by_id`.
+///
+/// Like [`QueryState`](super::QueryState), entities with the [`Disabled`] component are skipped
+/// unless the query mentions it.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, query::DynamicQuery};
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// let health = world.init_component::<Health>();
+/// world.spawn(Health(10));
+///
+/// let mut query = DynamicQuery::new(&mut world).with(health);
+/// for mut entity in query.iter_mut(&mut world) {
+///     let mut value = entity.get_mut_by_id(health).unwrap();
+///     // SAFETY: `health` is the id of the `Health` component.
+///     unsafe { value.as_mut().deref_mut::<Health>().0 += 5 };
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DynamicQuery {
+    world_id: WorldId,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+    disabled: Option<ComponentId>,
+    archetype_generation: ArchetypeGeneration,
+    matched_archetypes: Vec<ArchetypeId>,
+}
+
+impl DynamicQuery {
+    /// Creates a query matching every entity of the `world`.
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            world_id: world.id(),
+            with: Vec::new(),
+            without: Vec::new(),
+            disabled: Some(world.init_component::<Disabled>()),
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_archetypes: Vec::new(),
+        }
+    }
+
+    /// Only matches the entities which have the component.
+    pub fn with(mut self, component_id: ComponentId) -> Self {
+        self.with.push(component_id);
+        self.reset();
+        self
+    }
+
+    /// Only matches the entities which don't have the component.
+    pub fn without(mut self, component_id: ComponentId) -> Self {
+        self.without.push(component_id);
+        self.reset();
+        self
+    }
+
+    fn reset(&mut self) {
+        if self
+            .disabled
+            .is_some_and(|id| self.with.contains(&id) || self.without.contains(&id))
+        {
+            self.disabled = None;
+        }
+        self.archetype_generation = ArchetypeGeneration::initial();
+        self.matched_archetypes.clear();
+    }
+
+    /// Returns `true` if the query matches the entities of the archetype.
+    pub fn matches(&self, archetype: &Archetype) -> bool {
+        self.with.iter().all(|&id| archetype.contains(id))
+            && !self.without.iter().any(|&id| archetype.contains(id))
+            && !self.disabled.is_some_and(|id| archetype.contains(id))
+    }
+
+    /// Updates the archetypes matched by the query with those created since the last update.
+    ///
+    /// # Panics
+    ///
+    /// If `world` is not the one used to create the query.
+    pub fn update_archetypes(&mut self, world: &World) {
+        assert_eq!(
+            self.world_id,
+            world.id(),
+            "Encountered a mismatched World. This DynamicQuery was created from another World."
+        );
+        let archetypes = world.archetypes();
+        let old_generation =
+            std::mem::replace(&mut self.archetype_generation, archetypes.generation());
+        for archetype in &archetypes[old_generation..] {
+            if self.matches(archetype) {
+                self.matched_archetypes.push(archetype.id());
+            }
+        }
+    }
+
+    /// Returns an iterator over the matched entities, with read-only access to all of their
+    /// components.
+    ///
+    /// # Panics
+    ///
+    /// If `world` is not the one used to create the query.
+    pub fn iter<'a>(&'a mut self, world: &'a World) -> impl Iterator<Item = EntityRef<'a>> + 'a {
+        self.update_archetypes(world);
+        let world = world.as_unsafe_world_cell_readonly();
+        self.matched_archetypes
+            .iter()
+            .flat_map(move |&id| world.archetypes()[id].entities())
+            .map(move |entity| {
+                // SAFETY: The entity exists as it is in one of the archetypes of the world, and
+                // the world is borrowed immutably for the lifetime of the `EntityRef`.
+                unsafe { EntityRef::new(world.get_entity(entity.entity()).debug_checked_unwrap()) }
+            })
+    }
+
+    /// Returns an iterator over the matched entities, with mutable access to all of their
+    /// components.
+    ///
+    /// # Panics
+    ///
+    /// If `world` is not the one used to create the query.
+    pub fn iter_mut<'a>(
+        &'a mut self,
+        world: &'a mut World,
+    ) -> impl Iterator<Item = EntityMut<'a>> + 'a {
+        self.update_archetypes(world);
+        let world = world.as_unsafe_world_cell();
+        self.matched_archetypes
+            .iter()
+            .flat_map(move |&id| world.archetypes()[id].entities())
+            .map(move |entity| {
+                // SAFETY: The entity exists as it is in one of the archetypes of the world, which
+                // is borrowed mutably for the lifetime of the `EntityMut`, and each entity is
+                // yielded only once so their components are never aliased.
+                unsafe { EntityMut::new(world.get_entity(entity.entity()).debug_checked_unwrap()) }
+            })
+    }
+}
@@ -1217,8 +1217,13 @@ unsafe impl<T: Component> WorldQuery for Has<T> {
         *fetch
     }
 
-    fn update_component_access(_state: &Self::State, _access: &mut FilteredAccess<ComponentId>) {
-        // Do nothing as presence of `Has<T>` never affects whether two queries are disjoint
+    fn update_component_access(
+        &component_id: &Self::State,
+        access: &mut FilteredAccess<ComponentId>,
+    ) {
+        // Presence of `Has<T>` never affects whether two queries are disjoint, but it does
+        // affect the results.
+        access.access_mut().add_archetypal(component_id);
     }
 
     fn update_archetype_component_access(
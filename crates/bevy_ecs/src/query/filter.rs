@@ -320,6 +320,110 @@ impl<T: Component> QueryFilter for Without<T> {
     }
 }
 
+/// Filter that selects entities whether or not they have a component `T`.
+///
+/// This is used to include the entities with a component hidden from queries by default, such
+/// as [`Disabled`](crate::entity_disabling::Disabled), without filtering on it.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::entity_disabling::Disabled;
+/// # use bevy_ecs::query::Allows;
+/// # use bevy_ecs::system::Query;
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// fn heal_everyone(mut query: Query<&mut Health, Allows<Disabled>>) {
+///     for mut health in &mut query {
+///         health.0 = 100;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(heal_everyone);
+/// ```
+pub struct Allows<T>(PhantomData<T>);
+
+/// SAFETY:
+/// `update_component_access` and `update_archetype_component_access` do not add any accesses.
+/// This is sound because `fetch` does not access any components.
+unsafe impl<T: Component> WorldQuery for Allows<T> {
+    type Item<'w> = ();
+    type Fetch<'w> = ();
+    type State = ComponentId;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(_: Self::Item<'wlong>) -> Self::Item<'wshort> {}
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell,
+        _state: &ComponentId,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    const IS_DENSE: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype(
+        _fetch: &mut (),
+        _state: &ComponentId,
+        _archetype: &Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table(_fetch: &mut (), _state: &Self::State, _table: &Table) {}
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+    }
+
+    #[inline]
+    fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess<ComponentId>) {
+        access.access_mut().add_archetypal(id);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        _state: &ComponentId,
+        _archetype: &Archetype,
+        _access: &mut Access<ArchetypeComponentId>,
+    ) {
+    }
+
+    fn init_state(world: &mut World) -> ComponentId {
+        world.init_component::<T>()
+    }
+
+    fn matches_component_set(
+        _state: &ComponentId,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+impl<T: Component> QueryFilter for Allows<T> {
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        _fetch: &mut Self::Fetch<'_>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> bool {
+        true
+    }
+}
+
 /// A filter that tests if any of the given filters apply.
 ///
 /// This is useful for example if a system with multiple components in a query only wants to run
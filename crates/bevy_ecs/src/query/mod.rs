@@ -1,6 +1,7 @@
 //! Contains APIs for retrieving component data from the world.
 
 mod access;
+mod dynamic;
 mod error;
 mod fetch;
 mod filter;
@@ -11,6 +12,7 @@ mod world_query;
 
 pub use access::*;
 pub use bevy_ecs_macros::{QueryData, QueryFilter};
+pub use dynamic::*;
 pub use error::*;
 pub use fetch::*;
 pub use filter::*;
@@ -72,8 +74,12 @@ mod tests {
     use crate::prelude::{AnyOf, Changed, Entity, Or, QueryState, With, Without};
     use crate::query::{ArchetypeFilter, Has, QueryCombinationIter, ReadOnlyQueryData};
     use crate::schedule::{IntoSystemConfigs, Schedule};
-    use crate::system::{IntoSystem, Query, System, SystemState};
+    use crate::system::{
+        IntoSystem, Local, ParallelCommands, Query, ResMut, Resource, System, SystemState,
+    };
     use crate::{self as bevy_ecs, component::Component, world::World};
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+    use bevy_utils::Parallel;
     use std::any::type_name;
     use std::collections::HashSet;
 
@@ -803,4 +809,40 @@ mod tests {
         let values = world.query::<&B>().iter(&world).collect::<Vec<&B>>();
         assert_eq!(values, vec![&B(2)]);
     }
+
+    #[test]
+    fn par_iter_parallel_commands_and_locals() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::new();
+        world.spawn_batch((0..100).map(A));
+
+        fn despawn_odd_system(
+            query: Query<(Entity, &A)>,
+            par_commands: ParallelCommands,
+            mut despawned: Local<Parallel<Vec<usize>>>,
+            mut total: ResMut<Total>,
+        ) {
+            query.par_iter().for_each(|(entity, a)| {
+                if a.0 % 2 == 1 {
+                    par_commands.command_scope(|mut commands| commands.entity(entity).despawn());
+                    despawned.scope(|despawned| despawned.push(a.0));
+                }
+            });
+            total.0 = despawned.drain().sum();
+        }
+
+        #[derive(Resource, Default)]
+        struct Total(usize);
+
+        world.init_resource::<Total>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(despawn_odd_system);
+        schedule.run(&mut world);
+
+        assert_eq!(world.entities().len(), 50);
+        assert_eq!(
+            world.resource::<Total>().0,
+            (0..100).filter(|i| i % 2 == 1).sum()
+        );
+    }
 }
@@ -3,6 +3,7 @@ use crate::{
     change_detection::Mut,
     component::{ComponentId, Tick},
     entity::Entity,
+    entity_disabling::Disabled,
     prelude::{Component, FromWorld},
     query::{
         Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter,
@@ -39,6 +40,8 @@ pub struct QueryState<D: QueryData, F: QueryFilter = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: D::State,
     pub(crate) filter_state: F::State,
+    /// The [`Disabled`] component, if the entities with it are hidden from this query.
+    pub(crate) disabled: Option<ComponentId>,
     #[cfg(feature = "trace")]
     par_iter_span: Span,
 }
@@ -116,6 +119,13 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        // Disabled entities are hidden from queries which don't mention the `Disabled` component.
+        let disabled = world.init_component::<Disabled>();
+        let disabled = (!component_access.mentions(disabled)).then(|| {
+            component_access.and_without(disabled);
+            disabled
+        });
+
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -123,6 +133,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             matched_archetype_ids: Vec::new(),
             fetch_state,
             filter_state,
+            disabled,
             component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
@@ -250,6 +261,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     pub fn new_archetype(&mut self, archetype: &Archetype) {
         if D::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
+            && !self.disabled.is_some_and(|id| archetype.contains(id))
         {
             D::update_archetype_component_access(
                 &self.fetch_state,
@@ -716,6 +728,46 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the query results for the given [`World`], detecting the
+    /// changes made since the `tick` instead of since the last change tick of the world.
+    ///
+    /// See [`Query::since`](crate::system::Query::since) for more details.
+    ///
+    /// This can only be called for read-only queries, see [`Self::iter_mut_since`] for
+    /// write-queries.
+    #[inline]
+    pub fn iter_since<'w, 's>(
+        &'s mut self,
+        world: &'w World,
+        tick: Tick,
+    ) -> QueryIter<'w, 's, D::ReadOnly, F> {
+        self.update_archetypes(world);
+        // SAFETY: query is read only
+        unsafe {
+            self.as_readonly().iter_unchecked_manual(
+                world.as_unsafe_world_cell_readonly(),
+                tick,
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the query results for the given [`World`], detecting the
+    /// changes made since the `tick` instead of since the last change tick of the world.
+    ///
+    /// See [`Query::since`](crate::system::Query::since) for more details.
+    #[inline]
+    pub fn iter_mut_since<'w, 's>(
+        &'s mut self,
+        world: &'w mut World,
+        tick: Tick,
+    ) -> QueryIter<'w, 's, D, F> {
+        self.update_archetypes(world);
+        let change_tick = world.change_tick();
+        // SAFETY: query has unique world access
+        unsafe { self.iter_unchecked_manual(world.as_unsafe_world_cell(), tick, change_tick) }
+    }
+
     /// Returns an [`Iterator`] over the query results for the given [`World`] without updating the query's archetypes.
     /// Archetypes must be manually updated before by using [`Self::update_archetypes`].
     ///
@@ -1307,7 +1359,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{prelude::*, query::QueryEntityError};
+    use crate as bevy_ecs;
+    use crate::{component::Tick, prelude::*, query::QueryEntityError, system::RunSystemOnce};
 
     #[test]
     fn get_many_unchecked_manual_uniqueness() {
@@ -1412,4 +1465,33 @@ mod tests {
         let mut query_state = world_1.query::<Entity>();
         let _panics = query_state.get_many_mut(&mut world_2, []);
     }
+
+    #[test]
+    fn changes_since_tick() {
+        #[derive(Component)]
+        struct A(u32);
+
+        let mut world = World::new();
+        let a = world.spawn(A(0)).id();
+        let b = world.spawn(A(0)).id();
+        let since = world.increment_change_tick();
+        world.get_mut::<A>(b).unwrap().0 = 1;
+
+        let mut query_state = world.query_filtered::<Entity, Changed<A>>();
+        let changed: Vec<_> = query_state.iter_since(&world, since).collect();
+        assert_eq!(changed, [b]);
+        let changed: Vec<_> = query_state.iter_since(&world, Tick::new(0)).collect();
+        assert_eq!(changed, [a, b]);
+
+        let changed = world.run_system_once(move |mut query: Query<Entity, Changed<A>>| {
+            query.since(since).iter().collect::<Vec<_>>()
+        });
+        assert_eq!(changed, [b]);
+
+        let mut query_state = world.query::<&mut A>();
+        for mut value in query_state.iter_mut_since(&mut world, since) {
+            assert_eq!(value.is_changed(), value.0 == 1);
+            value.0 += 1;
+        }
+    }
 }
@@ -0,0 +1,179 @@
+use std::any::TypeId;
+
+use crate::{
+    component::Component,
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    system::{Command, CommandError, CommandErrorHandler},
+    world::World,
+};
+use bevy_reflect::{FromType, Reflect};
+use bevy_utils::HashSet;
+
+/// Duplicates the components of an entity onto another entity, using the reflection data in
+/// [`AppTypeRegistry`].
+///
+/// Every component of the entity whose type is registered with [`ReflectComponent`] is cloned,
+/// except the types [denied](Self::deny) by the duplicator and the types registered with
+/// [`ReflectNotDuplicated`]. The components which aren't registered are skipped.
+///
+/// Components referencing other entities are copied as is, so they reference the same entities
+/// as the components of the original entity. The hierarchy components of `bevy_hierarchy` are
+/// [not duplicated](ReflectNotDuplicated), so the copy of an entity has no parent nor children:
+/// the hierarchy of an entity should be duplicated with `bevy_hierarchy`'s `DuplicateRecursive`
+/// instead.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::EntityDuplicator};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default, PartialEq, Debug)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct NetworkId(u64);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// world.resource::<AppTypeRegistry>().write().register::<NetworkId>();
+/// let enemy = world.spawn((Health(10), NetworkId(1))).id();
+///
+/// let copy = EntityDuplicator::new()
+///     .deny::<NetworkId>()
+///     .duplicate(&mut world, enemy);
+/// assert_eq!(world.get::<Health>(copy), Some(&Health(10)));
+/// assert!(world.get::<NetworkId>(copy).is_none());
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct EntityDuplicator {
+    denied: HashSet<TypeId>,
+}
+
+impl EntityDuplicator {
+    /// Creates a duplicator cloning all of the reflected components.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prevents the duplication of the components of type `T`.
+    pub fn deny<T: Component>(self) -> Self {
+        self.deny_by_type_id(TypeId::of::<T>())
+    }
+
+    /// Prevents the duplication of the components with the [`TypeId`].
+    pub fn deny_by_type_id(mut self, type_id: TypeId) -> Self {
+        self.denied.insert(type_id);
+        self
+    }
+
+    /// Returns `true` if the components with the [`TypeId`] are duplicated.
+    pub fn is_allowed(&self, type_id: TypeId) -> bool {
+        !self.denied.contains(&type_id)
+    }
+
+    /// Spawns a new entity with the components of the `source` entity, and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `source` entity doesn't exist, or if [`AppTypeRegistry`] is not present in
+    /// the [`World`].
+    pub fn duplicate(&self, world: &mut World, source: Entity) -> Entity {
+        let target = world.spawn_empty().id();
+        self.duplicate_into(world, source, target);
+        target
+    }
+
+    /// Inserts the components of the `source` entity into the `target` entity, replacing the
+    /// components of the same types.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either entity doesn't exist, or if [`AppTypeRegistry`] is not present in the
+    /// [`World`].
+    pub fn duplicate_into(&self, world: &mut World, source: Entity, target: Entity) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let source = world.entity(source);
+        let components: Vec<(&ReflectComponent, Box<dyn Reflect>)> = source
+            .archetype()
+            .components()
+            .filter_map(|component_id| {
+                let type_id = world.components().get_info(component_id)?.type_id()?;
+                if !self.is_allowed(type_id)
+                    || registry
+                        .get_type_data::<ReflectNotDuplicated>(type_id)
+                        .is_some()
+                {
+                    return None;
+                }
+                let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+                let component = reflect_component.reflect(source)?.clone_value();
+                Some((reflect_component, component))
+            })
+            .collect();
+
+        let mut target = world.entity_mut(target);
+        for (reflect_component, component) in components {
+            reflect_component.insert(&mut target, &*component);
+        }
+    }
+}
+
+/// Type data marking a component which is never duplicated by an [`EntityDuplicator`], as
+/// copying it would make an inconsistent world.
+///
+/// For example, the `Parent` and `Children` components of `bevy_hierarchy` are marked with
+/// `#[reflect(NotDuplicated)]`: a copy holding them would claim the children of the original
+/// entity without being listed in the children of its parent.
+#[derive(Clone, Copy, Debug)]
+pub struct ReflectNotDuplicated;
+
+impl<C: Component> FromType<C> for ReflectNotDuplicated {
+    fn from_type() -> Self {
+        ReflectNotDuplicated
+    }
+}
+
+impl World {
+    /// Spawns a new entity with clones of the reflected components of the `entity`, and
+    /// returns it.
+    ///
+    /// See [`EntityDuplicator`] to skip some of the components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `entity` doesn't exist, or if [`AppTypeRegistry`] is not present in the
+    /// [`World`].
+    pub fn duplicate_entity(&mut self, entity: Entity) -> Entity {
+        EntityDuplicator::new().duplicate(self, entity)
+    }
+}
+
+/// A [`Command`] that duplicates the components of an entity onto another entity, with an
+/// [`EntityDuplicator`].
+pub struct DuplicateEntity {
+    /// The entity whose components are duplicated.
+    pub source: Entity,
+    /// The entity the components are inserted into.
+    pub target: Entity,
+    /// The duplicator selecting the components.
+    pub duplicator: EntityDuplicator,
+}
+
+impl Command for DuplicateEntity {
+    fn apply(self, world: &mut World) {
+        for entity in [self.source, self.target] {
+            if world.get_entity(entity).is_none() {
+                CommandError::no_such_entity("duplicate entity", entity)
+                    .report(world, CommandErrorHandler::panic());
+                return;
+            }
+        }
+        self.duplicator
+            .duplicate_into(world, self.source, self.target);
+    }
+}
@@ -0,0 +1,205 @@
+//! Components defined at runtime whose values are reflected.
+//!
+//! Scripting and modding layers can't declare new Rust types, so the components they add are
+//! registered with [`World::init_dynamic_component`] instead. Each value of these components
+//! is a `Box<dyn Reflect>`, which is usually a [`DynamicStruct`](bevy_reflect::DynamicStruct)
+//! built at runtime, and is read and written through reflection.
+//!
+//! ```
+//! # use bevy_ecs::{component::StorageType, prelude::*, query::DynamicQuery};
+//! # use bevy_reflect::{DynamicStruct, GetField};
+//! let mut world = World::new();
+//! let mana = world.init_dynamic_component("Mana", StorageType::Table);
+//!
+//! let mut value = DynamicStruct::default();
+//! value.insert("current", 10.0f32);
+//! world.spawn_empty().insert_reflect_by_id(mana, Box::new(value));
+//!
+//! let mut query = DynamicQuery::new(&mut world).with(mana);
+//! for mut entity in query.iter_mut(&mut world) {
+//!     let mut value = entity.get_reflect_mut_by_id(mana).unwrap();
+//!     let Some(value) = value.as_reflect_mut().downcast_mut::<DynamicStruct>() else {
+//!         continue;
+//!     };
+//!     *value.get_field_mut::<f32>("current").unwrap() += 5.0;
+//! }
+//! ```
+
+use crate::{
+    component::{ComponentDescriptor, ComponentId, Components, StorageType},
+    world::{EntityMut, EntityRef, EntityWorldMut, Mut, World},
+};
+use bevy_ptr::OwningPtr;
+use bevy_reflect::Reflect;
+use std::{any::TypeId, borrow::Cow};
+
+/// Returns `true` if the component was registered with [`World::init_dynamic_component`].
+fn is_dynamic_component(components: &Components, component_id: ComponentId) -> bool {
+    components
+        .get_info(component_id)
+        .is_some_and(|info| info.type_id() == Some(TypeId::of::<Box<dyn Reflect>>()))
+}
+
+impl World {
+    /// Registers a new component which isn't a Rust type, whose values are reflected values
+    /// inserted with [`EntityWorldMut::insert_reflect_by_id`].
+    ///
+    /// Each call registers a distinct component, even with the same name.
+    pub fn init_dynamic_component(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        storage_type: StorageType,
+    ) -> ComponentId {
+        self.init_component_with_descriptor(ComponentDescriptor::new_dynamic::<Box<dyn Reflect>>(
+            name,
+            storage_type,
+        ))
+    }
+}
+
+impl<'w> EntityRef<'w> {
+    /// Gets the reflected value of a component registered with
+    /// [`World::init_dynamic_component`].
+    ///
+    /// Returns `None` if the entity doesn't have the component, or if it wasn't registered
+    /// with [`World::init_dynamic_component`].
+    pub fn get_reflect_by_id(&self, component_id: ComponentId) -> Option<&'w dyn Reflect> {
+        if !is_dynamic_component(self.components(), component_id) {
+            return None;
+        }
+        let ptr = self.get_by_id(component_id)?;
+        // SAFETY: The values of dynamic components are `Box<dyn Reflect>`.
+        Some(unsafe { ptr.deref::<Box<dyn Reflect>>() }.as_ref())
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Gets the reflected value of a component registered with
+    /// [`World::init_dynamic_component`].
+    ///
+    /// Returns `None` if the entity doesn't have the component, or if it wasn't registered
+    /// with [`World::init_dynamic_component`].
+    pub fn get_reflect_by_id(&self, component_id: ComponentId) -> Option<&'_ dyn Reflect> {
+        self.as_readonly().get_reflect_by_id(component_id)
+    }
+
+    /// Gets mutable access to the reflected value of a component registered with
+    /// [`World::init_dynamic_component`].
+    ///
+    /// Returns `None` if the entity doesn't have the component, or if it wasn't registered
+    /// with [`World::init_dynamic_component`].
+    pub fn get_reflect_mut_by_id(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Option<Mut<'_, dyn Reflect>> {
+        if !is_dynamic_component(self.as_readonly().components(), component_id) {
+            return None;
+        }
+        let value = self.get_mut_by_id(component_id)?;
+        // SAFETY: The values of dynamic components are `Box<dyn Reflect>`.
+        Some(value.map_unchanged(|ptr| unsafe { ptr.deref_mut::<Box<dyn Reflect>>() }.as_mut()))
+    }
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Inserts the reflected value of a component registered with
+    /// [`World::init_dynamic_component`].
+    ///
+    /// This will overwrite any previous value of the component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component wasn't registered with [`World::init_dynamic_component`].
+    pub fn insert_reflect_by_id(
+        &mut self,
+        component_id: ComponentId,
+        value: Box<dyn Reflect>,
+    ) -> &mut Self {
+        assert!(
+            is_dynamic_component(self.world().components(), component_id),
+            "{component_id:?} is not a dynamic component, register it with `World::init_dynamic_component`"
+        );
+        OwningPtr::make(value, |ptr| {
+            // SAFETY: The component belongs to this world and its values are `Box<dyn Reflect>`.
+            unsafe { self.insert_by_id(component_id, ptr) }
+        })
+    }
+
+    /// Gets the reflected value of a component registered with
+    /// [`World::init_dynamic_component`].
+    ///
+    /// Returns `None` if the entity doesn't have the component, or if it wasn't registered
+    /// with [`World::init_dynamic_component`].
+    pub fn get_reflect_by_id(&self, component_id: ComponentId) -> Option<&'_ dyn Reflect> {
+        EntityRef::from(self).get_reflect_by_id(component_id)
+    }
+
+    /// Gets mutable access to the reflected value of a component registered with
+    /// [`World::init_dynamic_component`].
+    ///
+    /// Returns `None` if the entity doesn't have the component, or if it wasn't registered
+    /// with [`World::init_dynamic_component`].
+    pub fn get_reflect_mut_by_id(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Option<Mut<'_, dyn Reflect>> {
+        if !is_dynamic_component(self.world().components(), component_id) {
+            return None;
+        }
+        let value = self.get_mut_by_id(component_id)?;
+        // SAFETY: The values of dynamic components are `Box<dyn Reflect>`.
+        Some(value.map_unchanged(|ptr| unsafe { ptr.deref_mut::<Box<dyn Reflect>>() }.as_mut()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component::StorageType, query::DynamicQuery, world::World};
+    use bevy_reflect::{DynamicStruct, GetField, Reflect};
+
+    fn health(value: u32) -> Box<dyn Reflect> {
+        let mut health = DynamicStruct::default();
+        health.insert("value", value);
+        Box::new(health)
+    }
+
+    fn value(reflect: &dyn Reflect) -> u32 {
+        let health = reflect.downcast_ref::<DynamicStruct>().unwrap();
+        *health.get_field::<u32>("value").unwrap()
+    }
+
+    #[test]
+    fn dynamic_components() {
+        let mut world = World::new();
+        let table = world.init_dynamic_component("Health", StorageType::Table);
+        let sparse = world.init_dynamic_component("Health", StorageType::SparseSet);
+        assert_ne!(table, sparse);
+
+        let a = world
+            .spawn_empty()
+            .insert_reflect_by_id(table, health(1))
+            .insert_reflect_by_id(sparse, health(2))
+            .id();
+        let b = world
+            .spawn_empty()
+            .insert_reflect_by_id(table, health(3))
+            .id();
+
+        let mut query = DynamicQuery::new(&mut world).with(table).without(sparse);
+        for mut entity in query.iter_mut(&mut world) {
+            let mut health = entity.get_reflect_mut_by_id(table).unwrap();
+            let health = health.downcast_mut::<DynamicStruct>().unwrap();
+            *health.get_field_mut::<u32>("value").unwrap() += 10;
+        }
+        let entities: Vec<_> = query.iter(&world).map(|entity| entity.id()).collect();
+        assert_eq!(entities, [b]);
+
+        assert_eq!(value(world.entity(a).get_reflect_by_id(table).unwrap()), 1);
+        assert_eq!(value(world.entity(b).get_reflect_by_id(table).unwrap()), 13);
+        assert!(world.entity(b).get_reflect_by_id(sparse).is_none());
+
+        world.entity_mut(a).remove_by_id(sparse);
+        let entities: Vec<_> = query.iter(&world).map(|entity| entity.id()).collect();
+        assert_eq!(entities, [b, a]);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::prelude::Mut;
 use crate::reflect::AppTypeRegistry;
-use crate::system::{Command, EntityCommands, Resource};
-use crate::{entity::Entity, reflect::ReflectComponent, world::World};
+use crate::system::{Command, CommandError, CommandErrorHandler, EntityCommands, Resource};
+use crate::{
+    entity::Entity,
+    reflect::{DuplicateEntity, EntityDuplicator, ReflectComponent},
+    world::World,
+};
 use bevy_reflect::{Reflect, TypeRegistry};
 use std::borrow::Cow;
 use std::marker::PhantomData;
@@ -15,7 +19,8 @@ pub trait ReflectCommandExt {
     ///
     /// # Panics
     ///
-    /// - If the entity doesn't exist.
+    /// - If the entity doesn't exist, unless the world has a
+    ///   [`CommandErrorHandler`](crate::system::CommandErrorHandler) handling the error otherwise.
     /// - If [`AppTypeRegistry`] does not have the reflection data for the given [`Component`](crate::component::Component).
     /// - If the component data is invalid. See [`Reflect::apply`] for further details.
     /// - If [`AppTypeRegistry`] is not present in the [`World`].
@@ -138,6 +143,23 @@ pub trait ReflectCommandExt {
         &mut self,
         component_type_name: impl Into<Cow<'static, str>>,
     ) -> &mut Self;
+
+    /// Spawns a new entity with clones of the reflected components of the entity, like
+    /// [`World::duplicate_entity`], and returns it.
+    ///
+    /// The components are cloned when the command is applied, so the duplicate gets the
+    /// components the entity has at that point.
+    ///
+    /// # Panics
+    ///
+    /// - If the entity doesn't exist, unless the world has a
+    ///   [`CommandErrorHandler`](crate::system::CommandErrorHandler) handling the error otherwise.
+    /// - If [`AppTypeRegistry`] is not present in the [`World`].
+    fn duplicate(&mut self) -> Entity;
+
+    /// Same as [`duplicate`](ReflectCommandExt::duplicate), but skipping the components denied
+    /// by the `duplicator`.
+    fn duplicate_with(&mut self, duplicator: EntityDuplicator) -> Entity;
 }
 
 impl<'w, 's, 'a> ReflectCommandExt for EntityCommands<'w, 's, 'a> {
@@ -180,6 +202,20 @@ impl<'w, 's, 'a> ReflectCommandExt for EntityCommands<'w, 's, 'a> {
         });
         self
     }
+
+    fn duplicate(&mut self) -> Entity {
+        self.duplicate_with(EntityDuplicator::new())
+    }
+
+    fn duplicate_with(&mut self, duplicator: EntityDuplicator) -> Entity {
+        let target = self.commands.spawn_empty().id();
+        self.commands.add(DuplicateEntity {
+            source: self.entity,
+            target,
+            duplicator,
+        });
+        target
+    }
 }
 
 /// Helper function to add a reflect component to a given entity
@@ -193,9 +229,12 @@ fn insert_reflect(
         .get_represented_type_info()
         .expect("component should represent a type.");
     let type_path = type_info.type_path();
-    let Some(mut entity) = world.get_entity_mut(entity) else {
-        panic!("error[B0003]: Could not insert a reflected component (of type {type_path}) for entity {entity:?} because it doesn't exist in this World.");
-    };
+    if world.get_entity(entity).is_none() {
+        let action = format!("insert a reflected component (of type {type_path}) for entity");
+        CommandError::no_such_entity(action, entity).report(world, CommandErrorHandler::panic());
+        return;
+    }
+    let mut entity = world.entity_mut(entity);
     let Some(type_registration) = type_registry.get_with_type_path(type_path) else {
         panic!("Could not get type registration (for component type {type_path}) because it doesn't exist in the TypeRegistry.");
     };
@@ -251,9 +290,13 @@ fn remove_reflect(
     type_registry: &TypeRegistry,
     component_type_path: Cow<'static, str>,
 ) {
-    let Some(mut entity) = world.get_entity_mut(entity) else {
+    if world.get_entity(entity).is_none() {
+        let action =
+            format!("remove a reflected component (of type {component_type_path}) from entity");
+        CommandError::no_such_entity(action, entity).report(world, CommandErrorHandler::ignore());
         return;
-    };
+    }
+    let mut entity = world.entity_mut(entity);
     let Some(type_registration) = type_registry.get_with_type_path(&component_type_path) else {
         return;
     };
@@ -312,7 +355,7 @@ impl<T: Resource + AsRef<TypeRegistry>> Command for RemoveReflectWithRegistry<T>
 #[cfg(test)]
 mod tests {
     use crate::prelude::{AppTypeRegistry, ReflectComponent};
-    use crate::reflect::ReflectCommandExt;
+    use crate::reflect::{EntityDuplicator, ReflectCommandExt};
     use crate::system::{Commands, SystemState};
     use crate::{self as bevy_ecs, component::Component, world::World};
     use bevy_ecs_macros::Resource;
@@ -457,4 +500,43 @@ mod tests {
 
         assert_eq!(world.entity(entity).get::<ComponentA>(), None);
     }
+
+    #[test]
+    fn duplicate() {
+        #[derive(Component, Reflect, Default, PartialEq, Eq, Debug)]
+        #[reflect(Component)]
+        struct ComponentB(u32);
+
+        #[derive(Component)]
+        struct NotReflected;
+
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<ComponentA>();
+            registry.register::<ComponentB>();
+        }
+        world.insert_resource(type_registry);
+        let entity = world
+            .spawn((ComponentA(1), ComponentB(2), NotReflected))
+            .id();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        let copy = commands.entity(entity).duplicate();
+        let partial_copy = commands
+            .entity(entity)
+            .duplicate_with(EntityDuplicator::new().deny::<ComponentB>());
+        system_state.apply(&mut world);
+
+        assert_eq!(world.get::<ComponentA>(copy), Some(&ComponentA(1)));
+        assert_eq!(world.get::<ComponentB>(copy), Some(&ComponentB(2)));
+        assert!(world.get::<NotReflected>(copy).is_none());
+        assert_eq!(world.get::<ComponentA>(partial_copy), Some(&ComponentA(1)));
+        assert!(world.get::<ComponentB>(partial_copy).is_none());
+
+        let world_copy = world.duplicate_entity(entity);
+        assert_eq!(world.get::<ComponentB>(world_copy), Some(&ComponentB(2)));
+    }
 }
@@ -0,0 +1,109 @@
+//! Definitions for [`Event`] reflection.
+//!
+//! This allows code which doesn't know the type of an event at compile time, such as a scripting
+//! language, to send and read events as reflected values.
+
+use crate::{
+    event::{Event, Events, ManualEventReader},
+    world::World,
+};
+use bevy_reflect::{FromReflect, FromType, Reflect};
+use std::any::Any;
+
+/// A struct used to send and read reflected [`Event`]s of a type.
+///
+/// A [`ReflectEvent`] for type `T` can be obtained via [`bevy_reflect::TypeRegistration::data`],
+/// once it is registered with `#[reflect(Event)]` or
+/// [`TypeRegistry::register_type_data`](bevy_reflect::TypeRegistry::register_type_data).
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::ReflectEvent};
+/// # use bevy_reflect::{DynamicStruct, FromType, Reflect};
+/// #[derive(Event, Reflect, Debug, PartialEq)]
+/// struct Damage {
+///     amount: u32,
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Events<Damage>>();
+/// let reflect_event = <ReflectEvent as FromType<Damage>>::from_type();
+///
+/// let mut damage = DynamicStruct::default();
+/// damage.insert("amount", 5_u32);
+/// assert!(reflect_event.send(&mut world, &damage));
+///
+/// let mut reader = reflect_event.reader();
+/// let events = reflect_event.read(&world, &mut reader);
+/// assert_eq!(Some(&Damage { amount: 5 }), events[0].downcast_ref::<Damage>());
+/// assert!(reflect_event.read(&world, &mut reader).is_empty());
+/// ```
+#[derive(Clone)]
+pub struct ReflectEvent {
+    send: fn(&mut World, &dyn Reflect) -> bool,
+    read: for<'a> fn(&'a World, &'a mut ReflectEventReader) -> Vec<&'a dyn Reflect>,
+    reader: fn() -> ReflectEventReader,
+}
+
+/// The state of a reader of the reflected events of a type, created by [`ReflectEvent::reader`].
+///
+/// Like an [`EventReader`](crate::event::EventReader), each reader reads every event once.
+pub struct ReflectEventReader(Box<dyn Any + Send + Sync>);
+
+impl ReflectEvent {
+    /// Sends `event` to the [`Events`] resource of the type.
+    ///
+    /// Returns `false` if the event couldn't be converted to the type with [`FromReflect`], or if
+    /// the world doesn't have the [`Events`] resource of the type.
+    pub fn send(&self, world: &mut World, event: &dyn Reflect) -> bool {
+        (self.send)(world, event)
+    }
+
+    /// Returns the events of the type which `reader` hasn't read yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reader` was created for another type of event.
+    pub fn read<'a>(
+        &self,
+        world: &'a World,
+        reader: &'a mut ReflectEventReader,
+    ) -> Vec<&'a dyn Reflect> {
+        (self.read)(world, reader)
+    }
+
+    /// Creates a reader of the events of the type, which hasn't read any event yet.
+    pub fn reader(&self) -> ReflectEventReader {
+        (self.reader)()
+    }
+}
+
+impl<E: Event + Reflect + FromReflect> FromType<E> for ReflectEvent {
+    fn from_type() -> Self {
+        ReflectEvent {
+            send: |world, event| {
+                let Some(event) = E::from_reflect(event) else {
+                    return false;
+                };
+                let Some(mut events) = world.get_resource_mut::<Events<E>>() else {
+                    return false;
+                };
+                events.send(event);
+                true
+            },
+            read: |world, reader| {
+                let reader = reader
+                    .0
+                    .downcast_mut::<ManualEventReader<E>>()
+                    .expect("the reader should read the events of this type");
+                match world.get_resource::<Events<E>>() {
+                    Some(events) => reader
+                        .read(events)
+                        .map(|event| event as &dyn Reflect)
+                        .collect(),
+                    None => Vec::new(),
+                }
+            },
+            reader: || ReflectEventReader(Box::<ManualEventReader<E>>::default()),
+        }
+    }
+}
@@ -4,19 +4,31 @@ use std::ops::{Deref, DerefMut};
 
 use crate as bevy_ecs;
 use crate::{entity::Entity, system::Resource};
-use bevy_reflect::{impl_reflect_value, ReflectDeserialize, ReflectSerialize, TypeRegistryArc};
+use bevy_reflect::{
+    func::FunctionRegistryArc, impl_reflect_value, ReflectDeserialize, ReflectSerialize,
+    TypeRegistryArc,
+};
 
 mod bundle;
 mod component;
+mod duplicate;
+mod dynamic_component;
 mod entity_commands;
+mod event;
 mod map_entities;
 mod resource;
+mod system;
+mod world;
 
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
+pub use duplicate::{DuplicateEntity, EntityDuplicator, ReflectNotDuplicated};
 pub use entity_commands::ReflectCommandExt;
+pub use event::{ReflectEvent, ReflectEventReader};
 pub use map_entities::ReflectMapEntities;
 pub use resource::{ReflectResource, ReflectResourceFns};
+pub use system::{ReflectSystemError, ReflectSystemId, ReflectSystems};
+pub use world::ReflectComponentAccessError;
 
 /// A [`Resource`] storing [`TypeRegistry`](bevy_reflect::TypeRegistry) for
 /// type registrations relevant to a whole app.
@@ -39,4 +51,25 @@ impl DerefMut for AppTypeRegistry {
     }
 }
 
+/// A [`Resource`] storing [`FunctionRegistry`](bevy_reflect::func::FunctionRegistry) for
+/// reflected functions relevant to a whole app.
+#[derive(Resource, Clone, Default)]
+pub struct AppFunctionRegistry(pub FunctionRegistryArc);
+
+impl Deref for AppFunctionRegistry {
+    type Target = FunctionRegistryArc;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for AppFunctionRegistry {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl_reflect_value!((in bevy_ecs) Entity(Hash, PartialEq, Serialize, Deserialize));
@@ -0,0 +1,277 @@
+//! Running registered systems with reflected inputs and outputs.
+//!
+//! A [`SystemId<I, O>`] can only be run by code which knows the types `I` and `O`. Converting it
+//! to a [`ReflectSystemId`] erases them, so that a console or a command-pattern layer can run the
+//! system with a [`Box<dyn Reflect>`] input, for example deserialized from user input, and get
+//! its output as a [`Box<dyn Reflect>`].
+
+use std::borrow::Cow;
+
+use crate as bevy_ecs;
+use crate::{
+    entity::Entity,
+    system::{BoxedSystem, IntoSystem, RegisteredSystemError, Resource, SystemId},
+    world::World,
+};
+use bevy_reflect::{FromReflect, Reflect, TypePath};
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+/// An identifier for a registered system, which runs it with a reflected input and returns its
+/// reflected output.
+///
+/// It is created from a [`SystemId<I, O>`] with [`SystemId::reflect`], or by
+/// [`World::register_reflect_system`], and run by [`World::run_reflect_system`].
+#[derive(Clone, Copy, Debug)]
+pub struct ReflectSystemId {
+    entity: Entity,
+    input_type_path: &'static str,
+    run: ReflectSystemFn,
+}
+
+type ReflectSystemFn =
+    fn(&mut World, Entity, Box<dyn Reflect>) -> Result<Box<dyn Reflect>, ReflectSystemError>;
+
+// A manual impl is used because the function pointer is determined by the entity.
+impl PartialEq for ReflectSystemId {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+    }
+}
+
+impl Eq for ReflectSystemId {}
+
+// A manual impl is used because the function pointer is determined by the entity.
+impl std::hash::Hash for ReflectSystemId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.entity.hash(state);
+    }
+}
+
+impl ReflectSystemId {
+    /// Returns the type path of the input of the system.
+    pub fn input_type_path(&self) -> &'static str {
+        self.input_type_path
+    }
+}
+
+impl<I: FromReflect + TypePath, O: Reflect> SystemId<I, O> {
+    /// Returns a [`ReflectSystemId`] running this system with reflected inputs and outputs.
+    pub fn reflect(self) -> ReflectSystemId {
+        ReflectSystemId {
+            entity: self.entity(),
+            input_type_path: I::type_path(),
+            run: run_reflect_system::<I, O>,
+        }
+    }
+}
+
+fn run_reflect_system<I: FromReflect + TypePath, O: Reflect>(
+    world: &mut World,
+    entity: Entity,
+    input: Box<dyn Reflect>,
+) -> Result<Box<dyn Reflect>, ReflectSystemError> {
+    let input = I::take_from_reflect(input).map_err(|input| ReflectSystemError::InvalidInput {
+        expected: I::type_path(),
+        found: input.reflect_type_path().to_owned(),
+    })?;
+    world
+        .run_system_with_input(SystemId::<I, O>::from_entity(entity), input)
+        .map(|output| Box::new(output) as Box<dyn Reflect>)
+        .map_err(|error| match error {
+            RegisteredSystemError::SystemIdNotRegistered(_) => {
+                ReflectSystemError::NotRegistered(entity)
+            }
+            RegisteredSystemError::Recursive(_) | RegisteredSystemError::SelfRemove(_) => {
+                ReflectSystemError::Recursive(entity)
+            }
+        })
+}
+
+/// An error that occurred while running a system by its [`ReflectSystemId`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReflectSystemError {
+    /// The input isn't of the type of the input of the system.
+    #[error("the system expects an input of type {expected}, but was given a {found}")]
+    InvalidInput {
+        /// The type path of the input of the system.
+        expected: &'static str,
+        /// The type path of the input which was given.
+        found: String,
+    },
+    /// No system is registered with the id, it was removed.
+    #[error("system {0:?} was not registered")]
+    NotRegistered(Entity),
+    /// The system tried to run itself recursively.
+    #[error("system {0:?} tried to run itself recursively")]
+    Recursive(Entity),
+    /// No system is registered with the name in [`ReflectSystems`].
+    #[error("no system is named {0:?}")]
+    UnknownName(String),
+}
+
+/// A [`Resource`] naming systems registered with [`World::register_reflect_system`], so that
+/// they can be run by name, for example from a console.
+#[derive(Resource, Default, Debug)]
+pub struct ReflectSystems {
+    systems: HashMap<Cow<'static, str>, ReflectSystemId>,
+}
+
+impl ReflectSystems {
+    /// Names the system, replacing and returning the system which had the name before.
+    pub fn insert(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        id: ReflectSystemId,
+    ) -> Option<ReflectSystemId> {
+        self.systems.insert(name.into(), id)
+    }
+
+    /// Returns the system with the name.
+    pub fn get(&self, name: &str) -> Option<ReflectSystemId> {
+        self.systems.get(name).copied()
+    }
+
+    /// Removes the name of a system, returning the system.
+    ///
+    /// This doesn't remove the system from the world: use [`World::remove_system`] for that.
+    pub fn remove(&mut self, name: &str) -> Option<ReflectSystemId> {
+        self.systems.remove(name)
+    }
+
+    /// Returns the names of the systems with their id, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ReflectSystemId)> {
+        self.systems.iter().map(|(name, id)| (&**name, *id))
+    }
+}
+
+impl World {
+    /// Registers a system like [`World::register_system`], and names it in the
+    /// [`ReflectSystems`] resource so that it can be run with a reflected input by
+    /// [`World::run_reflect_system_by_name`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::reflect::ReflectSystemError;
+    /// fn double(In(value): In<i32>) -> i32 {
+    ///     value * 2
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.register_reflect_system("double", double);
+    ///
+    /// let output = world.run_reflect_system_by_name("double", Box::new(21_i32)).unwrap();
+    /// assert_eq!(output.downcast_ref::<i32>(), Some(&42));
+    ///
+    /// let error = world.run_reflect_system_by_name("double", Box::new("21".to_string()));
+    /// assert!(matches!(error, Err(ReflectSystemError::InvalidInput { .. })));
+    /// ```
+    pub fn register_reflect_system<I, O, M, S>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        system: S,
+    ) -> ReflectSystemId
+    where
+        I: FromReflect + TypePath,
+        O: Reflect,
+        S: IntoSystem<I, O, M> + 'static,
+    {
+        self.register_boxed_reflect_system(name, Box::new(IntoSystem::into_system(system)))
+    }
+
+    /// Similar to [`Self::register_reflect_system`], but allows passing in a [`BoxedSystem`].
+    pub fn register_boxed_reflect_system<I: FromReflect + TypePath, O: Reflect>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        system: BoxedSystem<I, O>,
+    ) -> ReflectSystemId {
+        let id = self.register_boxed_system(system).reflect();
+        self.get_resource_or_insert_with(ReflectSystems::default)
+            .insert(name, id);
+        id
+    }
+
+    /// Runs a registered system by its [`ReflectSystemId`], with the reflected `input`, and
+    /// returns its reflected output.
+    ///
+    /// The input must be of the type of the input of the system, or a dynamic value which
+    /// can be converted to it with [`FromReflect`].
+    pub fn run_reflect_system(
+        &mut self,
+        id: ReflectSystemId,
+        input: Box<dyn Reflect>,
+    ) -> Result<Box<dyn Reflect>, ReflectSystemError> {
+        (id.run)(self, id.entity, input)
+    }
+
+    /// Runs the system with the name in the [`ReflectSystems`] resource, like
+    /// [`World::run_reflect_system`].
+    pub fn run_reflect_system_by_name(
+        &mut self,
+        name: &str,
+        input: Box<dyn Reflect>,
+    ) -> Result<Box<dyn Reflect>, ReflectSystemError> {
+        let id = self
+            .get_resource::<ReflectSystems>()
+            .and_then(|systems| systems.get(name))
+            .ok_or_else(|| ReflectSystemError::UnknownName(name.to_owned()))?;
+        self.run_reflect_system(id, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReflectSystemError;
+    use crate::{
+        self as bevy_ecs,
+        system::{In, ResMut, Resource},
+        world::World,
+    };
+    use bevy_reflect::{DynamicStruct, Reflect};
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Spawn {
+        count: u32,
+    }
+
+    #[derive(Resource, Default)]
+    struct Spawned(u32);
+
+    fn spawn(In(spawn): In<Spawn>, mut spawned: ResMut<Spawned>) -> u32 {
+        spawned.0 += spawn.count;
+        spawned.0
+    }
+
+    #[test]
+    fn run_reflect_systems() {
+        let mut world = World::new();
+        world.init_resource::<Spawned>();
+        let id = world.register_reflect_system("spawn", spawn);
+        assert_eq!(id.input_type_path(), std::any::type_name::<Spawn>());
+
+        let output = world
+            .run_reflect_system(id, Box::new(Spawn { count: 2 }))
+            .unwrap();
+        assert_eq!(output.downcast_ref::<u32>(), Some(&2));
+
+        // Dynamic inputs are converted to the input of the system.
+        let mut input = DynamicStruct::default();
+        input.insert("count", 3_u32);
+        let output = world
+            .run_reflect_system_by_name("spawn", Box::new(input))
+            .unwrap();
+        assert_eq!(output.downcast_ref::<u32>(), Some(&5));
+
+        assert_eq!(
+            world.run_reflect_system(id, Box::new(3_u32)).err(),
+            Some(ReflectSystemError::InvalidInput {
+                expected: id.input_type_path(),
+                found: "u32".to_string(),
+            })
+        );
+        assert!(matches!(
+            world.run_reflect_system_by_name("despawn", Box::new(())),
+            Err(ReflectSystemError::UnknownName(_))
+        ));
+    }
+}
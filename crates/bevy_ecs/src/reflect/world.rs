@@ -0,0 +1,227 @@
+//! Accessing the components of entities as reflected values.
+//!
+//! These methods let code which only knows the [`TypeId`] of a component, or has its value as a
+//! [`Box<dyn Reflect>`], such as a scripting language, read and write the components of an
+//! entity through the [`ReflectComponent`] type data registered in the [`AppTypeRegistry`].
+//! Unlike the [`ReflectCommandExt`](crate::reflect::ReflectCommandExt) commands, they return an
+//! error instead of panicking.
+
+use crate::{
+    change_detection::Mut,
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::World,
+};
+use bevy_reflect::Reflect;
+use std::any::TypeId;
+use thiserror::Error;
+
+/// An error accessing a reflected component of an entity.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReflectComponentAccessError {
+    /// The entity doesn't exist.
+    #[error("entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    /// The world doesn't have an [`AppTypeRegistry`].
+    #[error("the world has no `AppTypeRegistry`")]
+    MissingAppTypeRegistry,
+    /// A boxed component doesn't represent a type, see [`Reflect::get_represented_type_info`].
+    #[error("the value of type {0} doesn't represent a type")]
+    NoRepresentedType(String),
+    /// The type isn't registered, or isn't registered with [`ReflectComponent`].
+    #[error("the type {0:?} is not registered with `#[reflect(Component)]`")]
+    UnregisteredComponent(TypeId),
+    /// The entity doesn't have the component.
+    #[error("entity {entity:?} does not have the component {type_path}")]
+    MissingComponent {
+        /// The entity.
+        entity: Entity,
+        /// The type path of the component.
+        type_path: &'static str,
+    },
+}
+
+impl World {
+    /// Returns the component of `entity` with the [`TypeId`] `type_id`, as a reflected value.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// # use std::any::TypeId;
+    /// #[derive(Component, Reflect, Default)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<AppTypeRegistry>();
+    /// world.resource::<AppTypeRegistry>().write().register::<Health>();
+    ///
+    /// let entity = world.spawn(Health(10)).id();
+    /// world.insert_reflect(entity, Box::new(Health(5))).unwrap();
+    /// let health = world.get_reflect(entity, TypeId::of::<Health>()).unwrap();
+    /// assert_eq!(Some(&5), health.downcast_ref::<Health>().map(|health| &health.0));
+    /// ```
+    pub fn get_reflect(
+        &self,
+        entity: Entity,
+        type_id: TypeId,
+    ) -> Result<&dyn Reflect, ReflectComponentAccessError> {
+        let reflect_component = self.reflect_component(type_id)?;
+        let entity_ref = self
+            .get_entity(entity)
+            .ok_or(ReflectComponentAccessError::NoSuchEntity(entity))?;
+        reflect_component.reflect(entity_ref).ok_or_else(|| {
+            ReflectComponentAccessError::MissingComponent {
+                entity,
+                type_path: self.component_type_path(type_id),
+            }
+        })
+    }
+
+    /// Returns mutable access to the component of `entity` with the [`TypeId`] `type_id`, as a
+    /// reflected value.
+    pub fn get_reflect_mut(
+        &mut self,
+        entity: Entity,
+        type_id: TypeId,
+    ) -> Result<Mut<'_, dyn Reflect>, ReflectComponentAccessError> {
+        let reflect_component = self.reflect_component(type_id)?;
+        let type_path = self.component_type_path(type_id);
+        let entity_cell = self
+            .as_unsafe_world_cell()
+            .get_entity(entity)
+            .ok_or(ReflectComponentAccessError::NoSuchEntity(entity))?;
+        // SAFETY: `&mut self` gives exclusive access to the world, and only this component is
+        // accessed.
+        unsafe { reflect_component.reflect_unchecked_mut(entity_cell) }
+            .ok_or(ReflectComponentAccessError::MissingComponent { entity, type_path })
+    }
+
+    /// Inserts the reflected `component` into `entity`, replacing its previous value.
+    ///
+    /// The component may be a dynamic value representing the type of the component, which is
+    /// converted with [`ReflectComponent::insert`].
+    pub fn insert_reflect(
+        &mut self,
+        entity: Entity,
+        component: Box<dyn Reflect>,
+    ) -> Result<(), ReflectComponentAccessError> {
+        let type_id = component
+            .get_represented_type_info()
+            .ok_or_else(|| {
+                ReflectComponentAccessError::NoRepresentedType(
+                    component.reflect_type_path().to_string(),
+                )
+            })?
+            .type_id();
+        let reflect_component = self.reflect_component(type_id)?;
+        let mut entity_mut = self
+            .get_entity_mut(entity)
+            .ok_or(ReflectComponentAccessError::NoSuchEntity(entity))?;
+        reflect_component.insert(&mut entity_mut, &*component);
+        Ok(())
+    }
+
+    /// Removes the component of `entity` with the [`TypeId`] `type_id`, and returns its value as
+    /// cloned by [`Reflect::clone_value`].
+    pub fn remove_reflect(
+        &mut self,
+        entity: Entity,
+        type_id: TypeId,
+    ) -> Result<Box<dyn Reflect>, ReflectComponentAccessError> {
+        let component = self.get_reflect(entity, type_id)?.clone_value();
+        let reflect_component = self.reflect_component(type_id)?;
+        reflect_component.remove(&mut self.entity_mut(entity));
+        Ok(component)
+    }
+
+    /// Returns the [`ReflectComponent`] of the type `type_id`.
+    fn reflect_component(
+        &self,
+        type_id: TypeId,
+    ) -> Result<ReflectComponent, ReflectComponentAccessError> {
+        let registry = self
+            .get_resource::<AppTypeRegistry>()
+            .ok_or(ReflectComponentAccessError::MissingAppTypeRegistry)?
+            .read();
+        registry
+            .get_type_data::<ReflectComponent>(type_id)
+            .cloned()
+            .ok_or(ReflectComponentAccessError::UnregisteredComponent(type_id))
+    }
+
+    /// Returns the type path of the registered type `type_id`.
+    fn component_type_path(&self, type_id: TypeId) -> &'static str {
+        self.resource::<AppTypeRegistry>()
+            .read()
+            .get(type_id)
+            .map_or("<unknown>", |registration| {
+                registration.type_info().type_path()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReflectComponentAccessError;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        prelude::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_reflect::{DynamicTupleStruct, FromReflect, Reflect};
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Unregistered;
+
+    #[test]
+    fn should_access_reflected_components() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Health>();
+        let entity = world.spawn_empty().id();
+
+        let mut health = DynamicTupleStruct::default();
+        health.insert(10_u32);
+        health.set_represented_type(Some(<Health as bevy_reflect::Typed>::type_info()));
+        world.insert_reflect(entity, Box::new(health)).unwrap();
+        assert_eq!(Some(&Health(10)), world.get::<Health>(entity));
+
+        world
+            .get_reflect_mut(entity, TypeId::of::<Health>())
+            .unwrap()
+            .apply(&Health(3));
+        assert_eq!(Some(&Health(3)), world.get::<Health>(entity));
+
+        let removed = world
+            .remove_reflect(entity, TypeId::of::<Health>())
+            .unwrap();
+        assert_eq!(Some(Health(3)), Health::from_reflect(&*removed));
+        assert_eq!(
+            Err(ReflectComponentAccessError::MissingComponent {
+                entity,
+                type_path: "bevy_ecs::reflect::world::tests::Health",
+            }),
+            world
+                .get_reflect(entity, TypeId::of::<Health>())
+                .map(|_| ())
+        );
+        assert_eq!(
+            Err(ReflectComponentAccessError::UnregisteredComponent(
+                TypeId::of::<Unregistered>()
+            )),
+            world
+                .get_reflect(entity, TypeId::of::<Unregistered>())
+                .map(|_| ())
+        );
+    }
+}
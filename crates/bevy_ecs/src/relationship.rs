@@ -0,0 +1,479 @@
+//! Relationships between entities, stored as components on both ends.
+//!
+//! A [`Relationship`] is a type naming a kind of link from a source entity to target entities,
+//! such as "targets" or "owned by". Relating two entities adds a [`Relations`] component to
+//! the source listing its targets, and a [`ReverseRelations`] component to the target listing
+//! its sources, which are kept in sync as relations are added and removed and as entities are
+//! despawned.
+//!
+//! Relations are queried like any other component: `Query<&Relations<Targets>>` iterates the
+//! targets of each source, while `With<Relations<Targets>>` and
+//! `With<ReverseRelations<Targets>>` filter for the sources and targets of the relationship.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::relationship::DespawnPolicy;
+//! /// The units a turret is aiming at.
+//! struct Targets;
+//!
+//! impl Relationship for Targets {}
+//!
+//! /// The ship an item is stored in, despawning the items along with the ship.
+//! struct StoredIn;
+//!
+//! impl Relationship for StoredIn {
+//!     const EXCLUSIVE: bool = true;
+//!     const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::DespawnSources;
+//! }
+//!
+//! fn aim(mut commands: Commands, turrets: Query<Entity, Without<Relations<Targets>>>) {
+//!     for turret in &turrets {
+//!         let unit = commands.spawn_empty().id();
+//!         commands.entity(turret).relate::<Targets>(unit);
+//!     }
+//! }
+//!
+//! fn threatened(units: Query<(Entity, &ReverseRelations<Targets>)>) {
+//!     for (unit, turrets) in &units {
+//!         println!("{unit:?} is targeted by {} turrets", turrets.len());
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(aim);
+//! # bevy_ecs::system::assert_is_system(threatened);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId},
+    entity::{Entity, EntityMapper, MapEntities},
+    system::EntityCommands,
+    world::{EntityWorldMut, World},
+};
+use std::{fmt, marker::PhantomData, ops::Deref};
+
+/// A kind of relation from source entities to target entities.
+///
+/// See the [module-level documentation](self) for an example.
+pub trait Relationship: Send + Sync + 'static {
+    /// Whether a source is related to at most one target, relating it to a new target removing
+    /// its previous relation.
+    const EXCLUSIVE: bool = false;
+    /// What happens to the related entities of a despawned entity.
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Detach;
+}
+
+/// What happens to the related entities of an entity when it is despawned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DespawnPolicy {
+    /// The related entities are kept and only the relations with the despawned entity are
+    /// removed.
+    Detach,
+    /// The sources of a despawned target are despawned as well.
+    DespawnSources,
+    /// The targets of a despawned source are despawned as well.
+    DespawnTargets,
+}
+
+/// The targets of an entity in the relationship `R`.
+///
+/// This component is added by [`EntityWorldMut::relate`] and removed when the entity has no
+/// targets left, it dereferences to the list of targets in the order they were added.
+#[derive(Component)]
+pub struct Relations<R: Relationship> {
+    entities: Vec<Entity>,
+    marker: PhantomData<R>,
+}
+
+/// The sources relating to an entity in the relationship `R`.
+///
+/// This component is added by [`EntityWorldMut::relate`] and removed when the entity has no
+/// sources left, it dereferences to the list of sources in the order they were added.
+#[derive(Component)]
+pub struct ReverseRelations<R: Relationship> {
+    entities: Vec<Entity>,
+    marker: PhantomData<R>,
+}
+
+macro_rules! impl_relation_list {
+    ($list:ident) => {
+        impl<R: Relationship> $list<R> {
+            fn new(entity: Entity) -> Self {
+                Self {
+                    entities: vec![entity],
+                    marker: PhantomData,
+                }
+            }
+        }
+
+        impl<R: Relationship> Deref for $list<R> {
+            type Target = [Entity];
+
+            fn deref(&self) -> &Self::Target {
+                &self.entities
+            }
+        }
+
+        impl<R: Relationship> fmt::Debug for $list<R> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($list))
+                    .field(&self.entities)
+                    .finish()
+            }
+        }
+
+        impl<R: Relationship> MapEntities for $list<R> {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                for entity in &mut self.entities {
+                    *entity = entity_mapper.get_or_reserve(*entity);
+                }
+            }
+        }
+
+        impl<'a, R: Relationship> IntoIterator for &'a $list<R> {
+            type Item = &'a Entity;
+            type IntoIter = std::slice::Iter<'a, Entity>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.entities.iter()
+            }
+        }
+    };
+}
+
+impl_relation_list!(Relations);
+impl_relation_list!(ReverseRelations);
+
+/// The relationships used in a [`World`], to clean up the relations of despawned entities.
+#[derive(Default, Debug)]
+pub(crate) struct Relationships {
+    kinds: Vec<RelationshipKind>,
+}
+
+#[derive(Debug)]
+struct RelationshipKind {
+    relations: ComponentId,
+    reverse_relations: ComponentId,
+    /// Removes the relations of an entity about to be despawned, adding the related entities
+    /// to despawn with it to the list.
+    detach: fn(&mut World, Entity, &mut Vec<Entity>),
+}
+
+fn register<R: Relationship>(world: &mut World) {
+    let relations = world.init_component::<Relations<R>>();
+    let reverse_relations = world.init_component::<ReverseRelations<R>>();
+    let kinds = &mut world.relationships.kinds;
+    if !kinds.iter().any(|kind| kind.relations == relations) {
+        kinds.push(RelationshipKind {
+            relations,
+            reverse_relations,
+            detach: detach::<R>,
+        });
+    }
+}
+
+fn relate<R: Relationship>(world: &mut World, source: Entity, target: Entity) {
+    register::<R>(world);
+    if let Some(relations) = world.get::<Relations<R>>(source) {
+        if relations.contains(&target) {
+            return;
+        }
+        if R::EXCLUSIVE {
+            for previous in relations.entities.clone() {
+                unrelate::<R>(world, source, previous);
+            }
+        }
+    }
+
+    let mut target_mut = world.entity_mut(target);
+    match target_mut.get_mut::<ReverseRelations<R>>() {
+        Some(mut reverse_relations) => reverse_relations.entities.push(source),
+        None => {
+            target_mut.insert(ReverseRelations::<R>::new(source));
+        }
+    }
+    let mut source_mut = world.entity_mut(source);
+    match source_mut.get_mut::<Relations<R>>() {
+        Some(mut relations) => relations.entities.push(target),
+        None => {
+            source_mut.insert(Relations::<R>::new(target));
+        }
+    }
+}
+
+fn unrelate<R: Relationship>(world: &mut World, source: Entity, target: Entity) {
+    remove_relation::<Relations<R>>(world, source, target, |relations| &mut relations.entities);
+    remove_relation::<ReverseRelations<R>>(world, target, source, |reverse_relations| {
+        &mut reverse_relations.entities
+    });
+}
+
+/// Removes `related` from the list `T` of `entity`, and the list itself if it becomes empty.
+fn remove_relation<T: Component>(
+    world: &mut World,
+    entity: Entity,
+    related: Entity,
+    entities: fn(&mut T) -> &mut Vec<Entity>,
+) {
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+    let Some(mut list) = entity.get_mut::<T>() else {
+        return;
+    };
+    let list = entities(&mut list);
+    list.retain(|&entity| entity != related);
+    if list.is_empty() {
+        entity.remove::<T>();
+    }
+}
+
+fn detach<R: Relationship>(world: &mut World, entity: Entity, despawn: &mut Vec<Entity>) {
+    let mut entity_mut = world.entity_mut(entity);
+    let relations = entity_mut.take::<Relations<R>>();
+    let reverse_relations = entity_mut.take::<ReverseRelations<R>>();
+    if let Some(relations) = relations {
+        for &target in &relations {
+            remove_relation::<ReverseRelations<R>>(world, target, entity, |reverse_relations| {
+                &mut reverse_relations.entities
+            });
+        }
+        if R::DESPAWN_POLICY == DespawnPolicy::DespawnTargets {
+            despawn.extend(relations.entities);
+        }
+    }
+    if let Some(reverse_relations) = reverse_relations {
+        for &source in &reverse_relations {
+            remove_relation::<Relations<R>>(world, source, entity, |relations| {
+                &mut relations.entities
+            });
+        }
+        if R::DESPAWN_POLICY == DespawnPolicy::DespawnSources {
+            despawn.extend(reverse_relations.entities);
+        }
+    }
+}
+
+/// Removes the relations of an entity about to be despawned and despawns the related entities
+/// according to the [`DespawnPolicy`] of each relationship.
+pub(crate) fn despawn_relations(world: &mut World, entity: Entity) {
+    if world.relationships.kinds.is_empty() {
+        return;
+    }
+    let Some(location) = world.entities.get(entity) else {
+        return;
+    };
+    let archetype = &world.archetypes[location.archetype_id];
+    let detaches: Vec<_> = world
+        .relationships
+        .kinds
+        .iter()
+        .filter(|kind| {
+            archetype.contains(kind.relations) || archetype.contains(kind.reverse_relations)
+        })
+        .map(|kind| kind.detach)
+        .collect();
+
+    let mut despawn = Vec::new();
+    for detach in detaches {
+        detach(world, entity, &mut despawn);
+    }
+    for related in despawn {
+        if related != entity {
+            if let Some(related) = world.get_entity_mut(related) {
+                related.despawn();
+            }
+        }
+    }
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Relates this entity to `target` in the relationship `R`.
+    ///
+    /// If `R` is [exclusive](Relationship::EXCLUSIVE), the previous relation of this entity is
+    /// removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` does not exist.
+    pub fn relate<R: Relationship>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| relate::<R>(world, source, target));
+        self
+    }
+
+    /// Removes the relation from this entity to `target` in the relationship `R`, if any.
+    pub fn unrelate<R: Relationship>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| unrelate::<R>(world, source, target));
+        self
+    }
+
+    /// Removes every relation from this entity in the relationship `R`.
+    pub fn clear_relations<R: Relationship>(&mut self) -> &mut Self {
+        let Some(relations) = self.get::<Relations<R>>() else {
+            return self;
+        };
+        let source = self.id();
+        let targets = relations.entities.clone();
+        self.world_scope(|world| {
+            for target in targets {
+                unrelate::<R>(world, source, target);
+            }
+        });
+        self
+    }
+}
+
+impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
+    /// Relates this entity to `target` in the relationship `R`.
+    ///
+    /// See [`EntityWorldMut::relate`].
+    pub fn relate<R: Relationship>(&mut self, target: Entity) -> &mut Self {
+        self.add(move |mut entity: EntityWorldMut| {
+            entity.relate::<R>(target);
+        })
+    }
+
+    /// Removes the relation from this entity to `target` in the relationship `R`, if any.
+    ///
+    /// See [`EntityWorldMut::unrelate`].
+    pub fn unrelate<R: Relationship>(&mut self, target: Entity) -> &mut Self {
+        self.add(move |mut entity: EntityWorldMut| {
+            entity.unrelate::<R>(target);
+        })
+    }
+
+    /// Removes every relation from this entity in the relationship `R`.
+    ///
+    /// See [`EntityWorldMut::clear_relations`].
+    pub fn clear_relations<R: Relationship>(&mut self) -> &mut Self {
+        self.add(|mut entity: EntityWorldMut| {
+            entity.clear_relations::<R>();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        query::With,
+        system::{CommandQueue, Commands},
+    };
+
+    struct Targets;
+
+    impl Relationship for Targets {}
+
+    struct OwnedBy;
+
+    impl Relationship for OwnedBy {
+        const EXCLUSIVE: bool = true;
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::DespawnSources;
+    }
+
+    fn targets(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Relations<Targets>>(entity)
+            .map_or(Vec::new(), |relations| relations.to_vec())
+    }
+
+    fn sources(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<ReverseRelations<Targets>>(entity)
+            .map_or(Vec::new(), |reverse_relations| reverse_relations.to_vec())
+    }
+
+    #[test]
+    fn relate_and_unrelate() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+
+        world
+            .entity_mut(a)
+            .relate::<Targets>(b)
+            .relate::<Targets>(c)
+            .relate::<Targets>(b);
+        world.entity_mut(b).relate::<Targets>(c);
+        assert_eq!(targets(&world, a), [b, c]);
+        assert_eq!(sources(&world, c), [a, b]);
+
+        world.entity_mut(a).unrelate::<Targets>(c);
+        assert_eq!(targets(&world, a), [b]);
+        assert_eq!(sources(&world, c), [b]);
+
+        world.entity_mut(b).clear_relations::<Targets>();
+        assert!(!world.entity(b).contains::<Relations<Targets>>());
+        assert!(!world.entity(c).contains::<ReverseRelations<Targets>>());
+
+        let mut query = world.query_filtered::<Entity, With<Relations<Targets>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [a]);
+    }
+
+    #[test]
+    fn exclusive() {
+        let mut world = World::new();
+        let [item, ship, other_ship] = [(); 3].map(|_| world.spawn_empty().id());
+
+        world.entity_mut(item).relate::<OwnedBy>(ship);
+        world.entity_mut(item).relate::<OwnedBy>(other_ship);
+        assert_eq!(
+            **world.get::<Relations<OwnedBy>>(item).unwrap(),
+            [other_ship]
+        );
+        assert!(!world.entity(ship).contains::<ReverseRelations<OwnedBy>>());
+    }
+
+    #[test]
+    fn despawn_detaches() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        world
+            .entity_mut(a)
+            .relate::<Targets>(b)
+            .relate::<Targets>(c);
+        world.entity_mut(c).relate::<Targets>(a);
+
+        world.despawn(a);
+        assert!(world.get_entity(b).is_some());
+        assert!(world.get_entity(c).is_some());
+        assert!(!world.entity(b).contains::<ReverseRelations<Targets>>());
+        assert!(!world.entity(c).contains::<Relations<Targets>>());
+    }
+
+    #[test]
+    fn despawn_cascades() {
+        let mut world = World::new();
+        let [ship, item, nested, unrelated] = [(); 4].map(|_| world.spawn_empty().id());
+        world.entity_mut(item).relate::<OwnedBy>(ship);
+        world.entity_mut(nested).relate::<OwnedBy>(item);
+        // Cycles don't despawn an entity twice.
+        world.entity_mut(ship).relate::<OwnedBy>(nested);
+        world.entity_mut(unrelated).relate::<Targets>(nested);
+
+        world.despawn(ship);
+        assert!(world.get_entity(item).is_none());
+        assert!(world.get_entity(nested).is_none());
+        assert!(!world.entity(unrelated).contains::<Relations<Targets>>());
+    }
+
+    #[test]
+    fn commands() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let (a, b) = {
+            let mut commands = Commands::new(&mut queue, &world);
+            let b = commands.spawn_empty().id();
+            let a = commands.spawn_empty().relate::<Targets>(b).id();
+            (a, b)
+        };
+        queue.apply(&mut world);
+        assert_eq!(targets(&world, a), [b]);
+        assert_eq!(sources(&world, b), [a]);
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(b).despawn();
+        queue.apply(&mut world);
+        assert!(!world.entity(a).contains::<Relations<Targets>>());
+    }
+}
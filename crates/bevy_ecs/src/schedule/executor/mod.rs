@@ -6,6 +6,7 @@ pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
 
+use bevy_utils::Duration;
 use fixedbitset::FixedBitSet;
 
 use crate::{
@@ -18,7 +19,14 @@ use crate::{
 pub(super) trait SystemExecutor: Send + Sync {
     fn kind(&self) -> ExecutorKind;
     fn init(&mut self, schedule: &SystemSchedule);
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World);
+    /// Runs the schedule, skipping the systems in `skip_systems` as if their conditions were
+    /// not met.
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    );
     fn set_apply_final_deferred(&mut self, value: bool);
 }
 
@@ -68,6 +76,9 @@ pub struct SystemSchedule {
     pub(super) set_conditions: Vec<Vec<BoxedCondition>>,
     /// Indexed by system set node id.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// Indexed by system node id, how long each system took to run, or `None` if it was
+    /// skipped. Left empty when the run times are not recorded.
+    pub(super) system_run_times: Vec<Option<Duration>>,
 }
 
 impl SystemSchedule {
@@ -83,6 +94,7 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            system_run_times: Vec::new(),
         }
     }
 }
@@ -4,10 +4,10 @@ use std::{
 };
 
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
-use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Instrument, Span};
+use bevy_utils::{default, Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use async_channel::{Receiver, Sender};
@@ -71,6 +71,8 @@ struct SystemTaskMetadata {
 struct SystemResult {
     system_index: usize,
     success: bool,
+    /// How long the system took to run, if the run times are recorded.
+    run_time: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -115,6 +117,10 @@ pub struct MultiThreadedExecutor {
     panic_payload: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
     /// When set, stops the executor from running any more systems.
     stop_spawning: bool,
+    /// Is `true` if the run times of the systems are recorded.
+    record_run_times: bool,
+    /// How long each system took to run, or `None` if it was skipped.
+    system_run_times: Vec<Option<Duration>>,
 }
 
 impl Default for MultiThreadedExecutor {
@@ -163,12 +169,25 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
         // reset counts
         self.num_systems = schedule.systems.len();
         if self.num_systems == 0 {
             return;
         }
+        if let Some(skip_systems) = skip_systems {
+            self.skipped_systems.union_with(skip_systems);
+        }
+        self.record_run_times = !schedule.system_run_times.is_empty();
+        if self.record_run_times {
+            self.system_run_times.clear();
+            self.system_run_times.resize(self.num_systems, None);
+        }
         self.num_running_systems = 0;
         self.num_completed_systems = 0;
         self.num_dependencies_remaining.clear();
@@ -244,6 +263,12 @@ impl SystemExecutor for MultiThreadedExecutor {
             debug_assert!(self.unapplied_systems.is_clear());
         }
 
+        if self.record_run_times {
+            schedule
+                .system_run_times
+                .copy_from_slice(&self.system_run_times);
+        }
+
         // check to see if there was a panic
         let mut payload = self.panic_payload.lock().unwrap();
         if let Some(payload) = payload.take() {
@@ -290,6 +315,8 @@ impl MultiThreadedExecutor {
             apply_final_deferred: true,
             panic_payload: Arc::new(Mutex::new(None)),
             stop_spawning: false,
+            record_run_times: false,
+            system_run_times: Vec::new(),
         }
     }
 
@@ -496,7 +523,9 @@ impl MultiThreadedExecutor {
         let system = unsafe { &mut *systems[system_index].get() };
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let record_run_times = self.record_run_times;
         let task = async move {
+            let start = record_run_times.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -509,6 +538,7 @@ impl MultiThreadedExecutor {
                 .try_send(SystemResult {
                     system_index,
                     success: res.is_ok(),
+                    run_time: start.map(|start| start.elapsed()),
                 })
                 .unwrap_or_else(|error| unreachable!("{}", error));
             if let Err(payload) = res {
@@ -554,17 +584,20 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let record_run_times = self.record_run_times;
         if is_apply_deferred(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
+                let start = record_run_times.then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, systems, world);
                 // tell the executor that the system finished
                 sender
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        run_time: start.map(|start| start.elapsed()),
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -583,6 +616,7 @@ impl MultiThreadedExecutor {
             scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let start = record_run_times.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
@@ -591,6 +625,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        run_time: start.map(|start| start.elapsed()),
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -621,8 +656,13 @@ impl MultiThreadedExecutor {
         let SystemResult {
             system_index,
             success,
+            run_time,
         } = result;
 
+        if run_time.is_some() {
+            self.system_run_times[system_index] = run_time;
+        }
+
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
         }
@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -30,7 +31,16 @@ impl SystemExecutor for SimpleExecutor {
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
+        if let Some(skip_systems) = skip_systems {
+            self.completed_systems.union_with(skip_systems);
+        }
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -72,6 +82,7 @@ impl SystemExecutor for SimpleExecutor {
                 continue;
             }
 
+            let start = (!schedule.system_run_times.is_empty()).then(Instant::now);
             let system = &mut schedule.systems[system_index];
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 system.run((), world);
@@ -82,6 +93,9 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             system.apply_deferred(world);
+            if let Some(start) = start {
+                schedule.system_run_times[system_index] = Some(start.elapsed());
+            }
         }
 
         self.evaluated_sets.clear();
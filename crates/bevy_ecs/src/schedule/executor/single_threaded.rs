@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -38,7 +39,16 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
+        if let Some(skip_systems) = skip_systems {
+            self.completed_systems.union_with(skip_systems);
+        }
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -80,6 +90,7 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            let start = (!schedule.system_run_times.is_empty()).then(Instant::now);
             let system = &mut schedule.systems[system_index];
             if is_apply_deferred(system) {
                 self.apply_deferred(schedule, world);
@@ -93,6 +104,9 @@ impl SystemExecutor for SingleThreadedExecutor {
                 }
                 self.unapplied_systems.insert(system_index);
             }
+            if let Some(start) = start {
+                schedule.system_run_times[system_index] = Some(start.elapsed());
+            }
         }
 
         if self.apply_final_deferred {
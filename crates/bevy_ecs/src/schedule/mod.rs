@@ -4,18 +4,22 @@ mod condition;
 mod config;
 mod executor;
 mod graph_utils;
+mod run_times;
 #[allow(clippy::module_inception)]
 mod schedule;
 mod set;
 mod state;
+mod stepping;
 
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
 use self::graph_utils::*;
+pub use self::run_times::*;
 pub use self::schedule::*;
 pub use self::set::*;
 pub use self::state::*;
+pub use self::stepping::*;
 
 pub use self::graph_utils::NodeId;
 
@@ -241,6 +245,113 @@ mod tests {
             );
             assert!(order.len() == 11, "must have exactly 11 order entries");
         }
+
+        #[test]
+        fn deterministic_order() {
+            use crate::{entity::Entity, system::Commands};
+            use std::sync::Mutex;
+
+            #[derive(Resource, Default)]
+            struct Spawned(Mutex<Vec<(u32, Entity)>>);
+
+            fn make_spawning_system(tag: u32) -> impl FnMut(Commands, Res<Spawned>) {
+                move |mut commands: Commands, spawned: Res<Spawned>| {
+                    let entity = commands.spawn_empty().id();
+                    spawned.0.lock().unwrap().push((tag, entity));
+                }
+            }
+
+            for _ in 0..10 {
+                let mut world = World::new();
+                let mut schedule = Schedule::default();
+                schedule.set_build_settings(ScheduleBuildSettings {
+                    deterministic: true,
+                    ..Default::default()
+                });
+
+                world.init_resource::<SystemOrder>();
+                world.init_resource::<Spawned>();
+
+                schedule.add_systems((
+                    make_function_system(0),
+                    make_function_system(1).ambiguous_with(TestSet::A),
+                    make_function_system(2).in_set(TestSet::A),
+                    make_exclusive_system(3),
+                    make_function_system(4),
+                    make_spawning_system(0),
+                    make_spawning_system(1),
+                ));
+                schedule.run(&mut world);
+
+                assert_eq!(world.resource::<SystemOrder>().0, vec![0, 1, 2, 3, 4]);
+                let spawned = world.resource::<Spawned>().0.lock().unwrap();
+                assert_eq!(spawned[0].0, 0);
+                assert!(spawned[0].1.index() < spawned[1].1.index());
+            }
+        }
+
+        #[test]
+        fn deterministic_worlds() {
+            use crate::{component::Component, entity::Entity, system::Commands, system::Query};
+
+            #[derive(Component)]
+            struct Value(u32);
+
+            #[derive(Resource, Default)]
+            struct Iterated(Vec<(Entity, u32)>);
+
+            fn make_spawning_system(first: u32) -> impl FnMut(Commands) {
+                move |mut commands: Commands| {
+                    for value in first..first + 4 {
+                        commands.spawn(Value(value));
+                    }
+                }
+            }
+
+            fn despawning_system(mut commands: Commands, query: Query<(Entity, &Value)>) {
+                for (entity, value) in &query {
+                    if value.0 % 3 == 0 {
+                        commands.entity(entity).despawn();
+                    }
+                }
+            }
+
+            fn iterating_system(query: Query<(Entity, &Value)>, mut iterated: ResMut<Iterated>) {
+                iterated
+                    .0
+                    .extend(query.iter().map(|(entity, value)| (entity, value.0)));
+            }
+
+            // Spawns and despawns entities from systems the executor could run in any order,
+            // then returns the entities alive and the order the queries iterated them in.
+            fn run_world() -> (Vec<Entity>, Vec<(Entity, u32)>) {
+                let mut world = World::new();
+                let mut schedule = Schedule::default();
+                schedule.set_build_settings(ScheduleBuildSettings {
+                    deterministic: true,
+                    ..Default::default()
+                });
+                world.init_resource::<Iterated>();
+                schedule.add_systems((
+                    make_spawning_system(0),
+                    make_spawning_system(10),
+                    despawning_system,
+                    make_spawning_system(20),
+                    iterating_system,
+                ));
+                for _ in 0..5 {
+                    schedule.run(&mut world);
+                }
+                let entities = world.query::<Entity>().iter(&world).collect();
+                (entities, world.remove_resource::<Iterated>().unwrap().0)
+            }
+
+            let (entities, iterated) = run_world();
+            assert!(!iterated.is_empty());
+            for _ in 0..10 {
+                assert_eq!(run_world(), (entities.clone(), iterated.clone()));
+            }
+        }
     }
 
     mod conditions {
@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use bevy_utils::Duration;
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{InternedScheduleLabel, Schedule},
+    system::Resource,
+};
+
+/// A [`Resource`] recording how long each system takes to run.
+///
+/// Recording is opt-in: the schedules only time their systems while this resource is in the
+/// world. Each run of a schedule appends the run times of the systems that were not skipped,
+/// which accumulate until they are [drained](Self::drain), usually once per frame by the
+/// diagnostics reporting them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::SystemRunTimes;
+/// fn slow_system() {
+///     std::thread::sleep(std::time::Duration::from_millis(1));
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<SystemRunTimes>();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(slow_system);
+/// schedule.run(&mut world);
+///
+/// let mut run_times = world.resource_mut::<SystemRunTimes>();
+/// let run_time = run_times.drain().next().unwrap();
+/// assert!(run_time.name.ends_with("slow_system"));
+/// assert!(run_time.duration.as_millis() >= 1);
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct SystemRunTimes {
+    run_times: Vec<SystemRunTime>,
+}
+
+/// How long a system took to run, recorded in [`SystemRunTimes`].
+#[derive(Clone, Debug)]
+pub struct SystemRunTime {
+    /// The schedule the system ran in.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// How long the system took to run.
+    pub duration: Duration,
+}
+
+impl SystemRunTimes {
+    /// Returns the run times recorded since they were last drained, in the order the
+    /// schedules ran.
+    pub fn iter(&self) -> impl Iterator<Item = &SystemRunTime> {
+        self.run_times.iter()
+    }
+
+    /// Removes the run times recorded so far and returns them, in the order the schedules ran.
+    pub fn drain(&mut self) -> impl Iterator<Item = SystemRunTime> + '_ {
+        self.run_times.drain(..)
+    }
+
+    /// Removes the run times recorded so far.
+    pub fn clear(&mut self) {
+        self.run_times.clear();
+    }
+
+    /// Returns the number of run times recorded since they were last drained.
+    pub fn len(&self) -> usize {
+        self.run_times.len()
+    }
+
+    /// Returns `true` if no run times were recorded since they were last drained.
+    pub fn is_empty(&self) -> bool {
+        self.run_times.is_empty()
+    }
+
+    /// Appends the run times the schedule recorded during its last run.
+    pub(super) fn record(&mut self, schedule: &Schedule) {
+        let executable = &schedule.executable;
+        for (system, run_time) in executable.systems.iter().zip(&executable.system_run_times) {
+            if let Some(duration) = *run_time {
+                self.run_times.push(SystemRunTime {
+                    schedule: schedule.label(),
+                    name: system.name(),
+                    duration,
+                });
+            }
+        }
+    }
+}
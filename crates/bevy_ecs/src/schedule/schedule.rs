@@ -1,5 +1,6 @@
 use std::{
-    collections::BTreeSet,
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap},
     fmt::{Debug, Write},
     result::Result,
 };
@@ -29,6 +30,8 @@ use crate::{
 #[derive(Default, Resource)]
 pub struct Schedules {
     inner: HashMap<InternedScheduleLabel, Schedule>,
+    /// Systems added to schedules which aren't in the map, added to them once they are inserted.
+    pending: HashMap<InternedScheduleLabel, Vec<SystemConfigs>>,
     /// List of [`ComponentId`]s to ignore when reporting system order ambiguity conflicts
     pub ignored_scheduling_ambiguities: BTreeSet<ComponentId>,
 }
@@ -38,6 +41,7 @@ impl Schedules {
     pub fn new() -> Self {
         Self {
             inner: HashMap::new(),
+            pending: HashMap::new(),
             ignored_scheduling_ambiguities: BTreeSet::new(),
         }
     }
@@ -46,10 +50,36 @@ impl Schedules {
     ///
     /// If the map already had an entry for `label`, `schedule` is inserted,
     /// and the old schedule is returned. Otherwise, `None` is returned.
-    pub fn insert(&mut self, schedule: Schedule) -> Option<Schedule> {
+    pub fn insert(&mut self, mut schedule: Schedule) -> Option<Schedule> {
+        for systems in self.pending.remove(&schedule.name).into_iter().flatten() {
+            schedule.add_systems(systems);
+        }
         self.inner.insert(schedule.name, schedule)
     }
 
+    /// Adds systems to the schedule associated with `label`.
+    ///
+    /// If the map has no schedule for `label`, for example because the schedule is running,
+    /// the systems are added to it once it is [inserted](Self::insert).
+    pub fn add_systems<M>(
+        &mut self,
+        label: impl ScheduleLabel,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let label = label.intern();
+        match self.inner.get_mut(&label) {
+            Some(schedule) => {
+                schedule.add_systems(systems);
+            }
+            None => self
+                .pending
+                .entry(label)
+                .or_default()
+                .push(systems.into_configs()),
+        }
+        self
+    }
+
     /// Removes the schedule corresponding to the `label` from the map, returning it if it existed.
     pub fn remove(&mut self, label: impl ScheduleLabel) -> Option<Schedule> {
         self.inner.remove(&label.intern())
@@ -208,7 +238,7 @@ pub enum Chain {
 pub struct Schedule {
     name: InternedScheduleLabel,
     graph: ScheduleGraph,
-    executable: SystemSchedule,
+    pub(super) executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
 }
@@ -238,6 +268,11 @@ impl Schedule {
         }
     }
 
+    /// Returns the label of the schedule.
+    pub fn label(&self) -> InternedScheduleLabel {
+        self.name
+    }
+
     /// Add a collection of systems to the schedule.
     pub fn add_systems<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> &mut Self {
         self.graph.process_configs(systems.into_configs(), false);
@@ -293,7 +328,22 @@ impl Schedule {
         world.check_change_ticks();
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.name));
-        self.executor.run(&mut self.executable, world);
+        let skip_systems = world
+            .get_resource_mut::<Stepping>()
+            .and_then(|mut stepping| stepping.skipped_systems(self));
+        let record_run_times = world.contains_resource::<SystemRunTimes>();
+        self.executable.system_run_times.clear();
+        if record_run_times {
+            let system_count = self.executable.systems.len();
+            self.executable.system_run_times.resize(system_count, None);
+        }
+        self.executor
+            .run(&mut self.executable, skip_systems.as_ref(), world);
+        if record_run_times {
+            if let Some(mut run_times) = world.get_resource_mut::<SystemRunTimes>() {
+                run_times.record(self);
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -325,6 +375,33 @@ impl Schedule {
         Ok(())
     }
 
+    /// Returns the systems of the executable schedule which are in the set, directly or through
+    /// its subsets, indexed like the systems of the executable schedule.
+    pub(super) fn systems_in_set(&self, set: InternedSystemSet) -> FixedBitSet {
+        let mut systems = FixedBitSet::with_capacity(self.executable.system_ids.len());
+        let Some(&set_id) = self.graph.system_set_ids.get(&set) else {
+            return systems;
+        };
+        let hierarchy = self.graph.hierarchy.graph();
+        let mut in_set = HashSet::new();
+        let mut stack = vec![set_id];
+        while let Some(id) = stack.pop() {
+            for child in hierarchy.neighbors_directed(id, Outgoing) {
+                if child.is_system() {
+                    in_set.insert(child);
+                } else {
+                    stack.push(child);
+                }
+            }
+        }
+        for (index, id) in self.executable.system_ids.iter().enumerate() {
+            if in_set.contains(id) {
+                systems.insert(index);
+            }
+        }
+        systems
+    }
+
     /// Returns the [`ScheduleGraph`].
     pub fn graph(&self) -> &ScheduleGraph {
         &self.graph
@@ -1017,6 +1094,14 @@ impl ScheduleGraph {
         self.optionally_check_conflicts(&conflicting_systems, components, schedule_label)?;
         self.conflicting_systems = conflicting_systems;
 
+        // order the systems whose relative order would otherwise be decided at runtime
+        if self.settings.deterministic {
+            self.order_nondeterministic_systems(
+                &mut dependency_flattened_dag,
+                &flat_results.disconnected,
+            );
+        }
+
         // build the schedule
         Ok(self.build_schedule_inner(dependency_flattened_dag, hier_results.reachable))
     }
@@ -1259,6 +1344,74 @@ impl ScheduleGraph {
         conflicting_systems
     }
 
+    /// Sorts the systems in the order they were added in, as far as their dependencies allow,
+    /// and adds an edge between each pair of unordered systems whose relative order can change
+    /// the results, so they always run in that order.
+    ///
+    /// Unlike [`get_conflicting_systems`](Self::get_conflicting_systems), this ignores
+    /// `ambiguous_with` and the ignored ambiguities, and also orders the systems which both
+    /// have deferred parameters, as they reserve entities and queue their commands in the
+    /// order they run.
+    fn order_nondeterministic_systems(
+        &self,
+        dependency_flattened_dag: &mut Dag,
+        flat_results_disconnected: &[(NodeId, NodeId)],
+    ) {
+        let graph = &dependency_flattened_dag.graph;
+
+        // Kahn's algorithm, always picking the earliest added system among the ready ones
+        let mut in_degrees = graph
+            .nodes()
+            .map(|node| (node, graph.neighbors_directed(node, Incoming).count()))
+            .collect::<HashMap<_, _>>();
+        let mut ready = in_degrees
+            .iter()
+            .filter(|(_, &in_degree)| in_degree == 0)
+            .map(|(&node, _)| Reverse(node.index()))
+            .collect::<BinaryHeap<_>>();
+        let mut topsort = Vec::with_capacity(in_degrees.len());
+        while let Some(Reverse(index)) = ready.pop() {
+            let node = NodeId::System(index);
+            topsort.push(node);
+            for target in graph.neighbors_directed(node, Outgoing) {
+                let in_degree = in_degrees.get_mut(&target).unwrap();
+                *in_degree -= 1;
+                if *in_degree == 0 {
+                    ready.push(Reverse(target.index()));
+                }
+            }
+        }
+
+        let topsort_index = topsort
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect::<HashMap<_, _>>();
+
+        for &(a, b) in flat_results_disconnected {
+            let system_a = self.systems[a.index()].get().unwrap();
+            let system_b = self.systems[b.index()].get().unwrap();
+            let nondeterministic = system_a.is_exclusive()
+                || system_b.is_exclusive()
+                || (!system_a.is_send() && !system_b.is_send())
+                || (system_a.has_deferred() && system_b.has_deferred())
+                || !system_a
+                    .component_access()
+                    .is_compatible(system_b.component_access());
+
+            if nondeterministic {
+                let (before, after) = if topsort_index[&a] < topsort_index[&b] {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                dependency_flattened_dag.graph.add_edge(before, after, ());
+            }
+        }
+
+        dependency_flattened_dag.topsort = topsort;
+    }
+
     fn build_schedule_inner(
         &self,
         dependency_flattened_dag: Dag,
@@ -1356,6 +1509,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            system_run_times: Vec::new(),
         }
     }
 
@@ -1886,6 +2040,27 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// If set to true, the systems whose relative order isn't specified but can change the
+    /// results always run in the same order: the order they were added to the schedule in,
+    /// unless their dependencies require otherwise.
+    ///
+    /// This includes the systems with conflicting data access, even those marked as
+    /// `ambiguous_with` each other, and the systems which both have
+    /// [`Deferred`](crate::prelude::Deferred) parameters, so that entities are reserved and
+    /// commands are applied in the same order. The entity allocator doesn't need a seed: it
+    /// hands out the indices in an order which only depends on the previous allocations and
+    /// frees, and entities reserved concurrently by commands are reserved in the order of
+    /// their systems. Queries also iterate in a stable order, visiting their archetypes in
+    /// creation order and the entities of each table in row order. So running the same
+    /// schedule on the same world gives identical results across runs and machines, as
+    /// required by lockstep networking and replays.
+    ///
+    /// This limits the parallelism of the multi-threaded executor. Closures passed to
+    /// [`Query::par_iter`](crate::system::Query::par_iter) still run in an arbitrary order,
+    /// so they must not depend on the order of the entities.
+    ///
+    /// Defaults to `false`.
+    pub deterministic: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -1904,6 +2079,7 @@ impl ScheduleBuildSettings {
             auto_insert_apply_deferred: true,
             use_shortnames: true,
             report_sets: true,
+            deterministic: false,
         }
     }
 }
@@ -1,14 +1,17 @@
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 
 use crate as bevy_ecs;
 use crate::change_detection::DetectChangesMut;
+use crate::component::Component;
+use crate::entity::Entity;
 #[cfg(feature = "bevy_reflect")]
 use crate::reflect::ReflectResource;
-use crate::schedule::ScheduleLabel;
-use crate::system::Resource;
+use crate::schedule::{ScheduleLabel, SystemSet};
+use crate::system::{Commands, Local, Query, Res, Resource};
 use crate::world::World;
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::std_traits::ReflectDefault;
@@ -42,6 +45,90 @@ pub use bevy_ecs_macros::States;
 /// ```
 pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug + Default {}
 
+/// [`States`] computed from the value of another state, the source state.
+///
+/// A computed state can't be set through [`NextState`]: its [`State<S>`] resource is updated
+/// whenever its source state changes, and is removed while [`compute`](Self::compute) returns
+/// `None`. Its [`OnEnter`], [`OnExit`] and [`OnTransition`] schedules run like those of any
+/// other state, right after the transition of the source state.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     Level(u32),
+/// }
+///
+/// /// Exists whenever the game is in a level, whichever it is.
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// struct InLevel;
+///
+/// impl ComputedStates for InLevel {
+///     type SourceStates = GameState;
+///
+///     fn compute(source: &GameState) -> Option<Self> {
+///         matches!(source, GameState::Level(_)).then_some(InLevel)
+///     }
+/// }
+/// ```
+pub trait ComputedStates: States {
+    /// The state this state is computed from.
+    type SourceStates: States;
+
+    /// Computes the value of this state for the value of the source state, or `None` if this
+    /// state shouldn't exist.
+    fn compute(source: &Self::SourceStates) -> Option<Self>;
+}
+
+/// [`States`] which only exist while another state, the source state, has some values.
+///
+/// A sub-state refines its source state, like a `Paused` or `Running` state which only makes
+/// sense while the game is `InGame`. Its [`State<S>`] resource is inserted with its [`Default`]
+/// value when the source state enters a value for which [`should_exist`](Self::should_exist)
+/// returns `true`, and removed when it leaves them. While it exists, it transitions through
+/// [`NextState<S>`] like any other state. Transitions queued while it doesn't exist are
+/// discarded.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum InGame {
+///     #[default]
+///     Running,
+///     Paused,
+/// }
+///
+/// impl SubStates for InGame {
+///     type SourceStates = GameState;
+///
+///     fn should_exist(source: &GameState) -> bool {
+///         *source == GameState::InGame
+///     }
+/// }
+/// ```
+pub trait SubStates: States {
+    /// The state this state refines.
+    type SourceStates: States;
+
+    /// Returns `true` if this state should exist for the value of the source state.
+    fn should_exist(source: &Self::SourceStates) -> bool;
+}
+
 /// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`]
 /// enters this state.
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
@@ -162,3 +249,119 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
         }
     }
 }
+
+/// Updates the [`State<S>`] resource of a [`ComputedStates`] from its source state, inserting
+/// or removing it as needed, and runs the [`OnExit`], [`OnTransition`] and [`OnEnter`]
+/// schedules of the transition, if any.
+pub fn apply_computed_state_transition<S: ComputedStates>(world: &mut World) {
+    let entered = world
+        .get_resource::<State<S::SourceStates>>()
+        .and_then(|source| S::compute(source.get()));
+    let exited = world
+        .get_resource::<State<S>>()
+        .map(|state| state.0.clone());
+    set_state(world, exited, entered);
+}
+
+/// Inserts or removes the [`State<S>`] resource of a [`SubStates`] depending on its source
+/// state, or applies the transition queued in [`NextState<S>`] while it exists, and runs the
+/// [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules of the transition, if any.
+pub fn apply_sub_state_transition<S: SubStates>(world: &mut World) {
+    let should_exist = world
+        .get_resource::<State<S::SourceStates>>()
+        .is_some_and(|source| S::should_exist(source.get()));
+    let next = world
+        .get_resource_mut::<NextState<S>>()
+        .and_then(|mut next_state_resource| {
+            let next = next_state_resource.bypass_change_detection().0.take();
+            if next.is_some() {
+                next_state_resource.set_changed();
+            }
+            next
+        });
+    let exited = world
+        .get_resource::<State<S>>()
+        .map(|state| state.0.clone());
+    let entered = should_exist.then(|| next.or_else(|| exited.clone()).unwrap_or_default());
+    set_state(world, exited, entered);
+}
+
+/// Replaces the value of the [`State<S>`] resource, inserting or removing it as needed, and
+/// runs the schedules of the transition if the value changed.
+fn set_state<S: States>(world: &mut World, exited: Option<S>, entered: Option<S>) {
+    if exited == entered {
+        return;
+    }
+    match &entered {
+        Some(entered) => world.insert_resource(State(entered.clone())),
+        None => {
+            world.remove_resource::<State<S>>();
+        }
+    }
+    if let Some(exited) = exited.clone() {
+        world.try_run_schedule(OnExit(exited)).ok();
+    }
+    if let (Some(from), Some(to)) = (exited, entered.clone()) {
+        world.try_run_schedule(OnTransition { from, to }).ok();
+    }
+    if let Some(entered) = entered {
+        world.try_run_schedule(OnEnter(entered)).ok();
+    }
+}
+
+/// The [`SystemSet`] of the systems applying the transitions of the state `S`.
+///
+/// The transitions of [`ComputedStates`] and [`SubStates`] are applied after this set for
+/// their source state, so that they see its new value in the same frame.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct ApplyStateTransition<S: States>(PhantomData<fn() -> S>);
+
+/// A [`Component`] scoping its entity to a value of the state `S`: the entity is despawned when
+/// the state exits that value.
+///
+/// The entities are despawned by the [`clear_state_scoped_entities::<S>`] system, after the
+/// [`OnExit`] schedule of the state ran. Only the entity itself is despawned, so its children
+/// should be scoped to the same state.
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn spawn_player(mut commands: Commands) {
+///     commands.spawn((Player, StateScoped(GameState::InGame)));
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct StateScoped<S: States>(pub S);
+
+/// Despawns the entities scoped to the value the state `S` exited since this system last ran,
+/// with a [`StateScoped<S>`] component.
+///
+/// This system should run after the transitions of `S` were applied.
+pub fn clear_state_scoped_entities<S: States>(
+    mut previous: Local<Option<S>>,
+    state: Option<Res<State<S>>>,
+    query: Query<(Entity, &StateScoped<S>)>,
+    mut commands: Commands,
+) {
+    let current = state.map(|state| state.0.clone());
+    if *previous == current {
+        return;
+    }
+    if let Some(exited) = mem::replace(&mut *previous, current) {
+        for (entity, scope) in &query {
+            if scope.0 == exited {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
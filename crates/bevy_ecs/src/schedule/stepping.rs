@@ -0,0 +1,386 @@
+use std::borrow::Cow;
+
+use bevy_utils::tracing::info;
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    self as bevy_ecs,
+    component::ComponentId,
+    query::Access,
+    schedule::{
+        InternedScheduleLabel, InternedSystemSet, IntoSystemSet, Schedule, ScheduleLabel, SystemSet,
+    },
+    system::Resource,
+};
+
+/// A [`Resource`] to run the systems of some schedules one at a time, to debug them frame by
+/// frame.
+///
+/// Once [enabled](Self::enable), the systems of the schedules [added](Self::add_schedule) to it
+/// only run when asked to, in the order of the schedules and in the order each schedule runs its
+/// systems. Each call to [`step_system`](Self::step_system), [`step_set`](Self::step_set) or
+/// [`continue_frame`](Self::continue_frame) runs some of the systems the next time their
+/// schedule runs, and the others are skipped as if their conditions were not met. Systems that
+/// must keep running while stepping, like the debug overlay driving it, are added with
+/// [`always_run`](Self::always_run).
+///
+/// The systems that ran for the last step, with their accesses, are listed by
+/// [`stepped_systems`](Self::stepped_systems).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, Stepping};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Update;
+///
+/// fn first() {}
+/// fn second() {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::new(Update);
+/// schedule.add_systems((first, second).chain());
+///
+/// let mut stepping = Stepping::new();
+/// stepping.add_schedule(Update).enable();
+/// world.insert_resource(stepping);
+///
+/// // Nothing runs until a step is requested.
+/// schedule.run(&mut world);
+///
+/// world.resource_mut::<Stepping>().step_system();
+/// schedule.run(&mut world);
+/// let stepping = world.resource::<Stepping>();
+/// assert!(stepping.stepped_systems()[0].name.ends_with("first"));
+/// ```
+#[derive(Resource, Default)]
+pub struct Stepping {
+    enabled: bool,
+    schedules: Vec<InternedScheduleLabel>,
+    always_run: Vec<InternedSystemSet>,
+    action: Action,
+    cursor: Cursor,
+    stepped_systems: Vec<SteppedSystem>,
+}
+
+/// What to run the next time the schedule at the cursor runs.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    /// Run nothing.
+    #[default]
+    Wait,
+    /// Run the system at the cursor.
+    StepSystem,
+    /// Run the systems up to the last system of the set.
+    StepSet(InternedSystemSet),
+    /// Run the systems up to the end of the frame.
+    Continue,
+}
+
+/// The next system to run, as an index into the stepped schedules and one into the systems of
+/// that schedule.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+struct Cursor {
+    schedule: usize,
+    system: usize,
+}
+
+/// A system that ran for the last step of a [`Stepping`].
+#[derive(Clone, Debug)]
+pub struct SteppedSystem {
+    /// The schedule the system is in.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// The components and resources the system accesses.
+    pub component_access: Access<ComponentId>,
+}
+
+impl Stepping {
+    /// Creates a disabled `Stepping` without any schedules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts stepping through the systems of the schedules, waiting for a step to run any of
+    /// them.
+    pub fn enable(&mut self) -> &mut Self {
+        if !self.enabled {
+            info!("System stepping enabled");
+            self.enabled = true;
+            self.action = Action::Wait;
+        }
+        self
+    }
+
+    /// Stops stepping, running all the systems of the schedules every time they run.
+    ///
+    /// The systems of a partially stepped frame are not run a second time, the schedules next
+    /// run all of their systems.
+    pub fn disable(&mut self) -> &mut Self {
+        if self.enabled {
+            info!("System stepping disabled");
+            self.enabled = false;
+            self.cursor = Cursor::default();
+        }
+        self
+    }
+
+    /// Returns `true` if the systems of the schedules only run when stepped through.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Adds a schedule to step through. The schedules are stepped through in the order they
+    /// are added, which should be the order they run in.
+    pub fn add_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        let schedule = schedule.intern();
+        if !self.schedules.contains(&schedule) {
+            self.schedules.push(schedule);
+        }
+        self
+    }
+
+    /// Removes a schedule to step through, running all of its systems again.
+    pub fn remove_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        let schedule = schedule.intern();
+        if let Some(index) = self.schedules.iter().position(|&other| other == schedule) {
+            self.schedules.remove(index);
+            if self.cursor.schedule == index {
+                self.cursor.system = 0;
+            }
+            if self.cursor.schedule >= index && self.cursor.schedule > 0 {
+                self.cursor.schedule -= 1;
+            }
+        }
+        self
+    }
+
+    /// Always runs the systems in the set, or the system, while stepping.
+    pub fn always_run<M>(&mut self, set: impl IntoSystemSet<M>) -> &mut Self {
+        let set = set.into_system_set().intern();
+        if !self.always_run.contains(&set) {
+            self.always_run.push(set);
+        }
+        self
+    }
+
+    /// Runs the next system the next time its schedule runs.
+    pub fn step_system(&mut self) -> &mut Self {
+        self.step(Action::StepSystem)
+    }
+
+    /// Runs the next systems up to the last system in the set, or up to the system, the next
+    /// time their schedule runs.
+    ///
+    /// The systems which are not in the set but run in between are run as well. If the
+    /// schedule doesn't have any system of the set left, the following schedules are searched
+    /// for one.
+    pub fn step_set<M>(&mut self, set: impl IntoSystemSet<M>) -> &mut Self {
+        self.step(Action::StepSet(set.into_system_set().intern()))
+    }
+
+    /// Runs the remaining systems of the frame.
+    pub fn continue_frame(&mut self) -> &mut Self {
+        self.step(Action::Continue)
+    }
+
+    fn step(&mut self, action: Action) -> &mut Self {
+        self.action = action;
+        self.stepped_systems.clear();
+        self
+    }
+
+    /// Returns the schedule of the next system to run, and the index of that system in the
+    /// order the schedule runs its systems, or `None` if stepping is disabled.
+    pub fn cursor(&self) -> Option<(InternedScheduleLabel, usize)> {
+        if !self.enabled {
+            return None;
+        }
+        let schedule = *self.schedules.get(self.cursor.schedule)?;
+        Some((schedule, self.cursor.system))
+    }
+
+    /// Returns `true` if a step was requested and its systems have not all run yet.
+    pub fn is_stepping(&self) -> bool {
+        self.action != Action::Wait
+    }
+
+    /// Returns the systems that ran for the last step, in the order they ran.
+    ///
+    /// The systems added with [`always_run`](Self::always_run) are not listed.
+    pub fn stepped_systems(&self) -> &[SteppedSystem] {
+        &self.stepped_systems
+    }
+
+    /// Returns the systems of the schedule to skip this time it runs, and moves the cursor past
+    /// the others.
+    pub(super) fn skipped_systems(&mut self, schedule: &Schedule) -> Option<FixedBitSet> {
+        if !self.enabled {
+            return None;
+        }
+        let index = self
+            .schedules
+            .iter()
+            .position(|&label| label == schedule.label())?;
+
+        let systems = &schedule.executable.systems;
+        let mut skipped = FixedBitSet::with_capacity(systems.len());
+        skipped.insert_range(..);
+        for &set in &self.always_run {
+            skipped.difference_with(&schedule.systems_in_set(set));
+        }
+        if index != self.cursor.schedule {
+            return Some(skipped);
+        }
+
+        // The step is done once it ran a system, unless it goes on through the next schedules.
+        let start = self.cursor.system.min(systems.len());
+        let (end, done) = match self.action {
+            Action::StepSystem if start < systems.len() => (start + 1, true),
+            Action::Wait | Action::StepSystem => (start, false),
+            Action::StepSet(set) => {
+                let last = schedule
+                    .systems_in_set(set)
+                    .ones()
+                    .filter(|&system| system >= start)
+                    .last();
+                last.map_or((systems.len(), false), |last| (last + 1, true))
+            }
+            Action::Continue => (systems.len(), false),
+        };
+        for (index, system) in systems.iter().enumerate().take(end).skip(start) {
+            if skipped.contains(index) {
+                self.stepped_systems.push(SteppedSystem {
+                    schedule: schedule.label(),
+                    name: system.name(),
+                    component_access: system.component_access().clone(),
+                });
+            }
+        }
+        skipped.set_range(start..end, false);
+        if done {
+            self.action = Action::Wait;
+        }
+
+        self.cursor.system = end;
+        if end == systems.len() {
+            self.cursor = Cursor {
+                schedule: self.cursor.schedule + 1,
+                system: 0,
+            };
+            if self.cursor.schedule == self.schedules.len() {
+                // The frame is over.
+                self.cursor.schedule = 0;
+                if self.action == Action::Continue {
+                    self.action = Action::Wait;
+                }
+            }
+        }
+
+        Some(skipped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schedule::{ExecutorKind, IntoSystemConfigs},
+        system::ResMut,
+        world::World,
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct First;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Second;
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Set;
+
+    #[derive(Resource, Default)]
+    struct Ran(Vec<u32>);
+
+    fn push<const N: u32>(mut ran: ResMut<Ran>) {
+        ran.0.push(N);
+    }
+
+    fn run_frame(world: &mut World, schedules: &mut [Schedule]) -> Vec<u32> {
+        for schedule in schedules.iter_mut() {
+            schedule.run(world);
+        }
+        std::mem::take(&mut world.resource_mut::<Ran>().0)
+    }
+
+    #[test]
+    fn stepping() {
+        let mut world = World::new();
+        world.init_resource::<Ran>();
+        let mut first = Schedule::new(First);
+        first.add_systems((push::<0>, push::<1>.in_set(Set), push::<2>).chain());
+        let mut second = Schedule::new(Second);
+        second.add_systems((push::<3>, push::<4>).chain());
+        let mut schedules = [first, second];
+        for schedule in &mut schedules {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        }
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(First).add_schedule(Second);
+        world.insert_resource(stepping);
+        assert_eq!(run_frame(&mut world, &mut schedules), [0, 1, 2, 3, 4]);
+
+        world.resource_mut::<Stepping>().enable();
+        assert_eq!(run_frame(&mut world, &mut schedules), []);
+
+        world.resource_mut::<Stepping>().step_system();
+        assert_eq!(run_frame(&mut world, &mut schedules), [0]);
+        assert_eq!(run_frame(&mut world, &mut schedules), []);
+
+        world.resource_mut::<Stepping>().step_set(Set);
+        assert_eq!(run_frame(&mut world, &mut schedules), [1]);
+        let stepping = world.resource::<Stepping>();
+        assert_eq!(stepping.cursor(), Some((First.intern(), 2)));
+        assert_eq!(stepping.stepped_systems().len(), 1);
+        assert!(stepping.stepped_systems()[0].name.ends_with("push<1>"));
+
+        world
+            .resource_mut::<Stepping>()
+            .step_system()
+            .always_run(push::<4>);
+        assert_eq!(run_frame(&mut world, &mut schedules), [2, 4]);
+        assert_eq!(
+            world.resource::<Stepping>().cursor(),
+            Some((Second.intern(), 0))
+        );
+
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(run_frame(&mut world, &mut schedules), [3, 4]);
+        assert!(!world.resource::<Stepping>().is_stepping());
+        assert_eq!(run_frame(&mut world, &mut schedules), [4]);
+
+        world.resource_mut::<Stepping>().disable();
+        assert_eq!(run_frame(&mut world, &mut schedules), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stepping_multi_threaded() {
+        let mut world = World::new();
+        world.init_resource::<Ran>();
+        let mut schedule = Schedule::new(First);
+        schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        schedule.add_systems((push::<0>, push::<1>, push::<2>).chain());
+        let mut schedules = [schedule];
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(First).enable();
+        world.insert_resource(stepping);
+        assert_eq!(run_frame(&mut world, &mut schedules), []);
+
+        world.resource_mut::<Stepping>().step_system();
+        assert_eq!(run_frame(&mut world, &mut schedules), [0]);
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(run_frame(&mut world, &mut schedules), [1, 2]);
+    }
+}
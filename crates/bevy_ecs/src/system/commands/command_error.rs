@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+use bevy_utils::{
+    thiserror::Error,
+    tracing::{error, warn},
+};
+
+use crate::{self as bevy_ecs, entity::Entity, system::Resource, world::World};
+
+/// An error that occurred while applying a [`Command`](super::Command).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The entity the command targets doesn't exist, usually because it was despawned by a
+    /// command applied before it.
+    #[error("error[B0003]: Could not {action} {entity:?} because it doesn't exist in this World.")]
+    NoSuchEntity {
+        /// What the command tried to do to the entity, like "despawn entity".
+        action: Cow<'static, str>,
+        /// The entity that doesn't exist.
+        entity: Entity,
+    },
+}
+
+impl CommandError {
+    /// Creates a [`CommandError::NoSuchEntity`] for the command trying to `action` the entity.
+    pub fn no_such_entity(action: impl Into<Cow<'static, str>>, entity: Entity) -> Self {
+        Self::NoSuchEntity {
+            action: action.into(),
+            entity,
+        }
+    }
+
+    /// Reports the error to the [`CommandErrorHandler`] of the world, or to the `default`
+    /// handler of the command if the world doesn't have one.
+    pub fn report(self, world: &mut World, default: CommandErrorHandler) {
+        let handler = world
+            .get_resource::<CommandErrorHandler>()
+            .copied()
+            .unwrap_or(default);
+        (handler.0)(world, self);
+    }
+}
+
+/// A [`Resource`] deciding what happens when a [`Command`](super::Command) fails.
+///
+/// Without this resource, each command keeps its own behavior: inserting components on an
+/// entity that doesn't exist panics, despawning it logs a warning, and removing components
+/// from it is ignored. With this resource, the errors of all the commands are handled the same
+/// way, for example [collected](Self::collect) to be reported in batches instead of panicking
+/// because a despawn raced an insert.
+///
+/// The `try_` commands of [`EntityCommands`](super::EntityCommands) never report errors.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::{CommandErrorHandler, CommandErrors}};
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.insert_resource(CommandErrorHandler::collect());
+/// let entity = world.spawn_empty().id();
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(move |mut commands: Commands| {
+///     commands.entity(entity).despawn();
+///     commands.entity(entity).insert(Health(10));
+/// });
+/// schedule.run(&mut world);
+///
+/// let mut errors = world.resource_mut::<CommandErrors>();
+/// assert_eq!(errors.drain().count(), 1);
+/// ```
+#[derive(Resource, Clone, Copy)]
+pub struct CommandErrorHandler(pub fn(&mut World, CommandError));
+
+impl CommandErrorHandler {
+    /// Panics with the error.
+    pub fn panic() -> Self {
+        Self(|_, error| panic!("{error}"))
+    }
+
+    /// Logs the error at the error level.
+    pub fn error() -> Self {
+        Self(|_, error| error!("{error}"))
+    }
+
+    /// Logs the error at the warn level.
+    pub fn warn() -> Self {
+        Self(|_, error| warn!("{error}"))
+    }
+
+    /// Ignores the error.
+    pub fn ignore() -> Self {
+        Self(|_, _| {})
+    }
+
+    /// Adds the error to the [`CommandErrors`] resource, inserting it if needed.
+    pub fn collect() -> Self {
+        Self(|world, error| {
+            world
+                .get_resource_or_insert_with(CommandErrors::default)
+                .errors
+                .push(error);
+        })
+    }
+}
+
+/// A [`Resource`] collecting the errors of the commands, when the [`CommandErrorHandler`] is
+/// [`CommandErrorHandler::collect`].
+#[derive(Resource, Default, Debug)]
+pub struct CommandErrors {
+    errors: Vec<CommandError>,
+}
+
+impl CommandErrors {
+    /// Returns the errors collected since they were last drained, in the order they occurred.
+    pub fn iter(&self) -> impl Iterator<Item = &CommandError> {
+        self.errors.iter()
+    }
+
+    /// Removes the errors collected so far and returns them, in the order they occurred.
+    pub fn drain(&mut self) -> impl Iterator<Item = CommandError> + '_ {
+        self.errors.drain(..)
+    }
+
+    /// Returns the number of errors collected since they were last drained.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no errors were collected since they were last drained.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
@@ -1,3 +1,4 @@
+mod command_error;
 mod command_queue;
 mod parallel_scope;
 
@@ -10,6 +11,7 @@ use crate::{
 };
 use bevy_ecs_macros::SystemParam;
 use bevy_utils::tracing::{error, info};
+pub use command_error::*;
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
@@ -701,7 +703,8 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     ///
     /// # Panics
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// The command will panic when applied if the associated entity does not exist, unless the
+    /// world has a [`CommandErrorHandler`] handling the error otherwise.
     ///
     /// To avoid a panic in this case, use the command [`Self::try_insert`] instead.
     ///
@@ -760,7 +763,8 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     ///
     /// # Note
     ///
-    /// Unlike [`Self::insert`], this will not panic if the associated entity does not exist.
+    /// Unlike [`Self::insert`], this will not panic if the associated entity does not exist,
+    /// nor report an error to the [`CommandErrorHandler`].
     ///
     /// # Example
     ///
@@ -853,6 +857,18 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Tries to remove a [`Bundle`] of components from the entity.
+    ///
+    /// Unlike [`Self::remove`], this will not report an error to the [`CommandErrorHandler`]
+    /// if the associated entity does not exist.
+    pub fn try_remove<T>(&mut self) -> &mut Self
+    where
+        T: Bundle,
+    {
+        self.commands.add(TryRemove::<T>::new(self.entity));
+        self
+    }
+
     /// Despawns the entity.
     ///
     /// See [`World::despawn`] for more details.
@@ -862,9 +878,11 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     /// This won't clean up external references to the entity (such as parent-child relationships
     /// if you're using `bevy_hierarchy`), which may leave the world in an invalid state.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// The command will log a warning when applied if the associated entity does not exist,
+    /// unless the world has a [`CommandErrorHandler`] handling the error otherwise. To ignore
+    /// this case, use the command [`Self::try_despawn`] instead.
     ///
     /// # Example
     ///
@@ -889,6 +907,16 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         });
     }
 
+    /// Tries to despawn the entity.
+    ///
+    /// Unlike [`Self::despawn`], this will neither log a warning nor report an error to the
+    /// [`CommandErrorHandler`] if the associated entity does not exist.
+    pub fn try_despawn(&mut self) {
+        self.commands.add(TryDespawn {
+            entity: self.entity,
+        });
+    }
+
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
     ///
     /// # Examples
@@ -959,7 +987,8 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     ///
     /// # Panics
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// The command will panic when applied if the associated entity does not exist, unless the
+    /// world has a [`CommandErrorHandler`] handling the error otherwise.
     pub fn log_components(&mut self) {
         self.commands.add(LogComponents {
             entity: self.entity,
@@ -986,7 +1015,12 @@ where
     F: FnOnce(EntityWorldMut) + Send + 'static,
 {
     fn apply(self, id: Entity, world: &mut World) {
-        self(world.entity_mut(id));
+        if let Some(entity) = world.get_entity_mut(id) {
+            self(entity);
+        } else {
+            CommandError::no_such_entity("apply a command to entity", id)
+                .report(world, CommandErrorHandler::panic());
+        }
     }
 }
 
@@ -1060,7 +1094,8 @@ where
 }
 
 /// A [`Command`] that despawns a specific entity.
-/// This will emit a warning if the entity does not exist.
+/// This will emit a warning if the entity does not exist, unless the world has a
+/// [`CommandErrorHandler`].
 ///
 /// # Note
 ///
@@ -1074,7 +1109,28 @@ pub struct Despawn {
 
 impl Command for Despawn {
     fn apply(self, world: &mut World) {
-        world.despawn(self.entity);
+        if world.get_entity(self.entity).is_some() {
+            world.despawn(self.entity);
+        } else {
+            CommandError::no_such_entity("despawn entity", self.entity)
+                .report(world, CommandErrorHandler::warn());
+        }
+    }
+}
+
+/// A [`Command`] that attempts to despawn a specific entity.
+/// Nothing happens if the entity does not exist.
+#[derive(Debug)]
+pub struct TryDespawn {
+    /// The entity that will be despawned.
+    pub entity: Entity,
+}
+
+impl Command for TryDespawn {
+    fn apply(self, world: &mut World) {
+        if let Some(entity) = world.get_entity_mut(self.entity) {
+            entity.despawn();
+        }
     }
 }
 
@@ -1094,7 +1150,12 @@ where
         if let Some(mut entity) = world.get_entity_mut(self.entity) {
             entity.insert(self.bundle);
         } else {
-            panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
+            let action = format!(
+                "insert a bundle (of type `{}`) for entity",
+                std::any::type_name::<T>()
+            );
+            CommandError::no_such_entity(action, self.entity)
+                .report(world, CommandErrorHandler::panic());
         }
     }
 }
@@ -1120,7 +1181,8 @@ where
 
 /// A [`Command`] that removes components from an entity.
 /// For a [`Bundle`] type `T`, this will remove any components in the bundle.
-/// Any components in the bundle that aren't found on the entity will be ignored.
+/// Any components in the bundle that aren't found on the entity will be ignored, as will the
+/// entity not existing, unless the world has a [`CommandErrorHandler`].
 #[derive(Debug)]
 pub struct Remove<T> {
     /// The entity from which the components will be removed.
@@ -1135,6 +1197,13 @@ where
     fn apply(self, world: &mut World) {
         if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
             entity_mut.remove::<T>();
+        } else {
+            let action = format!(
+                "remove a bundle (of type `{}`) from entity",
+                std::any::type_name::<T>()
+            );
+            CommandError::no_such_entity(action, self.entity)
+                .report(world, CommandErrorHandler::ignore());
         }
     }
 }
@@ -1149,6 +1218,37 @@ impl<T> Remove<T> {
     }
 }
 
+/// A [`Command`] that attempts to remove components from an entity.
+/// Nothing happens if the entity does not exist.
+#[derive(Debug)]
+pub struct TryRemove<T> {
+    /// The entity from which the components will be removed.
+    pub entity: Entity,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Command for TryRemove<T>
+where
+    T: Bundle,
+{
+    fn apply(self, world: &mut World) {
+        if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
+            entity_mut.remove::<T>();
+        }
+    }
+}
+
+impl<T> TryRemove<T> {
+    /// Creates a [`Command`] which will remove the components from the specified [`Entity`]
+    /// when applied, if it exists.
+    pub const fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            _marker: PhantomData,
+        }
+    }
+}
+
 /// A [`Command`] that removes components from an entity.
 /// For a [`Bundle`] type `T`, this will remove all components except those in the bundle.
 /// Any components in the bundle that aren't found on the entity will be ignored.
@@ -1166,6 +1266,13 @@ where
     fn apply(self, world: &mut World) {
         if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
             entity_mut.retain::<T>();
+        } else {
+            let action = format!(
+                "retain a bundle (of type `{}`) on entity",
+                std::any::type_name::<T>()
+            );
+            CommandError::no_such_entity(action, self.entity)
+                .report(world, CommandErrorHandler::ignore());
         }
     }
 }
@@ -1240,6 +1347,11 @@ pub struct LogComponents {
 
 impl Command for LogComponents {
     fn apply(self, world: &mut World) {
+        if world.get_entity(self.entity).is_none() {
+            CommandError::no_such_entity("log the components of entity", self.entity)
+                .report(world, CommandErrorHandler::panic());
+            return;
+        }
         let debug_infos: Vec<_> = world
             .inspect_entity(self.entity)
             .into_iter()
@@ -1255,7 +1367,9 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{CommandQueue, Commands, Resource},
+        system::{
+            CommandError, CommandErrorHandler, CommandErrors, CommandQueue, Commands, Resource,
+        },
         world::World,
     };
     use std::sync::{
@@ -1386,6 +1500,36 @@ mod tests {
         assert_eq!(results_after_u64, vec![]);
     }
 
+    #[test]
+    fn command_errors() {
+        let mut world = World::default();
+        world.insert_resource(CommandErrorHandler::collect());
+        let entity = world.spawn_empty().id();
+
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands
+            .entity(entity)
+            .try_insert(W(0u32))
+            .try_remove::<W<u32>>()
+            .insert(W(1u32))
+            .remove::<W<u32>>()
+            .despawn();
+        commands.entity(entity).try_despawn();
+        world.despawn(entity);
+        command_queue.apply(&mut world);
+
+        let errors: Vec<_> = world.resource_mut::<CommandErrors>().drain().collect();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(
+            |error| matches!(error, CommandError::NoSuchEntity { entity: e, .. } if *e == entity)
+        ));
+        assert_eq!(
+            errors[2].to_string(),
+            format!("error[B0003]: Could not despawn entity {entity:?} because it doesn't exist in this World.")
+        );
+    }
+
     #[test]
     fn remove_resources() {
         let mut world = World::default();
@@ -329,8 +329,8 @@ mod tests {
             Schedule,
         },
         system::{
-            Commands, In, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query, Res, ResMut,
-            Resource, StaticSystemParam, System, SystemState,
+            Commands, In, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query, QuerySortCache,
+            Res, ResMut, Resource, StaticSystemParam, System, SystemState,
         },
         world::{FromWorld, World},
     };
@@ -491,6 +491,85 @@ mod tests {
         assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
     }
 
+    #[test]
+    fn join_queries() {
+        #[derive(Component)]
+        struct Target(Entity);
+
+        fn join_system(mut attackers: Query<(&W<u32>, &Target)>, mut healths: Query<&mut A>) {
+            attackers.join_mut(
+                &mut healths,
+                |(_, target)| target.0,
+                |_, mut health| *health = A,
+            );
+            assert_eq!(
+                attackers.join(&healths.to_readonly(), |(_, t)| t.0).count(),
+                3
+            );
+        }
+
+        fn join_self_system(mut query: Query<(&mut W<u32>, &Target)>) {
+            query.join_self_mut(
+                |(_, target)| target.0,
+                |(mut attacker, _), (mut target, _)| {
+                    target.0 += attacker.0;
+                    attacker.0 = 0;
+                },
+            );
+        }
+
+        let mut world = World::default();
+        let a = world.spawn(A).id();
+        let b = world.spawn((A, W(1u32))).id();
+        world.entity_mut(b).insert(Target(a));
+        world.spawn((W(2u32), Target(b)));
+        // Targets without the joined components, and the attacker itself, are skipped.
+        let lone = world.spawn(W(4u32)).id();
+        world.entity_mut(lone).insert(Target(lone));
+        world.spawn((W(8u32), Target(a)));
+
+        run_system(&mut world, join_system);
+        run_system(&mut world, join_self_system);
+        let mut values: Vec<u32> = world.query::<&W<u32>>().iter(&world).map(|w| w.0).collect();
+        values.sort_unstable();
+        assert_eq!(values, [0, 3, 4, 8]);
+    }
+
+    #[test]
+    fn sorted_queries() {
+        fn sorted_system(mut query: Query<&mut W<u32>>) {
+            let values: Vec<u32> = query.iter_sorted_by_key(|w| w.0).map(|w| w.0).collect();
+            assert_eq!(values, [1, 2, 3, 5]);
+            let mut query_iter = query.iter_mut_sorted_by_key(|w| std::cmp::Reverse(w.0));
+            query_iter.next().unwrap().0 = 0;
+            assert_eq!(query_iter.len(), 3);
+        }
+
+        fn cached_system(mut query: Query<&mut W<u32>>, mut cache: Local<QuerySortCache<u32>>) {
+            let values: Vec<u32> = query
+                .iter_sorted_by_key_cached(&mut cache, |w| w.0)
+                .map(|w| w.0)
+                .collect();
+            assert_eq!(values, [0, 1, 2, 3]);
+            let mut query_iter = query.iter_mut_sorted_by_key_cached(&mut cache, |w| w.0);
+            while let Some(mut w) = query_iter.fetch_next() {
+                w.0 += 10;
+            }
+        }
+
+        let mut world = World::default();
+        world.spawn(W(3u32));
+        world.spawn((W(1u32), A));
+        world.spawn(W(5u32));
+        world.spawn((W(2u32), B));
+
+        run_system(&mut world, sorted_system);
+        run_system(&mut world, cached_system);
+        let mut values: Vec<u32> = world.query::<&W<u32>>().iter(&world).map(|w| w.0).collect();
+        values.sort_unstable();
+        assert_eq!(values, [10, 11, 12, 13]);
+    }
+
     #[test]
     fn or_param_set_system() {
         // Regression test for issue #762
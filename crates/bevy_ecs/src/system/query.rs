@@ -402,6 +402,70 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns another `Query` from this that detects the changes made since the `tick`, instead
+    /// of since the last run of the system.
+    ///
+    /// The [`Added`](crate::query::Added) and [`Changed`](crate::query::Changed) filters, and the
+    /// change detection of [`Ref`](crate::change_detection::Ref) and
+    /// [`Mut`](crate::change_detection::Mut), of the returned query compare the change ticks of
+    /// the components to the `tick`. This lets systems consume change sets on their own
+    /// schedule, for example to replicate the components changed since the last acknowledged
+    /// network packet, by storing the [`this_run`](Self::this_run) tick of the query once the
+    /// changes were consumed.
+    ///
+    /// The `tick` should be recent: like the ticks of the systems, a tick older than
+    /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE) sees every component as
+    /// changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::{component::Tick, prelude::*};
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// #
+    /// #[derive(Resource, Default)]
+    /// struct LastAcknowledged(Option<Tick>);
+    ///
+    /// fn replicate_system(
+    ///     mut last_acknowledged: ResMut<LastAcknowledged>,
+    ///     mut query: Query<(Entity, &Position), Changed<Position>>,
+    /// ) {
+    ///     let this_run = query.this_run();
+    ///     let since = last_acknowledged.0.unwrap_or(query.last_run());
+    ///     for (entity, position) in &query.since(since) {
+    ///         // Send the position...
+    ///     }
+    ///     last_acknowledged.0 = Some(this_run);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(replicate_system);
+    /// ```
+    pub fn since(&mut self, tick: Tick) -> Query<'_, 's, D, F> {
+        // SAFETY: This is memory safe because it borrows the query mutably.
+        unsafe {
+            Query::new(
+                self.world,
+                self.state,
+                tick,
+                self.this_run,
+                self.force_read_only_component_access,
+            )
+        }
+    }
+
+    /// Returns the tick the changes are detected since, which is the tick of the last run of
+    /// the system, unless the query was created by [`since`](Self::since).
+    pub fn last_run(&self) -> Tick {
+        self.last_run
+    }
+
+    /// Returns the tick of the current run of the system, which is the tick the components
+    /// changed through this query are marked with.
+    pub fn this_run(&self) -> Tick {
+        self.this_run
+    }
+
     /// Returns an [`Iterator`] over the read-only query items.
     ///
     /// # Example
@@ -1059,6 +1123,293 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an iterator over the pairs of read-only items of this query and of `other`,
+    /// joined on the entity returned by `key` for each item of this query.
+    ///
+    /// Items whose key entity doesn't match `other` are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Attacker { damage: u32 }
+    ///
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn report_attacks(attackers: Query<(&Attacker, &Target)>, healths: Query<&Health>) {
+    ///     for ((attacker, _), health) in attackers.join(&healths, |(_, target)| target.0) {
+    ///         println!("dealing {} damage out of {}", attacker.damage, health.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(report_attacks);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`join_mut`](Self::join_mut) to get mutable query items.
+    /// - [`join_self_mut`](Self::join_self_mut) to join a query with itself.
+    pub fn join<'a, D2: QueryData, F2: QueryFilter>(
+        &'a self,
+        other: &'a Query<'_, '_, D2, F2>,
+        mut key: impl FnMut(&ROQueryItem<'a, D>) -> Entity + 'a,
+    ) -> impl Iterator<Item = (ROQueryItem<'a, D>, ROQueryItem<'a, D2>)> + 'a {
+        self.iter().filter_map(move |item| {
+            let joined = other.get(key(&item)).ok()?;
+            Some((item, joined))
+        })
+    }
+
+    /// Calls `f` on each pair of items of this query and of `other`, joined on the entity
+    /// returned by `key` for each item of this query.
+    ///
+    /// Items whose key entity doesn't match `other` are skipped. As several items of this query
+    /// can be joined to the same item of `other`, the pairs are passed to a closure rather than
+    /// returned by an iterator, so that no two mutable borrows of an item coexist.
+    ///
+    /// # Panics
+    ///
+    /// If the accesses of the two queries conflict, which can't happen for queries which are
+    /// parameters of the same system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Attacker { damage: u32 }
+    ///
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn attack(mut attackers: Query<(&Attacker, &Target)>, mut healths: Query<&mut Health>) {
+    ///     attackers.join_mut(&mut healths, |(_, target)| target.0, |(attacker, _), mut health| {
+    ///         health.0 = health.0.saturating_sub(attacker.damage);
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(attack);
+    /// ```
+    pub fn join_mut<D2: QueryData, F2: QueryFilter>(
+        &mut self,
+        other: &mut Query<'_, '_, D2, F2>,
+        mut key: impl FnMut(&D::Item<'_>) -> Entity,
+        mut f: impl FnMut(D::Item<'_>, D2::Item<'_>),
+    ) {
+        assert!(
+            self.world.id() != other.world.id()
+                || self
+                    .state
+                    .component_access
+                    .is_compatible(&other.state.component_access),
+            "Cannot join the queries {} and {} as their accesses conflict.",
+            std::any::type_name::<Self>(),
+            std::any::type_name::<Query<D2, F2>>(),
+        );
+        for item in self.iter_mut() {
+            if let Ok(joined) = other.get_mut(key(&item)) {
+                f(item, joined);
+            }
+        }
+    }
+
+    /// Calls `f` on each pair of items of this query, where the second item is the one of the
+    /// entity returned by `key` for the first item.
+    ///
+    /// Items whose key entity doesn't match this query are skipped, and so are those whose key
+    /// is their own entity, as both items of a pair can't borrow the same entity mutably.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Follow(Entity);
+    ///
+    /// #[derive(Component)]
+    /// struct Position(f32);
+    ///
+    /// #[derive(Component)]
+    /// struct Leash(f32);
+    ///
+    /// fn pull_leashes(mut query: Query<(&Follow, &Leash, &mut Position)>) {
+    ///     query.join_self_mut(
+    ///         |(follow, ..)| follow.0,
+    ///         |(_, leash, mut position), (.., mut leader)| {
+    ///             // Keep the follower within the leash of its leader, pulling them both.
+    ///             let excess = (position.0 - leader.0).abs() - leash.0;
+    ///             if excess > 0.0 {
+    ///                 let pull = (leader.0 - position.0).signum() * excess / 2.0;
+    ///                 position.0 += pull;
+    ///                 leader.0 -= pull;
+    ///             }
+    ///         },
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(pull_leashes);
+    /// ```
+    pub fn join_self_mut(
+        &mut self,
+        mut key: impl FnMut(ROQueryItem<'_, D>) -> Entity,
+        mut f: impl FnMut(D::Item<'_>, D::Item<'_>),
+    ) {
+        let archetypes = self.world.archetypes();
+        let pairs: Vec<(Entity, Entity)> = self
+            .state
+            .matched_archetype_ids
+            .iter()
+            .flat_map(|&id| archetypes[id].entities())
+            .filter_map(|entity| {
+                let entity = entity.entity();
+                Some((entity, key(self.get(entity).ok()?)))
+            })
+            .collect();
+        for (entity, joined) in pairs {
+            if let Ok([item, joined]) = self.get_many_mut([entity, joined]) {
+                f(item, joined);
+            }
+        }
+    }
+
+    /// Returns an iterator over the read-only query items, sorted by the key of each item.
+    ///
+    /// The items with the same key are in the order of [`iter`](Self::iter). This collects the
+    /// items in a new allocation on each call, see
+    /// [`iter_sorted_by_key_cached`](Self::iter_sorted_by_key_cached) to reuse one instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Sprite { name: String, layer: i32 }
+    ///
+    /// fn draw_sprites(sprites: Query<&Sprite>) {
+    ///     // Draws the sprites back to front.
+    ///     for sprite in sprites.iter_sorted_by_key(|sprite| sprite.layer) {
+    ///         println!("drawing {}", sprite.name);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(draw_sprites);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_mut_sorted_by_key`](Self::iter_mut_sorted_by_key) to get mutable query items.
+    pub fn iter_sorted_by_key<K: Ord>(
+        &self,
+        mut key: impl FnMut(&ROQueryItem<'_, D>) -> K,
+    ) -> impl ExactSizeIterator<Item = ROQueryItem<'_, D>> {
+        let mut items: Vec<_> = self.iter().map(|item| (key(&item), item)).collect();
+        items.sort_by(|(a, _), (b, _)| a.cmp(b));
+        items.into_iter().map(|(_, item)| item)
+    }
+
+    /// Returns an iterator over the query items, sorted by the key of each item.
+    ///
+    /// The items with the same key are in the order of [`iter_mut`](Self::iter_mut). This
+    /// collects the items in a new allocation on each call, see
+    /// [`iter_mut_sorted_by_key_cached`](Self::iter_mut_sorted_by_key_cached) to reuse one
+    /// instead.
+    ///
+    /// # See also
+    ///
+    /// - [`iter_sorted_by_key`](Self::iter_sorted_by_key) to get read-only query items.
+    pub fn iter_mut_sorted_by_key<K: Ord>(
+        &mut self,
+        mut key: impl FnMut(&D::Item<'_>) -> K,
+    ) -> impl ExactSizeIterator<Item = D::Item<'_>> {
+        let mut items: Vec<_> = self.iter_mut().map(|item| (key(&item), item)).collect();
+        items.sort_by(|(a, _), (b, _)| a.cmp(b));
+        items.into_iter().map(|(_, item)| item)
+    }
+
+    /// Returns an iterator over the read-only query items, sorted by the key of each item, using
+    /// the allocations of the `cache` instead of allocating on each call.
+    ///
+    /// The `cache` is usually a [`Local`](crate::system::Local) of the system. The items are
+    /// fetched again after sorting their entities, so this is faster than
+    /// [`iter_sorted_by_key`](Self::iter_sorted_by_key) when the query items are large, or
+    /// when the allocation is the bottleneck. The order of the items with the same key is
+    /// unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, system::QuerySortCache};
+    /// #[derive(Component)]
+    /// struct Distance(u32);
+    ///
+    /// fn closest_first(
+    ///     targets: Query<(Entity, &Distance)>,
+    ///     mut cache: Local<QuerySortCache<u32>>,
+    /// ) {
+    ///     for (target, distance) in targets.iter_sorted_by_key_cached(&mut cache, |(_, d)| d.0) {
+    ///         println!("{target:?} is {} away", distance.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(closest_first);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_mut_sorted_by_key_cached`](Self::iter_mut_sorted_by_key_cached) to get mutable
+    ///   query items.
+    pub fn iter_sorted_by_key_cached<'a, K: Ord>(
+        &'a self,
+        cache: &'a mut QuerySortCache<K>,
+        key: impl FnMut(&ROQueryItem<'_, D>) -> K,
+    ) -> QueryManyIter<'a, 's, D::ReadOnly, F, std::slice::Iter<'a, Entity>> {
+        self.sort_entities(cache, key);
+        self.iter_many(&cache.entities)
+    }
+
+    /// Returns an iterator over the query items, sorted by the key of each item, using the
+    /// allocations of the `cache` instead of allocating on each call.
+    ///
+    /// The items are fetched with [`QueryManyIter::fetch_next`], like
+    /// [`iter_many_mut`](Self::iter_many_mut). See
+    /// [`iter_sorted_by_key_cached`](Self::iter_sorted_by_key_cached) for more details.
+    pub fn iter_mut_sorted_by_key_cached<'a, K: Ord>(
+        &'a mut self,
+        cache: &'a mut QuerySortCache<K>,
+        key: impl FnMut(&ROQueryItem<'_, D>) -> K,
+    ) -> QueryManyIter<'a, 's, D, F, std::slice::Iter<'a, Entity>> {
+        self.sort_entities(cache, key);
+        self.iter_many_mut(&cache.entities)
+    }
+
+    /// Fills the `cache` with the entities of the query, sorted by the key of their item.
+    fn sort_entities<K: Ord>(
+        &self,
+        cache: &mut QuerySortCache<K>,
+        mut key: impl FnMut(&ROQueryItem<'_, D>) -> K,
+    ) {
+        let archetypes = self.world.archetypes();
+        cache.keys.clear();
+        cache.keys.extend(
+            self.state
+                .matched_archetype_ids
+                .iter()
+                .flat_map(|&id| archetypes[id].entities())
+                .filter_map(|entity| {
+                    let entity = entity.entity();
+                    Some((key(&self.get(entity).ok()?), entity))
+                }),
+        );
+        cache.keys.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        cache.entities.clear();
+        cache
+            .entities
+            .extend(cache.keys.iter().map(|&(_, entity)| entity));
+    }
+
     /// Returns the query item for the given [`Entity`].
     ///
     /// In case of a nonexisting entity or mismatched component, a [`QueryEntityError`] is returned instead.
@@ -1433,6 +1784,23 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     }
 }
 
+/// Reusable allocations of [`Query::iter_sorted_by_key_cached`] and
+/// [`Query::iter_mut_sorted_by_key_cached`], holding the sorted entities and their keys.
+#[derive(Debug)]
+pub struct QuerySortCache<K> {
+    keys: Vec<(K, Entity)>,
+    entities: Vec<Entity>,
+}
+
+impl<K> Default for QuerySortCache<K> {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            entities: Vec::new(),
+        }
+    }
+}
+
 impl<'w, 's, D: QueryData, F: QueryFilter> IntoIterator for &'w Query<'_, 's, D, F> {
     type Item = ROQueryItem<'w, D>;
     type IntoIter = QueryIter<'w, 's, D::ReadOnly, F>;
@@ -65,6 +65,18 @@ impl<I, O> std::hash::Hash for SystemId<I, O> {
     }
 }
 
+impl<I, O> SystemId<I, O> {
+    /// Returns the entity storing the system.
+    pub(crate) fn entity(self) -> Entity {
+        self.0
+    }
+
+    /// Creates the id of the system stored in the entity.
+    pub(crate) fn from_entity(entity: Entity) -> Self {
+        Self(entity, std::marker::PhantomData)
+    }
+}
+
 impl<I, O> std::fmt::Debug for SystemId<I, O> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("SystemId")
@@ -4,6 +4,7 @@ use crate::{
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    relationship,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -152,6 +153,12 @@ impl<'w> EntityRef<'w> {
         // SAFETY: We have read-only access to all components of this entity.
         unsafe { self.0.get_by_id(component_id) }
     }
+
+    /// Returns the metadata of the components of the world the entity is in.
+    #[inline]
+    pub(crate) fn components(&self) -> &'w Components {
+        self.0.world().components()
+    }
 }
 
 impl<'w> From<EntityWorldMut<'w>> for EntityRef<'w> {
@@ -921,6 +928,45 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Removes a dynamic [`Component`] from the entity if it exists.
+    ///
+    /// You should prefer to use the typed API [`EntityWorldMut::remove`] where possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided [`ComponentId`] does not exist in the [`World`].
+    pub fn remove_by_id(&mut self, component_id: ComponentId) -> &mut Self {
+        let archetypes = &mut self.world.archetypes;
+        let storages = &mut self.world.storages;
+        let components = &mut self.world.components;
+        let entities = &mut self.world.entities;
+        let removed_components = &mut self.world.removed_components;
+
+        let (bundle_info, _) = self
+            .world
+            .bundles
+            .init_component_info(components, component_id);
+        let old_location = self.location;
+
+        // SAFETY: The component exists because `Bundles::init_component_info` panics if it
+        // doesn't.
+        unsafe {
+            Self::remove_bundle_info(
+                self.entity,
+                &mut self.location,
+                old_location,
+                bundle_info,
+                archetypes,
+                storages,
+                components,
+                entities,
+                removed_components,
+            );
+        }
+
+        self
+    }
+
     /// Removes any components except those in the [`Bundle`] from the entity.
     ///
     /// See [`EntityCommands::retain`](crate::system::EntityCommands::retain) for more details.
@@ -970,6 +1016,7 @@ impl<'w> EntityWorldMut<'w> {
     pub fn despawn(self) {
         debug!("Despawning entity {:?}", self.entity);
         let world = self.world;
+        relationship::despawn_relations(world, self.entity);
         world.flush();
         let location = world
             .entities
@@ -19,8 +19,9 @@ use crate::{
     entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
     event::{Event, EventId, Events, SendBatchIds},
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
+    relationship::Relationships,
     removal_detection::RemovedComponentEvents,
-    schedule::{Schedule, ScheduleLabel, Schedules},
+    schedule::{IntoSystemConfigs, Schedule, ScheduleLabel, Schedules},
     storage::{ResourceData, Storages},
     system::Resource,
     world::error::TryRunScheduleError,
@@ -66,6 +67,7 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: RemovedComponentEvents,
+    pub(crate) relationships: Relationships,
     /// Access cache used by [`WorldCell`]. Is only accessed in the `Drop` impl of `WorldCell`.
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     pub(crate) change_tick: AtomicU32,
@@ -83,6 +85,7 @@ impl Default for World {
             storages: Default::default(),
             bundles: Default::default(),
             removed_components: Default::default(),
+            relationships: Default::default(),
             archetype_component_access: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
             // are detected on first system runs and for direct world queries.
@@ -1996,6 +1999,44 @@ impl World {
         schedules.insert(schedule);
     }
 
+    /// Adds systems to the schedule associated with `label`, while the app is running.
+    ///
+    /// This lets systems created at runtime, such as the systems of a scripting language, be added
+    /// from an exclusive system or a command. If the schedule is currently running, or hasn't been
+    /// added yet, the systems are added to it once it is inserted back in the [`Schedules`].
+    ///
+    /// The `Schedules` resource will be initialized if it does not already exist.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
+    /// # #[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    /// # pub struct Update;
+    /// #[derive(Resource, Default)]
+    /// struct Counter(usize);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Counter>();
+    /// world.add_schedule(Schedule::new(Update));
+    /// world.add_systems(Update, |world: &mut World| {
+    ///     // Systems can be added to the running schedule, and run from its next run
+    ///     world.add_systems(Update, |mut counter: ResMut<Counter>| counter.0 += 1);
+    /// });
+    ///
+    /// world.run_schedule(Update);
+    /// assert_eq!(0, world.resource::<Counter>().0);
+    /// world.run_schedule(Update);
+    /// assert_eq!(1, world.resource::<Counter>().0);
+    /// ```
+    pub fn add_systems<M>(
+        &mut self,
+        label: impl ScheduleLabel,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.get_resource_or_insert_with(Schedules::default)
+            .add_systems(label, systems);
+        self
+    }
+
     /// Temporarily removes the schedule associated with `label` from the world,
     /// runs user code, and finally re-adds the schedule.
     /// This returns a [`TryRunScheduleError`] if there is no schedule
@@ -11,11 +11,11 @@ mod rumble;
 
 use bevy_app::{App, Plugin, PostUpdate, PreStartup, PreUpdate};
 use bevy_ecs::prelude::*;
-use bevy_input::InputSystem;
+use bevy_input::{haptics::gamepad_haptics_system, InputSystem};
 use bevy_utils::tracing::error;
 use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
-use rumble::{play_gilrs_rumble, RunningRumbleEffects};
+use rumble::{play_gilrs_haptics, play_gilrs_rumble, RunningHapticsEffects, RunningRumbleEffects};
 
 /// Plugin that provides gamepad handling to an [`App`].
 #[derive(Default)]
@@ -35,9 +35,17 @@ impl Plugin for GilrsPlugin {
             Ok(gilrs) => {
                 app.insert_non_send_resource(gilrs)
                     .init_non_send_resource::<RunningRumbleEffects>()
+                    .init_non_send_resource::<RunningHapticsEffects>()
                     .add_systems(PreStartup, gilrs_event_startup_system)
                     .add_systems(PreUpdate, gilrs_event_system.before(InputSystem))
-                    .add_systems(PostUpdate, play_gilrs_rumble.in_set(RumbleSystem));
+                    .add_systems(
+                        PostUpdate,
+                        (
+                            play_gilrs_rumble,
+                            play_gilrs_haptics.after(gamepad_haptics_system),
+                        )
+                            .in_set(RumbleSystem),
+                    );
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
         }
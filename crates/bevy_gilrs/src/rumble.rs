@@ -3,7 +3,10 @@ use bevy_ecs::{
     prelude::{EventReader, Res},
     system::NonSendMut,
 };
-use bevy_input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_input::{
+    gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+    haptics::GamepadHaptics,
+};
 use bevy_log::{debug, warn};
 use bevy_time::{Real, Time};
 use bevy_utils::{Duration, HashMap};
@@ -42,6 +45,12 @@ pub(crate) struct RunningRumbleEffects {
     rumbles: HashMap<GamepadId, Vec<RunningRumble>>,
 }
 
+/// Contains the gilrs effects playing the intensities of [`GamepadHaptics`] for each gamepad
+#[derive(Default)]
+pub(crate) struct RunningHapticsEffects {
+    effects: HashMap<GamepadId, ff::Effect>,
+}
+
 /// gilrs uses magnitudes from 0 to [`u16::MAX`], while ours go from `0.0` to `1.0` ([`f32`])
 fn to_gilrs_magnitude(ratio: f32) -> u16 {
     (ratio * u16::MAX as f32) as u16
@@ -156,6 +165,58 @@ pub(crate) fn play_gilrs_rumble(
     }
 }
 
+/// Plays the intensities of [`GamepadHaptics`] that changed, with an effect looping until the next change.
+pub(crate) fn play_gilrs_haptics(
+    haptics: Res<GamepadHaptics>,
+    mut gilrs: NonSendMut<Gilrs>,
+    mut running_effects: NonSendMut<RunningHapticsEffects>,
+) {
+    for (gamepad, intensity) in haptics.changed() {
+        let Some((gamepad_id, _)) = gilrs
+            .gamepads()
+            .find(|(pad_id, _)| convert_gamepad_id(*pad_id) == gamepad)
+        else {
+            continue;
+        };
+        // `ff::Effect` uses RAII, dropping = deactivating
+        running_effects.effects.remove(&gamepad_id);
+        if intensity.strong_motor <= 0. && intensity.weak_motor <= 0. {
+            continue;
+        }
+
+        let mut effect_builder = ff::EffectBuilder::new();
+        effect_builder
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: to_gilrs_magnitude(intensity.strong_motor),
+                },
+                ..Default::default()
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: to_gilrs_magnitude(intensity.weak_motor),
+                },
+                ..Default::default()
+            })
+            .repeat(Repeat::Infinitely);
+        let effect = effect_builder
+            .gamepads(&[gamepad_id])
+            .finish(&mut gilrs)
+            .and_then(|effect| effect.play().map(|()| effect));
+        match effect {
+            Ok(effect) => {
+                running_effects.effects.insert(gamepad_id, effect);
+            }
+            Err(ff::Error::FfNotSupported(_)) => {
+                debug!("Tried to rumble {gamepad:?}, but it doesn't support force feedback");
+            }
+            Err(err) => {
+                warn!("Tried to play haptics for {gamepad:?} but an error occurred: {err}");
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::to_gilrs_magnitude;
@@ -0,0 +1,478 @@
+//! Additional [`Gizmos`] Functions -- Filled shapes
+//!
+//! Includes the implementation of [`Gizmos::triangle`], [`Gizmos::quad`],
+//! [`Gizmos::filled_rect`], [`Gizmos::filled_circle`], [`Gizmos::solid_cuboid`] and
+//! [`Gizmos::solid_sphere`], their 2D variants, and assorted support items.
+//!
+//! Filled shapes are drawn with the color of the gizmo, which can be translucent to see the
+//! geometry behind them. They don't hide each other, nor the geometry drawn after them.
+
+use crate::{
+    circles::{circle_inner, DEFAULT_CIRCLE_SEGMENTS},
+    gizmos::rect_inner,
+    prelude::Gizmos,
+};
+use bevy_math::{Mat2, Quat, Vec2, Vec3};
+use bevy_render::color::Color;
+use bevy_transform::TransformPoint;
+use std::f32::consts::{PI, TAU};
+
+impl<'s> Gizmos<'s> {
+    /// Draw a filled triangle in 3D.
+    ///
+    /// This should be called for each frame the triangle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.triangle(Vec3::ZERO, Vec3::X, Vec3::Y, Color::GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn triangle(&mut self, a: Vec3, b: Vec3, c: Vec3, color: Color) {
+        self.extend_triangles([a, b, c], color);
+    }
+
+    /// Draw a filled quadrilateral in 3D, from its corners in order.
+    ///
+    /// This should be called for each frame the quad needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.quad([Vec3::ZERO, Vec3::X, Vec3::ONE, Vec3::Y], Color::GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn quad(&mut self, corners: [Vec3; 4], color: Color) {
+        let [a, b, c, d] = corners;
+        self.extend_triangles([a, b, c, a, c, d], color);
+    }
+
+    /// Draw a filled rectangle in 3D.
+    ///
+    /// This should be called for each frame the rectangle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.filled_rect(Vec3::ZERO, Quat::IDENTITY, Vec2::ONE, Color::rgba(0., 1., 0., 0.3));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn filled_rect(&mut self, position: Vec3, rotation: Quat, size: Vec2, color: Color) {
+        let corners = rect_inner(size).map(|vec2| position + rotation * vec2.extend(0.));
+        self.quad(corners, color);
+    }
+
+    /// Draw a filled circle in 3D at `position` with the flat side facing `normal`.
+    ///
+    /// This should be called for each frame the circle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.filled_circle(Vec3::ZERO, Vec3::Y, 2., Color::rgba(1., 0., 0., 0.3));
+    ///
+    ///     // Filled circles have 32 triangles by default.
+    ///     gizmos
+    ///         .filled_circle(Vec3::ZERO, Vec3::Y, 8., Color::rgba(1., 0., 0., 0.3))
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn filled_circle(
+        &mut self,
+        position: Vec3,
+        normal: Vec3,
+        radius: f32,
+        color: Color,
+    ) -> FilledCircleBuilder<'_, 's> {
+        FilledCircleBuilder {
+            gizmos: self,
+            position,
+            normal,
+            radius,
+            color,
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+
+    /// Draw a solid cuboid, the unit cube transformed by `transform`.
+    ///
+    /// This should be called for each frame the cuboid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_transform::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A translucent trigger volume.
+    ///     gizmos.solid_cuboid(
+    ///         Transform::from_xyz(0., 1., 0.).with_scale(Vec3::splat(2.)),
+    ///         Color::rgba(1., 1., 0., 0.2),
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn solid_cuboid(&mut self, transform: impl TransformPoint, color: Color) {
+        let rect = rect_inner(Vec2::ONE);
+        // Front
+        let [tlf, trf, brf, blf] = rect.map(|vec2| transform.transform_point(vec2.extend(0.5)));
+        // Back
+        let [tlb, trb, brb, blb] = rect.map(|vec2| transform.transform_point(vec2.extend(-0.5)));
+
+        for corners in [
+            [tlf, trf, brf, blf], // Front
+            [trb, tlb, blb, brb], // Back
+            [tlb, trb, trf, tlf], // Top
+            [blf, brf, brb, blb], // Bottom
+            [tlb, tlf, blf, blb], // Left
+            [trf, trb, brb, brf], // Right
+        ] {
+            self.quad(corners, color);
+        }
+    }
+
+    /// Draw a solid sphere in 3D at `position`.
+    ///
+    /// This should be called for each frame the sphere needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.solid_sphere(Vec3::ZERO, 1., Color::rgba(0., 0., 1., 0.3));
+    ///
+    ///     // Solid spheres have 32 segments around and 16 from pole to pole by default.
+    ///     gizmos
+    ///         .solid_sphere(Vec3::ZERO, 5., Color::rgba(0., 0., 1., 0.3))
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn solid_sphere(
+        &mut self,
+        position: Vec3,
+        radius: f32,
+        color: Color,
+    ) -> SolidSphereBuilder<'_, 's> {
+        SolidSphereBuilder {
+            gizmos: self,
+            position,
+            radius,
+            color,
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+
+    /// Draw a filled triangle in 2D.
+    ///
+    /// This should be called for each frame the triangle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.triangle_2d(Vec2::ZERO, Vec2::X, Vec2::Y, Color::GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn triangle_2d(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.triangle(a.extend(0.), b.extend(0.), c.extend(0.), color);
+    }
+
+    /// Draw a filled quadrilateral in 2D, from its corners in order.
+    ///
+    /// This should be called for each frame the quad needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.quad_2d([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y], Color::GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn quad_2d(&mut self, corners: [Vec2; 4], color: Color) {
+        self.quad(corners.map(|vec2| vec2.extend(0.)), color);
+    }
+
+    /// Draw a filled rectangle in 2D.
+    ///
+    /// This should be called for each frame the rectangle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.filled_rect_2d(Vec2::ZERO, 0., Vec2::ONE, Color::rgba(0., 1., 0., 0.3));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn filled_rect_2d(&mut self, position: Vec2, rotation: f32, size: Vec2, color: Color) {
+        let rotation = Mat2::from_angle(rotation);
+        let corners = rect_inner(size).map(|vec2| position + rotation * vec2);
+        self.quad_2d(corners, color);
+    }
+
+    /// Draw a filled circle in 2D.
+    ///
+    /// This should be called for each frame the circle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.filled_circle_2d(Vec2::ZERO, 10., Color::rgba(1., 0., 0., 0.3));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn filled_circle_2d(
+        &mut self,
+        position: Vec2,
+        radius: f32,
+        color: Color,
+    ) -> FilledCircleBuilder<'_, 's> {
+        self.filled_circle(position.extend(0.), Vec3::Z, radius, color)
+    }
+}
+
+/// A builder returned by [`Gizmos::filled_circle`] and [`Gizmos::filled_circle_2d`].
+pub struct FilledCircleBuilder<'a, 's> {
+    gizmos: &'a mut Gizmos<'s>,
+    position: Vec3,
+    normal: Vec3,
+    radius: f32,
+    color: Color,
+    segments: usize,
+}
+
+impl FilledCircleBuilder<'_, '_> {
+    /// Set the number of triangles for this circle.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl Drop for FilledCircleBuilder<'_, '_> {
+    fn drop(&mut self) {
+        let rotation = Quat::from_rotation_arc(Vec3::Z, self.normal);
+        let points = circle_inner(self.radius, self.segments)
+            .map(|vec2| self.position + rotation * vec2.extend(0.))
+            .collect::<Vec<_>>();
+        let triangles = points
+            .windows(2)
+            .flat_map(|edge| [self.position, edge[0], edge[1]])
+            .collect::<Vec<_>>();
+        self.gizmos.extend_triangles(triangles, self.color);
+    }
+}
+
+/// A builder returned by [`Gizmos::solid_sphere`].
+pub struct SolidSphereBuilder<'a, 's> {
+    gizmos: &'a mut Gizmos<'s>,
+    position: Vec3,
+    radius: f32,
+    color: Color,
+    segments: usize,
+}
+
+impl SolidSphereBuilder<'_, '_> {
+    /// Set the number of segments around the sphere, half of which go from pole to pole.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl Drop for SolidSphereBuilder<'_, '_> {
+    fn drop(&mut self) {
+        let sectors = self.segments.max(3);
+        let stacks = (sectors / 2).max(2);
+        let point = |stack: usize, sector: usize| {
+            let (sin_theta, cos_theta) = (stack as f32 * PI / stacks as f32).sin_cos();
+            let (sin_phi, cos_phi) = (sector as f32 * TAU / sectors as f32).sin_cos();
+            self.position
+                + Vec3::new(sin_theta * cos_phi, cos_theta, sin_theta * sin_phi) * self.radius
+        };
+        let triangles = (0..stacks)
+            .flat_map(|stack| (0..sectors).map(move |sector| (stack, sector)))
+            .flat_map(|(stack, sector)| {
+                let top_left = point(stack, sector);
+                let top_right = point(stack, sector + 1);
+                let bottom_left = point(stack + 1, sector);
+                let bottom_right = point(stack + 1, sector + 1);
+                [
+                    top_left,
+                    bottom_left,
+                    bottom_right,
+                    top_left,
+                    bottom_right,
+                    top_right,
+                ]
+            })
+            .collect::<Vec<_>>();
+        self.gizmos.extend_triangles(triangles, self.color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_transform::components::Transform;
+
+    use super::*;
+    use crate::gizmos::{tests::draw, GizmoStorage};
+
+    /// Draws with `draw`, and returns the vertices of the triangles drawn.
+    fn triangles(draw_gizmos: impl FnMut(&mut Gizmos) + Send + Sync + 'static) -> Vec<Vec3> {
+        let world = draw(draw_gizmos);
+        let storage = world.resource::<GizmoStorage>();
+        assert_eq!(
+            storage.triangle_colors.len(),
+            storage.triangle_positions.len()
+        );
+        assert!(storage.list_positions.is_empty() && storage.strip_positions.is_empty());
+        storage
+            .triangle_positions
+            .iter()
+            .map(|position| Vec3::from(*position))
+            .collect()
+    }
+
+    #[test]
+    fn quads_are_two_triangles() {
+        let [a, b, c, d] = [Vec3::ZERO, Vec3::X, Vec3::ONE, Vec3::Y];
+        assert_eq!(
+            triangles(move |gizmos| gizmos.quad([a, b, c, d], Color::GREEN)),
+            [a, b, c, a, c, d]
+        );
+        assert_eq!(
+            triangles(|gizmos| {
+                gizmos.filled_rect_2d(Vec2::ONE, 0., Vec2::new(2., 4.), Color::GREEN);
+            }),
+            [
+                (0., 3.),
+                (2., 3.),
+                (2., -1.),
+                (0., 3.),
+                (2., -1.),
+                (0., -1.)
+            ]
+            .map(|(x, y)| Vec3::new(x, y, 0.))
+        );
+    }
+
+    #[test]
+    fn filled_circle_is_a_fan() {
+        let center = Vec3::new(1., 2., 3.);
+        let vertices = triangles(move |gizmos| {
+            gizmos
+                .filled_circle(center, Vec3::Y, 2., Color::RED)
+                .segments(8);
+        });
+        assert_eq!(vertices.len(), 8 * 3);
+        for triangle in vertices.chunks(3) {
+            assert_eq!(triangle[0], center);
+            for edge in &triangle[1..] {
+                assert!((edge.distance(center) - 2.).abs() < 1e-5);
+                // The circle faces its normal
+                assert!((edge.y - center.y).abs() < 1e-5);
+            }
+        }
+        // The fan closes on its first edge
+        assert!(vertices[1].abs_diff_eq(vertices[vertices.len() - 1], 1e-5));
+    }
+
+    #[test]
+    fn solid_cuboid_has_two_triangles_per_face() {
+        let vertices = triangles(|gizmos| {
+            gizmos.solid_cuboid(
+                Transform::from_xyz(0., 1., 0.).with_scale(Vec3::splat(2.)),
+                Color::YELLOW,
+            );
+        });
+        assert_eq!(vertices.len(), 6 * 2 * 3);
+
+        let mut faces = Vec::new();
+        for triangle in vertices.chunks(3) {
+            // The vertices are the corners of the cuboid, in the plane of one of its faces
+            let local = triangle
+                .iter()
+                .map(|vertex| (*vertex - Vec3::Y) / 2.)
+                .collect::<Vec<_>>();
+            for vertex in &local {
+                assert_eq!(vertex.abs(), Vec3::splat(0.5));
+            }
+            let face = (0..3)
+                .find(|&axis| local.iter().all(|vertex| vertex[axis] == local[0][axis]))
+                .map(|axis| (axis, local[0][axis] > 0.))
+                .unwrap();
+            faces.push(face);
+        }
+        for axis in 0..3 {
+            for positive in [false, true] {
+                let count = faces
+                    .iter()
+                    .filter(|face| **face == (axis, positive))
+                    .count();
+                assert_eq!(count, 2, "face {axis} {positive}");
+            }
+        }
+    }
+
+    #[test]
+    fn solid_sphere_stacks_and_sectors() {
+        let center = Vec3::new(0., 5., 0.);
+        for (segments, triangle_count) in [
+            (DEFAULT_CIRCLE_SEGMENTS, 32 * 16 * 2),
+            (8, 8 * 4 * 2),
+            // At least 3 sectors and 2 stacks
+            (0, 3 * 2 * 2),
+        ] {
+            let vertices = triangles(move |gizmos| {
+                gizmos
+                    .solid_sphere(center, 3., Color::BLUE)
+                    .segments(segments);
+            });
+            assert_eq!(vertices.len(), triangle_count * 3, "{segments} segments");
+            for vertex in vertices {
+                assert!((vertex.distance(center) - 3.).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -1,8 +1,13 @@
 //! A module for the [`Gizmos`] [`SystemParam`].
 
-use std::iter;
+use std::{borrow::Cow, iter};
 
-use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
+use crate::{
+    circles::DEFAULT_CIRCLE_SEGMENTS,
+    retained::{GizmoGeometry, GizmoGroups},
+    text::GizmoText,
+    timed::{GizmoLifetime, TimedGizmos},
+};
 use bevy_ecs::{
     system::{Deferred, Resource, SystemBuffer, SystemMeta, SystemParam},
     world::World,
@@ -20,6 +25,25 @@ pub(crate) struct GizmoStorage {
     pub list_colors: Vec<ColorItem>,
     pub strip_positions: Vec<PositionItem>,
     pub strip_colors: Vec<ColorItem>,
+    pub triangle_positions: Vec<PositionItem>,
+    pub triangle_colors: Vec<ColorItem>,
+    pub texts: Vec<GizmoText>,
+}
+
+impl GizmoStorage {
+    /// Adds a copy of `geometry`, to be drawn this frame.
+    pub(crate) fn extend_geometry(&mut self, geometry: &GizmoGeometry) {
+        self.list_positions
+            .extend_from_slice(&geometry.list_positions);
+        self.list_colors.extend_from_slice(&geometry.list_colors);
+        self.strip_positions
+            .extend_from_slice(&geometry.strip_positions);
+        self.strip_colors.extend_from_slice(&geometry.strip_colors);
+        self.triangle_positions
+            .extend_from_slice(&geometry.triangle_positions);
+        self.triangle_colors
+            .extend_from_slice(&geometry.triangle_colors);
+    }
 }
 
 /// A [`SystemParam`] for drawing gizmos.
@@ -38,6 +62,11 @@ struct GizmoBuffer {
     list_colors: Vec<ColorItem>,
     strip_positions: Vec<PositionItem>,
     strip_colors: Vec<ColorItem>,
+    triangle_positions: Vec<PositionItem>,
+    triangle_colors: Vec<ColorItem>,
+    texts: Vec<GizmoText>,
+    retained: Vec<(Cow<'static, str>, GizmoGeometry)>,
+    timed: Vec<(GizmoLifetime, GizmoGeometry)>,
 }
 
 impl SystemBuffer for GizmoBuffer {
@@ -47,6 +76,23 @@ impl SystemBuffer for GizmoBuffer {
         storage.list_colors.append(&mut self.list_colors);
         storage.strip_positions.append(&mut self.strip_positions);
         storage.strip_colors.append(&mut self.strip_colors);
+        storage
+            .triangle_positions
+            .append(&mut self.triangle_positions);
+        storage.triangle_colors.append(&mut self.triangle_colors);
+        storage.texts.append(&mut self.texts);
+        if !self.retained.is_empty() {
+            let mut groups = world.resource_mut::<GizmoGroups>();
+            for (name, geometry) in self.retained.drain(..) {
+                groups.set_geometry(name, geometry);
+            }
+        }
+        if !self.timed.is_empty() {
+            let mut timed = world.resource_mut::<TimedGizmos>();
+            for (lifetime, geometry) in self.timed.drain(..) {
+                timed.push(lifetime, geometry);
+            }
+        }
     }
 }
 
@@ -448,6 +494,63 @@ impl<'s> Gizmos<'s> {
         self.linestrip_2d([tl, tr, br, bl, tl], color);
     }
 
+    /// Returns the number of positions and colors in the buffer, to split off what is drawn next
+    /// with [`Self::split_off_geometry`].
+    pub(crate) fn geometry_counts(&self) -> [usize; 6] {
+        [
+            self.buffer.list_positions.len(),
+            self.buffer.list_colors.len(),
+            self.buffer.strip_positions.len(),
+            self.buffer.strip_colors.len(),
+            self.buffer.triangle_positions.len(),
+            self.buffer.triangle_colors.len(),
+        ]
+    }
+
+    /// Removes the positions and colors added since `counts` were returned by
+    /// [`Self::geometry_counts`].
+    pub(crate) fn split_off_geometry(&mut self, counts: [usize; 6]) -> GizmoGeometry {
+        GizmoGeometry {
+            list_positions: self.buffer.list_positions.split_off(counts[0]),
+            list_colors: self.buffer.list_colors.split_off(counts[1]),
+            strip_positions: self.buffer.strip_positions.split_off(counts[2]),
+            strip_colors: self.buffer.strip_colors.split_off(counts[3]),
+            triangle_positions: self.buffer.triangle_positions.split_off(counts[4]),
+            triangle_colors: self.buffer.triangle_colors.split_off(counts[5]),
+        }
+    }
+
+    /// Adds filled triangles, each made of three consecutive `positions`.
+    #[inline]
+    pub(crate) fn extend_triangles(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec3>,
+        color: Color,
+    ) {
+        self.buffer
+            .triangle_positions
+            .extend(positions.into_iter().map(|vec3| vec3.to_array()));
+        let len = self.buffer.triangle_positions.len();
+        self.buffer
+            .triangle_colors
+            .resize(len, color.as_linear_rgba_f32());
+    }
+
+    #[inline]
+    pub(crate) fn add_retained(&mut self, name: Cow<'static, str>, geometry: GizmoGeometry) {
+        self.buffer.retained.push((name, geometry));
+    }
+
+    #[inline]
+    pub(crate) fn add_timed(&mut self, lifetime: GizmoLifetime, geometry: GizmoGeometry) {
+        self.buffer.timed.push((lifetime, geometry));
+    }
+
+    #[inline]
+    pub(crate) fn add_text(&mut self, text: GizmoText) {
+        self.buffer.texts.push(text);
+    }
+
     #[inline]
     fn extend_list_positions(&mut self, positions: impl IntoIterator<Item = Vec3>) {
         self.buffer
@@ -508,7 +611,7 @@ impl Drop for SphereBuilder<'_, '_> {
     }
 }
 
-fn rect_inner(size: Vec2) -> [Vec2; 4] {
+pub(crate) fn rect_inner(size: Vec2) -> [Vec2; 4] {
     let half_size = size / 2.;
     let tl = Vec2::new(-half_size.x, half_size.y);
     let tr = Vec2::new(half_size.x, half_size.y);
@@ -516,3 +619,39 @@ fn rect_inner(size: Vec2) -> [Vec2; 4] {
     let br = Vec2::new(half_size.x, -half_size.y);
     [tl, tr, br, bl]
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use bevy_ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    /// Runs `draw` in a system, and returns the world with what it drew in the [`GizmoStorage`].
+    pub(crate) fn draw(mut draw: impl FnMut(&mut Gizmos) + Send + Sync + 'static) -> World {
+        let mut world = World::new();
+        world.init_resource::<GizmoStorage>();
+        world.init_resource::<GizmoGroups>();
+        world.init_resource::<TimedGizmos>();
+        world.run_system_once(move |mut gizmos: Gizmos| draw(&mut gizmos));
+        world
+    }
+
+    #[test]
+    fn lines_and_strips() {
+        let world = draw(|gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::X, Color::RED);
+            gizmos.linestrip([Vec3::ZERO, Vec3::Y, Vec3::Z], Color::BLUE);
+        });
+        let storage = world.resource::<GizmoStorage>();
+        assert_eq!(storage.list_positions, [[0., 0., 0.], [1., 0., 0.]]);
+        assert_eq!(storage.list_colors, [Color::RED.as_linear_rgba_f32(); 2]);
+        // Strips are separated by a NaN point
+        assert_eq!(storage.strip_positions.len(), 4);
+        assert_eq!(
+            storage.strip_positions[..3],
+            [[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+        );
+        assert!(storage.strip_positions[3][0].is_nan());
+        assert_eq!(storage.strip_colors.len(), 4);
+    }
+}
@@ -18,7 +18,13 @@
 pub mod arcs;
 pub mod arrows;
 pub mod circles;
+pub mod filled;
 pub mod gizmos;
+pub mod retained;
+pub mod shapes;
+pub mod text;
+pub mod timed;
+pub mod transform_handle;
 
 #[cfg(feature = "bevy_sprite")]
 mod pipeline_2d;
@@ -28,14 +34,18 @@ mod pipeline_3d;
 /// The `bevy_gizmos` prelude.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{gizmos::Gizmos, AabbGizmo, AabbGizmoConfig, GizmoConfig};
+    pub use crate::{
+        gizmos::Gizmos,
+        retained::{GizmoGroup, GizmoGroupConfig, GizmoGroups},
+        transform_handle::{TransformHandle, TransformHandlePlugin},
+        AabbGizmo, AabbGizmoConfig, GizmoConfig,
+    };
 }
 
 use bevy_app::{Last, Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, Asset, AssetApp, Assets, Handle};
 use bevy_core::cast_slice;
 use bevy_ecs::{
-    change_detection::DetectChanges,
     component::Component,
     entity::Entity,
     query::{ROQueryItem, Without},
@@ -46,6 +56,7 @@ use bevy_ecs::{
         Commands, Query, Res, ResMut, Resource, SystemParamItem,
     },
 };
+use bevy_math::Mat4;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
 use bevy_render::{
     color::Color,
@@ -67,9 +78,11 @@ use bevy_transform::{
     TransformSystem,
 };
 use gizmos::{GizmoStorage, Gizmos};
+use retained::GizmoGroups;
 use std::mem;
 
 const LINE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(7414812689238026784);
+const TRIANGLE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(2864190415287519063);
 
 /// A [`Plugin`] that provides an immediate mode drawing api for visual debugging.
 pub struct GizmoPlugin;
@@ -77,6 +90,12 @@ pub struct GizmoPlugin;
 impl Plugin for GizmoPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         load_internal_asset!(app, LINE_SHADER_HANDLE, "lines.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            TRIANGLE_SHADER_HANDLE,
+            "triangles.wgsl",
+            Shader::from_wgsl
+        );
 
         app.register_type::<GizmoConfig>()
             .register_type::<AabbGizmoConfig>()
@@ -86,7 +105,20 @@ impl Plugin for GizmoPlugin {
             .init_resource::<LineGizmoHandles>()
             .init_resource::<GizmoConfig>()
             .init_resource::<GizmoStorage>()
-            .add_systems(Last, update_gizmo_meshes)
+            .init_resource::<GizmoGroups>()
+            .init_resource::<timed::TimedGizmos>()
+            .add_systems(
+                Last,
+                (
+                    (
+                        timed::draw_timed_gizmos,
+                        text::draw_gizmo_texts,
+                        update_gizmo_meshes,
+                    )
+                        .chain(),
+                    retained::update_retained_gizmo_meshes,
+                ),
+            )
             .add_systems(
                 PostUpdate,
                 (
@@ -267,6 +299,7 @@ fn aabb_transform(aabb: Aabb, transform: GlobalTransform) -> GlobalTransform {
 struct LineGizmoHandles {
     list: Option<Handle<LineGizmo>>,
     strip: Option<Handle<LineGizmo>>,
+    triangles: Option<Handle<LineGizmo>>,
 }
 
 fn update_gizmo_meshes(
@@ -274,73 +307,120 @@ fn update_gizmo_meshes(
     mut handles: ResMut<LineGizmoHandles>,
     mut storage: ResMut<GizmoStorage>,
 ) {
-    if storage.list_positions.is_empty() {
-        handles.list = None;
-    } else if let Some(handle) = handles.list.as_ref() {
-        let list = line_gizmos.get_mut(handle).unwrap();
-
-        list.positions = mem::take(&mut storage.list_positions);
-        list.colors = mem::take(&mut storage.list_colors);
-    } else {
-        let mut list = LineGizmo {
-            strip: false,
-            ..Default::default()
-        };
-
-        list.positions = mem::take(&mut storage.list_positions);
-        list.colors = mem::take(&mut storage.list_colors);
+    let storage = &mut *storage;
+    update_line_gizmo(
+        &mut line_gizmos,
+        &mut handles.list,
+        GizmoTopology::LineList,
+        mem::take(&mut storage.list_positions),
+        mem::take(&mut storage.list_colors),
+    );
+    update_line_gizmo(
+        &mut line_gizmos,
+        &mut handles.strip,
+        GizmoTopology::LineStrip,
+        mem::take(&mut storage.strip_positions),
+        mem::take(&mut storage.strip_colors),
+    );
+    update_line_gizmo(
+        &mut line_gizmos,
+        &mut handles.triangles,
+        GizmoTopology::TriangleList,
+        mem::take(&mut storage.triangle_positions),
+        mem::take(&mut storage.triangle_colors),
+    );
+}
 
-        handles.list = Some(line_gizmos.add(list));
+/// Sets the positions and colors of the [`LineGizmo`] of `handle`, adding it if needed, or
+/// removes it when there are no positions.
+fn update_line_gizmo(
+    line_gizmos: &mut Assets<LineGizmo>,
+    handle: &mut Option<Handle<LineGizmo>>,
+    topology: GizmoTopology,
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+) {
+    if positions.is_empty() {
+        *handle = None;
+        return;
     }
-
-    if storage.strip_positions.is_empty() {
-        handles.strip = None;
-    } else if let Some(handle) = handles.strip.as_ref() {
-        let strip = line_gizmos.get_mut(handle).unwrap();
-
-        strip.positions = mem::take(&mut storage.strip_positions);
-        strip.colors = mem::take(&mut storage.strip_colors);
-    } else {
-        let mut strip = LineGizmo {
-            strip: true,
-            ..Default::default()
-        };
-
-        strip.positions = mem::take(&mut storage.strip_positions);
-        strip.colors = mem::take(&mut storage.strip_colors);
-
-        handles.strip = Some(line_gizmos.add(strip));
+    let line_gizmo = LineGizmo {
+        positions,
+        colors,
+        topology,
+    };
+    match handle
+        .as_ref()
+        .and_then(|handle| line_gizmos.get_mut(handle))
+    {
+        Some(current) => *current = line_gizmo,
+        None => *handle = Some(line_gizmos.add(line_gizmo)),
     }
 }
 
 fn extract_gizmo_data(
     mut commands: Commands,
     handles: Extract<Res<LineGizmoHandles>>,
+    groups: Extract<Res<GizmoGroups>>,
     config: Extract<Res<GizmoConfig>>,
 ) {
-    if config.is_changed() {
-        commands.insert_resource(config.clone());
-    }
-
     if !config.enabled {
         return;
     }
 
-    for handle in [&handles.list, &handles.strip].into_iter().flatten() {
+    for handle in [&handles.list, &handles.strip, &handles.triangles]
+        .into_iter()
+        .flatten()
+    {
         commands.spawn((
             LineGizmoUniform {
+                transform: Mat4::IDENTITY,
                 line_width: config.line_width,
                 depth_bias: config.depth_bias,
                 #[cfg(feature = "webgl")]
                 _padding: Default::default(),
             },
+            LineGizmoRenderConfig {
+                line_perspective: config.line_perspective,
+                render_layers: config.render_layers,
+            },
             handle.clone_weak(),
         ));
     }
+
+    for (_, group) in groups.iter().filter(|(_, group)| group.enabled) {
+        for handle in [&group.list, &group.strip, &group.triangles]
+            .into_iter()
+            .flatten()
+        {
+            commands.spawn((
+                LineGizmoUniform {
+                    transform: group.transform.compute_matrix(),
+                    line_width: group.config.line_width,
+                    depth_bias: group.config.effective_depth_bias(),
+                    #[cfg(feature = "webgl")]
+                    _padding: Default::default(),
+                },
+                LineGizmoRenderConfig {
+                    line_perspective: group.config.line_perspective,
+                    render_layers: group.config.render_layers,
+                },
+                handle.clone_weak(),
+            ));
+        }
+    }
+}
+
+/// The settings of a [`LineGizmo`] used when queuing it, in the render world.
+#[derive(Component, Clone, Copy)]
+struct LineGizmoRenderConfig {
+    line_perspective: bool,
+    render_layers: RenderLayers,
 }
 
 #[derive(Component, ShaderType, Clone, Copy)]
 struct LineGizmoUniform {
+    transform: Mat4,
     line_width: f32,
     depth_bias: f32,
     /// WebGL2 structs must be 16 byte aligned.
@@ -352,8 +432,19 @@ struct LineGizmoUniform {
 struct LineGizmo {
     positions: Vec<[f32; 3]>,
     colors: Vec<[f32; 4]>,
-    /// Whether this gizmo's topology is a line-strip or line-list
-    strip: bool,
+    topology: GizmoTopology,
+}
+
+/// How the positions of a [`LineGizmo`] are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+enum GizmoTopology {
+    /// Each pair of positions is a line.
+    #[default]
+    LineList,
+    /// Each position is joined to the next one by a line, unless one of them is `NaN`.
+    LineStrip,
+    /// Each three positions are a filled triangle.
+    TriangleList,
 }
 
 #[derive(Debug, Clone)]
@@ -361,7 +452,7 @@ struct GpuLineGizmo {
     position_buffer: Buffer,
     color_buffer: Buffer,
     vertex_count: u32,
-    strip: bool,
+    topology: GizmoTopology,
 }
 
 impl RenderAsset for LineGizmo {
@@ -397,7 +488,7 @@ impl RenderAsset for LineGizmo {
             position_buffer,
             color_buffer,
             vertex_count: line_gizmo.positions.len() as u32,
-            strip: line_gizmo.strip,
+            topology: line_gizmo.topology,
         })
     }
 }
@@ -470,11 +561,18 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
             return RenderCommandResult::Failure;
         };
 
+        if line_gizmo.topology == GizmoTopology::TriangleList {
+            pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..));
+            pass.set_vertex_buffer(1, line_gizmo.color_buffer.slice(..));
+            pass.draw(0..line_gizmo.vertex_count / 3 * 3, 0..1);
+            return RenderCommandResult::Success;
+        }
+
         if line_gizmo.vertex_count < 2 {
             return RenderCommandResult::Success;
         }
 
-        let instances = if line_gizmo.strip {
+        let instances = if line_gizmo.topology == GizmoTopology::LineStrip {
             let item_size = VertexFormat::Float32x3.size();
             let buffer_size = line_gizmo.position_buffer.size() - item_size;
             pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..buffer_size));
@@ -499,7 +597,7 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
     }
 }
 
-fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
+fn line_gizmo_vertex_buffer_layouts(topology: GizmoTopology) -> Vec<VertexBufferLayout> {
     use VertexFormat::*;
     let mut position_layout = VertexBufferLayout {
         array_stride: Float32x3.size(),
@@ -521,8 +619,8 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
         }],
     };
 
-    if strip {
-        vec![
+    match topology {
+        GizmoTopology::LineStrip => vec![
             position_layout.clone(),
             {
                 position_layout.attributes[0].shader_location = 1;
@@ -533,22 +631,38 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
                 color_layout.attributes[0].shader_location = 3;
                 color_layout
             },
-        ]
-    } else {
-        position_layout.array_stride *= 2;
-        position_layout.attributes.push(VertexAttribute {
-            format: Float32x3,
-            offset: Float32x3.size(),
-            shader_location: 1,
-        });
+        ],
+        GizmoTopology::LineList => {
+            position_layout.array_stride *= 2;
+            position_layout.attributes.push(VertexAttribute {
+                format: Float32x3,
+                offset: Float32x3.size(),
+                shader_location: 1,
+            });
+
+            color_layout.array_stride *= 2;
+            color_layout.attributes.push(VertexAttribute {
+                format: Float32x4,
+                offset: Float32x4.size(),
+                shader_location: 3,
+            });
+
+            vec![position_layout, color_layout]
+        }
+        GizmoTopology::TriangleList => {
+            position_layout.step_mode = VertexStepMode::Vertex;
+            color_layout.step_mode = VertexStepMode::Vertex;
+            color_layout.attributes[0].shader_location = 1;
 
-        color_layout.array_stride *= 2;
-        color_layout.attributes.push(VertexAttribute {
-            format: Float32x4,
-            offset: Float32x4.size(),
-            shader_location: 3,
-        });
+            vec![position_layout, color_layout]
+        }
+    }
+}
 
-        vec![position_layout, color_layout]
+/// Returns the shader drawing gizmos with the given `topology`.
+fn gizmo_shader(topology: GizmoTopology) -> Handle<Shader> {
+    match topology {
+        GizmoTopology::LineList | GizmoTopology::LineStrip => LINE_SHADER_HANDLE,
+        GizmoTopology::TriangleList => TRIANGLE_SHADER_HANDLE,
     }
 }
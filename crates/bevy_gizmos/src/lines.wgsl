@@ -5,6 +5,7 @@
 
 
 struct LineGizmoUniform {
+    transform: mat4x4<f32>,
     line_width: f32,
     depth_bias: f32,
 #ifdef SIXTEEN_BYTE_ALIGNMENT
@@ -43,8 +44,8 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     let position = positions[vertex.index];
 
     // algorithm based on https://wwwtyro.net/2019/11/18/instanced-lines.html
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
+    var clip_a = view.view_proj * line_gizmo.transform * vec4(vertex.position_a, 1.);
+    var clip_b = view.view_proj * line_gizmo.transform * vec4(vertex.position_b, 1.);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_b);
@@ -1,6 +1,6 @@
 use crate::{
-    line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoConfig, LineGizmo,
-    LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_SHADER_HANDLE,
+    gizmo_shader, line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoTopology, LineGizmo,
+    LineGizmoRenderConfig, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
@@ -72,7 +72,7 @@ impl FromWorld for LineGizmoPipeline {
 #[derive(PartialEq, Eq, Hash, Clone)]
 struct LineGizmoPipelineKey {
     mesh_key: Mesh2dPipelineKey,
-    strip: bool,
+    topology: GizmoTopology,
 }
 
 impl SpecializedRenderPipeline for LineGizmoPipeline {
@@ -97,13 +97,13 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
 
         RenderPipelineDescriptor {
             vertex: VertexState {
-                shader: LINE_SHADER_HANDLE,
+                shader: gizmo_shader(key.topology),
                 entry_point: "vertex".into(),
                 shader_defs: shader_defs.clone(),
-                buffers: line_gizmo_vertex_buffer_layouts(key.strip),
+                buffers: line_gizmo_vertex_buffer_layouts(key.topology),
             },
             fragment: Some(FragmentState {
-                shader: LINE_SHADER_HANDLE,
+                shader: gizmo_shader(key.topology),
                 shader_defs,
                 entry_point: "fragment".into(),
                 targets: vec![Some(ColorTargetState {
@@ -140,8 +140,7 @@ fn queue_line_gizmos_2d(
     mut pipelines: ResMut<SpecializedRenderPipelines<LineGizmoPipeline>>,
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
-    config: Res<GizmoConfig>,
-    line_gizmos: Query<(Entity, &Handle<LineGizmo>)>,
+    line_gizmos: Query<(Entity, &Handle<LineGizmo>, &LineGizmoRenderConfig)>,
     line_gizmo_assets: Res<RenderAssets<LineGizmo>>,
     mut views: Query<(
         &ExtractedView,
@@ -153,13 +152,13 @@ fn queue_line_gizmos_2d(
 
     for (view, mut transparent_phase, render_layers) in &mut views {
         let render_layers = render_layers.copied().unwrap_or_default();
-        if !config.render_layers.intersects(&render_layers) {
-            continue;
-        }
         let mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
             | Mesh2dPipelineKey::from_hdr(view.hdr);
 
-        for (entity, handle) in &line_gizmos {
+        for (entity, handle, config) in &line_gizmos {
+            if !config.render_layers.intersects(&render_layers) {
+                continue;
+            }
             let Some(line_gizmo) = line_gizmo_assets.get(handle) else {
                 continue;
             };
@@ -169,7 +168,7 @@ fn queue_line_gizmos_2d(
                 &pipeline,
                 LineGizmoPipelineKey {
                     mesh_key,
-                    strip: line_gizmo.strip,
+                    topology: line_gizmo.topology,
                 },
             );
 
@@ -1,6 +1,6 @@
 use crate::{
-    line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoConfig, LineGizmo,
-    LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_SHADER_HANDLE,
+    gizmo_shader, line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoTopology, LineGizmo,
+    LineGizmoRenderConfig, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
@@ -74,7 +74,7 @@ impl FromWorld for LineGizmoPipeline {
 #[derive(PartialEq, Eq, Hash, Clone)]
 struct LineGizmoPipelineKey {
     view_key: MeshPipelineKey,
-    strip: bool,
+    topology: GizmoTopology,
     perspective: bool,
 }
 
@@ -106,13 +106,13 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
 
         RenderPipelineDescriptor {
             vertex: VertexState {
-                shader: LINE_SHADER_HANDLE,
+                shader: gizmo_shader(key.topology),
                 entry_point: "vertex".into(),
                 shader_defs: shader_defs.clone(),
-                buffers: line_gizmo_vertex_buffer_layouts(key.strip),
+                buffers: line_gizmo_vertex_buffer_layouts(key.topology),
             },
             fragment: Some(FragmentState {
-                shader: LINE_SHADER_HANDLE,
+                shader: gizmo_shader(key.topology),
                 shader_defs,
                 entry_point: "fragment".into(),
                 targets: vec![Some(ColorTargetState {
@@ -125,7 +125,8 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
                 format: CORE_3D_DEPTH_FORMAT,
-                depth_write_enabled: true,
+                // Filled gizmos are usually translucent, and shouldn't hide what is behind them.
+                depth_write_enabled: key.topology != GizmoTopology::TriangleList,
                 depth_compare: CompareFunction::Greater,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
@@ -155,8 +156,7 @@ fn queue_line_gizmos_3d(
     mut pipelines: ResMut<SpecializedRenderPipelines<LineGizmoPipeline>>,
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
-    config: Res<GizmoConfig>,
-    line_gizmos: Query<(Entity, &Handle<LineGizmo>)>,
+    line_gizmos: Query<(Entity, &Handle<LineGizmo>, &LineGizmoRenderConfig)>,
     line_gizmo_assets: Res<RenderAssets<LineGizmo>>,
     mut views: Query<(
         &ExtractedView,
@@ -180,9 +180,6 @@ fn queue_line_gizmos_3d(
     ) in &mut views
     {
         let render_layers = render_layers.copied().unwrap_or_default();
-        if !config.render_layers.intersects(&render_layers) {
-            continue;
-        }
 
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
             | MeshPipelineKey::from_hdr(view.hdr);
@@ -203,7 +200,10 @@ fn queue_line_gizmos_3d(
             view_key |= MeshPipelineKey::DEFERRED_PREPASS;
         }
 
-        for (entity, handle) in &line_gizmos {
+        for (entity, handle, config) in &line_gizmos {
+            if !config.render_layers.intersects(&render_layers) {
+                continue;
+            }
             let Some(line_gizmo) = line_gizmo_assets.get(handle) else {
                 continue;
             };
@@ -213,7 +213,7 @@ fn queue_line_gizmos_3d(
                 &pipeline,
                 LineGizmoPipelineKey {
                     view_key,
-                    strip: line_gizmo.strip,
+                    topology: line_gizmo.topology,
                     perspective: config.line_perspective,
                 },
             );
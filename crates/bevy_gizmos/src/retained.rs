@@ -0,0 +1,350 @@
+//! Retained gizmos, drawn every frame without being submitted again.
+//!
+//! Static debug geometry, such as navmesh wireframes or collider shapes, can be drawn once into
+//! a named group with [`Gizmos::retain`]. The group is then drawn every frame until it is
+//! replaced or removed from [`GizmoGroups`], and can be toggled, moved and configured through
+//! its [`GizmoGroup`].
+
+use crate::{prelude::Gizmos, update_line_gizmo, GizmoTopology, LineGizmo};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::system::{ResMut, Resource};
+use bevy_render::view::RenderLayers;
+use bevy_transform::components::Transform;
+use bevy_utils::HashMap;
+use std::borrow::Cow;
+
+/// The line segments of a [`GizmoGroup`], recorded by [`Gizmos::retain`].
+pub(crate) struct GizmoGeometry {
+    pub list_positions: Vec<[f32; 3]>,
+    pub list_colors: Vec<[f32; 4]>,
+    pub strip_positions: Vec<[f32; 3]>,
+    pub strip_colors: Vec<[f32; 4]>,
+    pub triangle_positions: Vec<[f32; 3]>,
+    pub triangle_colors: Vec<[f32; 4]>,
+}
+
+/// A [`Resource`] holding the retained gizmo groups, by name.
+///
+/// # Example
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_render::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// fn draw_colliders(mut gizmos: Gizmos) {
+///     gizmos.retain("colliders", |gizmos| {
+///         gizmos.cuboid(Transform::from_xyz(0., 1., 0.), Color::GREEN);
+///         gizmos.sphere(Vec3::X, Quat::IDENTITY, 0.5, Color::GREEN);
+///     });
+/// }
+///
+/// fn toggle_colliders(mut groups: ResMut<GizmoGroups>) {
+///     if let Some(group) = groups.get_mut("colliders") {
+///         group.enabled = !group.enabled;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(draw_colliders);
+/// # bevy_ecs::system::assert_is_system(toggle_colliders);
+/// ```
+#[derive(Resource, Default)]
+pub struct GizmoGroups {
+    groups: HashMap<Cow<'static, str>, GizmoGroup>,
+}
+
+impl GizmoGroups {
+    /// Returns the group `name`.
+    pub fn get(&self, name: &str) -> Option<&GizmoGroup> {
+        self.groups.get(name)
+    }
+
+    /// Returns the group `name` mutably.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut GizmoGroup> {
+        self.groups.get_mut(name)
+    }
+
+    /// Returns the group `name`, adding an empty group if it doesn't exist.
+    ///
+    /// This lets a group be configured before its gizmos are drawn.
+    pub fn get_or_insert(&mut self, name: impl Into<Cow<'static, str>>) -> &mut GizmoGroup {
+        self.groups.entry(name.into()).or_default()
+    }
+
+    /// Removes the group `name`, which stops being drawn.
+    pub fn remove(&mut self, name: &str) -> Option<GizmoGroup> {
+        self.groups.remove(name)
+    }
+
+    /// Returns an iterator over the names and the groups.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &GizmoGroup)> {
+        self.groups.iter().map(|(name, group)| (&**name, group))
+    }
+
+    /// Returns an iterator over the names and the groups, with the groups mutable.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut GizmoGroup)> {
+        self.groups.iter_mut().map(|(name, group)| (&**name, group))
+    }
+
+    pub(crate) fn set_geometry(&mut self, name: Cow<'static, str>, geometry: GizmoGeometry) {
+        self.get_or_insert(name).geometry = Some(geometry);
+    }
+}
+
+/// A group of retained gizmos, drawn every frame with its own settings.
+///
+/// See [`GizmoGroups`].
+pub struct GizmoGroup {
+    /// Set to `false` to stop drawing the group, while keeping its gizmos.
+    ///
+    /// Defaults to `true`.
+    pub enabled: bool,
+    /// The transform applied to the gizmos of the group when they are drawn.
+    pub transform: Transform,
+    /// The settings used to draw the group.
+    pub config: GizmoGroupConfig,
+    /// The geometry waiting to be uploaded.
+    geometry: Option<GizmoGeometry>,
+    pub(crate) list: Option<Handle<LineGizmo>>,
+    pub(crate) strip: Option<Handle<LineGizmo>>,
+    pub(crate) triangles: Option<Handle<LineGizmo>>,
+}
+
+impl Default for GizmoGroup {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            transform: Transform::IDENTITY,
+            config: Default::default(),
+            geometry: None,
+            list: None,
+            strip: None,
+            triangles: None,
+        }
+    }
+}
+
+/// The settings used to draw a [`GizmoGroup`], in place of the ones of the
+/// [`GizmoConfig`](crate::GizmoConfig).
+#[derive(Clone, Debug)]
+pub struct GizmoGroupConfig {
+    /// Line width specified in pixels.
+    ///
+    /// If `line_perspective` is `true` then this is the size in pixels at the camera's near plane.
+    ///
+    /// Defaults to `2.0`.
+    pub line_width: f32,
+    /// Apply perspective to gizmo lines.
+    ///
+    /// This setting only affects 3D, non-orthographic cameras.
+    ///
+    /// Defaults to `false`.
+    pub line_perspective: bool,
+    /// Set to `false` to draw the lines in front of all other geometry.
+    ///
+    /// Defaults to `true`.
+    pub depth_test: bool,
+    /// How closer to the camera than real geometry the line should be, when `depth_test` is
+    /// `true`.
+    ///
+    /// See [`GizmoConfig::depth_bias`](crate::GizmoConfig::depth_bias).
+    pub depth_bias: f32,
+    /// Describes which rendering layers the group will be rendered to.
+    pub render_layers: RenderLayers,
+}
+
+impl Default for GizmoGroupConfig {
+    fn default() -> Self {
+        Self {
+            line_width: 2.,
+            line_perspective: false,
+            depth_test: true,
+            depth_bias: 0.,
+            render_layers: Default::default(),
+        }
+    }
+}
+
+impl GizmoGroupConfig {
+    /// The depth bias given to the shader, which is `-1` when `depth_test` is `false`.
+    pub(crate) fn effective_depth_bias(&self) -> f32 {
+        if self.depth_test {
+            self.depth_bias
+        } else {
+            -1.
+        }
+    }
+}
+
+impl<'s> Gizmos<'s> {
+    /// Draw the gizmos of `draw` into the retained group `name`, replacing its previous gizmos.
+    ///
+    /// The group is drawn every frame until it is replaced, or removed from [`GizmoGroups`],
+    /// so this only needs to be called when the gizmos change. Text isn't retained, and is only
+    /// drawn for this frame.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// fn system(mut gizmos: Gizmos, mut built: Local<bool>) {
+    ///     if !*built {
+    ///         gizmos.retain("navmesh", |gizmos| {
+    ///             gizmos.line(Vec3::ZERO, Vec3::X, Color::BLUE);
+    ///             gizmos.line(Vec3::X, Vec3::Z, Color::BLUE);
+    ///         });
+    ///         *built = true;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn retain(&mut self, name: impl Into<Cow<'static, str>>, draw: impl FnOnce(&mut Self)) {
+        let start = self.geometry_counts();
+        draw(self);
+        let geometry = self.split_off_geometry(start);
+        self.add_retained(name.into(), geometry);
+    }
+}
+
+/// Uploads the geometry of the [`GizmoGroup`]s that changed.
+pub(crate) fn update_retained_gizmo_meshes(
+    mut line_gizmos: ResMut<Assets<LineGizmo>>,
+    mut groups: ResMut<GizmoGroups>,
+) {
+    for (_, group) in groups.iter_mut() {
+        let Some(geometry) = group.geometry.take() else {
+            continue;
+        };
+        update_line_gizmo(
+            &mut line_gizmos,
+            &mut group.list,
+            GizmoTopology::LineList,
+            geometry.list_positions,
+            geometry.list_colors,
+        );
+        update_line_gizmo(
+            &mut line_gizmos,
+            &mut group.strip,
+            GizmoTopology::LineStrip,
+            geometry.strip_positions,
+            geometry.strip_colors,
+        );
+        update_line_gizmo(
+            &mut line_gizmos,
+            &mut group.triangles,
+            GizmoTopology::TriangleList,
+            geometry.triangle_positions,
+            geometry.triangle_colors,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::Vec3;
+    use bevy_render::color::Color;
+
+    use super::*;
+    use crate::gizmos::{tests::draw, GizmoStorage};
+
+    fn draw_navmesh(gizmos: &mut Gizmos) {
+        gizmos.retain("navmesh", |gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::X, Color::BLUE);
+            gizmos.linestrip([Vec3::X, Vec3::Y, Vec3::Z], Color::BLUE);
+        });
+    }
+
+    #[test]
+    fn retained_gizmos_are_split_off() {
+        let world = draw(|gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::Y, Color::RED);
+            draw_navmesh(gizmos);
+            gizmos.line(Vec3::ZERO, Vec3::Z, Color::RED);
+        });
+
+        // Only the gizmos drawn outside of the group are drawn for this frame
+        let storage = world.resource::<GizmoStorage>();
+        assert_eq!(
+            storage.list_positions,
+            [[0., 0., 0.], [0., 1., 0.], [0., 0., 0.], [0., 0., 1.]]
+        );
+        assert_eq!(storage.list_colors.len(), 4);
+        assert!(storage.strip_positions.is_empty());
+
+        let group = world.resource::<GizmoGroups>().get("navmesh").unwrap();
+        assert!(group.enabled);
+        let geometry = group.geometry.as_ref().unwrap();
+        assert_eq!(geometry.list_positions, [[0., 0., 0.], [1., 0., 0.]]);
+        assert_eq!(geometry.list_colors, [Color::BLUE.as_linear_rgba_f32(); 2]);
+        assert_eq!(geometry.strip_positions.len(), 4);
+        assert_eq!(geometry.strip_colors.len(), 4);
+        assert!(geometry.triangle_positions.is_empty());
+    }
+
+    #[test]
+    fn groups_keep_their_settings_when_replaced() {
+        let mut world = draw(draw_navmesh);
+        world.run_system_once(|mut groups: ResMut<GizmoGroups>| {
+            groups.get_mut("navmesh").unwrap().enabled = false;
+        });
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.retain("navmesh", |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::NEG_X, Color::BLUE);
+            });
+        });
+
+        let groups = world.resource::<GizmoGroups>();
+        assert_eq!(groups.iter().count(), 1);
+        let group = groups.get("navmesh").unwrap();
+        assert!(!group.enabled);
+        let geometry = group.geometry.as_ref().unwrap();
+        assert_eq!(geometry.list_positions, [[0., 0., 0.], [-1., 0., 0.]]);
+        assert!(geometry.strip_positions.is_empty());
+    }
+
+    #[test]
+    fn changed_groups_are_uploaded() {
+        let mut world = draw(draw_navmesh);
+        world.init_resource::<Assets<LineGizmo>>();
+        world.run_system_once(update_retained_gizmo_meshes);
+
+        let group = world.resource::<GizmoGroups>().get("navmesh").unwrap();
+        assert!(group.geometry.is_none());
+        assert!(group.triangles.is_none());
+        let (list, strip) = (group.list.clone().unwrap(), group.strip.clone().unwrap());
+        let line_gizmos = world.resource::<Assets<LineGizmo>>();
+        let list_gizmo = line_gizmos.get(&list).unwrap();
+        assert_eq!(list_gizmo.topology, GizmoTopology::LineList);
+        assert_eq!(list_gizmo.positions, [[0., 0., 0.], [1., 0., 0.]]);
+        assert_eq!(line_gizmos.get(&strip).unwrap().positions.len(), 4);
+
+        // The meshes are updated in place, and dropped when the group has nothing to draw
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.retain("navmesh", |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::NEG_X, Color::BLUE);
+            });
+        });
+        world.run_system_once(update_retained_gizmo_meshes);
+        let group = world.resource::<GizmoGroups>().get("navmesh").unwrap();
+        assert_eq!(group.list, Some(list.clone()));
+        assert!(group.strip.is_none());
+        let line_gizmos = world.resource::<Assets<LineGizmo>>();
+        assert_eq!(
+            line_gizmos.get(&list).unwrap().positions,
+            [[0., 0., 0.], [-1., 0., 0.]]
+        );
+    }
+
+    #[test]
+    fn depth_bias() {
+        let mut config = GizmoGroupConfig {
+            depth_bias: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(config.effective_depth_bias(), 0.5);
+        config.depth_test = false;
+        assert_eq!(config.effective_depth_bias(), -1.);
+    }
+}
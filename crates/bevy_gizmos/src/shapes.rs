@@ -0,0 +1,320 @@
+//! Additional [`Gizmos`] Functions -- Capsules, Cones and Frusta
+//!
+//! Includes the implementation of [`Gizmos::capsule`], [`Gizmos::cone`] and
+//! [`Gizmos::frustum`], and assorted support items.
+
+use crate::{circles::DEFAULT_CIRCLE_SEGMENTS, prelude::Gizmos};
+use bevy_math::{Quat, Vec3};
+use bevy_render::{camera::CameraProjection, color::Color};
+use bevy_transform::TransformPoint;
+use std::f32::consts::{PI, TAU};
+
+impl<'s> Gizmos<'s> {
+    /// Draw a wireframe capsule in 3D, around the segment from `start` to `end`.
+    ///
+    /// This should be called for each frame the capsule needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.capsule(Vec3::ZERO, Vec3::Y * 2., 0.5, Color::GREEN);
+    ///
+    ///     // Capsules have 32 line-segments per circle by default.
+    ///     gizmos
+    ///         .capsule(Vec3::ZERO, Vec3::Y * 10., 5., Color::RED)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn capsule(
+        &mut self,
+        start: Vec3,
+        end: Vec3,
+        radius: f32,
+        color: Color,
+    ) -> CapsuleBuilder<'_, 's> {
+        CapsuleBuilder {
+            gizmos: self,
+            start,
+            end,
+            radius,
+            color,
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+
+    /// Draw a wireframe cone in 3D, from the circle at `base` to `apex`.
+    ///
+    /// This should be called for each frame the cone needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A vision cone.
+    ///     gizmos.cone(Vec3::Z * 10., Vec3::ZERO, 4., Color::YELLOW);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn cone(
+        &mut self,
+        base: Vec3,
+        apex: Vec3,
+        radius: f32,
+        color: Color,
+    ) -> ConeBuilder<'_, 's> {
+        ConeBuilder {
+            gizmos: self,
+            base,
+            apex,
+            radius,
+            color,
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+
+    /// Draw the outline of the frustum of a camera with `projection`, placed by `transform`,
+    /// between the distances `near` and `far` from the camera.
+    ///
+    /// This should be called for each frame the frustum needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_transform::prelude::*;
+    /// fn system(mut gizmos: Gizmos, cameras: Query<(&GlobalTransform, &Projection)>) {
+    ///     for (transform, projection) in &cameras {
+    ///         gizmos.frustum(*transform, projection, 0.1, 20., Color::WHITE);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn frustum(
+        &mut self,
+        transform: impl TransformPoint,
+        projection: &impl CameraProjection,
+        near: f32,
+        far: f32,
+        color: Color,
+    ) {
+        // The corners are in view space, where the camera looks towards -Z.
+        let [nbr, ntr, ntl, nbl, fbr, ftr, ftl, fbl] = projection
+            .get_frustum_corners(-near.abs(), -far.abs())
+            .map(|corner| transform.transform_point(Vec3::from(corner)));
+
+        self.linestrip([nbr, ntr, ntl, nbl, nbr], color);
+        self.linestrip([fbr, ftr, ftl, fbl, fbr], color);
+        for (near, far) in [(nbr, fbr), (ntr, ftr), (ntl, ftl), (nbl, fbl)] {
+            self.line(near, far, color);
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::capsule`].
+pub struct CapsuleBuilder<'a, 's> {
+    gizmos: &'a mut Gizmos<'s>,
+    start: Vec3,
+    end: Vec3,
+    radius: f32,
+    color: Color,
+    segments: usize,
+}
+
+impl CapsuleBuilder<'_, '_> {
+    /// Set the number of line-segments per circle for this capsule.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl Drop for CapsuleBuilder<'_, '_> {
+    fn drop(&mut self) {
+        let (start, end, radius, color, segments) =
+            (self.start, self.end, self.radius, self.color, self.segments);
+        let axis = (end - start).try_normalize().unwrap_or(Vec3::Y);
+        let rotation = Quat::from_rotation_arc(Vec3::Y, axis);
+        let half_segments = (segments / 2).max(1);
+
+        for position in [start, end] {
+            self.gizmos
+                .circle(position, axis, radius, color)
+                .segments(segments);
+        }
+        for side in [Vec3::X, Vec3::Z] {
+            let side = rotation * side * radius;
+            self.gizmos.line(start + side, end + side, color);
+            self.gizmos.line(start - side, end - side, color);
+
+            // The half circles closing the ends.
+            for (center, direction) in [(end, axis), (start, -axis)] {
+                let positions = (0..=half_segments).map(|i| {
+                    let (sin, cos) = (i as f32 * PI / half_segments as f32).sin_cos();
+                    center + side * cos + direction * radius * sin
+                });
+                self.gizmos.linestrip(positions, color);
+            }
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::cone`].
+pub struct ConeBuilder<'a, 's> {
+    gizmos: &'a mut Gizmos<'s>,
+    base: Vec3,
+    apex: Vec3,
+    radius: f32,
+    color: Color,
+    segments: usize,
+}
+
+impl ConeBuilder<'_, '_> {
+    /// Set the number of line-segments for the circle of this cone.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl Drop for ConeBuilder<'_, '_> {
+    fn drop(&mut self) {
+        let normal = (self.apex - self.base).try_normalize().unwrap_or(Vec3::Y);
+        let rotation = Quat::from_rotation_arc(Vec3::Z, normal);
+        self.gizmos
+            .circle(self.base, normal, self.radius, self.color)
+            .segments(self.segments);
+        for i in 0..4 {
+            let (sin, cos) = (i as f32 * TAU / 4.).sin_cos();
+            let edge = self.base + rotation * Vec3::new(sin, cos, 0.) * self.radius;
+            self.gizmos.line(edge, self.apex, self.color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy_render::camera::PerspectiveProjection;
+    use bevy_transform::components::Transform;
+
+    use super::*;
+    use crate::gizmos::{tests::draw, GizmoStorage};
+
+    fn positions(positions: &[[f32; 3]]) -> Vec<Vec3> {
+        positions
+            .iter()
+            .map(|position| Vec3::from(*position))
+            .collect()
+    }
+
+    /// The distance between `point` and the segment from `start` to `end`.
+    fn distance_to_segment(point: Vec3, start: Vec3, end: Vec3) -> f32 {
+        let t = ((point - start).dot(end - start) / start.distance_squared(end)).clamp(0., 1.);
+        point.distance(start.lerp(end, t))
+    }
+
+    #[test]
+    fn capsule_outline() {
+        let (start, end) = (Vec3::new(1., 0., 0.), Vec3::new(1., 2., 0.));
+        let world = draw(move |gizmos| {
+            gizmos.capsule(start, end, 0.5, Color::GREEN).segments(8);
+        });
+        let storage = world.resource::<GizmoStorage>();
+
+        // Four lines along the sides
+        let lines = positions(&storage.list_positions);
+        assert_eq!(lines.len(), 4 * 2);
+        for line in lines.chunks(2) {
+            assert!((line[1] - line[0]).abs_diff_eq(end - start, 1e-5));
+            assert!((distance_to_segment(line[0], start, end) - 0.5).abs() < 1e-5);
+        }
+
+        // Two circles of 9 points, and four half circles of 5 points, each ended by NaN
+        let strips = positions(&storage.strip_positions);
+        assert_eq!(strips.len(), 2 * 10 + 4 * 6);
+        assert_eq!(strips.iter().filter(|point| point.is_nan()).count(), 6);
+        for point in strips.iter().filter(|point| !point.is_nan()) {
+            assert!((distance_to_segment(*point, start, end) - 0.5).abs() < 1e-5);
+        }
+        // The half circles reach the ends of the capsule
+        let highest = strips
+            .iter()
+            .filter(|point| !point.is_nan())
+            .fold(f32::MIN, |highest, point| highest.max(point.y));
+        assert!((highest - 2.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cone_outline() {
+        let (base, apex) = (Vec3::Z * 10., Vec3::ZERO);
+        let world = draw(move |gizmos| {
+            gizmos.cone(base, apex, 4., Color::YELLOW).segments(16);
+        });
+        let storage = world.resource::<GizmoStorage>();
+
+        // A circle around the base
+        let circle = positions(&storage.strip_positions);
+        assert_eq!(circle.len(), 16 + 2);
+        for point in &circle[..17] {
+            assert!((point.distance(base) - 4.).abs() < 1e-5);
+            assert!((point.z - base.z).abs() < 1e-5);
+        }
+
+        // Four lines from the circle to the apex
+        let lines = positions(&storage.list_positions);
+        assert_eq!(lines.len(), 4 * 2);
+        for line in lines.chunks(2) {
+            assert!((line[0].distance(base) - 4.).abs() < 1e-5);
+            assert_eq!(line[1], apex);
+        }
+    }
+
+    #[test]
+    fn frustum_outline() {
+        let projection = PerspectiveProjection {
+            fov: FRAC_PI_2,
+            aspect_ratio: 2.,
+            ..Default::default()
+        };
+        let transform = Transform::from_xyz(0., 0., 5.);
+        // Distances are in front of the camera whatever their sign
+        for (near, far) in [(1., 3.), (-1., -3.)] {
+            let projection = projection.clone();
+            let world = draw(move |gizmos| {
+                gizmos.frustum(transform, &projection, near, far, Color::WHITE);
+            });
+            let storage = world.resource::<GizmoStorage>();
+
+            // The near and far rectangles, in front of the camera
+            let strips = positions(&storage.strip_positions);
+            assert_eq!(strips.len(), 2 * 6);
+            let near_corners = [(2., -1.), (2., 1.), (-2., 1.), (-2., -1.), (2., -1.)];
+            for (point, (x, y)) in strips[..5].iter().zip(near_corners) {
+                assert!(point.abs_diff_eq(Vec3::new(x, y, 4.), 1e-5), "{point}");
+            }
+            for (point, (x, y)) in strips[6..11].iter().zip(near_corners) {
+                assert!(
+                    point.abs_diff_eq(Vec3::new(x * 3., y * 3., 2.), 1e-5),
+                    "{point}"
+                );
+            }
+
+            // The edges joining their corners
+            let lines = positions(&storage.list_positions);
+            assert_eq!(lines.len(), 4 * 2);
+            for line in lines.chunks(2) {
+                assert!(line[1].abs_diff_eq(Vec3::new(line[0].x * 3., line[0].y * 3., 2.), 1e-5));
+            }
+        }
+    }
+}
@@ -0,0 +1,656 @@
+//! Additional [`Gizmos`] Functions -- Text
+//!
+//! Includes the implementation of [`Gizmos::text`], [`Gizmos::text_2d`],
+//! [`Gizmos::annotation`] and [`Gizmos::screen_text`], and assorted support items.
+//!
+//! Text is drawn with a built-in stroke font made of line segments, so it goes through the same
+//! pipeline as the other gizmos. The font covers printable ASCII, with lowercase letters drawn
+//! as capitals.
+
+use crate::{gizmos::GizmoStorage, prelude::Gizmos};
+use bevy_ecs::system::{Query, ResMut};
+use bevy_math::{Vec2, Vec3, Vec3Swizzles};
+use bevy_render::{camera::Camera, color::Color};
+use bevy_transform::components::GlobalTransform;
+use std::mem;
+
+/// The height of capital letters in font units.
+const CAP_HEIGHT: f32 = 6.;
+/// The width of a character in font units.
+const GLYPH_WIDTH: f32 = 4.;
+/// The horizontal distance between two characters in font units.
+const ADVANCE: f32 = 6.;
+/// The vertical distance between two lines in font units.
+const LINE_HEIGHT: f32 = 10.;
+/// The depth in normalized device coordinates at which screen-space text is drawn when it has no
+/// world position, close to the near plane.
+const SCREEN_DEPTH: f32 = 0.99;
+
+const DEFAULT_TEXT_SIZE: f32 = 0.25;
+const DEFAULT_TEXT_2D_SIZE: f32 = 16.;
+const DEFAULT_ANNOTATION_SIZE: f32 = 16.;
+
+/// A text queued by [`Gizmos`], turned into line segments once the camera is known.
+pub(crate) struct GizmoText {
+    text: String,
+    placement: TextPlacement,
+    color: Color,
+    size: f32,
+    anchor: Vec2,
+}
+
+#[derive(Clone, Copy)]
+enum TextPlacement {
+    /// Faces the camera at a world position.
+    Billboard(Vec3),
+    /// Lies in the XY plane at a world position.
+    Plane(Vec3),
+    /// Is drawn at a constant size on screen, `offset` logical pixels away from a world position.
+    Annotation { position: Vec3, offset: Vec2 },
+    /// Is drawn at a constant size on screen, at a position in logical pixels.
+    Screen(Vec2),
+}
+
+impl<'s> Gizmos<'s> {
+    /// Draw a text label in 3D at `position`, facing the camera.
+    ///
+    /// The label is `0.25` units tall by default, and grows smaller with the distance to the
+    /// camera. Use [`Gizmos::annotation`] for a label with a constant size on screen.
+    ///
+    /// This should be called for each frame the text needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.text(Vec3::ZERO, "spawn_point_3", Color::WHITE);
+    ///
+    ///     // Labels are centered on their position by default.
+    ///     gizmos
+    ///         .text(Vec3::Y, "state: FLEEING", Color::RED)
+    ///         .size(0.5)
+    ///         .anchor(Vec2::new(-0.5, 0.));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn text(
+        &mut self,
+        position: Vec3,
+        text: impl Into<String>,
+        color: Color,
+    ) -> TextBuilder<'_, 's> {
+        TextBuilder {
+            gizmos: self,
+            text: Some(GizmoText {
+                text: text.into(),
+                placement: TextPlacement::Billboard(position),
+                color,
+                size: DEFAULT_TEXT_SIZE,
+                anchor: Vec2::ZERO,
+            }),
+        }
+    }
+
+    /// Draw a text label in 2D at `position`.
+    ///
+    /// The label is `16` units tall by default.
+    ///
+    /// This should be called for each frame the text needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.text_2d(Vec2::ZERO, "spawn_point_3", Color::WHITE);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn text_2d(
+        &mut self,
+        position: Vec2,
+        text: impl Into<String>,
+        color: Color,
+    ) -> TextBuilder<'_, 's> {
+        TextBuilder {
+            gizmos: self,
+            text: Some(GizmoText {
+                text: text.into(),
+                placement: TextPlacement::Plane(position.extend(0.)),
+                color,
+                size: DEFAULT_TEXT_2D_SIZE,
+                anchor: Vec2::ZERO,
+            }),
+        }
+    }
+
+    /// Draw an annotation attached to `position`, with a constant size on screen.
+    ///
+    /// The annotation is drawn `16` logical pixels tall, just above `position` by default.
+    /// It isn't drawn while `position` is behind the camera.
+    ///
+    /// This should be called for each frame the annotation needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.annotation(Vec3::ZERO, "patrolling", Color::YELLOW);
+    ///
+    ///     // Draw the annotation on the right of the position instead.
+    ///     gizmos
+    ///         .annotation(Vec3::X, "target", Color::RED)
+    ///         .anchor(Vec2::new(-0.5, 0.))
+    ///         .offset(Vec2::new(12., 0.));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn annotation(
+        &mut self,
+        position: Vec3,
+        text: impl Into<String>,
+        color: Color,
+    ) -> AnnotationBuilder<'_, 's> {
+        AnnotationBuilder {
+            gizmos: self,
+            text: Some(GizmoText {
+                text: text.into(),
+                placement: TextPlacement::Annotation {
+                    position,
+                    offset: Vec2::new(0., 8.),
+                },
+                color,
+                size: DEFAULT_ANNOTATION_SIZE,
+                anchor: Vec2::new(0., -0.5),
+            }),
+        }
+    }
+
+    /// Draw a text at a fixed place on screen, regardless of the camera.
+    ///
+    /// `position` is in logical pixels from the top-left corner of the viewport, like
+    /// [`Camera::world_to_viewport`]. The text is `16` logical pixels tall and its top-left
+    /// corner is at `position` by default.
+    ///
+    /// This should be called for each frame the text needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.screen_text(Vec2::new(10., 10.), "agents: 12", Color::WHITE);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn screen_text(
+        &mut self,
+        position: Vec2,
+        text: impl Into<String>,
+        color: Color,
+    ) -> AnnotationBuilder<'_, 's> {
+        AnnotationBuilder {
+            gizmos: self,
+            text: Some(GizmoText {
+                text: text.into(),
+                placement: TextPlacement::Screen(position),
+                color,
+                size: DEFAULT_ANNOTATION_SIZE,
+                anchor: Vec2::new(-0.5, 0.5),
+            }),
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::text`] and [`Gizmos::text_2d`].
+pub struct TextBuilder<'a, 's> {
+    gizmos: &'a mut Gizmos<'s>,
+    text: Option<GizmoText>,
+}
+
+impl TextBuilder<'_, '_> {
+    /// Set the height of capital letters, in world units.
+    pub fn size(mut self, size: f32) -> Self {
+        if let Some(text) = &mut self.text {
+            text.size = size;
+        }
+        self
+    }
+
+    /// Set the point of the text placed at its position.
+    ///
+    /// `(-0.5, -0.5)` is the bottom-left corner of the text, `(0.5, 0.5)` the top-right corner.
+    /// Defaults to the center.
+    pub fn anchor(mut self, anchor: Vec2) -> Self {
+        if let Some(text) = &mut self.text {
+            text.anchor = anchor;
+        }
+        self
+    }
+}
+
+impl Drop for TextBuilder<'_, '_> {
+    fn drop(&mut self) {
+        if let Some(text) = self.text.take() {
+            self.gizmos.add_text(text);
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::annotation`] and [`Gizmos::screen_text`].
+pub struct AnnotationBuilder<'a, 's> {
+    gizmos: &'a mut Gizmos<'s>,
+    text: Option<GizmoText>,
+}
+
+impl AnnotationBuilder<'_, '_> {
+    /// Set the height of capital letters, in logical pixels.
+    pub fn size(mut self, size: f32) -> Self {
+        if let Some(text) = &mut self.text {
+            text.size = size;
+        }
+        self
+    }
+
+    /// Set the point of the text placed at its position.
+    ///
+    /// `(-0.5, -0.5)` is the bottom-left corner of the text, `(0.5, 0.5)` the top-right corner.
+    pub fn anchor(mut self, anchor: Vec2) -> Self {
+        if let Some(text) = &mut self.text {
+            text.anchor = anchor;
+        }
+        self
+    }
+
+    /// Set the distance in logical pixels between the anchor of the text and its position, with
+    /// the Y axis pointing up.
+    pub fn offset(mut self, offset: Vec2) -> Self {
+        if let Some(text) = &mut self.text {
+            match &mut text.placement {
+                TextPlacement::Annotation {
+                    offset: current, ..
+                } => *current = offset,
+                TextPlacement::Screen(position) => *position += Vec2::new(offset.x, -offset.y),
+                TextPlacement::Billboard(_) | TextPlacement::Plane(_) => {}
+            }
+        }
+        self
+    }
+}
+
+impl Drop for AnnotationBuilder<'_, '_> {
+    fn drop(&mut self) {
+        if let Some(text) = self.text.take() {
+            self.gizmos.add_text(text);
+        }
+    }
+}
+
+/// Where the points of a text, in font units, end up in the world.
+enum TextTransform<'a> {
+    World {
+        origin: Vec3,
+        right: Vec3,
+        up: Vec3,
+    },
+    Screen {
+        camera: &'a Camera,
+        camera_transform: &'a GlobalTransform,
+        ndc: Vec3,
+        scale: Vec2,
+    },
+}
+
+impl<'a> TextTransform<'a> {
+    /// Returns `None` when the text can't be seen by `camera`.
+    fn new(text: &GizmoText, camera: Option<(&'a Camera, &'a GlobalTransform)>) -> Option<Self> {
+        let unit = text.size / CAP_HEIGHT;
+        let (pixel_to_ndc, ndc) = match text.placement {
+            TextPlacement::Billboard(origin) => {
+                let (right, up) = camera.map_or((Vec3::X, Vec3::Y), |(_, transform)| {
+                    (transform.right(), transform.up())
+                });
+                return Some(Self::World {
+                    origin,
+                    right: right * unit,
+                    up: up * unit,
+                });
+            }
+            TextPlacement::Plane(origin) => {
+                return Some(Self::World {
+                    origin,
+                    right: Vec3::X * unit,
+                    up: Vec3::Y * unit,
+                });
+            }
+            TextPlacement::Annotation { position, offset } => {
+                let (camera, transform) = camera?;
+                let ndc = camera.world_to_ndc(transform, position)?;
+                if !(0.0..=1.0).contains(&ndc.z) {
+                    return None;
+                }
+                let pixel_to_ndc = 2. / camera.logical_viewport_size()?;
+                (pixel_to_ndc, ndc + (offset * pixel_to_ndc).extend(0.))
+            }
+            TextPlacement::Screen(position) => {
+                let pixel_to_ndc = 2. / camera?.0.logical_viewport_size()?;
+                let ndc = Vec2::new(
+                    position.x * pixel_to_ndc.x - 1.,
+                    1. - position.y * pixel_to_ndc.y,
+                );
+                (pixel_to_ndc, ndc.extend(SCREEN_DEPTH))
+            }
+        };
+        let (camera, camera_transform) = camera?;
+        Some(Self::Screen {
+            camera,
+            camera_transform,
+            ndc,
+            scale: pixel_to_ndc * unit,
+        })
+    }
+
+    fn apply(&self, point: Vec2) -> Option<Vec3> {
+        match *self {
+            Self::World { origin, right, up } => Some(origin + right * point.x + up * point.y),
+            Self::Screen {
+                camera,
+                camera_transform,
+                ndc,
+                scale,
+            } => camera.ndc_to_world(camera_transform, (ndc.xy() + point * scale).extend(ndc.z)),
+        }
+    }
+}
+
+/// Turns the texts queued by [`Gizmos`] into line segments, using the active camera with the
+/// highest order to face and size them.
+pub(crate) fn draw_gizmo_texts(
+    mut storage: ResMut<GizmoStorage>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+) {
+    if storage.texts.is_empty() {
+        return;
+    }
+    let camera = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order);
+
+    for text in mem::take(&mut storage.texts) {
+        let Some(transform) = TextTransform::new(&text, camera) else {
+            continue;
+        };
+        let color = text.color.as_linear_rgba_f32();
+        for [start, end] in text_segments(&text.text, text.anchor) {
+            let (Some(start), Some(end)) = (transform.apply(start), transform.apply(end)) else {
+                continue;
+            };
+            storage
+                .list_positions
+                .extend([start.to_array(), end.to_array()]);
+            storage.list_colors.extend([color, color]);
+        }
+    }
+}
+
+/// Returns the line segments drawing `text` in font units, relative to its `anchor`.
+fn text_segments(text: &str, anchor: Vec2) -> impl Iterator<Item = [Vec2; 2]> + '_ {
+    let lines = text.lines().count().max(1);
+    let columns = text
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let size = Vec2::new(
+        (columns as f32 * ADVANCE - (ADVANCE - GLYPH_WIDTH)).max(0.),
+        lines as f32 * LINE_HEIGHT - (LINE_HEIGHT - CAP_HEIGHT),
+    );
+    let origin = (anchor + 0.5) * size;
+
+    text.lines().enumerate().flat_map(move |(row, line)| {
+        let baseline = size.y - CAP_HEIGHT - row as f32 * LINE_HEIGHT;
+        line.chars().enumerate().flat_map(move |(column, c)| {
+            let glyph_origin = Vec2::new(column as f32 * ADVANCE, baseline) - origin;
+            glyph(c).iter().flat_map(move |stroke| {
+                stroke.windows(2).map(move |points| {
+                    [points[0], points[1]]
+                        .map(|(x, y)| glyph_origin + Vec2::new(x as f32, y as f32))
+                })
+            })
+        })
+    })
+}
+
+type Glyph = &'static [&'static [(i8, i8)]];
+
+const O: &[(i8, i8)] = &[
+    (1, 0),
+    (0, 1),
+    (0, 5),
+    (1, 6),
+    (3, 6),
+    (4, 5),
+    (4, 1),
+    (3, 0),
+    (1, 0),
+];
+const P: &[(i8, i8)] = &[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)];
+const S: &[(i8, i8)] = &[
+    (4, 5),
+    (3, 6),
+    (1, 6),
+    (0, 5),
+    (0, 4),
+    (1, 3),
+    (3, 3),
+    (4, 2),
+    (4, 1),
+    (3, 0),
+    (1, 0),
+    (0, 1),
+];
+
+/// Returns the strokes of the character `c`, on a grid 4 units wide with capitals 6 units tall.
+///
+/// Characters without a glyph are drawn as a box.
+#[rustfmt::skip]
+fn glyph(c: char) -> Glyph {
+    match c.to_ascii_uppercase() {
+        ' ' => &[],
+        '!' => &[&[(2, 6), (2, 2)], &[(2, 1), (2, 0)]],
+        '"' => &[&[(1, 6), (1, 4)], &[(3, 6), (3, 4)]],
+        '#' => &[&[(1, 0), (1, 6)], &[(3, 0), (3, 6)], &[(0, 2), (4, 2)], &[(0, 4), (4, 4)]],
+        '$' => &[S, &[(2, 7), (2, -1)]],
+        '%' => &[&[(0, 0), (4, 6)], &[(0, 6), (0, 5)], &[(4, 1), (4, 0)]],
+        '&' => &[&[(4, 0), (1, 5), (2, 6), (3, 5), (0, 2), (0, 1), (1, 0), (2, 0), (4, 2)]],
+        '\'' => &[&[(2, 6), (2, 4)]],
+        '(' => &[&[(3, 6), (1, 4), (1, 2), (3, 0)]],
+        ')' => &[&[(1, 6), (3, 4), (3, 2), (1, 0)]],
+        '*' => &[&[(2, 1), (2, 5)], &[(0, 2), (4, 4)], &[(0, 4), (4, 2)]],
+        '+' => &[&[(2, 1), (2, 5)], &[(0, 3), (4, 3)]],
+        ',' => &[&[(2, 1), (1, -1)]],
+        '-' => &[&[(0, 3), (4, 3)]],
+        '.' => &[&[(2, 0), (2, 1)]],
+        '/' => &[&[(0, 0), (4, 6)]],
+        '0' => &[O, &[(0, 1), (4, 5)]],
+        '1' => &[&[(1, 5), (2, 6), (2, 0)], &[(1, 0), (3, 0)]],
+        '2' => &[&[(0, 5), (1, 6), (3, 6), (4, 5), (4, 4), (0, 0), (4, 0)]],
+        '3' => &[&[(0, 5), (1, 6), (3, 6), (4, 5), (4, 4), (3, 3), (4, 2), (4, 1), (3, 0), (1, 0), (0, 1)], &[(1, 3), (3, 3)]],
+        '4' => &[&[(3, 0), (3, 6), (0, 2), (4, 2)]],
+        '5' => &[&[(4, 6), (0, 6), (0, 3), (3, 3), (4, 2), (4, 1), (3, 0), (0, 0)]],
+        '6' => &[&[(4, 5), (3, 6), (1, 6), (0, 5), (0, 1), (1, 0), (3, 0), (4, 1), (4, 2), (3, 3), (0, 3)]],
+        '7' => &[&[(0, 6), (4, 6), (1, 0)]],
+        '8' => &[&[(1, 3), (0, 4), (0, 5), (1, 6), (3, 6), (4, 5), (4, 4), (3, 3), (1, 3), (0, 2), (0, 1), (1, 0), (3, 0), (4, 1), (4, 2), (3, 3)]],
+        '9' => &[&[(4, 3), (1, 3), (0, 4), (0, 5), (1, 6), (3, 6), (4, 5), (4, 1), (3, 0), (1, 0), (0, 1)]],
+        ':' => &[&[(2, 1), (2, 2)], &[(2, 4), (2, 5)]],
+        ';' => &[&[(2, 4), (2, 5)], &[(2, 1), (1, -1)]],
+        '<' => &[&[(4, 6), (0, 3), (4, 0)]],
+        '=' => &[&[(0, 2), (4, 2)], &[(0, 4), (4, 4)]],
+        '>' => &[&[(0, 6), (4, 3), (0, 0)]],
+        '?' => &[&[(0, 5), (1, 6), (3, 6), (4, 5), (4, 4), (2, 3), (2, 2)], &[(2, 1), (2, 0)]],
+        '@' => &[&[(3, 2), (1, 2), (1, 4), (3, 4), (3, 1), (4, 1), (4, 5), (3, 6), (1, 6), (0, 5), (0, 1), (1, 0), (4, 0)]],
+        'A' => &[&[(0, 0), (0, 4), (2, 6), (4, 4), (4, 0)], &[(0, 3), (4, 3)]],
+        'B' => &[&[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)], &[(3, 3), (4, 2), (4, 1), (3, 0), (0, 0)]],
+        'C' => &[&[(4, 5), (3, 6), (1, 6), (0, 5), (0, 1), (1, 0), (3, 0), (4, 1)]],
+        'D' => &[&[(0, 0), (0, 6), (2, 6), (4, 4), (4, 2), (2, 0), (0, 0)]],
+        'E' => &[&[(4, 6), (0, 6), (0, 0), (4, 0)], &[(0, 3), (3, 3)]],
+        'F' => &[&[(4, 6), (0, 6), (0, 0)], &[(0, 3), (3, 3)]],
+        'G' => &[&[(4, 5), (3, 6), (1, 6), (0, 5), (0, 1), (1, 0), (3, 0), (4, 1), (4, 3), (2, 3)]],
+        'H' => &[&[(0, 0), (0, 6)], &[(4, 0), (4, 6)], &[(0, 3), (4, 3)]],
+        'I' => &[&[(1, 6), (3, 6)], &[(2, 6), (2, 0)], &[(1, 0), (3, 0)]],
+        'J' => &[&[(4, 6), (4, 1), (3, 0), (1, 0), (0, 1)]],
+        'K' => &[&[(0, 0), (0, 6)], &[(4, 6), (0, 2)], &[(1, 3), (4, 0)]],
+        'L' => &[&[(0, 6), (0, 0), (4, 0)]],
+        'M' => &[&[(0, 0), (0, 6), (2, 3), (4, 6), (4, 0)]],
+        'N' => &[&[(0, 0), (0, 6), (4, 0), (4, 6)]],
+        'O' => &[O],
+        'P' => &[P],
+        'Q' => &[O, &[(2, 2), (4, 0)]],
+        'R' => &[P, &[(2, 3), (4, 0)]],
+        'S' => &[S],
+        'T' => &[&[(0, 6), (4, 6)], &[(2, 6), (2, 0)]],
+        'U' => &[&[(0, 6), (0, 1), (1, 0), (3, 0), (4, 1), (4, 6)]],
+        'V' => &[&[(0, 6), (2, 0), (4, 6)]],
+        'W' => &[&[(0, 6), (1, 0), (2, 3), (3, 0), (4, 6)]],
+        'X' => &[&[(0, 0), (4, 6)], &[(0, 6), (4, 0)]],
+        'Y' => &[&[(0, 6), (2, 3), (4, 6)], &[(2, 3), (2, 0)]],
+        'Z' => &[&[(0, 6), (4, 6), (0, 0), (4, 0)]],
+        '[' => &[&[(3, 6), (1, 6), (1, 0), (3, 0)]],
+        '\\' => &[&[(0, 6), (4, 0)]],
+        ']' => &[&[(1, 6), (3, 6), (3, 0), (1, 0)]],
+        '^' => &[&[(0, 4), (2, 6), (4, 4)]],
+        '_' => &[&[(0, -1), (4, -1)]],
+        '`' => &[&[(1, 6), (2, 5)]],
+        '{' => &[&[(3, 6), (2, 5), (2, 4), (1, 3), (2, 2), (2, 1), (3, 0)]],
+        '|' => &[&[(2, -1), (2, 7)]],
+        '}' => &[&[(1, 6), (2, 5), (2, 4), (3, 3), (2, 2), (2, 1), (1, 0)]],
+        '~' => &[&[(0, 3), (1, 4), (3, 2), (4, 3)]],
+        _ => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)]],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::Quat;
+    use bevy_transform::components::Transform;
+
+    use super::*;
+    use crate::gizmos::tests::draw;
+
+    fn segments(text: &str, anchor: Vec2) -> Vec<[Vec2; 2]> {
+        text_segments(text, anchor).collect()
+    }
+
+    #[test]
+    fn glyphs_fit_the_grid() {
+        for c in ' '..='~' {
+            for stroke in glyph(c) {
+                assert!(stroke.len() >= 2, "{c:?}");
+                for &(x, y) in *stroke {
+                    assert!((0..=4).contains(&x) && (-1..=7).contains(&y), "{c:?}");
+                }
+            }
+        }
+        assert_eq!(glyph('a'), glyph('A'));
+        assert!(glyph(' ').is_empty());
+        // Characters without a glyph are drawn as a box
+        assert_eq!(glyph('é'), glyph('\u{7f}'));
+        assert_eq!(glyph('é').len(), 1);
+    }
+
+    #[test]
+    fn glyph_segments() {
+        // Anchored at its bottom-left corner, a glyph keeps its font units
+        assert_eq!(
+            segments("L", Vec2::splat(-0.5)),
+            [
+                [Vec2::new(0., 6.), Vec2::ZERO],
+                [Vec2::ZERO, Vec2::new(4., 0.)]
+            ]
+        );
+        assert_eq!(
+            segments("-", Vec2::ZERO),
+            [[Vec2::new(-2., 0.), Vec2::new(2., 0.)]]
+        );
+        // A stroke of n points is drawn with n - 1 segments
+        assert_eq!(segments("HI!", Vec2::ZERO).len(), 8);
+        assert!(segments(" \n ", Vec2::ZERO).is_empty());
+        assert!(segments("", Vec2::ZERO).is_empty());
+    }
+
+    #[test]
+    fn anchors_place_the_bounds_of_the_text() {
+        // Two lines of two characters are 10 units wide and 16 units tall
+        for (anchor, min, max) in [
+            (Vec2::splat(-0.5), Vec2::ZERO, Vec2::new(10., 16.)),
+            (Vec2::ZERO, Vec2::new(-5., -8.), Vec2::new(5., 8.)),
+            (Vec2::splat(0.5), Vec2::new(-10., -16.), Vec2::ZERO),
+        ] {
+            let bounds = segments("8#\nHE", anchor)
+                .into_iter()
+                .flatten()
+                .fold((Vec2::MAX, Vec2::MIN), |(min, max), point| {
+                    (min.min(point), max.max(point))
+                });
+            assert_eq!(bounds, (min, max), "anchored at {anchor}");
+        }
+    }
+
+    #[test]
+    fn billboards_face_the_camera() {
+        let text = GizmoText {
+            text: String::new(),
+            placement: TextPlacement::Billboard(Vec3::Y),
+            color: Color::WHITE,
+            size: CAP_HEIGHT,
+            anchor: Vec2::ZERO,
+        };
+        let point = TextTransform::new(&text, None)
+            .unwrap()
+            .apply(Vec2::new(1., 2.));
+        assert_eq!(point, Some(Vec3::new(1., 3., 0.)));
+
+        let camera = Camera::default();
+        let transform =
+            GlobalTransform::from(Transform::from_rotation(Quat::from_rotation_y(FRAC_PI_2)));
+        let point = TextTransform::new(&text, Some((&camera, &transform)))
+            .unwrap()
+            .apply(Vec2::new(1., 2.))
+            .unwrap();
+        assert!(point.abs_diff_eq(Vec3::new(0., 3., -1.), 1e-6));
+    }
+
+    #[test]
+    fn texts_are_drawn_as_lines() {
+        let mut world = draw(|gizmos| {
+            gizmos
+                .text_2d(Vec2::new(10., 0.), "-", Color::RED)
+                .size(12.);
+            // Texts with a constant size on screen aren't drawn without a camera
+            gizmos.annotation(Vec3::ZERO, "-", Color::RED);
+            gizmos.screen_text(Vec2::ZERO, "-", Color::RED);
+        });
+        assert_eq!(world.resource::<GizmoStorage>().texts.len(), 3);
+        world.run_system_once(draw_gizmo_texts);
+
+        let storage = world.resource::<GizmoStorage>();
+        assert!(storage.texts.is_empty());
+        // Font units are scaled to the size of the text
+        assert_eq!(storage.list_positions, [[6., 0., 0.], [14., 0., 0.]]);
+        assert_eq!(storage.list_colors, [Color::RED.as_linear_rgba_f32(); 2]);
+    }
+}
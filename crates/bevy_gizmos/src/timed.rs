@@ -0,0 +1,251 @@
+//! Timed gizmos, drawn for a duration or a number of frames.
+//!
+//! One-shot events, such as hits or raycasts, only happen for a single frame, which is too short
+//! for their gizmos to be seen. Drawing them with [`Gizmos::timed`], or one of the `_timed`
+//! shorthands, keeps them visible until they expire.
+
+use crate::{gizmos::GizmoStorage, prelude::Gizmos, retained::GizmoGeometry};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_math::{Quat, Vec3};
+use bevy_render::color::Color;
+use bevy_time::Time;
+
+/// How long timed gizmos are drawn, see [`Gizmos::timed`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GizmoLifetime {
+    /// The gizmos are drawn for this many seconds of [`Time`].
+    ///
+    /// They are always drawn for at least the frame they were submitted in.
+    Seconds(f32),
+    /// The gizmos are drawn for this many frames.
+    Frames(u32),
+}
+
+impl GizmoLifetime {
+    /// Advances the lifetime by a frame of `delta_seconds`, returning `false` once expired.
+    fn advance(&mut self, delta_seconds: f32) -> bool {
+        match self {
+            Self::Seconds(seconds) => {
+                *seconds -= delta_seconds;
+                *seconds > 0.
+            }
+            Self::Frames(frames) => {
+                *frames = frames.saturating_sub(1);
+                *frames > 0
+            }
+        }
+    }
+}
+
+/// The timed gizmos waiting to expire.
+#[derive(Resource, Default)]
+pub(crate) struct TimedGizmos {
+    entries: Vec<(GizmoLifetime, GizmoGeometry)>,
+}
+
+impl TimedGizmos {
+    pub(crate) fn push(&mut self, lifetime: GizmoLifetime, geometry: GizmoGeometry) {
+        self.entries.push((lifetime, geometry));
+    }
+}
+
+impl<'s> Gizmos<'s> {
+    /// Draw the gizmos of `draw` for the given `lifetime`, instead of only for this frame.
+    ///
+    /// Text isn't timed, and is only drawn for this frame.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::{prelude::*, timed::GizmoLifetime};
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.timed(GizmoLifetime::Seconds(2.), |gizmos| {
+    ///         gizmos.circle(Vec3::ZERO, Vec3::Y, 1., Color::RED);
+    ///         gizmos.arrow(Vec3::Y, Vec3::ZERO, Color::RED);
+    ///     });
+    ///
+    ///     // Visible for the next 10 frames.
+    ///     gizmos.timed(GizmoLifetime::Frames(10), |gizmos| {
+    ///         gizmos.line(Vec3::ZERO, Vec3::X, Color::GREEN);
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn timed(&mut self, lifetime: GizmoLifetime, draw: impl FnOnce(&mut Self)) {
+        let start = self.geometry_counts();
+        draw(self);
+        let geometry = self.split_off_geometry(start);
+        self.add_timed(lifetime, geometry);
+    }
+
+    /// Draw a line in 3D from `start` to `end`, for `seconds`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.line_timed(Vec3::ZERO, Vec3::X, Color::GREEN, 2.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn line_timed(&mut self, start: Vec3, end: Vec3, color: Color, seconds: f32) {
+        self.timed(GizmoLifetime::Seconds(seconds), |gizmos| {
+            gizmos.line(start, end, color);
+        });
+    }
+
+    /// Draw a line in 3D from `start` to `start + vector`, for `seconds`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A raycast, kept visible for half a second.
+    ///     gizmos.ray_timed(Vec3::Y, Vec3::X * 10., Color::YELLOW, 0.5);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn ray_timed(&mut self, start: Vec3, vector: Vec3, color: Color, seconds: f32) {
+        self.timed(GizmoLifetime::Seconds(seconds), |gizmos| {
+            gizmos.ray(start, vector, color);
+        });
+    }
+
+    /// Draw an arrow in 3D from `start` to `end`, for `seconds`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.arrow_timed(Vec3::ZERO, Vec3::ONE, Color::GREEN, 2.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn arrow_timed(&mut self, start: Vec3, end: Vec3, color: Color, seconds: f32) {
+        self.timed(GizmoLifetime::Seconds(seconds), |gizmos| {
+            gizmos.arrow(start, end, color);
+        });
+    }
+
+    /// Draw a wireframe sphere in 3D made out of 3 circles, for `seconds`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A hit, kept visible for two seconds.
+    ///     gizmos.sphere_timed(Vec3::ZERO, 0.25, Color::RED, 2.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn sphere_timed(&mut self, position: Vec3, radius: f32, color: Color, seconds: f32) {
+        self.timed(GizmoLifetime::Seconds(seconds), |gizmos| {
+            gizmos.sphere(position, Quat::IDENTITY, radius, color);
+        });
+    }
+}
+
+/// Draws the timed gizmos for this frame, and removes the expired ones.
+pub(crate) fn draw_timed_gizmos(
+    time: Res<Time>,
+    mut timed: ResMut<TimedGizmos>,
+    mut storage: ResMut<GizmoStorage>,
+) {
+    let delta_seconds = time.delta_seconds();
+    timed.entries.retain_mut(|(lifetime, geometry)| {
+        storage.extend_geometry(geometry);
+        lifetime.advance(delta_seconds)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::gizmos::tests::draw;
+
+    #[test]
+    fn lifetimes() {
+        let frames_drawn = |mut lifetime: GizmoLifetime, delta_seconds| {
+            let mut frames = 1;
+            while lifetime.advance(delta_seconds) {
+                frames += 1;
+            }
+            frames
+        };
+        assert_eq!(frames_drawn(GizmoLifetime::Frames(3), 0.1), 3);
+        assert_eq!(frames_drawn(GizmoLifetime::Frames(1), 0.1), 1);
+        // Drawn for at least the frame they were submitted in
+        assert_eq!(frames_drawn(GizmoLifetime::Frames(0), 0.1), 1);
+        assert_eq!(frames_drawn(GizmoLifetime::Seconds(1.), 0.3), 4);
+        assert_eq!(frames_drawn(GizmoLifetime::Seconds(1.), 2.), 1);
+        assert_eq!(frames_drawn(GizmoLifetime::Seconds(0.), 0.1), 1);
+    }
+
+    #[test]
+    fn timed_gizmos_are_split_off() {
+        let world = draw(|gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::Y, Color::RED);
+            gizmos.line_timed(Vec3::ZERO, Vec3::X, Color::GREEN, 1.);
+            gizmos.timed(GizmoLifetime::Frames(2), |gizmos| {
+                gizmos.linestrip([Vec3::X, Vec3::Y, Vec3::Z], Color::BLUE);
+            });
+        });
+
+        let storage = world.resource::<GizmoStorage>();
+        assert_eq!(storage.list_positions, [[0., 0., 0.], [0., 1., 0.]]);
+        assert!(storage.strip_positions.is_empty());
+
+        let timed = &world.resource::<TimedGizmos>().entries;
+        assert_eq!(timed.len(), 2);
+        assert_eq!(timed[0].0, GizmoLifetime::Seconds(1.));
+        assert_eq!(timed[0].1.list_positions, [[0., 0., 0.], [1., 0., 0.]]);
+        assert_eq!(
+            timed[0].1.list_colors,
+            [Color::GREEN.as_linear_rgba_f32(); 2]
+        );
+        assert_eq!(timed[1].0, GizmoLifetime::Frames(2));
+        assert!(timed[1].1.list_positions.is_empty());
+        assert_eq!(timed[1].1.strip_positions.len(), 4);
+        assert_eq!(timed[1].1.strip_colors.len(), 4);
+    }
+
+    #[test]
+    fn timed_gizmos_are_drawn_until_they_expire() {
+        let mut world = draw(|gizmos| {
+            gizmos.line_timed(Vec3::ZERO, Vec3::X, Color::GREEN, 0.5);
+            gizmos.timed(GizmoLifetime::Frames(2), |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::Y, Color::RED);
+            });
+        });
+        world.init_resource::<Time>();
+
+        // Runs a frame of 0.2 seconds, and returns the lines drawn in it
+        let mut frame = || {
+            world.insert_resource(GizmoStorage::default());
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(200));
+            world.run_system_once(draw_timed_gizmos);
+            world.resource::<GizmoStorage>().list_positions.clone()
+        };
+        let both = [[0., 0., 0.], [1., 0., 0.], [0., 0., 0.], [0., 1., 0.]];
+        assert_eq!(frame(), both);
+        assert_eq!(frame(), both);
+        assert_eq!(frame(), both[..2]);
+        assert!(frame().is_empty());
+        assert!(world.resource::<TimedGizmos>().entries.is_empty());
+    }
+}
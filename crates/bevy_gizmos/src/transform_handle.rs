@@ -0,0 +1,828 @@
+//! Interactive handles editing the [`Transform`] of entities.
+//!
+//! Add the [`TransformHandlePlugin`] and the [`TransformHandle`] component to an entity to draw
+//! draggable handles on it: axes and planes to translate it, rings to rotate it, and axes to
+//! scale it. The handles are picked and dragged with the ray of the [`TransformHandleInput`]
+//! resource, which follows the cursor of the primary window by default.
+//!
+//! # Example
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_gizmos::transform_handle::*;
+//! # use bevy_transform::prelude::*;
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((
+//!         TransformBundle::default(),
+//!         TransformHandle {
+//!             mode: TransformHandleMode::Rotate,
+//!             ..Default::default()
+//!         },
+//!     ));
+//! }
+//! # bevy_ecs::system::assert_is_system(setup);
+//! ```
+
+use crate::prelude::Gizmos;
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    reflect::{ReflectComponent, ReflectResource},
+    schedule::IntoSystemConfigs,
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy_input::{mouse::MouseButton, ButtonInput, InputSystem};
+use bevy_math::{primitives::Plane3d, Quat, Ray3d, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{camera::Camera, color::Color};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+use bevy_window::{PrimaryWindow, Window};
+
+/// A [`Plugin`] that lets the [`TransformHandle`] of entities be dragged to edit their
+/// [`Transform`].
+pub struct TransformHandlePlugin;
+
+impl Plugin for TransformHandlePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransformHandle>()
+            .register_type::<TransformHandleSettings>()
+            .init_resource::<TransformHandleSettings>()
+            .init_resource::<TransformHandleInput>()
+            .init_resource::<TransformHandleState>()
+            .add_systems(
+                PreUpdate,
+                update_transform_handle_input
+                    .after(InputSystem)
+                    .run_if(|settings: Res<TransformHandleSettings>| settings.cursor_input),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_transform_handles.before(TransformSystem::TransformPropagate),
+                    draw_transform_handles.after(TransformSystem::TransformPropagate),
+                ),
+            );
+    }
+}
+
+/// The settings of the [`TransformHandlePlugin`].
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct TransformHandleSettings {
+    /// Whether the [`TransformHandleInput`] follows the cursor of the primary window.
+    ///
+    /// Set to `false` to fill the input from another source, such as a VR controller.
+    ///
+    /// Defaults to `true`.
+    pub cursor_input: bool,
+    /// The mouse button dragging the handles, when `cursor_input` is `true`.
+    ///
+    /// Defaults to [`MouseButton::Left`].
+    pub button: MouseButton,
+    /// How far from a handle the ray can pass and still pick it, relative to the
+    /// [`TransformHandle::size`].
+    ///
+    /// Defaults to `0.08`.
+    pub pick_tolerance: f32,
+}
+
+impl Default for TransformHandleSettings {
+    fn default() -> Self {
+        Self {
+            cursor_input: true,
+            button: MouseButton::Left,
+            pick_tolerance: 0.08,
+        }
+    }
+}
+
+/// Add this [`Component`] to an entity to draw handles editing its [`Transform`].
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct TransformHandle {
+    /// What the handles edit.
+    pub mode: TransformHandleMode,
+    /// Whether the axes of the handles are the world axes, or the axes of the entity.
+    ///
+    /// Scale handles always use the axes of the entity.
+    pub space: TransformHandleSpace,
+    /// The length of the axes and the radius of the rings, in world units.
+    ///
+    /// Defaults to `1.0`.
+    pub size: f32,
+}
+
+impl Default for TransformHandle {
+    fn default() -> Self {
+        Self {
+            mode: Default::default(),
+            space: Default::default(),
+            size: 1.,
+        }
+    }
+}
+
+/// What a [`TransformHandle`] edits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum TransformHandleMode {
+    /// Draggable axes and planes move the entity.
+    #[default]
+    Translate,
+    /// Draggable rings rotate the entity around its axes.
+    Rotate,
+    /// Draggable axes scale the entity along its axes.
+    Scale,
+}
+
+/// The axes of a [`TransformHandle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum TransformHandleSpace {
+    /// The handles follow the world axes.
+    #[default]
+    World,
+    /// The handles follow the rotation of the entity.
+    Local,
+}
+
+/// A draggable part of a [`TransformHandle`].
+///
+/// The axes are indexed `0` for X, `1` for Y and `2` for Z.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransformHandlePart {
+    /// The axis translating or scaling along it.
+    Axis(usize),
+    /// The square translating in the plane with this normal axis.
+    Plane(usize),
+    /// The ring rotating around this axis.
+    Ring(usize),
+}
+
+/// The ray and the button dragging the [`TransformHandle`]s.
+///
+/// It is updated from the cursor of the primary window unless
+/// [`TransformHandleSettings::cursor_input`] is `false`.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct TransformHandleInput {
+    /// The ray picking the handles, in world space.
+    pub ray: Option<Ray3d>,
+    /// Whether the handles are being dragged.
+    pub pressed: bool,
+}
+
+/// The state of the interaction with the [`TransformHandle`]s.
+///
+/// Other tools, such as the selection of an editor, can use it to ignore the input while a
+/// handle is hovered or dragged.
+#[derive(Resource, Debug, Default)]
+pub struct TransformHandleState {
+    hovered: Option<(Entity, TransformHandlePart)>,
+    drag: Option<Drag>,
+    was_pressed: bool,
+}
+
+impl TransformHandleState {
+    /// Returns the entity and the part of the handle under the ray, when not dragging.
+    pub fn hovered(&self) -> Option<(Entity, TransformHandlePart)> {
+        self.hovered
+    }
+
+    /// Returns the entity and the part of the handle being dragged.
+    pub fn dragged(&self) -> Option<(Entity, TransformHandlePart)> {
+        self.drag.as_ref().map(|drag| (drag.entity, drag.part))
+    }
+
+    /// Returns the entity and the part of the handle being dragged, or else hovered.
+    fn active(&self) -> Option<(Entity, TransformHandlePart)> {
+        self.dragged().or(self.hovered)
+    }
+}
+
+/// A handle being dragged.
+#[derive(Debug)]
+struct Drag {
+    entity: Entity,
+    part: TransformHandlePart,
+    mode: TransformHandleMode,
+    center: Vec3,
+    axes: [Vec3; 3],
+    /// The transform of the entity when the drag started.
+    start: Transform,
+    /// The transform of the parent of the entity, from its [`GlobalTransform`].
+    parent: GlobalTransform,
+    /// The position of the ray on the dragged part when the drag started.
+    grab: Grab,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Grab {
+    /// The distance along an axis.
+    Axis(f32),
+    /// The point in a plane.
+    Plane(Vec3),
+    /// The angle around an axis.
+    Ring(f32),
+}
+
+/// Updates the [`TransformHandleInput`] from the cursor of the primary window, as seen by the
+/// active camera with the highest order.
+fn update_transform_handle_input(
+    settings: Res<TransformHandleSettings>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut input: ResMut<TransformHandleInput>,
+) {
+    let camera = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order);
+    input.ray = windows
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .zip(camera)
+        .and_then(|(cursor, (camera, transform))| camera.viewport_to_world(transform, cursor));
+    input.pressed = buttons.pressed(settings.button);
+}
+
+/// Picks the hovered handle, and drags it to edit the [`Transform`] of its entity.
+fn update_transform_handles(
+    settings: Res<TransformHandleSettings>,
+    input: Res<TransformHandleInput>,
+    mut state: ResMut<TransformHandleState>,
+    mut handles: Query<(Entity, &mut Transform, &GlobalTransform, &TransformHandle)>,
+) {
+    let state = &mut *state;
+    let just_pressed = input.pressed && !state.was_pressed;
+    state.was_pressed = input.pressed;
+    if !input.pressed {
+        state.drag = None;
+    }
+
+    if let Some(drag) = &state.drag {
+        state.hovered = None;
+        if let (Some(ray), Ok((_, mut transform, _, _))) = (input.ray, handles.get_mut(drag.entity))
+        {
+            if let Some(dragged) = drag.apply(ray) {
+                *transform = dragged;
+            }
+        }
+        return;
+    }
+
+    let Some(ray) = input.ray else {
+        state.hovered = None;
+        return;
+    };
+    let hovered = handles
+        .iter()
+        .filter_map(|(entity, transform, global_transform, handle)| {
+            let center = global_transform.translation();
+            let axes = handle_axes(global_transform, handle);
+            let tolerance = settings.pick_tolerance * handle.size;
+            pick_part(ray, center, axes, handle.mode, handle.size, tolerance)
+                .map(|(part, distance)| (distance, entity, part, *transform, *global_transform))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+    state.hovered = hovered.map(|(_, entity, part, ..)| (entity, part));
+
+    if !just_pressed {
+        return;
+    }
+    let Some((_, entity, part, transform, global_transform)) = hovered else {
+        return;
+    };
+    let Ok((.., handle)) = handles.get(entity) else {
+        return;
+    };
+    let center = global_transform.translation();
+    let axes = handle_axes(&global_transform, handle);
+    let Some(grab) = grab(ray, center, axes, part) else {
+        return;
+    };
+    state.drag = Some(Drag {
+        entity,
+        part,
+        mode: handle.mode,
+        center,
+        axes,
+        start: transform,
+        parent: GlobalTransform::from(
+            global_transform.affine() * transform.compute_affine().inverse(),
+        ),
+        grab,
+    });
+}
+
+impl Drag {
+    /// Returns the transform of the entity with the handle dragged to `ray`.
+    fn apply(&self, ray: Ray3d) -> Option<Transform> {
+        let mut transform = self.start;
+        match (self.grab, grab(ray, self.center, self.axes, self.part)?) {
+            (Grab::Axis(start), Grab::Axis(current)) => {
+                let TransformHandlePart::Axis(axis) = self.part else {
+                    return None;
+                };
+                if self.mode == TransformHandleMode::Scale {
+                    if start.abs() <= f32::EPSILON {
+                        return None;
+                    }
+                    transform.scale[axis] = self.start.scale[axis] * current / start;
+                } else {
+                    let delta = self.axes[axis] * (current - start);
+                    transform.translation = self.local_translation(delta);
+                }
+            }
+            (Grab::Plane(start), Grab::Plane(current)) => {
+                transform.translation = self.local_translation(current - start);
+            }
+            (Grab::Ring(start), Grab::Ring(current)) => {
+                let TransformHandlePart::Ring(axis) = self.part else {
+                    return None;
+                };
+                let rotation = Quat::from_axis_angle(self.axes[axis], current - start);
+                let (_, parent_rotation, _) = self.parent.to_scale_rotation_translation();
+                transform.rotation =
+                    (parent_rotation.inverse() * rotation * parent_rotation * self.start.rotation)
+                        .normalize();
+            }
+            _ => return None,
+        }
+        Some(transform)
+    }
+
+    /// Returns the translation of the entity relative to its parent, once moved by `delta` in
+    /// world space.
+    fn local_translation(&self, delta: Vec3) -> Vec3 {
+        let center = self.center + delta;
+        self.parent.affine().inverse().transform_point3(center)
+    }
+}
+
+/// Returns the axes of the handle of an entity, in world space.
+fn handle_axes(global_transform: &GlobalTransform, handle: &TransformHandle) -> [Vec3; 3] {
+    match (handle.mode, handle.space) {
+        (TransformHandleMode::Scale, _) | (_, TransformHandleSpace::Local) => {
+            let (_, rotation, _) = global_transform.to_scale_rotation_translation();
+            Vec3::AXES.map(|axis| rotation * axis)
+        }
+        (_, TransformHandleSpace::World) => Vec3::AXES,
+    }
+}
+
+/// Returns the part of a handle hit by `ray`, and the distance along the ray.
+fn pick_part(
+    ray: Ray3d,
+    center: Vec3,
+    axes: [Vec3; 3],
+    mode: TransformHandleMode,
+    size: f32,
+    tolerance: f32,
+) -> Option<(TransformHandlePart, f32)> {
+    let mut parts = Vec::new();
+    match mode {
+        TransformHandleMode::Translate | TransformHandleMode::Scale => {
+            for (index, axis) in axes.into_iter().enumerate() {
+                if let Some((distance, along_ray)) = ray_segment_distance(ray, center, axis, size) {
+                    if distance <= tolerance {
+                        parts.push((TransformHandlePart::Axis(index), along_ray));
+                    }
+                }
+            }
+        }
+        TransformHandleMode::Rotate => {}
+    }
+    for (index, normal) in axes.into_iter().enumerate() {
+        let Some(along_ray) = ray.intersect_plane(center, Plane3d::new(normal)) else {
+            continue;
+        };
+        let offset = ray.get_point(along_ray) - center;
+        match mode {
+            TransformHandleMode::Translate => {
+                let [u, v] = plane_axes(axes, index);
+                let inside =
+                    |coordinate: f32| (PLANE_START * size..=PLANE_END * size).contains(&coordinate);
+                if inside(offset.dot(u)) && inside(offset.dot(v)) {
+                    parts.push((TransformHandlePart::Plane(index), along_ray));
+                }
+            }
+            TransformHandleMode::Rotate => {
+                if (offset.length() - size).abs() <= tolerance {
+                    parts.push((TransformHandlePart::Ring(index), along_ray));
+                }
+            }
+            TransformHandleMode::Scale => {}
+        }
+    }
+    parts.into_iter().min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Where the plane handles start and end along their axes, relative to the size of the handle.
+const PLANE_START: f32 = 0.2;
+const PLANE_END: f32 = 0.4;
+
+/// Returns the two axes spanning the plane with the normal `axes[index]`.
+fn plane_axes(axes: [Vec3; 3], index: usize) -> [Vec3; 2] {
+    [axes[(index + 1) % 3], axes[(index + 2) % 3]]
+}
+
+/// Returns where `ray` is on the `part` of a handle.
+fn grab(ray: Ray3d, center: Vec3, axes: [Vec3; 3], part: TransformHandlePart) -> Option<Grab> {
+    match part {
+        TransformHandlePart::Axis(index) => {
+            closest_line_parameter(ray, center, axes[index]).map(Grab::Axis)
+        }
+        TransformHandlePart::Plane(index) => ray
+            .intersect_plane(center, Plane3d::new(axes[index]))
+            .map(|distance| Grab::Plane(ray.get_point(distance))),
+        TransformHandlePart::Ring(index) => {
+            let distance = ray.intersect_plane(center, Plane3d::new(axes[index]))?;
+            let offset = ray.get_point(distance) - center;
+            let [u, v] = plane_axes(axes, index);
+            Some(Grab::Ring(offset.dot(v).atan2(offset.dot(u))))
+        }
+    }
+}
+
+/// Returns the parameter of the point of the line through `origin` along the unit vector
+/// `axis` closest to `ray`, or `None` if they are parallel.
+fn closest_line_parameter(ray: Ray3d, origin: Vec3, axis: Vec3) -> Option<f32> {
+    let direction = *ray.direction;
+    let offset = ray.origin - origin;
+    let cos = axis.dot(direction);
+    let denominator = 1. - cos * cos;
+    if denominator <= 1e-6 {
+        return None;
+    }
+    let along_ray = (cos * offset.dot(axis) - offset.dot(direction)) / denominator;
+    Some(offset.dot(axis) + cos * along_ray)
+}
+
+/// Returns the distance between `ray` and the segment from `origin` to `origin + axis * length`,
+/// and the distance along the ray of the closest point.
+fn ray_segment_distance(ray: Ray3d, origin: Vec3, axis: Vec3, length: f32) -> Option<(f32, f32)> {
+    let parameter = closest_line_parameter(ray, origin, axis)?.clamp(0., length);
+    let point = origin + axis * parameter;
+    let along_ray = (point - ray.origin).dot(*ray.direction).max(0.);
+    Some((ray.get_point(along_ray).distance(point), along_ray))
+}
+
+const AXIS_COLORS: [Color; 3] = [
+    Color::rgb(0.9, 0.2, 0.2),
+    Color::rgb(0.3, 0.8, 0.2),
+    Color::rgb(0.2, 0.4, 0.9),
+];
+const ACTIVE_COLOR: Color = Color::YELLOW;
+
+/// Draws the [`TransformHandle`]s, highlighting the hovered or dragged part.
+fn draw_transform_handles(
+    handles: Query<(Entity, &GlobalTransform, &TransformHandle)>,
+    state: Res<TransformHandleState>,
+    mut gizmos: Gizmos,
+) {
+    for (entity, global_transform, handle) in &handles {
+        let center = global_transform.translation();
+        let axes = handle_axes(global_transform, handle);
+        let size = handle.size;
+        let color = |part: TransformHandlePart, index: usize| {
+            if state.active() == Some((entity, part)) {
+                ACTIVE_COLOR
+            } else {
+                AXIS_COLORS[index]
+            }
+        };
+
+        match handle.mode {
+            TransformHandleMode::Translate => {
+                for (index, axis) in axes.into_iter().enumerate() {
+                    gizmos.arrow(
+                        center,
+                        center + axis * size,
+                        color(TransformHandlePart::Axis(index), index),
+                    );
+                    let [u, v] = plane_axes(axes, index);
+                    let corners = [
+                        (PLANE_START, PLANE_START),
+                        (PLANE_END, PLANE_START),
+                        (PLANE_END, PLANE_END),
+                        (PLANE_START, PLANE_END),
+                    ]
+                    .map(|(a, b)| center + (u * a + v * b) * size);
+                    gizmos.quad(
+                        corners,
+                        color(TransformHandlePart::Plane(index), index).with_a(0.4),
+                    );
+                }
+            }
+            TransformHandleMode::Rotate => {
+                for (index, axis) in axes.into_iter().enumerate() {
+                    gizmos
+                        .circle(
+                            center,
+                            axis,
+                            size,
+                            color(TransformHandlePart::Ring(index), index),
+                        )
+                        .segments(64);
+                }
+            }
+            TransformHandleMode::Scale => {
+                let (_, rotation, _) = global_transform.to_scale_rotation_translation();
+                for (index, axis) in axes.into_iter().enumerate() {
+                    let color = color(TransformHandlePart::Axis(index), index);
+                    let end = center + axis * size;
+                    gizmos.line(center, end, color);
+                    gizmos.solid_cuboid(
+                        Transform::from_translation(end)
+                            .with_rotation(rotation)
+                            .with_scale(Vec3::splat(size * 0.1)),
+                        color,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::{gizmos::GizmoStorage, retained::GizmoGroups, timed::TimedGizmos};
+
+    /// A ray looking down at the point `(x, y, 0)`.
+    fn down(x: f32, y: f32) -> Ray3d {
+        Ray3d::new(Vec3::new(x, y, 5.), Vec3::NEG_Z)
+    }
+
+    fn world(mode: TransformHandleMode, transform: Transform, global: GlobalTransform) -> World {
+        let mut world = World::new();
+        world.init_resource::<TransformHandleSettings>();
+        world.init_resource::<TransformHandleInput>();
+        world.init_resource::<TransformHandleState>();
+        world.spawn((
+            transform,
+            global,
+            TransformHandle {
+                mode,
+                ..Default::default()
+            },
+        ));
+        world
+    }
+
+    /// Updates the handles with the ray at `(x, y)`, and returns the transform of the entity.
+    fn update(world: &mut World, x: f32, y: f32, pressed: bool) -> Transform {
+        *world.resource_mut::<TransformHandleInput>() = TransformHandleInput {
+            ray: Some(down(x, y)),
+            pressed,
+        };
+        world.run_system_once(update_transform_handles);
+        *world.query::<&Transform>().single(world)
+    }
+
+    #[test]
+    fn closest_point_on_an_axis() {
+        assert_eq!(
+            closest_line_parameter(down(2., 1.), Vec3::ZERO, Vec3::X),
+            Some(2.)
+        );
+        assert_eq!(
+            closest_line_parameter(down(2., 1.), Vec3::new(1., 0., 0.), Vec3::Y),
+            Some(1.)
+        );
+        assert_eq!(
+            closest_line_parameter(down(2., 1.), Vec3::ZERO, Vec3::Z),
+            None
+        );
+
+        // The segment ends at its length
+        let (distance, along_ray) =
+            ray_segment_distance(down(1.5, 0.), Vec3::ZERO, Vec3::X, 1.).unwrap();
+        assert!((distance - 0.5).abs() < 1e-5);
+        assert!((along_ray - 5.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn axes_follow_the_space_of_the_handle() {
+        let rotation = Quat::from_rotation_z(FRAC_PI_2);
+        let global_transform = GlobalTransform::from(Transform::from_rotation(rotation));
+        let axes = |mode, space| {
+            handle_axes(
+                &global_transform,
+                &TransformHandle {
+                    mode,
+                    space,
+                    size: 1.,
+                },
+            )
+        };
+        assert_eq!(
+            axes(TransformHandleMode::Translate, TransformHandleSpace::World),
+            Vec3::AXES
+        );
+        for (mode, space) in [
+            (TransformHandleMode::Translate, TransformHandleSpace::Local),
+            (TransformHandleMode::Scale, TransformHandleSpace::World),
+        ] {
+            let [x, y, z] = axes(mode, space);
+            assert!(x.abs_diff_eq(Vec3::Y, 1e-6));
+            assert!(y.abs_diff_eq(Vec3::NEG_X, 1e-6));
+            assert!(z.abs_diff_eq(Vec3::Z, 1e-6));
+        }
+        assert_eq!(plane_axes(Vec3::AXES, 0), [Vec3::Y, Vec3::Z]);
+        assert_eq!(plane_axes(Vec3::AXES, 2), [Vec3::X, Vec3::Y]);
+    }
+
+    #[test]
+    fn picking() {
+        let pick = |mode, x, y| {
+            pick_part(down(x, y), Vec3::ZERO, Vec3::AXES, mode, 1., 0.08).map(|(part, _)| part)
+        };
+        let translate = TransformHandleMode::Translate;
+        assert_eq!(pick(translate, 0.5, 0.), Some(TransformHandlePart::Axis(0)));
+        assert_eq!(pick(translate, 0., 0.7), Some(TransformHandlePart::Axis(1)));
+        assert_eq!(
+            pick(translate, 0.3, 0.3),
+            Some(TransformHandlePart::Plane(2))
+        );
+        assert_eq!(pick(translate, 0.5, 0.5), None);
+        // Past the end of the axis
+        assert_eq!(pick(translate, 1.5, 0.), None);
+
+        let rotate = TransformHandleMode::Rotate;
+        assert_eq!(pick(rotate, 0., 1.05), Some(TransformHandlePart::Ring(2)));
+        assert_eq!(pick(rotate, -0.6, -0.8), Some(TransformHandlePart::Ring(2)));
+        assert_eq!(pick(rotate, 0.5, 0.), None);
+
+        let scale = TransformHandleMode::Scale;
+        assert_eq!(pick(scale, 0.5, 0.), Some(TransformHandlePart::Axis(0)));
+        assert_eq!(pick(scale, 0.3, 0.3), None);
+
+        // The ray passes through the X axis at (0.5, 0, 0) and through the square of the YZ plane
+        // at (0, 0.3, 0.3), and picks the closest of them
+        let [axis_point, plane_point] = [Vec3::new(0.5, 0., 0.), Vec3::new(0., 0.3, 0.3)];
+        let length = axis_point.distance(plane_point);
+        for (from, to, part) in [
+            (axis_point, plane_point, TransformHandlePart::Axis(0)),
+            (plane_point, axis_point, TransformHandlePart::Plane(0)),
+        ] {
+            // Starts away from `from`, twice the distance between the points
+            let ray = Ray3d::new(from * 3. - to * 2., to - from);
+            let picked = pick_part(ray, Vec3::ZERO, Vec3::AXES, translate, 1., 0.08).unwrap();
+            assert_eq!(picked.0, part);
+            assert!((picked.1 - length * 2.).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn hover_and_drag_along_an_axis() {
+        // The parent of the entity doubles its size
+        let mut world = world(
+            TransformHandleMode::Translate,
+            Transform::from_xyz(1., 0., 0.),
+            GlobalTransform::from(Transform::from_xyz(2., 0., 0.).with_scale(Vec3::splat(2.))),
+        );
+        let entity = world.query::<Entity>().single(&world);
+
+        update(&mut world, 2.5, 0., false);
+        let state = world.resource::<TransformHandleState>();
+        assert_eq!(
+            state.hovered(),
+            Some((entity, TransformHandlePart::Axis(0)))
+        );
+        assert_eq!(state.dragged(), None);
+
+        update(&mut world, 2.5, 0., true);
+        let state = world.resource::<TransformHandleState>();
+        assert_eq!(state.hovered(), None);
+        assert_eq!(
+            state.dragged(),
+            Some((entity, TransformHandlePart::Axis(0)))
+        );
+
+        // Moving the ray off the axis keeps dragging along it
+        let transform = update(&mut world, 3.5, 0.7, true);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.5, 0., 0.), 1e-5));
+
+        let transform = update(&mut world, 3.5, 0.7, false);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.5, 0., 0.), 1e-5));
+        assert_eq!(world.resource::<TransformHandleState>().dragged(), None);
+    }
+
+    #[test]
+    fn drag_in_a_plane() {
+        let mut world = world(
+            TransformHandleMode::Translate,
+            Transform::IDENTITY,
+            GlobalTransform::IDENTITY,
+        );
+        update(&mut world, 0.3, 0.3, true);
+        let transform = update(&mut world, 1.3, -0.7, true);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1., -1., 0.), 1e-5));
+    }
+
+    #[test]
+    fn drag_a_ring() {
+        let mut world = world(
+            TransformHandleMode::Rotate,
+            Transform::IDENTITY,
+            GlobalTransform::IDENTITY,
+        );
+        update(&mut world, 1., 0., true);
+        let transform = update(&mut world, 0., 2., true);
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2), 1e-5));
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn drag_a_scale_axis() {
+        let mut world = world(
+            TransformHandleMode::Scale,
+            Transform::IDENTITY,
+            GlobalTransform::IDENTITY,
+        );
+        update(&mut world, 0.5, 0., true);
+        let transform = update(&mut world, 1., 0., true);
+        assert!(transform.scale.abs_diff_eq(Vec3::new(2., 1., 1.), 1e-5));
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn rings_are_drawn_with_the_hovered_one_highlighted() {
+        let mut world = world(
+            TransformHandleMode::Rotate,
+            Transform::IDENTITY,
+            GlobalTransform::IDENTITY,
+        );
+        world.init_resource::<GizmoStorage>();
+        world.init_resource::<GizmoGroups>();
+        world.init_resource::<TimedGizmos>();
+        let entity = world.query::<Entity>().single(&world);
+        world.resource_mut::<TransformHandleState>().hovered =
+            Some((entity, TransformHandlePart::Ring(1)));
+        world.run_system_once(draw_transform_handles);
+
+        let storage = world.resource::<GizmoStorage>();
+        // Three rings of 64 segments, each ending with a NaN separator
+        assert_eq!(storage.strip_positions.len(), 3 * 66);
+        for (index, ring) in storage.strip_positions.chunks(66).enumerate() {
+            for position in &ring[..65] {
+                let position = Vec3::from(*position);
+                assert!((position.length() - 1.).abs() < 1e-5);
+                assert!(position.dot(Vec3::AXES[index]).abs() < 1e-5);
+            }
+            assert!(ring[65][0].is_nan());
+        }
+        let colors = &storage.strip_colors;
+        assert_eq!(colors[0], AXIS_COLORS[0].as_linear_rgba_f32());
+        assert_eq!(colors[66], ACTIVE_COLOR.as_linear_rgba_f32());
+        assert_eq!(colors[132], AXIS_COLORS[2].as_linear_rgba_f32());
+    }
+
+    #[test]
+    fn translate_handles_draw_a_square_per_plane() {
+        let mut world = world(
+            TransformHandleMode::Translate,
+            Transform::IDENTITY,
+            GlobalTransform::IDENTITY,
+        );
+        world.init_resource::<GizmoStorage>();
+        world.init_resource::<GizmoGroups>();
+        world.init_resource::<TimedGizmos>();
+        world.run_system_once(draw_transform_handles);
+
+        let storage = world.resource::<GizmoStorage>();
+        // An arrow of five lines per axis
+        assert_eq!(storage.list_positions.len(), 3 * 5 * 2);
+        assert_eq!(storage.list_positions[..2], [[0., 0., 0.], [1., 0., 0.]]);
+        assert_eq!(storage.triangle_positions.len(), 3 * 6);
+        // The square of the XY plane
+        assert_eq!(
+            storage.triangle_positions[12..],
+            [
+                [0.2, 0.2, 0.],
+                [0.4, 0.2, 0.],
+                [0.4, 0.4, 0.],
+                [0.2, 0.2, 0.],
+                [0.4, 0.4, 0.],
+                [0.2, 0.4, 0.]
+            ]
+        );
+    }
+}
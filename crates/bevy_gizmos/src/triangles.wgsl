@@ -0,0 +1,56 @@
+// TODO use common view binding
+#import bevy_render::view::View
+
+@group(0) @binding(0) var<uniform> view: View;
+
+
+struct LineGizmoUniform {
+    transform: mat4x4<f32>,
+    line_width: f32,
+    depth_bias: f32,
+#ifdef SIXTEEN_BYTE_ALIGNMENT
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: vec2<f32>,
+#endif
+}
+
+@group(1) @binding(0) var<uniform> line_gizmo: LineGizmoUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+const EPSILON: f32 = 4.88e-04;
+
+@vertex
+fn vertex(vertex: VertexInput) -> VertexOutput {
+    var clip = view.view_proj * line_gizmo.transform * vec4(vertex.position, 1.);
+
+    // Same depth bias as the lines, see `lines.wgsl`.
+    if line_gizmo.depth_bias >= 0. {
+        clip.z = clip.z * (1. - line_gizmo.depth_bias);
+    } else {
+        clip.z = clip.z * exp2(-line_gizmo.depth_bias * log2(clip.w / clip.z - EPSILON));
+    }
+
+    return VertexOutput(clip, vertex.color);
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+};
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+};
+
+@fragment
+fn fragment(in: FragmentInput) -> FragmentOutput {
+    return FragmentOutput(in.color);
+}
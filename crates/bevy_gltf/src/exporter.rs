@@ -0,0 +1,945 @@
+use crate::loader::reflectance;
+use bevy_asset::{AssetId, Assets, Handle};
+use bevy_core::Name;
+use bevy_ecs::{
+    entity::Entity,
+    world::{EntityRef, World},
+};
+use bevy_hierarchy::{Children, Parent};
+use bevy_log::warn;
+use bevy_math::{Affine2, Quat, Vec3};
+use bevy_pbr::{AlphaMode, DirectionalLight, PointLight, SpotLight, StandardMaterial};
+use bevy_render::{
+    camera::Projection,
+    color::Color,
+    mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues},
+    render_resource::{PrimitiveTopology, VertexFormat},
+    texture::Image,
+};
+use bevy_scene::Scene;
+use bevy_transform::components::Transform;
+use bevy_utils::HashMap;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde_json::{json, Map, Value};
+use std::{borrow::Cow, collections::BTreeSet, f32::consts::PI};
+use thiserror::Error;
+
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::{AnimationClip, AnimationPlayer, EntityPath, Keyframes};
+
+/// An error that occurs when writing a [`GltfExport`].
+#[derive(Error, Debug)]
+pub enum GltfExportError {
+    /// Failed to serialize the glTF JSON.
+    #[error("failed to serialize the glTF JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Failed to write the binary glTF file.
+    #[error("failed to write the binary glTF file: {0}")]
+    Glb(#[from] gltf::Error),
+}
+
+/// Exports entities to glTF, with their [`Transform`], [`Name`], meshes, [`StandardMaterial`]s,
+/// cameras, lights and the animation clips of their [`AnimationPlayer`](bevy_animation::AnimationPlayer)s.
+///
+/// The assets of the entities are read from the [`Assets`] of the world the exporter is created
+/// from, usually the main world of the app:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gltf::GltfExporter;
+/// # use bevy_scene::Scene;
+/// fn export_scene(world: &mut World) {
+///     let scene = Scene::new(World::new());
+///     let glb = GltfExporter::new(world).export_scene(&scene).to_glb().unwrap();
+///     std::fs::write("scene.glb", glb).unwrap();
+/// }
+/// ```
+///
+/// The textures of the materials are not embedded in the export: they are referenced by their asset
+/// path, relative to the asset folder, and textures without a path are skipped. Skins and morph
+/// targets are not exported.
+pub struct GltfExporter<'a> {
+    meshes: Option<&'a Assets<Mesh>>,
+    materials: Option<&'a Assets<StandardMaterial>>,
+    #[cfg(feature = "bevy_animation")]
+    animation_clips: Option<&'a Assets<AnimationClip>>,
+}
+
+impl<'a> GltfExporter<'a> {
+    /// Creates an exporter reading the assets of the entities from `world`.
+    pub fn new(world: &'a World) -> Self {
+        Self {
+            meshes: world.get_resource(),
+            materials: world.get_resource(),
+            #[cfg(feature = "bevy_animation")]
+            animation_clips: world.get_resource(),
+        }
+    }
+
+    /// Exports the entities of `scene`.
+    pub fn export_scene(&self, scene: &Scene) -> GltfExport {
+        let roots: Vec<Entity> = scene
+            .world
+            .iter_entities()
+            .filter(|entity| !entity.contains::<Parent>())
+            .map(|entity| entity.id())
+            .collect();
+        self.export_entities(&scene.world, &roots)
+    }
+
+    /// Exports the entities `roots` of `world`, with their descendants.
+    pub fn export_entities(&self, world: &World, roots: &[Entity]) -> GltfExport {
+        let mut builder = GltfBuilder {
+            exporter: self,
+            world,
+            document: GltfDocument::default(),
+        };
+        let nodes: Vec<usize> = roots
+            .iter()
+            .filter_map(|root| world.get_entity(*root))
+            .map(|root| builder.add_node(root))
+            .collect();
+        #[cfg(feature = "bevy_animation")]
+        builder.add_animations();
+        builder.finish(nodes)
+    }
+}
+
+/// Entities exported to glTF by a [`GltfExporter`].
+pub struct GltfExport {
+    json: Value,
+    buffer: Vec<u8>,
+}
+
+impl GltfExport {
+    /// Returns the glTF JSON of the export, which describes its binary buffer without including it.
+    pub fn json(&self) -> &Value {
+        &self.json
+    }
+
+    /// Returns the binary buffer holding the data of the meshes and animations of the export.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Writes the export as a binary glTF (`.glb`) file.
+    pub fn to_glb(&self) -> Result<Vec<u8>, GltfExportError> {
+        let glb = gltf::Glb {
+            header: gltf::binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                // Computed when writing
+                length: 0,
+            },
+            json: Cow::Owned(serde_json::to_vec(&self.json)?),
+            bin: (!self.buffer.is_empty()).then_some(Cow::Borrowed(self.buffer.as_slice())),
+        };
+        Ok(glb.to_vec()?)
+    }
+
+    /// Writes the export as a glTF (`.gltf`) file, with its binary buffer embedded as a base64 data
+    /// URI.
+    pub fn to_gltf(&self) -> Result<String, GltfExportError> {
+        let mut json = self.json.clone();
+        if let Some(buffer) = json
+            .get_mut("buffers")
+            .and_then(|buffers| buffers.get_mut(0))
+        {
+            buffer["uri"] = format!(
+                "data:application/octet-stream;base64,{}",
+                base64::encode(&self.buffer)
+            )
+            .into();
+        }
+        Ok(serde_json::to_string(&json)?)
+    }
+}
+
+const UNSIGNED_BYTE: u32 = 5121;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const FLOAT: u32 = 5126;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// The vertex attributes exported as their standard glTF attributes.
+const ATTRIBUTES: [(MeshVertexAttribute, &str); 6] = [
+    (Mesh::ATTRIBUTE_POSITION, "POSITION"),
+    (Mesh::ATTRIBUTE_NORMAL, "NORMAL"),
+    (Mesh::ATTRIBUTE_TANGENT, "TANGENT"),
+    (Mesh::ATTRIBUTE_UV_0, "TEXCOORD_0"),
+    (Mesh::ATTRIBUTE_UV_1, "TEXCOORD_1"),
+    (Mesh::ATTRIBUTE_COLOR, "COLOR_0"),
+];
+
+/// Builds the glTF JSON and binary buffer of a [`GltfExport`].
+struct GltfBuilder<'a> {
+    exporter: &'a GltfExporter<'a>,
+    world: &'a World,
+    document: GltfDocument,
+}
+
+/// The top-level arrays of a glTF JSON being built, and the indices of the exported entities and
+/// assets in them.
+#[derive(Default)]
+struct GltfDocument {
+    nodes: Vec<Value>,
+    meshes: Vec<Value>,
+    materials: Vec<Value>,
+    textures: Vec<Value>,
+    images: Vec<Value>,
+    cameras: Vec<Value>,
+    lights: Vec<Value>,
+    animations: Vec<Value>,
+    accessors: Vec<Value>,
+    buffer_views: Vec<Value>,
+    buffer: Vec<u8>,
+    extensions_used: BTreeSet<String>,
+    entity_to_node: HashMap<Entity, usize>,
+    mesh_indices: HashMap<(AssetId<Mesh>, Option<AssetId<StandardMaterial>>), usize>,
+    material_indices: HashMap<AssetId<StandardMaterial>, Option<usize>>,
+    texture_indices: HashMap<AssetId<Image>, Option<usize>>,
+}
+
+impl<'a> GltfBuilder<'a> {
+    /// Adds the node of `entity` and of its descendants, and returns its index.
+    fn add_node(&mut self, entity: EntityRef<'a>) -> usize {
+        let index = self.document.nodes.len();
+        self.document.nodes.push(Value::Null);
+        self.document.entity_to_node.insert(entity.id(), index);
+
+        let mut node = Map::new();
+        if let Some(name) = entity.get::<Name>() {
+            node.insert("name".into(), name.as_str().into());
+        }
+        if let Some(transform) = entity.get::<Transform>() {
+            if transform.translation != Vec3::ZERO {
+                node.insert(
+                    "translation".into(),
+                    json!(transform.translation.to_array()),
+                );
+            }
+            if transform.rotation != Quat::IDENTITY {
+                node.insert("rotation".into(), json!(transform.rotation.to_array()));
+            }
+            if transform.scale != Vec3::ONE {
+                node.insert("scale".into(), json!(transform.scale.to_array()));
+            }
+        }
+        if let Some(mesh) = entity.get::<Handle<Mesh>>() {
+            if let Some(mesh) = self.add_mesh(mesh, entity.get::<Handle<StandardMaterial>>()) {
+                node.insert("mesh".into(), mesh.into());
+            }
+        }
+        if let Some(projection) = entity.get::<Projection>() {
+            node.insert("camera".into(), self.add_camera(projection).into());
+        }
+        if let Some(light) = self.add_light(entity) {
+            node.insert(
+                "extensions".into(),
+                json!({ "KHR_lights_punctual": { "light": light } }),
+            );
+        }
+        if let Some(children) = entity.get::<Children>() {
+            let world = self.world;
+            let children: Vec<usize> = children
+                .iter()
+                .filter_map(|child| world.get_entity(*child))
+                .map(|child| self.add_node(child))
+                .collect();
+            if !children.is_empty() {
+                node.insert("children".into(), children.into());
+            }
+        }
+
+        self.document.nodes[index] = node.into();
+        index
+    }
+
+    /// Adds the glTF mesh of `mesh` with `material`, and returns its index, or `None` if the mesh
+    /// can't be exported.
+    fn add_mesh(
+        &mut self,
+        mesh: &Handle<Mesh>,
+        material: Option<&Handle<StandardMaterial>>,
+    ) -> Option<usize> {
+        let key = (mesh.id(), material.map(Handle::id));
+        if let Some(index) = self.document.mesh_indices.get(&key) {
+            return Some(*index);
+        }
+        let mesh = self.exporter.meshes?.get(mesh)?;
+
+        let mode = match mesh.primitive_topology() {
+            PrimitiveTopology::PointList => 0,
+            PrimitiveTopology::LineList => 1,
+            PrimitiveTopology::LineStrip => 3,
+            PrimitiveTopology::TriangleList => 4,
+            PrimitiveTopology::TriangleStrip => 5,
+        };
+        let mut attributes = Map::new();
+        for (attribute, name) in ATTRIBUTES {
+            let Some(values) = mesh.attribute(attribute) else {
+                continue;
+            };
+            if let Some(accessor) = self.add_vertex_attribute(values) {
+                attributes.insert(name.into(), accessor.into());
+            } else {
+                warn!(
+                    "Vertex attribute {name} with format {:?} is not supported by glTF and was not exported",
+                    VertexFormat::from(values)
+                );
+            }
+        }
+        if !attributes.contains_key("POSITION") {
+            warn!("Mesh without positions was not exported");
+            return None;
+        }
+
+        let mut primitive = json!({ "attributes": attributes, "mode": mode });
+        if let Some(indices) = mesh.indices() {
+            primitive["indices"] = self.add_indices(indices).into();
+        }
+        if let Some(material) = material.and_then(|material| self.add_material(material)) {
+            primitive["material"] = material.into();
+        }
+
+        let index = self.document.meshes.len();
+        self.document
+            .meshes
+            .push(json!({ "primitives": [primitive] }));
+        self.document.mesh_indices.insert(key, index);
+        Some(index)
+    }
+
+    /// Adds the accessor of the vertex attribute `values`, and returns its index, or `None` if its
+    /// format is not supported by glTF.
+    fn add_vertex_attribute(&mut self, values: &VertexAttributeValues) -> Option<usize> {
+        let (component_type, normalized, ty) = match VertexFormat::from(values) {
+            VertexFormat::Float32 => (FLOAT, false, "SCALAR"),
+            VertexFormat::Float32x2 => (FLOAT, false, "VEC2"),
+            VertexFormat::Float32x3 => (FLOAT, false, "VEC3"),
+            VertexFormat::Float32x4 => (FLOAT, false, "VEC4"),
+            VertexFormat::Unorm8x2 => (UNSIGNED_BYTE, true, "VEC2"),
+            VertexFormat::Unorm8x4 => (UNSIGNED_BYTE, true, "VEC4"),
+            VertexFormat::Unorm16x2 => (UNSIGNED_SHORT, true, "VEC2"),
+            VertexFormat::Unorm16x4 => (UNSIGNED_SHORT, true, "VEC4"),
+            _ => return None,
+        };
+        let mut accessor = json!({
+            "componentType": component_type,
+            "count": values.len(),
+            "type": ty,
+        });
+        if normalized {
+            accessor["normalized"] = true.into();
+        }
+        // The bounds of the positions are required by glTF
+        if let VertexAttributeValues::Float32x3(positions) = values {
+            let (min, max) = positions.iter().fold(
+                (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+                |(min, max), position| {
+                    let position = Vec3::from_array(*position);
+                    (min.min(position), max.max(position))
+                },
+            );
+            if !positions.is_empty() {
+                accessor["min"] = json!(min.to_array());
+                accessor["max"] = json!(max.to_array());
+            }
+        }
+        Some(self.add_accessor(values.get_bytes(), Some(ARRAY_BUFFER), accessor))
+    }
+
+    /// Adds the accessor of the vertex `indices`, and returns its index.
+    fn add_indices(&mut self, indices: &Indices) -> usize {
+        let (component_type, bytes): (_, Vec<u8>) = match indices {
+            Indices::U16(indices) => (
+                UNSIGNED_SHORT,
+                indices
+                    .iter()
+                    .flat_map(|index| index.to_le_bytes())
+                    .collect(),
+            ),
+            Indices::U32(indices) => (
+                UNSIGNED_INT,
+                indices
+                    .iter()
+                    .flat_map(|index| index.to_le_bytes())
+                    .collect(),
+            ),
+        };
+        let accessor = json!({
+            "componentType": component_type,
+            "count": indices.len(),
+            "type": "SCALAR",
+        });
+        self.add_accessor(&bytes, Some(ELEMENT_ARRAY_BUFFER), accessor)
+    }
+
+    /// Adds `bytes` to the buffer in a new buffer view, and adds `accessor` reading from it.
+    /// Returns the index of the accessor.
+    fn add_accessor(&mut self, bytes: &[u8], target: Option<u32>, mut accessor: Value) -> usize {
+        let document = &mut self.document;
+        // Accessors must be aligned to the size of their components, which is at most 4 bytes
+        document
+            .buffer
+            .resize(document.buffer.len().div_ceil(4) * 4, 0);
+        let mut buffer_view = json!({
+            "buffer": 0,
+            "byteOffset": document.buffer.len(),
+            "byteLength": bytes.len(),
+        });
+        if let Some(target) = target {
+            buffer_view["target"] = target.into();
+        }
+        document.buffer.extend_from_slice(bytes);
+
+        accessor["bufferView"] = document.buffer_views.len().into();
+        document.buffer_views.push(buffer_view);
+        document.accessors.push(accessor);
+        document.accessors.len() - 1
+    }
+
+    /// Adds the glTF material of `material`, and returns its index, or `None` if the material
+    /// doesn't exist.
+    fn add_material(&mut self, material: &Handle<StandardMaterial>) -> Option<usize> {
+        if let Some(index) = self.document.material_indices.get(&material.id()) {
+            return *index;
+        }
+        let id = material.id();
+        let index = self.export_material(material);
+        self.document.material_indices.insert(id, index);
+        index
+    }
+
+    fn export_material(&mut self, material: &Handle<StandardMaterial>) -> Option<usize> {
+        let material = self.exporter.materials?.get(material)?;
+        let uv_transform = material.uv_transform;
+
+        let mut pbr = json!({
+            "baseColorFactor": material.base_color.as_linear_rgba_f32(),
+            "metallicFactor": material.metallic,
+            "roughnessFactor": material.perceptual_roughness,
+        });
+        if let Some(info) = self.texture_info(&material.base_color_texture, uv_transform) {
+            pbr["baseColorTexture"] = info;
+        }
+        if let Some(info) = self.texture_info(&material.metallic_roughness_texture, uv_transform) {
+            pbr["metallicRoughnessTexture"] = info;
+        }
+
+        let mut gltf_material = json!({ "pbrMetallicRoughness": pbr });
+        if let Some(info) = self.texture_info(&material.normal_map_texture, uv_transform) {
+            gltf_material["normalTexture"] = info;
+        }
+        if let Some(info) = self.texture_info(&material.occlusion_texture, uv_transform) {
+            gltf_material["occlusionTexture"] = info;
+        }
+        if let Some(info) = self.texture_info(&material.emissive_texture, uv_transform) {
+            gltf_material["emissiveTexture"] = info;
+        }
+
+        let mut extensions = Map::new();
+        let [red, green, blue, _] = material.emissive.as_linear_rgba_f32();
+        // glTF emissive factors are at most 1, with a separate strength for brighter materials
+        let emissive_strength = red.max(green).max(blue);
+        if emissive_strength > 1.0 {
+            gltf_material["emissiveFactor"] = json!([
+                red / emissive_strength,
+                green / emissive_strength,
+                blue / emissive_strength
+            ]);
+            extensions.insert(
+                "KHR_materials_emissive_strength".into(),
+                json!({ "emissiveStrength": emissive_strength }),
+            );
+        } else if emissive_strength > 0.0 {
+            gltf_material["emissiveFactor"] = json!([red, green, blue]);
+        }
+
+        if material.double_sided {
+            gltf_material["doubleSided"] = true.into();
+        }
+        match material.alpha_mode {
+            AlphaMode::Opaque => {}
+            AlphaMode::Mask(cutoff) => {
+                gltf_material["alphaMode"] = "MASK".into();
+                gltf_material["alphaCutoff"] = cutoff.into();
+            }
+            AlphaMode::Blend | AlphaMode::Premultiplied | AlphaMode::Add | AlphaMode::Multiply => {
+                gltf_material["alphaMode"] = "BLEND".into();
+            }
+        }
+
+        if material.unlit {
+            extensions.insert("KHR_materials_unlit".into(), json!({}));
+        }
+        if material.ior != 1.5 {
+            extensions.insert("KHR_materials_ior".into(), json!({ "ior": material.ior }));
+        }
+        // The reflectance of a dielectric is given by its index of refraction in glTF, and scaled by
+        // the specular factor
+        let ior_reflectance = reflectance(material.ior, 1.0);
+        if ior_reflectance > 0.0 && (material.reflectance - ior_reflectance).abs() > 1e-4 {
+            let specular_factor = (material.reflectance / ior_reflectance).powi(2);
+            extensions.insert(
+                "KHR_materials_specular".into(),
+                json!({ "specularFactor": specular_factor }),
+            );
+        }
+        if material.specular_transmission > 0.0 {
+            extensions.insert(
+                "KHR_materials_transmission".into(),
+                json!({ "transmissionFactor": material.specular_transmission }),
+            );
+        }
+        if material.thickness > 0.0 {
+            let [red, green, blue, _] = material.attenuation_color.as_linear_rgba_f32();
+            let mut volume = json!({
+                "thicknessFactor": material.thickness,
+                "attenuationColor": [red, green, blue],
+            });
+            if material.attenuation_distance.is_finite() {
+                volume["attenuationDistance"] = material.attenuation_distance.into();
+            }
+            extensions.insert("KHR_materials_volume".into(), volume);
+        }
+        if material.clearcoat > 0.0 {
+            extensions.insert(
+                "KHR_materials_clearcoat".into(),
+                json!({
+                    "clearcoatFactor": material.clearcoat,
+                    "clearcoatRoughnessFactor": material.clearcoat_perceptual_roughness,
+                }),
+            );
+        }
+        if !extensions.is_empty() {
+            self.document
+                .extensions_used
+                .extend(extensions.keys().cloned());
+            gltf_material["extensions"] = extensions.into();
+        }
+
+        self.document.materials.push(gltf_material);
+        Some(self.document.materials.len() - 1)
+    }
+
+    /// Returns the texture info of a material referencing `texture`, with the transform of the
+    /// texture coordinates of the material.
+    fn texture_info(
+        &mut self,
+        texture: &Option<Handle<Image>>,
+        uv_transform: Affine2,
+    ) -> Option<Value> {
+        let index = self.add_texture(texture.as_ref()?)?;
+        let mut info = json!({ "index": index });
+        if uv_transform != Affine2::IDENTITY {
+            let (scale, angle, offset) = uv_transform.to_scale_angle_translation();
+            // The rotation of glTF is clockwise in the texture coordinates
+            info["extensions"] = json!({
+                "KHR_texture_transform": {
+                    "offset": offset.to_array(),
+                    "rotation": -angle,
+                    "scale": scale.to_array(),
+                }
+            });
+            self.document
+                .extensions_used
+                .insert("KHR_texture_transform".into());
+        }
+        Some(info)
+    }
+
+    /// Adds the texture of `image`, and returns its index, or `None` if the image has no path to
+    /// reference it by.
+    fn add_texture(&mut self, image: &Handle<Image>) -> Option<usize> {
+        if let Some(index) = self.document.texture_indices.get(&image.id()) {
+            return *index;
+        }
+        let index = match image.path() {
+            Some(path) if path.label().is_none() => {
+                let uri = path.path().to_string_lossy().replace('\\', "/");
+                let uri = utf8_percent_encode(&uri, URI_PATH).to_string();
+                self.document.images.push(json!({ "uri": uri }));
+                self.document
+                    .textures
+                    .push(json!({ "source": self.document.images.len() - 1 }));
+                Some(self.document.textures.len() - 1)
+            }
+            _ => {
+                warn!(
+                    "Texture {:?} has no file to reference it by and was not exported",
+                    image.path()
+                );
+                None
+            }
+        };
+        self.document.texture_indices.insert(image.id(), index);
+        index
+    }
+
+    /// Adds the glTF camera of `projection`, and returns its index.
+    fn add_camera(&mut self, projection: &Projection) -> usize {
+        let camera = match projection {
+            Projection::Perspective(perspective) => json!({
+                "type": "perspective",
+                "perspective": {
+                    "yfov": perspective.fov,
+                    "aspectRatio": perspective.aspect_ratio,
+                    "znear": perspective.near,
+                    "zfar": perspective.far,
+                },
+            }),
+            Projection::Orthographic(orthographic) => json!({
+                "type": "orthographic",
+                "orthographic": {
+                    "xmag": orthographic.scale,
+                    "ymag": orthographic.scale,
+                    "znear": orthographic.near,
+                    "zfar": orthographic.far,
+                },
+            }),
+        };
+        self.document.cameras.push(camera);
+        self.document.cameras.len() - 1
+    }
+
+    /// Adds the punctual light of `entity`, if it has one, and returns its index.
+    fn add_light(&mut self, entity: EntityRef) -> Option<usize> {
+        let rgb = |color: Color| {
+            let [red, green, blue, _] = color.as_rgba_f32();
+            [red, green, blue]
+        };
+        // NOTE: KHR_punctual_lights defines the intensity units for point and spot lights in
+        // candela (lm/sr), while Bevy uses their luminous power, 4 * pi * luminous intensity.
+        let light = if let Some(light) = entity.get::<PointLight>() {
+            json!({
+                "type": "point",
+                "color": rgb(light.color),
+                "intensity": light.intensity / (4.0 * PI),
+                "range": light.range,
+            })
+        } else if let Some(light) = entity.get::<SpotLight>() {
+            json!({
+                "type": "spot",
+                "color": rgb(light.color),
+                "intensity": light.intensity / (4.0 * PI),
+                "range": light.range,
+                "spot": {
+                    "innerConeAngle": light.inner_angle,
+                    "outerConeAngle": light.outer_angle,
+                },
+            })
+        } else if let Some(light) = entity.get::<DirectionalLight>() {
+            json!({
+                "type": "directional",
+                "color": rgb(light.color),
+                "intensity": light.illuminance,
+            })
+        } else {
+            return None;
+        };
+        self.document.lights.push(light);
+        self.document
+            .extensions_used
+            .insert("KHR_lights_punctual".into());
+        Some(self.document.lights.len() - 1)
+    }
+
+    /// Adds the animation clips of the exported [`AnimationPlayer`]s, with the channels of the
+    /// exported nodes they animate.
+    #[cfg(feature = "bevy_animation")]
+    fn add_animations(&mut self) {
+        let Some(clips) = self.exporter.animation_clips else {
+            return;
+        };
+        let world = self.world;
+        let mut players: Vec<(Entity, &AnimationPlayer)> = self
+            .document
+            .entity_to_node
+            .keys()
+            .filter_map(|entity| Some((*entity, world.get::<AnimationPlayer>(*entity)?)))
+            .collect();
+        players.sort_by_key(|(entity, _)| self.document.entity_to_node[entity]);
+
+        for (player_entity, player) in players {
+            let Some(clip) = clips.get(player.animation_clip()) else {
+                continue;
+            };
+            let mut channels = Vec::new();
+            let mut samplers = Vec::new();
+            for (path, curves) in clip.paths() {
+                let Some(node) = self.find_node(player_entity, path) else {
+                    warn!("Animation target {path:?} was not exported, its curves are skipped");
+                    continue;
+                };
+                for curve in &clip.curves()[*curves] {
+                    let keyframes = match &curve.keyframes {
+                        Keyframes::Compressed(keyframes) if keyframes.is_empty() => continue,
+                        Keyframes::Compressed(keyframes) => {
+                            Cow::Owned(keyframes.decode(0, keyframes.len() - 1))
+                        }
+                        keyframes => Cow::Borrowed(keyframes),
+                    };
+                    let (target_path, values, ty, count): (_, Vec<f32>, _, _) =
+                        match keyframes.as_ref() {
+                            Keyframes::Translation(translations) => (
+                                "translation",
+                                translations.iter().flat_map(|v| v.to_array()).collect(),
+                                "VEC3",
+                                translations.len(),
+                            ),
+                            Keyframes::Rotation(rotations) => (
+                                "rotation",
+                                rotations.iter().flat_map(|q| q.to_array()).collect(),
+                                "VEC4",
+                                rotations.len(),
+                            ),
+                            Keyframes::Scale(scales) => (
+                                "scale",
+                                scales.iter().flat_map(|v| v.to_array()).collect(),
+                                "VEC3",
+                                scales.len(),
+                            ),
+                            // Morph targets are not exported
+                            Keyframes::Weights(_) | Keyframes::Compressed(_) => continue,
+                        };
+                    let timestamps = &curve.keyframe_timestamps;
+                    let (Some(first), Some(last)) = (timestamps.first(), timestamps.last()) else {
+                        continue;
+                    };
+                    let input = self.add_floats(
+                        timestamps,
+                        json!({
+                            "count": timestamps.len(),
+                            "type": "SCALAR",
+                            "min": [first],
+                            "max": [last],
+                        }),
+                    );
+                    let output = self.add_floats(&values, json!({ "count": count, "type": ty }));
+                    channels.push(json!({
+                        "sampler": samplers.len(),
+                        "target": { "node": node, "path": target_path },
+                    }));
+                    samplers.push(json!({
+                        "input": input,
+                        "output": output,
+                        "interpolation": "LINEAR",
+                    }));
+                }
+            }
+            if channels.is_empty() {
+                continue;
+            }
+            let mut animation = json!({ "channels": channels, "samplers": samplers });
+            if let Some(label) = player.animation_clip().path().and_then(|path| path.label()) {
+                animation["name"] = label.into();
+            }
+            self.document.animations.push(animation);
+        }
+    }
+
+    /// Adds the accessor of the float `values`, and returns its index.
+    #[cfg(feature = "bevy_animation")]
+    fn add_floats(&mut self, values: &[f32], mut accessor: Value) -> usize {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        accessor["componentType"] = FLOAT.into();
+        self.add_accessor(&bytes, None, accessor)
+    }
+
+    /// Finds the node of the entity at `path` from the entity of an [`AnimationPlayer`], like the
+    /// animation player does.
+    #[cfg(feature = "bevy_animation")]
+    fn find_node(&self, player: Entity, path: &EntityPath) -> Option<usize> {
+        let world = self.world;
+        let (root, parts) = path.parts.split_first()?;
+        if world.get::<Name>(player)? != root {
+            return None;
+        }
+        let mut current = player;
+        for part in parts {
+            current = world
+                .get::<Children>(current)?
+                .iter()
+                .copied()
+                .find(|child| world.get::<Name>(*child) == Some(part))?;
+        }
+        self.document.entity_to_node.get(&current).copied()
+    }
+
+    /// Returns the export of the document, with the scene of the `nodes`.
+    fn finish(self, nodes: Vec<usize>) -> GltfExport {
+        let document = self.document;
+        let mut json = json!({
+            "asset": { "version": "2.0", "generator": "Bevy" },
+            "scene": 0,
+            "scenes": [{ "nodes": nodes }],
+        });
+        for (key, values) in [
+            ("nodes", document.nodes),
+            ("meshes", document.meshes),
+            ("materials", document.materials),
+            ("textures", document.textures),
+            ("images", document.images),
+            ("cameras", document.cameras),
+            ("animations", document.animations),
+            ("accessors", document.accessors),
+            ("bufferViews", document.buffer_views),
+        ] {
+            if !values.is_empty() {
+                json[key] = values.into();
+            }
+        }
+        if !document.buffer.is_empty() {
+            json["buffers"] = json!([{ "byteLength": document.buffer.len() }]);
+        }
+        if !document.lights.is_empty() {
+            json["extensions"] = json!({ "KHR_lights_punctual": { "lights": document.lights } });
+        }
+        if !document.extensions_used.is_empty() {
+            json["extensionsUsed"] = json!(document.extensions_used);
+        }
+        GltfExport {
+            json,
+            buffer: document.buffer,
+        }
+    }
+}
+
+/// The characters escaped in the URIs of the exported images.
+const URI_PATH: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'~');
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy_app::App;
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        AssetApp, AssetPlugin, AssetServer,
+    };
+    use bevy_core::TaskPoolPlugin;
+    use bevy_hierarchy::BuildWorldChildren;
+
+    use super::*;
+    use crate::{Gltf, GltfMesh, GltfNode, GltfPlugin};
+
+    const POSITIONS: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+
+    fn triangle() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, POSITIONS.to_vec());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2])));
+        mesh
+    }
+
+    #[test]
+    fn exported_scene_loads_back() {
+        let mut meshes = Assets::<Mesh>::default();
+        let mesh = meshes.add(triangle());
+        let mut materials = Assets::<StandardMaterial>::default();
+        let material = materials.add(StandardMaterial::from(Color::RED));
+        let mut world = World::new();
+        world.insert_resource(meshes);
+        world.insert_resource(materials);
+
+        let root_transform = Transform::from_xyz(1.0, 2.0, 3.0);
+        let crate_transform = Transform::from_rotation(Quat::from_rotation_y(1.0))
+            .with_translation(Vec3::new(0.0, 0.5, 0.0))
+            .with_scale(Vec3::splat(2.0));
+        let mut scene_world = World::new();
+        scene_world
+            .spawn((Name::new("Root"), root_transform))
+            .with_children(|parent| {
+                parent.spawn((Name::new("Crate"), crate_transform, mesh, material));
+            });
+        let glb = GltfExporter::new(&world)
+            .export_scene(&Scene::new(scene_world))
+            .to_glb()
+            .unwrap();
+
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("scene.glb"), glb);
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            GltfPlugin::default(),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<Image>()
+        .init_asset::<Scene>();
+        app.finish();
+        let handle = app
+            .world
+            .resource::<AssetServer>()
+            .load::<Gltf>("scene.glb");
+
+        for _ in 0..10000 {
+            app.update();
+            let Some(gltf) = app.world.resource::<Assets<Gltf>>().get(&handle) else {
+                continue;
+            };
+            let nodes = app.world.resource::<Assets<GltfNode>>();
+            let same_transform = |loaded: &Transform, exported: &Transform| {
+                loaded
+                    .compute_matrix()
+                    .abs_diff_eq(exported.compute_matrix(), 1e-6)
+            };
+            let root = nodes.get(&gltf.named_nodes["Root"]).unwrap();
+            assert!(same_transform(&root.transform, &root_transform));
+            assert_eq!(root.children.len(), 1);
+            let crate_node = nodes.get(&gltf.named_nodes["Crate"]).unwrap();
+            assert!(same_transform(&crate_node.transform, &crate_transform));
+
+            assert_eq!(gltf.meshes.len(), 1);
+            let gltf_mesh = app
+                .world
+                .resource::<Assets<GltfMesh>>()
+                .get(crate_node.mesh.as_ref().unwrap())
+                .unwrap();
+            assert_eq!(gltf_mesh.primitives.len(), 1);
+            let primitive = &gltf_mesh.primitives[0];
+            let loaded_mesh = app
+                .world
+                .resource::<Assets<Mesh>>()
+                .get(&primitive.mesh)
+                .unwrap();
+            assert_eq!(
+                loaded_mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+                Some(&VertexAttributeValues::Float32x3(POSITIONS.to_vec()))
+            );
+            assert_eq!(loaded_mesh.indices().unwrap().len(), 3);
+            let loaded_material = app
+                .world
+                .resource::<Assets<StandardMaterial>>()
+                .get(primitive.material.as_ref().unwrap())
+                .unwrap();
+            assert_eq!(
+                loaded_material.base_color.as_rgba_u8(),
+                Color::RED.as_rgba_u8()
+            );
+            return;
+        }
+        panic!("the exported scene should be loaded");
+    }
+}
@@ -0,0 +1,509 @@
+use crate::{loader::resolve_node_hierarchy, Gltf, GltfExtras, GltfMesh, GltfNode, GltfPrimitive};
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::{AnimationClip, AnimationPlayer};
+use bevy_app::App;
+use bevy_asset::{io::Reader, meta::Settings, AssetApp, AssetLoader, Handle, LoadContext};
+use bevy_core::Name;
+use bevy_core_pipeline::prelude::Camera3dBundle;
+use bevy_ecs::{entity::Entity, world::World};
+use bevy_hierarchy::{BuildWorldChildren, WorldChildBuilder};
+use bevy_log::warn;
+use bevy_pbr::{
+    DirectionalLight, DirectionalLightBundle, PbrBundle, PointLight, PointLightBundle, SpotLight,
+    SpotLightBundle, StandardMaterial,
+};
+use bevy_render::{
+    camera::{Camera, Projection},
+    mesh::Mesh,
+    prelude::SpatialBundle,
+};
+use bevy_scene::Scene;
+use bevy_transform::components::Transform;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+/// Imports a scene file format into a [`SceneImport`], which is loaded as a [`Gltf`].
+///
+/// This lets formats such as FBX or USD be supported outside of Bevy, with their files loaded like
+/// glTF files: the loaded [`Gltf`] has the same labeled sub-assets as a glTF file, such as
+/// `Scene0`, `Node0`, `Mesh0`, `Mesh0/Primitive0`, `Material0` and `Animation0`, and its scenes are
+/// spawned the same way, with a [`SceneBundle`](bevy_scene::SceneBundle). An importer is
+/// registered with [`SceneImporterApp::register_scene_importer`].
+///
+/// ```
+/// # use bevy_asset::{io::Reader, AsyncReadExt, LoadContext};
+/// # use bevy_gltf::{ImportedNode, ImportedScene, SceneImport, SceneImporter};
+/// # use bevy_utils::BoxedFuture;
+/// struct ObjImporter;
+///
+/// impl SceneImporter for ObjImporter {
+///     type Settings = ();
+///     type Error = std::io::Error;
+///
+///     fn import<'a>(
+///         &'a self,
+///         reader: &'a mut Reader,
+///         _settings: &'a (),
+///         _load_context: &'a mut LoadContext,
+///     ) -> BoxedFuture<'a, Result<SceneImport, std::io::Error>> {
+///         Box::pin(async move {
+///             let mut text = String::new();
+///             reader.read_to_string(&mut text).await?;
+///             // Parse the meshes and materials of the file...
+///             Ok(SceneImport {
+///                 nodes: vec![ImportedNode::default()],
+///                 scenes: vec![ImportedScene {
+///                     name: None,
+///                     roots: vec![0],
+///                 }],
+///                 ..Default::default()
+///             })
+///         })
+///     }
+///
+///     fn extensions(&self) -> &[&str] {
+///         &["obj"]
+///     }
+/// }
+/// ```
+pub trait SceneImporter: Send + Sync + 'static {
+    /// The settings type used by this importer.
+    type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
+    /// The type of [error](`std::error::Error`) which could be encountered by this importer.
+    type Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    /// Asynchronously imports the scene file read by `reader`.
+    ///
+    /// Textures and other dependencies of the file can be loaded with the `load_context`, and their
+    /// handles used in the [`StandardMaterial`]s of the import.
+    fn import<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<SceneImport, Self::Error>>;
+
+    /// Returns a list of extensions supported by this importer, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+}
+
+/// A scene file imported by a [`SceneImporter`], with its hierarchy, meshes, materials and
+/// animations.
+///
+/// Its parts reference each other by their index, like in a glTF file.
+#[derive(Default)]
+pub struct SceneImport {
+    /// The scenes of the file.
+    pub scenes: Vec<ImportedScene>,
+    /// The index of the scene to display by default.
+    pub default_scene: Option<usize>,
+    /// The nodes of the hierarchy of the scenes.
+    pub nodes: Vec<ImportedNode>,
+    /// The meshes of the nodes.
+    pub meshes: Vec<ImportedMesh>,
+    /// The materials of the primitives of the meshes.
+    pub materials: Vec<ImportedMaterial>,
+    /// The animations of the scenes, whose [`EntityPath`](bevy_animation::EntityPath)s start at the
+    /// name of a root node of a scene.
+    #[cfg(feature = "bevy_animation")]
+    pub animations: Vec<ImportedAnimation>,
+}
+
+/// A scene of a [`SceneImport`].
+#[derive(Default)]
+pub struct ImportedScene {
+    /// The name of the scene.
+    pub name: Option<String>,
+    /// The indices of the root nodes of the scene.
+    pub roots: Vec<usize>,
+}
+
+/// A node of a [`SceneImport`].
+#[derive(Default)]
+pub struct ImportedNode {
+    /// The name of the node, which names its entity.
+    pub name: Option<String>,
+    /// The local transform of the node.
+    pub transform: Transform,
+    /// The indices of the children of the node.
+    pub children: Vec<usize>,
+    /// The index of the mesh of the node.
+    pub mesh: Option<usize>,
+    /// The projection of the camera of the node.
+    pub camera: Option<Projection>,
+    /// The light of the node.
+    pub light: Option<ImportedLight>,
+    /// Additional data.
+    pub extras: Option<GltfExtras>,
+}
+
+/// A light of an [`ImportedNode`].
+pub enum ImportedLight {
+    /// A directional light.
+    Directional(DirectionalLight),
+    /// A point light.
+    Point(PointLight),
+    /// A spot light.
+    Spot(SpotLight),
+}
+
+/// A mesh of a [`SceneImport`].
+#[derive(Default)]
+pub struct ImportedMesh {
+    /// The name of the mesh.
+    pub name: Option<String>,
+    /// The primitives of the mesh.
+    pub primitives: Vec<ImportedPrimitive>,
+    /// Additional data.
+    pub extras: Option<GltfExtras>,
+}
+
+/// A primitive of an [`ImportedMesh`].
+pub struct ImportedPrimitive {
+    /// Topology to be rendered.
+    pub mesh: Mesh,
+    /// The index of the material of the primitive, or `None` for the default material.
+    pub material: Option<usize>,
+    /// Additional data.
+    pub extras: Option<GltfExtras>,
+}
+
+/// A material of a [`SceneImport`].
+pub struct ImportedMaterial {
+    /// The name of the material.
+    pub name: Option<String>,
+    /// The material.
+    pub material: StandardMaterial,
+    /// Additional data.
+    pub extras: Option<GltfExtras>,
+}
+
+/// An animation of a [`SceneImport`].
+#[cfg(feature = "bevy_animation")]
+pub struct ImportedAnimation {
+    /// The name of the animation.
+    pub name: Option<String>,
+    /// The animation clip.
+    pub clip: AnimationClip,
+}
+
+/// Adds [`SceneImporter`] registration to [`App`].
+pub trait SceneImporterApp {
+    /// Registers the given `importer`, to load the files with its extensions as [`Gltf`]s.
+    fn register_scene_importer<I: SceneImporter>(&mut self, importer: I) -> &mut Self;
+}
+
+impl SceneImporterApp for App {
+    fn register_scene_importer<I: SceneImporter>(&mut self, importer: I) -> &mut Self {
+        self.register_asset_loader(SceneImporterLoader { importer })
+    }
+}
+
+/// Loads the files of a [`SceneImporter`] as [`Gltf`]s.
+struct SceneImporterLoader<I: SceneImporter> {
+    importer: I,
+}
+
+impl<I: SceneImporter> AssetLoader for SceneImporterLoader<I> {
+    type Asset = Gltf;
+    type Settings = I::Settings;
+    type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        settings: &'a I::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Gltf, Self::Error>> {
+        Box::pin(async move {
+            let import = self
+                .importer
+                .import(reader, settings, load_context)
+                .await
+                .map_err(Into::<Self::Error>::into)?;
+            Ok(load_import(import, load_context))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.importer.extensions()
+    }
+}
+
+/// Adds the parts of `import` as labeled assets, with the labels of the parts of a glTF file.
+fn load_import(import: SceneImport, load_context: &mut LoadContext) -> Gltf {
+    let mut materials = vec![];
+    let mut named_materials = HashMap::default();
+    let mut material_extras = vec![];
+    for (index, material) in import.materials.into_iter().enumerate() {
+        let handle = load_context.add_labeled_asset(format!("Material{index}"), material.material);
+        if let Some(name) = material.name {
+            named_materials.insert(name, handle.clone());
+        }
+        materials.push(handle);
+        material_extras.push(material.extras);
+    }
+    let default_material = import
+        .meshes
+        .iter()
+        .flat_map(|mesh| &mesh.primitives)
+        .any(|primitive| primitive.material.is_none())
+        .then(|| {
+            load_context
+                .add_labeled_asset("MaterialDefault".to_string(), StandardMaterial::default())
+        });
+
+    let mut meshes = vec![];
+    let mut named_meshes = HashMap::default();
+    // The names and primitives of the meshes, to spawn them in the scenes
+    let mut scene_meshes = vec![];
+    for (index, mesh) in import.meshes.into_iter().enumerate() {
+        let primitives = mesh
+            .primitives
+            .into_iter()
+            .enumerate()
+            .map(|(primitive_index, primitive)| GltfPrimitive {
+                mesh: load_context.add_labeled_asset(
+                    format!("Mesh{index}/Primitive{primitive_index}"),
+                    primitive.mesh,
+                ),
+                material: match primitive.material {
+                    Some(material) => materials.get(material).cloned(),
+                    None => default_material.clone(),
+                },
+                extras: primitive.extras,
+                material_extras: primitive
+                    .material
+                    .and_then(|material| material_extras.get(material).cloned().flatten()),
+                custom_attributes: HashMap::default(),
+            })
+            .collect();
+        let gltf_mesh = GltfMesh {
+            primitives,
+            extras: mesh.extras,
+        };
+        scene_meshes.push((mesh.name.clone(), gltf_mesh.clone()));
+        let handle = load_context.add_labeled_asset(format!("Mesh{index}"), gltf_mesh);
+        if let Some(name) = mesh.name {
+            named_meshes.insert(name, handle.clone());
+        }
+        meshes.push(handle);
+    }
+
+    let nodes_intermediate = import
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            (
+                format!("Node{index}"),
+                GltfNode {
+                    children: vec![],
+                    mesh: node.mesh.and_then(|mesh| meshes.get(mesh).cloned()),
+                    transform: node.transform,
+                    extras: node.extras.clone(),
+                },
+                node.children.clone(),
+            )
+        })
+        .collect();
+    let node_handles: HashMap<String, Handle<GltfNode>> =
+        resolve_node_hierarchy(nodes_intermediate, load_context.path())
+            .into_iter()
+            .map(|(label, node)| {
+                let handle = load_context.add_labeled_asset(label.clone(), node);
+                (label, handle)
+            })
+            .collect();
+    let mut nodes = vec![];
+    let mut named_nodes = HashMap::default();
+    for (index, node) in import.nodes.iter().enumerate() {
+        let Some(handle) = node_handles.get(&format!("Node{index}")) else {
+            continue;
+        };
+        if let Some(name) = &node.name {
+            named_nodes.insert(name.clone(), handle.clone());
+        }
+        nodes.push(handle.clone());
+    }
+
+    #[cfg(feature = "bevy_animation")]
+    let (animations, named_animations, animation_roots) = {
+        let mut animations = vec![];
+        let mut named_animations = HashMap::default();
+        let mut animation_roots = HashSet::new();
+        for (index, animation) in import.animations.into_iter().enumerate() {
+            animation_roots.extend(
+                animation
+                    .clip
+                    .paths()
+                    .keys()
+                    .filter_map(|path| path.parts.first().cloned()),
+            );
+            let handle =
+                load_context.add_labeled_asset(format!("Animation{index}"), animation.clip);
+            if let Some(name) = animation.name {
+                named_animations.insert(name, handle.clone());
+            }
+            animations.push(handle);
+        }
+        (animations, named_animations, animation_roots)
+    };
+
+    let mut scenes = vec![];
+    let mut named_scenes = HashMap::default();
+    let mut default_scene = None;
+    let mut active_camera_found = false;
+    for (index, imported_scene) in import.scenes.iter().enumerate() {
+        let mut world = World::default();
+        let mut spawner = NodeSpawner {
+            nodes: &import.nodes,
+            meshes: &scene_meshes,
+            active_camera_found: &mut active_camera_found,
+            spawned: HashSet::new(),
+        };
+        let mut roots = vec![];
+        world
+            .spawn(SpatialBundle::INHERITED_IDENTITY)
+            .with_children(|parent| {
+                roots = imported_scene
+                    .roots
+                    .iter()
+                    .filter_map(|root| spawner.spawn_node(*root, parent))
+                    .collect();
+            });
+
+        // Play the animations of the root nodes, like the glTF loader does
+        #[cfg(feature = "bevy_animation")]
+        for root in roots {
+            let mut root = world.entity_mut(root);
+            if root
+                .get::<Name>()
+                .is_some_and(|name| animation_roots.contains(name))
+            {
+                root.insert(AnimationPlayer::default());
+            }
+        }
+        #[cfg(not(feature = "bevy_animation"))]
+        let _ = roots;
+
+        let handle = load_context.add_labeled_asset(format!("Scene{index}"), Scene::new(world));
+        if let Some(name) = &imported_scene.name {
+            named_scenes.insert(name.clone(), handle.clone());
+        }
+        if import.default_scene == Some(index) {
+            default_scene = Some(handle.clone());
+        }
+        scenes.push(handle);
+    }
+
+    Gltf {
+        scenes,
+        named_scenes,
+        meshes,
+        named_meshes,
+        materials,
+        named_materials,
+        nodes,
+        named_nodes,
+        default_scene,
+        #[cfg(feature = "bevy_animation")]
+        animations,
+        #[cfg(feature = "bevy_animation")]
+        named_animations,
+    }
+}
+
+/// Spawns the entities of the nodes of a [`SceneImport`], like the glTF loader spawns the nodes of
+/// a glTF scene.
+struct NodeSpawner<'a> {
+    nodes: &'a [ImportedNode],
+    meshes: &'a [(Option<String>, GltfMesh)],
+    active_camera_found: &'a mut bool,
+    spawned: HashSet<usize>,
+}
+
+impl<'a> NodeSpawner<'a> {
+    /// Spawns the node `index` with its descendants as a child of `parent`, and returns its entity.
+    fn spawn_node(&mut self, index: usize, parent: &mut WorldChildBuilder) -> Option<Entity> {
+        let Some(node) = self.nodes.get(index) else {
+            warn!("Imported scene node {index} doesn't exist");
+            return None;
+        };
+        if !self.spawned.insert(index) {
+            warn!(
+                "Imported scene node {index} is used more than once, the hierarchy must be a tree"
+            );
+            return None;
+        }
+
+        let mut entity = parent.spawn(SpatialBundle::from(node.transform));
+        entity.insert(Name::new(
+            node.name.clone().unwrap_or_else(|| format!("Node{index}")),
+        ));
+        if let Some(extras) = &node.extras {
+            entity.insert(extras.clone());
+        }
+        if let Some(projection) = &node.camera {
+            entity.insert(Camera3dBundle {
+                projection: projection.clone(),
+                transform: node.transform,
+                camera: Camera {
+                    is_active: !*self.active_camera_found,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            *self.active_camera_found = true;
+        }
+
+        entity.with_children(|parent| {
+            if let Some((name, mesh)) = node.mesh.and_then(|mesh| self.meshes.get(mesh)) {
+                let mesh_name = name.as_deref().unwrap_or("Mesh");
+                for (primitive_index, primitive) in mesh.primitives.iter().enumerate() {
+                    let mut primitive_entity = parent.spawn(PbrBundle {
+                        mesh: primitive.mesh.clone(),
+                        material: primitive.material.clone().unwrap_or_default(),
+                        ..Default::default()
+                    });
+                    primitive_entity.insert(Name::new(if mesh.primitives.len() > 1 {
+                        format!("{mesh_name}.{primitive_index}")
+                    } else {
+                        mesh_name.to_string()
+                    }));
+                    if let Some(extras) = &primitive.extras {
+                        primitive_entity.insert(extras.clone());
+                    }
+                }
+            }
+
+            match &node.light {
+                Some(ImportedLight::Directional(light)) => {
+                    parent.spawn(DirectionalLightBundle {
+                        directional_light: light.clone(),
+                        ..Default::default()
+                    });
+                }
+                Some(ImportedLight::Point(light)) => {
+                    parent.spawn(PointLightBundle {
+                        point_light: light.clone(),
+                        ..Default::default()
+                    });
+                }
+                Some(ImportedLight::Spot(light)) => {
+                    parent.spawn(SpotLightBundle {
+                        spot_light: light.clone(),
+                        ..Default::default()
+                    });
+                }
+                None => {}
+            }
+
+            for child in &node.children {
+                self.spawn_node(*child, parent);
+            }
+        });
+
+        Some(entity.id())
+    }
+}
@@ -9,17 +9,26 @@
 use bevy_animation::AnimationClip;
 use bevy_utils::HashMap;
 
+mod exporter;
+mod importer;
 mod loader;
+mod meshopt;
 mod vertex_attributes;
+pub use exporter::*;
+pub use importer::*;
 pub use loader::*;
+pub use meshopt::MeshoptError;
 
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp, Handle};
-use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_ecs::{
+    prelude::Component,
+    reflect::{AppTypeRegistry, ReflectComponent},
+};
 use bevy_pbr::StandardMaterial;
 use bevy_reflect::{Reflect, TypePath};
 use bevy_render::{
-    mesh::{Mesh, MeshVertexAttribute},
+    mesh::{Mesh, MeshVertexAttribute, VertexAttributeValues},
     renderer::RenderDevice,
     texture::CompressedImageFormats,
 };
@@ -51,6 +60,8 @@ impl GltfPlugin {
 impl Plugin for GltfPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<GltfExtras>()
+            .register_type::<GltfMeshExtras>()
+            .register_type::<GltfMaterialExtras>()
             .init_asset::<Gltf>()
             .init_asset::<GltfNode>()
             .init_asset::<GltfPrimitive>()
@@ -64,9 +75,11 @@ impl Plugin for GltfPlugin {
 
             None => CompressedImageFormats::NONE,
         };
+        let type_registry = app.world.resource::<AppTypeRegistry>().clone();
         app.register_asset_loader(GltfLoader {
             supported_compressed_formats,
             custom_vertex_attributes: self.custom_vertex_attributes.clone(),
+            type_registry,
         });
     }
 }
@@ -141,6 +154,10 @@ pub struct GltfPrimitive {
     pub extras: Option<GltfExtras>,
     /// Additional data of the `material`.
     pub material_extras: Option<GltfExtras>,
+    /// Custom vertex attributes of the primitive, whose names start with an underscore, which were
+    /// not registered with [`GltfPlugin::add_custom_vertex_attribute`] and so are not part of the
+    /// `mesh`.
+    pub custom_attributes: HashMap<String, VertexAttributeValues>,
 }
 
 /// Additional untyped data that can be present on most glTF types.
@@ -152,3 +169,19 @@ pub struct GltfExtras {
     /// Content of the extra data.
     pub value: String,
 }
+
+/// Additional untyped data of the glTF mesh of a primitive, on the entity of the primitive.
+#[derive(Clone, Debug, Reflect, Default, Component)]
+#[reflect(Component)]
+pub struct GltfMeshExtras {
+    /// Content of the extra data.
+    pub value: String,
+}
+
+/// Additional untyped data of the glTF material of a primitive, on the entity of the primitive.
+#[derive(Clone, Debug, Reflect, Default, Component)]
+#[reflect(Component)]
+pub struct GltfMaterialExtras {
+    /// Content of the extra data.
+    pub value: String,
+}
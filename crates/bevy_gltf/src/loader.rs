@@ -1,17 +1,27 @@
-use crate::{vertex_attributes::convert_attribute, Gltf, GltfExtras, GltfNode};
+use crate::{
+    meshopt::{self, MeshoptCompression, MeshoptError},
+    vertex_attributes::{convert_attribute, read_custom_attribute},
+    Gltf, GltfExtras, GltfMaterialExtras, GltfMeshExtras, GltfNode,
+};
 use bevy_asset::{
-    io::Reader, AssetLoadError, AssetLoader, AsyncReadExt, Handle, LoadContext, ReadAssetBytesError,
+    io::Reader, AssetLoadError, AssetLoader, AssetPath, AssetServer, AsyncReadExt, Handle,
+    LoadContext, ReadAssetBytesError,
 };
 use bevy_core::Name;
 use bevy_core_pipeline::prelude::Camera3dBundle;
-use bevy_ecs::{entity::Entity, world::World};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::{EntityWorldMut, World},
+};
 use bevy_hierarchy::{BuildWorldChildren, WorldChildBuilder};
 use bevy_log::{error, warn};
-use bevy_math::{Mat4, Vec3};
+use bevy_math::{Affine2, Mat4, Vec3};
 use bevy_pbr::{
     AlphaMode, DirectionalLight, DirectionalLightBundle, PbrBundle, PointLight, PointLightBundle,
     SpotLight, SpotLightBundle, StandardMaterial, MAX_JOINTS,
 };
+use bevy_reflect::{serde::TypedReflectDeserializer, TypeRegistry};
 use bevy_render::{
     camera::{Camera, OrthographicProjection, PerspectiveProjection, Projection, ScalingMode},
     color::Color,
@@ -29,17 +39,19 @@ use bevy_render::{
     },
 };
 use bevy_scene::Scene;
+use bevy_tasks::AsyncComputeTaskPool;
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_tasks::IoTaskPool;
 use bevy_transform::components::Transform;
 use bevy_utils::{HashMap, HashSet};
 use gltf::{
     accessor::Iter,
+    json::extras::RawValue,
     mesh::{util::ReadIndices, Mode},
-    texture::{MagFilter, MinFilter, WrappingMode},
+    texture::{Info, MagFilter, MinFilter, TextureTransform, WrappingMode},
     Material, Node, Primitive, Semantic,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
 use std::{
     collections::VecDeque,
     path::{Path, PathBuf},
@@ -91,9 +103,35 @@ pub enum GltfError {
     /// Failed to load a file.
     #[error("failed to load file: {0}")]
     Io(#[from] std::io::Error),
+    /// The glTF file requires an extension which isn't supported.
+    ///
+    /// This is the case of files requiring `KHR_draco_mesh_compression`, as Draco compressed meshes
+    /// aren't decoded.
+    #[error("unsupported required glTF extension: {0}")]
+    UnsupportedExtension(String),
+    /// Failed to decode a buffer view compressed with the `EXT_meshopt_compression` extension.
+    #[error("failed to decode compressed buffer view {buffer_view}: {error}")]
+    MeshoptDecode {
+        /// The index of the buffer view.
+        buffer_view: usize,
+        /// The decoding error.
+        #[source]
+        error: MeshoptError,
+    },
 }
 
+/// The glTF extension for meshes compressed with Draco.
+///
+/// Draco compressed meshes aren't decoded: the uncompressed data of the meshes is loaded instead
+/// when the extension is only used, and files requiring it fail to load with
+/// [`GltfError::UnsupportedExtension`]. Files can be converted to `EXT_meshopt_compression`, which
+/// is decoded, with tools such as `gltfpack`.
+const DRACO_EXTENSION: &str = "KHR_draco_mesh_compression";
+
 /// Loads glTF files with all of their data as their corresponding bevy representations.
+///
+/// Buffer views compressed with `EXT_meshopt_compression` are decoded on the task pool. Meshes
+/// compressed with `KHR_draco_mesh_compression` are not, see [`GltfError::UnsupportedExtension`].
 pub struct GltfLoader {
     /// List of compressed image formats handled by the loader.
     pub supported_compressed_formats: CompressedImageFormats,
@@ -103,6 +141,9 @@ pub struct GltfLoader {
     /// See [this section of the glTF specification](https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#meshes-overview)
     /// for additional details on custom attributes.
     pub custom_vertex_attributes: HashMap<String, MeshVertexAttribute>,
+    /// The type registry used to insert the reflected components of the extras of the nodes, when
+    /// [`GltfLoaderSettings::extras_as_components`] is enabled.
+    pub type_registry: AppTypeRegistry,
 }
 
 /// Specifies optional settings for processing gltfs at load time. By default, all recognized contents of
@@ -122,7 +163,26 @@ pub struct GltfLoader {
 ///     }
 /// );    
 /// ```
+///
+/// Parts of large glTF files can be loaded on their own, to save memory and loading time. To load
+/// only the mesh named `"Crate"`, without its textures:
+/// ```no_run
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_gltf::*;
+/// # let asset_server: AssetServer = panic!();
+/// let gltf_handle: Handle<Gltf> = asset_server.load_with_settings(
+///     "props.glb",
+///     |s: &mut GltfLoaderSettings| {
+///         s.meshes = GltfSelection::Named(vec!["Crate".to_string()]);
+///         s.scenes = GltfSelection::Named(vec![]);
+///         s.load_textures = false;
+///     }
+/// );
+/// ```
+/// The labeled sub-assets of a glTF file can be listed before loading it with
+/// [`read_gltf_sub_assets`].
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct GltfLoaderSettings {
     /// If true, the loader will load mesh nodes and the associated materials.
     pub load_meshes: bool,
@@ -130,6 +190,27 @@ pub struct GltfLoaderSettings {
     pub load_cameras: bool,
     /// If true, the loader will spawn lights for gltf light nodes.
     pub load_lights: bool,
+    /// If true, the loader will load the textures of the materials. Otherwise the materials are
+    /// loaded without textures.
+    pub load_textures: bool,
+    /// If true, the loader will load the skins and spawn skinned meshes. Otherwise the joints and
+    /// weights of the meshes are ignored.
+    pub load_skins: bool,
+    /// The meshes to load, by name. Only the materials and textures used by the selected meshes are
+    /// loaded, and the nodes of the other meshes are spawned without them.
+    pub meshes: GltfSelection,
+    /// The scenes to load, by name.
+    pub scenes: GltfSelection,
+    /// The animations to load, by name.
+    pub animations: GltfSelection,
+    /// If true, the loader will insert reflected components from the extras of the nodes, primitives
+    /// and lights of the scenes.
+    ///
+    /// Each entry of the extras whose key is the type path, or short type path, of a registered
+    /// component is deserialized as that component. For example, the custom property
+    /// `"my_game::Health": { "max": 100 }` set in Blender inserts a `Health { max: 100 }` component.
+    /// The extras are still inserted as a [`GltfExtras`] component.
+    pub extras_as_components: bool,
 }
 
 impl Default for GltfLoaderSettings {
@@ -138,6 +219,41 @@ impl Default for GltfLoaderSettings {
             load_meshes: true,
             load_cameras: true,
             load_lights: true,
+            load_textures: true,
+            load_skins: true,
+            meshes: GltfSelection::All,
+            scenes: GltfSelection::All,
+            animations: GltfSelection::All,
+            extras_as_components: false,
+        }
+    }
+}
+
+impl GltfLoaderSettings {
+    /// Returns true if the loader will load `mesh`.
+    fn loads_mesh(&self, mesh: &gltf::Mesh) -> bool {
+        self.load_meshes && self.meshes.contains(mesh.name())
+    }
+}
+
+/// Selects parts of a glTF file to load with the [`GltfLoader`] by their name.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GltfSelection {
+    /// Select all of them.
+    #[default]
+    All,
+    /// Select those with one of these names. Parts without a name are not selected.
+    Named(Vec<String>),
+}
+
+impl GltfSelection {
+    /// Returns true if the part named `name` is selected.
+    pub fn contains(&self, name: Option<&str>) -> bool {
+        match self {
+            GltfSelection::All => true,
+            GltfSelection::Named(names) => {
+                name.is_some_and(|name| names.iter().any(|selected| selected == name))
+            }
         }
     }
 }
@@ -164,6 +280,146 @@ impl AssetLoader for GltfLoader {
     }
 }
 
+/// The kind of a [`GltfSubAsset`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GltfSubAssetKind {
+    /// A [`Scene`].
+    Scene,
+    /// A [`GltfNode`].
+    Node,
+    /// A [`GltfMesh`](crate::GltfMesh).
+    Mesh,
+    /// The [`Mesh`] of a primitive of a glTF mesh.
+    Primitive,
+    /// The morph targets of a primitive, as an [`Image`].
+    MorphTargets,
+    /// A [`StandardMaterial`].
+    Material,
+    /// An [`Image`] embedded in the glTF file.
+    Texture,
+    /// The [`SkinnedMeshInverseBindposes`] of a skin.
+    Skin,
+    /// An [`AnimationClip`](bevy_animation::AnimationClip).
+    Animation,
+}
+
+/// A labeled sub-asset of a glTF file, which can be loaded on its own with the path of the file and
+/// its label, like `models/props.glb#Mesh0/Primitive0`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GltfSubAsset {
+    /// The label of the sub-asset.
+    pub label: String,
+    /// The kind of the sub-asset.
+    pub kind: GltfSubAssetKind,
+    /// The name of the sub-asset in the glTF file, if it has one.
+    pub name: Option<String>,
+}
+
+/// Lists the labeled sub-assets of the glTF file with the contents `bytes`, without loading its
+/// buffers and images.
+///
+/// The materials of meshes with a negative scale are loaded as additional sub-assets, with the label
+/// of the material followed by ` (inverted)`, and are not listed.
+pub fn gltf_sub_assets(bytes: &[u8]) -> Result<Vec<GltfSubAsset>, GltfError> {
+    let gltf = gltf::Gltf::from_slice(bytes)?;
+    let mut sub_assets = Vec::new();
+    let mut add = |label: String, kind: GltfSubAssetKind, name: Option<&str>| {
+        sub_assets.push(GltfSubAsset {
+            label,
+            kind,
+            name: name.map(ToString::to_string),
+        });
+    };
+
+    for scene in gltf.scenes() {
+        add(scene_label(&scene), GltfSubAssetKind::Scene, scene.name());
+    }
+    for node in gltf.nodes() {
+        add(node_label(&node), GltfSubAssetKind::Node, node.name());
+    }
+    let mut uses_default_material = false;
+    for mesh in gltf.meshes() {
+        add(mesh_label(&mesh), GltfSubAssetKind::Mesh, mesh.name());
+        for primitive in mesh.primitives() {
+            add(
+                primitive_label(&mesh, &primitive),
+                GltfSubAssetKind::Primitive,
+                None,
+            );
+            if primitive.morph_targets().len() != 0 {
+                add(
+                    morph_targets_label(&mesh, &primitive),
+                    GltfSubAssetKind::MorphTargets,
+                    None,
+                );
+            }
+            uses_default_material |= primitive.material().index().is_none();
+        }
+    }
+    for material in gltf.materials() {
+        add(
+            material_label(&material, false),
+            GltfSubAssetKind::Material,
+            material.name(),
+        );
+    }
+    if uses_default_material {
+        add(
+            "MaterialDefault".to_string(),
+            GltfSubAssetKind::Material,
+            None,
+        );
+    }
+    for texture in gltf.textures() {
+        // Textures of image files are loaded as their own assets
+        let embedded = match texture.source().source() {
+            gltf::image::Source::View { .. } => true,
+            gltf::image::Source::Uri { uri, .. } => uri.starts_with("data:"),
+        };
+        if embedded {
+            add(
+                texture_label(&texture),
+                GltfSubAssetKind::Texture,
+                texture.name(),
+            );
+        }
+    }
+    for skin in gltf.skins() {
+        add(skin_label(&skin), GltfSubAssetKind::Skin, skin.name());
+    }
+    #[cfg(feature = "bevy_animation")]
+    for animation in gltf.animations() {
+        add(
+            format!("Animation{}", animation.index()),
+            GltfSubAssetKind::Animation,
+            animation.name(),
+        );
+    }
+    Ok(sub_assets)
+}
+
+/// Reads the glTF file at `path` and lists its labeled sub-assets, without loading it.
+///
+/// This can be used to choose the parts of a large glTF file to load, with the
+/// [`GltfLoaderSettings`] or the labels of the sub-assets.
+pub async fn read_gltf_sub_assets<'a>(
+    asset_server: &AssetServer,
+    path: impl Into<AssetPath<'a>>,
+) -> Result<Vec<GltfSubAsset>, GltfError> {
+    let path = path.into();
+    let source = asset_server
+        .get_source(path.source())
+        .map_err(ReadAssetBytesError::from)?;
+    let mut reader = source
+        .reader()
+        .read(path.path())
+        .await
+        .map_err(ReadAssetBytesError::from)?;
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    gltf_sub_assets(&bytes)
+}
+
 /// Loads an entire glTF file.
 async fn load_gltf<'a, 'b, 'c>(
     loader: &GltfLoader,
@@ -172,7 +428,43 @@ async fn load_gltf<'a, 'b, 'c>(
     settings: &'b GltfLoaderSettings,
 ) -> Result<Gltf, GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
-    let buffer_data = load_buffers(&gltf, load_context).await?;
+    // The Draco compressed meshes of files which don't require the extension have uncompressed data
+    if gltf
+        .extensions_required()
+        .any(|extension| extension == DRACO_EXTENSION)
+    {
+        return Err(GltfError::UnsupportedExtension(DRACO_EXTENSION.to_string()));
+    }
+    let mut buffer_data = load_buffers(&gltf, load_context).await?;
+    decode_compressed_buffer_views(&gltf, &mut buffer_data)?;
+
+    // Only the materials and textures of the selected meshes are loaded, unless all of them are
+    let loaded_materials: HashSet<usize> = if settings.meshes == GltfSelection::All {
+        gltf.materials()
+            .filter_map(|material| material.index())
+            .collect()
+    } else {
+        gltf.meshes()
+            .filter(|mesh| settings.loads_mesh(mesh))
+            .flat_map(|mesh| mesh.primitives())
+            .filter_map(|primitive| primitive.material().index())
+            .collect()
+    };
+    let loaded_textures: HashSet<usize> = if !settings.load_textures {
+        HashSet::default()
+    } else if loaded_materials.len() == gltf.materials().len() {
+        gltf.textures().map(|texture| texture.index()).collect()
+    } else {
+        gltf.materials()
+            .filter(|material| {
+                material
+                    .index()
+                    .is_some_and(|index| loaded_materials.contains(&index))
+            })
+            .flat_map(|material| material_textures(&material))
+            .map(|texture| texture.index())
+            .collect()
+    };
 
     let mut linear_textures = HashSet::default();
 
@@ -210,7 +502,10 @@ async fn load_gltf<'a, 'b, 'c>(
         let mut animations = vec![];
         let mut named_animations = HashMap::default();
         let mut animation_roots = HashSet::default();
-        for animation in gltf.animations() {
+        for animation in gltf
+            .animations()
+            .filter(|animation| settings.animations.contains(animation.name()))
+        {
             let mut animation_clip = bevy_animation::AnimationClip::default();
             for channel in animation.channels() {
                 match channel.sampler().interpolation() {
@@ -316,8 +611,12 @@ async fn load_gltf<'a, 'b, 'c>(
     // later in the loader when looking up handles for materials. However this would mean
     // that the material's load context would no longer track those images as dependencies.
     let mut _texture_handles = Vec::new();
-    if gltf.textures().len() == 1 || cfg!(target_arch = "wasm32") {
-        for texture in gltf.textures() {
+    let textures: Vec<_> = gltf
+        .textures()
+        .filter(|texture| loaded_textures.contains(&texture.index()))
+        .collect();
+    if textures.len() == 1 || cfg!(target_arch = "wasm32") {
+        for texture in textures {
             let parent_path = load_context.path().parent().unwrap();
             let image = load_image(
                 texture,
@@ -333,7 +632,7 @@ async fn load_gltf<'a, 'b, 'c>(
         #[cfg(not(target_arch = "wasm32"))]
         IoTaskPool::get()
             .scope(|scope| {
-                gltf.textures().for_each(|gltf_texture| {
+                textures.into_iter().for_each(|gltf_texture| {
                     let parent_path = load_context.path().parent().unwrap();
                     let linear_textures = &linear_textures;
                     let buffer_data = &buffer_data;
@@ -362,17 +661,26 @@ async fn load_gltf<'a, 'b, 'c>(
 
     let mut materials = vec![];
     let mut named_materials = HashMap::default();
+    let mut material_handles = HashMap::new();
     // NOTE: materials must be loaded after textures because image load() calls will happen before load_with_settings, preventing is_srgb from being set properly
     for material in gltf.materials() {
-        let handle = load_material(&material, load_context, false);
+        let Some(index) = material
+            .index()
+            .filter(|index| loaded_materials.contains(index))
+        else {
+            continue;
+        };
+        let handle = load_material(&material, load_context, settings, false);
         if let Some(name) = material.name() {
             named_materials.insert(name.to_string(), handle.clone());
         }
+        material_handles.insert(index, handle.clone());
         materials.push(handle);
     }
 
     let mut meshes = vec![];
     let mut named_meshes = HashMap::default();
+    let mut mesh_handles = HashMap::new();
     let mut meshes_on_skinned_nodes = HashSet::default();
     let mut meshes_on_non_skinned_nodes = HashSet::default();
     for gltf_node in gltf.nodes() {
@@ -384,18 +692,33 @@ async fn load_gltf<'a, 'b, 'c>(
             meshes_on_non_skinned_nodes.insert(mesh.index());
         }
     }
-    for gltf_mesh in gltf.meshes() {
+    for gltf_mesh in gltf.meshes().filter(|mesh| settings.loads_mesh(mesh)) {
         let mut primitives = vec![];
         for primitive in gltf_mesh.primitives() {
             let primitive_label = primitive_label(&gltf_mesh, &primitive);
             let primitive_topology = get_primitive_topology(primitive.mode())?;
 
             let mut mesh = Mesh::new(primitive_topology);
+            let mut custom_attributes = HashMap::new();
 
             // Read vertex attributes
             for (semantic, accessor) in primitive.attributes() {
+                // Custom attributes which aren't registered are kept on the primitive
+                if let Semantic::Extras(name) = &semantic {
+                    if !loader.custom_vertex_attributes.contains_key(name) {
+                        match read_custom_attribute(accessor, &buffer_data) {
+                            Ok(values) => {
+                                custom_attributes.insert(semantic.to_string(), values);
+                            }
+                            Err(err) => warn!("{}", err),
+                        }
+                        continue;
+                    }
+                }
                 if [Semantic::Joints(0), Semantic::Weights(0)].contains(&semantic) {
-                    if !meshes_on_skinned_nodes.contains(&gltf_mesh.index()) {
+                    if !settings.load_skins {
+                        continue;
+                    } else if !meshes_on_skinned_nodes.contains(&gltf_mesh.index()) {
                         warn!(
                         "Ignoring attribute {:?} for skinned mesh {:?} used on non skinned nodes (NODE_SKINNED_MESH_WITHOUT_SKIN)",
                         semantic,
@@ -490,9 +813,10 @@ async fn load_gltf<'a, 'b, 'c>(
                 material: primitive
                     .material()
                     .index()
-                    .and_then(|i| materials.get(i).cloned()),
+                    .and_then(|i| material_handles.get(&i).cloned()),
                 extras: get_gltf_extras(primitive.extras()),
                 material_extras: get_gltf_extras(primitive.material().extras()),
+                custom_attributes,
             });
         }
 
@@ -506,6 +830,7 @@ async fn load_gltf<'a, 'b, 'c>(
         if let Some(name) = gltf_mesh.name() {
             named_meshes.insert(name.to_string(), handle.clone());
         }
+        mesh_handles.insert(gltf_mesh.index(), handle.clone());
         meshes.push(handle);
     }
 
@@ -520,7 +845,7 @@ async fn load_gltf<'a, 'b, 'c>(
                 mesh: node
                     .mesh()
                     .map(|mesh| mesh.index())
-                    .and_then(|i| meshes.get(i).cloned()),
+                    .and_then(|i| mesh_handles.get(&i).cloned()),
                 transform: match node.transform() {
                     gltf::scene::Transform::Matrix { matrix } => {
                         Transform::from_matrix(Mat4::from_cols_array_2d(&matrix))
@@ -560,6 +885,7 @@ async fn load_gltf<'a, 'b, 'c>(
 
     let skinned_mesh_inverse_bindposes: Vec<_> = gltf
         .skins()
+        .filter(|_| settings.load_skins)
         .map(|gltf_skin| {
             let reader = gltf_skin.reader(|buffer| Some(&buffer_data[buffer.index()]));
             let inverse_bindposes: Vec<Mat4> = reader
@@ -577,8 +903,15 @@ async fn load_gltf<'a, 'b, 'c>(
 
     let mut scenes = vec![];
     let mut named_scenes = HashMap::default();
+    let mut default_scene = None;
     let mut active_camera_found = false;
-    for scene in gltf.scenes() {
+    let type_registry = settings
+        .extras_as_components
+        .then(|| loader.type_registry.read());
+    for scene in gltf
+        .scenes()
+        .filter(|scene| settings.scenes.contains(scene.name()))
+    {
         let mut err = None;
         let mut world = World::default();
         let mut node_index_to_entity_map = HashMap::new();
@@ -594,6 +927,7 @@ async fn load_gltf<'a, 'b, 'c>(
                         load_context,
                         &mut scene_load_context,
                         settings,
+                        type_registry.as_deref(),
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
                         &mut active_camera_found,
@@ -652,14 +986,14 @@ async fn load_gltf<'a, 'b, 'c>(
         if let Some(name) = scene.name() {
             named_scenes.insert(name.to_string(), scene_handle.clone());
         }
+        if gltf.default_scene().map(|scene| scene.index()) == Some(scene.index()) {
+            default_scene = Some(scene_handle.clone());
+        }
         scenes.push(scene_handle);
     }
 
     Ok(Gltf {
-        default_scene: gltf
-            .default_scene()
-            .and_then(|scene| scenes.get(scene.index()))
-            .cloned(),
+        default_scene,
         scenes,
         named_scenes,
         meshes,
@@ -681,6 +1015,39 @@ fn get_gltf_extras(extras: &gltf::json::Extras) -> Option<GltfExtras> {
     })
 }
 
+/// Inserts the `extras` of a glTF object on its `entity` as [`GltfExtras`], and the reflected
+/// components they contain if `type_registry` is set.
+fn insert_extras(
+    entity: &mut EntityWorldMut,
+    extras: &RawValue,
+    type_registry: Option<&TypeRegistry>,
+) {
+    entity.insert(GltfExtras {
+        value: extras.get().to_string(),
+    });
+    let Some(type_registry) = type_registry else {
+        return;
+    };
+    let Ok(serde_json::Value::Object(values)) = serde_json::from_str(extras.get()) else {
+        return;
+    };
+    for (key, value) in values {
+        let Some((registration, reflect_component)) = type_registry
+            .get_with_type_path(&key)
+            .or_else(|| type_registry.get_with_short_type_path(&key))
+            .and_then(|registration| {
+                Some((registration, registration.data::<ReflectComponent>()?))
+            })
+        else {
+            continue;
+        };
+        match TypedReflectDeserializer::new(registration, type_registry).deserialize(value) {
+            Ok(component) => reflect_component.insert(entity, component.as_ref()),
+            Err(err) => warn!("Failed to deserialize the glTF extras {key} as a component: {err}"),
+        }
+    }
+}
+
 fn node_name(node: &Node) -> Name {
     let name = node
         .name()
@@ -765,6 +1132,7 @@ async fn load_image<'a, 'b>(
 fn load_material(
     material: &Material,
     load_context: &mut LoadContext,
+    settings: &GltfLoaderSettings,
     is_scale_inverted: bool,
 ) -> Handle<StandardMaterial> {
     let material_label = material_label(material, is_scale_inverted);
@@ -773,34 +1141,34 @@ fn load_material(
 
         // TODO: handle missing label handle errors here?
         let color = pbr.base_color_factor();
-        let base_color_texture = pbr.base_color_texture().map(|info| {
+        let base_color_texture = pbr.base_color_texture().and_then(|info| {
             // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
-            texture_handle(load_context, &info.texture())
+            texture_handle(load_context, settings, &info.texture())
         });
 
         let normal_map_texture: Option<Handle<Image>> =
-            material.normal_texture().map(|normal_texture| {
+            material.normal_texture().and_then(|normal_texture| {
                 // TODO: handle normal_texture.scale
                 // TODO: handle normal_texture.tex_coord() (the *set* index for the right texcoords)
-                texture_handle(load_context, &normal_texture.texture())
+                texture_handle(load_context, settings, &normal_texture.texture())
             });
 
-        let metallic_roughness_texture = pbr.metallic_roughness_texture().map(|info| {
+        let metallic_roughness_texture = pbr.metallic_roughness_texture().and_then(|info| {
             // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
-            texture_handle(load_context, &info.texture())
+            texture_handle(load_context, settings, &info.texture())
         });
 
-        let occlusion_texture = material.occlusion_texture().map(|occlusion_texture| {
+        let occlusion_texture = material.occlusion_texture().and_then(|occlusion_texture| {
             // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
             // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
-            texture_handle(load_context, &occlusion_texture.texture())
+            texture_handle(load_context, settings, &occlusion_texture.texture())
         });
 
         let emissive = material.emissive_factor();
-        let emissive_texture = material.emissive_texture().map(|info| {
+        let emissive_texture = material.emissive_texture().and_then(|info| {
             // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
             // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
-            texture_handle(load_context, &info.texture())
+            texture_handle(load_context, settings, &info.texture())
         });
 
         #[cfg(feature = "pbr_transmission_textures")]
@@ -808,9 +1176,9 @@ fn load_material(
             material.transmission().map_or((0.0, None), |transmission| {
                 let transmission_texture: Option<Handle<Image>> = transmission
                     .transmission_texture()
-                    .map(|transmission_texture| {
+                    .and_then(|transmission_texture| {
                         // TODO: handle transmission_texture.tex_coord() (the *set* index for the right texcoords)
-                        texture_handle(load_context, &transmission_texture.texture())
+                        texture_handle(load_context, settings, &transmission_texture.texture())
                     });
 
                 (transmission.transmission_factor(), transmission_texture)
@@ -826,9 +1194,9 @@ fn load_material(
             .volume()
             .map_or((0.0, None, f32::INFINITY, [1.0, 1.0, 1.0]), |volume| {
                 let thickness_texture: Option<Handle<Image>> =
-                    volume.thickness_texture().map(|thickness_texture| {
+                    volume.thickness_texture().and_then(|thickness_texture| {
                         // TODO: handle thickness_texture.tex_coord() (the *set* index for the right texcoords)
-                        texture_handle(load_context, &thickness_texture.texture())
+                        texture_handle(load_context, settings, &thickness_texture.texture())
                     });
 
                 (
@@ -853,12 +1221,68 @@ fn load_material(
 
         let ior = material.ior().unwrap_or(1.5);
 
+        // The glTF specular extension scales the reflectance given by the index of refraction
+        let specular = material.specular();
+        if specular.as_ref().is_some_and(|specular| {
+            specular.specular_texture().is_some() || specular.specular_color_texture().is_some()
+        }) {
+            warn!(
+                "Specular textures of material {} are not supported, only the specular factors are used",
+                material_label(material, is_scale_inverted)
+            );
+        }
+        let reflectance = reflectance(
+            ior,
+            specular.as_ref().map_or(1.0, |specular| {
+                let color = specular.specular_color_factor();
+                specular.specular_factor() * color[0].max(color[1]).max(color[2])
+            }),
+        );
+
+        let clearcoat = material.extension_value("KHR_materials_clearcoat");
+        let clearcoat_factor = |key: &str| {
+            clearcoat
+                .and_then(|clearcoat| clearcoat.get(key))
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(0.0) as f32
+        };
+        if clearcoat.is_some_and(|clearcoat| {
+            ["clearcoatTexture", "clearcoatRoughnessTexture", "clearcoatNormalTexture"]
+                .iter()
+                .any(|key| clearcoat.get(key).is_some())
+        }) {
+            warn!(
+                "Clearcoat textures of material {} are not supported, only the clearcoat factors are used",
+                material_label(material, is_scale_inverted)
+            );
+        }
+
+        // Bevy only supports a single transform for all the textures of a material: the transform of
+        // the base color texture is used, as it is the texture most likely to have one
+        let uv_transform = pbr
+            .base_color_texture()
+            .map_or(Affine2::IDENTITY, |info| texture_transform(&info));
+        if [
+            pbr.metallic_roughness_texture(),
+            material.emissive_texture(),
+        ]
+        .iter()
+        .flatten()
+        .any(|info| texture_transform(info) != uv_transform)
+        {
+            warn!(
+                "Textures of material {} have different texture transforms, only the transform of the base color texture is used",
+                material_label(material, is_scale_inverted)
+            );
+        }
+
         StandardMaterial {
             base_color: Color::rgba_linear(color[0], color[1], color[2], color[3]),
             base_color_texture,
             perceptual_roughness: pbr.roughness_factor(),
             metallic: pbr.metallic_factor(),
             metallic_roughness_texture,
+            reflectance,
             normal_map_texture,
             double_sided: material.double_sided(),
             cull_mode: if material.double_sided() {
@@ -885,6 +1309,9 @@ fn load_material(
                 attenuation_color[1],
                 attenuation_color[2],
             ),
+            clearcoat: clearcoat_factor("clearcoatFactor"),
+            clearcoat_perceptual_roughness: clearcoat_factor("clearcoatRoughnessFactor"),
+            uv_transform,
             unlit: material.unlit(),
             alpha_mode: alpha_mode(material),
             ..Default::default()
@@ -892,6 +1319,31 @@ fn load_material(
     })
 }
 
+/// Returns the [`StandardMaterial::reflectance`] of a dielectric material with the index of refraction
+/// `ior`, scaled by the factor of the glTF specular extension.
+pub(crate) fn reflectance(ior: f32, specular_factor: f32) -> f32 {
+    // glTF computes the reflectance at normal incidence from the index of refraction, while Bevy
+    // maps the reflectance to it with `0.16 * reflectance * reflectance`
+    let f0 = ((ior - 1.0) / (ior + 1.0)).powi(2) * specular_factor;
+    (f0.min(1.0) / 0.16).sqrt()
+}
+
+/// Returns the transform of the texture coordinates of a texture, or the identity if it has none.
+fn texture_transform(info: &Info) -> Affine2 {
+    info.texture_transform()
+        .map_or(Affine2::IDENTITY, texture_transform_to_affine2)
+}
+
+/// Converts a `KHR_texture_transform` to the transform of the texture coordinates.
+fn texture_transform_to_affine2(texture_transform: TextureTransform) -> Affine2 {
+    // The rotation of glTF is clockwise in the texture coordinates
+    Affine2::from_scale_angle_translation(
+        texture_transform.scale().into(),
+        -texture_transform.rotation(),
+        texture_transform.offset().into(),
+    )
+}
+
 /// Loads a glTF node.
 #[allow(clippy::too_many_arguments)]
 fn load_node(
@@ -900,6 +1352,7 @@ fn load_node(
     root_load_context: &LoadContext,
     load_context: &mut LoadContext,
     settings: &GltfLoaderSettings,
+    type_registry: Option<&TypeRegistry>,
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut HashMap<Entity, usize>,
     active_camera_found: &mut bool,
@@ -921,9 +1374,7 @@ fn load_node(
     node.insert(node_name(gltf_node));
 
     if let Some(extras) = gltf_node.extras() {
-        node.insert(GltfExtras {
-            value: extras.get().to_string(),
-        });
+        insert_extras(&mut node, extras, type_registry);
     }
 
     // create camera node
@@ -977,67 +1428,73 @@ fn load_node(
     let mut morph_weights = None;
 
     node.with_children(|parent| {
-        if settings.load_meshes {
-            if let Some(mesh) = gltf_node.mesh() {
-                // append primitives
-                for primitive in mesh.primitives() {
-                    let material = primitive.material();
-                    let material_label = material_label(&material, is_scale_inverted);
-
-                    // This will make sure we load the default material now since it would not have been
-                    // added when iterating over all the gltf materials (since the default material is
-                    // not explicitly listed in the gltf).
-                    // It also ensures an inverted scale copy is instantiated if required.
-                    if !root_load_context.has_labeled_asset(&material_label)
-                        && !load_context.has_labeled_asset(&material_label)
-                    {
-                        load_material(&material, load_context, is_scale_inverted);
-                    }
+        if let Some(mesh) = gltf_node.mesh().filter(|mesh| settings.loads_mesh(mesh)) {
+            // append primitives
+            for primitive in mesh.primitives() {
+                let material = primitive.material();
+                let material_label = material_label(&material, is_scale_inverted);
+
+                // This will make sure we load the default material now since it would not have been
+                // added when iterating over all the gltf materials (since the default material is
+                // not explicitly listed in the gltf).
+                // It also ensures an inverted scale copy is instantiated if required.
+                if !root_load_context.has_labeled_asset(&material_label)
+                    && !load_context.has_labeled_asset(&material_label)
+                {
+                    load_material(&material, load_context, settings, is_scale_inverted);
+                }
 
-                    let primitive_label = primitive_label(&mesh, &primitive);
-                    let bounds = primitive.bounding_box();
+                let primitive_label = primitive_label(&mesh, &primitive);
+                let bounds = primitive.bounding_box();
 
-                    let mut mesh_entity = parent.spawn(PbrBundle {
-                        // TODO: handle missing label handle errors here?
-                        mesh: load_context.get_label_handle(&primitive_label),
-                        material: load_context.get_label_handle(&material_label),
-                        ..Default::default()
-                    });
-                    let target_count = primitive.morph_targets().len();
-                    if target_count != 0 {
-                        let weights = match mesh.weights() {
-                            Some(weights) => weights.to_vec(),
-                            None => vec![0.0; target_count],
-                        };
-
-                        if morph_weights.is_none() {
-                            morph_weights = Some(weights.clone());
-                        }
+                let mut mesh_entity = parent.spawn(PbrBundle {
+                    // TODO: handle missing label handle errors here?
+                    mesh: load_context.get_label_handle(&primitive_label),
+                    material: load_context.get_label_handle(&material_label),
+                    ..Default::default()
+                });
+                let target_count = primitive.morph_targets().len();
+                if target_count != 0 {
+                    let weights = match mesh.weights() {
+                        Some(weights) => weights.to_vec(),
+                        None => vec![0.0; target_count],
+                    };
 
-                        // unwrap: the parent's call to `MeshMorphWeights::new`
-                        // means this code doesn't run if it returns an `Err`.
-                        // According to https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#morph-targets
-                        // they should all have the same length.
-                        // > All morph target accessors MUST have the same count as
-                        // > the accessors of the original primitive.
-                        mesh_entity.insert(MeshMorphWeights::new(weights).unwrap());
-                    }
-                    mesh_entity.insert(Aabb::from_min_max(
-                        Vec3::from_slice(&bounds.min),
-                        Vec3::from_slice(&bounds.max),
-                    ));
-
-                    if let Some(extras) = primitive.extras() {
-                        mesh_entity.insert(GltfExtras {
-                            value: extras.get().to_string(),
-                        });
+                    if morph_weights.is_none() {
+                        morph_weights = Some(weights.clone());
                     }
 
-                    mesh_entity.insert(Name::new(primitive_name(&mesh, &primitive)));
-                    // Mark for adding skinned mesh
-                    if let Some(skin) = gltf_node.skin() {
-                        entity_to_skin_index_map.insert(mesh_entity.id(), skin.index());
-                    }
+                    // unwrap: the parent's call to `MeshMorphWeights::new`
+                    // means this code doesn't run if it returns an `Err`.
+                    // According to https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#morph-targets
+                    // they should all have the same length.
+                    // > All morph target accessors MUST have the same count as
+                    // > the accessors of the original primitive.
+                    mesh_entity.insert(MeshMorphWeights::new(weights).unwrap());
+                }
+                mesh_entity.insert(Aabb::from_min_max(
+                    Vec3::from_slice(&bounds.min),
+                    Vec3::from_slice(&bounds.max),
+                ));
+
+                if let Some(extras) = primitive.extras() {
+                    insert_extras(&mut mesh_entity, extras, type_registry);
+                }
+                if let Some(extras) = mesh.extras() {
+                    mesh_entity.insert(GltfMeshExtras {
+                        value: extras.get().to_string(),
+                    });
+                }
+                if let Some(extras) = material.extras() {
+                    mesh_entity.insert(GltfMaterialExtras {
+                        value: extras.get().to_string(),
+                    });
+                }
+
+                mesh_entity.insert(Name::new(primitive_name(&mesh, &primitive)));
+                // Mark for adding skinned mesh
+                if let Some(skin) = gltf_node.skin().filter(|_| settings.load_skins) {
+                    entity_to_skin_index_map.insert(mesh_entity.id(), skin.index());
                 }
             }
         }
@@ -1060,9 +1517,7 @@ fn load_node(
                             entity.insert(Name::new(name.to_string()));
                         }
                         if let Some(extras) = light.extras() {
-                            entity.insert(GltfExtras {
-                                value: extras.get().to_string(),
-                            });
+                            insert_extras(&mut entity, extras, type_registry);
                         }
                     }
                     gltf::khr_lights_punctual::Kind::Point => {
@@ -1083,9 +1538,7 @@ fn load_node(
                             entity.insert(Name::new(name.to_string()));
                         }
                         if let Some(extras) = light.extras() {
-                            entity.insert(GltfExtras {
-                                value: extras.get().to_string(),
-                            });
+                            insert_extras(&mut entity, extras, type_registry);
                         }
                     }
                     gltf::khr_lights_punctual::Kind::Spot {
@@ -1111,9 +1564,7 @@ fn load_node(
                             entity.insert(Name::new(name.to_string()));
                         }
                         if let Some(extras) = light.extras() {
-                            entity.insert(GltfExtras {
-                                value: extras.get().to_string(),
-                            });
+                            insert_extras(&mut entity, extras, type_registry);
                         }
                     }
                 }
@@ -1128,6 +1579,7 @@ fn load_node(
                 root_load_context,
                 load_context,
                 settings,
+                type_registry,
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
                 active_camera_found,
@@ -1199,8 +1651,16 @@ fn texture_label(texture: &gltf::Texture) -> String {
     format!("Texture{}", texture.index())
 }
 
-fn texture_handle(load_context: &mut LoadContext, texture: &gltf::Texture) -> Handle<Image> {
-    match texture.source().source() {
+/// Returns the handle of the `texture` used by a material, or `None` if textures are not loaded.
+fn texture_handle(
+    load_context: &mut LoadContext,
+    settings: &GltfLoaderSettings,
+    texture: &gltf::Texture,
+) -> Option<Handle<Image>> {
+    if !settings.load_textures {
+        return None;
+    }
+    let handle = match texture.source().source() {
         gltf::image::Source::View { .. } => {
             let label = texture_label(texture);
             load_context.get_label_handle(&label)
@@ -1219,7 +1679,31 @@ fn texture_handle(load_context: &mut LoadContext, texture: &gltf::Texture) -> Ha
                 load_context.load(image_path)
             }
         }
-    }
+    };
+    Some(handle)
+}
+
+/// Returns the textures used by `material`.
+fn material_textures<'a>(material: &Material<'a>) -> Vec<gltf::Texture<'a>> {
+    let pbr = material.pbr_metallic_roughness();
+    [
+        pbr.base_color_texture().map(|info| info.texture()),
+        pbr.metallic_roughness_texture().map(|info| info.texture()),
+        material.normal_texture().map(|info| info.texture()),
+        material.occlusion_texture().map(|info| info.texture()),
+        material.emissive_texture().map(|info| info.texture()),
+        material
+            .transmission()
+            .and_then(|transmission| transmission.transmission_texture())
+            .map(|info| info.texture()),
+        material
+            .volume()
+            .and_then(|volume| volume.thickness_texture())
+            .map(|info| info.texture()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
 }
 
 /// Returns the label for the `node`.
@@ -1319,6 +1803,11 @@ async fn load_buffers(
 
     let mut buffer_data = Vec::new();
     for buffer in gltf.buffers() {
+        // The data of the fallback buffers is decoded from the compressed buffer views instead
+        if meshopt::is_fallback_buffer(&buffer) {
+            buffer_data.push(vec![0; buffer.length()]);
+            continue;
+        }
         match buffer.source() {
             gltf::buffer::Source::Uri(uri) => {
                 let uri = percent_encoding::percent_decode_str(uri)
@@ -1351,7 +1840,65 @@ async fn load_buffers(
     Ok(buffer_data)
 }
 
-fn resolve_node_hierarchy(
+/// Decodes the buffer views compressed with the `EXT_meshopt_compression` extension into their
+/// buffers.
+fn decode_compressed_buffer_views(
+    gltf: &gltf::Gltf,
+    buffer_data: &mut [Vec<u8>],
+) -> Result<(), GltfError> {
+    let mut compressed_views = Vec::new();
+    for view in gltf.views() {
+        match meshopt::buffer_view_compression(&view) {
+            Ok(Some(compression)) => compressed_views.push((view, compression)),
+            Ok(None) => {}
+            Err(error) => {
+                return Err(GltfError::MeshoptDecode {
+                    buffer_view: view.index(),
+                    error,
+                })
+            }
+        }
+    }
+
+    let buffers: &[Vec<u8>] = buffer_data;
+    let decode = |(view, compression): &(gltf::buffer::View, MeshoptCompression)| {
+        compression
+            .decode(buffers)
+            .map_err(|error| GltfError::MeshoptDecode {
+                buffer_view: view.index(),
+                error,
+            })
+    };
+    // Large meshes can take a while to decode, so their buffer views are decoded in parallel
+    let decoded_views: Vec<Result<Vec<u8>, GltfError>> = if compressed_views.len() <= 1 {
+        compressed_views.iter().map(decode).collect()
+    } else {
+        let decode = &decode;
+        AsyncComputeTaskPool::get().scope(|scope| {
+            for compressed_view in &compressed_views {
+                scope.spawn(async move { decode(compressed_view) });
+            }
+        })
+    };
+
+    for ((view, _), decoded) in compressed_views.iter().zip(decoded_views) {
+        let decoded = decoded?;
+        let range = view.offset()..view.offset() + view.length();
+        match buffer_data[view.buffer().index()].get_mut(range) {
+            Some(data) if data.len() == decoded.len() => data.copy_from_slice(&decoded),
+            _ => {
+                return Err(GltfError::MeshoptDecode {
+                    buffer_view: view.index(),
+                    error: MeshoptError::LengthMismatch,
+                })
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn resolve_node_hierarchy(
     nodes_intermediate: Vec<(String, GltfNode, Vec<usize>)>,
     asset_path: &Path,
 ) -> Vec<(String, GltfNode)> {
@@ -1491,7 +2038,7 @@ struct MorphTargetNames {
 mod test {
     use std::path::PathBuf;
 
-    use super::resolve_node_hierarchy;
+    use super::{gltf_sub_assets, resolve_node_hierarchy, GltfSelection, GltfSubAssetKind};
     use crate::GltfNode;
 
     impl GltfNode {
@@ -1609,4 +2156,78 @@ mod test {
         assert_eq!(result[0].0, "l2");
         assert_eq!(result[0].1.children.len(), 0);
     }
+
+    #[test]
+    fn sub_assets() {
+        let gltf = r#"{
+            "asset": { "version": "2.0" },
+            "scenes": [{ "name": "Main", "nodes": [0] }],
+            "nodes": [{ "name": "Crate", "mesh": 0 }],
+            "meshes": [{
+                "name": "Crate",
+                "primitives": [
+                    { "attributes": { "POSITION": 0 }, "material": 0 },
+                    { "attributes": { "POSITION": 0 } }
+                ]
+            }],
+            "materials": [{ "name": "Wood" }],
+            "images": [{ "uri": "wood.png" }, { "uri": "data:image/png;base64," }],
+            "textures": [{ "source": 0 }, { "source": 1 }],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 1,
+                "type": "VEC3",
+                "min": [0, 0, 0],
+                "max": [0, 0, 0]
+            }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 12 }],
+            "buffers": [{ "byteLength": 12, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAA" }]
+        }"#;
+        let labels: Vec<_> = gltf_sub_assets(gltf.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|sub_asset| (sub_asset.label, sub_asset.kind, sub_asset.name))
+            .collect();
+
+        let name = |name: &str| Some(name.to_string());
+        assert_eq!(
+            labels,
+            vec![
+                ("Scene0".to_string(), GltfSubAssetKind::Scene, name("Main")),
+                ("Node0".to_string(), GltfSubAssetKind::Node, name("Crate")),
+                ("Mesh0".to_string(), GltfSubAssetKind::Mesh, name("Crate")),
+                (
+                    "Mesh0/Primitive0".to_string(),
+                    GltfSubAssetKind::Primitive,
+                    None
+                ),
+                (
+                    "Mesh0/Primitive1".to_string(),
+                    GltfSubAssetKind::Primitive,
+                    None
+                ),
+                (
+                    "Material0".to_string(),
+                    GltfSubAssetKind::Material,
+                    name("Wood")
+                ),
+                (
+                    "MaterialDefault".to_string(),
+                    GltfSubAssetKind::Material,
+                    None
+                ),
+                ("Texture1".to_string(), GltfSubAssetKind::Texture, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn selection() {
+        let selection = GltfSelection::Named(vec!["Crate".to_string()]);
+        assert!(selection.contains(Some("Crate")));
+        assert!(!selection.contains(Some("Barrel")));
+        assert!(!selection.contains(None));
+        assert!(GltfSelection::All.contains(None));
+    }
 }
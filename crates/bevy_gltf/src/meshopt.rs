@@ -0,0 +1,648 @@
+//! Decoding of the buffer views compressed with the
+//! [`EXT_meshopt_compression`](https://github.com/KhronosGroup/glTF/tree/main/extensions/2.0/Vendor/EXT_meshopt_compression)
+//! glTF extension, a port of the decoders of [meshoptimizer](https://github.com/zeux/meshoptimizer).
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The name of the glTF extension.
+pub(crate) const EXTENSION: &str = "EXT_meshopt_compression";
+
+/// An error that occurs when decoding a buffer view compressed with the `EXT_meshopt_compression`
+/// glTF extension.
+#[derive(Error, Debug)]
+pub enum MeshoptError {
+    /// The extension data of the buffer view is invalid.
+    #[error("invalid extension data: {0}")]
+    InvalidExtension(#[from] serde_json::Error),
+    /// The compressed data is out of the bounds of its buffer.
+    #[error("compressed data is out of the bounds of buffer {0}")]
+    OutOfBounds(usize),
+    /// The byte stride isn't supported by the compression mode or filter.
+    #[error("unsupported byte stride {0}")]
+    UnsupportedStride(usize),
+    /// The header or the version of the compressed data isn't supported.
+    #[error("unsupported encoding header {0:#x}")]
+    UnsupportedHeader(u8),
+    /// The compressed data is truncated or malformed.
+    #[error("compressed data is malformed")]
+    Malformed,
+    /// The decoded data doesn't fit in the buffer view.
+    #[error("decoded data doesn't match the length of the buffer view")]
+    LengthMismatch,
+}
+
+/// The `EXT_meshopt_compression` extension data of a buffer view.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MeshoptCompression {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    byte_length: usize,
+    byte_stride: usize,
+    count: usize,
+    mode: MeshoptMode,
+    #[serde(default)]
+    filter: MeshoptFilter,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum MeshoptMode {
+    Attributes,
+    Triangles,
+    Indices,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum MeshoptFilter {
+    #[default]
+    None,
+    Octahedral,
+    Quaternion,
+    Exponential,
+}
+
+/// Returns `true` if `buffer` only exists to hold the data of compressed buffer views once decoded.
+pub(crate) fn is_fallback_buffer(buffer: &gltf::Buffer) -> bool {
+    buffer
+        .extension_value(EXTENSION)
+        .and_then(|extension| extension.get("fallback"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Returns the compression of `view`, if it is compressed.
+pub(crate) fn buffer_view_compression(
+    view: &gltf::buffer::View,
+) -> Result<Option<MeshoptCompression>, MeshoptError> {
+    view.extension_value(EXTENSION)
+        .map(|extension| MeshoptCompression::deserialize(extension).map_err(Into::into))
+        .transpose()
+}
+
+impl MeshoptCompression {
+    /// Decodes the data of the buffer view from the compressed data in `buffer_data`.
+    pub(crate) fn decode(&self, buffer_data: &[Vec<u8>]) -> Result<Vec<u8>, MeshoptError> {
+        let encoded = buffer_data
+            .get(self.buffer)
+            .and_then(|buffer| {
+                buffer.get(self.byte_offset..self.byte_offset.checked_add(self.byte_length)?)
+            })
+            .ok_or(MeshoptError::OutOfBounds(self.buffer))?;
+
+        let mut decoded = vec![0; self.count * self.byte_stride];
+        match self.mode {
+            MeshoptMode::Attributes => {
+                if self.byte_stride == 0 || self.byte_stride % 4 != 0 || self.byte_stride > 256 {
+                    return Err(MeshoptError::UnsupportedStride(self.byte_stride));
+                }
+                decode_vertex_buffer(&mut decoded, self.byte_stride, encoded)?;
+            }
+            MeshoptMode::Triangles => {
+                if self.byte_stride != 2 && self.byte_stride != 4 {
+                    return Err(MeshoptError::UnsupportedStride(self.byte_stride));
+                }
+                if self.count % 3 != 0 {
+                    return Err(MeshoptError::Malformed);
+                }
+                decode_index_buffer(&mut decoded, self.byte_stride, encoded)?;
+            }
+            MeshoptMode::Indices => {
+                if self.byte_stride != 2 && self.byte_stride != 4 {
+                    return Err(MeshoptError::UnsupportedStride(self.byte_stride));
+                }
+                decode_index_sequence(&mut decoded, self.byte_stride, encoded)?;
+            }
+        }
+
+        match self.filter {
+            MeshoptFilter::None => {}
+            MeshoptFilter::Octahedral => match self.byte_stride {
+                4 => decode_filter_oct::<1>(&mut decoded),
+                8 => decode_filter_oct::<2>(&mut decoded),
+                stride => return Err(MeshoptError::UnsupportedStride(stride)),
+            },
+            MeshoptFilter::Quaternion => match self.byte_stride {
+                8 => decode_filter_quat(&mut decoded),
+                stride => return Err(MeshoptError::UnsupportedStride(stride)),
+            },
+            MeshoptFilter::Exponential => decode_filter_exp(&mut decoded),
+        }
+
+        Ok(decoded)
+    }
+}
+
+/// A cursor over compressed data, which fails instead of reading past its end.
+struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8], position: usize) -> Self {
+        Self { data, position }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    fn read(&mut self) -> Result<u8, MeshoptError> {
+        let byte = *self
+            .data
+            .get(self.position)
+            .ok_or(MeshoptError::Malformed)?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8], MeshoptError> {
+        let slice = self
+            .data
+            .get(self.position..self.position + len)
+            .ok_or(MeshoptError::Malformed)?;
+        self.position += len;
+        Ok(slice)
+    }
+
+    fn read_vbyte(&mut self) -> Result<u32, MeshoptError> {
+        let lead = self.read()?;
+        if lead < 128 {
+            return Ok(lead as u32);
+        }
+        // Values are stored in 7-bit groups, the high bit of each byte marks the continuation
+        let mut result = (lead & 127) as u32;
+        let mut shift = 7;
+        for _ in 0..4 {
+            let group = self.read()?;
+            result |= ((group & 127) as u32) << shift;
+            shift += 7;
+            if group < 128 {
+                break;
+            }
+        }
+        Ok(result)
+    }
+}
+
+const VERTEX_HEADER: u8 = 0xa0;
+const VERTEX_BLOCK_SIZE_BYTES: usize = 8192;
+const VERTEX_BLOCK_MAX_SIZE: usize = 256;
+const BYTE_GROUP_SIZE: usize = 16;
+const TAIL_MIN_SIZE: usize = 32;
+
+/// Decodes the vertices of `vertex_size` bytes of a vertex buffer into `destination`.
+fn decode_vertex_buffer(
+    destination: &mut [u8],
+    vertex_size: usize,
+    encoded: &[u8],
+) -> Result<(), MeshoptError> {
+    let header = *encoded.first().ok_or(MeshoptError::Malformed)?;
+    if header != VERTEX_HEADER {
+        return Err(MeshoptError::UnsupportedHeader(header));
+    }
+
+    // The tail holds the first vertex, which the first block is delta-encoded from
+    let tail_size = vertex_size.max(TAIL_MIN_SIZE);
+    if encoded.len() < 1 + tail_size {
+        return Err(MeshoptError::Malformed);
+    }
+    let data_end = encoded.len() - tail_size;
+    let mut last_vertex = encoded[encoded.len() - vertex_size..].to_vec();
+
+    let block_size = ((VERTEX_BLOCK_SIZE_BYTES / vertex_size) & !(BYTE_GROUP_SIZE - 1))
+        .min(VERTEX_BLOCK_MAX_SIZE);
+    let mut cursor = Cursor::new(&encoded[..data_end], 1);
+    for block in destination.chunks_mut(block_size * vertex_size) {
+        decode_vertex_block(&mut cursor, block, vertex_size, &mut last_vertex)?;
+    }
+
+    if cursor.remaining() != 0 {
+        return Err(MeshoptError::Malformed);
+    }
+    Ok(())
+}
+
+/// Decodes a block of vertices, which stores each byte of the vertices in a separate stream of
+/// deltas from the previous vertex.
+fn decode_vertex_block(
+    cursor: &mut Cursor,
+    block: &mut [u8],
+    vertex_size: usize,
+    last_vertex: &mut [u8],
+) -> Result<(), MeshoptError> {
+    let vertex_count = block.len() / vertex_size;
+    let vertex_count_aligned = (vertex_count + BYTE_GROUP_SIZE - 1) & !(BYTE_GROUP_SIZE - 1);
+    let mut deltas = [0; VERTEX_BLOCK_MAX_SIZE];
+
+    for k in 0..vertex_size {
+        decode_bytes(cursor, &mut deltas[..vertex_count_aligned])?;
+
+        let mut previous = last_vertex[k];
+        for (i, delta) in deltas[..vertex_count].iter().enumerate() {
+            // Deltas are zigzag-encoded to keep small negative deltas small
+            let value = ((delta & 1).wrapping_neg() ^ (delta >> 1)).wrapping_add(previous);
+            block[i * vertex_size + k] = value;
+            previous = value;
+        }
+    }
+
+    last_vertex.copy_from_slice(&block[block.len() - vertex_size..]);
+    Ok(())
+}
+
+/// Decodes a stream of bytes, stored in groups of 16 bytes encoded with 0, 2, 4 or 8 bits each.
+fn decode_bytes(cursor: &mut Cursor, buffer: &mut [u8]) -> Result<(), MeshoptError> {
+    let group_count = buffer.len() / BYTE_GROUP_SIZE;
+    let header = cursor.read_slice(group_count.div_ceil(4))?;
+
+    for (i, group) in buffer.chunks_mut(BYTE_GROUP_SIZE).enumerate() {
+        let bits_log2 = (header[i / 4] >> ((i % 4) * 2)) & 3;
+        match bits_log2 {
+            0 => group.fill(0),
+            1 => decode_bytes_group::<2>(cursor, group)?,
+            2 => decode_bytes_group::<4>(cursor, group)?,
+            _ => group.copy_from_slice(cursor.read_slice(BYTE_GROUP_SIZE)?),
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a group of 16 bytes packed in `BITS` bits each, where the largest value means that the
+/// byte is stored after the packed bits instead.
+fn decode_bytes_group<const BITS: usize>(
+    cursor: &mut Cursor,
+    group: &mut [u8],
+) -> Result<(), MeshoptError> {
+    let packed = cursor.read_slice(BYTE_GROUP_SIZE * BITS / 8)?;
+    let escape = (1 << BITS) - 1;
+    for (i, byte) in group.iter_mut().enumerate() {
+        let bit = i * BITS;
+        let value = (packed[bit / 8] >> (8 - BITS - bit % 8)) & escape;
+        *byte = if value == escape {
+            cursor.read()?
+        } else {
+            value
+        };
+    }
+    Ok(())
+}
+
+const INDEX_HEADER: u8 = 0xe0;
+const SEQUENCE_HEADER: u8 = 0xd0;
+
+/// Writes the index `value` at `index` of `destination`, which holds indices of `index_size` bytes.
+fn write_index(destination: &mut [u8], index_size: usize, index: usize, value: u32) {
+    let offset = index * index_size;
+    if index_size == 2 {
+        destination[offset..offset + 2].copy_from_slice(&(value as u16).to_le_bytes());
+    } else {
+        destination[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn push_edge(fifo: &mut [[u32; 2]; 16], offset: &mut usize, a: u32, b: u32) {
+    fifo[*offset] = [a, b];
+    *offset = (*offset + 1) & 15;
+}
+
+fn push_vertex(fifo: &mut [u32; 16], offset: &mut usize, v: u32, advance: bool) {
+    fifo[*offset] = v;
+    if advance {
+        *offset = (*offset + 1) & 15;
+    }
+}
+
+/// Decodes a triangle list, encoded from the edges and vertices of the previous triangles.
+fn decode_index_buffer(
+    destination: &mut [u8],
+    index_size: usize,
+    encoded: &[u8],
+) -> Result<(), MeshoptError> {
+    let index_count = destination.len() / index_size;
+    let triangle_count = index_count / 3;
+    // The last 16 bytes hold the table of the auxiliary codes, and pad the data
+    if encoded.len() < 1 + triangle_count + 16 {
+        return Err(MeshoptError::Malformed);
+    }
+    let header = encoded[0];
+    let version = header & 0x0f;
+    if header & 0xf0 != INDEX_HEADER || version > 1 {
+        return Err(MeshoptError::UnsupportedHeader(header));
+    }
+
+    let mut edge_fifo = [[u32::MAX; 2]; 16];
+    let mut vertex_fifo = [u32::MAX; 16];
+    let mut edge_fifo_offset = 0;
+    let mut vertex_fifo_offset = 0;
+
+    let mut next = 0u32;
+    let mut last = 0u32;
+    let fec_max = if version >= 1 { 13 } else { 15 };
+
+    let codes = &encoded[1..1 + triangle_count];
+    let data_end = encoded.len() - 16;
+    let code_aux_table = &encoded[data_end..];
+    let mut data = Cursor::new(&encoded[..data_end], 1 + triangle_count);
+
+    let decode_index = |data: &mut Cursor, last: u32| -> Result<u32, MeshoptError> {
+        let v = data.read_vbyte()?;
+        Ok(last.wrapping_add((v >> 1) ^ (v & 1).wrapping_neg()))
+    };
+
+    for (triangle, &code) in codes.iter().enumerate() {
+        let [a, b, c] = if code < 0xf0 {
+            // The triangle shares an edge with a recent triangle
+            let fe = (code >> 4) as usize;
+            let [a, b] = edge_fifo[(edge_fifo_offset + 15 - fe) & 15];
+            let fec = (code & 15) as usize;
+            let c = if fec < fec_max {
+                let c = if fec == 0 {
+                    next
+                } else {
+                    vertex_fifo[(vertex_fifo_offset + 15 - fec) & 15]
+                };
+                next += (fec == 0) as u32;
+                push_vertex(&mut vertex_fifo, &mut vertex_fifo_offset, c, fec == 0);
+                c
+            } else {
+                // 13 and 14 encode the previous free index -1 and +1, 15 a free index delta
+                last = if fec != 15 {
+                    last.wrapping_add(if fec == 13 { u32::MAX } else { 1 })
+                } else {
+                    decode_index(&mut data, last)?
+                };
+                push_vertex(&mut vertex_fifo, &mut vertex_fifo_offset, last, true);
+                last
+            };
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, c, b);
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, a, c);
+            [a, b, c]
+        } else {
+            // The triangle doesn't share an edge with a recent triangle: 0 encodes a new vertex, other
+            // values a recent vertex or, when the auxiliary code isn't from the table, 15 a free index
+            let (fea, feb, fec, free) = if code < 0xfe {
+                let code_aux = code_aux_table[(code & 15) as usize];
+                (0, (code_aux >> 4) as usize, (code_aux & 15) as usize, false)
+            } else {
+                let code_aux = data.read()?;
+                // An explicit auxiliary code of 0 restarts the new vertices
+                if code_aux == 0 {
+                    next = 0;
+                }
+                let fea = if code == 0xfe { 0 } else { 15 };
+                (
+                    fea,
+                    (code_aux >> 4) as usize,
+                    (code_aux & 15) as usize,
+                    true,
+                )
+            };
+            let is_free = |fe: usize| free && fe == 15;
+            let vertex = |fe: usize, next: &mut u32| {
+                if fe == 0 {
+                    *next += 1;
+                    *next - 1
+                } else if is_free(fe) {
+                    0
+                } else {
+                    vertex_fifo[(vertex_fifo_offset + 16 - fe) & 15]
+                }
+            };
+            let mut a = vertex(fea, &mut next);
+            let mut b = vertex(feb, &mut next);
+            let mut c = vertex(fec, &mut next);
+            if is_free(fea) {
+                last = decode_index(&mut data, last)?;
+                a = last;
+            }
+            if is_free(feb) {
+                last = decode_index(&mut data, last)?;
+                b = last;
+            }
+            if is_free(fec) {
+                last = decode_index(&mut data, last)?;
+                c = last;
+            }
+
+            push_vertex(&mut vertex_fifo, &mut vertex_fifo_offset, a, true);
+            push_vertex(
+                &mut vertex_fifo,
+                &mut vertex_fifo_offset,
+                b,
+                feb == 0 || is_free(feb),
+            );
+            push_vertex(
+                &mut vertex_fifo,
+                &mut vertex_fifo_offset,
+                c,
+                fec == 0 || is_free(fec),
+            );
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, b, a);
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, c, b);
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, a, c);
+            [a, b, c]
+        };
+
+        write_index(destination, index_size, triangle * 3, a);
+        write_index(destination, index_size, triangle * 3 + 1, b);
+        write_index(destination, index_size, triangle * 3 + 2, c);
+    }
+
+    if data.remaining() != 0 {
+        return Err(MeshoptError::Malformed);
+    }
+    Ok(())
+}
+
+/// Decodes a sequence of indices, delta-encoded from one of the two previous indices.
+fn decode_index_sequence(
+    destination: &mut [u8],
+    index_size: usize,
+    encoded: &[u8],
+) -> Result<(), MeshoptError> {
+    let index_count = destination.len() / index_size;
+    // The last 4 bytes pad the data
+    if encoded.len() < 1 + index_count + 4 {
+        return Err(MeshoptError::Malformed);
+    }
+    let header = encoded[0];
+    if header & 0xf0 != SEQUENCE_HEADER || header & 0x0f > 1 {
+        return Err(MeshoptError::UnsupportedHeader(header));
+    }
+
+    let mut data = Cursor::new(&encoded[..encoded.len() - 4], 1);
+    let mut last = [0u32; 2];
+    for index in 0..index_count {
+        let v = data.read_vbyte()?;
+        // The lowest bit selects the baseline the delta is from
+        let baseline = (v & 1) as usize;
+        let v = v >> 1;
+        let value = last[baseline].wrapping_add((v >> 1) ^ (v & 1).wrapping_neg());
+        last[baseline] = value;
+        write_index(destination, index_size, index, value);
+    }
+
+    if data.remaining() != 0 {
+        return Err(MeshoptError::Malformed);
+    }
+    Ok(())
+}
+
+/// Rounds `value` to the nearest integer, away from zero.
+fn round(value: f32) -> i32 {
+    (value + if value >= 0.0 { 0.5 } else { -0.5 }) as i32
+}
+
+/// Decodes unit vectors stored as octahedral coordinates in the `x` and `y` components of
+/// `BYTES`-byte signed integers, with the `z` component holding the value of `1.0`.
+fn decode_filter_oct<const BYTES: usize>(data: &mut [u8]) {
+    let read = |bytes: &[u8]| -> f32 {
+        if BYTES == 1 {
+            bytes[0] as i8 as f32
+        } else {
+            i16::from_le_bytes([bytes[0], bytes[1]]) as f32
+        }
+    };
+    let write = |bytes: &mut [u8], value: i32| {
+        if BYTES == 1 {
+            bytes[0] = value as i8 as u8;
+        } else {
+            bytes.copy_from_slice(&(value as i16).to_le_bytes());
+        }
+    };
+    let max = ((1 << (BYTES * 8 - 1)) - 1) as f32;
+
+    for vector in data.chunks_exact_mut(4 * BYTES) {
+        let mut x = read(&vector[..BYTES]);
+        let mut y = read(&vector[BYTES..2 * BYTES]);
+        let z = read(&vector[2 * BYTES..3 * BYTES]) - x.abs() - y.abs();
+
+        // Unfold the octahedron for the lower hemisphere
+        let t = z.min(0.0);
+        x += if x >= 0.0 { t } else { -t };
+        y += if y >= 0.0 { t } else { -t };
+
+        let scale = max / (x * x + y * y + z * z).sqrt();
+        write(&mut vector[..BYTES], round(x * scale));
+        write(&mut vector[BYTES..2 * BYTES], round(y * scale));
+        write(&mut vector[2 * BYTES..3 * BYTES], round(z * scale));
+    }
+}
+
+/// Decodes quaternions stored as three 16-bit components, with the index of the largest, omitted,
+/// component and the scale of the others in the fourth component.
+fn decode_filter_quat(data: &mut [u8]) {
+    let scale = 1.0 / 2.0f32.sqrt();
+
+    for quat in data.chunks_exact_mut(8) {
+        let component = |i: usize| i16::from_le_bytes([quat[2 * i], quat[2 * i + 1]]);
+        let encoded_scale = component(3);
+        let s = scale / (encoded_scale | 3) as f32;
+
+        let x = component(0) as f32 * s;
+        let y = component(1) as f32 * s;
+        let z = component(2) as f32 * s;
+        let w = (1.0 - x * x - y * y - z * z).max(0.0).sqrt();
+
+        let largest = (encoded_scale & 3) as usize;
+        for (offset, value) in [
+            (1, round(x * 32767.0)),
+            (2, round(y * 32767.0)),
+            (3, round(z * 32767.0)),
+            (0, (w * 32767.0 + 0.5) as i32),
+        ] {
+            let i = (largest + offset) & 3;
+            quat[2 * i..2 * i + 2].copy_from_slice(&(value as i16).to_le_bytes());
+        }
+    }
+}
+
+/// Decodes floats stored as a 24-bit signed mantissa and an 8-bit signed exponent.
+fn decode_filter_exp(data: &mut [u8]) {
+    for value in data.chunks_exact_mut(4) {
+        let v = u32::from_le_bytes([value[0], value[1], value[2], value[3]]);
+        let mantissa = ((v << 8) as i32) >> 8;
+        let exponent = (v as i32) >> 24;
+        // `2^exponent` is built from its bits, like the reference decoder
+        let decoded = f32::from_bits(((exponent + 127) as u32) << 23) * mantissa as f32;
+        value.copy_from_slice(&decoded.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a single vertex: the vertex stream only holds the first vertex, in the tail.
+    fn single_vertex(vertex: [u8; 4]) -> Vec<u8> {
+        let mut encoded = vec![VERTEX_HEADER, 0, 0, 0, 0];
+        encoded.extend([0; TAIL_MIN_SIZE - 4]);
+        encoded.extend(vertex);
+        encoded
+    }
+
+    fn compression(mode: &str, filter: &str, byte_length: usize) -> MeshoptCompression {
+        serde_json::from_value(serde_json::json!({
+            "buffer": 0,
+            "byteLength": byte_length,
+            "byteStride": 4,
+            "count": 1,
+            "mode": mode,
+            "filter": filter,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_vertex() {
+        let encoded = single_vertex([1, 2, 3, 4]);
+        let compression = compression("ATTRIBUTES", "NONE", encoded.len());
+        assert_eq!(compression.decode(&[encoded]).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn decode_truncated_vertex() {
+        let mut encoded = single_vertex([1, 2, 3, 4]);
+        encoded.truncate(20);
+        let compression = compression("ATTRIBUTES", "NONE", encoded.len());
+        assert!(compression.decode(&[encoded]).is_err());
+    }
+
+    #[test]
+    fn decode_filters() {
+        let encoded = single_vertex([0, 0, 127, 9]);
+        let octahedral = compression("ATTRIBUTES", "OCTAHEDRAL", encoded.len());
+        assert_eq!(octahedral.decode(&[encoded]).unwrap(), vec![0, 0, 127, 9]);
+
+        // 3 * 2^-1
+        let encoded = single_vertex(((-1i32 as u32) << 24 | 3).to_le_bytes());
+        let exponential = compression("ATTRIBUTES", "EXPONENTIAL", encoded.len());
+        let decoded = exponential.decode(&[encoded]).unwrap();
+        assert_eq!(f32::from_le_bytes(decoded.try_into().unwrap()), 1.5);
+    }
+
+    #[test]
+    fn decode_index_sequence() {
+        let mut encoded = vec![SEQUENCE_HEADER];
+        // 5 and 7 from the first baseline, then 6 from the second baseline
+        encoded.extend([5 << 2, 2 << 2, (6 << 2) | 1]);
+        encoded.extend([0; 4]);
+        let compression: MeshoptCompression = serde_json::from_value(serde_json::json!({
+            "buffer": 0,
+            "byteLength": encoded.len(),
+            "byteStride": 2,
+            "count": 3,
+            "mode": "INDICES",
+        }))
+        .unwrap();
+        assert_eq!(
+            compression.decode(&[encoded]).unwrap(),
+            vec![5, 0, 7, 0, 6, 0]
+        );
+    }
+}
@@ -303,3 +303,13 @@ pub(crate) fn convert_attribute(
         Err(ConvertAttributeError::UnknownName(semantic.to_string()))
     }
 }
+
+/// Reads the values of a custom vertex attribute which isn't registered as a [`MeshVertexAttribute`].
+pub(crate) fn read_custom_attribute(
+    accessor: gltf::Accessor,
+    buffer_data: &Vec<Vec<u8>>,
+) -> Result<Values, ConvertAttributeError> {
+    VertexAttributeIter::from_accessor(accessor.clone(), buffer_data)
+        .and_then(|iter| iter.into_any_values())
+        .map_err(|err| ConvertAttributeError::AccessFailed(err, accessor.index()))
+}
@@ -689,9 +689,137 @@ impl<'w> BuildWorldChildren for EntityWorldMut<'w> {
     }
 }
 
+/// Spawns each parent with its children, batching the spawning of all the parents, then of all
+/// the children with their [`Parent`], then the insertion of the [`Children`] of the parents.
+fn spawn_batch_with_children<P, I, C>(
+    world: &mut World,
+    hierarchies: impl IntoIterator<Item = (P, I)>,
+) where
+    P: Bundle,
+    I: IntoIterator<Item = C>,
+    C: Bundle,
+{
+    let (parents, children): (Vec<P>, Vec<Vec<C>>) = hierarchies
+        .into_iter()
+        .map(|(parent, children)| (parent, children.into_iter().collect()))
+        .unzip();
+    let child_counts: Vec<usize> = children.iter().map(Vec::len).collect();
+
+    let parents: Vec<Entity> = world.spawn_batch(parents).collect();
+    let children: Vec<Entity> = world
+        .spawn_batch(
+            parents
+                .iter()
+                .zip(children)
+                .flat_map(|(&parent, children)| {
+                    children
+                        .into_iter()
+                        .map(move |child| (child, Parent(parent)))
+                }),
+        )
+        .collect();
+
+    let mut events = Vec::with_capacity(children.len());
+    let mut parents_children = Vec::with_capacity(parents.len());
+    let mut remaining_children = children.as_slice();
+    for (&parent, count) in parents.iter().zip(child_counts) {
+        let (children, rest) = remaining_children.split_at(count);
+        remaining_children = rest;
+        if !children.is_empty() {
+            events.extend(
+                children
+                    .iter()
+                    .map(|&child| HierarchyEvent::ChildAdded { child, parent }),
+            );
+            parents_children.push((parent, Children::from_entities(children)));
+        }
+    }
+    // The parents were just spawned, so none of them can be invalid.
+    let _ = world.insert_or_spawn_batch(parents_children);
+    push_events(world, events);
+}
+
+/// Command that spawns a batch of parents with their children.
+///
+/// See [`BuildChildrenBatch::spawn_batch_with_children`].
+pub struct SpawnBatchWithChildren<H> {
+    /// The bundle of each parent and the bundles of its children.
+    pub hierarchies: H,
+}
+
+impl<H, P, I, C> Command for SpawnBatchWithChildren<H>
+where
+    H: IntoIterator<Item = (P, I)> + Send + Sync + 'static,
+    P: Bundle,
+    I: IntoIterator<Item = C>,
+    C: Bundle,
+{
+    fn apply(self, world: &mut World) {
+        spawn_batch_with_children(world, self.hierarchies);
+    }
+}
+
+/// Trait for spawning many small hierarchies at once, like projectiles with their muzzle
+/// flashes or the rows of a list with their cells.
+///
+/// Spawning them in a batch spawns the entities with the same components together and inserts
+/// the [`Parent`] and [`Children`] components along with them, instead of moving each entity
+/// to a new archetype for each child added to it.
+pub trait BuildChildrenBatch {
+    /// Spawns an entity with each parent bundle of `hierarchies`, with a child for each bundle
+    /// of its children.
+    ///
+    /// Parents without children don't get a [`Children`] component.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_hierarchy::BuildChildrenBatch;
+    /// #[derive(Component)]
+    /// struct Projectile;
+    ///
+    /// #[derive(Component)]
+    /// struct MuzzleFlash;
+    ///
+    /// fn fire(mut commands: Commands) {
+    ///     commands.spawn_batch_with_children((0..100).map(|_| (Projectile, [MuzzleFlash])));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(fire);
+    /// ```
+    fn spawn_batch_with_children<H, P, I, C>(&mut self, hierarchies: H)
+    where
+        H: IntoIterator<Item = (P, I)> + Send + Sync + 'static,
+        P: Bundle,
+        I: IntoIterator<Item = C>,
+        C: Bundle;
+}
+
+impl<'w, 's> BuildChildrenBatch for Commands<'w, 's> {
+    fn spawn_batch_with_children<H, P, I, C>(&mut self, hierarchies: H)
+    where
+        H: IntoIterator<Item = (P, I)> + Send + Sync + 'static,
+        P: Bundle,
+        I: IntoIterator<Item = C>,
+        C: Bundle,
+    {
+        self.add(SpawnBatchWithChildren { hierarchies });
+    }
+}
+
+impl BuildChildrenBatch for World {
+    fn spawn_batch_with_children<H, P, I, C>(&mut self, hierarchies: H)
+    where
+        H: IntoIterator<Item = (P, I)> + Send + Sync + 'static,
+        P: Bundle,
+        I: IntoIterator<Item = C>,
+        C: Bundle,
+    {
+        spawn_batch_with_children(self, hierarchies);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{BuildChildren, BuildWorldChildren};
+    use super::{BuildChildren, BuildChildrenBatch, BuildWorldChildren};
     use crate::{
         components::{Children, Parent},
         HierarchyEvent::{self, ChildAdded, ChildMoved, ChildRemoved},
@@ -1241,4 +1369,68 @@ mod tests {
         let children = query.get(&world, parent).unwrap();
         assert_eq!(**children, [child]);
     }
+
+    #[test]
+    fn spawn_batch_with_children() {
+        #[derive(Component, Debug, PartialEq)]
+        struct Row(u32);
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Cell(u32);
+
+        let mut world = World::default();
+        world.init_resource::<Events<HierarchyEvent>>();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.spawn_batch_with_children((0..3).map(|row| {
+            let cells: Vec<_> = (0..row).map(|cell| Cell(row * 10 + cell)).collect();
+            (Row(row), cells)
+        }));
+        queue.apply(&mut world);
+
+        let mut rows = world.query::<(Entity, &Row, Option<&Children>)>();
+        let rows: Vec<_> = rows
+            .iter(&world)
+            .map(|(entity, row, children)| (entity, row.0, children.map(|c| c.to_vec())))
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].2, None);
+        for (parent, row, children) in &rows[1..] {
+            let children = children.as_ref().unwrap();
+            let cells: Vec<_> = children
+                .iter()
+                .map(|&child| {
+                    assert_parent(&world, child, Some(*parent));
+                    world.get::<Cell>(child).unwrap().0
+                })
+                .collect();
+            assert_eq!(
+                cells,
+                (0..*row).map(|cell| row * 10 + cell).collect::<Vec<_>>()
+            );
+        }
+
+        let (row_1, row_2) = (rows[1].0, rows[2].0);
+        let cells: Vec<_> = rows[1..]
+            .iter()
+            .flat_map(|(_, _, children)| children.clone().unwrap())
+            .collect();
+        assert_events(
+            &mut world,
+            &[
+                ChildAdded {
+                    child: cells[0],
+                    parent: row_1,
+                },
+                ChildAdded {
+                    child: cells[1],
+                    parent: row_2,
+                },
+                ChildAdded {
+                    child: cells[2],
+                    parent: row_2,
+                },
+            ],
+        );
+    }
 }
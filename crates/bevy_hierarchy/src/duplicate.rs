@@ -0,0 +1,279 @@
+use crate::{
+    child_builder::BuildWorldChildren,
+    components::{Children, Parent},
+};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::EntityDuplicator,
+    system::{Command, CommandError, CommandErrorHandler, EntityCommands},
+    world::{EntityWorldMut, World},
+};
+
+/// Duplicates the given entity and all its descendants recursively
+pub struct DuplicateRecursive {
+    /// The entity to duplicate
+    pub source: Entity,
+    /// The entity receiving the components of `source`, which becomes the root of the copy of
+    /// the hierarchy
+    pub target: Entity,
+    /// The duplicator selecting the components, of the entity and of its descendants
+    pub duplicator: EntityDuplicator,
+}
+
+/// Function for duplicating an entity and all its descendants onto the `target` entity.
+///
+/// The components are duplicated with the `duplicator`, except for [`Parent`] and [`Children`]:
+/// the copies of the descendants are children of the copies of their parents, and the `target`
+/// entity is added to the children of the parent of the `source` entity, if it has one.
+///
+/// # Panics
+///
+/// Panics if either entity doesn't exist, or if
+/// [`AppTypeRegistry`](bevy_ecs::reflect::AppTypeRegistry) is not present in the [`World`].
+pub fn duplicate_with_children_recursive(
+    world: &mut World,
+    source: Entity,
+    target: Entity,
+    duplicator: &EntityDuplicator,
+) {
+    let duplicator = duplicator.clone().deny::<Parent>().deny::<Children>();
+    duplicate_with_children_recursive_inner(world, source, target, &duplicator);
+
+    if let Some(parent) = world.get::<Parent>(source).map(Parent::get) {
+        world.entity_mut(parent).add_child(target);
+    }
+}
+
+// Should only be called by `duplicate_with_children_recursive`!
+fn duplicate_with_children_recursive_inner(
+    world: &mut World,
+    source: Entity,
+    target: Entity,
+    duplicator: &EntityDuplicator,
+) {
+    duplicator.duplicate_into(world, source, target);
+
+    let Some(children) = world
+        .get::<Children>(source)
+        .map(|children| children.to_vec())
+    else {
+        return;
+    };
+    let copies: Vec<_> = children
+        .into_iter()
+        .map(|child| {
+            let copy = world.spawn_empty().id();
+            duplicate_with_children_recursive_inner(world, child, copy, duplicator);
+            copy
+        })
+        .collect();
+    world.entity_mut(target).push_children(&copies);
+}
+
+impl Command for DuplicateRecursive {
+    fn apply(self, world: &mut World) {
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!(
+            "command",
+            name = "DuplicateRecursive",
+            entity = bevy_utils::tracing::field::debug(self.source)
+        )
+        .entered();
+        for entity in [self.source, self.target] {
+            if world.get_entity(entity).is_none() {
+                CommandError::no_such_entity("duplicate entity", entity)
+                    .report(world, CommandErrorHandler::panic());
+                return;
+            }
+        }
+        duplicate_with_children_recursive(world, self.source, self.target, &self.duplicator);
+    }
+}
+
+/// Trait that holds functions for duplicating an entity with its descendants
+pub trait DuplicateRecursiveExt {
+    /// Spawns a copy of the entity and of all its descendants, with clones of their reflected
+    /// components, and returns the copy of the entity.
+    ///
+    /// The copy is a sibling of the entity, if the entity has a parent.
+    /// See [`duplicate_with_children_recursive`] for more details.
+    fn duplicate_recursive(&mut self) -> Entity;
+
+    /// Same as [`duplicate_recursive`](Self::duplicate_recursive), but skipping the components
+    /// denied by the `duplicator`.
+    fn duplicate_recursive_with(&mut self, duplicator: EntityDuplicator) -> Entity;
+}
+
+impl<'w, 's, 'a> DuplicateRecursiveExt for EntityCommands<'w, 's, 'a> {
+    fn duplicate_recursive(&mut self) -> Entity {
+        self.duplicate_recursive_with(EntityDuplicator::new())
+    }
+
+    fn duplicate_recursive_with(&mut self, duplicator: EntityDuplicator) -> Entity {
+        let source = self.id();
+        let commands = self.commands();
+        let target = commands.spawn_empty().id();
+        commands.add(DuplicateRecursive {
+            source,
+            target,
+            duplicator,
+        });
+        target
+    }
+}
+
+impl<'w> DuplicateRecursiveExt for EntityWorldMut<'w> {
+    fn duplicate_recursive(&mut self) -> Entity {
+        self.duplicate_recursive_with(EntityDuplicator::new())
+    }
+
+    fn duplicate_recursive_with(&mut self, duplicator: EntityDuplicator) -> Entity {
+        let source = self.id();
+
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!(
+            "duplicate_recursive",
+            entity = bevy_utils::tracing::field::debug(source)
+        )
+        .entered();
+
+        self.world_scope(|world| {
+            let target = world.spawn_empty().id();
+            duplicate_with_children_recursive(world, source, target, &duplicator);
+            target
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        reflect::{AppTypeRegistry, EntityDuplicator, ReflectCommandExt, ReflectComponent},
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+
+    use super::DuplicateRecursiveExt;
+    use crate::{
+        child_builder::BuildWorldChildren,
+        components::{Children, Parent},
+        hierarchy::DespawnRecursiveExt,
+    };
+
+    #[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+    #[reflect(Component)]
+    struct Idx(u32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Selected;
+
+    fn idx(world: &World, entity: Entity) -> u32 {
+        world.get::<Idx>(entity).unwrap().0
+    }
+
+    fn world_with_registry() -> World {
+        let mut world = World::default();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Idx>();
+            registry.register::<Selected>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        world
+    }
+
+    #[test]
+    fn duplicate_recursive() {
+        let mut world = world_with_registry();
+        let root = world.spawn(Idx(0)).id();
+        let mut parent = None;
+        world.entity_mut(root).with_children(|root| {
+            parent = Some(
+                root.spawn((Idx(1), Selected))
+                    .with_children(|parent| {
+                        parent.spawn(Idx(2));
+                        parent.spawn((Idx(3), Selected));
+                    })
+                    .id(),
+            );
+        });
+        let parent = parent.unwrap();
+
+        let copy = world
+            .entity_mut(parent)
+            .duplicate_recursive_with(EntityDuplicator::new().deny::<Selected>());
+        assert_eq!(idx(&world, copy), 1);
+        assert!(world.get::<Selected>(copy).is_none());
+        assert_eq!(world.get::<Parent>(copy).unwrap().get(), root);
+        assert_eq!(&**world.get::<Children>(root).unwrap(), [parent, copy]);
+
+        let copied_children = world.get::<Children>(copy).unwrap().to_vec();
+        assert_eq!(copied_children.len(), 2);
+        for (i, child) in copied_children.into_iter().enumerate() {
+            assert_eq!(idx(&world, child), i as u32 + 2);
+            assert!(world.get::<Selected>(child).is_none());
+            assert_eq!(world.get::<Parent>(child).unwrap().get(), copy);
+        }
+
+        let mut queue = CommandQueue::default();
+        let root_copy = {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(root).duplicate_recursive()
+        };
+        queue.apply(&mut world);
+        assert!(world.get::<Parent>(root_copy).is_none());
+        let copied_children = world.get::<Children>(root_copy).unwrap().to_vec();
+        assert_eq!(copied_children.len(), 2);
+        assert!(world.get::<Selected>(copied_children[0]).is_some());
+        assert_eq!(world.get::<Children>(copied_children[1]).unwrap().len(), 2);
+        // The originals are untouched.
+        assert_eq!(world.get::<Children>(parent).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn duplicate_child_without_hierarchy() {
+        let mut world = world_with_registry();
+        let root = world.spawn(Idx(0)).id();
+        let mut child = None;
+        world.entity_mut(root).with_children(|root| {
+            child = Some(
+                root.spawn(Idx(1))
+                    .with_children(|child| {
+                        child.spawn(Idx(2));
+                    })
+                    .id(),
+            );
+        });
+        let child = child.unwrap();
+        let grandchild = world.get::<Children>(child).unwrap()[0];
+
+        let copy = world.duplicate_entity(child);
+        assert_eq!(idx(&world, copy), 1);
+        assert!(world.get::<Parent>(copy).is_none());
+        assert!(world.get::<Children>(copy).is_none());
+        assert_eq!(&**world.get::<Children>(root).unwrap(), [child]);
+
+        let mut queue = CommandQueue::default();
+        let command_copy = {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(child).duplicate()
+        };
+        queue.apply(&mut world);
+        assert_eq!(idx(&world, command_copy), 1);
+        assert!(world.get::<Parent>(command_copy).is_none());
+        assert!(world.get::<Children>(command_copy).is_none());
+
+        // Despawning the copies leaves the original hierarchy untouched.
+        world.entity_mut(copy).despawn_recursive();
+        world.entity_mut(command_copy).despawn_recursive();
+        assert_eq!(&**world.get::<Children>(child).unwrap(), [grandchild]);
+        assert_eq!(world.get::<Parent>(grandchild).unwrap().get(), child);
+    }
+}
@@ -22,11 +22,20 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+#[cfg(feature = "reflect")]
+mod duplicate;
+#[cfg(feature = "reflect")]
+pub use duplicate::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{child_builder::*, components::*, hierarchy::*, query_extension::*};
 
+    #[doc(hidden)]
+    #[cfg(feature = "reflect")]
+    pub use crate::DuplicateRecursiveExt;
+
     #[doc(hidden)]
     #[cfg(feature = "bevy_app")]
     pub use crate::{HierarchyPlugin, ValidParentCheckPlugin};
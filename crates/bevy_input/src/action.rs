@@ -0,0 +1,724 @@
+//! Mapping raw inputs to game-specific actions.
+//!
+//! Games usually don't care whether the player jumped by pressing space or the south button of
+//! their gamepad. An [`InputMap`] binds the actions of a game, typically the variants of an enum,
+//! to any number of keys, mouse buttons, gamepad buttons and axes or touches. The
+//! [`ActionState`] next to it is then updated each frame with the state of every action, which
+//! gameplay systems read instead of the raw inputs.
+//!
+//! Both are components, so each player of a local multiplayer game can have their own bindings
+//! and read their own gamepad. Bindings can be changed at runtime, e.g. from a settings menu,
+//! with the help of [`ActionInputs::just_pressed_binding`].
+//!
+//! Contexts such as menus and gameplay are best modeled with one action type each, by enabling
+//! only the [`InputMap`] of the current context with [`InputMap::set_enabled`].
+//!
+//! ```
+//! # use bevy_app::{App, Update};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{action::*, gamepad::{GamepadAxisType, GamepadButtonType}, keyboard::KeyCode};
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! enum PlayerAction {
+//!     Jump,
+//!     Move,
+//! }
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     let input_map = InputMap::default()
+//!         .with(PlayerAction::Jump, KeyCode::Space)
+//!         .with(PlayerAction::Jump, GamepadButtonType::South)
+//!         .with(PlayerAction::Move, InputBinding::WASD)
+//!         .with(
+//!             PlayerAction::Move,
+//!             InputBinding::GamepadStick {
+//!                 x: GamepadAxisType::LeftStickX,
+//!                 y: GamepadAxisType::LeftStickY,
+//!             },
+//!         );
+//!     commands.spawn((input_map, ActionState::<PlayerAction>::default()));
+//! }
+//!
+//! fn move_player(players: Query<&ActionState<PlayerAction>>) {
+//!     for actions in &players {
+//!         if actions.just_pressed(PlayerAction::Jump) {
+//!             // jump
+//!         }
+//!         let direction = actions.axis_pair(PlayerAction::Move);
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins(InputActionPlugin::<PlayerAction>::default())
+//!     .add_systems(Update, move_player);
+//! ```
+
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    touch::Touches,
+    Axis, ButtonInput, InputSystem,
+};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    prelude::{Component, Query},
+    schedule::IntoSystemConfigs,
+    system::{Res, SystemParam},
+};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A game-specific action that inputs can be bound to, usually an enum.
+///
+/// Implemented for all types with the required bounds.
+pub trait InputAction: Debug + Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Debug + Copy + Eq + Hash + Send + Sync + 'static> InputAction for T {}
+
+/// A digital input that is either pressed or released.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ButtonBinding {
+    /// A key of the keyboard.
+    Key(KeyCode),
+    /// A button of the mouse.
+    Mouse(MouseButton),
+    /// A button of the gamepad of the [`InputMap`], or of any gamepad if it doesn't have one.
+    GamepadButton(GamepadButtonType),
+    /// Any finger touching the screen.
+    Touch,
+}
+
+/// An input an action can be bound to with an [`InputMap`].
+///
+/// Each binding produces a value in the `-1.0..=1.0` range on both axes. Buttons and single axes
+/// only produce a value on the `x` axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A button, with a value of `1.0` while it is pressed.
+    ///
+    /// Analog gamepad buttons such as triggers report how far they are pressed instead.
+    Button(ButtonBinding),
+    /// An axis of the gamepad of the [`InputMap`], or of any gamepad if it doesn't have one.
+    GamepadAxis(GamepadAxisType),
+    /// Two axes of a gamepad combined into a stick, with a circular dead zone.
+    GamepadStick {
+        /// The horizontal axis.
+        x: GamepadAxisType,
+        /// The vertical axis.
+        y: GamepadAxisType,
+    },
+    /// An axis emulated with two buttons.
+    VirtualAxis {
+        /// The button for the negative direction.
+        negative: ButtonBinding,
+        /// The button for the positive direction.
+        positive: ButtonBinding,
+    },
+    /// A stick emulated with four buttons. Diagonals have a length of `1.0`.
+    VirtualDPad {
+        /// The button for the positive `y` direction.
+        up: ButtonBinding,
+        /// The button for the negative `y` direction.
+        down: ButtonBinding,
+        /// The button for the negative `x` direction.
+        left: ButtonBinding,
+        /// The button for the positive `x` direction.
+        right: ButtonBinding,
+    },
+}
+
+impl InputBinding {
+    /// The W, A, S and D keys as a [`InputBinding::VirtualDPad`].
+    pub const WASD: Self = Self::VirtualDPad {
+        up: ButtonBinding::Key(KeyCode::W),
+        down: ButtonBinding::Key(KeyCode::S),
+        left: ButtonBinding::Key(KeyCode::A),
+        right: ButtonBinding::Key(KeyCode::D),
+    };
+
+    /// The arrow keys as a [`InputBinding::VirtualDPad`].
+    pub const ARROW_KEYS: Self = Self::VirtualDPad {
+        up: ButtonBinding::Key(KeyCode::Up),
+        down: ButtonBinding::Key(KeyCode::Down),
+        left: ButtonBinding::Key(KeyCode::Left),
+        right: ButtonBinding::Key(KeyCode::Right),
+    };
+
+    /// The D-Pad of a gamepad as a [`InputBinding::VirtualDPad`].
+    pub const GAMEPAD_DPAD: Self = Self::VirtualDPad {
+        up: ButtonBinding::GamepadButton(GamepadButtonType::DPadUp),
+        down: ButtonBinding::GamepadButton(GamepadButtonType::DPadDown),
+        left: ButtonBinding::GamepadButton(GamepadButtonType::DPadLeft),
+        right: ButtonBinding::GamepadButton(GamepadButtonType::DPadRight),
+    };
+}
+
+impl From<ButtonBinding> for InputBinding {
+    fn from(button: ButtonBinding) -> Self {
+        Self::Button(button)
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Button(ButtonBinding::Key(key))
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Button(ButtonBinding::Mouse(button))
+    }
+}
+
+impl From<GamepadButtonType> for InputBinding {
+    fn from(button: GamepadButtonType) -> Self {
+        Self::Button(ButtonBinding::GamepadButton(button))
+    }
+}
+
+impl From<GamepadAxisType> for InputBinding {
+    fn from(axis: GamepadAxisType) -> Self {
+        Self::GamepadAxis(axis)
+    }
+}
+
+/// The bindings of an action in an [`InputMap`].
+#[derive(Debug, Clone, PartialEq)]
+struct ActionBindings {
+    bindings: Vec<InputBinding>,
+    dead_zone: f32,
+}
+
+/// Binds the actions of type `A` to inputs.
+///
+/// The [`ActionState<A>`] of the same entity is updated from the bound inputs by the
+/// [`InputActionPlugin<A>`].
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct InputMap<A: InputAction> {
+    actions: HashMap<A, ActionBindings>,
+    gamepad: Option<Gamepad>,
+    enabled: bool,
+}
+
+impl<A: InputAction> Default for InputMap<A> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::default(),
+            gamepad: None,
+            enabled: true,
+        }
+    }
+}
+
+impl<A: InputAction> InputMap<A> {
+    /// The dead zone of actions that didn't set one with [`InputMap::set_dead_zone`].
+    pub const DEFAULT_DEAD_ZONE: f32 = 0.1;
+
+    /// Binds `action` to `binding`, in addition to its existing bindings.
+    #[must_use]
+    pub fn with(mut self, action: A, binding: impl Into<InputBinding>) -> Self {
+        self.insert(action, binding);
+        self
+    }
+
+    /// Reads gamepad inputs from `gamepad` only.
+    #[must_use]
+    pub fn with_gamepad(mut self, gamepad: Gamepad) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Binds `action` to `binding`, in addition to its existing bindings.
+    pub fn insert(&mut self, action: A, binding: impl Into<InputBinding>) {
+        let binding = binding.into();
+        let action = self
+            .actions
+            .entry(action)
+            .or_insert_with(|| ActionBindings {
+                bindings: Vec::new(),
+                dead_zone: Self::DEFAULT_DEAD_ZONE,
+            });
+        if !action.bindings.contains(&binding) {
+            action.bindings.push(binding);
+        }
+    }
+
+    /// Removes `binding` from the bindings of `action`.
+    ///
+    /// Returns `true` if the action was bound to it.
+    pub fn remove(&mut self, action: A, binding: impl Into<InputBinding>) -> bool {
+        let binding = binding.into();
+        let Some(action) = self.actions.get_mut(&action) else {
+            return false;
+        };
+        let len = action.bindings.len();
+        action.bindings.retain(|b| *b != binding);
+        action.bindings.len() != len
+    }
+
+    /// Replaces the `old` binding of `action` with `new`, keeping its position among the other bindings.
+    ///
+    /// If the action wasn't bound to `old`, `new` is added to its bindings.
+    pub fn rebind(
+        &mut self,
+        action: A,
+        old: impl Into<InputBinding>,
+        new: impl Into<InputBinding>,
+    ) {
+        let (old, new) = (old.into(), new.into());
+        let position = self
+            .actions
+            .get(&action)
+            .and_then(|action| action.bindings.iter().position(|binding| *binding == old));
+        match position {
+            Some(position) if !self.bindings(action).contains(&new) => {
+                self.actions.get_mut(&action).unwrap().bindings[position] = new;
+            }
+            Some(_) => {
+                self.remove(action, old);
+            }
+            None => self.insert(action, new),
+        }
+    }
+
+    /// Removes all bindings of `action`.
+    pub fn clear_action(&mut self, action: A) {
+        if let Some(action) = self.actions.get_mut(&action) {
+            action.bindings.clear();
+        }
+    }
+
+    /// The inputs bound to `action`.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        self.actions
+            .get(&action)
+            .map_or(&[], |action| &action.bindings)
+    }
+
+    /// Iterates over the actions with at least one binding.
+    pub fn actions(&self) -> impl Iterator<Item = A> + '_ {
+        self.actions
+            .iter()
+            .filter(|(_, action)| !action.bindings.is_empty())
+            .map(|(action, _)| *action)
+    }
+
+    /// Sets the dead zone of the analog inputs bound to `action`.
+    ///
+    /// Analog values whose magnitude is below the dead zone are treated as `0.0`, and values above
+    /// it are rescaled so they still cover the full `0.0..=1.0` range.
+    pub fn set_dead_zone(&mut self, action: A, dead_zone: f32) {
+        let dead_zone = dead_zone.clamp(0.0, 1.0);
+        self.actions
+            .entry(action)
+            .or_insert_with(|| ActionBindings {
+                bindings: Vec::new(),
+                dead_zone,
+            })
+            .dead_zone = dead_zone;
+    }
+
+    /// The dead zone of the analog inputs bound to `action`.
+    pub fn dead_zone(&self, action: A) -> f32 {
+        self.actions
+            .get(&action)
+            .map_or(Self::DEFAULT_DEAD_ZONE, |action| action.dead_zone)
+    }
+
+    /// The gamepad inputs are read from, or `None` if they are read from all gamepads.
+    pub fn gamepad(&self) -> Option<Gamepad> {
+        self.gamepad
+    }
+
+    /// Sets the gamepad inputs are read from, or `None` to read them from all gamepads.
+    pub fn set_gamepad(&mut self, gamepad: Option<Gamepad>) {
+        self.gamepad = gamepad;
+    }
+
+    /// Whether the [`ActionState`] is updated from this map.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the map, e.g. when switching between menus and gameplay.
+    ///
+    /// All the actions of a disabled map are released.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+/// The state of a single action in an [`ActionState`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ActionData {
+    /// Whether the action is pressed.
+    pub pressed: bool,
+    /// Whether the action was pressed in the previous update.
+    pub previously_pressed: bool,
+    /// The value of the action. Only the `x` axis is used by buttons and single axes.
+    pub value: Vec2,
+}
+
+/// The state of the actions of type `A`, updated from the [`InputMap<A>`] of the same entity.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ActionState<A: InputAction> {
+    actions: HashMap<A, ActionData>,
+}
+
+impl<A: InputAction> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::default(),
+        }
+    }
+}
+
+impl<A: InputAction> ActionState<A> {
+    /// The state of `action`.
+    pub fn data(&self, action: A) -> ActionData {
+        self.actions.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if `action` is pressed.
+    ///
+    /// An analog binding presses its action while its value is outside of the dead zone.
+    pub fn pressed(&self, action: A) -> bool {
+        self.data(action).pressed
+    }
+
+    /// Returns `true` if `action` started being pressed in the last update.
+    pub fn just_pressed(&self, action: A) -> bool {
+        let data = self.data(action);
+        data.pressed && !data.previously_pressed
+    }
+
+    /// Returns `true` if `action` stopped being pressed in the last update.
+    pub fn just_released(&self, action: A) -> bool {
+        let data = self.data(action);
+        !data.pressed && data.previously_pressed
+    }
+
+    /// The value of `action` along a single axis, in the `-1.0..=1.0` range.
+    pub fn value(&self, action: A) -> f32 {
+        self.data(action).value.x
+    }
+
+    /// The value of `action` along two axes, e.g. for a stick.
+    pub fn axis_pair(&self, action: A) -> Vec2 {
+        self.data(action).value
+    }
+
+    /// Sets the state of `action`, e.g. to simulate inputs.
+    ///
+    /// The state is overwritten by the next update.
+    pub fn set(&mut self, action: A, pressed: bool, value: Vec2) {
+        let data = self.actions.entry(action).or_default();
+        data.pressed = pressed;
+        data.value = value;
+    }
+
+    /// Releases all actions.
+    pub fn release_all(&mut self) {
+        for data in self.actions.values_mut() {
+            data.pressed = false;
+            data.value = Vec2::ZERO;
+        }
+    }
+}
+
+/// The raw inputs actions can be bound to.
+#[derive(SystemParam)]
+pub struct ActionInputs<'w> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    gamepad_button_axes: Res<'w, Axis<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+    gamepads: Res<'w, Gamepads>,
+    touches: Res<'w, Touches>,
+}
+
+impl<'w> ActionInputs<'w> {
+    /// The button that was pressed in the last update, if any.
+    ///
+    /// This is useful to let players rebind an action to the next button they press.
+    /// Only gamepad buttons of `gamepad` are considered, or of any gamepad if it's `None`.
+    pub fn just_pressed_binding(&self, gamepad: Option<Gamepad>) -> Option<ButtonBinding> {
+        self.keys
+            .get_just_pressed()
+            .next()
+            .map(|key| ButtonBinding::Key(*key))
+            .or_else(|| {
+                self.mouse_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| ButtonBinding::Mouse(*button))
+            })
+            .or_else(|| {
+                self.gamepad_buttons
+                    .get_just_pressed()
+                    .find(|button| gamepad.is_none() || gamepad == Some(button.gamepad))
+                    .map(|button| ButtonBinding::GamepadButton(button.button_type))
+            })
+            .or_else(|| {
+                self.touches
+                    .any_just_pressed()
+                    .then_some(ButtonBinding::Touch)
+            })
+    }
+
+    fn gamepads(&self, gamepad: Option<Gamepad>) -> impl Iterator<Item = Gamepad> + '_ {
+        self.gamepads
+            .iter()
+            .filter(move |connected| gamepad.is_none() || gamepad == Some(*connected))
+    }
+
+    fn button_value(&self, button: ButtonBinding, gamepad: Option<Gamepad>) -> f32 {
+        let pressed = |pressed: bool| if pressed { 1.0 } else { 0.0 };
+        match button {
+            ButtonBinding::Key(key) => pressed(self.keys.pressed(key)),
+            ButtonBinding::Mouse(button) => pressed(self.mouse_buttons.pressed(button)),
+            ButtonBinding::GamepadButton(button_type) => self
+                .gamepads(gamepad)
+                .map(|gamepad| {
+                    let button = GamepadButton::new(gamepad, button_type);
+                    self.gamepad_button_axes
+                        .get(button)
+                        .unwrap_or_else(|| pressed(self.gamepad_buttons.pressed(button)))
+                })
+                .fold(0.0, f32::max),
+            ButtonBinding::Touch => pressed(self.touches.iter().next().is_some()),
+        }
+    }
+
+    fn axis_value(&self, axis_type: GamepadAxisType, gamepad: Option<Gamepad>) -> f32 {
+        self.gamepads(gamepad)
+            .filter_map(|gamepad| self.gamepad_axes.get(GamepadAxis::new(gamepad, axis_type)))
+            .fold(0.0, |a, b| if b.abs() > a.abs() { b } else { a })
+    }
+
+    /// The value of `binding` with `dead_zone` applied.
+    fn binding_value(
+        &self,
+        binding: InputBinding,
+        gamepad: Option<Gamepad>,
+        dead_zone: f32,
+    ) -> Vec2 {
+        let value = match binding {
+            InputBinding::Button(button) => Vec2::new(self.button_value(button, gamepad), 0.0),
+            InputBinding::GamepadAxis(axis) => Vec2::new(self.axis_value(axis, gamepad), 0.0),
+            InputBinding::GamepadStick { x, y } => {
+                Vec2::new(self.axis_value(x, gamepad), self.axis_value(y, gamepad))
+            }
+            InputBinding::VirtualAxis { negative, positive } => Vec2::new(
+                self.button_value(positive, gamepad) - self.button_value(negative, gamepad),
+                0.0,
+            ),
+            InputBinding::VirtualDPad {
+                up,
+                down,
+                left,
+                right,
+            } => Vec2::new(
+                self.button_value(right, gamepad) - self.button_value(left, gamepad),
+                self.button_value(up, gamepad) - self.button_value(down, gamepad),
+            )
+            .clamp_length_max(1.0),
+        };
+        apply_dead_zone(value, dead_zone)
+    }
+}
+
+/// Zeroes `value` if its length is within `dead_zone`, and rescales it so the values outside
+/// of the dead zone still cover the full range.
+fn apply_dead_zone(value: Vec2, dead_zone: f32) -> Vec2 {
+    let length = value.length();
+    if length <= dead_zone {
+        return Vec2::ZERO;
+    }
+    if dead_zone >= 1.0 {
+        return value;
+    }
+    let rescaled = ((length - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    value * (rescaled / length)
+}
+
+/// Updates each [`ActionState<A>`] from the [`InputMap<A>`] of the same entity.
+///
+/// When several bindings of an action are active, its value is the one with the largest magnitude.
+pub fn update_action_state<A: InputAction>(
+    inputs: ActionInputs,
+    mut query: Query<(&InputMap<A>, &mut ActionState<A>)>,
+) {
+    for (input_map, mut action_state) in &mut query {
+        for data in action_state.actions.values_mut() {
+            data.previously_pressed = data.pressed;
+        }
+        if !input_map.enabled {
+            action_state.release_all();
+            continue;
+        }
+        for (action, bindings) in &input_map.actions {
+            let value = bindings
+                .bindings
+                .iter()
+                .map(|binding| {
+                    inputs.binding_value(*binding, input_map.gamepad, bindings.dead_zone)
+                })
+                .fold(Vec2::ZERO, |a, b| {
+                    if b.length_squared() > a.length_squared() {
+                        b
+                    } else {
+                        a
+                    }
+                });
+            action_state.set(*action, value != Vec2::ZERO, value);
+        }
+    }
+}
+
+/// Updates the [`ActionState<A>`] of each entity from its [`InputMap<A>`].
+pub struct InputActionPlugin<A: InputAction>(PhantomData<A>);
+
+impl<A: InputAction> Default for InputActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: InputAction> Plugin for InputActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, update_action_state::<A>.after(InputSystem));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum TestAction {
+        Jump,
+        Move,
+    }
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        world.init_resource::<ButtonInput<GamepadButton>>();
+        world.init_resource::<Axis<GamepadButton>>();
+        world.init_resource::<Axis<GamepadAxis>>();
+        world.init_resource::<Gamepads>();
+        world.init_resource::<Touches>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_action_state::<TestAction>);
+        (world, schedule)
+    }
+
+    #[test]
+    fn buttons_press_actions() {
+        let (mut world, mut schedule) = setup();
+        let input_map = InputMap::default()
+            .with(TestAction::Jump, KeyCode::Space)
+            .with(TestAction::Move, InputBinding::WASD);
+        let player = world
+            .spawn((input_map, ActionState::<TestAction>::default()))
+            .id();
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::D);
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::W);
+        schedule.run(&mut world);
+        let state = world.get::<ActionState<TestAction>>(player).unwrap();
+        assert!(state.just_pressed(TestAction::Jump));
+        let direction = state.axis_pair(TestAction::Move);
+        assert!((direction - Vec2::ONE.normalize()).length() < 1e-5);
+
+        schedule.run(&mut world);
+        let state = world.get::<ActionState<TestAction>>(player).unwrap();
+        assert!(state.pressed(TestAction::Jump));
+        assert!(!state.just_pressed(TestAction::Jump));
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::Space);
+        schedule.run(&mut world);
+        let state = world.get::<ActionState<TestAction>>(player).unwrap();
+        assert!(state.just_released(TestAction::Jump));
+    }
+
+    #[test]
+    fn disabled_map_releases_actions() {
+        let (mut world, mut schedule) = setup();
+        let input_map = InputMap::default().with(TestAction::Jump, KeyCode::Space);
+        let player = world
+            .spawn((input_map, ActionState::<TestAction>::default()))
+            .id();
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        schedule.run(&mut world);
+        world
+            .get_mut::<InputMap<TestAction>>(player)
+            .unwrap()
+            .set_enabled(false);
+        schedule.run(&mut world);
+        let state = world.get::<ActionState<TestAction>>(player).unwrap();
+        assert!(state.just_released(TestAction::Jump));
+    }
+
+    #[test]
+    fn rebind_action() {
+        let mut input_map = InputMap::default()
+            .with(TestAction::Jump, KeyCode::Space)
+            .with(TestAction::Jump, GamepadButtonType::South);
+        input_map.rebind(TestAction::Jump, KeyCode::Space, KeyCode::Return);
+        assert_eq!(
+            input_map.bindings(TestAction::Jump),
+            &[KeyCode::Return.into(), GamepadButtonType::South.into()]
+        );
+        assert!(input_map.remove(TestAction::Jump, GamepadButtonType::South));
+        assert_eq!(
+            input_map.bindings(TestAction::Jump),
+            &[KeyCode::Return.into()]
+        );
+    }
+
+    #[test]
+    fn dead_zone_rescales_values() {
+        assert_eq!(apply_dead_zone(Vec2::new(0.05, 0.0), 0.1), Vec2::ZERO);
+        assert_eq!(
+            apply_dead_zone(Vec2::new(1.0, 0.0), 0.1),
+            Vec2::new(1.0, 0.0)
+        );
+        let value = apply_dead_zone(Vec2::new(0.55, 0.0), 0.1);
+        assert!((value.x - 0.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,552 @@
+//! Recognition of touch gestures such as taps, swipes and pinches.
+//!
+//! Gestures are recognized from the [`Touches`] resource by the [`touch_gesture_system`], and sent as
+//! events. Positions are in the logical pixels of the window, with `y` pointing down like the positions
+//! of [`Touch`](crate::touch::Touch)es. The thresholds used to recognize gestures can be changed in
+//! the [`GestureSettings`] resource.
+
+use crate::touch::Touches;
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::{Local, Res, Resource},
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::{Duration, HashMap, Instant};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The thresholds used to recognize touch gestures.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub struct GestureSettings {
+    /// The longest a touch can last to be a tap.
+    pub tap_max_duration: Duration,
+    /// The farthest a touch can move, in logical pixels, to be a tap or a long press.
+    pub tap_max_distance: f32,
+    /// The longest time between two taps for them to be a double tap.
+    pub double_tap_max_interval: Duration,
+    /// The farthest two taps can be from each other, in logical pixels, to be a double tap.
+    pub double_tap_max_distance: f32,
+    /// How long a touch must be held in place to be a long press.
+    pub long_press_duration: Duration,
+    /// The shortest distance a touch must move, in logical pixels, to be a swipe.
+    pub swipe_min_distance: f32,
+    /// The longest a touch can last to be a swipe.
+    pub swipe_max_duration: Duration,
+    /// How much the distance between two touches must change, in logical pixels, before pinch
+    /// events are sent.
+    pub pinch_min_distance: f32,
+    /// How much the angle between two touches must change, in radians, before rotate events are sent.
+    pub rotate_min_angle: f32,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: Duration::from_millis(250),
+            tap_max_distance: 10.0,
+            double_tap_max_interval: Duration::from_millis(300),
+            double_tap_max_distance: 30.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_max_duration: Duration::from_millis(500),
+            pinch_min_distance: 10.0,
+            rotate_min_angle: 0.1,
+        }
+    }
+}
+
+/// A touch that was quickly pressed and released in place.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TapGesture {
+    /// Where the touch was released.
+    pub position: Vec2,
+}
+
+/// A second [`TapGesture`] shortly after and close to a first one.
+///
+/// The second tap is also sent as a [`TapGesture`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DoubleTapGesture {
+    /// Where the second touch was released.
+    pub position: Vec2,
+}
+
+/// A touch held in place for a while.
+///
+/// Sent once while the touch is still pressed, which then isn't recognized as a tap or swipe.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LongPressGesture {
+    /// The position of the touch.
+    pub position: Vec2,
+}
+
+/// The main direction of a [`SwipeGesture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum SwipeDirection {
+    /// Towards the top of the window.
+    Up,
+    /// Towards the bottom of the window.
+    Down,
+    /// Towards the left of the window.
+    Left,
+    /// Towards the right of the window.
+    Right,
+}
+
+/// A touch that quickly moved in a direction before being released.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SwipeGesture {
+    /// Where the touch started.
+    pub start: Vec2,
+    /// Where the touch was released.
+    pub end: Vec2,
+    /// The main direction of the swipe.
+    pub direction: SwipeDirection,
+    /// The average velocity of the touch, in logical pixels per second.
+    pub velocity: Vec2,
+}
+
+/// Two touches moving towards or away from each other.
+///
+/// Sent every frame the distance between the touches changes, once it changed by more than
+/// [`GestureSettings::pinch_min_distance`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PinchGesture {
+    /// The point between the two touches.
+    pub center: Vec2,
+    /// The ratio between the distance of the touches in this frame and the previous one.
+    ///
+    /// Greater than `1.0` when the touches move apart, e.g. to zoom in.
+    pub scale: f32,
+}
+
+/// Two touches rotating around each other.
+///
+/// Sent every frame the angle between the touches changes, once it changed by more than
+/// [`GestureSettings::rotate_min_angle`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct RotateGesture {
+    /// The point between the two touches.
+    pub center: Vec2,
+    /// The change of angle since the previous frame, in radians.
+    ///
+    /// Positive when the touches rotate clockwise on screen.
+    pub delta: f32,
+}
+
+/// A gesture recognized by a [`GestureRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Gesture {
+    Tap(TapGesture),
+    DoubleTap(DoubleTapGesture),
+    LongPress(LongPressGesture),
+    Swipe(SwipeGesture),
+    Pinch(PinchGesture),
+    Rotate(RotateGesture),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    start_time: Duration,
+    start_position: Vec2,
+    last_position: Vec2,
+    /// Set once the touch was part of a multi-touch gesture, or a long press.
+    consumed: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TwoTouchGesture {
+    ids: (u64, u64),
+    distance_change: f32,
+    angle_change: f32,
+    pinching: bool,
+    rotating: bool,
+}
+
+/// Tracks touches over time to recognize gestures.
+#[derive(Debug, Default)]
+pub struct GestureRecognizer {
+    start: Option<Instant>,
+    touches: HashMap<u64, TrackedTouch>,
+    last_tap: Option<(Duration, Vec2)>,
+    two_touch: Option<TwoTouchGesture>,
+}
+
+impl GestureRecognizer {
+    /// Recognizes the gestures made by `touches` up to `now`, the time since the app started.
+    fn update(
+        &mut self,
+        touches: &Touches,
+        settings: &GestureSettings,
+        now: Duration,
+    ) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        for touch in touches.iter_just_pressed() {
+            self.touches.insert(
+                touch.id(),
+                TrackedTouch {
+                    start_time: now,
+                    start_position: touch.start_position(),
+                    last_position: touch.start_position(),
+                    consumed: false,
+                },
+            );
+        }
+
+        let mut pressed: Vec<_> = touches.iter().map(|touch| touch.id()).collect();
+        pressed.sort_unstable();
+        if pressed.len() >= 2 {
+            for id in &pressed {
+                if let Some(tracked) = self.touches.get_mut(id) {
+                    tracked.consumed = true;
+                }
+            }
+            self.update_two_touch_gesture(
+                touches,
+                settings,
+                (pressed[0], pressed[1]),
+                &mut gestures,
+            );
+        } else {
+            self.two_touch = None;
+        }
+
+        if let [id] = pressed[..] {
+            if let (Some(tracked), Some(touch)) =
+                (self.touches.get_mut(&id), touches.get_pressed(id))
+            {
+                if !tracked.consumed
+                    && now - tracked.start_time >= settings.long_press_duration
+                    && touch.position().distance(tracked.start_position)
+                        <= settings.tap_max_distance
+                {
+                    tracked.consumed = true;
+                    gestures.push(Gesture::LongPress(LongPressGesture {
+                        position: touch.position(),
+                    }));
+                }
+            }
+        }
+
+        for touch in touches.iter_just_released() {
+            let Some(tracked) = self.touches.remove(&touch.id()) else {
+                continue;
+            };
+            if tracked.consumed {
+                continue;
+            }
+            let duration = now - tracked.start_time;
+            let movement = touch.position() - tracked.start_position;
+            if duration <= settings.tap_max_duration
+                && movement.length() <= settings.tap_max_distance
+            {
+                gestures.push(Gesture::Tap(TapGesture {
+                    position: touch.position(),
+                }));
+                match self.last_tap {
+                    Some((time, position))
+                        if now - time <= settings.double_tap_max_interval
+                            && position.distance(touch.position())
+                                <= settings.double_tap_max_distance =>
+                    {
+                        gestures.push(Gesture::DoubleTap(DoubleTapGesture {
+                            position: touch.position(),
+                        }));
+                        self.last_tap = None;
+                    }
+                    _ => self.last_tap = Some((now, touch.position())),
+                }
+            } else if duration <= settings.swipe_max_duration
+                && movement.length() >= settings.swipe_min_distance
+            {
+                let direction = if movement.x.abs() > movement.y.abs() {
+                    if movement.x > 0.0 {
+                        SwipeDirection::Right
+                    } else {
+                        SwipeDirection::Left
+                    }
+                } else if movement.y > 0.0 {
+                    SwipeDirection::Down
+                } else {
+                    SwipeDirection::Up
+                };
+                gestures.push(Gesture::Swipe(SwipeGesture {
+                    start: tracked.start_position,
+                    end: touch.position(),
+                    direction,
+                    velocity: movement / duration.as_secs_f32().max(f32::EPSILON),
+                }));
+            }
+        }
+        for touch in touches.iter_just_canceled() {
+            self.touches.remove(&touch.id());
+        }
+
+        for touch in touches.iter() {
+            if let Some(tracked) = self.touches.get_mut(&touch.id()) {
+                tracked.last_position = touch.position();
+            }
+        }
+
+        gestures
+    }
+
+    fn update_two_touch_gesture(
+        &mut self,
+        touches: &Touches,
+        settings: &GestureSettings,
+        ids: (u64, u64),
+        gestures: &mut Vec<Gesture>,
+    ) {
+        let (Some(first), Some(second), Some(first_tracked), Some(second_tracked)) = (
+            touches.get_pressed(ids.0),
+            touches.get_pressed(ids.1),
+            self.touches.get(&ids.0),
+            self.touches.get(&ids.1),
+        ) else {
+            return;
+        };
+        let gesture = match &mut self.two_touch {
+            Some(gesture) if gesture.ids == ids => gesture,
+            two_touch => two_touch.insert(TwoTouchGesture {
+                ids,
+                ..Default::default()
+            }),
+        };
+
+        let previous = second_tracked.last_position - first_tracked.last_position;
+        let current = second.position() - first.position();
+        let center = (first.position() + second.position()) / 2.0;
+        if previous == Vec2::ZERO || current == Vec2::ZERO || previous == current {
+            return;
+        }
+
+        let scale = current.length() / previous.length();
+        gesture.distance_change += current.length() - previous.length();
+        gesture.pinching |= gesture.distance_change.abs() >= settings.pinch_min_distance;
+        if gesture.pinching && scale != 1.0 {
+            gestures.push(Gesture::Pinch(PinchGesture { center, scale }));
+        }
+
+        let delta = previous.angle_between(current);
+        gesture.angle_change += delta;
+        gesture.rotating |= gesture.angle_change.abs() >= settings.rotate_min_angle;
+        if gesture.rotating && delta != 0.0 {
+            gestures.push(Gesture::Rotate(RotateGesture { center, delta }));
+        }
+    }
+}
+
+/// Sends the gestures recognized from the [`Touches`] resource as events.
+#[allow(clippy::too_many_arguments)]
+pub fn touch_gesture_system(
+    mut recognizer: Local<GestureRecognizer>,
+    touches: Res<Touches>,
+    settings: Res<GestureSettings>,
+    mut taps: EventWriter<TapGesture>,
+    mut double_taps: EventWriter<DoubleTapGesture>,
+    mut long_presses: EventWriter<LongPressGesture>,
+    mut swipes: EventWriter<SwipeGesture>,
+    mut pinches: EventWriter<PinchGesture>,
+    mut rotations: EventWriter<RotateGesture>,
+) {
+    let now = recognizer.start.get_or_insert_with(Instant::now).elapsed();
+    for gesture in recognizer.update(&touches, &settings, now) {
+        match gesture {
+            Gesture::Tap(event) => {
+                taps.send(event);
+            }
+            Gesture::DoubleTap(event) => {
+                double_taps.send(event);
+            }
+            Gesture::LongPress(event) => {
+                long_presses.send(event);
+            }
+            Gesture::Swipe(event) => {
+                swipes.send(event);
+            }
+            Gesture::Pinch(event) => {
+                pinches.send(event);
+            }
+            Gesture::Rotate(event) => {
+                rotations.send(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::touch::{touch_screen_input_system, TouchInput, TouchPhase};
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    struct TestTouches {
+        world: World,
+        schedule: Schedule,
+        recognizer: GestureRecognizer,
+        settings: GestureSettings,
+    }
+
+    impl TestTouches {
+        fn new() -> Self {
+            let mut world = World::new();
+            world.init_resource::<Touches>();
+            world.init_resource::<Events<TouchInput>>();
+            let mut schedule = Schedule::default();
+            schedule.add_systems(touch_screen_input_system);
+            Self {
+                world,
+                schedule,
+                recognizer: GestureRecognizer::default(),
+                settings: GestureSettings::default(),
+            }
+        }
+
+        fn touch(&mut self, id: u64, phase: TouchPhase, position: Vec2) -> &mut Self {
+            self.world.send_event(TouchInput {
+                phase,
+                position,
+                force: None,
+                id,
+            });
+            self
+        }
+
+        fn update(&mut self, now_millis: u64) -> Vec<Gesture> {
+            self.schedule.run(&mut self.world);
+            self.world.resource_mut::<Events<TouchInput>>().update();
+            let touches = self.world.resource::<Touches>();
+            self.recognizer
+                .update(touches, &self.settings, Duration::from_millis(now_millis))
+        }
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let mut touches = TestTouches::new();
+        let position = Vec2::new(100.0, 100.0);
+        touches.touch(0, TouchPhase::Started, position);
+        assert!(touches.update(0).is_empty());
+        touches.touch(0, TouchPhase::Ended, position);
+        assert_eq!(
+            touches.update(100),
+            vec![Gesture::Tap(TapGesture { position })]
+        );
+
+        let position = position + Vec2::new(5.0, 0.0);
+        touches.touch(1, TouchPhase::Started, position);
+        touches.update(200);
+        touches.touch(1, TouchPhase::Ended, position);
+        assert_eq!(
+            touches.update(250),
+            vec![
+                Gesture::Tap(TapGesture { position }),
+                Gesture::DoubleTap(DoubleTapGesture { position })
+            ]
+        );
+    }
+
+    #[test]
+    fn long_press_is_not_a_tap() {
+        let mut touches = TestTouches::new();
+        let position = Vec2::new(100.0, 100.0);
+        touches.touch(0, TouchPhase::Started, position);
+        touches.update(0);
+        assert_eq!(
+            touches.update(600),
+            vec![Gesture::LongPress(LongPressGesture { position })]
+        );
+        assert!(touches.update(700).is_empty());
+        touches.touch(0, TouchPhase::Ended, position);
+        assert!(touches.update(800).is_empty());
+    }
+
+    #[test]
+    fn swipe() {
+        let mut touches = TestTouches::new();
+        touches.touch(0, TouchPhase::Started, Vec2::new(100.0, 100.0));
+        touches.update(0);
+        touches.touch(0, TouchPhase::Moved, Vec2::new(150.0, 110.0));
+        touches.update(100);
+        touches.touch(0, TouchPhase::Moved, Vec2::new(200.0, 120.0));
+        touches.touch(0, TouchPhase::Ended, Vec2::new(200.0, 120.0));
+        let gestures = touches.update(200);
+        let [Gesture::Swipe(swipe)] = gestures[..] else {
+            panic!("expected a swipe, got {gestures:?}");
+        };
+        assert_eq!(swipe.direction, SwipeDirection::Right);
+        assert!((swipe.velocity - Vec2::new(500.0, 100.0)).length() < 1e-3);
+    }
+
+    #[test]
+    fn pinch() {
+        let mut touches = TestTouches::new();
+        touches
+            .touch(0, TouchPhase::Started, Vec2::new(90.0, 100.0))
+            .touch(1, TouchPhase::Started, Vec2::new(110.0, 100.0));
+        touches.update(0);
+        // Below the threshold, no pinch yet
+        touches.touch(1, TouchPhase::Moved, Vec2::new(115.0, 100.0));
+        assert!(touches.update(16).is_empty());
+        touches.touch(0, TouchPhase::Moved, Vec2::new(85.0, 100.0));
+        assert_eq!(
+            touches.update(32),
+            vec![Gesture::Pinch(PinchGesture {
+                center: Vec2::new(100.0, 100.0),
+                scale: 30.0 / 25.0,
+            })]
+        );
+        // A pinch is not a tap
+        touches
+            .touch(0, TouchPhase::Ended, Vec2::new(85.0, 100.0))
+            .touch(1, TouchPhase::Ended, Vec2::new(115.0, 100.0));
+        assert!(touches.update(48).is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+//! Rumble patterns for gamepads, and the hook used by platforms to play them.
+//!
+//! A [`RumblePattern`] is a sequence of pulses and pauses, each with its own intensity and
+//! fade in and out, that can be played once, a number of times, or in a loop. Patterns are played
+//! on a gamepad by sending a [`RumblePatternRequest`]. Several patterns can play on the same gamepad
+//! at once: only the ones with the highest priority are felt, and their intensities add up.
+//!
+//! The resulting intensity of each gamepad is stored in the [`GamepadHaptics`] resource, and sent
+//! to every [`GamepadHapticsBackend`] when it changes. `bevy_gilrs` plays it on the gamepads it
+//! manages, and other backends can be added to [`GamepadHapticsBackends`] for platforms or devices
+//! it doesn't support.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{gamepad::{GamepadRumbleIntensity, Gamepads}, haptics::*};
+//! # use bevy_utils::Duration;
+//! fn heartbeat(mut requests: EventWriter<RumblePatternRequest>, gamepads: Res<Gamepads>) {
+//!     let pattern = RumblePattern::new()
+//!         .pulse(GamepadRumbleIntensity::strong_motor(0.8), Duration::from_millis(100))
+//!         .pause(Duration::from_millis(100))
+//!         .pulse(GamepadRumbleIntensity::strong_motor(0.5), Duration::from_millis(100))
+//!         .fade(Duration::ZERO, Duration::from_millis(50))
+//!         .pause(Duration::from_millis(600))
+//!         .repeat(RumbleRepeat::Forever);
+//!     for gamepad in gamepads.iter() {
+//!         requests.send(RumblePatternRequest::Play {
+//!             gamepad,
+//!             pattern: pattern.clone(),
+//!             priority: 0,
+//!         });
+//!     }
+//! }
+//! ```
+
+use crate::gamepad::{Gamepad, GamepadRumbleIntensity};
+use bevy_ecs::{
+    event::{Event, EventReader},
+    system::{ResMut, Resource},
+};
+use bevy_utils::{Duration, HashMap, Instant};
+
+/// A step of a [`RumblePattern`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumbleStep {
+    /// The intensity of the motors during the step.
+    pub intensity: GamepadRumbleIntensity,
+    /// How long the step lasts.
+    pub duration: Duration,
+    /// How long the intensity takes to ramp up from zero at the start of the step.
+    pub fade_in: Duration,
+    /// How long the intensity takes to ramp down to zero at the end of the step.
+    pub fade_out: Duration,
+}
+
+impl RumbleStep {
+    /// The intensity of the step `elapsed` after it started.
+    pub fn intensity_at(&self, elapsed: Duration) -> GamepadRumbleIntensity {
+        let ramp = |elapsed: Duration, fade: Duration| {
+            if fade.is_zero() {
+                1.0
+            } else {
+                (elapsed.as_secs_f32() / fade.as_secs_f32()).min(1.0)
+            }
+        };
+        let factor = ramp(elapsed, self.fade_in)
+            .min(ramp(self.duration.saturating_sub(elapsed), self.fade_out));
+        GamepadRumbleIntensity {
+            strong_motor: self.intensity.strong_motor * factor,
+            weak_motor: self.intensity.weak_motor * factor,
+        }
+    }
+}
+
+/// How many times a [`RumblePattern`] is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RumbleRepeat {
+    /// The pattern is played once.
+    #[default]
+    Once,
+    /// The pattern is played the given number of times.
+    Times(u32),
+    /// The pattern loops until it is stopped with [`RumblePatternRequest::Stop`].
+    Forever,
+}
+
+/// A sequence of rumble pulses and pauses.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RumblePattern {
+    /// The steps of the pattern, played in order.
+    pub steps: Vec<RumbleStep>,
+    /// How many times the steps are played.
+    pub repeat: RumbleRepeat,
+}
+
+impl RumblePattern {
+    /// Creates an empty pattern, played once.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a step rumbling at `intensity` for `duration`.
+    #[must_use]
+    pub fn pulse(mut self, intensity: GamepadRumbleIntensity, duration: Duration) -> Self {
+        self.steps.push(RumbleStep {
+            intensity,
+            duration,
+            fade_in: Duration::ZERO,
+            fade_out: Duration::ZERO,
+        });
+        self
+    }
+
+    /// Adds a step without rumble for `duration`.
+    #[must_use]
+    pub fn pause(self, duration: Duration) -> Self {
+        self.pulse(
+            GamepadRumbleIntensity {
+                strong_motor: 0.0,
+                weak_motor: 0.0,
+            },
+            duration,
+        )
+    }
+
+    /// Sets the fade in and fade out of the last step.
+    #[must_use]
+    pub fn fade(mut self, fade_in: Duration, fade_out: Duration) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.fade_in = fade_in;
+            step.fade_out = fade_out;
+        }
+        self
+    }
+
+    /// Sets how many times the pattern is played.
+    #[must_use]
+    pub fn repeat(mut self, repeat: RumbleRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// The duration of a single play of the steps.
+    pub fn cycle_duration(&self) -> Duration {
+        self.steps.iter().map(|step| step.duration).sum()
+    }
+
+    /// The duration of the whole pattern, or `None` if it loops forever.
+    pub fn duration(&self) -> Option<Duration> {
+        match self.repeat {
+            RumbleRepeat::Once => Some(self.cycle_duration()),
+            RumbleRepeat::Times(times) => Some(self.cycle_duration() * times),
+            RumbleRepeat::Forever => None,
+        }
+    }
+
+    /// The intensity of the pattern `elapsed` after it started, or `None` once it is finished.
+    pub fn intensity_at(&self, elapsed: Duration) -> Option<GamepadRumbleIntensity> {
+        let cycle = self.cycle_duration();
+        if cycle.is_zero() || self.duration().is_some_and(|duration| elapsed >= duration) {
+            return None;
+        }
+        let cycle_nanos = cycle.as_nanos();
+        let mut elapsed = Duration::from_nanos((elapsed.as_nanos() % cycle_nanos) as u64);
+        for step in &self.steps {
+            if elapsed < step.duration {
+                return Some(step.intensity_at(elapsed));
+            }
+            elapsed -= step.duration;
+        }
+        None
+    }
+}
+
+/// Controls the [`RumblePattern`]s playing on a [`Gamepad`].
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum RumblePatternRequest {
+    /// Plays a pattern on a gamepad, along with the patterns already playing on it.
+    Play {
+        /// The gamepad to rumble.
+        gamepad: Gamepad,
+        /// The pattern to play.
+        pattern: RumblePattern,
+        /// While patterns with a higher priority are playing on the same gamepad, this pattern
+        /// keeps advancing but isn't felt. Patterns with the same priority add up.
+        priority: i32,
+    },
+    /// Stops all the patterns playing on a gamepad.
+    Stop {
+        /// The gamepad to stop.
+        gamepad: Gamepad,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct PlayingPattern {
+    pattern: RumblePattern,
+    priority: i32,
+    start: Duration,
+}
+
+/// The [`RumblePattern`]s playing on each gamepad, and the resulting intensity of its motors.
+#[derive(Resource, Debug, Default)]
+pub struct GamepadHaptics {
+    start: Option<Instant>,
+    playing: HashMap<Gamepad, Vec<PlayingPattern>>,
+    intensities: HashMap<Gamepad, GamepadRumbleIntensity>,
+    changed: Vec<Gamepad>,
+}
+
+impl GamepadHaptics {
+    /// The intensity the motors of `gamepad` should rumble at.
+    pub fn intensity(&self, gamepad: Gamepad) -> GamepadRumbleIntensity {
+        self.intensities
+            .get(&gamepad)
+            .copied()
+            .unwrap_or(GamepadRumbleIntensity {
+                strong_motor: 0.0,
+                weak_motor: 0.0,
+            })
+    }
+
+    /// Returns `true` if a pattern is playing on `gamepad`.
+    pub fn is_playing(&self, gamepad: Gamepad) -> bool {
+        self.playing.contains_key(&gamepad)
+    }
+
+    /// The gamepads whose intensity changed in the last update, and their new intensity.
+    pub fn changed(&self) -> impl Iterator<Item = (Gamepad, GamepadRumbleIntensity)> + '_ {
+        self.changed
+            .iter()
+            .map(|gamepad| (*gamepad, self.intensity(*gamepad)))
+    }
+
+    fn handle_request(&mut self, request: RumblePatternRequest, now: Duration) {
+        match request {
+            RumblePatternRequest::Play {
+                gamepad,
+                pattern,
+                priority,
+            } => self
+                .playing
+                .entry(gamepad)
+                .or_default()
+                .push(PlayingPattern {
+                    pattern,
+                    priority,
+                    start: now,
+                }),
+            RumblePatternRequest::Stop { gamepad } => {
+                self.playing.remove(&gamepad);
+            }
+        }
+    }
+
+    /// Mixes the patterns playing at `now`, and drops the finished ones.
+    fn update(&mut self, now: Duration) {
+        self.changed.clear();
+        let mut intensities = HashMap::default();
+        self.playing.retain(|gamepad, patterns| {
+            let samples: Vec<_> = patterns
+                .iter()
+                .map(|playing| {
+                    playing
+                        .pattern
+                        .intensity_at(now.saturating_sub(playing.start))
+                })
+                .collect();
+            let mut priority = i32::MIN;
+            let mut intensity = (0.0, 0.0);
+            for (playing, sample) in patterns.iter().zip(&samples) {
+                let Some(sample) = sample else {
+                    continue;
+                };
+                if playing.priority > priority {
+                    priority = playing.priority;
+                    intensity = (0.0, 0.0);
+                }
+                if playing.priority == priority {
+                    intensity.0 += sample.strong_motor;
+                    intensity.1 += sample.weak_motor;
+                }
+            }
+            let mut samples = samples.iter();
+            patterns.retain(|_| samples.next().is_some_and(Option::is_some));
+            intensities.insert(
+                *gamepad,
+                GamepadRumbleIntensity {
+                    strong_motor: intensity.0.min(1.0),
+                    weak_motor: intensity.1.min(1.0),
+                },
+            );
+            !patterns.is_empty()
+        });
+
+        for (gamepad, intensity) in &intensities {
+            if self.intensities.get(gamepad) != Some(intensity) {
+                self.changed.push(*gamepad);
+            }
+        }
+        for gamepad in self.intensities.keys() {
+            if !intensities.contains_key(gamepad) {
+                self.changed.push(*gamepad);
+            }
+        }
+        // Gamepads whose patterns all finished are reported once with a zero intensity
+        intensities.retain(|gamepad, intensity| {
+            self.playing.contains_key(gamepad)
+                || intensity.strong_motor > 0.0
+                || intensity.weak_motor > 0.0
+        });
+        self.intensities = intensities;
+    }
+}
+
+/// Plays the intensities computed by [`GamepadHaptics`] on a device.
+///
+/// Implement this to support haptics on platforms or devices that `bevy_gilrs` doesn't handle,
+/// and add it to the [`GamepadHapticsBackends`] resource.
+pub trait GamepadHapticsBackend: Send + Sync + 'static {
+    /// Sets the intensity `gamepad` should rumble at until the next call.
+    ///
+    /// Only called when the intensity changes.
+    fn set_rumble(&mut self, gamepad: Gamepad, intensity: GamepadRumbleIntensity);
+}
+
+/// The [`GamepadHapticsBackend`]s the intensities of [`GamepadHaptics`] are sent to.
+#[derive(Resource, Default)]
+pub struct GamepadHapticsBackends(pub Vec<Box<dyn GamepadHapticsBackend>>);
+
+impl GamepadHapticsBackends {
+    /// Adds a backend.
+    pub fn add(&mut self, backend: impl GamepadHapticsBackend) {
+        self.0.push(Box::new(backend));
+    }
+}
+
+/// Handles the [`RumblePatternRequest`]s, updates [`GamepadHaptics`], and sends the intensities
+/// that changed to the [`GamepadHapticsBackends`].
+pub fn gamepad_haptics_system(
+    mut haptics: ResMut<GamepadHaptics>,
+    mut backends: ResMut<GamepadHapticsBackends>,
+    mut requests: EventReader<RumblePatternRequest>,
+) {
+    let haptics = &mut *haptics;
+    let now = haptics.start.get_or_insert_with(Instant::now).elapsed();
+    for request in requests.read() {
+        haptics.handle_request(request.clone(), now);
+    }
+    haptics.update(now);
+    for (gamepad, intensity) in haptics.changed() {
+        for backend in &mut backends.0 {
+            backend.set_rumble(gamepad, intensity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn pattern_steps_and_fades() {
+        let pattern = RumblePattern::new()
+            .pulse(GamepadRumbleIntensity::MAX, ms(100))
+            .fade(ms(50), ms(0))
+            .pause(ms(100))
+            .repeat(RumbleRepeat::Times(2));
+        assert_eq!(pattern.duration(), Some(ms(400)));
+        assert_eq!(
+            pattern.intensity_at(ms(25)).unwrap(),
+            GamepadRumbleIntensity {
+                strong_motor: 0.5,
+                weak_motor: 0.5
+            }
+        );
+        assert_eq!(
+            pattern.intensity_at(ms(275)).unwrap(),
+            GamepadRumbleIntensity::MAX
+        );
+        assert_eq!(pattern.intensity_at(ms(350)).unwrap().strong_motor, 0.0);
+        assert_eq!(pattern.intensity_at(ms(400)), None);
+    }
+
+    #[test]
+    fn higher_priority_patterns_are_felt() {
+        let gamepad = Gamepad::new(0);
+        let mut haptics = GamepadHaptics::default();
+        let engine = RumblePattern::new()
+            .pulse(GamepadRumbleIntensity::weak_motor(0.2), ms(100))
+            .repeat(RumbleRepeat::Forever);
+        let hit = RumblePattern::new().pulse(GamepadRumbleIntensity::STRONG_MAX, ms(50));
+        for (pattern, priority) in [(engine.clone(), 0), (engine, 0), (hit, 1)] {
+            haptics.handle_request(
+                RumblePatternRequest::Play {
+                    gamepad,
+                    pattern,
+                    priority,
+                },
+                ms(0),
+            );
+        }
+
+        haptics.update(ms(10));
+        assert_eq!(
+            haptics.intensity(gamepad),
+            GamepadRumbleIntensity::STRONG_MAX
+        );
+        assert_eq!(haptics.changed().count(), 1);
+
+        // Once the hit finishes, the two engine patterns add up
+        haptics.update(ms(60));
+        assert_eq!(haptics.intensity(gamepad).weak_motor, 0.4);
+        assert_eq!(haptics.intensity(gamepad).strong_motor, 0.0);
+
+        haptics.update(ms(70));
+        assert_eq!(haptics.changed().count(), 0);
+
+        haptics.handle_request(RumblePatternRequest::Stop { gamepad }, ms(80));
+        haptics.update(ms(80));
+        assert!(!haptics.is_playing(gamepad));
+        assert_eq!(haptics.changed().count(), 1);
+        assert_eq!(haptics.intensity(gamepad).weak_motor, 0.0);
+    }
+}
@@ -6,13 +6,17 @@
 //!
 //! `bevy` currently supports keyboard, mouse, gamepad, and touch inputs.
 
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
 pub mod common_conditions;
 pub mod gamepad;
+pub mod gestures;
+pub mod haptics;
 pub mod keyboard;
 pub mod mouse;
+pub mod recording;
 pub mod touch;
 pub mod touchpad;
 
@@ -23,6 +27,7 @@ pub use button_input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionState, InputActionPlugin, InputBinding, InputMap},
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads,
         },
@@ -44,6 +49,15 @@ use mouse::{
 use touch::{touch_screen_input_system, ForceTouch, TouchInput, TouchPhase, Touches};
 use touchpad::{TouchpadMagnify, TouchpadRotate};
 
+use haptics::{
+    gamepad_haptics_system, GamepadHaptics, GamepadHapticsBackends, RumblePatternRequest,
+};
+
+use gestures::{
+    touch_gesture_system, DoubleTapGesture, GestureSettings, LongPressGesture, PinchGesture,
+    RotateGesture, SwipeDirection, SwipeGesture, TapGesture,
+};
+
 use gamepad::{
     gamepad_axis_event_system, gamepad_button_event_system, gamepad_connection_system,
     gamepad_event_system, AxisSettings, ButtonAxisSettings, ButtonSettings, Gamepad, GamepadAxis,
@@ -86,6 +100,10 @@ impl Plugin for InputPlugin {
             .add_event::<GamepadAxisChangedEvent>()
             .add_event::<GamepadEvent>()
             .add_event::<GamepadRumbleRequest>()
+            .add_event::<RumblePatternRequest>()
+            .init_resource::<GamepadHaptics>()
+            .init_resource::<GamepadHapticsBackends>()
+            .add_systems(PostUpdate, gamepad_haptics_system)
             .init_resource::<GamepadSettings>()
             .init_resource::<Gamepads>()
             .init_resource::<ButtonInput<GamepadButton>>()
@@ -108,11 +126,29 @@ impl Plugin for InputPlugin {
             // touch
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
-            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem));
+            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem))
+            // gestures
+            .add_event::<TapGesture>()
+            .add_event::<DoubleTapGesture>()
+            .add_event::<LongPressGesture>()
+            .add_event::<SwipeGesture>()
+            .add_event::<PinchGesture>()
+            .add_event::<RotateGesture>()
+            .init_resource::<GestureSettings>()
+            .add_systems(
+                PreUpdate,
+                touch_gesture_system
+                    .after(touch_screen_input_system)
+                    .in_set(InputSystem),
+            );
 
         // Register common types
         app.register_type::<ButtonState>();
 
+        // Register action types
+        app.register_type::<action::ButtonBinding>()
+            .register_type::<action::InputBinding>();
+
         // Register keyboard types
         app.register_type::<KeyboardInput>()
             .register_type::<KeyCode>()
@@ -134,6 +170,16 @@ impl Plugin for InputPlugin {
             .register_type::<ForceTouch>()
             .register_type::<TouchPhase>();
 
+        // Register gesture types
+        app.register_type::<GestureSettings>()
+            .register_type::<TapGesture>()
+            .register_type::<DoubleTapGesture>()
+            .register_type::<LongPressGesture>()
+            .register_type::<SwipeDirection>()
+            .register_type::<SwipeGesture>()
+            .register_type::<PinchGesture>()
+            .register_type::<RotateGesture>();
+
         // Register gamepad types
         app.register_type::<Gamepad>()
             .register_type::<GamepadConnection>()
@@ -0,0 +1,441 @@
+//! Recording input events and replaying them in a later run of the app.
+//!
+//! An [`InputRecorder`] captures every raw input event along with the index of the frame it was
+//! received on, relative to the start of the recording. The resulting [`InputRecording`] can be
+//! stored (it is serializable with the `serialize` feature) and replayed by an [`InputPlayback`],
+//! which sends the recorded events again on the same frames. This is useful to write automated
+//! gameplay tests, or to reproduce a bug from the inputs of a player.
+//!
+//! Playback is only deterministic if the rest of the app is: systems relying on wall-clock time,
+//! such as those using a variable timestep, may behave differently when replayed.
+//!
+//! ```
+//! # use bevy_app::App;
+//! # use bevy_input::{keyboard::{KeyCode, KeyboardInput}, recording::*, ButtonState, InputPlugin};
+//! let mut app = App::new();
+//! app.add_plugins((InputPlugin, InputRecordingPlugin));
+//! app.world.resource_mut::<InputRecorder>().start();
+//! app.world.send_event(KeyboardInput {
+//!     scan_code: 57,
+//!     key_code: Some(KeyCode::Space),
+//!     state: ButtonState::Pressed,
+//!     window: bevy_ecs::entity::Entity::PLACEHOLDER,
+//! });
+//! app.update();
+//! let recording = app.world.resource_mut::<InputRecorder>().stop();
+//! assert_eq!(recording.records.len(), 1);
+//!
+//! // Replay the recording in a fresh app
+//! let mut app = App::new();
+//! app.add_plugins((InputPlugin, InputRecordingPlugin));
+//! app.world.resource_mut::<InputPlayback>().play(recording);
+//! app.update();
+//! assert!(app.world.resource::<bevy_input::ButtonInput<KeyCode>>().pressed(KeyCode::Space));
+//! ```
+
+use crate::{
+    gamepad::GamepadEvent,
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    touchpad::{TouchpadMagnify, TouchpadRotate},
+    InputSystem,
+};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    schedule::IntoSystemConfigs,
+    system::{ResMut, Resource},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A raw input event captured by an [`InputRecorder`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] event.
+    Keyboard(KeyboardInput),
+    /// A [`MouseButtonInput`] event.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] event.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] event.
+    MouseWheel(MouseWheel),
+    /// A [`TouchInput`] event.
+    Touch(TouchInput),
+    /// A [`TouchpadMagnify`] event.
+    TouchpadMagnify(TouchpadMagnify),
+    /// A [`TouchpadRotate`] event.
+    TouchpadRotate(TouchpadRotate),
+    /// A [`GamepadEvent`], covering gamepad connections, buttons and axes.
+    Gamepad(GamepadEvent),
+}
+
+/// An input event and the frame it was received on.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputRecord {
+    /// The index of the frame the event was received on, starting at 0 for the first recorded frame.
+    pub frame: u64,
+    /// The recorded event.
+    pub input: RecordedInput,
+}
+
+/// A sequence of input events recorded by an [`InputRecorder`].
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputRecording {
+    /// The recorded events, ordered by frame.
+    pub records: Vec<InputRecord>,
+    /// The number of frames the recording lasted for.
+    pub frame_count: u64,
+}
+
+/// Records the input events received while it is [started](InputRecorder::start).
+///
+/// Within a frame, events are recorded grouped by type, so the relative order of events of different
+/// types received on the same frame is not preserved.
+#[derive(Resource, Debug, Default)]
+pub struct InputRecorder {
+    recording: Option<InputRecording>,
+}
+
+impl InputRecorder {
+    /// Starts a new recording, discarding the current one if any.
+    pub fn start(&mut self) {
+        self.recording = Some(InputRecording::default());
+    }
+
+    /// Stops recording and returns the recorded events.
+    ///
+    /// Returns an empty recording if the recorder wasn't started.
+    pub fn stop(&mut self) -> InputRecording {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// Returns `true` if events are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// The events recorded so far.
+    pub fn recording(&self) -> Option<&InputRecording> {
+        self.recording.as_ref()
+    }
+}
+
+/// Replays the events of an [`InputRecording`] on the frames they were recorded on.
+///
+/// Events received from the platform are not blocked during playback, so tests should not
+/// add the window backend, or should ignore real inputs.
+#[derive(Resource, Debug, Default)]
+pub struct InputPlayback {
+    recording: Option<InputRecording>,
+    frame: u64,
+    next_record: usize,
+}
+
+impl InputPlayback {
+    /// Starts replaying `recording` from its first frame, from the next update.
+    pub fn play(&mut self, recording: InputRecording) {
+        self.recording = Some(recording);
+        self.frame = 0;
+        self.next_record = 0;
+    }
+
+    /// Stops replaying the current recording.
+    pub fn stop(&mut self) {
+        self.recording = None;
+    }
+
+    /// Returns `true` if a recording is being replayed.
+    pub fn is_playing(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// The index of the next frame that will be replayed.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+}
+
+/// Records the input events of the current frame if the [`InputRecorder`] is started.
+#[allow(clippy::too_many_arguments)]
+pub fn record_input_system(
+    mut recorder: ResMut<InputRecorder>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut mouse_button: EventReader<MouseButtonInput>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut touch: EventReader<TouchInput>,
+    mut touchpad_magnify: EventReader<TouchpadMagnify>,
+    mut touchpad_rotate: EventReader<TouchpadRotate>,
+    mut gamepad: EventReader<GamepadEvent>,
+) {
+    let Some(recording) = recorder.recording.as_mut() else {
+        // Mark the events as read, so they aren't recorded once the recorder is started
+        keyboard.clear();
+        mouse_button.clear();
+        mouse_motion.clear();
+        mouse_wheel.clear();
+        touch.clear();
+        touchpad_magnify.clear();
+        touchpad_rotate.clear();
+        gamepad.clear();
+        return;
+    };
+    let frame = recording.frame_count;
+    let inputs = keyboard
+        .read()
+        .map(|event| RecordedInput::Keyboard(*event))
+        .chain(
+            mouse_button
+                .read()
+                .map(|event| RecordedInput::MouseButton(*event)),
+        )
+        .chain(
+            mouse_motion
+                .read()
+                .map(|event| RecordedInput::MouseMotion(*event)),
+        )
+        .chain(
+            mouse_wheel
+                .read()
+                .map(|event| RecordedInput::MouseWheel(*event)),
+        )
+        .chain(touch.read().map(|event| RecordedInput::Touch(*event)))
+        .chain(
+            touchpad_magnify
+                .read()
+                .map(|event| RecordedInput::TouchpadMagnify(*event)),
+        )
+        .chain(
+            touchpad_rotate
+                .read()
+                .map(|event| RecordedInput::TouchpadRotate(*event)),
+        )
+        .chain(
+            gamepad
+                .read()
+                .map(|event| RecordedInput::Gamepad(event.clone())),
+        );
+    recording
+        .records
+        .extend(inputs.map(|input| InputRecord { frame, input }));
+    recording.frame_count += 1;
+}
+
+/// Sends the recorded events of the current frame if the [`InputPlayback`] is playing.
+///
+/// The playback stops automatically after the last recorded frame.
+#[allow(clippy::too_many_arguments)]
+pub fn play_input_system(
+    mut playback: ResMut<InputPlayback>,
+    mut keyboard: EventWriter<KeyboardInput>,
+    mut mouse_button: EventWriter<MouseButtonInput>,
+    mut mouse_motion: EventWriter<MouseMotion>,
+    mut mouse_wheel: EventWriter<MouseWheel>,
+    mut touch: EventWriter<TouchInput>,
+    mut touchpad_magnify: EventWriter<TouchpadMagnify>,
+    mut touchpad_rotate: EventWriter<TouchpadRotate>,
+    mut gamepad: EventWriter<GamepadEvent>,
+) {
+    let playback = &mut *playback;
+    let Some(recording) = &playback.recording else {
+        return;
+    };
+    while let Some(record) = recording
+        .records
+        .get(playback.next_record)
+        .filter(|record| record.frame <= playback.frame)
+    {
+        match &record.input {
+            RecordedInput::Keyboard(event) => {
+                keyboard.send(*event);
+            }
+            RecordedInput::MouseButton(event) => {
+                mouse_button.send(*event);
+            }
+            RecordedInput::MouseMotion(event) => {
+                mouse_motion.send(*event);
+            }
+            RecordedInput::MouseWheel(event) => {
+                mouse_wheel.send(*event);
+            }
+            RecordedInput::Touch(event) => {
+                touch.send(*event);
+            }
+            RecordedInput::TouchpadMagnify(event) => {
+                touchpad_magnify.send(*event);
+            }
+            RecordedInput::TouchpadRotate(event) => {
+                touchpad_rotate.send(*event);
+            }
+            RecordedInput::Gamepad(event) => {
+                gamepad.send(event.clone());
+            }
+        }
+        playback.next_record += 1;
+    }
+    playback.frame += 1;
+    if playback.frame >= recording.frame_count && playback.next_record >= recording.records.len() {
+        playback.recording = None;
+    }
+}
+
+/// Adds the [`InputRecorder`] and [`InputPlayback`] resources.
+///
+/// Requires the [`InputPlugin`](crate::InputPlugin).
+#[derive(Default)]
+pub struct InputRecordingPlugin;
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputRecorder>()
+            .init_resource::<InputPlayback>()
+            .register_type::<InputRecording>()
+            .add_systems(
+                PreUpdate,
+                (play_input_system, record_input_system)
+                    .chain()
+                    .before(InputSystem),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{keyboard::KeyCode, mouse::MouseButton, ButtonInput, ButtonState, InputPlugin};
+    use bevy_ecs::entity::Entity;
+    use bevy_utils::HashSet;
+
+    fn key(key_code: KeyCode, state: ButtonState) -> RecordedInput {
+        RecordedInput::Keyboard(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    fn mouse_button(button: MouseButton, state: ButtonState) -> RecordedInput {
+        RecordedInput::MouseButton(MouseButtonInput {
+            button,
+            state,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    /// The pressed, just pressed and just released buttons of an input.
+    type ButtonSets<T> = [HashSet<T>; 3];
+
+    fn button_state<T: Copy + Eq + std::hash::Hash + Send + Sync + 'static>(
+        app: &App,
+    ) -> ButtonSets<T> {
+        let input = app.world.resource::<ButtonInput<T>>();
+        [
+            input.get_pressed().copied().collect(),
+            input.get_just_pressed().copied().collect(),
+            input.get_just_released().copied().collect(),
+        ]
+    }
+
+    fn input_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputRecordingPlugin));
+        app
+    }
+
+    #[test]
+    fn replay_matches_recording() {
+        let frames = [
+            vec![
+                key(KeyCode::Space, ButtonState::Pressed),
+                mouse_button(MouseButton::Left, ButtonState::Pressed),
+            ],
+            vec![],
+            vec![
+                key(KeyCode::Space, ButtonState::Released),
+                key(KeyCode::A, ButtonState::Pressed),
+            ],
+            vec![mouse_button(MouseButton::Left, ButtonState::Released)],
+            vec![],
+        ];
+
+        let mut app = input_app();
+        app.world.resource_mut::<InputRecorder>().start();
+        let mut recorded_states = Vec::new();
+        for inputs in &frames {
+            for input in inputs {
+                match input {
+                    RecordedInput::Keyboard(event) => {
+                        app.world.send_event(*event);
+                    }
+                    RecordedInput::MouseButton(event) => {
+                        app.world.send_event(*event);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            app.update();
+            recorded_states.push((
+                button_state::<KeyCode>(&app),
+                button_state::<MouseButton>(&app),
+            ));
+        }
+        let recording = app.world.resource_mut::<InputRecorder>().stop();
+        assert_eq!(recording.frame_count, frames.len() as u64);
+        assert_eq!(recording.records.len(), 5);
+        assert_eq!(recording.records[2].frame, 2);
+
+        let mut app = input_app();
+        app.world.resource_mut::<InputPlayback>().play(recording);
+        for (frame, recorded_state) in recorded_states.iter().enumerate() {
+            assert!(app.world.resource::<InputPlayback>().is_playing());
+            app.update();
+            let state = (
+                button_state::<KeyCode>(&app),
+                button_state::<MouseButton>(&app),
+            );
+            assert_eq!(&state, recorded_state, "frame {frame}");
+        }
+        assert!(!app.world.resource::<InputPlayback>().is_playing());
+    }
+
+    #[test]
+    fn events_before_start_are_not_recorded() {
+        let mut app = input_app();
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::Space),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        app.world.resource_mut::<InputRecorder>().start();
+        app.update();
+
+        let recording = app.world.resource_mut::<InputRecorder>().stop();
+        assert!(recording.records.is_empty());
+        assert_eq!(recording.frame_count, 1);
+    }
+}
@@ -133,6 +133,11 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_gizmos::GizmoPlugin);
         }
 
+        #[cfg(feature = "bevy_navmesh")]
+        {
+            group = group.add(bevy_navmesh::NavMeshPlugin);
+        }
+
         group
     }
 }
@@ -188,6 +188,18 @@ pub mod gizmos {
     pub use bevy_gizmos::*;
 }
 
+#[cfg(feature = "bevy_navmesh")]
+pub mod navmesh {
+    //! Navigation meshes baked from scene geometry, with path and raycast queries.
+    pub use bevy_navmesh::*;
+}
+
+#[cfg(feature = "bevy_dev_tools")]
+pub mod dev_tools {
+    //! Developer tools, like an in-game console and an entity inspector.
+    pub use bevy_dev_tools::*;
+}
+
 #[cfg(feature = "bevy_dynamic_plugin")]
 pub mod dynamic_plugin {
     //! Dynamic linking of plugins
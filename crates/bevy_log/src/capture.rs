@@ -0,0 +1,301 @@
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::Resource,
+};
+use bevy_utils::tracing::{
+    field::{Field, Visit},
+    Level, Subscriber,
+};
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::{layer::Context, Layer};
+
+/// A log record, sent as an [`Event`] by the [`LogCapturePlugin`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_log::{Level, LogEvent};
+/// fn show_errors(mut logs: EventReader<LogEvent>) {
+///     for log in logs.read().filter(|log| log.level == Level::ERROR) {
+///         println!("{}: {}", log.target, log.message);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(show_errors);
+/// ```
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct LogEvent {
+    /// The level of the record.
+    pub level: Level,
+    /// The target of the record, by default the module path where it was logged.
+    pub target: String,
+    /// The message of the record.
+    pub message: String,
+    /// The other fields of the record, formatted, in the order they were given.
+    pub fields: Vec<(String, String)>,
+}
+
+/// Sends the records logged through the [`LogPlugin`](crate::LogPlugin) as [`LogEvent`]s, so
+/// they can be shown in the app, for example by a console or a crash reporter.
+///
+/// The records are only captured once this plugin is added, and up to
+/// [`LogCapturePlugin::capacity`] of them are kept between two runs of the [`First`] schedule,
+/// where they are sent. Since the subscriber is global, a single app should capture the logs.
+pub struct LogCapturePlugin {
+    /// The maximum number of records kept until they are sent, the oldest being dropped first.
+    pub capacity: usize,
+}
+
+impl Default for LogCapturePlugin {
+    fn default() -> Self {
+        Self { capacity: 1024 }
+    }
+}
+
+impl Plugin for LogCapturePlugin {
+    fn build(&self, app: &mut App) {
+        CAPTURE_CAPACITY.store(self.capacity, Ordering::Relaxed);
+        app.add_event::<LogEvent>()
+            .insert_resource(LogCaptureGuard)
+            .add_systems(First, send_log_events);
+    }
+}
+
+/// The maximum number of records kept by [`LogCaptureLayer`], `0` when not capturing.
+static CAPTURE_CAPACITY: AtomicUsize = AtomicUsize::new(0);
+
+/// The records captured by [`LogCaptureLayer`], waiting to be sent as [`LogEvent`]s.
+static CAPTURED_LOGS: Mutex<VecDeque<LogEvent>> = Mutex::new(VecDeque::new());
+
+/// Stops capturing the logs when the app capturing them is dropped.
+#[derive(Resource)]
+struct LogCaptureGuard;
+
+impl Drop for LogCaptureGuard {
+    fn drop(&mut self) {
+        CAPTURE_CAPACITY.store(0, Ordering::Relaxed);
+        if let Ok(mut logs) = CAPTURED_LOGS.lock() {
+            logs.clear();
+        }
+    }
+}
+
+/// A [`Layer`] capturing the records for the [`LogCapturePlugin`].
+///
+/// It is part of the subscriber set up by the [`LogPlugin`](crate::LogPlugin), and only needs
+/// to be added to custom subscribers.
+#[derive(Default)]
+pub struct LogCaptureLayer;
+
+impl<S: Subscriber> Layer<S> for LogCaptureLayer {
+    fn on_event(&self, event: &bevy_utils::tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let capacity = CAPTURE_CAPACITY.load(Ordering::Relaxed);
+        if capacity == 0 {
+            return;
+        }
+        // Records of the `log` crate keep their metadata in fields.
+        let normalized_metadata = event.normalized_metadata();
+        let metadata = normalized_metadata
+            .as_ref()
+            .unwrap_or_else(|| event.metadata());
+        let mut visitor = LogEventVisitor(LogEvent {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: String::new(),
+            fields: Vec::new(),
+        });
+        event.record(&mut visitor);
+        let Ok(mut logs) = CAPTURED_LOGS.lock() else {
+            return;
+        };
+        while logs.len() >= capacity {
+            logs.pop_front();
+        }
+        logs.push_back(visitor.0);
+    }
+}
+
+/// Fills a [`LogEvent`] with the fields of a record.
+struct LogEventVisitor(LogEvent);
+
+impl Visit for LogEventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.message.push_str(value);
+        } else if !field.name().starts_with("log.") {
+            self.0
+                .fields
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0.message, "{value:?}");
+        } else if !field.name().starts_with("log.") {
+            self.0
+                .fields
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+}
+
+/// Sends the records captured since the last frame as [`LogEvent`]s.
+fn send_log_events(mut events: EventWriter<LogEvent>) {
+    // Sending the events may log, so the lock is released first.
+    let logs = match CAPTURED_LOGS.lock() {
+        Ok(mut logs) => std::mem::take(&mut *logs),
+        Err(_) => return,
+    };
+    events.send_batch(logs);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::Events;
+    use bevy_utils::tracing::{debug, error, info, subscriber, trace, warn};
+    use tracing_log::log;
+    use tracing_subscriber::{layer::SubscriberExt, EnvFilter, Registry};
+
+    use super::*;
+
+    /// The captured logs are global, so the tests capturing them run one at a time.
+    static CAPTURE_TEST: Mutex<()> = Mutex::new(());
+
+    /// Runs `log` with a subscriber filtering the records with `filter`, then updates `app` and
+    /// returns the [`LogEvent`]s it sent.
+    fn capture(app: &mut App, filter: &str, log: impl FnOnce()) -> Vec<LogEvent> {
+        let subscriber = Registry::default()
+            .with(EnvFilter::new(filter))
+            .with(LogCaptureLayer);
+        subscriber::with_default(subscriber, log);
+        app.update();
+        app.world
+            .resource_mut::<Events<LogEvent>>()
+            .drain()
+            .collect()
+    }
+
+    fn app(capacity: usize) -> App {
+        let mut app = App::new();
+        app.add_plugins(LogCapturePlugin { capacity });
+        app
+    }
+
+    #[test]
+    fn filtered_records_are_not_captured() {
+        let _lock = CAPTURE_TEST
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let mut app = app(16);
+        let logs = capture(&mut app, "warn,capture_test=debug", || {
+            info!("filtered by level");
+            warn!("warned");
+            debug!(target: "capture_test", id = 3, name = "enemy", "spawned {}", "here");
+            trace!(target: "capture_test", "filtered by target");
+            error!(target: "other_test", "failed");
+        });
+        assert_eq!(
+            logs,
+            [
+                LogEvent {
+                    level: Level::WARN,
+                    target: module_path!().to_string(),
+                    message: "warned".to_string(),
+                    fields: Vec::new(),
+                },
+                LogEvent {
+                    level: Level::DEBUG,
+                    target: "capture_test".to_string(),
+                    message: "spawned here".to_string(),
+                    fields: vec![
+                        ("id".to_string(), "3".to_string()),
+                        ("name".to_string(), "enemy".to_string()),
+                    ],
+                },
+                LogEvent {
+                    level: Level::ERROR,
+                    target: "other_test".to_string(),
+                    message: "failed".to_string(),
+                    fields: Vec::new(),
+                },
+            ]
+        );
+
+        // Nothing is sent again on the next frame
+        assert!(capture(&mut app, "trace", || {}).is_empty());
+    }
+
+    #[test]
+    fn records_of_the_log_crate_keep_their_metadata() {
+        let _lock = CAPTURE_TEST
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let mut app = app(16);
+        let logs = capture(&mut app, "info", || {
+            tracing_log::format_trace(
+                &log::Record::builder()
+                    .level(log::Level::Warn)
+                    .target("capture_test")
+                    .module_path(Some("capture_test::module"))
+                    .args(format_args!("from log"))
+                    .build(),
+            )
+            .unwrap();
+        });
+        assert_eq!(
+            logs,
+            [LogEvent {
+                level: Level::WARN,
+                target: "capture_test".to_string(),
+                message: "from log".to_string(),
+                fields: Vec::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn oldest_records_are_dropped_past_the_capacity() {
+        let _lock = CAPTURE_TEST
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let mut app = app(3);
+        let logs = capture(&mut app, "info", || {
+            for index in 0..5 {
+                info!("{index}");
+            }
+        });
+        let messages: Vec<_> = logs.iter().map(|log| log.message.as_str()).collect();
+        assert_eq!(messages, ["2", "3", "4"]);
+
+        // The records are kept until they are sent, and the buffer is emptied when they are
+        let logs = capture(&mut app, "info", || info!("5"));
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].message, "5");
+    }
+
+    #[test]
+    fn nothing_is_captured_once_the_app_is_dropped() {
+        let _lock = CAPTURE_TEST
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let mut app = app(16);
+        drop(app);
+
+        let subscriber = Registry::default().with(LogCaptureLayer);
+        subscriber::with_default(subscriber, || info!("not captured"));
+        assert!(CAPTURED_LOGS.lock().unwrap().is_empty());
+
+        // An app without the plugin doesn't capture either
+        app = App::new();
+        app.add_event::<LogEvent>();
+        assert!(capture(&mut app, "info", || info!("not captured")).is_empty());
+    }
+}
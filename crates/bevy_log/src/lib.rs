@@ -11,6 +11,7 @@
 //! For more fine-tuned control over logging behavior, set up the [`LogPlugin`] or
 //! `DefaultPlugins` during app initialization.
 
+mod capture;
 mod once;
 
 #[cfg(feature = "trace")]
@@ -38,8 +39,11 @@ pub use bevy_utils::tracing::{
     debug, debug_span, error, error_span, info, info_span, trace, trace_span, warn, warn_span,
     Level,
 };
+pub use capture::{LogCaptureLayer, LogCapturePlugin, LogEvent};
+pub use tracing_subscriber;
 
 use bevy_app::{App, Plugin};
+use bevy_utils::tracing::Subscriber;
 use tracing_log::LogTracer;
 #[cfg(feature = "tracing-chrome")]
 use tracing_subscriber::fmt::{format::DefaultFields, FormattedFields};
@@ -64,6 +68,7 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 ///         .add_plugins(DefaultPlugins.set(LogPlugin {
 ///             level: Level::DEBUG,
 ///             filter: "wgpu=error,bevy_render=info,bevy_ecs=trace".to_string(),
+///             update_subscriber: None,
 ///         }))
 ///         .run();
 /// }
@@ -100,13 +105,21 @@ pub struct LogPlugin {
     /// Filters out logs that are "less than" the given level.
     /// This can be further filtered using the `filter` setting.
     pub level: Level,
+
+    /// Optionally wraps the subscriber set up by this plugin, for example to add a
+    /// [`Layer`](tracing_subscriber::Layer) capturing the logs.
+    pub update_subscriber: Option<fn(BoxedSubscriber) -> BoxedSubscriber>,
 }
 
+/// The type of the subscriber given to [`LogPlugin::update_subscriber`].
+pub type BoxedSubscriber = Box<dyn Subscriber + Send + Sync + 'static>;
+
 impl Default for LogPlugin {
     fn default() -> Self {
         Self {
             filter: "wgpu=error,naga=warn".to_string(),
             level: Level::INFO,
+            update_subscriber: None,
         }
     }
 }
@@ -128,7 +141,7 @@ impl Plugin for LogPlugin {
         let filter_layer = EnvFilter::try_from_default_env()
             .or_else(|_| EnvFilter::try_new(&default_filter))
             .unwrap();
-        let subscriber = Registry::default().with(filter_layer);
+        let subscriber = Registry::default().with(filter_layer).with(LogCaptureLayer);
 
         #[cfg(feature = "trace")]
         let subscriber = subscriber.with(tracing_error::ErrorLayer::default());
@@ -195,6 +208,11 @@ impl Plugin for LogPlugin {
             finished_subscriber = subscriber.with(android_tracing::AndroidLayer::default());
         }
 
+        let finished_subscriber: BoxedSubscriber = match self.update_subscriber {
+            Some(update_subscriber) => update_subscriber(Box::new(finished_subscriber)),
+            None => Box::new(finished_subscriber),
+        };
+
         let logger_already_set = LogTracer::init().is_err();
         let subscriber_already_set =
             bevy_utils::tracing::subscriber::set_global_default(finished_subscriber).is_err();
@@ -0,0 +1,429 @@
+//! Provides the [`Curve`] trait for values varying over a parameter, such as positions along a
+//! path or an animated value over time, and adapters to resample and reparameterize them.
+
+use glam::{Vec2, Vec3, Vec3A};
+
+use std::{marker::PhantomData, ops::RangeInclusive};
+
+use crate::cubic_splines::{CubicCurve, CubicSegment, Point};
+
+/// A value of type `T` varying over a parameter `t`, in the [`domain`](Curve::domain) of the
+/// curve.
+///
+/// ```
+/// # use bevy_math::{*, prelude::*};
+/// let points = [vec2(-1.0, -1.0), vec2(0.0, 2.0), vec2(1.0, 0.0), vec2(3.0, 1.0)];
+/// let path = CubicCardinalSpline::new_catmull_rom(points).to_curve();
+///
+/// // Move along the path at a constant speed
+/// let path = path.arc_length_parameterized(100);
+/// let halfway = path.sample(path.length() / 2.0);
+/// ```
+pub trait Curve<T> {
+    /// The range of `t` the curve is defined on.
+    fn domain(&self) -> RangeInclusive<f32>;
+
+    /// The value of the curve at `t`, which should be in its [`domain`](Curve::domain).
+    fn sample(&self, t: f32) -> T;
+
+    /// The value of the curve at `t`, clamped to its [`domain`](Curve::domain).
+    fn sample_clamped(&self, t: f32) -> T {
+        let domain = self.domain();
+        self.sample(t.clamp(*domain.start(), *domain.end()))
+    }
+
+    /// Iterate over the domain of the curve split into `subdivisions`, sampling the value at each
+    /// step.
+    fn iter_samples(&self, subdivisions: usize) -> CurveSamples<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        CurveSamples {
+            curve: self,
+            subdivisions: subdivisions.max(1),
+            next: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a curve applying `f` to the values of this curve.
+    fn map<S, F: Fn(T) -> S>(self, f: F) -> MapCurve<Self, F, T>
+    where
+        Self: Sized,
+    {
+        MapCurve {
+            curve: self,
+            f,
+            marker: PhantomData,
+        }
+    }
+
+    /// Approximates the curve by sampling it `subdivisions + 1` times uniformly over its domain,
+    /// interpolating linearly between the samples.
+    ///
+    /// This is useful to cache an expensive curve, or to make a curve out of data that can be
+    /// stored or sent.
+    fn resample(&self, subdivisions: usize) -> SampledCurve<T>
+    where
+        Self: Sized,
+        T: Point,
+    {
+        SampledCurve::new(self.domain(), self.iter_samples(subdivisions).collect())
+    }
+
+    /// Creates a curve parameterized by the distance traveled along this curve, so that sampling
+    /// it at regular intervals gives evenly spaced points.
+    ///
+    /// The length of the curve is approximated by the polyline through `subdivisions + 1` points
+    /// sampled uniformly over its domain.
+    fn arc_length_parameterized(self, subdivisions: usize) -> ArcLengthCurve<Self, T>
+    where
+        Self: Sized,
+        T: MetricPoint,
+    {
+        ArcLengthCurve::new(self, subdivisions)
+    }
+}
+
+impl<T, C: Curve<T> + ?Sized> Curve<T> for &C {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        (**self).domain()
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> T {
+        (**self).sample(t)
+    }
+}
+
+/// A [`Point`] with a notion of distance, needed to measure the length of a [`Curve`].
+pub trait MetricPoint: Point {
+    /// The distance between `self` and `other`.
+    fn distance(self, other: Self) -> f32;
+}
+
+impl MetricPoint for Vec3 {
+    #[inline]
+    fn distance(self, other: Self) -> f32 {
+        Vec3::distance(self, other)
+    }
+}
+
+impl MetricPoint for Vec3A {
+    #[inline]
+    fn distance(self, other: Self) -> f32 {
+        Vec3A::distance(self, other)
+    }
+}
+
+impl MetricPoint for Vec2 {
+    #[inline]
+    fn distance(self, other: Self) -> f32 {
+        Vec2::distance(self, other)
+    }
+}
+
+impl MetricPoint for f32 {
+    #[inline]
+    fn distance(self, other: Self) -> f32 {
+        (self - other).abs()
+    }
+}
+
+impl<P: Point> Curve<P> for CubicSegment<P> {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        0.0..=1.0
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> P {
+        self.position(t)
+    }
+}
+
+impl<P: Point> Curve<P> for CubicCurve<P> {
+    /// The domain is `0..=n`, where `n` is the number of segments of the curve.
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        0.0..=self.segments().len() as f32
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> P {
+        self.position(t)
+    }
+}
+
+/// A [`Curve`] defined by a function of `t`.
+///
+/// ```
+/// # use bevy_math::{*, prelude::*, curve::FunctionCurve};
+/// let wave = FunctionCurve::new(0.0..=std::f32::consts::TAU, f32::sin);
+/// assert_eq!(wave.sample(0.0), 0.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FunctionCurve<F> {
+    domain: RangeInclusive<f32>,
+    f: F,
+}
+
+impl<F> FunctionCurve<F> {
+    /// Creates a curve defined by `f` on `domain`.
+    pub fn new(domain: RangeInclusive<f32>, f: F) -> Self {
+        Self { domain, f }
+    }
+}
+
+impl<T, F: Fn(f32) -> T> Curve<T> for FunctionCurve<F> {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        self.domain.clone()
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> T {
+        (self.f)(t)
+    }
+}
+
+/// A [`Curve`] applying a function to the values of another curve, created by [`Curve::map`].
+#[derive(Clone, Debug)]
+pub struct MapCurve<C, F, T> {
+    curve: C,
+    f: F,
+    marker: PhantomData<fn(T)>,
+}
+
+impl<S, T, C: Curve<T>, F: Fn(T) -> S> Curve<S> for MapCurve<C, F, T> {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        self.curve.domain()
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> S {
+        (self.f)(self.curve.sample(t))
+    }
+}
+
+/// An iterator over values of a [`Curve`] sampled uniformly over its domain, created by
+/// [`Curve::iter_samples`].
+pub struct CurveSamples<'a, C, T> {
+    curve: &'a C,
+    subdivisions: usize,
+    next: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, C: Curve<T>> Iterator for CurveSamples<'a, C, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.next > self.subdivisions {
+            return None;
+        }
+        let domain = self.curve.domain();
+        let (start, end) = (*domain.start(), *domain.end());
+        let t = start + (end - start) * self.next as f32 / self.subdivisions as f32;
+        self.next += 1;
+        Some(self.curve.sample(t))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.subdivisions + 1).saturating_sub(self.next);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, C: Curve<T>> ExactSizeIterator for CurveSamples<'a, C, T> {}
+
+/// A [`Curve`] interpolating linearly between values sampled uniformly over its domain.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SampledCurve<P: Point> {
+    start: f32,
+    end: f32,
+    samples: Vec<P>,
+}
+
+impl<P: Point> SampledCurve<P> {
+    /// Creates a curve from `samples` spaced uniformly over `domain`, with the first sample at
+    /// its start and the last one at its end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is empty.
+    pub fn new(domain: RangeInclusive<f32>, samples: Vec<P>) -> Self {
+        assert!(
+            !samples.is_empty(),
+            "a sampled curve needs at least one sample"
+        );
+        Self {
+            start: *domain.start(),
+            end: *domain.end(),
+            samples,
+        }
+    }
+
+    /// The samples of the curve.
+    #[inline]
+    pub fn samples(&self) -> &[P] {
+        &self.samples
+    }
+}
+
+impl<P: Point> Curve<P> for SampledCurve<P> {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        self.start..=self.end
+    }
+
+    fn sample(&self, t: f32) -> P {
+        let last = self.samples.len() - 1;
+        if last == 0 || self.end <= self.start {
+            return self.samples[0];
+        }
+        let position =
+            ((t - self.start) / (self.end - self.start) * last as f32).clamp(0.0, last as f32);
+        let i = (position as usize).min(last - 1);
+        let (a, b) = (self.samples[i], self.samples[i + 1]);
+        a + (b - a) * (position - i as f32)
+    }
+}
+
+/// A [`Curve`] parameterized by the distance traveled along another curve, created by
+/// [`Curve::arc_length_parameterized`].
+///
+/// Its domain is `0..=length`, and sampling it at regular intervals gives evenly spaced points,
+/// such as to move along a path at a constant speed.
+#[derive(Clone, Debug)]
+pub struct ArcLengthCurve<C, T> {
+    curve: C,
+    /// The distance traveled along the curve at each of the uniformly spaced parameter values.
+    lengths: Vec<f32>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: MetricPoint, C: Curve<T>> ArcLengthCurve<C, T> {
+    /// Measures `curve` by the polyline through `subdivisions + 1` points sampled uniformly over
+    /// its domain.
+    pub fn new(curve: C, subdivisions: usize) -> Self {
+        let mut lengths = Vec::with_capacity(subdivisions.max(1) + 1);
+        let mut length = 0.0;
+        let mut previous = None;
+        for point in curve.iter_samples(subdivisions) {
+            if let Some(previous) = previous {
+                length += MetricPoint::distance(previous, point);
+            }
+            lengths.push(length);
+            previous = Some(point);
+        }
+        Self {
+            curve,
+            lengths,
+            marker: PhantomData,
+        }
+    }
+
+    /// The approximate length of the curve.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        *self.lengths.last().unwrap_or(&0.0)
+    }
+
+    /// The parameter of the underlying curve at `distance` along it.
+    pub fn parameter(&self, distance: f32) -> f32 {
+        let domain = self.curve.domain();
+        let (start, end) = (*domain.start(), *domain.end());
+        let last = self.lengths.len() - 1;
+        let distance = distance.clamp(0.0, self.length());
+        // The first sample at or beyond `distance`
+        let i = self
+            .lengths
+            .partition_point(|length| *length < distance)
+            .clamp(1, last);
+        let (before, after) = (self.lengths[i - 1], self.lengths[i]);
+        let lerp = if after > before {
+            (distance - before) / (after - before)
+        } else {
+            0.0
+        };
+        start + (end - start) * (i as f32 - 1.0 + lerp) / last as f32
+    }
+
+    /// The underlying curve.
+    #[inline]
+    pub fn inner(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the underlying curve.
+    #[inline]
+    pub fn into_inner(self) -> C {
+        self.curve
+    }
+}
+
+impl<T: MetricPoint, C: Curve<T>> Curve<T> for ArcLengthCurve<C, T> {
+    #[inline]
+    fn domain(&self) -> RangeInclusive<f32> {
+        0.0..=self.length()
+    }
+
+    #[inline]
+    fn sample(&self, distance: f32) -> T {
+        self.curve.sample(self.parameter(distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{vec2, Vec2};
+
+    use super::{Curve, FunctionCurve};
+    use crate::cubic_splines::{CubicBezier, CubicGenerator};
+
+    /// How close two floats can be and still be considered equal
+    const FLOAT_EQ: f32 = 1e-3;
+
+    #[test]
+    fn resample_line() {
+        let line = FunctionCurve::new(1.0..=3.0, |t| vec2(t, 2.0 * t));
+        let sampled = line.resample(4);
+        assert_eq!(sampled.samples().len(), 5);
+        assert_eq!(sampled.domain(), 1.0..=3.0);
+        for t in [1.0, 1.3, 2.0, 2.75, 3.0] {
+            assert!(sampled.sample(t).distance(line.sample(t)) <= FLOAT_EQ);
+        }
+    }
+
+    /// Points sampled uniformly by distance along a curve should be evenly spaced.
+    #[test]
+    fn arc_length_spacing() {
+        let points = [[
+            vec2(0.0, 0.0),
+            vec2(1.0, 4.0),
+            vec2(2.0, 4.0),
+            vec2(10.0, 0.0),
+        ]];
+        let curve = CubicBezier::new(points)
+            .to_curve()
+            .arc_length_parameterized(1000);
+        let positions: Vec<Vec2> = curve.iter_samples(20).collect();
+        let spacing = curve.length() / 20.0;
+        for pair in positions.windows(2) {
+            assert!((pair[0].distance(pair[1]) - spacing).abs() <= spacing * 0.01);
+        }
+        assert!(positions[0].distance(points[0][0]) <= FLOAT_EQ);
+        assert!(positions[20].distance(points[0][3]) <= FLOAT_EQ);
+    }
+
+    #[test]
+    fn arc_length_of_line() {
+        let line = FunctionCurve::new(0.0..=1.0, |t| vec2(3.0, 4.0) * t * t);
+        let line = line.arc_length_parameterized(100);
+        assert!((line.length() - 5.0).abs() <= FLOAT_EQ);
+        assert!(line.sample(2.5).distance(vec2(1.5, 2.0)) <= FLOAT_EQ);
+    }
+}
@@ -8,6 +8,7 @@
 
 mod affine3;
 pub mod cubic_splines;
+pub mod curve;
 pub mod primitives;
 mod ray;
 mod rects;
@@ -24,6 +25,7 @@ pub mod prelude {
             CubicBSpline, CubicBezier, CubicCardinalSpline, CubicGenerator, CubicHermite,
             CubicSegment,
         },
+        curve::Curve,
         primitives, BVec2, BVec3, BVec4, EulerRot, IRect, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4,
         Quat, Ray2d, Ray3d, Rect, URect, UVec2, UVec3, UVec4, Vec2, Vec2Swizzles, Vec3,
         Vec3Swizzles, Vec4, Vec4Swizzles,
@@ -0,0 +1,279 @@
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use std::{cmp::Reverse, collections::BinaryHeap, f32::consts::FRAC_PI_4};
+
+/// Settings used to bake a [`NavMesh`](crate::NavMesh) from scene geometry.
+///
+/// The geometry is rasterized into voxels of `cell_size` by `cell_height`, so smaller cells
+/// give a more precise result at the cost of baking time and memory.
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct NavMeshSettings {
+    /// The width and depth of the voxels.
+    pub cell_size: f32,
+    /// The height of the voxels.
+    pub cell_height: f32,
+    /// How far agents keep away from walls and ledges.
+    pub agent_radius: f32,
+    /// The free space agents need above a surface to walk on it.
+    pub agent_height: f32,
+    /// The highest step agents can climb.
+    pub max_climb: f32,
+    /// The steepest slope agents can walk on, in radians.
+    pub max_slope: f32,
+}
+
+impl Default for NavMeshSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.25,
+            cell_height: 0.1,
+            agent_radius: 0.5,
+            agent_height: 2.0,
+            max_climb: 0.4,
+            max_slope: FRAC_PI_4,
+        }
+    }
+}
+
+/// The directions to the neighbors of a cell, each a quarter turn from the previous one.
+pub(crate) const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// A walkable surface in one column of the grid.
+#[derive(Clone, Debug)]
+pub(crate) struct Cell {
+    pub x: i32,
+    pub z: i32,
+    /// The height of the surface, in multiples of the cell height.
+    pub y: i32,
+    /// The cells an agent can step to in each of the [`DIRECTIONS`].
+    pub neighbors: [Option<usize>; 4],
+}
+
+/// The walkable surfaces found in the scene geometry, ordered by row and then by column.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CellGrid {
+    pub origin: Vec3,
+    pub cells: Vec<Cell>,
+}
+
+/// A solid range of voxels in a column.
+#[derive(Clone, Copy)]
+struct Span {
+    min: i32,
+    max: i32,
+    /// Whether the top of the span is a walkable surface.
+    walkable: bool,
+}
+
+impl CellGrid {
+    pub fn bake(settings: &NavMeshSettings, triangles: &[[Vec3; 3]]) -> Self {
+        let Some((min, max)) = bounds(triangles) else {
+            return Self::default();
+        };
+        let size = ((max - min) / settings.cell_size).ceil().max(Vec3::ONE);
+        let (width, depth) = (size.x as i32, size.z as i32);
+        let climb = (settings.max_climb / settings.cell_height).floor() as i32;
+        let clearance = (settings.agent_height / settings.cell_height).ceil() as i32;
+
+        let mut columns = vec![Vec::new(); (width * depth) as usize];
+        let min_normal_y = settings.max_slope.cos();
+        for triangle in triangles {
+            let normal = (triangle[1] - triangle[0])
+                .cross(triangle[2] - triangle[0])
+                .normalize_or_zero();
+            let walkable = normal.y >= min_normal_y;
+            rasterize(triangle, min, width, depth, settings, |x, z, min, max| {
+                add_span(
+                    &mut columns[(z * width + x) as usize],
+                    Span { min, max, walkable },
+                    climb,
+                );
+            });
+        }
+
+        // The top of each walkable span is a cell, provided agents fit below the next span.
+        let mut cells = Vec::new();
+        let mut ceilings = Vec::new();
+        let mut column_cells = Vec::with_capacity(columns.len());
+        for (index, column) in columns.iter().enumerate() {
+            let first = cells.len();
+            for (i, span) in column.iter().enumerate() {
+                let ceiling = column.get(i + 1).map_or(i32::MAX, |above| above.min);
+                if span.walkable && ceiling - span.max >= clearance {
+                    cells.push(Cell {
+                        x: index as i32 % width,
+                        z: index as i32 / width,
+                        y: span.max,
+                        neighbors: [None; 4],
+                    });
+                    ceilings.push(ceiling);
+                }
+            }
+            column_cells.push(first..cells.len());
+        }
+
+        for cell in 0..cells.len() {
+            for (direction, (dx, dz)) in DIRECTIONS.into_iter().enumerate() {
+                let (x, z) = (cells[cell].x + dx, cells[cell].z + dz);
+                if x < 0 || z < 0 || x >= width || z >= depth {
+                    continue;
+                }
+                let (y, ceiling) = (cells[cell].y, ceilings[cell]);
+                cells[cell].neighbors[direction] = column_cells[(z * width + x) as usize]
+                    .clone()
+                    .find(|&other| {
+                        (cells[other].y - y).abs() <= climb
+                            && ceilings[other].min(ceiling) - cells[other].y.max(y) >= clearance
+                    });
+            }
+        }
+
+        let radius = (settings.agent_radius / settings.cell_size).ceil() as u32;
+        Self {
+            origin: min,
+            cells: erode(cells, radius),
+        }
+    }
+}
+
+fn bounds(triangles: &[[Vec3; 3]]) -> Option<(Vec3, Vec3)> {
+    let mut points = triangles.iter().flatten();
+    let first = *points.next()?;
+    Some(points.fold((first, first), |(min, max), &point| {
+        (min.min(point), max.max(point))
+    }))
+}
+
+/// Calls `add_span` with the range of voxels the triangle covers in each column it touches.
+fn rasterize(
+    triangle: &[Vec3; 3],
+    origin: Vec3,
+    width: i32,
+    depth: i32,
+    settings: &NavMeshSettings,
+    mut add_span: impl FnMut(i32, i32, i32, i32),
+) {
+    let cell_size = settings.cell_size;
+    let min = triangle[0].min(triangle[1]).min(triangle[2]) - origin;
+    let max = triangle[0].max(triangle[1]).max(triangle[2]) - origin;
+    let z_range = (min.z / cell_size) as i32..=((max.z / cell_size) as i32).min(depth - 1);
+    let x_range = (min.x / cell_size) as i32..=((max.x / cell_size) as i32).min(width - 1);
+
+    let polygon: Vec<Vec3> = triangle.iter().map(|&point| point - origin).collect();
+    for z in z_range {
+        let row = clip(&polygon, 2, z as f32 * cell_size, true);
+        let row = clip(&row, 2, (z + 1) as f32 * cell_size, false);
+        if row.is_empty() {
+            continue;
+        }
+        for x in x_range.clone() {
+            let cell = clip(&row, 0, x as f32 * cell_size, true);
+            let cell = clip(&cell, 0, (x + 1) as f32 * cell_size, false);
+            let Some((min, max)) = cell.iter().map(|point| point.y).fold(None, |range, y| {
+                let (min, max) = range.unwrap_or((y, y));
+                Some((min.min(y), max.max(y)))
+            }) else {
+                continue;
+            };
+            let min = (min / settings.cell_height).floor() as i32;
+            let max = (max / settings.cell_height).ceil() as i32;
+            add_span(x, z, min, max.max(min + 1));
+        }
+    }
+}
+
+/// Clips a convex polygon to the side of the plane `point[axis] == value` given by `above`.
+fn clip(polygon: &[Vec3], axis: usize, value: f32, above: bool) -> Vec<Vec3> {
+    let distance = |point: Vec3| {
+        if above {
+            point[axis] - value
+        } else {
+            value - point[axis]
+        }
+    };
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &point) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (d, next_d) = (distance(point), distance(next));
+        if d >= 0.0 {
+            clipped.push(point);
+        }
+        if (d >= 0.0) != (next_d >= 0.0) {
+            clipped.push(point.lerp(next, d / (d - next_d)));
+        }
+    }
+    clipped
+}
+
+/// Adds a span to a column, merging it with the spans it overlaps.
+fn add_span(column: &mut Vec<Span>, mut span: Span, climb: i32) {
+    column.retain(|other| {
+        if other.min > span.max || other.max < span.min {
+            return true;
+        }
+        span.min = span.min.min(other.min);
+        if other.max > span.max {
+            span.max = other.max;
+            span.walkable = other.walkable;
+        } else if span.max - other.max <= climb {
+            span.walkable |= other.walkable;
+        }
+        false
+    });
+    let index = column.partition_point(|other| other.min < span.min);
+    column.insert(index, span);
+}
+
+/// Removes the cells closer than `radius` cells to the edge of the walkable area.
+fn erode(cells: Vec<Cell>, radius: u32) -> Vec<Cell> {
+    if radius == 0 {
+        return cells;
+    }
+
+    // Distances are measured in half cells, with diagonal steps costing 3 half cells as an
+    // approximation of their true length.
+    let mut distances = vec![u32::MAX; cells.len()];
+    let mut queue = BinaryHeap::new();
+    for (index, cell) in cells.iter().enumerate() {
+        if cell.neighbors.contains(&None) {
+            distances[index] = 0;
+            queue.push(Reverse((0, index)));
+        }
+    }
+    while let Some(Reverse((distance, index))) = queue.pop() {
+        if distance > distances[index] {
+            continue;
+        }
+        for direction in 0..4 {
+            let Some(neighbor) = cells[index].neighbors[direction] else {
+                continue;
+            };
+            let diagonal = cells[neighbor].neighbors[(direction + 1) % 4];
+            for (other, cost) in [(Some(neighbor), 2), (diagonal, 3)] {
+                let Some(other) = other else {
+                    continue;
+                };
+                if distance + cost < distances[other] {
+                    distances[other] = distance + cost;
+                    queue.push(Reverse((distance + cost, other)));
+                }
+            }
+        }
+    }
+
+    let mut remap = vec![None; cells.len()];
+    let mut kept = Vec::new();
+    for (index, cell) in cells.into_iter().enumerate() {
+        if distances[index] >= radius * 2 {
+            remap[index] = Some(kept.len());
+            kept.push(cell);
+        }
+    }
+    for cell in &mut kept {
+        for neighbor in &mut cell.neighbors {
+            *neighbor = neighbor.and_then(|neighbor| remap[neighbor]);
+        }
+    }
+    kept
+}
@@ -0,0 +1,204 @@
+#![warn(missing_docs)]
+
+//! Navigation meshes for Bevy Engine.
+//!
+//! A [`NavMesh`] describes where agents of a given size can walk. It is baked by rasterizing
+//! scene geometry into voxels, finding the surfaces that are flat enough and have enough room
+//! above them, and shrinking them by the agent radius so that agents keep clear of walls.
+//!
+//! Paths are found with [`NavMesh::find_path`], straight lines of sight are checked with
+//! [`NavMesh::raycast`], and entities with a [`NavMeshObstacle`] carve holes into every
+//! navigation mesh as they move.
+//!
+//! # Example
+//! ```
+//! # use bevy_asset::{Assets, Handle};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::Vec3;
+//! # use bevy_navmesh::prelude::*;
+//! #[derive(Resource)]
+//! struct Level(Handle<NavMesh>);
+//!
+//! fn bake(
+//!     mut commands: Commands,
+//!     mut navmeshes: ResMut<Assets<NavMesh>>,
+//!     mut bake: EventWriter<BakeNavMesh>,
+//! ) {
+//!     // Every entity with a `NavMeshSource` will be baked into this navigation mesh.
+//!     let handle = navmeshes.add(NavMesh::default());
+//!     bake.send(BakeNavMesh {
+//!         handle: handle.clone(),
+//!         settings: NavMeshSettings::default(),
+//!     });
+//!     commands.insert_resource(Level(handle));
+//! }
+//!
+//! fn walk(level: Res<Level>, navmeshes: Res<Assets<NavMesh>>) {
+//!     let Some(navmesh) = navmeshes.get(&level.0) else {
+//!         return;
+//!     };
+//!     // Follow the points of the path, if one was found.
+//!     let _path = navmesh.find_path(Vec3::ZERO, Vec3::new(10.0, 0.0, 10.0));
+//! }
+//! # bevy_ecs::system::assert_is_system(bake);
+//! # bevy_ecs::system::assert_is_system(walk);
+//! ```
+
+mod bake;
+mod navmesh;
+
+pub use bake::NavMeshSettings;
+pub use navmesh::{NavMesh, NavMeshObstacle, NavPolygon};
+
+/// The `bevy_navmesh` prelude.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        BakeNavMesh, NavMesh, NavMeshObstacle, NavMeshPlugin, NavMeshSettings, NavMeshSource,
+    };
+}
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{AssetApp, Assets, Handle};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_log::warn;
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{mesh::Mesh, render_resource::PrimitiveTopology};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+/// Adds support for baking [`NavMesh`]es and carving [`NavMeshObstacle`]s into them.
+#[derive(Default)]
+pub struct NavMeshPlugin;
+
+/// The systems of the [`NavMeshPlugin`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavMeshSystem {
+    /// Bakes the requested [`NavMesh`]es.
+    Bake,
+    /// Carves [`NavMeshObstacle`]s into the [`NavMesh`]es.
+    Carve,
+}
+
+impl Plugin for NavMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<NavMesh>()
+            .register_type::<NavMeshSource>()
+            .register_type::<NavMeshObstacle>()
+            .register_type::<NavMeshSettings>()
+            .add_event::<BakeNavMesh>()
+            .configure_sets(
+                PostUpdate,
+                (NavMeshSystem::Bake, NavMeshSystem::Carve)
+                    .chain()
+                    .after(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    bake_navmeshes.in_set(NavMeshSystem::Bake),
+                    carve_obstacles.in_set(NavMeshSystem::Carve),
+                ),
+            );
+    }
+}
+
+/// Marks an entity's [`Mesh`] as walkable geometry to bake into [`NavMesh`]es.
+///
+/// Only surfaces flat enough for agents are walkable, the rest of the mesh blocks them.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct NavMeshSource;
+
+/// Bakes a [`NavMesh`] from every entity with a [`NavMeshSource`] and replaces the asset of
+/// `handle` with it.
+///
+/// Meshes that haven't been loaded yet are left out.
+#[derive(Event, Clone, Debug)]
+pub struct BakeNavMesh {
+    /// The navigation mesh to replace.
+    pub handle: Handle<NavMesh>,
+    /// The settings to bake with.
+    pub settings: NavMeshSettings,
+}
+
+impl NavMesh {
+    /// Bakes a navigation mesh from meshes placed in the world.
+    ///
+    /// Meshes with a topology other than [`PrimitiveTopology::TriangleList`] are skipped.
+    pub fn from_meshes<'a>(
+        settings: &NavMeshSettings,
+        meshes: impl IntoIterator<Item = (&'a Mesh, &'a GlobalTransform)>,
+    ) -> Self {
+        let mut triangles = Vec::new();
+        for (mesh, transform) in meshes {
+            if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+                warn!("Skipping a mesh with a {:?} topology while baking a navigation mesh, only triangle lists are supported", mesh.primitive_topology());
+                continue;
+            }
+            let Some(positions) = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(|positions| positions.as_float3())
+            else {
+                continue;
+            };
+            let positions: Vec<Vec3> = positions
+                .iter()
+                .map(|&position| transform.transform_point(position.into()))
+                .collect();
+            let indices: Vec<usize> = match mesh.indices() {
+                Some(indices) => indices.iter().collect(),
+                None => (0..positions.len()).collect(),
+            };
+            triangles.extend(indices.chunks_exact(3).map(|triangle| {
+                [
+                    positions[triangle[0]],
+                    positions[triangle[1]],
+                    positions[triangle[2]],
+                ]
+            }));
+        }
+        Self::from_triangles(settings, triangles)
+    }
+}
+
+/// Bakes the [`NavMesh`]es requested with [`BakeNavMesh`] events.
+pub fn bake_navmeshes(
+    mut events: EventReader<BakeNavMesh>,
+    sources: Query<(&Handle<Mesh>, &GlobalTransform), With<NavMeshSource>>,
+    obstacles: Query<(&GlobalTransform, &NavMeshObstacle)>,
+    meshes: Res<Assets<Mesh>>,
+    mut navmeshes: ResMut<Assets<NavMesh>>,
+) {
+    for event in events.read() {
+        let sources = sources
+            .iter()
+            .filter_map(|(handle, transform)| Some((meshes.get(handle)?, transform)));
+        let mut navmesh = NavMesh::from_meshes(&event.settings, sources);
+        navmesh.carve(&obstacles);
+        navmeshes.insert(&event.handle, navmesh);
+    }
+}
+
+/// Carves the [`NavMeshObstacle`]s into every [`NavMesh`] when any of them moves, changes, or
+/// is removed.
+pub fn carve_obstacles(
+    obstacles: Query<(&GlobalTransform, &NavMeshObstacle)>,
+    changed: Query<
+        (),
+        (
+            With<NavMeshObstacle>,
+            Or<(Changed<GlobalTransform>, Changed<NavMeshObstacle>)>,
+        ),
+    >,
+    mut removed: RemovedComponents<NavMeshObstacle>,
+    mut navmeshes: ResMut<Assets<NavMesh>>,
+) {
+    let removed = removed.read().count() > 0;
+    if !removed && changed.is_empty() {
+        return;
+    }
+    for (_, navmesh) in navmeshes.iter_mut() {
+        navmesh.carve(&obstacles);
+    }
+}
@@ -0,0 +1,584 @@
+use crate::bake::{CellGrid, NavMeshSettings, DIRECTIONS};
+use bevy_asset::Asset;
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{Vec2, Vec3, Vec3Swizzles};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// The index of the neighbor towards `+x` in [`DIRECTIONS`].
+const POSITIVE_X: usize = 2;
+/// The index of the neighbor towards `+z` in [`DIRECTIONS`].
+const POSITIVE_Z: usize = 1;
+
+/// The area agents can walk on, baked from scene geometry.
+///
+/// The walkable surface is stored as a set of connected [`NavPolygon`]s, which paths are found
+/// across with [`NavMesh::find_path`]. Holes can be carved into it at runtime for dynamic
+/// obstacles with [`NavMesh::carve`].
+///
+/// Navigation meshes are usually baked from entities with a [`NavMeshSource`](crate::NavMeshSource)
+/// by sending a [`BakeNavMesh`](crate::BakeNavMesh) event, or directly from geometry with
+/// [`NavMesh::from_meshes`] or [`NavMesh::from_triangles`].
+#[derive(Asset, TypePath, Clone, Debug, Default)]
+pub struct NavMesh {
+    settings: NavMeshSettings,
+    grid: CellGrid,
+    /// Whether each cell of the grid is covered by an obstacle.
+    blocked: Vec<bool>,
+    polygons: Vec<NavPolygon>,
+}
+
+/// A rectangular piece of a [`NavMesh`].
+#[derive(Clone, Debug)]
+pub struct NavPolygon {
+    min: Vec2,
+    max: Vec2,
+    height: f32,
+    links: Vec<NavLink>,
+}
+
+/// A portal from one polygon to another.
+#[derive(Clone, Debug)]
+struct NavLink {
+    polygon: usize,
+    start: Vec3,
+    end: Vec3,
+    /// The direction of the step across the portal, on the horizontal plane.
+    direction: Vec2,
+}
+
+/// Carves a hole around the entity into every [`NavMesh`], which paths will avoid.
+///
+/// The hole covers a box of the given size around the entity's [`GlobalTransform`], widened
+/// by the agent radius the navigation mesh was baked with.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct NavMeshObstacle {
+    /// Half the size of the obstacle along each of its axes.
+    pub half_extents: Vec3,
+}
+
+impl Default for NavMeshObstacle {
+    fn default() -> Self {
+        Self {
+            half_extents: Vec3::splat(0.5),
+        }
+    }
+}
+
+impl NavPolygon {
+    /// The corners of the polygon, in order around it.
+    pub fn corners(&self) -> [Vec3; 4] {
+        [
+            (self.min.x, self.min.y),
+            (self.max.x, self.min.y),
+            (self.max.x, self.max.y),
+            (self.min.x, self.max.y),
+        ]
+        .map(|(x, z)| Vec3::new(x, self.height, z))
+    }
+
+    /// The center of the polygon.
+    pub fn center(&self) -> Vec3 {
+        self.at((self.min + self.max) / 2.0)
+    }
+
+    /// The point of the polygon above or below a point on the horizontal plane.
+    fn at(&self, point: Vec2) -> Vec3 {
+        Vec3::new(point.x, self.height, point.y)
+    }
+
+    /// The closest point of the polygon to `point`.
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.at(point.xz().clamp(self.min, self.max))
+    }
+}
+
+impl NavMesh {
+    /// Bakes a navigation mesh from triangles in world space.
+    pub fn from_triangles(
+        settings: &NavMeshSettings,
+        triangles: impl IntoIterator<Item = [Vec3; 3]>,
+    ) -> Self {
+        let triangles: Vec<_> = triangles.into_iter().collect();
+        let grid = CellGrid::bake(settings, &triangles);
+        let mut navmesh = Self {
+            settings: settings.clone(),
+            blocked: vec![false; grid.cells.len()],
+            grid,
+            polygons: Vec::new(),
+        };
+        navmesh.build_polygons();
+        navmesh
+    }
+
+    /// The settings this navigation mesh was baked with.
+    pub fn settings(&self) -> &NavMeshSettings {
+        &self.settings
+    }
+
+    /// The polygons making up the walkable area.
+    pub fn polygons(&self) -> &[NavPolygon] {
+        &self.polygons
+    }
+
+    /// Returns `true` if there is no walkable area.
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// Returns the closest point to `point` on the walkable area, if there is one.
+    pub fn closest_point(&self, point: Vec3) -> Option<Vec3> {
+        self.closest_polygon(point).map(|(_, point)| point)
+    }
+
+    fn closest_polygon(&self, point: Vec3) -> Option<(usize, Vec3)> {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.closest_point(point))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+    }
+
+    /// Finds the shortest path across the walkable area from `start` to `end`.
+    ///
+    /// Both points are first moved to the closest point on the walkable area. The path starts
+    /// and ends with those points, and turns only at the corners it has to go around.
+    /// Returns `None` if there is no walkable area or if `end` can't be reached from `start`.
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let (start_polygon, start) = self.closest_polygon(start)?;
+        let (end_polygon, end) = self.closest_polygon(end)?;
+
+        // A* over the polygons, entering each one through the middle of a portal.
+        let mut entries = vec![None; self.polygons.len()];
+        let mut costs = vec![f32::INFINITY; self.polygons.len()];
+        let mut queue = BinaryHeap::new();
+        costs[start_polygon] = 0.0;
+        queue.push(Candidate {
+            estimate: start.distance(end),
+            polygon: start_polygon,
+        });
+        let mut points = vec![start; self.polygons.len()];
+        while let Some(Candidate { polygon, .. }) = queue.pop() {
+            if polygon == end_polygon {
+                break;
+            }
+            for (index, link) in self.polygons[polygon].links.iter().enumerate() {
+                let point = (link.start + link.end) / 2.0;
+                let cost = costs[polygon] + points[polygon].distance(point);
+                if cost < costs[link.polygon] {
+                    costs[link.polygon] = cost;
+                    entries[link.polygon] = Some((polygon, index));
+                    points[link.polygon] = point;
+                    queue.push(Candidate {
+                        estimate: cost + point.distance(end),
+                        polygon: link.polygon,
+                    });
+                }
+            }
+        }
+
+        let mut portals = vec![(end, end)];
+        let mut polygon = end_polygon;
+        while polygon != start_polygon {
+            let (previous, index) = entries[polygon]?;
+            let link = &self.polygons[previous].links[index];
+            // Order the ends of the portal as seen when crossing it.
+            if link.direction.perp_dot((link.end - link.start).xz()) > 0.0 {
+                portals.push((link.end, link.start));
+            } else {
+                portals.push((link.start, link.end));
+            }
+            polygon = previous;
+        }
+        portals.push((start, start));
+        portals.reverse();
+
+        Some(string_pull(&portals))
+    }
+
+    /// Casts a ray across the walkable area from `start` towards `end`, as seen from above.
+    ///
+    /// Returns the point where the ray leaves the walkable area, or `None` if `end` can be
+    /// reached in a straight line. `start` is first moved to the closest point on the walkable
+    /// area, which is returned if there is none.
+    pub fn raycast(&self, start: Vec3, end: Vec3) -> Option<Vec3> {
+        let Some((mut polygon, start)) = self.closest_polygon(start) else {
+            return Some(start);
+        };
+        let origin = start.xz();
+        let delta = end.xz() - origin;
+
+        for _ in 0..=self.polygons.len() {
+            let current = &self.polygons[polygon];
+            // Where the ray exits the rectangle of the current polygon.
+            let exit = [0, 1]
+                .map(|axis| match delta[axis] {
+                    d if d > 0.0 => (current.max[axis] - origin[axis]) / d,
+                    d if d < 0.0 => (current.min[axis] - origin[axis]) / d,
+                    _ => f32::INFINITY,
+                })
+                .into_iter()
+                .fold(f32::INFINITY, f32::min);
+            if exit >= 1.0 {
+                return None;
+            }
+
+            let point = origin + delta * exit;
+            let next = current.links.iter().find(|link| {
+                link.direction.dot(delta) > 0.0 && {
+                    let (a, b) = (link.start.xz(), link.end.xz());
+                    let (min, max) = (a.min(b), a.max(b));
+                    point.cmpge(min - 1e-4).all() && point.cmple(max + 1e-4).all()
+                }
+            });
+            match next {
+                Some(link) => polygon = link.polygon,
+                None => return Some(current.at(point)),
+            }
+        }
+        Some(start)
+    }
+
+    /// Carves the given obstacles into the navigation mesh, replacing any previous ones.
+    ///
+    /// The polygons are only rebuilt if the carved area changes.
+    pub fn carve<'a>(
+        &mut self,
+        obstacles: impl IntoIterator<Item = (&'a GlobalTransform, &'a NavMeshObstacle)>,
+    ) {
+        let radius = self.settings.agent_radius;
+        let height = self.settings.agent_height;
+        let obstacles: Vec<_> = obstacles
+            .into_iter()
+            .map(|(transform, obstacle)| {
+                let (scale, _, _) = transform.to_scale_rotation_translation();
+                // An agent touches the obstacle if the middle of its body is within half its
+                // height and its radius of it.
+                let margin = Vec3::new(radius, height / 2.0, radius) / scale.abs();
+                (transform.affine().inverse(), obstacle.half_extents + margin)
+            })
+            .collect();
+
+        let blocked: Vec<bool> = self
+            .grid
+            .cells
+            .iter()
+            .map(|cell| {
+                let point = self.cell_position(cell.x, cell.z, cell.y)
+                    + Vec3::new(
+                        0.5 * self.settings.cell_size,
+                        height / 2.0,
+                        0.5 * self.settings.cell_size,
+                    );
+                obstacles.iter().any(|(inverse, extents)| {
+                    inverse.transform_point3(point).abs().cmple(*extents).all()
+                })
+            })
+            .collect();
+
+        if blocked != self.blocked {
+            self.blocked = blocked;
+            self.build_polygons();
+        }
+    }
+
+    /// The world position of the corner of a cell with the lowest coordinates.
+    fn cell_position(&self, x: i32, z: i32, y: i32) -> Vec3 {
+        self.grid.origin
+            + Vec3::new(
+                x as f32 * self.settings.cell_size,
+                y as f32 * self.settings.cell_height,
+                z as f32 * self.settings.cell_size,
+            )
+    }
+
+    /// Merges the unblocked cells into rectangles, and links the rectangles that touch.
+    fn build_polygons(&mut self) {
+        let cells = &self.grid.cells;
+        let mut polygon_of = vec![None; cells.len()];
+        let mut rectangles = Vec::new();
+
+        for start in 0..cells.len() {
+            // Cells are only merged with cells of the same height, so that polygons are flat.
+            let free = |cell: usize, polygon_of: &[Option<usize>]| {
+                !self.blocked[cell] && polygon_of[cell].is_none() && cells[cell].y == cells[start].y
+            };
+            if !free(start, &polygon_of) {
+                continue;
+            }
+
+            let mut row = vec![start];
+            while let Some(next) = cells[*row.last().unwrap()].neighbors[POSITIVE_X] {
+                if !free(next, &polygon_of) {
+                    break;
+                }
+                row.push(next);
+            }
+            let mut rows = vec![row];
+            loop {
+                let previous = rows.last().unwrap();
+                let mut next_row: Vec<usize> = Vec::with_capacity(previous.len());
+                for &cell in previous {
+                    let Some(next) = cells[cell].neighbors[POSITIVE_Z] else {
+                        break;
+                    };
+                    let connected = match next_row.last() {
+                        Some(&last) => cells[last].neighbors[POSITIVE_X] == Some(next),
+                        None => true,
+                    };
+                    if !connected || !free(next, &polygon_of) {
+                        break;
+                    }
+                    next_row.push(next);
+                }
+                if next_row.len() != previous.len() {
+                    break;
+                }
+                rows.push(next_row);
+            }
+
+            for &cell in rows.iter().flatten() {
+                polygon_of[cell] = Some(rectangles.len());
+            }
+            rectangles.push((start, rows[0].len(), rows.len()));
+        }
+
+        let cell_size = self.settings.cell_size;
+        let height = |cell: usize| self.cell_position(0, 0, cells[cell].y).y;
+        let mut polygons: Vec<NavPolygon> = rectangles
+            .iter()
+            .map(|&(start, columns, rows)| {
+                let corner = self.cell_position(cells[start].x, cells[start].z, cells[start].y);
+                NavPolygon {
+                    min: corner.xz(),
+                    max: corner.xz() + Vec2::new(columns as f32, rows as f32) * cell_size,
+                    height: corner.y,
+                    links: Vec::new(),
+                }
+            })
+            .collect();
+
+        // Collect the cell edges between each pair of polygons, as offsets along the edge.
+        let mut borders = HashMap::<(usize, usize, usize), Vec<(i32, usize, usize)>>::new();
+        for (cell, polygon) in polygon_of.iter().enumerate() {
+            let Some(polygon) = *polygon else {
+                continue;
+            };
+            for (direction, neighbor) in cells[cell].neighbors.iter().enumerate() {
+                let Some(other) = neighbor.and_then(|neighbor| polygon_of[neighbor]) else {
+                    continue;
+                };
+                if other != polygon {
+                    let offset = if DIRECTIONS[direction].0 == 0 {
+                        cells[cell].x
+                    } else {
+                        cells[cell].z
+                    };
+                    borders
+                        .entry((polygon, other, direction))
+                        .or_default()
+                        .push((offset, cell, neighbor.unwrap()));
+                }
+            }
+        }
+
+        // Each contiguous run of cell edges becomes a portal.
+        let mut borders: Vec<_> = borders.into_iter().collect();
+        borders.sort_unstable_by_key(|(key, _)| *key);
+        for ((polygon, other, direction), mut edges) in borders {
+            edges.sort_unstable();
+            let (dx, dz) = DIRECTIONS[direction];
+            let step = Vec2::new(dx as f32, dz as f32);
+            let along = Vec2::new(dz.abs() as f32, dx.abs() as f32);
+            // The start or the end of the edge between a cell and its neighbor.
+            let point = |(_, cell, neighbor): (i32, usize, usize), side: f32| {
+                let corner = self.cell_position(cells[cell].x, cells[cell].z, 0).xz();
+                let point = corner + (Vec2::ONE + step + along * side) * cell_size / 2.0;
+                let y = (height(cell) + height(neighbor)) / 2.0;
+                Vec3::new(point.x, y, point.y)
+            };
+
+            let mut first = 0;
+            for i in 1..=edges.len() {
+                if i < edges.len() && edges[i].0 == edges[i - 1].0 + 1 {
+                    continue;
+                }
+                polygons[polygon].links.push(NavLink {
+                    polygon: other,
+                    start: point(edges[first], -1.0),
+                    end: point(edges[i - 1], 1.0),
+                    direction: step,
+                });
+                first = i;
+            }
+        }
+
+        self.polygons = polygons;
+    }
+}
+
+/// A polygon to visit while finding a path, ordered so that the lowest estimate is popped first.
+struct Candidate {
+    estimate: f32,
+    polygon: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.polygon.cmp(&self.polygon))
+    }
+}
+
+/// Finds the shortest path through a sequence of portals, given as their left and right ends
+/// as seen when crossing them, with the first and last portals being the start and the end.
+fn string_pull(portals: &[(Vec3, Vec3)]) -> Vec<Vec3> {
+    let cross = |apex: Vec3, a: Vec3, b: Vec3| (a - apex).xz().perp_dot((b - apex).xz());
+
+    let mut path = vec![portals[0].0];
+    let (mut apex, mut left, mut right) = (portals[0].0, portals[0].0, portals[0].1);
+    let (mut left_index, mut right_index) = (0, 0);
+    let mut i = 1;
+    while i < portals.len() {
+        let (new_left, new_right) = portals[i];
+
+        // Narrow the funnel from the right, unless that crosses over its left side, in which
+        // case the left side is a corner of the path.
+        if cross(apex, right, new_right) >= 0.0 {
+            if apex == right || cross(apex, left, new_right) < 0.0 {
+                (right, right_index) = (new_right, i);
+            } else {
+                path.push(left);
+                (apex, right, right_index) = (left, left, left_index);
+                i = left_index + 1;
+                continue;
+            }
+        }
+
+        // And the same from the left.
+        if cross(apex, left, new_left) <= 0.0 {
+            if apex == left || cross(apex, right, new_left) > 0.0 {
+                (left, left_index) = (new_left, i);
+            } else {
+                path.push(right);
+                (apex, left, left_index) = (right, right, right_index);
+                i = right_index + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    let end = portals[portals.len() - 1].0;
+    if path.last() != Some(&end) {
+        path.push(end);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NavMesh, NavMeshObstacle};
+    use crate::NavMeshSettings;
+    use bevy_math::Vec3;
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    /// The triangles of an axis aligned box.
+    fn cuboid(min: Vec3, max: Vec3) -> Vec<[Vec3; 3]> {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        };
+        [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ]
+        .into_iter()
+        .flat_map(|[a, b, c, d]| {
+            [
+                [corner(a), corner(b), corner(c)],
+                [corner(a), corner(c), corner(d)],
+            ]
+        })
+        .collect()
+    }
+
+    /// A 20 by 20 floor split by a wall along `x == 0`, with a gap at its `+z` end.
+    fn walled_floor() -> NavMesh {
+        let mut triangles = cuboid(Vec3::new(-10.0, -1.0, -10.0), Vec3::new(10.0, 0.0, 10.0));
+        triangles.extend(cuboid(
+            Vec3::new(-0.5, 0.0, -10.0),
+            Vec3::new(0.5, 3.0, 6.0),
+        ));
+        NavMesh::from_triangles(&NavMeshSettings::default(), triangles)
+    }
+
+    #[test]
+    fn path_around_wall() {
+        let navmesh = walled_floor();
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0));
+
+        assert!(navmesh.raycast(start, end).is_some());
+        assert!(navmesh.raycast(start, Vec3::new(-5.0, 0.0, 5.0)).is_none());
+
+        // Heights are only as precise as the cell height.
+        let path = navmesh.find_path(start, end).unwrap();
+        assert!(path.first().unwrap().distance(start) < 0.2);
+        assert!(path.last().unwrap().distance(end) < 0.2);
+        // The path has to go through the gap, while keeping an agent radius from the wall.
+        assert!(path.iter().any(|point| point.z > 6.4));
+        for segment in path.windows(2) {
+            assert!(navmesh.raycast(segment[0], segment[1]).is_none());
+        }
+        // Only the corners of the gap are turned around.
+        let length: f32 = path.windows(2).map(|s| s[0].distance(s[1])).sum();
+        assert!(length < 2.0 * Vec3::new(5.0, 0.0, 6.5).length() + 2.5);
+    }
+
+    #[test]
+    fn carve_obstacle() {
+        let mut navmesh = walled_floor();
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0));
+
+        // Block the gap in the wall.
+        let transform = GlobalTransform::from(Transform::from_xyz(0.0, 1.0, 8.0));
+        let obstacle = NavMeshObstacle {
+            half_extents: Vec3::new(0.5, 1.0, 2.0),
+        };
+        navmesh.carve([(&transform, &obstacle)]);
+        assert!(navmesh.find_path(start, end).is_none());
+
+        navmesh.carve([]);
+        assert!(navmesh.find_path(start, end).is_some());
+    }
+}
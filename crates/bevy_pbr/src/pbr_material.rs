@@ -1,5 +1,5 @@
 use bevy_asset::{Asset, Handle};
-use bevy_math::Vec4;
+use bevy_math::{Affine2, Mat3, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     color::Color, mesh::MeshVertexBufferLayout, render_asset::RenderAssets, render_resource::*,
@@ -284,6 +284,31 @@ pub struct StandardMaterial {
     #[doc(alias = "extinction_color")]
     pub attenuation_color: Color,
 
+    /// The strength of a thin, clear and dielectric layer on top of the material, like the
+    /// varnish on a car paint or the lacquer on wood, on a linear scale of `[0.0, 1.0]`.
+    ///
+    /// The clearcoat layer adds a second specular highlight, with its own
+    /// [`StandardMaterial::clearcoat_perceptual_roughness`] and a fixed 4% reflectance, and
+    /// darkens the base material underneath it by the light it reflects.
+    ///
+    /// Defaults to `0.0`, i.e. no clearcoat. The clearcoat is only rendered by the forward
+    /// renderer.
+    pub clearcoat: f32,
+
+    /// The perceptual roughness of the clearcoat layer, like [`StandardMaterial::perceptual_roughness`]
+    /// for the base material.
+    ///
+    /// Defaults to `0.5`.
+    ///
+    /// **Note:** Only has an effect when [`StandardMaterial::clearcoat`] is above `0.0`.
+    pub clearcoat_perceptual_roughness: f32,
+
+    /// The transform applied to the UV coordinates of the mesh before sampling the textures of
+    /// the material, for example to scroll, tile or rotate them.
+    ///
+    /// Defaults to [`Affine2::IDENTITY`].
+    pub uv_transform: Affine2,
+
     /// Used to fake the lighting of bumps and dents on a material.
     ///
     /// A typical usage would be faking cobblestones on a flat plane mesh in 3D.
@@ -501,6 +526,9 @@ impl Default for StandardMaterial {
             ior: 1.5,
             attenuation_color: Color::WHITE,
             attenuation_distance: f32::INFINITY,
+            clearcoat: 0.0,
+            clearcoat_perceptual_roughness: 0.5,
+            uv_transform: Affine2::IDENTITY,
             occlusion_texture: None,
             normal_map_texture: None,
             flip_normal_map_y: false,
@@ -609,6 +637,12 @@ pub struct StandardMaterialUniform {
     pub attenuation_distance: f32,
     /// Color white light takes after travelling through the attenuation distance underneath the material surface
     pub attenuation_color: Vec4,
+    /// Strength of the clearcoat layer on top of the material
+    pub clearcoat: f32,
+    /// Linear perceptual roughness of the clearcoat layer
+    pub clearcoat_roughness: f32,
+    /// The transform applied to the UV coordinates before sampling the textures
+    pub uv_transform: Mat3,
     /// The [`StandardMaterialFlags`] accessible in the `wgsl` shader.
     pub flags: u32,
     /// When the alpha mode mask flag is set, any base color alpha above this cutoff means fully opaque,
@@ -716,6 +750,9 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             ior: self.ior,
             attenuation_distance: self.attenuation_distance,
             attenuation_color: self.attenuation_color.as_linear_rgba_f32().into(),
+            clearcoat: self.clearcoat,
+            clearcoat_roughness: self.clearcoat_perceptual_roughness,
+            uv_transform: self.uv_transform.into(),
             flags: flags.bits(),
             alpha_cutoff,
             parallax_depth_scale: self.parallax_depth_scale,
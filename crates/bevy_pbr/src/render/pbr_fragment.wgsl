@@ -68,7 +68,7 @@ fn pbr_input_from_standard_material(
     pbr_input.material.deferred_lighting_pass_id = pbr_bindings::material.deferred_lighting_pass_id;
 
 #ifdef VERTEX_UVS
-    var uv = in.uv;
+    var uv = (pbr_bindings::material.uv_transform * vec3(in.uv, 1.0)).xy;
 
 #ifdef VERTEX_TANGENTS
     if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DEPTH_MAP_BIT) != 0u) {
@@ -105,6 +105,8 @@ fn pbr_input_from_standard_material(
         pbr_input.material.attenuation_color = pbr_bindings::material.attenuation_color;
         pbr_input.material.attenuation_distance = pbr_bindings::material.attenuation_distance;
         pbr_input.material.alpha_cutoff = pbr_bindings::material.alpha_cutoff;
+        pbr_input.material.clearcoat = pbr_bindings::material.clearcoat;
+        pbr_input.material.clearcoat_perceptual_roughness = pbr_bindings::material.clearcoat_perceptual_roughness;
 
         // emissive
         // TODO use .a for exposure compensation in HDR
@@ -187,8 +187,19 @@ fn apply_pbr_lighting(
 
     let f_ab = lighting::F_AB(perceptual_roughness, NdotV);
 
+    // The clearcoat layer is a second, dielectric specular lobe with a fixed 4% reflectance on top
+    // of the material, which uses the same normal
+    let clearcoat = in.material.clearcoat;
+    let clearcoat_perceptual_roughness = in.material.clearcoat_perceptual_roughness;
+    let clearcoat_roughness = lighting::perceptualRoughnessToRoughness(clearcoat_perceptual_roughness);
+    let clearcoat_F0 = vec3<f32>(0.04);
+    let clearcoat_f_ab = lighting::F_AB(clearcoat_perceptual_roughness, NdotV);
+
     var direct_light: vec3<f32> = vec3<f32>(0.0);
 
+    // Light reflected by the clearcoat layer
+    var clearcoat_light: vec3<f32> = vec3<f32>(0.0);
+
     // Transmitted Light (Specular and Diffuse)
     var transmitted_light: vec3<f32> = vec3<f32>(0.0);
 
@@ -212,6 +223,11 @@ fn apply_pbr_lighting(
         let light_contrib = lighting::point_light(in.world_position.xyz, light_id, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
         direct_light += light_contrib * shadow;
 
+        if clearcoat > 0.0 {
+            let clearcoat_contrib = lighting::point_light(in.world_position.xyz, light_id, clearcoat_roughness, NdotV, in.N, in.V, R, clearcoat_F0, clearcoat_f_ab, vec3<f32>(0.0));
+            clearcoat_light += clearcoat_contrib * shadow;
+        }
+
         if diffuse_transmission > 0.0 {
             // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
             // world position, inverted normal and view vectors, and the following simplified
@@ -244,6 +260,11 @@ fn apply_pbr_lighting(
         let light_contrib = lighting::spot_light(in.world_position.xyz, light_id, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
         direct_light += light_contrib * shadow;
 
+        if clearcoat > 0.0 {
+            let clearcoat_contrib = lighting::spot_light(in.world_position.xyz, light_id, clearcoat_roughness, NdotV, in.N, in.V, R, clearcoat_F0, clearcoat_f_ab, vec3<f32>(0.0));
+            clearcoat_light += clearcoat_contrib * shadow;
+        }
+
         if diffuse_transmission > 0.0 {
             // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
             // world position, inverted normal and view vectors, and the following simplified
@@ -285,6 +306,11 @@ fn apply_pbr_lighting(
 #endif
         direct_light += light_contrib * shadow;
 
+        if clearcoat > 0.0 {
+            let clearcoat_contrib = lighting::directional_light(i, clearcoat_roughness, NdotV, in.N, in.V, R, clearcoat_F0, clearcoat_f_ab, vec3<f32>(0.0));
+            clearcoat_light += clearcoat_contrib * shadow;
+        }
+
         if diffuse_transmission > 0.0 {
             // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
             // world position, inverted normal and view vectors, and the following simplified
@@ -308,6 +334,10 @@ fn apply_pbr_lighting(
     // Ambient light (indirect)
     var indirect_light = ambient::ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, occlusion);
 
+    if clearcoat > 0.0 {
+        clearcoat_light += ambient::ambient_light(in.world_position, in.N, in.V, NdotV, vec3<f32>(0.0), clearcoat_F0, clearcoat_perceptual_roughness, occlusion);
+    }
+
     if diffuse_transmission > 0.0 {
         // NOTE: We use the diffuse transmissive color, the second Lambertian lobe's calculated
         // world position, inverted normal and view vectors, and the following simplified
@@ -325,6 +355,11 @@ fn apply_pbr_lighting(
     let environment_light = environment_map::environment_map_light(perceptual_roughness, roughness, diffuse_color, NdotV, f_ab, in.N, R, F0);
     indirect_light += (environment_light.diffuse * occlusion) + environment_light.specular;
 
+    if clearcoat > 0.0 {
+        let clearcoat_environment_light = environment_map::environment_map_light(clearcoat_perceptual_roughness, clearcoat_roughness, vec3<f32>(0.0), NdotV, clearcoat_f_ab, in.N, R, clearcoat_F0);
+        clearcoat_light += clearcoat_environment_light.specular;
+    }
+
     // we'll use the specular component of the transmitted environment
     // light in the call to `specular_transmissive_light()` below
     var specular_transmitted_environment_light = vec3<f32>(0.0);
@@ -379,9 +414,14 @@ fn apply_pbr_lighting(
         ).rgb;
     }
 
+    // The light reflected by the clearcoat layer doesn't reach the base material underneath it
+    let clearcoat_fresnel = clearcoat * lighting::F_Schlick(0.04, 1.0, NdotV);
+
     // Total light
     output_color = vec4<f32>(
-        transmitted_light + direct_light + indirect_light + emissive_light,
+        (transmitted_light + direct_light + indirect_light) * (1.0 - clearcoat_fresnel)
+            + clearcoat_light * clearcoat
+            + emissive_light,
         output_color.a
     );
 
@@ -43,7 +43,7 @@ fn fragment(
 #endif // STANDARDMATERIAL_NORMAL_MAP
 #endif // VERTEX_TANGENTS
 #ifdef VERTEX_UVS
-            in.uv,
+            (material.uv_transform * vec3(in.uv, 1.0)).xy,
 #endif // VERTEX_UVS
             view.mip_bias,
         );
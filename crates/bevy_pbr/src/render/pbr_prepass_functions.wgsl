@@ -19,7 +19,8 @@ fn prepass_alpha_discard(in: VertexOutput) {
 
 #ifdef VERTEX_UVS
     if (pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u {
-        output_color = output_color * textureSampleBias(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, in.uv, view.mip_bias);
+        let uv = (pbr_bindings::material.uv_transform * vec3(in.uv, 1.0)).xy;
+        output_color = output_color * textureSampleBias(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, uv, view.mip_bias);
     }
 #endif // VERTEX_UVS
 
@@ -12,6 +12,9 @@ struct StandardMaterial {
     ior: f32,
     attenuation_distance: f32,
     attenuation_color: vec4<f32>,
+    clearcoat: f32,
+    clearcoat_perceptual_roughness: f32,
+    uv_transform: mat3x3<f32>,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     alpha_cutoff: f32,
@@ -67,6 +70,9 @@ fn standard_material_new() -> StandardMaterial {
     material.ior = 1.5;
     material.attenuation_distance = 1.0;
     material.attenuation_color = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+    material.clearcoat = 0.0;
+    material.clearcoat_perceptual_roughness = 0.5;
+    material.uv_transform = mat3x3<f32>(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0);
     material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE;
     material.alpha_cutoff = 0.5;
     material.parallax_depth_scale = 0.1;
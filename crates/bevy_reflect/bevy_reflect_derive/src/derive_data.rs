@@ -45,6 +45,8 @@ pub(crate) struct ReflectMeta<'a> {
     type_path: ReflectTypePath<'a>,
     /// A cached instance of the path to the `bevy_reflect` crate.
     bevy_reflect_path: Path,
+    /// The type from another crate this type wraps, when generated by `#[reflect_remote]`.
+    remote_ty: Option<&'a Path>,
     /// The documentation for this type, if any
     #[cfg(feature = "documentation")]
     docs: crate::documentation::Documentation,
@@ -108,6 +110,15 @@ pub(crate) struct StructField<'a> {
     pub doc: crate::documentation::Documentation,
 }
 
+impl<'a> StructField<'a> {
+    /// The type of this field as seen by the reflection API.
+    ///
+    /// This is the wrapper given by `#[reflect(remote = ...)]`, if any, or the type of the field.
+    pub fn reflected_type(&self) -> &Type {
+        self.attrs.remote.as_ref().unwrap_or(&self.data.ty)
+    }
+}
+
 /// Represents a variant on an enum.
 pub(crate) struct EnumVariant<'a> {
     /// The raw variant.
@@ -312,6 +323,17 @@ impl<'a> ReflectDerive<'a> {
         }
     }
 
+    /// Sets the type from another crate this type wraps, for `#[reflect_remote]`.
+    pub fn set_remote(&mut self, remote_ty: Option<&'a Path>) {
+        match self {
+            ReflectDerive::Struct(data)
+            | ReflectDerive::TupleStruct(data)
+            | ReflectDerive::UnitStruct(data) => data.meta.remote_ty = remote_ty,
+            ReflectDerive::Enum(data) => data.meta.remote_ty = remote_ty,
+            ReflectDerive::Value(meta) => meta.remote_ty = remote_ty,
+        }
+    }
+
     fn collect_struct_fields(fields: &'a Fields) -> Result<Vec<StructField<'a>>, syn::Error> {
         let mut active_index = 0;
         let sifter: utility::ResultSifter<StructField<'a>> = fields
@@ -355,6 +377,13 @@ impl<'a> ReflectDerive<'a> {
             .map(|(index, variant)| -> Result<EnumVariant, syn::Error> {
                 let fields = Self::collect_struct_fields(&variant.fields)?;
 
+                if let Some(field) = fields.iter().find(|field| field.attrs.remote.is_some()) {
+                    return Err(syn::Error::new(
+                        field.data.span(),
+                        format_args!("`#[{REFLECT_ATTRIBUTE_NAME}(remote = ...)]` is not supported on enum variant fields"),
+                    ));
+                }
+
                 let fields = match variant.fields {
                     Fields::Named(..) => EnumVariantFields::Named(fields),
                     Fields::Unnamed(..) => EnumVariantFields::Unnamed(fields),
@@ -384,6 +413,7 @@ impl<'a> ReflectMeta<'a> {
             traits,
             type_path,
             bevy_reflect_path: utility::get_bevy_reflect_path(),
+            remote_ty: None,
             #[cfg(feature = "documentation")]
             docs: Default::default(),
         }
@@ -416,6 +446,11 @@ impl<'a> ReflectMeta<'a> {
         &self.bevy_reflect_path
     }
 
+    /// The type from another crate this type wraps, when generated by `#[reflect_remote]`.
+    pub fn remote_ty(&self) -> Option<&'a Path> {
+        self.remote_ty
+    }
+
     /// Returns the `GetTypeRegistration` impl as a `TokenStream`.
     pub fn get_type_registration(
         &self,
@@ -459,7 +494,37 @@ impl<'a> ReflectStruct<'a> {
     /// Get a collection of types which are exposed to the reflection API
     pub fn active_types(&self) -> Vec<Type> {
         self.active_fields()
-            .map(|field| field.data.ty.clone())
+            .map(|field| field.reflected_type().clone())
+            .collect()
+    }
+
+    /// Get the expressions borrowing the active fields of `self` as reflected values,
+    /// mutably if `is_mut` is true.
+    ///
+    /// For a `#[reflect_remote]` wrapper, the fields are those of the wrapped type.
+    /// Fields marked with `#[reflect(remote = ...)]` are borrowed as their wrapper.
+    pub fn active_field_refs(&self, is_mut: bool) -> Vec<proc_macro2::TokenStream> {
+        let bevy_reflect_path = self.meta.bevy_reflect_path();
+        let this = if self.meta.remote_ty().is_some() {
+            quote!(self.0)
+        } else {
+            quote!(self)
+        };
+
+        self.active_fields()
+            .map(|field| {
+                let member = utility::ident_or_index(field.data.ident.as_ref(), field.declaration_index);
+                match (&field.attrs.remote, is_mut) {
+                    (None, false) => quote!(&#this.#member),
+                    (None, true) => quote!(&mut #this.#member),
+                    (Some(wrapper), false) => quote! {
+                        <#wrapper as #bevy_reflect_path::ReflectRemote>::as_wrapper(&#this.#member)
+                    },
+                    (Some(wrapper), true) => quote! {
+                        <#wrapper as #bevy_reflect_path::ReflectRemote>::as_wrapper_mut(&mut #this.#member)
+                    },
+                }
+            })
             .collect()
     }
 
@@ -6,13 +6,15 @@
 
 use crate::REFLECT_ATTRIBUTE_NAME;
 use syn::meta::ParseNestedMeta;
-use syn::{Attribute, LitStr, Token};
+use syn::{Attribute, LitStr, Token, Type};
 
 pub(crate) static IGNORE_SERIALIZATION_ATTR: &str = "skip_serializing";
 pub(crate) static IGNORE_ALL_ATTR: &str = "ignore";
 
 pub(crate) static DEFAULT_ATTR: &str = "default";
 
+pub(crate) static REMOTE_ATTR: &str = "remote";
+
 /// Stores data about if the field should be visible via the Reflect and serialization interfaces
 ///
 /// Note the relationship between serialization and reflection is such that a member must be reflected in order to be serialized.
@@ -51,6 +53,8 @@ pub(crate) struct ReflectFieldAttr {
     pub ignore: ReflectIgnoreBehavior,
     /// Sets the default behavior of this field.
     pub default: DefaultBehavior,
+    /// The wrapper generated by `#[reflect_remote]` through which this field is reflected, if any.
+    pub remote: Option<Type>,
 }
 
 /// Controls how the default value is determined for a field.
@@ -136,11 +140,26 @@ fn parse_meta(args: &mut ReflectFieldAttr, meta: ParseNestedMeta) -> Result<(),
 
         args.ignore = ReflectIgnoreBehavior::IgnoreSerialization;
 
+        Ok(())
+    } else if meta.path.is_ident(REMOTE_ATTR) {
+        // Allow:
+        // - `#[reflect(remote = path::to::Wrapper)]`
+        if args.remote.is_some() {
+            return Err(meta.error(format!("only one of [{:?}] is allowed", [REMOTE_ATTR])));
+        }
+
+        args.remote = Some(meta.value()?.parse()?);
+
         Ok(())
     } else {
         Err(meta.error(format!(
             "unknown attribute, expected {:?}",
-            [DEFAULT_ATTR, IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
+            [
+                DEFAULT_ATTR,
+                IGNORE_ALL_ATTR,
+                IGNORE_SERIALIZATION_ATTR,
+                REMOTE_ATTR
+            ]
         )))
     }
 }
@@ -2,7 +2,7 @@ use crate::container_attributes::REFLECT_DEFAULT;
 use crate::derive_data::ReflectEnum;
 use crate::enum_utility::{get_variant_constructors, EnumVariantConstructors};
 use crate::field_attributes::DefaultBehavior;
-use crate::utility::{extend_where_clause, ident_or_index, WhereClauseOptions};
+use crate::utility::{self, extend_where_clause, ident_or_index, WhereClauseOptions};
 use crate::{ReflectMeta, ReflectStruct};
 use bevy_macro_utils::fq_std::{FQAny, FQClone, FQDefault, FQOption};
 use proc_macro2::Span;
@@ -113,13 +113,20 @@ fn impl_struct_internal(
         get_active_fields(reflect_struct, &ref_struct, &ref_struct_type, is_tuple);
 
     let is_defaultable = reflect_struct.meta().traits().contains(REFLECT_DEFAULT);
+    let remote_ty = reflect_struct.meta().remote_ty();
     let constructor = if is_defaultable {
+        let this = if remote_ty.is_some() {
+            quote!(__this.0)
+        } else {
+            quote!(__this)
+        };
+
         quote!(
             let mut __this: Self = #FQDefault::default();
             #(
                 if let #fqoption::Some(__field) = #active_values() {
                     // Iff field exists -> use its value
-                    __this.#active_members = __field;
+                    #this.#active_members = __field;
                 }
             )*
             #FQOption::Some(__this)
@@ -127,14 +134,26 @@ fn impl_struct_internal(
     } else {
         let MemberValuePair(ignored_members, ignored_values) = get_ignored_fields(reflect_struct);
 
-        quote!(
-            #FQOption::Some(
-                Self {
-                    #(#active_members: #active_values()?,)*
-                    #(#ignored_members: #ignored_values,)*
-                }
+        if let Some(remote_ty) = remote_ty {
+            let remote_ty = utility::as_expr_path(remote_ty);
+            quote!(
+                #FQOption::Some(
+                    Self(#remote_ty {
+                        #(#active_members: #active_values()?,)*
+                        #(#ignored_members: #ignored_values,)*
+                    })
+                )
             )
-        )
+        } else {
+            quote!(
+                #FQOption::Some(
+                    Self {
+                        #(#active_members: #active_values()?,)*
+                        #(#ignored_members: #ignored_values,)*
+                    }
+                )
+            )
+        }
     };
 
     let (impl_generics, ty_generics, where_clause) = reflect_struct
@@ -224,17 +243,36 @@ fn get_active_fields(
                     field.reflection_index.expect("field should be active"),
                     is_tuple,
                 );
-                let ty = field.data.ty.clone();
+                let ty = field.reflected_type();
 
                 let get_field = quote! {
                     #bevy_reflect_path::#struct_type::field(#dyn_struct_name, #accessor)
                 };
 
+                // Fields reflected through a remote wrapper are unwrapped into their own type
+                let (from_reflect, default) = match &field.attrs.remote {
+                    Some(wrapper) => (
+                        quote! {
+                            |field| #FQOption::map(
+                                <#wrapper as #bevy_reflect_path::FromReflect>::from_reflect(field),
+                                <#wrapper as #bevy_reflect_path::ReflectRemote>::into_remote,
+                            )
+                        },
+                        quote! {
+                            <#wrapper as #bevy_reflect_path::ReflectRemote>::into_remote(#FQDefault::default())
+                        },
+                    ),
+                    None => (
+                        quote!(<#ty as #bevy_reflect_path::FromReflect>::from_reflect),
+                        quote!(#FQDefault::default()),
+                    ),
+                };
+
                 let value = match &field.attrs.default {
                     DefaultBehavior::Func(path) => quote! {
                         (||
                             if let #FQOption::Some(field) = #get_field {
-                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)
+                                (#from_reflect)(field)
                             } else {
                                 #FQOption::Some(#path())
                             }
@@ -243,14 +281,14 @@ fn get_active_fields(
                     DefaultBehavior::Default => quote! {
                         (||
                             if let #FQOption::Some(field) = #get_field {
-                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)
+                                (#from_reflect)(field)
                             } else {
-                                #FQOption::Some(#FQDefault::default())
+                                #FQOption::Some(#default)
                             }
                         )
                     },
                     DefaultBehavior::Required => quote! {
-                        (|| <#ty as #bevy_reflect_path::FromReflect>::from_reflect(#get_field?))
+                        (|| (#from_reflect)(#get_field?))
                     },
                 };
 
@@ -5,12 +5,13 @@
 //! such as `Struct`, `GetTypeRegistration`, and more— all with a single derive!
 //!
 //! Some other noteworthy exports include the derive macros for [`FromReflect`] and
-//! [`TypeUuid`], as well as the [`reflect_trait`] attribute macro.
+//! [`TypeUuid`], as well as the [`reflect_trait`] and [`reflect_remote`] attribute macros.
 //!
 //! [`Reflect`]: crate::derive_reflect
 //! [`FromReflect`]: crate::derive_from_reflect
 //! [`TypeUuid`]: crate::derive_type_uuid
 //! [`reflect_trait`]: macro@reflect_trait
+//! [`reflect_remote`]: macro@reflect_remote
 
 extern crate proc_macro;
 
@@ -24,6 +25,7 @@ mod from_reflect;
 mod impls;
 mod reflect_value;
 mod registration;
+mod remote;
 mod serialization;
 mod trait_reflection;
 mod type_path;
@@ -156,6 +158,14 @@ pub(crate) static TYPE_NAME_ATTRIBUTE_NAME: &str = "type_name";
 /// What this does is register the `SerializationData` type within the `GetTypeRegistration` implementation,
 /// which will be used by the reflection serializers to determine whether or not the field is serializable.
 ///
+/// ## `#[reflect(remote = path::to::Wrapper)]`
+///
+/// This attribute reflects a field whose type comes from another crate through its wrapper generated
+/// by [`#[reflect_remote]`](macro@reflect_remote).
+/// The field is seen by the reflection API as the wrapper, while keeping its own type in the struct.
+///
+/// This attribute is not supported on the fields of enum variants.
+///
 /// [`reflect_trait`]: macro@reflect_trait
 #[proc_macro_derive(Reflect, attributes(reflect, reflect_value, type_path, type_name))]
 pub fn derive_reflect(input: TokenStream) -> TokenStream {
@@ -363,6 +373,53 @@ pub fn reflect_trait(args: TokenStream, input: TokenStream) -> TokenStream {
     trait_reflection::reflect_trait(&args, input)
 }
 
+/// Implements `Reflect` for a type from another crate, from a local definition of its fields.
+///
+/// Reflection can't be derived for types of other crates, such as math types or the handles
+/// of a physics engine, and the orphan rule forbids implementing it on them manually.
+/// This attribute takes the path to such a type, and is placed on a local struct mirroring
+/// its fields, with the same names and types.
+/// The struct is replaced by a `#[repr(transparent)]` wrapper around the remote type,
+/// which implements `Reflect`, `FromReflect` and `GetTypeRegistration` as if it was derived
+/// from the local definition, along with the `ReflectRemote` trait to convert between the two.
+///
+/// The container and field attributes of the [`Reflect`] derive can be used on the definition,
+/// and the other attributes, such as derives, are applied to the wrapper.
+/// The fields of the remote type must be visible to the definition, and a definition which
+/// doesn't match the remote type fails to compile.
+///
+/// Fields of other reflected types can then use the remote type directly,
+/// by marking them with `#[reflect(remote = path::to::Wrapper)]`.
+///
+/// Only structs, tuple structs and unit structs are supported.
+///
+/// # Example
+///
+/// ```ignore
+/// mod physics {
+///     pub struct RigidBody {
+///         pub mass: f32,
+///         pub velocity: (f32, f32),
+///     }
+/// }
+///
+/// #[reflect_remote(physics::RigidBody)]
+/// struct RigidBodyDef {
+///     mass: f32,
+///     velocity: (f32, f32),
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Body {
+///     #[reflect(remote = RigidBodyDef)]
+///     rigid_body: physics::RigidBody,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn reflect_remote(args: TokenStream, input: TokenStream) -> TokenStream {
+    remote::reflect_remote(args, input)
+}
+
 /// A macro used to generate reflection trait implementations for the given type.
 ///
 /// This is functionally the same as [deriving `Reflect`] using the `#[reflect_value]` container attribute.
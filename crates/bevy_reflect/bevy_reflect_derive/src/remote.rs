@@ -0,0 +1,148 @@
+use crate::derive_data::{ReflectDerive, ReflectStruct};
+use crate::utility::ident_or_index;
+use crate::{
+    from_reflect, impls, REFLECT_ATTRIBUTE_NAME, REFLECT_VALUE_ATTRIBUTE_NAME,
+    TYPE_NAME_ATTRIBUTE_NAME, TYPE_PATH_ATTRIBUTE_NAME,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, DeriveInput, Path};
+
+/// Generates a wrapper implementing `Reflect` for the type `remote` of another crate, from a
+/// local definition of its fields.
+///
+/// The local definition is replaced by a `#[repr(transparent)]` tuple struct wrapping `remote`.
+/// The attributes used by the `Reflect` derive are removed from it, the others are kept.
+pub(crate) fn reflect_remote(args: TokenStream, input: TokenStream) -> TokenStream {
+    let remote_ty = parse_macro_input!(args as Path);
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let mut derive_data = match ReflectDerive::from_input(&ast, false) {
+        Ok(data) => data,
+        Err(err) => return err.into_compile_error().into(),
+    };
+    derive_data.set_remote(Some(&remote_ty));
+
+    let (reflect_impls, from_reflect_impl, assertions) = match &derive_data {
+        ReflectDerive::Struct(struct_data) | ReflectDerive::UnitStruct(struct_data) => (
+            impls::impl_struct(struct_data),
+            struct_data
+                .meta()
+                .from_reflect()
+                .should_auto_derive()
+                .then(|| from_reflect::impl_struct(struct_data)),
+            impl_field_assertions(struct_data, &remote_ty),
+        ),
+        ReflectDerive::TupleStruct(struct_data) => (
+            impls::impl_tuple_struct(struct_data),
+            struct_data
+                .meta()
+                .from_reflect()
+                .should_auto_derive()
+                .then(|| from_reflect::impl_tuple_struct(struct_data)),
+            impl_field_assertions(struct_data, &remote_ty),
+        ),
+        ReflectDerive::Enum(_) | ReflectDerive::Value(_) => {
+            return syn::Error::new(
+                ast.span(),
+                "`#[reflect_remote]` only supports structs, tuple structs, and unit structs",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
+
+    let bevy_reflect_path = derive_data.meta().bevy_reflect_path();
+    let attrs = ast.attrs.iter().filter(|attr| {
+        ![
+            REFLECT_ATTRIBUTE_NAME,
+            REFLECT_VALUE_ATTRIBUTE_NAME,
+            TYPE_PATH_ATTRIBUTE_NAME,
+            TYPE_NAME_ATTRIBUTE_NAME,
+        ]
+        .iter()
+        .any(|name| attr.path().is_ident(name))
+    });
+    let vis = &ast.vis;
+    let ident = &ast.ident;
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #(#attrs)*
+        #[repr(transparent)]
+        #vis struct #ident #generics (pub #remote_ty) #where_clause;
+
+        const _: () = {
+            #reflect_impls
+
+            #from_reflect_impl
+
+            #assertions
+
+            impl #impl_generics #bevy_reflect_path::ReflectRemote for #ident #ty_generics #where_clause {
+                type Remote = #remote_ty;
+
+                #[inline]
+                fn as_remote(&self) -> &Self::Remote {
+                    &self.0
+                }
+
+                #[inline]
+                fn as_remote_mut(&mut self) -> &mut Self::Remote {
+                    &mut self.0
+                }
+
+                #[inline]
+                fn into_remote(self) -> Self::Remote {
+                    self.0
+                }
+
+                #[inline]
+                fn as_wrapper(remote: &Self::Remote) -> &Self {
+                    // SAFETY: `Self` is a `#[repr(transparent)]` wrapper around `Self::Remote`
+                    unsafe { &*(remote as *const Self::Remote as *const Self) }
+                }
+
+                #[inline]
+                fn as_wrapper_mut(remote: &mut Self::Remote) -> &mut Self {
+                    // SAFETY: `Self` is a `#[repr(transparent)]` wrapper around `Self::Remote`
+                    unsafe { &mut *(remote as *mut Self::Remote as *mut Self) }
+                }
+
+                #[inline]
+                fn into_wrapper(remote: Self::Remote) -> Self {
+                    Self(remote)
+                }
+            }
+        };
+    })
+}
+
+/// Generates a function which fails to compile if the fields of the local definition don't
+/// match the fields of the remote type.
+fn impl_field_assertions(
+    reflect_struct: &ReflectStruct,
+    remote_ty: &Path,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _, where_clause) = reflect_struct
+        .meta()
+        .type_path()
+        .generics()
+        .split_for_impl();
+    let assertions = reflect_struct.fields().iter().map(|field| {
+        let member = ident_or_index(field.data.ident.as_ref(), field.declaration_index);
+        let ty = &field.data.ty;
+        quote! {
+            let _: &#ty = &remote.#member;
+        }
+    });
+
+    quote! {
+        #[allow(dead_code, clippy::extra_unused_type_parameters)]
+        fn assert_remote_fields #impl_generics (remote: &#remote_ty) #where_clause {
+            #(#assertions)*
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::derive_data::StructField;
 use crate::field_attributes::{DefaultBehavior, ReflectIgnoreBehavior};
+use crate::utility::get_bevy_reflect_path;
 use bevy_macro_utils::fq_std::{FQBox, FQDefault};
 use quote::quote;
 use std::collections::HashMap;
@@ -75,10 +76,16 @@ pub(crate) struct SkippedFieldDef {
 
 impl SkippedFieldDef {
     pub fn new(field: &StructField<'_>) -> Result<Self, syn::Error> {
-        let ty = &field.data.ty;
+        let ty = field.reflected_type();
 
-        let default_fn = match &field.attrs.default {
-            DefaultBehavior::Func(func) => quote! {
+        let default_fn = match (&field.attrs.default, &field.attrs.remote) {
+            (DefaultBehavior::Func(func), Some(wrapper)) => {
+                let bevy_reflect_path = get_bevy_reflect_path();
+                quote! {
+                  || { #FQBox::new(<#wrapper as #bevy_reflect_path::ReflectRemote>::into_wrapper(#func())) }
+                }
+            }
+            (DefaultBehavior::Func(func), None) => quote! {
               || { #FQBox::new(#func()) }
             },
             _ => quote! {
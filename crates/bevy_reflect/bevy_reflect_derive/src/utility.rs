@@ -7,7 +7,7 @@ use bevy_macro_utils::{
 };
 use proc_macro2::{Ident, Span};
 use quote::{quote, ToTokens};
-use syn::{spanned::Spanned, LitStr, Member, Path, Type, WhereClause};
+use syn::{spanned::Spanned, LitStr, Member, Path, PathArguments, Type, WhereClause};
 
 /// Returns the correct path for `bevy_reflect`.
 pub(crate) fn get_bevy_reflect_path() -> Path {
@@ -60,6 +60,18 @@ pub(crate) fn ident_or_index(ident: Option<&Ident>, index: usize) -> Member {
     )
 }
 
+/// Returns `path` with the turbofish syntax for its generic arguments, such as `foo::Bar::<T>`,
+/// so that it can be used in expressions.
+pub(crate) fn as_expr_path(path: &Path) -> Path {
+    let mut path = path.clone();
+    for segment in &mut path.segments {
+        if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+            arguments.colon2_token = Some(Default::default());
+        }
+    }
+    path
+}
+
 /// Options defining how to extend the `where` clause in reflection with any additional bounds needed.
 pub(crate) struct WhereClauseOptions {
     /// Type parameters that need extra trait bounds.
@@ -127,7 +139,7 @@ impl WhereClauseOptions {
 
         let (active_types, active_trait_bounds): (Vec<_>, Vec<_>) = active_fields
             .map(|field| {
-                let ty = field.data.ty.clone();
+                let ty = field.reflected_type().clone();
 
                 let custom_bounds = active_bounds(field).map(|bounds| quote!(+ #bounds));
 
@@ -0,0 +1,615 @@
+use crate::{Reflect, ReflectMut, ReflectRef};
+use thiserror::Error;
+
+/// The field-level differences between two reflected values, created with [`Reflect::diff`].
+///
+/// A patch only contains the parts of the value which changed, down to the first differing
+/// value of each field: applying it with [`Reflect::apply_patch`] to the original value
+/// turns it into the new one, without touching the other fields.
+/// This makes patches small enough to be sent as network deltas, or kept in an undo stack,
+/// with the patch computed in the other direction to revert it.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Patch, Reflect};
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Player {
+///     name: String,
+///     health: f32,
+///     inventory: Vec<String>,
+/// }
+///
+/// let before = Player {
+///     name: String::from("Ferris"),
+///     health: 100.0,
+///     inventory: vec![String::from("Sword")],
+/// };
+/// let mut after = before.clone();
+/// after.health = 75.0;
+/// after.inventory.push(String::from("Shield"));
+///
+/// let patch = before.diff(&after);
+/// let undo = after.diff(&before);
+/// let Patch::Struct(fields) = &patch else { unreachable!() };
+/// assert_eq!(2, fields.len());
+///
+/// let mut player = before.clone();
+/// player.apply_patch(&patch).unwrap();
+/// assert_eq!(after, player);
+///
+/// player.apply_patch(&undo).unwrap();
+/// assert_eq!(before, player);
+/// ```
+#[derive(Debug)]
+pub enum Patch {
+    /// The values are equal.
+    Unchanged,
+    /// The value is replaced.
+    ///
+    /// This is used for the values which can't be patched field by field, such as
+    /// [`ReflectRef::Value`]s, or when the variant of an enum changed.
+    Replace(Box<dyn Reflect>),
+    /// The changed fields of a struct, by name.
+    Struct(Vec<(String, Patch)>),
+    /// The changed fields of a tuple struct, by index.
+    TupleStruct(Vec<(usize, Patch)>),
+    /// The changed fields of a tuple, by index.
+    Tuple(Vec<(usize, Patch)>),
+    /// The changed elements of an array, by index.
+    Array(Vec<(usize, Patch)>),
+    /// The changes to a list.
+    List {
+        /// The changed elements present in both lists, by index.
+        changed: Vec<(usize, Patch)>,
+        /// The number of elements removed from the end of the list.
+        removed: usize,
+        /// The elements added to the end of the list, after the removed elements.
+        appended: Vec<Box<dyn Reflect>>,
+    },
+    /// The changes to a map.
+    Map {
+        /// The changed values present in both maps, by key.
+        changed: Vec<(Box<dyn Reflect>, Patch)>,
+        /// The keys removed from the map.
+        removed: Vec<Box<dyn Reflect>>,
+        /// The entries inserted in the map.
+        inserted: Vec<(Box<dyn Reflect>, Box<dyn Reflect>)>,
+    },
+    /// The changed fields of an enum whose variant didn't change, by index.
+    Enum {
+        /// The name of the variant.
+        variant: String,
+        /// The changed fields of the variant, by index.
+        fields: Vec<(usize, Patch)>,
+    },
+}
+
+impl Patch {
+    /// Returns `true` if the patch doesn't change anything.
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Patch::Unchanged)
+    }
+}
+
+impl Clone for Patch {
+    fn clone(&self) -> Self {
+        match self {
+            Patch::Unchanged => Patch::Unchanged,
+            Patch::Replace(value) => Patch::Replace(value.clone_value()),
+            Patch::Struct(fields) => Patch::Struct(fields.clone()),
+            Patch::TupleStruct(fields) => Patch::TupleStruct(fields.clone()),
+            Patch::Tuple(fields) => Patch::Tuple(fields.clone()),
+            Patch::Array(elements) => Patch::Array(elements.clone()),
+            Patch::List {
+                changed,
+                removed,
+                appended,
+            } => Patch::List {
+                changed: changed.clone(),
+                removed: *removed,
+                appended: appended.iter().map(|value| value.clone_value()).collect(),
+            },
+            Patch::Map {
+                changed,
+                removed,
+                inserted,
+            } => Patch::Map {
+                changed: changed
+                    .iter()
+                    .map(|(key, patch)| (key.clone_value(), patch.clone()))
+                    .collect(),
+                removed: removed.iter().map(|key| key.clone_value()).collect(),
+                inserted: inserted
+                    .iter()
+                    .map(|(key, value)| (key.clone_value(), value.clone_value()))
+                    .collect(),
+            },
+            Patch::Enum { variant, fields } => Patch::Enum {
+                variant: variant.clone(),
+                fields: fields.clone(),
+            },
+        }
+    }
+}
+
+/// An error applying a [`Patch`] to a value it wasn't created from.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PatchError {
+    /// The kind of the value doesn't match the kind of the patch.
+    #[error("expected a {expected} but found a {received}")]
+    MismatchedKinds {
+        expected: &'static str,
+        received: &'static str,
+    },
+    /// The value can't be replaced by a value of another type.
+    #[error("expected a value of type `{expected}` but found `{received}`")]
+    MismatchedTypes { expected: String, received: String },
+    /// The variant of the enum doesn't match the variant of the patch.
+    #[error("expected the variant `{expected}` but found `{received}`")]
+    MismatchedVariants { expected: String, received: String },
+    /// The struct doesn't have a field of the patch.
+    #[error("missing field `{0}`")]
+    MissingField(String),
+    /// The value doesn't have an element or field of the patch.
+    #[error("missing element at index {0}")]
+    MissingIndex(usize),
+    /// The map doesn't have a key of the patch.
+    #[error("missing key `{0}`")]
+    MissingKey(String),
+}
+
+/// Returns the name of the kind of `value`, for errors.
+fn kind_name(value: ReflectRef) -> &'static str {
+    match value {
+        ReflectRef::Struct(_) => "struct",
+        ReflectRef::TupleStruct(_) => "tuple struct",
+        ReflectRef::Tuple(_) => "tuple",
+        ReflectRef::List(_) => "list",
+        ReflectRef::Array(_) => "array",
+        ReflectRef::Map(_) => "map",
+        ReflectRef::Enum(_) => "enum",
+        ReflectRef::Value(_) => "value",
+    }
+}
+
+/// Collects the patches of the changed fields.
+fn changed_fields<K>(fields: impl Iterator<Item = (K, Patch)>) -> Vec<(K, Patch)> {
+    fields.filter(|(_, patch)| !patch.is_unchanged()).collect()
+}
+
+/// Returns the [`Patch`] turning `from` into `to`.
+pub(crate) fn diff(from: &dyn Reflect, to: &dyn Reflect) -> Patch {
+    let replace = || Patch::Replace(to.clone_value());
+    match (from.reflect_ref(), to.reflect_ref()) {
+        (ReflectRef::Struct(from), ReflectRef::Struct(to)) => {
+            if from.field_len() != to.field_len() {
+                return replace();
+            }
+            let mut fields = Vec::new();
+            for (index, value) in from.iter_fields().enumerate() {
+                let name = from.name_at(index).unwrap();
+                let Some(other) = to.field(name) else {
+                    return replace();
+                };
+                fields.push((name.to_string(), diff(value, other)));
+            }
+            let fields = changed_fields(fields.into_iter());
+            if fields.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::Struct(fields)
+            }
+        }
+        (ReflectRef::TupleStruct(from), ReflectRef::TupleStruct(to)) => {
+            if from.field_len() != to.field_len() {
+                return replace();
+            }
+            let fields = changed_fields(
+                from.iter_fields()
+                    .zip(to.iter_fields())
+                    .map(diff_pair)
+                    .enumerate(),
+            );
+            if fields.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::TupleStruct(fields)
+            }
+        }
+        (ReflectRef::Tuple(from), ReflectRef::Tuple(to)) => {
+            if from.field_len() != to.field_len() {
+                return replace();
+            }
+            let fields = changed_fields(
+                from.iter_fields()
+                    .zip(to.iter_fields())
+                    .map(diff_pair)
+                    .enumerate(),
+            );
+            if fields.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::Tuple(fields)
+            }
+        }
+        (ReflectRef::Array(from), ReflectRef::Array(to)) => {
+            if from.len() != to.len() {
+                return replace();
+            }
+            let elements = changed_fields(from.iter().zip(to.iter()).map(diff_pair).enumerate());
+            if elements.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::Array(elements)
+            }
+        }
+        (ReflectRef::List(from), ReflectRef::List(to)) => {
+            let changed = changed_fields(from.iter().zip(to.iter()).map(diff_pair).enumerate());
+            let removed = from.len().saturating_sub(to.len());
+            let appended: Vec<_> = to
+                .iter()
+                .skip(from.len())
+                .map(|value| value.clone_value())
+                .collect();
+            if changed.is_empty() && removed == 0 && appended.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::List {
+                    changed,
+                    removed,
+                    appended,
+                }
+            }
+        }
+        (ReflectRef::Map(from), ReflectRef::Map(to)) => {
+            let mut changed = Vec::new();
+            let mut removed = Vec::new();
+            for (key, value) in from.iter() {
+                match to.get(key) {
+                    Some(other) => changed.push((key.clone_value(), diff(value, other))),
+                    None => removed.push(key.clone_value()),
+                }
+            }
+            let changed = changed_fields(changed.into_iter());
+            let inserted: Vec<_> = to
+                .iter()
+                .filter(|(key, _)| from.get(*key).is_none())
+                .map(|(key, value)| (key.clone_value(), value.clone_value()))
+                .collect();
+            if changed.is_empty() && removed.is_empty() && inserted.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::Map {
+                    changed,
+                    removed,
+                    inserted,
+                }
+            }
+        }
+        (ReflectRef::Enum(from), ReflectRef::Enum(to)) => {
+            if from.variant_name() != to.variant_name() || from.field_len() != to.field_len() {
+                return replace();
+            }
+            let fields = changed_fields(
+                (0..from.field_len())
+                    .map(|index| diff(from.field_at(index).unwrap(), to.field_at(index).unwrap()))
+                    .enumerate(),
+            );
+            if fields.is_empty() {
+                Patch::Unchanged
+            } else {
+                Patch::Enum {
+                    variant: from.variant_name().to_string(),
+                    fields,
+                }
+            }
+        }
+        (ReflectRef::Value(from), ReflectRef::Value(to)) => {
+            if from.reflect_partial_eq(to) == Some(true) {
+                Patch::Unchanged
+            } else {
+                replace()
+            }
+        }
+        _ => replace(),
+    }
+}
+
+fn diff_pair((from, to): (&dyn Reflect, &dyn Reflect)) -> Patch {
+    diff(from, to)
+}
+
+/// Applies `patch` to `target`.
+pub(crate) fn apply_patch(target: &mut dyn Reflect, patch: &Patch) -> Result<(), PatchError> {
+    let mismatched_kinds = |expected, target: &dyn Reflect| PatchError::MismatchedKinds {
+        expected,
+        received: kind_name(target.reflect_ref()),
+    };
+    match patch {
+        Patch::Unchanged => {}
+        Patch::Replace(value) => replace(target, &**value)?,
+        Patch::Struct(fields) => {
+            let ReflectMut::Struct(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("struct", target));
+            };
+            for (name, patch) in fields {
+                let field = target
+                    .field_mut(name)
+                    .ok_or_else(|| PatchError::MissingField(name.clone()))?;
+                apply_patch(field, patch)?;
+            }
+        }
+        Patch::TupleStruct(fields) => {
+            let ReflectMut::TupleStruct(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("tuple struct", target));
+            };
+            for (index, patch) in fields {
+                let field = target
+                    .field_mut(*index)
+                    .ok_or(PatchError::MissingIndex(*index))?;
+                apply_patch(field, patch)?;
+            }
+        }
+        Patch::Tuple(fields) => {
+            let ReflectMut::Tuple(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("tuple", target));
+            };
+            for (index, patch) in fields {
+                let field = target
+                    .field_mut(*index)
+                    .ok_or(PatchError::MissingIndex(*index))?;
+                apply_patch(field, patch)?;
+            }
+        }
+        Patch::Array(elements) => {
+            let ReflectMut::Array(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("array", target));
+            };
+            for (index, patch) in elements {
+                let element = target
+                    .get_mut(*index)
+                    .ok_or(PatchError::MissingIndex(*index))?;
+                apply_patch(element, patch)?;
+            }
+        }
+        Patch::List {
+            changed,
+            removed,
+            appended,
+        } => {
+            let ReflectMut::List(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("list", target));
+            };
+            for (index, patch) in changed {
+                let element = target
+                    .get_mut(*index)
+                    .ok_or(PatchError::MissingIndex(*index))?;
+                apply_patch(element, patch)?;
+            }
+            for _ in 0..*removed {
+                target.pop();
+            }
+            for value in appended {
+                target.push(value.clone_value());
+            }
+        }
+        Patch::Map {
+            changed,
+            removed,
+            inserted,
+        } => {
+            let ReflectMut::Map(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("map", target));
+            };
+            for (key, patch) in changed {
+                let value = target
+                    .get_mut(&**key)
+                    .ok_or_else(|| PatchError::MissingKey(format!("{key:?}")))?;
+                apply_patch(value, patch)?;
+            }
+            for key in removed {
+                target.remove(&**key);
+            }
+            for (key, value) in inserted {
+                target.insert_boxed(key.clone_value(), value.clone_value());
+            }
+        }
+        Patch::Enum { variant, fields } => {
+            let ReflectMut::Enum(target) = target.reflect_mut() else {
+                return Err(mismatched_kinds("enum", target));
+            };
+            if target.variant_name() != variant {
+                return Err(PatchError::MismatchedVariants {
+                    expected: variant.clone(),
+                    received: target.variant_name().to_string(),
+                });
+            }
+            for (index, patch) in fields {
+                let field = target
+                    .field_at_mut(*index)
+                    .ok_or(PatchError::MissingIndex(*index))?;
+                apply_patch(field, patch)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces `target` with `value`, which must be of the same kind.
+fn replace(target: &mut dyn Reflect, value: &dyn Reflect) -> Result<(), PatchError> {
+    let expected = kind_name(value.reflect_ref());
+    let received = kind_name(target.reflect_ref());
+    if expected != received {
+        return Err(PatchError::MismatchedKinds { expected, received });
+    }
+    let Err(value) = target.set(value.clone_value()) else {
+        return Ok(());
+    };
+    match value.reflect_ref() {
+        // Values are only applied to values of the same type
+        ReflectRef::Value(_) => Err(PatchError::MismatchedTypes {
+            expected: value.reflect_type_path().to_string(),
+            received: target.reflect_type_path().to_string(),
+        }),
+        _ => {
+            target.apply(&*value);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: f32 },
+        Square(f32),
+        Point,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Transform {
+        position: (f32, f32),
+        scale: f32,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Entity {
+        name: String,
+        transform: Transform,
+        shape: Shape,
+        tags: Vec<String>,
+        stats: HashMap<String, u32>,
+    }
+
+    fn entity() -> Entity {
+        Entity {
+            name: String::from("Ferris"),
+            transform: Transform {
+                position: (1.0, 2.0),
+                scale: 1.0,
+            },
+            shape: Shape::Circle { radius: 1.0 },
+            tags: vec![String::from("crab"), String::from("rust")],
+            stats: HashMap::from_iter([(String::from("hp"), 10), (String::from("mp"), 5)]),
+        }
+    }
+
+    fn assert_round_trip(from: &Entity, to: &Entity) {
+        let mut value = from.clone();
+        value.apply_patch(&from.diff(to)).unwrap();
+        assert_eq!(to, &value);
+
+        value.apply_patch(&to.diff(from)).unwrap();
+        assert_eq!(from, &value);
+    }
+
+    #[test]
+    fn should_diff_nested_fields() {
+        let from = entity();
+        assert!(from.diff(&from).is_unchanged());
+
+        let mut to = from.clone();
+        to.transform.position.1 = 3.0;
+        let Patch::Struct(fields) = from.diff(&to) else {
+            panic!("expected a struct patch");
+        };
+        assert_eq!(1, fields.len());
+        assert_eq!("transform", fields[0].0);
+        let Patch::Struct(transform) = &fields[0].1 else {
+            panic!("expected a struct patch");
+        };
+        assert_eq!(1, transform.len());
+        assert!(matches!(&transform[0].1, Patch::Tuple(fields) if fields[0].0 == 1));
+
+        assert_round_trip(&from, &to);
+    }
+
+    #[test]
+    fn should_diff_lists_and_maps() {
+        let from = entity();
+        let mut to = from.clone();
+        to.tags = vec![String::from("ferris")];
+        to.stats.remove("mp");
+        to.stats.insert(String::from("hp"), 8);
+        to.stats.insert(String::from("xp"), 1);
+
+        let Patch::Struct(fields) = from.diff(&to) else {
+            panic!("expected a struct patch");
+        };
+        let Patch::List {
+            changed,
+            removed,
+            appended,
+        } = &fields[0].1
+        else {
+            panic!("expected a list patch");
+        };
+        assert_eq!((1, 1, 0), (changed.len(), *removed, appended.len()));
+        let Patch::Map {
+            changed,
+            removed,
+            inserted,
+        } = &fields[1].1
+        else {
+            panic!("expected a map patch");
+        };
+        assert_eq!((1, 1, 1), (changed.len(), removed.len(), inserted.len()));
+
+        assert_round_trip(&from, &to);
+    }
+
+    #[test]
+    fn should_diff_enums() {
+        let from = entity();
+        let mut to = from.clone();
+        to.shape = Shape::Circle { radius: 2.0 };
+        let Patch::Struct(fields) = from.diff(&to) else {
+            panic!("expected a struct patch");
+        };
+        assert!(matches!(&fields[0].1, Patch::Enum { variant, .. } if variant == "Circle"));
+        assert_round_trip(&from, &to);
+
+        for shape in [Shape::Square(1.0), Shape::Point] {
+            to.shape = shape;
+            let Patch::Struct(fields) = from.diff(&to) else {
+                panic!("expected a struct patch");
+            };
+            assert!(matches!(&fields[0].1, Patch::Replace(_)));
+            assert_round_trip(&from, &to);
+        }
+    }
+
+    #[test]
+    fn should_reject_mismatched_patches() {
+        let patch = 1_u32.diff(&2_u32);
+        assert_eq!(
+            Err(PatchError::MismatchedTypes {
+                expected: String::from("u32"),
+                received: String::from("f32"),
+            }),
+            1.0_f32.apply_patch(&patch)
+        );
+
+        let patch = Shape::Square(1.0).diff(&Shape::Square(2.0));
+        assert_eq!(
+            Err(PatchError::MismatchedVariants {
+                expected: String::from("Square"),
+                received: String::from("Point"),
+            }),
+            Shape::Point.apply_patch(&patch)
+        );
+        assert_eq!(
+            Err(PatchError::MismatchedKinds {
+                expected: "enum",
+                received: "value",
+            }),
+            1_u32.apply_patch(&patch)
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{FromReflect, Reflect, TypePath};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+/// How an argument is passed to a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// The argument is passed by value.
+    Owned,
+    /// The argument is passed by shared reference.
+    Ref,
+    /// The argument is passed by mutable reference.
+    Mut,
+}
+
+impl Display for Ownership {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ownership::Owned => f.write_str("by value"),
+            Ownership::Ref => f.write_str("by reference"),
+            Ownership::Mut => f.write_str("by mutable reference"),
+        }
+    }
+}
+
+/// An argument passed to a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Debug)]
+pub enum Arg<'a> {
+    /// An argument passed by value.
+    Owned(Box<dyn Reflect>),
+    /// An argument passed by shared reference.
+    Ref(&'a dyn Reflect),
+    /// An argument passed by mutable reference.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Arg<'a> {
+    /// Returns how this argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        match self {
+            Arg::Owned(_) => Ownership::Owned,
+            Arg::Ref(_) => Ownership::Ref,
+            Arg::Mut(_) => Ownership::Mut,
+        }
+    }
+
+    /// Returns the value of this argument.
+    pub fn value(&self) -> &dyn Reflect {
+        match self {
+            Arg::Owned(value) => value.as_reflect(),
+            Arg::Ref(value) => *value,
+            Arg::Mut(value) => value.as_reflect(),
+        }
+    }
+}
+
+/// The ordered list of arguments passed to a [`DynamicFunction`](crate::func::DynamicFunction).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::{ArgList, Ownership};
+/// let mut value = 123_i32;
+/// let args = ArgList::new()
+///     .push_owned(String::from("Hello"))
+///     .push_ref(&1.5_f32)
+///     .push_mut(&mut value);
+///
+/// assert_eq!(3, args.len());
+/// assert_eq!(Ownership::Mut, args.iter().last().unwrap().ownership());
+/// ```
+#[derive(Debug, Default)]
+pub struct ArgList<'a>(Vec<Arg<'a>>);
+
+impl<'a> ArgList<'a> {
+    /// Creates an empty list of arguments.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends `arg` to the list.
+    pub fn push_arg(mut self, arg: Arg<'a>) -> Self {
+        self.0.push(arg);
+        self
+    }
+
+    /// Appends an argument passed by value.
+    pub fn push_owned(self, value: impl Reflect) -> Self {
+        self.push_arg(Arg::Owned(Box::new(value)))
+    }
+
+    /// Appends an argument passed by value, from a boxed value.
+    pub fn push_boxed(self, value: Box<dyn Reflect>) -> Self {
+        self.push_arg(Arg::Owned(value))
+    }
+
+    /// Appends an argument passed by shared reference.
+    pub fn push_ref(self, value: &'a dyn Reflect) -> Self {
+        self.push_arg(Arg::Ref(value))
+    }
+
+    /// Appends an argument passed by mutable reference.
+    pub fn push_mut(self, value: &'a mut dyn Reflect) -> Self {
+        self.push_arg(Arg::Mut(value))
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list contains no argument.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the arguments.
+    pub fn iter(&self) -> impl Iterator<Item = &Arg<'a>> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for ArgList<'a> {
+    type Item = Arg<'a>;
+    type IntoIter = std::vec::IntoIter<Arg<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Information about an argument of a function.
+#[derive(Debug, Clone)]
+pub struct ArgInfo {
+    index: usize,
+    name: Option<Cow<'static, str>>,
+    ownership: Ownership,
+    type_path: &'static str,
+    type_id: TypeId,
+}
+
+impl ArgInfo {
+    /// Creates the information of the argument at `index`, of type `T` passed as `ownership`.
+    pub fn new<T: TypePath + ?Sized + 'static>(index: usize, ownership: Ownership) -> Self {
+        Self {
+            index,
+            name: None,
+            ownership,
+            type_path: T::type_path(),
+            type_id: TypeId::of::<T>(),
+        }
+    }
+
+    /// Sets the name of the argument.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The index of the argument in the list of arguments of the function.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the argument, if it was given one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// How the argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [type path] of the argument, without its reference.
+    ///
+    /// [type path]: TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_path
+    }
+
+    /// The [`TypeId`] of the argument, without its reference.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+}
+
+/// An error converting an [`Arg`] to the type of a parameter.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ArgError {
+    /// The argument has the wrong type.
+    #[error("expected an argument of type `{expected}` but received `{received}`")]
+    UnexpectedType {
+        expected: Cow<'static, str>,
+        received: Cow<'static, str>,
+    },
+    /// The argument is passed the wrong way.
+    #[error("expected the argument to be passed {expected} but it was passed {received}")]
+    InvalidOwnership {
+        expected: Ownership,
+        received: Ownership,
+    },
+}
+
+/// Marker for the parameters taken by value, see [`FromArg`].
+pub struct OwnedArg;
+
+/// Marker for the parameters taken by shared reference, see [`FromArg`].
+pub struct RefArg;
+
+/// Marker for the parameters taken by mutable reference, see [`FromArg`].
+pub struct MutArg;
+
+/// A type which can be the parameter of a reflected function.
+///
+/// This is implemented for:
+/// * the types implementing [`FromReflect`], taken by value,
+/// * shared and mutable references to the types implementing [`Reflect`].
+///
+/// The `Marker` distinguishes these implementations, and is one of [`OwnedArg`], [`RefArg`]
+/// and [`MutArg`].
+pub trait FromArg<Marker> {
+    /// This type, with the lifetime of the argument.
+    type This<'a>;
+
+    /// Converts `arg` to this type.
+    ///
+    /// A value passed by value which isn't of this type is converted with [`FromReflect`],
+    /// so that dynamic types can be passed as well.
+    fn from_arg(arg: Arg<'_>) -> Result<Self::This<'_>, ArgError>;
+
+    /// Returns the information of a parameter of this type at `index`.
+    fn arg_info(index: usize) -> ArgInfo;
+}
+
+impl<T: FromReflect + TypePath> FromArg<OwnedArg> for T {
+    type This<'a> = T;
+
+    fn from_arg(arg: Arg<'_>) -> Result<Self::This<'_>, ArgError> {
+        let Arg::Owned(value) = arg else {
+            return Err(ArgError::InvalidOwnership {
+                expected: Ownership::Owned,
+                received: arg.ownership(),
+            });
+        };
+        value.take::<T>().or_else(|value| {
+            T::from_reflect(&*value).ok_or_else(|| ArgError::UnexpectedType {
+                expected: Cow::Borrowed(T::type_path()),
+                received: Cow::Owned(value.reflect_type_path().to_string()),
+            })
+        })
+    }
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Owned)
+    }
+}
+
+impl<T: Reflect + TypePath> FromArg<RefArg> for &T {
+    type This<'a> = &'a T;
+
+    fn from_arg(arg: Arg<'_>) -> Result<Self::This<'_>, ArgError> {
+        let value = match arg {
+            Arg::Ref(value) => value,
+            Arg::Mut(value) => value,
+            Arg::Owned(_) => {
+                return Err(ArgError::InvalidOwnership {
+                    expected: Ownership::Ref,
+                    received: Ownership::Owned,
+                })
+            }
+        };
+        value
+            .downcast_ref::<T>()
+            .ok_or_else(|| ArgError::UnexpectedType {
+                expected: Cow::Borrowed(T::type_path()),
+                received: Cow::Owned(value.reflect_type_path().to_string()),
+            })
+    }
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Ref)
+    }
+}
+
+impl<T: Reflect + TypePath> FromArg<MutArg> for &mut T {
+    type This<'a> = &'a mut T;
+
+    fn from_arg(arg: Arg<'_>) -> Result<Self::This<'_>, ArgError> {
+        let Arg::Mut(value) = arg else {
+            return Err(ArgError::InvalidOwnership {
+                expected: Ownership::Mut,
+                received: arg.ownership(),
+            });
+        };
+        if !value.is::<T>() {
+            return Err(ArgError::UnexpectedType {
+                expected: Cow::Borrowed(T::type_path()),
+                received: Cow::Owned(value.reflect_type_path().to_string()),
+            });
+        }
+        Ok(value.downcast_mut::<T>().unwrap())
+    }
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Mut)
+    }
+}
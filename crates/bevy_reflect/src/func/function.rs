@@ -0,0 +1,278 @@
+use crate::func::args::{ArgError, ArgInfo, ArgList};
+use crate::{Reflect, TypePath};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// The result of calling a [`DynamicFunction`]: the value it returned or the reason it couldn't
+/// be called.
+pub type FunctionResult = Result<Box<dyn Reflect>, FunctionError>;
+
+/// An error calling a [`DynamicFunction`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FunctionError {
+    /// The function received the wrong number of arguments.
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCount { expected: usize, received: usize },
+    /// An argument couldn't be converted to the type of its parameter.
+    #[error("invalid argument at index {index}: {error}")]
+    Arg { index: usize, error: ArgError },
+    /// No function is registered under the name.
+    #[error("no function is registered under the name `{0}`")]
+    NotFound(Cow<'static, str>),
+}
+
+/// Information about the value returned by a function.
+#[derive(Debug, Clone)]
+pub struct ReturnInfo {
+    type_path: &'static str,
+    type_id: TypeId,
+}
+
+impl ReturnInfo {
+    /// Creates the information of a returned value of type `T`.
+    pub fn new<T: TypePath + ?Sized + 'static>() -> Self {
+        Self {
+            type_path: T::type_path(),
+            type_id: TypeId::of::<T>(),
+        }
+    }
+
+    /// The [type path] of the returned value.
+    ///
+    /// [type path]: TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_path
+    }
+
+    /// The [`TypeId`] of the returned value.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+}
+
+/// Information about a function: its name and signature.
+#[derive(Debug, Clone)]
+pub struct FunctionInfo {
+    name: Cow<'static, str>,
+    args: Vec<ArgInfo>,
+    return_info: ReturnInfo,
+}
+
+impl FunctionInfo {
+    /// Creates the information of a function.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        args: Vec<ArgInfo>,
+        return_info: ReturnInfo,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            args,
+            return_info,
+        }
+    }
+
+    /// The name of the function.
+    ///
+    /// This defaults to the [type name] of the function, such as `my_crate::foo::bar`.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The parameters of the function, in order.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The value returned by the function.
+    pub fn return_info(&self) -> &ReturnInfo {
+        &self.return_info
+    }
+}
+
+/// A function which can be called with reflected arguments.
+///
+/// It's usually created from a Rust function or closure with [`IntoFunction::into_function`],
+/// and can be stored in a [`FunctionRegistry`](crate::func::FunctionRegistry).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::{ArgList, IntoFunction};
+/// fn add(a: i32, b: &i32) -> i32 {
+///     a + *b
+/// }
+///
+/// let function = add.into_function();
+/// let args = ArgList::new().push_owned(25_i32).push_ref(&75_i32);
+/// let value = function.call(args).unwrap();
+/// assert_eq!(Some(&100), value.downcast_ref::<i32>());
+/// ```
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    #[allow(clippy::type_complexity)]
+    func: Arc<dyn for<'a> Fn(ArgList<'a>) -> FunctionResult + Send + Sync>,
+}
+
+impl DynamicFunction {
+    /// Creates a function from the closure `func` receiving the arguments, with the given information.
+    ///
+    /// The number of arguments is checked against `info` before calling `func`.
+    pub fn new(
+        func: impl for<'a> Fn(ArgList<'a>) -> FunctionResult + Send + Sync + 'static,
+        info: FunctionInfo,
+    ) -> Self {
+        Self {
+            info,
+            func: Arc::new(func),
+        }
+    }
+
+    /// Sets the name of the function.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.info.name = name.into();
+        self
+    }
+
+    /// Sets the names of the parameters of the function, in order.
+    pub fn with_arg_names<N: Into<Cow<'static, str>>>(
+        mut self,
+        names: impl IntoIterator<Item = N>,
+    ) -> Self {
+        for (arg, name) in self.info.args.iter_mut().zip(names) {
+            *arg = arg.clone().with_name(name);
+        }
+        self
+    }
+
+    /// Calls the function with `args`.
+    pub fn call(&self, args: ArgList) -> FunctionResult {
+        let expected = self.info.args.len();
+        if args.len() != expected {
+            return Err(FunctionError::ArgCount {
+                expected,
+                received: args.len(),
+            });
+        }
+        (self.func)(args)
+    }
+
+    /// Returns the information of the function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        self.info.name()
+    }
+}
+
+impl Debug for DynamicFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A Rust function or closure which can be converted into a [`DynamicFunction`].
+///
+/// This is implemented for functions and closures of up to 8 parameters, implementing [`Fn`]
+/// and living for `'static`, whose parameters implement [`FromArg`](crate::func::FromArg):
+/// values implementing [`FromReflect`](crate::FromReflect), and references to values implementing
+/// [`Reflect`].
+/// Their return type must implement [`Reflect`], and is returned by value.
+///
+/// Methods are converted in the same way, with their receiver as their first parameter, such as
+/// `Player::health.into_function()`.
+///
+/// The `Marker` is only used to tell the implementations apart.
+pub trait IntoFunction<Marker> {
+    /// Converts this function into a [`DynamicFunction`].
+    fn into_function(self) -> DynamicFunction;
+}
+
+macro_rules! impl_into_function {
+    ($(($arg:ident, $marker:ident, $index:tt)),*) => {
+        impl<F, R, $($arg, $marker,)*> IntoFunction<(fn($($arg,)*) -> R, ($($marker,)*))> for F
+        where
+            F: Fn($($arg),*) -> R + Send + Sync + 'static,
+            F: for<'a> Fn($(<$arg as $crate::func::FromArg<$marker>>::This<'a>),*) -> R,
+            R: Reflect + TypePath,
+            $($arg: $crate::func::FromArg<$marker>,)*
+        {
+            #[allow(unused_variables, unused_mut)]
+            fn into_function(self) -> DynamicFunction {
+                let info = FunctionInfo::new(
+                    std::any::type_name::<F>(),
+                    vec![$(<$arg as $crate::func::FromArg<$marker>>::arg_info($index),)*],
+                    ReturnInfo::new::<R>(),
+                );
+                DynamicFunction::new(
+                    move |args: ArgList| {
+                        let mut args = args.into_iter();
+                        let value = (self)($(
+                            <$arg as $crate::func::FromArg<$marker>>::from_arg(args.next().unwrap())
+                                .map_err(|error| FunctionError::Arg { index: $index, error })?,
+                        )*);
+                        Ok(Box::new(value) as Box<dyn Reflect>)
+                    },
+                    info,
+                )
+            }
+        }
+    };
+}
+
+impl_into_function!();
+impl_into_function!((A0, M0, 0));
+impl_into_function!((A0, M0, 0), (A1, M1, 1));
+impl_into_function!((A0, M0, 0), (A1, M1, 1), (A2, M2, 2));
+impl_into_function!((A0, M0, 0), (A1, M1, 1), (A2, M2, 2), (A3, M3, 3));
+impl_into_function!(
+    (A0, M0, 0),
+    (A1, M1, 1),
+    (A2, M2, 2),
+    (A3, M3, 3),
+    (A4, M4, 4)
+);
+impl_into_function!(
+    (A0, M0, 0),
+    (A1, M1, 1),
+    (A2, M2, 2),
+    (A3, M3, 3),
+    (A4, M4, 4),
+    (A5, M5, 5)
+);
+impl_into_function!(
+    (A0, M0, 0),
+    (A1, M1, 1),
+    (A2, M2, 2),
+    (A3, M3, 3),
+    (A4, M4, 4),
+    (A5, M5, 5),
+    (A6, M6, 6)
+);
+impl_into_function!(
+    (A0, M0, 0),
+    (A1, M1, 1),
+    (A2, M2, 2),
+    (A3, M3, 3),
+    (A4, M4, 4),
+    (A5, M5, 5),
+    (A6, M6, 6),
+    (A7, M7, 7)
+);
+
+impl IntoFunction<()> for DynamicFunction {
+    fn into_function(self) -> DynamicFunction {
+        self
+    }
+}
@@ -0,0 +1,220 @@
+//! Reflection of functions and methods.
+//!
+//! Rust functions, closures and methods can be converted into [`DynamicFunction`]s with
+//! [`IntoFunction`], which are then called with a list of reflected arguments, an [`ArgList`],
+//! and return their value as a `Box<dyn Reflect>`.
+//! The name, parameters and return type of a function are described by its [`FunctionInfo`].
+//!
+//! Functions can be stored by name in a [`FunctionRegistry`], so that scripting languages
+//! and editors can find and call them.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_reflect::func::{ArgList, IntoFunction};
+//! # use bevy_reflect::Reflect;
+//! #[derive(Reflect)]
+//! struct Counter(u32);
+//!
+//! fn increment(counter: &mut Counter, by: u32) -> u32 {
+//!     counter.0 += by;
+//!     counter.0
+//! }
+//!
+//! let function = increment.into_function();
+//! assert_eq!(2, function.info().args().len());
+//!
+//! let mut counter = Counter(1);
+//! let args = ArgList::new().push_mut(&mut counter).push_owned(2_u32);
+//! let value = function.call(args).unwrap();
+//! assert_eq!(Some(&3), value.downcast_ref::<u32>());
+//! ```
+//!
+//! # Limitations
+//!
+//! Functions are called through [`Fn`], so closures mutating their captured state aren't
+//! supported, and values are always returned by value: functions returning references can't
+//! be converted.
+
+mod args;
+mod function;
+mod registry;
+
+pub use args::*;
+pub use function::*;
+pub use registry::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{DynamicStruct, Reflect, TypePath};
+    use std::any::TypeId;
+
+    #[derive(Reflect, Debug, PartialEq, Default)]
+    struct Player {
+        name: String,
+        health: f32,
+    }
+
+    impl Player {
+        fn heal(&mut self, amount: f32) -> f32 {
+            self.health += amount;
+            self.health
+        }
+
+        fn is_alive(&self) -> bool {
+            self.health > 0.0
+        }
+    }
+
+    #[test]
+    fn should_call_functions() {
+        fn greet(player: &Player, greeting: String) -> String {
+            format!("{greeting}, {}!", player.name)
+        }
+
+        let player = Player {
+            name: String::from("Ferris"),
+            health: 10.0,
+        };
+        let function = greet.into_function();
+        let args = ArgList::new()
+            .push_ref(&player)
+            .push_owned(String::from("Hello"));
+        let value = function.call(args).unwrap();
+        assert_eq!("Hello, Ferris!", value.downcast_ref::<String>().unwrap());
+
+        let offset = 5;
+        let function = (move |a: i32| a + offset).into_function();
+        let value = function.call(ArgList::new().push_owned(1_i32)).unwrap();
+        assert_eq!(Some(&6), value.downcast_ref::<i32>());
+
+        let function = (|| ()).into_function();
+        assert!(function.call(ArgList::new()).unwrap().is::<()>());
+    }
+
+    #[test]
+    fn should_call_methods() {
+        let mut player = Player {
+            name: String::from("Ferris"),
+            health: 10.0,
+        };
+
+        let heal = Player::heal.into_function();
+        let args = ArgList::new().push_mut(&mut player).push_owned(5.0_f32);
+        let value = heal.call(args).unwrap();
+        assert_eq!(Some(&15.0), value.downcast_ref::<f32>());
+        assert_eq!(15.0, player.health);
+
+        let is_alive = Player::is_alive.into_function();
+        let value = is_alive.call(ArgList::new().push_ref(&player)).unwrap();
+        assert_eq!(Some(&true), value.downcast_ref::<bool>());
+    }
+
+    #[test]
+    fn should_describe_functions() {
+        let function = Player::heal
+            .into_function()
+            .with_arg_names(["self", "amount"]);
+        let info = function.info();
+        assert!(info.name().ends_with("Player::heal"));
+
+        let args = info.args();
+        assert_eq!(2, args.len());
+        assert_eq!(Ownership::Mut, args[0].ownership());
+        assert_eq!(Player::type_path(), args[0].type_path());
+        assert_eq!(TypeId::of::<Player>(), args[0].type_id());
+        assert_eq!(Some("amount"), args[1].name());
+        assert_eq!(Ownership::Owned, args[1].ownership());
+        assert_eq!(TypeId::of::<f32>(), info.return_info().type_id());
+    }
+
+    #[test]
+    fn should_convert_dynamic_arguments() {
+        fn health(player: Player) -> f32 {
+            player.health
+        }
+
+        let mut dynamic = DynamicStruct::default();
+        dynamic.insert("name", String::from("Ferris"));
+        dynamic.insert("health", 3.0_f32);
+        let value = health
+            .into_function()
+            .call(ArgList::new().push_owned(dynamic))
+            .unwrap();
+        assert_eq!(Some(&3.0), value.downcast_ref::<f32>());
+    }
+
+    #[test]
+    fn should_reject_invalid_arguments() {
+        let heal = Player::heal.into_function();
+        let mut player = Player::default();
+
+        assert_eq!(
+            FunctionError::ArgCount {
+                expected: 2,
+                received: 1
+            },
+            heal.call(ArgList::new().push_mut(&mut player)).unwrap_err()
+        );
+        assert_eq!(
+            FunctionError::Arg {
+                index: 0,
+                error: ArgError::InvalidOwnership {
+                    expected: Ownership::Mut,
+                    received: Ownership::Ref,
+                },
+            },
+            heal.call(ArgList::new().push_ref(&player).push_owned(1.0_f32))
+                .unwrap_err()
+        );
+        assert_eq!(
+            FunctionError::Arg {
+                index: 1,
+                error: ArgError::UnexpectedType {
+                    expected: f32::type_path().into(),
+                    received: String::type_path().into(),
+                },
+            },
+            heal.call(
+                ArgList::new()
+                    .push_mut(&mut player)
+                    .push_owned(String::from("1.0"))
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn should_register_functions() {
+        fn damage(player: &mut Player, amount: f32) {
+            player.health -= amount;
+        }
+
+        let mut registry = FunctionRegistry::default();
+        registry
+            .register("damage", damage)
+            .register_method::<Player, _>("heal", Player::heal)
+            .register_method::<Player, _>("is_alive", Player::is_alive);
+        assert_eq!(3, registry.len());
+        assert_eq!(2, registry.iter_methods::<Player>().count());
+
+        let heal = format!("{}::heal", Player::type_path());
+        assert_eq!(heal, registry.get(&heal).unwrap().name());
+
+        let mut player = Player::default();
+        registry
+            .call(
+                "damage",
+                ArgList::new().push_mut(&mut player).push_owned(2.0_f32),
+            )
+            .unwrap();
+        assert_eq!(-2.0, player.health);
+
+        assert_eq!(
+            FunctionError::NotFound("kill".into()),
+            registry.call("kill", ArgList::new()).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::func::args::ArgList;
+use crate::func::function::{DynamicFunction, FunctionError, FunctionResult, IntoFunction};
+use crate::TypePath;
+use bevy_utils::HashMap;
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A registry of [reflected functions], by name.
+///
+/// Functions are registered under the name given to them, and methods under the
+/// [type path] of their type followed by their name, such as `my_crate::Player::heal`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::{ArgList, FunctionRegistry};
+/// # use bevy_reflect::{Reflect, TypePath};
+/// #[derive(Reflect)]
+/// struct Player {
+///     health: f32,
+/// }
+///
+/// impl Player {
+///     fn heal(&mut self, amount: f32) {
+///         self.health += amount;
+///     }
+/// }
+///
+/// let mut registry = FunctionRegistry::default();
+/// registry.register_method::<Player, _>("heal", Player::heal);
+///
+/// let mut player = Player { health: 50.0 };
+/// let name = format!("{}::heal", Player::type_path());
+/// let args = ArgList::new().push_mut(&mut player).push_owned(25.0_f32);
+/// registry.call(&name, args).unwrap();
+/// assert_eq!(75.0, player.health);
+/// ```
+///
+/// [reflected functions]: DynamicFunction
+/// [type path]: TypePath::type_path
+#[derive(Default, Clone, Debug)]
+pub struct FunctionRegistry {
+    functions: HashMap<Cow<'static, str>, DynamicFunction>,
+}
+
+impl FunctionRegistry {
+    /// Registers `function` under `name`, replacing the function previously registered under it.
+    pub fn register<M>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: impl IntoFunction<M>,
+    ) -> &mut Self {
+        let name = name.into();
+        let function = function.into_function().with_name(name.clone());
+        self.functions.insert(name, function);
+        self
+    }
+
+    /// Registers `method` of the type `T` under the [type path] of `T` followed by `name`,
+    /// replacing the function previously registered under it.
+    ///
+    /// [type path]: TypePath::type_path
+    pub fn register_method<T: TypePath, M>(
+        &mut self,
+        name: &str,
+        method: impl IntoFunction<M>,
+    ) -> &mut Self {
+        self.register(format!("{}::{name}", T::type_path()), method)
+    }
+
+    /// Removes the function registered under `name`, and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<DynamicFunction> {
+        self.functions.remove(name)
+    }
+
+    /// Returns the function registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns `true` if a function is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Calls the function registered under `name` with `args`.
+    pub fn call(&self, name: &str, args: ArgList) -> FunctionResult {
+        self.get(name)
+            .ok_or_else(|| FunctionError::NotFound(Cow::Owned(name.to_string())))?
+            .call(args)
+    }
+
+    /// Returns an iterator over the registered functions, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
+
+    /// Returns an iterator over the methods registered for the type `T`, in no particular order.
+    pub fn iter_methods<T: TypePath>(&self) -> impl Iterator<Item = &DynamicFunction> {
+        let prefix = format!("{}::", T::type_path());
+        self.functions
+            .iter()
+            .filter(move |(name, _)| {
+                name.strip_prefix(&prefix)
+                    .is_some_and(|method| !method.contains("::"))
+            })
+            .map(|(_, function)| function)
+    }
+
+    /// Returns the number of registered functions.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns `true` if no function is registered.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+/// A synchronized wrapper around a [`FunctionRegistry`].
+#[derive(Clone, Default)]
+pub struct FunctionRegistryArc {
+    pub internal: Arc<RwLock<FunctionRegistry>>,
+}
+
+impl Debug for FunctionRegistryArc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.internal
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .functions
+            .keys()
+            .fmt(f)
+    }
+}
+
+impl FunctionRegistryArc {
+    /// Takes a read lock on the underlying [`FunctionRegistry`].
+    pub fn read(&self) -> RwLockReadGuard<'_, FunctionRegistry> {
+        self.internal.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Takes a write lock on the underlying [`FunctionRegistry`].
+    pub fn write(&self) -> RwLockWriteGuard<'_, FunctionRegistry> {
+        self.internal
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -174,6 +174,9 @@
 //! assert_eq!(None, value);
 //! ```
 //!
+//! The minimal set of changes between two values can also be computed with [`Reflect::diff`],
+//! which returns a [`Patch`] applied with [`Reflect::apply_patch`].
+//!
 //! ## `FromReflect`
 //!
 //! It's important to remember that dynamic types are _not_ the concrete type they may be representing.
@@ -411,6 +414,13 @@
 //! This means types must manually be registered, including their desired monomorphized
 //! representations if generic.
 //!
+//! ## External Types
+//!
+//! The [orphan rule] prevents implementing the reflection traits on types from other crates.
+//! Such types can instead be reflected through a local wrapper generated by [`reflect_remote`],
+//! which mirrors their fields, and then be used directly as the fields of reflected types
+//! marked with `#[reflect(remote = ...)]`.
+//!
 //! # Features
 //!
 //! ## `bevy`
@@ -464,14 +474,17 @@
 //! [orphan rule]: https://doc.rust-lang.org/book/ch10-02-traits.html#implementing-a-trait-on-a-type:~:text=But%20we%20can%E2%80%99t,implementation%20to%20use.
 //! [`bevy_reflect_derive/documentation`]: bevy_reflect_derive
 //! [derive `Reflect`]: derive@crate::Reflect
+//! [`reflect_remote`]: macro@crate::reflect_remote
 
 mod array;
+mod diff;
 mod fields;
 mod from_reflect;
 mod list;
 mod map;
 mod path;
 mod reflect;
+mod remote;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -495,6 +508,7 @@ mod impls {
 }
 
 mod enums;
+pub mod func;
 pub mod serde;
 pub mod std_traits;
 pub mod utility;
@@ -503,13 +517,14 @@ pub mod prelude {
     pub use crate::std_traits::*;
     #[doc(hidden)]
     pub use crate::{
-        reflect_trait, FromReflect, GetField, GetPath, GetTupleStructField, Reflect,
-        ReflectDeserialize, ReflectFromReflect, ReflectPath, ReflectSerialize, Struct, TupleStruct,
-        TypePath,
+        reflect_remote, reflect_trait, FromReflect, GetField, GetPath, GetTupleStructField,
+        Reflect, ReflectDeserialize, ReflectFromReflect, ReflectPath, ReflectRemote,
+        ReflectSerialize, Struct, TupleStruct, TypePath,
     };
 }
 
 pub use array::*;
+pub use diff::*;
 pub use enums::*;
 pub use fields::*;
 pub use from_reflect::*;
@@ -517,6 +532,7 @@ pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use remote::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -1927,6 +1943,93 @@ bevy_reflect::tests::Test {
         );
     }
 
+    #[test]
+    fn should_reflect_remote_types() {
+        mod external {
+            #[derive(Debug, PartialEq)]
+            pub struct Body<T> {
+                pub mass: T,
+                pub handle: Handle,
+            }
+
+            #[derive(Debug, PartialEq, Default)]
+            pub struct Handle(pub u32);
+        }
+
+        #[reflect_remote(external::Body<T>)]
+        struct BodyDef<T: Reflect + FromReflect + TypePath> {
+            mass: T,
+            #[reflect(remote = HandleDef)]
+            handle: external::Handle,
+        }
+
+        #[reflect_remote(external::Handle)]
+        #[derive(Default)]
+        #[reflect(Default)]
+        struct HandleDef(u32);
+
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Player {
+            #[reflect(remote = BodyDef<f32>)]
+            body: external::Body<f32>,
+            #[reflect(remote = HandleDef, default)]
+            target: external::Handle,
+        }
+
+        let info = <Player as Typed>::type_info();
+        let TypeInfo::Struct(info) = info else {
+            panic!("expected a struct");
+        };
+        assert!(info.field("body").unwrap().is::<BodyDef<f32>>());
+
+        let mut player = Player {
+            body: external::Body {
+                mass: 1.0,
+                handle: external::Handle(2),
+            },
+            target: external::Handle(3),
+        };
+        assert_eq!(Some(&1.0), player.path::<f32>("body.mass").ok());
+        assert_eq!(Some(&2), player.path::<u32>("body.handle.0").ok());
+
+        *player.path_mut::<u32>("body.handle.0").unwrap() = 4;
+        assert_eq!(external::Handle(4), player.body.handle);
+
+        let mut patch = DynamicStruct::default();
+        patch.insert("mass", 5.0f32);
+        player.field_mut("body").unwrap().apply(&patch);
+        assert_eq!(5.0, player.body.mass);
+
+        let clone = Player::from_reflect(&player.clone_dynamic()).unwrap();
+        assert_eq!(player, clone);
+
+        let mut dynamic = DynamicStruct::default();
+        dynamic.insert(
+            "body",
+            BodyDef(external::Body {
+                mass: 6.0f32,
+                handle: external::Handle(7),
+            }),
+        );
+        let from_dynamic = Player::from_reflect(&dynamic).unwrap();
+        assert_eq!(
+            Player {
+                body: external::Body {
+                    mass: 6.0,
+                    handle: external::Handle(7),
+                },
+                target: external::Handle(0),
+            },
+            from_dynamic
+        );
+
+        let wrapper = HandleDef::into_wrapper(external::Handle(8));
+        assert!(wrapper
+            .reflect_partial_eq(&HandleDef(external::Handle(8)))
+            .unwrap());
+        assert_eq!(external::Handle(8), wrapper.into_remote());
+    }
+
     #[test]
     fn dynamic_types_debug_format() {
         #[derive(Debug, Reflect)]
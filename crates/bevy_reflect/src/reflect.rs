@@ -1,7 +1,7 @@
 use crate::{
     array_debug, enum_debug, list_debug, map_debug, serde::Serializable, struct_debug, tuple_debug,
-    tuple_struct_debug, Array, DynamicTypePath, Enum, List, Map, Struct, Tuple, TupleStruct,
-    TypeInfo, TypePath, Typed, ValueInfo,
+    tuple_struct_debug, Array, DynamicTypePath, Enum, List, Map, Patch, PatchError, Struct, Tuple,
+    TupleStruct, TypeInfo, TypePath, Typed, ValueInfo,
 };
 use std::{
     any::{Any, TypeId},
@@ -194,6 +194,26 @@ pub trait Reflect: DynamicTypePath + Any + Send + Sync {
         None
     }
 
+    /// Returns the [`Patch`] turning this value into `value`.
+    ///
+    /// The patch only contains the fields and elements which differ, and is applied with
+    /// [`Reflect::apply_patch`]. Values of different kinds or types, as well as enums
+    /// of different variants, are replaced as a whole.
+    fn diff(&self, value: &dyn Reflect) -> Patch {
+        crate::diff::diff(self.as_reflect(), value)
+    }
+
+    /// Applies a [`Patch`] created by [`Reflect::diff`] to this value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the patch doesn't match the shape of this value, such as a
+    /// missing field or a value of another type. The changes applied before the error
+    /// are kept.
+    fn apply_patch(&mut self, patch: &Patch) -> Result<(), PatchError> {
+        crate::diff::apply_patch(self.as_reflect_mut(), patch)
+    }
+
     /// Debug formatter for the value.
     ///
     /// Any value that is not an implementor of other `Reflect` subtraits
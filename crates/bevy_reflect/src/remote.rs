@@ -0,0 +1,66 @@
+use crate::Reflect;
+
+/// A wrapper implementing [`Reflect`] on behalf of a type from another crate.
+///
+/// This trait is implemented by the `#[repr(transparent)]` wrappers generated by the
+/// [`reflect_remote`] attribute macro, and lets the reflected types use the remote type
+/// as the type of their fields marked with `#[reflect(remote = ...)]`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{reflect_remote, Reflect, ReflectRemote, Struct};
+/// mod external {
+///     pub struct Velocity {
+///         pub x: f32,
+///         pub y: f32,
+///     }
+/// }
+///
+/// #[reflect_remote(external::Velocity)]
+/// struct VelocityDef {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Body {
+///     #[reflect(remote = VelocityDef)]
+///     velocity: external::Velocity,
+/// }
+///
+/// let mut body = Body {
+///     velocity: external::Velocity { x: 1.0, y: 2.0 },
+/// };
+///
+/// let velocity = body.field_mut("velocity").unwrap();
+/// velocity.apply(&VelocityDef(external::Velocity { x: 3.0, y: 4.0 }));
+/// assert_eq!(3.0, body.velocity.x);
+///
+/// let wrapper = VelocityDef::as_wrapper(&body.velocity);
+/// assert_eq!(Some(&4.0), wrapper.field("y").unwrap().downcast_ref::<f32>());
+/// ```
+///
+/// [`reflect_remote`]: crate::reflect_remote
+pub trait ReflectRemote: Reflect {
+    /// The type from another crate this type wraps.
+    type Remote: 'static;
+
+    /// Returns a reference to the wrapped value.
+    fn as_remote(&self) -> &Self::Remote;
+
+    /// Returns a mutable reference to the wrapped value.
+    fn as_remote_mut(&mut self) -> &mut Self::Remote;
+
+    /// Returns the wrapped value.
+    fn into_remote(self) -> Self::Remote;
+
+    /// Returns a reference to `remote` as this wrapper.
+    fn as_wrapper(remote: &Self::Remote) -> &Self;
+
+    /// Returns a mutable reference to `remote` as this wrapper.
+    fn as_wrapper_mut(remote: &mut Self::Remote) -> &mut Self;
+
+    /// Wraps `remote`.
+    fn into_wrapper(remote: Self::Remote) -> Self;
+}
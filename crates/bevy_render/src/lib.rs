@@ -22,6 +22,7 @@ pub mod render_resource;
 pub mod renderer;
 pub mod settings;
 mod spatial_bundle;
+pub mod spatial_index;
 pub mod texture;
 pub mod view;
 pub mod prelude {
@@ -32,8 +33,12 @@ pub mod prelude {
         mesh::{morph::MorphWeights, shape, Mesh},
         render_resource::Shader,
         spatial_bundle::SpatialBundle,
+        spatial_index::SpatialQuery,
         texture::{Image, ImagePlugin},
-        view::{InheritedVisibility, Msaa, ViewVisibility, Visibility, VisibilityBundle},
+        view::{
+            InheritedVisibility, Msaa, ViewVisibility, Visibility, VisibilityBundle,
+            WindowIconImage,
+        },
         ExtractSchedule,
     };
 }
@@ -52,6 +57,7 @@ use crate::{
     render_resource::{PipelineCache, Shader, ShaderLoader},
     renderer::{render_system, RenderInstance},
     settings::RenderCreation,
+    spatial_index::SpatialIndexPlugin,
     view::{ViewPlugin, WindowRenderPlugin},
 };
 use bevy_app::{App, AppLabel, Plugin, SubApp};
@@ -300,6 +306,7 @@ impl Plugin for RenderPlugin {
             MeshPlugin,
             GlobalsPlugin,
             MorphPlugin,
+            SpatialIndexPlugin,
         ));
 
         app.register_type::<color::Color>()
@@ -0,0 +1,677 @@
+//! Constructive solid geometry: boolean operations between closed meshes.
+//!
+//! The meshes are converted to polygons, classified against binary space partitioning trees of
+//! each other, and converted back, following the algorithm of [csg.js](https://github.com/evanw/csg.js).
+
+use super::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues};
+use bevy_math::{Mat3, Mat4, Vec3};
+use thiserror::Error;
+use wgpu::PrimitiveTopology;
+
+/// Distance under which a point is considered to be on a plane.
+const EPSILON: f32 = 1e-5;
+
+/// An error converting a [`Mesh`] to a [`Csg`].
+#[derive(Error, Debug)]
+pub enum CsgError {
+    #[error("constructive solid geometry needs a `TriangleList`, not a {0:?}")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[error("the mesh has no `Float32x3` positions")]
+    MissingPositions,
+}
+
+/// A solid made of polygons, to combine with other solids with [`Csg::union`], [`Csg::subtract`]
+/// and [`Csg::intersect`].
+///
+/// Solids should be closed, like the shapes in [`shape`](crate::mesh::shape), for their inside
+/// to be well defined. The floating point vertex attributes of the meshes, such as their
+/// normals, UVs or colors, are interpolated where polygons are cut. Other attributes, such as
+/// joint indices, are dropped, and so are the attributes only one of the solids has.
+///
+/// ```
+/// # use bevy_render::mesh::{shape, Csg, Mesh};
+/// # use bevy_math::Mat4;
+/// let wall = Csg::try_from(&Mesh::from(shape::Box::new(4.0, 3.0, 0.2))).unwrap();
+/// let window = Csg::try_from(&Mesh::from(shape::Cube::new(1.0)))
+///     .unwrap()
+///     .transformed(Mat4::from_translation([1.0, 0.5, 0.0].into()));
+/// let wall = Mesh::from(wall.subtract(&window));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Csg {
+    /// The attributes of the vertices other than their positions, with their number of
+    /// components.
+    attributes: Vec<(MeshVertexAttribute, usize)>,
+    polygons: Vec<Polygon>,
+}
+
+impl TryFrom<&Mesh> for Csg {
+    type Error = CsgError;
+
+    fn try_from(mesh: &Mesh) -> Result<Self, Self::Error> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return Err(CsgError::UnsupportedTopology(mesh.primitive_topology()));
+        }
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return Err(CsgError::MissingPositions);
+        };
+
+        let mut attributes = Vec::new();
+        let mut values = Vec::new();
+        for data in mesh.attributes.values() {
+            if data.attribute.id == Mesh::ATTRIBUTE_POSITION.id {
+                continue;
+            }
+            let components = match &data.values {
+                VertexAttributeValues::Float32(_) => 1,
+                VertexAttributeValues::Float32x2(_) => 2,
+                VertexAttributeValues::Float32x3(_) => 3,
+                VertexAttributeValues::Float32x4(_) => 4,
+                // Other formats can't be interpolated
+                _ => continue,
+            };
+            attributes.push((data.attribute.clone(), components));
+            values.push(&data.values);
+        }
+
+        let vertex = |index: usize| Vertex {
+            position: positions[index].into(),
+            data: values
+                .iter()
+                .flat_map(|values| match values {
+                    VertexAttributeValues::Float32(values) => values[index..=index].iter(),
+                    VertexAttributeValues::Float32x2(values) => values[index].iter(),
+                    VertexAttributeValues::Float32x3(values) => values[index].iter(),
+                    VertexAttributeValues::Float32x4(values) => values[index].iter(),
+                    _ => unreachable!("only floating point attributes are kept"),
+                })
+                .copied()
+                .collect(),
+        };
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+        let polygons = indices
+            .chunks_exact(3)
+            .filter_map(|triangle| Polygon::new(triangle.iter().map(|&i| vertex(i)).collect()))
+            .collect();
+
+        Ok(Csg {
+            attributes,
+            polygons,
+        })
+    }
+}
+
+impl From<Csg> for Mesh {
+    fn from(csg: Csg) -> Self {
+        let vertex_count = csg.polygons.iter().map(|p| p.vertices.len()).sum();
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(vertex_count);
+        let mut indices: Vec<u32> = Vec::new();
+        for polygon in &csg.polygons {
+            let first = positions.len() as u32;
+            positions.extend(
+                polygon
+                    .vertices
+                    .iter()
+                    .map(|vertex| vertex.position.to_array()),
+            );
+            for i in 1..polygon.vertices.len() as u32 - 1 {
+                indices.extend_from_slice(&[first, first + i, first + i + 1]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_indices(Some(Indices::U32(indices)));
+        let mut offset = 0;
+        for (attribute, components) in &csg.attributes {
+            let values = csg.polygons.iter().flat_map(|polygon| {
+                polygon
+                    .vertices
+                    .iter()
+                    .map(move |vertex| &vertex.data[offset..offset + components])
+            });
+            let values = match components {
+                1 => VertexAttributeValues::Float32(values.map(|v| v[0]).collect()),
+                2 => VertexAttributeValues::Float32x2(values.map(|v| [v[0], v[1]]).collect()),
+                3 if attribute.id == Mesh::ATTRIBUTE_NORMAL.id => VertexAttributeValues::Float32x3(
+                    values
+                        .map(|v| Vec3::from_slice(v).normalize_or_zero().to_array())
+                        .collect(),
+                ),
+                3 => VertexAttributeValues::Float32x3(values.map(|v| [v[0], v[1], v[2]]).collect()),
+                _ if attribute.id == Mesh::ATTRIBUTE_TANGENT.id => {
+                    VertexAttributeValues::Float32x4(
+                        values
+                            .map(|v| {
+                                Vec3::from_slice(v)
+                                    .normalize_or_zero()
+                                    .extend(v[3])
+                                    .to_array()
+                            })
+                            .collect(),
+                    )
+                }
+                _ => VertexAttributeValues::Float32x4(
+                    values.map(|v| [v[0], v[1], v[2], v[3]]).collect(),
+                ),
+            };
+            mesh.insert_attribute(attribute.clone(), values);
+            offset += components;
+        }
+        mesh
+    }
+}
+
+impl Csg {
+    /// Transforms the solid by `transform`, such as to place it relative to another solid.
+    #[must_use]
+    pub fn transformed(mut self, transform: Mat4) -> Self {
+        let normal_transform = Mat3::from_mat4(transform).inverse().transpose();
+        let tangent_transform = Mat3::from_mat4(transform);
+        let mirrored = transform.determinant() < 0.0;
+        let normal = self.offset_of(Mesh::ATTRIBUTE_NORMAL);
+        let tangent = self.offset_of(Mesh::ATTRIBUTE_TANGENT);
+
+        for polygon in &mut self.polygons {
+            for vertex in &mut polygon.vertices {
+                vertex.position = transform.transform_point3(vertex.position);
+                if let Some(offset) = normal {
+                    let data = &mut vertex.data[offset..offset + 3];
+                    let normal = normal_transform * Vec3::from_slice(data);
+                    normal.normalize_or_zero().write_to_slice(data);
+                }
+                if let Some(offset) = tangent {
+                    let data = &mut vertex.data[offset..offset + 3];
+                    let tangent = tangent_transform * Vec3::from_slice(data);
+                    tangent.normalize_or_zero().write_to_slice(data);
+                }
+            }
+            if mirrored {
+                // Keep the triangles facing outward, and the bitangents in the same direction
+                polygon.vertices.reverse();
+                if let Some(offset) = tangent {
+                    for vertex in &mut polygon.vertices {
+                        vertex.data[offset + 3] = -vertex.data[offset + 3];
+                    }
+                }
+            }
+            polygon.plane = Plane::new(&polygon.vertices).unwrap_or(polygon.plane);
+        }
+        self
+    }
+
+    /// The solid made of both this solid and `other`.
+    pub fn union(&self, other: &Csg) -> Csg {
+        let (mut a, mut b, attributes) = self.trees(other);
+        let flip = FlippedAttributes::new(&attributes);
+        a.clip_to(&b);
+        b.clip_to(&a);
+        b.invert(flip);
+        b.clip_to(&a);
+        b.invert(flip);
+        a.build(b.into_polygons());
+        Csg {
+            attributes,
+            polygons: a.into_polygons(),
+        }
+    }
+
+    /// The solid made of this solid without `other`.
+    pub fn subtract(&self, other: &Csg) -> Csg {
+        let (mut a, mut b, attributes) = self.trees(other);
+        let flip = FlippedAttributes::new(&attributes);
+        a.invert(flip);
+        a.clip_to(&b);
+        b.clip_to(&a);
+        b.invert(flip);
+        b.clip_to(&a);
+        b.invert(flip);
+        a.build(b.into_polygons());
+        a.invert(flip);
+        Csg {
+            attributes,
+            polygons: a.into_polygons(),
+        }
+    }
+
+    /// The solid made of the parts of this solid inside `other`.
+    pub fn intersect(&self, other: &Csg) -> Csg {
+        let (mut a, mut b, attributes) = self.trees(other);
+        let flip = FlippedAttributes::new(&attributes);
+        a.invert(flip);
+        b.clip_to(&a);
+        b.invert(flip);
+        a.clip_to(&b);
+        b.clip_to(&a);
+        a.build(b.into_polygons());
+        a.invert(flip);
+        Csg {
+            attributes,
+            polygons: a.into_polygons(),
+        }
+    }
+
+    /// The offset of the components of `attribute` in the data of the vertices.
+    fn offset_of(&self, attribute: MeshVertexAttribute) -> Option<usize> {
+        let mut offset = 0;
+        for (other, components) in &self.attributes {
+            if other.id == attribute.id {
+                return Some(offset);
+            }
+            offset += components;
+        }
+        None
+    }
+
+    /// Builds the trees of the polygons of both solids, with the attributes they have in common.
+    fn trees(&self, other: &Csg) -> (Node, Node, Vec<(MeshVertexAttribute, usize)>) {
+        let attributes: Vec<_> =
+            self.attributes
+                .iter()
+                .filter(|(attribute, _)| {
+                    other.attributes.iter().any(|(other, _)| {
+                        other.id == attribute.id && other.format == attribute.format
+                    })
+                })
+                .cloned()
+                .collect();
+        let tree = |csg: &Csg| {
+            let offsets: Vec<_> = attributes
+                .iter()
+                .map(|(attribute, components)| {
+                    (csg.offset_of(attribute.clone()).unwrap(), *components)
+                })
+                .collect();
+            let polygons = csg
+                .polygons
+                .iter()
+                .map(|polygon| Polygon {
+                    vertices: polygon
+                        .vertices
+                        .iter()
+                        .map(|vertex| Vertex {
+                            position: vertex.position,
+                            data: offsets
+                                .iter()
+                                .flat_map(|&(offset, components)| {
+                                    &vertex.data[offset..offset + components]
+                                })
+                                .copied()
+                                .collect(),
+                        })
+                        .collect(),
+                    plane: polygon.plane,
+                })
+                .collect();
+            let mut node = Node::default();
+            node.build(polygons);
+            node
+        };
+        (tree(self), tree(other), attributes)
+    }
+}
+
+impl Mesh {
+    /// Combines this mesh and `other`, as [`Csg::union`].
+    pub fn union(&self, other: &Mesh) -> Result<Mesh, CsgError> {
+        Ok(Csg::try_from(self)?.union(&Csg::try_from(other)?).into())
+    }
+
+    /// Cuts `other` out of this mesh, as [`Csg::subtract`].
+    pub fn subtract(&self, other: &Mesh) -> Result<Mesh, CsgError> {
+        Ok(Csg::try_from(self)?.subtract(&Csg::try_from(other)?).into())
+    }
+
+    /// Keeps the parts of this mesh inside `other`, as [`Csg::intersect`].
+    pub fn intersect(&self, other: &Mesh) -> Result<Mesh, CsgError> {
+        Ok(Csg::try_from(self)?
+            .intersect(&Csg::try_from(other)?)
+            .into())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Vertex {
+    position: Vec3,
+    /// The components of the other attributes of the vertex.
+    data: Vec<f32>,
+}
+
+impl Vertex {
+    fn lerp(&self, other: &Vertex, t: f32) -> Vertex {
+        Vertex {
+            position: self.position.lerp(other.position, t),
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| a + (b - a) * t)
+                .collect(),
+        }
+    }
+}
+
+/// The offsets of the attributes that change when a polygon is turned inside out.
+#[derive(Clone, Copy, Debug, Default)]
+struct FlippedAttributes {
+    normal: Option<usize>,
+    tangent: Option<usize>,
+}
+
+impl FlippedAttributes {
+    fn new(attributes: &[(MeshVertexAttribute, usize)]) -> Self {
+        let offset = |id| {
+            let i = attributes
+                .iter()
+                .position(|(attribute, _)| attribute.id == id)?;
+            Some(
+                attributes[..i]
+                    .iter()
+                    .map(|(_, components)| components)
+                    .sum(),
+            )
+        };
+        FlippedAttributes {
+            normal: offset(Mesh::ATTRIBUTE_NORMAL.id),
+            tangent: offset(Mesh::ATTRIBUTE_TANGENT.id),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Plane {
+    normal: Vec3,
+    /// The distance of the plane to the origin along its normal.
+    w: f32,
+}
+
+impl Plane {
+    /// The plane of a polygon, or `None` if it's degenerate.
+    fn new(vertices: &[Vertex]) -> Option<Plane> {
+        let [a, b, c] = [0, 1, 2].map(|i| vertices.get(i).map(|vertex| vertex.position));
+        let (a, b, c) = (a?, b?, c?);
+        let normal = (b - a).cross(c - a).try_normalize()?;
+        Some(Plane {
+            normal,
+            w: normal.dot(a),
+        })
+    }
+
+    fn flip(&mut self) {
+        self.normal = -self.normal;
+        self.w = -self.w;
+    }
+
+    /// Splits `polygon` by this plane, putting the parts in the matching lists.
+    fn split(
+        &self,
+        polygon: Polygon,
+        coplanar_front: &mut Vec<Polygon>,
+        coplanar_back: &mut Vec<Polygon>,
+        front: &mut Vec<Polygon>,
+        back: &mut Vec<Polygon>,
+    ) {
+        const COPLANAR: u8 = 0;
+        const FRONT: u8 = 1;
+        const BACK: u8 = 2;
+        const SPANNING: u8 = 3;
+
+        let mut polygon_type = COPLANAR;
+        let types: Vec<u8> = polygon
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let distance = self.normal.dot(vertex.position) - self.w;
+                let vertex_type = if distance < -EPSILON {
+                    BACK
+                } else if distance > EPSILON {
+                    FRONT
+                } else {
+                    COPLANAR
+                };
+                polygon_type |= vertex_type;
+                vertex_type
+            })
+            .collect();
+
+        match polygon_type {
+            COPLANAR => {
+                if self.normal.dot(polygon.plane.normal) > 0.0 {
+                    coplanar_front.push(polygon);
+                } else {
+                    coplanar_back.push(polygon);
+                }
+            }
+            FRONT => front.push(polygon),
+            BACK => back.push(polygon),
+            _ => {
+                let mut front_vertices = Vec::new();
+                let mut back_vertices = Vec::new();
+                let count = polygon.vertices.len();
+                for i in 0..count {
+                    let j = (i + 1) % count;
+                    let (ti, tj) = (types[i], types[j]);
+                    let (vi, vj) = (&polygon.vertices[i], &polygon.vertices[j]);
+                    if ti != BACK {
+                        front_vertices.push(vi.clone());
+                    }
+                    if ti != FRONT {
+                        back_vertices.push(vi.clone());
+                    }
+                    if ti | tj == SPANNING {
+                        let t = (self.w - self.normal.dot(vi.position))
+                            / self.normal.dot(vj.position - vi.position);
+                        let vertex = vi.lerp(vj, t);
+                        front_vertices.push(vertex.clone());
+                        back_vertices.push(vertex);
+                    }
+                }
+                if front_vertices.len() >= 3 {
+                    front.push(Polygon {
+                        vertices: front_vertices,
+                        plane: polygon.plane,
+                    });
+                }
+                if back_vertices.len() >= 3 {
+                    back.push(Polygon {
+                        vertices: back_vertices,
+                        plane: polygon.plane,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A convex polygon.
+#[derive(Clone, Debug)]
+struct Polygon {
+    vertices: Vec<Vertex>,
+    plane: Plane,
+}
+
+impl Polygon {
+    fn new(vertices: Vec<Vertex>) -> Option<Polygon> {
+        let plane = Plane::new(&vertices)?;
+        Some(Polygon { vertices, plane })
+    }
+
+    fn flip(&mut self, flip: FlippedAttributes) {
+        self.vertices.reverse();
+        for vertex in &mut self.vertices {
+            if let Some(offset) = flip.normal {
+                for component in &mut vertex.data[offset..offset + 3] {
+                    *component = -*component;
+                }
+            }
+            if let Some(offset) = flip.tangent {
+                vertex.data[offset + 3] = -vertex.data[offset + 3];
+            }
+        }
+        self.plane.flip();
+    }
+}
+
+/// A node of a binary space partitioning tree of polygons.
+///
+/// The methods walk the tree with explicit stacks, since it can be as deep as the number of
+/// polygons.
+#[derive(Clone, Debug, Default)]
+struct Node {
+    plane: Option<Plane>,
+    front: Option<Box<Node>>,
+    back: Option<Box<Node>>,
+    /// The polygons on the plane of the node.
+    polygons: Vec<Polygon>,
+}
+
+impl Node {
+    /// Adds `polygons` to the tree, splitting them by the planes of its nodes.
+    fn build(&mut self, polygons: Vec<Polygon>) {
+        let mut stack = vec![(self, polygons)];
+        while let Some((node, polygons)) = stack.pop() {
+            if polygons.is_empty() {
+                continue;
+            }
+            let plane = *node.plane.get_or_insert(polygons[0].plane);
+            let mut coplanar_front = Vec::new();
+            let mut coplanar_back = Vec::new();
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                plane.split(
+                    polygon,
+                    &mut coplanar_front,
+                    &mut coplanar_back,
+                    &mut front,
+                    &mut back,
+                );
+            }
+            node.polygons.append(&mut coplanar_front);
+            node.polygons.append(&mut coplanar_back);
+
+            if !front.is_empty() {
+                stack.push((node.front.get_or_insert_with(Default::default), front));
+            }
+            if !back.is_empty() {
+                stack.push((node.back.get_or_insert_with(Default::default), back));
+            }
+        }
+    }
+
+    /// Turns the solid of the tree inside out.
+    fn invert(&mut self, flip: FlippedAttributes) {
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            for polygon in &mut node.polygons {
+                polygon.flip(flip);
+            }
+            if let Some(plane) = &mut node.plane {
+                plane.flip();
+            }
+            std::mem::swap(&mut node.front, &mut node.back);
+            stack.extend(node.front.as_deref_mut());
+            stack.extend(node.back.as_deref_mut());
+        }
+    }
+
+    /// Removes the parts of `polygons` inside the solid of the tree.
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        let mut result = Vec::new();
+        let mut stack = vec![(self, polygons)];
+        while let Some((node, polygons)) = stack.pop() {
+            let Some(plane) = node.plane else {
+                result.extend(polygons);
+                continue;
+            };
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                let mut coplanar_front = Vec::new();
+                let mut coplanar_back = Vec::new();
+                plane.split(
+                    polygon,
+                    &mut coplanar_front,
+                    &mut coplanar_back,
+                    &mut front,
+                    &mut back,
+                );
+                front.append(&mut coplanar_front);
+                back.append(&mut coplanar_back);
+            }
+            match &node.front {
+                Some(child) => stack.push((child, front)),
+                None => result.append(&mut front),
+            }
+            // Polygons behind a leaf are inside the solid
+            if let Some(child) = &node.back {
+                stack.push((child, back));
+            }
+        }
+        result
+    }
+
+    /// Removes the parts of the polygons of this tree inside the solid of `other`.
+    fn clip_to(&mut self, other: &Node) {
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            node.polygons = other.clip_polygons(std::mem::take(&mut node.polygons));
+            stack.extend(node.front.as_deref_mut());
+            stack.extend(node.back.as_deref_mut());
+        }
+    }
+
+    /// Takes all the polygons of the tree.
+    fn into_polygons(self) -> Vec<Polygon> {
+        let mut polygons = Vec::new();
+        let mut stack = vec![self];
+        while let Some(mut node) = stack.pop() {
+            polygons.append(&mut node.polygons);
+            stack.extend(node.front.map(|node| *node));
+            stack.extend(node.back.map(|node| *node));
+        }
+        polygons
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Csg;
+    use crate::mesh::{shape, Mesh, VertexAttributeValues};
+    use bevy_math::{Mat4, Vec3};
+
+    /// The volume enclosed by a closed mesh, from the signed volumes of its triangles.
+    fn volume(mesh: &Mesh) -> f32 {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("the mesh has no positions");
+        };
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(positions[triangle[i]]));
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn boolean_operations() {
+        // Two cubes of volume 8 overlapping on a cube of volume 1
+        let a = Csg::try_from(&Mesh::from(shape::Cube::new(2.0))).unwrap();
+        let b = Csg::try_from(&Mesh::from(shape::Cube::new(2.0)))
+            .unwrap()
+            .transformed(Mat4::from_translation(Vec3::ONE));
+
+        assert!((volume(&a.union(&b).into()) - 15.0).abs() < 1e-4);
+        assert!((volume(&a.subtract(&b).into()) - 7.0).abs() < 1e-4);
+        assert!((volume(&a.intersect(&b).into()) - 1.0).abs() < 1e-4);
+    }
+}
@@ -1,5 +1,8 @@
 mod conversions;
+mod csg;
+mod simplify;
 pub mod skinning;
+pub use csg::{Csg, CsgError};
 pub use wgpu::PrimitiveTopology;
 
 use crate::{
@@ -9,14 +12,14 @@ use crate::{
     render_resource::{Buffer, TextureView, VertexBufferLayout},
     renderer::RenderDevice,
 };
-use bevy_asset::{Asset, Handle};
+use bevy_asset::{Asset, AssetMemoryUsage, Handle};
 use bevy_core::cast_slice;
 use bevy_derive::EnumVariantMeta;
 use bevy_ecs::system::{lifetimeless::SRes, SystemParamItem};
 use bevy_log::warn;
 use bevy_math::*;
 use bevy_reflect::Reflect;
-use bevy_utils::{tracing::error, Hashed};
+use bevy_utils::{tracing::error, HashMap, Hashed};
 use std::{collections::BTreeMap, hash::Hash, iter::FusedIterator};
 use thiserror::Error;
 use wgpu::{
@@ -125,6 +128,17 @@ pub struct Mesh {
     morph_target_names: Option<Vec<String>>,
 }
 
+impl AssetMemoryUsage for Mesh {
+    fn memory_usage(&self) -> usize {
+        let vertex_bytes: usize = self
+            .attributes
+            .values()
+            .map(|data| data.values.get_bytes().len())
+            .sum();
+        vertex_bytes + self.get_index_buffer_bytes().map_or(0, <[u8]>::len)
+    }
+}
+
 impl Mesh {
     /// Where the vertex is located in space. Use in conjunction with [`Mesh::insert_attribute`]
     /// or [`Mesh::with_inserted_attribute`].
@@ -443,48 +457,13 @@ impl Mesh {
     ///
     /// This can dramatically increase the vertex count, so make sure this is what you want.
     /// Does nothing if no [Indices] are set.
-    #[allow(clippy::match_same_arms)]
     pub fn duplicate_vertices(&mut self) {
-        fn duplicate<T: Copy>(values: &[T], indices: impl Iterator<Item = usize>) -> Vec<T> {
-            indices.map(|i| values[i]).collect()
-        }
-
         let Some(indices) = self.indices.take() else {
             return;
         };
 
         for attributes in self.attributes.values_mut() {
-            let indices = indices.iter();
-            match &mut attributes.values {
-                VertexAttributeValues::Float32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm8x4(vec) => *vec = duplicate(vec, indices),
-            }
+            attributes.values = attributes.values.remapped(indices.iter());
         }
     }
 
@@ -543,6 +522,129 @@ impl Mesh {
         self
     }
 
+    /// Calculates smooth [`Mesh::ATTRIBUTE_NORMAL`]s by averaging the normals of the faces
+    /// around each vertex position.
+    ///
+    /// Faces whose normals differ by more than `crease_angle` (in radians) are not averaged
+    /// together, which keeps hard edges sharp. Vertices on such edges are split, so an indexed
+    /// mesh may gain vertices; in that case any morph targets are removed. Passing
+    /// [`PI`](std::f32::consts::PI) smooths every edge.
+    ///
+    /// Existing tangents are not updated, consider calling [`Mesh::generate_tangents`] afterwards.
+    ///
+    /// # Panics
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is not of type `float3` or if the mesh has any
+    /// other topology than [`PrimitiveTopology::TriangleList`].
+    pub fn compute_smooth_normals(&mut self, crease_angle: f32) {
+        assert!(
+            matches!(self.primitive_topology, PrimitiveTopology::TriangleList),
+            "`compute_smooth_normals` can only work on `TriangleList`s"
+        );
+
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .expect("`Mesh::ATTRIBUTE_POSITION` vertex attributes should be of type `float3`");
+
+        let corners: Vec<usize> = match &self.indices {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+
+        // Each face is weighted by its angle at the vertex, so that the result doesn't depend
+        // on how the surface was triangulated.
+        let mut directions = Vec::with_capacity(corners.len() / 3);
+        let mut angles = Vec::with_capacity(corners.len());
+        for corners in corners.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(positions[corners[i]]));
+            directions.push((b - a).cross(c - a).normalize_or_zero());
+            angles.extend(
+                [(b - a, c - a), (c - b, a - b), (a - c, b - c)].map(|(u, v)| {
+                    let (u, v) = (u.normalize_or_zero(), v.normalize_or_zero());
+                    u.dot(v).clamp(-1.0, 1.0).acos()
+                }),
+            );
+        }
+
+        // Faces are grouped by position rather than by vertex, so that vertices which were
+        // split for their uvs are still smoothed together.
+        let mut position_ids = HashMap::new();
+        let mut corners_at_position: Vec<Vec<usize>> = Vec::new();
+        let corner_positions: Vec<usize> = corners
+            .iter()
+            .enumerate()
+            .map(|(corner, &vertex)| {
+                let id = *position_ids
+                    .entry(bits_key(positions[vertex]))
+                    .or_insert_with(|| {
+                        corners_at_position.push(Vec::new());
+                        corners_at_position.len() - 1
+                    });
+                corners_at_position[id].push(corner);
+                id
+            })
+            .collect();
+
+        let min_cos = crease_angle.cos();
+        let normals: Vec<[f32; 3]> = corner_positions
+            .iter()
+            .enumerate()
+            .map(|(corner, &id)| {
+                let direction = directions[corner / 3];
+                corners_at_position[id]
+                    .iter()
+                    .filter(|&&other| {
+                        other / 3 == corner / 3 || directions[other / 3].dot(direction) >= min_cos
+                    })
+                    .map(|&other| directions[other / 3] * angles[other])
+                    .sum::<Vec3>()
+                    .normalize_or_zero()
+                    .into()
+            })
+            .collect();
+
+        if self.indices.is_none() {
+            self.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+            return;
+        }
+
+        // Split each vertex into one vertex per distinct normal it ends up with.
+        let mut vertex_ids = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut vertex_normals = Vec::new();
+        let indices = corners
+            .iter()
+            .zip(normals)
+            .map(|(&vertex, normal)| {
+                *vertex_ids
+                    .entry((vertex, bits_key(normal)))
+                    .or_insert_with(|| {
+                        vertices.push(vertex);
+                        vertex_normals.push(normal);
+                        vertices.len() as u32 - 1
+                    })
+            })
+            .collect();
+
+        self.remap_vertices(&vertices, indices);
+        self.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vertex_normals);
+    }
+
+    /// Consumes the mesh and returns a mesh with smooth [`Mesh::ATTRIBUTE_NORMAL`]s, keeping
+    /// edges sharper than `crease_angle` (in radians) hard.
+    ///
+    /// (Alternatively, you can use [`Mesh::compute_smooth_normals`] to mutate an existing mesh in-place)
+    ///
+    /// # Panics
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is not of type `float3` or if the mesh has any
+    /// other topology than [`PrimitiveTopology::TriangleList`].
+    #[must_use]
+    pub fn with_computed_smooth_normals(mut self, crease_angle: f32) -> Self {
+        self.compute_smooth_normals(crease_angle);
+        self
+    }
+
     /// Generate tangents for the mesh using the `mikktspace` algorithm.
     ///
     /// Sets the [`Mesh::ATTRIBUTE_TANGENT`] attribute if successful.
@@ -565,6 +667,27 @@ impl Mesh {
         Ok(self)
     }
 
+    /// Rebuilds the vertex buffers from the given `vertices` of the current ones, and sets the
+    /// triangles to `indices` into the new vertices.
+    ///
+    /// Morph targets are removed if the vertex count changes, as they are laid out per vertex.
+    fn remap_vertices(&mut self, vertices: &[usize], indices: Vec<u32>) {
+        if vertices.len() != self.count_vertices() {
+            self.morph_targets = None;
+            self.morph_target_names = None;
+        }
+
+        for attributes in self.attributes.values_mut() {
+            attributes.values = attributes.values.remapped(vertices.iter().copied());
+        }
+
+        self.indices = Some(if vertices.len() <= u16::MAX as usize + 1 {
+            Indices::U16(indices.into_iter().map(|i| i as u16).collect())
+        } else {
+            Indices::U32(indices)
+        });
+    }
+
     /// Compute the Axis-Aligned Bounding Box of the mesh vertices in model space
     pub fn compute_aabb(&self) -> Option<Aabb> {
         let Some(VertexAttributeValues::Float32x3(values)) =
@@ -747,6 +870,11 @@ struct MeshAttributeData {
     values: VertexAttributeValues,
 }
 
+/// A hashable key for a vector, treating `0.0` and `-0.0` as equal.
+fn bits_key(vector: [f32; 3]) -> [u32; 3] {
+    vector.map(|x| (x + 0.0).to_bits())
+}
+
 fn face_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
     let (a, b, c) = (Vec3::from(a), Vec3::from(b), Vec3::from(c));
     (b - a).cross(c - a).normalize().into()
@@ -874,6 +1002,101 @@ impl VertexAttributeValues {
         self.len() == 0
     }
 
+    /// Returns the values of the vertices at `indices`, in order.
+    #[allow(clippy::match_same_arms)]
+    fn remapped(&self, indices: impl Iterator<Item = usize>) -> VertexAttributeValues {
+        fn remap<T: Copy>(values: &[T], indices: impl Iterator<Item = usize>) -> Vec<T> {
+            indices.map(|i| values[i]).collect()
+        }
+
+        match self {
+            VertexAttributeValues::Float32(values) => {
+                VertexAttributeValues::Float32(remap(values, indices))
+            }
+            VertexAttributeValues::Sint32(values) => {
+                VertexAttributeValues::Sint32(remap(values, indices))
+            }
+            VertexAttributeValues::Uint32(values) => {
+                VertexAttributeValues::Uint32(remap(values, indices))
+            }
+            VertexAttributeValues::Float32x2(values) => {
+                VertexAttributeValues::Float32x2(remap(values, indices))
+            }
+            VertexAttributeValues::Sint32x2(values) => {
+                VertexAttributeValues::Sint32x2(remap(values, indices))
+            }
+            VertexAttributeValues::Uint32x2(values) => {
+                VertexAttributeValues::Uint32x2(remap(values, indices))
+            }
+            VertexAttributeValues::Float32x3(values) => {
+                VertexAttributeValues::Float32x3(remap(values, indices))
+            }
+            VertexAttributeValues::Sint32x3(values) => {
+                VertexAttributeValues::Sint32x3(remap(values, indices))
+            }
+            VertexAttributeValues::Uint32x3(values) => {
+                VertexAttributeValues::Uint32x3(remap(values, indices))
+            }
+            VertexAttributeValues::Sint32x4(values) => {
+                VertexAttributeValues::Sint32x4(remap(values, indices))
+            }
+            VertexAttributeValues::Uint32x4(values) => {
+                VertexAttributeValues::Uint32x4(remap(values, indices))
+            }
+            VertexAttributeValues::Float32x4(values) => {
+                VertexAttributeValues::Float32x4(remap(values, indices))
+            }
+            VertexAttributeValues::Sint16x2(values) => {
+                VertexAttributeValues::Sint16x2(remap(values, indices))
+            }
+            VertexAttributeValues::Snorm16x2(values) => {
+                VertexAttributeValues::Snorm16x2(remap(values, indices))
+            }
+            VertexAttributeValues::Uint16x2(values) => {
+                VertexAttributeValues::Uint16x2(remap(values, indices))
+            }
+            VertexAttributeValues::Unorm16x2(values) => {
+                VertexAttributeValues::Unorm16x2(remap(values, indices))
+            }
+            VertexAttributeValues::Sint16x4(values) => {
+                VertexAttributeValues::Sint16x4(remap(values, indices))
+            }
+            VertexAttributeValues::Snorm16x4(values) => {
+                VertexAttributeValues::Snorm16x4(remap(values, indices))
+            }
+            VertexAttributeValues::Uint16x4(values) => {
+                VertexAttributeValues::Uint16x4(remap(values, indices))
+            }
+            VertexAttributeValues::Unorm16x4(values) => {
+                VertexAttributeValues::Unorm16x4(remap(values, indices))
+            }
+            VertexAttributeValues::Sint8x2(values) => {
+                VertexAttributeValues::Sint8x2(remap(values, indices))
+            }
+            VertexAttributeValues::Snorm8x2(values) => {
+                VertexAttributeValues::Snorm8x2(remap(values, indices))
+            }
+            VertexAttributeValues::Uint8x2(values) => {
+                VertexAttributeValues::Uint8x2(remap(values, indices))
+            }
+            VertexAttributeValues::Unorm8x2(values) => {
+                VertexAttributeValues::Unorm8x2(remap(values, indices))
+            }
+            VertexAttributeValues::Sint8x4(values) => {
+                VertexAttributeValues::Sint8x4(remap(values, indices))
+            }
+            VertexAttributeValues::Snorm8x4(values) => {
+                VertexAttributeValues::Snorm8x4(remap(values, indices))
+            }
+            VertexAttributeValues::Uint8x4(values) => {
+                VertexAttributeValues::Uint8x4(remap(values, indices))
+            }
+            VertexAttributeValues::Unorm8x4(values) => {
+                VertexAttributeValues::Unorm8x4(remap(values, indices))
+            }
+        }
+    }
+
     /// Returns the values as float triples if possible.
     pub fn as_float3(&self) -> Option<&[[f32; 3]]> {
         match self {
@@ -1225,6 +1448,9 @@ fn generate_tangents_for_mesh(mesh: &Mesh) -> Result<Vec<[f32; 4]>, GenerateTang
 #[cfg(test)]
 mod tests {
     use super::Mesh;
+    use crate::mesh::shape;
+    use bevy_math::Vec3;
+    use std::f32::consts::{FRAC_PI_4, PI};
     use wgpu::PrimitiveTopology;
 
     #[test]
@@ -1233,4 +1459,30 @@ mod tests {
         let _mesh = Mesh::new(PrimitiveTopology::TriangleList)
             .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0, 0.0]]);
     }
+
+    #[test]
+    fn smooth_normals() {
+        let cube = Mesh::from(shape::Cube::new(1.0));
+
+        // Below the angle between the faces, every edge stays hard.
+        let sharp = cube.clone().with_computed_smooth_normals(FRAC_PI_4);
+        assert_eq!(
+            sharp.attribute(Mesh::ATTRIBUTE_NORMAL).unwrap().as_float3(),
+            cube.attribute(Mesh::ATTRIBUTE_NORMAL).unwrap().as_float3()
+        );
+
+        // Above it, every corner points away from the center.
+        let smooth = cube.with_computed_smooth_normals(PI);
+        let positions = smooth.attribute(Mesh::ATTRIBUTE_POSITION).unwrap();
+        let normals = smooth.attribute(Mesh::ATTRIBUTE_NORMAL).unwrap();
+        for (position, normal) in positions
+            .as_float3()
+            .unwrap()
+            .iter()
+            .zip(normals.as_float3().unwrap())
+        {
+            let expected = Vec3::from(*position).normalize();
+            assert!(Vec3::from(*normal).abs_diff_eq(expected, 1e-5));
+        }
+    }
 }
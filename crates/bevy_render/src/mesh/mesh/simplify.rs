@@ -0,0 +1,451 @@
+use super::{bits_key, Mesh, PrimitiveTopology};
+use bevy_math::DVec3;
+use bevy_utils::{HashMap, HashSet};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// How strongly open edges resist being moved, relative to the faces around them.
+const BOUNDARY_WEIGHT: f64 = 10.0;
+/// Collapses that rotate a remaining face by more than roughly 78 degrees are rejected.
+const MIN_FACE_COS: f64 = 0.2;
+
+impl Mesh {
+    /// Reduces the number of triangles of the mesh to at most `target_triangle_count`, using
+    /// quadric error metrics to pick the edges whose removal changes the shape the least.
+    ///
+    /// Edges are collapsed onto one of their vertices, so the remaining vertices keep their
+    /// exact positions and attributes. Vertices sharing a position, such as along uv seams,
+    /// are treated as one, and open edges are preserved where possible. Collapses that would
+    /// flip a face or make the surface non-manifold are skipped, so the target may not be
+    /// reached for some meshes.
+    ///
+    /// The mesh is indexed afterwards and unused vertices are removed, which also removes any
+    /// morph targets. Normals and tangents are kept from the original vertices, consider
+    /// calling [`Mesh::compute_smooth_normals`] or [`Mesh::generate_tangents`] afterwards.
+    ///
+    /// # Panics
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is not of type `float3` or if the mesh has any
+    /// other topology than [`PrimitiveTopology::TriangleList`].
+    pub fn simplify(&mut self, target_triangle_count: usize) {
+        assert!(
+            matches!(self.primitive_topology, PrimitiveTopology::TriangleList),
+            "`simplify` can only work on `TriangleList`s"
+        );
+
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .expect("`Mesh::ATTRIBUTE_POSITION` vertex attributes should be of type `float3`");
+        let corners: Vec<usize> = match &self.indices {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+
+        let mut simplifier = Simplifier::new(positions, corners);
+        simplifier.run(target_triangle_count);
+        let corners = simplifier.into_corners();
+
+        // Keep only the vertices that are still in use, in order of first use.
+        let mut vertex_ids = HashMap::new();
+        let mut vertices = Vec::new();
+        let indices = corners
+            .into_iter()
+            .map(|vertex| {
+                *vertex_ids.entry(vertex).or_insert_with(|| {
+                    vertices.push(vertex);
+                    vertices.len() as u32 - 1
+                })
+            })
+            .collect();
+
+        self.remap_vertices(&vertices, indices);
+    }
+
+    /// Consumes the mesh and returns a mesh with at most `target_triangle_count` triangles.
+    ///
+    /// (Alternatively, you can use [`Mesh::simplify`] to mutate an existing mesh in-place)
+    ///
+    /// # Panics
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is not of type `float3` or if the mesh has any
+    /// other topology than [`PrimitiveTopology::TriangleList`].
+    #[must_use]
+    pub fn with_simplified(mut self, target_triangle_count: usize) -> Self {
+        self.simplify(target_triangle_count);
+        self
+    }
+}
+
+/// A symmetric 4x4 matrix measuring the squared distance of a point to a set of planes.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn from_plane(normal: DVec3, point: DVec3, weight: f64) -> Self {
+        let DVec3 { x: a, y: b, z: c } = normal;
+        let d = -normal.dot(point);
+        Self(
+            [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ]
+            .map(|q| q * weight),
+        )
+    }
+
+    fn error(&self, p: DVec3) -> f64 {
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+        let error = aa * p.x * p.x
+            + bb * p.y * p.y
+            + cc * p.z * p.z
+            + 2.0 * (ab * p.x * p.y + ac * p.x * p.z + bc * p.y * p.z)
+            + 2.0 * (ad * p.x + bd * p.y + cd * p.z)
+            + dd;
+        error.max(0.0)
+    }
+}
+
+impl std::ops::Add for Quadric {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        for (q, o) in self.0.iter_mut().zip(other.0) {
+            *q += o;
+        }
+        self
+    }
+}
+
+/// A candidate collapse of the position `from` onto the position `to`.
+struct Collapse {
+    cost: f64,
+    from: usize,
+    to: usize,
+    /// The versions of `from` and `to` when the cost was computed.
+    versions: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    // Reversed, so that the cheapest collapse is at the top of the heap. Ties are broken by
+    // position so that the result doesn't depend on hash map iteration order.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| (other.from, other.to).cmp(&(self.from, self.to)))
+    }
+}
+
+/// Edge collapse state. Vertices sharing a position are welded into one position, which is
+/// what the topology is built from, while triangles keep pointing at the original vertices.
+struct Simplifier {
+    /// The vertices of each triangle.
+    triangles: Vec<[usize; 3]>,
+    alive: Vec<bool>,
+    alive_count: usize,
+    /// The welded position of each vertex.
+    vertex_positions: Vec<usize>,
+    points: Vec<DVec3>,
+    quadrics: Vec<Quadric>,
+    /// The triangles around each position. May contain removed triangles.
+    position_triangles: Vec<Vec<usize>>,
+    /// Bumped whenever a position changes, to invalidate queued collapses.
+    versions: Vec<u32>,
+    removed: Vec<bool>,
+    /// Whether each position lies on an open edge.
+    boundary: Vec<bool>,
+    heap: BinaryHeap<Collapse>,
+}
+
+impl Simplifier {
+    fn new(positions: &[[f32; 3]], corners: Vec<usize>) -> Self {
+        let mut ids = HashMap::new();
+        let mut points = Vec::new();
+        let vertex_positions: Vec<usize> = positions
+            .iter()
+            .map(|&position| {
+                *ids.entry(bits_key(position)).or_insert_with(|| {
+                    points.push(DVec3::from(position.map(f64::from)));
+                    points.len() - 1
+                })
+            })
+            .collect();
+
+        let triangles: Vec<[usize; 3]> = corners
+            .chunks_exact(3)
+            .map(|corners| [corners[0], corners[1], corners[2]])
+            .collect();
+
+        let mut simplifier = Self {
+            alive: vec![true; triangles.len()],
+            alive_count: triangles.len(),
+            vertex_positions,
+            quadrics: vec![Quadric::default(); points.len()],
+            position_triangles: vec![Vec::new(); points.len()],
+            versions: vec![0; points.len()],
+            removed: vec![false; points.len()],
+            boundary: vec![false; points.len()],
+            points,
+            triangles,
+            heap: BinaryHeap::new(),
+        };
+
+        // Each face contributes its plane to its corners, weighted by its area, and each open
+        // edge contributes a plane perpendicular to its face to keep the outline in place.
+        let mut edges = HashMap::<(usize, usize), (u32, usize)>::new();
+        for triangle in 0..simplifier.triangles.len() {
+            let [a, b, c] = simplifier.positions(triangle);
+            if a == b || b == c || c == a {
+                simplifier.alive[triangle] = false;
+                simplifier.alive_count -= 1;
+                continue;
+            }
+            let [pa, pb, pc] = [a, b, c].map(|p| simplifier.points[p]);
+            let cross = (pb - pa).cross(pc - pa);
+            let quadric = Quadric::from_plane(cross.normalize_or_zero(), pa, cross.length() / 2.0);
+            for p in [a, b, c] {
+                simplifier.quadrics[p] = simplifier.quadrics[p] + quadric;
+                simplifier.position_triangles[p].push(triangle);
+            }
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                edges
+                    .entry((from.min(to), from.max(to)))
+                    .or_insert((0, triangle))
+                    .0 += 1;
+            }
+        }
+        for (&(a, b), &(count, triangle)) in &edges {
+            if count != 1 {
+                continue;
+            }
+            let [pa, pb, pc] = simplifier.positions(triangle).map(|p| simplifier.points[p]);
+            let edge = simplifier.points[b] - simplifier.points[a];
+            let normal = (pb - pa).cross(pc - pa);
+            let perpendicular = edge.cross(normal).normalize_or_zero();
+            let quadric = Quadric::from_plane(
+                perpendicular,
+                simplifier.points[a],
+                edge.length_squared() * BOUNDARY_WEIGHT,
+            );
+            simplifier.quadrics[a] = simplifier.quadrics[a] + quadric;
+            simplifier.quadrics[b] = simplifier.quadrics[b] + quadric;
+            simplifier.boundary[a] = true;
+            simplifier.boundary[b] = true;
+        }
+
+        for &(a, b) in edges.keys() {
+            simplifier.push(a, b);
+            simplifier.push(b, a);
+        }
+        simplifier
+    }
+
+    fn positions(&self, triangle: usize) -> [usize; 3] {
+        self.triangles[triangle].map(|vertex| self.vertex_positions[vertex])
+    }
+
+    fn push(&mut self, from: usize, to: usize) {
+        let quadric = self.quadrics[from] + self.quadrics[to];
+        self.heap.push(Collapse {
+            cost: quadric.error(self.points[to]),
+            from,
+            to,
+            versions: (self.versions[from], self.versions[to]),
+        });
+    }
+
+    fn neighbors(&self, position: usize) -> HashSet<usize> {
+        self.position_triangles[position]
+            .iter()
+            .filter(|&&triangle| self.alive[triangle])
+            .flat_map(|&triangle| self.positions(triangle))
+            .filter(|&p| p != position)
+            .collect()
+    }
+
+    fn run(&mut self, target_triangle_count: usize) {
+        while self.alive_count > target_triangle_count {
+            let Some(collapse) = self.heap.pop() else {
+                break;
+            };
+            let Collapse { from, to, .. } = collapse;
+            if self.removed[from]
+                || self.removed[to]
+                || collapse.versions != (self.versions[from], self.versions[to])
+            {
+                continue;
+            }
+            self.collapse(from, to);
+        }
+    }
+
+    /// Moves the position `from` onto `to`, unless that would damage the surface.
+    fn collapse(&mut self, from: usize, to: usize) {
+        let (shared, moved): (Vec<usize>, Vec<usize>) = self.position_triangles[from]
+            .iter()
+            .copied()
+            .filter(|&triangle| self.alive[triangle])
+            .partition(|&triangle| self.positions(triangle).contains(&to));
+        if shared.is_empty() {
+            return;
+        }
+
+        // An inner edge between two open edges would pinch the surface.
+        if self.boundary[from] && self.boundary[to] && shared.len() != 1 {
+            return;
+        }
+
+        // Removing the last triangle of a part of the surface would erase it.
+        if moved.is_empty()
+            && self.position_triangles[to]
+                .iter()
+                .all(|triangle| !self.alive[*triangle] || shared.contains(triangle))
+        {
+            return;
+        }
+
+        // Every position next to both ends must be opposite the edge in a shared triangle,
+        // or the collapse would glue two parts of the surface together.
+        let common = self
+            .neighbors(from)
+            .intersection(&self.neighbors(to))
+            .count();
+        if common != shared.len() {
+            return;
+        }
+
+        // Nor may a moved triangle end up on top of an existing one.
+        let sorted = |mut positions: [usize; 3]| {
+            positions.sort_unstable();
+            positions
+        };
+        let existing: Vec<[usize; 3]> = self.position_triangles[to]
+            .iter()
+            .filter(|&&triangle| self.alive[triangle] && !shared.contains(&triangle))
+            .map(|&triangle| sorted(self.positions(triangle)))
+            .collect();
+        if moved.iter().any(|&triangle| {
+            let positions = self
+                .positions(triangle)
+                .map(|p| if p == from { to } else { p });
+            existing.contains(&sorted(positions))
+        }) {
+            return;
+        }
+
+        let target = self.points[to];
+        for &triangle in &moved {
+            let points = self.positions(triangle).map(|p| self.points[p]);
+            let before = (points[1] - points[0]).cross(points[2] - points[0]);
+            let points =
+                self.positions(triangle)
+                    .map(|p| if p == from { target } else { self.points[p] });
+            let after = (points[1] - points[0]).cross(points[2] - points[0]);
+            if before.length_squared() > 0.0
+                && after.normalize_or_zero().dot(before.normalize()) < MIN_FACE_COS
+            {
+                return;
+            }
+        }
+
+        // Vertices of the removed triangles tell which vertex on the other side of a seam
+        // each moved vertex corresponds to.
+        let mut vertex_map = Vec::new();
+        for &triangle in &shared {
+            let vertices = self.triangles[triangle];
+            let find = |position| {
+                vertices
+                    .into_iter()
+                    .find(|&vertex| self.vertex_positions[vertex] == position)
+            };
+            if let (Some(from_vertex), Some(to_vertex)) = (find(from), find(to)) {
+                vertex_map.push((from_vertex, to_vertex));
+            }
+            self.alive[triangle] = false;
+            self.alive_count -= 1;
+        }
+        let fallback = vertex_map[0].1;
+
+        for &triangle in &moved {
+            for vertex in &mut self.triangles[triangle] {
+                if self.vertex_positions[*vertex] == from {
+                    *vertex = vertex_map
+                        .iter()
+                        .find(|(from_vertex, _)| from_vertex == vertex)
+                        .map_or(fallback, |&(_, to_vertex)| to_vertex);
+                }
+            }
+        }
+
+        self.removed[from] = true;
+        self.boundary[to] |= self.boundary[from];
+        self.quadrics[to] = self.quadrics[to] + self.quadrics[from];
+        self.versions[to] += 1;
+        let mut triangles = std::mem::take(&mut self.position_triangles[to]);
+        triangles.retain(|&triangle| self.alive[triangle]);
+        triangles.extend(moved);
+        self.position_triangles[to] = triangles;
+
+        for neighbor in self.neighbors(to) {
+            self.push(to, neighbor);
+            self.push(neighbor, to);
+        }
+    }
+
+    /// The vertices of the remaining triangles.
+    fn into_corners(self) -> Vec<usize> {
+        self.triangles
+            .into_iter()
+            .zip(self.alive)
+            .filter(|(_, alive)| *alive)
+            .flat_map(|(triangle, _)| triangle)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mesh::{shape, Mesh, VertexAttributeValues};
+
+    #[test]
+    fn simplify_plane() {
+        let mesh = Mesh::from(shape::Plane {
+            size: 2.0,
+            subdivisions: 10,
+        })
+        .with_simplified(2);
+
+        assert_eq!(mesh.indices().unwrap().len(), 6);
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("the mesh has no positions");
+        };
+        // A flat surface loses nothing but its inner vertices.
+        assert_eq!(positions.len(), 4);
+        assert!(positions
+            .iter()
+            .all(|p| p[0].abs() == 1.0 && p[1] == 0.0 && p[2].abs() == 1.0));
+    }
+}
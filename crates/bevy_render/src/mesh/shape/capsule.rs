@@ -2,6 +2,8 @@ use crate::mesh::{Indices, Mesh};
 use bevy_math::{Vec2, Vec3};
 use wgpu::PrimitiveTopology;
 
+use super::generate_tangents;
+
 /// A cylinder with hemispheres at the top and bottom
 #[derive(Debug, Copy, Clone)]
 pub struct Capsule {
@@ -17,6 +19,8 @@ pub struct Capsule {
     pub longitudes: usize,
     /// Manner in which UV coordinates are distributed vertically.
     pub uv_profile: CapsuleUvProfile,
+    /// Whether to generate the tangents of the mesh, needed for normal maps.
+    pub tangents: bool,
 }
 impl Default for Capsule {
     fn default() -> Self {
@@ -27,10 +31,53 @@ impl Default for Capsule {
             latitudes: 16,
             longitudes: 32,
             uv_profile: CapsuleUvProfile::Aspect,
+            tangents: false,
         }
     }
 }
 
+impl Capsule {
+    /// Creates a capsule with the given radius and depth of the middle cylinder, and the default
+    /// options.
+    pub fn new(radius: f32, depth: f32) -> Self {
+        Self {
+            radius,
+            depth,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the number of sections in the cylinder between the hemispheres.
+    pub fn with_rings(mut self, rings: usize) -> Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Sets the number of latitudes, which must be even.
+    pub fn with_latitudes(mut self, latitudes: usize) -> Self {
+        self.latitudes = latitudes;
+        self
+    }
+
+    /// Sets the number of longitudes.
+    pub fn with_longitudes(mut self, longitudes: usize) -> Self {
+        self.longitudes = longitudes;
+        self
+    }
+
+    /// Sets how the UV coordinates are distributed vertically.
+    pub fn with_uv_profile(mut self, uv_profile: CapsuleUvProfile) -> Self {
+        self.uv_profile = uv_profile;
+        self
+    }
+
+    /// Sets whether to generate the tangents of the mesh.
+    pub fn with_tangents(mut self, tangents: bool) -> Self {
+        self.tangents = tangents;
+        self
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 /// Manner in which UV coordinates are distributed vertically.
 pub enum CapsuleUvProfile {
@@ -56,6 +103,7 @@ impl From<Capsule> for Mesh {
             latitudes,
             longitudes,
             uv_profile,
+            tangents,
         } = capsule;
 
         let calc_middle = rings > 0;
@@ -364,10 +412,11 @@ impl From<Capsule> for Mesh {
         assert_eq!(vs.len(), vert_len);
         assert_eq!(tris.len(), fs_len);
 
-        Mesh::new(PrimitiveTopology::TriangleList)
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
             .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vs)
             .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vns)
             .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vts)
-            .with_indices(Some(Indices::U32(tris)))
+            .with_indices(Some(Indices::U32(tris)));
+        generate_tangents(mesh, tangents)
     }
 }
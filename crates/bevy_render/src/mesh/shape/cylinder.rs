@@ -1,6 +1,8 @@
 use crate::mesh::{Indices, Mesh};
 use wgpu::PrimitiveTopology;
 
+use super::{generate_tangents, UvMapping};
+
 /// A cylinder which stands on the XZ plane
 #[derive(Clone, Copy, Debug)]
 pub struct Cylinder {
@@ -16,6 +18,13 @@ pub struct Cylinder {
     /// height of the cylinder. Setting it to 2 will have two sets of triangles with a horizontal slice in the middle of
     /// cylinder. Greater numbers increase triangles/slices in the same way.
     pub segments: u32,
+    /// Whether the ends of the cylinder are closed by caps. Without them, the cylinder is an open
+    /// tube.
+    pub caps: bool,
+    /// How the UV coordinates are laid out on the side and the caps.
+    pub uv_mapping: UvMapping,
+    /// Whether to generate the tangents of the mesh, needed for normal maps.
+    pub tangents: bool,
 }
 
 impl Default for Cylinder {
@@ -25,8 +34,52 @@ impl Default for Cylinder {
             height: 1.0,
             resolution: 16,
             segments: 1,
+            caps: true,
+            uv_mapping: UvMapping::Stretch,
+            tangents: false,
+        }
+    }
+}
+
+impl Cylinder {
+    /// Creates a cylinder with the given radius and height, and the default options.
+    pub fn new(radius: f32, height: f32) -> Self {
+        Self {
+            radius,
+            height,
+            ..Default::default()
         }
     }
+
+    /// Sets the number of vertices around each horizontal slice of the cylinder.
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets the number of segments between the two ends.
+    pub fn with_segments(mut self, segments: u32) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets whether the ends of the cylinder are closed by caps.
+    pub fn with_caps(mut self, caps: bool) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// Sets how the UV coordinates are laid out.
+    pub fn with_uv_mapping(mut self, uv_mapping: UvMapping) -> Self {
+        self.uv_mapping = uv_mapping;
+        self
+    }
+
+    /// Sets whether to generate the tangents of the mesh.
+    pub fn with_tangents(mut self, tangents: bool) -> Self {
+        self.tangents = tangents;
+        self
+    }
 }
 
 impl From<Cylinder> for Mesh {
@@ -61,8 +114,10 @@ impl From<Cylinder> for Mesh {
                 positions.push([c.radius * cos, y, c.radius * sin]);
                 normals.push([cos, 0., sin]);
                 uvs.push([
-                    segment as f32 / c.resolution as f32,
-                    ring as f32 / c.segments as f32,
+                    c.uv_mapping
+                        .coordinate(segment as f32 / c.resolution as f32, theta * c.radius),
+                    c.uv_mapping
+                        .coordinate(ring as f32 / c.segments as f32, ring as f32 * step_y),
                 ]);
             }
         }
@@ -101,7 +156,11 @@ impl From<Cylinder> for Mesh {
 
                 positions.push([cos * c.radius, y, sin * c.radius]);
                 normals.push([0.0, normal_y, 0.0]);
-                uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
+                uvs.push([
+                    c.uv_mapping.coordinate(0.5 * (cos + 1.0), cos * c.radius),
+                    c.uv_mapping
+                        .coordinate(1.0 - 0.5 * (sin + 1.0), -sin * c.radius),
+                ]);
             }
 
             for i in 1..(c.resolution - 1) {
@@ -113,15 +172,16 @@ impl From<Cylinder> for Mesh {
             }
         };
 
-        // top
-
-        build_cap(true);
-        build_cap(false);
+        if c.caps {
+            build_cap(true);
+            build_cap(false);
+        }
 
-        Mesh::new(PrimitiveTopology::TriangleList)
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
             .with_indices(Some(Indices::U32(indices)))
             .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
             .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
-            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        generate_tangents(mesh, c.tangents)
     }
 }
@@ -261,6 +261,37 @@ impl From<Plane> for Mesh {
     }
 }
 
+/// How the UV coordinates of a shape are laid out on its surface.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum UvMapping {
+    /// The texture is stretched to cover each surface of the shape once.
+    #[default]
+    Stretch,
+    /// The texture is repeated without being stretched, each repetition covering a square of
+    /// this side length on the surface.
+    Tile(f32),
+}
+
+impl UvMapping {
+    /// The UV coordinate of a point at `stretched` of a surface, or at `length` on it when tiling.
+    fn coordinate(self, stretched: f32, length: f32) -> f32 {
+        match self {
+            UvMapping::Stretch => stretched,
+            UvMapping::Tile(size) => length / size,
+        }
+    }
+}
+
+/// Generates the tangents of the mesh of a shape, if they're requested.
+fn generate_tangents(mesh: Mesh, tangents: bool) -> Mesh {
+    if tangents {
+        mesh.with_generated_tangents()
+            .expect("shapes have triangles with positions, normals and UVs")
+    } else {
+        mesh
+    }
+}
+
 mod capsule;
 mod cylinder;
 mod icosphere;
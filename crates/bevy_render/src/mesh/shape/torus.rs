@@ -2,13 +2,24 @@ use crate::mesh::{Indices, Mesh};
 use bevy_math::Vec3;
 use wgpu::PrimitiveTopology;
 
+use super::{generate_tangents, UvMapping};
+
 /// A torus (donut) shape.
 #[derive(Debug, Clone, Copy)]
 pub struct Torus {
+    /// Radius of the circle through the centers of the ring, on the `XZ` plane.
     pub radius: f32,
+    /// Radius of the ring, the tube around that circle.
     pub ring_radius: f32,
+    /// Number of segments around the `Y` axis.
     pub subdivisions_segments: usize,
+    /// Number of sides around the tube of each segment.
     pub subdivisions_sides: usize,
+    /// How the UV coordinates are laid out, `U` going around the `Y` axis and `V` around the
+    /// tube.
+    pub uv_mapping: UvMapping,
+    /// Whether to generate the tangents of the mesh, needed for normal maps.
+    pub tangents: bool,
 }
 
 impl Default for Torus {
@@ -18,8 +29,40 @@ impl Default for Torus {
             ring_radius: 0.5,
             subdivisions_segments: 32,
             subdivisions_sides: 24,
+            uv_mapping: UvMapping::Stretch,
+            tangents: false,
+        }
+    }
+}
+
+impl Torus {
+    /// Creates a torus with the given radii, and the default options.
+    pub fn new(radius: f32, ring_radius: f32) -> Self {
+        Self {
+            radius,
+            ring_radius,
+            ..Default::default()
         }
     }
+
+    /// Sets the number of segments around the `Y` axis, and of sides around the tube.
+    pub fn with_subdivisions(mut self, segments: usize, sides: usize) -> Self {
+        self.subdivisions_segments = segments;
+        self.subdivisions_sides = sides;
+        self
+    }
+
+    /// Sets how the UV coordinates are laid out.
+    pub fn with_uv_mapping(mut self, uv_mapping: UvMapping) -> Self {
+        self.uv_mapping = uv_mapping;
+        self
+    }
+
+    /// Sets whether to generate the tangents of the mesh.
+    pub fn with_tangents(mut self, tangents: bool) -> Self {
+        self.tangents = tangents;
+        self
+    }
 }
 
 impl From<Torus> for Mesh {
@@ -53,8 +96,14 @@ impl From<Torus> for Mesh {
                 positions.push(position.into());
                 normals.push(normal.into());
                 uvs.push([
-                    segment as f32 / torus.subdivisions_segments as f32,
-                    side as f32 / torus.subdivisions_sides as f32,
+                    torus.uv_mapping.coordinate(
+                        segment as f32 / torus.subdivisions_segments as f32,
+                        theta * torus.radius,
+                    ),
+                    torus.uv_mapping.coordinate(
+                        side as f32 / torus.subdivisions_sides as f32,
+                        phi * torus.ring_radius,
+                    ),
                 ]);
             }
         }
@@ -84,10 +133,11 @@ impl From<Torus> for Mesh {
             }
         }
 
-        Mesh::new(PrimitiveTopology::TriangleList)
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
             .with_indices(Some(Indices::U32(indices)))
             .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
             .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
-            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        generate_tangents(mesh, torus.tangents)
     }
 }
@@ -122,6 +122,9 @@ impl Sphere {
         let aabb_center_world = local_to_world.transform_point3a(aabb.center);
         let v = aabb_center_world - self.center;
         let d = v.length();
+        if d == 0.0 {
+            return true;
+        }
         let relative_radius = aabb.relative_radius(&(v / d), &local_to_world.matrix3);
         d < self.radius + relative_radius
     }
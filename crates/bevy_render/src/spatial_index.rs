@@ -0,0 +1,718 @@
+//! A bounding volume hierarchy over the entities of the world, for fast spatial queries.
+
+use crate::{
+    primitives::{Aabb, Frustum, Sphere},
+    view::VisibilitySystems,
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_derive::Deref;
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Affine3A, Ray3d, Vec3A};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_utils::HashMap;
+
+/// Maintains the [`SpatialIndex`] of every entity with an [`Aabb`] and a [`GlobalTransform`].
+#[derive(Default)]
+pub struct SpatialIndexPlugin;
+
+/// The systems of the [`SpatialIndexPlugin`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpatialIndexSystem {
+    /// Updates the [`SpatialIndex`] with the entities whose bounds moved.
+    UpdateIndex,
+}
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>().add_systems(
+            PostUpdate,
+            update_spatial_index
+                .in_set(SpatialIndexSystem::UpdateIndex)
+                .after(VisibilitySystems::CalculateBounds)
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// A [`SystemParam`] to query the entities of the [`SpatialIndex`].
+///
+/// The index is updated in [`PostUpdate`], so systems running before
+/// [`SpatialIndexSystem::UpdateIndex`] see the entities where they were at the end of the
+/// previous frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{Ray3d, Vec3};
+/// # use bevy_render::spatial_index::SpatialQuery;
+/// fn shoot(spatial: SpatialQuery) {
+///     let ray = Ray3d::new(Vec3::ZERO, Vec3::NEG_Z);
+///     if let Some((entity, distance)) = spatial.cast_ray(ray, 100.0) {
+///         println!("Hit {entity:?} {distance} units away");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(shoot);
+/// ```
+#[derive(SystemParam, Deref)]
+pub struct SpatialQuery<'w> {
+    index: Res<'w, SpatialIndex>,
+}
+
+/// A dynamic bounding volume hierarchy over entities, which finds the entities along a ray,
+/// in a sphere, or in a [`Frustum`] without testing every one of them.
+///
+/// Each entity is stored with its [`Aabb`] and the transform placing it in the world, and is
+/// found by the queries when its oriented bounding box intersects the queried shape. The tree
+/// encloses each entity in a slightly larger box, so that an entity moving a little doesn't
+/// need to be reinserted.
+///
+/// The [`SpatialIndexPlugin`] keeps this resource in sync with the entities with an [`Aabb`]
+/// and a [`GlobalTransform`], and it is read through the [`SpatialQuery`] system parameter.
+#[derive(Resource, Default, Debug)]
+pub struct SpatialIndex {
+    nodes: Vec<Node>,
+    /// Indices of the unused nodes, to be reused before growing `nodes`.
+    free: Vec<usize>,
+    root: Option<usize>,
+    leaves: HashMap<Entity, usize>,
+}
+
+#[derive(Debug)]
+struct Node {
+    bounds: Bounds,
+    parent: Option<usize>,
+    /// The length of the longest path to a leaf below this node.
+    height: u32,
+    kind: NodeKind,
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    Leaf {
+        entity: Entity,
+        aabb: Aabb,
+        local_to_world: Affine3A,
+        world_to_local: Affine3A,
+    },
+    Branch([usize; 2]),
+}
+
+impl SpatialIndex {
+    /// The fraction of its size by which the box of an entity is grown in the tree.
+    const MARGIN: f32 = 0.1;
+
+    /// Returns the number of entities in the index.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns `true` if there are no entities in the index.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Returns `true` if the entity is in the index.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.leaves.contains_key(&entity)
+    }
+
+    /// Inserts an entity with the given bounds and transform, or moves it there if it was
+    /// already in the index.
+    pub fn insert(&mut self, entity: Entity, aabb: Aabb, transform: &GlobalTransform) {
+        let local_to_world = transform.affine();
+        let world_to_local = local_to_world.inverse();
+        let tight = Bounds::from_obb(&aabb, &local_to_world);
+
+        if let Some(&leaf) = self.leaves.get(&entity) {
+            let node = &mut self.nodes[leaf];
+            node.kind = NodeKind::Leaf {
+                entity,
+                aabb,
+                local_to_world,
+                world_to_local,
+            };
+            if node.bounds.contains(&tight) {
+                return;
+            }
+            node.bounds = tight.grow(Self::MARGIN);
+            self.remove_leaf(leaf);
+            self.insert_leaf(leaf);
+            return;
+        }
+
+        let leaf = self.allocate(Node {
+            bounds: tight.grow(Self::MARGIN),
+            parent: None,
+            height: 0,
+            kind: NodeKind::Leaf {
+                entity,
+                aabb,
+                local_to_world,
+                world_to_local,
+            },
+        });
+        self.leaves.insert(entity, leaf);
+        self.insert_leaf(leaf);
+    }
+
+    /// Removes an entity from the index, returning `true` if it was in it.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(leaf) = self.leaves.remove(&entity) else {
+            return false;
+        };
+        self.remove_leaf(leaf);
+        self.free.push(leaf);
+        true
+    }
+
+    /// Removes every entity from the index.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.root = None;
+        self.leaves.clear();
+    }
+
+    /// Returns the closest entity hit by the ray within `max_distance`, and the distance along
+    /// the ray to the point where it was hit.
+    ///
+    /// The distance is measured in multiples of the ray direction, which is normalized.
+    pub fn cast_ray(&self, ray: Ray3d, max_distance: f32) -> Option<(Entity, f32)> {
+        let origin = Vec3A::from(ray.origin);
+        let direction = Vec3A::from(*ray.direction);
+        let inverse_direction = direction.recip();
+
+        let mut closest = None;
+        let mut max_distance = max_distance;
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node
+                .bounds
+                .ray_distance(origin, inverse_direction, max_distance)
+                .is_none()
+            {
+                continue;
+            }
+            match &node.kind {
+                NodeKind::Leaf {
+                    entity,
+                    aabb,
+                    world_to_local,
+                    ..
+                } => {
+                    if let Some(distance) =
+                        ray_obb_distance(origin, direction, aabb, world_to_local, max_distance)
+                    {
+                        closest = Some((*entity, distance));
+                        max_distance = distance;
+                    }
+                }
+                NodeKind::Branch(children) => stack.extend(children),
+            }
+        }
+        closest
+    }
+
+    /// Returns every entity hit by the ray within `max_distance`, with the distance along the
+    /// ray to the point where it was hit, from the closest to the farthest.
+    pub fn intersect_ray(&self, ray: Ray3d, max_distance: f32) -> Vec<(Entity, f32)> {
+        let origin = Vec3A::from(ray.origin);
+        let direction = Vec3A::from(*ray.direction);
+        let inverse_direction = direction.recip();
+
+        let mut hits: Vec<(Entity, f32)> = self
+            .query(
+                |bounds| {
+                    bounds
+                        .ray_distance(origin, inverse_direction, max_distance)
+                        .is_some()
+                },
+                |_, _| true,
+            )
+            .filter_map(|index| {
+                let NodeKind::Leaf {
+                    entity,
+                    aabb,
+                    world_to_local,
+                    ..
+                } = &self.nodes[index].kind
+                else {
+                    unreachable!()
+                };
+                ray_obb_distance(origin, direction, aabb, world_to_local, max_distance)
+                    .map(|distance| (*entity, distance))
+            })
+            .collect();
+        hits.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        hits
+    }
+
+    /// Returns the entities whose bounds intersect the sphere.
+    ///
+    /// Like frustum culling, this test is conservative: an entity near the sphere but not
+    /// quite touching it may be returned as well.
+    pub fn intersect_sphere<'a>(&'a self, sphere: &'a Sphere) -> impl Iterator<Item = Entity> + 'a {
+        self.query(
+            |bounds| bounds.intersects_sphere(sphere),
+            |aabb, local_to_world| sphere.intersects_obb(aabb, local_to_world),
+        )
+        .map(|index| self.entity(index))
+    }
+
+    /// Returns the entities whose bounds intersect the frustum.
+    ///
+    /// Like frustum culling, this test is conservative: an entity near the frustum but not
+    /// quite inside it may be returned as well.
+    pub fn intersect_frustum<'a>(
+        &'a self,
+        frustum: &'a Frustum,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.query(
+            |bounds| frustum.intersects_obb(&bounds.to_aabb(), &Affine3A::IDENTITY, true, true),
+            |aabb, local_to_world| frustum.intersects_obb(aabb, local_to_world, true, true),
+        )
+        .map(|index| self.entity(index))
+    }
+
+    /// Returns the leaves under the nodes accepted by `node_filter` that are accepted by
+    /// `leaf_filter`.
+    fn query<'a>(
+        &'a self,
+        node_filter: impl Fn(&Bounds) -> bool + 'a,
+        leaf_filter: impl Fn(&Aabb, &Affine3A) -> bool + 'a,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        std::iter::from_fn(move || {
+            while let Some(index) = stack.pop() {
+                let node = &self.nodes[index];
+                if !node_filter(&node.bounds) {
+                    continue;
+                }
+                match &node.kind {
+                    NodeKind::Leaf {
+                        aabb,
+                        local_to_world,
+                        ..
+                    } => {
+                        if leaf_filter(aabb, local_to_world) {
+                            return Some(index);
+                        }
+                    }
+                    NodeKind::Branch(children) => stack.extend(children),
+                }
+            }
+            None
+        })
+    }
+
+    fn entity(&self, leaf: usize) -> Entity {
+        match self.nodes[leaf].kind {
+            NodeKind::Leaf { entity, .. } => entity,
+            NodeKind::Branch(_) => unreachable!(),
+        }
+    }
+
+    fn allocate(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn children(&self, index: usize) -> [usize; 2] {
+        match self.nodes[index].kind {
+            NodeKind::Branch(children) => children,
+            NodeKind::Leaf { .. } => unreachable!(),
+        }
+    }
+
+    /// Replaces `old` with `new` in the children of `parent`, or as the root.
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        self.nodes[new].parent = parent;
+        let Some(parent) = parent else {
+            self.root = Some(new);
+            return;
+        };
+        if let NodeKind::Branch(children) = &mut self.nodes[parent].kind {
+            let slot = children.iter().position(|&child| child == old).unwrap();
+            children[slot] = new;
+        }
+    }
+
+    /// Links a leaf into the tree, next to the node which grows the tree the least according
+    /// to the surface area heuristic.
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(mut sibling) = self.root else {
+            self.nodes[leaf].parent = None;
+            self.root = Some(leaf);
+            return;
+        };
+
+        let bounds = self.nodes[leaf].bounds;
+        while let NodeKind::Branch(children) = self.nodes[sibling].kind {
+            let area = self.nodes[sibling].bounds.area();
+            let combined_area = self.nodes[sibling].bounds.union(&bounds).area();
+            // The cost of pairing the leaf with this node, and the cost that every node below
+            // it inherits from growing it.
+            let cost = 2.0 * combined_area;
+            let inherited_cost = 2.0 * (combined_area - area);
+            let child_cost = |child: usize| {
+                let child = &self.nodes[child];
+                let area = child.bounds.union(&bounds).area();
+                match child.kind {
+                    NodeKind::Leaf { .. } => area + inherited_cost,
+                    NodeKind::Branch(_) => area - child.bounds.area() + inherited_cost,
+                }
+            };
+            let (cost_0, cost_1) = (child_cost(children[0]), child_cost(children[1]));
+            if cost < cost_0 && cost < cost_1 {
+                break;
+            }
+            sibling = if cost_0 < cost_1 {
+                children[0]
+            } else {
+                children[1]
+            };
+        }
+
+        let old_parent = self.nodes[sibling].parent;
+        let parent = self.allocate(Node {
+            bounds: self.nodes[sibling].bounds.union(&bounds),
+            parent: old_parent,
+            height: self.nodes[sibling].height + 1,
+            kind: NodeKind::Branch([sibling, leaf]),
+        });
+        self.replace_child(old_parent, sibling, parent);
+        self.nodes[sibling].parent = Some(parent);
+        self.nodes[leaf].parent = Some(parent);
+        self.refit(old_parent);
+    }
+
+    /// Unlinks a leaf from the tree, replacing its parent with its sibling.
+    fn remove_leaf(&mut self, leaf: usize) {
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return;
+        };
+        let [first, second] = self.children(parent);
+        let sibling = if first == leaf { second } else { first };
+        let grandparent = self.nodes[parent].parent;
+        self.replace_child(grandparent, parent, sibling);
+        self.free.push(parent);
+        self.refit(grandparent);
+    }
+
+    /// Rebalances the ancestors of a changed node, starting with `index`, and updates their
+    /// bounds and heights.
+    fn refit(&mut self, mut index: Option<usize>) {
+        while let Some(node) = index {
+            let node = self.balance(node);
+            self.update_branch(node);
+            index = self.nodes[node].parent;
+        }
+    }
+
+    fn update_branch(&mut self, index: usize) {
+        let [first, second] = self.children(index);
+        let (first, second) = (&self.nodes[first], &self.nodes[second]);
+        let bounds = first.bounds.union(&second.bounds);
+        let height = first.height.max(second.height) + 1;
+        let node = &mut self.nodes[index];
+        node.bounds = bounds;
+        node.height = height;
+    }
+
+    /// Rotates the taller child of a branch up in its place if the heights of its children
+    /// differ by more than one, returning the node now in its place.
+    fn balance(&mut self, index: usize) -> usize {
+        let NodeKind::Branch(children) = self.nodes[index].kind else {
+            return index;
+        };
+        let heights = children.map(|child| self.nodes[child].height);
+        let taller = match heights[0].abs_diff(heights[1]) {
+            0 | 1 => return index,
+            _ if heights[0] > heights[1] => 0,
+            _ => 1,
+        };
+
+        // The taller child takes the place of the branch, and the branch takes the place of
+        // the shorter child of the taller one.
+        let up = children[taller];
+        let [first, second] = self.children(up);
+        let (kept, moved) = if self.nodes[first].height > self.nodes[second].height {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        self.replace_child(self.nodes[index].parent, index, up);
+        self.nodes[up].kind = NodeKind::Branch([index, kept]);
+        self.nodes[index].parent = Some(up);
+        if let NodeKind::Branch(children) = &mut self.nodes[index].kind {
+            children[taller] = moved;
+        }
+        self.nodes[moved].parent = Some(index);
+        self.update_branch(index);
+        self.update_branch(up);
+        up
+    }
+}
+
+/// The distance along a ray to where it enters an oriented box, or `0.0` if it starts inside.
+fn ray_obb_distance(
+    origin: Vec3A,
+    direction: Vec3A,
+    aabb: &Aabb,
+    world_to_local: &Affine3A,
+    max_distance: f32,
+) -> Option<f32> {
+    // The transform is affine, so distances along the ray are the same in local space.
+    let origin = world_to_local.transform_point3a(origin);
+    let direction = world_to_local.transform_vector3a(direction);
+    Bounds {
+        min: aabb.min(),
+        max: aabb.max(),
+    }
+    .ray_distance(origin, direction.recip(), max_distance)
+}
+
+/// An axis-aligned box in world space.
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: Vec3A,
+    max: Vec3A,
+}
+
+impl Bounds {
+    fn from_obb(aabb: &Aabb, local_to_world: &Affine3A) -> Self {
+        let center = local_to_world.transform_point3a(aabb.center);
+        let matrix = &local_to_world.matrix3;
+        let half_extents = matrix.x_axis.abs() * aabb.half_extents.x
+            + matrix.y_axis.abs() * aabb.half_extents.y
+            + matrix.z_axis.abs() * aabb.half_extents.z;
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    fn to_aabb(self) -> Aabb {
+        Aabb {
+            center: (self.min + self.max) * 0.5,
+            half_extents: (self.max - self.min) * 0.5,
+        }
+    }
+
+    /// Returns these bounds grown on every side by `fraction` of their largest dimension.
+    fn grow(self, fraction: f32) -> Self {
+        let margin = Vec3A::splat((self.max - self.min).max_element() * fraction);
+        Self {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    fn contains(&self, other: &Self) -> bool {
+        self.min.cmple(other.min).all() && self.max.cmpge(other.max).all()
+    }
+
+    /// Half the surface area of the box.
+    fn area(&self) -> f32 {
+        let size = self.max - self.min;
+        size.x * size.y + size.y * size.z + size.z * size.x
+    }
+
+    fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        let closest = sphere.center.clamp(self.min, self.max);
+        closest.distance_squared(sphere.center) <= sphere.radius * sphere.radius
+    }
+
+    /// The distance along a ray to where it enters the box, or `0.0` if it starts inside.
+    fn ray_distance(
+        &self,
+        origin: Vec3A,
+        inverse_direction: Vec3A,
+        max_distance: f32,
+    ) -> Option<f32> {
+        let t_0 = (self.min - origin) * inverse_direction;
+        let t_1 = (self.max - origin) * inverse_direction;
+        let near = t_0.min(t_1).max_element().max(0.0);
+        let far = t_0.max(t_1).min_element().min(max_distance);
+        (near <= far).then_some(near)
+    }
+}
+
+/// Updates the [`SpatialIndex`] with the entities whose [`Aabb`] or [`GlobalTransform`]
+/// changed, and removes the entities which lost either of them.
+pub fn update_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    changed: Query<
+        (Entity, &Aabb, &GlobalTransform),
+        Or<(Changed<Aabb>, Changed<GlobalTransform>)>,
+    >,
+    mut removed_aabbs: RemovedComponents<Aabb>,
+    mut removed_transforms: RemovedComponents<GlobalTransform>,
+) {
+    for entity in removed_aabbs.read().chain(removed_transforms.read()) {
+        index.remove(entity);
+    }
+    for (entity, aabb, transform) in &changed {
+        index.insert(entity, *aabb, transform);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Mat4, Vec3};
+    use bevy_transform::components::Transform;
+
+    fn unit_cube() -> Aabb {
+        Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5))
+    }
+
+    /// A row of unit cubes along the x axis, one every two units starting at the origin.
+    fn cube_row(count: u32) -> SpatialIndex {
+        let mut index = SpatialIndex::default();
+        for i in 0..count {
+            let transform = GlobalTransform::from_translation(Vec3::X * (i * 2) as f32);
+            index.insert(Entity::from_raw(i), unit_cube(), &transform);
+        }
+        index
+    }
+
+    fn check_tree(index: &SpatialIndex) {
+        fn check(index: &SpatialIndex, node: usize, leaves: &mut usize) -> u32 {
+            match index.nodes[node].kind {
+                NodeKind::Leaf { .. } => {
+                    *leaves += 1;
+                    0
+                }
+                NodeKind::Branch(children) => {
+                    let heights = children.map(|child| {
+                        assert_eq!(index.nodes[child].parent, Some(node));
+                        assert!(index.nodes[node]
+                            .bounds
+                            .contains(&index.nodes[child].bounds));
+                        check(index, child, leaves)
+                    });
+                    assert!(heights[0].abs_diff(heights[1]) <= 1);
+                    assert_eq!(index.nodes[node].height, heights[0].max(heights[1]) + 1);
+                    index.nodes[node].height
+                }
+            }
+        }
+        let mut leaves = 0;
+        if let Some(root) = index.root {
+            assert_eq!(index.nodes[root].parent, None);
+            check(index, root, &mut leaves);
+        }
+        assert_eq!(leaves, index.len());
+    }
+
+    #[test]
+    fn insert_move_remove() {
+        let mut index = cube_row(100);
+        check_tree(&index);
+        assert_eq!(index.len(), 100);
+
+        for i in 0..100 {
+            let transform = GlobalTransform::from_translation(Vec3::new(0.0, i as f32 * 2.0, 0.0));
+            index.insert(Entity::from_raw(i), unit_cube(), &transform);
+        }
+        check_tree(&index);
+        assert_eq!(index.len(), 100);
+
+        for i in (0..100).step_by(2) {
+            assert!(index.remove(Entity::from_raw(i)));
+        }
+        assert!(!index.remove(Entity::from_raw(0)));
+        check_tree(&index);
+        assert_eq!(index.len(), 50);
+        assert!(index.contains(Entity::from_raw(1)));
+        assert!(!index.contains(Entity::from_raw(2)));
+    }
+
+    #[test]
+    fn ray() {
+        let index = cube_row(10);
+        let ray = Ray3d::new(Vec3::new(-10.0, 0.0, 0.0), Vec3::X);
+        assert_eq!(index.cast_ray(ray, 100.0), Some((Entity::from_raw(0), 9.5)));
+        assert_eq!(index.cast_ray(ray, 9.0), None);
+
+        let hits = index.intersect_ray(ray, 14.0);
+        assert_eq!(
+            hits,
+            vec![
+                (Entity::from_raw(0), 9.5),
+                (Entity::from_raw(1), 11.5),
+                (Entity::from_raw(2), 13.5),
+            ]
+        );
+
+        // The ray passes between the cubes.
+        let ray = Ray3d::new(Vec3::new(1.0, 10.0, 0.0), Vec3::NEG_Y);
+        assert_eq!(index.cast_ray(ray, 100.0), None);
+
+        // A rotated cube is hit on its corner.
+        let mut index = SpatialIndex::default();
+        let transform = Transform::from_rotation(bevy_math::Quat::from_rotation_z(
+            std::f32::consts::FRAC_PI_4,
+        ));
+        index.insert(Entity::from_raw(0), unit_cube(), &transform.into());
+        let ray = Ray3d::new(Vec3::new(0.0, 10.0, 0.0), Vec3::NEG_Y);
+        let (_, distance) = index.cast_ray(ray, 100.0).unwrap();
+        assert!((distance - (10.0 - std::f32::consts::FRAC_1_SQRT_2)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sphere() {
+        let index = cube_row(10);
+        let sphere = Sphere {
+            center: Vec3A::new(4.0, 0.0, 0.0),
+            radius: 1.6,
+        };
+        let mut entities: Vec<Entity> = index.intersect_sphere(&sphere).collect();
+        entities.sort();
+        assert_eq!(
+            entities,
+            vec![
+                Entity::from_raw(1),
+                Entity::from_raw(2),
+                Entity::from_raw(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn frustum() {
+        let index = cube_row(10);
+        // A box from x = 5 to 11 seen along the negative z axis.
+        let projection = Mat4::orthographic_rh(-3.0, 3.0, -1.0, 1.0, 0.0, 10.0);
+        let frustum = Frustum::from_view_projection(
+            &(projection * Mat4::from_translation(Vec3::new(-8.0, 0.0, -5.0))),
+        );
+        let mut entities: Vec<Entity> = index.intersect_frustum(&frustum).collect();
+        entities.sort();
+        assert_eq!(
+            entities,
+            vec![
+                Entity::from_raw(3),
+                Entity::from_raw(4),
+                Entity::from_raw(5)
+            ]
+        );
+    }
+}
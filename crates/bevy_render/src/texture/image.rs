@@ -11,7 +11,7 @@ use crate::{
     renderer::{RenderDevice, RenderQueue},
     texture::BevyDefault,
 };
-use bevy_asset::Asset;
+use bevy_asset::{Asset, AssetMemoryUsage};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{lifetimeless::SRes, Resource, SystemParamItem};
 use bevy_math::{UVec2, Vec2};
@@ -468,6 +468,12 @@ impl Default for Image {
     }
 }
 
+impl AssetMemoryUsage for Image {
+    fn memory_usage(&self) -> usize {
+        self.data.len()
+    }
+}
+
 impl Image {
     /// Creates a new image from raw binary data and the corresponding metadata.
     ///
@@ -5,7 +5,7 @@ pub use render_layers::*;
 
 use bevy_app::{Plugin, PostUpdate};
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, query::Allows};
 use bevy_hierarchy::{Children, Parent};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_transform::{components::GlobalTransform, TransformSystem};
@@ -293,24 +293,43 @@ pub fn update_frusta<T: Component + CameraProjection + Send + Sync + 'static>(
 
 fn visibility_propagate_system(
     changed: Query<
-        (Entity, &Visibility, Option<&Parent>, Option<&Children>),
-        (With<InheritedVisibility>, Changed<Visibility>),
+        Entity,
+        (
+            With<InheritedVisibility>,
+            Allows<Disabled>,
+            Or<(Changed<Visibility>, Added<Disabled>)>,
+        ),
+    >,
+    mut enabled: RemovedComponents<Disabled>,
+    parent_query: Query<&Parent, Allows<Disabled>>,
+    mut visibility_query: Query<(&Visibility, Has<Disabled>, &mut InheritedVisibility)>,
+    children_query: Query<
+        &Children,
+        (
+            With<Visibility>,
+            With<InheritedVisibility>,
+            Allows<Disabled>,
+        ),
     >,
-    mut visibility_query: Query<(&Visibility, &mut InheritedVisibility)>,
-    children_query: Query<&Children, (With<Visibility>, With<InheritedVisibility>)>,
 ) {
-    for (entity, visibility, parent, children) in &changed {
-        let is_visible = match visibility {
-            Visibility::Visible => true,
-            Visibility::Hidden => false,
-            // fall back to true if no parent is found or parent lacks components
-            Visibility::Inherited => parent
-                .and_then(|p| visibility_query.get(p.get()).ok())
-                .map_or(true, |(_, x)| x.get()),
+    for entity in changed.iter().chain(enabled.read()) {
+        // fall back to true if no parent is found or parent lacks components
+        let parent_is_visible = parent_query
+            .get(entity)
+            .ok()
+            .and_then(|p| visibility_query.get(p.get()).ok())
+            .map_or(true, |(_, _, x)| x.get());
+        // Entities enabled this frame may have been despawned since.
+        let Ok((visibility, disabled, mut inherited_visibility)) = visibility_query.get_mut(entity)
+        else {
+            continue;
         };
-        let (_, mut inherited_visibility) = visibility_query
-            .get_mut(entity)
-            .expect("With<InheritedVisibility> ensures this query will return a value");
+        let is_visible = !disabled
+            && match visibility {
+                Visibility::Visible => true,
+                Visibility::Hidden => false,
+                Visibility::Inherited => parent_is_visible,
+            };
 
         // Only update the visibility if it has changed.
         // This will also prevent the visibility from propagating multiple times in the same frame
@@ -319,7 +338,7 @@ fn visibility_propagate_system(
             inherited_visibility.0 = is_visible;
 
             // Recursively update the visibility of each child.
-            for &child in children.into_iter().flatten() {
+            for &child in children_query.get(entity).ok().into_iter().flatten() {
                 let _ =
                     propagate_recursive(is_visible, child, &mut visibility_query, &children_query);
             }
@@ -330,20 +349,29 @@ fn visibility_propagate_system(
 fn propagate_recursive(
     parent_is_visible: bool,
     entity: Entity,
-    visibility_query: &mut Query<(&Visibility, &mut InheritedVisibility)>,
-    children_query: &Query<&Children, (With<Visibility>, With<InheritedVisibility>)>,
+    visibility_query: &mut Query<(&Visibility, Has<Disabled>, &mut InheritedVisibility)>,
+    children_query: &Query<
+        &Children,
+        (
+            With<Visibility>,
+            With<InheritedVisibility>,
+            Allows<Disabled>,
+        ),
+    >,
     // BLOCKED: https://github.com/rust-lang/rust/issues/31436
     // We use a result here to use the `?` operator. Ideally we'd use a try block instead
 ) -> Result<(), ()> {
     // Get the visibility components for the current entity.
     // If the entity does not have the required components, just return early.
-    let (visibility, mut inherited_visibility) = visibility_query.get_mut(entity).map_err(drop)?;
+    let (visibility, disabled, mut inherited_visibility) =
+        visibility_query.get_mut(entity).map_err(drop)?;
 
-    let is_visible = match visibility {
-        Visibility::Visible => true,
-        Visibility::Hidden => false,
-        Visibility::Inherited => parent_is_visible,
-    };
+    let is_visible = !disabled
+        && match visibility {
+            Visibility::Visible => true,
+            Visibility::Hidden => false,
+            Visibility::Inherited => parent_is_visible,
+        };
 
     // Only update the visibility if it has changed.
     if inherited_visibility.get() != is_visible {
@@ -361,7 +389,7 @@ fn propagate_recursive(
 /// Resets the view visibility of every entity.
 /// Entities that are visible will be marked as such later this frame
 /// by a [`VisibilitySystems::CheckVisibility`] system.
-fn reset_view_visibility(mut query: Query<&mut ViewVisibility>) {
+fn reset_view_visibility(mut query: Query<&mut ViewVisibility, Allows<Disabled>>) {
     for mut view_visibility in &mut query {
         // NOTE: We do not use `set_if_neq` here, as we don't care about
         // change detection for view visibility, and adding a branch to every
@@ -713,6 +741,44 @@ mod test {
         assert!(child_visible);
     }
 
+    #[test]
+    fn visibility_propagation_disabled() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(visibility_propagate_system);
+
+        let parent = world.spawn(VisibilityBundle::default()).id();
+        let child = world.spawn(VisibilityBundle::default()).id();
+        world.entity_mut(parent).push_children(&[child]);
+        schedule.run(&mut world);
+
+        let is_visible = |world: &World, entity: Entity| {
+            world
+                .entity(entity)
+                .get::<InheritedVisibility>()
+                .unwrap()
+                .get()
+        };
+        assert!(is_visible(&world, parent));
+        assert!(is_visible(&world, child));
+
+        world.entity_mut(parent).insert(Disabled);
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, parent), "a disabled entity is hidden");
+        assert!(
+            !is_visible(&world, child),
+            "an inheriting child of a disabled entity is hidden"
+        );
+
+        world.entity_mut(parent).remove::<Disabled>();
+        schedule.run(&mut world);
+        assert!(
+            is_visible(&world, parent),
+            "an enabled entity is visible again"
+        );
+        assert!(is_visible(&world, child));
+    }
+
     #[test]
     fn ensure_visibility_enum_size() {
         use std::mem;
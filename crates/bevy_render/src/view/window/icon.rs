@@ -0,0 +1,103 @@
+use crate::texture::Image;
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use bevy_utils::{tracing::warn, HashSet};
+use bevy_window::WindowIcon;
+use wgpu::TextureFormat;
+
+/// Sets the [`WindowIcon`] of a window from an [`Image`] asset.
+///
+/// The icon is updated once the image is loaded, and again whenever it is modified.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct WindowIconImage(pub Handle<Image>);
+
+/// Converts the [`Image`] of each [`WindowIconImage`] into a [`WindowIcon`] when either of them
+/// changes.
+pub fn update_window_icon_images(
+    mut commands: Commands,
+    mut image_asset_events: EventReader<AssetEvent<Image>>,
+    images: Res<Assets<Image>>,
+    windows: Query<(Entity, Ref<WindowIconImage>)>,
+) {
+    let changed_image_handles: HashSet<&AssetId<Image>> = image_asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } | AssetEvent::Added { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, icon) in &windows {
+        if !icon.is_changed() && !changed_image_handles.contains(&icon.0.id()) {
+            continue;
+        }
+        let Some(image) = images.get(&icon.0) else {
+            continue;
+        };
+        let Some(window_icon) = image_to_window_icon(image) else {
+            warn!(
+                "Could not use an image with the {:?} format as a window icon",
+                image.texture_descriptor.format
+            );
+            continue;
+        };
+        commands.entity(entity).insert(window_icon);
+    }
+}
+
+/// Converts the pixels of an [`Image`] to the RGBA values of a [`WindowIcon`], if its format
+/// can be converted.
+fn image_to_window_icon(image: &Image) -> Option<WindowIcon> {
+    let rgba = image.convert(TextureFormat::Rgba8UnormSrgb)?;
+    Some(WindowIcon {
+        rgba: rgba.data,
+        width: image.width(),
+        height: image.height(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::{Extent3d, TextureDimension};
+
+    fn image(pixel: &[u8], format: TextureFormat) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            pixel,
+            format,
+        )
+    }
+
+    #[test]
+    fn image_to_rgba_icon() {
+        let icon =
+            image_to_window_icon(&image(&[255, 0, 0, 128], TextureFormat::Rgba8UnormSrgb)).unwrap();
+        assert_eq!((icon.width, icon.height), (2, 1));
+        assert_eq!(icon.rgba, [255, 0, 0, 128, 255, 0, 0, 128]);
+
+        // The blue and red channels are swapped.
+        let icon =
+            image_to_window_icon(&image(&[255, 0, 0, 255], TextureFormat::Bgra8UnormSrgb)).unwrap();
+        assert_eq!(icon.rgba, [0, 0, 255, 255, 0, 0, 255, 255]);
+
+        let icon = image_to_window_icon(&image(&[64], TextureFormat::R8Unorm)).unwrap();
+        assert_eq!(icon.rgba, [64, 64, 64, 255, 64, 64, 64, 255]);
+    }
+
+    #[test]
+    fn unsupported_image_format() {
+        let pixel = [0; 16];
+        assert_eq!(
+            image_to_window_icon(&image(&pixel, TextureFormat::Rgba32Float)),
+            None
+        );
+    }
+}
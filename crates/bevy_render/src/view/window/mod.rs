@@ -3,10 +3,11 @@ use crate::{
         BindGroupEntries, PipelineCache, SpecializedRenderPipelines, SurfaceTexture, TextureView,
     },
     renderer::{RenderAdapter, RenderDevice, RenderInstance},
-    texture::TextureFormatPixelInfo,
+    texture::{Image, TextureFormatPixelInfo},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::Assets;
 use bevy_ecs::prelude::*;
 use bevy_utils::{default, tracing::debug, HashMap, HashSet};
 use bevy_window::{
@@ -18,8 +19,11 @@ use std::{
 };
 use wgpu::{BufferUsages, TextureFormat, TextureUsages, TextureViewDescriptor};
 
+mod icon;
 pub mod screenshot;
 
+pub use icon::*;
+
 use screenshot::{
     ScreenshotManager, ScreenshotPlugin, ScreenshotPreparedState, ScreenshotToScreenPipeline,
 };
@@ -34,7 +38,12 @@ pub struct WindowRenderPlugin;
 
 impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ScreenshotPlugin);
+        app.add_plugins(ScreenshotPlugin)
+            .register_type::<WindowIconImage>()
+            .add_systems(
+                PostUpdate,
+                update_window_icon_images.run_if(resource_exists::<Assets<Image>>()),
+            );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
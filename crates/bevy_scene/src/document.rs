@@ -0,0 +1,1696 @@
+//! A terser scene format, meant to be written by hand.
+//!
+//! ```ron
+//! (
+//!   resources: {
+//!     "Score": (value: 10),
+//!   },
+//!   entities: [
+//!     (
+//!       name: "Player",
+//!       components: {
+//!         "Transform": (translation: (x: 1.0, y: 0.0, z: 2.0)),
+//!         "Handle<Mesh>": "models/player.glb#Mesh0/Primitive0",
+//!         "Target": ("Enemy"),
+//!       },
+//!       children: [
+//!         (
+//!           name: "Sword",
+//!           components: {
+//!             "Transform": (translation: (x: 0.5, y: 0.0, z: 0.0)),
+//!           },
+//!         ),
+//!       ],
+//!     ),
+//!     (
+//!       name: "Enemy",
+//!     ),
+//!   ],
+//! )
+//! ```
+//!
+//! Compared to the format of the [`SceneSerializer`](crate::serde::SceneSerializer):
+//! - types are written with their short type path when it isn't ambiguous,
+//! - the fields of components and resources equal to their [`Default`] value are omitted, and
+//!   can be omitted by hand,
+//! - entities are identified by their [`Name`], which is used to reference them in components,
+//! - children are nested in their parent instead of being linked with the [`Parent`] and
+//!   [`Children`] components,
+//! - handles are written as the path of their asset, which is loaded with the scene.
+//!
+//! Documents are loaded from `.scene.ron` files by the
+//! [`SceneDocumentLoader`](crate::SceneDocumentLoader).
+
+use crate::{
+    serde::{ENTITY_FIELD_COMPONENTS, SCENE_ENTITIES, SCENE_RESOURCES},
+    DynamicEntity, DynamicScene,
+};
+use bevy_asset::{LoadContext, ReflectHandle};
+use bevy_core::Name;
+use bevy_ecs::entity::Entity;
+use bevy_hierarchy::{Children, Parent};
+use bevy_reflect::{
+    serde::{SerializationData, TypedReflectDeserializer, TypedReflectSerializer},
+    std_traits::ReflectDefault,
+    DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, DynamicVariant, EnumInfo, FromReflect, Map, Reflect, ReflectFromReflect,
+    ReflectRef, Struct, TypeInfo, TypeRegistration, TypeRegistry, TypeRegistryArc, VariantInfo,
+    VariantType,
+};
+use bevy_utils::{EntityHashMap, HashMap, HashSet};
+use serde::{
+    de::{DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor},
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{any::TypeId, cell::RefCell, fmt::Formatter};
+
+/// Name of the serialized scene document struct type.
+pub const DOCUMENT_STRUCT: &str = "SceneDocument";
+/// Name of the serialized entity document struct type.
+pub const ENTITY_DOCUMENT_STRUCT: &str = "EntityDocument";
+/// Name of the serialized name field in an entity document struct.
+pub const ENTITY_DOCUMENT_NAME: &str = "name";
+/// Name of the serialized children field in an entity document struct.
+pub const ENTITY_DOCUMENT_CHILDREN: &str = "children";
+
+/// Handles serialization of a scene as a [document](self).
+///
+/// Serialization fails if a component references an entity without a [`Name`], or with a name
+/// shared by several entities of the scene, or if a handle doesn't have an asset path.
+pub struct SceneDocumentSerializer<'a> {
+    /// The scene to serialize.
+    pub scene: &'a DynamicScene,
+    /// Type registry in which the components and resources types used in the scene are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> SceneDocumentSerializer<'a> {
+    /// Creates a scene document serializer.
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistryArc) -> Self {
+        SceneDocumentSerializer { scene, registry }
+    }
+}
+
+impl<'a> Serialize for SceneDocumentSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry.read();
+        let context = SerializerContext::new(self.scene, &registry);
+        let mut state = serializer.serialize_struct(DOCUMENT_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &ComponentsSerializer {
+                components: self.scene.resources.iter().map(|r| &**r).collect(),
+                context: &context,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_ENTITIES,
+            &EntitiesSerializer {
+                entities: &context.roots,
+                context: &context,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// The names and hierarchy of the entities of a scene being serialized.
+struct SerializerContext<'a> {
+    registry: &'a TypeRegistry,
+    entities: EntityHashMap<Entity, &'a DynamicEntity>,
+    names: EntityHashMap<Entity, String>,
+    /// The names shared by several entities, which can't be referenced.
+    ambiguous_names: HashSet<String>,
+    parents: EntityHashMap<Entity, Entity>,
+    children: EntityHashMap<Entity, Vec<Entity>>,
+    roots: Vec<Entity>,
+}
+
+impl<'a> SerializerContext<'a> {
+    fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry) -> Self {
+        let entities: EntityHashMap<Entity, &DynamicEntity> = scene
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity))
+            .collect();
+
+        let mut names = EntityHashMap::default();
+        let mut used_names = HashSet::default();
+        let mut ambiguous_names = HashSet::default();
+        let mut parents = EntityHashMap::default();
+        for entity in &scene.entities {
+            if let Some(name) = find_component::<Name>(entity) {
+                if !used_names.insert(name.as_str().to_string()) {
+                    ambiguous_names.insert(name.as_str().to_string());
+                }
+                names.insert(entity.entity, name.as_str().to_string());
+            }
+            if let Some(parent) = find_component::<Parent>(entity) {
+                if entities.contains_key(&parent.get()) {
+                    parents.insert(entity.entity, parent.get());
+                }
+            }
+        }
+
+        let mut children: EntityHashMap<Entity, Vec<Entity>> = EntityHashMap::default();
+        let mut roots = Vec::new();
+        for entity in &scene.entities {
+            match parents.get(&entity.entity) {
+                Some(parent) => children.entry(*parent).or_default().push(entity.entity),
+                None => roots.push(entity.entity),
+            }
+        }
+        // Keep the order of the children of their parent
+        for (parent, children) in &mut children {
+            if let Some(order) = find_component::<Children>(entities[parent]) {
+                children.sort_by_key(|child| {
+                    order
+                        .iter()
+                        .position(|entity| entity == child)
+                        .unwrap_or(usize::MAX)
+                });
+            }
+        }
+
+        Self {
+            registry,
+            entities,
+            names,
+            ambiguous_names,
+            parents,
+            children,
+            roots,
+        }
+    }
+
+    /// Returns the name used to reference `entity`, if it has a unique one.
+    fn reference(&self, entity: Entity) -> Option<&str> {
+        self.names
+            .get(&entity)
+            .filter(|name| !self.ambiguous_names.contains(*name))
+            .map(String::as_str)
+    }
+
+    /// Returns the short type path of a type if it isn't ambiguous, or its full type path.
+    fn type_path(&self, type_info: &'static TypeInfo) -> &'static str {
+        let short_type_path = type_info.type_path_table().short_path();
+        if self
+            .registry
+            .get_with_short_type_path(short_type_path)
+            .is_some()
+        {
+            short_type_path
+        } else {
+            type_info.type_path()
+        }
+    }
+}
+
+/// Returns the component `T` of `entity`, if it has one.
+fn find_component<T: FromReflect>(entity: &DynamicEntity) -> Option<T> {
+    entity
+        .components
+        .iter()
+        .find(|component| {
+            component
+                .get_represented_type_info()
+                .is_some_and(|type_info| type_info.type_id() == TypeId::of::<T>())
+        })
+        .and_then(|component| T::from_reflect(&**component))
+}
+
+struct EntitiesSerializer<'a> {
+    entities: &'a [Entity],
+    context: &'a SerializerContext<'a>,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for &entity in self.entities {
+            state.serialize_element(&EntitySerializer {
+                entity,
+                context: self.context,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct EntitySerializer<'a> {
+    entity: Entity,
+    context: &'a SerializerContext<'a>,
+}
+
+impl<'a> Serialize for EntitySerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let has_parent = self.context.parents.contains_key(&self.entity);
+        // The name and the hierarchy are written as the fields of the entity
+        let components = self.context.entities[&self.entity]
+            .components
+            .iter()
+            .filter(|component| {
+                let Some(type_info) = component.get_represented_type_info() else {
+                    return true;
+                };
+                let type_id = type_info.type_id();
+                type_id != TypeId::of::<Name>()
+                    && type_id != TypeId::of::<Children>()
+                    && !(has_parent && type_id == TypeId::of::<Parent>())
+            })
+            .map(|component| &**component)
+            .collect();
+        let children = self
+            .context
+            .children
+            .get(&self.entity)
+            .map_or(&[][..], Vec::as_slice);
+
+        let mut state = serializer.serialize_struct(ENTITY_DOCUMENT_STRUCT, 3)?;
+        match self.context.names.get(&self.entity) {
+            Some(name) => state.serialize_field(ENTITY_DOCUMENT_NAME, name)?,
+            None => state.skip_field(ENTITY_DOCUMENT_NAME)?,
+        }
+        state.serialize_field(
+            ENTITY_FIELD_COMPONENTS,
+            &ComponentsSerializer {
+                components,
+                context: self.context,
+            },
+        )?;
+        if children.is_empty() {
+            state.skip_field(ENTITY_DOCUMENT_CHILDREN)?;
+        } else {
+            state.serialize_field(
+                ENTITY_DOCUMENT_CHILDREN,
+                &EntitiesSerializer {
+                    entities: children,
+                    context: self.context,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Serializes components or resources as a map of type path to value, without the fields equal
+/// to the [`Default`] value of their type.
+struct ComponentsSerializer<'a> {
+    components: Vec<&'a dyn Reflect>,
+    context: &'a SerializerContext<'a>,
+}
+
+impl<'a> Serialize for ComponentsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.components.len()))?;
+        for &component in &self.components {
+            let type_info = component.get_represented_type_info().ok_or_else(|| {
+                serde::ser::Error::custom(format_args!(
+                    "type `{}` doesn't have type info",
+                    component.reflect_type_path()
+                ))
+            })?;
+            let default = self
+                .context
+                .registry
+                .get_type_data::<ReflectDefault>(type_info.type_id())
+                .map(ReflectDefault::default);
+            state.serialize_entry(
+                self.context.type_path(type_info),
+                &ValueSerializer {
+                    value: component,
+                    default: default.as_deref(),
+                    context: self.context,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Serializes a reflected value, with entities as their name and handles as their asset path.
+struct ValueSerializer<'a> {
+    value: &'a dyn Reflect,
+    /// The default value of `value`, whose equal fields are omitted.
+    default: Option<&'a dyn Reflect>,
+    context: &'a SerializerContext<'a>,
+}
+
+impl<'a> ValueSerializer<'a> {
+    fn field(&self, value: &'a dyn Reflect, default: Option<&'a dyn Reflect>) -> Self {
+        ValueSerializer {
+            value,
+            default,
+            context: self.context,
+        }
+    }
+}
+
+impl<'a> Serialize for ValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        let registry = self.context.registry;
+        let type_info = self.value.get_represented_type_info().ok_or_else(|| {
+            Error::custom(format_args!(
+                "type `{}` doesn't have type info",
+                self.value.reflect_type_path()
+            ))
+        })?;
+
+        if let Some(entity) = self.value.as_any().downcast_ref::<Entity>() {
+            let name = self.context.reference(*entity).ok_or_else(|| {
+                Error::custom(format_args!(
+                    "entity {entity:?} is referenced but doesn't have a unique name in the scene"
+                ))
+            })?;
+            return serializer.serialize_str(name);
+        }
+        if let Some(reflect_handle) = registry.get_type_data::<ReflectHandle>(type_info.type_id()) {
+            let handle = registry
+                .get_type_data::<ReflectFromReflect>(type_info.type_id())
+                .and_then(|from_reflect| from_reflect.from_reflect(self.value))
+                .and_then(|handle| reflect_handle.downcast_handle_untyped(handle.as_any()))
+                .ok_or_else(|| {
+                    Error::custom(format_args!("invalid handle `{}`", type_info.type_path()))
+                })?;
+            let path = handle.path().ok_or_else(|| {
+                Error::custom(format_args!(
+                    "handle `{}` doesn't have an asset path",
+                    type_info.type_path()
+                ))
+            })?;
+            return serializer.serialize_str(&path.to_string());
+        }
+
+        let ident = type_info.type_path_table().ident().unwrap_or_default();
+        let serialization_data = registry
+            .get(type_info.type_id())
+            .and_then(|registration| registration.data::<SerializationData>());
+        let is_skipped =
+            |index| serialization_data.is_some_and(|data| data.is_field_skipped(index));
+
+        match self.value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                let TypeInfo::Struct(struct_info) = type_info else {
+                    return Err(Error::custom(format_args!(
+                        "expected struct info for `{}`",
+                        type_info.type_path()
+                    )));
+                };
+                let default = match self.default.map(Reflect::reflect_ref) {
+                    Some(ReflectRef::Struct(default)) => Some(default),
+                    _ => None,
+                };
+                let mut state = serializer.serialize_struct(ident, value.field_len())?;
+                for (index, field) in value.iter_fields().enumerate() {
+                    let Some(field_info) = value
+                        .name_at(index)
+                        .and_then(|name| struct_info.field(name))
+                    else {
+                        continue;
+                    };
+                    let name = field_info.name();
+                    let default_field = default.and_then(|default| default.field(name));
+                    let is_default = default_field
+                        .and_then(|default| default.reflect_partial_eq(field))
+                        .unwrap_or(false);
+                    if is_default || struct_info.index_of(name).is_some_and(is_skipped) {
+                        state.skip_field(name)?;
+                    } else {
+                        state.serialize_field(name, &self.field(field, default_field))?;
+                    }
+                }
+                state.end()
+            }
+            ReflectRef::TupleStruct(value) => {
+                let default = match self.default.map(Reflect::reflect_ref) {
+                    Some(ReflectRef::TupleStruct(default)) => Some(default),
+                    _ => None,
+                };
+                let fields: Vec<_> = value
+                    .iter_fields()
+                    .enumerate()
+                    .filter(|(index, _)| !is_skipped(*index))
+                    .collect();
+                let mut state = serializer.serialize_tuple_struct(ident, fields.len())?;
+                for (index, field) in fields {
+                    let default_field = default.and_then(|default| default.field(index));
+                    state.serialize_field(&self.field(field, default_field))?;
+                }
+                state.end()
+            }
+            ReflectRef::Tuple(value) => {
+                let default = match self.default.map(Reflect::reflect_ref) {
+                    Some(ReflectRef::Tuple(default)) => Some(default),
+                    _ => None,
+                };
+                let mut state = serializer.serialize_tuple(value.field_len())?;
+                for (index, field) in value.iter_fields().enumerate() {
+                    let default_field = default.and_then(|default| default.field(index));
+                    state.serialize_element(&self.field(field, default_field))?;
+                }
+                state.end()
+            }
+            ReflectRef::List(value) => {
+                let mut state = serializer.serialize_seq(Some(value.len()))?;
+                for item in value.iter() {
+                    state.serialize_element(&self.field(item, None))?;
+                }
+                state.end()
+            }
+            ReflectRef::Array(value) => {
+                let mut state = serializer.serialize_tuple(value.len())?;
+                for item in value.iter() {
+                    state.serialize_element(&self.field(item, None))?;
+                }
+                state.end()
+            }
+            ReflectRef::Map(value) => {
+                let mut state = serializer.serialize_map(Some(value.len()))?;
+                for (key, value) in value.iter() {
+                    state.serialize_entry(&self.field(key, None), &self.field(value, None))?;
+                }
+                state.end()
+            }
+            ReflectRef::Enum(value) => {
+                let TypeInfo::Enum(enum_info) = type_info else {
+                    return Err(Error::custom(format_args!(
+                        "expected enum info for `{}`",
+                        type_info.type_path()
+                    )));
+                };
+                let variant_index = value.variant_index() as u32;
+                let variant_info = enum_info.variant(value.variant_name()).ok_or_else(|| {
+                    Error::custom(format_args!(
+                        "unknown variant `{}` of `{}`",
+                        value.variant_name(),
+                        type_info.type_path()
+                    ))
+                })?;
+                let variant_name = variant_info.name();
+                match value.variant_type() {
+                    VariantType::Unit if is_option(enum_info) => serializer.serialize_none(),
+                    VariantType::Unit => {
+                        serializer.serialize_unit_variant(ident, variant_index, variant_name)
+                    }
+                    VariantType::Tuple if value.field_len() == 1 => {
+                        let field = self.field(value.field_at(0).unwrap(), None);
+                        if is_option(enum_info) {
+                            serializer.serialize_some(&field)
+                        } else {
+                            serializer.serialize_newtype_variant(
+                                ident,
+                                variant_index,
+                                variant_name,
+                                &field,
+                            )
+                        }
+                    }
+                    VariantType::Tuple => {
+                        let mut state = serializer.serialize_tuple_variant(
+                            ident,
+                            variant_index,
+                            variant_name,
+                            value.field_len(),
+                        )?;
+                        for field in value.iter_fields() {
+                            state.serialize_field(&self.field(field.value(), None))?;
+                        }
+                        state.end()
+                    }
+                    VariantType::Struct => {
+                        let VariantInfo::Struct(variant_info) = variant_info else {
+                            return Err(Error::custom(format_args!(
+                                "expected struct variant info for `{}`",
+                                enum_info.variant_path(variant_name)
+                            )));
+                        };
+                        let mut state = serializer.serialize_struct_variant(
+                            ident,
+                            variant_index,
+                            variant_name,
+                            value.field_len(),
+                        )?;
+                        for field in value.iter_fields() {
+                            let Some(field_info) =
+                                field.name().and_then(|name| variant_info.field(name))
+                            else {
+                                continue;
+                            };
+                            state.serialize_field(
+                                field_info.name(),
+                                &self.field(field.value(), None),
+                            )?;
+                        }
+                        state.end()
+                    }
+                }
+            }
+            ReflectRef::Value(value) => {
+                TypedReflectSerializer::new(value, registry).serialize(serializer)
+            }
+        }
+    }
+}
+
+fn is_option(enum_info: &EnumInfo) -> bool {
+    enum_info.type_path_table().module_path() == Some("core::option")
+        && enum_info.type_path_table().ident() == Some("Option")
+}
+
+/// Handles deserialization of a scene [document](self).
+pub struct SceneDocumentDeserializer<'a, 'ctx> {
+    /// Type registry in which the components and resources types used in the document are
+    /// registered.
+    pub type_registry: &'a TypeRegistry,
+    /// The context used to load the assets of the handles of the document, which can't be
+    /// deserialized without one.
+    pub load_context: Option<&'a mut LoadContext<'ctx>>,
+}
+
+impl<'a, 'ctx, 'de> DeserializeSeed<'de> for SceneDocumentDeserializer<'a, 'ctx> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let context = DeserializerContext {
+            registry: self.type_registry,
+            load_context: RefCell::new(self.load_context),
+            entities: RefCell::default(),
+        };
+        let resources = deserializer.deserialize_struct(
+            DOCUMENT_STRUCT,
+            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            DocumentVisitor { context: &context },
+        )?;
+        let entities = context.entities.into_inner();
+        entities.check().map_err(Error::custom)?;
+        Ok(DynamicScene {
+            resources,
+            entities: entities.entities,
+        })
+    }
+}
+
+struct DeserializerContext<'a, 'ctx> {
+    registry: &'a TypeRegistry,
+    load_context: RefCell<Option<&'a mut LoadContext<'ctx>>>,
+    entities: RefCell<DocumentEntities>,
+}
+
+/// The entities of a document being deserialized, and the ids of their names.
+#[derive(Default)]
+struct DocumentEntities {
+    entities: Vec<DynamicEntity>,
+    /// The ids of the names of the document, allocated when the name is first used.
+    ids: HashMap<String, Entity>,
+    /// The number of entities defined with each name.
+    definitions: HashMap<String, usize>,
+    /// The names referenced by components, in order.
+    references: Vec<String>,
+    next_id: u32,
+}
+
+impl DocumentEntities {
+    fn allocate(&mut self) -> Entity {
+        let entity = Entity::from_raw(self.next_id);
+        self.next_id += 1;
+        entity
+    }
+
+    /// Returns the id of the entity named `name`, which may be defined later in the document.
+    fn reference(&mut self, name: String) -> Entity {
+        if let Some(&entity) = self.ids.get(&name) {
+            self.references.push(name);
+            return entity;
+        }
+        let entity = self.allocate();
+        self.ids.insert(name.clone(), entity);
+        self.references.push(name);
+        entity
+    }
+
+    /// Returns the id of a new entity, with the id of its name if it was already referenced.
+    fn define(&mut self, name: Option<&str>) -> Entity {
+        let Some(name) = name else {
+            return self.allocate();
+        };
+        let definitions = self.definitions.entry(name.to_string()).or_default();
+        *definitions += 1;
+        if *definitions > 1 {
+            return self.allocate();
+        }
+        match self.ids.get(name) {
+            Some(&entity) => entity,
+            None => {
+                let entity = self.allocate();
+                self.ids.insert(name.to_string(), entity);
+                entity
+            }
+        }
+    }
+
+    /// Checks that the referenced names are the names of a single entity.
+    fn check(&self) -> Result<(), String> {
+        for name in &self.references {
+            match self.definitions.get(name) {
+                Some(1) => {}
+                Some(_) => {
+                    return Err(format!(
+                        "entity `{name}` is referenced but several entities have this name"
+                    ))
+                }
+                None => return Err(format!("entity `{name}` is referenced but doesn't exist")),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An identifier, like the name of a field or of an enum variant.
+struct Ident(String);
+
+impl<'de> Deserialize<'de> for Ident {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct IdentVisitor;
+
+        impl<'de> Visitor<'de> for IdentVisitor {
+            type Value = Ident;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("identifier")
+            }
+
+            fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(Ident(value.to_string()))
+            }
+
+            fn visit_string<E: Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(Ident(value))
+            }
+        }
+
+        deserializer.deserialize_identifier(IdentVisitor)
+    }
+}
+
+struct DocumentVisitor<'s, 'a, 'ctx> {
+    context: &'s DeserializerContext<'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx, 'de> Visitor<'de> for DocumentVisitor<'s, 'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("scene document struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut has_entities = false;
+        while let Some(Ident(key)) = map.next_key()? {
+            match key.as_str() {
+                SCENE_RESOURCES => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentsDeserializer {
+                        context: self.context,
+                    })?);
+                }
+                SCENE_ENTITIES => {
+                    if has_entities {
+                        return Err(Error::duplicate_field(SCENE_ENTITIES));
+                    }
+                    has_entities = true;
+                    map.next_value_seed(EntitiesDeserializer {
+                        context: self.context,
+                    })?;
+                }
+                _ => {
+                    return Err(Error::unknown_field(
+                        &key,
+                        &[SCENE_RESOURCES, SCENE_ENTITIES],
+                    ))
+                }
+            }
+        }
+        Ok(resources.unwrap_or_default())
+    }
+}
+
+struct EntitiesDeserializer<'s, 'a, 'ctx> {
+    context: &'s DeserializerContext<'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx, 'de> DeserializeSeed<'de> for EntitiesDeserializer<'s, 'a, 'ctx> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'s, 'a, 'ctx, 'de> Visitor<'de> for EntitiesDeserializer<'s, 'a, 'ctx> {
+    type Value = Vec<Entity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("list of entity documents")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = seq.next_element_seed(EntityDeserializer {
+            context: self.context,
+        })? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+struct EntityDeserializer<'s, 'a, 'ctx> {
+    context: &'s DeserializerContext<'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx, 'de> DeserializeSeed<'de> for EntityDeserializer<'s, 'a, 'ctx> {
+    type Value = Entity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_DOCUMENT_STRUCT,
+            &[
+                ENTITY_DOCUMENT_NAME,
+                ENTITY_FIELD_COMPONENTS,
+                ENTITY_DOCUMENT_CHILDREN,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'s, 'a, 'ctx, 'de> Visitor<'de> for EntityDeserializer<'s, 'a, 'ctx> {
+    type Value = Entity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("entity document struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut name: Option<String> = None;
+        let mut components = None;
+        let mut children = None;
+        while let Some(Ident(key)) = map.next_key()? {
+            match key.as_str() {
+                ENTITY_DOCUMENT_NAME => {
+                    if name.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_DOCUMENT_NAME));
+                    }
+                    name = Some(map.next_value()?);
+                }
+                ENTITY_FIELD_COMPONENTS => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(ComponentsDeserializer {
+                        context: self.context,
+                    })?);
+                }
+                ENTITY_DOCUMENT_CHILDREN => {
+                    if children.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_DOCUMENT_CHILDREN));
+                    }
+                    children = Some(map.next_value_seed(EntitiesDeserializer {
+                        context: self.context,
+                    })?);
+                }
+                _ => {
+                    return Err(Error::unknown_field(
+                        &key,
+                        &[
+                            ENTITY_DOCUMENT_NAME,
+                            ENTITY_FIELD_COMPONENTS,
+                            ENTITY_DOCUMENT_CHILDREN,
+                        ],
+                    ))
+                }
+            }
+        }
+
+        let registry = self.context.registry;
+        let mut entities = self.context.entities.borrow_mut();
+        let entity = entities.define(name.as_deref());
+        let mut components = components.unwrap_or_default();
+        if let Some(name) = name {
+            components.push(Box::new(Name::new(name)));
+        }
+
+        let children = children.unwrap_or_default();
+        if !children.is_empty() {
+            let mut list = DynamicList::default();
+            for &child in &children {
+                list.push(child);
+            }
+            let mut children_component = DynamicTupleStruct::default();
+            children_component.insert_boxed(Box::new(list));
+            children_component
+                .set_represented_type(Some(hierarchy_type_info::<Children, A>(registry)?));
+            components.push(Box::new(children_component));
+        }
+        for child in entities
+            .entities
+            .iter_mut()
+            .filter(|child| children.contains(&child.entity))
+        {
+            let mut parent = DynamicTupleStruct::default();
+            parent.insert(entity);
+            parent.set_represented_type(Some(hierarchy_type_info::<Parent, A>(registry)?));
+            child.components.push(Box::new(parent));
+        }
+
+        entities.entities.push(DynamicEntity { entity, components });
+        Ok(entity)
+    }
+}
+
+/// Returns the type info of the hierarchy component `T`, which must be registered to load the
+/// hierarchy of a document.
+fn hierarchy_type_info<'de, T: 'static, A: MapAccess<'de>>(
+    registry: &TypeRegistry,
+) -> Result<&'static TypeInfo, A::Error> {
+    registry
+        .get(TypeId::of::<T>())
+        .map(TypeRegistration::type_info)
+        .ok_or_else(|| {
+            Error::custom(format_args!(
+                "`{}` must be registered to load children",
+                std::any::type_name::<T>()
+            ))
+        })
+}
+
+/// Deserializes components or resources from a map of type path to value.
+struct ComponentsDeserializer<'s, 'a, 'ctx> {
+    context: &'s DeserializerContext<'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx, 'de> DeserializeSeed<'de> for ComponentsDeserializer<'s, 'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'s, 'a, 'ctx, 'de> Visitor<'de> for ComponentsDeserializer<'s, 'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of reflect types")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let registry = self.context.registry;
+        let mut added = HashSet::new();
+        let mut components = Vec::new();
+        while let Some(type_path) = map.next_key::<String>()? {
+            let registration = registry
+                .get_with_short_type_path(&type_path)
+                .or_else(|| registry.get_with_type_path(&type_path))
+                .ok_or_else(|| {
+                    Error::custom(format_args!("no registration found for type `{type_path}`"))
+                })?;
+            if !added.insert(registration.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{type_path}`"
+                )));
+            }
+
+            let mut value = map.next_value_seed(ValueDeserializer {
+                type_id: registration.type_id(),
+                type_path: registration.type_info().type_path(),
+                partial: true,
+                context: self.context,
+            })?;
+            // Fill the omitted fields with their default value
+            if let Some(reflect_default) = registration.data::<ReflectDefault>() {
+                if matches!(
+                    value.reflect_ref(),
+                    ReflectRef::Struct(_) | ReflectRef::TupleStruct(_) | ReflectRef::Tuple(_)
+                ) {
+                    let mut default = reflect_default.default();
+                    default.apply(&*value);
+                    value = default;
+                }
+            }
+            components.push(value);
+        }
+        Ok(components)
+    }
+}
+
+/// Deserializes a reflected value, with entities from their name and handles from their asset
+/// path.
+struct ValueDeserializer<'s, 'a, 'ctx> {
+    type_id: TypeId,
+    type_path: &'static str,
+    /// Whether the fields of structs can be omitted, because the value will be applied to the
+    /// default value of its component or resource.
+    partial: bool,
+    context: &'s DeserializerContext<'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx> ValueDeserializer<'s, 'a, 'ctx> {
+    fn field(&self, type_id: TypeId, type_path: &'static str, partial: bool) -> Self {
+        ValueDeserializer {
+            type_id,
+            type_path,
+            partial,
+            context: self.context,
+        }
+    }
+}
+
+impl<'s, 'a, 'ctx, 'de> DeserializeSeed<'de> for ValueDeserializer<'s, 'a, 'ctx> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let registry = self.context.registry;
+        if self.type_id == TypeId::of::<Entity>() {
+            let name = String::deserialize(deserializer)?;
+            let entity = self.context.entities.borrow_mut().reference(name);
+            return Ok(Box::new(entity));
+        }
+
+        let registration = registry.get(self.type_id).ok_or_else(|| {
+            Error::custom(format_args!(
+                "no registration found for type `{}`",
+                self.type_path
+            ))
+        })?;
+        if let Some(reflect_handle) = registration.data::<ReflectHandle>() {
+            let path = String::deserialize(deserializer)?;
+            let mut load_context = self.context.load_context.borrow_mut();
+            let load_context = load_context.as_mut().ok_or_else(|| {
+                Error::custom(format_args!(
+                    "the asset `{path}` can't be loaded without a `LoadContext`"
+                ))
+            })?;
+            let handle = reflect_handle.load(load_context, path);
+            return Ok(reflect_handle.typed(handle));
+        }
+
+        let type_info = registration.type_info();
+        let ident = type_info.type_path_table().ident().unwrap_or_default();
+        match type_info {
+            TypeInfo::Struct(struct_info) => {
+                let mut value = deserializer.deserialize_struct(
+                    ident,
+                    struct_info.field_names(),
+                    StructVisitor {
+                        field: &|name| {
+                            struct_info
+                                .field(name)
+                                .map(|field| (field.type_id(), field.type_path()))
+                        },
+                        field_names: struct_info.field_names(),
+                        partial: self.partial,
+                        seed: &self,
+                    },
+                )?;
+                if !self.partial && value.field_len() < struct_info.field_len() {
+                    if let Some(reflect_default) = registration.data::<ReflectDefault>() {
+                        let mut default = reflect_default.default();
+                        default.apply(&value);
+                        return Ok(default);
+                    }
+                    let serialization_data = registration.data::<SerializationData>();
+                    for (index, field) in struct_info.iter().enumerate() {
+                        if value.field(field.name()).is_some() {
+                            continue;
+                        }
+                        let default = serialization_data
+                            .and_then(|data| data.generate_default(index))
+                            .ok_or_else(|| Error::missing_field(field.name()))?;
+                        value.insert_boxed(field.name(), default);
+                    }
+                }
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::TupleStruct(tuple_struct_info) => {
+                let serialization_data = registration.data::<SerializationData>();
+                let fields = tuple_struct_info
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        match serialization_data.and_then(|data| data.generate_default(index)) {
+                            Some(default) => TupleField::Skipped(default),
+                            None => TupleField::Read(field.type_id(), field.type_path()),
+                        }
+                    })
+                    .collect();
+                let len =
+                    tuple_struct_info.field_len() - serialization_data.map_or(0, |data| data.len());
+                let fields = deserializer.deserialize_tuple_struct(
+                    ident,
+                    len,
+                    TupleVisitor {
+                        fields,
+                        partial: self.partial,
+                        seed: &self,
+                    },
+                )?;
+                let mut value = DynamicTupleStruct::default();
+                for field in fields {
+                    value.insert_boxed(field);
+                }
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::Tuple(tuple_info) => {
+                let fields = tuple_info
+                    .iter()
+                    .map(|field| TupleField::Read(field.type_id(), field.type_path()))
+                    .collect();
+                let fields = deserializer.deserialize_tuple(
+                    tuple_info.field_len(),
+                    TupleVisitor {
+                        fields,
+                        partial: self.partial,
+                        seed: &self,
+                    },
+                )?;
+                let mut value = DynamicTuple::default();
+                for field in fields {
+                    value.insert_boxed(field);
+                }
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::List(list_info) => {
+                let items = deserializer.deserialize_seq(ListVisitor {
+                    item: self.field(
+                        list_info.item_type_id(),
+                        list_info.item_type_path_table().path(),
+                        false,
+                    ),
+                })?;
+                let mut value = DynamicList::default();
+                for item in items {
+                    value.push_box(item);
+                }
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::Array(array_info) => {
+                let fields = (0..array_info.capacity())
+                    .map(|_| {
+                        TupleField::Read(
+                            array_info.item_type_id(),
+                            array_info.item_type_path_table().path(),
+                        )
+                    })
+                    .collect();
+                let items = deserializer.deserialize_tuple(
+                    array_info.capacity(),
+                    TupleVisitor {
+                        fields,
+                        partial: false,
+                        seed: &self,
+                    },
+                )?;
+                let mut value = DynamicArray::new(items.into_boxed_slice());
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::Map(map_info) => {
+                let mut value = deserializer.deserialize_map(MapVisitor {
+                    key: self.field(
+                        map_info.key_type_id(),
+                        map_info.key_type_path_table().path(),
+                        false,
+                    ),
+                    value: self.field(
+                        map_info.value_type_id(),
+                        map_info.value_type_path_table().path(),
+                        false,
+                    ),
+                })?;
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::Enum(enum_info) => {
+                let mut value = if is_option(enum_info) {
+                    deserializer.deserialize_option(OptionVisitor {
+                        enum_info,
+                        seed: &self,
+                    })?
+                } else {
+                    deserializer.deserialize_enum(
+                        ident,
+                        enum_info.variant_names(),
+                        EnumVisitor {
+                            enum_info,
+                            seed: &self,
+                        },
+                    )?
+                };
+                value.set_represented_type(Some(type_info));
+                Ok(Box::new(value))
+            }
+            TypeInfo::Value(_) => {
+                TypedReflectDeserializer::new(registration, registry).deserialize(deserializer)
+            }
+        }
+    }
+}
+
+struct StructVisitor<'v, 's, 'a, 'ctx> {
+    /// Returns the type of the field with the given name.
+    field: &'v dyn Fn(&str) -> Option<(TypeId, &'static str)>,
+    field_names: &'static [&'static str],
+    partial: bool,
+    seed: &'v ValueDeserializer<'s, 'a, 'ctx>,
+}
+
+impl<'v, 's, 'a, 'ctx, 'de> Visitor<'de> for StructVisitor<'v, 's, 'a, 'ctx> {
+    type Value = DynamicStruct;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected struct value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut value = DynamicStruct::default();
+        while let Some(Ident(name)) = map.next_key()? {
+            let (type_id, type_path) =
+                (self.field)(&name).ok_or_else(|| Error::unknown_field(&name, self.field_names))?;
+            let field = map.next_value_seed(self.seed.field(type_id, type_path, self.partial))?;
+            value.insert_boxed(&name, field);
+        }
+        Ok(value)
+    }
+}
+
+enum TupleField {
+    Read(TypeId, &'static str),
+    /// A field skipped by serialization, with its default value.
+    Skipped(Box<dyn Reflect>),
+}
+
+struct TupleVisitor<'v, 's, 'a, 'ctx> {
+    fields: Vec<TupleField>,
+    partial: bool,
+    seed: &'v ValueDeserializer<'s, 'a, 'ctx>,
+}
+
+impl<'v, 's, 'a, 'ctx, 'de> Visitor<'de> for TupleVisitor<'v, 's, 'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected tuple value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let len = self.fields.len();
+        let mut values = Vec::with_capacity(len);
+        for (index, field) in self.fields.into_iter().enumerate() {
+            let value = match field {
+                TupleField::Read(type_id, type_path) => seq
+                    .next_element_seed(self.seed.field(type_id, type_path, self.partial))?
+                    .ok_or_else(|| {
+                        Error::invalid_length(index, &format!("{len} fields").as_str())
+                    })?,
+                TupleField::Skipped(default) => default,
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+struct ListVisitor<'s, 'a, 'ctx> {
+    item: ValueDeserializer<'s, 'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx, 'de> Visitor<'de> for ListVisitor<'s, 'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected list value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(self.item.field(
+            self.item.type_id,
+            self.item.type_path,
+            false,
+        ))? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+struct MapVisitor<'s, 'a, 'ctx> {
+    key: ValueDeserializer<'s, 'a, 'ctx>,
+    value: ValueDeserializer<'s, 'a, 'ctx>,
+}
+
+impl<'s, 'a, 'ctx, 'de> Visitor<'de> for MapVisitor<'s, 'a, 'ctx> {
+    type Value = DynamicMap;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected map value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut dynamic_map = DynamicMap::default();
+        while let Some(key) =
+            map.next_key_seed(self.key.field(self.key.type_id, self.key.type_path, false))?
+        {
+            let value = map.next_value_seed(self.value.field(
+                self.value.type_id,
+                self.value.type_path,
+                false,
+            ))?;
+            dynamic_map.insert_boxed(key, value);
+        }
+        Ok(dynamic_map)
+    }
+}
+
+struct OptionVisitor<'v, 's, 'a, 'ctx> {
+    enum_info: &'static EnumInfo,
+    seed: &'v ValueDeserializer<'s, 'a, 'ctx>,
+}
+
+impl<'v, 's, 'a, 'ctx, 'de> Visitor<'de> for OptionVisitor<'v, 's, 'a, 'ctx> {
+    type Value = DynamicEnum;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected option value")
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(DynamicEnum::new("None", DynamicVariant::Unit))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Some(VariantInfo::Tuple(variant_info)) = self.enum_info.variant("Some") else {
+            return Err(Error::custom("expected a `Some` tuple variant"));
+        };
+        let field = variant_info
+            .field_at(0)
+            .ok_or_else(|| Error::custom("expected a `Some` field"))?;
+        let value = self
+            .seed
+            .field(field.type_id(), field.type_path(), false)
+            .deserialize(deserializer)?;
+        let mut tuple = DynamicTuple::default();
+        tuple.insert_boxed(value);
+        Ok(DynamicEnum::new("Some", DynamicVariant::Tuple(tuple)))
+    }
+}
+
+struct EnumVisitor<'v, 's, 'a, 'ctx> {
+    enum_info: &'static EnumInfo,
+    seed: &'v ValueDeserializer<'s, 'a, 'ctx>,
+}
+
+impl<'v, 's, 'a, 'ctx, 'de> Visitor<'de> for EnumVisitor<'v, 's, 'a, 'ctx> {
+    type Value = DynamicEnum;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected enum value")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (Ident(variant_name), variant) = data.variant()?;
+        let variant_info = self
+            .enum_info
+            .variant(&variant_name)
+            .ok_or_else(|| Error::unknown_variant(&variant_name, self.enum_info.variant_names()))?;
+        let dynamic_variant = match variant_info {
+            VariantInfo::Unit(_) => {
+                variant.unit_variant()?;
+                DynamicVariant::Unit
+            }
+            VariantInfo::Tuple(tuple_info) if tuple_info.field_len() == 1 => {
+                let field = tuple_info.field_at(0).unwrap();
+                let value = variant.newtype_variant_seed(self.seed.field(
+                    field.type_id(),
+                    field.type_path(),
+                    false,
+                ))?;
+                let mut tuple = DynamicTuple::default();
+                tuple.insert_boxed(value);
+                DynamicVariant::Tuple(tuple)
+            }
+            VariantInfo::Tuple(tuple_info) => {
+                let fields = tuple_info
+                    .iter()
+                    .map(|field| TupleField::Read(field.type_id(), field.type_path()))
+                    .collect();
+                let fields = variant.tuple_variant(
+                    tuple_info.field_len(),
+                    TupleVisitor {
+                        fields,
+                        partial: false,
+                        seed: self.seed,
+                    },
+                )?;
+                let mut tuple = DynamicTuple::default();
+                for field in fields {
+                    tuple.insert_boxed(field);
+                }
+                DynamicVariant::Tuple(tuple)
+            }
+            VariantInfo::Struct(struct_info) => {
+                let value = variant.struct_variant(
+                    struct_info.field_names(),
+                    StructVisitor {
+                        field: &|name| {
+                            struct_info
+                                .field(name)
+                                .map(|field| (field.type_id(), field.type_path()))
+                        },
+                        field_names: struct_info.field_names(),
+                        partial: false,
+                        seed: self.seed,
+                    },
+                )?;
+                if let Some(field) = struct_info
+                    .iter()
+                    .find(|field| value.field(field.name()).is_none())
+                {
+                    return Err(Error::missing_field(field.name()));
+                }
+                DynamicVariant::Struct(value)
+            }
+        };
+        Ok(DynamicEnum::new(variant_name, dynamic_variant))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{document::SceneDocumentDeserializer, ron, DynamicScene, DynamicSceneBuilder};
+    use bevy_core::Name;
+    use bevy_ecs::{
+        entity::{Entity, EntityMapper, MapEntities},
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
+        reflect::{AppTypeRegistry, ReflectMapEntities},
+        world::FromWorld,
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+    use bevy_utils::EntityHashMap;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, Default)]
+    struct Stats {
+        health: u32,
+        speed: f32,
+        tags: Vec<String>,
+    }
+
+    impl Default for Stats {
+        fn default() -> Self {
+            Self {
+                health: 100,
+                speed: 1.0,
+                tags: Vec::new(),
+            }
+        }
+    }
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Option<Entity>);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            if let Some(entity) = &mut self.0 {
+                *entity = entity_mapper.get_or_reserve(*entity);
+            }
+        }
+    }
+
+    impl FromWorld for Target {
+        fn from_world(_world: &mut World) -> Self {
+            Self(None)
+        }
+    }
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource, Default)]
+    struct Score {
+        value: u32,
+    }
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Stats>();
+            registry.register::<Target>();
+            registry.register::<Option<Entity>>();
+            registry.register::<Vec<String>>();
+            registry.register::<Score>();
+            registry.register::<Name>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+            registry.register::<Entity>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    fn deserialize(world: &World, input: &str) -> DynamicScene {
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let document_deserializer = SceneDocumentDeserializer {
+            type_registry: &world.resource::<AppTypeRegistry>().read(),
+            load_context: None,
+        };
+        document_deserializer
+            .deserialize(&mut deserializer)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_roundtrip_document() {
+        let mut world = create_world();
+        world.insert_resource(Score { value: 7 });
+        let enemy = world.spawn(Name::new("Enemy")).id();
+        let player = world
+            .spawn((
+                Name::new("Player"),
+                Stats {
+                    health: 50,
+                    ..Default::default()
+                },
+                Target(Some(enemy)),
+            ))
+            .with_children(|parent| {
+                parent.spawn(Stats {
+                    tags: vec!["sharp".to_string()],
+                    ..Default::default()
+                });
+            })
+            .id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities(
+                [
+                    player,
+                    world.entity(player).get::<Children>().unwrap()[0],
+                    enemy,
+                ]
+                .into_iter(),
+            )
+            .extract_resources()
+            .build();
+        let output = scene
+            .serialize_document(&world.resource::<AppTypeRegistry>().0)
+            .unwrap();
+
+        let expected = r#"(
+  resources: {
+    "Score": (
+      value: 7,
+    ),
+  },
+  entities: [
+    (
+      name: "Enemy",
+      components: {},
+    ),
+    (
+      name: "Player",
+      components: {
+        "Stats": (
+          health: 50,
+        ),
+        "Target": (Some("Enemy")),
+      },
+      children: [
+        (
+          components: {
+            "Stats": (
+              tags: [
+                "sharp",
+              ],
+            ),
+          },
+        ),
+      ],
+    ),
+  ],
+)"#;
+        assert_eq!(expected, output);
+
+        let scene = deserialize(&world, &output);
+        let mut dst_world = create_world();
+        scene
+            .write_to_world(&mut dst_world, &mut EntityHashMap::default())
+            .unwrap();
+
+        assert_eq!(Some(&Score { value: 7 }), dst_world.get_resource::<Score>());
+        let mut query = dst_world.query::<(Entity, &Name)>();
+        let mut named = |name: &str| {
+            query
+                .iter(&dst_world)
+                .find(|(_, entity_name)| entity_name.as_str() == name)
+                .unwrap()
+                .0
+        };
+        let (player, enemy) = (named("Player"), named("Enemy"));
+        assert_eq!(
+            Some(&Stats {
+                health: 50,
+                ..Default::default()
+            }),
+            dst_world.get::<Stats>(player)
+        );
+        assert_eq!(Some(&Target(Some(enemy))), dst_world.get::<Target>(player));
+        let children = dst_world.get::<Children>(player).unwrap();
+        assert_eq!(1, children.len());
+        assert_eq!(player, dst_world.get::<Parent>(children[0]).unwrap().get());
+        assert_eq!(
+            Some(&Stats {
+                tags: vec!["sharp".to_string()],
+                ..Default::default()
+            }),
+            dst_world.get::<Stats>(children[0])
+        );
+    }
+
+    #[test]
+    fn should_reference_entities_by_name() {
+        let world = create_world();
+
+        let scene = deserialize(
+            &world,
+            r#"(
+  entities: [
+    (name: "A", components: { "Target": (Some("B")) }),
+    (name: "B", components: { "bevy_scene::document::tests::Target": (Some("A")) }),
+  ],
+)"#,
+        );
+        assert_eq!(2, scene.entities.len());
+        let mut dst_world = create_world();
+        let mut entity_map = EntityHashMap::default();
+        scene
+            .write_to_world(&mut dst_world, &mut entity_map)
+            .unwrap();
+        let a = entity_map[&scene.entities[0].entity];
+        let b = entity_map[&scene.entities[1].entity];
+        assert_eq!(Some(&Target(Some(b))), dst_world.get::<Target>(a));
+        assert_eq!(Some(&Target(Some(a))), dst_world.get::<Target>(b));
+
+        let mut deserializer = ron::de::Deserializer::from_str(
+            r#"(entities: [(name: "A", components: { "Target": (Some("C")) })])"#,
+        )
+        .unwrap();
+        let document_deserializer = SceneDocumentDeserializer {
+            type_registry: &world.resource::<AppTypeRegistry>().read(),
+            load_context: None,
+        };
+        assert!(document_deserializer
+            .deserialize(&mut deserializer)
+            .is_err());
+    }
+}
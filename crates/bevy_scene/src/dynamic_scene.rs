@@ -1,15 +1,18 @@
-use crate::{ron, DynamicSceneBuilder, Scene, SceneSpawnError};
+use crate::{
+    entity_mapping::{bind_anchors, SceneMappings},
+    ron, DynamicSceneBuilder, Scene, SceneAnchor, SceneSpawnError,
+};
 use bevy_ecs::{
     entity::Entity,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    reflect::{AppTypeRegistry, ReflectComponent},
     world::World,
 };
-use bevy_reflect::{Reflect, TypePath, TypeRegistryArc};
-use bevy_utils::{EntityHashMap, HashMap};
+use bevy_reflect::{FromReflect, Reflect, TypePath, TypeRegistryArc};
+use bevy_utils::EntityHashMap;
 use std::any::TypeId;
 
 #[cfg(feature = "serialize")]
-use crate::serde::SceneSerializer;
+use crate::{document::SceneDocumentSerializer, serde::SceneSerializer};
 use bevy_asset::Asset;
 use bevy_ecs::reflect::ReflectResource;
 #[cfg(feature = "serialize")]
@@ -93,13 +96,35 @@ impl DynamicScene {
             reflect_resource.apply_or_insert(world, &**resource);
         }
 
+        // Bind the anchors of the scene to the anchors of the world, whose components are kept
+        let bound = bind_anchors(
+            world,
+            self.entities.iter().filter_map(|scene_entity| {
+                let anchor = scene_entity
+                    .components
+                    .iter()
+                    .find(|component| {
+                        component
+                            .get_represented_type_info()
+                            .is_some_and(|info| info.type_id() == TypeId::of::<SceneAnchor>())
+                    })
+                    .and_then(|component| SceneAnchor::from_reflect(&**component))?;
+                Some((scene_entity.entity, anchor))
+            }),
+            entity_map,
+        );
+
         // For each component types that reference other entities, we keep track
         // of which entities in the scene use that component.
         // This is so we can update the scene-internal references to references
         // of the actual entities in the world.
-        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+        let mut scene_mappings = SceneMappings::default();
 
         for scene_entity in &self.entities {
+            if bound.contains(&scene_entity.entity) {
+                continue;
+            }
+
             // Fetch the entity with the given entity id from the `entity_map`
             // or spawn a new entity with a transiently unique id if there is
             // no corresponding entry.
@@ -129,12 +154,7 @@ impl DynamicScene {
 
                 // If this component references entities in the scene, track it
                 // so we can update it to the entity in the world.
-                if registration.data::<ReflectMapEntities>().is_some() {
-                    scene_mappings
-                        .entry(registration.type_id())
-                        .or_insert(Vec::new())
-                        .push(entity);
-                }
+                scene_mappings.track(registration, &type_registry, entity);
 
                 // If the entity already has the given component attached,
                 // just apply the (possibly) new value, otherwise add the
@@ -144,13 +164,11 @@ impl DynamicScene {
         }
 
         // Updates references to entities in the scene to entities in the world
-        for (type_id, entities) in scene_mappings.into_iter() {
-            let registration = type_registry.get(type_id).expect(
-                "we should be getting TypeId from this TypeRegistration in the first place",
-            );
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_entities(world, entity_map, &entities);
-            }
+        scene_mappings.map(world, entity_map, &type_registry);
+
+        // The bound anchors aren't part of the instance of the scene
+        for scene_entity in bound {
+            entity_map.remove(&scene_entity);
         }
 
         Ok(())
@@ -176,6 +194,12 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into a scene [document](crate::document).
+    #[cfg(feature = "serialize")]
+    pub fn serialize_document(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        serialize_ron(SceneDocumentSerializer::new(self, registry))
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -192,11 +216,26 @@ where
 
 #[cfg(test)]
 mod tests {
-    use bevy_ecs::{reflect::AppTypeRegistry, system::Command, world::World};
+    use bevy_ecs::{
+        entity::Entity,
+        prelude::{Component, ReflectComponent},
+        reflect::AppTypeRegistry,
+        system::Command,
+        world::World,
+    };
     use bevy_hierarchy::{AddChild, Parent};
+    use bevy_reflect::Reflect;
     use bevy_utils::EntityHashMap;
 
-    use crate::dynamic_scene_builder::DynamicSceneBuilder;
+    use crate::{dynamic_scene_builder::DynamicSceneBuilder, SceneAnchor};
+
+    /// A component referencing entities without implementing `MapEntities`.
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Targets {
+        entities: Vec<Entity>,
+        main: Option<Entity>,
+    }
 
     #[test]
     fn components_not_defined_in_scene_should_not_be_affected_by_scene_entity_map() {
@@ -274,4 +313,48 @@ mod tests {
             "something is wrong with the this test or the code reloading scenes since the relationship between scene entities is broken"
         );
     }
+
+    #[test]
+    fn entities_should_be_mapped_through_reflection_and_anchors() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Targets>();
+            registry.register::<SceneAnchor>();
+            registry.register::<Vec<Entity>>();
+            registry.register::<Option<Entity>>();
+            registry.register::<Entity>();
+        }
+
+        // The scene references an anchor named "player", which has a component of its own
+        let anchor = world
+            .spawn((SceneAnchor::new("player"), Targets::default()))
+            .id();
+        let target = world.spawn_empty().id();
+        let source = world
+            .spawn(Targets {
+                entities: vec![anchor, target],
+                main: Some(target),
+            })
+            .id();
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities([anchor, target, source].into_iter())
+            .build();
+
+        let mut dst_world = World::new();
+        dst_world.insert_resource(world.resource::<AppTypeRegistry>().clone());
+        let player = dst_world.spawn(SceneAnchor::new("player")).id();
+        let mut entity_map = EntityHashMap::default();
+        scene
+            .write_to_world(&mut dst_world, &mut entity_map)
+            .unwrap();
+
+        assert!(!entity_map.contains_key(&anchor));
+        assert!(dst_world.get::<Targets>(player).is_none());
+        let targets = dst_world.get::<Targets>(entity_map[&source]).unwrap();
+        assert_eq!(vec![player, entity_map[&target]], targets.entities);
+        assert_eq!(Some(entity_map[&target]), targets.main);
+    }
 }
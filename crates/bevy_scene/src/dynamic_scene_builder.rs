@@ -1,4 +1,4 @@
-use crate::{DynamicEntity, DynamicScene, SceneFilter};
+use crate::{DynamicEntity, DynamicScene, SceneFilter, StableId};
 use bevy_ecs::component::{Component, ComponentId};
 use bevy_ecs::system::Resource;
 use bevy_ecs::{
@@ -8,7 +8,7 @@ use bevy_ecs::{
 };
 use bevy_reflect::Reflect;
 use bevy_utils::default;
-use std::collections::BTreeMap;
+use std::{any::TypeId, collections::BTreeMap};
 
 /// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities and resources.
 ///
@@ -20,6 +20,8 @@ use std::collections::BTreeMap;
 /// [allowing](DynamicSceneBuilder::allow)/[denying](DynamicSceneBuilder::deny) certain components.
 ///
 /// Extraction happens immediately and uses the filter as it exists during the time of extraction.
+/// The [`StableId`] of an entity is always extracted, so that the entity can be identified
+/// when the scene is loaded.
 ///
 /// # Resource Extraction
 ///
@@ -238,7 +240,8 @@ impl<'w> DynamicSceneBuilder<'w> {
     ///     .build();
     /// ```
     ///
-    /// Note that components extracted from queried entities must still pass through the filter if one is set.
+    /// Note that components extracted from queried entities must still pass through the filter if one is set,
+    /// except for the [`StableId`] of the entities, which is always extracted.
     ///
     /// [`allow`]: Self::allow
     /// [`deny`]: Self::deny
@@ -265,7 +268,9 @@ impl<'w> DynamicSceneBuilder<'w> {
                         .get_info(component_id)?
                         .type_id()?;
 
-                    let is_denied = self.component_filter.is_denied_by_id(type_id);
+                    // The stable id identifies the entity across scenes, so it is always extracted
+                    let is_denied = self.component_filter.is_denied_by_id(type_id)
+                        && type_id != TypeId::of::<StableId>();
 
                     if is_denied {
                         // Component is either in the denylist or _not_ in the allowlist
@@ -356,9 +361,10 @@ mod tests {
         world::World,
     };
 
-    use bevy_reflect::Reflect;
+    use bevy_reflect::{FromReflect, Reflect};
 
     use super::DynamicSceneBuilder;
+    use crate::StableId;
 
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
@@ -606,6 +612,33 @@ mod tests {
         assert!(scene.entities[2].components[0].represents::<ComponentB>());
     }
 
+    #[test]
+    fn should_always_extract_stable_ids() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<StableId>();
+        }
+        world.insert_resource(atr);
+
+        let id = StableId::new();
+        let entity = world.spawn((ComponentA, id)).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny_all()
+            .extract_entity(entity)
+            .build();
+
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert_eq!(
+            Some(id),
+            StableId::from_reflect(&*scene.entities[0].components[0])
+        );
+    }
+
     #[test]
     fn should_extract_allowed_resources() {
         let mut world = World::default();
@@ -0,0 +1,226 @@
+use bevy_ecs::{
+    entity::{Entity, EntityMapper},
+    prelude::Component,
+    reflect::{ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+use bevy_reflect::{
+    std_traits::ReflectDefault, Reflect, ReflectMut, TypeInfo, TypeRegistration, TypeRegistry,
+    VariantInfo,
+};
+use bevy_utils::{EntityHashMap, HashMap};
+use std::any::TypeId;
+
+/// Names an entity, so that scenes can reference it across the boundaries of scene instances.
+///
+/// When a scene is written to a world, each of its entities with a [`SceneAnchor`] which is also
+/// the anchor of an entity of the world is bound to the entity of the world instead of being
+/// spawned: the references of the scene to the anchor are mapped to the entity of the world, and
+/// the components of the anchor in the scene are not written. This lets a scene reference
+/// entities which already exist, like the player, with a placeholder entity.
+///
+/// The other anchors of the scene are spawned as usual, so the scenes spawned afterwards can
+/// reference them in turn.
+///
+/// A bound anchor isn't part of the instance of the scene: it isn't kept in the entity map of the
+/// instance, and it isn't despawned with it.
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, PartialEq, Hash)]
+pub struct SceneAnchor(pub String);
+
+impl SceneAnchor {
+    /// Creates an anchor with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        SceneAnchor(name.into())
+    }
+}
+
+/// Binds the anchors of a scene to the anchors of `world` with the same name, by adding them to
+/// `entity_map`, and returns the bound scene entities.
+///
+/// The scene entities which are already in `entity_map` are left as they are.
+pub(crate) fn bind_anchors(
+    world: &mut World,
+    anchors: impl Iterator<Item = (Entity, SceneAnchor)>,
+    entity_map: &mut EntityHashMap<Entity, Entity>,
+) -> Vec<Entity> {
+    let anchors: Vec<_> = anchors
+        .filter(|(entity, _)| !entity_map.contains_key(entity))
+        .collect();
+    if anchors.is_empty() {
+        return Vec::new();
+    }
+
+    let mut world_anchors = HashMap::default();
+    for (entity, anchor) in world.query::<(Entity, &SceneAnchor)>().iter(world) {
+        world_anchors.entry(anchor.0.clone()).or_insert(entity);
+    }
+    let mut bound = Vec::new();
+    for (scene_entity, anchor) in anchors {
+        if let Some(&entity) = world_anchors.get(&anchor.0) {
+            entity_map.insert(scene_entity, entity);
+            bound.push(scene_entity);
+        }
+    }
+    bound
+}
+
+/// The entities with components written from a scene which may reference entities of the scene,
+/// by component type.
+#[derive(Default)]
+pub(crate) struct SceneMappings {
+    /// The components implementing [`MapEntities`](bevy_ecs::entity::MapEntities).
+    map_entities: HashMap<TypeId, Vec<Entity>>,
+    /// The other components containing an [`Entity`], which are mapped through reflection.
+    reflected: HashMap<TypeId, Vec<Entity>>,
+    /// Whether values of a type may contain an [`Entity`].
+    may_contain_entity: HashMap<TypeId, bool>,
+}
+
+impl SceneMappings {
+    /// Tracks the component of the type of `registration` written to `entity`.
+    pub(crate) fn track(
+        &mut self,
+        registration: &TypeRegistration,
+        type_registry: &TypeRegistry,
+        entity: Entity,
+    ) {
+        let type_id = registration.type_id();
+        if registration.data::<ReflectMapEntities>().is_some() {
+            self.map_entities.entry(type_id).or_default().push(entity);
+        } else if may_contain_entity(type_id, type_registry, &mut self.may_contain_entity) {
+            self.reflected.entry(type_id).or_default().push(entity);
+        }
+    }
+
+    /// Updates the references to entities of the scene in the tracked components to the entities
+    /// of the world.
+    pub(crate) fn map(
+        self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity, Entity>,
+        type_registry: &TypeRegistry,
+    ) {
+        for (type_id, entities) in self.map_entities {
+            let registration = type_registry.get(type_id).expect(
+                "we should be getting TypeId from this TypeRegistration in the first place",
+            );
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect.map_entities(world, entity_map, &entities);
+            }
+        }
+
+        for (type_id, entities) in self.reflected {
+            let Some(reflect_component) = type_registry
+                .get(type_id)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            else {
+                continue;
+            };
+            EntityMapper::world_scope(entity_map, world, |world, mapper| {
+                for entity in entities {
+                    if let Some(mut component) =
+                        reflect_component.reflect_mut(&mut world.entity_mut(entity))
+                    {
+                        map_reflected_entities(&mut *component, mapper);
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Returns whether a value of the type `type_id` may contain an [`Entity`], which is assumed for
+/// the types which aren't registered.
+fn may_contain_entity(
+    type_id: TypeId,
+    type_registry: &TypeRegistry,
+    cache: &mut HashMap<TypeId, bool>,
+) -> bool {
+    if type_id == TypeId::of::<Entity>() {
+        return true;
+    }
+    if let Some(&result) = cache.get(&type_id) {
+        return result;
+    }
+    let Some(registration) = type_registry.get(type_id) else {
+        return true;
+    };
+    // Recursive types are handled by their fields
+    cache.insert(type_id, false);
+
+    let mut fields = Vec::new();
+    match registration.type_info() {
+        TypeInfo::Struct(info) => fields.extend(info.iter().map(|field| field.type_id())),
+        TypeInfo::TupleStruct(info) => fields.extend(info.iter().map(|field| field.type_id())),
+        TypeInfo::Tuple(info) => fields.extend(info.iter().map(|field| field.type_id())),
+        TypeInfo::List(info) => fields.push(info.item_type_id()),
+        TypeInfo::Array(info) => fields.push(info.item_type_id()),
+        TypeInfo::Map(info) => fields.push(info.value_type_id()),
+        TypeInfo::Enum(info) => {
+            for variant in info.iter() {
+                match variant {
+                    VariantInfo::Struct(variant) => {
+                        fields.extend(variant.iter().map(|field| field.type_id()));
+                    }
+                    VariantInfo::Tuple(variant) => {
+                        fields.extend(variant.iter().map(|field| field.type_id()));
+                    }
+                    VariantInfo::Unit(_) => {}
+                }
+            }
+        }
+        TypeInfo::Value(_) => {}
+    }
+    let result = fields
+        .into_iter()
+        .any(|field| may_contain_entity(field, type_registry, cache));
+    cache.insert(type_id, result);
+    result
+}
+
+/// Maps the entities contained in `value`.
+fn map_reflected_entities(value: &mut dyn Reflect, mapper: &mut EntityMapper) {
+    if let Some(entity) = value.as_any_mut().downcast_mut::<Entity>() {
+        *entity = mapper.get_or_reserve(*entity);
+        return;
+    }
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                map_reflected_entities(value.field_at_mut(index).unwrap(), mapper);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                map_reflected_entities(value.field_mut(index).unwrap(), mapper);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                map_reflected_entities(value.field_mut(index).unwrap(), mapper);
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                map_reflected_entities(value.get_mut(index).unwrap(), mapper);
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                map_reflected_entities(value.get_mut(index).unwrap(), mapper);
+            }
+        }
+        ReflectMut::Map(value) => {
+            for index in 0..value.len() {
+                map_reflected_entities(value.get_at_mut(index).unwrap().1, mapper);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                map_reflected_entities(value.field_at_mut(index).unwrap(), mapper);
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
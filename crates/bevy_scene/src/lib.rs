@@ -9,11 +9,21 @@
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod entity_mapping;
 mod scene;
 mod scene_filter;
 mod scene_loader;
+mod scene_patch;
 mod scene_spawner;
+mod stable_id;
+mod streaming;
 
+#[cfg(feature = "serialize")]
+pub mod document;
+#[cfg(feature = "serialize")]
+pub mod replication;
+#[cfg(feature = "serialize")]
+pub mod save;
 #[cfg(feature = "serialize")]
 pub mod serde;
 
@@ -24,17 +34,21 @@ use bevy_ecs::schedule::IntoSystemConfigs;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use entity_mapping::SceneAnchor;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_patch::*;
 pub use scene_spawner::*;
+pub use stable_id::*;
+pub use streaming::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneFilter,
-        SceneSpawner,
+        ChunkedScene, DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle,
+        SceneFilter, ScenePatch, SceneSpawner, StableId, StableIdIndex, StreamingObserver,
     };
 }
 
@@ -50,10 +64,20 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<DynamicScene>()
             .init_asset::<Scene>()
+            .init_asset::<ScenePatch>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<ScenePatchLoader>()
+            .init_asset_loader::<SceneDocumentLoader>()
+            .init_asset_loader::<SaveLoader>()
+            .register_type::<StableId>()
+            .register_type::<SceneAnchor>()
             .add_event::<SceneInstanceReady>()
             .init_resource::<SceneSpawner>()
-            .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
+            .init_resource::<SceneStreamingSettings>()
+            .init_resource::<StableIdIndex>()
+            .add_systems(Update, stream_chunked_scenes)
+            .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain())
+            .add_systems(PostUpdate, update_stable_id_index);
     }
 }
 
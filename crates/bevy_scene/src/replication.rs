@@ -0,0 +1,692 @@
+//! Replication of entities from one world to others, such as from a server to its clients.
+//!
+//! The entities to replicate are marked with a [`StableId`], and only their components registered
+//! with the [`ReflectReplicated`] type data are replicated. On the sending side, a [`Replicator`]
+//! keeps track of what a receiver knows, and creates a [`ChangeSet`] of the components which
+//! changed since its previous one, using the change detection of the world. The change set is
+//! serialized with a [`ChangeSetSerializer`], sent over any transport, deserialized with a
+//! [`ChangeSetDeserializer`] and applied to the receiving world by its [`Replica`].
+//!
+//! Each [`Replicator`] can filter the entities its receiver is interested in, for example the
+//! entities close to its player: entities leaving the interest of the receiver are despawned from
+//! its world, and are sent again as a whole when they enter it again.
+//!
+//! Change sets must be applied in the order they were created, so the transport must be reliable
+//! and ordered, or the replicator must be [reset](Replicator::reset) when one is lost.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! # use bevy_scene::{replication::{ReflectReplicated, Replica, Replicator}, StableId};
+//! #[derive(Component, Reflect, Default, Debug, PartialEq)]
+//! #[reflect(Component, Replicated)]
+//! struct Health(u32);
+//!
+//! let registry = AppTypeRegistry::default();
+//! registry.write().register::<Health>();
+//! registry.write().register::<StableId>();
+//!
+//! let mut server = World::new();
+//! server.insert_resource(registry.clone());
+//! let mut client = World::new();
+//! client.insert_resource(registry);
+//!
+//! let player_id = StableId::new();
+//! let player = server.spawn((player_id, Health(10))).id();
+//! let mut replicator = Replicator::default();
+//! let mut replica = Replica::default();
+//!
+//! replica.apply(&replicator.collect(&server), &mut client).unwrap();
+//! server.get_mut::<Health>(player).unwrap().0 = 5;
+//! replica.apply(&replicator.collect(&server), &mut client).unwrap();
+//!
+//! let replicated = replica.entity(player).unwrap();
+//! assert_eq!(Some(&Health(5)), client.get::<Health>(replicated));
+//! assert_eq!(Some(&player_id), client.get::<StableId>(replicated));
+//! ```
+
+use crate::{
+    serde::{EntityPatchesDeserializer, EntityPatchesSerializer},
+    EntityPatch, ScenePatch, SceneSpawnError, StableId,
+};
+use bevy_ecs::{
+    component::{Component, Tick},
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::{EntityRef, World},
+};
+use bevy_hierarchy::despawn_with_children_recursive;
+use bevy_reflect::{FromReflect, FromType, TypeRegistry, TypeRegistryArc};
+use bevy_utils::{EntityHashMap, HashMap, HashSet};
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    any::TypeId,
+    fmt::{Debug, Formatter},
+};
+
+/// Name of the serialized change set struct type.
+pub const CHANGE_SET_STRUCT: &str = "ChangeSet";
+/// Name of the serialized field containing the index of a change set.
+pub const CHANGE_SET_TICK: &str = "tick";
+/// Name of the serialized field containing the changed entities of a change set.
+pub const CHANGE_SET_ENTITIES: &str = "entities";
+/// Name of the serialized field containing the despawned entities of a change set.
+pub const CHANGE_SET_DESPAWNED: &str = "despawned";
+
+/// Type data of the components replicated by a [`Replicator`].
+///
+/// It is registered with `#[reflect(Replicated)]`, or with
+/// [`TypeRegistry::register_type_data`].
+#[derive(Clone)]
+pub struct ReflectReplicated;
+
+impl<T: Component> FromType<T> for ReflectReplicated {
+    fn from_type() -> Self {
+        ReflectReplicated
+    }
+}
+
+/// The changes of the replicated entities of a world since the previous change set, created by
+/// a [`Replicator`] and applied by a [`Replica`].
+///
+/// Entities are identified by their [`Entity`] in the sending world, and components referencing
+/// other entities are mapped to the entities of the receiving world when the change set is
+/// applied.
+#[derive(Default)]
+pub struct ChangeSet {
+    /// The index of the change set among the change sets of its [`Replicator`], starting at 0.
+    pub tick: u64,
+    /// The changed and removed components of the entities.
+    ///
+    /// An entity sent for the first time contains all its replicated components and its
+    /// [`StableId`].
+    pub entities: ScenePatch,
+    /// The entities which were despawned, or which left the interest of the receiver.
+    pub despawned: Vec<Entity>,
+}
+
+impl ChangeSet {
+    /// Returns `true` if the change set doesn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.entities.entities.is_empty() && self.despawned.is_empty()
+    }
+}
+
+/// Creates the [`ChangeSet`]s of a world for a receiver.
+///
+/// A replicator remembers the entities and components it sent, so each receiver has its own
+/// replicator.
+pub struct Replicator {
+    tick: u64,
+    last_run: Tick,
+    sent: EntityHashMap<Entity, HashSet<TypeId>>,
+    #[allow(clippy::type_complexity)]
+    interest: Option<Box<dyn Fn(EntityRef) -> bool + Send + Sync>>,
+}
+
+impl Default for Replicator {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            last_run: Tick::new(0),
+            sent: EntityHashMap::default(),
+            interest: None,
+        }
+    }
+}
+
+impl Debug for Replicator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Replicator")
+            .field("tick", &self.tick)
+            .field("sent", &self.sent.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Replicator {
+    /// Only replicates the entities for which `interest` returns `true`.
+    ///
+    /// The interest is evaluated for each replicated entity every time a change set is created.
+    pub fn with_interest(
+        mut self,
+        interest: impl Fn(EntityRef) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.interest = Some(Box::new(interest));
+        self
+    }
+
+    /// The index of the next change set.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Forgets what was sent to the receiver, so that the next change set contains the whole
+    /// state of the replicated entities, such as after a reconnection.
+    ///
+    /// The receiver keeps the entities it knows, which are updated by the next change set.
+    pub fn reset(&mut self) {
+        self.sent.clear();
+    }
+
+    /// Creates the change set of the replicated entities of `world` since the previous one.
+    ///
+    /// The change set contains:
+    /// - the replicated components of the entities which weren't sent yet,
+    /// - the replicated components which were added or changed since the previous change set,
+    /// - the replicated components which were removed since the previous change set,
+    /// - the entities which were despawned, lost their [`StableId`] or left the interest of
+    ///   the receiver.
+    pub fn collect(&mut self, world: &World) -> ChangeSet {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let this_run = world.increment_change_tick();
+
+        let mut entities = Vec::new();
+        let mut sent = EntityHashMap::default();
+        for entity in world.iter_entities() {
+            if !entity.contains::<StableId>() {
+                continue;
+            }
+            if let Some(interest) = &self.interest {
+                if !interest(entity) {
+                    continue;
+                }
+            }
+
+            let known = self.sent.remove(&entity.id());
+            let mut patch = EntityPatch {
+                entity: entity.id(),
+                components: Vec::new(),
+                removed: Vec::new(),
+            };
+            let mut replicated = HashSet::default();
+            for component_id in entity.archetype().components() {
+                let Some(type_id) = world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                else {
+                    continue;
+                };
+                let Some(registration) = type_registry.get(type_id) else {
+                    continue;
+                };
+                if registration.data::<ReflectReplicated>().is_none()
+                    && type_id != TypeId::of::<StableId>()
+                {
+                    continue;
+                }
+                replicated.insert(type_id);
+
+                let changed = match &known {
+                    Some(known) => {
+                        !known.contains(&type_id)
+                            || entity
+                                .get_change_ticks_by_id(component_id)
+                                .is_some_and(|ticks| ticks.is_changed(self.last_run, this_run))
+                    }
+                    None => true,
+                };
+                if !changed {
+                    continue;
+                }
+                if let Some(component) = registration
+                    .data::<ReflectComponent>()
+                    .and_then(|reflect_component| reflect_component.reflect(entity))
+                {
+                    patch.components.push(component.clone_value());
+                }
+            }
+
+            if let Some(known) = known {
+                for type_id in known.difference(&replicated) {
+                    if let Some(registration) = type_registry.get(*type_id) {
+                        patch
+                            .removed
+                            .push(registration.type_info().type_path().to_string());
+                    }
+                }
+            }
+            if !patch.components.is_empty() || !patch.removed.is_empty() {
+                entities.push(patch);
+            }
+            sent.insert(entity.id(), replicated);
+        }
+
+        // The entities which are left were sent before but aren't replicated anymore
+        let despawned = self.sent.keys().copied().collect();
+        self.sent = sent;
+        self.last_run = this_run;
+        let tick = self.tick;
+        self.tick += 1;
+
+        ChangeSet {
+            tick,
+            entities: ScenePatch { entities },
+            despawned,
+        }
+    }
+}
+
+/// Applies the [`ChangeSet`]s of a [`Replicator`] to a receiving world.
+///
+/// A replica maps the entities of the sending world to the entities of the receiving world.
+/// Entities sent for the first time are bound to the entity of the receiving world with the same
+/// [`StableId`] if there is one, such as an entity loaded from a save, and are spawned otherwise.
+#[derive(Default, Debug)]
+pub struct Replica {
+    entity_map: EntityHashMap<Entity, Entity>,
+    tick: Option<u64>,
+}
+
+impl Replica {
+    /// Applies `change_set` to `world`.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered in the
+    /// world's [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) trait.
+    pub fn apply(
+        &mut self,
+        change_set: &ChangeSet,
+        world: &mut World,
+    ) -> Result<(), SceneSpawnError> {
+        for entity in &change_set.despawned {
+            if let Some(entity) = self.entity_map.remove(entity) {
+                if world.get_entity(entity).is_some() {
+                    despawn_with_children_recursive(world, entity);
+                }
+            }
+        }
+
+        let new_ids: Vec<_> = change_set
+            .entities
+            .entities
+            .iter()
+            .filter(|patch| !self.entity_map.contains_key(&patch.entity))
+            .filter_map(|patch| Some((patch.entity, find_stable_id(patch)?)))
+            .collect();
+        if !new_ids.is_empty() {
+            let mut world_ids = HashMap::default();
+            for (entity, id) in world.query::<(Entity, &StableId)>().iter(world) {
+                world_ids.insert(*id, entity);
+            }
+            for (remote, id) in new_ids {
+                let entity = world_ids
+                    .get(&id)
+                    .copied()
+                    .unwrap_or_else(|| world.spawn_empty().id());
+                self.entity_map.insert(remote, entity);
+            }
+        }
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        change_set
+            .entities
+            .write_to_world_with(world, &mut self.entity_map, &registry)?;
+        self.tick = Some(change_set.tick);
+        Ok(())
+    }
+
+    /// Returns the entity of the receiving world replicating the entity `remote` of the
+    /// sending world.
+    pub fn entity(&self, remote: Entity) -> Option<Entity> {
+        self.entity_map.get(&remote).copied()
+    }
+
+    /// Returns the map of the entities of the sending world to the entities of the receiving
+    /// world.
+    pub fn entity_map(&self) -> &EntityHashMap<Entity, Entity> {
+        &self.entity_map
+    }
+
+    /// The index of the last change set applied.
+    pub fn tick(&self) -> Option<u64> {
+        self.tick
+    }
+}
+
+/// Returns the [`StableId`] of a replicated entity.
+fn find_stable_id(patch: &EntityPatch) -> Option<StableId> {
+    patch
+        .components
+        .iter()
+        .find(|component| {
+            component
+                .get_represented_type_info()
+                .is_some_and(|type_info| type_info.type_id() == TypeId::of::<StableId>())
+        })
+        .and_then(|component| StableId::from_reflect(&**component))
+}
+
+/// Handles serialization of a change set as a struct containing its index, changed entities and
+/// despawned entities.
+pub struct ChangeSetSerializer<'a> {
+    /// The change set to serialize.
+    pub change_set: &'a ChangeSet,
+    /// Type registry in which the component types used in the change set are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> ChangeSetSerializer<'a> {
+    /// Creates a change set serializer.
+    pub fn new(change_set: &'a ChangeSet, registry: &'a TypeRegistryArc) -> Self {
+        ChangeSetSerializer {
+            change_set,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for ChangeSetSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(CHANGE_SET_STRUCT, 3)?;
+        state.serialize_field(CHANGE_SET_TICK, &self.change_set.tick)?;
+        state.serialize_field(
+            CHANGE_SET_ENTITIES,
+            &EntityPatchesSerializer {
+                entities: &self.change_set.entities.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(CHANGE_SET_DESPAWNED, &self.change_set.despawned)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum ChangeSetField {
+    Tick,
+    Entities,
+    Despawned,
+}
+
+/// Handles change set deserialization.
+pub struct ChangeSetDeserializer<'a> {
+    /// Type registry in which the component types used in the change set are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ChangeSetDeserializer<'a> {
+    type Value = ChangeSet;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            CHANGE_SET_STRUCT,
+            &[CHANGE_SET_TICK, CHANGE_SET_ENTITIES, CHANGE_SET_DESPAWNED],
+            ChangeSetVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct ChangeSetVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ChangeSetVisitor<'a> {
+    type Value = ChangeSet;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("change set struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let tick = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(CHANGE_SET_TICK))?;
+        let entities = seq
+            .next_element_seed(EntityPatchesDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(CHANGE_SET_ENTITIES))?;
+        let despawned = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(CHANGE_SET_DESPAWNED))?;
+
+        Ok(ChangeSet {
+            tick,
+            entities: ScenePatch { entities },
+            despawned,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut tick = None;
+        let mut entities = None;
+        let mut despawned = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                ChangeSetField::Tick => {
+                    if tick.is_some() {
+                        return Err(Error::duplicate_field(CHANGE_SET_TICK));
+                    }
+                    tick = Some(map.next_value()?);
+                }
+                ChangeSetField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(CHANGE_SET_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(EntityPatchesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+                ChangeSetField::Despawned => {
+                    if despawned.is_some() {
+                        return Err(Error::duplicate_field(CHANGE_SET_DESPAWNED));
+                    }
+                    despawned = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let tick = tick.ok_or_else(|| Error::missing_field(CHANGE_SET_TICK))?;
+        let entities = entities.ok_or_else(|| Error::missing_field(CHANGE_SET_ENTITIES))?;
+        let despawned = despawned.ok_or_else(|| Error::missing_field(CHANGE_SET_DESPAWNED))?;
+        Ok(ChangeSet {
+            tick,
+            entities: ScenePatch { entities },
+            despawned,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChangeSetDeserializer, ChangeSetSerializer, ReflectReplicated, Replica, Replicator,
+    };
+    use crate::StableId;
+    use bevy_ecs::{
+        entity::{Entity, EntityMapper, MapEntities},
+        prelude::{Component, ReflectComponent, World},
+        reflect::{AppTypeRegistry, ReflectMapEntities},
+        world::FromWorld,
+    };
+    use bevy_reflect::Reflect;
+    use bevy_utils::Uuid;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, Replicated)]
+    struct Position(f32, f32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, Replicated)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct ServerOnly;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities, Replicated)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    impl FromWorld for Target {
+        fn from_world(_world: &mut World) -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    fn create_world(registry: &AppTypeRegistry) -> World {
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        world
+    }
+
+    fn create_registry() -> AppTypeRegistry {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<StableId>();
+            registry.register::<Uuid>();
+            registry.register::<Position>();
+            registry.register::<Health>();
+            registry.register::<ServerOnly>();
+            registry.register::<Target>();
+            registry.register::<Entity>();
+        }
+        registry
+    }
+
+    /// Sends a change set through postcard.
+    fn send(replicator: &mut Replicator, server: &World, registry: &AppTypeRegistry) -> Vec<u8> {
+        let change_set = replicator.collect(server);
+        postcard::to_allocvec(&ChangeSetSerializer::new(&change_set, registry)).unwrap()
+    }
+
+    fn receive(replica: &mut Replica, client: &mut World, bytes: &[u8]) {
+        let registry = client.resource::<AppTypeRegistry>().clone();
+        let change_set = ChangeSetDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut postcard::Deserializer::from_bytes(bytes))
+        .unwrap();
+        replica.apply(&change_set, client).unwrap();
+    }
+
+    #[test]
+    fn should_replicate_changes() {
+        let registry = create_registry();
+        let mut server = create_world(&registry);
+        let mut client = create_world(&registry);
+        let mut replicator = Replicator::default();
+        let mut replica = Replica::default();
+
+        let player = server
+            .spawn((StableId::new(), Position(0.0, 0.0), Health(10), ServerOnly))
+            .id();
+        let enemy = server
+            .spawn((StableId::new(), Position(5.0, 5.0), Target(player)))
+            .id();
+        server.spawn((Position(1.0, 1.0), Health(1)));
+
+        let bytes = send(&mut replicator, &server, &registry);
+        receive(&mut replica, &mut client, &bytes);
+        assert_eq!(2, client.entities().len());
+        let client_player = replica.entity(player).unwrap();
+        let client_enemy = replica.entity(enemy).unwrap();
+        assert!(client.get::<ServerOnly>(client_player).is_none());
+        assert_eq!(Some(&Health(10)), client.get::<Health>(client_player));
+        assert_eq!(
+            Some(&Target(client_player)),
+            client.get::<Target>(client_enemy)
+        );
+
+        // Nothing changed
+        assert!(replicator.collect(&server).is_empty());
+
+        server.get_mut::<Position>(player).unwrap().0 = 2.0;
+        server.entity_mut(player).remove::<Health>();
+        server.despawn(enemy);
+        let change_set = replicator.collect(&server);
+        assert_eq!(1, change_set.entities.entities.len());
+        assert_eq!(1, change_set.entities.entities[0].components.len());
+        assert_eq!(vec![enemy], change_set.despawned);
+
+        replica.apply(&change_set, &mut client).unwrap();
+        assert_eq!(1, client.entities().len());
+        assert_eq!(
+            Some(&Position(2.0, 0.0)),
+            client.get::<Position>(client_player)
+        );
+        assert!(client.get::<Health>(client_player).is_none());
+        assert_eq!(Some(2), replica.tick());
+    }
+
+    #[test]
+    fn should_replicate_interest() {
+        let registry = create_registry();
+        let mut server = create_world(&registry);
+        let mut client = create_world(&registry);
+        let mut replicator = Replicator::default().with_interest(|entity| {
+            entity
+                .get::<Position>()
+                .is_some_and(|position| position.0 < 10.0)
+        });
+        let mut replica = Replica::default();
+
+        let near = server.spawn((StableId::new(), Position(0.0, 0.0))).id();
+        let far = server.spawn((StableId::new(), Position(20.0, 0.0))).id();
+        replica
+            .apply(&replicator.collect(&server), &mut client)
+            .unwrap();
+        assert!(replica.entity(near).is_some());
+        assert!(replica.entity(far).is_none());
+
+        server.get_mut::<Position>(near).unwrap().0 = 15.0;
+        server.get_mut::<Position>(far).unwrap().0 = 5.0;
+        replica
+            .apply(&replicator.collect(&server), &mut client)
+            .unwrap();
+        assert!(replica.entity(near).is_none());
+        let far = replica.entity(far).unwrap();
+        assert_eq!(1, client.entities().len());
+        assert_eq!(Some(&Position(5.0, 0.0)), client.get::<Position>(far));
+    }
+
+    #[test]
+    fn should_bind_entities_by_stable_id() {
+        let registry = create_registry();
+        let mut server = create_world(&registry);
+        let mut client = create_world(&registry);
+        let mut replica = Replica::default();
+
+        let id = StableId::new();
+        let player = server.spawn((id, Health(3))).id();
+        let loaded = client.spawn((id, Health(10))).id();
+
+        replica
+            .apply(&Replicator::default().collect(&server), &mut client)
+            .unwrap();
+        assert_eq!(Some(loaded), replica.entity(player));
+        assert_eq!(1, client.entities().len());
+        assert_eq!(Some(&Health(3)), client.get::<Health>(loaded));
+    }
+}
@@ -0,0 +1,729 @@
+//! Saving the state of a world and restoring it later, such as for save games.
+//!
+//! The entities to save are marked with a [`StableId`], which identifies them across saves and
+//! sessions. [`SaveSettings::snapshot`] extracts them with the resources of the world into a
+//! [`DynamicScene`], which is serialized with a [`SaveSerializer`] and loaded back from a
+//! `.save.ron` file by the [`SaveLoader`](crate::SaveLoader). [`DynamicScene::restore`] then
+//! applies the save to a running world, updating the entities which still exist in place.
+//!
+//! Compared to the format of the [`SceneSerializer`](crate::serde::SceneSerializer):
+//! - [`Handle`](bevy_asset::Handle) components and resources are written as the path of their
+//!   asset, which is loaded with the save,
+//! - components and resources implementing [`SaveHook`] are written in their custom saved
+//!   representation.
+
+use crate::{
+    serde::{ENTITY_FIELD_COMPONENTS, ENTITY_STRUCT, SCENE_ENTITIES, SCENE_RESOURCES},
+    DynamicEntity, DynamicScene, DynamicSceneBuilder, SceneFilter, SceneSpawnError, StableId,
+};
+use bevy_asset::{AssetPath, LoadContext, ReflectHandle};
+use bevy_ecs::{entity::Entity, prelude::Resource, reflect::AppTypeRegistry, world::World};
+use bevy_hierarchy::despawn_with_children_recursive;
+use bevy_reflect::{
+    serde::{TypeRegistrationDeserializer, TypedReflectDeserializer, TypedReflectSerializer},
+    FromReflect, FromType, Reflect, ReflectFromReflect, TypeRegistration, TypeRegistry,
+    TypeRegistryArc,
+};
+use bevy_utils::{EntityHashMap, HashMap, HashSet};
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, Visitor},
+    ser::{SerializeMap, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{any::TypeId, fmt::Formatter};
+
+/// Name of the serialized save struct type.
+pub const SAVE_STRUCT: &str = "Save";
+
+/// A component or resource saved in a custom representation.
+///
+/// This is used for types which can't be serialized with reflection, or whose state should be
+/// saved differently, and requires the type data to be registered with
+/// `#[reflect(SaveHook)]` or [`TypeRegistry::register_type_data`].
+///
+/// ```
+/// # use bevy_ecs::prelude::Component;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::save::{ReflectSaveHook, SaveHook};
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(SaveHook)]
+/// struct Inventory {
+///     items: Vec<String>,
+/// }
+///
+/// impl SaveHook for Inventory {
+///     type Saved = String;
+///
+///     fn save(&self) -> String {
+///         self.items.join(",")
+///     }
+///
+///     fn restore(saved: String) -> Self {
+///         Inventory {
+///             items: saved.split(',').map(str::to_string).collect(),
+///         }
+///     }
+/// }
+/// ```
+pub trait SaveHook: Reflect + FromReflect {
+    /// The type this type is saved as, which must be registered.
+    type Saved: Reflect + FromReflect;
+
+    /// Converts this value to its saved representation.
+    fn save(&self) -> Self::Saved;
+
+    /// Converts a saved representation back to a value.
+    fn restore(saved: Self::Saved) -> Self;
+}
+
+/// Type data of the types implementing [`SaveHook`].
+#[derive(Clone)]
+pub struct ReflectSaveHook {
+    saved_type_id: TypeId,
+    save: fn(&dyn Reflect) -> Option<Box<dyn Reflect>>,
+    restore: fn(&dyn Reflect) -> Option<Box<dyn Reflect>>,
+}
+
+impl ReflectSaveHook {
+    /// The [`TypeId`] of the saved representation of the type.
+    pub fn saved_type_id(&self) -> TypeId {
+        self.saved_type_id
+    }
+
+    /// Converts `value` to its saved representation, or returns `None` if it isn't a value of
+    /// the type.
+    pub fn save(&self, value: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        (self.save)(value)
+    }
+
+    /// Converts a saved representation back to a value of the type, or returns `None` if `saved`
+    /// isn't a value of the saved type.
+    pub fn restore(&self, saved: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        (self.restore)(saved)
+    }
+}
+
+impl<T: SaveHook> FromType<T> for ReflectSaveHook {
+    fn from_type() -> Self {
+        ReflectSaveHook {
+            saved_type_id: TypeId::of::<T::Saved>(),
+            save: |value| T::from_reflect(value).map(|value| Box::new(value.save()) as _),
+            restore: |saved| {
+                T::Saved::from_reflect(saved).map(|saved| Box::new(T::restore(saved)) as _)
+            },
+        }
+    }
+}
+
+/// Selects the components and resources to save.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SaveSettings {
+    /// The components saved with the entities with a [`StableId`].
+    pub component_filter: SceneFilter,
+    /// The resources to save.
+    pub resource_filter: SceneFilter,
+}
+
+impl SaveSettings {
+    /// Extracts the entities with a [`StableId`] and the resources of `world` allowed by the
+    /// filters.
+    ///
+    /// The [`StableId`] of the entities is always extracted, since it is needed to restore them.
+    pub fn snapshot(&self, world: &World) -> DynamicScene {
+        DynamicSceneBuilder::from_world(world)
+            .with_filter(self.component_filter.clone())
+            .with_resource_filter(self.resource_filter.clone())
+            .extract_entities(
+                world
+                    .iter_entities()
+                    .filter(|entity| entity.contains::<StableId>())
+                    .map(|entity| entity.id()),
+            )
+            .extract_resources()
+            .build()
+    }
+}
+
+impl DynamicScene {
+    /// Restores a save created by [`SaveSettings::snapshot`] to `world`.
+    ///
+    /// The entities of `world` with the [`StableId`] of a saved entity are updated in place, with
+    /// the saved components inserted or overwritten, while the other saved entities are spawned.
+    /// The entities of `world` with a [`StableId`] which isn't in the save are despawned with their
+    /// descendants. Entities without a [`StableId`] are left untouched.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered in the
+    /// world's [`AppTypeRegistry`] resource, or doesn't reflect the [`Component`](bevy_ecs::component::Component) trait.
+    pub fn restore(&self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let saved_ids: HashMap<StableId, Entity> = self
+            .entities
+            .iter()
+            .filter_map(|entity| Some((find_stable_id(entity)?, entity.entity)))
+            .collect();
+
+        let mut entity_map = EntityHashMap::default();
+        let mut stale = Vec::new();
+        for entity in world.iter_entities() {
+            let Some(stable_id) = entity.get::<StableId>() else {
+                continue;
+            };
+            match saved_ids.get(stable_id) {
+                Some(&saved) => {
+                    entity_map.insert(saved, entity.id());
+                }
+                None => stale.push(entity.id()),
+            }
+        }
+        for entity in stale {
+            if world.get_entity(entity).is_some() {
+                despawn_with_children_recursive(world, entity);
+            }
+        }
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.write_to_world_with(world, &mut entity_map, &registry)
+    }
+
+    /// Serialize this dynamic scene into the [save](self) format.
+    pub fn serialize_save(&self, registry: &TypeRegistryArc) -> Result<String, crate::ron::Error> {
+        crate::serialize_ron(SaveSerializer::new(self, registry))
+    }
+}
+
+/// Returns the [`StableId`] of a saved entity.
+fn find_stable_id(entity: &DynamicEntity) -> Option<StableId> {
+    entity
+        .components
+        .iter()
+        .find(|component| {
+            component
+                .get_represented_type_info()
+                .is_some_and(|type_info| type_info.type_id() == TypeId::of::<StableId>())
+        })
+        .and_then(|component| StableId::from_reflect(&**component))
+}
+
+/// Handles serialization of a save as a struct containing its entities and resources.
+pub struct SaveSerializer<'a> {
+    /// The save to serialize.
+    pub scene: &'a DynamicScene,
+    /// Type registry in which the components and resources types used in the save are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> SaveSerializer<'a> {
+    /// Creates a save serializer.
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistryArc) -> Self {
+        SaveSerializer { scene, registry }
+    }
+}
+
+impl<'a> Serialize for SaveSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry.read();
+        let mut state = serializer.serialize_struct(SAVE_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &SaveMapSerializer {
+                entries: &self.scene.resources,
+                registry: &registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_ENTITIES,
+            &SaveEntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: &registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct SaveEntitiesSerializer<'a> {
+    entities: &'a [DynamicEntity],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SaveEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_entry(
+                &entity.entity,
+                &SaveEntitySerializer {
+                    entity,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+struct SaveEntitySerializer<'a> {
+    entity: &'a DynamicEntity,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SaveEntitySerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 1)?;
+        state.serialize_field(
+            ENTITY_FIELD_COMPONENTS,
+            &SaveMapSerializer {
+                entries: &self.entity.components,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// Serializes values of unique types as a map of type path to value, with the handles as their
+/// asset path and the types with a [`SaveHook`] in their saved representation.
+struct SaveMapSerializer<'a> {
+    entries: &'a [Box<dyn Reflect>],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SaveMapSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        let mut state = serializer.serialize_map(Some(self.entries.len()))?;
+        for reflect in self.entries {
+            let type_info = reflect.get_represented_type_info().ok_or_else(|| {
+                Error::custom(format_args!(
+                    "type `{}` doesn't have type info",
+                    reflect.reflect_type_path()
+                ))
+            })?;
+            let type_path = type_info.type_path();
+            let registration = self.registry.get(type_info.type_id());
+
+            if let Some(hook) = registration.and_then(TypeRegistration::data::<ReflectSaveHook>) {
+                let saved = hook
+                    .save(&**reflect)
+                    .ok_or_else(|| Error::custom(format_args!("failed to save `{type_path}`")))?;
+                state.serialize_entry(
+                    type_path,
+                    &TypedReflectSerializer::new(&*saved, self.registry),
+                )?;
+            } else if let Some(reflect_handle) =
+                registration.and_then(TypeRegistration::data::<ReflectHandle>)
+            {
+                let path = registration
+                    .and_then(TypeRegistration::data::<ReflectFromReflect>)
+                    .and_then(|from_reflect| from_reflect.from_reflect(&**reflect))
+                    .and_then(|handle| reflect_handle.downcast_handle_untyped(handle.as_any()))
+                    .and_then(|handle| handle.path().cloned())
+                    .ok_or_else(|| {
+                        Error::custom(format_args!(
+                            "handle `{type_path}` doesn't have an asset path"
+                        ))
+                    })?;
+                state.serialize_entry(type_path, &path)?;
+            } else {
+                state.serialize_entry(
+                    type_path,
+                    &TypedReflectSerializer::new(&**reflect, self.registry),
+                )?;
+            }
+        }
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveField {
+    Resources,
+    Entities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveEntityField {
+    Components,
+}
+
+/// Handles save deserialization.
+pub struct SaveDeserializer<'a, 'ctx> {
+    /// Type registry in which the components and resources types used in the save are registered.
+    pub type_registry: &'a TypeRegistry,
+    /// The context used to load the assets of the handles of the save, which can't be
+    /// deserialized without one.
+    pub load_context: Option<&'a mut LoadContext<'ctx>>,
+}
+
+impl<'a, 'ctx, 'de> DeserializeSeed<'de> for SaveDeserializer<'a, 'ctx> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_STRUCT,
+            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            SaveVisitor {
+                registry: self.type_registry,
+                load_context: self.load_context,
+            },
+        )
+    }
+}
+
+struct SaveVisitor<'a, 'ctx> {
+    registry: &'a TypeRegistry,
+    load_context: Option<&'a mut LoadContext<'ctx>>,
+}
+
+impl<'a, 'ctx, 'de> Visitor<'de> for SaveVisitor<'a, 'ctx> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("save struct")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(SaveMapDeserializer {
+                        registry: self.registry,
+                        load_context: self.load_context.as_deref_mut(),
+                    })?);
+                }
+                SaveField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SaveEntitiesDeserializer {
+                        registry: self.registry,
+                        load_context: self.load_context.as_deref_mut(),
+                    })?);
+                }
+            }
+        }
+
+        Ok(DynamicScene {
+            resources: resources.ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?,
+            entities: entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?,
+        })
+    }
+}
+
+struct SaveEntitiesDeserializer<'a, 'ctx> {
+    registry: &'a TypeRegistry,
+    load_context: Option<&'a mut LoadContext<'ctx>>,
+}
+
+impl<'a, 'ctx, 'de> DeserializeSeed<'de> for SaveEntitiesDeserializer<'a, 'ctx> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'ctx, 'de> Visitor<'de> for SaveEntitiesDeserializer<'a, 'ctx> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            let components = map.next_value_seed(SaveEntityDeserializer {
+                registry: self.registry,
+                load_context: self.load_context.as_deref_mut(),
+            })?;
+            entities.push(DynamicEntity { entity, components });
+        }
+        Ok(entities)
+    }
+}
+
+struct SaveEntityDeserializer<'a, 'ctx> {
+    registry: &'a TypeRegistry,
+    load_context: Option<&'a mut LoadContext<'ctx>>,
+}
+
+impl<'a, 'ctx, 'de> DeserializeSeed<'de> for SaveEntityDeserializer<'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(ENTITY_STRUCT, &[ENTITY_FIELD_COMPONENTS], self)
+    }
+}
+
+impl<'a, 'ctx, 'de> Visitor<'de> for SaveEntityDeserializer<'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("entity struct")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveEntityField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(SaveMapDeserializer {
+                        registry: self.registry,
+                        load_context: self.load_context.as_deref_mut(),
+                    })?);
+                }
+            }
+        }
+        components.ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))
+    }
+}
+
+/// Deserializes a map of type path to value, with the handles loaded from their asset path and
+/// the types with a [`SaveHook`] restored from their saved representation.
+struct SaveMapDeserializer<'a, 'ctx> {
+    registry: &'a TypeRegistry,
+    load_context: Option<&'a mut LoadContext<'ctx>>,
+}
+
+impl<'a, 'ctx, 'de> DeserializeSeed<'de> for SaveMapDeserializer<'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'ctx, 'de> Visitor<'de> for SaveMapDeserializer<'a, 'ctx> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of reflect types")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut added = HashSet::new();
+        let mut entries = Vec::new();
+        while let Some(registration) =
+            map.next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        {
+            let type_path = registration.type_info().type_path();
+            if !added.insert(registration.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{type_path}`"
+                )));
+            }
+
+            if let Some(hook) = registration.data::<ReflectSaveHook>() {
+                let saved_registration =
+                    self.registry.get(hook.saved_type_id()).ok_or_else(|| {
+                        Error::custom(format_args!(
+                            "no registration found for the saved type of `{type_path}`"
+                        ))
+                    })?;
+                let saved = map.next_value_seed(TypedReflectDeserializer::new(
+                    saved_registration,
+                    self.registry,
+                ))?;
+                entries.push(hook.restore(&*saved).ok_or_else(|| {
+                    Error::custom(format_args!("failed to restore `{type_path}`"))
+                })?);
+            } else if let Some(reflect_handle) = registration.data::<ReflectHandle>() {
+                let path = map.next_value::<AssetPath<'static>>()?;
+                let load_context = self.load_context.as_deref_mut().ok_or_else(|| {
+                    Error::custom(format_args!(
+                        "the asset `{path}` can't be loaded without a `LoadContext`"
+                    ))
+                })?;
+                let handle = reflect_handle.load(load_context, path);
+                entries.push(reflect_handle.typed(handle));
+            } else {
+                entries.push(
+                    map.next_value_seed(TypedReflectDeserializer::new(
+                        registration,
+                        self.registry,
+                    ))?,
+                );
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ron,
+        save::{ReflectSaveHook, SaveDeserializer, SaveHook, SaveSettings},
+        SceneFilter, StableId,
+    };
+    use bevy_ecs::{
+        entity::{Entity, EntityMapper, MapEntities},
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
+        reflect::{AppTypeRegistry, ReflectMapEntities},
+        world::FromWorld,
+    };
+    use bevy_reflect::Reflect;
+    use bevy_utils::Uuid;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, SaveHook)]
+    struct Inventory {
+        items: Vec<String>,
+    }
+
+    impl SaveHook for Inventory {
+        type Saved = String;
+
+        fn save(&self) -> String {
+            self.items.join(",")
+        }
+
+        fn restore(saved: String) -> Self {
+            Inventory {
+                items: saved.split(',').map(str::to_string).collect(),
+            }
+        }
+    }
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Follow(Entity);
+
+    impl MapEntities for Follow {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    impl FromWorld for Follow {
+        fn from_world(_world: &mut World) -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<StableId>();
+            registry.register::<Uuid>();
+            registry.register::<Inventory>();
+            registry.register::<Vec<String>>();
+            registry.register::<Follow>();
+            registry.register::<Entity>();
+            registry.register::<Score>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn should_restore_save_in_place() {
+        let mut world = create_world();
+        world.insert_resource(Score(3));
+        let chest = world
+            .spawn((
+                StableId::new(),
+                Inventory {
+                    items: vec!["sword".to_string(), "shield".to_string()],
+                },
+            ))
+            .id();
+        let follower_id = StableId::new();
+        let follower = world.spawn((follower_id, Follow(chest))).id();
+        let unsaved = world.spawn(Inventory::default()).id();
+
+        let settings = SaveSettings {
+            component_filter: SceneFilter::default(),
+            resource_filter: SceneFilter::default(),
+        };
+        let save = settings.snapshot(&world);
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let output = save.serialize_save(&registry.0).unwrap();
+        assert!(output.contains("\"sword,shield\""));
+
+        let mut deserializer = ron::de::Deserializer::from_str(&output).unwrap();
+        let save = SaveDeserializer {
+            type_registry: &registry.read(),
+            load_context: None,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        // Change the world after saving
+        world.resource_mut::<Score>().0 = 10;
+        world.get_mut::<Inventory>(chest).unwrap().items.clear();
+        world.despawn(follower);
+        let stale = world.spawn(StableId::new()).id();
+
+        save.restore(&mut world).unwrap();
+
+        assert_eq!(&Score(3), world.resource::<Score>());
+        assert_eq!(
+            &Inventory {
+                items: vec!["sword".to_string(), "shield".to_string()],
+            },
+            world.get::<Inventory>(chest).unwrap()
+        );
+        assert!(world.get_entity(stale).is_none());
+        assert!(world.get_entity(unsaved).is_some());
+
+        let mut query = world.query::<(&StableId, &Follow)>();
+        let (stable_id, follow) = query.single(&world);
+        assert_eq!(&follower_id, stable_id);
+        assert_eq!(chest, follow.0);
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{DynamicScene, InstanceInfo, SceneSpawnError};
+use crate::{
+    entity_mapping::{bind_anchors, SceneMappings},
+    DynamicScene, InstanceInfo, SceneAnchor, SceneSpawnError,
+};
 use bevy_asset::Asset;
 use bevy_ecs::{
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
     world::World,
 };
 use bevy_reflect::TypePath;
@@ -92,8 +95,27 @@ impl Scene {
             reflect_resource.copy(&self.world, world);
         }
 
+        // Bind the anchors of the scene to the anchors of the world, whose components are kept
+        let bound = bind_anchors(
+            world,
+            self.world.iter_entities().filter_map(|scene_entity| {
+                Some((
+                    scene_entity.id(),
+                    scene_entity.get::<SceneAnchor>()?.clone(),
+                ))
+            }),
+            &mut instance_info.entity_map,
+        );
+
+        // For each component types that reference other entities, we keep track
+        // of which entities in the scene use that component.
+        let mut scene_mappings = SceneMappings::default();
+
         for archetype in self.world.archetypes().iter() {
             for scene_entity in archetype.entities() {
+                if bound.contains(&scene_entity.entity()) {
+                    continue;
+                }
                 let entity = *instance_info
                     .entity_map
                     .entry(scene_entity.entity())
@@ -105,27 +127,29 @@ impl Scene {
                         .get_info(component_id)
                         .expect("component_ids in archetypes should have ComponentInfo");
 
-                    let reflect_component = type_registry
+                    let registration = type_registry
                         .get(component_info.type_id().unwrap())
                         .ok_or_else(|| SceneSpawnError::UnregisteredType {
                             std_type_name: component_info.name().to_string(),
-                        })
-                        .and_then(|registration| {
-                            registration.data::<ReflectComponent>().ok_or_else(|| {
-                                SceneSpawnError::UnregisteredComponent {
-                                    type_path: registration.type_info().type_path().to_string(),
-                                }
-                            })
+                        })?;
+                    let reflect_component =
+                        registration.data::<ReflectComponent>().ok_or_else(|| {
+                            SceneSpawnError::UnregisteredComponent {
+                                type_path: registration.type_info().type_path().to_string(),
+                            }
                         })?;
                     reflect_component.copy(&self.world, world, scene_entity.entity(), entity);
+                    scene_mappings.track(registration, &type_registry, entity);
                 }
             }
         }
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_all_entities(world, &mut instance_info.entity_map);
-            }
+        // Updates references to entities in the scene to entities in the world
+        scene_mappings.map(world, &mut instance_info.entity_map, &type_registry);
+
+        // The bound anchors aren't part of the instance of the scene
+        for scene_entity in bound {
+            instance_info.entity_map.remove(&scene_entity);
         }
 
         Ok(instance_info)
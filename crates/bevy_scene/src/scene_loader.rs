@@ -1,7 +1,9 @@
-use crate::ron;
 #[cfg(feature = "serialize")]
-use crate::serde::SceneDeserializer;
-use crate::DynamicScene;
+use crate::document::SceneDocumentDeserializer;
+use crate::ron;
+use crate::save::SaveDeserializer;
+use crate::serde::{SceneDeserializer, ScenePatchDeserializer};
+use crate::{DynamicScene, ScenePatch};
 use bevy_asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
 use bevy_ecs::reflect::AppTypeRegistry;
 use bevy_ecs::world::{FromWorld, World};
@@ -67,3 +69,144 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+/// [`AssetLoader`] for loading serialized [`ScenePatch`] files.
+#[derive(Debug)]
+pub struct ScenePatchLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for ScenePatchLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        ScenePatchLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for ScenePatchLoader {
+    type Asset = ScenePatch;
+    type Settings = ();
+    type Error = SceneLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let patch_deserializer = ScenePatchDeserializer {
+                type_registry: &self.type_registry.read(),
+            };
+            Ok(patch_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scnpatch", "scnpatch.ron"]
+    }
+}
+
+/// [`AssetLoader`] for loading scene [documents](crate::document) as [`DynamicScene`].
+///
+/// The assets of the handles of the document are loaded as dependencies of the scene.
+#[derive(Debug)]
+pub struct SceneDocumentLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneDocumentLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        SceneDocumentLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for SceneDocumentLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = SceneLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let document_deserializer = SceneDocumentDeserializer {
+                type_registry: &self.type_registry.read(),
+                load_context: Some(load_context),
+            };
+            Ok(document_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scene.ron"]
+    }
+}
+
+/// [`AssetLoader`] for loading [saves](crate::save) as [`DynamicScene`].
+///
+/// The assets of the handles of the save are loaded as dependencies of the scene.
+#[derive(Debug)]
+pub struct SaveLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SaveLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        SaveLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for SaveLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = SceneLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let save_deserializer = SaveDeserializer {
+                type_registry: &self.type_registry.read(),
+                load_context: Some(load_context),
+            };
+            Ok(save_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["save.ron"]
+    }
+}
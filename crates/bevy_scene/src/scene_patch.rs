@@ -0,0 +1,389 @@
+#[cfg(feature = "serialize")]
+use crate::{ron, serde::ScenePatchSerializer};
+use crate::{DynamicScene, SceneFilter, SceneSpawnError};
+use bevy_asset::Asset;
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+#[cfg(feature = "serialize")]
+use bevy_reflect::TypeRegistryArc;
+use bevy_reflect::{Reflect, TypePath, TypeRegistration};
+use bevy_utils::{EntityHashMap, HashMap};
+use std::any::TypeId;
+
+/// The per-instance overrides of a spawned [`DynamicScene`]: the components of its entities that
+/// differ from the scene.
+///
+/// A patch is created from a spawned instance with [`ScenePatch::from_instance`] or
+/// [`SceneSpawner::diff_instance`](crate::SceneSpawner::diff_instance), and only contains the
+/// components that changed, so it can be saved next to the scene it applies to. Spawning the scene
+/// with [`SceneSpawner::spawn_dynamic_with_patch`](crate::SceneSpawner::spawn_dynamic_with_patch)
+/// applies the patch to the new instance, and applies it again when the scene is modified, so the
+/// overrides are kept when the scene file is edited.
+#[derive(Asset, TypePath, Default)]
+pub struct ScenePatch {
+    /// The patches of the entities of the scene that differ in the instance.
+    pub entities: Vec<EntityPatch>,
+}
+
+/// The differences between an entity of a scene instance and the entity of the scene.
+pub struct EntityPatch {
+    /// The identifier of the entity in the [`DynamicScene`].
+    pub entity: Entity,
+    /// The components whose value differs from the scene, or that the entity of the scene doesn't
+    /// have.
+    ///
+    /// Components that reference entities use the identifiers of the scene.
+    pub components: Vec<Box<dyn Reflect>>,
+    /// The type paths of the components of the entity of the scene that were removed from the
+    /// instance.
+    pub removed: Vec<String>,
+}
+
+impl ScenePatch {
+    /// Create the patch of an instance of `scene` spawned in `world`, where `entity_map` maps the
+    /// entities of the scene to the entities of the instance.
+    ///
+    /// Only the components allowed by `filter` are compared. Components added to the instance by
+    /// the app rather than edited, like the `Parent` of the root of an instance spawned as a
+    /// child or the computed `GlobalTransform`, should be denied. Components that can't be
+    /// compared with [`Reflect::reflect_partial_eq`] are always part of the patch.
+    pub fn from_instance(
+        scene: &DynamicScene,
+        world: &World,
+        entity_map: &EntityHashMap<Entity, Entity>,
+        filter: &SceneFilter,
+    ) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        // Maps the entities of the instance back to the entities of the scene
+        let instance_map: EntityHashMap<Entity, Entity> = entity_map
+            .iter()
+            .map(|(&scene_entity, &entity)| (entity, scene_entity))
+            .collect();
+
+        let mut entities = Vec::new();
+        for scene_entity in &scene.entities {
+            let Some(entity) = entity_map
+                .get(&scene_entity.entity)
+                .and_then(|&entity| world.get_entity(entity))
+            else {
+                continue;
+            };
+            let mut patch = EntityPatch {
+                entity: scene_entity.entity,
+                components: Vec::new(),
+                removed: Vec::new(),
+            };
+
+            for component_id in entity.archetype().components() {
+                let Some(type_id) = world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                else {
+                    continue;
+                };
+                if filter.is_denied_by_id(type_id) {
+                    continue;
+                }
+                let Some(registration) = type_registry.get(type_id) else {
+                    continue;
+                };
+                let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                    continue;
+                };
+                let Some(component) = reflect_component.reflect(entity) else {
+                    continue;
+                };
+                let component = match registration.data::<ReflectMapEntities>() {
+                    Some(reflect_map_entities) => map_to_scene(
+                        component,
+                        reflect_component,
+                        reflect_map_entities,
+                        &instance_map,
+                    ),
+                    None => component.clone_value(),
+                };
+
+                let unchanged = scene_entity
+                    .components
+                    .iter()
+                    .find(|scene_component| {
+                        scene_component
+                            .get_represented_type_info()
+                            .is_some_and(|type_info| type_info.type_id() == type_id)
+                    })
+                    .and_then(|scene_component| scene_component.reflect_partial_eq(&*component))
+                    .unwrap_or(false);
+                if !unchanged {
+                    patch.components.push(component);
+                }
+            }
+
+            for scene_component in &scene_entity.components {
+                let Some(type_info) = scene_component.get_represented_type_info() else {
+                    continue;
+                };
+                if filter.is_denied_by_id(type_info.type_id()) {
+                    continue;
+                }
+                let removed = type_registry
+                    .get(type_info.type_id())
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .is_some_and(|reflect_component| !reflect_component.contains(entity));
+                if removed {
+                    patch.removed.push(type_info.type_path().to_string());
+                }
+            }
+
+            if !patch.components.is_empty() || !patch.removed.is_empty() {
+                entities.push(patch);
+            }
+        }
+
+        Self { entities }
+    }
+
+    /// Apply the patch to an instance of its scene spawned in `world`, where `entity_map` maps the
+    /// entities of the scene to the entities of the instance.
+    ///
+    /// Entities of the patch that aren't in the instance, for example because they were removed
+    /// from the scene since the patch was created, are skipped.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered
+    /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) trait.
+    pub fn write_to_world_with(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity, Entity>,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = type_registry.read();
+
+        // For each component types that reference other entities, we keep track
+        // of which entities of the instance use that component.
+        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+
+        for entity_patch in &self.entities {
+            let Some(&entity) = entity_map.get(&entity_patch.entity) else {
+                continue;
+            };
+            let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+
+            for component in &entity_patch.components {
+                let type_info = component.get_represented_type_info().ok_or_else(|| {
+                    SceneSpawnError::NoRepresentedType {
+                        type_path: component.reflect_type_path().to_string(),
+                    }
+                })?;
+                let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
+                    SceneSpawnError::UnregisteredButReflectedType {
+                        type_path: type_info.type_path().to_string(),
+                    }
+                })?;
+                if registration.data::<ReflectMapEntities>().is_some() {
+                    scene_mappings
+                        .entry(registration.type_id())
+                        .or_default()
+                        .push(entity);
+                }
+                reflect_component(registration)?.apply_or_insert(&mut entity_mut, &**component);
+            }
+
+            for type_path in &entity_patch.removed {
+                let registration =
+                    type_registry.get_with_type_path(type_path).ok_or_else(|| {
+                        SceneSpawnError::UnregisteredButReflectedType {
+                            type_path: type_path.clone(),
+                        }
+                    })?;
+                reflect_component(registration)?.remove(&mut entity_mut);
+            }
+        }
+
+        // Updates references to entities in the scene to entities in the world
+        for (type_id, entities) in scene_mappings {
+            let registration = type_registry.get(type_id).expect(
+                "we should be getting TypeId from this TypeRegistration in the first place",
+            );
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect.map_entities(world, entity_map, &entities);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the patch to an instance of its scene spawned in `world`, where `entity_map` maps the
+    /// entities of the scene to the entities of the instance.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered
+    /// in the world's [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) trait.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity, Entity>,
+    ) -> Result<(), SceneSpawnError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.write_to_world_with(world, entity_map, &registry)
+    }
+
+    /// Serialize this scene patch into rust object notation (ron).
+    #[cfg(feature = "serialize")]
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        crate::serialize_ron(ScenePatchSerializer::new(self, registry))
+    }
+}
+
+fn reflect_component(
+    registration: &TypeRegistration,
+) -> Result<&ReflectComponent, SceneSpawnError> {
+    registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+            type_path: registration.type_info().type_path().to_string(),
+        })
+}
+
+/// Maps the entities referenced by `component` from the entities of an instance to the entities
+/// of its scene, with `instance_map`.
+///
+/// The component is mapped in a world of its own since [`ReflectMapEntities`] can only map the
+/// components of a [`World`].
+fn map_to_scene(
+    component: &dyn Reflect,
+    reflect_component: &ReflectComponent,
+    reflect_map_entities: &ReflectMapEntities,
+    instance_map: &EntityHashMap<Entity, Entity>,
+) -> Box<dyn Reflect> {
+    let mut world = World::new();
+    let mut entity = world.spawn_empty();
+    reflect_component.insert(&mut entity, component);
+    let entity = entity.id();
+    // Mapping reserves entities for the references to entities outside of the instance
+    let mut instance_map = instance_map.iter().map(|(&a, &b)| (a, b)).collect();
+    reflect_map_entities.map_entities(&mut world, &mut instance_map, &[entity]);
+    reflect_component
+        .reflect(world.entity(entity))
+        .expect("the component was inserted in the entity")
+        .clone_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicSceneBuilder, SceneFilter, ScenePatch};
+    use bevy_ecs::{
+        entity::{Entity, EntityMapper, MapEntities},
+        prelude::{Component, ReflectComponent},
+        reflect::{AppTypeRegistry, ReflectMapEntities},
+        world::{FromWorld, World},
+    };
+    use bevy_reflect::{Reflect, TypePath};
+    use bevy_utils::EntityHashMap;
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, PartialEq)]
+    struct Armor(u32);
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component, MapEntities, PartialEq)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    impl FromWorld for Target {
+        fn from_world(_world: &mut World) -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Armor>();
+            registry.register::<Target>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn patch_is_reapplied_to_new_instance() {
+        let mut scene_world = create_world();
+        let a = scene_world.spawn((Health(10), Armor(1))).id();
+        let b = scene_world.spawn((Health(20), Target(a))).id();
+        let scene = DynamicSceneBuilder::from_world(&scene_world)
+            .extract_entities([a, b].into_iter())
+            .build();
+
+        let mut world = create_world();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let instance_a = entity_map[&a];
+        let instance_b = entity_map[&b];
+
+        world.entity_mut(instance_a).insert(Health(15));
+        world.entity_mut(instance_a).remove::<Armor>();
+        world.entity_mut(instance_b).insert(Target(instance_b));
+
+        let patch = ScenePatch::from_instance(&scene, &world, &entity_map, &SceneFilter::Unset);
+        assert_eq!(2, patch.entities.len());
+        let patch_a = patch
+            .entities
+            .iter()
+            .find(|patch| patch.entity == a)
+            .unwrap();
+        assert_eq!(1, patch_a.components.len());
+        assert_eq!(vec![Armor::type_path()], patch_a.removed);
+        let patch_b = patch
+            .entities
+            .iter()
+            .find(|patch| patch.entity == b)
+            .unwrap();
+        assert_eq!(1, patch_b.components.len());
+        assert!(patch_b.components[0]
+            .reflect_partial_eq(&Target(b))
+            .unwrap());
+
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        patch.write_to_world(&mut world, &mut entity_map).unwrap();
+        let new_a = entity_map[&a];
+        let new_b = entity_map[&b];
+
+        assert_eq!(Some(&Health(15)), world.get::<Health>(new_a));
+        assert_eq!(None, world.get::<Armor>(new_a));
+        assert_eq!(Some(&Health(20)), world.get::<Health>(new_b));
+        assert_eq!(Some(&Target(new_b)), world.get::<Target>(new_b));
+
+        let patch = ScenePatch::from_instance(
+            &scene,
+            &world,
+            &entity_map,
+            &SceneFilter::default().deny::<Health>(),
+        );
+        let patch_a = patch
+            .entities
+            .iter()
+            .find(|patch| patch.entity == a)
+            .unwrap();
+        assert!(patch_a.components.is_empty());
+    }
+}
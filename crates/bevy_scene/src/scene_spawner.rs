@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Scene};
+use crate::{DynamicScene, Scene, SceneFilter, ScenePatch};
 use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::{
     entity::Entity,
@@ -53,15 +53,23 @@ impl InstanceId {
 /// Deferred methods: (Scene operations will be processed when the [`scene_spawner_system`] is run)
 /// - [`spawn_dynamic`](Self::spawn_dynamic)
 /// - [`spawn_dynamic_as_child`](Self::spawn_dynamic_as_child)
+/// - [`spawn_dynamic_with_patch`](Self::spawn_dynamic_with_patch)
+/// - [`spawn_dynamic_as_child_with_patch`](Self::spawn_dynamic_as_child_with_patch)
 /// - [`spawn`](Self::spawn)
 /// - [`spawn_as_child`](Self::spawn_as_child)
 /// - [`despawn`](Self::despawn)
 /// - [`despawn_instance`](Self::despawn_instance)
+///
+/// The references to entities of a scene in the components of its instances are mapped to the
+/// spawned entities, whether the components implement [`MapEntities`](bevy_ecs::entity::MapEntities)
+/// or only contain an [`Entity`] found through reflection. A scene can reference entities of the
+/// world outside of its instances with a [`SceneAnchor`](crate::SceneAnchor).
 #[derive(Default, Resource)]
 pub struct SceneSpawner {
     spawned_scenes: HashMap<AssetId<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<AssetId<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
+    instance_patches: HashMap<InstanceId, Handle<ScenePatch>>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
@@ -123,6 +131,12 @@ pub enum SceneSpawnError {
         /// Id of the non-existent scene.
         id: AssetId<Scene>,
     },
+    /// Dynamic scene instance with the given id does not exist.
+    #[error("dynamic scene instance does not exist")]
+    NonExistentInstance {
+        /// Id of the non-existent instance.
+        id: InstanceId,
+    },
 }
 
 impl SceneSpawner {
@@ -145,6 +159,36 @@ impl SceneSpawner {
         instance_id
     }
 
+    /// Schedule the spawn of a new instance of the provided dynamic scene, with the overrides of
+    /// `patch` applied to it.
+    ///
+    /// The instance is spawned once both the scene and the patch are loaded, and the patch is
+    /// applied again each time the scene is modified.
+    pub fn spawn_dynamic_with_patch(
+        &mut self,
+        id: impl Into<Handle<DynamicScene>>,
+        patch: impl Into<Handle<ScenePatch>>,
+    ) -> InstanceId {
+        let instance_id = self.spawn_dynamic(id);
+        self.instance_patches.insert(instance_id, patch.into());
+        instance_id
+    }
+
+    /// Schedule the spawn of a new instance of the provided dynamic scene as a child of `parent`,
+    /// with the overrides of `patch` applied to it.
+    ///
+    /// See [`spawn_dynamic_with_patch`](Self::spawn_dynamic_with_patch).
+    pub fn spawn_dynamic_as_child_with_patch(
+        &mut self,
+        id: impl Into<Handle<DynamicScene>>,
+        patch: impl Into<Handle<ScenePatch>>,
+        parent: Entity,
+    ) -> InstanceId {
+        let instance_id = self.spawn_dynamic_as_child(id, parent);
+        self.instance_patches.insert(instance_id, patch.into());
+        instance_id
+    }
+
     /// Schedule the spawn of a new instance of the provided scene.
     pub fn spawn(&mut self, id: impl Into<Handle<Scene>>) -> InstanceId {
         let instance_id = InstanceId::new();
@@ -186,6 +230,7 @@ impl SceneSpawner {
 
     /// Immediately despawns a scene instance, removing all its entities from the world.
     pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
+        self.instance_patches.remove(instance_id);
         if let Some(instance) = self.spawned_instances.remove(instance_id) {
             for &entity in instance.entity_map.values() {
                 let _ = world.despawn(entity);
@@ -223,6 +268,60 @@ impl SceneSpawner {
         })
     }
 
+    /// Applies the patch of an instance, if it has one and it is loaded.
+    fn patch_instance(
+        world: &mut World,
+        patch: Option<&Handle<ScenePatch>>,
+        entity_map: &mut EntityHashMap<Entity, Entity>,
+    ) -> Result<(), SceneSpawnError> {
+        let Some(patch) = patch else {
+            return Ok(());
+        };
+        if !world.contains_resource::<Assets<ScenePatch>>() {
+            return Ok(());
+        }
+        world.resource_scope(
+            |world, patches: Mut<Assets<ScenePatch>>| match patches.get(patch) {
+                Some(patch) => patch.write_to_world(world, entity_map),
+                None => Ok(()),
+            },
+        )
+    }
+
+    /// Returns the differences between an instance of a dynamic scene and the scene, with the
+    /// components allowed by `filter`.
+    ///
+    /// The returned [`ScenePatch`] can be saved and used to spawn the scene again with the same
+    /// overrides with [`spawn_dynamic_with_patch`](Self::spawn_dynamic_with_patch). See
+    /// [`ScenePatch::from_instance`].
+    pub fn diff_instance(
+        &self,
+        world: &World,
+        instance_id: InstanceId,
+        filter: &SceneFilter,
+    ) -> Result<ScenePatch, SceneSpawnError> {
+        let not_found = || SceneSpawnError::NonExistentInstance { id: instance_id };
+        let instance = self
+            .spawned_instances
+            .get(&instance_id)
+            .ok_or_else(not_found)?;
+        let id = self
+            .spawned_dynamic_scenes
+            .iter()
+            .find_map(|(id, instances)| instances.contains(&instance_id).then_some(*id))
+            .ok_or_else(not_found)?;
+        let scene = world
+            .resource::<Assets<DynamicScene>>()
+            .get(id)
+            .ok_or(SceneSpawnError::NonExistentScene { id })?;
+        Ok(ScenePatch::from_instance(
+            scene,
+            world,
+            &instance.entity_map,
+            filter,
+        ))
+    }
+
     /// Immediately spawns a new instance of the provided scene.
     pub fn spawn_sync(
         &mut self,
@@ -266,6 +365,11 @@ impl SceneSpawner {
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
                         Self::spawn_dynamic_internal(world, *id, &mut instance_info.entity_map)?;
+                        Self::patch_instance(
+                            world,
+                            self.instance_patches.get(instance_id),
+                            &mut instance_info.entity_map,
+                        )?;
                     }
                 }
             }
@@ -297,9 +401,22 @@ impl SceneSpawner {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
         for (handle, instance_id) in scenes_to_spawn {
+            let patch = self.instance_patches.get(&instance_id);
+            if patch.is_some_and(|patch| {
+                !world
+                    .get_resource::<Assets<ScenePatch>>()
+                    .is_some_and(|patches| patches.contains(patch))
+            }) {
+                // Wait for the patch to be loaded to spawn the instance with its overrides
+                self.dynamic_scenes_to_spawn.push((handle, instance_id));
+                continue;
+            }
+
             let mut entity_map = EntityHashMap::default();
 
-            match Self::spawn_dynamic_internal(world, handle.id(), &mut entity_map) {
+            match Self::spawn_dynamic_internal(world, handle.id(), &mut entity_map)
+                .and_then(|_| Self::patch_instance(world, patch, &mut entity_map))
+            {
                 Ok(_) => {
                     self.spawned_instances
                         .insert(instance_id, InstanceInfo { entity_map });
@@ -407,6 +524,9 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner
             .scenes_to_spawn
             .retain(|(_, instance)| !dead_instances.contains(instance));
+        scene_spawner
+            .instance_patches
+            .retain(|instance, _| !dead_instances.contains(instance));
 
         let scene_asset_events = world.resource::<Events<AssetEvent<DynamicScene>>>();
 
@@ -1,6 +1,6 @@
 //! `serde` serialization and deserialization implementation for Bevy scenes.
 
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, EntityPatch, ScenePatch};
 use bevy_ecs::entity::Entity;
 use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
 use bevy_reflect::{
@@ -28,6 +28,16 @@ pub const ENTITY_STRUCT: &str = "Entity";
 /// Name of the serialized component field in an entity struct.
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
+/// Name of the serialized scene patch struct type.
+pub const PATCH_STRUCT: &str = "ScenePatch";
+/// Name of the serialized entities field in a scene patch struct.
+pub const PATCH_ENTITIES: &str = "entities";
+
+/// Name of the serialized entity patch struct type.
+pub const ENTITY_PATCH_STRUCT: &str = "EntityPatch";
+/// Name of the serialized removed components field in an entity patch struct.
+pub const ENTITY_PATCH_FIELD_REMOVED: &str = "removed";
+
 /// Handles serialization of a scene as a struct containing its entities and resources.
 ///
 /// # Examples
@@ -189,6 +199,92 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
     }
 }
 
+/// Handles serialization of a scene patch as a struct containing its entity patches.
+pub struct ScenePatchSerializer<'a> {
+    /// The scene patch to serialize.
+    pub patch: &'a ScenePatch,
+    /// Type registry in which the component types used in the patch are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> ScenePatchSerializer<'a> {
+    /// Creates a scene patch serializer.
+    pub fn new(patch: &'a ScenePatch, registry: &'a TypeRegistryArc) -> Self {
+        ScenePatchSerializer { patch, registry }
+    }
+}
+
+impl<'a> Serialize for ScenePatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(PATCH_STRUCT, 1)?;
+        state.serialize_field(
+            PATCH_ENTITIES,
+            &EntityPatchesSerializer {
+                entities: &self.patch.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// Handles serialization of multiple entity patches as a map of entity id to serialized patch.
+pub struct EntityPatchesSerializer<'a> {
+    /// The entity patches to serialize.
+    pub entities: &'a [EntityPatch],
+    /// Type registry in which the component types used by the patches are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntityPatchesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
+        for patch in self.entities {
+            state.serialize_entry(
+                &patch.entity,
+                &EntityPatchSerializer {
+                    patch,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Handles entity patch serialization as a map of changed components and a list of removed
+/// component types.
+pub struct EntityPatchSerializer<'a> {
+    /// The entity patch to serialize.
+    pub patch: &'a EntityPatch,
+    /// Type registry in which the component types used by the patch are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntityPatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(ENTITY_PATCH_STRUCT, 2)?;
+        state.serialize_field(
+            ENTITY_FIELD_COMPONENTS,
+            &SceneMapSerializer {
+                entries: &self.patch.components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(ENTITY_PATCH_FIELD_REMOVED, &self.patch.removed)?;
+        state.end()
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum SceneField {
@@ -202,6 +298,19 @@ enum EntityField {
     Components,
 }
 
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PatchField {
+    Entities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntityPatchField {
+    Components,
+    Removed,
+}
+
 /// Handles scene deserialization.
 pub struct SceneDeserializer<'a> {
     /// Type registry in which the components and resources types used in the scene to deserialize are registered.
@@ -494,11 +603,219 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     }
 }
 
+/// Handles scene patch deserialization.
+pub struct ScenePatchDeserializer<'a> {
+    /// Type registry in which the component types used in the patch to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ScenePatchDeserializer<'a> {
+    type Value = ScenePatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PATCH_STRUCT,
+            &[PATCH_ENTITIES],
+            ScenePatchVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct ScenePatchVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ScenePatchVisitor<'a> {
+    type Value = ScenePatch;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("scene patch struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PatchField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(PATCH_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(EntityPatchesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let entities = entities.ok_or_else(|| Error::missing_field(PATCH_ENTITIES))?;
+        Ok(ScenePatch { entities })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entities = seq
+            .next_element_seed(EntityPatchesDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(PATCH_ENTITIES))?;
+
+        Ok(ScenePatch { entities })
+    }
+}
+
+/// Handles deserialization for a collection of entity patches.
+pub struct EntityPatchesDeserializer<'a> {
+    /// Type registry in which the component types used by the patches to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityPatchesDeserializer<'a> {
+    type Value = Vec<EntityPatch>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EntityPatchesVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+struct EntityPatchesVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EntityPatchesVisitor<'a> {
+    type Value = Vec<EntityPatch>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of entity patches")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            let patch = map.next_value_seed(EntityPatchDeserializer {
+                entity,
+                type_registry: self.type_registry,
+            })?;
+            entities.push(patch);
+        }
+
+        Ok(entities)
+    }
+}
+
+/// Handle deserialization of an entity patch.
+pub struct EntityPatchDeserializer<'a> {
+    /// Id of the patched entity in its scene.
+    pub entity: Entity,
+    /// Type registry in which the component types used by the patch to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityPatchDeserializer<'a> {
+    type Value = EntityPatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_PATCH_STRUCT,
+            &[ENTITY_FIELD_COMPONENTS, ENTITY_PATCH_FIELD_REMOVED],
+            EntityPatchVisitor {
+                entity: self.entity,
+                registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct EntityPatchVisitor<'a> {
+    pub entity: Entity,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EntityPatchVisitor<'a> {
+    type Value = EntityPatch;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("entity patch")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let components = seq
+            .next_element_seed(SceneMapDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+        let removed = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(ENTITY_PATCH_FIELD_REMOVED))?;
+
+        Ok(EntityPatch {
+            entity: self.entity,
+            components,
+            removed,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        let mut removed = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                EntityPatchField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(SceneMapDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                EntityPatchField::Removed => {
+                    if removed.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_PATCH_FIELD_REMOVED));
+                    }
+                    removed = Some(map.next_value()?);
+                }
+            }
+        }
+
+        Ok(EntityPatch {
+            entity: self.entity,
+            components: components.unwrap_or_default(),
+            removed: removed.unwrap_or_default(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ron;
-    use crate::serde::{SceneDeserializer, SceneSerializer};
-    use crate::{DynamicScene, DynamicSceneBuilder};
+    use crate::serde::{SceneDeserializer, ScenePatchDeserializer, SceneSerializer};
+    use crate::{DynamicScene, DynamicSceneBuilder, EntityPatch, ScenePatch};
     use bevy_ecs::entity::{Entity, EntityMapper, MapEntities};
     use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
     use bevy_ecs::query::{With, Without};
@@ -867,6 +1184,51 @@ mod tests {
         assert_scene_eq(&scene, &deserialized_scene);
     }
 
+    #[test]
+    fn should_roundtrip_patch() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>();
+
+        let patch = ScenePatch {
+            entities: vec![EntityPatch {
+                entity: Entity::from_raw(1),
+                components: vec![Box::new(Foo(7)), Box::new(Bar(3))],
+                removed: vec!["bevy_scene::serde::tests::Baz".to_string()],
+            }],
+        };
+
+        let expected = r#"(
+  entities: {
+    1: (
+      components: {
+        "bevy_scene::serde::tests::Foo": (7),
+        "bevy_scene::serde::tests::Bar": (3),
+      },
+      removed: [
+        "bevy_scene::serde::tests::Baz",
+      ],
+    ),
+  },
+)"#;
+        let output = patch.serialize_ron(&registry.0).unwrap();
+        assert_eq!(expected, output);
+
+        let mut deserializer = ron::de::Deserializer::from_str(&output).unwrap();
+        let patch_deserializer = ScenePatchDeserializer {
+            type_registry: &registry.read(),
+        };
+        let deserialized_patch = patch_deserializer.deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(1, deserialized_patch.entities.len());
+        let entity_patch = &deserialized_patch.entities[0];
+        assert_eq!(Entity::from_raw(1), entity_patch.entity);
+        assert_eq!(2, entity_patch.components.len());
+        assert!(entity_patch.components[0]
+            .reflect_partial_eq(&Foo(7))
+            .unwrap_or_default());
+        assert_eq!(patch.entities[0].removed, entity_patch.removed);
+    }
+
     /// A crude equality checker for [`DynamicScene`], used solely for testing purposes.
     fn assert_scene_eq(expected: &DynamicScene, received: &DynamicScene) {
         assert_eq!(
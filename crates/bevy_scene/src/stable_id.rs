@@ -0,0 +1,170 @@
+use bevy_ecs::{
+    entity::Entity,
+    prelude::{Changed, Component, ReflectComponent, RemovedComponents, Resource},
+    system::{Query, ResMut},
+};
+use bevy_reflect::Reflect;
+use bevy_utils::{tracing::warn, EntityHashMap, HashMap, Uuid};
+use std::fmt::{Display, Formatter};
+
+/// A stable identifier of an entity, which identifies it across sessions and machines.
+///
+/// An [`Entity`] is only valid in the world which allocated it, and its index is reused once
+/// it is despawned, so it can't be used to refer to an entity in a save file or from another
+/// process. Entities which need to be referenced this way are given a [`StableId`] instead:
+/// either a new random identifier with [`StableId::new`], or an identifier provided by the
+/// application with [`StableId::from_u128`] or [`From<Uuid>`].
+///
+/// Stable identifiers are always extracted with their entity by the
+/// [`DynamicSceneBuilder`](crate::DynamicSceneBuilder), so they are part of serialized scenes
+/// and saves, and the entities of a world are found from their identifier with the
+/// [`StableIdIndex`].
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect(Component, Debug, PartialEq, Hash)]
+pub struct StableId(pub Uuid);
+
+impl StableId {
+    /// Creates a new random identifier.
+    pub fn new() -> Self {
+        StableId(Uuid::new_v4())
+    }
+
+    /// Creates an identifier from a value provided by the application.
+    pub const fn from_u128(value: u128) -> Self {
+        StableId(Uuid::from_u128(value))
+    }
+}
+
+impl Default for StableId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for StableId {
+    fn from(uuid: Uuid) -> Self {
+        StableId(uuid)
+    }
+}
+
+impl Display for StableId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Finds the entities of the world from their [`StableId`].
+///
+/// The index is updated by the [`update_stable_id_index`] system, which is added to
+/// [`PostUpdate`](bevy_app::PostUpdate) by the [`ScenePlugin`](crate::ScenePlugin), so the
+/// identifiers added during a frame, including by the scenes spawned in
+/// [`SpawnScene`](bevy_app::SpawnScene), are found after it ran.
+///
+/// Each identifier should be given to a single entity. When several entities share an
+/// identifier, the last one indexed is found.
+#[derive(Resource, Debug, Default)]
+pub struct StableIdIndex {
+    entities: HashMap<StableId, Entity>,
+    ids: EntityHashMap<Entity, StableId>,
+}
+
+impl StableIdIndex {
+    /// Returns the entity with the identifier `id`.
+    pub fn get(&self, id: StableId) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    /// Returns the identifier of `entity`.
+    pub fn id(&self, entity: Entity) -> Option<StableId> {
+        self.ids.get(&entity).copied()
+    }
+
+    /// Returns `true` if an entity has the identifier `id`.
+    pub fn contains(&self, id: StableId) -> bool {
+        self.entities.contains_key(&id)
+    }
+
+    /// Returns an iterator over the indexed identifiers and their entity, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (StableId, Entity)> + '_ {
+        self.entities.iter().map(|(&id, &entity)| (id, entity))
+    }
+
+    /// Returns the number of indexed identifiers.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no identifier is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Indexes `entity` under `id`, replacing its previous identifier.
+    pub fn insert(&mut self, entity: Entity, id: StableId) {
+        self.remove(entity);
+        if let Some(previous) = self.entities.insert(id, entity) {
+            warn!("The stable id {id} of {entity:?} is already used by {previous:?}");
+            self.ids.remove(&previous);
+        }
+        self.ids.insert(entity, id);
+    }
+
+    /// Removes `entity` from the index, and returns its identifier.
+    pub fn remove(&mut self, entity: Entity) -> Option<StableId> {
+        let id = self.ids.remove(&entity)?;
+        if self.entities.get(&id) == Some(&entity) {
+            self.entities.remove(&id);
+        }
+        Some(id)
+    }
+}
+
+/// Updates the [`StableIdIndex`] with the [`StableId`]s added, changed and removed since the
+/// system last ran.
+pub fn update_stable_id_index(
+    mut index: ResMut<StableIdIndex>,
+    changed: Query<(Entity, &StableId), Changed<StableId>>,
+    mut removed: RemovedComponents<StableId>,
+) {
+    for entity in removed.read() {
+        index.remove(entity);
+    }
+    for (entity, &id) in &changed {
+        index.insert(entity, id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_stable_id_index, StableId, StableIdIndex};
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    #[test]
+    fn should_index_stable_ids() {
+        let mut world = World::new();
+        world.init_resource::<StableIdIndex>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_stable_id_index);
+
+        let player_id = StableId::from_u128(1);
+        let player = world.spawn(player_id).id();
+        let chest = world.spawn(StableId::new()).id();
+        schedule.run(&mut world);
+
+        let index = world.resource::<StableIdIndex>();
+        assert_eq!(2, index.len());
+        assert_eq!(Some(player), index.get(player_id));
+        assert_eq!(world.get::<StableId>(chest).copied(), index.id(chest));
+
+        let renamed = StableId::from_u128(2);
+        *world.get_mut::<StableId>(player).unwrap() = renamed;
+        world.despawn(chest);
+        schedule.run(&mut world);
+
+        let index = world.resource::<StableIdIndex>();
+        assert_eq!(1, index.len());
+        assert!(!index.contains(player_id));
+        assert_eq!(Some(player), index.get(renamed));
+        assert_eq!(None, index.id(chest));
+    }
+}
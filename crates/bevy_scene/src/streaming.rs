@@ -0,0 +1,477 @@
+use bevy_asset::{AssetPath, AssetServer, Handle, LoadState, RecursiveDependencyLoadState};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::{Component, With},
+    system::{Commands, Query, Res, Resource},
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_math::{IVec3, Vec3};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::{DynamicScene, DynamicSceneBundle, Scene, SceneBundle};
+
+/// A grid of scene chunks, which are loaded and spawned as the [`StreamingObserver`]s approach
+/// them, and despawned as they move away.
+///
+/// The chunk at a key `k` of [`chunks`](Self::chunks) covers the box from `k * chunk_size` to
+/// `(k + 1) * chunk_size`, in the local space of the entity with this component. Its scene is
+/// spawned as a child of this entity, at the minimum corner of the box.
+///
+/// A chunk is loaded once an observer is within [`load_distance`](Self::load_distance) of its
+/// box, and its scene is spawned once it's loaded with its dependencies. It is despawned, and its
+/// scene asset released, once all the observers are farther than
+/// [`unload_distance`](Self::unload_distance), so that chunks near the limit aren't spawned and
+/// despawned repeatedly.
+#[derive(Component)]
+pub struct ChunkedScene {
+    /// The size of a chunk.
+    pub chunk_size: Vec3,
+    /// The scenes of the chunks of the grid, by chunk key.
+    pub chunks: HashMap<IVec3, ChunkSource>,
+    /// The distance to a chunk at which it is loaded.
+    pub load_distance: f32,
+    /// The distance to a chunk at which it is despawned, which should be greater than
+    /// [`load_distance`](Self::load_distance).
+    pub unload_distance: f32,
+    streamed: HashMap<IVec3, StreamedChunk>,
+}
+
+impl ChunkedScene {
+    /// Creates a grid of chunks of the given size, without any chunk.
+    pub fn new(chunk_size: Vec3, load_distance: f32, unload_distance: f32) -> Self {
+        Self {
+            chunk_size,
+            chunks: HashMap::default(),
+            load_distance,
+            unload_distance,
+            streamed: HashMap::default(),
+        }
+    }
+
+    /// Sets the scene of the chunk at `key`.
+    #[must_use]
+    pub fn with_chunk(mut self, key: IVec3, source: ChunkSource) -> Self {
+        self.chunks.insert(key, source);
+        self
+    }
+
+    /// Returns the streaming state of the chunk at `key`.
+    pub fn chunk_state(&self, key: IVec3) -> ChunkState {
+        match self.streamed.get(&key) {
+            None => ChunkState::Unloaded,
+            Some(StreamedChunk { entity: None, .. }) => ChunkState::Loading,
+            Some(StreamedChunk {
+                entity: Some(entity),
+                ..
+            }) => ChunkState::Spawned(*entity),
+        }
+    }
+
+    /// Returns the distance from `position`, in the local space of the grid, to the box of the
+    /// chunk at `key`.
+    fn distance(&self, key: IVec3, position: Vec3) -> f32 {
+        let min = key.as_vec3() * self.chunk_size;
+        let max = min + self.chunk_size;
+        (position.clamp(min, max) - position).length()
+    }
+}
+
+/// The scene of a chunk of a [`ChunkedScene`].
+#[derive(Clone, Debug)]
+pub enum ChunkSource {
+    /// The path of a [`Scene`].
+    Scene(AssetPath<'static>),
+    /// The path of a [`DynamicScene`].
+    DynamicScene(AssetPath<'static>),
+}
+
+/// The streaming state of a chunk of a [`ChunkedScene`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkState {
+    /// The chunk isn't loaded.
+    Unloaded,
+    /// The scene of the chunk is loading, or waiting to be spawned.
+    Loading,
+    /// The scene of the chunk is spawned as a child of this entity.
+    Spawned(Entity),
+}
+
+/// A loaded chunk of a [`ChunkedScene`], which keeps its scene asset alive.
+struct StreamedChunk {
+    handle: ChunkHandle,
+    entity: Option<Entity>,
+    /// Whether the scene failed to load, in which case it's not spawned.
+    failed: bool,
+}
+
+enum ChunkHandle {
+    Scene(Handle<Scene>),
+    DynamicScene(Handle<DynamicScene>),
+}
+
+/// Marks an entity, usually a camera, around which the chunks of [`ChunkedScene`]s are streamed.
+#[derive(Component, Default)]
+pub struct StreamingObserver;
+
+/// Limits the work done by [`stream_chunked_scenes`] in a single frame, to avoid hitches.
+#[derive(Resource, Clone, Debug)]
+pub struct SceneStreamingSettings {
+    /// The maximum number of chunks spawned in a frame.
+    pub max_spawns_per_frame: usize,
+    /// The maximum number of chunks despawned in a frame.
+    pub max_despawns_per_frame: usize,
+}
+
+impl Default for SceneStreamingSettings {
+    fn default() -> Self {
+        Self {
+            max_spawns_per_frame: 1,
+            max_despawns_per_frame: 4,
+        }
+    }
+}
+
+/// System that loads, spawns and despawns the chunks of [`ChunkedScene`]s around the
+/// [`StreamingObserver`]s.
+///
+/// The nearest loaded chunks are spawned first.
+pub fn stream_chunked_scenes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<SceneStreamingSettings>,
+    observers: Query<&GlobalTransform, With<StreamingObserver>>,
+    mut grids: Query<(Entity, &mut ChunkedScene, Option<&GlobalTransform>)>,
+) {
+    let mut spawns = settings.max_spawns_per_frame;
+    let mut despawns = settings.max_despawns_per_frame;
+    for (grid_entity, mut grid, grid_transform) in &mut grids {
+        let grid = &mut *grid;
+        let to_local = grid_transform
+            .map(|transform| transform.affine().inverse())
+            .unwrap_or_default();
+        let positions: Vec<Vec3> = observers
+            .iter()
+            .map(|observer| to_local.transform_point3(observer.translation()))
+            .collect();
+        let distance = |grid: &ChunkedScene, key| {
+            positions
+                .iter()
+                .map(|position| grid.distance(key, *position))
+                .fold(f32::INFINITY, f32::min)
+        };
+
+        // Release the chunks which are too far
+        let mut far: Vec<(IVec3, f32)> = grid
+            .streamed
+            .keys()
+            .map(|key| (*key, distance(grid, *key)))
+            .filter(|(_, distance)| *distance > grid.unload_distance)
+            .collect();
+        far.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        for (key, _) in far {
+            if let Some(entity) = grid.streamed[&key].entity {
+                if despawns == 0 {
+                    continue;
+                }
+                despawns -= 1;
+                commands.entity(entity).despawn_recursive();
+            }
+            grid.streamed.remove(&key);
+        }
+
+        // Start loading the chunks which are close enough
+        for position in &positions {
+            let min = ((*position - grid.load_distance) / grid.chunk_size)
+                .floor()
+                .as_ivec3();
+            let max = ((*position + grid.load_distance) / grid.chunk_size)
+                .floor()
+                .as_ivec3();
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        let key = IVec3::new(x, y, z);
+                        if grid.streamed.contains_key(&key)
+                            || grid.distance(key, *position) > grid.load_distance
+                        {
+                            continue;
+                        }
+                        let Some(source) = grid.chunks.get(&key) else {
+                            continue;
+                        };
+                        let handle = match source {
+                            ChunkSource::Scene(path) => {
+                                ChunkHandle::Scene(asset_server.load(path.clone()))
+                            }
+                            ChunkSource::DynamicScene(path) => {
+                                ChunkHandle::DynamicScene(asset_server.load(path.clone()))
+                            }
+                        };
+                        grid.streamed.insert(
+                            key,
+                            StreamedChunk {
+                                handle,
+                                entity: None,
+                                failed: false,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        // Spawn the nearest loaded chunks
+        let mut loaded: Vec<(IVec3, f32)> = Vec::new();
+        for (key, chunk) in &mut grid.streamed {
+            if chunk.entity.is_some() || chunk.failed {
+                continue;
+            }
+            let id = match &chunk.handle {
+                ChunkHandle::Scene(handle) => handle.id().untyped(),
+                ChunkHandle::DynamicScene(handle) => handle.id().untyped(),
+            };
+            if asset_server.load_state(id) == LoadState::Failed
+                || asset_server.recursive_dependency_load_state(id)
+                    == RecursiveDependencyLoadState::Failed
+            {
+                warn!("Failed to load the scene of chunk {key} of a chunked scene");
+                chunk.failed = true;
+            } else if asset_server.is_loaded_with_dependencies(id) {
+                loaded.push((*key, 0.0));
+            }
+        }
+        for (key, distance_to_chunk) in &mut loaded {
+            *distance_to_chunk = distance(grid, *key);
+        }
+        loaded.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        for (key, _) in loaded {
+            if spawns == 0 {
+                break;
+            }
+            spawns -= 1;
+            let transform = Transform::from_translation(key.as_vec3() * grid.chunk_size);
+            let chunk = grid.streamed.get_mut(&key).unwrap();
+            let mut entity = match &chunk.handle {
+                ChunkHandle::Scene(handle) => commands.spawn(SceneBundle {
+                    scene: handle.clone(),
+                    transform,
+                    ..Default::default()
+                }),
+                ChunkHandle::DynamicScene(handle) => commands.spawn(DynamicSceneBundle {
+                    scene: handle.clone(),
+                    transform,
+                    ..Default::default()
+                }),
+            };
+            entity.set_parent(grid_entity);
+            chunk.entity = Some(entity.id());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy_app::App;
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        AssetApp, AssetPlugin,
+    };
+    use bevy_core::TaskPoolPlugin;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{IVec3, Vec3};
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{ChunkSource, ChunkState, ChunkedScene, SceneStreamingSettings, StreamingObserver};
+    use crate::ScenePlugin;
+
+    const LARGE_ITERATION_COUNT: usize = 10000;
+
+    /// Creates an app with a grid of chunks of size 10 along the x axis, loaded within 5 and
+    /// unloaded beyond 15, and an observer at `observer`.
+    fn test_app(chunks: &[(i32, &'static str)], observer: Vec3) -> (App, Entity, Entity) {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("chunk.scn.ron"), "(resources: {}, entities: {})");
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            ScenePlugin,
+        ));
+
+        let mut grid = ChunkedScene::new(Vec3::splat(10.0), 5.0, 15.0);
+        for (x, path) in chunks {
+            grid = grid.with_chunk(
+                IVec3::new(*x, 0, 0),
+                ChunkSource::DynamicScene((*path).into()),
+            );
+        }
+        let grid = app.world.spawn(grid).id();
+        let observer = app
+            .world
+            .spawn((
+                StreamingObserver,
+                GlobalTransform::from_translation(observer),
+            ))
+            .id();
+        (app, grid, observer)
+    }
+
+    fn state(app: &App, grid: Entity, x: i32) -> ChunkState {
+        app.world
+            .get::<ChunkedScene>(grid)
+            .unwrap()
+            .chunk_state(IVec3::new(x, 0, 0))
+    }
+
+    fn spawned_count(app: &App, grid: Entity, chunks: i32) -> usize {
+        (0..chunks)
+            .filter(|x| matches!(state(app, grid, *x), ChunkState::Spawned(_)))
+            .count()
+    }
+
+    fn move_observer(app: &mut App, observer: Entity, position: Vec3) {
+        *app.world.get_mut::<GlobalTransform>(observer).unwrap() =
+            GlobalTransform::from_translation(position);
+    }
+
+    fn run_app_until(app: &mut App, mut predicate: impl FnMut(&App) -> bool) {
+        for _ in 0..LARGE_ITERATION_COUNT {
+            app.update();
+            if predicate(app) {
+                return;
+            }
+        }
+        panic!("Ran out of loops to return `true` from `predicate`");
+    }
+
+    #[test]
+    fn load_and_unload_hysteresis() {
+        let (mut app, grid, observer) = test_app(
+            &[
+                (0, "chunk.scn.ron"),
+                (1, "chunk.scn.ron"),
+                (2, "chunk.scn.ron"),
+            ],
+            Vec3::new(5.0, 5.0, 5.0),
+        );
+
+        // Chunk 1 is 5 away, chunk 2 is 15 away.
+        app.update();
+        assert_eq!(state(&app, grid, 0), ChunkState::Loading);
+        assert_eq!(state(&app, grid, 1), ChunkState::Loading);
+        assert_eq!(state(&app, grid, 2), ChunkState::Unloaded);
+        run_app_until(&mut app, |app| spawned_count(app, grid, 3) == 2);
+        let ChunkState::Spawned(chunk_1) = state(&app, grid, 1) else {
+            panic!("chunk 1 should be spawned");
+        };
+        assert!(app.world.get_entity(chunk_1).is_some());
+
+        // Chunk 1 is now 14 away: too far to be loaded, but not to be unloaded.
+        move_observer(&mut app, observer, Vec3::new(-4.0, 5.0, 5.0));
+        app.update();
+        assert_eq!(state(&app, grid, 1), ChunkState::Spawned(chunk_1));
+
+        // Chunk 1 is now 16 away.
+        move_observer(&mut app, observer, Vec3::new(-6.0, 5.0, 5.0));
+        app.update();
+        assert_eq!(state(&app, grid, 1), ChunkState::Unloaded);
+        assert!(app.world.get_entity(chunk_1).is_none());
+        assert!(matches!(state(&app, grid, 0), ChunkState::Spawned(_)));
+
+        // Chunk 1 is 5 away again, and is spawned again, right away since chunk 0 keeps its
+        // scene loaded.
+        move_observer(&mut app, observer, Vec3::new(5.0, 5.0, 5.0));
+        app.update();
+        let ChunkState::Spawned(chunk_1_again) = state(&app, grid, 1) else {
+            panic!("chunk 1 should be spawned again");
+        };
+        assert_ne!(chunk_1_again, chunk_1);
+    }
+
+    #[test]
+    fn spawn_and_despawn_limits() {
+        let chunks: Vec<_> = (0..5).map(|x| (x, "chunk.scn.ron")).collect();
+        let (mut app, grid, observer) = test_app(&chunks, Vec3::new(25.0, 5.0, 5.0));
+        app.insert_resource(SceneStreamingSettings {
+            max_spawns_per_frame: 1,
+            max_despawns_per_frame: 2,
+        });
+        let mut grid_state = app.world.get_mut::<ChunkedScene>(grid).unwrap();
+        grid_state.load_distance = 25.0;
+        grid_state.unload_distance = 30.0;
+
+        // All the chunks are within 25, and are spawned one per frame, the nearest first.
+        let mut spawned = 0;
+        let mut order = Vec::new();
+        run_app_until(&mut app, |app| {
+            let count = spawned_count(app, grid, 5);
+            assert!(count <= spawned + 1);
+            if count > spawned {
+                order.push(
+                    (0..5)
+                        .find(|x| {
+                            matches!(state(app, grid, *x), ChunkState::Spawned(_))
+                                && !order.contains(x)
+                        })
+                        .unwrap(),
+                );
+            }
+            spawned = count;
+            count == 5
+        });
+        assert_eq!(order[0], 2);
+
+        // All the chunks are farther than 30, and are despawned two per frame.
+        move_observer(&mut app, observer, Vec3::new(200.0, 5.0, 5.0));
+        app.update();
+        assert_eq!(spawned_count(&app, grid, 5), 3);
+        app.update();
+        assert_eq!(spawned_count(&app, grid, 5), 1);
+        app.update();
+        assert_eq!(spawned_count(&app, grid, 5), 0);
+        assert!(app
+            .world
+            .get::<ChunkedScene>(grid)
+            .unwrap()
+            .streamed
+            .is_empty());
+    }
+
+    #[test]
+    fn failed_load_is_not_retried() {
+        let (mut app, grid, _) = test_app(&[(0, "missing.scn.ron")], Vec3::new(5.0, 5.0, 5.0));
+
+        app.update();
+        let handle_id = |app: &App| match &app.world.get::<ChunkedScene>(grid).unwrap().streamed
+            [&IVec3::ZERO]
+            .handle
+        {
+            super::ChunkHandle::DynamicScene(handle) => handle.id(),
+            super::ChunkHandle::Scene(_) => unreachable!(),
+        };
+        let first_handle = handle_id(&app);
+        run_app_until(&mut app, |app| {
+            app.world.get::<ChunkedScene>(grid).unwrap().streamed[&IVec3::ZERO].failed
+        });
+
+        // The chunk keeps its failed load, instead of loading it again every frame.
+        for _ in 0..10 {
+            app.update();
+            assert_eq!(state(&app, grid, 0), ChunkState::Loading);
+            assert!(app.world.get::<ChunkedScene>(grid).unwrap().streamed[&IVec3::ZERO].failed);
+            assert_eq!(handle_id(&app), first_handle);
+        }
+    }
+}
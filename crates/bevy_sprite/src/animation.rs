@@ -0,0 +1,427 @@
+use std::{ops::Range, time::Duration};
+
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+
+use crate::TextureAtlasSprite;
+
+/// A frame of a [`SpriteAnimationClip`].
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct SpriteFrame {
+    /// The index of the frame in the [`TextureAtlas`](crate::TextureAtlas).
+    pub index: usize,
+    /// How long the frame is shown.
+    pub duration: Duration,
+    /// The names of the [`SpriteAnimationEvent`]s sent when the frame is shown.
+    pub events: Vec<String>,
+}
+
+/// A flipbook animation, showing frames of a [`TextureAtlas`](crate::TextureAtlas) one after
+/// the other.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_sprite::SpriteAnimationClip;
+/// // The run cycle is on frames 8 to 13 of the atlas, with a footstep on the third frame
+/// let run = SpriteAnimationClip::from_range(8..14, Duration::from_millis(100))
+///     .with_event(2, "footstep");
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Default)]
+pub struct SpriteAnimationClip {
+    frames: Vec<SpriteFrame>,
+}
+
+impl SpriteAnimationClip {
+    /// Creates a clip without frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a clip showing the frames of `range` of the atlas, each for `frame_duration`.
+    pub fn from_range(range: Range<usize>, frame_duration: Duration) -> Self {
+        Self::new().with_range(range, frame_duration)
+    }
+
+    /// Adds a frame showing `index` of the atlas for `duration`.
+    pub fn with_frame(mut self, index: usize, duration: Duration) -> Self {
+        self.frames.push(SpriteFrame {
+            index,
+            duration,
+            events: Vec::new(),
+        });
+        self
+    }
+
+    /// Adds frames showing the frames of `range` of the atlas, each for `frame_duration`.
+    pub fn with_range(mut self, range: Range<usize>, frame_duration: Duration) -> Self {
+        for index in range {
+            self = self.with_frame(index, frame_duration);
+        }
+        self
+    }
+
+    /// Sends a [`SpriteAnimationEvent`] called `name` when the `frame`th frame of the clip is
+    /// shown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clip has no `frame`th frame.
+    pub fn with_event(mut self, frame: usize, name: impl Into<String>) -> Self {
+        self.frames[frame].events.push(name.into());
+        self
+    }
+
+    /// The frames of the clip.
+    pub fn frames(&self) -> &[SpriteFrame] {
+        &self.frames
+    }
+
+    /// The frames of the clip, to change their durations or events.
+    pub fn frames_mut(&mut self) -> &mut [SpriteFrame] {
+        &mut self.frames
+    }
+
+    /// The duration of the clip, showing each frame once.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+}
+
+/// Plays a [`SpriteAnimationClip`] on the [`TextureAtlasSprite`] of this entity.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_sprite::{AnimationState, SpriteAnimationClip, TextureAtlasSprite};
+/// fn spawn_player(mut commands: Commands, mut clips: ResMut<Assets<SpriteAnimationClip>>) {
+///     let idle = clips.add(SpriteAnimationClip::from_range(0..4, Duration::from_millis(150)));
+///     commands.spawn((TextureAtlasSprite::new(0), AnimationState::new(idle)));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct AnimationState {
+    clip: Handle<SpriteAnimationClip>,
+    /// The index of the current frame in the clip.
+    frame: usize,
+    /// How long the current frame has been shown.
+    elapsed: Duration,
+    looping: bool,
+    paused: bool,
+    speed: f32,
+    finished: bool,
+    /// Whether the current frame was shown, to send its events once.
+    shown: bool,
+}
+
+impl Default for AnimationState {
+    fn default() -> Self {
+        Self {
+            clip: Handle::default(),
+            frame: 0,
+            elapsed: Duration::ZERO,
+            looping: true,
+            paused: false,
+            speed: 1.0,
+            finished: false,
+            shown: false,
+        }
+    }
+}
+
+impl AnimationState {
+    /// Plays `clip` in a loop.
+    pub fn new(clip: Handle<SpriteAnimationClip>) -> Self {
+        Self {
+            clip,
+            ..Default::default()
+        }
+    }
+
+    /// Plays the clip once, stopping on its last frame.
+    pub fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+
+    /// Plays `clip` from its first frame, unless it's already playing.
+    pub fn play(&mut self, clip: Handle<SpriteAnimationClip>) -> &mut Self {
+        if self.clip != clip {
+            self.clip = clip;
+            self.replay();
+        }
+        self
+    }
+
+    /// Plays the clip again from its first frame.
+    pub fn replay(&mut self) -> &mut Self {
+        self.frame = 0;
+        self.elapsed = Duration::ZERO;
+        self.finished = false;
+        self.shown = false;
+        self
+    }
+
+    /// Handle to the clip being played.
+    pub fn clip(&self) -> &Handle<SpriteAnimationClip> {
+        &self.clip
+    }
+
+    /// The index of the current frame in the clip.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Shows the `frame`th frame of the clip, and sends its events.
+    pub fn set_frame(&mut self, frame: usize) -> &mut Self {
+        self.frame = frame;
+        self.elapsed = Duration::ZERO;
+        self.finished = false;
+        self.shown = false;
+        self
+    }
+
+    /// Whether the clip loops.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets whether the clip loops, or stops on its last frame.
+    pub fn set_looping(&mut self, looping: bool) -> &mut Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Whether the clip was played until the end of its last frame, when it doesn't loop.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Pause the animation.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the animation.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the animation paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Speed of the animation playback
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set the speed of the animation playback, which must not be negative.
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed.max(0.0);
+        self
+    }
+
+    /// Advances the animation by `delta`, calling `on_event` for the events of the frames shown,
+    /// and returns the index in the atlas of the current frame.
+    fn update(
+        &mut self,
+        clip: &SpriteAnimationClip,
+        delta: Duration,
+        mut on_event: impl FnMut(usize, &str),
+    ) -> Option<usize> {
+        let frames = &clip.frames;
+        if frames.is_empty() {
+            return None;
+        }
+        self.frame = self.frame.min(frames.len() - 1);
+        let mut show = |state: &mut Self| {
+            if !state.shown {
+                state.shown = true;
+                for event in &frames[state.frame].events {
+                    on_event(state.frame, event);
+                }
+            }
+        };
+        show(self);
+
+        if !self.paused && !self.finished {
+            self.elapsed += delta.mul_f32(self.speed);
+            let duration = clip.duration();
+            if self.looping && !duration.is_zero() && self.elapsed > duration {
+                // Skip the whole loops of this update
+                self.elapsed =
+                    Duration::from_nanos((self.elapsed.as_nanos() % duration.as_nanos()) as u64);
+            }
+            while self.elapsed >= frames[self.frame].duration {
+                if self.frame == frames.len() - 1 && !self.looping {
+                    self.finished = true;
+                    self.elapsed = frames[self.frame].duration;
+                    break;
+                }
+                if duration.is_zero() {
+                    break;
+                }
+                self.elapsed -= frames[self.frame].duration;
+                self.frame = (self.frame + 1) % frames.len();
+                self.shown = false;
+                show(self);
+            }
+        }
+        Some(frames[self.frame].index)
+    }
+}
+
+/// Sent when an [`AnimationState`] shows a frame of its clip with events.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct SpriteAnimationEvent {
+    /// The entity with the [`AnimationState`].
+    pub entity: Entity,
+    /// The clip playing.
+    pub clip: Handle<SpriteAnimationClip>,
+    /// The index of the frame in the clip.
+    pub frame: usize,
+    /// The name of the event.
+    pub name: String,
+}
+
+/// System that advances the [`AnimationState`]s, and shows their current frame on their
+/// [`TextureAtlasSprite`].
+pub fn animate_sprites(
+    time: Res<Time>,
+    clips: Res<Assets<SpriteAnimationClip>>,
+    mut events: EventWriter<SpriteAnimationEvent>,
+    mut sprites: Query<(Entity, &mut AnimationState, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut state, mut sprite) in &mut sprites {
+        let Some(clip) = clips.get(&state.clip) else {
+            continue;
+        };
+        let handle = state.clip.clone();
+        let index = state.update(clip, time.delta(), |frame, name| {
+            events.send(SpriteAnimationEvent {
+                entity,
+                clip: handle.clone(),
+                frame,
+                name: name.to_string(),
+            });
+        });
+        if let Some(index) = index {
+            if sprite.index != index {
+                sprite.index = index;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A run cycle on frames 8 to 11 of the atlas, with a footstep on its third frame.
+    fn run() -> SpriteAnimationClip {
+        SpriteAnimationClip::from_range(8..12, Duration::from_millis(100)).with_event(2, "footstep")
+    }
+
+    /// Advances `state` by `millis`, and returns the index in the atlas and the events sent.
+    fn update(
+        state: &mut AnimationState,
+        clip: &SpriteAnimationClip,
+        millis: u64,
+    ) -> (Option<usize>, Vec<(usize, String)>) {
+        let mut events = Vec::new();
+        let index = state.update(clip, Duration::from_millis(millis), |frame, name| {
+            events.push((frame, name.to_string()));
+        });
+        (index, events)
+    }
+
+    #[test]
+    fn frame_at_time() {
+        let clip = run();
+        assert_eq!(clip.duration(), Duration::from_millis(400));
+        for millis in [0, 50, 100, 199, 250, 399, 400, 1050, 12_345] {
+            let mut state = AnimationState::default();
+            let (index, _) = update(&mut state, &clip, millis);
+            let frame = (millis / 100 % 4) as usize;
+            assert_eq!(index, Some(8 + frame), "at {millis}ms");
+            assert_eq!(state.frame(), frame);
+        }
+
+        // Frames can have different durations
+        let clip = SpriteAnimationClip::new()
+            .with_frame(3, Duration::from_millis(100))
+            .with_frame(5, Duration::from_millis(300));
+        let mut state = AnimationState::default();
+        assert_eq!(update(&mut state, &clip, 350).0, Some(5));
+        assert_eq!(update(&mut state, &clip, 100).0, Some(3));
+
+        assert_eq!(
+            update(
+                &mut AnimationState::default(),
+                &SpriteAnimationClip::new(),
+                100
+            )
+            .0,
+            None
+        );
+    }
+
+    #[test]
+    fn once_stops_on_the_last_frame() {
+        let clip = run();
+        let mut state = AnimationState::default().once();
+        assert_eq!(update(&mut state, &clip, 350).0, Some(11));
+        assert!(!state.is_finished());
+        assert_eq!(update(&mut state, &clip, 1000).0, Some(11));
+        assert!(state.is_finished());
+
+        state.replay();
+        assert_eq!(update(&mut state, &clip, 0).0, Some(8));
+        assert!(!state.is_finished());
+    }
+
+    #[test]
+    fn speed_and_pause() {
+        let clip = run();
+        let mut state = AnimationState::default();
+        state.set_speed(2.0);
+        assert_eq!(update(&mut state, &clip, 100).0, Some(10));
+
+        state.pause();
+        assert_eq!(update(&mut state, &clip, 100).0, Some(10));
+        state.resume();
+        state.set_speed(-1.0);
+        assert_eq!(state.speed(), 0.0);
+        assert_eq!(update(&mut state, &clip, 100).0, Some(10));
+    }
+
+    #[test]
+    fn events_are_sent_once_per_frame_shown() {
+        let clip = run().with_event(0, "start");
+        let mut state = AnimationState::default();
+        assert_eq!(update(&mut state, &clip, 0).1, [(0, "start".to_string())]);
+        assert!(update(&mut state, &clip, 50).1.is_empty());
+        assert_eq!(
+            update(&mut state, &clip, 200).1,
+            [(2, "footstep".to_string())]
+        );
+        assert!(update(&mut state, &clip, 0).1.is_empty());
+
+        // Every frame crossed in an update sends its events
+        assert_eq!(
+            update(&mut state, &clip, 350).1,
+            [(0, "start".to_string()), (2, "footstep".to_string())]
+        );
+
+        state.set_frame(2);
+        assert_eq!(
+            update(&mut state, &clip, 0).1,
+            [(2, "footstep".to_string())]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 //! Provides 2D sprite rendering functionality.
+mod animation;
 mod bundle;
 mod dynamic_texture_atlas_builder;
 mod mesh2d;
@@ -12,6 +13,7 @@ pub mod collide_aabb;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        animation::{AnimationState, SpriteAnimationClip, SpriteAnimationEvent},
         bundle::{SpriteBundle, SpriteSheetBundle},
         sprite::Sprite,
         texture_atlas::{TextureAtlas, TextureAtlasSprite},
@@ -19,6 +21,7 @@ pub mod prelude {
     };
 }
 
+pub use animation::*;
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
 pub use mesh2d::*;
@@ -63,14 +66,21 @@ impl Plugin for SpritePlugin {
         );
         app.init_asset::<TextureAtlas>()
             .register_asset_reflect::<TextureAtlas>()
+            .init_asset::<SpriteAnimationClip>()
+            .register_asset_reflect::<SpriteAnimationClip>()
             .register_type::<Sprite>()
             .register_type::<TextureAtlasSprite>()
             .register_type::<Anchor>()
             .register_type::<Mesh2dHandle>()
+            .register_type::<AnimationState>()
+            .add_event::<SpriteAnimationEvent>()
             .add_plugins((Mesh2dRenderPlugin, ColorMaterialPlugin))
             .add_systems(
                 PostUpdate,
-                calculate_bounds_2d.in_set(VisibilitySystems::CalculateBounds),
+                (
+                    animate_sprites.before(VisibilitySystems::CalculateBounds),
+                    calculate_bounds_2d.in_set(VisibilitySystems::CalculateBounds),
+                ),
             );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
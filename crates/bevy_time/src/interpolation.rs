@@ -0,0 +1,207 @@
+use std::marker::PhantomData;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+
+use crate::{Fixed, Time};
+
+/// A value which can be blended with another, to interpolate between two states of a
+/// component.
+pub trait Interpolate {
+    /// Returns the value a fraction `t` of the way from `self` to `other`, where `t` is between
+    /// `0.0` and `1.0`.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t as f64
+    }
+}
+
+/// A [`Component`] interpolating the component `T` of its entity between the states it had at
+/// the end of the last two [`FixedUpdate`] steps.
+///
+/// The systems of the [`InterpolationPlugin::<T>`] store the state of `T` after each fixed
+/// step, and set `T` to the blend of the last two states by the
+/// [overstep fraction](Time::overstep_fraction) of [`Time<Fixed>`] in [`Update`]. This smooths
+/// the movement of entities simulated at a fixed rate, which otherwise looks juddery when the
+/// frame rate doesn't match the fixed rate.
+///
+/// The component `T` is restored to its latest state before each fixed step, so the systems of
+/// [`FixedUpdate`] only ever see the simulated states, while changes made to `T` outside of
+/// the fixed steps are overwritten.
+#[derive(Component, Clone, Debug)]
+pub struct Interpolated<T: Component + Interpolate + Clone> {
+    previous: Option<T>,
+    current: Option<T>,
+}
+
+impl<T: Component + Interpolate + Clone> Default for Interpolated<T> {
+    fn default() -> Self {
+        Self {
+            previous: None,
+            current: None,
+        }
+    }
+}
+
+impl<T: Component + Interpolate + Clone> Interpolated<T> {
+    /// Creates an interpolation starting at the `value`, which should be the initial value of
+    /// the component `T` of the entity.
+    ///
+    /// An interpolation created with [`Default`] instead starts at the first state stored
+    /// after a fixed step.
+    pub fn new(value: T) -> Self {
+        Self {
+            previous: Some(value.clone()),
+            current: Some(value),
+        }
+    }
+
+    /// Returns the state of the component at the end of the second to last fixed step.
+    pub fn previous(&self) -> Option<&T> {
+        self.previous.as_ref()
+    }
+
+    /// Returns the state of the component at the end of the last fixed step.
+    pub fn current(&self) -> Option<&T> {
+        self.current.as_ref()
+    }
+
+    /// Returns the state a fraction `t` of the way between the two last states, or `None`
+    /// before a state was stored.
+    pub fn interpolate(&self, t: f32) -> Option<T> {
+        match (&self.previous, &self.current) {
+            (Some(previous), Some(current)) => Some(previous.interpolate(current, t)),
+            _ => self.current.clone(),
+        }
+    }
+}
+
+/// Label for the system interpolating the components `T` of the [`Interpolated<T>`] entities,
+/// which runs in [`Update`].
+///
+/// Systems reading the interpolated components should run after this set.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct InterpolationSystem;
+
+/// Adds the systems interpolating the component `T` of the entities with an
+/// [`Interpolated<T>`] component.
+pub struct InterpolationPlugin<T: Component + Interpolate + Clone>(PhantomData<fn() -> T>);
+
+impl<T: Component + Interpolate + Clone> Default for InterpolationPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Component + Interpolate + Clone> Plugin for InterpolationPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, restore_interpolated::<T>)
+            .add_systems(FixedLast, store_interpolated::<T>)
+            .add_systems(
+                Update,
+                interpolate::<T>
+                    .in_set(InterpolationSystem)
+                    .run_if(resource_exists::<Time<Fixed>>()),
+            );
+    }
+}
+
+/// Restores the components `T` to their latest simulated state, before a fixed step.
+pub fn restore_interpolated<T: Component + Interpolate + Clone>(
+    mut query: Query<(&mut T, &Interpolated<T>)>,
+) {
+    for (mut value, interpolated) in &mut query {
+        if let Some(current) = &interpolated.current {
+            *value = current.clone();
+        }
+    }
+}
+
+/// Stores the state of the components `T`, after a fixed step.
+pub fn store_interpolated<T: Component + Interpolate + Clone>(
+    mut query: Query<(&T, &mut Interpolated<T>)>,
+) {
+    for (value, mut interpolated) in &mut query {
+        let previous = interpolated.current.replace(value.clone());
+        interpolated.previous = previous.or_else(|| Some(value.clone()));
+    }
+}
+
+/// Sets the components `T` to the blend of their last two states by the overstep fraction of
+/// [`Time<Fixed>`].
+pub fn interpolate<T: Component + Interpolate + Clone>(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut T, &Interpolated<T>)>,
+) {
+    let t = fixed_time.overstep_fraction().clamp(0.0, 1.0);
+    for (mut value, interpolated) in &mut query {
+        if let Some(interpolated) = interpolated.interpolate(t) {
+            *value = interpolated;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::schedule::Schedule;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(f32);
+
+    impl Interpolate for Position {
+        fn interpolate(&self, other: &Self, t: f32) -> Self {
+            Position(self.0.interpolate(&other.0, t))
+        }
+    }
+
+    fn step(mut query: Query<&mut Position>) {
+        for mut position in &mut query {
+            position.0 += 1.0;
+        }
+    }
+
+    #[test]
+    fn interpolate_fixed_steps() {
+        let mut world = World::new();
+        world.insert_resource(Time::<Fixed>::from_seconds(1.0));
+        let entity = world
+            .spawn((Position(0.0), Interpolated::new(Position(0.0))))
+            .id();
+        let mut fixed_step = Schedule::default();
+        fixed_step.add_systems(
+            (
+                restore_interpolated::<Position>,
+                step,
+                store_interpolated::<Position>,
+            )
+                .chain(),
+        );
+        let mut update = Schedule::default();
+        update.add_systems(interpolate::<Position>);
+
+        fixed_step.run(&mut world);
+        fixed_step.run(&mut world);
+        let interpolated = world.get::<Interpolated<Position>>(entity).unwrap();
+        assert_eq!(interpolated.previous(), Some(&Position(1.0)));
+        assert_eq!(interpolated.current(), Some(&Position(2.0)));
+        assert_eq!(interpolated.interpolate(0.25), Some(Position(1.25)));
+
+        // Without overstep, the entity is at the previous state.
+        update.run(&mut world);
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1.0)));
+
+        // The next step starts from the latest state, not the interpolated one.
+        fixed_step.run(&mut world);
+        assert_eq!(world.get::<Position>(entity), Some(&Position(3.0)));
+    }
+}
@@ -4,6 +4,7 @@
 /// Common run conditions
 pub mod common_conditions;
 mod fixed;
+mod interpolation;
 mod real;
 mod stopwatch;
 #[allow(clippy::module_inception)]
@@ -12,6 +13,7 @@ mod timer;
 mod virt;
 
 pub use fixed::*;
+pub use interpolation::*;
 pub use real::*;
 pub use stopwatch::*;
 pub use time::*;
@@ -21,7 +23,7 @@ pub use virt::*;
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{Fixed, Real, Time, Timer, TimerMode, Virtual};
+    pub use crate::{Fixed, Interpolated, Real, Time, Timer, TimerMode, Virtual};
 }
 
 use bevy_app::{prelude::*, RunFixedMainLoop};
@@ -3,6 +3,7 @@ use bevy_ecs::{component::Component, reflect::ReflectComponent};
 use bevy_math::{Affine3A, Mat3, Mat4, Quat, Vec3};
 use bevy_reflect::prelude::*;
 use bevy_reflect::Reflect;
+use bevy_time::Interpolate;
 use std::ops::Mul;
 
 /// Describe the position of an entity. If the entity has a parent, the position is relative
@@ -414,6 +415,17 @@ impl Default for Transform {
 
 /// The transform is expected to be non-degenerate and without shearing, or the output
 /// will be invalid.
+impl Interpolate for Transform {
+    /// Interpolates the translation and scale linearly, and the rotation spherically.
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Transform {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
 impl From<GlobalTransform> for Transform {
     fn from(transform: GlobalTransform) -> Self {
         transform.compute_transform()
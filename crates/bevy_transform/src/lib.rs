@@ -21,6 +21,7 @@ use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::ValidParentCheckPlugin;
 use bevy_math::{Affine3A, Mat4, Vec3};
+use bevy_time::InterpolationPlugin;
 
 use prelude::{GlobalTransform, Transform};
 use systems::{propagate_transforms, sync_simple_transforms};
@@ -88,6 +89,9 @@ pub enum TransformSystem {
 }
 
 /// The base plugin for handling [`Transform`] components
+///
+/// It also interpolates the [`Transform`] of the entities with an
+/// [`Interpolated<Transform>`](bevy_time::Interpolated) component between the fixed steps.
 #[derive(Default)]
 pub struct TransformPlugin;
 
@@ -100,7 +104,10 @@ impl Plugin for TransformPlugin {
 
         app.register_type::<Transform>()
             .register_type::<GlobalTransform>()
-            .add_plugins(ValidParentCheckPlugin::<GlobalTransform>::default())
+            .add_plugins((
+                ValidParentCheckPlugin::<GlobalTransform>::default(),
+                InterpolationPlugin::<Transform>::default(),
+            ))
             .configure_sets(
                 PostStartup,
                 PropagateTransformsSet.in_set(TransformSystem::TransformPropagate),
@@ -4,7 +4,7 @@ use crate::{
 };
 use bevy_a11y::{
     accesskit::{NodeBuilder, Rect, Role},
-    AccessibilityNode,
+    AccessibilityNode, AccessibleWidget,
 };
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::{
@@ -59,7 +59,10 @@ fn calc_bounds(
 
 fn button_changed(
     mut commands: Commands,
-    mut query: Query<(Entity, &Children, Option<&mut AccessibilityNode>), Changed<Button>>,
+    mut query: Query<
+        (Entity, &Children, Option<&mut AccessibilityNode>),
+        (Changed<Button>, Without<AccessibleWidget>),
+    >,
     texts: Query<&Text>,
 ) {
     for (entity, children, accessible) in &mut query {
@@ -87,7 +90,7 @@ fn image_changed(
     mut commands: Commands,
     mut query: Query<
         (Entity, &Children, Option<&mut AccessibilityNode>),
-        (Changed<UiImage>, Without<Button>),
+        (Changed<UiImage>, Without<Button>, Without<AccessibleWidget>),
     >,
     texts: Query<&Text>,
 ) {
@@ -114,7 +117,10 @@ fn image_changed(
 
 fn label_changed(
     mut commands: Commands,
-    mut query: Query<(Entity, &Text, Option<&mut AccessibilityNode>), Changed<Label>>,
+    mut query: Query<
+        (Entity, &Text, Option<&mut AccessibilityNode>),
+        (Changed<Label>, Without<AccessibleWidget>),
+    >,
 ) {
     for (entity, text, accessible) in &mut query {
         let values = text
@@ -143,6 +149,9 @@ fn label_changed(
 }
 
 /// `AccessKit` integration for `bevy_ui`.
+///
+/// Buttons, images and labels are described to assistive technologies automatically, unless they
+/// have an [`AccessibleWidget`] describing them instead.
 pub(crate) struct AccessibilityPlugin;
 
 impl Plugin for AccessibilityPlugin {
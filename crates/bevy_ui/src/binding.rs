@@ -0,0 +1,471 @@
+//! Binding the properties of UI nodes to data stored in resources or components.
+//!
+//! A [`UiBinding`] describes how to compute some of a node's properties from a value.
+//! It is attached to the node with a [`BindResource`] or a [`BindComponent`], and the node's
+//! properties are recomputed whenever the bound resource or component changes. This replaces
+//! the systems that would otherwise have to be written for every widget, such as one updating a
+//! score label each time the score changes.
+//!
+//! Bindings are applied after [stylesheets](crate::StyleSheet), and reapplied whenever the
+//! node's [`Style`] or [`BackgroundColor`] is changed by something else, such as a restyle, so
+//! bound properties take precedence over the rules of stylesheets.
+//!
+//! Bindings to a type only update once the matching [`ResourceBindingPlugin`] or
+//! [`ComponentBindingPlugin`] has been added to the app.
+use crate::{BackgroundColor, Style, UiSystem};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    entity::Entity,
+    prelude::{Component, Ref},
+    query::QueryData,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res, Resource},
+};
+use bevy_render::{color::Color, view::Visibility};
+#[cfg(feature = "bevy_text")]
+use bevy_text::Text;
+use std::marker::PhantomData;
+
+type BindingFn<T, U> = Box<dyn Fn(&T) -> U + Send + Sync>;
+
+/// Describes how to compute the properties of a UI node from a value of type `T`.
+///
+/// Only the properties with a binding are written to. They are only written when their computed
+/// value differs from the current one, so unchanged properties don't trigger change detection.
+pub struct UiBinding<T> {
+    style: Vec<Box<dyn Fn(&T, &mut Style) + Send + Sync>>,
+    visibility: Option<BindingFn<T, Visibility>>,
+    background_color: Option<BindingFn<T, Color>>,
+    #[cfg(feature = "bevy_text")]
+    text_sections: Vec<(usize, BindingFn<T, String>)>,
+}
+
+impl<T> Default for UiBinding<T> {
+    fn default() -> Self {
+        Self {
+            style: Vec::new(),
+            visibility: None,
+            background_color: None,
+            #[cfg(feature = "bevy_text")]
+            text_sections: Vec::new(),
+        }
+    }
+}
+
+impl<T> UiBinding<T> {
+    /// Creates a binding that doesn't set any property.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the node's [`Style`] with `update`.
+    ///
+    /// Can be called several times, in which case the updates are applied in order.
+    #[must_use]
+    pub fn style(mut self, update: impl Fn(&T, &mut Style) + Send + Sync + 'static) -> Self {
+        self.style.push(Box::new(update));
+        self
+    }
+
+    /// Sets the node's [`Visibility`] to the value returned by `visibility`.
+    #[must_use]
+    pub fn visibility(
+        mut self,
+        visibility: impl Fn(&T) -> Visibility + Send + Sync + 'static,
+    ) -> Self {
+        self.visibility = Some(Box::new(visibility));
+        self
+    }
+
+    /// Sets the node's [`BackgroundColor`] to the value returned by `color`.
+    #[must_use]
+    pub fn background_color(mut self, color: impl Fn(&T) -> Color + Send + Sync + 'static) -> Self {
+        self.background_color = Some(Box::new(color));
+        self
+    }
+
+    /// Sets the value of the first section of the node's [`Text`] to the string returned by `text`.
+    #[cfg(feature = "bevy_text")]
+    #[must_use]
+    pub fn text(self, text: impl Fn(&T) -> String + Send + Sync + 'static) -> Self {
+        self.text_section(0, text)
+    }
+
+    /// Sets the value of the section at `index` of the node's [`Text`] to the string returned by `text`.
+    #[cfg(feature = "bevy_text")]
+    #[must_use]
+    pub fn text_section(
+        mut self,
+        index: usize,
+        text: impl Fn(&T) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.text_sections.push((index, Box::new(text)));
+        self
+    }
+
+    fn apply(&self, value: &T, node: BoundNodeQueryItem<'_>) {
+        if let Some(mut style) = node.style.filter(|_| !self.style.is_empty()) {
+            let mut new_style = style.clone();
+            for update in &self.style {
+                update(value, &mut new_style);
+            }
+            style.set_if_neq(new_style);
+        }
+        if let (Some(mut visibility), Some(compute)) = (node.visibility, &self.visibility) {
+            visibility.set_if_neq(compute(value));
+        }
+        if let (Some(mut background_color), Some(compute)) =
+            (node.background_color, &self.background_color)
+        {
+            background_color.set_if_neq(BackgroundColor(compute(value)));
+        }
+    }
+
+    #[cfg(feature = "bevy_text")]
+    fn apply_text(&self, value: &T, mut text: bevy_ecs::change_detection::Mut<Text>) {
+        for (index, compute) in &self.text_sections {
+            let new_value = compute(value);
+            if text
+                .sections
+                .get(*index)
+                .is_some_and(|section| section.value != new_value)
+            {
+                text.sections[*index].value = new_value;
+            }
+        }
+    }
+}
+
+/// Binds the properties of a UI node to the resource `R`.
+///
+/// The node is updated when it is spawned, and every time the resource changes.
+/// Requires the [`ResourceBindingPlugin`] for `R`.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::{prelude::*, BindResource, ResourceBindingPlugin, UiBinding};
+/// #[derive(Resource)]
+/// struct Health(f32);
+///
+/// fn spawn_health_bar(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle::default(),
+///         BindResource::new(
+///             UiBinding::new().style(|health: &Health, style| style.width = Val::Percent(health.0)),
+///         ),
+///     ));
+/// }
+///
+/// App::new()
+///     .insert_resource(Health(100.))
+///     .add_plugins(ResourceBindingPlugin::<Health>::default())
+///     .add_systems(Startup, spawn_health_bar);
+/// ```
+#[derive(Component)]
+pub struct BindResource<R: Resource>(pub UiBinding<R>);
+
+impl<R: Resource> BindResource<R> {
+    /// Binds a node to the resource `R` with `binding`.
+    pub fn new(binding: UiBinding<R>) -> Self {
+        Self(binding)
+    }
+}
+
+/// Binds the properties of a UI node to the component `C` of the `source` entity.
+///
+/// The node is updated when it is spawned, and every time the source's component changes.
+/// Requires the [`ComponentBindingPlugin`] for `C`.
+#[derive(Component)]
+pub struct BindComponent<C: Component> {
+    /// The entity the bound component is read from.
+    pub source: Entity,
+    /// How the properties of the node are computed from the component.
+    pub binding: UiBinding<C>,
+}
+
+impl<C: Component> BindComponent<C> {
+    /// Binds a node to the component `C` of `source` with `binding`.
+    pub fn new(source: Entity, binding: UiBinding<C>) -> Self {
+        Self { source, binding }
+    }
+}
+
+/// The node properties that can be written by a [`UiBinding`].
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct BoundNodeQuery {
+    style: Option<&'static mut Style>,
+    visibility: Option<&'static mut Visibility>,
+    background_color: Option<&'static mut BackgroundColor>,
+}
+
+impl BoundNodeQueryItem<'_> {
+    /// Returns `true` if the node's [`Style`] or [`BackgroundColor`] changed since the binding
+    /// system last ran, for example because a stylesheet restyled the node.
+    fn is_overwritten(&self) -> bool {
+        self.style.as_ref().is_some_and(|style| style.is_changed())
+            || self
+                .background_color
+                .as_ref()
+                .is_some_and(|color| color.is_changed())
+    }
+}
+
+/// Updates the nodes with a [`BindResource<R>`] when `R` or the binding changes, or when the
+/// bound properties of the node were overwritten.
+pub fn update_resource_bindings<R: Resource>(
+    resource: Option<Res<R>>,
+    bindings: Query<(Entity, Ref<BindResource<R>>)>,
+    mut nodes: Query<BoundNodeQuery>,
+    #[cfg(feature = "bevy_text")] mut texts: Query<&mut Text>,
+) {
+    let Some(resource) = resource else {
+        return;
+    };
+    for (entity, binding) in &bindings {
+        let changed = resource.is_changed() || binding.is_changed();
+        if let Ok(node) = nodes.get_mut(entity) {
+            if changed || node.is_overwritten() {
+                binding.0.apply(&resource, node);
+            }
+        }
+        #[cfg(feature = "bevy_text")]
+        if changed {
+            if let Ok(text) = texts.get_mut(entity) {
+                binding.0.apply_text(&resource, text);
+            }
+        }
+    }
+}
+
+/// Updates the nodes with a [`BindComponent<C>`] when the source's `C` or the binding changes,
+/// or when the bound properties of the node were overwritten.
+pub fn update_component_bindings<C: Component>(
+    sources: Query<Ref<C>>,
+    bindings: Query<(Entity, Ref<BindComponent<C>>)>,
+    mut nodes: Query<BoundNodeQuery>,
+    #[cfg(feature = "bevy_text")] mut texts: Query<&mut Text>,
+) {
+    for (entity, binding) in &bindings {
+        let Ok(source) = sources.get(binding.source) else {
+            continue;
+        };
+        let changed = source.is_changed() || binding.is_changed();
+        if let Ok(node) = nodes.get_mut(entity) {
+            if changed || node.is_overwritten() {
+                binding.binding.apply(&source, node);
+            }
+        }
+        #[cfg(feature = "bevy_text")]
+        if changed {
+            if let Ok(text) = texts.get_mut(entity) {
+                binding.binding.apply_text(&source, text);
+            }
+        }
+    }
+}
+
+/// Adds the system updating the UI nodes bound to the resource `R` with [`BindResource<R>`].
+pub struct ResourceBindingPlugin<R: Resource>(PhantomData<R>);
+
+impl<R: Resource> Default for ResourceBindingPlugin<R> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<R: Resource> Plugin for ResourceBindingPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_resource_bindings::<R>
+                .in_set(UiSystem::Bindings)
+                .after(UiSystem::Stylesheets)
+                .before(UiSystem::Layout),
+        );
+    }
+}
+
+/// Adds the system updating the UI nodes bound to the component `C` with [`BindComponent<C>`].
+pub struct ComponentBindingPlugin<C: Component>(PhantomData<C>);
+
+impl<C: Component> Default for ComponentBindingPlugin<C> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<C: Component> Plugin for ComponentBindingPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_component_bindings::<C>
+                .in_set(UiSystem::Bindings)
+                .after(UiSystem::Stylesheets)
+                .before(UiSystem::Layout),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_component_bindings, update_resource_bindings, BindComponent, BindResource};
+    use crate::{
+        ui_stylesheet_system, BackgroundColor, Classes, Node, Style, StyleProperties, StyleSheet,
+        UiBinding, UiStyleSheet, Val,
+    };
+    use bevy_asset::{AssetEvent, Assets};
+    use bevy_ecs::{
+        event::Events,
+        prelude::{Component, Resource},
+        schedule::{IntoSystemConfigs, Schedule},
+        world::World,
+    };
+    use bevy_render::{color::Color, view::Visibility};
+
+    #[derive(Resource)]
+    struct Health(f32);
+
+    #[derive(Component)]
+    struct Team(bool);
+
+    fn health_binding() -> BindResource<Health> {
+        BindResource::new(
+            UiBinding::new()
+                .style(|health: &Health, style| style.width = Val::Percent(health.0))
+                .background_color(|health: &Health| {
+                    if health.0 < 25.0 {
+                        Color::RED
+                    } else {
+                        Color::GREEN
+                    }
+                }),
+        )
+    }
+
+    fn test_world() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<Assets<StyleSheet>>();
+        world.init_resource::<Events<AssetEvent<StyleSheet>>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                ui_stylesheet_system,
+                update_resource_bindings::<Health>,
+                update_component_bindings::<Team>,
+            )
+                .chain(),
+        );
+        (world, schedule)
+    }
+
+    #[test]
+    fn resource_binding() {
+        let (mut world, mut schedule) = test_world();
+        let node = world
+            .spawn((
+                Node::default(),
+                Style::default(),
+                BackgroundColor::default(),
+                health_binding(),
+            ))
+            .id();
+
+        // Nothing is written without the resource
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Auto);
+
+        world.insert_resource(Health(100.0));
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Percent(100.0));
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, Color::GREEN);
+
+        world.resource_mut::<Health>().0 = 20.0;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Percent(20.0));
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, Color::RED);
+    }
+
+    #[test]
+    fn component_binding() {
+        let (mut world, mut schedule) = test_world();
+        let player = world.spawn(Team(true)).id();
+        let node = world
+            .spawn((
+                Node::default(),
+                Visibility::Hidden,
+                BindComponent::new(
+                    player,
+                    UiBinding::new().visibility(|team: &Team| {
+                        if team.0 {
+                            Visibility::Inherited
+                        } else {
+                            Visibility::Hidden
+                        }
+                    }),
+                ),
+            ))
+            .id();
+
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Inherited));
+
+        world.get_mut::<Team>(player).unwrap().0 = false;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Hidden));
+
+        // Nodes bound to a despawned source keep their properties
+        world.despawn(player);
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Hidden));
+    }
+
+    #[test]
+    fn bindings_survive_restyles() {
+        let (mut world, mut schedule) = test_world();
+        let stylesheet = StyleSheet::default()
+            .with_rule(
+                ".bar".parse().unwrap(),
+                StyleProperties {
+                    width: Some(Val::Px(50.0)),
+                    height: Some(Val::Px(10.0)),
+                    ..Default::default()
+                },
+            )
+            .with_rule(
+                ".big".parse().unwrap(),
+                StyleProperties {
+                    height: Some(Val::Px(30.0)),
+                    ..Default::default()
+                },
+            );
+        let stylesheet = world.resource_mut::<Assets<StyleSheet>>().add(stylesheet);
+        world.insert_resource(Health(80.0));
+        let node = world
+            .spawn((
+                Node::default(),
+                Style::default(),
+                BackgroundColor::default(),
+                Classes::new(["bar"]),
+                UiStyleSheet(stylesheet),
+                health_binding(),
+            ))
+            .id();
+
+        // The bound width takes precedence over the width of the rule
+        schedule.run(&mut world);
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Percent(80.0));
+        assert_eq!(style.height, Val::Px(10.0));
+
+        // Restyling the node keeps the bound properties, without the resource changing
+        world.get_mut::<Classes>(node).unwrap().add("big");
+        schedule.run(&mut world);
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Percent(80.0));
+        assert_eq!(style.height, Val::Px(30.0));
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, Color::GREEN);
+    }
+}
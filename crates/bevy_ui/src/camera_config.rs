@@ -28,3 +28,17 @@ impl Default for UiCameraConfig {
         Self { show_ui: true }
     }
 }
+
+/// Overrides the [`UiScale`](crate::UiScale) of the UI rendered to a camera.
+///
+/// Combined with [`TargetCamera`](crate::TargetCamera), this allows the UI of each window or
+/// split-screen viewport to have its own scale.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct CameraUiScale(pub f32);
+
+impl Default for CameraUiScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
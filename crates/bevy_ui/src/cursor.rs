@@ -0,0 +1,68 @@
+//! Changing the cursor icon while the pointer is over UI nodes.
+//!
+//! Add [`HoverCursor`] to a node with an [`Interaction`] component to show a different cursor
+//! while it is hovered or pressed. The icon is picked by [`hover_cursor_system`] alone, so nodes
+//! that overlap don't fight over the cursor from frame to frame.
+use crate::{Interaction, UiStack};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    reflect::ReflectComponent,
+    system::{Local, Query, Res},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::HashMap;
+use bevy_window::{CursorIcon, Window};
+
+/// The cursor icon shown while the pointer hovers or presses this node.
+///
+/// The node also needs an [`Interaction`] component (e.g. from a
+/// [`ButtonBundle`](crate::node_bundles::ButtonBundle)). When several hovered nodes have a
+/// `HoverCursor`, the one on top wins. The window's [`Cursor::icon`](bevy_window::Cursor::icon)
+/// is restored once no such node is hovered anymore.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct HoverCursor(pub CursorIcon);
+
+/// Sets the cursor icon of the window under the pointer to the [`HoverCursor`] of the topmost
+/// hovered or pressed node.
+///
+/// If another system changes the icon while it is overridden, that icon is restored instead of
+/// the original one once the node isn't hovered anymore.
+pub fn hover_cursor_system(
+    // The icon each overridden window had before, and the icon it was overridden with
+    mut overrides: Local<HashMap<Entity, (CursorIcon, CursorIcon)>>,
+    ui_stack: Res<UiStack>,
+    nodes: Query<(&HoverCursor, &Interaction)>,
+    mut windows: Query<(Entity, &mut Window)>,
+) {
+    let hovered_icon = ui_stack.uinodes.iter().rev().find_map(|entity| {
+        let (cursor, interaction) = nodes.get(*entity).ok()?;
+        (*interaction != Interaction::None).then_some(cursor.0)
+    });
+
+    for (entity, mut window) in &mut windows {
+        let icon = hovered_icon.filter(|_| window.cursor_position().is_some());
+        let current = window.cursor.icon;
+        match (icon, overrides.get(&entity).copied()) {
+            (Some(icon), previous) => {
+                let base = match previous {
+                    Some((base, applied)) if applied == current => base,
+                    _ => current,
+                };
+                overrides.insert(entity, (base, icon));
+                if current != icon {
+                    window.cursor.icon = icon;
+                }
+            }
+            (None, Some((base, applied))) => {
+                overrides.remove(&entity);
+                if current == applied && current != base {
+                    window.cursor.icon = base;
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    overrides.retain(|entity, _| windows.contains(*entity));
+}
@@ -0,0 +1,569 @@
+//! Drag-and-drop support for UI nodes.
+//!
+//! Add [`Draggable`] to a node with an [`Interaction`] component to allow it to be picked up,
+//! and [`DropTarget`] (also alongside [`Interaction`]) to nodes that should accept drops.
+//! [`ui_drag_system`] then emits [`DragStart`], [`DragOver`], [`DragDrop`] and [`DragEnd`] events
+//! as the pointer moves, and renders a translucent "ghost" copy of the dragged node under the cursor.
+use crate::{
+    node_bundles::NodeBundle, BackgroundColor, FocusPolicy, Interaction, Node, PositionType, Style,
+    UiImage, UiScale, Val, ZIndex,
+};
+use bevy_ecs::{
+    entity::Entity,
+    event::{Event, EventWriter},
+    prelude::{Component, With},
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{PrimaryWindow, Window};
+use std::{any::Any, sync::Arc};
+
+/// Marks a UI node as something that can be dragged with the pointer.
+///
+/// The node also needs an [`Interaction`] component (e.g. from a [`ButtonBundle`](crate::node_bundles::ButtonBundle)),
+/// as a drag can only begin from a node that is [`Interaction::Pressed`].
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Draggable {
+    /// The distance in logical pixels the pointer has to travel while pressed before a drag starts.
+    ///
+    /// This prevents simple clicks on the node from being interpreted as drags.
+    pub threshold: f32,
+    /// Whether a ghost copy of the node should follow the pointer while it is dragged.
+    ///
+    /// The ghost only copies the node's [`BackgroundColor`] and [`UiImage`], not its text or
+    /// children. Hide the ghost and move a node of your own in response to the drag events
+    /// when the dragged node needs to be shown as a whole.
+    pub show_ghost: bool,
+    /// The opacity multiplier applied to the ghost's background color.
+    pub ghost_alpha: f32,
+}
+
+impl Draggable {
+    pub const DEFAULT: Self = Self {
+        threshold: 4.,
+        show_ghost: true,
+        ghost_alpha: 0.6,
+    };
+}
+
+impl Default for Draggable {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Marks a UI node as a place [`Draggable`] nodes can be dropped on.
+///
+/// The node also needs an [`Interaction`] component, which is used to detect whether
+/// the pointer is over it.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct DropTarget;
+
+/// Arbitrary data carried along with a drag operation.
+///
+/// Insert it next to a [`Draggable`] and it will be cloned into every drag event
+/// emitted for that node, so drop targets can inspect what they received without
+/// having to query the dragged entity.
+///
+/// ```
+/// # use bevy_ui::DragPayload;
+/// struct ItemId(u32);
+///
+/// let payload = DragPayload::new(ItemId(7));
+/// assert_eq!(payload.downcast_ref::<ItemId>().map(|id| id.0), Some(7));
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct DragPayload(pub Arc<dyn Any + Send + Sync>);
+
+impl DragPayload {
+    /// Creates a payload holding `value`.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns a reference to the payload's value if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+
+    /// Returns `true` if the payload's value is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+}
+
+/// Sent when the pointer has moved a [`Draggable`] node past its threshold.
+#[derive(Event, Clone, Debug)]
+pub struct DragStart {
+    /// The node being dragged.
+    pub dragged: Entity,
+    /// The payload of the dragged node, if any.
+    pub payload: Option<DragPayload>,
+}
+
+/// Sent when the drop target under a dragged node changes.
+#[derive(Event, Clone, Debug)]
+pub struct DragOver {
+    /// The node being dragged.
+    pub dragged: Entity,
+    /// The [`DropTarget`] now under the pointer, or `None` if the pointer left all drop targets.
+    pub target: Option<Entity>,
+    /// The payload of the dragged node, if any.
+    pub payload: Option<DragPayload>,
+}
+
+/// Sent when a dragged node is released over a [`DropTarget`].
+#[derive(Event, Clone, Debug)]
+pub struct DragDrop {
+    /// The node that was dragged.
+    pub dragged: Entity,
+    /// The [`DropTarget`] it was dropped on.
+    pub target: Entity,
+    /// The payload of the dragged node, if any.
+    pub payload: Option<DragPayload>,
+}
+
+/// Sent when a drag operation finishes, whether or not it ended on a [`DropTarget`].
+#[derive(Event, Clone, Debug)]
+pub struct DragEnd {
+    /// The node that was dragged.
+    pub dragged: Entity,
+    /// The [`DropTarget`] the node was dropped on, or `None` if the drop was cancelled.
+    pub target: Option<Entity>,
+    /// The payload of the dragged node, if any.
+    pub payload: Option<DragPayload>,
+}
+
+/// Marks the ghost node spawned by [`ui_drag_system`] while a drag is active.
+#[derive(Component, Copy, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct DragGhost;
+
+/// The state of the current drag-and-drop operation.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct DragState {
+    /// The pressed [`Draggable`] node and the pointer position it was pressed at.
+    pressed: Option<(Entity, Vec2)>,
+    /// Information about the drag in progress, if any.
+    active: Option<ActiveDrag>,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveDrag {
+    dragged: Entity,
+    /// Offset from the pointer to the top-left corner of the dragged node.
+    grab_offset: Vec2,
+    target: Option<Entity>,
+    ghost: Option<Entity>,
+}
+
+impl DragState {
+    /// Returns the node currently being dragged, if any.
+    pub fn dragged(&self) -> Option<Entity> {
+        self.active.as_ref().map(|drag| drag.dragged)
+    }
+
+    /// Returns the [`DropTarget`] currently under the dragged node, if any.
+    pub fn target(&self) -> Option<Entity> {
+        self.active.as_ref().and_then(|drag| drag.target)
+    }
+
+    /// Returns `true` if a drag is in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.active.is_some()
+    }
+}
+
+/// The system that drives drag-and-drop for [`Draggable`] nodes.
+///
+/// Runs after [`ui_focus_system`](crate::ui_focus_system) and relies on the [`Interaction`]
+/// it computed to detect presses and hovered drop targets.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_drag_system(
+    mut commands: Commands,
+    mut state: ResMut<DragState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches_input: Res<Touches>,
+    ui_scale: Res<UiScale>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    draggables: Query<(
+        Entity,
+        &Draggable,
+        &Interaction,
+        &Node,
+        &GlobalTransform,
+        Option<&DragPayload>,
+        Option<&BackgroundColor>,
+        Option<&UiImage>,
+    )>,
+    targets: Query<(Entity, &Interaction), With<DropTarget>>,
+    mut ghosts: Query<&mut Style, With<DragGhost>>,
+    mut drag_start_events: EventWriter<DragStart>,
+    mut drag_over_events: EventWriter<DragOver>,
+    mut drag_drop_events: EventWriter<DragDrop>,
+    mut drag_end_events: EventWriter<DragEnd>,
+) {
+    let cursor_position = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .or_else(|| touches_input.first_pressed_position())
+        .map(|cursor_position| cursor_position / ui_scale.0);
+
+    let released =
+        mouse_button_input.just_released(MouseButton::Left) || touches_input.any_just_released();
+    let held =
+        mouse_button_input.pressed(MouseButton::Left) || touches_input.iter().next().is_some();
+
+    let payload_of = |entity: Entity| {
+        draggables
+            .get(entity)
+            .ok()
+            .and_then(|(.., payload, _, _)| payload.cloned())
+    };
+
+    if let Some(drag) = state.active.as_mut() {
+        let payload = payload_of(drag.dragged);
+        let target = targets
+            .iter()
+            .find(|(entity, interaction)| {
+                *entity != drag.dragged && **interaction == Interaction::Hovered
+            })
+            .map(|(entity, _)| entity);
+
+        if target != drag.target {
+            drag.target = target;
+            drag_over_events.send(DragOver {
+                dragged: drag.dragged,
+                target,
+                payload: payload.clone(),
+            });
+        }
+
+        if let (Some(ghost), Some(cursor_position)) = (drag.ghost, cursor_position) {
+            if let Ok(mut style) = ghosts.get_mut(ghost) {
+                let position = cursor_position - drag.grab_offset;
+                style.left = Val::Px(position.x);
+                style.top = Val::Px(position.y);
+            }
+        }
+
+        if released || !held {
+            if let Some(target) = drag.target {
+                drag_drop_events.send(DragDrop {
+                    dragged: drag.dragged,
+                    target,
+                    payload: payload.clone(),
+                });
+            }
+            drag_end_events.send(DragEnd {
+                dragged: drag.dragged,
+                target: drag.target,
+                payload,
+            });
+            if let Some(ghost) = drag.ghost {
+                if let Some(ghost) = commands.get_entity(ghost) {
+                    ghost.despawn_recursive();
+                }
+            }
+            state.active = None;
+            state.pressed = None;
+        }
+        return;
+    }
+
+    if released || !held {
+        state.pressed = None;
+        return;
+    }
+
+    let Some(cursor_position) = cursor_position else {
+        return;
+    };
+
+    let Some((pressed, press_position)) = state.pressed else {
+        // Remember which draggable node was pressed, and where.
+        state.pressed = draggables
+            .iter()
+            .find(|(_, _, interaction, ..)| **interaction == Interaction::Pressed)
+            .map(|(entity, ..)| (entity, cursor_position));
+        return;
+    };
+
+    let Ok((entity, draggable, _, node, transform, payload, background_color, image)) =
+        draggables.get(pressed)
+    else {
+        state.pressed = None;
+        return;
+    };
+
+    if cursor_position.distance(press_position) < draggable.threshold {
+        return;
+    }
+
+    let node_rect = node.logical_rect(transform);
+    let grab_offset = press_position - node_rect.min;
+
+    let ghost = draggable.show_ghost.then(|| {
+        let position = cursor_position - grab_offset;
+        let mut background = background_color.copied().unwrap_or_default();
+        background.0.set_a(background.0.a() * draggable.ghost_alpha);
+        let mut ghost = commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(position.x),
+                    top: Val::Px(position.y),
+                    width: Val::Px(node_rect.width()),
+                    height: Val::Px(node_rect.height()),
+                    ..Default::default()
+                },
+                background_color: background,
+                focus_policy: FocusPolicy::Pass,
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+            DragGhost,
+        ));
+        if let Some(image) = image {
+            ghost.insert(image.clone());
+        }
+        ghost.id()
+    });
+
+    drag_start_events.send(DragStart {
+        dragged: entity,
+        payload: payload.cloned(),
+    });
+
+    state.active = Some(ActiveDrag {
+        dragged: entity,
+        grab_offset,
+        target: None,
+        ghost,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    struct DragTest {
+        world: World,
+        schedule: Schedule,
+        dragged: Entity,
+        target: Entity,
+    }
+
+    impl DragTest {
+        fn new() -> Self {
+            let mut world = World::new();
+            world.init_resource::<DragState>();
+            world.init_resource::<ButtonInput<MouseButton>>();
+            world.init_resource::<Touches>();
+            world.insert_resource(UiScale(1.));
+            world.init_resource::<Events<DragStart>>();
+            world.init_resource::<Events<DragOver>>();
+            world.init_resource::<Events<DragDrop>>();
+            world.init_resource::<Events<DragEnd>>();
+            world.spawn((Window::default(), PrimaryWindow));
+            let dragged = world
+                .spawn((
+                    Draggable::default(),
+                    Interaction::None,
+                    Node::DEFAULT,
+                    GlobalTransform::default(),
+                    DragPayload::new(7_u32),
+                ))
+                .id();
+            let target = world.spawn((DropTarget, Interaction::None)).id();
+            let mut schedule = Schedule::default();
+            schedule.add_systems(ui_drag_system);
+            Self {
+                world,
+                schedule,
+                dragged,
+                target,
+            }
+        }
+
+        fn set_interaction(&mut self, entity: Entity, interaction: Interaction) {
+            *self.world.get_mut::<Interaction>(entity).unwrap() = interaction;
+        }
+
+        /// Runs the drag system for a frame with the pointer at `position`.
+        fn pointer(&mut self, position: Vec2, pressed: bool) {
+            let mut windows = self.world.query::<&mut Window>();
+            windows
+                .single_mut(&mut self.world)
+                .set_cursor_position(Some(position));
+            let mut input = self.world.resource_mut::<ButtonInput<MouseButton>>();
+            input.clear();
+            if pressed {
+                input.press(MouseButton::Left);
+            } else {
+                input.release(MouseButton::Left);
+            }
+            self.schedule.run(&mut self.world);
+        }
+
+        /// Presses the dragged node and moves the pointer past the threshold.
+        fn start_drag(&mut self) {
+            self.set_interaction(self.dragged, Interaction::Pressed);
+            self.pointer(Vec2::ZERO, true);
+            self.pointer(Vec2::new(10., 0.), true);
+        }
+
+        fn events<E: Event + Clone>(&mut self) -> Vec<E> {
+            self.world.resource_mut::<Events<E>>().drain().collect()
+        }
+
+        fn ghosts(&mut self) -> usize {
+            self.world
+                .query_filtered::<(), With<DragGhost>>()
+                .iter(&self.world)
+                .count()
+        }
+    }
+
+    fn payload_value(payload: &Option<DragPayload>) -> Option<u32> {
+        payload.as_ref()?.downcast_ref::<u32>().copied()
+    }
+
+    #[test]
+    fn click_does_not_start_drag() {
+        let mut test = DragTest::new();
+        test.set_interaction(test.dragged, Interaction::Pressed);
+        test.pointer(Vec2::ZERO, true);
+        test.pointer(Vec2::new(2., 1.), true);
+        test.pointer(Vec2::new(2., 1.), false);
+
+        assert!(!test.world.resource::<DragState>().is_dragging());
+        assert!(test.events::<DragStart>().is_empty());
+        assert!(test.events::<DragEnd>().is_empty());
+        assert_eq!(test.ghosts(), 0);
+    }
+
+    #[test]
+    fn drag_and_drop_on_target() {
+        let mut test = DragTest::new();
+        test.start_drag();
+        let starts = test.events::<DragStart>();
+        assert_eq!(starts.len(), 1);
+        assert_eq!(starts[0].dragged, test.dragged);
+        assert_eq!(
+            test.world.resource::<DragState>().dragged(),
+            Some(test.dragged)
+        );
+        assert_eq!(test.ghosts(), 1);
+
+        test.set_interaction(test.target, Interaction::Hovered);
+        test.pointer(Vec2::new(20., 0.), true);
+        let overs = test.events::<DragOver>();
+        assert_eq!(overs.len(), 1);
+        assert_eq!(overs[0].target, Some(test.target));
+
+        // The target hasn't changed, so there is no new `DragOver`
+        test.pointer(Vec2::new(25., 0.), true);
+        assert!(test.events::<DragOver>().is_empty());
+
+        test.pointer(Vec2::new(25., 0.), false);
+        let drops = test.events::<DragDrop>();
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].dragged, test.dragged);
+        assert_eq!(drops[0].target, test.target);
+        let ends = test.events::<DragEnd>();
+        assert_eq!(ends.len(), 1);
+        assert_eq!(ends[0].target, Some(test.target));
+        assert!(!test.world.resource::<DragState>().is_dragging());
+    }
+
+    #[test]
+    fn drop_outside_targets_is_cancelled() {
+        let mut test = DragTest::new();
+        test.start_drag();
+
+        // Leave a target before releasing
+        test.set_interaction(test.target, Interaction::Hovered);
+        test.pointer(Vec2::new(20., 0.), true);
+        test.set_interaction(test.target, Interaction::None);
+        test.pointer(Vec2::new(40., 0.), true);
+        let overs = test.events::<DragOver>();
+        assert_eq!(overs.len(), 2);
+        assert_eq!(overs[1].target, None);
+
+        test.pointer(Vec2::new(40., 0.), false);
+        assert!(test.events::<DragDrop>().is_empty());
+        let ends = test.events::<DragEnd>();
+        assert_eq!(ends.len(), 1);
+        assert_eq!(ends[0].target, None);
+    }
+
+    #[test]
+    fn dragged_node_is_not_its_own_target() {
+        let mut test = DragTest::new();
+        let dragged = test.dragged;
+        test.world.entity_mut(dragged).insert(DropTarget);
+        test.start_drag();
+
+        test.set_interaction(dragged, Interaction::Hovered);
+        test.pointer(Vec2::new(20., 0.), true);
+        assert!(test.events::<DragOver>().is_empty());
+        assert_eq!(test.world.resource::<DragState>().target(), None);
+
+        test.pointer(Vec2::new(20., 0.), false);
+        assert!(test.events::<DragDrop>().is_empty());
+        assert_eq!(test.events::<DragEnd>()[0].target, None);
+    }
+
+    #[test]
+    fn ghost_is_despawned_when_drag_ends() {
+        let mut test = DragTest::new();
+        test.start_drag();
+        assert_eq!(test.ghosts(), 1);
+
+        test.pointer(Vec2::new(20., 0.), false);
+        assert_eq!(test.ghosts(), 0);
+
+        // Without `show_ghost`, no ghost is spawned at all
+        let mut test = DragTest::new();
+        test.world
+            .get_mut::<Draggable>(test.dragged)
+            .unwrap()
+            .show_ghost = false;
+        test.start_drag();
+        assert!(test.world.resource::<DragState>().is_dragging());
+        assert_eq!(test.ghosts(), 0);
+    }
+
+    #[test]
+    fn payload_is_passed_to_every_event() {
+        let mut test = DragTest::new();
+        test.start_drag();
+        test.set_interaction(test.target, Interaction::Hovered);
+        test.pointer(Vec2::new(20., 0.), true);
+        test.pointer(Vec2::new(20., 0.), false);
+
+        assert_eq!(
+            payload_value(&test.events::<DragStart>()[0].payload),
+            Some(7)
+        );
+        assert_eq!(
+            payload_value(&test.events::<DragOver>()[0].payload),
+            Some(7)
+        );
+        assert_eq!(
+            payload_value(&test.events::<DragDrop>()[0].payload),
+            Some(7)
+        );
+        assert_eq!(payload_value(&test.events::<DragEnd>()[0].payload), Some(7));
+    }
+}
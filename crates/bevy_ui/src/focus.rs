@@ -1,4 +1,7 @@
-use crate::{camera_config::UiCameraConfig, CalculatedClip, Node, UiScale, UiStack};
+use crate::{
+    camera_config::{CameraUiScale, UiCameraConfig},
+    CalculatedClip, Node, UiScale, UiStack, WorldSpaceUi,
+};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::Entity,
@@ -12,6 +15,7 @@ use bevy_math::{Rect, Vec2};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_render::{camera::NormalizedRenderTarget, prelude::Camera, view::ViewVisibility};
 use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
 
 use bevy_window::{PrimaryWindow, Window};
 use serde::{Deserialize, Serialize};
@@ -121,10 +125,21 @@ pub struct NodeQuery {
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
 ///
 /// Entities with a hidden [`ViewVisibility`] are always treated as released.
+///
+/// Nodes rendered to a [`TargetCamera`](crate::TargetCamera) use the cursor position over that camera's
+/// viewport, or the position where the cursor hits a [`WorldSpaceUi`] surface displaying that camera's output.
+/// This means the UI of each split-screen viewport only reacts to the cursor while it is over that viewport.
 #[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
-    camera: Query<(&Camera, Option<&UiCameraConfig>)>,
+    camera: Query<(
+        Entity,
+        &Camera,
+        &GlobalTransform,
+        Option<&UiCameraConfig>,
+        Option<&CameraUiScale>,
+    )>,
+    world_space_surfaces: Query<(&WorldSpaceUi, &GlobalTransform)>,
     windows: Query<&Window>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     touches_input: Res<Touches>,
@@ -160,24 +175,65 @@ pub fn ui_focus_system(
     let is_ui_disabled =
         |camera_ui| matches!(camera_ui, Some(&UiCameraConfig { show_ui: false, .. }));
 
-    let cursor_position = camera
-        .iter()
-        .filter(|(_, camera_ui)| !is_ui_disabled(*camera_ui))
-        .filter_map(|(camera, _)| {
-            if let Some(NormalizedRenderTarget::Window(window_ref)) =
-                camera.target.normalize(primary_window)
-            {
-                Some(window_ref)
-            } else {
-                None
-            }
-        })
-        .find_map(|window_ref| {
+    let window_cursor_position = |camera: &Camera| {
+        if let Some(NormalizedRenderTarget::Window(window_ref)) =
+            camera.target.normalize(primary_window)
+        {
             windows
                 .get(window_ref.entity())
                 .ok()
                 .and_then(|window| window.cursor_position())
-        })
+        } else {
+            None
+        }
+    };
+
+    // The cursor position relative to the top left corner of a camera's viewport,
+    // or `None` if the cursor isn't over the viewport.
+    let viewport_cursor_position = |camera: &Camera| {
+        let cursor_position = window_cursor_position(camera)?;
+        match camera.logical_viewport_rect() {
+            Some(viewport) => viewport
+                .contains(cursor_position)
+                .then_some(cursor_position - viewport.min),
+            None => Some(cursor_position),
+        }
+    };
+    let camera_ui_scale = |camera_ui_scale: Option<&CameraUiScale>| {
+        camera_ui_scale.map_or(ui_scale.0, |camera_ui_scale| camera_ui_scale.0)
+    };
+
+    // The cursor position over each camera that UI nodes may target, in logical UI viewport coordinates.
+    let mut camera_cursor_positions = HashMap::<Entity, Vec2>::default();
+    for (camera_entity, camera, _, _, scale) in &camera {
+        if let Some(cursor_position) = viewport_cursor_position(camera) {
+            camera_cursor_positions.insert(camera_entity, cursor_position / camera_ui_scale(scale));
+        }
+    }
+    for (surface, surface_transform) in &world_space_surfaces {
+        let Ok((_, ui_camera, _, _, scale)) = camera.get(surface.ui_camera) else {
+            continue;
+        };
+        let Some(ui_viewport_size) = ui_camera.logical_viewport_size() else {
+            continue;
+        };
+        let hit = camera
+            .iter()
+            .filter(|(camera_entity, ..)| *camera_entity != surface.ui_camera)
+            .filter_map(|(_, camera, camera_transform, ..)| {
+                let cursor_position = viewport_cursor_position(camera)?;
+                camera.viewport_to_world(camera_transform, cursor_position)
+            })
+            .find_map(|ray| surface.ray_to_viewport(surface_transform, ray, ui_viewport_size));
+        if let Some(hit) = hit {
+            camera_cursor_positions.insert(surface.ui_camera, hit / camera_ui_scale(scale));
+        }
+    }
+
+    let cursor_position = camera
+        .iter()
+        .filter(|(.., camera_ui, _)| !is_ui_disabled(*camera_ui))
+        .find_map(|(_, camera, ..)| window_cursor_position(camera))
         .or_else(|| touches_input.first_pressed_position())
         // The cursor position returned by `Window` only takes into account the window scale factor and not `UiScale`.
         // To convert the cursor position to logical UI viewport coordinates we have to divide it by `UiScale`.
@@ -214,6 +270,12 @@ pub fn ui_focus_system(
                     .map(|clip| node_rect.intersect(clip.clip))
                     .unwrap_or(node_rect);
 
+                // Nodes rendered to a specific camera use the cursor position over that camera
+                let cursor_position = match node.node.target_camera() {
+                    Some(camera_entity) => camera_cursor_positions.get(&camera_entity).copied(),
+                    None => cursor_position,
+                };
+
                 // The mouse position relative to the node
                 // (0., 0.) is the top-left corner, (1., 1.) is the bottom-right corner
                 // Coordinates are relative to the entire node, not just the visible region.
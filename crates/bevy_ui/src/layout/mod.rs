@@ -1,19 +1,23 @@
 mod convert;
 pub mod debug;
 
-use crate::{ContentSize, Node, Outline, Style, UiScale};
+use crate::{
+    camera_config::CameraUiScale, ContentSize, LayoutRounding, Node, Outline, Style, TargetCamera,
+    UiScale,
+};
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
     entity::Entity,
     event::EventReader,
     query::{With, Without},
     removal_detection::RemovedComponents,
-    system::{Query, Res, ResMut, Resource},
+    system::{Local, Query, Res, ResMut, Resource},
     world::Ref,
 };
 use bevy_hierarchy::{Children, Parent};
 use bevy_log::warn;
 use bevy_math::Vec2;
+use bevy_render::camera::Camera;
 use bevy_transform::components::Transform;
 use bevy_utils::{default, HashMap};
 use bevy_window::{PrimaryWindow, Window, WindowResolution, WindowScaleFactorChanged};
@@ -21,6 +25,13 @@ use std::fmt;
 use taffy::Taffy;
 use thiserror::Error;
 
+/// The scale factor and viewport size a camera's UI was last laid out with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraLayoutInfo {
+    scale_factor: f32,
+    physical_size: Vec2,
+}
+
 pub struct LayoutContext {
     pub scale_factor: f32,
     pub physical_size: Vec2,
@@ -190,13 +201,21 @@ without UI components as a child of an entity with UI components, results may be
 
     /// Compute the layout for each window entity's corresponding root node in the layout.
     pub fn compute_window_layout(&mut self, window: Entity, window_resolution: &WindowResolution) {
+        let physical_size = Vec2::new(
+            window_resolution.physical_width() as f32,
+            window_resolution.physical_height() as f32,
+        );
+        self.compute_camera_layout(window, physical_size);
+    }
+
+    /// Compute the layout for the root nodes targeting the given camera entity,
+    /// using the physical size of the camera's viewport as the available space.
+    pub fn compute_camera_layout(&mut self, camera: Entity, physical_size: Vec2) {
         let available_space = taffy::geometry::Size {
-            width: taffy::style::AvailableSpace::Definite(window_resolution.physical_width() as f32),
-            height: taffy::style::AvailableSpace::Definite(
-                window_resolution.physical_height() as f32
-            ),
+            width: taffy::style::AvailableSpace::Definite(physical_size.x),
+            height: taffy::style::AvailableSpace::Definite(physical_size.y),
         };
-        for root_nodes in self.window_roots.entry(window).or_default() {
+        for root_nodes in self.window_roots.entry(camera).or_default() {
             self.taffy
                 .compute_layout(root_nodes.implicit_viewport_node, available_space)
                 .unwrap();
@@ -238,15 +257,20 @@ pub enum LayoutError {
 }
 
 /// Updates the UI's layout tree, computes the new layout geometry and then updates the sizes and transforms of all the UI nodes.
+///
+/// Root nodes with a [`TargetCamera`] are laid out against that camera's viewport and scale factor,
+/// all other root nodes are laid out against the primary window.
 #[allow(clippy::too_many_arguments)]
 pub fn ui_layout_system(
     primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
     windows: Query<(Entity, &Window)>,
+    cameras: Query<(&Camera, Option<&CameraUiScale>)>,
     ui_scale: Res<UiScale>,
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut resize_events: EventReader<bevy_window::WindowResized>,
     mut ui_surface: ResMut<UiSurface>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    mut camera_layout_info: Local<HashMap<Entity, CameraLayoutInfo>>,
+    root_node_query: Query<(Entity, Option<&TargetCamera>), (With<Node>, Without<Parent>)>,
     style_query: Query<(Entity, Ref<Style>), With<Node>>,
     mut measure_query: Query<(Entity, &mut ContentSize)>,
     children_query: Query<(Entity, Ref<Children>), With<Node>>,
@@ -254,10 +278,9 @@ pub fn ui_layout_system(
     mut removed_children: RemovedComponents<Children>,
     mut removed_content_sizes: RemovedComponents<ContentSize>,
     mut node_transform_query: Query<(&mut Node, &mut Transform)>,
+    rounding_query: Query<&LayoutRounding>,
     mut removed_nodes: RemovedComponents<Node>,
 ) {
-    // assume one window for time being...
-    // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
     let (primary_window_entity, logical_to_physical_factor, physical_size) =
         if let Ok((entity, primary_window)) = primary_window.get_single() {
             (
@@ -280,16 +303,76 @@ pub fn ui_layout_system(
 
     let layout_context = LayoutContext::new(scale_factor, physical_size);
 
+    // Gather the root nodes rendered to each camera along with the camera's layout context.
+    let mut camera_roots: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    let mut camera_contexts: HashMap<Entity, LayoutContext> = HashMap::default();
+    let mut changed_cameras = Vec::new();
+    for (root, target_camera) in root_node_query.iter() {
+        let Some(&TargetCamera(camera_entity)) = target_camera else {
+            continue;
+        };
+        camera_roots.entry(camera_entity).or_default().push(root);
+        if camera_contexts.contains_key(&camera_entity) {
+            continue;
+        }
+        let Some((camera_scale_factor, camera_ui_scale, camera_physical_size)) = cameras
+            .get(camera_entity)
+            .ok()
+            .and_then(|(camera, camera_ui_scale)| {
+                Some((
+                    camera.target_scaling_factor()?,
+                    camera_ui_scale.map_or(ui_scale.0, |scale| scale.0),
+                    camera.physical_viewport_size()?.as_vec2(),
+                ))
+            })
+        else {
+            continue;
+        };
+        let info = CameraLayoutInfo {
+            scale_factor: camera_scale_factor * camera_ui_scale,
+            physical_size: camera_physical_size,
+        };
+        if camera_layout_info.insert(camera_entity, info) != Some(info) {
+            changed_cameras.push(camera_entity);
+        }
+        camera_contexts.insert(
+            camera_entity,
+            LayoutContext::new(info.scale_factor, info.physical_size),
+        );
+    }
+
+    // Map every node of a camera-targeted tree to its camera.
+    let mut node_cameras: HashMap<Entity, Entity> = HashMap::default();
+    for (&camera_entity, roots) in &camera_roots {
+        let mut stack = roots.clone();
+        while let Some(entity) = stack.pop() {
+            node_cameras.insert(entity, camera_entity);
+            if let Ok(children) = just_children_query.get(entity) {
+                stack.extend(children.iter().copied());
+            }
+        }
+    }
+
+    let context_for = |entity: Entity| {
+        node_cameras
+            .get(&entity)
+            .and_then(|camera_entity| camera_contexts.get(camera_entity))
+            .unwrap_or(&layout_context)
+    };
+
     if !scale_factor_events.is_empty() || ui_scale.is_changed() || resized {
         scale_factor_events.clear();
         // update all nodes
         for (entity, style) in style_query.iter() {
-            ui_surface.upsert_node(entity, &style, &layout_context);
+            ui_surface.upsert_node(entity, &style, context_for(entity));
         }
     } else {
         for (entity, style) in style_query.iter() {
-            if style.is_changed() {
-                ui_surface.upsert_node(entity, &style, &layout_context);
+            let camera_changed = node_cameras
+                .get(&entity)
+                .is_some_and(|camera_entity| changed_cameras.contains(camera_entity));
+            if style.is_changed() || camera_changed {
+                ui_surface.upsert_node(entity, &style, context_for(entity));
             }
         }
     }
@@ -308,8 +391,26 @@ pub fn ui_layout_system(
     // clean up removed nodes
     ui_surface.remove_entities(removed_nodes.read());
 
-    // update window children (for now assuming all Nodes live in the primary window)
-    ui_surface.set_window_children(primary_window_entity, root_node_query.iter());
+    // update window children, nodes without a `TargetCamera` live in the primary window
+    ui_surface.set_window_children(
+        primary_window_entity,
+        root_node_query
+            .iter()
+            .filter(|(_, target_camera)| target_camera.is_none())
+            .map(|(entity, _)| entity),
+    );
+
+    // update camera children, and forget about cameras that no longer have any UI
+    camera_layout_info.retain(|camera_entity, _| {
+        let retained = camera_roots.contains_key(camera_entity);
+        if !retained {
+            ui_surface.set_window_children(*camera_entity, std::iter::empty());
+        }
+        retained
+    });
+    for (&camera_entity, roots) in &camera_roots {
+        ui_surface.set_window_children(camera_entity, roots.iter().copied());
+    }
 
     // update and remove children
     for entity in removed_children.read() {
@@ -325,15 +426,20 @@ pub fn ui_layout_system(
     for (entity, window) in windows.iter() {
         ui_surface.compute_window_layout(entity, &window.resolution);
     }
+    for (&camera_entity, context) in &camera_contexts {
+        ui_surface.compute_camera_layout(camera_entity, context.physical_size);
+    }
 
-    let inverse_target_scale_factor = 1. / scale_factor;
-
+    #[allow(clippy::too_many_arguments)]
     fn update_uinode_geometry_recursive(
         entity: Entity,
         ui_surface: &UiSurface,
         node_transform_query: &mut Query<(&mut Node, &mut Transform)>,
         children_query: &Query<&Children>,
+        rounding_query: &Query<&LayoutRounding>,
         inverse_target_scale_factor: f32,
+        target_camera: Option<Entity>,
+        mut rounding: LayoutRounding,
         parent_size: Vec2,
         mut absolute_location: Vec2,
     ) {
@@ -346,17 +452,23 @@ pub fn ui_layout_system(
 
             absolute_location += layout_location;
 
-            let rounded_size = round_layout_coords(absolute_location + layout_size)
-                - round_layout_coords(absolute_location);
+            if let Ok(node_rounding) = rounding_query.get(entity) {
+                rounding = *node_rounding;
+            }
+            let round = |value| round_layout(rounding, value, inverse_target_scale_factor);
+
+            let rounded_size = round(absolute_location + layout_size) - round(absolute_location);
 
-            let rounded_location =
-                round_layout_coords(layout_location) + 0.5 * (rounded_size - parent_size);
+            let rounded_location = round(layout_location) + 0.5 * (rounded_size - parent_size);
 
             // only trigger change detection when the new values are different
             if node.calculated_size != rounded_size || node.unrounded_size != layout_size {
                 node.calculated_size = rounded_size;
                 node.unrounded_size = layout_size;
             }
+            if node.target_camera != target_camera {
+                node.target_camera = target_camera;
+            }
             if transform.translation.truncate() != rounded_location {
                 transform.translation = rounded_location.extend(0.);
             }
@@ -367,7 +479,10 @@ pub fn ui_layout_system(
                         ui_surface,
                         node_transform_query,
                         children_query,
+                        rounding_query,
                         inverse_target_scale_factor,
+                        target_camera,
+                        rounding,
                         rounded_size,
                         absolute_location,
                     );
@@ -376,13 +491,21 @@ pub fn ui_layout_system(
         }
     }
 
-    for entity in root_node_query.iter() {
+    for (entity, target_camera) in root_node_query.iter() {
+        let target_camera = target_camera.map(TargetCamera::entity);
+        let root_scale_factor = target_camera
+            .and_then(|camera_entity| camera_contexts.get(&camera_entity))
+            .map(|context| context.scale_factor)
+            .unwrap_or(scale_factor);
         update_uinode_geometry_recursive(
             entity,
             &ui_surface,
             &mut node_transform_query,
             &just_children_query,
-            inverse_target_scale_factor,
+            &rounding_query,
+            root_scale_factor.recip(),
+            target_camera,
+            LayoutRounding::DEFAULT,
             Vec2::ZERO,
             Vec2::ZERO,
         );
@@ -445,8 +568,23 @@ fn round_layout_coords(value: Vec2) -> Vec2 {
     }
 }
 
+/// Rounds logical layout coordinates according to `rounding`.
+///
+/// `inverse_scale_factor` is the number of logical pixels per physical pixel.
+fn round_layout(rounding: LayoutRounding, value: Vec2, inverse_scale_factor: f32) -> Vec2 {
+    match rounding {
+        LayoutRounding::Logical => round_layout_coords(value),
+        LayoutRounding::Snap => {
+            round_layout_coords(value / inverse_scale_factor) * inverse_scale_factor
+        }
+        LayoutRounding::Floor => (value / inverse_scale_factor).floor() * inverse_scale_factor,
+        LayoutRounding::None => value,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::layout::round_layout;
     use crate::layout::round_layout_coords;
     use crate::prelude::*;
     use crate::ui_layout_system;
@@ -475,6 +613,30 @@ mod tests {
         assert_eq!(round_layout_coords(vec2(-50.5, 49.5)), vec2(-50., 50.));
     }
 
+    #[test]
+    fn round_layout_to_physical_pixels() {
+        // a scale factor of 1.5 physical pixels per logical pixel
+        let inverse_scale_factor = 1. / 1.5;
+        let value = vec2(10.2, 10.5);
+        assert_eq!(
+            round_layout(LayoutRounding::Logical, value, inverse_scale_factor),
+            vec2(10., 11.)
+        );
+        // 15.3 and 15.75 physical pixels are rounded to 15 and 16
+        assert_eq!(
+            round_layout(LayoutRounding::Snap, value, inverse_scale_factor),
+            vec2(15., 16.) * inverse_scale_factor
+        );
+        assert_eq!(
+            round_layout(LayoutRounding::Floor, value, inverse_scale_factor),
+            vec2(15., 15.) * inverse_scale_factor
+        );
+        assert_eq!(
+            round_layout(LayoutRounding::None, value, inverse_scale_factor),
+            value
+        );
+    }
+
     // these window dimensions are easy to convert to and from percentage values
     const WINDOW_WIDTH: f32 = 1000.;
     const WINDOW_HEIGHT: f32 = 100.;
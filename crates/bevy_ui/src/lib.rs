@@ -9,6 +9,7 @@ pub mod node_bundles;
 pub mod ui_material;
 pub mod update;
 pub mod widget;
+pub mod world_space;
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_reflect::Reflect;
@@ -16,36 +17,49 @@ use bevy_reflect::Reflect;
 use bevy_text::TextLayoutInfo;
 #[cfg(feature = "bevy_text")]
 mod accessibility;
+mod binding;
+mod cursor;
+mod drag;
 mod focus;
 mod geometry;
 mod layout;
 mod render;
 mod stack;
+mod stylesheet;
+mod transition;
 mod ui_node;
 
+pub use binding::*;
+pub use cursor::*;
+pub use drag::*;
 pub use focus::*;
 pub use geometry::*;
 pub use layout::*;
 pub use measurement::*;
 pub use render::*;
+pub use stylesheet::*;
+pub use transition::*;
 pub use ui_material::*;
 pub use ui_node::*;
 use widget::UiImageSize;
+pub use world_space::*;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         camera_config::*, geometry::*, node_bundles::*, ui_material::*, ui_node::*, widget::Button,
-        widget::Label, Interaction, UiMaterialPlugin, UiScale,
+        widget::Label, BindComponent, BindResource, Classes, DragDrop, DragPayload, Draggable,
+        DropTarget, HoverCursor, Interaction, StyleSheet, UiBinding, UiMaterialPlugin, UiScale,
+        UiStyleSheet, UiTransition, UiTransitionCommandsExt, WorldSpaceUi,
     };
 }
 
-use crate::prelude::UiCameraConfig;
+use crate::prelude::{CameraUiScale, UiCameraConfig};
 #[cfg(feature = "bevy_text")]
 use crate::widget::TextFlags;
 use bevy_app::prelude::*;
-use bevy_asset::Assets;
+use bevy_asset::{AssetApp, Assets};
 use bevy_ecs::prelude::*;
 use bevy_input::InputSystem;
 use bevy_render::{extract_component::ExtractComponentPlugin, texture::Image, RenderApp};
@@ -65,10 +79,18 @@ pub enum UiSystem {
     Layout,
     /// After this label, input interactions with UI entities have been updated for this frame
     Focus,
+    /// After this label, drag-and-drop state and events have been updated for this frame
+    Drag,
     /// After this label, the [`UiStack`] resource has been updated
     Stack,
     /// After this label, node outline widths have been updated
     Outlines,
+    /// After this label, the properties animated by [`UiTransition`]s have been updated
+    Transitions,
+    /// After this label, the rules of [`StyleSheet`]s have been applied to nodes with [`Classes`]
+    Stylesheets,
+    /// After this label, the properties of nodes bound with [`BindResource`] and [`BindComponent`] have been updated
+    Bindings,
 }
 
 /// The current scale of the UI.
@@ -90,13 +112,25 @@ impl Plugin for UiPlugin {
             .init_resource::<UiSurface>()
             .init_resource::<UiScale>()
             .init_resource::<UiStack>()
+            .init_resource::<DragState>()
+            .init_asset::<StyleSheet>()
+            .init_asset_loader::<StyleSheetLoader>()
+            .add_event::<DragStart>()
+            .add_event::<DragOver>()
+            .add_event::<DragDrop>()
+            .add_event::<DragEnd>()
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
             .register_type::<BackgroundColor>()
+            .register_type::<BackgroundGradient>()
             .register_type::<CalculatedClip>()
+            .register_type::<Classes>()
             .register_type::<ContentSize>()
             .register_type::<Direction>()
+            .register_type::<DragGhost>()
+            .register_type::<Draggable>()
+            .register_type::<DropTarget>()
             .register_type::<Display>()
             .register_type::<FlexDirection>()
             .register_type::<FlexWrap>()
@@ -104,10 +138,13 @@ impl Plugin for UiPlugin {
             .register_type::<GridAutoFlow>()
             .register_type::<GridPlacement>()
             .register_type::<GridTrack>()
+            .register_type::<HoverCursor>()
+            .register_type::<InlineStyle>()
             .register_type::<Interaction>()
             .register_type::<JustifyContent>()
             .register_type::<JustifyItems>()
             .register_type::<JustifySelf>()
+            .register_type::<LayoutRounding>()
             .register_type::<Node>()
             // NOTE: used by Style::aspect_ratio
             .register_type::<Option<f32>>()
@@ -117,21 +154,31 @@ impl Plugin for UiPlugin {
             .register_type::<RelativeCursorPosition>()
             .register_type::<RepeatedGridTrack>()
             .register_type::<Style>()
+            .register_type::<TargetCamera>()
             .register_type::<UiCameraConfig>()
+            .register_type::<CameraUiScale>()
             .register_type::<UiImage>()
             .register_type::<UiImageSize>()
             .register_type::<UiRect>()
             .register_type::<UiScale>()
+            .register_type::<UiStyleSheet>()
             .register_type::<UiTextureAtlasImage>()
             .register_type::<Val>()
             .register_type::<BorderColor>()
+            .register_type::<BoxShadow>()
             .register_type::<widget::Button>()
             .register_type::<widget::Label>()
             .register_type::<ZIndex>()
             .register_type::<Outline>()
+            .register_type::<WorldSpaceUi>()
             .add_systems(
                 PreUpdate,
-                ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                (
+                    ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                    ui_drag_system.in_set(UiSystem::Drag).after(UiSystem::Focus),
+                    widget::virtual_list_scroll_system.after(UiSystem::Focus),
+                    hover_cursor_system.after(UiSystem::Focus),
+                ),
             );
 
         #[cfg(feature = "bevy_text")]
@@ -177,9 +224,19 @@ impl Plugin for UiPlugin {
             PostUpdate,
             widget::update_atlas_content_size_system.before(UiSystem::Layout),
         );
+        app.add_systems(
+            PostUpdate,
+            widget::update_virtual_list_system.before(UiSystem::Layout),
+        );
         app.add_systems(
             PostUpdate,
             (
+                ui_stylesheet_system
+                    .in_set(UiSystem::Stylesheets)
+                    .before(UiSystem::Transitions),
+                ui_transition_system
+                    .in_set(UiSystem::Transitions)
+                    .before(UiSystem::Layout),
                 ui_layout_system
                     .in_set(UiSystem::Layout)
                     .before(TransformSystem::TransformPropagate),
@@ -188,6 +245,7 @@ impl Plugin for UiPlugin {
                     .after(UiSystem::Layout),
                 ui_stack_system.in_set(UiSystem::Stack),
                 update_clipping_system.after(TransformSystem::TransformPropagate),
+                world_space_ui_billboard_system.before(TransformSystem::TransformPropagate),
             ),
         );
 
@@ -14,8 +14,9 @@ pub use ui_material_pipeline::*;
 
 use crate::Outline;
 use crate::{
-    prelude::UiCameraConfig, BackgroundColor, BorderColor, CalculatedClip, ContentSize, Node,
-    Style, UiImage, UiScale, UiTextureAtlasImage, Val,
+    camera_config::CameraUiScale, prelude::UiCameraConfig, BackgroundColor, BackgroundGradient,
+    BorderColor, BoxShadow, CalculatedClip, ContentSize, Node, Style, TargetCamera, UiImage,
+    UiScale, UiTextureAtlasImage, Val,
 };
 
 use bevy_app::prelude::*;
@@ -89,6 +90,8 @@ pub fn build_ui_render(app: &mut App) {
                 #[cfg(feature = "bevy_text")]
                 extract_text_uinodes.after(RenderUiSystem::ExtractAtlasNode),
                 extract_uinode_outlines.after(RenderUiSystem::ExtractAtlasNode),
+                extract_uinode_box_shadows.after(RenderUiSystem::ExtractAtlasNode),
+                extract_uinode_gradients.after(RenderUiSystem::ExtractNode),
             ),
         )
         .add_systems(
@@ -155,6 +158,8 @@ fn get_ui_graph(render_app: &mut App) -> RenderGraph {
 
 pub struct ExtractedUiNode {
     pub stack_index: u32,
+    /// The camera this node is rendered to, see [`TargetCamera`].
+    pub camera_entity: Option<Entity>,
     pub transform: Mat4,
     pub color: Color,
     pub rect: Rect,
@@ -163,6 +168,21 @@ pub struct ExtractedUiNode {
     pub clip: Option<Rect>,
     pub flip_x: bool,
     pub flip_y: bool,
+    pub shading: ExtractedUiShading,
+}
+
+/// How an [`ExtractedUiNode`] is shaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExtractedUiShading {
+    /// The node is filled with its color, multiplied by its image if it has one.
+    #[default]
+    Fill,
+    /// The node is a [`BoxShadow`], whose rect includes `blur_radius` on each side of the shadow.
+    BoxShadow { blur_radius: f32 },
+    /// The node is filled with a [`BackgroundGradient::Linear`] from its color to `end_color`.
+    LinearGradient { angle: f32, end_color: Color },
+    /// The node is filled with a [`BackgroundGradient::Radial`] from its color to `end_color`.
+    RadialGradient { center: Vec2, end_color: Color },
 }
 
 #[derive(Resource, Default)]
@@ -242,6 +262,7 @@ pub fn extract_atlas_uinodes(
             entity,
             ExtractedUiNode {
                 stack_index: uinode.stack_index,
+                camera_entity: uinode.target_camera(),
                 transform: transform.compute_matrix(),
                 color: color.0,
                 rect: atlas_rect,
@@ -250,6 +271,7 @@ pub fn extract_atlas_uinodes(
                 atlas_size: Some(atlas_size),
                 flip_x: atlas_image.flip_x,
                 flip_y: atlas_image.flip_y,
+                shading: ExtractedUiShading::Fill,
             },
         );
     }
@@ -362,6 +384,7 @@ pub fn extract_uinode_borders(
                     commands.spawn_empty().id(),
                     ExtractedUiNode {
                         stack_index: node.stack_index,
+                        camera_entity: node.target_camera(),
                         // This translates the uinode's transform to the center of the current border rectangle
                         transform: transform * Mat4::from_translation(edge.center().extend(0.)),
                         color: border_color.0,
@@ -374,6 +397,7 @@ pub fn extract_uinode_borders(
                         clip: clip.map(|clip| clip.clip),
                         flip_x: false,
                         flip_y: false,
+                        shading: ExtractedUiShading::Fill,
                     },
                 );
             }
@@ -446,6 +470,7 @@ pub fn extract_uinode_outlines(
                     commands.spawn_empty().id(),
                     ExtractedUiNode {
                         stack_index: node.stack_index,
+                        camera_entity: node.target_camera(),
                         // This translates the uinode's transform to the center of the current border rectangle
                         transform: transform * Mat4::from_translation(edge.center().extend(0.)),
                         color: outline.color,
@@ -458,6 +483,7 @@ pub fn extract_uinode_outlines(
                         clip: maybe_clip.map(|clip| clip.clip),
                         flip_x: false,
                         flip_y: false,
+                        shading: ExtractedUiShading::Fill,
                     },
                 );
             }
@@ -465,6 +491,131 @@ pub fn extract_uinode_outlines(
     }
 }
 
+pub fn extract_uinode_box_shadows(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    windows: Extract<Query<&Window, With<PrimaryWindow>>>,
+    ui_scale: Extract<Res<UiScale>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &BoxShadow,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+) {
+    let ui_logical_viewport_size = windows
+        .get_single()
+        .map(|window| Vec2::new(window.resolution.width(), window.resolution.height()))
+        .unwrap_or(Vec2::ZERO)
+        / ui_scale.0;
+
+    for (node, global_transform, shadow, view_visibility, clip) in uinode_query.iter() {
+        // Skip invisible shadows
+        if !view_visibility.get() || shadow.color.is_fully_transparent() {
+            continue;
+        }
+
+        let resolve = |value: Val| {
+            value
+                .resolve(node.size().x, ui_logical_viewport_size)
+                .unwrap_or(0.)
+        };
+        let offset = Vec2::new(resolve(shadow.x_offset), resolve(shadow.y_offset));
+        let shadow_size = (node.size() + 2. * resolve(shadow.spread_radius)).max(Vec2::ZERO);
+        let blur_radius = resolve(shadow.blur_radius).max(0.);
+        if shadow_size.cmple(Vec2::ZERO).any() {
+            continue;
+        }
+
+        extracted_uinodes.uinodes.insert(
+            commands.spawn_empty().id(),
+            ExtractedUiNode {
+                stack_index: node.stack_index,
+                camera_entity: node.target_camera(),
+                transform: global_transform.compute_matrix()
+                    * Mat4::from_translation(offset.extend(0.)),
+                color: shadow.color,
+                // The quad is grown by the blur radius so the faded edges of the shadow fit in it
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: shadow_size + 2. * blur_radius,
+                },
+                image: AssetId::default(),
+                atlas_size: None,
+                clip: clip.map(|clip| clip.clip),
+                flip_x: false,
+                flip_y: false,
+                shading: ExtractedUiShading::BoxShadow { blur_radius },
+            },
+        );
+    }
+}
+
+/// Replaces the background of the nodes with a [`BackgroundGradient`] extracted by [`extract_uinodes`].
+pub fn extract_uinode_gradients(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    uinode_query: Extract<
+        Query<
+            (
+                Entity,
+                &Node,
+                &GlobalTransform,
+                &BackgroundGradient,
+                &ViewVisibility,
+                Option<&CalculatedClip>,
+            ),
+            (Without<UiImage>, Without<UiTextureAtlasImage>),
+        >,
+    >,
+) {
+    for (entity, uinode, transform, gradient, view_visibility, clip) in uinode_query.iter() {
+        // Skip invisible nodes
+        if !view_visibility.get() {
+            continue;
+        }
+
+        let (color, shading) = match *gradient {
+            BackgroundGradient::Linear { angle, start, end } => (
+                start,
+                ExtractedUiShading::LinearGradient {
+                    angle,
+                    end_color: end,
+                },
+            ),
+            BackgroundGradient::Radial { center, start, end } => (
+                start,
+                ExtractedUiShading::RadialGradient {
+                    center,
+                    end_color: end,
+                },
+            ),
+        };
+
+        extracted_uinodes.uinodes.insert(
+            entity,
+            ExtractedUiNode {
+                stack_index: uinode.stack_index,
+                camera_entity: uinode.target_camera(),
+                transform: transform.compute_matrix(),
+                color,
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: uinode.calculated_size,
+                },
+                clip: clip.map(|clip| clip.clip),
+                image: AssetId::default(),
+                atlas_size: None,
+                flip_x: false,
+                flip_y: false,
+                shading,
+            },
+        );
+    }
+}
+
 pub fn extract_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     images: Extract<Res<Assets<Image>>>,
@@ -505,6 +656,7 @@ pub fn extract_uinodes(
             entity,
             ExtractedUiNode {
                 stack_index: uinode.stack_index,
+                camera_entity: uinode.target_camera(),
                 transform: transform.compute_matrix(),
                 color: color.0,
                 rect: Rect {
@@ -516,6 +668,7 @@ pub fn extract_uinodes(
                 atlas_size: None,
                 flip_x,
                 flip_y,
+                shading: ExtractedUiShading::Fill,
             },
         );
     }
@@ -535,13 +688,41 @@ const UI_CAMERA_TRANSFORM_OFFSET: f32 = -0.1;
 #[derive(Component)]
 pub struct DefaultCameraView(pub Entity);
 
+/// Marks a camera in the render world that is the target of at least one [`TargetCamera`].
+///
+/// UI nodes without a [`TargetCamera`] are not drawn to these cameras.
+#[derive(Component)]
+pub struct UiTargetedCamera;
+
+/// Returns whether a UI node with the given target camera should be drawn to `view_camera`.
+pub(crate) fn is_drawn_to_camera(
+    node_camera: Option<Entity>,
+    view_camera: Entity,
+    view_is_targeted: bool,
+) -> bool {
+    match node_camera {
+        Some(node_camera) => node_camera == view_camera,
+        None => !view_is_targeted,
+    }
+}
+
 pub fn extract_default_ui_camera_view<T: Component>(
     mut commands: Commands,
     ui_scale: Extract<Res<UiScale>>,
-    query: Extract<Query<(Entity, &Camera, Option<&UiCameraConfig>), With<T>>>,
+    query: Extract<
+        Query<
+            (
+                Entity,
+                &Camera,
+                Option<&UiCameraConfig>,
+                Option<&CameraUiScale>,
+            ),
+            With<T>,
+        >,
+    >,
+    target_cameras: Extract<Query<&TargetCamera, Without<Parent>>>,
 ) {
-    let scale = (ui_scale.0).recip();
-    for (entity, camera, camera_ui) in &query {
+    for (entity, camera, camera_ui, camera_ui_scale) in &query {
         // ignore cameras with disabled ui
         if matches!(camera_ui, Some(&UiCameraConfig { show_ui: false, .. })) {
             continue;
@@ -564,6 +745,7 @@ pub fn extract_default_ui_camera_view<T: Component>(
             camera.physical_viewport_size(),
         ) {
             // use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection
+            let scale = camera_ui_scale.map_or(ui_scale.0, |scale| scale.0).recip();
             let projection_matrix = Mat4::orthographic_rh(
                 0.0,
                 logical_size.x * scale,
@@ -591,10 +773,17 @@ pub fn extract_default_ui_camera_view<T: Component>(
                     color_grading: Default::default(),
                 })
                 .id();
-            commands.get_or_spawn(entity).insert((
+            let mut camera_commands = commands.get_or_spawn(entity);
+            camera_commands.insert((
                 DefaultCameraView(default_camera_view),
                 RenderPhase::<TransparentUi>::default(),
             ));
+            if target_cameras
+                .iter()
+                .any(|target_camera| target_camera.entity() == entity)
+            {
+                camera_commands.insert(UiTargetedCamera);
+            }
         }
     }
 }
@@ -671,6 +860,7 @@ pub fn extract_text_uinodes(
                 commands.spawn_empty().id(),
                 ExtractedUiNode {
                     stack_index: uinode.stack_index,
+                    camera_entity: uinode.target_camera(),
                     transform: transform
                         * Mat4::from_translation(position.extend(0.) * inverse_scale_factor),
                     color,
@@ -680,6 +870,7 @@ pub fn extract_text_uinodes(
                     clip: clip.map(|clip| clip.clip),
                     flip_x: false,
                     flip_y: false,
+                    shading: ExtractedUiShading::Fill,
                 },
             );
         }
@@ -693,6 +884,10 @@ struct UiVertex {
     pub uv: [f32; 2],
     pub color: [f32; 4],
     pub mode: u32,
+    /// The color at the end of gradients.
+    pub end_color: [f32; 4],
+    /// The size of the quad in logical pixels, followed by parameters specific to the `mode`.
+    pub shape: [f32; 4],
 }
 
 #[derive(Resource)]
@@ -727,18 +922,26 @@ pub struct UiBatch {
 
 const TEXTURED_QUAD: u32 = 0;
 const UNTEXTURED_QUAD: u32 = 1;
+const BOX_SHADOW: u32 = 2;
+const LINEAR_GRADIENT: u32 = 3;
+const RADIAL_GRADIENT: u32 = 4;
 
 #[allow(clippy::too_many_arguments)]
 pub fn queue_uinodes(
     extracted_uinodes: Res<ExtractedUiNodes>,
     ui_pipeline: Res<UiPipeline>,
     mut pipelines: ResMut<SpecializedRenderPipelines<UiPipeline>>,
-    mut views: Query<(&ExtractedView, &mut RenderPhase<TransparentUi>)>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        &mut RenderPhase<TransparentUi>,
+        Has<UiTargetedCamera>,
+    )>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
 ) {
     let draw_function = draw_functions.read().id::<DrawUi>();
-    for (view, mut transparent_phase) in &mut views {
+    for (view_entity, view, mut transparent_phase, view_is_targeted) in &mut views {
         let pipeline = pipelines.specialize(
             &pipeline_cache,
             &ui_pipeline,
@@ -749,12 +952,24 @@ pub fn queue_uinodes(
             .reserve(extracted_uinodes.uinodes.len());
 
         for (entity, extracted_uinode) in extracted_uinodes.uinodes.iter() {
+            if !is_drawn_to_camera(
+                extracted_uinode.camera_entity,
+                view_entity,
+                view_is_targeted,
+            ) {
+                continue;
+            }
+            // Shadows are drawn behind the node casting them, but above the nodes below it
+            let stack_offset = match extracted_uinode.shading {
+                ExtractedUiShading::BoxShadow { .. } => -0.5,
+                _ => 0.,
+            };
             transparent_phase.add(TransparentUi {
                 draw_function,
                 pipeline,
                 entity: *entity,
                 sort_key: (
-                    FloatOrd(extracted_uinode.stack_index as f32),
+                    FloatOrd(extracted_uinode.stack_index as f32 + stack_offset),
                     entity.index(),
                 ),
                 // batch_range will be calculated in prepare_uinodes
@@ -878,10 +1093,24 @@ pub fn prepare_uinodes(
                         }
                     }
 
-                    let mode = if extracted_uinode.image != AssetId::default() {
-                        TEXTURED_QUAD
-                    } else {
-                        UNTEXTURED_QUAD
+                    let (mode, end_color, shape_parameters) = match extracted_uinode.shading {
+                        ExtractedUiShading::Fill => {
+                            let mode = if extracted_uinode.image != AssetId::default() {
+                                TEXTURED_QUAD
+                            } else {
+                                UNTEXTURED_QUAD
+                            };
+                            (mode, Color::NONE, Vec2::ZERO)
+                        }
+                        ExtractedUiShading::BoxShadow { blur_radius } => {
+                            (BOX_SHADOW, Color::NONE, Vec2::new(blur_radius, 0.))
+                        }
+                        ExtractedUiShading::LinearGradient { angle, end_color } => {
+                            (LINEAR_GRADIENT, end_color, Vec2::new(angle, 0.))
+                        }
+                        ExtractedUiShading::RadialGradient { center, end_color } => {
+                            (RADIAL_GRADIENT, end_color, center)
+                        }
                     };
 
                     let mut uinode_rect = extracted_uinode.rect;
@@ -982,12 +1211,21 @@ pub fn prepare_uinodes(
                     };
 
                     let color = extracted_uinode.color.as_linear_rgba_f32();
+                    let end_color = end_color.as_linear_rgba_f32();
+                    let shape = extracted_uinode
+                        .rect
+                        .size()
+                        .extend(shape_parameters.x)
+                        .extend(shape_parameters.y)
+                        .to_array();
                     for i in QUAD_INDICES {
                         ui_meta.vertices.push(UiVertex {
                             position: positions_clipped[i].into(),
                             uv: uvs[i].into(),
                             color,
                             mode,
+                            end_color,
+                            shape,
                         });
                     }
                     index += QUAD_INDICES.len() as u32;
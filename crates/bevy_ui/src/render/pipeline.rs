@@ -65,6 +65,10 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x4,
                 // mode
                 VertexFormat::Uint32,
+                // end_color
+                VertexFormat::Float32x4,
+                // shape
+                VertexFormat::Float32x4,
             ],
         );
         let shader_defs = Vec::new();
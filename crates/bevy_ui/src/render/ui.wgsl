@@ -1,13 +1,19 @@
 #import bevy_render::view::View
 
 const TEXTURED_QUAD: u32 = 0u;
+const BOX_SHADOW: u32 = 2u;
+const LINEAR_GRADIENT: u32 = 3u;
+const RADIAL_GRADIENT: u32 = 4u;
 
 @group(0) @binding(0) var<uniform> view: View;
 
 struct VertexOutput {
     @location(0) uv: vec2<f32>,
     @location(1) color: vec4<f32>,
-    @location(3) @interpolate(flat) mode: u32,
+    @location(2) end_color: vec4<f32>,
+    // The size of the quad in logical pixels, followed by parameters specific to the mode.
+    @location(3) @interpolate(flat) shape: vec4<f32>,
+    @location(4) @interpolate(flat) mode: u32,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -17,11 +23,15 @@ fn vertex(
     @location(1) vertex_uv: vec2<f32>,
     @location(2) vertex_color: vec4<f32>,
     @location(3) mode: u32,
+    @location(4) end_color: vec4<f32>,
+    @location(5) shape: vec4<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
     out.color = vertex_color;
+    out.end_color = end_color;
+    out.shape = shape;
     out.mode = mode;
     return out;
 }
@@ -29,12 +39,57 @@ fn vertex(
 @group(1) @binding(0) var sprite_texture: texture_2d<f32>;
 @group(1) @binding(1) var sprite_sampler: sampler;
 
+// Signed distance from `point` to the edge of a rectangle centered on the origin.
+fn sd_rect(point: vec2<f32>, half_size: vec2<f32>) -> f32 {
+    let d = abs(point) - half_size;
+    return length(max(d, vec2<f32>(0.0))) + min(max(d.x, d.y), 0.0);
+}
+
+fn box_shadow(in: VertexOutput) -> vec4<f32> {
+    let size = in.shape.xy;
+    let blur_radius = in.shape.z;
+    let point = (in.uv - 0.5) * size;
+    let distance = sd_rect(point, 0.5 * size - blur_radius);
+    var alpha = clamp(0.5 - distance, 0.0, 1.0);
+    if blur_radius > 0.0 {
+        // Approximates a gaussian falloff over the blur radius on each side of the edge.
+        alpha = 1.0 - smoothstep(-blur_radius, blur_radius, distance);
+    }
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
+}
+
+fn linear_gradient(in: VertexOutput) -> vec4<f32> {
+    let size = in.shape.xy;
+    let angle = in.shape.z;
+    // The angle is clockwise from the top of the node, and the y axis points down.
+    let direction = vec2<f32>(sin(angle), -cos(angle));
+    // As in CSS, the gradient line is long enough for the corners to get the start and end colors.
+    let half_length = 0.5 * dot(abs(direction), size);
+    let t = clamp(0.5 + 0.5 * dot((in.uv - 0.5) * size, direction) / half_length, 0.0, 1.0);
+    return mix(in.color, in.end_color, t);
+}
+
+fn radial_gradient(in: VertexOutput) -> vec4<f32> {
+    let size = in.shape.xy;
+    let center = in.shape.zw;
+    // The gradient ends at the corner furthest from the center.
+    let radius = length(max(center, 1.0 - center) * size);
+    let t = clamp(length((in.uv - center) * size) / radius, 0.0, 1.0);
+    return mix(in.color, in.end_color, t);
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     // textureSample can only be called in unform control flow, not inside an if branch.
     var color = textureSample(sprite_texture, sprite_sampler, in.uv);
     if in.mode == TEXTURED_QUAD {
         color = in.color * color;
+    } else if in.mode == BOX_SHADOW {
+        color = box_shadow(in);
+    } else if in.mode == LINEAR_GRADIENT {
+        color = linear_gradient(in);
+    } else if in.mode == RADIAL_GRADIENT {
+        color = radial_gradient(in);
     } else {
         color = in.color;
     }
@@ -14,11 +14,13 @@ fn vertex(
     @location(0) vertex_position: vec3<f32>,
     @location(1) vertex_uv: vec2<f32>,
     @location(2) border_widths: vec4<f32>,
+    @location(3) size: vec2<f32>,
 ) -> UiVertexOutput {
     var out: UiVertexOutput;
     out.uv = vertex_uv;
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
     out.border_widths = border_widths;
+    out.size = size;
     return out;
 }
 
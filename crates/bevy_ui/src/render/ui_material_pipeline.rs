@@ -5,7 +5,7 @@ use bevy_asset::*;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::{Component, Entity, EventReader},
-    query::{ROQueryItem, With},
+    query::{Has, ROQueryItem, With},
     schedule::IntoSystemConfigs,
     storage::SparseSet,
     system::lifetimeless::{Read, SRes},
@@ -29,6 +29,7 @@ use bevy_utils::{FloatOrd, HashMap, HashSet};
 use bevy_window::{PrimaryWindow, Window};
 use bytemuck::{Pod, Zeroable};
 
+use super::is_drawn_to_camera;
 use crate::*;
 
 pub const UI_MATERIAL_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(10074188772096983955);
@@ -121,6 +122,7 @@ pub struct UiMaterialVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
     pub border_widths: [f32; 4],
+    pub size: [f32; 2],
 }
 
 // in this [`UiMaterialPipeline`] there is (currently) no batching going on.
@@ -158,6 +160,8 @@ where
                 VertexFormat::Float32x2,
                 // border_widths
                 VertexFormat::Float32x4,
+                // size
+                VertexFormat::Float32x2,
             ],
         );
         let shader_defs = Vec::new();
@@ -327,6 +331,7 @@ impl<P: PhaseItem, M: UiMaterial> RenderCommand<P> for DrawUiMaterialNode<M> {
 
 pub struct ExtractedUiMaterialNode<M: UiMaterial> {
     pub stack_index: usize,
+    pub camera_entity: Option<Entity>,
     pub transform: Mat4,
     pub rect: Rect,
     pub border: [f32; 4],
@@ -413,6 +418,7 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
                 entity,
                 ExtractedUiMaterialNode {
                     stack_index,
+                    camera_entity: uinode.target_camera(),
                     transform: transform.compute_matrix(),
                     material: handle.id(),
                     rect: Rect {
@@ -559,6 +565,7 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                             position: positions_clipped[i].into(),
                             uv: uvs[i].into(),
                             border_widths: extracted_uinode.border,
+                            size: uinode_rect.size().into(),
                         });
                     }
 
@@ -727,7 +734,12 @@ pub fn queue_ui_material_nodes<M: UiMaterial>(
     mut pipelines: ResMut<SpecializedRenderPipelines<UiMaterialPipeline<M>>>,
     pipeline_cache: Res<PipelineCache>,
     render_materials: Res<RenderUiMaterials<M>>,
-    mut views: Query<(&ExtractedView, &mut RenderPhase<TransparentUi>)>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        &mut RenderPhase<TransparentUi>,
+        Has<UiTargetedCamera>,
+    )>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
@@ -737,7 +749,14 @@ pub fn queue_ui_material_nodes<M: UiMaterial>(
         let Some(material) = render_materials.get(&extracted_uinode.material) else {
             continue;
         };
-        for (view, mut transparent_phase) in &mut views {
+        for (view_entity, view, mut transparent_phase, view_is_targeted) in &mut views {
+            if !is_drawn_to_camera(
+                extracted_uinode.camera_entity,
+                view_entity,
+                view_is_targeted,
+            ) {
+                continue;
+            }
             let pipeline = pipelines.specialize(
                 &pipeline_cache,
                 &ui_material_pipeline,
@@ -5,5 +5,7 @@ struct UiVertexOutput {
     @location(0) uv: vec2<f32>,
     // The size of the borders in UV space. Order is Left, Right, Top, Bottom.
     @location(1) border_widths: vec4<f32>,
+    // The size of the node in logical pixels, e.g. to keep shapes drawn in UV space from being stretched.
+    @location(2) size: vec2<f32>,
     @builtin(position) position: vec4<f32>,
 };
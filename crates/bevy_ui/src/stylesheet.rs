@@ -0,0 +1,739 @@
+//! CSS-like stylesheets for UI nodes.
+//!
+//! A [`StyleSheet`] is an asset holding a list of rules, each made of a [`UiSelector`] and the
+//! [`StyleProperties`] it sets. Stylesheets are loaded from RON files with the `.style.ron` extension
+//! and reloaded whenever the file changes when asset hot-reloading is enabled:
+//!
+//! ```ron
+//! (
+//!     rules: [
+//!         (
+//!             selector: ".button",
+//!             style: (
+//!                 width: Px(150.0),
+//!                 justify_content: Center,
+//!                 background_color: Rgba(red: 0.15, green: 0.15, blue: 0.15, alpha: 1.0),
+//!             ),
+//!         ),
+//!         (
+//!             selector: ".menu .button:hover",
+//!             style: (background_color: Rgba(red: 0.25, green: 0.25, blue: 0.25, alpha: 1.0)),
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! A stylesheet is attached to a UI tree with the [`UiStyleSheet`] component, and applies to the node
+//! it is attached to and all of its descendants. Nodes opt into rules with a [`Classes`] component.
+//!
+//! When several rules set the same property on a node, the one with the highest
+//! [specificity](UiSelector::specificity) wins. Ties are broken by the stylesheets' position in the
+//! hierarchy, with stylesheets attached closer to the node taking precedence, and then by the order
+//! of the rules within a stylesheet, with later rules taking precedence.
+use crate::{
+    AlignContent, AlignItems, AlignSelf, BackgroundColor, BorderColor, Direction, Display,
+    FlexDirection, FlexWrap, GridAutoFlow, GridPlacement, GridTrack, Interaction, JustifyContent,
+    JustifyItems, JustifySelf, Node, Overflow, PositionType, RepeatedGridTrack, Style, UiRect, Val,
+};
+use bevy_asset::{
+    io::Reader, Asset, AssetEvent, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext,
+};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    entity::Entity,
+    event::EventReader,
+    prelude::{Changed, Component, Or, With, Without},
+    reflect::ReflectComponent,
+    removal_detection::RemovedComponents,
+    system::{Commands, Query, Res},
+};
+use bevy_hierarchy::Parent;
+use bevy_reflect::{Reflect, TypePath};
+use bevy_render::color::Color;
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// The classes of a UI node, used to match it against the rules of [`StyleSheet`]s.
+///
+/// The node is styled by the stylesheets of its nearest [`UiStyleSheet`] ancestors.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Classes(pub Vec<String>);
+
+impl Classes {
+    /// Creates a set of classes from a list of class names.
+    pub fn new(classes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut new = Self::default();
+        for class in classes {
+            new.add(class);
+        }
+        new
+    }
+
+    /// Returns `true` if `class` is one of the classes.
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.iter().any(|c| c == class)
+    }
+
+    /// Adds `class` to the classes, if it isn't already present.
+    pub fn add(&mut self, class: impl Into<String>) {
+        let class = class.into();
+        if !self.contains(&class) {
+            self.0.push(class);
+        }
+    }
+
+    /// Removes `class` from the classes, returning `true` if it was present.
+    pub fn remove(&mut self, class: &str) -> bool {
+        let len = self.0.len();
+        self.0.retain(|c| c != class);
+        self.0.len() != len
+    }
+
+    /// Returns an iterator over the class names.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// Attaches a [`StyleSheet`] to a UI node.
+///
+/// The rules of the stylesheet apply to the node and all of its descendants that have [`Classes`].
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct UiStyleSheet(pub Handle<StyleSheet>);
+
+/// The styles of a node with [`Classes`] before any stylesheet rules are applied.
+///
+/// This is captured from the node's [`Style`], [`BackgroundColor`] and [`BorderColor`] the first time
+/// it is styled, and rules are layered over it every time the node is restyled. To change the
+/// styling of a node that has [`Classes`], modify this component instead of the node's [`Style`],
+/// which would be overwritten the next time the node is restyled.
+///
+/// When [`Classes`] is removed from the node, its styles are restored from this component.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct InlineStyle {
+    /// The node's own [`Style`].
+    pub style: Style,
+    /// The node's own background color, if it has a [`BackgroundColor`].
+    pub background_color: Option<Color>,
+    /// The node's own border color, if it has a [`BorderColor`].
+    pub border_color: Option<Color>,
+}
+
+impl InlineStyle {
+    fn write(
+        &self,
+        style: &mut Style,
+        background_color: Option<&mut BackgroundColor>,
+        border_color: Option<&mut BorderColor>,
+    ) {
+        *style = self.style.clone();
+        if let (Some(background_color), Some(color)) = (background_color, self.background_color) {
+            background_color.0 = color;
+        }
+        if let (Some(border_color), Some(color)) = (border_color, self.border_color) {
+            border_color.0 = color;
+        }
+    }
+}
+
+/// A pseudo-class matching the [`Interaction`] state of a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PseudoClass {
+    /// `:hover`, matches nodes that are hovered or pressed.
+    Hover,
+    /// `:pressed` (or `:active`), matches nodes that are pressed.
+    Pressed,
+}
+
+impl PseudoClass {
+    fn matches(self, interaction: Option<Interaction>) -> bool {
+        match self {
+            PseudoClass::Hover => matches!(
+                interaction,
+                Some(Interaction::Hovered | Interaction::Pressed)
+            ),
+            PseudoClass::Pressed => interaction == Some(Interaction::Pressed),
+        }
+    }
+}
+
+/// A selector matching a single node, such as `.button.primary:hover`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompoundSelector {
+    /// The classes the node must have.
+    pub classes: Vec<String>,
+    /// The pseudo-classes the node must match.
+    pub pseudo_classes: Vec<PseudoClass>,
+}
+
+impl CompoundSelector {
+    fn matches(&self, classes: Option<&Classes>, interaction: Option<Interaction>) -> bool {
+        self.classes
+            .iter()
+            .all(|class| classes.is_some_and(|classes| classes.contains(class)))
+            && self
+                .pseudo_classes
+                .iter()
+                .all(|pseudo_class| pseudo_class.matches(interaction))
+    }
+}
+
+/// An error produced when parsing a [`UiSelector`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum UiSelectorError {
+    /// The selector doesn't contain any class or pseudo-class.
+    #[error("the selector is empty")]
+    Empty,
+    /// A class or pseudo-class name is empty or contains invalid characters.
+    #[error("invalid name `{0}` in selector, names may only contain alphanumeric characters, `-` and `_`")]
+    InvalidName(String),
+    /// The pseudo-class isn't supported.
+    #[error("unknown pseudo-class `:{0}`")]
+    UnknownPseudoClass(String),
+    /// The selector contains a character that doesn't start a class or pseudo-class.
+    #[error("unexpected character `{0}` in selector, expected `.` or `:`")]
+    UnexpectedCharacter(char),
+}
+
+/// Selects the nodes a [`StyleRule`] applies to.
+///
+/// Selectors are written like CSS selectors, with the following subset of the syntax supported:
+/// - `.name` matches nodes whose [`Classes`] contain `name`.
+/// - `:hover` matches hovered or pressed nodes, and `:pressed` (or `:active`) matches pressed nodes,
+///   as reported by their [`Interaction`].
+/// - These can be combined to match nodes satisfying all of them, e.g. `.button.primary:hover`.
+/// - Selectors separated by whitespace match descendants, e.g. `.menu .button` matches nodes with the
+///   `button` class that have an ancestor with the `menu` class.
+///
+/// ```
+/// # use bevy_ui::UiSelector;
+/// let selector: UiSelector = ".menu .button:hover".parse().unwrap();
+/// assert_eq!(selector.specificity(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UiSelector {
+    /// The compound selectors, from the outermost ancestor to the selected node.
+    pub parts: Vec<CompoundSelector>,
+}
+
+impl UiSelector {
+    /// The specificity of the selector, which is the number of classes and pseudo-classes it contains.
+    ///
+    /// When several rules set the same property, the rule with the most specific selector wins.
+    pub fn specificity(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|part| part.classes.len() + part.pseudo_classes.len())
+            .sum()
+    }
+
+    /// Returns `true` if the selector matches a node.
+    ///
+    /// `nodes` yields the [`Classes`] and [`Interaction`] of the node, followed by those of each of its ancestors,
+    /// from its parent to the root.
+    pub fn matches<'a>(
+        &self,
+        mut nodes: impl Iterator<Item = (Option<&'a Classes>, Option<Interaction>)>,
+    ) -> bool {
+        let mut parts = self.parts.iter().rev();
+        let (Some(last), Some((classes, interaction))) = (parts.next(), nodes.next()) else {
+            return false;
+        };
+        // Matching each remaining part against the closest ancestor it matches is always correct,
+        // since there are only descendant combinators.
+        last.matches(classes, interaction)
+            && parts
+                .all(|part| nodes.any(|(classes, interaction)| part.matches(classes, interaction)))
+    }
+
+    fn parse_compound(compound: &str) -> Result<CompoundSelector, UiSelectorError> {
+        let mut selector = CompoundSelector::default();
+        let mut rest = compound;
+        while let Some(prefix) = rest.chars().next() {
+            if prefix != '.' && prefix != ':' {
+                return Err(UiSelectorError::UnexpectedCharacter(prefix));
+            }
+            let body = &rest[1..];
+            let end = body.find(['.', ':']).unwrap_or(body.len());
+            let name = &body[..end];
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(UiSelectorError::InvalidName(name.to_string()));
+            }
+            if prefix == '.' {
+                selector.classes.push(name.to_string());
+            } else {
+                selector.pseudo_classes.push(match name {
+                    "hover" => PseudoClass::Hover,
+                    "pressed" | "active" => PseudoClass::Pressed,
+                    _ => return Err(UiSelectorError::UnknownPseudoClass(name.to_string())),
+                });
+            }
+            rest = &body[end..];
+        }
+        Ok(selector)
+    }
+}
+
+impl FromStr for UiSelector {
+    type Err = UiSelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split_whitespace()
+            .map(Self::parse_compound)
+            .collect::<Result<Vec<_>, _>>()?;
+        if parts.is_empty() {
+            return Err(UiSelectorError::Empty);
+        }
+        Ok(Self { parts })
+    }
+}
+
+impl TryFrom<String> for UiSelector {
+    type Error = UiSelectorError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for UiSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            for class in &part.classes {
+                write!(f, ".{class}")?;
+            }
+            for pseudo_class in &part.pseudo_classes {
+                f.write_str(match pseudo_class {
+                    PseudoClass::Hover => ":hover",
+                    PseudoClass::Pressed => ":pressed",
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<UiSelector> for String {
+    fn from(selector: UiSelector) -> Self {
+        selector.to_string()
+    }
+}
+
+macro_rules! style_properties {
+    ($($field:ident: $ty:ty,)*) => {
+        /// The properties set by a [`StyleRule`].
+        ///
+        /// Each property is optional, and left untouched on the styled node when `None`.
+        #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct StyleProperties {
+            $(
+                #[doc = concat!("Overrides [`Style::", stringify!($field), "`].")]
+                pub $field: Option<$ty>,
+            )*
+            /// Overrides [`Style::aspect_ratio`].
+            pub aspect_ratio: Option<f32>,
+            /// Overrides the color of the node's [`BackgroundColor`], if it has one.
+            pub background_color: Option<Color>,
+            /// Overrides the color of the node's [`BorderColor`], if it has one.
+            pub border_color: Option<Color>,
+        }
+
+        impl StyleProperties {
+            /// Writes the properties that are set to `style`.
+            pub fn apply_to_style(&self, style: &mut Style) {
+                $(
+                    if let Some(value) = &self.$field {
+                        style.$field = value.clone();
+                    }
+                )*
+                if let Some(aspect_ratio) = self.aspect_ratio {
+                    style.aspect_ratio = Some(aspect_ratio);
+                }
+            }
+        }
+    };
+}
+
+style_properties! {
+    display: Display,
+    position_type: PositionType,
+    overflow: Overflow,
+    direction: Direction,
+    left: Val,
+    right: Val,
+    top: Val,
+    bottom: Val,
+    width: Val,
+    height: Val,
+    min_width: Val,
+    min_height: Val,
+    max_width: Val,
+    max_height: Val,
+    align_items: AlignItems,
+    justify_items: JustifyItems,
+    align_self: AlignSelf,
+    justify_self: JustifySelf,
+    align_content: AlignContent,
+    justify_content: JustifyContent,
+    margin: UiRect,
+    padding: UiRect,
+    border: UiRect,
+    flex_direction: FlexDirection,
+    flex_wrap: FlexWrap,
+    flex_grow: f32,
+    flex_shrink: f32,
+    flex_basis: Val,
+    row_gap: Val,
+    column_gap: Val,
+    grid_auto_flow: GridAutoFlow,
+    grid_template_rows: Vec<RepeatedGridTrack>,
+    grid_template_columns: Vec<RepeatedGridTrack>,
+    grid_auto_rows: Vec<GridTrack>,
+    grid_auto_columns: Vec<GridTrack>,
+    grid_row: GridPlacement,
+    grid_column: GridPlacement,
+}
+
+/// A rule of a [`StyleSheet`], setting properties on the nodes matching its selector.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StyleRule {
+    /// The nodes this rule applies to.
+    pub selector: UiSelector,
+    /// The properties set on the matching nodes.
+    #[serde(default)]
+    pub style: StyleProperties,
+}
+
+/// A list of [`StyleRule`]s, applied to UI trees with a [`UiStyleSheet`] component.
+///
+/// See the [module-level documentation](self) for the file format and cascading rules.
+#[derive(Asset, TypePath, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StyleSheet {
+    /// The rules of the stylesheet, in increasing order of precedence.
+    pub rules: Vec<StyleRule>,
+}
+
+impl StyleSheet {
+    /// Parses a stylesheet from RON.
+    ///
+    /// Optional properties can be written without wrapping them in `Some`.
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_bytes(bytes)
+    }
+
+    /// Adds a rule to the end of the stylesheet, giving it precedence over the existing rules.
+    pub fn with_rule(mut self, selector: UiSelector, style: StyleProperties) -> Self {
+        self.rules.push(StyleRule { selector, style });
+        self
+    }
+}
+
+/// [`AssetLoader`] for `.style.ron` files, loading them as [`StyleSheet`]s.
+#[derive(Default)]
+pub struct StyleSheetLoader;
+
+/// Possible errors that can be produced by [`StyleSheetLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum StyleSheetLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the stylesheet file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for StyleSheetLoader {
+    type Asset = StyleSheet;
+    type Settings = ();
+    type Error = StyleSheetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(StyleSheet::from_ron(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["style.ron"]
+    }
+}
+
+/// Applies the rules of [`StyleSheet`]s to the nodes with [`Classes`].
+///
+/// Nodes are restyled whenever a stylesheet is loaded or modified, or when the classes, [`Interaction`],
+/// [`InlineStyle`], attached stylesheets or hierarchy of any node changes.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn ui_stylesheet_system(
+    mut commands: Commands,
+    stylesheets: Res<Assets<StyleSheet>>,
+    mut stylesheet_events: EventReader<AssetEvent<StyleSheet>>,
+    mut removed_classes: RemovedComponents<Classes>,
+    changed_nodes: Query<
+        (),
+        (
+            With<Node>,
+            Or<(
+                Changed<Classes>,
+                Changed<Interaction>,
+                Changed<InlineStyle>,
+                Changed<UiStyleSheet>,
+                Changed<Parent>,
+            )>,
+        ),
+    >,
+    selector_query: Query<
+        (
+            Option<&Classes>,
+            Option<&Interaction>,
+            Option<&UiStyleSheet>,
+            Option<&Parent>,
+        ),
+        With<Node>,
+    >,
+    mut styled_query: Query<
+        (
+            Entity,
+            Option<&InlineStyle>,
+            &mut Style,
+            Option<&mut BackgroundColor>,
+            Option<&mut BorderColor>,
+        ),
+        With<Classes>,
+    >,
+    mut unstyled_query: Query<
+        (
+            Entity,
+            &InlineStyle,
+            &mut Style,
+            Option<&mut BackgroundColor>,
+            Option<&mut BorderColor>,
+        ),
+        Without<Classes>,
+    >,
+) {
+    // Nodes that lost their classes go back to their inline styles.
+    for entity in removed_classes.read() {
+        if let Ok((entity, inline, mut style, background_color, border_color)) =
+            unstyled_query.get_mut(entity)
+        {
+            inline.write(
+                &mut style,
+                background_color.map(|color| color.into_inner()),
+                border_color.map(|color| color.into_inner()),
+            );
+            commands.entity(entity).remove::<InlineStyle>();
+        }
+    }
+
+    let stylesheets_changed = !stylesheet_events.is_empty();
+    stylesheet_events.clear();
+    if !stylesheets_changed && changed_nodes.is_empty() {
+        return;
+    }
+
+    let mut ancestors = Vec::new();
+    let mut node_stylesheets = Vec::new();
+    let mut matched_rules = Vec::new();
+    for (entity, inline, mut style, background_color, border_color) in &mut styled_query {
+        let inline = match inline {
+            Some(inline) => inline.clone(),
+            None => {
+                let inline = InlineStyle {
+                    style: style.clone(),
+                    background_color: background_color.as_ref().map(|color| color.0),
+                    border_color: border_color.as_ref().map(|color| color.0),
+                };
+                commands.entity(entity).insert(inline.clone());
+                inline
+            }
+        };
+
+        // Collect the node and its ancestors, and the stylesheets attached to them.
+        ancestors.clear();
+        node_stylesheets.clear();
+        let mut current = Some(entity);
+        while let Some(Ok((classes, interaction, stylesheet, parent))) =
+            current.map(|entity| selector_query.get(entity))
+        {
+            ancestors.push((classes, interaction.copied()));
+            if let Some(stylesheet) = stylesheet.and_then(|handle| stylesheets.get(&handle.0)) {
+                node_stylesheets.push(stylesheet);
+            }
+            current = parent.map(Parent::get);
+        }
+        // Stylesheets attached closer to the node take precedence.
+        node_stylesheets.reverse();
+
+        matched_rules.clear();
+        for (stylesheet_index, stylesheet) in node_stylesheets.iter().copied().enumerate() {
+            for (rule_index, rule) in stylesheet.rules.iter().enumerate() {
+                if rule.selector.matches(ancestors.iter().copied()) {
+                    matched_rules.push((
+                        rule.selector.specificity(),
+                        stylesheet_index,
+                        rule_index,
+                        &rule.style,
+                    ));
+                }
+            }
+        }
+        matched_rules.sort_by_key(|(specificity, stylesheet_index, rule_index, _)| {
+            (*specificity, *stylesheet_index, *rule_index)
+        });
+
+        let mut new_style = inline.style;
+        let mut new_background_color = inline.background_color;
+        let mut new_border_color = inline.border_color;
+        for (.., properties) in &matched_rules {
+            properties.apply_to_style(&mut new_style);
+            new_background_color = properties.background_color.or(new_background_color);
+            new_border_color = properties.border_color.or(new_border_color);
+        }
+
+        style.set_if_neq(new_style);
+        if let (Some(mut background_color), Some(color)) = (background_color, new_background_color)
+        {
+            background_color.set_if_neq(BackgroundColor(color));
+        }
+        if let (Some(mut border_color), Some(color)) = (border_color, new_border_color) {
+            border_color.set_if_neq(BorderColor(color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ui_stylesheet_system, Classes, InlineStyle, PseudoClass, StyleProperties, StyleSheet,
+        UiSelector, UiSelectorError, UiStyleSheet,
+    };
+    use crate::{Interaction, Node, Style, Val};
+    use bevy_asset::{AssetEvent, Assets};
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    #[test]
+    fn parse_selectors() {
+        let selector: UiSelector = ".menu  .button.primary:hover".parse().unwrap();
+        assert_eq!(selector.parts.len(), 2);
+        assert_eq!(selector.parts[0].classes, vec!["menu"]);
+        assert_eq!(selector.parts[1].classes, vec!["button", "primary"]);
+        assert_eq!(selector.parts[1].pseudo_classes, vec![PseudoClass::Hover]);
+        assert_eq!(selector.specificity(), 4);
+        assert_eq!(selector.to_string(), ".menu .button.primary:hover");
+
+        assert_eq!("  ".parse::<UiSelector>(), Err(UiSelectorError::Empty));
+        assert_eq!(
+            "button".parse::<UiSelector>(),
+            Err(UiSelectorError::UnexpectedCharacter('b'))
+        );
+        assert_eq!(
+            ".a..b".parse::<UiSelector>(),
+            Err(UiSelectorError::InvalidName(String::new()))
+        );
+        assert_eq!(
+            ".a:focus".parse::<UiSelector>(),
+            Err(UiSelectorError::UnknownPseudoClass("focus".to_string()))
+        );
+    }
+
+    #[test]
+    fn match_selectors() {
+        let menu = Classes::new(["menu"]);
+        let button = Classes::new(["button"]);
+        let selector: UiSelector = ".menu .button:hover".parse().unwrap();
+
+        // a hovered button inside a menu, with a node between them
+        let nodes = [
+            (Some(&button), Some(Interaction::Hovered)),
+            (None, None),
+            (Some(&menu), None),
+        ];
+        assert!(selector.matches(nodes.iter().copied()));
+
+        // pressed nodes are also hovered
+        let nodes = [
+            (Some(&button), Some(Interaction::Pressed)),
+            (Some(&menu), None),
+        ];
+        assert!(selector.matches(nodes.iter().copied()));
+
+        // not hovered
+        let nodes = [
+            (Some(&button), Some(Interaction::None)),
+            (Some(&menu), None),
+        ];
+        assert!(!selector.matches(nodes.iter().copied()));
+
+        // not inside a menu
+        let nodes = [(Some(&button), Some(Interaction::Hovered)), (None, None)];
+        assert!(!selector.matches(nodes.iter().copied()));
+
+        // the menu itself doesn't match
+        let nodes = [(Some(&menu), Some(Interaction::Hovered))];
+        assert!(!selector.matches(nodes.iter().copied()));
+    }
+
+    #[test]
+    fn restyle_on_inline_style_change() {
+        let mut world = World::new();
+        world.init_resource::<Assets<StyleSheet>>();
+        world.init_resource::<Events<AssetEvent<StyleSheet>>>();
+        let stylesheet = StyleSheet::default().with_rule(
+            ".a".parse().unwrap(),
+            StyleProperties {
+                width: Some(Val::Px(10.0)),
+                ..Default::default()
+            },
+        );
+        let stylesheet = world.resource_mut::<Assets<StyleSheet>>().add(stylesheet);
+        let node = world
+            .spawn((
+                Node::default(),
+                Style::default(),
+                Classes::new(["a"]),
+                UiStyleSheet(stylesheet),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_stylesheet_system);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(10.0));
+
+        // Editing the inline style restyles the node, keeping the rules on top of it
+        let mut inline = world.get_mut::<InlineStyle>(node).unwrap();
+        inline.style.width = Val::Px(5.0);
+        inline.style.height = Val::Px(20.0);
+        schedule.run(&mut world);
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Px(10.0));
+        assert_eq!(style.height, Val::Px(20.0));
+    }
+}
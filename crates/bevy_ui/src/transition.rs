@@ -0,0 +1,529 @@
+//! Animated transitions for UI node properties.
+//!
+//! A [`UiTransition`] animates properties of a node (see [`UiProperty`]) from their current value
+//! towards a target value over time, following an easing curve ([`UiEasing`]).
+//!
+//! [`UiEnterTransition`] plays a transition when a node is spawned, and [`UiExitTransition`] plays
+//! one before a node is despawned with [`UiTransitionCommandsExt::despawn_with_transition`].
+use crate::{BackgroundColor, BorderColor, Style, Val};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    prelude::{Added, Component},
+    query::Without,
+    system::{Commands, EntityCommands, Query, Res},
+    world::Ref,
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_math::{cubic_splines::CubicSegment, Quat, Vec2};
+use bevy_render::color::Color;
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+use std::time::Duration;
+
+/// An easing curve shaping the progress of a [`UiTween`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum UiEasing {
+    /// Progress at a constant rate.
+    #[default]
+    Linear,
+    /// Progress following a cubic Bezier easing curve, as in CSS's `cubic-bezier()`.
+    CubicBezier(CubicSegment<Vec2>),
+}
+
+impl UiEasing {
+    /// The CSS `ease` timing function.
+    pub fn ease() -> Self {
+        Self::cubic_bezier((0.25, 0.1), (0.25, 1.0))
+    }
+
+    /// The CSS `ease-in` timing function.
+    pub fn ease_in() -> Self {
+        Self::cubic_bezier((0.42, 0.0), (1.0, 1.0))
+    }
+
+    /// The CSS `ease-out` timing function.
+    pub fn ease_out() -> Self {
+        Self::cubic_bezier((0.0, 0.0), (0.58, 1.0))
+    }
+
+    /// The CSS `ease-in-out` timing function.
+    pub fn ease_in_out() -> Self {
+        Self::cubic_bezier((0.42, 0.0), (0.58, 1.0))
+    }
+
+    /// A cubic Bezier easing curve with the control points `p1` and `p2`, see [`CubicSegment::new_bezier`].
+    pub fn cubic_bezier(p1: impl Into<Vec2>, p2: impl Into<Vec2>) -> Self {
+        Self::CubicBezier(CubicSegment::new_bezier(p1, p2))
+    }
+
+    /// Returns the eased progress for the linear progress `t` in `0..=1`.
+    pub fn sample(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            UiEasing::Linear => t,
+            UiEasing::CubicBezier(curve) => curve.ease(t),
+        }
+    }
+}
+
+/// A property of a UI node that can be animated by a [`UiTransition`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UiProperty {
+    /// The node's [`BackgroundColor`].
+    BackgroundColor(Color),
+    /// The node's [`BorderColor`].
+    BorderColor(Color),
+    /// [`Style::width`].
+    Width(Val),
+    /// [`Style::height`].
+    Height(Val),
+    /// [`Style::left`].
+    Left(Val),
+    /// [`Style::right`].
+    Right(Val),
+    /// [`Style::top`].
+    Top(Val),
+    /// [`Style::bottom`].
+    Bottom(Val),
+    /// The scale of the node's [`Transform`].
+    Scale(Vec2),
+    /// The rotation of the node's [`Transform`] around the z axis, in radians.
+    Rotation(f32),
+}
+
+impl UiProperty {
+    /// Reads the current value of this property from a node, returning it as a [`UiProperty`]
+    /// of the same kind, or `None` if the node lacks the relevant component.
+    fn read(
+        &self,
+        style: Option<&Style>,
+        background_color: Option<&BackgroundColor>,
+        border_color: Option<&BorderColor>,
+        transform: &Transform,
+    ) -> Option<Self> {
+        Some(match self {
+            UiProperty::BackgroundColor(_) => UiProperty::BackgroundColor(background_color?.0),
+            UiProperty::BorderColor(_) => UiProperty::BorderColor(border_color?.0),
+            UiProperty::Width(_) => UiProperty::Width(style?.width),
+            UiProperty::Height(_) => UiProperty::Height(style?.height),
+            UiProperty::Left(_) => UiProperty::Left(style?.left),
+            UiProperty::Right(_) => UiProperty::Right(style?.right),
+            UiProperty::Top(_) => UiProperty::Top(style?.top),
+            UiProperty::Bottom(_) => UiProperty::Bottom(style?.bottom),
+            UiProperty::Scale(_) => UiProperty::Scale(transform.scale.truncate()),
+            UiProperty::Rotation(_) => {
+                UiProperty::Rotation(transform.rotation.to_euler(bevy_math::EulerRot::XYZ).2)
+            }
+        })
+    }
+
+    /// Writes this property's value to a node.
+    fn write(
+        &self,
+        style: Option<&mut Style>,
+        background_color: Option<&mut BackgroundColor>,
+        border_color: Option<&mut BorderColor>,
+        transform: &mut Transform,
+    ) {
+        match *self {
+            UiProperty::BackgroundColor(color) => {
+                if let Some(background_color) = background_color {
+                    background_color.0 = color;
+                }
+            }
+            UiProperty::BorderColor(color) => {
+                if let Some(border_color) = border_color {
+                    border_color.0 = color;
+                }
+            }
+            UiProperty::Scale(scale) => transform.scale = scale.extend(transform.scale.z),
+            UiProperty::Rotation(angle) => transform.rotation = Quat::from_rotation_z(angle),
+            _ => {
+                let Some(style) = style else {
+                    return;
+                };
+                match *self {
+                    UiProperty::Width(val) => style.width = val,
+                    UiProperty::Height(val) => style.height = val,
+                    UiProperty::Left(val) => style.left = val,
+                    UiProperty::Right(val) => style.right = val,
+                    UiProperty::Top(val) => style.top = val,
+                    UiProperty::Bottom(val) => style.bottom = val,
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    /// Interpolates between two values of the same property.
+    ///
+    /// [`Val`]s with different units can't be interpolated and jump to `end` once `t` reaches `1.`.
+    pub fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+        use UiProperty::*;
+        match (*start, *end) {
+            (BackgroundColor(a), BackgroundColor(b)) => BackgroundColor(lerp_color(a, b, t)),
+            (BorderColor(a), BorderColor(b)) => BorderColor(lerp_color(a, b, t)),
+            (Width(a), Width(b)) => Width(lerp_val(a, b, t)),
+            (Height(a), Height(b)) => Height(lerp_val(a, b, t)),
+            (Left(a), Left(b)) => Left(lerp_val(a, b, t)),
+            (Right(a), Right(b)) => Right(lerp_val(a, b, t)),
+            (Top(a), Top(b)) => Top(lerp_val(a, b, t)),
+            (Bottom(a), Bottom(b)) => Bottom(lerp_val(a, b, t)),
+            (Scale(a), Scale(b)) => Scale(a.lerp(b, t)),
+            (Rotation(a), Rotation(b)) => Rotation(a + (b - a) * t),
+            _ if t >= 1. => *end,
+            _ => *start,
+        }
+    }
+}
+
+fn lerp_color(start: Color, end: Color, t: f32) -> Color {
+    let start = start.as_linear_rgba_f32();
+    let end = end.as_linear_rgba_f32();
+    let result: [f32; 4] = std::array::from_fn(|i| start[i] + (end[i] - start[i]) * t);
+    Color::rgba_linear_from_array(result)
+}
+
+fn lerp_val(start: Val, end: Val, t: f32) -> Val {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    match (start, end) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(lerp(a, b)),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(lerp(a, b)),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(lerp(a, b)),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(lerp(a, b)),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(lerp(a, b)),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(lerp(a, b)),
+        _ if t >= 1. => end,
+        _ => start,
+    }
+}
+
+/// Animates a single [`UiProperty`] towards a target value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiTween {
+    /// The value the property is animated to.
+    pub target: UiProperty,
+    /// The value the property is animated from.
+    ///
+    /// If `None`, the property's value when the tween starts is used.
+    pub start: Option<UiProperty>,
+    /// How long the tween takes, once started.
+    pub duration: Duration,
+    /// How long to wait before starting the tween.
+    pub delay: Duration,
+    /// The easing curve applied to the tween's progress.
+    pub easing: UiEasing,
+    elapsed: Duration,
+}
+
+impl UiTween {
+    /// Creates a tween animating a property to `target` over `duration`.
+    pub fn new(target: UiProperty, duration: Duration, easing: UiEasing) -> Self {
+        Self {
+            target,
+            start: None,
+            duration,
+            delay: Duration::ZERO,
+            easing,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Sets the value the property is animated from.
+    #[must_use]
+    pub fn from(mut self, start: UiProperty) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Delays the start of the tween.
+    #[must_use]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns `true` once the tween has reached its target.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.delay + self.duration
+    }
+
+    /// The linear progress of the tween, in `0..=1`.
+    pub fn progress(&self) -> f32 {
+        if self.elapsed < self.delay {
+            return 0.;
+        }
+        if self.duration.is_zero() {
+            return 1.;
+        }
+        let elapsed = self.elapsed - self.delay;
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.)
+    }
+}
+
+/// The tweens currently animating the properties of a UI node.
+///
+/// Finished tweens are removed automatically by [`ui_transition_system`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_ui::{UiEasing, UiProperty, UiTransition};
+/// # use bevy_render::prelude::Color;
+/// # use std::time::Duration;
+/// fn highlight_hovered(mut buttons: Query<(&Interaction, &mut UiTransition), Changed<Interaction>>) {
+///     for (interaction, mut transition) in &mut buttons {
+///         let color = match interaction {
+///             Interaction::Hovered => Color::WHITE,
+///             _ => Color::GRAY,
+///         };
+///         transition.animate(
+///             UiProperty::BackgroundColor(color),
+///             Duration::from_millis(150),
+///             UiEasing::ease_out(),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default)]
+pub struct UiTransition {
+    tweens: Vec<UiTween>,
+}
+
+impl UiTransition {
+    /// Creates a transition playing the given tweens.
+    pub fn new(tweens: impl IntoIterator<Item = UiTween>) -> Self {
+        Self {
+            tweens: tweens.into_iter().collect(),
+        }
+    }
+
+    /// Adds a tween to the transition, replacing any tween animating the same property.
+    pub fn push(&mut self, tween: UiTween) -> &mut Self {
+        let kind = std::mem::discriminant(&tween.target);
+        self.tweens
+            .retain(|existing| std::mem::discriminant(&existing.target) != kind);
+        self.tweens.push(tween);
+        self
+    }
+
+    /// Animates a property from its current value to `target`, replacing any tween animating the same property.
+    pub fn animate(
+        &mut self,
+        target: UiProperty,
+        duration: Duration,
+        easing: UiEasing,
+    ) -> &mut Self {
+        self.push(UiTween::new(target, duration, easing))
+    }
+
+    /// Returns the tweens that are currently playing.
+    pub fn tweens(&self) -> &[UiTween] {
+        &self.tweens
+    }
+
+    /// Returns `true` if no tweens are playing.
+    pub fn is_finished(&self) -> bool {
+        self.tweens.is_empty()
+    }
+}
+
+/// Plays a transition when the node is spawned.
+///
+/// Each property is set to the `from` value when the node is added, then animated back
+/// to the value it was spawned with.
+#[derive(Component, Clone, Debug)]
+pub struct UiEnterTransition {
+    /// The values the animated properties start from.
+    pub from: Vec<UiProperty>,
+    /// How long the transition takes.
+    pub duration: Duration,
+    /// The easing curve of the transition.
+    pub easing: UiEasing,
+}
+
+/// Plays a transition before the node is despawned with [`UiTransitionCommandsExt::despawn_with_transition`].
+#[derive(Component, Clone, Debug)]
+pub struct UiExitTransition {
+    /// The values the animated properties end at.
+    pub to: Vec<UiProperty>,
+    /// How long the transition takes.
+    pub duration: Duration,
+    /// The easing curve of the transition.
+    pub easing: UiEasing,
+}
+
+/// Marks a node that is playing its [`UiExitTransition`], and will be despawned once it finishes.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct UiExiting;
+
+/// Extension trait for despawning UI nodes after their [`UiExitTransition`] has played.
+pub trait UiTransitionCommandsExt {
+    /// Despawns the node and its descendants once its [`UiExitTransition`] has finished.
+    ///
+    /// Nodes without a [`UiExitTransition`] are despawned on the next update of [`ui_transition_system`].
+    fn despawn_with_transition(&mut self);
+}
+
+impl<'w, 's, 'a> UiTransitionCommandsExt for EntityCommands<'w, 's, 'a> {
+    fn despawn_with_transition(&mut self) {
+        self.insert(UiExiting);
+    }
+}
+
+/// Starts enter and exit transitions, and advances all [`UiTransition`]s.
+pub fn ui_transition_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    entering: Query<(Entity, &UiEnterTransition), Added<UiEnterTransition>>,
+    exiting: Query<(Entity, Option<&UiExitTransition>), Added<UiExiting>>,
+    finished_exits: Query<(Entity, Ref<UiExiting>), Without<UiTransition>>,
+    mut nodes: Query<(
+        Entity,
+        Option<&mut UiTransition>,
+        Option<&mut Style>,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        &mut Transform,
+    )>,
+) {
+    // Nodes whose exit transition has finished playing. Newly exiting nodes are handled below.
+    for (entity, exiting) in &finished_exits {
+        if !exiting.is_added() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for (entity, enter) in &entering {
+        let Ok((_, transition, mut style, mut background_color, mut border_color, mut transform)) =
+            nodes.get_mut(entity)
+        else {
+            continue;
+        };
+        let mut tweens = Vec::with_capacity(enter.from.len());
+        for from in &enter.from {
+            let Some(target) = from.read(
+                style.as_deref(),
+                background_color.as_deref(),
+                border_color.as_deref(),
+                &transform,
+            ) else {
+                continue;
+            };
+            from.write(
+                style.as_deref_mut(),
+                background_color.as_deref_mut(),
+                border_color.as_deref_mut(),
+                &mut transform,
+            );
+            tweens.push(UiTween::new(target, enter.duration, enter.easing.clone()).from(*from));
+        }
+        match transition {
+            Some(mut transition) => {
+                for tween in tweens {
+                    transition.push(tween);
+                }
+            }
+            None => {
+                commands.entity(entity).insert(UiTransition::new(tweens));
+            }
+        }
+    }
+
+    for (entity, exit) in &exiting {
+        let Some(exit) = exit else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let tweens = exit
+            .to
+            .iter()
+            .map(|to| UiTween::new(*to, exit.duration, exit.easing.clone()));
+        match nodes.get_mut(entity) {
+            Ok((_, Some(mut transition), ..)) => {
+                for tween in tweens {
+                    transition.push(tween);
+                }
+            }
+            _ => {
+                commands.entity(entity).insert(UiTransition::new(tweens));
+            }
+        }
+    }
+
+    let delta = time.delta();
+    for (entity, transition, mut style, mut background_color, mut border_color, mut transform) in
+        &mut nodes
+    {
+        let Some(mut transition) = transition else {
+            continue;
+        };
+        for tween in &mut transition.tweens {
+            tween.elapsed += delta;
+            if tween.elapsed < tween.delay {
+                continue;
+            }
+            let start = *tween.start.get_or_insert_with(|| {
+                tween
+                    .target
+                    .read(
+                        style.as_deref(),
+                        background_color.as_deref(),
+                        border_color.as_deref(),
+                        &transform,
+                    )
+                    .unwrap_or(tween.target)
+            });
+            let value = UiProperty::interpolate(
+                &start,
+                &tween.target,
+                tween.easing.sample(tween.progress()),
+            );
+            value.write(
+                style.as_deref_mut(),
+                background_color.as_deref_mut(),
+                border_color.as_deref_mut(),
+                &mut transform,
+            );
+        }
+        transition.tweens.retain(|tween| !tween.is_finished());
+        if transition.tweens.is_empty() {
+            commands.entity(entity).remove::<UiTransition>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UiEasing, UiProperty};
+    use crate::Val;
+
+    #[test]
+    fn easing_starts_and_ends_at_bounds() {
+        for easing in [
+            UiEasing::Linear,
+            UiEasing::ease(),
+            UiEasing::ease_in(),
+            UiEasing::ease_out(),
+            UiEasing::ease_in_out(),
+        ] {
+            assert_eq!(easing.sample(0.), 0.);
+            assert_eq!(easing.sample(1.), 1.);
+        }
+    }
+
+    #[test]
+    fn interpolate_vals_with_matching_units() {
+        let start = UiProperty::Width(Val::Px(10.));
+        let end = UiProperty::Width(Val::Px(20.));
+        assert_eq!(
+            UiProperty::interpolate(&start, &end, 0.5),
+            UiProperty::Width(Val::Px(15.))
+        );
+
+        let end = UiProperty::Width(Val::Percent(50.));
+        assert_eq!(UiProperty::interpolate(&start, &end, 0.5), start);
+        assert_eq!(UiProperty::interpolate(&start, &end, 1.), end);
+    }
+}
@@ -16,6 +16,13 @@ use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, Shader
 /// from `bevy_ui::ui_vertex_output` and uses it as the input of your fragment shader like the
 /// example below does.
 ///
+/// `UiVertexOutput` contains the UV coordinates of the fragment within the node, the widths of the node's
+/// borders in UV space, and the size of the node in logical pixels. The size can be used to draw shapes,
+/// such as circles for progress rings, that keep their proportions whatever the aspect ratio of the node.
+///
+/// Nodes using the same material are drawn in a single batch, and are clipped by their
+/// [`CalculatedClip`](crate::CalculatedClip) like any other node.
+///
 /// # Example
 ///
 /// Here is a simple [`UiMaterial`] implementation. The [`AsBindGroup`] derive has many features. To see what else is available,
@@ -71,7 +78,7 @@ use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, Shader
 /// and the [`Globals Uniform`](bevy_render::globals::GlobalsUniform).
 ///
 /// ```wgsl
-/// #import bevy_ui::ui_vertex_output UiVertexOutput
+/// #import bevy_ui::ui_vertex_output::UiVertexOutput
 ///
 /// struct CustomMaterial {
 ///     color: vec4<f32>,
@@ -86,7 +93,7 @@ use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, Shader
 ///
 /// @fragment
 /// fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
-///
+///     return material.color * textureSample(color_texture, color_sampler, in.uv);
 /// }
 /// ```
 pub trait UiMaterial: AsBindGroup + Asset + Clone + Sized {
@@ -1,6 +1,6 @@
 use crate::{UiRect, Val};
 use bevy_asset::Handle;
-use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_ecs::{entity::Entity, prelude::Component, reflect::ReflectComponent};
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::prelude::*;
 use bevy_render::{color::Color, texture::Image};
@@ -32,6 +32,10 @@ pub struct Node {
     ///
     /// Automatically calculated by [`super::layout::ui_layout_system`].
     pub(crate) unrounded_size: Vec2,
+    /// The camera this node's UI tree is rendered to, as set by a [`TargetCamera`] on its root node.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) target_camera: Option<Entity>,
 }
 
 impl Node {
@@ -55,6 +59,13 @@ impl Node {
         self.unrounded_size
     }
 
+    /// The camera this node is rendered to, or `None` if its root node has no [`TargetCamera`].
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn target_camera(&self) -> Option<Entity> {
+        self.target_camera
+    }
+
     /// Returns the size of the node in physical pixels based on the given scale factor and `UiScale`.
     #[inline]
     pub fn physical_size(&self, scale_factor: f32, ui_scale: f32) -> Vec2 {
@@ -106,6 +117,7 @@ impl Node {
         outline_width: 0.,
         outline_offset: 0.,
         unrounded_size: Vec2::ZERO,
+        target_camera: None,
     };
 }
 
@@ -1606,6 +1618,170 @@ impl Outline {
     }
 }
 
+/// The [`BoxShadow`] component adds a drop shadow behind a UI node.
+/// Like outlines, shadows do not take up space in the layout.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_render::prelude::Color;
+/// fn setup_ui(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle {
+///             style: Style {
+///                 width: Val::Px(100.),
+///                 height: Val::Px(100.),
+///                 ..Default::default()
+///             },
+///             background_color: Color::WHITE.into(),
+///             ..Default::default()
+///         },
+///         BoxShadow {
+///             color: Color::BLACK.with_a(0.5),
+///             y_offset: Val::Px(4.),
+///             blur_radius: Val::Px(8.),
+///             ..Default::default()
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, PartialEq, Deserialize, Serialize, Reflect)]
+#[reflect(Component, Default, PartialEq, Deserialize, Serialize)]
+pub struct BoxShadow {
+    /// The color of the shadow.
+    pub color: Color,
+    /// Horizontal offset of the shadow from the node, positive values move it to the right.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub x_offset: Val,
+    /// Vertical offset of the shadow from the node, positive values move it down.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub y_offset: Val,
+    /// How much the shadow is grown on each side before it is blurred. Negative values shrink it.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub spread_radius: Val,
+    /// The distance over which the edges of the shadow fade out, on each side of the edge.
+    ///
+    /// Percentage `Val` values are resolved based on the width of the [`Node`].
+    pub blur_radius: Val,
+}
+
+impl BoxShadow {
+    pub const DEFAULT: Self = Self {
+        color: Color::BLACK,
+        x_offset: Val::ZERO,
+        y_offset: Val::ZERO,
+        spread_radius: Val::ZERO,
+        blur_radius: Val::ZERO,
+    };
+
+    /// Creates a shadow of the given color, offset and blur radius.
+    pub const fn new(color: Color, x_offset: Val, y_offset: Val, blur_radius: Val) -> Self {
+        Self {
+            color,
+            x_offset,
+            y_offset,
+            spread_radius: Val::ZERO,
+            blur_radius,
+        }
+    }
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Fills a UI node with a gradient between two colors, instead of its [`BackgroundColor`].
+///
+/// The gradient is not drawn for nodes with a [`UiImage`] or a [`UiTextureAtlasImage`].
+#[derive(Component, Copy, Clone, Debug, PartialEq, Deserialize, Serialize, Reflect)]
+#[reflect(Component, Default, PartialEq, Deserialize, Serialize)]
+pub enum BackgroundGradient {
+    /// A gradient along a straight line through the center of the node.
+    Linear {
+        /// The direction of the gradient in radians, clockwise from the top of the node.
+        ///
+        /// `0.` goes from the bottom to the top of the node, and `FRAC_PI_2` from left to right.
+        angle: f32,
+        /// The color at the start of the gradient.
+        start: Color,
+        /// The color at the end of the gradient.
+        end: Color,
+    },
+    /// A circular gradient, from a center point to the corner of the node furthest from it.
+    Radial {
+        /// The center of the gradient, with `(0., 0.)` the top-left corner of the node
+        /// and `(1., 1.)` its bottom-right corner.
+        center: Vec2,
+        /// The color at the center of the gradient.
+        start: Color,
+        /// The color at the edge of the gradient.
+        end: Color,
+    },
+}
+
+impl BackgroundGradient {
+    pub const DEFAULT: Self = Self::Linear {
+        angle: 0.,
+        start: Color::WHITE,
+        end: Color::WHITE,
+    };
+
+    /// Creates a linear gradient going from `start` to `end` along `angle`.
+    pub const fn linear(angle: f32, start: Color, end: Color) -> Self {
+        Self::Linear { angle, start, end }
+    }
+
+    /// Creates a radial gradient centered on the node, going from `start` to `end`.
+    pub const fn radial(start: Color, end: Color) -> Self {
+        Self::Radial {
+            center: Vec2::splat(0.5),
+            start,
+            end,
+        }
+    }
+}
+
+impl Default for BackgroundGradient {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Controls how the layout of a UI node is rounded to whole pixels.
+///
+/// The rounding applies to the node and all of its descendants, up to descendants with
+/// their own [`LayoutRounding`]. Add it to a root node to change the rounding of a whole UI tree.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, PartialEq, Serialize, Deserialize)]
+pub enum LayoutRounding {
+    /// Rounds the edges of nodes to the nearest logical pixel.
+    ///
+    /// At fractional scale factors, logical pixels don't line up with physical pixels,
+    /// which can make borders and gaps between adjacent nodes look blurry or uneven.
+    Logical,
+    /// Rounds the edges of nodes to the nearest physical pixel, keeping edges crisp at any scale factor.
+    Snap,
+    /// Rounds the edges of nodes down to the physical pixel before them.
+    Floor,
+    /// Doesn't round the edges of nodes, for smooth movement in animated layouts.
+    None,
+}
+
+impl LayoutRounding {
+    pub const DEFAULT: Self = Self::Logical;
+}
+
+impl Default for LayoutRounding {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// The 2D texture displayed for this UI node
 #[derive(Component, Clone, Debug, Reflect, Default)]
 #[reflect(Component, Default)]
@@ -1684,6 +1860,35 @@ impl Default for ZIndex {
     }
 }
 
+/// Indicates that this root [`Node`] entity should be rendered to a specific camera.
+///
+/// UI then will be laid out respecting the camera's viewport and scale factor, and
+/// rendered to this camera's [`bevy_render::camera::RenderTarget`].
+///
+/// Setting this component on a non-root node will have no effect. It will be overridden
+/// by the root node's component.
+///
+/// Root nodes without this component are laid out against the primary window and rendered
+/// to every camera that isn't the target of a [`TargetCamera`].
+///
+/// Combined with a camera rendering to an [`Image`], this can be used to draw UI into a
+/// texture and display it in the world, see [`WorldSpaceUi`](crate::WorldSpaceUi).
+///
+/// Several UI trees can target cameras rendering to different windows, or to different
+/// viewports of the same window for split-screen games. Each camera can override the
+/// [`UiScale`](crate::UiScale) of its UI with a [`CameraUiScale`](crate::camera_config::CameraUiScale),
+/// and nodes only receive input while the cursor is over their camera's viewport.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct TargetCamera(pub Entity);
+
+impl TargetCamera {
+    /// The camera entity the UI tree is rendered to.
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::GridPlacement;
@@ -5,9 +5,11 @@ mod image;
 mod label;
 #[cfg(feature = "bevy_text")]
 mod text;
+mod virtual_list;
 
 pub use button::*;
 pub use image::*;
 pub use label::*;
 #[cfg(feature = "bevy_text")]
 pub use text::*;
+pub use virtual_list::*;
@@ -0,0 +1,373 @@
+use crate::{
+    node_bundles::NodeBundle, Display, Node, PositionType, RelativeCursorPosition, Style, Val,
+};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    entity::{Entities, Entity},
+    event::EventReader,
+    prelude::{Changed, Component, Or},
+    system::{Commands, EntityCommands, Query},
+    world::World,
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_input::mouse::{MouseScrollUnit, MouseWheel};
+use std::{fmt, ops::Range, sync::Arc};
+
+type ItemFactory = Arc<dyn Fn(&mut EntityCommands, usize) + Send + Sync>;
+
+/// A scrollable list that only spawns entities for the items that are visible.
+///
+/// The list node should clip its content with [`Overflow::clip`](crate::Overflow::clip) and have a bounded height.
+/// Items are spawned as children of the list by the item factory as they scroll into view, and are recycled
+/// for other items once they scroll out of view, so lists of thousands of rows only ever need a few dozen entities.
+///
+/// Each item is a [`VirtualListItem`] node, absolutely positioned by [`update_virtual_list_system`].
+/// All items have the same height, and span the full width of the list.
+///
+/// Add a [`RelativeCursorPosition`] to the list node to scroll it with the mouse wheel.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::color::Color;
+/// # use bevy_ui::{prelude::*, widget::VirtualList, RelativeCursorPosition};
+/// fn setup_list(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle {
+///             style: Style {
+///                 height: Val::Px(400.),
+///                 overflow: Overflow::clip(),
+///                 ..Default::default()
+///             },
+///             ..Default::default()
+///         },
+///         RelativeCursorPosition::default(),
+///         VirtualList::new(10_000, 24., |item, index| {
+///             item.insert(BackgroundColor(if index % 2 == 0 {
+///                 Color::DARK_GRAY
+///             } else {
+///                 Color::GRAY
+///             }));
+///         }),
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct VirtualList {
+    item_count: usize,
+    item_height: f32,
+    scroll_offset: f32,
+    /// How many items are kept spawned above and below the visible ones, to avoid
+    /// spawning items on every frame while scrolling.
+    pub overscan: usize,
+    factory: ItemFactory,
+    /// The spawned items and the index they display.
+    items: Vec<(usize, Entity)>,
+    /// Spawned items that are hidden until they are reused.
+    pool: Vec<Entity>,
+    needs_refresh: bool,
+}
+
+impl VirtualList {
+    /// Creates a list of `item_count` items of height `item_height` in logical pixels.
+    ///
+    /// `factory` is called with the commands of an item entity and the index of the item it should display,
+    /// both when an item is spawned and when it is recycled to display another index. Children spawned by
+    /// a previous call for a recycled item are despawned before calling it.
+    pub fn new(
+        item_count: usize,
+        item_height: f32,
+        factory: impl Fn(&mut EntityCommands, usize) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            item_count,
+            item_height,
+            scroll_offset: 0.,
+            overscan: 2,
+            factory: Arc::new(factory),
+            items: Vec::new(),
+            pool: Vec::new(),
+            needs_refresh: false,
+        }
+    }
+
+    /// The number of items in the list.
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    /// Changes the number of items in the list, and rebuilds all visible items.
+    pub fn set_item_count(&mut self, item_count: usize) {
+        self.item_count = item_count;
+        self.refresh();
+    }
+
+    /// The height of each item in logical pixels.
+    pub fn item_height(&self) -> f32 {
+        self.item_height
+    }
+
+    /// The distance in logical pixels between the top of the first item and the top of the list.
+    pub fn scroll_offset(&self) -> f32 {
+        self.scroll_offset
+    }
+
+    /// Scrolls the list so `offset` logical pixels are hidden above its top.
+    ///
+    /// The offset is clamped so the list can't be scrolled past its last item when it is next updated.
+    pub fn set_scroll_offset(&mut self, offset: f32) {
+        self.scroll_offset = offset.max(0.);
+    }
+
+    /// Scrolls the list so the item at `index` is at its top.
+    pub fn scroll_to_item(&mut self, index: usize) {
+        self.set_scroll_offset(index as f32 * self.item_height);
+    }
+
+    /// Calls the item factory again for all visible items, e.g. after the data they display has changed.
+    pub fn refresh(&mut self) {
+        self.needs_refresh = true;
+    }
+
+    /// Returns the entity displaying the item at `index`, if it is spawned.
+    pub fn item_entity(&self, index: usize) -> Option<Entity> {
+        self.items
+            .iter()
+            .find(|(item_index, _)| *item_index == index)
+            .map(|(_, entity)| *entity)
+    }
+
+    /// The highest scroll offset for a list of the given height.
+    pub fn max_scroll_offset(&self, viewport_height: f32) -> f32 {
+        (self.item_count as f32 * self.item_height - viewport_height).max(0.)
+    }
+
+    /// The indices of the items that should be spawned for a list of the given height,
+    /// including the [`overscan`](Self::overscan).
+    pub fn visible_range(&self, viewport_height: f32) -> Range<usize> {
+        if self.item_height <= 0. {
+            return 0..0;
+        }
+        let first = (self.scroll_offset / self.item_height).floor() as usize;
+        let last = ((self.scroll_offset + viewport_height) / self.item_height).ceil() as usize;
+        first.saturating_sub(self.overscan)..(last + self.overscan).min(self.item_count)
+    }
+}
+
+impl fmt::Debug for VirtualList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VirtualList")
+            .field("item_count", &self.item_count)
+            .field("item_height", &self.item_height)
+            .field("scroll_offset", &self.scroll_offset)
+            .field("overscan", &self.overscan)
+            .field("items", &self.items)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An item spawned by a [`VirtualList`].
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VirtualListItem {
+    /// The index of the item displayed by this entity.
+    pub index: usize,
+}
+
+/// Spawns, recycles and positions the items of [`VirtualList`]s when they are scrolled or resized.
+pub fn update_virtual_list_system(
+    mut commands: Commands,
+    entities: &Entities,
+    mut lists: Query<(Entity, &mut VirtualList, &Node), Or<(Changed<VirtualList>, Changed<Node>)>>,
+) {
+    for (list_entity, mut list, node) in &mut lists {
+        // Internal bookkeeping shouldn't cause the list to be updated again next frame.
+        let list = list.bypass_change_detection();
+        let viewport_height = node.size().y;
+        list.scroll_offset = list
+            .scroll_offset
+            .min(list.max_scroll_offset(viewport_height));
+        let visible = list.visible_range(viewport_height);
+
+        let needs_refresh = std::mem::take(&mut list.needs_refresh);
+        // Items may have been despawned by something else, in which case they are forgotten, and
+        // spawned again if they are visible.
+        list.pool.retain(|entity| entities.contains(*entity));
+        let pool = &mut list.pool;
+        list.items.retain(|&(index, entity)| {
+            if !entities.contains(entity) {
+                return false;
+            }
+            let keep = !needs_refresh && visible.contains(&index);
+            if !keep {
+                pool.push(entity);
+            }
+            keep
+        });
+
+        for index in visible.clone() {
+            if list
+                .items
+                .iter()
+                .any(|(item_index, _)| *item_index == index)
+            {
+                continue;
+            }
+            let mut item = match list.pool.pop() {
+                Some(entity) => {
+                    let mut item = commands.entity(entity);
+                    item.despawn_descendants();
+                    item
+                }
+                None => {
+                    let mut item = commands.spawn(NodeBundle::default());
+                    item.set_parent(list_entity);
+                    item
+                }
+            };
+            item.insert(VirtualListItem { index });
+            (list.factory)(&mut item, index);
+            list.items.push((index, item.id()));
+        }
+
+        let item_height = list.item_height;
+        let scroll_offset = list.scroll_offset;
+        let positions = list
+            .items
+            .iter()
+            .map(|&(index, entity)| (entity, Some(index as f32 * item_height - scroll_offset)))
+            .chain(list.pool.iter().map(|&entity| (entity, None)))
+            .collect::<Vec<_>>();
+        // Applied after the factory's commands, so it can't override the position of the items.
+        commands.add(move |world: &mut World| {
+            for (entity, top) in positions {
+                let Some(mut style) = world.get_mut::<Style>(entity) else {
+                    continue;
+                };
+                match top {
+                    Some(top) => {
+                        style.display = Display::Flex;
+                        style.position_type = PositionType::Absolute;
+                        style.left = Val::ZERO;
+                        style.right = Val::ZERO;
+                        style.top = Val::Px(top);
+                        style.height = Val::Px(item_height);
+                    }
+                    // Pooled items are hidden until they are reused.
+                    None => style.display = Display::None,
+                }
+            }
+        });
+    }
+}
+
+/// Scrolls the hovered [`VirtualList`]s with the mouse wheel.
+///
+/// Only lists with a [`RelativeCursorPosition`] can be scrolled.
+pub fn virtual_list_scroll_system(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut lists: Query<(&mut VirtualList, &Node, &RelativeCursorPosition)>,
+) {
+    let events = mouse_wheel_events.read().collect::<Vec<_>>();
+    if events.is_empty() {
+        return;
+    }
+    for (mut list, node, cursor_position) in &mut lists {
+        if !cursor_position.mouse_over() {
+            continue;
+        }
+        let delta: f32 = events
+            .iter()
+            .map(|event| match event.unit {
+                MouseScrollUnit::Line => event.y * list.item_height,
+                MouseScrollUnit::Pixel => event.y,
+            })
+            .sum();
+        let offset = (list.scroll_offset - delta).clamp(0., list.max_scroll_offset(node.size().y));
+        if offset != list.scroll_offset {
+            list.set_scroll_offset(offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_virtual_list_system, VirtualList, VirtualListItem};
+    use crate::Node;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn visible_range_includes_overscan() {
+        let mut list = VirtualList::new(100, 10., |_, _| {});
+        list.overscan = 2;
+        assert_eq!(list.visible_range(35.), 0..6);
+
+        list.set_scroll_offset(105.);
+        assert_eq!(list.visible_range(35.), 8..16);
+
+        // the range never goes past the last item
+        list.set_scroll_offset(list.max_scroll_offset(35.));
+        assert_eq!(list.max_scroll_offset(35.), 965.);
+        assert_eq!(list.visible_range(35.), 94..100);
+    }
+
+    #[test]
+    fn despawned_items_are_not_reused() {
+        let mut world = World::new();
+        let mut list = VirtualList::new(4, 10., |_, _| {});
+        list.overscan = 0;
+        let list_entity = world
+            .spawn((
+                list,
+                Node {
+                    calculated_size: Vec2::new(100., 35.),
+                    ..Node::DEFAULT
+                },
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_virtual_list_system);
+        let item = |world: &World, index| {
+            world
+                .get::<VirtualList>(list_entity)
+                .unwrap()
+                .item_entity(index)
+        };
+
+        schedule.run(&mut world);
+        let first_items: Vec<_> = (0..4).map(|index| item(&world, index).unwrap()).collect();
+
+        // Two items are pooled when the list shrinks
+        world
+            .get_mut::<VirtualList>(list_entity)
+            .unwrap()
+            .set_item_count(2);
+        schedule.run(&mut world);
+        let visible = [item(&world, 0).unwrap(), item(&world, 1).unwrap()];
+        let pooled: Vec<_> = first_items
+            .iter()
+            .copied()
+            .filter(|entity| !visible.contains(entity))
+            .collect();
+        assert_eq!(pooled.len(), 2);
+
+        // Something else despawns a pooled item and a visible one
+        world.despawn(pooled[0]);
+        world.despawn(visible[0]);
+
+        // Only the items that still exist are reused
+        world
+            .get_mut::<VirtualList>(list_entity)
+            .unwrap()
+            .set_item_count(4);
+        schedule.run(&mut world);
+        for index in 0..4 {
+            let entity = item(&world, index).unwrap();
+            assert_ne!(entity, pooled[0]);
+            assert_ne!(entity, visible[0]);
+            assert_eq!(
+                world.get::<VirtualListItem>(entity),
+                Some(&VirtualListItem { index })
+            );
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Displaying UI trees in world space.
+//!
+//! A UI tree is drawn into the world in two steps:
+//! 1. Its root node gets a [`TargetCamera`](crate::TargetCamera) pointing at a camera that renders to an
+//!    [`Image`](bevy_render::texture::Image), which lays the tree out against that image's size.
+//! 2. A mesh in the world (usually a quad) displays that image, and gets a [`WorldSpaceUi`] component
+//!    so pointer input hitting the mesh is mapped back onto the UI tree.
+use bevy_ecs::{
+    entity::Entity,
+    prelude::{Component, With, Without},
+    reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_math::{primitives::Plane3d, Ray3d, Vec2, Vec3};
+use bevy_reflect::Reflect;
+use bevy_render::camera::Camera;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// A surface in the world that displays the UI rendered by a camera, and forwards pointer
+/// input to that UI.
+///
+/// The surface lies on the local XY plane of the entity, centered on its origin and facing
+/// the entity's local +Z axis, which matches the orientation of a `Quad` or `Rectangle` mesh.
+///
+/// The UI camera should render to an image that is used as the texture of the surface's material,
+/// and have a lower [`Camera::order`] than the cameras looking at the surface so the UI is drawn first.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct WorldSpaceUi {
+    /// The camera rendering the UI displayed on this surface.
+    ///
+    /// UI root nodes rendered to it should have a matching [`TargetCamera`](crate::TargetCamera).
+    pub ui_camera: Entity,
+    /// The size of the surface in the entity's local space, before any scaling is applied.
+    pub size: Vec2,
+    /// If set, the surface is rotated each frame to face this camera.
+    pub billboard: Option<Entity>,
+}
+
+impl WorldSpaceUi {
+    /// Creates a new surface of the given `size` displaying the UI rendered by `ui_camera`.
+    pub fn new(ui_camera: Entity, size: Vec2) -> Self {
+        Self {
+            ui_camera,
+            size,
+            billboard: None,
+        }
+    }
+
+    /// Makes the surface always face the given camera.
+    #[must_use]
+    pub const fn with_billboard(mut self, camera: Entity) -> Self {
+        self.billboard = Some(camera);
+        self
+    }
+
+    /// Maps a world-space `ray` onto the UI viewport of this surface.
+    ///
+    /// Returns the position where the ray hits the surface in the logical coordinates of a UI viewport
+    /// of size `ui_viewport_size`, with `(0., 0.)` in the top-left corner, or `None` if the ray misses it.
+    pub fn ray_to_viewport(
+        &self,
+        surface_transform: &GlobalTransform,
+        ray: Ray3d,
+        ui_viewport_size: Vec2,
+    ) -> Option<Vec2> {
+        let distance = ray.intersect_plane(
+            surface_transform.translation(),
+            Plane3d::new(surface_transform.back()),
+        )?;
+        let local_hit = surface_transform
+            .affine()
+            .inverse()
+            .transform_point3(ray.get_point(distance));
+        let normalized = Vec2::new(
+            local_hit.x / self.size.x + 0.5,
+            0.5 - local_hit.y / self.size.y,
+        );
+        if normalized.cmplt(Vec2::ZERO).any() || normalized.cmpgt(Vec2::ONE).any() {
+            return None;
+        }
+        Some(normalized * ui_viewport_size)
+    }
+}
+
+/// Rotates [`WorldSpaceUi`] surfaces with a billboard camera to face it.
+///
+/// The rotation is computed from the surface's [`Transform`], so billboarded surfaces
+/// should not have rotated parents.
+pub fn world_space_ui_billboard_system(
+    mut surfaces: Query<(&WorldSpaceUi, &mut Transform)>,
+    cameras: Query<&GlobalTransform, (With<Camera>, Without<WorldSpaceUi>)>,
+) {
+    for (surface, mut transform) in &mut surfaces {
+        let Some(camera_transform) = surface
+            .billboard
+            .and_then(|camera| cameras.get(camera).ok())
+        else {
+            continue;
+        };
+        let direction = transform.translation - camera_transform.translation();
+        if direction.length_squared() <= f32::EPSILON {
+            continue;
+        }
+        let rotation = Transform::IDENTITY.looking_to(direction, Vec3::Y).rotation;
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorldSpaceUi;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Ray3d, Vec2, Vec3};
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn ray_to_viewport_maps_hits_to_ui_coordinates() {
+        let surface = WorldSpaceUi::new(Entity::PLACEHOLDER, Vec2::new(2., 1.));
+        let transform = GlobalTransform::from_xyz(0., 0., -5.);
+        let viewport = Vec2::new(200., 100.);
+
+        // the center of the surface maps to the center of the viewport
+        let ray = Ray3d::new(Vec3::ZERO, Vec3::NEG_Z);
+        assert_eq!(
+            surface.ray_to_viewport(&transform, ray, viewport),
+            Some(Vec2::new(100., 50.))
+        );
+
+        // the top-left corner of the surface maps to the origin of the viewport
+        let ray = Ray3d::new(Vec3::new(-1., 0.5, 0.), Vec3::NEG_Z);
+        assert_eq!(
+            surface.ray_to_viewport(&transform, ray, viewport),
+            Some(Vec2::ZERO)
+        );
+
+        // rays missing the surface, or pointing away from it, don't hit
+        let ray = Ray3d::new(Vec3::new(1.5, 0., 0.), Vec3::NEG_Z);
+        assert_eq!(surface.ray_to_viewport(&transform, ray, viewport), None);
+        let ray = Ray3d::new(Vec3::ZERO, Vec3::Z);
+        assert_eq!(surface.ray_to_viewport(&transform, ray, viewport), None);
+    }
+}
@@ -23,6 +23,7 @@ mod cow_arc;
 mod default;
 mod float_ord;
 pub mod intern;
+mod parallel_queue;
 
 pub use crate::uuid::Uuid;
 pub use ahash::{AHasher, RandomState};
@@ -32,6 +33,7 @@ pub use default::default;
 pub use float_ord::*;
 pub use hashbrown;
 pub use instant::{Duration, Instant, SystemTime};
+pub use parallel_queue::*;
 pub use petgraph;
 pub use thiserror;
 pub use tracing;
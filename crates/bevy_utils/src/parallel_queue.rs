@@ -0,0 +1,89 @@
+use core::cell::Cell;
+use thread_local::ThreadLocal;
+
+/// A cohesive set of thread-local values of a given type.
+///
+/// Mutable references can be fetched if `T: Default` via [`Parallel::scope`], and the values of
+/// all of the threads can be consumed afterwards through a mutable reference to the whole set.
+/// Used as a `Local` of a system, it provides scratch storage which can be written from the
+/// closures of a parallel query iteration without locking.
+///
+/// ```
+/// # use bevy_utils::Parallel;
+/// # use std::thread;
+/// let queue = Parallel::<Vec<u32>>::default();
+/// thread::scope(|scope| {
+///     for i in 0..4 {
+///         let queue = &queue;
+///         scope.spawn(move || queue.scope(|values| values.push(i)));
+///     }
+/// });
+///
+/// let mut queue = queue;
+/// let mut values: Vec<_> = queue.drain().collect();
+/// values.sort();
+/// assert_eq!(values, [0, 1, 2, 3]);
+/// ```
+#[derive(Default)]
+pub struct Parallel<T: Send> {
+    locals: ThreadLocal<Cell<T>>,
+}
+
+impl<T: Send> Parallel<T> {
+    /// Gets a mutable iterator over all of the per-thread values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &'_ mut T> {
+        self.locals.iter_mut().map(Cell::get_mut)
+    }
+
+    /// Clears all of the stored thread local values.
+    pub fn clear(&mut self) {
+        self.locals.clear();
+    }
+}
+
+impl<T: Default + Send> Parallel<T> {
+    /// Retrieves the thread-local value for the current thread and runs `f` on it.
+    ///
+    /// If there is no thread-local value, it will be initialized to its default.
+    pub fn scope<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let cell = self.locals.get_or_default();
+        let mut value = cell.take();
+        let ret = f(&mut value);
+        cell.set(value);
+        ret
+    }
+}
+
+impl<T, I> Parallel<I>
+where
+    I: IntoIterator<Item = T> + Default + Send + 'static,
+{
+    /// Drains all enqueued items from all threads and returns an iterator over them.
+    ///
+    /// Unlike [`Vec::drain`], this will piecemeal remove chunks of the data stored.
+    /// If iteration is terminated part way, the rest of the enqueued items in the same
+    /// chunk will be dropped, and the rest of the undrained elements will remain.
+    ///
+    /// The ordering is not guaranteed.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.locals.iter_mut().flat_map(|item| item.take())
+    }
+}
+
+impl<T: Send> Parallel<Vec<T>> {
+    /// Collect all enqueued items from all threads and appends them to the end of a
+    /// single Vec.
+    ///
+    /// The ordering is not guaranteed.
+    pub fn drain_into(&mut self, out: &mut Vec<T>) {
+        let size = self
+            .locals
+            .iter_mut()
+            .map(|queue| queue.get_mut().len())
+            .sum();
+        out.reserve(size);
+        for queue in self.locals.iter_mut() {
+            out.append(queue.get_mut());
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::path::PathBuf;
+
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{CursorMoved, FileDragAndDrop, Window};
+
+/// An event sent while files are dragged over a window, built from the [`FileDragAndDrop`]
+/// events of the window backend.
+///
+/// Unlike [`FileDragAndDrop`], all the files of a single drag are grouped in one event, and the
+/// position of the drag within the window is tracked so drop targets can be highlighted.
+///
+/// Positions are in logical pixels. Some platforms don't report cursor movement while files are
+/// being dragged, in which case the position is the last known cursor position in the window.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum FileDragEvent {
+    /// Files started being dragged over a window.
+    Entered {
+        /// Window the files are dragged over.
+        window: Entity,
+        /// Paths of the dragged files.
+        paths: Vec<PathBuf>,
+        /// Position of the drag in the window, if known.
+        position: Option<Vec2>,
+    },
+    /// Files dragged over a window moved.
+    Moved {
+        /// Window the files are dragged over.
+        window: Entity,
+        /// New position of the drag in the window.
+        position: Vec2,
+    },
+    /// Files stopped being dragged over a window without being dropped.
+    Left {
+        /// Window the files were dragged over.
+        window: Entity,
+    },
+    /// Files were dropped into a window.
+    Dropped {
+        /// Window the files were dropped into.
+        window: Entity,
+        /// Paths of the dropped files.
+        paths: Vec<PathBuf>,
+        /// Position of the drop in the window, if known.
+        position: Option<Vec2>,
+    },
+}
+
+/// Files being dragged over a window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraggedFiles {
+    /// Paths of the dragged files.
+    pub paths: Vec<PathBuf>,
+    /// Position of the drag in the window in logical pixels, if known.
+    pub position: Option<Vec2>,
+}
+
+/// Keeps track of the files currently dragged over each window.
+#[derive(Resource, Debug, Default)]
+pub struct FileDragState {
+    dragged: HashMap<Entity, DraggedFiles>,
+}
+
+impl FileDragState {
+    /// Returns the files dragged over `window`, if any.
+    pub fn get(&self, window: Entity) -> Option<&DraggedFiles> {
+        self.dragged.get(&window)
+    }
+
+    /// Returns `true` if files are being dragged over any window.
+    pub fn is_dragging(&self) -> bool {
+        !self.dragged.is_empty()
+    }
+}
+
+/// Groups [`FileDragAndDrop`] events into [`FileDragEvent`]s and updates the [`FileDragState`].
+///
+/// This system is added by the [`WindowPlugin`](crate::WindowPlugin).
+pub fn file_drag_system(
+    mut file_drag_and_drop: EventReader<FileDragAndDrop>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut file_drag: EventWriter<FileDragEvent>,
+    mut state: ResMut<FileDragState>,
+    windows: Query<&Window>,
+) {
+    // Backends send one event per file, so consecutive events of the same kind for the same
+    // window belong to the same drag.
+    let mut batches: Vec<(FileDragAndDrop, Vec<PathBuf>)> = Vec::new();
+    for event in file_drag_and_drop.read() {
+        let (window, path_buf) = match event {
+            FileDragAndDrop::HoveredFile { window, path_buf }
+            | FileDragAndDrop::DroppedFile { window, path_buf } => (window, path_buf),
+            FileDragAndDrop::HoveredFileCanceled { .. } => {
+                batches.push((event.clone(), Vec::new()));
+                continue;
+            }
+        };
+        match batches.last_mut() {
+            Some((last, paths))
+                if std::mem::discriminant(last) == std::mem::discriminant(event)
+                    && batch_window(last) == *window =>
+            {
+                paths.push(path_buf.clone());
+            }
+            _ => batches.push((event.clone(), vec![path_buf.clone()])),
+        }
+    }
+
+    for (event, paths) in batches {
+        let window = batch_window(&event);
+        match event {
+            FileDragAndDrop::HoveredFile { .. } => {
+                let position = windows.get(window).ok().and_then(Window::cursor_position);
+                state.dragged.insert(
+                    window,
+                    DraggedFiles {
+                        paths: paths.clone(),
+                        position,
+                    },
+                );
+                file_drag.send(FileDragEvent::Entered {
+                    window,
+                    paths,
+                    position,
+                });
+            }
+            FileDragAndDrop::DroppedFile { .. } => {
+                let position = state
+                    .dragged
+                    .remove(&window)
+                    .and_then(|dragged| dragged.position)
+                    .or_else(|| windows.get(window).ok().and_then(Window::cursor_position));
+                file_drag.send(FileDragEvent::Dropped {
+                    window,
+                    paths,
+                    position,
+                });
+            }
+            FileDragAndDrop::HoveredFileCanceled { .. } => {
+                if state.dragged.remove(&window).is_some() {
+                    file_drag.send(FileDragEvent::Left { window });
+                }
+            }
+        }
+    }
+
+    for event in cursor_moved.read() {
+        if let Some(dragged) = state.dragged.get_mut(&event.window) {
+            if dragged.position != Some(event.position) {
+                dragged.position = Some(event.position);
+                file_drag.send(FileDragEvent::Moved {
+                    window: event.window,
+                    position: event.position,
+                });
+            }
+        }
+    }
+}
+
+fn batch_window(event: &FileDragAndDrop) -> Entity {
+    match event {
+        FileDragAndDrop::HoveredFile { window, .. }
+        | FileDragAndDrop::DroppedFile { window, .. }
+        | FileDragAndDrop::HoveredFileCanceled { window } => *window,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_event::<FileDragAndDrop>()
+            .add_event::<CursorMoved>()
+            .add_event::<FileDragEvent>()
+            .init_resource::<FileDragState>()
+            .add_systems(Update, file_drag_system);
+        app
+    }
+
+    fn drag_events(app: &mut App) -> Vec<FileDragEvent> {
+        app.world
+            .resource_mut::<Events<FileDragEvent>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn files_are_batched() {
+        let mut app = setup();
+        let window = app.world.spawn(Window::default()).id();
+        let paths = vec![PathBuf::from("a.png"), PathBuf::from("b.png")];
+        for path_buf in &paths {
+            app.world.send_event(FileDragAndDrop::HoveredFile {
+                window,
+                path_buf: path_buf.clone(),
+            });
+        }
+        app.update();
+        assert_eq!(
+            drag_events(&mut app),
+            vec![FileDragEvent::Entered {
+                window,
+                paths: paths.clone(),
+                position: None,
+            }]
+        );
+        assert!(app.world.resource::<FileDragState>().is_dragging());
+
+        for path_buf in &paths {
+            app.world.send_event(FileDragAndDrop::DroppedFile {
+                window,
+                path_buf: path_buf.clone(),
+            });
+        }
+        app.update();
+        assert_eq!(
+            drag_events(&mut app),
+            vec![FileDragEvent::Dropped {
+                window,
+                paths,
+                position: None,
+            }]
+        );
+        assert!(!app.world.resource::<FileDragState>().is_dragging());
+    }
+
+    #[test]
+    fn drag_moves_and_leaves() {
+        let mut app = setup();
+        let window = app.world.spawn(Window::default()).id();
+        app.world.send_event(FileDragAndDrop::HoveredFile {
+            window,
+            path_buf: PathBuf::from("a.png"),
+        });
+        app.update();
+        drag_events(&mut app);
+
+        let position = Vec2::new(10., 20.);
+        app.world.send_event(CursorMoved { window, position });
+        app.update();
+        assert_eq!(
+            drag_events(&mut app),
+            vec![FileDragEvent::Moved { window, position }]
+        );
+        assert_eq!(
+            app.world
+                .resource::<FileDragState>()
+                .get(window)
+                .unwrap()
+                .position,
+            Some(position)
+        );
+
+        app.world
+            .send_event(FileDragAndDrop::HoveredFileCanceled { window });
+        app.update();
+        assert_eq!(drag_events(&mut app), vec![FileDragEvent::Left { window }]);
+        assert!(app.world.resource::<FileDragState>().get(window).is_none());
+    }
+}
@@ -10,6 +10,8 @@ use bevy_a11y::Focus;
 
 mod cursor;
 mod event;
+mod file_drag;
+mod monitor;
 mod raw_handle;
 mod system;
 mod window;
@@ -18,6 +20,8 @@ pub use crate::raw_handle::*;
 
 pub use cursor::*;
 pub use event::*;
+pub use file_drag::*;
+pub use monitor::*;
 pub use system::*;
 pub use window::*;
 
@@ -25,8 +29,8 @@ pub use window::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
-        ReceivedCharacter, Window, WindowMoved, WindowPlugin, WindowPosition,
+        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, FileDragEvent, Ime,
+        MonitorSelection, ReceivedCharacter, Window, WindowMoved, WindowPlugin, WindowPosition,
         WindowResizeConstraints,
     };
 }
@@ -97,9 +101,15 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
+            .add_event::<FileDragEvent>()
+            .add_event::<MonitorConnected>()
+            .add_event::<MonitorDisconnected>()
             .add_event::<WindowMoved>()
             .add_event::<WindowThemeChanged>()
-            .add_event::<ApplicationLifetime>();
+            .add_event::<ApplicationLifetime>()
+            .init_resource::<FileDragState>()
+            .init_resource::<Monitors>()
+            .add_systems(PreUpdate, file_drag_system);
 
         if let Some(primary_window) = &self.primary_window {
             let initial_focus = app
@@ -142,6 +152,9 @@ impl Plugin for WindowPlugin {
             .register_type::<WindowScaleFactorChanged>()
             .register_type::<WindowBackendScaleFactorChanged>()
             .register_type::<FileDragAndDrop>()
+            .register_type::<FileDragEvent>()
+            .register_type::<MonitorConnected>()
+            .register_type::<MonitorDisconnected>()
             .register_type::<WindowMoved>()
             .register_type::<WindowThemeChanged>()
             .register_type::<ApplicationLifetime>();
@@ -160,11 +173,15 @@ impl Plugin for WindowPlugin {
             .register_type::<PresentMode>()
             .register_type::<InternalWindowState>()
             .register_type::<MonitorSelection>()
+            .register_type::<VideoMode>()
+            .register_type::<WindowAttention>()
+            .register_type::<WindowIcon>()
+            .register_type::<WindowProgress>()
             .register_type::<WindowResizeConstraints>()
             .register_type::<WindowTheme>()
             .register_type::<EnabledButtons>();
 
-        // Register `PathBuf` as it's used by `FileDragAndDrop`
+        // Register `PathBuf` as it's used by `FileDragAndDrop` and `FileDragEvent`
         app.register_type::<PathBuf>();
     }
 }
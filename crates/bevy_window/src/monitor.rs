@@ -0,0 +1,174 @@
+use bevy_ecs::{event::Event, system::Resource};
+use bevy_math::{IVec2, UVec2};
+use bevy_reflect::Reflect;
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A video mode a [`Monitor`] supports in exclusive fullscreen.
+///
+/// Pick one from [`Monitor::video_modes`] and set it as [`Window::fullscreen_video_mode`](crate::Window::fullscreen_video_mode)
+/// to choose the resolution and refresh rate of [`WindowMode::Fullscreen`](crate::WindowMode::Fullscreen).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash)]
+pub struct VideoMode {
+    /// The resolution in physical pixels.
+    pub physical_size: UVec2,
+    /// The number of bits per pixel.
+    pub bit_depth: u16,
+    /// The refresh rate in millihertz.
+    pub refresh_rate_millihertz: u32,
+}
+
+/// A display connected to the system.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq)]
+pub struct Monitor {
+    /// The human-readable name of the monitor, if available.
+    pub name: Option<String>,
+    /// The position of the top-left corner of the monitor on the desktop, in physical pixels.
+    pub physical_position: IVec2,
+    /// The current resolution of the monitor in physical pixels.
+    pub physical_size: UVec2,
+    /// The ratio of physical pixels to logical pixels, based on the DPI of the monitor.
+    pub scale_factor: f64,
+    /// The current refresh rate in millihertz, if available.
+    pub refresh_rate_millihertz: Option<u32>,
+    /// The video modes the monitor supports in exclusive fullscreen.
+    pub video_modes: Vec<VideoMode>,
+    /// Whether this is the primary monitor of the system.
+    pub is_primary: bool,
+}
+
+impl Monitor {
+    /// Returns `true` if `other` describes the same physical display, even if its settings changed.
+    fn is_same_display(&self, other: &Monitor) -> bool {
+        self.name == other.name && self.physical_position == other.physical_position
+    }
+}
+
+/// The displays connected to the system, kept up to date by the window backend.
+///
+/// Monitors are listed in the order used by [`MonitorSelection::Index`](crate::MonitorSelection::Index),
+/// so a window can be moved onto a monitor by setting its position to
+/// [`WindowPosition::Centered`](crate::WindowPosition::Centered) with the monitor's index.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Monitors {
+    monitors: Vec<Monitor>,
+}
+
+impl Monitors {
+    /// Returns the monitor at `index`.
+    pub fn get(&self, index: usize) -> Option<&Monitor> {
+        self.monitors.get(index)
+    }
+
+    /// Returns the primary monitor, if the platform reports one.
+    pub fn primary(&self) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.is_primary)
+    }
+
+    /// Iterates over the connected monitors.
+    pub fn iter(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+
+    /// Returns the number of connected monitors.
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// Returns `true` if no monitor is connected, or if the window backend can't list them.
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    /// Replaces the list of monitors, and returns the monitors that were connected and
+    /// disconnected since the last update. This should only be called by window backends.
+    pub fn update(&mut self, monitors: Vec<Monitor>) -> (Vec<Monitor>, Vec<Monitor>) {
+        let connected = monitors
+            .iter()
+            .filter(|monitor| !self.monitors.iter().any(|m| m.is_same_display(monitor)))
+            .cloned()
+            .collect();
+        let disconnected = self
+            .monitors
+            .iter()
+            .filter(|monitor| !monitors.iter().any(|m| m.is_same_display(monitor)))
+            .cloned()
+            .collect();
+        self.monitors = monitors;
+        (connected, disconnected)
+    }
+}
+
+/// An event that is sent when a monitor is connected, including the monitors found when the app
+/// starts.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MonitorConnected {
+    /// The monitor that was connected.
+    pub monitor: Monitor,
+}
+
+/// An event that is sent when a monitor is disconnected.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MonitorDisconnected {
+    /// The monitor that was disconnected.
+    pub monitor: Monitor,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32) -> Monitor {
+        Monitor {
+            name: Some(name.to_string()),
+            physical_position: IVec2::new(x, 0),
+            physical_size: UVec2::new(1920, 1080),
+            scale_factor: 1.0,
+            refresh_rate_millihertz: Some(60_000),
+            video_modes: Vec::new(),
+            is_primary: x == 0,
+        }
+    }
+
+    #[test]
+    fn update_reports_hot_plugged_monitors() {
+        let mut monitors = Monitors::default();
+        let (connected, disconnected) = monitors.update(vec![monitor("A", 0), monitor("B", 1920)]);
+        assert_eq!(connected.len(), 2);
+        assert!(disconnected.is_empty());
+
+        // Changing the settings of a monitor doesn't reconnect it.
+        let mut a = monitor("A", 0);
+        a.refresh_rate_millihertz = Some(144_000);
+        let (connected, disconnected) = monitors.update(vec![a.clone(), monitor("C", 1920)]);
+        assert_eq!(connected, vec![monitor("C", 1920)]);
+        assert_eq!(disconnected, vec![monitor("B", 1920)]);
+        assert_eq!(monitors.primary(), Some(&a));
+        assert_eq!(monitors.len(), 2);
+    }
+}
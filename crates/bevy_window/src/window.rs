@@ -10,7 +10,7 @@ use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
 use bevy_utils::tracing::warn;
 
-use crate::CursorIcon;
+use crate::{CursorIcon, VideoMode};
 
 /// Marker [`Component`] for the window considered the primary window.
 ///
@@ -132,6 +132,12 @@ pub struct Window {
     pub present_mode: PresentMode,
     /// Which fullscreen or windowing mode should be used.
     pub mode: WindowMode,
+    /// The video mode to use in [`WindowMode::Fullscreen`], picked from the
+    /// [`video_modes`](crate::Monitor::video_modes) of a [`Monitor`](crate::Monitor).
+    ///
+    /// If `None`, or if the monitor doesn't support it, the biggest resolution with the highest
+    /// refresh rate is used. Otherwise the supported mode closest to it is used.
+    pub fullscreen_video_mode: Option<VideoMode>,
     /// Where the window should be placed.
     pub position: WindowPosition,
     /// What resolution the window should have.
@@ -253,6 +259,7 @@ impl Default for Window {
             cursor: Default::default(),
             present_mode: Default::default(),
             mode: Default::default(),
+            fullscreen_video_mode: None,
             position: Default::default(),
             resolution: Default::default(),
             internal: Default::default(),
@@ -290,6 +297,18 @@ impl Window {
         self.internal.minimize_request = Some(minimized);
     }
 
+    /// Requests the user's attention, e.g. by flashing the window in the taskbar or bouncing
+    /// the dock icon. The request is dropped by the platform once the window gets focus.
+    ///
+    /// `None` cancels a previous request.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`iOS`**, **`Android`**, **`Web`**, **`Wayland`**: Unsupported.
+    pub fn request_attention(&mut self, attention: Option<WindowAttention>) {
+        self.internal.attention_request = Some(attention);
+    }
+
     /// The window's client area width in logical pixels.
     ///
     /// See [`WindowResolution`] for an explanation about logical/physical sizes.
@@ -378,6 +397,22 @@ impl Window {
     pub fn set_physical_cursor_position(&mut self, position: Option<DVec2>) {
         self.internal.physical_cursor_position = position;
     }
+
+    /// Returns `true` if the cursor is captured in [relative mode](Cursor::relative_mode) and
+    /// [`MouseMotion`](bevy_input::mouse::MouseMotion) reports unaccelerated deltas.
+    ///
+    /// This is `false` while the window is unfocused, if capturing the cursor failed, or if the
+    /// platform only provides accelerated mouse motion.
+    #[inline]
+    pub fn is_raw_mouse_motion_active(&self) -> bool {
+        self.internal.raw_mouse_motion_active
+    }
+
+    /// Sets whether raw mouse motion is active for this window. This should only be called by
+    /// window backends.
+    pub fn set_raw_mouse_motion_active(&mut self, active: bool) {
+        self.internal.raw_mouse_motion_active = active;
+    }
 }
 
 /// The size limits on a [`Window`].
@@ -493,6 +528,22 @@ pub struct Cursor {
     ///
     /// - iOS / Android / Web / X11: Unsupported.
     pub hit_test: bool,
+
+    /// Whether the cursor is captured for relative mouse input, as needed by first-person cameras.
+    ///
+    /// While the window is focused, the cursor is hidden and locked in place (or confined to the
+    /// window where locking isn't supported) and [`MouseMotion`](bevy_input::mouse::MouseMotion)
+    /// reports unaccelerated deltas where the platform provides them. The capture is released when
+    /// the window loses focus and restored when it regains it. [`Cursor::grab_mode`] and
+    /// [`Cursor::visible`] are ignored while this is enabled.
+    ///
+    /// Use [`Window::is_raw_mouse_motion_active`] to check whether raw input is actually active.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`macOS`** and **`Web`**: The cursor is captured, but mouse motion is still accelerated.
+    /// - **`iOS/Android`** don't have cursors.
+    pub relative_mode: bool,
 }
 
 impl Default for Cursor {
@@ -502,6 +553,7 @@ impl Default for Cursor {
             visible: true,
             grab_mode: CursorGrabMode::None,
             hit_test: true,
+            relative_mode: false,
         }
     }
 }
@@ -803,6 +855,10 @@ pub struct InternalWindowState {
     maximize_request: Option<bool>,
     /// Unscaled cursor position.
     physical_cursor_position: Option<DVec2>,
+    /// Whether the cursor is captured in relative mode with unaccelerated mouse motion.
+    raw_mouse_motion_active: bool,
+    /// If this is `Some` then next frame we will request or cancel the user's attention.
+    attention_request: Option<Option<WindowAttention>>,
 }
 
 impl InternalWindowState {
@@ -815,6 +871,79 @@ impl InternalWindowState {
     pub fn take_minimize_request(&mut self) -> Option<bool> {
         self.minimize_request.take()
     }
+
+    /// Consumes the current attention request, if it exists. This should only be called by window backends.
+    pub fn take_attention_request(&mut self) -> Option<Option<WindowAttention>> {
+        self.attention_request.take()
+    }
+}
+
+/// How urgently a [`Window`] requests the user's attention.
+///
+/// See [`Window::request_attention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum WindowAttention {
+    /// Requests the user's attention until the window is focused, e.g. by flashing the window
+    /// continuously on Windows or bouncing the dock icon until the app is activated on macOS.
+    Critical,
+    /// Requests the user's attention once, e.g. by flashing the taskbar button once on Windows or
+    /// bouncing the dock icon once on macOS.
+    Informational,
+}
+
+/// The icon of a [`Window`], shown in the title bar and the taskbar.
+///
+/// Add this component to a window entity to change its icon. Removing it restores the default
+/// icon. With `bevy_render`, the icon can be loaded from an image asset with `WindowIconImage`.
+///
+/// ## Platform-specific
+///
+/// - **`macOS`**: Unsupported, as the icon comes from the application bundle.
+/// - **`iOS`**, **`Android`**, **`Web`**, **`Wayland`**: Unsupported.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct WindowIcon {
+    /// The pixels of the icon, as rows of 8-bit RGBA values.
+    pub rgba: Vec<u8>,
+    /// The width of the icon in pixels.
+    pub width: u32,
+    /// The height of the icon in pixels.
+    pub height: u32,
+}
+
+/// The progress of a long task, such as loading or exporting, shown on the taskbar button of a
+/// [`Window`].
+///
+/// Add this component to a window entity to show the progress, and remove it to hide it.
+///
+/// ## Platform-specific
+///
+/// - **`Windows`**: Shown on the taskbar button of the window.
+/// - **`macOS`**, **`Linux`**, **`iOS`**, **`Android`**, **`Web`**: Unsupported, the progress
+///   isn't shown.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub enum WindowProgress {
+    /// The task is running, and this fraction of it is done, from `0.0` to `1.0`.
+    Normal(f32),
+    /// The task is paused, with this fraction of it done.
+    Paused(f32),
+    /// The task failed, with this fraction of it done.
+    Error(f32),
+    /// The task is running, but how much of it is done isn't known.
+    #[default]
+    Indeterminate,
 }
 
 /// References a screen monitor.
@@ -1007,7 +1136,8 @@ pub enum WindowMode {
     /// The window should be in "true"/"legacy" Fullscreen mode.
     ///
     /// When setting this, the operating system will be requested to use the
-    /// **biggest** resolution available for the current monitor.
+    /// **biggest** resolution available for the current monitor, or the
+    /// [`Window::fullscreen_video_mode`] if set.
     /// After that, the window's physical size will be modified to match
     /// that monitor resolution, and the logical size will follow based on the
     /// scale factor, see [`WindowResolution`].
@@ -1156,4 +1286,21 @@ mod tests {
         window.set_physical_cursor_position(Some(DVec2::new(400., 600.)));
         assert!(window.physical_cursor_position().is_none());
     }
+
+    #[test]
+    fn attention_request_is_consumed() {
+        let mut window = Window::default();
+        assert_eq!(window.internal.take_attention_request(), None);
+
+        window.request_attention(Some(WindowAttention::Critical));
+        assert_eq!(
+            window.internal.take_attention_request(),
+            Some(Some(WindowAttention::Critical))
+        );
+        assert_eq!(window.internal.take_attention_request(), None);
+
+        window.request_attention(None);
+        assert_eq!(window.internal.take_attention_request(), Some(None));
+        assert_eq!(window.internal.take_attention_request(), None);
+    }
 }
@@ -0,0 +1,168 @@
+//! Reading and writing the system clipboard.
+
+use bevy_ecs::system::Resource;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// An error that occurred while accessing the [`Clipboard`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The clipboard is not supported on this platform.
+    #[error("the clipboard is not supported on this platform")]
+    Unsupported,
+    /// The clipboard doesn't contain data in the requested format.
+    #[error("the clipboard doesn't contain text")]
+    ContentNotAvailable,
+    /// The clipboard couldn't be accessed, for example because another application is using it or
+    /// because the browser denied the permission.
+    #[error("the clipboard couldn't be accessed: {0}")]
+    Unavailable(String),
+}
+
+/// Provides access to the system clipboard.
+///
+/// The clipboard can be cloned cheaply and moved into async tasks. Reading is asynchronous because
+/// browsers only expose the clipboard through promises: [`Clipboard::fetch_text`] returns a
+/// [`ClipboardRead`] that can be polled from a system in later frames. On native platforms the read
+/// completes immediately.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::clipboard::{Clipboard, ClipboardRead};
+/// fn paste(clipboard: Res<Clipboard>, mut pending: Local<Option<ClipboardRead>>) {
+///     let read = pending.get_or_insert_with(|| clipboard.fetch_text());
+///     if let Some(result) = read.poll_result() {
+///         *pending = None;
+///         match result {
+///             Ok(text) => println!("Pasted {text}"),
+///             Err(err) => println!("Couldn't paste: {err}"),
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## Platform-specific
+///
+/// - **`X11`**: The clipboard only keeps its contents while the app is running.
+/// - **`Web`**: Browsers only allow accessing the clipboard in response to user input, and may ask
+/// the user for permission.
+/// - **`iOS`** and **`Android`**: Unsupported.
+#[derive(Resource, Clone, Default)]
+pub struct Clipboard {
+    // The backend is created lazily, as it can't be created without a display server on Linux.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    backend: Arc<Mutex<Option<arboard::Clipboard>>>,
+}
+
+impl Clipboard {
+    /// Replaces the contents of the clipboard with `text`.
+    ///
+    /// On the web, the clipboard is written asynchronously and failures are only logged.
+    pub fn set_text(&self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        let text = text.into();
+
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+        {
+            self.with_backend(|backend| backend.set_text(text))
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let clipboard = web_clipboard()?;
+            wasm_bindgen_futures::spawn_local(async move {
+                let promise = clipboard.write_text(&text);
+                if let Err(err) = wasm_bindgen_futures::JsFuture::from(promise).await {
+                    bevy_utils::tracing::warn!("Couldn't write to the clipboard: {:?}", err);
+                }
+            });
+            Ok(())
+        }
+
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        {
+            let _ = text;
+            Err(ClipboardError::Unsupported)
+        }
+    }
+
+    /// Starts reading the text contents of the clipboard.
+    pub fn fetch_text(&self) -> ClipboardRead {
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+        {
+            ClipboardRead::ready(self.with_backend(|backend| backend.get_text()))
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let clipboard = match web_clipboard() {
+                Ok(clipboard) => clipboard,
+                Err(err) => return ClipboardRead::ready(Err(err)),
+            };
+            let read = ClipboardRead::default();
+            let result = read.result.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let text = wasm_bindgen_futures::JsFuture::from(clipboard.read_text())
+                    .await
+                    .map_err(|err| ClipboardError::Unavailable(format!("{err:?}")))
+                    .and_then(|text| text.as_string().ok_or(ClipboardError::ContentNotAvailable));
+                *result.lock().unwrap() = Some(text);
+            });
+            read
+        }
+
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        {
+            ClipboardRead::ready(Err(ClipboardError::Unsupported))
+        }
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    fn with_backend<T>(
+        &self,
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+    ) -> Result<T, ClipboardError> {
+        let mut backend = self.backend.lock().unwrap();
+        if backend.is_none() {
+            *backend = Some(arboard::Clipboard::new().map_err(convert_arboard_error)?);
+        }
+        f(backend.as_mut().unwrap()).map_err(convert_arboard_error)
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+fn convert_arboard_error(err: arboard::Error) -> ClipboardError {
+    match err {
+        arboard::Error::ContentNotAvailable => ClipboardError::ContentNotAvailable,
+        arboard::Error::ClipboardNotSupported => ClipboardError::Unsupported,
+        err => ClipboardError::Unavailable(err.to_string()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn web_clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+    web_sys::window()
+        .map(|window| window.navigator().clipboard())
+        .ok_or(ClipboardError::Unsupported)
+}
+
+/// A pending read of the [`Clipboard`].
+#[derive(Debug, Default, Clone)]
+pub struct ClipboardRead {
+    result: Arc<Mutex<Option<Result<String, ClipboardError>>>>,
+}
+
+impl ClipboardRead {
+    fn ready(result: Result<String, ClipboardError>) -> Self {
+        Self {
+            result: Arc::new(Mutex::new(Some(result))),
+        }
+    }
+
+    /// Returns the contents of the clipboard once they have been read, or `None` if the read is
+    /// still pending.
+    ///
+    /// The result is only returned once.
+    pub fn poll_result(&self) -> Option<Result<String, ClipboardError>> {
+        self.result.lock().unwrap().take()
+    }
+}
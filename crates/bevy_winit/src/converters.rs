@@ -5,8 +5,11 @@ use bevy_input::{
     touch::{ForceTouch, TouchInput, TouchPhase},
     ButtonState,
 };
-use bevy_math::Vec2;
-use bevy_window::{CursorIcon, EnabledButtons, WindowLevel, WindowTheme};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_window::{
+    CursorIcon, EnabledButtons, Monitor, VideoMode, WindowAttention, WindowLevel, WindowTheme,
+};
+use winit::monitor::MonitorHandle;
 
 pub fn convert_keyboard_input(
     keyboard_input: &winit::event::KeyboardInput,
@@ -307,3 +310,35 @@ pub fn convert_enabled_buttons(enabled_buttons: EnabledButtons) -> winit::window
     }
     window_buttons
 }
+
+pub fn convert_window_attention(attention: WindowAttention) -> winit::window::UserAttentionType {
+    match attention {
+        WindowAttention::Critical => winit::window::UserAttentionType::Critical,
+        WindowAttention::Informational => winit::window::UserAttentionType::Informational,
+    }
+}
+
+pub fn convert_video_mode(video_mode: &winit::monitor::VideoMode) -> VideoMode {
+    VideoMode {
+        physical_size: UVec2::new(video_mode.size().width, video_mode.size().height),
+        bit_depth: video_mode.bit_depth(),
+        refresh_rate_millihertz: video_mode.refresh_rate_millihertz(),
+    }
+}
+
+pub fn convert_monitor(monitor: &MonitorHandle, primary: Option<&MonitorHandle>) -> Monitor {
+    let position = monitor.position();
+    let size = monitor.size();
+    Monitor {
+        name: monitor.name(),
+        physical_position: IVec2::new(position.x, position.y),
+        physical_size: UVec2::new(size.width, size.height),
+        scale_factor: monitor.scale_factor(),
+        refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+        video_modes: monitor
+            .video_modes()
+            .map(|video_mode| convert_video_mode(&video_mode))
+            .collect(),
+        is_primary: primary == Some(monitor),
+    }
+}
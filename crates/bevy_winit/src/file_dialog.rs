@@ -0,0 +1,263 @@
+//! Native file dialogs that don't block the event loop.
+//!
+//! Dialogs are opened through the [`FileDialogs`] resource and run on the
+//! [`AsyncComputeTaskPool`]. A [`FileDialogClosed`] event is sent once the user picks files or
+//! cancels the dialog.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_winit::file_dialog::{FileDialog, FileDialogClosed, FileDialogs};
+//! fn open_scene(mut dialogs: ResMut<FileDialogs>) {
+//!     dialogs.open_file(FileDialog::new().with_filter("Scenes", &["scn.ron"]));
+//! }
+//!
+//! fn load_picked_scene(mut closed: EventReader<FileDialogClosed>) {
+//!     for event in closed.read() {
+//!         for file in &event.files {
+//!             println!("Picked {}", file.name);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **`Web`**: Files are picked with the browser's file picker and their contents are read into
+//! [`PickedFile::contents`], as the browser doesn't expose their path. Saving files and picking
+//! folders are unsupported.
+//! - **`iOS`** and **`Android`**: Unsupported.
+
+use std::path::PathBuf;
+
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::{Res, Resource},
+};
+use bevy_tasks::AsyncComputeTaskPool;
+use crossbeam_channel::{Receiver, Sender};
+
+/// A filter limiting the files shown in a [`FileDialog`] to some extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDialogFilter {
+    /// The name of the filter, e.g. "Images".
+    pub name: String,
+    /// The extensions of the files to show, without the leading dot.
+    pub extensions: Vec<String>,
+}
+
+/// Options of a file dialog opened through [`FileDialogs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileDialog {
+    /// The title of the dialog.
+    pub title: Option<String>,
+    /// The directory the dialog starts in.
+    pub directory: Option<PathBuf>,
+    /// The file name initially filled in, for save dialogs.
+    pub file_name: Option<String>,
+    /// The filters the user can choose from. All files are shown if empty.
+    pub filters: Vec<FileDialogFilter>,
+}
+
+impl FileDialog {
+    /// Creates a dialog with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the dialog.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the directory the dialog starts in.
+    #[must_use]
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Sets the file name initially filled in.
+    #[must_use]
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Adds a filter showing only the files with one of the given `extensions`.
+    #[must_use]
+    pub fn with_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileDialogFilter {
+            name: name.into(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        });
+        self
+    }
+
+    fn to_rfd(&self) -> rfd::AsyncFileDialog {
+        let mut dialog = rfd::AsyncFileDialog::new();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(title) = &self.title {
+                dialog = dialog.set_title(title);
+            }
+            if let Some(directory) = &self.directory {
+                dialog = dialog.set_directory(directory);
+            }
+            if let Some(file_name) = &self.file_name {
+                dialog = dialog.set_file_name(file_name);
+            }
+        }
+        for filter in &self.filters {
+            dialog = dialog.add_filter(&filter.name, &filter.extensions);
+        }
+        dialog
+    }
+}
+
+/// The kind of a file dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileDialogKind {
+    /// Picks a single existing file.
+    OpenFile,
+    /// Picks one or more existing files.
+    OpenFiles,
+    /// Picks the path to save a file to.
+    SaveFile,
+    /// Picks a folder.
+    PickFolder,
+}
+
+/// Identifies a dialog opened through [`FileDialogs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileDialogId(u64);
+
+/// A file or folder picked in a file dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickedFile {
+    /// The name of the file.
+    pub name: String,
+    /// The path of the file. Always set, except on the web.
+    pub path: Option<PathBuf>,
+    /// The contents of the file. Only set on the web, where the file can't be read from its path.
+    pub contents: Option<Vec<u8>>,
+}
+
+impl PickedFile {
+    async fn from_handle(handle: rfd::FileHandle) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self {
+                name: handle.file_name(),
+                path: Some(handle.path().to_path_buf()),
+                contents: None,
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                name: handle.file_name(),
+                path: None,
+                contents: Some(handle.read().await),
+            }
+        }
+    }
+}
+
+/// Sent when a dialog opened through [`FileDialogs`] is closed.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct FileDialogClosed {
+    /// The dialog that was closed.
+    pub id: FileDialogId,
+    /// The kind of the dialog.
+    pub kind: FileDialogKind,
+    /// The picked files, or an empty list if the dialog was canceled.
+    pub files: Vec<PickedFile>,
+}
+
+/// Opens file dialogs without blocking the event loop.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Resource)]
+pub struct FileDialogs {
+    next_id: u64,
+    sender: Sender<FileDialogClosed>,
+    receiver: Receiver<FileDialogClosed>,
+}
+
+impl Default for FileDialogs {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            next_id: 0,
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl FileDialogs {
+    /// Opens a dialog to pick a single file.
+    pub fn open_file(&mut self, dialog: FileDialog) -> FileDialogId {
+        self.open(dialog, FileDialogKind::OpenFile)
+    }
+
+    /// Opens a dialog to pick one or more files.
+    pub fn open_files(&mut self, dialog: FileDialog) -> FileDialogId {
+        self.open(dialog, FileDialogKind::OpenFiles)
+    }
+
+    /// Opens a dialog to pick the path to save a file to.
+    pub fn save_file(&mut self, dialog: FileDialog) -> FileDialogId {
+        self.open(dialog, FileDialogKind::SaveFile)
+    }
+
+    /// Opens a dialog to pick a folder.
+    pub fn pick_folder(&mut self, dialog: FileDialog) -> FileDialogId {
+        self.open(dialog, FileDialogKind::PickFolder)
+    }
+
+    fn open(&mut self, dialog: FileDialog, kind: FileDialogKind) -> FileDialogId {
+        let id = FileDialogId(self.next_id);
+        self.next_id += 1;
+        let sender = self.sender.clone();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                let dialog = dialog.to_rfd();
+                let handles: Vec<rfd::FileHandle> = match kind {
+                    FileDialogKind::OpenFile => dialog.pick_file().await.into_iter().collect(),
+                    FileDialogKind::OpenFiles => dialog.pick_files().await.unwrap_or_default(),
+                    #[cfg(not(target_arch = "wasm32"))]
+                    FileDialogKind::SaveFile => dialog.save_file().await.into_iter().collect(),
+                    #[cfg(not(target_arch = "wasm32"))]
+                    FileDialogKind::PickFolder => dialog.pick_folder().await.into_iter().collect(),
+                    #[cfg(target_arch = "wasm32")]
+                    FileDialogKind::SaveFile | FileDialogKind::PickFolder => {
+                        bevy_utils::tracing::warn!(
+                            "{:?} file dialogs are not supported on the web",
+                            kind
+                        );
+                        Vec::new()
+                    }
+                };
+                let mut files = Vec::with_capacity(handles.len());
+                for handle in handles {
+                    files.push(PickedFile::from_handle(handle).await);
+                }
+                // The receiver is only dropped with the app.
+                let _ = sender.send(FileDialogClosed { id, kind, files });
+            })
+            .detach();
+        id
+    }
+}
+
+/// Sends a [`FileDialogClosed`] event for each dialog closed since the last frame.
+pub fn send_file_dialog_events(
+    dialogs: Res<FileDialogs>,
+    mut closed: EventWriter<FileDialogClosed>,
+) {
+    closed.send_batch(dialogs.receiver.try_iter());
+}
@@ -7,7 +7,11 @@
 //! See `winit_runner` for details.
 
 pub mod accessibility;
+pub mod clipboard;
 mod converters;
+#[cfg(feature = "file_dialog")]
+pub mod file_dialog;
+mod progress;
 mod system;
 #[cfg(target_arch = "wasm32")]
 mod web_resize;
@@ -15,7 +19,11 @@ mod winit_config;
 mod winit_windows;
 
 use bevy_a11y::AccessibilityRequested;
-use system::{changed_windows, create_windows, despawn_windows, CachedWindow};
+use clipboard::Clipboard;
+use system::{
+    changed_windows, create_windows, despawn_windows, update_monitors, update_window_icons,
+    update_window_progress, CachedWindow,
+};
 pub use winit_config::*;
 pub use winit_windows::*;
 
@@ -130,6 +138,7 @@ impl Plugin for WinitPlugin {
 
         app.init_non_send_resource::<WinitWindows>()
             .init_resource::<WinitSettings>()
+            .init_resource::<Clipboard>()
             .set_runner(winit_runner)
             .add_systems(
                 Last,
@@ -137,11 +146,18 @@ impl Plugin for WinitPlugin {
                     // `exit_on_all_closed` only checks if windows exist but doesn't access data,
                     // so we don't need to care about its ordering relative to `changed_windows`
                     changed_windows.ambiguous_with(exit_on_all_closed),
+                    update_window_icons,
+                    update_window_progress,
                     despawn_windows,
                 )
                     .chain(),
             );
 
+        #[cfg(feature = "file_dialog")]
+        app.init_resource::<file_dialog::FileDialogs>()
+            .add_event::<file_dialog::FileDialogClosed>()
+            .add_systems(bevy_app::PreUpdate, file_dialog::send_file_dialog_events);
+
         app.add_plugins(AccessKitPlugin);
 
         #[cfg(target_arch = "wasm32")]
@@ -299,6 +315,9 @@ struct WindowAndInputEventWriters<'w> {
 
 /// Persistent state that is used to run the [`App`] according to the current
 /// [`UpdateMode`].
+/// How often the list of connected monitors is refreshed.
+const MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 struct WinitAppRunnerState {
     /// Current active state of the app.
     active: ActiveState,
@@ -312,6 +331,8 @@ struct WinitAppRunnerState {
     last_update: Instant,
     /// The time the next update is scheduled to start.
     scheduled_update: Option<Instant>,
+    /// The time the list of monitors was last updated.
+    last_monitor_update: Option<Instant>,
 }
 
 #[derive(PartialEq, Eq)]
@@ -341,6 +362,7 @@ impl Default for WinitAppRunnerState {
             wait_elapsed: false,
             last_update: Instant::now(),
             scheduled_update: None,
+            last_monitor_update: None,
         }
     }
 }
@@ -848,6 +870,17 @@ pub fn winit_runner(mut app: App) {
                         runner_state.redraw_requested = false;
                         runner_state.last_update = Instant::now();
 
+                        // winit doesn't notify about monitors being connected or disconnected,
+                        // so poll them regularly.
+                        let update_monitors_now = match runner_state.last_monitor_update {
+                            Some(last) => last.elapsed() >= MONITOR_POLL_INTERVAL,
+                            None => true,
+                        };
+                        if update_monitors_now {
+                            update_monitors(event_loop, &mut app.world);
+                            runner_state.last_monitor_update = Some(runner_state.last_update);
+                        }
+
                         app.update();
 
                         // decide when to run the next update
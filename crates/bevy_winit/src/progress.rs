@@ -0,0 +1,56 @@
+use bevy_window::WindowProgress;
+use winit::window::Window as WinitWindow;
+
+/// Shows the progress on the taskbar button of a window, or hides it if `None`.
+#[cfg(target_os = "windows")]
+pub(crate) fn set_window_progress(winit_window: &WinitWindow, progress: Option<WindowProgress>) {
+    use bevy_utils::tracing::warn;
+    use windows::Win32::{
+        Foundation::HWND,
+        System::Com::{
+            CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+        },
+        UI::Shell::{
+            ITaskbarList3, TaskbarList, TBPF_ERROR, TBPF_INDETERMINATE, TBPF_NOPROGRESS,
+            TBPF_NORMAL, TBPF_PAUSED,
+        },
+    };
+    use winit::platform::windows::WindowExtWindows;
+
+    // The progress is shown as a fraction of this total.
+    const TOTAL: u64 = 10_000;
+
+    let hwnd = HWND(winit_window.hwnd());
+    let (state, done) = match progress {
+        None => (TBPF_NOPROGRESS, None),
+        Some(WindowProgress::Normal(done)) => (TBPF_NORMAL, Some(done)),
+        Some(WindowProgress::Paused(done)) => (TBPF_PAUSED, Some(done)),
+        Some(WindowProgress::Error(done)) => (TBPF_ERROR, Some(done)),
+        Some(WindowProgress::Indeterminate) => (TBPF_INDETERMINATE, None),
+    };
+    // SAFETY: `hwnd` is the handle of a window which is still open, and COM is initialized on this
+    // thread before the taskbar is created.
+    let result = unsafe {
+        // winit already initializes COM on the main thread for drag and drop, in which case this
+        // does nothing.
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        CoCreateInstance::<_, ITaskbarList3>(&TaskbarList, None, CLSCTX_INPROC_SERVER).and_then(
+            |taskbar| {
+                taskbar.HrInit()?;
+                taskbar.SetProgressState(hwnd, state)?;
+                if let Some(done) = done {
+                    let completed = (done.clamp(0.0, 1.0) * TOTAL as f32) as u64;
+                    taskbar.SetProgressValue(hwnd, completed, TOTAL)?;
+                }
+                Ok(())
+            },
+        )
+    };
+    if let Err(err) = result {
+        warn!("Could not set the taskbar progress of a window: {}", err);
+    }
+}
+
+/// Does nothing, as the progress of windows can't be shown on this platform.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn set_window_progress(_winit_window: &WinitWindow, _progress: Option<WindowProgress>) {}
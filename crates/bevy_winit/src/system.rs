@@ -2,16 +2,19 @@ use bevy_a11y::AccessibilityRequested;
 use bevy_ecs::{
     entity::Entity,
     event::EventWriter,
-    prelude::{Changed, Component, Resource},
+    prelude::{Added, Changed, Component, Or, Resource},
     removal_detection::RemovedComponents,
     system::{Commands, NonSendMut, Query, ResMut},
-    world::Mut,
+    world::{Mut, World},
 };
 use bevy_utils::{
     tracing::{error, info, warn},
     HashMap,
 };
-use bevy_window::{RawHandleWrapper, Window, WindowClosed, WindowCreated};
+use bevy_window::{
+    MonitorConnected, MonitorDisconnected, Monitors, RawHandleWrapper, Window, WindowClosed,
+    WindowCreated, WindowIcon, WindowMode, WindowProgress,
+};
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
 use winit::{
@@ -24,10 +27,12 @@ use crate::web_resize::{CanvasParentResizeEventChannel, WINIT_CANVAS_SELECTOR};
 use crate::{
     accessibility::{AccessKitAdapters, WinitActionHandlers},
     converters::{
-        self, convert_enabled_buttons, convert_window_level, convert_window_theme,
-        convert_winit_theme,
+        self, convert_enabled_buttons, convert_monitor, convert_window_attention,
+        convert_window_level, convert_window_theme, convert_winit_theme,
     },
-    get_best_videomode, get_fitting_videomode, WinitWindows,
+    get_fitting_videomode, get_selected_videomode,
+    progress::set_window_progress,
+    WinitWindows,
 };
 
 /// Creates new windows on the [`winit`] backend for each entity with a newly-added
@@ -74,6 +79,13 @@ pub(crate) fn create_windows<'a>(
         window
             .resolution
             .set_scale_factor(winit_window.scale_factor() as f32);
+
+        if window.cursor.relative_mode {
+            let raw_mouse_motion =
+                crate::winit_windows::apply_cursor_relative_mode(winit_window, &window);
+            window.set_raw_mouse_motion_active(raw_mouse_motion);
+        }
+
         commands
             .entity(entity)
             .insert(RawHandleWrapper {
@@ -145,16 +157,20 @@ pub(crate) fn changed_windows(
                 winit_window.set_title(window.title.as_str());
             }
 
-            if window.mode != cache.window.mode {
+            if window.mode != cache.window.mode
+                || (window.mode == WindowMode::Fullscreen
+                    && window.fullscreen_video_mode != cache.window.fullscreen_video_mode)
+            {
                 let new_mode = match window.mode {
                     bevy_window::WindowMode::BorderlessFullscreen => {
                         Some(winit::window::Fullscreen::Borderless(None))
                     }
-                    bevy_window::WindowMode::Fullscreen => {
-                        Some(winit::window::Fullscreen::Exclusive(get_best_videomode(
+                    bevy_window::WindowMode::Fullscreen => Some(
+                        winit::window::Fullscreen::Exclusive(get_selected_videomode(
                             &winit_window.current_monitor().unwrap(),
-                        )))
-                    }
+                            window.fullscreen_video_mode,
+                        )),
+                    ),
                     bevy_window::WindowMode::SizedFullscreen => {
                         Some(winit::window::Fullscreen::Exclusive(get_fitting_videomode(
                             &winit_window.current_monitor().unwrap(),
@@ -191,12 +207,22 @@ pub(crate) fn changed_windows(
                 winit_window.set_cursor_icon(converters::convert_cursor_icon(window.cursor.icon));
             }
 
-            if window.cursor.grab_mode != cache.window.cursor.grab_mode {
-                crate::winit_windows::attempt_grab(winit_window, window.cursor.grab_mode);
-            }
+            // The cursor is released when the window loses focus, so relative mode has to be
+            // applied again when it regains it.
+            if window.cursor.relative_mode != cache.window.cursor.relative_mode
+                || (window.cursor.relative_mode && window.focused != cache.window.focused)
+            {
+                let raw_mouse_motion =
+                    crate::winit_windows::apply_cursor_relative_mode(winit_window, &window);
+                window.set_raw_mouse_motion_active(raw_mouse_motion);
+            } else if !window.cursor.relative_mode {
+                if window.cursor.grab_mode != cache.window.cursor.grab_mode {
+                    crate::winit_windows::attempt_grab(winit_window, window.cursor.grab_mode);
+                }
 
-            if window.cursor.visible != cache.window.cursor.visible {
-                winit_window.set_cursor_visible(window.cursor.visible);
+                if window.cursor.visible != cache.window.cursor.visible {
+                    winit_window.set_cursor_visible(window.cursor.visible);
+                }
             }
 
             if window.cursor.hit_test != cache.window.cursor.hit_test {
@@ -269,6 +295,10 @@ pub(crate) fn changed_windows(
                 winit_window.set_minimized(minimized);
             }
 
+            if let Some(attention) = window.internal.take_attention_request() {
+                winit_window.request_user_attention(attention.map(convert_window_attention));
+            }
+
             if window.focused != cache.window.focused && window.focused {
                 winit_window.focus_window();
             }
@@ -316,3 +346,76 @@ pub(crate) fn changed_windows(
         }
     }
 }
+
+/// Sets the icon of windows whose [`WindowIcon`] changed, and restores the default icon of windows
+/// whose [`WindowIcon`] was removed.
+pub(crate) fn update_window_icons(
+    // Windows are created after the schedule has run, so icons added with their window are
+    // applied on the next frame.
+    icons: Query<(Entity, &WindowIcon), Or<(Changed<WindowIcon>, Added<CachedWindow>)>>,
+    mut removed_icons: RemovedComponents<WindowIcon>,
+    winit_windows: NonSendMut<WinitWindows>,
+) {
+    for (entity, icon) in &icons {
+        let Some(winit_window) = winit_windows.get_window(entity) else {
+            continue;
+        };
+        match winit::window::Icon::from_rgba(icon.rgba.clone(), icon.width, icon.height) {
+            Ok(icon) => winit_window.set_window_icon(Some(icon)),
+            Err(err) => warn!("Could not set the window icon: {}", err),
+        }
+    }
+
+    for entity in removed_icons.read() {
+        if let Some(winit_window) = winit_windows.get_window(entity) {
+            winit_window.set_window_icon(None);
+        }
+    }
+}
+
+/// Shows the progress of windows whose [`WindowProgress`] changed, and hides the progress of
+/// windows whose [`WindowProgress`] was removed.
+///
+/// The progress is only shown on Windows, this does nothing on other platforms.
+pub(crate) fn update_window_progress(
+    progress: Query<(Entity, &WindowProgress), Or<(Changed<WindowProgress>, Added<CachedWindow>)>>,
+    mut removed_progress: RemovedComponents<WindowProgress>,
+    winit_windows: NonSendMut<WinitWindows>,
+) {
+    for (entity, window_progress) in &progress {
+        if let Some(winit_window) = winit_windows.get_window(entity) {
+            set_window_progress(winit_window, Some(*window_progress));
+        }
+    }
+
+    for entity in removed_progress.read() {
+        if let Some(winit_window) = winit_windows.get_window(entity) {
+            set_window_progress(winit_window, None);
+        }
+    }
+}
+
+/// Updates the [`Monitors`] resource with the monitors currently connected, and sends
+/// [`MonitorConnected`] and [`MonitorDisconnected`] events for the monitors that were hot-plugged
+/// since the last call.
+pub(crate) fn update_monitors(event_loop: &EventLoopWindowTarget<()>, world: &mut World) {
+    let primary = event_loop.primary_monitor();
+    let monitors: Vec<_> = event_loop
+        .available_monitors()
+        .map(|monitor| convert_monitor(&monitor, primary.as_ref()))
+        .collect();
+
+    let Some(mut current) = world.get_resource_mut::<Monitors>() else {
+        return;
+    };
+    if current.iter().eq(monitors.iter()) {
+        return;
+    }
+    let (connected, disconnected) = current.update(monitors);
+    for monitor in disconnected {
+        world.send_event(MonitorDisconnected { monitor });
+    }
+    for monitor in connected {
+        world.send_event(MonitorConnected { monitor });
+    }
+}
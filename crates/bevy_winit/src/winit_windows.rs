@@ -8,7 +8,9 @@ use bevy_a11y::{
 use bevy_ecs::entity::Entity;
 
 use bevy_utils::{tracing::warn, HashMap};
-use bevy_window::{CursorGrabMode, Window, WindowMode, WindowPosition, WindowResolution};
+use bevy_window::{
+    CursorGrabMode, VideoMode, Window, WindowMode, WindowPosition, WindowResolution,
+};
 
 use winit::{
     dpi::{LogicalSize, PhysicalPosition},
@@ -57,11 +59,12 @@ impl WinitWindows {
             WindowMode::BorderlessFullscreen => winit_window_builder.with_fullscreen(Some(
                 winit::window::Fullscreen::Borderless(event_loop.primary_monitor()),
             )),
-            WindowMode::Fullscreen => {
-                winit_window_builder.with_fullscreen(Some(winit::window::Fullscreen::Exclusive(
-                    get_best_videomode(&event_loop.primary_monitor().unwrap()),
-                )))
-            }
+            WindowMode::Fullscreen => winit_window_builder.with_fullscreen(Some(
+                winit::window::Fullscreen::Exclusive(get_selected_videomode(
+                    &event_loop.primary_monitor().unwrap(),
+                    window.fullscreen_video_mode,
+                )),
+            )),
             WindowMode::SizedFullscreen => winit_window_builder.with_fullscreen(Some(
                 winit::window::Fullscreen::Exclusive(get_fitting_videomode(
                     &event_loop.primary_monitor().unwrap(),
@@ -168,12 +171,15 @@ impl WinitWindows {
         adapters.insert(entity, adapter);
         handlers.insert(entity, handler);
 
-        // Do not set the grab mode on window creation if it's none. It can fail on mobile.
-        if window.cursor.grab_mode != CursorGrabMode::None {
-            attempt_grab(&winit_window, window.cursor.grab_mode);
-        }
+        // Relative mode is applied by `create_windows`, which can store whether it's active.
+        if !window.cursor.relative_mode {
+            // Do not set the grab mode on window creation if it's none. It can fail on mobile.
+            if window.cursor.grab_mode != CursorGrabMode::None {
+                attempt_grab(&winit_window, window.cursor.grab_mode);
+            }
 
-        winit_window.set_cursor_visible(window.cursor.visible);
+            winit_window.set_cursor_visible(window.cursor.visible);
+        }
 
         // Do not set the cursor hittest on window creation if it's false, as it will always fail on
         // some platforms and log an unfixable warning.
@@ -291,7 +297,47 @@ pub fn get_best_videomode(monitor: &MonitorHandle) -> winit::monitor::VideoMode
     modes.first().unwrap().clone()
 }
 
-pub(crate) fn attempt_grab(winit_window: &winit::window::Window, grab_mode: CursorGrabMode) {
+/// Gets the video mode of a monitor closest to `video_mode`, or the "best" one if it is `None`.
+///
+/// Video modes are compared by width, height, refresh rate and bit depth in that order.
+pub fn get_selected_videomode(
+    monitor: &MonitorHandle,
+    video_mode: Option<VideoMode>,
+) -> winit::monitor::VideoMode {
+    let Some(video_mode) = video_mode else {
+        return get_best_videomode(monitor);
+    };
+    monitor
+        .video_modes()
+        .min_by_key(|mode| {
+            (
+                mode.size().width.abs_diff(video_mode.physical_size.x),
+                mode.size().height.abs_diff(video_mode.physical_size.y),
+                mode.refresh_rate_millihertz()
+                    .abs_diff(video_mode.refresh_rate_millihertz),
+                mode.bit_depth().abs_diff(video_mode.bit_depth),
+            )
+        })
+        .unwrap_or_else(|| get_best_videomode(monitor))
+}
+
+/// Whether [`DeviceEvent::MouseMotion`](winit::event::DeviceEvent::MouseMotion) reports
+/// unaccelerated deltas on this platform.
+const RAW_MOUSE_MOTION_SUPPORTED: bool = cfg!(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+));
+
+/// Sets the grab mode of the cursor, falling back to the other grab mode if the requested one is
+/// unsupported. Returns `true` if the cursor could be grabbed or released as requested.
+pub(crate) fn attempt_grab(
+    winit_window: &winit::window::Window,
+    grab_mode: CursorGrabMode,
+) -> bool {
     let grab_result = match grab_mode {
         CursorGrabMode::None => winit_window.set_cursor_grab(winit::window::CursorGrabMode::None),
         CursorGrabMode::Confined => winit_window
@@ -309,6 +355,27 @@ pub(crate) fn attempt_grab(winit_window: &winit::window::Window, grab_mode: Curs
         };
 
         bevy_utils::tracing::error!("Unable to {} cursor: {}", err_desc, err);
+        return false;
+    }
+    true
+}
+
+/// Applies the [relative mode](bevy_window::Cursor::relative_mode) of the cursor of `window`.
+///
+/// The cursor is captured while the window is focused, and restored to its regular grab mode and
+/// visibility otherwise. Returns `true` if raw mouse motion is active.
+pub(crate) fn apply_cursor_relative_mode(
+    winit_window: &winit::window::Window,
+    window: &Window,
+) -> bool {
+    if window.cursor.relative_mode && window.focused {
+        let grabbed = attempt_grab(winit_window, CursorGrabMode::Locked);
+        winit_window.set_cursor_visible(false);
+        grabbed && RAW_MOUSE_MOTION_SUPPORTED
+    } else {
+        attempt_grab(winit_window, window.cursor.grab_mode);
+        winit_window.set_cursor_visible(window.cursor.visible);
+        false
     }
 }
 
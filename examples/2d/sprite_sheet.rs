@@ -1,5 +1,7 @@
 //! Renders an animated sprite by loading all animation frames from a single image (a sprite sheet)
-//! into a texture atlas, and changing the displayed image periodically.
+//! into a texture atlas, and playing them as a [`SpriteAnimationClip`].
+
+use std::time::Duration;
 
 use bevy::prelude::*;
 
@@ -7,59 +9,45 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest())) // prevents blurry sprites
         .add_systems(Startup, setup)
-        .add_systems(Update, animate_sprite)
+        .add_systems(Update, toggle_pause)
         .run();
 }
 
-#[derive(Component)]
-struct AnimationIndices {
-    first: usize,
-    last: usize,
-}
-
-#[derive(Component, Deref, DerefMut)]
-struct AnimationTimer(Timer);
-
-fn animate_sprite(
-    time: Res<Time>,
-    mut query: Query<(
-        &AnimationIndices,
-        &mut AnimationTimer,
-        &mut TextureAtlasSprite,
-    )>,
-) {
-    for (indices, mut timer, mut sprite) in &mut query {
-        timer.tick(time.delta());
-        if timer.just_finished() {
-            sprite.index = if sprite.index == indices.last {
-                indices.first
-            } else {
-                sprite.index + 1
-            };
-        }
-    }
-}
-
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut clips: ResMut<Assets<SpriteAnimationClip>>,
 ) {
     let texture_handle = asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.png");
     let texture_atlas =
         TextureAtlas::from_grid(texture_handle, Vec2::new(24.0, 24.0), 7, 1, None, None);
     let texture_atlas_handle = texture_atlases.add(texture_atlas);
     // Use only the subset of sprites in the sheet that make up the run animation
-    let animation_indices = AnimationIndices { first: 1, last: 6 };
+    let run = clips.add(SpriteAnimationClip::from_range(
+        1..7,
+        Duration::from_millis(100),
+    ));
     commands.spawn(Camera2dBundle::default());
     commands.spawn((
         SpriteSheetBundle {
             texture_atlas: texture_atlas_handle,
-            sprite: TextureAtlasSprite::new(animation_indices.first),
+            sprite: TextureAtlasSprite::new(1),
             transform: Transform::from_scale(Vec3::splat(6.0)),
             ..default()
         },
-        animation_indices,
-        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+        AnimationState::new(run),
     ));
 }
+
+fn toggle_pause(keyboard_input: Res<Input<KeyCode>>, mut animations: Query<&mut AnimationState>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        for mut animation in &mut animations {
+            if animation.is_paused() {
+                animation.resume();
+            } else {
+                animation.pause();
+            }
+        }
+    }
+}
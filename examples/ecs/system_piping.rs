@@ -14,6 +14,7 @@ fn main() {
         .add_plugins(LogPlugin {
             level: Level::TRACE,
             filter: "".to_string(),
+            update_subscriber: None,
         })
         .add_systems(
             Update,